@@ -4,6 +4,14 @@
 //! then opens a Dioxus desktop WebView with the shared `oxidgene-ui`
 //! frontend.
 //!
+//! The embedded API can be pinned for external tools (e.g. a phone on the
+//! same network); `--port` and `--bind` take precedence over the variables:
+//! - `OXIDGENE_PORT`: port to listen on (default: random)
+//! - `OXIDGENE_BIND`: `loopback` (default, this machine only) or `all`
+//!   (every interface, reachable from the LAN; the API has no
+//!   authentication, so only on a trusted network). `127.0.0.1` and
+//!   `0.0.0.0` are accepted too.
+//!
 //! The SQLite database is stored in the platform data directory:
 //! - Linux:   `~/.local/share/oxidgene/oxidgene.db`
 //! - macOS:   `~/Library/Application Support/oxidgene/oxidgene.db`
@@ -14,25 +22,44 @@
 //! - macOS:   `~/Library/Caches/oxidgene/`
 //! - Windows: `C:\Users\<user>\AppData\Local\oxidgene\`
 //!
+//! Backups of the database are taken periodically into `<data_dir>/backups/`
+//! (see `oxidgene_api::service::backup`):
+//! - `OXIDGENE_BACKUP_INTERVAL_HOURS`: hours between backups (default: 24,
+//!   `0` disables periodic backups; manual ones stay available)
+//! - `OXIDGENE_BACKUP_KEEP`: number of backups retained (default: 7)
+//!
 //! The WebView (cookies, HSTS, DOM storage, media keys, HTTP cache) stores
 //! its own data under `<data_dir>/webview/`, keeping everything under the
 //! same `oxidgene` namespace instead of WebKit's default (which derives a
 //! separate `oxidgene-desktop` directory from the binary name).
+//!
+//! The window's size, position and maximized state, and the last tree
+//! opened, are restored from `<data_dir>/settings.json` (see [`settings`]).
 
-use std::net::SocketAddr;
+mod settings;
+
+use std::cell::RefCell;
+use std::io::ErrorKind;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::process::ExitCode;
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use axum::Router;
 use axum::routing::get;
 use clap::Parser;
 use dioxus::desktop::tao::event::Event;
-use dioxus::desktop::tao::window::Icon;
-use dioxus::desktop::{Config, WindowBuilder, icon_from_memory};
+use dioxus::desktop::tao::window::{Icon, Window};
+use dioxus::desktop::{Config, WindowBuilder, WindowEvent, icon_from_memory};
+use oxidgene_api::service::backup::{Backups, apply_pending_restore};
 use oxidgene_api::{AppState, build_router};
 use oxidgene_cache::store::disk;
 use oxidgene_cache::store::memory::MemoryCacheStore;
 use oxidgene_db::repo::{connect, run_migrations};
 use oxidgene_ui::api::ApiClient;
+use oxidgene_ui::session::Session;
+use settings::{DesktopSettings, WindowState};
 use tokio::net::TcpListener;
 use tower_http::cors::CorsLayer;
 use tracing::{error, info, warn};
@@ -43,15 +70,71 @@ const ICON_PNG: &[u8] = include_bytes!("../assets/icon.png");
 /// Default pedigree LRU budget in bytes (64 MB).
 const DEFAULT_PEDIGREE_BUDGET_BYTES: usize = 64 * 1024 * 1024;
 
+/// Default number of hours between two periodic backups.
+const DEFAULT_BACKUP_INTERVAL_HOURS: u64 = 24;
+
+/// Default number of backups retained.
+const DEFAULT_BACKUP_KEEP: usize = 7;
+
 #[derive(Parser)]
 #[command(name = "oxidgene-desktop", about = "OxidGene desktop genealogy app")]
 struct Cli {
     /// Enable debug logging (logs all person data received from the backend)
     #[arg(long)]
     debug: bool,
+
+    /// Port of the embedded API server (overrides `OXIDGENE_PORT`; default:
+    /// random)
+    #[arg(long)]
+    port: Option<u16>,
+
+    /// Interfaces the embedded API server listens on: `loopback` or `all`
+    /// (overrides `OXIDGENE_BIND`; default: `loopback`)
+    #[arg(long)]
+    bind: Option<String>,
+}
+
+/// Where the embedded server listens: `--port`/`OXIDGENE_PORT` (port 0, a
+/// random one, when neither is set) on `--bind`/`OXIDGENE_BIND`.
+fn resolve_listen_addr(cli: &Cli) -> Result<SocketAddr, String> {
+    let port = match cli.port {
+        Some(port) => port,
+        None => match std::env::var("OXIDGENE_PORT") {
+            Ok(v) if !v.trim().is_empty() => v.trim().parse::<u16>().map_err(|_| {
+                format!("invalid OXIDGENE_PORT `{v}`: expected a port number (0-65535)")
+            })?,
+            _ => 0,
+        },
+    };
+    let bind = match &cli.bind {
+        Some(bind) => Some(bind.clone()),
+        None => std::env::var("OXIDGENE_BIND")
+            .ok()
+            .filter(|v| !v.trim().is_empty()),
+    };
+    let ip = match bind.as_deref().map(str::trim) {
+        None | Some("loopback") | Some("127.0.0.1") => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        Some("all") | Some("0.0.0.0") => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        Some(other) => {
+            return Err(format!(
+                "invalid bind `{other}`: expected `loopback` or `all`"
+            ));
+        }
+    };
+    Ok(SocketAddr::new(ip, port))
+}
+
+/// A bind failure worded for the user, with what to do about it.
+fn bind_error(addr: SocketAddr, e: &std::io::Error) -> String {
+    let hint = match e.kind() {
+        ErrorKind::AddrInUse => "the port is in use, pick another or unset it for a random one",
+        ErrorKind::PermissionDenied => "ports below 1024 usually need elevated rights",
+        _ => "check --port/OXIDGENE_PORT and --bind/OXIDGENE_BIND",
+    };
+    format!("cannot listen on {addr} ({e}): {hint}")
 }
 
-fn main() {
+fn main() -> ExitCode {
     let cli = Cli::parse();
 
     // ── Initialize tracing ───────────────────────────────────────────
@@ -77,6 +160,27 @@ fn main() {
     });
 
     let db_path = data_dir.join("oxidgene.db");
+
+    // ── Resolve the embedded server's address ────────────────────────
+    let listen_addr = match resolve_listen_addr(&cli) {
+        Ok(addr) => addr,
+        Err(e) => {
+            error!("{e}");
+            eprintln!("oxidgene-desktop: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // ── Load window state and last tree ──────────────────────────────
+    let settings_path = DesktopSettings::path(&data_dir);
+    let settings = Arc::new(Mutex::new(DesktopSettings::load(&settings_path)));
+
+    // ── Apply a restore staged from the backups list ─────────────────
+    let restored = apply_pending_restore(&db_path).unwrap_or_else(|e| {
+        error!(%e, "Failed to apply staged restore");
+        std::process::exit(1);
+    });
+
     let database_url = format!("sqlite://{}?mode=rwc", db_path.display());
     info!(%database_url, "Using SQLite database");
 
@@ -97,7 +201,7 @@ fn main() {
         .unwrap_or(DEFAULT_PEDIGREE_BUDGET_BYTES);
 
     // ── Load cache from disk (if available and not stale) ────────────
-    let memory_store = if disk::is_cache_stale(&cache_dir, &db_path) {
+    let memory_store = if restored || disk::is_cache_stale(&cache_dir, &db_path) {
         info!("Disk cache is stale or missing, starting with empty cache");
         MemoryCacheStore::with_budget(pedigree_budget)
     } else {
@@ -113,8 +217,30 @@ fn main() {
         }
     };
 
+    // ── Read backup settings ─────────────────────────────────────────
+    let backup_interval_hours = std::env::var("OXIDGENE_BACKUP_INTERVAL_HOURS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_BACKUP_INTERVAL_HOURS);
+    let backup_keep = std::env::var("OXIDGENE_BACKUP_KEEP")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_BACKUP_KEEP);
+    let backups = Arc::new(Backups::new(
+        data_dir.join("backups"),
+        &db_path,
+        backup_keep,
+    ));
+    info!(
+        backup_dir = %backups.dir().display(),
+        backup_interval_hours,
+        backup_keep,
+        "Using backup directory"
+    );
+
     // ── Start embedded Axum server in a background tokio runtime ─────
-    let (tx, rx) = std::sync::mpsc::channel::<u16>();
+    // The server thread reports the address it bound, or why it could not.
+    let (tx, rx) = std::sync::mpsc::channel::<Result<SocketAddr, String>>();
     let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
 
     // Wrap shutdown_tx so it can be captured by the Dioxus event handler closure.
@@ -141,8 +267,16 @@ fn main() {
                 std::process::exit(1);
             });
 
+            // Periodic backups, alongside the server.
+            if backup_interval_hours > 0 {
+                Arc::clone(&backups).spawn_periodic(
+                    db.clone(),
+                    Duration::from_secs(backup_interval_hours * 3600),
+                );
+            }
+
             // Build router with the pre-loaded memory store.
-            let state = AppState::with_memory_store(db, memory_store);
+            let state = AppState::with_memory_store(db, memory_store).with_backups(backups);
             let api_router = build_router(state.clone());
 
             let app = Router::new()
@@ -150,19 +284,27 @@ fn main() {
                 .merge(api_router)
                 .layer(CorsLayer::permissive());
 
-            // Bind to random port on loopback
-            let addr = SocketAddr::from(([127, 0, 0, 1], 0));
-            let listener = TcpListener::bind(addr).await.unwrap_or_else(|e| {
-                error!(%e, "Failed to bind TCP listener");
-                std::process::exit(1);
-            });
+            let listener = match TcpListener::bind(listen_addr).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    // The main thread reports it and exits.
+                    let _ = tx.send(Err(bind_error(listen_addr, &e)));
+                    return;
+                }
+            };
 
             let local_addr = listener.local_addr().expect("failed to get local address");
             info!(%local_addr, "Embedded API server listening");
+            if local_addr.ip().is_unspecified() {
+                warn!(
+                    port = local_addr.port(),
+                    "Embedded API reachable from the network without authentication"
+                );
+            }
 
-            // Send the port back to the main thread
-            tx.send(local_addr.port())
-                .expect("failed to send port to main thread");
+            // Send the address back to the main thread
+            tx.send(Ok(local_addr))
+                .expect("failed to send address to main thread");
 
             // Serve with graceful shutdown.
             let shutdown = async {
@@ -193,35 +335,75 @@ fn main() {
     });
 
     // Wait for the server to be ready
-    let port = rx
+    let port = match rx
         .recv()
-        .expect("failed to receive port from server thread");
+        .expect("failed to receive address from server thread")
+    {
+        Ok(addr) => addr.port(),
+        Err(e) => {
+            error!("{e}");
+            eprintln!("oxidgene-desktop: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // The WebView always goes through loopback, whatever the bind.
     let api_url = format!("http://127.0.0.1:{port}");
     info!(%api_url, "API server ready");
 
     // Create the API client that will be shared with the UI
     let api_client = ApiClient::new(&api_url);
 
+    // Reopen the last tree; remember each one opened.
+    let settings_for_session = Arc::clone(&settings);
+    let session = Session::new(settings.lock().unwrap().last_tree_id, move |tree_id| {
+        settings_for_session.lock().unwrap().last_tree_id = Some(tree_id);
+    });
+
     // ── Launch Dioxus desktop window ─────────────────────────────────
     // Dioxus `launch()` returns `-> !` (never returns), so we use a
     // custom event handler to intercept `Event::LoopDestroyed` and
     // trigger cache persistence before the process exits.
     let window_icon: Option<Icon> = icon_from_memory(ICON_PNG).ok();
     let shutdown_tx_for_handler = Arc::clone(&shutdown_tx);
+    let window_state = settings.lock().unwrap().window.unwrap_or_default();
+    // The window once built, and its geometry when last neither maximized
+    // nor minimized (what a maximized window returns to).
+    let window: Rc<RefCell<Option<Arc<Window>>>> = Rc::default();
+    let window_for_build = Rc::clone(&window);
+    let mut normal_state = settings.lock().unwrap().window;
     let mut cfg = Config::new()
         .with_data_directory(data_dir.join("webview"))
         .with_menu(None::<dioxus::desktop::muda::Menu>)
-        .with_window(
-            WindowBuilder::new()
-                .with_title("OxidGene")
-                .with_inner_size(dioxus::desktop::LogicalSize::new(1280.0, 800.0)),
-        );
+        .with_window(window_state.apply(WindowBuilder::new().with_title("OxidGene")))
+        .with_on_window(move |built, _dom| {
+            window_for_build.borrow_mut().replace(built);
+        });
     if let Some(icon) = window_icon {
         cfg = cfg.with_icon(icon);
     }
     dioxus::LaunchBuilder::new()
         .with_context(api_client)
+        .with_context(session)
         .with_cfg(cfg.with_custom_event_handler(move |event, _target| {
+            if let Event::WindowEvent { event, .. } = event
+                && let Some(window) = window.borrow().as_ref()
+            {
+                match event {
+                    WindowEvent::Resized(_) | WindowEvent::Moved(_)
+                        if !window.is_maximized() && !window.is_minimized() =>
+                    {
+                        normal_state = Some(WindowState::capture(window, None));
+                    }
+                    WindowEvent::CloseRequested => {
+                        let mut settings = settings.lock().unwrap();
+                        settings.window = Some(WindowState::capture(window, normal_state));
+                        if let Err(e) = settings.save(&settings_path) {
+                            warn!(%e, "Failed to save desktop settings");
+                        }
+                    }
+                    _ => {}
+                }
+            }
             if let Event::LoopDestroyed = event {
                 info!("Window closing, signalling server to persist cache…");
                 // Take the sender (only fires once).
@@ -237,6 +419,7 @@ fn main() {
             }
         }))
         .launch(oxidgene_ui::App);
+    ExitCode::SUCCESS
 }
 
 /// Persist the cache from the CacheService's inner store.
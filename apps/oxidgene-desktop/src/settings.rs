@@ -0,0 +1,128 @@
+//! Desktop settings kept across launches in `<data_dir>/settings.json`:
+//! the window's size, position and maximized state, and the last tree
+//! opened.
+//!
+//! A missing or unreadable file gives the defaults (a 1280×800 window,
+//! placed by the OS, on the home page).
+
+use std::path::{Path, PathBuf};
+
+use dioxus::desktop::tao::window::Window;
+use dioxus::desktop::{LogicalPosition, LogicalSize, WindowBuilder};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use uuid::Uuid;
+
+/// Window size when none was saved.
+const DEFAULT_WIDTH: f64 = 1280.0;
+const DEFAULT_HEIGHT: f64 = 800.0;
+
+/// Smallest saved size restored, so a bad file cannot open a sliver.
+const MIN_WIDTH: f64 = 400.0;
+const MIN_HEIGHT: f64 = 300.0;
+
+/// The settings file's contents.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DesktopSettings {
+    pub window: Option<WindowState>,
+    /// Tree open when the app was closed, reopened on launch.
+    pub last_tree_id: Option<Uuid>,
+}
+
+/// Window geometry, in logical pixels (independent of the screen's scale).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowState {
+    pub width: f64,
+    pub height: f64,
+    /// Outer position; `None` lets the OS place the window.
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+    pub maximized: bool,
+}
+
+impl WindowState {
+    /// The current state of `window`. A maximized or minimized window is
+    /// saved with the geometry it returns to, `normal` (its last state
+    /// while neither), since the OS reports the maximized or hidden one.
+    pub fn capture(window: &Window, normal: Option<WindowState>) -> Self {
+        let maximized = window.is_maximized();
+        if (maximized || window.is_minimized())
+            && let Some(normal) = normal
+        {
+            return Self {
+                maximized,
+                ..normal
+            };
+        }
+        let scale = window.scale_factor();
+        let size = window.inner_size().to_logical::<f64>(scale);
+        let position = window
+            .outer_position()
+            .ok()
+            .map(|p| p.to_logical::<f64>(scale));
+        Self {
+            width: size.width,
+            height: size.height,
+            x: position.map(|p| p.x),
+            y: position.map(|p| p.y),
+            maximized,
+        }
+    }
+
+    /// Apply the state to a window about to be built.
+    pub fn apply(&self, builder: WindowBuilder) -> WindowBuilder {
+        let builder = builder
+            .with_inner_size(LogicalSize::new(
+                self.width.max(MIN_WIDTH),
+                self.height.max(MIN_HEIGHT),
+            ))
+            .with_maximized(self.maximized);
+        match (self.x, self.y) {
+            (Some(x), Some(y)) => builder.with_position(LogicalPosition::new(x, y)),
+            _ => builder,
+        }
+    }
+}
+
+impl Default for WindowState {
+    fn default() -> Self {
+        Self {
+            width: DEFAULT_WIDTH,
+            height: DEFAULT_HEIGHT,
+            x: None,
+            y: None,
+            maximized: false,
+        }
+    }
+}
+
+impl DesktopSettings {
+    /// Path of the settings file in `data_dir`.
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join("settings.json")
+    }
+
+    /// Read the settings, falling back to the defaults.
+    pub fn load(path: &Path) -> Self {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                warn!(%e, path = %path.display(), "Failed to read settings, using defaults");
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&text).unwrap_or_else(|e| {
+            warn!(%e, path = %path.display(), "Invalid settings file, using defaults");
+            Self::default()
+        })
+    }
+
+    /// Write the settings, replacing the file atomically.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)
+    }
+}
@@ -1,30 +1,29 @@
 //! OxidGene web backend server.
 //!
 //! Starts an Axum HTTP server with:
+//! - TCP or Unix domain socket listener
 //! - REST API under `/api/v1/trees`
 //! - GraphQL at `/graphql` (POST) and GraphiQL playground (GET)
 //! - Health check at `/healthz`
 //! - CORS middleware
-//! - Structured tracing
+//! - Structured tracing, correlated by `X-Request-Id`
 //! - Graceful shutdown on SIGINT/SIGTERM
 
 mod config;
 
-use std::net::SocketAddr;
-
 use axum::Router;
 use axum::http::{HeaderValue, Method};
 use axum::routing::get;
+use oxidgene_api::rest::request_id::REQUEST_ID_HEADER;
 use oxidgene_api::{AppState, build_router};
 use oxidgene_db::repo::{connect, run_migrations};
 use tokio::net::TcpListener;
 use tokio::signal;
 use tower_http::cors::CorsLayer;
-use tower_http::trace::TraceLayer;
 use tracing::{error, info};
 use tracing_subscriber::EnvFilter;
 
-use crate::config::ServerConfig;
+use crate::config::{BindTarget, ServerConfig};
 
 #[tokio::main]
 async fn main() {
@@ -61,7 +60,7 @@ async fn main() {
     });
 
     // ── Build application router ─────────────────────────────────────
-    let state = AppState::new(db);
+    let state = AppState::new(db).with_config(cfg.api_config());
     let api_router = build_router(state);
 
     // CORS
@@ -82,34 +81,72 @@ async fn main() {
                 Method::OPTIONS,
             ])
             .allow_headers(tower_http::cors::Any)
+            .expose_headers([REQUEST_ID_HEADER])
     };
 
     let app = Router::new()
         .route("/healthz", get(healthz))
         .merge(api_router)
-        .layer(cors)
-        .layer(TraceLayer::new_for_http());
+        .layer(cors);
 
     // ── Bind and serve ───────────────────────────────────────────────
-    let addr = SocketAddr::new(cfg.host.parse().expect("invalid host address"), cfg.port);
-    let listener = TcpListener::bind(addr).await.unwrap_or_else(|e| {
-        error!(%e, %addr, "Failed to bind TCP listener");
+    let target = cfg.bind_target().unwrap_or_else(|e| {
+        error!(%e, "Invalid bind configuration");
         std::process::exit(1);
     });
-
-    info!(%addr, "Listening");
-
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await
-        .unwrap_or_else(|e| {
-            error!(%e, "Server error");
+    let served = match target {
+        BindTarget::Tcp(addr) => {
+            let listener = TcpListener::bind(addr).await.unwrap_or_else(|e| {
+                error!(%e, %addr, "Failed to bind TCP listener");
+                std::process::exit(1);
+            });
+            info!(%addr, "Listening");
+            axum::serve(listener, app)
+                .with_graceful_shutdown(shutdown_signal())
+                .await
+        }
+        #[cfg(unix)]
+        BindTarget::Unix(path) => {
+            let listener = bind_unix(&path).unwrap_or_else(|e| {
+                error!(%e, path = %path.display(), "Failed to bind Unix socket");
+                std::process::exit(1);
+            });
+            info!(path = %path.display(), "Listening");
+            let served = axum::serve(listener, app)
+                .with_graceful_shutdown(shutdown_signal())
+                .await;
+            if let Err(e) = std::fs::remove_file(&path) {
+                error!(%e, path = %path.display(), "Failed to remove Unix socket");
+            }
+            served
+        }
+        #[cfg(not(unix))]
+        BindTarget::Unix(_) => {
+            error!("Unix sockets are not supported on this platform");
             std::process::exit(1);
-        });
+        }
+    };
+    served.unwrap_or_else(|e| {
+        error!(%e, "Server error");
+        std::process::exit(1);
+    });
 
     info!("Server shut down gracefully");
 }
 
+/// Bind a Unix domain socket at `path`, replacing a socket left behind by
+/// a server that did not shut down cleanly. Any other file there is kept
+/// and the bind fails.
+#[cfg(unix)]
+fn bind_unix(path: &std::path::Path) -> std::io::Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::FileTypeExt;
+
+    if std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_socket()) {
+        std::fs::remove_file(path)?;
+    }
+    tokio::net::UnixListener::bind(path)
+}
+
 /// Health check handler returning `200 OK` with a JSON body.
 async fn healthz() -> axum::Json<serde_json::Value> {
     axum::Json(serde_json::json!({ "status": "ok" }))
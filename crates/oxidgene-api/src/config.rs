@@ -0,0 +1,177 @@
+//! Tunable API limits.
+//!
+//! Defaults suit the desktop app; the server binary fills these in from its
+//! `ServerConfig` and hands them over through [`AppState::with_config`].
+//!
+//! [`AppState::with_config`]: crate::AppState::with_config
+
+use axum::http::StatusCode;
+use oxidgene_db::repo::{DEFAULT_PAGE_SIZE, PaginationParams};
+use tracing::Level;
+
+/// Default maximum number of items a list request may return at once.
+pub const DEFAULT_MAX_PAGE_SIZE: u64 = 200;
+
+/// Default maximum number of generations an ancestor or descendant query
+/// may walk.
+pub const DEFAULT_MAX_ANCESTRY_DEPTH: i32 = 15;
+
+/// Default maximum nesting depth of a GraphQL query.
+pub const DEFAULT_GRAPHQL_MAX_DEPTH: usize = 12;
+
+/// Default maximum complexity score of a GraphQL query.
+pub const DEFAULT_GRAPHQL_MAX_COMPLEXITY: usize = 10_000;
+
+/// Default geocoding provider: the public OpenStreetMap Nominatim instance.
+pub const DEFAULT_GEOCODER_URL: &str = "https://nominatim.openstreetmap.org";
+
+/// Default minimum delay between geocoding provider calls. The public
+/// Nominatim instance allows at most one request per second.
+pub const DEFAULT_GEOCODER_MIN_INTERVAL_MS: u64 = 1000;
+
+/// Limits applied by the API layer.
+#[derive(Debug, Clone)]
+pub struct ApiConfig {
+    /// Queries nested deeper than this are rejected before execution.
+    pub graphql_max_depth: usize,
+    /// Queries whose computed complexity exceeds this are rejected before
+    /// execution (see the `complexity` attributes on list fields).
+    pub graphql_max_complexity: usize,
+    /// Serve the schema as SDL at `/graphql/schema.graphql`.
+    pub graphql_expose_schema: bool,
+    /// Base URL of a Nominatim-compatible geocoding API; `None` disables
+    /// geocoding.
+    pub geocoder_url: Option<String>,
+    /// Minimum delay between two geocoding provider calls, in milliseconds.
+    pub geocoder_min_interval_ms: u64,
+    /// Largest page a list request may ask for (`first` / `limit`); larger
+    /// requests are clamped to it.
+    pub max_page_size: u64,
+    /// Most generations an ancestor or descendant query (`max_depth`) may
+    /// walk; deeper or unbounded requests are clamped to it.
+    pub max_ancestry_depth: i32,
+    /// Which requests are traced, and at what level.
+    pub trace: TraceConfig,
+    /// How requests are authenticated; off by default.
+    pub auth: AuthConfig,
+}
+
+/// Request authentication (see [`crate::rest::auth`]).
+#[derive(Debug, Clone, Default)]
+pub enum AuthConfig {
+    /// No credentials are checked: every request acts as the local actor.
+    /// The single-user desktop app runs this way.
+    #[default]
+    Disabled,
+    /// Requests must carry `Authorization: Bearer <JWT>`; the token's `sub`
+    /// claim is the actor.
+    Jwt(JwtConfig),
+}
+
+/// How bearer tokens are verified. Tokens are signed with `secret`
+/// (`HS256`/`HS384`/`HS512`) or with a key of the `jwks_url` key set
+/// (RSA, ECDSA or EdDSA, picked by the token's `kid`).
+#[derive(Debug, Clone, Default)]
+pub struct JwtConfig {
+    pub secret: Option<String>,
+    pub jwks_url: Option<String>,
+    /// Required `iss` claim, if any.
+    pub issuer: Option<String>,
+    /// Required `aud` claim, if any.
+    pub audience: Option<String>,
+    /// Actors (token `sub` claims) administering the instance: owners of
+    /// every tree, and the only ones allowed on the `/admin` and
+    /// `/backup` routes.
+    pub admins: Vec<String>,
+}
+
+/// Paths not traced by default: health probes, polled every few seconds.
+pub const DEFAULT_TRACE_SKIP_PATHS: &[&str] = &["/healthz", "/readyz"];
+
+/// Request tracing: which requests get a span and a response event, and
+/// at which level depending on the response status.
+#[derive(Debug, Clone)]
+pub struct TraceConfig {
+    /// Request paths (exact match) that are not traced at all.
+    pub skip_paths: Vec<String>,
+    /// Level of requests answered with a 1xx, 2xx or 3xx status.
+    pub success_level: Level,
+    /// Level of requests answered with a 4xx or 5xx status.
+    pub failure_level: Level,
+}
+
+impl Default for TraceConfig {
+    fn default() -> Self {
+        Self {
+            skip_paths: DEFAULT_TRACE_SKIP_PATHS
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            success_level: Level::DEBUG,
+            failure_level: Level::INFO,
+        }
+    }
+}
+
+impl TraceConfig {
+    /// Whether requests to `path` are traced.
+    pub fn traces(&self, path: &str) -> bool {
+        !self.skip_paths.iter().any(|p| p == path)
+    }
+
+    /// Level of the response event for `status`.
+    pub fn response_level(&self, status: StatusCode) -> Level {
+        if status.is_client_error() || status.is_server_error() {
+            self.failure_level
+        } else {
+            self.success_level
+        }
+    }
+
+    /// Level of the request span: the less verbose of the two, so that the
+    /// span is enabled whenever its response event may be.
+    pub fn span_level(&self) -> Level {
+        self.success_level.min(self.failure_level)
+    }
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            graphql_max_depth: DEFAULT_GRAPHQL_MAX_DEPTH,
+            graphql_max_complexity: DEFAULT_GRAPHQL_MAX_COMPLEXITY,
+            graphql_expose_schema: true,
+            geocoder_url: Some(DEFAULT_GEOCODER_URL.to_string()),
+            geocoder_min_interval_ms: DEFAULT_GEOCODER_MIN_INTERVAL_MS,
+            max_page_size: DEFAULT_MAX_PAGE_SIZE,
+            max_ancestry_depth: DEFAULT_MAX_ANCESTRY_DEPTH,
+            trace: TraceConfig::default(),
+            auth: AuthConfig::Disabled,
+        }
+    }
+}
+
+impl ApiConfig {
+    /// A client's page size: `default` when absent, clamped to
+    /// `[1, max_page_size]`. Every list endpoint goes through this.
+    pub fn page_limit(&self, requested: Option<u64>, default: u64) -> u64 {
+        requested
+            .unwrap_or(default)
+            .clamp(1, self.max_page_size.max(1))
+    }
+
+    /// A client's `max_depth` for an ancestor or descendant query:
+    /// `max_ancestry_depth` when absent, clamped to `[1, max_ancestry_depth]`.
+    pub fn ancestry_depth(&self, requested: Option<i32>) -> i32 {
+        let max = self.max_ancestry_depth.max(1);
+        requested.unwrap_or(max).clamp(1, max)
+    }
+
+    /// Cursor pagination parameters for a client's `first` / `after`.
+    pub fn page_params(&self, first: Option<u64>, after: Option<String>) -> PaginationParams {
+        PaginationParams {
+            first: self.page_limit(first, DEFAULT_PAGE_SIZE),
+            after,
+        }
+    }
+}
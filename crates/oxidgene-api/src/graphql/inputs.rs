@@ -1,9 +1,18 @@
 //! GraphQL input types for mutations.
+//!
+//! Field checks run as `async-graphql` validators wrapping
+//! [`oxidgene_core::validation`], the checks the REST handlers run, so an
+//! invalid value is rejected as an error on its input field.
+//!
+//! Update inputs take a `MaybeUndefined` for every field that can be
+//! cleared: `null` clears it, an omitted field leaves it unchanged.
 
-use async_graphql::InputObject;
+use async_graphql::{CustomValidator, InputObject, InputValueError, MaybeUndefined};
+use oxidgene_core::validation;
 
 use super::types::{
-    GqlChildType, GqlConfidence, GqlEventType, GqlNameType, GqlPrivacy, GqlSex, GqlSpouseRole,
+    GqlChildType, GqlConfidence, GqlEventType, GqlFactType, GqlNameType, GqlNoteFormat,
+    GqlParticipantRole, GqlPrivacy, GqlSex, GqlSpouseRole,
 };
 
 // ── Tree Inputs ──────────────────────────────────────────────────────
@@ -11,6 +20,7 @@ use super::types::{
 /// Input for creating a new tree.
 #[derive(Debug, InputObject)]
 pub struct CreateTreeInput {
+    #[graphql(validator(custom = "NotBlank(\"name\")"))]
     pub name: String,
     pub description: Option<String>,
 }
@@ -18,9 +28,10 @@ pub struct CreateTreeInput {
 /// Input for updating an existing tree.
 #[derive(Debug, InputObject)]
 pub struct UpdateTreeInput {
+    #[graphql(validator(custom = "NotBlank(\"name\")"))]
     pub name: Option<String>,
-    pub description: Option<String>,
-    pub sosa_root_person_id: Option<String>,
+    pub description: MaybeUndefined<String>,
+    pub sosa_root_person_id: MaybeUndefined<String>,
 }
 
 // ── Person Inputs ────────────────────────────────────────────────────
@@ -36,6 +47,8 @@ pub struct CreatePersonInput {
 pub struct UpdatePersonInput {
     pub sex: Option<GqlSex>,
     pub privacy: Option<GqlPrivacy>,
+    /// Living-status override; `null` goes back to the date heuristic.
+    pub living: MaybeUndefined<bool>,
 }
 
 // ── PersonName Inputs ────────────────────────────────────────────────
@@ -56,11 +69,11 @@ pub struct PersonNameInput {
 #[derive(Debug, InputObject)]
 pub struct UpdatePersonNameInput {
     pub name_type: Option<GqlNameType>,
-    pub given_names: Option<String>,
-    pub surname: Option<String>,
-    pub prefix: Option<String>,
-    pub suffix: Option<String>,
-    pub nickname: Option<String>,
+    pub given_names: MaybeUndefined<String>,
+    pub surname: MaybeUndefined<String>,
+    pub prefix: MaybeUndefined<String>,
+    pub suffix: MaybeUndefined<String>,
+    pub nickname: MaybeUndefined<String>,
     pub is_primary: Option<bool>,
 }
 
@@ -75,7 +88,8 @@ pub struct UpdatePersonNameInput {
 #[derive(Debug, InputObject)]
 pub struct AddSpouseInput {
     pub person_id: String,
-    pub role: GqlSpouseRole,
+    /// Defaults from the person's sex.
+    pub role: Option<GqlSpouseRole>,
     #[graphql(default)]
     pub sort_order: i32,
 }
@@ -97,8 +111,11 @@ pub struct CreateEventInput {
     pub event_type: GqlEventType,
     pub date_value: Option<String>,
     /// Date for sorting, in YYYY-MM-DD format.
+    #[graphql(validator(custom = "DateSort"))]
     pub date_sort: Option<String>,
     pub place_id: Option<String>,
+    /// Destination of a move; ignored for other event types.
+    pub place_id_to: Option<String>,
     pub person_id: Option<String>,
     pub family_id: Option<String>,
     pub description: Option<String>,
@@ -108,17 +125,24 @@ pub struct CreateEventInput {
 #[derive(Debug, InputObject)]
 pub struct UpdateEventInput {
     pub event_type: Option<GqlEventType>,
-    pub date_value: Option<String>,
+    pub date_value: MaybeUndefined<String>,
     /// Date for sorting, in YYYY-MM-DD format.
-    pub date_sort: Option<String>,
-    pub place_id: Option<String>,
-    pub description: Option<String>,
+    #[graphql(validator(custom = "DateSort"))]
+    pub date_sort: MaybeUndefined<String>,
+    pub place_id: MaybeUndefined<String>,
+    /// Destination of a move; cleared when the type has none.
+    pub place_id_to: MaybeUndefined<String>,
+    pub description: MaybeUndefined<String>,
+    /// Age of the principal at the event (GEDCOM `AGE`, e.g. "32y").
+    pub age: MaybeUndefined<String>,
 }
 
-/// Input for adding a witness to an event.
+/// Input for adding a participant to an event.
 #[derive(Debug, InputObject)]
-pub struct AddEventWitnessInput {
+pub struct AddEventParticipantInput {
     pub person_id: String,
+    /// Defaults to `WITNESS`.
+    pub role: Option<GqlParticipantRole>,
     pub relation: Option<String>,
     #[graphql(default)]
     pub sort_order: i32,
@@ -129,17 +153,23 @@ pub struct AddEventWitnessInput {
 /// Input for creating a place.
 #[derive(Debug, InputObject)]
 pub struct CreatePlaceInput {
+    #[graphql(validator(custom = "NotBlank(\"name\")"))]
     pub name: String,
+    #[graphql(validator(custom = "Latitude"))]
     pub latitude: Option<f64>,
+    #[graphql(validator(custom = "Longitude"))]
     pub longitude: Option<f64>,
 }
 
 /// Input for updating a place.
 #[derive(Debug, InputObject)]
 pub struct UpdatePlaceInput {
+    #[graphql(validator(custom = "NotBlank(\"name\")"))]
     pub name: Option<String>,
-    pub latitude: Option<f64>,
-    pub longitude: Option<f64>,
+    #[graphql(validator(custom = "Latitude"))]
+    pub latitude: MaybeUndefined<f64>,
+    #[graphql(validator(custom = "Longitude"))]
+    pub longitude: MaybeUndefined<f64>,
 }
 
 // ── Source Inputs ────────────────────────────────────────────────────
@@ -147,6 +177,7 @@ pub struct UpdatePlaceInput {
 /// Input for creating a source.
 #[derive(Debug, InputObject)]
 pub struct CreateSourceInput {
+    #[graphql(validator(custom = "NotBlank(\"title\")"))]
     pub title: String,
     pub author: Option<String>,
     pub publisher: Option<String>,
@@ -157,11 +188,12 @@ pub struct CreateSourceInput {
 /// Input for updating a source.
 #[derive(Debug, InputObject)]
 pub struct UpdateSourceInput {
+    #[graphql(validator(custom = "NotBlank(\"title\")"))]
     pub title: Option<String>,
-    pub author: Option<String>,
-    pub publisher: Option<String>,
-    pub abbreviation: Option<String>,
-    pub repository_name: Option<String>,
+    pub author: MaybeUndefined<String>,
+    pub publisher: MaybeUndefined<String>,
+    pub abbreviation: MaybeUndefined<String>,
+    pub repository_name: MaybeUndefined<String>,
 }
 
 // ── Citation Inputs ──────────────────────────────────────────────────
@@ -176,14 +208,18 @@ pub struct CreateCitationInput {
     pub page: Option<String>,
     pub confidence: GqlConfidence,
     pub text: Option<String>,
+    /// Cite one fact instead of the whole person/event.
+    pub fact_type: Option<GqlFactType>,
+    /// With `fact_type: NAME`, the id of the cited `PersonName`.
+    pub fact_ref: Option<String>,
 }
 
 /// Input for updating a citation.
 #[derive(Debug, InputObject)]
 pub struct UpdateCitationInput {
-    pub page: Option<String>,
+    pub page: MaybeUndefined<String>,
     pub confidence: Option<GqlConfidence>,
-    pub text: Option<String>,
+    pub text: MaybeUndefined<String>,
 }
 
 // ── Media Inputs ─────────────────────────────────────────────────────
@@ -202,8 +238,8 @@ pub struct UploadMediaInput {
 /// Input for updating media metadata.
 #[derive(Debug, InputObject)]
 pub struct UpdateMediaInput {
-    pub title: Option<String>,
-    pub description: Option<String>,
+    pub title: MaybeUndefined<String>,
+    pub description: MaybeUndefined<String>,
 }
 
 // ── MediaLink Inputs ─────────────────────────────────────────────────
@@ -225,7 +261,10 @@ pub struct CreateMediaLinkInput {
 /// Input for creating a note.
 #[derive(Debug, InputObject)]
 pub struct CreateNoteInput {
+    #[graphql(validator(custom = "NotBlank(\"text\")"))]
     pub text: String,
+    /// Defaults to plain text.
+    pub format: Option<GqlNoteFormat>,
     pub person_id: Option<String>,
     pub event_id: Option<String>,
     pub family_id: Option<String>,
@@ -235,7 +274,9 @@ pub struct CreateNoteInput {
 /// Input for updating a note.
 #[derive(Debug, InputObject)]
 pub struct UpdateNoteInput {
+    #[graphql(validator(custom = "NotBlank(\"text\")"))]
     pub text: Option<String>,
+    pub format: Option<GqlNoteFormat>,
 }
 
 // ── GEDCOM Inputs ────────────────────────────────────────────────────
@@ -246,3 +287,40 @@ pub struct ImportGedcomInput {
     /// The raw GEDCOM string content.
     pub gedcom: String,
 }
+
+// ── Validators ───────────────────────────────────────────────────────
+
+/// A required text field; see [`validation::not_blank`].
+struct NotBlank(&'static str);
+
+impl CustomValidator<String> for NotBlank {
+    fn check(&self, value: &String) -> Result<(), InputValueError<String>> {
+        validation::not_blank(self.0, value).map_err(InputValueError::custom)
+    }
+}
+
+struct Latitude;
+
+impl CustomValidator<f64> for Latitude {
+    fn check(&self, value: &f64) -> Result<(), InputValueError<f64>> {
+        validation::latitude(*value).map_err(InputValueError::custom)
+    }
+}
+
+struct Longitude;
+
+impl CustomValidator<f64> for Longitude {
+    fn check(&self, value: &f64) -> Result<(), InputValueError<f64>> {
+        validation::longitude(*value).map_err(InputValueError::custom)
+    }
+}
+
+struct DateSort;
+
+impl CustomValidator<String> for DateSort {
+    fn check(&self, value: &String) -> Result<(), InputValueError<String>> {
+        validation::date_sort(value)
+            .map(|_| ())
+            .map_err(InputValueError::custom)
+    }
+}
@@ -0,0 +1,104 @@
+//! Request-scoped batching of per-person lookups: a field resolved on every
+//! person of a list queues its person, and the first one to ask fetches the
+//! whole queue in one go, so a page of persons costs two queries instead of
+//! two per person. Results are kept for the rest of the request.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_graphql::Result;
+use oxidgene_core::types::{Event, PersonName};
+use oxidgene_db::repo::{EventRepo, PersonNameRepo};
+use sea_orm::DatabaseConnection;
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+/// A person's names and own events.
+#[derive(Debug, Default)]
+pub(crate) struct PersonRecords {
+    pub names: Vec<PersonName>,
+    pub events: Vec<Event>,
+}
+
+type Reply = Result<Arc<PersonRecords>, String>;
+
+/// Batches [`PersonRecords`] lookups; one per GraphQL request.
+#[derive(Default)]
+pub(crate) struct PersonRecordsLoader {
+    state: Mutex<LoaderState>,
+}
+
+#[derive(Default)]
+struct LoaderState {
+    loaded: HashMap<Uuid, Arc<PersonRecords>>,
+    /// Persons queued for the next batch, with the callers waiting on them.
+    queued: HashMap<Uuid, Vec<oneshot::Sender<Reply>>>,
+    /// Whether a caller is already collecting the next batch.
+    collecting: bool,
+}
+
+impl PersonRecordsLoader {
+    /// The names and events of `person_id`, fetched with those of every
+    /// other person asked for while the batch was collecting.
+    pub(crate) async fn load(
+        &self,
+        db: &DatabaseConnection,
+        person_id: Uuid,
+    ) -> Result<Arc<PersonRecords>> {
+        let (tx, rx) = oneshot::channel();
+        let collects = {
+            let mut state = self.state.lock().unwrap();
+            if let Some(records) = state.loaded.get(&person_id) {
+                return Ok(Arc::clone(records));
+            }
+            state.queued.entry(person_id).or_default().push(tx);
+            !std::mem::replace(&mut state.collecting, true)
+        };
+
+        if collects {
+            // Sibling fields are polled in the same pass: let them queue.
+            tokio::task::yield_now().await;
+            let batch = {
+                let mut state = self.state.lock().unwrap();
+                state.collecting = false;
+                std::mem::take(&mut state.queued)
+            };
+            let ids: Vec<Uuid> = batch.keys().copied().collect();
+            let fetched = fetch(db, &ids).await;
+
+            let mut state = self.state.lock().unwrap();
+            let mut fetched = fetched.map_err(|e| e.to_string());
+            for (id, waiters) in batch {
+                let reply = match &mut fetched {
+                    Ok(records) => {
+                        let records = Arc::new(records.remove(&id).unwrap_or_default());
+                        state.loaded.insert(id, Arc::clone(&records));
+                        Ok(records)
+                    }
+                    Err(e) => Err(e.clone()),
+                };
+                for waiter in waiters {
+                    let _ = waiter.send(reply.clone());
+                }
+            }
+        }
+
+        Ok(rx.await??)
+    }
+}
+
+async fn fetch(
+    db: &DatabaseConnection,
+    person_ids: &[Uuid],
+) -> Result<HashMap<Uuid, PersonRecords>, oxidgene_core::OxidGeneError> {
+    let mut records: HashMap<Uuid, PersonRecords> = HashMap::new();
+    for name in PersonNameRepo::list_by_persons(db, person_ids).await? {
+        records.entry(name.person_id).or_default().names.push(name);
+    }
+    for event in EventRepo::list_by_persons(db, person_ids).await? {
+        if let Some(pid) = event.person_id {
+            records.entry(pid).or_default().events.push(event);
+        }
+    }
+    Ok(records)
+}
@@ -1,6 +1,7 @@
 //! GraphQL API layer: schema construction, Axum handlers, and module declarations.
 
 pub mod inputs;
+mod loader;
 pub mod mutation;
 pub mod query;
 pub mod types;
@@ -8,35 +9,71 @@ pub mod types;
 use async_graphql::{EmptySubscription, Schema, http::GraphiQLSource};
 use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
 use axum::extract::State;
+use axum::http::header;
 use axum::response::{Html, IntoResponse};
 use oxidgene_cache::CacheService;
 use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 
+use loader::PersonRecordsLoader;
 use mutation::MutationRoot;
 use query::QueryRoot;
 
+use crate::config::ApiConfig;
+
+/// Complexity multiplier for fields returning an unpaginated list.
+pub(crate) const LIST_COMPLEXITY: usize = 5;
+
+/// Complexity multiplier for ancestor/descendant traversals, which can return
+/// a whole lineage.
+pub(crate) const TRAVERSAL_COMPLEXITY: usize = 50;
+
+/// Complexity of a paginated field: `first` items (25 by default), each
+/// costing `child_complexity`.
+pub(crate) fn page_complexity(first: Option<u64>, child_complexity: usize) -> usize {
+    (first.unwrap_or(25) as usize).saturating_mul(child_complexity)
+}
+
 /// The full GraphQL schema type.
 pub type OxidGeneSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
 
 /// Build the async-graphql schema with the given database connection and cache
-/// service.
-pub fn build_schema(db: DatabaseConnection, cache: Arc<CacheService>) -> OxidGeneSchema {
+/// service. Queries exceeding the configured depth or complexity are rejected
+/// with an error before any resolver runs.
+pub fn build_schema(
+    db: DatabaseConnection,
+    cache: Arc<CacheService>,
+    config: &ApiConfig,
+) -> OxidGeneSchema {
     Schema::build(QueryRoot, MutationRoot, EmptySubscription)
         .data(db)
         .data(cache)
+        .data(config.clone())
+        .limit_depth(config.graphql_max_depth)
+        .limit_complexity(config.graphql_max_complexity)
         .finish()
 }
 
-/// Axum handler for `POST /graphql`.
+/// Axum handler for `POST /graphql`. Each request gets its own batching
+/// loaders.
 pub async fn graphql_handler(
     State(schema): State<OxidGeneSchema>,
     req: GraphQLRequest,
 ) -> GraphQLResponse {
-    schema.execute(req.into_inner()).await.into()
+    let req = req.into_inner().data(PersonRecordsLoader::default());
+    schema.execute(req).await.into()
 }
 
 /// Axum handler for `GET /graphql` — serves GraphiQL playground.
 pub async fn graphql_playground() -> impl IntoResponse {
     Html(GraphiQLSource::build().endpoint("/graphql").finish())
 }
+
+/// Axum handler for `GET /graphql/schema.graphql` — the schema as SDL, for
+/// client code generators.
+pub async fn graphql_schema_sdl(State(schema): State<OxidGeneSchema>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        schema.sdl(),
+    )
+}
@@ -1,25 +1,28 @@
 //! GraphQL mutation root with all write operations.
 
-use async_graphql::{Context, ID, Object, Result};
-use chrono::NaiveDate;
+use async_graphql::{Context, ErrorExtensions, ID, Object, Result};
 use oxidgene_cache::invalidation;
+use oxidgene_core::SpouseRole;
+use oxidgene_core::validation::{self, FieldError};
 use uuid::Uuid;
 
 use oxidgene_db::repo::{
-    CitationRepo, EventRepo, EventWitnessRepo, FamilyChildRepo, FamilyRepo, FamilySpouseRepo,
-    MediaLinkRepo, MediaRepo, NoteRepo, PersonNameRepo, PersonRepo, PlaceRepo, SourceRepo,
-    TreeRepo,
+    CitationRepo, EventParticipantRepo, EventRepo, FamilyChildRepo, FamilyRepo, FamilySpouseRepo,
+    MediaLinkRepo, MediaRepo, NoteRepo, PersonAncestryRepo, PersonNameRepo, PersonRepo, PlaceRepo,
+    SourceRepo, TreeRepo,
 };
 
+use crate::service::citation::validate_fact_target;
+
 use super::inputs::{
-    AddChildInput, AddEventWitnessInput, AddSpouseInput, CreateCitationInput, CreateEventInput,
+    AddChildInput, AddEventParticipantInput, AddSpouseInput, CreateCitationInput, CreateEventInput,
     CreateMediaLinkInput, CreateNoteInput, CreatePersonInput, CreatePlaceInput, CreateSourceInput,
     CreateTreeInput, ImportGedcomInput, PersonNameInput, UpdateCitationInput, UpdateEventInput,
     UpdateMediaInput, UpdateNoteInput, UpdatePersonInput, UpdatePersonNameInput, UpdatePlaceInput,
     UpdateSourceInput, UpdateTreeInput, UploadMediaInput,
 };
 use super::types::{
-    GqlCacheRebuildResult, GqlCitation, GqlEvent, GqlEventWitness, GqlFamily, GqlFamilyChild,
+    GqlCacheRebuildResult, GqlCitation, GqlEvent, GqlEventParticipant, GqlFamily, GqlFamilyChild,
     GqlFamilySpouse, GqlImportGedcomResult, GqlMedia, GqlMediaLink, GqlNote, GqlPedigreeDelta,
     GqlPedigreeDirection, GqlPerson, GqlPersonName, GqlPlace, GqlSource, GqlTree, cache_from_ctx,
     db_from_ctx,
@@ -51,11 +54,18 @@ impl MutationRoot {
         let uuid = Uuid::parse_str(id.as_str())?;
         let sosa_root = input
             .sosa_root_person_id
-            .map(|s| Uuid::parse_str(&s).map(Some))
+            .map_value(|s| Uuid::parse_str(&s))
             .transpose()
             .map_err(|e| async_graphql::Error::new(format!("Invalid sosa_root_person_id: {e}")))?;
-        let tree =
-            TreeRepo::update(db, uuid, input.name, input.description.map(Some), sosa_root).await?;
+        let tree = TreeRepo::update(
+            db,
+            uuid,
+            input.name,
+            input.description.into(),
+            sosa_root.into(),
+            None,
+        )
+        .await?;
         Ok(tree.into())
     }
 
@@ -103,6 +113,8 @@ impl MutationRoot {
             uuid,
             input.sex.map(|s| s.into()),
             input.privacy.map(|p| p.into()),
+            input.living.into(),
+            None,
         )
         .await?;
         // Rebuild the affected set (person + spouses + children + parents).
@@ -120,6 +132,7 @@ impl MutationRoot {
         let uuid = Uuid::parse_str(id.as_str())?;
         let person = PersonRepo::get(db, uuid).await?;
         PersonRepo::delete(db, uuid).await?;
+        PersonAncestryRepo::refresh(db, person.tree_id, &[uuid]).await?;
         // Removes the person from cache + search table, rebuilds affected
         // relatives, and drops pedigrees.
         cache
@@ -140,6 +153,8 @@ impl MutationRoot {
         let db = db_from_ctx(ctx);
         let cache = cache_from_ctx(ctx);
         let pid = Uuid::parse_str(person_id.as_str())?;
+        validation::person_name(input.given_names.as_deref(), input.surname.as_deref())
+            .map_err(field_error)?;
         let id = Uuid::now_v7();
         let name = PersonNameRepo::create(
             db,
@@ -173,15 +188,29 @@ impl MutationRoot {
         let db = db_from_ctx(ctx);
         let cache = cache_from_ctx(ctx);
         let uuid = Uuid::parse_str(id.as_str())?;
+        if !input.given_names.is_undefined() || !input.surname.is_undefined() {
+            let current = PersonNameRepo::get(db, uuid).await?;
+            validation::person_name(
+                input
+                    .given_names
+                    .as_opt_deref()
+                    .unwrap_or(current.given_names.as_deref()),
+                input
+                    .surname
+                    .as_opt_deref()
+                    .unwrap_or(current.surname.as_deref()),
+            )
+            .map_err(field_error)?;
+        }
         let name = PersonNameRepo::update(
             db,
             uuid,
             input.name_type.map(|nt| nt.into()),
-            input.given_names.map(Some),
-            input.surname.map(Some),
-            input.prefix.map(Some),
-            input.suffix.map(Some),
-            input.nickname.map(Some),
+            input.given_names.into(),
+            input.surname.into(),
+            input.prefix.into(),
+            input.suffix.into(),
+            input.nickname.into(),
             input.is_primary,
         )
         .await?;
@@ -237,6 +266,7 @@ impl MutationRoot {
         // Compute affected BEFORE delete.
         let affected = invalidation::affected_persons_for_family(db, uuid).await?;
         FamilyRepo::delete(db, uuid).await?;
+        PersonAncestryRepo::refresh_family(db, family.tree_id, uuid).await?;
         if !affected.is_empty() {
             cache
                 .invalidate_for_mutation(family.tree_id, &affected)
@@ -256,12 +286,17 @@ impl MutationRoot {
         let cache = cache_from_ctx(ctx);
         let fid = Uuid::parse_str(family_id.as_str())?;
         let pid = Uuid::parse_str(&input.person_id)?;
+        let role = match input.role {
+            Some(role) => role.into(),
+            None => SpouseRole::for_sex(PersonRepo::get(db, pid).await?.sex),
+        };
         let id = Uuid::now_v7();
-        let spouse =
-            FamilySpouseRepo::create(db, id, fid, pid, input.role.into(), input.sort_order).await?;
+        let sort_order = FamilySpouseRepo::sort_order_for_new(db, fid, input.sort_order).await?;
+        let spouse = FamilySpouseRepo::create(db, id, fid, pid, role, sort_order).await?;
         let affected =
             invalidation::affected_persons_for_family_spouse_change(db, fid, pid).await?;
         let family = FamilyRepo::get(db, fid).await?;
+        PersonAncestryRepo::refresh_family(db, family.tree_id, fid).await?;
         cache
             .invalidate_for_mutation(family.tree_id, &affected)
             .await?;
@@ -285,6 +320,7 @@ impl MutationRoot {
             vec![]
         };
         FamilySpouseRepo::delete(db, uuid).await?;
+        PersonAncestryRepo::refresh_family(db, family.tree_id, fid).await?;
         if !affected.is_empty() {
             cache
                 .invalidate_for_mutation(family.tree_id, &affected)
@@ -305,17 +341,60 @@ impl MutationRoot {
         let fid = Uuid::parse_str(family_id.as_str())?;
         let pid = Uuid::parse_str(&input.person_id)?;
         let id = Uuid::now_v7();
+        let sort_order = FamilyChildRepo::sort_order_for_new(db, fid, input.sort_order).await?;
         let child =
-            FamilyChildRepo::create(db, id, fid, pid, input.child_type.into(), input.sort_order)
-                .await?;
+            FamilyChildRepo::create(db, id, fid, pid, input.child_type.into(), sort_order).await?;
         let affected = invalidation::affected_persons_for_family_child_change(db, fid, pid).await?;
         let family = FamilyRepo::get(db, fid).await?;
+        PersonAncestryRepo::refresh(db, family.tree_id, &[pid]).await?;
         cache
             .invalidate_for_mutation(family.tree_id, &affected)
             .await?;
         Ok(child.into())
     }
 
+    /// Reorder a family's children. `child_ids` lists every child link of the
+    /// family in the new order.
+    async fn reorder_children(
+        &self,
+        ctx: &Context<'_>,
+        family_id: ID,
+        child_ids: Vec<ID>,
+    ) -> Result<Vec<GqlFamilyChild>> {
+        let db = db_from_ctx(ctx);
+        let cache = cache_from_ctx(ctx);
+        let fid = Uuid::parse_str(family_id.as_str())?;
+        let ids = child_ids
+            .iter()
+            .map(|id| Uuid::parse_str(id.as_str()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let children = FamilyChildRepo::reorder(db, fid, &ids).await?;
+        let affected = invalidation::affected_persons_for_family_reorder(db, fid).await?;
+        let family = FamilyRepo::get(db, fid).await?;
+        cache
+            .invalidate_for_mutation(family.tree_id, &affected)
+            .await?;
+        Ok(children.into_iter().map(Into::into).collect())
+    }
+
+    /// Order a family's children by birth date, undated ones last.
+    async fn sort_children_by_birth(
+        &self,
+        ctx: &Context<'_>,
+        family_id: ID,
+    ) -> Result<Vec<GqlFamilyChild>> {
+        let db = db_from_ctx(ctx);
+        let cache = cache_from_ctx(ctx);
+        let fid = Uuid::parse_str(family_id.as_str())?;
+        let children = FamilyChildRepo::sort_by_birth(db, fid).await?;
+        let affected = invalidation::affected_persons_for_family_reorder(db, fid).await?;
+        let family = FamilyRepo::get(db, fid).await?;
+        cache
+            .invalidate_for_mutation(family.tree_id, &affected)
+            .await?;
+        Ok(children.into_iter().map(Into::into).collect())
+    }
+
     /// Remove a child from a family (hard delete).
     async fn remove_child(&self, ctx: &Context<'_>, family_id: ID, id: ID) -> Result<bool> {
         let db = db_from_ctx(ctx);
@@ -332,6 +411,9 @@ impl MutationRoot {
             vec![]
         };
         FamilyChildRepo::delete(db, uuid).await?;
+        if let Some(pid) = person_id {
+            PersonAncestryRepo::refresh(db, family.tree_id, &[pid]).await?;
+        }
         if !affected.is_empty() {
             cache
                 .invalidate_for_mutation(family.tree_id, &affected)
@@ -354,6 +436,11 @@ impl MutationRoot {
         let tid = Uuid::parse_str(tree_id.as_str())?;
         let id = Uuid::now_v7();
         let place_id = input.place_id.as_deref().map(Uuid::parse_str).transpose()?;
+        let place_id_to = input
+            .place_id_to
+            .as_deref()
+            .map(Uuid::parse_str)
+            .transpose()?;
         let person_id = input
             .person_id
             .as_deref()
@@ -367,9 +454,9 @@ impl MutationRoot {
         let date_sort = input
             .date_sort
             .as_deref()
-            .map(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d"))
+            .map(validation::date_sort)
             .transpose()
-            .map_err(|e| async_graphql::Error::new(format!("Invalid date_sort: {e}")))?;
+            .map_err(field_error)?;
         let event = EventRepo::create(
             db,
             id,
@@ -378,6 +465,7 @@ impl MutationRoot {
             input.date_value,
             date_sort,
             place_id,
+            place_id_to,
             person_id,
             family_id,
             input.description,
@@ -404,25 +492,34 @@ impl MutationRoot {
         let db = db_from_ctx(ctx);
         let cache = cache_from_ctx(ctx);
         let uuid = Uuid::parse_str(id.as_str())?;
-        let place_id = input.place_id.as_deref().map(Uuid::parse_str).transpose()?;
+        let place_id = input
+            .place_id
+            .map_value(|s| Uuid::parse_str(&s))
+            .transpose()?;
+        let place_id_to = input
+            .place_id_to
+            .map_value(|s| Uuid::parse_str(&s))
+            .transpose()?;
         let date_sort = input
             .date_sort
-            .as_deref()
-            .map(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d"))
+            .map_value(|s| validation::date_sort(&s))
             .transpose()
-            .map_err(|e| async_graphql::Error::new(format!("Invalid date_sort: {e}")))?;
+            .map_err(field_error)?;
         let event = EventRepo::update(
             db,
             uuid,
             input.event_type.map(|et| et.into()),
-            input.date_value.map(Some),
-            date_sort.map(Some),
-            place_id.map(Some),
-            input.description.map(Some),
+            input.date_value.into(),
+            date_sort.into(),
+            place_id.into(),
+            place_id_to.into(),
+            input.description.into(),
             None,
             None,
             None,
             None,
+            input.age.into(),
+            None,
         )
         .await?;
         // Invalidate based on event ownership.
@@ -461,27 +558,29 @@ impl MutationRoot {
         Ok(true)
     }
 
-    /// Add a witness to an event.
-    async fn add_event_witness(
+    /// Add a participant (witness, godparent, ...) to an event.
+    async fn add_event_participant(
         &self,
         ctx: &Context<'_>,
         event_id: ID,
-        input: AddEventWitnessInput,
-    ) -> Result<GqlEventWitness> {
+        input: AddEventParticipantInput,
+    ) -> Result<GqlEventParticipant> {
         let db = db_from_ctx(ctx);
         let eid = Uuid::parse_str(event_id.as_str())?;
         let pid = Uuid::parse_str(&input.person_id)?;
         let id = Uuid::now_v7();
-        let witness =
-            EventWitnessRepo::create(db, id, eid, pid, input.relation, input.sort_order).await?;
-        Ok(witness.into())
+        let role = input.role.map(Into::into).unwrap_or_default();
+        let participant =
+            EventParticipantRepo::create(db, id, eid, pid, role, input.relation, input.sort_order)
+                .await?;
+        Ok(participant.into())
     }
 
-    /// Remove a witness from an event (hard delete).
-    async fn remove_event_witness(&self, ctx: &Context<'_>, id: ID) -> Result<bool> {
+    /// Remove a participant from an event (hard delete).
+    async fn remove_event_participant(&self, ctx: &Context<'_>, id: ID) -> Result<bool> {
         let db = db_from_ctx(ctx);
         let uuid = Uuid::parse_str(id.as_str())?;
-        EventWitnessRepo::delete(db, uuid).await?;
+        EventParticipantRepo::delete(db, uuid).await?;
         Ok(true)
     }
 
@@ -515,8 +614,9 @@ impl MutationRoot {
             db,
             uuid,
             input.name,
-            input.latitude.map(Some),
-            input.longitude.map(Some),
+            input.latitude.into(),
+            input.longitude.into(),
+            None,
         )
         .await?;
         // Place changes could affect event display — but the event cache stores
@@ -573,10 +673,11 @@ impl MutationRoot {
             db,
             uuid,
             input.title,
-            input.author.map(Some),
-            input.publisher.map(Some),
-            input.abbreviation.map(Some),
-            input.repository_name.map(Some),
+            input.author.into(),
+            input.publisher.into(),
+            input.abbreviation.into(),
+            input.repository_name.into(),
+            None,
         )
         .await?;
         Ok(source.into())
@@ -612,6 +713,9 @@ impl MutationRoot {
             .as_deref()
             .map(Uuid::parse_str)
             .transpose()?;
+        let fact_type = input.fact_type.map(Into::into);
+        let fact_ref = input.fact_ref.as_deref().map(Uuid::parse_str).transpose()?;
+        validate_fact_target(db, person_id, event_id, fact_type, fact_ref).await?;
         let citation = CitationRepo::create(
             db,
             id,
@@ -622,6 +726,8 @@ impl MutationRoot {
             input.page,
             input.confidence.into(),
             input.text,
+            fact_type,
+            fact_ref,
         )
         .await?;
         Ok(citation.into())
@@ -639,9 +745,9 @@ impl MutationRoot {
         let citation = CitationRepo::update(
             db,
             uuid,
-            input.page.map(Some),
+            input.page.into(),
             input.confidence.map(|c| c.into()),
-            input.text.map(Some),
+            input.text.into(),
         )
         .await?;
         Ok(citation.into())
@@ -692,7 +798,7 @@ impl MutationRoot {
         let db = db_from_ctx(ctx);
         let uuid = Uuid::parse_str(id.as_str())?;
         let media =
-            MediaRepo::update(db, uuid, input.title.map(Some), input.description.map(Some)).await?;
+            MediaRepo::update(db, uuid, input.title.into(), input.description.into(), None).await?;
         Ok(media.into())
     }
 
@@ -780,7 +886,15 @@ impl MutationRoot {
             .map(Uuid::parse_str)
             .transpose()?;
         let note = NoteRepo::create(
-            db, id, tid, input.text, person_id, event_id, family_id, source_id,
+            db,
+            id,
+            tid,
+            input.text,
+            input.format.map(Into::into).unwrap_or_default(),
+            person_id,
+            event_id,
+            family_id,
+            source_id,
         )
         .await?;
         Ok(note.into())
@@ -795,7 +909,8 @@ impl MutationRoot {
     ) -> Result<GqlNote> {
         let db = db_from_ctx(ctx);
         let uuid = Uuid::parse_str(id.as_str())?;
-        let note = NoteRepo::update(db, uuid, input.text).await?;
+        let note =
+            NoteRepo::update(db, uuid, input.text, input.format.map(Into::into), None).await?;
         Ok(note.into())
     }
 
@@ -825,9 +940,11 @@ impl MutationRoot {
         cache.rebuild_tree_full(tid).await?;
         Ok(GqlImportGedcomResult {
             persons_count: summary.persons_count as i32,
+            person_names_count: summary.person_names_count as i32,
             families_count: summary.families_count as i32,
             events_count: summary.events_count as i32,
             sources_count: summary.sources_count as i32,
+            citations_count: summary.citations_count as i32,
             media_count: summary.media_count as i32,
             places_count: summary.places_count as i32,
             notes_count: summary.notes_count as i32,
@@ -906,3 +1023,12 @@ impl MutationRoot {
         Ok(delta.into())
     }
 }
+
+/// A rejected input field as a GraphQL error, naming the field in its
+/// extensions for clients to attach the message to.
+fn field_error(e: FieldError) -> async_graphql::Error {
+    async_graphql::Error::new(e.message).extend_with(|_, ext| {
+        ext.set("code", "VALIDATION");
+        ext.set("field", e.field);
+    })
+}
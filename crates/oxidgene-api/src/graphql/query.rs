@@ -4,17 +4,19 @@ use async_graphql::{Context, ID, Object, Result};
 use uuid::Uuid;
 
 use oxidgene_db::repo::{
-    EventFilter, EventRepo, FamilyRepo, MediaRepo, PaginationParams, PersonAncestryRepo,
-    PersonRepo, PlaceRepo, SourceRepo, TreeRepo,
+    EventFilter, EventRepo, FamilyRepo, MediaRepo, PersonAncestryRepo, PersonRepo, PlaceRepo,
+    SourceRepo, TreeRepo,
 };
+use oxidgene_gedcom::ExportOptions;
 
 use super::types::{
     GqlCachedPedigree, GqlCachedPerson, GqlCachedSearchResult, GqlEvent, GqlEventConnection,
     GqlEventType, GqlExportGedcomResult, GqlFamily, GqlFamilyConnection, GqlMedia,
     GqlMediaConnection, GqlPerson, GqlPersonConnection, GqlPersonWithDepth, GqlPlace,
     GqlPlaceConnection, GqlSource, GqlSourceConnection, GqlTree, GqlTreeConnection, cache_from_ctx,
-    db_from_ctx,
+    config_from_ctx, db_from_ctx,
 };
+use super::{LIST_COMPLEXITY, TRAVERSAL_COMPLEXITY, page_complexity};
 
 /// The root query type.
 pub struct QueryRoot;
@@ -24,6 +26,7 @@ impl QueryRoot {
     // ── Trees ────────────────────────────────────────────────────────
 
     /// List all trees with cursor-based pagination.
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
     async fn trees(
         &self,
         ctx: &Context<'_>,
@@ -31,10 +34,7 @@ impl QueryRoot {
         after: Option<String>,
     ) -> Result<GqlTreeConnection> {
         let db = db_from_ctx(ctx);
-        let params = PaginationParams {
-            first: first.unwrap_or(25),
-            after,
-        };
+        let params = config_from_ctx(ctx).page_params(first, after);
         let conn = TreeRepo::list(db, &params).await?;
         Ok(conn.into())
     }
@@ -53,6 +53,7 @@ impl QueryRoot {
     // ── Persons ──────────────────────────────────────────────────────
 
     /// List persons in a tree with cursor-based pagination.
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
     async fn persons(
         &self,
         ctx: &Context<'_>,
@@ -62,10 +63,7 @@ impl QueryRoot {
     ) -> Result<GqlPersonConnection> {
         let db = db_from_ctx(ctx);
         let tid = Uuid::parse_str(tree_id.as_str())?;
-        let params = PaginationParams {
-            first: first.unwrap_or(25),
-            after,
-        };
+        let params = config_from_ctx(ctx).page_params(first, after);
         let conn = PersonRepo::list(db, tid, &params).await?;
         Ok(conn.into())
     }
@@ -83,6 +81,7 @@ impl QueryRoot {
     }
 
     /// Get ancestors of a person.
+    #[graphql(complexity = "TRAVERSAL_COMPLEXITY * child_complexity")]
     async fn ancestors(
         &self,
         ctx: &Context<'_>,
@@ -93,6 +92,7 @@ impl QueryRoot {
         let db = db_from_ctx(ctx);
         let _tid = Uuid::parse_str(tree_id.as_str())?;
         let pid = Uuid::parse_str(person_id.as_str())?;
+        let max_depth = config_from_ctx(ctx).ancestry_depth(max_depth);
         let rows = PersonAncestryRepo::ancestors(db, pid, max_depth).await?;
         let mut result = Vec::new();
         for row in rows {
@@ -106,6 +106,7 @@ impl QueryRoot {
     }
 
     /// Get descendants of a person.
+    #[graphql(complexity = "TRAVERSAL_COMPLEXITY * child_complexity")]
     async fn descendants(
         &self,
         ctx: &Context<'_>,
@@ -116,6 +117,7 @@ impl QueryRoot {
         let db = db_from_ctx(ctx);
         let _tid = Uuid::parse_str(tree_id.as_str())?;
         let pid = Uuid::parse_str(person_id.as_str())?;
+        let max_depth = config_from_ctx(ctx).ancestry_depth(max_depth);
         let rows = PersonAncestryRepo::descendants(db, pid, max_depth).await?;
         let mut result = Vec::new();
         for row in rows {
@@ -131,6 +133,7 @@ impl QueryRoot {
     // ── Families ─────────────────────────────────────────────────────
 
     /// List families in a tree with cursor-based pagination.
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
     async fn families(
         &self,
         ctx: &Context<'_>,
@@ -140,10 +143,7 @@ impl QueryRoot {
     ) -> Result<GqlFamilyConnection> {
         let db = db_from_ctx(ctx);
         let tid = Uuid::parse_str(tree_id.as_str())?;
-        let params = PaginationParams {
-            first: first.unwrap_or(25),
-            after,
-        };
+        let params = config_from_ctx(ctx).page_params(first, after);
         let conn = FamilyRepo::list(db, tid, &params).await?;
         Ok(conn.into())
     }
@@ -164,6 +164,7 @@ impl QueryRoot {
 
     /// List events in a tree with optional filters and cursor-based pagination.
     #[allow(clippy::too_many_arguments)]
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
     async fn events(
         &self,
         ctx: &Context<'_>,
@@ -186,11 +187,9 @@ impl QueryRoot {
                 .as_ref()
                 .map(|id| Uuid::parse_str(id.as_str()))
                 .transpose()?,
+            person_family_ids: Vec::new(),
         };
-        let params = PaginationParams {
-            first: first.unwrap_or(25),
-            after,
-        };
+        let params = config_from_ctx(ctx).page_params(first, after);
         let conn = EventRepo::list(db, tid, &filter, &params).await?;
         Ok(conn.into())
     }
@@ -210,6 +209,7 @@ impl QueryRoot {
     // ── Places ───────────────────────────────────────────────────────
 
     /// List places in a tree with optional search and cursor-based pagination.
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
     async fn places(
         &self,
         ctx: &Context<'_>,
@@ -220,10 +220,7 @@ impl QueryRoot {
     ) -> Result<GqlPlaceConnection> {
         let db = db_from_ctx(ctx);
         let tid = Uuid::parse_str(tree_id.as_str())?;
-        let params = PaginationParams {
-            first: first.unwrap_or(25),
-            after,
-        };
+        let params = config_from_ctx(ctx).page_params(first, after);
         let conn = PlaceRepo::list(db, tid, search.as_deref(), &params).await?;
         Ok(conn.into())
     }
@@ -243,6 +240,7 @@ impl QueryRoot {
     // ── Sources ──────────────────────────────────────────────────────
 
     /// List sources in a tree with cursor-based pagination.
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
     async fn sources(
         &self,
         ctx: &Context<'_>,
@@ -252,10 +250,7 @@ impl QueryRoot {
     ) -> Result<GqlSourceConnection> {
         let db = db_from_ctx(ctx);
         let tid = Uuid::parse_str(tree_id.as_str())?;
-        let params = PaginationParams {
-            first: first.unwrap_or(25),
-            after,
-        };
+        let params = config_from_ctx(ctx).page_params(first, after);
         let conn = SourceRepo::list(db, tid, &params).await?;
         Ok(conn.into())
     }
@@ -275,6 +270,7 @@ impl QueryRoot {
     // ── Media ────────────────────────────────────────────────────────
 
     /// List media in a tree with cursor-based pagination.
+    #[graphql(complexity = "page_complexity(first, child_complexity)")]
     async fn media_list(
         &self,
         ctx: &Context<'_>,
@@ -284,10 +280,7 @@ impl QueryRoot {
     ) -> Result<GqlMediaConnection> {
         let db = db_from_ctx(ctx);
         let tid = Uuid::parse_str(tree_id.as_str())?;
-        let params = PaginationParams {
-            first: first.unwrap_or(25),
-            after,
-        };
+        let params = config_from_ctx(ctx).page_params(first, after);
         let conn = MediaRepo::list(db, tid, &params).await?;
         Ok(conn.into())
     }
@@ -309,18 +302,31 @@ impl QueryRoot {
     /// Export all entities in a tree as a GEDCOM 5.5.1 string. Pass
     /// `merge_occupations: true` to collapse each person's multiple `OCCU`
     /// tags back into one, comma-separated (for importers, e.g. Geneanet,
-    /// that only support a single profession field).
+    /// that only support a single profession field). `submitter`,
+    /// `software_name`, `software_version` and `language` override the
+    /// header metadata.
+    #[allow(clippy::too_many_arguments)]
     async fn export_gedcom(
         &self,
         ctx: &Context<'_>,
         tree_id: ID,
         merge_occupations: Option<bool>,
+        submitter: Option<String>,
+        software_name: Option<String>,
+        software_version: Option<String>,
+        language: Option<String>,
     ) -> Result<GqlExportGedcomResult> {
         let db = db_from_ctx(ctx);
         let tid = Uuid::parse_str(tree_id.as_str())?;
-        let data =
-            crate::service::gedcom::load_and_export(db, tid, merge_occupations.unwrap_or(false))
-                .await?;
+        let defaults = ExportOptions::default();
+        let options = ExportOptions {
+            merge_occupations: merge_occupations.unwrap_or(false),
+            submitter: submitter.unwrap_or(defaults.submitter),
+            software_name: software_name.unwrap_or(defaults.software_name),
+            software_version: software_version.unwrap_or(defaults.software_version),
+            language,
+        };
+        let data = crate::service::gedcom::load_and_export(db, tid, &options, None).await?;
         Ok(GqlExportGedcomResult {
             gedcom: data.gedcom,
             warnings: data.warnings,
@@ -348,6 +354,7 @@ impl QueryRoot {
     /// Get all cached persons for a tree.
     ///
     /// If the cache is cold, triggers a full rebuild first.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
     async fn cached_persons(&self, ctx: &Context<'_>, tree_id: ID) -> Result<Vec<GqlCachedPerson>> {
         let cache = cache_from_ctx(ctx);
         let tid = Uuid::parse_str(tree_id.as_str())?;
@@ -365,6 +372,7 @@ impl QueryRoot {
     ///
     /// Backed by the `person_search_fts` DB table (SQLite FTS5 / PostgreSQL)
     /// with accent-folded, normalised matching. Returns paginated results.
+    #[graphql(complexity = "limit.min(100) * child_complexity")]
     async fn search_persons(
         &self,
         ctx: &Context<'_>,
@@ -383,6 +391,7 @@ impl QueryRoot {
     ///
     /// Returns nodes and edges within the given ancestor / descendant depth.
     /// If no cache exists yet, it is built lazily from DB.
+    #[graphql(complexity = "TRAVERSAL_COMPLEXITY * child_complexity")]
     async fn pedigree(
         &self,
         ctx: &Context<'_>,
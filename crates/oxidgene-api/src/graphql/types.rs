@@ -4,17 +4,22 @@
 //! nested relationships (e.g., Person -> names, events, families).
 
 use async_graphql::{ComplexObject, Context, Enum, ID, Result, SimpleObject};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Utc};
 use oxidgene_cache::CacheService;
+use oxidgene_core::summary::PersonSummary;
 use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use oxidgene_db::repo::{
-    CitationRepo, EventFilter, EventRepo, EventWitnessRepo, FamilyChildRepo, FamilySpouseRepo,
+    CitationRepo, EventFilter, EventParticipantRepo, EventRepo, FamilyChildRepo, FamilySpouseRepo,
     MediaLinkRepo, MediaRepo, NoteRepo, PaginationParams, PersonNameRepo, PersonRepo, PlaceRepo,
 };
 
+use super::LIST_COMPLEXITY;
+use super::loader::{PersonRecords, PersonRecordsLoader};
+use crate::config::ApiConfig;
+
 // ── GraphQL Enums ────────────────────────────────────────────────────
 
 /// Biological sex.
@@ -22,6 +27,7 @@ use oxidgene_db::repo::{
 pub enum GqlSex {
     Male,
     Female,
+    Intersex,
     Unknown,
 }
 
@@ -30,6 +36,7 @@ impl From<oxidgene_core::Sex> for GqlSex {
         match s {
             oxidgene_core::Sex::Male => Self::Male,
             oxidgene_core::Sex::Female => Self::Female,
+            oxidgene_core::Sex::Intersex => Self::Intersex,
             oxidgene_core::Sex::Unknown => Self::Unknown,
         }
     }
@@ -40,6 +47,7 @@ impl From<GqlSex> for oxidgene_core::Sex {
         match s {
             GqlSex::Male => Self::Male,
             GqlSex::Female => Self::Female,
+            GqlSex::Intersex => Self::Intersex,
             GqlSex::Unknown => Self::Unknown,
         }
     }
@@ -431,12 +439,50 @@ impl From<GqlConfidence> for oxidgene_core::Confidence {
     }
 }
 
+/// Fact a citation targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum GqlFactType {
+    Name,
+    Date,
+    Place,
+    Description,
+    Age,
+}
+
+impl From<oxidgene_core::FactType> for GqlFactType {
+    fn from(f: oxidgene_core::FactType) -> Self {
+        match f {
+            oxidgene_core::FactType::Name => Self::Name,
+            oxidgene_core::FactType::Date => Self::Date,
+            oxidgene_core::FactType::Place => Self::Place,
+            oxidgene_core::FactType::Description => Self::Description,
+            oxidgene_core::FactType::Age => Self::Age,
+        }
+    }
+}
+
+impl From<GqlFactType> for oxidgene_core::FactType {
+    fn from(f: GqlFactType) -> Self {
+        match f {
+            GqlFactType::Name => Self::Name,
+            GqlFactType::Date => Self::Date,
+            GqlFactType::Place => Self::Place,
+            GqlFactType::Description => Self::Description,
+            GqlFactType::Age => Self::Age,
+        }
+    }
+}
+
 // ── Helper ───────────────────────────────────────────────────────────
 
 pub(crate) fn db_from_ctx<'a>(ctx: &'a Context<'_>) -> &'a DatabaseConnection {
     ctx.data_unchecked::<DatabaseConnection>()
 }
 
+pub(crate) fn config_from_ctx<'a>(ctx: &'a Context<'_>) -> &'a ApiConfig {
+    ctx.data_unchecked::<ApiConfig>()
+}
+
 pub(crate) fn cache_from_ctx<'a>(ctx: &'a Context<'_>) -> &'a Arc<CacheService> {
     ctx.data_unchecked::<Arc<CacheService>>()
 }
@@ -448,6 +494,8 @@ pub(crate) fn cache_from_ctx<'a>(ctx: &'a Context<'_>) -> &'a Arc<CacheService>
 pub struct GqlPageInfo {
     pub has_next_page: bool,
     pub end_cursor: Option<String>,
+    /// Page size actually applied (`first`, clamped to the server maximum).
+    pub limit: u64,
 }
 
 // ── Tree ─────────────────────────────────────────────────────────────
@@ -533,6 +581,7 @@ impl From<oxidgene_core::types::Connection<oxidgene_core::types::Tree>> for GqlT
             page_info: GqlPageInfo {
                 has_next_page: c.page_info.has_next_page,
                 end_cursor: c.page_info.end_cursor,
+                limit: c.page_info.limit,
             },
             total_count: c.total_count,
         }
@@ -549,6 +598,10 @@ pub struct GqlPerson {
     pub tree_id: ID,
     pub sex: GqlSex,
     pub privacy: GqlPrivacy,
+    /// Explicit living status; `None` when left to the date heuristic.
+    pub living: Option<bool>,
+    /// Whether the person is locked against edits and deletion.
+    pub locked: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -556,6 +609,7 @@ pub struct GqlPerson {
 #[ComplexObject]
 impl GqlPerson {
     /// All names for this person.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
     async fn names(&self, ctx: &Context<'_>) -> Result<Vec<GqlPersonName>> {
         let db = db_from_ctx(ctx);
         let id = Uuid::parse_str(self.id.as_str())?;
@@ -575,6 +629,7 @@ impl GqlPerson {
     }
 
     /// Events associated with this person.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
     async fn events(&self, ctx: &Context<'_>) -> Result<Vec<GqlEvent>> {
         let db = db_from_ctx(ctx);
         let person_id = Uuid::parse_str(self.id.as_str())?;
@@ -583,6 +638,7 @@ impl GqlPerson {
             event_type: None,
             person_id: Some(person_id),
             family_id: None,
+            person_family_ids: Vec::new(),
         };
         let params = PaginationParams {
             first: 100,
@@ -597,6 +653,7 @@ impl GqlPerson {
     }
 
     /// Families this person belongs to (as spouse).
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
     async fn families(&self, ctx: &Context<'_>) -> Result<Vec<GqlFamily>> {
         let db = db_from_ctx(ctx);
         let tree_id = Uuid::parse_str(self.tree_id.as_str())?;
@@ -618,6 +675,7 @@ impl GqlPerson {
     }
 
     /// Citations referencing this person.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
     async fn citations(&self, ctx: &Context<'_>) -> Result<Vec<GqlCitation>> {
         let db = db_from_ctx(ctx);
         let tree_id = Uuid::parse_str(self.tree_id.as_str())?;
@@ -642,6 +700,7 @@ impl GqlPerson {
     }
 
     /// Media linked to this person.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
     async fn media(&self, ctx: &Context<'_>) -> Result<Vec<GqlMedia>> {
         let db = db_from_ctx(ctx);
         let tree_id = Uuid::parse_str(self.tree_id.as_str())?;
@@ -662,6 +721,7 @@ impl GqlPerson {
     }
 
     /// Notes attached to this person.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
     async fn notes(&self, ctx: &Context<'_>) -> Result<Vec<GqlNote>> {
         let db = db_from_ctx(ctx);
         let tree_id = Uuid::parse_str(self.tree_id.as_str())?;
@@ -670,6 +730,52 @@ impl GqlPerson {
             NoteRepo::list_by_entity(db, tree_id, Some(person_id), None, None, None).await?;
         Ok(notes.into_iter().map(GqlNote::from).collect())
     }
+
+    /// Name to show: the primary name, else the first one; `null` when the
+    /// person has no name.
+    async fn display_name(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        Ok(self.summary(ctx).await?.display_name)
+    }
+
+    /// `birth–death` years, either side blank when unknown (`1850–`).
+    async fn lifespan(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        Ok(self.summary(ctx).await?.lifespan())
+    }
+
+    /// Year of birth, else of baptism.
+    async fn birth_year(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        Ok(self.summary(ctx).await?.birth_year)
+    }
+
+    /// Year of death, else of burial or cremation.
+    async fn death_year(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        Ok(self.summary(ctx).await?.death_year)
+    }
+
+    /// Whether the person is presumed alive: the explicit `living` status,
+    /// else judged from their events (as for share-link privacy).
+    async fn is_living(&self, ctx: &Context<'_>) -> Result<bool> {
+        Ok(self.summary(ctx).await?.living)
+    }
+}
+
+impl GqlPerson {
+    /// The person's summary, from names and events batched with the other
+    /// persons of the request.
+    async fn summary(&self, ctx: &Context<'_>) -> Result<PersonSummary> {
+        let db = db_from_ctx(ctx);
+        let id = Uuid::parse_str(self.id.as_str())?;
+        let records: Arc<PersonRecords> = match ctx.data_opt::<PersonRecordsLoader>() {
+            Some(loader) => loader.load(db, id).await?,
+            None => PersonRecordsLoader::default().load(db, id).await?,
+        };
+        Ok(PersonSummary::new(
+            self.living,
+            &records.names,
+            &records.events,
+            Utc::now().year(),
+        ))
+    }
 }
 
 impl From<oxidgene_core::types::Person> for GqlPerson {
@@ -679,6 +785,8 @@ impl From<oxidgene_core::types::Person> for GqlPerson {
             tree_id: ID(p.tree_id.to_string()),
             sex: p.sex.into(),
             privacy: p.privacy.into(),
+            living: p.living,
+            locked: p.locked,
             created_at: p.created_at,
             updated_at: p.updated_at,
         }
@@ -714,6 +822,7 @@ impl From<oxidgene_core::types::Connection<oxidgene_core::types::Person>> for Gq
             page_info: GqlPageInfo {
                 has_next_page: c.page_info.has_next_page,
                 end_cursor: c.page_info.end_cursor,
+                limit: c.page_info.limit,
             },
             total_count: c.total_count,
         }
@@ -780,6 +889,7 @@ pub struct GqlFamily {
 #[ComplexObject]
 impl GqlFamily {
     /// Spouses in this family.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
     async fn spouses(&self, ctx: &Context<'_>) -> Result<Vec<GqlFamilySpouseDetail>> {
         let db = db_from_ctx(ctx);
         let family_id = Uuid::parse_str(self.id.as_str())?;
@@ -798,6 +908,7 @@ impl GqlFamily {
     }
 
     /// Children in this family.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
     async fn children(&self, ctx: &Context<'_>) -> Result<Vec<GqlFamilyChildDetail>> {
         let db = db_from_ctx(ctx);
         let family_id = Uuid::parse_str(self.id.as_str())?;
@@ -816,6 +927,7 @@ impl GqlFamily {
     }
 
     /// Events associated with this family.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
     async fn events(&self, ctx: &Context<'_>) -> Result<Vec<GqlEvent>> {
         let db = db_from_ctx(ctx);
         let family_id = Uuid::parse_str(self.id.as_str())?;
@@ -824,6 +936,7 @@ impl GqlFamily {
             event_type: None,
             person_id: None,
             family_id: Some(family_id),
+            person_family_ids: Vec::new(),
         };
         let params = PaginationParams {
             first: 100,
@@ -878,6 +991,7 @@ impl From<oxidgene_core::types::Connection<oxidgene_core::types::Family>> for Gq
             page_info: GqlPageInfo {
                 has_next_page: c.page_info.has_next_page,
                 end_cursor: c.page_info.end_cursor,
+                limit: c.page_info.limit,
             },
             total_count: c.total_count,
         }
@@ -921,7 +1035,10 @@ pub struct GqlEvent {
     pub date_value2: Option<String>,
     pub calendar: GqlCalendar,
     pub cause: Option<String>,
+    pub age: Option<String>,
     pub place_id: Option<ID>,
+    /// Destination of a move (emigration, immigration, residence).
+    pub place_id_to: Option<ID>,
     pub person_id: Option<ID>,
     pub family_id: Option<ID>,
     pub description: Option<String>,
@@ -944,6 +1061,19 @@ impl GqlEvent {
         }
     }
 
+    /// Resolved destination place for this event.
+    async fn place_to(&self, ctx: &Context<'_>) -> Result<Option<GqlPlace>> {
+        let Some(ref pid) = self.place_id_to else {
+            return Ok(None);
+        };
+        let db = db_from_ctx(ctx);
+        let id = Uuid::parse_str(pid.as_str())?;
+        match PlaceRepo::get(db, id).await {
+            Ok(p) => Ok(Some(GqlPlace::from(p))),
+            Err(_) => Ok(None),
+        }
+    }
+
     /// Resolved person for this event.
     async fn person(&self, ctx: &Context<'_>) -> Result<Option<GqlPerson>> {
         let Some(ref pid) = self.person_id else {
@@ -971,6 +1101,7 @@ impl GqlEvent {
     }
 
     /// Citations for this event.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
     async fn citations(&self, ctx: &Context<'_>) -> Result<Vec<GqlCitation>> {
         let db = db_from_ctx(ctx);
         let event_id = Uuid::parse_str(self.id.as_str())?;
@@ -993,6 +1124,7 @@ impl GqlEvent {
     }
 
     /// Media linked to this event.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
     async fn media(&self, ctx: &Context<'_>) -> Result<Vec<GqlMedia>> {
         let db = db_from_ctx(ctx);
         let event_id = Uuid::parse_str(self.id.as_str())?;
@@ -1013,6 +1145,7 @@ impl GqlEvent {
     }
 
     /// Notes for this event.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
     async fn notes(&self, ctx: &Context<'_>) -> Result<Vec<GqlNote>> {
         let db = db_from_ctx(ctx);
         let tree_id = Uuid::parse_str(self.tree_id.as_str())?;
@@ -1021,12 +1154,16 @@ impl GqlEvent {
         Ok(notes.into_iter().map(GqlNote::from).collect())
     }
 
-    /// Witnesses (godparents, etc.) linked to this event.
-    async fn witnesses(&self, ctx: &Context<'_>) -> Result<Vec<GqlEventWitness>> {
+    /// Participants besides the owning person (witnesses, godparents, ...).
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn participants(&self, ctx: &Context<'_>) -> Result<Vec<GqlEventParticipant>> {
         let db = db_from_ctx(ctx);
         let event_id = Uuid::parse_str(self.id.as_str())?;
-        let witnesses = EventWitnessRepo::list_by_event(db, event_id).await?;
-        Ok(witnesses.into_iter().map(GqlEventWitness::from).collect())
+        let participants = EventParticipantRepo::list_by_event(db, event_id).await?;
+        Ok(participants
+            .into_iter()
+            .map(GqlEventParticipant::from)
+            .collect())
     }
 }
 
@@ -1042,7 +1179,9 @@ impl From<oxidgene_core::types::Event> for GqlEvent {
             date_value2: e.date_value2,
             calendar: e.calendar.into(),
             cause: e.cause,
+            age: e.age,
             place_id: e.place_id.map(|id| ID(id.to_string())),
+            place_id_to: e.place_id_to.map(|id| ID(id.to_string())),
             person_id: e.person_id.map(|id| ID(id.to_string())),
             family_id: e.family_id.map(|id| ID(id.to_string())),
             description: e.description,
@@ -1052,23 +1191,58 @@ impl From<oxidgene_core::types::Event> for GqlEvent {
     }
 }
 
-// ── Event Witness ────────────────────────────────────────────────────
+// ── Event Participant ────────────────────────────────────────────────
+
+/// Part a participant plays in an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum GqlParticipantRole {
+    Primary,
+    Witness,
+    Godparent,
+    Informant,
+    Officiant,
+}
+
+impl From<oxidgene_core::ParticipantRole> for GqlParticipantRole {
+    fn from(r: oxidgene_core::ParticipantRole) -> Self {
+        match r {
+            oxidgene_core::ParticipantRole::Primary => Self::Primary,
+            oxidgene_core::ParticipantRole::Witness => Self::Witness,
+            oxidgene_core::ParticipantRole::Godparent => Self::Godparent,
+            oxidgene_core::ParticipantRole::Informant => Self::Informant,
+            oxidgene_core::ParticipantRole::Officiant => Self::Officiant,
+        }
+    }
+}
+
+impl From<GqlParticipantRole> for oxidgene_core::ParticipantRole {
+    fn from(r: GqlParticipantRole) -> Self {
+        match r {
+            GqlParticipantRole::Primary => Self::Primary,
+            GqlParticipantRole::Witness => Self::Witness,
+            GqlParticipantRole::Godparent => Self::Godparent,
+            GqlParticipantRole::Informant => Self::Informant,
+            GqlParticipantRole::Officiant => Self::Officiant,
+        }
+    }
+}
 
-/// A witness (or godparent, etc.) linked to an event — a pointer to another
-/// person in the tree, mirroring GEDCOM's `ASSO`/`RELA` association.
+/// A person taking part in an event besides its owner (witness, godparent,
+/// officiant, ...) — mirrors GEDCOM's `ASSO`/`RELA` association.
 #[derive(Debug, Clone, SimpleObject)]
 #[graphql(complex)]
-pub struct GqlEventWitness {
+pub struct GqlEventParticipant {
     pub id: ID,
     pub event_id: ID,
     pub person_id: ID,
+    pub role: GqlParticipantRole,
     pub relation: Option<String>,
     pub sort_order: i32,
 }
 
 #[ComplexObject]
-impl GqlEventWitness {
-    /// Resolved person for this witness.
+impl GqlEventParticipant {
+    /// Resolved person for this participant.
     async fn person(&self, ctx: &Context<'_>) -> Result<Option<GqlPerson>> {
         let db = db_from_ctx(ctx);
         let id = Uuid::parse_str(self.person_id.as_str())?;
@@ -1079,12 +1253,13 @@ impl GqlEventWitness {
     }
 }
 
-impl From<oxidgene_core::types::EventWitness> for GqlEventWitness {
-    fn from(w: oxidgene_core::types::EventWitness) -> Self {
+impl From<oxidgene_core::types::EventParticipant> for GqlEventParticipant {
+    fn from(w: oxidgene_core::types::EventParticipant) -> Self {
         Self {
             id: ID(w.id.to_string()),
             event_id: ID(w.event_id.to_string()),
             person_id: ID(w.person_id.to_string()),
+            role: w.role.into(),
             relation: w.relation,
             sort_order: w.sort_order,
         }
@@ -1120,6 +1295,7 @@ impl From<oxidgene_core::types::Connection<oxidgene_core::types::Event>> for Gql
             page_info: GqlPageInfo {
                 has_next_page: c.page_info.has_next_page,
                 end_cursor: c.page_info.end_cursor,
+                limit: c.page_info.limit,
             },
             total_count: c.total_count,
         }
@@ -1183,6 +1359,7 @@ impl From<oxidgene_core::types::Connection<oxidgene_core::types::Place>> for Gql
             page_info: GqlPageInfo {
                 has_next_page: c.page_info.has_next_page,
                 end_cursor: c.page_info.end_cursor,
+                limit: c.page_info.limit,
             },
             total_count: c.total_count,
         }
@@ -1209,6 +1386,7 @@ pub struct GqlSource {
 #[ComplexObject]
 impl GqlSource {
     /// Citations from this source.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
     async fn citations(&self, ctx: &Context<'_>) -> Result<Vec<GqlCitation>> {
         let db = db_from_ctx(ctx);
         let id = Uuid::parse_str(self.id.as_str())?;
@@ -1262,6 +1440,7 @@ impl From<oxidgene_core::types::Connection<oxidgene_core::types::Source>> for Gq
             page_info: GqlPageInfo {
                 has_next_page: c.page_info.has_next_page,
                 end_cursor: c.page_info.end_cursor,
+                limit: c.page_info.limit,
             },
             total_count: c.total_count,
         }
@@ -1281,6 +1460,10 @@ pub struct GqlCitation {
     pub page: Option<String>,
     pub confidence: GqlConfidence,
     pub text: Option<String>,
+    /// The fact cited; null cites the whole entity.
+    pub fact_type: Option<GqlFactType>,
+    /// The cited `PersonName` of a name citation.
+    pub fact_ref: Option<ID>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -1296,6 +1479,8 @@ impl From<oxidgene_core::types::Citation> for GqlCitation {
             page: c.page,
             confidence: c.confidence.into(),
             text: c.text,
+            fact_type: c.fact_type.map(Into::into),
+            fact_ref: c.fact_ref.map(|id| ID(id.to_string())),
             created_at: c.created_at,
             updated_at: c.updated_at,
         }
@@ -1371,6 +1556,7 @@ impl From<oxidgene_core::types::Connection<oxidgene_core::types::Media>> for Gql
             page_info: GqlPageInfo {
                 has_next_page: c.page_info.has_next_page,
                 end_cursor: c.page_info.end_cursor,
+                limit: c.page_info.limit,
             },
             total_count: c.total_count,
         }
@@ -1457,12 +1643,38 @@ impl From<oxidgene_core::types::FamilyChild> for GqlFamilyChild {
 
 // ── Note ─────────────────────────────────────────────────────────────
 
+/// How a note's text is meant to be displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum GqlNoteFormat {
+    Plain,
+    Markdown,
+}
+
+impl From<oxidgene_core::NoteFormat> for GqlNoteFormat {
+    fn from(f: oxidgene_core::NoteFormat) -> Self {
+        match f {
+            oxidgene_core::NoteFormat::Plain => Self::Plain,
+            oxidgene_core::NoteFormat::Markdown => Self::Markdown,
+        }
+    }
+}
+
+impl From<GqlNoteFormat> for oxidgene_core::NoteFormat {
+    fn from(f: GqlNoteFormat) -> Self {
+        match f {
+            GqlNoteFormat::Plain => Self::Plain,
+            GqlNoteFormat::Markdown => Self::Markdown,
+        }
+    }
+}
+
 /// A textual note.
 #[derive(Debug, Clone, SimpleObject)]
 pub struct GqlNote {
     pub id: ID,
     pub tree_id: ID,
     pub text: String,
+    pub format: GqlNoteFormat,
     pub person_id: Option<ID>,
     pub event_id: Option<ID>,
     pub family_id: Option<ID>,
@@ -1477,6 +1689,7 @@ impl From<oxidgene_core::types::Note> for GqlNote {
             id: ID(n.id.to_string()),
             tree_id: ID(n.tree_id.to_string()),
             text: n.text,
+            format: n.format.into(),
             person_id: n.person_id.map(|id| ID(id.to_string())),
             event_id: n.event_id.map(|id| ID(id.to_string())),
             family_id: n.family_id.map(|id| ID(id.to_string())),
@@ -1493,9 +1706,11 @@ impl From<oxidgene_core::types::Note> for GqlNote {
 #[derive(Debug, Clone, SimpleObject)]
 pub struct GqlImportGedcomResult {
     pub persons_count: i32,
+    pub person_names_count: i32,
     pub families_count: i32,
     pub events_count: i32,
     pub sources_count: i32,
+    pub citations_count: i32,
     pub media_count: i32,
     pub places_count: i32,
     pub notes_count: i32,
@@ -1599,6 +1814,8 @@ pub struct GqlCachedSearchEntry {
     pub birth_year: Option<String>,
     pub birth_place: Option<String>,
     pub death_year: Option<String>,
+    /// The other name or nickname the search matched, if not the primary.
+    pub matched_name: Option<String>,
 }
 
 /// Paginated search results from the cache.
@@ -1720,6 +1937,7 @@ impl From<oxidgene_cache::types::SearchEntry> for GqlCachedSearchEntry {
             birth_year: e.birth_year,
             birth_place: e.birth_place,
             death_year: e.death_year,
+            matched_name: e.matched_name,
         }
     }
 }
@@ -3,14 +3,18 @@
 //! This crate provides:
 //! - REST handlers for all CRUD endpoints under `/api/v1`
 //! - GraphQL schema and resolvers at `/graphql`
+//! - Renderers for export formats such as Graphviz DOT and SVG fan charts
 //! - A router builder to wire up all routes
 
+pub mod config;
 #[cfg(feature = "graphql")]
 pub mod graphql;
+pub mod render;
 pub mod rest;
 pub mod router;
 pub mod service;
 
+pub use config::ApiConfig;
 #[cfg(feature = "graphql")]
 pub use graphql::{OxidGeneSchema, build_schema};
 pub use rest::state::AppState;
@@ -0,0 +1,240 @@
+//! CSV rendering of a tree for spreadsheets: one flat row per person, per
+//! event or per family.
+//!
+//! Person rows reuse the search summary (display name, birth and death
+//! years) so a spreadsheet shows the same values as the rest of the app.
+//! Output is produced in chunks of [`CHUNK_ROWS`] rows, so a large tree can
+//! be streamed as it is encoded.
+
+use std::collections::HashSet;
+use std::io;
+
+use oxidgene_cache::builder::{build_search_entry, extract_year};
+use oxidgene_cache::{CachedEvent, CachedPerson};
+use oxidgene_core::enums::SpouseRole;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Rows encoded per output chunk.
+pub const CHUNK_ROWS: usize = 500;
+
+/// What each row describes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvEntity {
+    #[default]
+    Persons,
+    Events,
+    Families,
+}
+
+impl CsvEntity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Persons => "persons",
+            Self::Events => "events",
+            Self::Families => "families",
+        }
+    }
+
+    fn header(self) -> &'static [&'static str] {
+        match self {
+            Self::Persons => &[
+                "person_id",
+                "name",
+                "given_names",
+                "surname",
+                "sex",
+                "birth_year",
+                "birth_place",
+                "death_year",
+                "death_place",
+                "father_id",
+                "father",
+                "mother_id",
+                "mother",
+            ],
+            Self::Events => &[
+                "event_id",
+                "event_type",
+                "date",
+                "year",
+                "place",
+                "place_to",
+                "description",
+                "person_id",
+                "person",
+                "family_id",
+            ],
+            Self::Families => &[
+                "family_id",
+                "partner1_id",
+                "partner1",
+                "partner2_id",
+                "partner2",
+                "marriage_date",
+                "marriage_place",
+                "children",
+            ],
+        }
+    }
+}
+
+/// The CSV document for `entity`, header first, as encoded chunks. The
+/// first chunk starts with a UTF-8 byte order mark, without which Excel
+/// misreads accented names.
+///
+/// Persons are written in id (creation) order. Events of persons come
+/// before the events of their families; a family, and its events, appear
+/// once, reached through its first partner — families without partners
+/// are left out.
+pub fn render(
+    entity: CsvEntity,
+    mut persons: Vec<CachedPerson>,
+) -> impl Iterator<Item = io::Result<Vec<u8>>> + Send + 'static {
+    persons.sort_by_key(|p| p.person_id);
+    let mut seen_families = HashSet::new();
+    let mut rows = persons
+        .into_iter()
+        .flat_map(move |person| match entity {
+            CsvEntity::Persons => vec![person_row(&person)],
+            CsvEntity::Events => event_rows(&person, &mut seen_families),
+            CsvEntity::Families => family_rows(&person, &mut seen_families),
+        })
+        .peekable();
+
+    let mut header = Some(entity.header());
+    std::iter::from_fn(move || {
+        if header.is_none() && rows.peek().is_none() {
+            return None;
+        }
+        let header = header.take();
+        let bom = if header.is_some() { "\u{feff}" } else { "" };
+        let mut writer = ::csv::Writer::from_writer(bom.as_bytes().to_vec());
+        if let Some(header) = header
+            && let Err(e) = writer.write_record(header)
+        {
+            return Some(Err(e.into()));
+        }
+        for row in rows.by_ref().take(CHUNK_ROWS) {
+            if let Err(e) = writer.write_record(&row) {
+                return Some(Err(e.into()));
+            }
+        }
+        Some(writer.into_inner().map_err(|e| e.into_error()))
+    })
+}
+
+fn person_row(person: &CachedPerson) -> Vec<String> {
+    let summary = build_search_entry(person);
+    let name = person.primary_name.as_ref();
+    let parents = person.family_as_child.as_ref();
+    vec![
+        person.person_id.to_string(),
+        summary.display_name,
+        name.and_then(|n| n.given_names.clone()).unwrap_or_default(),
+        name.and_then(|n| n.surname.clone()).unwrap_or_default(),
+        person.sex.to_string(),
+        summary.birth_year.unwrap_or_default(),
+        summary.birth_place.unwrap_or_default(),
+        summary.death_year.unwrap_or_default(),
+        person
+            .death
+            .as_ref()
+            .and_then(|e| e.place_name.clone())
+            .unwrap_or_default(),
+        id(parents.and_then(|l| l.father_id)),
+        parents
+            .and_then(|l| l.father_display_name.clone())
+            .unwrap_or_default(),
+        id(parents.and_then(|l| l.mother_id)),
+        parents
+            .and_then(|l| l.mother_display_name.clone())
+            .unwrap_or_default(),
+    ]
+}
+
+fn event_rows(person: &CachedPerson, seen_families: &mut HashSet<Uuid>) -> Vec<Vec<String>> {
+    let name = build_search_entry(person).display_name;
+    let own = [
+        &person.birth,
+        &person.baptism,
+        &person.death,
+        &person.burial,
+    ]
+    .into_iter()
+    .flatten()
+    .chain(&person.other_events)
+    .map(|e| event_row(e, Some(person.person_id), &name, None));
+    let family = person
+        .families_as_spouse
+        .iter()
+        .filter(|l| seen_families.insert(l.family_id))
+        .flat_map(|l| {
+            l.events
+                .iter()
+                .map(|e| event_row(e, None, "", Some(l.family_id)))
+        });
+    own.chain(family).collect()
+}
+
+fn event_row(
+    event: &CachedEvent,
+    person_id: Option<Uuid>,
+    person: &str,
+    family_id: Option<Uuid>,
+) -> Vec<String> {
+    vec![
+        event.event_id.to_string(),
+        event.event_type.to_string(),
+        event.date_value.clone().unwrap_or_default(),
+        extract_year(event).unwrap_or_default(),
+        event.place_name.clone().unwrap_or_default(),
+        event.place_name_to.clone().unwrap_or_default(),
+        event.description.clone().unwrap_or_default(),
+        id(person_id),
+        person.to_string(),
+        id(family_id),
+    ]
+}
+
+fn family_rows(person: &CachedPerson, seen_families: &mut HashSet<Uuid>) -> Vec<Vec<String>> {
+    let name = build_search_entry(person).display_name;
+    person
+        .families_as_spouse
+        .iter()
+        .filter(|l| seen_families.insert(l.family_id))
+        .map(|l| {
+            let this = (Some(person.person_id), name.clone());
+            let spouse = (
+                l.spouse_id,
+                l.spouse_display_name.clone().unwrap_or_default(),
+            );
+            // Husbands first, as GEDCOM lists them.
+            let (first, second) = if l.role == SpouseRole::Wife {
+                (spouse, this)
+            } else {
+                (this, spouse)
+            };
+            let marriage = l.marriage.as_ref();
+            vec![
+                l.family_id.to_string(),
+                id(first.0),
+                first.1,
+                id(second.0),
+                second.1,
+                marriage
+                    .and_then(|e| e.date_value.clone())
+                    .unwrap_or_default(),
+                marriage
+                    .and_then(|e| e.place_name.clone())
+                    .unwrap_or_default(),
+                l.children_count.to_string(),
+            ]
+        })
+        .collect()
+}
+
+fn id(id: Option<Uuid>) -> String {
+    id.map(|id| id.to_string()).unwrap_or_default()
+}
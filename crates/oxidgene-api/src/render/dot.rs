@@ -0,0 +1,171 @@
+//! Graphviz DOT rendering of a family graph.
+//!
+//! Persons become nodes labelled with their name and lifespan, shaped and
+//! colored by sex. Each family becomes a small point node: spouses link to
+//! it with undirected edges, and it points to each child, which is the
+//! layout Graphviz's `dot` engine handles best for pedigrees.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+
+use oxidgene_cache::CachedPerson;
+use oxidgene_cache::builder::build_search_entry;
+use oxidgene_core::enums::Sex;
+use uuid::Uuid;
+
+/// Persons within `generations` of `root`: its ancestors and descendants up
+/// to that many generations away, plus the spouses of the root and of every
+/// included descendant so their families render whole. `None` means no
+/// limit.
+pub fn scope_to_root(
+    persons: &[CachedPerson],
+    root: Uuid,
+    generations: Option<u32>,
+) -> Vec<&CachedPerson> {
+    let by_id: HashMap<Uuid, &CachedPerson> = persons.iter().map(|p| (p.person_id, p)).collect();
+    let max_depth = generations.unwrap_or(u32::MAX);
+    let mut included = HashSet::from([root]);
+
+    // Ancestors.
+    let mut queue = VecDeque::from([(root, 0u32)]);
+    while let Some((id, depth)) = queue.pop_front() {
+        let Some(link) = by_id.get(&id).and_then(|p| p.family_as_child.as_ref()) else {
+            continue;
+        };
+        if depth >= max_depth {
+            continue;
+        }
+        for parent_id in [link.father_id, link.mother_id].into_iter().flatten() {
+            if included.insert(parent_id) {
+                queue.push_back((parent_id, depth + 1));
+            }
+        }
+    }
+
+    // Descendants and their spouses.
+    let mut queue = VecDeque::from([(root, 0u32)]);
+    let mut visited = HashSet::from([root]);
+    while let Some((id, depth)) = queue.pop_front() {
+        let Some(person) = by_id.get(&id) else {
+            continue;
+        };
+        for family in &person.families_as_spouse {
+            if let Some(spouse_id) = family.spouse_id {
+                included.insert(spouse_id);
+            }
+            if depth >= max_depth {
+                continue;
+            }
+            for &child_id in &family.children_ids {
+                included.insert(child_id);
+                if visited.insert(child_id) {
+                    queue.push_back((child_id, depth + 1));
+                }
+            }
+        }
+    }
+
+    persons
+        .iter()
+        .filter(|p| included.contains(&p.person_id))
+        .collect()
+}
+
+/// Render persons and the families linking them as a DOT digraph.
+///
+/// Families with fewer than two rendered members are left out, so scoping
+/// never produces dangling family nodes.
+pub fn render<'a>(tree_name: &str, persons: impl IntoIterator<Item = &'a CachedPerson>) -> String {
+    let mut persons: Vec<&CachedPerson> = persons.into_iter().collect();
+    persons.sort_by_key(|p| p.person_id);
+    let included: HashSet<Uuid> = persons.iter().map(|p| p.person_id).collect();
+
+    // family id → (spouses, children), each in first-seen order.
+    let mut families: BTreeMap<Uuid, (BTreeSet<Uuid>, Vec<Uuid>)> = BTreeMap::new();
+    for person in &persons {
+        for link in &person.families_as_spouse {
+            let (spouses, children) = families.entry(link.family_id).or_default();
+            spouses.insert(person.person_id);
+            spouses.extend(link.spouse_id.filter(|id| included.contains(id)));
+            for &child_id in &link.children_ids {
+                if included.contains(&child_id) && !children.contains(&child_id) {
+                    children.push(child_id);
+                }
+            }
+        }
+        if let Some(link) = &person.family_as_child {
+            let (spouses, children) = families.entry(link.family_id).or_default();
+            spouses.extend(
+                [link.father_id, link.mother_id]
+                    .into_iter()
+                    .flatten()
+                    .filter(|id| included.contains(id)),
+            );
+            if !children.contains(&person.person_id) {
+                children.push(person.person_id);
+            }
+        }
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(out, "digraph \"{}\" {{", escape(tree_name));
+    out.push_str("  rankdir=TB;\n");
+    out.push_str("  node [fontname=\"Helvetica\", fontsize=10, style=filled];\n");
+    out.push_str("  edge [color=\"#666666\"];\n");
+
+    for person in &persons {
+        let entry = build_search_entry(person);
+        let name = if entry.display_name.is_empty() {
+            "?"
+        } else {
+            entry.display_name.as_str()
+        };
+        let label = match (entry.birth_year, entry.death_year) {
+            (None, None) => escape(name),
+            (birth, death) => format!(
+                "{}\\n{}\u{2013}{}",
+                escape(name),
+                birth.unwrap_or_default(),
+                death.unwrap_or_default()
+            ),
+        };
+        let (shape, color) = match person.sex {
+            Sex::Male => ("box", "#cfe2ff"),
+            Sex::Female => ("ellipse", "#f8d7e3"),
+            Sex::Intersex => ("hexagon", "#e5dbff"),
+            Sex::Unknown => ("octagon", "#e9ecef"),
+        };
+        let _ = writeln!(
+            out,
+            "  \"p_{}\" [label=\"{label}\", shape={shape}, fillcolor=\"{color}\"];",
+            person.person_id
+        );
+    }
+
+    for (family_id, (spouses, children)) in &families {
+        if spouses.len() + children.len() < 2 {
+            continue;
+        }
+        let _ = writeln!(
+            out,
+            "  \"f_{family_id}\" [shape=point, width=0.08, fillcolor=\"#666666\"];"
+        );
+        for spouse_id in spouses {
+            let _ = writeln!(
+                out,
+                "  \"p_{spouse_id}\" -> \"f_{family_id}\" [arrowhead=none];"
+            );
+        }
+        for child_id in children {
+            let _ = writeln!(out, "  \"f_{family_id}\" -> \"p_{child_id}\";");
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Escape a string for use inside a double-quoted DOT ID.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
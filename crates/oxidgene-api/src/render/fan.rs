@@ -0,0 +1,240 @@
+//! SVG fan chart of a person's ancestors.
+//!
+//! The root person sits in a central disc and each ring around it is one
+//! generation. A ring of depth `d` holds `2^d` evenly sized wedges, one per
+//! Ahnentafel number in that generation, so an ancestor's wedge is found
+//! from their number alone: father's line on the top half, mother's on the
+//! bottom. Unknown ancestors keep their wedge, drawn empty, so the gaps in
+//! the research stay visible.
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+use oxidgene_cache::CachedPerson;
+use oxidgene_cache::builder::build_search_entry;
+use oxidgene_core::enums::Sex;
+use oxidgene_core::numbering::AhnentafelEntry;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Most ancestor rings a chart draws; the outer ring already has 256 wedges.
+pub const MAX_GENERATIONS: u32 = 8;
+
+/// Radius of the central disc holding the root person.
+const ROOT_RADIUS: f64 = 70.0;
+/// Radial thickness of each generation ring.
+const RING_WIDTH: f64 = 80.0;
+const MARGIN: f64 = 10.0;
+
+/// Color scheme of the wedges.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FanPalette {
+    /// Blue for men, pink for women, as in the other charts.
+    #[default]
+    Sex,
+    /// One color per generation ring.
+    Generation,
+    /// Shades of gray, for black-and-white printing.
+    Gray,
+}
+
+impl FanPalette {
+    fn fill(self, sex: Sex, depth: u32) -> &'static str {
+        const RINGS: [&str; 8] = [
+            "#fff3bf", "#d3f9d8", "#d0ebff", "#e5dbff", "#ffdeeb", "#ffe8cc", "#c3fae8", "#e9ecef",
+        ];
+        match (self, sex) {
+            (Self::Sex, Sex::Male) => "#cfe2ff",
+            (Self::Sex, Sex::Female) => "#f8d7e3",
+            (Self::Sex, Sex::Intersex) => "#e5dbff",
+            (Self::Sex, Sex::Unknown) => "#e9ecef",
+            (Self::Generation, _) => RINGS[depth as usize % RINGS.len()],
+            (Self::Gray, Sex::Male) => "#d9d9d9",
+            (Self::Gray, Sex::Female) => "#f0f0f0",
+            (Self::Gray, Sex::Intersex) => "#e0e0e0",
+            (Self::Gray, Sex::Unknown) => "#e6e6e6",
+        }
+    }
+}
+
+/// Render the fan chart of `entries` (the root's Ahnentafel numbering) over
+/// `generations` ancestor rings. Names and lifespans come from `persons`.
+pub fn render(
+    entries: &[AhnentafelEntry],
+    persons: &[CachedPerson],
+    generations: u32,
+    palette: FanPalette,
+) -> String {
+    let generations = generations.clamp(1, MAX_GENERATIONS);
+    let by_id: HashMap<Uuid, &CachedPerson> = persons.iter().map(|p| (p.person_id, p)).collect();
+    let by_number: HashMap<u64, Uuid> = entries.iter().map(|e| (e.number, e.person_id)).collect();
+
+    let radius = ROOT_RADIUS + f64::from(generations) * RING_WIDTH;
+    let size = 2.0 * (radius + MARGIN);
+    let center = size / 2.0;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" \
+         viewBox=\"0 0 {size} {size}\" font-family=\"Helvetica, Arial, sans-serif\">"
+    );
+    out.push_str("  <g stroke=\"#666666\" stroke-width=\"1\">\n");
+    // Written after the wedges so the text is not stroked.
+    let mut labels = String::new();
+
+    for depth in 1..=generations {
+        let slots = 1u64 << depth;
+        let inner = ROOT_RADIUS + f64::from(depth - 1) * RING_WIDTH;
+        let outer = inner + RING_WIDTH;
+        let sweep = 2.0 * PI / slots as f64;
+        for slot in 0..slots {
+            // Start on the left (9 o'clock) and go clockwise over the top.
+            let start = -PI + slot as f64 * sweep;
+            let end = start + sweep;
+            let path = wedge_path(center, inner, outer, start, end);
+            let person = by_number.get(&(slots + slot)).and_then(|id| by_id.get(id));
+            let Some(person) = person else {
+                let _ = writeln!(
+                    out,
+                    "    <path class=\"wedge empty\" d=\"{path}\" fill=\"#ffffff\"/>"
+                );
+                continue;
+            };
+            let _ = writeln!(
+                out,
+                "    <path class=\"wedge\" d=\"{path}\" fill=\"{}\"/>",
+                palette.fill(person.sex, depth)
+            );
+            write_wedge_label(&mut labels, person, center, inner, start, end, depth);
+        }
+    }
+
+    let root = entries
+        .iter()
+        .find(|e| e.number == 1)
+        .and_then(|e| by_id.get(&e.person_id));
+    let fill = root.map_or("#ffffff", |p| palette.fill(p.sex, 0));
+    let _ = writeln!(
+        out,
+        "    <circle class=\"wedge root\" cx=\"{center}\" cy=\"{center}\" r=\"{ROOT_RADIUS}\" fill=\"{fill}\"/>"
+    );
+    out.push_str("  </g>\n");
+    out.push_str(&labels);
+    if let Some(root) = root {
+        let (name, lifespan) = label(root);
+        let name = truncate(&name, 16);
+        let _ = writeln!(
+            out,
+            "  <text x=\"{center}\" y=\"{center}\" font-size=\"12\" text-anchor=\"middle\">\
+             <tspan x=\"{center}\" dy=\"-0.2em\">{}</tspan>\
+             <tspan x=\"{center}\" dy=\"1.2em\" font-size=\"10\">{}</tspan></text>",
+            escape(&name),
+            escape(&lifespan)
+        );
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Name and `birth–death` years of a person, `?` when nameless.
+fn label(person: &CachedPerson) -> (String, String) {
+    let entry = build_search_entry(person);
+    let name = if entry.display_name.is_empty() {
+        "?".to_string()
+    } else {
+        entry.display_name
+    };
+    let lifespan = match (entry.birth_year, entry.death_year) {
+        (None, None) => String::new(),
+        (birth, death) => format!(
+            "{}\u{2013}{}",
+            birth.unwrap_or_default(),
+            death.unwrap_or_default()
+        ),
+    };
+    (name, lifespan)
+}
+
+/// Label of an ancestor's wedge: horizontal in the wide parents' ring,
+/// along the radius further out so it fits the narrowing wedges.
+fn write_wedge_label(
+    out: &mut String,
+    person: &CachedPerson,
+    center: f64,
+    inner: f64,
+    start: f64,
+    end: f64,
+    depth: u32,
+) {
+    let (name, lifespan) = label(person);
+    let mid = (start + end) / 2.0;
+    let (x, y) = polar(center, inner + RING_WIDTH / 2.0, mid);
+    let font_size = (13.0 - f64::from(depth)).max(7.0);
+    let name = truncate(&name, if depth == 1 { 24 } else { 14 });
+    let lifespan_line = if depth <= 4 && !lifespan.is_empty() {
+        format!(
+            "<tspan x=\"{x:.2}\" dy=\"1.2em\" font-size=\"{:.0}\">{}</tspan>",
+            font_size - 1.0,
+            escape(&lifespan)
+        )
+    } else {
+        String::new()
+    };
+    let rotation = if depth == 1 {
+        0.0
+    } else {
+        // Keep text upright: flip labels on the left half.
+        let degrees = mid.to_degrees();
+        if mid.cos() < 0.0 {
+            degrees + 180.0
+        } else {
+            degrees
+        }
+    };
+    let _ = writeln!(
+        out,
+        "  <text x=\"{x:.2}\" y=\"{y:.2}\" font-size=\"{font_size:.0}\" text-anchor=\"middle\" \
+         transform=\"rotate({rotation:.2} {x:.2} {y:.2})\">\
+         <tspan x=\"{x:.2}\" dy=\"-0.2em\">{}</tspan>{lifespan_line}</text>",
+        escape(&name)
+    );
+}
+
+/// SVG path of the ring sector between radii `inner` and `outer` and
+/// angles `start` and `end` (radians, clockwise on screen).
+fn wedge_path(center: f64, inner: f64, outer: f64, start: f64, end: f64) -> String {
+    let large_arc = u8::from(end - start > PI);
+    let (ox0, oy0) = polar(center, outer, start);
+    let (ox1, oy1) = polar(center, outer, end);
+    let (ix1, iy1) = polar(center, inner, end);
+    let (ix0, iy0) = polar(center, inner, start);
+    format!(
+        "M{ox0:.2},{oy0:.2} A{outer},{outer} 0 {large_arc} 1 {ox1:.2},{oy1:.2} \
+         L{ix1:.2},{iy1:.2} A{inner},{inner} 0 {large_arc} 0 {ix0:.2},{iy0:.2} Z"
+    )
+}
+
+fn polar(center: f64, radius: f64, angle: f64) -> (f64, f64) {
+    (center + radius * angle.cos(), center + radius * angle.sin())
+}
+
+/// Shorten `s` to at most `max` characters, marking the cut with `…`.
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+    let mut cut: String = s.chars().take(max - 1).collect();
+    cut.push('\u{2026}');
+    cut
+}
+
+/// Escape a string for SVG text content and attribute values.
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,6 @@
+//! Renderers turning cached tree data into third-party formats.
+
+pub mod csv;
+pub mod dot;
+pub mod fan;
+pub mod portrait;
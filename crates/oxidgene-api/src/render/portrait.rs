@@ -0,0 +1,22 @@
+//! Placeholder portrait of a person without a profile photo: a head and
+//! shoulders silhouette on a background matching the charts' colors.
+
+use oxidgene_core::enums::Sex;
+
+/// Render the placeholder portrait for a person of sex `sex`, as a square
+/// SVG image.
+pub fn placeholder(sex: Sex) -> String {
+    let (background, figure) = match sex {
+        Sex::Male => ("#cfe2ff", "#8bb0e8"),
+        Sex::Female => ("#f8d7e3", "#e09ab4"),
+        Sex::Intersex => ("#e5dbff", "#b197fc"),
+        Sex::Unknown => ("#e9ecef", "#adb5bd"),
+    };
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"100\" viewBox=\"0 0 100 100\">\n  \
+         <rect width=\"100\" height=\"100\" fill=\"{background}\"/>\n  \
+         <circle cx=\"50\" cy=\"38\" r=\"18\" fill=\"{figure}\"/>\n  \
+         <path d=\"M16,100 C16,72 32,62 50,62 C68,62 84,72 84,100 Z\" fill=\"{figure}\"/>\n\
+         </svg>\n"
+    )
+}
@@ -0,0 +1,249 @@
+//! Per-tree access control: the [`TreeRole`] of the request's [`Actor`]
+//! on the tree in the path.
+//!
+//! [`RequireTreeRole`] rejects a request unless the actor's role is at
+//! least its marker's ([`Viewer`], [`Editor`], [`Owner`]).
+//! [`enforce_tree_role`] applies it to every `/trees/{tree_id}/...` route:
+//! reads need a viewer, writes an editor. Handlers reserved to owners
+//! (deleting or sharing the tree, managing its members) also take a
+//! `RequireTreeRole<Owner>`.
+//!
+//! Roles only apply with authentication enabled; without it the local actor
+//! owns every tree. Admins (see [`crate::config::JwtConfig::admins`]) own
+//! every tree too, and are the only ones [`require_admin`] lets through. A
+//! tree the actor is not a member of answers `404`, as if it did not exist;
+//! this includes a tree without any member (created before authentication
+//! was turned on), until an admin adds its first owner.
+//!
+//! [`enforce_tree_role`] also checks that the entities named in the path
+//! (`{person_id}`, `{event_id}`, ...) belong to `{tree_id}`: one of another
+//! tree answers `404` as well, whatever the actor's roles there.
+
+use std::marker::PhantomData;
+
+use axum::extract::{FromRequestParts, MatchedPath, RawPathParams, Request, State};
+use axum::http::Method;
+use axum::http::request::Parts;
+use axum::middleware::Next;
+use axum::response::Response;
+use oxidgene_core::OxidGeneError;
+use oxidgene_core::enums::TreeRole;
+use oxidgene_db::repo::{TreeEntity, TreeMembershipRepo, TreeScopeRepo};
+use uuid::Uuid;
+
+use super::auth::Actor;
+use super::error::ApiError;
+use super::state::AppState;
+
+/// Tree routes written with `POST` that only read the tree (exports), so
+/// viewers may call them.
+const READ_ONLY_POSTS: &[&str] = &["/{tree_id}/export", "/{tree_id}/export/jobs"];
+
+/// Path parameters naming an entity of the path's tree.
+const PATH_ENTITIES: &[(&str, TreeEntity)] = &[
+    ("person_id", TreeEntity::Person),
+    ("other_id", TreeEntity::Person),
+    ("root_person_id", TreeEntity::Person),
+    ("name_id", TreeEntity::PersonName),
+    ("family_id", TreeEntity::Family),
+    ("spouse_id", TreeEntity::FamilySpouse),
+    ("child_id", TreeEntity::FamilyChild),
+    ("event_id", TreeEntity::Event),
+    ("participant_id", TreeEntity::EventParticipant),
+    ("place_id", TreeEntity::Place),
+    ("source_id", TreeEntity::Source),
+    ("citation_id", TreeEntity::Citation),
+    ("media_id", TreeEntity::Media),
+    ("link_id", TreeEntity::MediaLink),
+    ("note_id", TreeEntity::Note),
+    ("task_id", TreeEntity::ResearchTask),
+    ("share_id", TreeEntity::ShareLink),
+];
+
+/// The least role a [`RequireTreeRole`] accepts.
+pub trait MinTreeRole: Send + Sync {
+    const ROLE: TreeRole;
+}
+
+/// Marker: [`TreeRole::Viewer`] or above.
+pub struct Viewer;
+
+/// Marker: [`TreeRole::Editor`] or above.
+pub struct Editor;
+
+/// Marker: [`TreeRole::Owner`].
+pub struct Owner;
+
+impl MinTreeRole for Viewer {
+    const ROLE: TreeRole = TreeRole::Viewer;
+}
+
+impl MinTreeRole for Editor {
+    const ROLE: TreeRole = TreeRole::Editor;
+}
+
+impl MinTreeRole for Owner {
+    const ROLE: TreeRole = TreeRole::Owner;
+}
+
+/// Extractor checking that the actor's role on the path's `{tree_id}` is
+/// at least `R::ROLE`: `403` for a lesser role, `404` for a non-member.
+/// Holds the actor's role.
+pub struct RequireTreeRole<R: MinTreeRole> {
+    pub role: TreeRole,
+    _min: PhantomData<R>,
+}
+
+impl<R: MinTreeRole> FromRequestParts<AppState> for RequireTreeRole<R> {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, ApiError> {
+        let tree_id = path_tree_id(parts, state).await.ok_or_else(|| {
+            ApiError(OxidGeneError::Internal(
+                "tree role required on a route without {tree_id}".to_string(),
+            ))
+        })?;
+        let actor = parts
+            .extensions
+            .get::<Actor>()
+            .ok_or_else(|| ApiError(OxidGeneError::Unauthorized("no actor".to_string())))?;
+        let role = require_tree_role(state, actor, tree_id, R::ROLE).await?;
+        Ok(Self {
+            role,
+            _min: PhantomData,
+        })
+    }
+}
+
+/// The role of `actor` on a tree: owner if authentication is off or they
+/// are an admin, theirs if they are a member, else `NotFound`.
+pub async fn tree_role(
+    state: &AppState,
+    actor: &Actor,
+    tree_id: Uuid,
+) -> Result<TreeRole, OxidGeneError> {
+    if !state.auth.is_enabled() || actor.is_admin {
+        return Ok(TreeRole::Owner);
+    }
+    if let Some(role) = TreeMembershipRepo::role(&state.db, tree_id, &actor.id).await? {
+        return Ok(role);
+    }
+    Err(OxidGeneError::NotFound {
+        entity: "Tree",
+        id: tree_id,
+    })
+}
+
+/// [`tree_role`], failing with `Forbidden` if it is less than `min`. For
+/// trees that are not the path's `{tree_id}` (the target of a move).
+pub async fn require_tree_role(
+    state: &AppState,
+    actor: &Actor,
+    tree_id: Uuid,
+    min: TreeRole,
+) -> Result<TreeRole, ApiError> {
+    let role = tree_role(state, actor, tree_id).await?;
+    if role < min {
+        return Err(ApiError(OxidGeneError::Forbidden(format!(
+            "this needs the {min} role on tree {tree_id}, `{}` is {role}",
+            actor.id
+        ))));
+    }
+    Ok(role)
+}
+
+/// Make `actor` the owner of a tree they just created. Nothing is recorded
+/// without authentication; an admin gives such trees their first owner
+/// once it is turned on.
+pub async fn claim_tree(state: &AppState, actor: &Actor, tree_id: Uuid) -> Result<(), ApiError> {
+    if state.auth.is_enabled() {
+        TreeMembershipRepo::set(&state.db, tree_id, &actor.id, TreeRole::Owner).await?;
+    }
+    Ok(())
+}
+
+/// The `{tree_id}` path parameter, if the route has one. A malformed id
+/// is `None`, left for the handler's `Path` extractor to reject.
+async fn path_tree_id(parts: &mut Parts, state: &AppState) -> Option<Uuid> {
+    let params = RawPathParams::from_request_parts(parts, state).await.ok()?;
+    let (_, value) = params.iter().find(|(key, _)| *key == "tree_id")?;
+    Uuid::parse_str(value).ok()
+}
+
+/// Fail with `NotFound` if an entity named in the path belongs to another
+/// tree than `tree_id`. Malformed ids are left for the handler's `Path`
+/// extractor to reject, unknown ones for the handler to report.
+async fn ensure_path_entities_in_tree(
+    parts: &mut Parts,
+    state: &AppState,
+    tree_id: Uuid,
+) -> Result<(), OxidGeneError> {
+    let Ok(params) = RawPathParams::from_request_parts(parts, state).await else {
+        return Ok(());
+    };
+    for (key, value) in params.iter() {
+        let Some((_, entity)) = PATH_ENTITIES.iter().find(|(name, _)| *name == key) else {
+            continue;
+        };
+        if let Ok(id) = Uuid::parse_str(value) {
+            TreeScopeRepo::ensure_in_tree(&state.db, tree_id, *entity, id).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Fail with `NotFound` if an entity a request body refers to belongs to
+/// another tree than `tree_id`, as the path's entities are checked by
+/// [`enforce_tree_role`]. `None` ids are skipped.
+pub async fn ensure_in_tree(
+    state: &AppState,
+    tree_id: Uuid,
+    refs: impl IntoIterator<Item = (TreeEntity, Option<Uuid>)>,
+) -> Result<(), ApiError> {
+    for (entity, id) in refs {
+        if let Some(id) = id {
+            TreeScopeRepo::ensure_in_tree(&state.db, tree_id, entity, id).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Middleware for the admin routes: only admins may call them.
+pub async fn require_admin(request: Request, next: Next) -> Result<Response, ApiError> {
+    let is_admin = request
+        .extensions()
+        .get::<Actor>()
+        .is_some_and(|actor| actor.is_admin);
+    if !is_admin {
+        return Err(ApiError(OxidGeneError::Forbidden(
+            "this needs an admin".to_string(),
+        )));
+    }
+    Ok(next.run(request).await)
+}
+
+/// Middleware for the `/trees` routes: a request on a tree needs the
+/// viewer role to read it and the editor role to change it, and may only
+/// name entities of that tree.
+pub async fn enforce_tree_role(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let (mut parts, body) = request.into_parts();
+    if let Some(tree_id) = path_tree_id(&mut parts, &state).await {
+        let read_only = matches!(parts.method, Method::GET | Method::HEAD)
+            || parts.extensions.get::<MatchedPath>().is_some_and(|path| {
+                READ_ONLY_POSTS
+                    .iter()
+                    .any(|suffix| path.as_str().ends_with(suffix))
+            });
+        if read_only {
+            RequireTreeRole::<Viewer>::from_request_parts(&mut parts, &state).await?;
+        } else {
+            RequireTreeRole::<Editor>::from_request_parts(&mut parts, &state).await?;
+        }
+        ensure_path_entities_in_tree(&mut parts, &state, tree_id).await?;
+    }
+    Ok(next.run(Request::from_parts(parts, body)).await)
+}
@@ -0,0 +1,60 @@
+//! REST handlers for instance-wide maintenance.
+
+use axum::Json;
+use axum::extract::State;
+use oxidgene_db::repo::{EventRepo, PersonNameRepo, PersonRepo};
+
+use super::dto::{DateSortBackfillResponse, DisplayNameBackfillResponse, PhoneticBackfillResponse};
+use super::error::ApiError;
+use super::state::AppState;
+
+/// Rows updated per transaction by the backfills.
+const BACKFILL_CHUNK_SIZE: u64 = 500;
+
+/// POST /api/v1/admin/backfill-date-sort
+///
+/// Fill in the missing `date_sort` of every event from its `date_value`,
+/// for data written before it was derived. Safe to run again: it reports
+/// how many events were updated and how many phrases could not be parsed.
+/// The caches of the trees touched are dropped, to be rebuilt on demand.
+pub async fn backfill_date_sort(
+    State(state): State<AppState>,
+) -> Result<Json<DateSortBackfillResponse>, ApiError> {
+    let result = EventRepo::backfill_date_sort(&state.db, BACKFILL_CHUNK_SIZE).await?;
+    for tree_id in &result.tree_ids {
+        state.cache.invalidate_tree(*tree_id).await?;
+    }
+    Ok(Json(DateSortBackfillResponse {
+        updated: result.updated,
+        unparsed: result.unparsed,
+    }))
+}
+
+/// POST /api/v1/admin/backfill-phonetic
+///
+/// Fill in the missing phonetic codes of every person name, for names
+/// written before they were stored. Safe to run again: it reports how many
+/// names were updated. The codes are not cached, so no cache is touched.
+pub async fn backfill_phonetic(
+    State(state): State<AppState>,
+) -> Result<Json<PhoneticBackfillResponse>, ApiError> {
+    let updated = PersonNameRepo::backfill_phonetic(&state.db, BACKFILL_CHUNK_SIZE).await?;
+    Ok(Json(PhoneticBackfillResponse { updated }))
+}
+
+/// POST /api/v1/admin/backfill-display-names
+///
+/// Recompute the cached display name of every person whose cache is out of
+/// date, e.g. written before it was stored. Safe to run again: it reports
+/// how many persons were updated. The caches of the trees touched are
+/// dropped, to be rebuilt on demand.
+pub async fn backfill_display_names(
+    State(state): State<AppState>,
+) -> Result<Json<DisplayNameBackfillResponse>, ApiError> {
+    let (updated, tree_ids) =
+        PersonRepo::backfill_display_names(&state.db, BACKFILL_CHUNK_SIZE).await?;
+    for tree_id in &tree_ids {
+        state.cache.invalidate_tree(*tree_id).await?;
+    }
+    Ok(Json(DisplayNameBackfillResponse { updated }))
+}
@@ -0,0 +1,222 @@
+//! Optional bearer-token authentication and the request's actor.
+//!
+//! [`authenticate`] runs in front of every route but the anonymous share
+//! views and attaches an [`Actor`] request extension for handlers to read
+//! (share links record who created them) and records its id on the request
+//! span, so that every logged request says who made it. With
+//! [`AuthConfig::Disabled`] — the desktop default — nothing is checked and
+//! the actor is always [`Actor::local`]; with [`AuthConfig::Jwt`] a request
+//! without a valid `Authorization: Bearer <JWT>` is refused with `401`.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::{Request, State};
+use axum::http::header;
+use axum::middleware::Next;
+use axum::response::Response;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use oxidgene_core::OxidGeneError;
+use serde::Deserialize;
+use tokio::sync::Mutex;
+
+use super::error::ApiError;
+use crate::config::{AuthConfig, JwtConfig};
+
+/// Actor id of requests when authentication is disabled.
+pub const LOCAL_ACTOR_ID: &str = "local";
+
+/// Timeout of a JWKS download.
+const JWKS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Shortest delay between two JWKS downloads: a token naming an unknown
+/// key triggers a refresh (the provider may have rotated its keys), but
+/// not more often than this.
+const JWKS_MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Who is making a request, attached as a request extension by
+/// [`authenticate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Actor {
+    /// The token's `sub` claim, or [`LOCAL_ACTOR_ID`].
+    pub id: String,
+    /// Whether the actor administers the instance (see
+    /// [`JwtConfig::admins`]); the local actor always does.
+    pub is_admin: bool,
+}
+
+impl Actor {
+    /// The single user of an instance without authentication.
+    pub fn local() -> Self {
+        Self {
+            id: LOCAL_ACTOR_ID.to_string(),
+            is_admin: true,
+        }
+    }
+}
+
+/// The claims read from a token; `exp` (and `iss`/`aud` when configured)
+/// are checked by `jsonwebtoken` itself.
+#[derive(Debug, Deserialize)]
+struct Claims {
+    sub: String,
+}
+
+/// The downloaded key set and when it was fetched.
+#[derive(Debug, Default)]
+struct JwksCache {
+    keys: Option<JwkSet>,
+    fetched_at: Option<Instant>,
+}
+
+/// Verifies bearer tokens as configured, shared through `AppState`.
+#[derive(Debug)]
+pub struct Authenticator {
+    config: AuthConfig,
+    http: reqwest::Client,
+    jwks: Mutex<JwksCache>,
+}
+
+impl Authenticator {
+    pub fn new(config: AuthConfig) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(JWKS_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self {
+            config,
+            http,
+            jwks: Mutex::new(JwksCache::default()),
+        }
+    }
+
+    /// Whether requests must carry a token (and tree roles are enforced).
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self.config, AuthConfig::Disabled)
+    }
+
+    /// The actor of a request with the given `Authorization` header value.
+    pub async fn actor(&self, authorization: Option<&str>) -> Result<Actor, OxidGeneError> {
+        let AuthConfig::Jwt(jwt) = &self.config else {
+            return Ok(Actor::local());
+        };
+        let token = authorization
+            .and_then(|value| {
+                value
+                    .strip_prefix("Bearer ")
+                    .or_else(|| value.strip_prefix("bearer "))
+            })
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| unauthorized("missing bearer token"))?;
+
+        let header = jsonwebtoken::decode_header(token)
+            .map_err(|e| unauthorized(&format!("malformed token: {e}")))?;
+        let key = self.decoding_key(jwt, &header).await?;
+        let mut validation = Validation::new(header.alg);
+        if let Some(issuer) = &jwt.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        match &jwt.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+        let claims = jsonwebtoken::decode::<Claims>(token, &key, &validation)
+            .map_err(|e| unauthorized(&format!("invalid token: {e}")))?
+            .claims;
+        if claims.sub.is_empty() {
+            return Err(unauthorized("the token has an empty `sub` claim"));
+        }
+        Ok(Actor {
+            is_admin: jwt.admins.contains(&claims.sub),
+            id: claims.sub,
+        })
+    }
+
+    /// The key to check a token signed with `header.alg` against: the
+    /// shared secret for HMAC algorithms, else the key set's key `kid`.
+    async fn decoding_key(
+        &self,
+        jwt: &JwtConfig,
+        header: &jsonwebtoken::Header,
+    ) -> Result<DecodingKey, OxidGeneError> {
+        let is_hmac = matches!(
+            header.alg,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        );
+        if is_hmac {
+            // An HMAC token must not be checked against a public key, which
+            // anyone may hold.
+            let secret = jwt
+                .secret
+                .as_deref()
+                .ok_or_else(|| unauthorized("HMAC-signed tokens are not accepted"))?;
+            return Ok(DecodingKey::from_secret(secret.as_bytes()));
+        }
+        let Some(url) = jwt.jwks_url.as_deref() else {
+            return Err(unauthorized(&format!(
+                "{:?}-signed tokens are not accepted",
+                header.alg
+            )));
+        };
+        let kid = header
+            .kid
+            .as_deref()
+            .ok_or_else(|| unauthorized("the token names no key (`kid`)"))?;
+
+        let mut cache = self.jwks.lock().await;
+        let known = cache
+            .keys
+            .as_ref()
+            .is_some_and(|keys| keys.find(kid).is_some());
+        let may_refresh = cache
+            .fetched_at
+            .is_none_or(|at| at.elapsed() >= JWKS_MIN_REFRESH_INTERVAL);
+        if !known && may_refresh {
+            cache.fetched_at = Some(Instant::now());
+            match self.fetch_jwks(url).await {
+                Ok(keys) => cache.keys = Some(keys),
+                Err(e) => tracing::warn!(url, error = %e, "JWKS download failed"),
+            }
+        }
+        let jwk = cache
+            .keys
+            .as_ref()
+            .and_then(|keys| keys.find(kid))
+            .ok_or_else(|| unauthorized(&format!("unknown signing key `{kid}`")))?;
+        DecodingKey::from_jwk(jwk)
+            .map_err(|e| unauthorized(&format!("unusable signing key `{kid}`: {e}")))
+    }
+
+    async fn fetch_jwks(&self, url: &str) -> Result<JwkSet, reqwest::Error> {
+        self.http
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+}
+
+fn unauthorized(reason: &str) -> OxidGeneError {
+    OxidGeneError::Unauthorized(reason.to_string())
+}
+
+/// Middleware resolving the request's [`Actor`]: attached as an
+/// extension, and recorded as the `actor` field of the request span.
+pub async fn authenticate(
+    State(auth): State<Arc<Authenticator>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let authorization = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let actor = auth.actor(authorization).await?;
+    tracing::Span::current().record("actor", actor.id.as_str());
+    request.extensions_mut().insert(actor);
+    Ok(next.run(request).await)
+}
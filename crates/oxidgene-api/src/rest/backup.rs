@@ -0,0 +1,62 @@
+//! REST handlers for database backups (desktop app).
+//!
+//! Available only when the state carries a backup directory (see
+//! [`AppState::with_backups`]); otherwise every endpoint answers with a
+//! validation error.
+
+use std::sync::Arc;
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use oxidgene_core::OxidGeneError;
+
+use super::dto::RestoreBackupResponse;
+use super::error::ApiError;
+use super::state::AppState;
+use crate::service::backup::{BackupInfo, Backups};
+
+/// The configured backups, or an error if backups are disabled.
+fn backups(state: &AppState) -> Result<&Arc<Backups>, ApiError> {
+    state.backups.as_ref().ok_or_else(|| {
+        ApiError(OxidGeneError::Validation(
+            "backups are not enabled".to_string(),
+        ))
+    })
+}
+
+/// POST /api/v1/backup
+///
+/// Take a backup now.
+pub async fn create_backup(
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<BackupInfo>), ApiError> {
+    let backup = backups(&state)?.create(&state.db).await?;
+    Ok((StatusCode::CREATED, Json(backup)))
+}
+
+/// GET /api/v1/backup
+///
+/// List the backups, newest first.
+pub async fn list_backups(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<BackupInfo>>, ApiError> {
+    Ok(Json(backups(&state)?.list()?))
+}
+
+/// POST /api/v1/backup/:file_name/restore
+///
+/// Stage a backup to replace the database; it takes effect when the app is
+/// restarted.
+pub async fn restore_backup(
+    State(state): State<AppState>,
+    Path(file_name): Path<String>,
+) -> Result<Json<RestoreBackupResponse>, ApiError> {
+    let restored = backups(&state)?
+        .stage_restore(&state.db, &file_name)
+        .await?;
+    Ok(Json(RestoreBackupResponse {
+        restored,
+        restart_required: true,
+    }))
+}
@@ -3,12 +3,17 @@
 use axum::Json;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
-use oxidgene_db::repo::{CitationRepo, SourceRepo};
+use oxidgene_core::OxidGeneError;
+use oxidgene_db::repo::{CitationRepo, SourceRepo, TreeEntity, with_transaction};
 use uuid::Uuid;
 
-use super::dto::{CitationListQuery, CreateCitationRequest, UpdateCitationRequest};
+use super::access;
+use super::dto::{
+    BulkCreateCitationRequest, CitationListQuery, CreateCitationRequest, UpdateCitationRequest,
+};
 use super::error::ApiError;
 use super::state::AppState;
+use crate::service::citation::validate_fact_target;
 
 /// GET /api/v1/trees/:tree_id/citations
 pub async fn list_citations(
@@ -40,6 +45,12 @@ pub async fn list_citations(
                 && query
                     .family_id
                     .is_none_or(|fid| citation.family_id == Some(fid))
+                && query
+                    .fact_type
+                    .is_none_or(|fact| citation.fact_type == Some(fact))
+                && query
+                    .fact_ref
+                    .is_none_or(|fref| citation.fact_ref == Some(fref))
         })
         .collect::<Vec<_>>();
     Ok(Json(serde_json::to_value(citations).unwrap()))
@@ -48,9 +59,28 @@ pub async fn list_citations(
 /// POST /api/v1/trees/:tree_id/citations
 pub async fn create_citation(
     State(state): State<AppState>,
-    Path(_tree_id): Path<Uuid>,
+    Path(tree_id): Path<Uuid>,
     Json(body): Json<CreateCitationRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    access::ensure_in_tree(
+        &state,
+        tree_id,
+        [
+            (TreeEntity::Source, Some(body.source_id)),
+            (TreeEntity::Person, body.person_id),
+            (TreeEntity::Event, body.event_id),
+            (TreeEntity::Family, body.family_id),
+        ],
+    )
+    .await?;
+    validate_fact_target(
+        &state.db,
+        body.person_id,
+        body.event_id,
+        body.fact_type,
+        body.fact_ref,
+    )
+    .await?;
     let id = Uuid::now_v7();
     let citation = CitationRepo::create(
         &state.db,
@@ -62,6 +92,8 @@ pub async fn create_citation(
         body.page,
         body.confidence,
         body.text,
+        body.fact_type,
+        body.fact_ref,
     )
     .await
     .map_err(ApiError::from)?;
@@ -71,6 +103,81 @@ pub async fn create_citation(
     ))
 }
 
+/// POST /api/v1/trees/:tree_id/citations/bulk
+///
+/// Cite one source, page and confidence for each of `owners`, e.g. every
+/// person on a census page. All citations are created or none.
+pub async fn bulk_create_citations(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Json(body): Json<BulkCreateCitationRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    if body.owners.is_empty() {
+        return Err(OxidGeneError::Validation("owners must not be empty".into()).into());
+    }
+    for owner in &body.owners {
+        let set = [owner.person_id, owner.event_id, owner.family_id]
+            .iter()
+            .filter(|id| id.is_some())
+            .count();
+        if set != 1 {
+            return Err(OxidGeneError::Validation(
+                "each owner needs exactly one of person_id, event_id, family_id".into(),
+            )
+            .into());
+        }
+    }
+    let source = SourceRepo::get(&state.db, body.source_id).await?;
+    if source.tree_id != tree_id {
+        return Err(OxidGeneError::NotFound {
+            entity: "Source",
+            id: body.source_id,
+        }
+        .into());
+    }
+    let owners: Vec<_> = body
+        .owners
+        .iter()
+        .flat_map(|owner| {
+            [
+                (TreeEntity::Person, owner.person_id),
+                (TreeEntity::Event, owner.event_id),
+                (TreeEntity::Family, owner.family_id),
+            ]
+        })
+        .collect();
+    access::ensure_in_tree(&state, tree_id, owners).await?;
+
+    let citations = with_transaction(&state.db, |txn| {
+        Box::pin(async move {
+            let mut citations = Vec::with_capacity(body.owners.len());
+            for owner in body.owners {
+                let citation = CitationRepo::create(
+                    txn,
+                    Uuid::now_v7(),
+                    body.source_id,
+                    owner.person_id,
+                    owner.event_id,
+                    owner.family_id,
+                    body.page.clone(),
+                    body.confidence,
+                    body.text.clone(),
+                    None,
+                    None,
+                )
+                .await?;
+                citations.push(citation);
+            }
+            Ok(citations)
+        })
+    })
+    .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::to_value(citations).unwrap()),
+    ))
+}
+
 /// PUT /api/v1/trees/:tree_id/citations/:citation_id
 pub async fn update_citation(
     State(state): State<AppState>,
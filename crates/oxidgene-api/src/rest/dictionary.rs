@@ -3,12 +3,13 @@
 
 use axum::Json;
 use axum::extract::{Path, Query, State};
-use oxidgene_db::repo::{DictionaryRepo, SOURCE_DRILL_THRESHOLD};
+use oxidgene_db::repo::{DictionaryRepo, SOURCE_DRILL_THRESHOLD, SurnameBucketing};
 use uuid::Uuid;
 
 use super::dto::{
     DictionaryEntryDto, DictionaryUsageQuery, PersonUsageEntryDto, PlaceDictionaryEntry,
     SourceDictionaryEntry, SourceDrillResponse, SourceGroupDto, SourcePrefixQuery,
+    SurnameIndexEntryDto, SurnameStatsOver, SurnameStatsQuery, SurnameStatsResponse,
 };
 use super::error::ApiError;
 use super::state::AppState;
@@ -24,6 +25,48 @@ pub async fn family_names(
     Ok(Json(entries.into_iter().map(Into::into).collect()))
 }
 
+/// GET /api/v1/trees/:tree_id/surname-index
+///
+/// Distinct surnames of primary names with their person count and index
+/// letter, for alphabetical browsing; persons under one surname are listed
+/// by `GET /persons?surname=...`.
+pub async fn surname_index(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+) -> Result<Json<Vec<SurnameIndexEntryDto>>, ApiError> {
+    let entries = DictionaryRepo::surname_index(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(entries.into_iter().map(Into::into).collect()))
+}
+
+/// Surnames counted when `limit` is not given, and the most allowed.
+const DEFAULT_SURNAME_STATS_LIMIT: usize = 10;
+const MAX_SURNAME_STATS_LIMIT: usize = 50;
+
+/// GET /api/v1/trees/:tree_id/stats/surnames?over=decade|place&limit=N
+///
+/// The most frequent surnames counted per birth decade or birth place, for
+/// charts of how a family name spread over time and space.
+pub async fn surname_stats(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Query(query): Query<SurnameStatsQuery>,
+) -> Result<Json<SurnameStatsResponse>, ApiError> {
+    let bucketing = match query.over {
+        SurnameStatsOver::Decade => SurnameBucketing::Decade,
+        SurnameStatsOver::Place => SurnameBucketing::Place,
+    };
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SURNAME_STATS_LIMIT)
+        .clamp(1, MAX_SURNAME_STATS_LIMIT);
+    let distribution = DictionaryRepo::surname_distribution(&state.db, tree_id, bucketing, limit)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(SurnameStatsResponse::new(query.over, distribution)))
+}
+
 /// GET /api/v1/trees/:tree_id/dictionary/occupations
 pub async fn occupations(
     State(state): State<AppState>,
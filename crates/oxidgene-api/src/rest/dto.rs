@@ -1,22 +1,84 @@
 //! Request/response DTOs for REST endpoints.
 
+use oxidgene_core::replace::{MatchMode, ReplaceEntity};
+use oxidgene_core::text::NameCase;
 use oxidgene_core::types::{Place, Source};
-use oxidgene_core::{ChildType, Confidence, EventType, NameType, Privacy, Sex, SpouseRole};
-use serde::{Deserialize, Serialize};
+use oxidgene_core::{
+    ChildType, Confidence, EventType, NameSelectionPolicy, NameType, NoteFormat, ParticipantRole,
+    Privacy, RelativeKind, Sex, SpouseRole, TaskStatus, TreeRole,
+};
+use serde::{Deserialize, Deserializer, Serialize};
 
 // ── Pagination query params ──────────────────────────────────────────
 
 /// Query parameters for cursor-based pagination.
 #[derive(Debug, Deserialize)]
 pub struct PaginationQuery {
-    /// Number of items to return (default: 25, max: 100).
+    /// Number of items to return (default: 25, clamped to the configured maximum page size).
     pub first: Option<u64>,
     /// Cursor to start after (UUID string).
     pub after: Option<String>,
 }
 
+/// Query parameters for listing persons (filters + pagination).
+#[derive(Debug, Deserialize)]
+pub struct PersonListQuery {
+    /// Number of items to return (default: 25, clamped to the configured maximum page size).
+    pub first: Option<u64>,
+    /// Cursor to start after (UUID string).
+    pub after: Option<String>,
+    /// Only persons whose primary name has exactly this surname.
+    pub surname: Option<String>,
+    /// Only persons carrying this tag.
+    pub tag: Option<String>,
+    /// Only persons without a recorded parent ("brick walls").
+    #[serde(default)]
+    pub no_parents: bool,
+    /// Which name fills each person's `display_name` (default: primary).
+    #[serde(default)]
+    pub name_policy: NameSelectionPolicy,
+}
+
+/// Query parameters for GET /api/v1/trees/:tree_id/persons/:person_id.
+#[derive(Debug, Deserialize)]
+pub struct PersonDetailQuery {
+    /// Which name fills `display_name` (default: primary).
+    #[serde(default)]
+    pub name_policy: NameSelectionPolicy,
+}
+
+/// A person in GET /api/v1/trees/:tree_id/persons, with its
+/// `display_name` resolved under the requested `name_policy`.
+#[derive(Debug, Serialize)]
+pub struct PersonListItem {
+    #[serde(flatten)]
+    pub person: oxidgene_core::types::Person,
+    /// How well the vital facts are sourced, from 0 to 1; see
+    /// [`oxidgene_core::summary::sourcing_score`].
+    pub sourcing_score: f32,
+    #[serde(flatten)]
+    pub lineage: oxidgene_core::summary::Lineage,
+}
+
 // ── Tree DTOs ────────────────────────────────────────────────────────
 
+/// A tree in GET /api/v1/trees and GET /api/v1/trees/:tree_id, with its
+/// live record counts.
+#[derive(Debug, Clone, Serialize)]
+pub struct TreeResponse {
+    #[serde(flatten)]
+    pub tree: oxidgene_core::types::Tree,
+    pub person_count: i64,
+    pub family_count: i64,
+    pub event_count: i64,
+    /// The latest change to the tree or any of its persons, families and
+    /// events.
+    pub last_modified_at: chrono::DateTime<chrono::Utc>,
+    /// Years of the earliest and latest dated events.
+    pub first_year: Option<i32>,
+    pub last_year: Option<i32>,
+}
+
 /// Request body for creating a tree.
 #[derive(Debug, Deserialize)]
 pub struct CreateTreeRequest {
@@ -24,13 +86,23 @@ pub struct CreateTreeRequest {
     pub description: Option<String>,
 }
 
+/// Query parameters for POST /api/v1/trees.
+#[derive(Debug, Deserialize)]
+pub struct CreateTreeQuery {
+    /// Starter content (`empty` or `self`; default `empty`).
+    #[serde(default)]
+    pub template: oxidgene_core::templates::TreeTemplate,
+}
+
 /// Request body for updating a tree.
 #[derive(Debug, Deserialize)]
 pub struct UpdateTreeRequest {
     pub name: Option<String>,
     /// `null` clears the description; absent field leaves it unchanged.
+    #[serde(default, deserialize_with = "nullable")]
     pub description: Option<Option<String>>,
     /// `null` clears the root person; absent field leaves it unchanged.
+    #[serde(default, deserialize_with = "nullable")]
     pub sosa_root_person_id: Option<Option<uuid::Uuid>>,
 }
 
@@ -40,6 +112,119 @@ pub struct DuplicateTreeRequest {
     pub name: String,
 }
 
+/// Response body for a tree integrity check.
+#[derive(Debug, Serialize)]
+pub struct IntegrityReportResponse {
+    pub error_count: usize,
+    pub warning_count: usize,
+    /// Errors first, then warnings.
+    pub issues: Vec<IntegrityIssueResponse>,
+}
+
+/// A single finding of a tree integrity check.
+#[derive(Debug, Serialize)]
+pub struct IntegrityIssueResponse {
+    /// Machine-readable issue kind, e.g. `event_missing_place`.
+    pub kind: &'static str,
+    /// `error` or `warning`.
+    pub severity: &'static str,
+    pub entity_id: uuid::Uuid,
+    pub message: String,
+    pub suggested_fix: &'static str,
+}
+
+/// Query parameters for GET /api/v1/trees/:tree_id/activity.
+#[derive(Debug, Deserialize)]
+pub struct ActivityQuery {
+    /// Maximum number of entries (default: 20, clamped to the configured maximum page size).
+    pub limit: Option<u64>,
+}
+
+/// A recently created or updated entity of a tree.
+#[derive(Debug, Serialize)]
+pub struct ActivityEntryResponse {
+    /// `person`, `family`, `event`, `place`, `source`, `media` or `note`.
+    pub kind: &'static str,
+    pub id: uuid::Uuid,
+    /// Names, title or excerpt; for events, the names of their person or
+    /// family (the type is in `event_type`).
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<EventType>,
+    /// Person whose page shows the entity, when there is one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub person_id: Option<uuid::Uuid>,
+    /// `created` if never edited since, `updated` otherwise.
+    pub action: &'static str,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<oxidgene_db::repo::ActivityEntry> for ActivityEntryResponse {
+    fn from(e: oxidgene_db::repo::ActivityEntry) -> Self {
+        Self {
+            kind: e.kind.as_str(),
+            action: if e.is_new() { "created" } else { "updated" },
+            id: e.id,
+            label: e.label,
+            event_type: e.event_type,
+            person_id: e.person_id,
+            timestamp: e.updated_at,
+        }
+    }
+}
+
+/// Query parameters for POST /api/v1/trees/:tree_id/replace and
+/// /api/v1/trees/:tree_id/persons/names/normalize-case.
+#[derive(Debug, Deserialize)]
+pub struct ReplaceQuery {
+    /// Report the changes without writing them.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Request body for a tree-wide find-and-replace.
+#[derive(Debug, Deserialize)]
+pub struct ReplaceRequest {
+    pub entity: ReplaceEntity,
+    /// Field of `entity`: `name` for places; `title`, `author`,
+    /// `publisher`, `abbreviation` or `repository_name` for sources.
+    pub field: String,
+    /// `exact` (default) replaces whole values; `regex` every match.
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+    pub find: String,
+    /// With `regex`, may refer to groups (`$1`).
+    pub replace: String,
+}
+
+/// Response body for a tree-wide find-and-replace.
+#[derive(Debug, Serialize)]
+pub struct ReplaceResponse {
+    pub dry_run: bool,
+    /// Number of places or sources changed (or to be changed).
+    pub count: usize,
+    pub changes: Vec<ReplacedValueResponse>,
+}
+
+/// One value changed by a find-and-replace.
+#[derive(Debug, Serialize)]
+pub struct ReplacedValueResponse {
+    pub id: uuid::Uuid,
+    pub before: String,
+    /// `null` when an optional field is cleared.
+    pub after: Option<String>,
+}
+
+impl From<oxidgene_db::repo::ReplacedValue> for ReplacedValueResponse {
+    fn from(v: oxidgene_db::repo::ReplacedValue) -> Self {
+        Self {
+            id: v.id,
+            before: v.before,
+            after: v.after,
+        }
+    }
+}
+
 // ── Person DTOs ──────────────────────────────────────────────────────
 
 /// Query parameters for free-text person search (Sprint E.6).
@@ -51,7 +236,23 @@ pub struct DuplicateTreeRequest {
 pub struct PersonSearchQuery {
     /// Free-text query.
     pub q: Option<String>,
-    /// Maximum results to return (default: 25, max: 100).
+    /// Maximum results to return (default: 25, clamped to the configured maximum page size).
+    pub limit: Option<usize>,
+    /// Offset for pagination (default: 0).
+    pub offset: Option<usize>,
+    /// Typo-tolerant name query; when set, results are ranked by edit
+    /// distance instead of matched by prefix, and `q` / `offset` are ignored.
+    pub name: Option<String>,
+    /// Maximum edit distance for `name` matches (default: 2).
+    pub max_distance: Option<usize>,
+}
+
+/// Query parameters for GET /api/v1/search.
+#[derive(Debug, Deserialize)]
+pub struct TreeSearchQuery {
+    /// Free-text query; empty or missing lists everyone.
+    pub q: Option<String>,
+    /// Maximum results to return (default: 25, clamped to the configured maximum page size).
     pub limit: Option<usize>,
     /// Offset for pagination (default: 0).
     pub offset: Option<usize>,
@@ -64,6 +265,10 @@ pub struct PersonDetailResponse {
     #[serde(flatten)]
     pub person: oxidgene_core::types::Person,
     pub sosa_number: Option<u64>,
+    /// How well the vital facts are sourced, from 0 to 1.
+    pub sourcing_score: f32,
+    #[serde(flatten)]
+    pub lineage: oxidgene_core::summary::Lineage,
 }
 
 /// Request body for creating a person.
@@ -72,11 +277,126 @@ pub struct CreatePersonRequest {
     pub sex: Sex,
 }
 
+/// Request body for adding a relative to a person in one step.
+#[derive(Debug, Deserialize)]
+pub struct AddRelativeRequest {
+    pub kind: RelativeKind,
+    /// Ignored for fathers and mothers; defaults to unknown.
+    pub sex: Option<Sex>,
+    pub given_names: Option<String>,
+    pub surname: Option<String>,
+}
+
+/// Response for adding a relative: the new person and the family that
+/// links them to the person they were added to.
+#[derive(Debug, Serialize)]
+pub struct AddRelativeResponse {
+    pub person: oxidgene_core::types::Person,
+    pub family_id: uuid::Uuid,
+    pub family_created: bool,
+}
+
+/// Request body for POST /api/v1/trees/:tree_id/persons/quick: a person
+/// with their primary name and birth and death, in one flat form. Places
+/// are given by name.
+#[derive(Debug, Deserialize)]
+pub struct QuickPersonRequest {
+    /// Defaults to unknown.
+    pub sex: Option<Sex>,
+    pub given_names: Option<String>,
+    pub surname: Option<String>,
+    pub birth_date: Option<String>,
+    pub birth_place: Option<String>,
+    pub death_date: Option<String>,
+    pub death_place: Option<String>,
+}
+
+/// Response for quick entry: the new person with their name, events and
+/// summary, ready to show and link to.
+#[derive(Debug, Serialize)]
+pub struct QuickPersonResponse {
+    pub person: oxidgene_core::types::Person,
+    pub name: oxidgene_core::types::PersonName,
+    /// The birth and/or death, when any of their fields was given.
+    pub events: Vec<oxidgene_core::types::Event>,
+    pub summary: oxidgene_core::summary::PersonSummary,
+}
+
+/// Response for moving a person to another tree: the moved person and the
+/// links to the source tree that were dropped.
+#[derive(Debug, Serialize)]
+pub struct MovePersonResponse {
+    pub person: oxidgene_core::types::Person,
+    pub warnings: Vec<String>,
+}
+
+/// Request body for POST /api/v1/trees/:tree_id/persons/bulk-delete.
+#[derive(Debug, Deserialize)]
+pub struct BulkDeletePersonsRequest {
+    pub person_ids: Vec<uuid::Uuid>,
+    /// Detach family members from their families (deleting the families
+    /// left empty) instead of skipping them.
+    #[serde(default)]
+    pub cascade: bool,
+}
+
+/// A person a bulk delete left alone.
+#[derive(Debug, Serialize)]
+pub struct BulkDeleteSkipped {
+    pub id: uuid::Uuid,
+    /// `not_found`, `family_member` or `locked`.
+    pub reason: &'static str,
+}
+
+/// Response for a bulk person delete.
+#[derive(Debug, Serialize)]
+pub struct BulkDeletePersonsResponse {
+    pub deleted: Vec<uuid::Uuid>,
+    pub skipped: Vec<BulkDeleteSkipped>,
+    pub families_deleted: Vec<uuid::Uuid>,
+}
+
+impl From<oxidgene_db::repo::BulkDeleted> for BulkDeletePersonsResponse {
+    fn from(r: oxidgene_db::repo::BulkDeleted) -> Self {
+        Self {
+            deleted: r.deleted,
+            skipped: r
+                .skipped
+                .into_iter()
+                .map(|(id, reason)| BulkDeleteSkipped {
+                    id,
+                    reason: reason.as_str(),
+                })
+                .collect(),
+            families_deleted: r.families_deleted,
+        }
+    }
+}
+
 /// Request body for updating a person.
 #[derive(Debug, Deserialize)]
 pub struct UpdatePersonRequest {
     pub sex: Option<Sex>,
     pub privacy: Option<Privacy>,
+    /// Living-status override: `true`/`false` set it, `null` goes back to
+    /// the date heuristic, an absent field leaves it unchanged.
+    #[serde(default, deserialize_with = "nullable")]
+    pub living: Option<Option<bool>>,
+}
+
+/// Deserialize a field that distinguishes `null` (`Some(None)`) from an
+/// absent field (`None`, through `#[serde(default)]`).
+///
+/// Every update request uses it for the fields that can be cleared: `null`
+/// clears them, an absent field leaves them unchanged. Required fields (a
+/// name, a title, an event type) stay a plain `Option`, where `null` is
+/// the same as an absent field.
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 // ── PersonName DTOs ──────────────────────────────────────────────────
@@ -97,26 +417,113 @@ pub struct CreatePersonNameRequest {
 #[derive(Debug, Deserialize)]
 pub struct UpdatePersonNameRequest {
     pub name_type: Option<NameType>,
+    #[serde(default, deserialize_with = "nullable")]
     pub given_names: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
     pub surname: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
     pub prefix: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
     pub suffix: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
     pub nickname: Option<Option<String>>,
     pub is_primary: Option<bool>,
 }
 
+/// Request body for a tree-wide name capitalization rewrite.
+#[derive(Debug, Deserialize)]
+pub struct NormalizeNameCaseRequest {
+    /// `keep`, `title_case` (default) or `upper`.
+    #[serde(default)]
+    pub given_names: NameCase,
+    /// `keep`, `title_case` (default) or `upper`.
+    #[serde(default)]
+    pub surname: NameCase,
+    /// Keep particles (`de`, `van`, `d'`) lowercase. Defaults to `true`.
+    #[serde(default = "default_preserve_particles")]
+    pub preserve_particles: bool,
+}
+
+fn default_preserve_particles() -> bool {
+    true
+}
+
+/// Response body for a tree-wide name capitalization rewrite.
+#[derive(Debug, Serialize)]
+pub struct NormalizeNameCaseResponse {
+    pub dry_run: bool,
+    /// Number of names changed (or to be changed).
+    pub count: usize,
+    pub changes: Vec<RecasedNameResponse>,
+    /// Locked persons whose names were left as they are.
+    pub skipped_locked: Vec<uuid::Uuid>,
+}
+
+/// One name whose capitalization changed.
+#[derive(Debug, Serialize)]
+pub struct RecasedNameResponse {
+    pub name_id: uuid::Uuid,
+    pub person_id: uuid::Uuid,
+    pub before: NameCaseParts,
+    pub after: NameCaseParts,
+}
+
+/// The parts of a name a capitalization rewrite touches.
+#[derive(Debug, Serialize)]
+pub struct NameCaseParts {
+    pub given_names: Option<String>,
+    pub surname: Option<String>,
+}
+
+impl From<oxidgene_db::repo::RecasedName> for RecasedNameResponse {
+    fn from(n: oxidgene_db::repo::RecasedName) -> Self {
+        Self {
+            name_id: n.id,
+            person_id: n.person_id,
+            before: NameCaseParts {
+                given_names: n.given_names,
+                surname: n.surname,
+            },
+            after: NameCaseParts {
+                given_names: n.new_given_names,
+                surname: n.new_surname,
+            },
+        }
+    }
+}
+
 // ── Family DTOs ──────────────────────────────────────────────────────
 
 // Family has no extra fields to create/update beyond tree_id (from path),
 // so we don't need a CreateFamilyRequest. Update just touches updated_at.
 
+/// A consistency problem among the events of a family.
+#[derive(Debug, Serialize)]
+pub struct FamilyEventWarning {
+    /// Currently always `divorce_before_marriage`.
+    pub kind: &'static str,
+    /// The offending event (the divorce).
+    pub event_id: uuid::Uuid,
+    /// The event it contradicts (the marriage).
+    pub related_event_id: uuid::Uuid,
+}
+
+/// Response of GET /api/v1/trees/:tree_id/families/:family_id/events.
+#[derive(Debug, Serialize)]
+pub struct FamilyEventsResponse {
+    /// Events attached to the family, in date order, undated last.
+    pub events: Vec<oxidgene_core::types::Event>,
+    pub warnings: Vec<FamilyEventWarning>,
+}
+
 // ── FamilySpouse DTOs ────────────────────────────────────────────────
 
 /// Request body for adding a spouse to a family.
 #[derive(Debug, Deserialize)]
 pub struct AddSpouseRequest {
     pub person_id: uuid::Uuid,
-    pub role: SpouseRole,
+    /// Defaults from the person's sex (see [`SpouseRole::for_sex`]).
+    pub role: Option<SpouseRole>,
     #[serde(default)]
     pub sort_order: i32,
 }
@@ -132,6 +539,28 @@ pub struct AddChildRequest {
     pub sort_order: i32,
 }
 
+/// Request body for adding several children to a family at once.
+#[derive(Debug, Deserialize)]
+pub struct AddChildrenRequest {
+    /// The children to add, in birth order; they go after the family's
+    /// current last child.
+    pub children: Vec<NewChild>,
+}
+
+/// One child of an [`AddChildrenRequest`].
+#[derive(Debug, Deserialize)]
+pub struct NewChild {
+    pub person_id: uuid::Uuid,
+    pub child_type: ChildType,
+}
+
+/// Request body for reordering a family's children.
+#[derive(Debug, Deserialize)]
+pub struct ReorderChildrenRequest {
+    /// Every child link ID of the family, in the new order.
+    pub child_ids: Vec<uuid::Uuid>,
+}
+
 // ── Ancestry query params ────────────────────────────────────────────
 
 /// Query parameters for ancestor/descendant queries.
@@ -141,6 +570,93 @@ pub struct AncestryQuery {
     pub max_depth: Option<i32>,
 }
 
+/// Query parameters for a person's descendant report.
+#[derive(Debug, Deserialize)]
+pub struct DescendantReportQuery {
+    /// Maximum depth to traverse.
+    pub max_depth: Option<i32>,
+    /// `daboville` (default) or `henry`.
+    #[serde(default)]
+    pub system: oxidgene_core::numbering::DescendantNumbering,
+}
+
+/// Query parameters for a person's date conflicts.
+#[derive(Debug, Deserialize)]
+pub struct DateConflictQuery {
+    /// Years two records may disagree by before being reported
+    /// (default: `chronology::DEFAULT_CONFLICT_TOLERANCE`).
+    pub tolerance: Option<i32>,
+}
+
+/// A record contributing to a date conflict, with the citations backing it.
+#[derive(Debug, Serialize)]
+pub struct ConflictRecordResponse {
+    #[serde(flatten)]
+    pub record: oxidgene_core::chronology::ConflictRecord,
+    pub citations: Vec<oxidgene_core::types::Citation>,
+}
+
+/// One entry of GET /api/v1/trees/:tree_id/persons/:person_id/date-conflicts.
+#[derive(Debug, Serialize)]
+pub struct DateConflictResponse {
+    pub event_type: EventType,
+    pub family_id: Option<uuid::Uuid>,
+    pub discrepancy_years: i32,
+    pub records: Vec<ConflictRecordResponse>,
+}
+
+/// Query parameters for comparing two persons.
+#[derive(Debug, Deserialize)]
+pub struct PersonCompareQuery {
+    pub a: uuid::Uuid,
+    pub b: uuid::Uuid,
+}
+
+/// Response of GET /api/v1/trees/:tree_id/persons/compare.
+#[derive(Debug, Serialize)]
+pub struct PersonCompareResponse {
+    pub a: uuid::Uuid,
+    pub b: uuid::Uuid,
+    #[serde(flatten)]
+    pub comparison: oxidgene_core::compare::PersonComparison,
+}
+
+/// Query parameters for GET /api/v1/trees/:tree_id/persons/duplicates.
+#[derive(Debug, Deserialize)]
+pub struct DuplicateCandidatesQuery {
+    /// Maximum pairs to return (default: 50, clamped to the configured maximum page size).
+    pub limit: Option<u64>,
+}
+
+/// Two persons whose names sound alike, in
+/// GET /api/v1/trees/:tree_id/persons/duplicates.
+#[derive(Debug, Serialize)]
+pub struct DuplicateCandidateResponse {
+    pub a: PersonListItem,
+    pub b: PersonListItem,
+}
+
+/// One person on a relationship path.
+#[derive(Debug, Serialize)]
+pub struct RelationshipStep {
+    pub person: PersonListItem,
+    /// How the person relates to the previous step; `null` for the first.
+    pub edge: Option<oxidgene_core::enums::KinshipEdge>,
+}
+
+/// Response of GET /api/v1/trees/:tree_id/persons/:a/path/:b.
+#[derive(Debug, Serialize)]
+pub struct RelationshipPathResponse {
+    /// `null`, with no steps, when the two persons share no ancestor.
+    pub common_ancestor_id: Option<uuid::Uuid>,
+    /// Generations from `a` up to the common ancestor.
+    pub generations_up: i32,
+    /// Generations from the common ancestor down to `b`.
+    pub generations_down: i32,
+    /// From `a` to `b`, both included.
+    pub steps: Vec<RelationshipStep>,
+}
+
 // ── Generic ID response ──────────────────────────────────────────────
 
 /// Minimal response for delete operations.
@@ -159,6 +675,68 @@ pub struct EventListQuery {
     pub event_type: Option<EventType>,
     pub person_id: Option<uuid::Uuid>,
     pub family_id: Option<uuid::Uuid>,
+    /// With `year_to`, switches to the tree-wide timeline: every dated
+    /// event in the span, by date (see [`TimelineEvent`]).
+    pub year_from: Option<i32>,
+    pub year_to: Option<i32>,
+    /// With `person_id`, also list the events of the families the person
+    /// belongs to, flagged as inherited (see [`PersonScoped`]).
+    #[serde(default)]
+    pub include_family: bool,
+}
+
+/// A record listed for a person with `include_family=true`: either attached
+/// to the person, or inherited from a family they belong to.
+#[derive(Debug, Serialize)]
+pub struct PersonScoped<T> {
+    #[serde(flatten)]
+    pub record: T,
+    /// The record is attached to one of the person's families, not to them.
+    pub inherited: bool,
+    /// For inherited records, the person's role in that family: `spouse`
+    /// (their own union) or `child` (their parents').
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family_role: Option<&'static str>,
+}
+
+impl<T> PersonScoped<T> {
+    /// Flag `record` as inherited when `family_id` is one of the person's
+    /// families and it is not on the person directly.
+    pub fn new(
+        record: T,
+        on_person: bool,
+        family_id: Option<uuid::Uuid>,
+        families: &[(uuid::Uuid, oxidgene_db::repo::PersonFamilyRole)],
+    ) -> Self {
+        let family_role = (!on_person)
+            .then(|| families.iter().find(|(id, _)| Some(*id) == family_id))
+            .flatten()
+            .map(|(_, role)| role.as_str());
+        Self {
+            record,
+            inherited: family_role.is_some(),
+            family_role,
+        }
+    }
+}
+
+/// Response of event create and update: the saved event with the date
+/// plausibility warnings it raises. Warnings never block the save.
+#[derive(Debug, Serialize)]
+pub struct SavedEventResponse {
+    #[serde(flatten)]
+    pub event: oxidgene_core::types::Event,
+    pub warnings: Vec<oxidgene_core::validation::plausibility::PlausibilityWarning>,
+}
+
+/// An event of the tree-wide timeline, with the name of its owner.
+#[derive(Debug, Serialize)]
+pub struct TimelineEvent {
+    #[serde(flatten)]
+    pub event: oxidgene_core::types::Event,
+    /// Display name of the person owning the event, or of the spouses of
+    /// the family owning it ("A & B").
+    pub owner_name: Option<String>,
 }
 
 /// Request body for creating an event.
@@ -168,27 +746,53 @@ pub struct CreateEventRequest {
     pub date_value: Option<String>,
     pub date_sort: Option<chrono::NaiveDate>,
     pub place_id: Option<uuid::Uuid>,
+    /// Destination of a move; ignored for other event types.
+    pub place_id_to: Option<uuid::Uuid>,
     pub person_id: Option<uuid::Uuid>,
     pub family_id: Option<uuid::Uuid>,
     pub description: Option<String>,
+    /// Participants besides `person_id`, who stays the primary one.
+    #[serde(default)]
+    pub participants: Vec<AddEventParticipantRequest>,
 }
 
 /// Request body for updating an event.
 #[derive(Debug, Deserialize)]
 pub struct UpdateEventRequest {
     pub event_type: Option<EventType>,
+    #[serde(default, deserialize_with = "nullable")]
     pub date_value: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
     pub date_sort: Option<Option<chrono::NaiveDate>>,
+    #[serde(default, deserialize_with = "nullable")]
     pub place_id: Option<Option<uuid::Uuid>>,
+    /// Destination of a move; cleared when the type has none.
+    #[serde(default, deserialize_with = "nullable")]
+    pub place_id_to: Option<Option<uuid::Uuid>>,
+    #[serde(default, deserialize_with = "nullable")]
     pub description: Option<Option<String>>,
+    /// Age of the principal at the event (GEDCOM `AGE`, e.g. "32y").
+    #[serde(default, deserialize_with = "nullable")]
+    pub age: Option<Option<String>>,
 }
 
-// ── EventWitness DTOs ────────────────────────────────────────────────
+/// Request body for PATCH /api/v1/trees/:tree_id/events/:event_id/owner:
+/// the new owner, exactly one of the two.
+#[derive(Debug, Deserialize)]
+pub struct ReassignEventRequest {
+    pub person_id: Option<uuid::Uuid>,
+    pub family_id: Option<uuid::Uuid>,
+}
+
+// ── EventParticipant DTOs ────────────────────────────────────────────
 
-/// Request body for adding a witness to an event.
+/// Request body for adding a participant to an event.
 #[derive(Debug, Deserialize)]
-pub struct AddEventWitnessRequest {
+pub struct AddEventParticipantRequest {
     pub person_id: uuid::Uuid,
+    /// Defaults to `witness`.
+    #[serde(default)]
+    pub role: ParticipantRole,
     pub relation: Option<String>,
     #[serde(default)]
     pub sort_order: i32,
@@ -204,6 +808,25 @@ pub struct PlaceListQuery {
     pub search: Option<String>,
 }
 
+/// Query parameters for GET /api/v1/trees/:tree_id/places/autocomplete.
+#[derive(Debug, Deserialize)]
+pub struct PlaceAutocompleteQuery {
+    /// Name prefix to match.
+    #[serde(default)]
+    pub q: String,
+    /// Maximum number of places (default: 10, max: 50).
+    pub limit: Option<u64>,
+}
+
+/// A place suggested by the autocomplete, with its usage.
+#[derive(Debug, Serialize)]
+pub struct PlaceAutocompleteEntry {
+    #[serde(flatten)]
+    pub place: Place,
+    /// Number of events held at this place.
+    pub event_count: i64,
+}
+
 /// Request body for creating a place.
 #[derive(Debug, Deserialize)]
 pub struct CreatePlaceRequest {
@@ -216,10 +839,32 @@ pub struct CreatePlaceRequest {
 #[derive(Debug, Deserialize)]
 pub struct UpdatePlaceRequest {
     pub name: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
     pub latitude: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
     pub longitude: Option<Option<f64>>,
 }
 
+/// Response body for place deduplication.
+#[derive(Debug, Serialize)]
+pub struct PlaceDedupeResponse {
+    /// Number of duplicate places merged into a survivor and deleted.
+    pub merged: u64,
+}
+
+/// Response body for pruning unused places.
+#[derive(Debug, Serialize)]
+pub struct PlacePruneResponse {
+    /// Number of places referenced by nothing that were deleted.
+    pub pruned: u64,
+}
+
+/// Response body for starting a batch geocoding job.
+#[derive(Debug, Serialize)]
+pub struct GeocodeJobResponse {
+    pub job_id: uuid::Uuid,
+}
+
 // ── Source DTOs ──────────────────────────────────────────────────────
 
 /// Request body for creating a source.
@@ -236,12 +881,139 @@ pub struct CreateSourceRequest {
 #[derive(Debug, Deserialize)]
 pub struct UpdateSourceRequest {
     pub title: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
     pub author: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
     pub publisher: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
     pub abbreviation: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
     pub repository_name: Option<Option<String>>,
 }
 
+/// Request body for attaching an existing media object to a source.
+#[derive(Debug, Deserialize)]
+pub struct LinkSourceMediaRequest {
+    pub media_id: uuid::Uuid,
+    #[serde(default)]
+    pub sort_order: i32,
+}
+
+/// A media object attached to a source, with the link that attaches it.
+#[derive(Debug, Serialize)]
+pub struct SourceMediaEntry {
+    /// Id of the `MediaLink`; delete it to detach the media.
+    pub link_id: uuid::Uuid,
+    pub sort_order: i32,
+    #[serde(flatten)]
+    pub media: oxidgene_core::types::Media,
+}
+
+/// Response for a single source, with its attached media.
+#[derive(Debug, Serialize)]
+pub struct SourceDetailResponse {
+    #[serde(flatten)]
+    pub source: oxidgene_core::types::Source,
+    pub media: Vec<SourceMediaEntry>,
+}
+
+/// A citation of a source and the person, event or family it supports.
+#[derive(Debug, Serialize)]
+pub struct SourceUsageEntryResponse {
+    pub citation_id: uuid::Uuid,
+    /// `person`, `event` or `family`: what the citation is attached to.
+    pub owner_kind: &'static str,
+    pub owner_id: uuid::Uuid,
+    /// Names of the person or family concerned.
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<EventType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_date: Option<String>,
+    /// Person concerned (directly or through the event), for linking.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub person_id: Option<uuid::Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family_id: Option<uuid::Uuid>,
+    pub page: Option<String>,
+    pub text: Option<String>,
+    pub confidence: Confidence,
+    pub fact_type: Option<oxidgene_core::enums::FactType>,
+}
+
+/// A possibly duplicated source with how many citations point at it.
+#[derive(Debug, Serialize)]
+pub struct SourceDuplicateEntry {
+    #[serde(flatten)]
+    pub source: Source,
+    pub citation_count: i64,
+}
+
+impl From<oxidgene_db::repo::SourceCitationCount> for SourceDuplicateEntry {
+    fn from(c: oxidgene_db::repo::SourceCitationCount) -> Self {
+        Self {
+            source: c.source,
+            citation_count: c.citation_count,
+        }
+    }
+}
+
+/// Response body for duplicate source detection: groups of sources sharing
+/// the same normalized title and author, oldest first within a group.
+#[derive(Debug, Serialize)]
+pub struct SourceDuplicatesResponse {
+    pub groups: Vec<Vec<SourceDuplicateEntry>>,
+}
+
+/// Request body for merging duplicate sources into one.
+#[derive(Debug, Deserialize)]
+pub struct MergeSourcesRequest {
+    pub survivor_id: uuid::Uuid,
+    pub duplicate_ids: Vec<uuid::Uuid>,
+}
+
+/// Response body for a source merge.
+#[derive(Debug, Serialize)]
+pub struct MergeSourcesResponse {
+    pub survivor: Source,
+    /// Number of duplicate sources merged into the survivor and deleted.
+    pub merged: u64,
+    pub citations_moved: u64,
+    pub media_links_moved: u64,
+    pub notes_moved: u64,
+}
+
+impl From<oxidgene_db::repo::SourceMerge> for MergeSourcesResponse {
+    fn from(m: oxidgene_db::repo::SourceMerge) -> Self {
+        Self {
+            survivor: m.survivor,
+            merged: m.merged,
+            citations_moved: m.citations_moved,
+            media_links_moved: m.media_links_moved,
+            notes_moved: m.notes_moved,
+        }
+    }
+}
+
+impl From<oxidgene_db::repo::SourceUsageEntry> for SourceUsageEntryResponse {
+    fn from(e: oxidgene_db::repo::SourceUsageEntry) -> Self {
+        Self {
+            citation_id: e.citation_id,
+            owner_kind: e.owner_kind.as_str(),
+            owner_id: e.owner_id,
+            label: e.label,
+            event_type: e.event_type,
+            event_date: e.event_date,
+            person_id: e.person_id,
+            family_id: e.family_id,
+            page: e.page,
+            text: e.text,
+            confidence: e.confidence,
+            fact_type: e.fact_type,
+        }
+    }
+}
+
 // ── Citation DTOs ───────────────────────────────────────────────────
 
 /// Request body for creating a citation.
@@ -254,13 +1026,41 @@ pub struct CreateCitationRequest {
     pub page: Option<String>,
     pub confidence: Confidence,
     pub text: Option<String>,
+    /// Cite one fact (`name`, `date`, `place`, `description`, `age`)
+    /// instead of the whole person/event.
+    pub fact_type: Option<oxidgene_core::enums::FactType>,
+    /// With `fact_type=name`, the id of the cited `PersonName`.
+    pub fact_ref: Option<uuid::Uuid>,
+}
+
+/// Request body for POST /api/v1/trees/:tree_id/citations/bulk: one
+/// source, page and confidence cited for several owners at once.
+#[derive(Debug, Deserialize)]
+pub struct BulkCreateCitationRequest {
+    pub source_id: uuid::Uuid,
+    pub page: Option<String>,
+    pub confidence: Confidence,
+    pub text: Option<String>,
+    /// What each citation is attached to, in order.
+    pub owners: Vec<CitationOwner>,
+}
+
+/// The person, event or family a bulk-created citation is attached to;
+/// exactly one is set.
+#[derive(Debug, Deserialize)]
+pub struct CitationOwner {
+    pub person_id: Option<uuid::Uuid>,
+    pub event_id: Option<uuid::Uuid>,
+    pub family_id: Option<uuid::Uuid>,
 }
 
 /// Request body for updating a citation.
 #[derive(Debug, Deserialize)]
 pub struct UpdateCitationRequest {
+    #[serde(default, deserialize_with = "nullable")]
     pub page: Option<Option<String>>,
     pub confidence: Option<Confidence>,
+    #[serde(default, deserialize_with = "nullable")]
     pub text: Option<Option<String>>,
 }
 
@@ -271,6 +1071,10 @@ pub struct CitationListQuery {
     pub event_id: Option<uuid::Uuid>,
     pub family_id: Option<uuid::Uuid>,
     pub source_id: Option<uuid::Uuid>,
+    /// Only citations of this fact.
+    pub fact_type: Option<oxidgene_core::enums::FactType>,
+    /// Only citations of this record (e.g. one `PersonName`).
+    pub fact_ref: Option<uuid::Uuid>,
 }
 
 // ── Media DTOs ──────────────────────────────────────────────────────
@@ -289,7 +1093,9 @@ pub struct CreateMediaRequest {
 /// Request body for updating media metadata.
 #[derive(Debug, Deserialize)]
 pub struct UpdateMediaRequest {
+    #[serde(default, deserialize_with = "nullable")]
     pub title: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
     pub description: Option<Option<String>>,
 }
 
@@ -298,11 +1104,58 @@ pub struct UpdateMediaRequest {
 /// Row returned by the bulk media-links endpoint.
 #[derive(Debug, Serialize)]
 pub struct MediaLinkListRow {
+    pub link_id: uuid::Uuid,
     pub entity_id: uuid::Uuid,
     pub entity_type: String,
     pub media_id: uuid::Uuid,
     pub file_path: String,
     pub file_name: String,
+    pub mime_type: String,
+    pub is_profile: bool,
+}
+
+/// Request body for designating a person's profile photo.
+#[derive(Debug, Deserialize)]
+pub struct SetPersonPhotoRequest {
+    /// One of the person's media links, to an image.
+    pub link_id: uuid::Uuid,
+}
+
+/// Query parameters for GET /api/v1/trees/:tree_id/persons/:person_id/media.
+#[derive(Debug, Deserialize)]
+pub struct PersonMediaQuery {
+    /// Number of entries to skip (default: 0).
+    #[serde(default)]
+    pub offset: u64,
+    /// Maximum number of entries (default: 12, clamped to the configured maximum page size).
+    pub limit: Option<u64>,
+}
+
+/// A media object linked to a person, with the link that attaches it.
+#[derive(Debug, Serialize)]
+pub struct PersonMediaEntry {
+    /// Id of the `MediaLink`; delete it to detach the media.
+    pub link_id: uuid::Uuid,
+    pub sort_order: i32,
+    /// The link designates the person's profile photo.
+    pub is_profile: bool,
+    #[serde(flatten)]
+    pub media: oxidgene_core::types::Media,
+}
+
+/// One page of a person's media, in display order.
+#[derive(Debug, Serialize)]
+pub struct PersonMediaPage {
+    pub items: Vec<PersonMediaEntry>,
+    /// Number of media linked to the person, across all pages.
+    pub total: usize,
+}
+
+/// Request body for reordering a person's media.
+#[derive(Debug, Deserialize)]
+pub struct ReorderPersonMediaRequest {
+    /// Every media link of the person, in the new display order.
+    pub link_ids: Vec<uuid::Uuid>,
 }
 
 /// Request body for creating a media link.
@@ -326,12 +1179,18 @@ pub struct NoteListQuery {
     pub event_id: Option<uuid::Uuid>,
     pub family_id: Option<uuid::Uuid>,
     pub source_id: Option<uuid::Uuid>,
+    /// With `person_id`, also list the notes of the families the person
+    /// belongs to, flagged as inherited (see [`PersonScoped`]).
+    #[serde(default)]
+    pub include_family: bool,
 }
 
 /// Request body for creating a note.
 #[derive(Debug, Deserialize)]
 pub struct CreateNoteRequest {
     pub text: String,
+    #[serde(default)]
+    pub format: NoteFormat,
     pub person_id: Option<uuid::Uuid>,
     pub event_id: Option<uuid::Uuid>,
     pub family_id: Option<uuid::Uuid>,
@@ -342,6 +1201,73 @@ pub struct CreateNoteRequest {
 #[derive(Debug, Deserialize)]
 pub struct UpdateNoteRequest {
     pub text: Option<String>,
+    pub format: Option<NoteFormat>,
+}
+
+// ── Research task DTOs ──────────────────────────────────────────────
+
+/// Query parameters for GET /api/v1/trees/:tree_id/tasks.
+#[derive(Debug, Deserialize)]
+pub struct TaskListQuery {
+    pub status: Option<TaskStatus>,
+    pub person_id: Option<uuid::Uuid>,
+    pub family_id: Option<uuid::Uuid>,
+}
+
+/// Request body for creating a research task; exactly one of `person_id`
+/// and `family_id`.
+#[derive(Debug, Deserialize)]
+pub struct CreateTaskRequest {
+    pub person_id: Option<uuid::Uuid>,
+    pub family_id: Option<uuid::Uuid>,
+    pub title: String,
+    pub description: Option<String>,
+    pub due_date: Option<chrono::NaiveDate>,
+}
+
+/// Request body for updating a research task.
+#[derive(Debug, Deserialize)]
+pub struct UpdateTaskRequest {
+    pub title: Option<String>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub description: Option<Option<String>>,
+    pub status: Option<TaskStatus>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub due_date: Option<Option<chrono::NaiveDate>>,
+}
+
+// ── Share link DTOs ─────────────────────────────────────────────────
+
+/// Request body for creating a share link.
+#[derive(Debug, Deserialize)]
+pub struct CreateShareLinkRequest {
+    /// Hide persons presumed living. Defaults to `true`.
+    #[serde(default = "default_privatize")]
+    pub privatize: bool,
+    /// Absent means the link never expires.
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+fn default_privatize() -> bool {
+    true
+}
+
+/// Response body for `GET /api/v1/shared/{token}`: the tree's public face.
+#[derive(Debug, Serialize)]
+pub struct SharedTreeResponse {
+    pub name: String,
+    pub description: Option<String>,
+    pub sosa_root_person_id: Option<uuid::Uuid>,
+    pub privatize: bool,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+// ── Tree member DTOs ─────────────────────────────────────────────────
+
+/// Request body for `PUT /api/v1/trees/{tree_id}/members/{actor_id}`.
+#[derive(Debug, Deserialize)]
+pub struct SetTreeMemberRequest {
+    pub role: TreeRole,
 }
 
 // ── GEDCOM DTOs ──────────────────────────────────────────────────────
@@ -352,17 +1278,26 @@ pub struct ImportGedcomRequest {
     pub gedcom: String,
 }
 
-/// Response body for GEDCOM import.
+/// Response body for `POST /api/v1/trees/{id}/import/preview`: what the
+/// import would do, and the token confirming it.
 #[derive(Debug, Serialize)]
-pub struct ImportGedcomResponse {
-    pub persons_count: usize,
-    pub families_count: usize,
-    pub events_count: usize,
-    pub sources_count: usize,
-    pub media_count: usize,
-    pub places_count: usize,
-    pub notes_count: usize,
-    pub warnings: Vec<String>,
+pub struct ImportPreviewResponse {
+    pub token: uuid::Uuid,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    #[serde(flatten)]
+    pub plan: crate::service::gedcom::ImportPlan,
+}
+
+/// Response body for starting a GEDCOM import job.
+#[derive(Debug, Serialize)]
+pub struct ImportJobResponse {
+    pub job_id: uuid::Uuid,
+}
+
+/// Response body for starting a GEDCOM export job.
+#[derive(Debug, Serialize)]
+pub struct ExportJobResponse {
+    pub job_id: uuid::Uuid,
 }
 
 /// Response body for GEDCOM export.
@@ -382,6 +1317,90 @@ pub struct ExportGedcomQuery {
     /// a single profession field. Defaults to `false` (one `OCCU` per
     /// profession, lossless).
     pub merge_occupations: Option<bool>,
+    /// Submitter name written to the header's `SUBM` record
+    /// (default: `Unknown`).
+    pub submitter: Option<String>,
+    /// Software name written to `HEAD.SOUR.NAME` (default: `OxidGene`).
+    pub software_name: Option<String>,
+    /// Software version written to `HEAD.SOUR.VERS` (default: this
+    /// server's version).
+    pub software_version: Option<String>,
+    /// Language written to `HEAD.LANG`, as a GEDCOM language name such as
+    /// `French` (default: none).
+    pub language: Option<String>,
+    /// Export only this person's branch: them and their ancestors.
+    pub person_id: Option<uuid::Uuid>,
+    /// With `person_id`, generations to include in each direction
+    /// (default: all).
+    pub generations: Option<i32>,
+    /// With `person_id`, also include their descendants.
+    #[serde(default)]
+    pub include_descendants: bool,
+}
+
+/// Request body for POST /api/v1/trees/:tree_id/export.
+#[derive(Debug, Deserialize)]
+pub struct SelectionExportRequest {
+    /// Persons to export (at least one).
+    pub person_ids: Vec<uuid::Uuid>,
+    /// Also export the spouses of every exported person.
+    #[serde(default)]
+    pub include_spouses: bool,
+    /// Also export the persons' ancestors, up to this many generations
+    /// (default: none).
+    pub ancestor_generations: Option<i32>,
+    /// Also export all the persons' descendants.
+    #[serde(default)]
+    pub include_descendants: bool,
+    /// `gedcom` (default) or `json`.
+    #[serde(default)]
+    pub format: SelectionExportFormat,
+}
+
+/// Output of POST /api/v1/trees/:tree_id/export.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SelectionExportFormat {
+    /// A GEDCOM 5.5.1 string, as [`ExportGedcomResponse`].
+    #[default]
+    Gedcom,
+    /// The exported records as JSON, one array per entity kind.
+    Json,
+}
+
+/// JSON output of POST /api/v1/trees/:tree_id/export.
+#[derive(Debug, Serialize)]
+pub struct SelectionExportJsonResponse {
+    pub records: crate::service::gedcom::ExportRecords,
+    pub warnings: Vec<String>,
+}
+
+/// Query parameters for GET /api/v1/trees/:tree_id/export.dot.
+#[derive(Debug, Deserialize)]
+pub struct DotExportQuery {
+    /// Scope the graph to this person's ancestors and descendants.
+    pub root: Option<uuid::Uuid>,
+    /// With `root`, how many generations up and down to include
+    /// (unlimited if absent).
+    pub generations: Option<u32>,
+}
+
+/// Query parameters for GET /api/v1/trees/:tree_id/export.csv.
+#[derive(Debug, Deserialize)]
+pub struct CsvExportQuery {
+    /// `persons` (default), `events` or `families`.
+    #[serde(default)]
+    pub entity: crate::render::csv::CsvEntity,
+}
+
+/// Query parameters for GET /api/v1/trees/:tree_id/persons/:person_id/fan.svg.
+#[derive(Debug, Deserialize)]
+pub struct FanChartQuery {
+    /// Ancestor rings to draw (default 4, at most 8).
+    pub generations: Option<u32>,
+    /// `sex` (default), `generation` or `gray`.
+    #[serde(default)]
+    pub palette: crate::render::fan::FanPalette,
 }
 
 // ── Cache DTOs ──────────────────────────────────────────────────────
@@ -393,6 +1412,29 @@ pub struct CacheRebuildResponse {
     pub persons_count: usize,
 }
 
+/// Response body for `POST /api/v1/admin/backfill-date-sort`.
+#[derive(Debug, Serialize)]
+pub struct DateSortBackfillResponse {
+    /// Events whose `date_sort` was filled in.
+    pub updated: u64,
+    /// Events whose `date_value` could not be parsed; left without one.
+    pub unparsed: u64,
+}
+
+/// Response body for `POST /api/v1/admin/backfill-phonetic`.
+#[derive(Debug, Serialize)]
+pub struct PhoneticBackfillResponse {
+    /// Person names whose phonetic codes were filled in.
+    pub updated: u64,
+}
+
+/// Response body for `POST /api/v1/admin/backfill-display-names`.
+#[derive(Debug, Serialize)]
+pub struct DisplayNameBackfillResponse {
+    /// Persons whose cached display name was recomputed.
+    pub updated: u64,
+}
+
 /// Response body for cache invalidation.
 #[derive(Debug, Serialize)]
 pub struct CacheInvalidateResponse {
@@ -419,6 +1461,16 @@ pub struct PedigreeExpandQuery {
     pub to_depth: u32,
 }
 
+// ── Backup DTOs ─────────────────────────────────────────────────────
+
+/// Response body for a staged restore.
+#[derive(Debug, Serialize)]
+pub struct RestoreBackupResponse {
+    pub restored: crate::service::backup::BackupInfo,
+    /// The restore is applied on the next launch, not right away.
+    pub restart_required: bool,
+}
+
 // ── Dictionary DTOs ───────────────────────────────────────────────────
 
 /// A distinct value (surname, occupation label) plus its usage count.
@@ -437,6 +1489,122 @@ impl From<oxidgene_db::repo::DictionaryValueEntry> for DictionaryEntryDto {
     }
 }
 
+/// A surname in the tree's alphabetical index.
+#[derive(Debug, Serialize)]
+pub struct SurnameIndexEntryDto {
+    pub surname: String,
+    /// Letter the surname is filed under (`#` for non-letters).
+    pub initial: char,
+    /// Persons whose primary name carries the surname.
+    pub count: i64,
+}
+
+impl From<oxidgene_db::repo::SurnameIndexEntry> for SurnameIndexEntryDto {
+    fn from(e: oxidgene_db::repo::SurnameIndexEntry) -> Self {
+        Self {
+            surname: e.surname,
+            initial: e.initial,
+            count: e.count,
+        }
+    }
+}
+
+/// What GET /api/v1/trees/:tree_id/stats/surnames spreads surnames over.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SurnameStatsOver {
+    /// Birth decade.
+    #[default]
+    Decade,
+    /// Birth place.
+    Place,
+}
+
+/// Query parameters for GET /api/v1/trees/:tree_id/stats/surnames.
+#[derive(Debug, Deserialize)]
+pub struct SurnameStatsQuery {
+    #[serde(default)]
+    pub over: SurnameStatsOver,
+    /// How many of the most frequent surnames to count (default 10, at
+    /// most 50).
+    pub limit: Option<usize>,
+}
+
+/// A surname with its count over all buckets.
+#[derive(Debug, Serialize)]
+pub struct SurnameTotalDto {
+    pub surname: String,
+    pub count: i64,
+}
+
+/// Persons of a surname born in one bucket.
+#[derive(Debug, Serialize)]
+pub struct SurnameBucketCountDto {
+    pub surname: String,
+    pub bucket: String,
+    pub count: i64,
+}
+
+/// Response for GET /api/v1/trees/:tree_id/stats/surnames.
+#[derive(Debug, Serialize)]
+pub struct SurnameStatsResponse {
+    pub over: SurnameStatsOver,
+    /// Most frequent first.
+    pub surnames: Vec<SurnameTotalDto>,
+    /// Decades chronologically, places most frequent first.
+    pub buckets: Vec<String>,
+    /// Non-zero counts only.
+    pub counts: Vec<SurnameBucketCountDto>,
+}
+
+impl SurnameStatsResponse {
+    pub fn new(over: SurnameStatsOver, d: oxidgene_db::repo::SurnameDistribution) -> Self {
+        Self {
+            over,
+            surnames: d
+                .surnames
+                .into_iter()
+                .map(|(surname, count)| SurnameTotalDto { surname, count })
+                .collect(),
+            buckets: d.buckets,
+            counts: d
+                .counts
+                .into_iter()
+                .map(|c| SurnameBucketCountDto {
+                    surname: c.surname,
+                    bucket: c.bucket,
+                    count: c.count,
+                })
+                .collect(),
+        }
+    }
+}
+
+// ── Tag DTOs ─────────────────────────────────────────────────────────
+
+/// Request body for tagging a person.
+#[derive(Debug, Deserialize)]
+pub struct AddPersonTagRequest {
+    pub tag: String,
+}
+
+/// A tag in use in a tree.
+#[derive(Debug, Serialize)]
+pub struct TagCountDto {
+    pub tag: String,
+    /// Persons carrying the tag.
+    pub count: i64,
+}
+
+impl From<oxidgene_db::repo::TagCount> for TagCountDto {
+    fn from(e: oxidgene_db::repo::TagCount) -> Self {
+        Self {
+            tag: e.tag,
+            count: e.count,
+        }
+    }
+}
+
 /// A source paired with its citation count.
 #[derive(Debug, Serialize)]
 pub struct SourceDictionaryEntry {
@@ -1,15 +1,69 @@
 //! Error handling: maps `OxidGeneError` to Axum HTTP responses.
+//!
+//! Every REST error is an RFC 7807 `application/problem+json` body
+//! ([`Problem`]). Domain errors are converted by [`ApiError`]'s
+//! `IntoResponse`; anything else that fails before reaching a handler
+//! (malformed path ids, bad JSON, unknown routes, ...) comes out of Axum as
+//! plain text and is rewrapped by the [`problem_json`] middleware.
 
-use axum::http::StatusCode;
+use axum::body::{Body, to_bytes};
+use axum::extract::Request;
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
 use oxidgene_core::OxidGeneError;
+use oxidgene_core::validation::FieldError;
 use serde::Serialize;
 
-/// JSON error body returned to clients.
+use super::request_id;
+
+/// Media type of error bodies.
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Prefix of the `type` URI of OxidGene's own problem kinds.
+const PROBLEM_TYPE_PREFIX: &str = "urn:oxidgene:problem:";
+
+/// Largest plain-text error body carried over into a problem's `detail`.
+const MAX_REWRAPPED_BODY: usize = 64 * 1024;
+
+/// RFC 7807 problem details returned to clients.
 #[derive(Debug, Serialize)]
-pub struct ErrorBody {
-    pub error: String,
-    pub message: String,
+pub struct Problem {
+    /// `urn:oxidgene:problem:<kind>` for domain errors, `about:blank` for
+    /// plain HTTP errors (the `title` is then the status reason).
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// `X-Request-Id` of the failed request, for bug reports.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl Problem {
+    /// A problem with no OxidGene-specific kind, described by its status.
+    fn from_status(status: StatusCode, detail: Option<String>) -> Self {
+        Self {
+            problem_type: "about:blank".to_string(),
+            title: status.canonical_reason().unwrap_or("Error").to_string(),
+            status: status.as_u16(),
+            detail,
+            request_id: request_id::current(),
+        }
+    }
+}
+
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = (status, axum::Json(self)).into_response();
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+        response
+    }
 }
 
 /// Wrapper around `OxidGeneError` that implements `IntoResponse`.
@@ -21,22 +75,90 @@ impl From<OxidGeneError> for ApiError {
     }
 }
 
+impl From<FieldError> for ApiError {
+    fn from(err: FieldError) -> Self {
+        Self(err.into())
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let (status, error_type) = match &self.0 {
-            OxidGeneError::NotFound { .. } => (StatusCode::NOT_FOUND, "not_found"),
-            OxidGeneError::Validation(_) => (StatusCode::BAD_REQUEST, "validation_error"),
-            OxidGeneError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "database_error"),
-            OxidGeneError::Gedcom(_) => (StatusCode::BAD_REQUEST, "gedcom_error"),
-            OxidGeneError::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, "io_error"),
-            OxidGeneError::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, "internal_error"),
+        let (status, kind, title) = match &self.0 {
+            OxidGeneError::NotFound { .. } => {
+                (StatusCode::NOT_FOUND, "not_found", "Resource not found")
+            }
+            OxidGeneError::Validation(_) => (
+                StatusCode::BAD_REQUEST,
+                "validation_error",
+                "Validation failed",
+            ),
+            OxidGeneError::Conflict(_) => (StatusCode::CONFLICT, "conflict", "Conflict"),
+            OxidGeneError::Locked { .. } => (StatusCode::LOCKED, "locked", "Resource locked"),
+            OxidGeneError::Unauthorized(_) => (
+                StatusCode::UNAUTHORIZED,
+                "unauthorized",
+                "Authentication required",
+            ),
+            OxidGeneError::Forbidden(_) => (StatusCode::FORBIDDEN, "forbidden", "Forbidden"),
+            OxidGeneError::Database(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "database_error",
+                "Database error",
+            ),
+            OxidGeneError::Gedcom(_) => (StatusCode::BAD_REQUEST, "gedcom_error", "GEDCOM error"),
+            OxidGeneError::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, "io_error", "I/O error"),
+            OxidGeneError::Internal(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal_error",
+                "Internal error",
+            ),
         };
 
-        let body = ErrorBody {
-            error: error_type.to_string(),
-            message: self.0.to_string(),
-        };
+        let mut response = Problem {
+            problem_type: format!("{PROBLEM_TYPE_PREFIX}{kind}"),
+            title: title.to_string(),
+            status: status.as_u16(),
+            detail: Some(self.0.to_string()),
+            request_id: request_id::current(),
+        }
+        .into_response();
+        if status == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        response
+    }
+}
 
-        (status, axum::Json(body)).into_response()
+/// Middleware turning error responses that are not already problem
+/// details (Axum's extractor rejections, routing 404/405s, bare status
+/// codes) into a [`Problem`], keeping their text as the `detail`.
+pub async fn problem_json(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+    let status = response.status();
+    if !(status.is_client_error() || status.is_server_error()) {
+        return response;
+    }
+    let is_problem = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| v.as_bytes().starts_with(PROBLEM_JSON.as_bytes()));
+    if is_problem {
+        return response;
+    }
+    let (parts, body): (_, Body) = response.into_parts();
+    let text = to_bytes(body, MAX_REWRAPPED_BODY)
+        .await
+        .map(|bytes| String::from_utf8_lossy(&bytes).trim().to_string())
+        .unwrap_or_default();
+    let detail = (!text.is_empty()).then_some(text);
+    let mut problem = Problem::from_status(status, detail).into_response();
+    // Keep headers such as `Allow` (405) or `X-Request-Id`.
+    for (name, value) in &parts.headers {
+        if name != header::CONTENT_TYPE && name != header::CONTENT_LENGTH {
+            problem.headers_mut().insert(name, value.clone());
+        }
     }
+    problem
 }
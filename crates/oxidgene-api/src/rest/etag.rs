@@ -0,0 +1,47 @@
+//! `ETag` / `If-Match` handling for the versioned entities (trees, persons,
+//! events, places, sources, media, notes).
+//!
+//! The entity's `version` is its entity tag. A client echoes it back in
+//! `If-Match` on `PUT`, and the update is rejected with `409 Conflict` if
+//! the entity changed in the meantime. Without `If-Match` the update is
+//! unconditional.
+
+use axum::Json;
+use axum::http::header::{ETAG, IF_MATCH};
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use oxidgene_core::OxidGeneError;
+use serde::Serialize;
+
+use super::error::ApiError;
+
+/// A JSON response carrying the entity's `ETag`.
+pub type ETagged = ([(HeaderName, HeaderValue); 1], Json<serde_json::Value>);
+
+/// Wrap `body` in a response tagged with `version`.
+pub fn tagged(version: i32, body: impl Serialize) -> ETagged {
+    let etag = HeaderValue::from_str(&format!("\"{version}\"")).unwrap();
+    ([(ETAG, etag)], Json(serde_json::to_value(body).unwrap()))
+}
+
+/// The version a client expects, from `If-Match`: `"3"` (or weak `W/"3"`).
+/// Absent or `*` means no precondition.
+pub fn if_match(headers: &HeaderMap) -> Result<Option<i32>, ApiError> {
+    let Some(value) = headers.get(IF_MATCH) else {
+        return Ok(None);
+    };
+    let invalid = || {
+        ApiError(OxidGeneError::Validation(
+            "If-Match must be an entity tag such as \"3\"".to_string(),
+        ))
+    };
+    let value = value.to_str().map_err(|_| invalid())?.trim();
+    if value == "*" {
+        return Ok(None);
+    }
+    value
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .parse()
+        .map(Some)
+        .map_err(|_| invalid())
+}
@@ -1,35 +1,156 @@
 //! REST handlers for Event CRUD operations.
 
+use std::collections::HashMap;
+
 use axum::Json;
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
+use axum::http::{HeaderMap, StatusCode};
 use oxidgene_cache::invalidation;
-use oxidgene_db::repo::{EventFilter, EventRepo, EventWitnessRepo, PaginationParams};
+use oxidgene_core::enums::{ParticipantRole, SpouseRole};
+use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::types::{Connection, Edge, Event};
+use oxidgene_db::repo::{
+    EventFilter, EventParticipantRepo, EventRepo, FamilyRepo, TreeEntity, with_transaction,
+};
 use uuid::Uuid;
 
-use super::dto::{AddEventWitnessRequest, CreateEventRequest, EventListQuery, UpdateEventRequest};
+use super::access;
+use super::dto::{
+    AddEventParticipantRequest, CreateEventRequest, EventListQuery, PersonScoped,
+    ReassignEventRequest, SavedEventResponse, TimelineEvent, UpdateEventRequest,
+};
 use super::error::ApiError;
+use super::etag::{self, ETagged};
 use super::state::AppState;
+use crate::service::plausibility;
 
 /// GET /api/v1/trees/:tree_id/events
+///
+/// With `year_from` or `year_to`, the tree-wide timeline instead of a page
+/// (see [`list_timeline`]). With `person_id` and `include_family=true`, the
+/// page also holds the events of the person's families, each flagged
+/// `inherited` or not.
 pub async fn list_events(
     State(state): State<AppState>,
     Path(tree_id): Path<Uuid>,
     Query(query): Query<EventListQuery>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    let params = PaginationParams {
-        first: query.first.unwrap_or(25),
-        after: query.after,
+    if query.year_from.is_some() || query.year_to.is_some() {
+        let timeline = list_timeline(&state, tree_id, &query).await?;
+        return Ok(Json(serde_json::to_value(timeline).unwrap()));
+    }
+    let families = match (query.include_family, query.person_id) {
+        (false, _) => Vec::new(),
+        (true, Some(person_id)) => FamilyRepo::list_for_person(&state.db, person_id).await?,
+        (true, None) => {
+            return Err(
+                OxidGeneError::Validation("include_family requires person_id".into()).into(),
+            );
+        }
     };
+    let params = state.config.page_params(query.first, query.after);
     let filter = EventFilter {
         event_type: query.event_type,
         person_id: query.person_id,
         family_id: query.family_id,
+        person_family_ids: families.iter().map(|(id, _)| *id).collect(),
     };
-    let connection = EventRepo::list(&state.db, tree_id, &filter, &params)
+    let mut connection = EventRepo::list(&state.db, tree_id, &filter, &params)
         .await
         .map_err(ApiError::from)?;
-    Ok(Json(serde_json::to_value(connection).unwrap()))
+    // Pages are cut by id; within one, show the events in life order.
+    connection
+        .edges
+        .sort_by(|a, b| a.node.chronological_cmp(&b.node));
+    if !query.include_family {
+        return Ok(Json(serde_json::to_value(connection).unwrap()));
+    }
+    let scoped = Connection {
+        edges: connection
+            .edges
+            .into_iter()
+            .map(|edge| {
+                let on_person = edge.node.person_id == query.person_id;
+                let family_id = edge.node.family_id;
+                Edge {
+                    cursor: edge.cursor,
+                    node: PersonScoped::new(edge.node, on_person, family_id, &families),
+                }
+            })
+            .collect(),
+        page_info: connection.page_info,
+        total_count: connection.total_count,
+    };
+    Ok(Json(serde_json::to_value(scoped).unwrap()))
+}
+
+/// Every dated event of the tree in the query's year span, whoever owns
+/// it, in chronological order and with its owner's name.
+async fn list_timeline(
+    state: &AppState,
+    tree_id: Uuid,
+    query: &EventListQuery,
+) -> Result<Vec<TimelineEvent>, ApiError> {
+    if let (Some(from), Some(to)) = (query.year_from, query.year_to)
+        && from > to
+    {
+        return Err(OxidGeneError::Validation(format!(
+            "year_from ({from}) is after year_to ({to})"
+        ))
+        .into());
+    }
+    let mut events = EventRepo::list_in_years(
+        &state.db,
+        tree_id,
+        query.year_from,
+        query.year_to,
+        query.event_type,
+    )
+    .await?;
+    events.sort_by(Event::chronological_cmp);
+
+    let persons = state.cache.get_all_persons(tree_id).await?;
+    let names: HashMap<Uuid, &str> = persons
+        .iter()
+        .filter_map(|p| Some((p.person_id, p.primary_name.as_ref()?.display_name.as_str())))
+        .collect();
+    let mut spouses: HashMap<Uuid, Vec<(u8, &str)>> = HashMap::new();
+    for person in &persons {
+        let Some(&name) = names.get(&person.person_id) else {
+            continue;
+        };
+        for link in &person.families_as_spouse {
+            let rank = match link.role {
+                SpouseRole::Husband => 0,
+                SpouseRole::Wife => 1,
+                SpouseRole::Partner => 2,
+            };
+            spouses
+                .entry(link.family_id)
+                .or_default()
+                .push((rank, name));
+        }
+    }
+    for names in spouses.values_mut() {
+        names.sort();
+    }
+
+    Ok(events
+        .into_iter()
+        .map(|event| {
+            let owner_name = match (event.person_id, event.family_id) {
+                (Some(pid), _) => names.get(&pid).map(|n| n.to_string()),
+                (None, Some(fid)) => spouses.get(&fid).map(|s| {
+                    s.iter()
+                        .map(|(_, name)| *name)
+                        .collect::<Vec<_>>()
+                        .join(" & ")
+                }),
+                (None, None) => None,
+            };
+            TimelineEvent { event, owner_name }
+        })
+        .collect())
 }
 
 /// POST /api/v1/trees/:tree_id/events
@@ -37,24 +158,62 @@ pub async fn create_event(
     State(state): State<AppState>,
     Path(tree_id): Path<Uuid>,
     Json(body): Json<CreateEventRequest>,
-) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
-    let id = Uuid::now_v7();
-    let event = EventRepo::create(
-        &state.db,
-        id,
-        tree_id,
-        body.event_type,
-        body.date_value,
-        body.date_sort,
-        body.place_id,
-        body.person_id,
-        body.family_id,
-        body.description,
-    )
+) -> Result<(StatusCode, Json<SavedEventResponse>), ApiError> {
+    let mut refs = vec![
+        (TreeEntity::Place, body.place_id),
+        (TreeEntity::Place, body.place_id_to),
+        (TreeEntity::Person, body.person_id),
+        (TreeEntity::Family, body.family_id),
+    ];
+    refs.extend(
+        body.participants
+            .iter()
+            .map(|p| (TreeEntity::Person, Some(p.person_id))),
+    );
+    access::ensure_in_tree(&state, tree_id, refs).await?;
+    let (person_id, family_id) = (body.person_id, body.family_id);
+    // The event and its participants are created together or not at all.
+    let event = with_transaction(&state.db, |txn| {
+        Box::pin(async move {
+            let id = Uuid::now_v7();
+            let event = EventRepo::create(
+                txn,
+                id,
+                tree_id,
+                body.event_type,
+                body.date_value,
+                body.date_sort,
+                body.place_id,
+                body.place_id_to,
+                body.person_id,
+                body.family_id,
+                body.description,
+            )
+            .await?;
+            for (idx, participant) in body.participants.into_iter().enumerate() {
+                let sort_order = if participant.sort_order == 0 {
+                    idx as i32
+                } else {
+                    participant.sort_order
+                };
+                EventParticipantRepo::create(
+                    txn,
+                    Uuid::now_v7(),
+                    id,
+                    participant.person_id,
+                    participant.role,
+                    participant.relation,
+                    sort_order,
+                )
+                .await?;
+            }
+            Ok(event)
+        })
+    })
     .await
     .map_err(ApiError::from)?;
     // Invalidate: person event or family event.
-    if let Some(pid) = body.person_id {
+    if let Some(pid) = person_id {
         let affected = invalidation::affected_persons(&state.db, pid)
             .await
             .map_err(ApiError)?;
@@ -63,7 +222,7 @@ pub async fn create_event(
             .invalidate_for_mutation(tree_id, &affected)
             .await
             .map_err(ApiError)?;
-    } else if let Some(fid) = body.family_id {
+    } else if let Some(fid) = family_id {
         let affected = invalidation::affected_persons_for_family(&state.db, fid)
             .await
             .map_err(ApiError)?;
@@ -73,9 +232,10 @@ pub async fn create_event(
             .await
             .map_err(ApiError)?;
     }
+    let warnings = plausibility::event_warnings(&state.db, &event).await?;
     Ok((
         StatusCode::CREATED,
-        Json(serde_json::to_value(event).unwrap()),
+        Json(SavedEventResponse { event, warnings }),
     ))
 }
 
@@ -83,19 +243,30 @@ pub async fn create_event(
 pub async fn get_event(
     State(state): State<AppState>,
     Path((_tree_id, event_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<serde_json::Value>, ApiError> {
+) -> Result<ETagged, ApiError> {
     let event = EventRepo::get(&state.db, event_id)
         .await
         .map_err(ApiError::from)?;
-    Ok(Json(serde_json::to_value(event).unwrap()))
+    Ok(etag::tagged(event.version, event))
 }
 
 /// PUT /api/v1/trees/:tree_id/events/:event_id
 pub async fn update_event(
     State(state): State<AppState>,
     Path((tree_id, event_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
     Json(body): Json<UpdateEventRequest>,
-) -> Result<Json<serde_json::Value>, ApiError> {
+) -> Result<ETagged, ApiError> {
+    access::ensure_in_tree(
+        &state,
+        tree_id,
+        [
+            (TreeEntity::Place, body.place_id.flatten()),
+            (TreeEntity::Place, body.place_id_to.flatten()),
+        ],
+    )
+    .await?;
+    let expected_version = etag::if_match(&headers)?;
     let event = EventRepo::update(
         &state.db,
         event_id,
@@ -103,11 +274,14 @@ pub async fn update_event(
         body.date_value,
         body.date_sort,
         body.place_id,
+        body.place_id_to,
         body.description,
         None,
         None,
         None,
         None,
+        body.age,
+        expected_version,
     )
     .await
     .map_err(ApiError::from)?;
@@ -131,7 +305,54 @@ pub async fn update_event(
             .await
             .map_err(ApiError)?;
     }
-    Ok(Json(serde_json::to_value(event).unwrap()))
+    let warnings = plausibility::event_warnings(&state.db, &event).await?;
+    Ok(etag::tagged(
+        event.version,
+        SavedEventResponse { event, warnings },
+    ))
+}
+
+/// PATCH /api/v1/trees/:tree_id/events/:event_id/owner
+///
+/// Move an event to another person or family of the tree, e.g. a census
+/// entry imported onto the wrong household member. The event keeps its
+/// citations and notes; honours `If-Match` like [`update_event`].
+pub async fn reassign_event(
+    State(state): State<AppState>,
+    Path((tree_id, event_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    Json(body): Json<ReassignEventRequest>,
+) -> Result<ETagged, ApiError> {
+    let expected_version = etag::if_match(&headers)?;
+    let previous = EventRepo::get(&state.db, event_id).await?;
+    let event = EventRepo::reassign(
+        &state.db,
+        tree_id,
+        event_id,
+        body.person_id,
+        body.family_id,
+        expected_version,
+    )
+    .await?;
+    // Both the former and the new owner show the event.
+    let mut affected = Vec::new();
+    for (person_id, family_id) in [
+        (previous.person_id, previous.family_id),
+        (event.person_id, event.family_id),
+    ] {
+        if let Some(pid) = person_id {
+            affected.extend(invalidation::affected_persons(&state.db, pid).await?);
+        } else if let Some(fid) = family_id {
+            affected.extend(invalidation::affected_persons_for_family(&state.db, fid).await?);
+        }
+    }
+    affected.sort_unstable();
+    affected.dedup();
+    state
+        .cache
+        .invalidate_for_mutation(tree_id, &affected)
+        .await?;
+    Ok(etag::tagged(event.version, event))
 }
 
 /// DELETE /api/v1/trees/:tree_id/events/:event_id
@@ -167,29 +388,54 @@ pub async fn delete_event(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// GET /api/v1/trees/:tree_id/events/:event_id/participants
+pub async fn list_participants(
+    State(state): State<AppState>,
+    Path((_tree_id, event_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let participants = EventParticipantRepo::list_by_event(&state.db, event_id)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(serde_json::to_value(participants).unwrap()))
+}
+
 /// GET /api/v1/trees/:tree_id/events/:event_id/witnesses
+///
+/// Deprecated alias of [`list_participants`], from before participants had
+/// roles: only the witnesses.
 pub async fn list_witnesses(
     State(state): State<AppState>,
     Path((_tree_id, event_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    let witnesses = EventWitnessRepo::list_by_event(&state.db, event_id)
+    let mut witnesses = EventParticipantRepo::list_by_event(&state.db, event_id)
         .await
         .map_err(ApiError::from)?;
+    witnesses.retain(|p| p.role == ParticipantRole::Witness);
     Ok(Json(serde_json::to_value(witnesses).unwrap()))
 }
 
-/// POST /api/v1/trees/:tree_id/events/:event_id/witnesses
-pub async fn add_witness(
+/// POST /api/v1/trees/:tree_id/events/:event_id/participants
+pub async fn add_participant(
     State(state): State<AppState>,
-    Path((_tree_id, event_id)): Path<(Uuid, Uuid)>,
-    Json(body): Json<AddEventWitnessRequest>,
+    Path((tree_id, event_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<AddEventParticipantRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    access::ensure_in_tree(
+        &state,
+        tree_id,
+        [(TreeEntity::Person, Some(body.person_id))],
+    )
+    .await?;
+    EventRepo::get(&state.db, event_id)
+        .await
+        .map_err(ApiError::from)?;
     let id = Uuid::now_v7();
-    let witness = EventWitnessRepo::create(
+    let participant = EventParticipantRepo::create(
         &state.db,
         id,
         event_id,
         body.person_id,
+        body.role,
         body.relation,
         body.sort_order,
     )
@@ -197,16 +443,16 @@ pub async fn add_witness(
     .map_err(ApiError::from)?;
     Ok((
         StatusCode::CREATED,
-        Json(serde_json::to_value(witness).unwrap()),
+        Json(serde_json::to_value(participant).unwrap()),
     ))
 }
 
-/// DELETE /api/v1/trees/:tree_id/events/:event_id/witnesses/:witness_id
-pub async fn remove_witness(
+/// DELETE /api/v1/trees/:tree_id/events/:event_id/participants/:participant_id
+pub async fn remove_participant(
     State(state): State<AppState>,
-    Path((_tree_id, _event_id, witness_id)): Path<(Uuid, Uuid, Uuid)>,
+    Path((_tree_id, _event_id, participant_id)): Path<(Uuid, Uuid, Uuid)>,
 ) -> Result<StatusCode, ApiError> {
-    EventWitnessRepo::delete(&state.db, witness_id)
+    EventParticipantRepo::delete(&state.db, participant_id)
         .await
         .map_err(ApiError::from)?;
     Ok(StatusCode::NO_CONTENT)
@@ -0,0 +1,145 @@
+//! REST handlers for non-GEDCOM tree exports.
+
+use axum::Json;
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use oxidgene_core::OxidGeneError;
+use oxidgene_db::repo::TreeRepo;
+use oxidgene_gedcom::ExportOptions;
+use uuid::Uuid;
+
+use super::dto::{
+    CsvExportQuery, DotExportQuery, ExportGedcomResponse, SelectionExportFormat,
+    SelectionExportJsonResponse, SelectionExportRequest,
+};
+use super::error::ApiError;
+use super::state::AppState;
+use crate::render::{csv, dot};
+use crate::service::gedcom::{self, ExportSubset, Reach};
+
+/// GET /api/v1/trees/:tree_id/export.dot
+///
+/// Graphviz DOT description of the tree, optionally scoped to the persons
+/// within `generations` of `root`.
+pub async fn export_dot(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Query(query): Query<DotExportQuery>,
+) -> Result<Response, ApiError> {
+    let tree = TreeRepo::get(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    let persons = state
+        .cache
+        .get_all_persons(tree_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    let body = match query.root {
+        Some(root) => {
+            if !persons.iter().any(|p| p.person_id == root) {
+                return Err(ApiError(OxidGeneError::NotFound {
+                    entity: "Person",
+                    id: root,
+                }));
+            }
+            dot::render(
+                &tree.name,
+                dot::scope_to_root(&persons, root, query.generations),
+            )
+        }
+        None => dot::render(&tree.name, &persons),
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/vnd.graphviz; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"tree.dot\"",
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// GET /api/v1/trees/:tree_id/export.csv?entity=persons|events|families
+///
+/// The tree as a flat CSV table for spreadsheets, streamed as it is
+/// encoded.
+pub async fn export_csv(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Query(query): Query<CsvExportQuery>,
+) -> Result<Response, ApiError> {
+    TreeRepo::get(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    let persons = state
+        .cache
+        .get_all_persons(tree_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    let chunks = csv::render(query.entity, persons);
+    let disposition = format!("attachment; filename=\"{}.csv\"", query.entity.as_str());
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(tokio_stream::iter(chunks)),
+    )
+        .into_response())
+}
+
+/// POST /api/v1/trees/:tree_id/export
+///
+/// Export a chosen set of persons, optionally with their ancestors,
+/// descendants and spouses, as GEDCOM (the same response as the GEDCOM
+/// export) or as JSON records. Family members outside the set are left out
+/// of family links, each with a warning.
+pub async fn export_selection(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Json(body): Json<SelectionExportRequest>,
+) -> Result<Response, ApiError> {
+    let subset = ExportSubset {
+        person_ids: body.person_ids,
+        ancestors: body.ancestor_generations.map_or(Reach::None, |n| {
+            Reach::Generations(state.config.ancestry_depth(Some(n)))
+        }),
+        descendants: if body.include_descendants {
+            Reach::Generations(state.config.ancestry_depth(None))
+        } else {
+            Reach::None
+        },
+        include_spouses: body.include_spouses,
+    };
+    match body.format {
+        SelectionExportFormat::Gedcom => {
+            let data = gedcom::load_and_export(
+                &state.db,
+                tree_id,
+                &ExportOptions::default(),
+                Some(&subset),
+            )
+            .await
+            .map_err(ApiError::from)?;
+            Ok(Json(ExportGedcomResponse {
+                gedcom: data.gedcom,
+                warnings: data.warnings,
+            })
+            .into_response())
+        }
+        SelectionExportFormat::Json => {
+            let (records, warnings) = gedcom::load_records(&state.db, tree_id, Some(&subset))
+                .await
+                .map_err(ApiError::from)?;
+            Ok(Json(SelectionExportJsonResponse { records, warnings }).into_response())
+        }
+    }
+}
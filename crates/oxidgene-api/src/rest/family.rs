@@ -4,10 +4,12 @@ use axum::Json;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use oxidgene_cache::invalidation;
-use oxidgene_db::repo::{FamilyRepo, PaginationParams};
+use oxidgene_core::chronology;
+use oxidgene_core::types::Event;
+use oxidgene_db::repo::{CitationRepo, EventRepo, FamilyRepo, NoteRepo, PersonAncestryRepo};
 use uuid::Uuid;
 
-use super::dto::PaginationQuery;
+use super::dto::{FamilyEventWarning, FamilyEventsResponse, PaginationQuery};
 use super::error::ApiError;
 use super::state::AppState;
 
@@ -17,10 +19,7 @@ pub async fn list_families(
     Path(tree_id): Path<Uuid>,
     Query(query): Query<PaginationQuery>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    let params = PaginationParams {
-        first: query.first.unwrap_or(25),
-        after: query.after,
-    };
+    let params = state.config.page_params(query.first, query.after);
     let connection = FamilyRepo::list(&state.db, tree_id, &params)
         .await
         .map_err(ApiError::from)?;
@@ -54,6 +53,55 @@ pub async fn get_family(
     Ok(Json(serde_json::to_value(family).unwrap()))
 }
 
+/// GET /api/v1/trees/:tree_id/families/:family_id/events
+///
+/// Marriage, divorce and other events attached to the family, with a
+/// warning for each divorce dated before the marriage.
+pub async fn list_family_events(
+    State(state): State<AppState>,
+    Path((_tree_id, family_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<FamilyEventsResponse>, ApiError> {
+    FamilyRepo::get(&state.db, family_id).await?;
+    let mut events = EventRepo::list_by_families(&state.db, &[family_id]).await?;
+    events.sort_by(Event::chronological_cmp);
+    let warnings = chronology::divorces_before_marriage(&events)
+        .into_iter()
+        .map(|w| FamilyEventWarning {
+            kind: "divorce_before_marriage",
+            event_id: w.divorce_event_id,
+            related_event_id: w.marriage_event_id,
+        })
+        .collect();
+    Ok(Json(FamilyEventsResponse { events, warnings }))
+}
+
+/// GET /api/v1/trees/:tree_id/families/:family_id/notes
+///
+/// Notes attached to the family itself, e.g. the circumstances of a
+/// marriage.
+pub async fn list_family_notes(
+    State(state): State<AppState>,
+    Path((tree_id, family_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    FamilyRepo::get(&state.db, family_id).await?;
+    let notes =
+        NoteRepo::list_by_entity(&state.db, tree_id, None, None, Some(family_id), None).await?;
+    Ok(Json(serde_json::to_value(notes).unwrap()))
+}
+
+/// GET /api/v1/trees/:tree_id/families/:family_id/citations
+///
+/// Citations attached to the family itself, e.g. the record of the union.
+/// Citations of the family's events are listed with each event.
+pub async fn list_family_citations(
+    State(state): State<AppState>,
+    Path((_tree_id, family_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    FamilyRepo::get(&state.db, family_id).await?;
+    let citations = CitationRepo::list_by_family(&state.db, family_id).await?;
+    Ok(Json(serde_json::to_value(citations).unwrap()))
+}
+
 /// PUT /api/v1/trees/:tree_id/families/:family_id
 pub async fn update_family(
     State(state): State<AppState>,
@@ -77,6 +125,7 @@ pub async fn delete_family(
     FamilyRepo::delete(&state.db, family_id)
         .await
         .map_err(ApiError::from)?;
+    PersonAncestryRepo::refresh_family(&state.db, tree_id, family_id).await?;
     if !affected.is_empty() {
         state
             .cache
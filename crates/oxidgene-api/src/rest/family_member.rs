@@ -4,10 +4,14 @@ use axum::Json;
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use oxidgene_cache::invalidation;
-use oxidgene_db::repo::{FamilyChildRepo, FamilySpouseRepo};
+use oxidgene_core::{ChildType, SpouseRole};
+use oxidgene_db::repo::{
+    FamilyChildRepo, FamilySpouseRepo, PersonAncestryRepo, PersonRepo, TreeEntity,
+};
 use uuid::Uuid;
 
-use super::dto::{AddChildRequest, AddSpouseRequest};
+use super::access;
+use super::dto::{AddChildRequest, AddChildrenRequest, AddSpouseRequest, ReorderChildrenRequest};
 use super::error::ApiError;
 use super::state::AppState;
 
@@ -30,17 +34,30 @@ pub async fn add_spouse(
     Path((tree_id, family_id)): Path<(Uuid, Uuid)>,
     Json(body): Json<AddSpouseRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
-    let id = Uuid::now_v7();
-    let spouse = FamilySpouseRepo::create(
-        &state.db,
-        id,
-        family_id,
-        body.person_id,
-        body.role,
-        body.sort_order,
+    access::ensure_in_tree(
+        &state,
+        tree_id,
+        [(TreeEntity::Person, Some(body.person_id))],
     )
-    .await
-    .map_err(ApiError::from)?;
+    .await?;
+    let role = match body.role {
+        Some(role) => role,
+        None => {
+            let person = PersonRepo::get(&state.db, body.person_id)
+                .await
+                .map_err(ApiError::from)?;
+            SpouseRole::for_sex(person.sex)
+        }
+    };
+    let id = Uuid::now_v7();
+    let sort_order = FamilySpouseRepo::sort_order_for_new(&state.db, family_id, body.sort_order)
+        .await
+        .map_err(ApiError::from)?;
+    let spouse =
+        FamilySpouseRepo::create(&state.db, id, family_id, body.person_id, role, sort_order)
+            .await
+            .map_err(ApiError::from)?;
+    PersonAncestryRepo::refresh_family(&state.db, tree_id, family_id).await?;
     let affected = invalidation::affected_persons_for_family_spouse_change(
         &state.db,
         family_id,
@@ -83,6 +100,7 @@ pub async fn remove_spouse(
     FamilySpouseRepo::delete(&state.db, spouse_id)
         .await
         .map_err(ApiError::from)?;
+    PersonAncestryRepo::refresh_family(&state.db, tree_id, family_id).await?;
     if !affected.is_empty() {
         state
             .cache
@@ -112,17 +130,27 @@ pub async fn add_child(
     Path((tree_id, family_id)): Path<(Uuid, Uuid)>,
     Json(body): Json<AddChildRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    access::ensure_in_tree(
+        &state,
+        tree_id,
+        [(TreeEntity::Person, Some(body.person_id))],
+    )
+    .await?;
     let id = Uuid::now_v7();
+    let sort_order = FamilyChildRepo::sort_order_for_new(&state.db, family_id, body.sort_order)
+        .await
+        .map_err(ApiError::from)?;
     let child = FamilyChildRepo::create(
         &state.db,
         id,
         family_id,
         body.person_id,
         body.child_type,
-        body.sort_order,
+        sort_order,
     )
     .await
     .map_err(ApiError::from)?;
+    PersonAncestryRepo::refresh(&state.db, tree_id, &[body.person_id]).await?;
     let affected = invalidation::affected_persons_for_family_child_change(
         &state.db,
         family_id,
@@ -141,6 +169,99 @@ pub async fn add_child(
     ))
 }
 
+/// POST /api/v1/trees/:tree_id/families/:family_id/children/bulk
+///
+/// Add a whole sibship in one transaction with a single ancestry refresh;
+/// returns the created links in order.
+pub async fn add_children(
+    State(state): State<AppState>,
+    Path((tree_id, family_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<AddChildrenRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    let persons: Vec<_> = body
+        .children
+        .iter()
+        .map(|c| (TreeEntity::Person, Some(c.person_id)))
+        .collect();
+    access::ensure_in_tree(&state, tree_id, persons).await?;
+    let children: Vec<(Uuid, ChildType)> = body
+        .children
+        .iter()
+        .map(|c| (c.person_id, c.child_type))
+        .collect();
+    let created = FamilyChildRepo::create_many(&state.db, tree_id, family_id, &children)
+        .await
+        .map_err(ApiError::from)?;
+    let mut affected = Vec::new();
+    for (person_id, _) in &children {
+        affected.extend(
+            invalidation::affected_persons_for_family_child_change(
+                &state.db, family_id, *person_id,
+            )
+            .await
+            .map_err(ApiError)?,
+        );
+    }
+    affected.sort();
+    affected.dedup();
+    if !affected.is_empty() {
+        state
+            .cache
+            .invalidate_for_mutation(tree_id, &affected)
+            .await
+            .map_err(ApiError)?;
+    }
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::to_value(created).unwrap()),
+    ))
+}
+
+/// PATCH /api/v1/trees/:tree_id/families/:family_id/children/reorder
+///
+/// Body lists every child link of the family in the new order; returns the
+/// children in that order.
+pub async fn reorder_children(
+    State(state): State<AppState>,
+    Path((tree_id, family_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<ReorderChildrenRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let children = FamilyChildRepo::reorder(&state.db, family_id, &body.child_ids)
+        .await
+        .map_err(ApiError::from)?;
+    let affected = invalidation::affected_persons_for_family_reorder(&state.db, family_id)
+        .await
+        .map_err(ApiError)?;
+    state
+        .cache
+        .invalidate_for_mutation(tree_id, &affected)
+        .await
+        .map_err(ApiError)?;
+    Ok(Json(serde_json::to_value(children).unwrap()))
+}
+
+/// POST /api/v1/trees/:tree_id/families/:family_id/children/sort-by-birth
+///
+/// Order the children by birth date, undated ones last; returns the new
+/// ordering.
+pub async fn sort_children_by_birth(
+    State(state): State<AppState>,
+    Path((tree_id, family_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let children = FamilyChildRepo::sort_by_birth(&state.db, family_id)
+        .await
+        .map_err(ApiError::from)?;
+    let affected = invalidation::affected_persons_for_family_reorder(&state.db, family_id)
+        .await
+        .map_err(ApiError)?;
+    state
+        .cache
+        .invalidate_for_mutation(tree_id, &affected)
+        .await
+        .map_err(ApiError)?;
+    Ok(Json(serde_json::to_value(children).unwrap()))
+}
+
 /// DELETE /api/v1/trees/:tree_id/families/:family_id/children/:child_id
 pub async fn remove_child(
     State(state): State<AppState>,
@@ -164,6 +285,9 @@ pub async fn remove_child(
     FamilyChildRepo::delete(&state.db, child_id)
         .await
         .map_err(ApiError::from)?;
+    if let Some(pid) = person_id {
+        PersonAncestryRepo::refresh(&state.db, tree_id, &[pid]).await?;
+    }
     if !affected.is_empty() {
         state
             .cache
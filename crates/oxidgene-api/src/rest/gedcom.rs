@@ -1,50 +1,200 @@
 //! REST handlers for GEDCOM import and export.
 
+use std::convert::Infallible;
+
 use axum::Json;
+use axum::body::Body;
 use axum::extract::{Path, Query, State};
 use axum::http::{StatusCode, header};
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::{IntoResponse, Response};
 use oxidgene_core::OxidGeneError;
+use oxidgene_db::repo::TreeRepo;
+use oxidgene_gedcom::ExportOptions;
+use tokio_stream::wrappers::WatchStream;
+use tokio_stream::{Stream, StreamExt};
+use tokio_util::io::ReaderStream;
 use uuid::Uuid;
 
 use super::dto::{
-    ExportGedcomQuery, ExportGedcomResponse, ImportGedcomRequest, ImportGedcomResponse,
+    ExportGedcomQuery, ExportGedcomResponse, ExportJobResponse, ImportGedcomRequest,
+    ImportJobResponse, ImportPreviewResponse,
 };
 use super::error::ApiError;
 use super::state::AppState;
-use crate::service::gedcom;
+use crate::config::ApiConfig;
+use crate::service::export_job::ExportPhase;
+use crate::service::gedcom::{self, ExportEstimate, ExportSubset};
+use crate::service::import_job::ImportPhase;
 
-/// POST /api/v1/trees/:tree_id/import
+/// POST /api/v1/trees/:tree_id/gedcom/import
 ///
-/// Import a GEDCOM string into the given tree, persisting all extracted entities.
+/// Start importing a GEDCOM string into the given tree. The import runs in
+/// the background; the response carries a `job_id` whose progress can be
+/// followed at `GET /api/v1/trees/:tree_id/import/:job_id/events`.
 pub async fn import_gedcom_handler(
     State(state): State<AppState>,
     Path(tree_id): Path<Uuid>,
     Json(body): Json<ImportGedcomRequest>,
-) -> Result<(StatusCode, Json<ImportGedcomResponse>), ApiError> {
-    let summary = gedcom::import_and_persist(&state.db, tree_id, &body.gedcom)
+) -> Result<(StatusCode, Json<ImportJobResponse>), ApiError> {
+    // Fail fast on an unknown tree rather than through the event stream.
+    TreeRepo::get(&state.db, tree_id)
         .await
         .map_err(ApiError::from)?;
 
-    // Eagerly rebuild the entire cache for this tree after GEDCOM import
-    state
-        .cache
-        .rebuild_tree_full(tree_id)
+    let job_id = spawn_import(state, tree_id, body.gedcom);
+    Ok((StatusCode::ACCEPTED, Json(ImportJobResponse { job_id })))
+}
+
+/// POST /api/v1/trees/:tree_id/import/preview
+///
+/// Parse a GEDCOM string and match it against the tree as an import would,
+/// without writing anything: what would be created, the existing places
+/// reused and the records already in the tree by `_UID`. The returned
+/// token confirms the import (see [`commit_import_preview_handler`]).
+pub async fn preview_import_handler(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Json(body): Json<ImportGedcomRequest>,
+) -> Result<Json<ImportPreviewResponse>, ApiError> {
+    let plan = gedcom::preview_import(&state.db, tree_id, &body.gedcom)
         .await
         .map_err(ApiError::from)?;
+    let (token, expires_at) = state
+        .import_previews
+        .insert(tree_id, body.gedcom, plan.clone());
+    Ok(Json(ImportPreviewResponse {
+        token,
+        expires_at,
+        plan,
+    }))
+}
 
-    let response = ImportGedcomResponse {
-        persons_count: summary.persons_count,
-        families_count: summary.families_count,
-        events_count: summary.events_count,
-        sources_count: summary.sources_count,
-        media_count: summary.media_count,
-        places_count: summary.places_count,
-        notes_count: summary.notes_count,
-        warnings: summary.warnings,
-    };
+/// POST /api/v1/trees/:tree_id/import/preview/:token/commit
+///
+/// Run the import previewed under `token`, as a background job like
+/// [`import_gedcom_handler`]. The file is matched against the tree again
+/// first: 409 if the outcome changed since the preview (the tree was
+/// edited meanwhile), in which case the import must be previewed again.
+/// A token is used once.
+pub async fn commit_import_preview_handler(
+    State(state): State<AppState>,
+    Path((tree_id, token)): Path<(Uuid, Uuid)>,
+) -> Result<(StatusCode, Json<ImportJobResponse>), ApiError> {
+    let pending = state
+        .import_previews
+        .take(tree_id, token)
+        .ok_or(OxidGeneError::NotFound {
+            entity: "ImportPreview",
+            id: token,
+        })
+        .map_err(ApiError::from)?;
+    let plan = gedcom::preview_import(&state.db, tree_id, &pending.gedcom)
+        .await
+        .map_err(ApiError::from)?;
+    if plan != pending.plan {
+        return Err(ApiError(OxidGeneError::Conflict(
+            "the tree changed since the import was previewed; preview it again".to_string(),
+        )));
+    }
+
+    let job_id = spawn_import(state, tree_id, pending.gedcom);
+    Ok((StatusCode::ACCEPTED, Json(ImportJobResponse { job_id })))
+}
+
+/// Start a background import job of `gedcom` into `tree_id` and return its
+/// id.
+fn spawn_import(state: AppState, tree_id: Uuid, gedcom: String) -> Uuid {
+    let (job_id, tracker) = state.imports.start(tree_id);
+
+    tokio::spawn(async move {
+        let result =
+            gedcom::import_and_persist_with_progress(&state.db, tree_id, &gedcom, &tracker).await;
+        let result = match result {
+            Ok(summary) => {
+                // Eagerly rebuild the entire cache for this tree after GEDCOM import
+                tracker.set_phase(ImportPhase::RebuildingCache);
+                state
+                    .cache
+                    .rebuild_tree_full(tree_id)
+                    .await
+                    .map(|_| summary)
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(summary) => tracker.finish(summary),
+            Err(_) if tracker.is_cancelled() => {
+                tracing::info!(%tree_id, %job_id, "GEDCOM import cancelled");
+                tracker.cancelled();
+            }
+            Err(e) => {
+                tracing::warn!(%tree_id, %job_id, error = %e, "GEDCOM import failed");
+                tracker.fail(e.to_string());
+            }
+        }
+    });
 
-    Ok((StatusCode::CREATED, Json(response)))
+    job_id
+}
+
+/// POST /api/v1/trees/:tree_id/import/:job_id/cancel
+///
+/// Ask a running import to stop. It stops before its next insert batch and
+/// rolls back everything it inserted, then ends its event stream with a
+/// `cancelled` event. 409 if the import has already committed.
+pub async fn cancel_import_handler(
+    State(state): State<AppState>,
+    Path((tree_id, job_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    let progress = state
+        .imports
+        .cancel(tree_id, job_id)
+        .ok_or(OxidGeneError::NotFound {
+            entity: "ImportJob",
+            id: job_id,
+        })
+        .map_err(ApiError::from)?;
+    if !progress.phase.is_cancellable() {
+        return Err(ApiError(OxidGeneError::Conflict(format!(
+            "import {job_id} can no longer be cancelled"
+        ))));
+    }
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// GET /api/v1/trees/:tree_id/import/:job_id/events
+///
+/// Server-Sent Events stream of an import job's progress. Emits `progress`
+/// events while the job runs, then a single `done` event (carrying the
+/// import summary), `failed` event (carrying `{ "error": ... }`) or
+/// `cancelled` event, after which the stream ends.
+pub async fn import_events_handler(
+    State(state): State<AppState>,
+    Path((tree_id, job_id)): Path<(Uuid, Uuid)>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let rx = state
+        .imports
+        .subscribe(tree_id, job_id)
+        .ok_or(OxidGeneError::NotFound {
+            entity: "ImportJob",
+            id: job_id,
+        })
+        .map_err(ApiError::from)?;
+
+    let events = WatchStream::new(rx).map(|progress| {
+        let event = match progress.phase {
+            ImportPhase::Done => Event::default().event("done").json_data(&progress.summary),
+            ImportPhase::Failed => Event::default()
+                .event("failed")
+                .json_data(serde_json::json!({ "error": progress.error })),
+            ImportPhase::Cancelled => Event::default().event("cancelled").json_data(&progress),
+            _ => Event::default().event("progress").json_data(&progress),
+        };
+        Ok(event.expect("import progress serializes to JSON"))
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
 }
 
 /// GET /api/v1/trees/:tree_id/export
@@ -53,17 +203,28 @@ pub async fn import_gedcom_handler(
 /// `?format=gedzip` to instead receive a GEDZIP archive (`application/zip`)
 /// wrapping the same GEDCOM data. Pass `?merge_occupations=true` to collapse
 /// each person's multiple `OCCU` tags back into one, comma-separated.
+///
+/// `?submitter=`, `?software_name=`, `?software_version=` and `?language=`
+/// override the header metadata (see [`ExportOptions`]).
+///
+/// Pass `?person_id=...` to export one branch only: that person and their
+/// ancestors (`&include_descendants=true` adds their descendants), up to
+/// `&generations=N` in each direction. Family members outside the branch
+/// are left out of family links, each with a warning.
+///
+/// The response only starts once the whole export is done; large trees are
+/// better exported as a job (see [`start_export_handler`]).
 pub async fn export_gedcom_handler(
     State(state): State<AppState>,
     Path(tree_id): Path<Uuid>,
     Query(query): Query<ExportGedcomQuery>,
 ) -> Result<Response, ApiError> {
-    let data =
-        gedcom::load_and_export(&state.db, tree_id, query.merge_occupations.unwrap_or(false))
-            .await
-            .map_err(ApiError::from)?;
+    let (options, subset, gedzip) = export_params(&state.config, query);
+    let data = gedcom::load_and_export(&state.db, tree_id, &options, subset.as_ref())
+        .await
+        .map_err(ApiError::from)?;
 
-    if query.format.as_deref() == Some("gedzip") {
+    if gedzip {
         let bytes = oxidgene_gedcom::export::export_gedzip(&data.gedcom)
             .map_err(OxidGeneError::Gedcom)
             .map_err(ApiError::from)?;
@@ -87,3 +248,163 @@ pub async fn export_gedcom_handler(
     })
     .into_response())
 }
+
+/// The export options, branch and GEDZIP flag of an export query. The
+/// branch's generations are clamped like an ancestor query's `max_depth`.
+fn export_params(
+    config: &ApiConfig,
+    query: ExportGedcomQuery,
+) -> (ExportOptions, Option<ExportSubset>, bool) {
+    let subset = query.person_id.map(|person_id| {
+        ExportSubset::branch(
+            person_id,
+            config.ancestry_depth(query.generations),
+            query.include_descendants,
+        )
+    });
+    let defaults = ExportOptions::default();
+    let options = ExportOptions {
+        merge_occupations: query.merge_occupations.unwrap_or(false),
+        submitter: query.submitter.unwrap_or(defaults.submitter),
+        software_name: query.software_name.unwrap_or(defaults.software_name),
+        software_version: query.software_version.unwrap_or(defaults.software_version),
+        language: query.language,
+    };
+    let gedzip = query.format.as_deref() == Some("gedzip");
+    (options, subset, gedzip)
+}
+
+/// GET /api/v1/trees/:tree_id/export/estimate
+///
+/// Approximate size of the tree's export, cheap to compute: its live
+/// persons, families, sources and media, and their sum `records_total`,
+/// which is what an export job counts its progress against.
+pub async fn export_estimate_handler(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+) -> Result<Json<ExportEstimate>, ApiError> {
+    let estimate = gedcom::estimate_export(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(estimate))
+}
+
+/// POST /api/v1/trees/:tree_id/export/jobs
+///
+/// Start exporting the tree in the background, with the same query
+/// parameters as [`export_gedcom_handler`]. The response carries a `job_id`
+/// whose progress can be followed at
+/// `GET /api/v1/trees/:tree_id/export/:job_id/events`; the file is then
+/// downloaded from `GET /api/v1/trees/:tree_id/export/:job_id/download`.
+pub async fn start_export_handler(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Query(query): Query<ExportGedcomQuery>,
+) -> Result<(StatusCode, Json<ExportJobResponse>), ApiError> {
+    // Fail fast on an unknown tree rather than through the event stream.
+    TreeRepo::get(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    let (options, subset, gedzip) = export_params(&state.config, query);
+    let (job_id, tracker) = state.exports.start(tree_id);
+    tokio::spawn(async move {
+        let result = gedcom::export_with_progress(
+            &state.db,
+            tree_id,
+            &options,
+            subset.as_ref(),
+            gedzip,
+            &tracker,
+        )
+        .await;
+        match result {
+            Ok(artifact) => tracker.finish(artifact),
+            Err(e) => {
+                tracing::warn!(%tree_id, %job_id, error = %e, "GEDCOM export failed");
+                tracker.fail(e.to_string());
+            }
+        }
+    });
+
+    Ok((StatusCode::ACCEPTED, Json(ExportJobResponse { job_id })))
+}
+
+/// GET /api/v1/trees/:tree_id/export/:job_id/events
+///
+/// Server-Sent Events stream of an export job's progress. Emits `progress`
+/// events (`records_serialized` out of `records_total`) while the job
+/// runs, then a single `done` event (carrying `{ file_name, size_bytes,
+/// warnings }`) or `failed` event (carrying `{ "error": ... }`), after
+/// which the stream ends.
+pub async fn export_events_handler(
+    State(state): State<AppState>,
+    Path((tree_id, job_id)): Path<(Uuid, Uuid)>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let rx = state
+        .exports
+        .subscribe(tree_id, job_id)
+        .ok_or(OxidGeneError::NotFound {
+            entity: "ExportJob",
+            id: job_id,
+        })
+        .map_err(ApiError::from)?;
+
+    let events = WatchStream::new(rx).map(|progress| {
+        let event = match progress.phase {
+            ExportPhase::Done => Event::default().event("done").json_data(&progress.summary),
+            ExportPhase::Failed => Event::default()
+                .event("failed")
+                .json_data(serde_json::json!({ "error": progress.error })),
+            _ => Event::default().event("progress").json_data(&progress),
+        };
+        Ok(event.expect("export progress serializes to JSON"))
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// GET /api/v1/trees/:tree_id/export/:job_id/download
+///
+/// The file of a finished export job, streamed as an attachment. 409 while
+/// the job is still running or if it failed. Files are kept for a few
+/// minutes after the job ends.
+pub async fn download_export_handler(
+    State(state): State<AppState>,
+    Path((tree_id, job_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, ApiError> {
+    let artifact = state
+        .exports
+        .output(tree_id, job_id)
+        .ok_or(OxidGeneError::NotFound {
+            entity: "ExportJob",
+            id: job_id,
+        })
+        .map_err(ApiError::from)?
+        .map_err(|progress| {
+            ApiError(OxidGeneError::Conflict(format!(
+                "export {job_id} has no file to download (phase: {:?})",
+                progress.phase
+            )))
+        })?;
+    let file = tokio::fs::File::open(&artifact.path)
+        .await
+        .map_err(|e| ApiError(OxidGeneError::Internal(format!("export file: {e}"))))?;
+    let content_type = artifact.content_type;
+    let disposition = format!("attachment; filename=\"{}\"", artifact.file_name);
+    // The stream holds on to the artifact, so a job pruned mid-download
+    // only deletes its file once the download is over.
+    let body = Body::from_stream(ReaderStream::new(file).map(move |chunk| {
+        let _ = &artifact;
+        chunk
+    }));
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
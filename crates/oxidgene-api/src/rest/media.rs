@@ -2,12 +2,13 @@
 
 use axum::Json;
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
-use oxidgene_db::repo::{MediaRepo, PaginationParams};
+use axum::http::{HeaderMap, StatusCode};
+use oxidgene_db::repo::MediaRepo;
 use uuid::Uuid;
 
 use super::dto::{CreateMediaRequest, PaginationQuery, UpdateMediaRequest};
 use super::error::ApiError;
+use super::etag::{self, ETagged};
 use super::state::AppState;
 
 /// GET /api/v1/trees/:tree_id/media
@@ -16,10 +17,7 @@ pub async fn list_media(
     Path(tree_id): Path<Uuid>,
     Query(query): Query<PaginationQuery>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    let params = PaginationParams {
-        first: query.first.unwrap_or(25),
-        after: query.after,
-    };
+    let params = state.config.page_params(query.first, query.after);
     let connection = MediaRepo::list(&state.db, tree_id, &params)
         .await
         .map_err(ApiError::from)?;
@@ -61,23 +59,31 @@ pub async fn create_media(
 pub async fn get_media(
     State(state): State<AppState>,
     Path((_tree_id, media_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<serde_json::Value>, ApiError> {
+) -> Result<ETagged, ApiError> {
     let media = MediaRepo::get(&state.db, media_id)
         .await
         .map_err(ApiError::from)?;
-    Ok(Json(serde_json::to_value(media).unwrap()))
+    Ok(etag::tagged(media.version, media))
 }
 
 /// PUT /api/v1/trees/:tree_id/media/:media_id
 pub async fn update_media(
     State(state): State<AppState>,
     Path((_tree_id, media_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
     Json(body): Json<UpdateMediaRequest>,
-) -> Result<Json<serde_json::Value>, ApiError> {
-    let media = MediaRepo::update(&state.db, media_id, body.title, body.description)
-        .await
-        .map_err(ApiError::from)?;
-    Ok(Json(serde_json::to_value(media).unwrap()))
+) -> Result<ETagged, ApiError> {
+    let expected_version = etag::if_match(&headers)?;
+    let media = MediaRepo::update(
+        &state.db,
+        media_id,
+        body.title,
+        body.description,
+        expected_version,
+    )
+    .await
+    .map_err(ApiError::from)?;
+    Ok(etag::tagged(media.version, media))
 }
 
 /// DELETE /api/v1/trees/:tree_id/media/:media_id
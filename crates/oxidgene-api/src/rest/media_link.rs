@@ -1,14 +1,23 @@
-//! REST handlers for MediaLink create/delete operations.
+//! REST handlers for MediaLink create/delete operations and the person
+//! profile photo.
 
 use axum::Json;
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
-use oxidgene_db::repo::MediaLinkRepo;
+use axum::extract::{Path, Query, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Redirect, Response};
+use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::types::MediaLink;
+use oxidgene_db::repo::{MediaLinkRepo, MediaRepo, PersonRepo, TreeEntity};
 use uuid::Uuid;
 
-use super::dto::{CreateMediaLinkRequest, MediaLinkListRow};
+use super::access;
+use super::dto::{
+    CreateMediaLinkRequest, MediaLinkListRow, PersonMediaEntry, PersonMediaPage, PersonMediaQuery,
+    ReorderPersonMediaRequest, SetPersonPhotoRequest,
+};
 use super::error::ApiError;
 use super::state::AppState;
+use crate::render::portrait;
 
 /// GET /api/v1/trees/:tree_id/media-links
 pub async fn list_media_links(
@@ -21,11 +30,14 @@ pub async fn list_media_links(
     let response = db_rows
         .into_iter()
         .map(|r| MediaLinkListRow {
+            link_id: r.link_id,
             entity_id: r.entity_id,
             entity_type: r.entity_type,
             media_id: r.media_id,
             file_path: r.file_path,
             file_name: r.file_name,
+            mime_type: r.mime_type,
+            is_profile: r.is_profile,
         })
         .collect();
     Ok(Json(response))
@@ -34,9 +46,21 @@ pub async fn list_media_links(
 /// POST /api/v1/trees/:tree_id/media-links
 pub async fn create_media_link(
     State(state): State<AppState>,
-    Path(_tree_id): Path<Uuid>,
+    Path(tree_id): Path<Uuid>,
     Json(body): Json<CreateMediaLinkRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    access::ensure_in_tree(
+        &state,
+        tree_id,
+        [
+            (TreeEntity::Media, Some(body.media_id)),
+            (TreeEntity::Person, body.person_id),
+            (TreeEntity::Event, body.event_id),
+            (TreeEntity::Source, body.source_id),
+            (TreeEntity::Family, body.family_id),
+        ],
+    )
+    .await?;
     let id = Uuid::now_v7();
     let link = MediaLinkRepo::create(
         &state.db,
@@ -66,3 +90,142 @@ pub async fn delete_media_link(
         .map_err(ApiError::from)?;
     Ok(StatusCode::NO_CONTENT)
 }
+
+/// GET /api/v1/trees/:tree_id/persons/:person_id/photo
+///
+/// Redirects to the person's profile photo, or serves a placeholder
+/// portrait when none is designated.
+pub async fn get_person_photo(
+    State(state): State<AppState>,
+    Path((_tree_id, person_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, ApiError> {
+    let person = PersonRepo::get(&state.db, person_id).await?;
+    if let Some(link) = MediaLinkRepo::profile(&state.db, person_id).await? {
+        match MediaRepo::get(&state.db, link.media_id).await {
+            Ok(media) => return Ok(Redirect::temporary(&media.file_path).into_response()),
+            // The media was deleted since: fall back to the placeholder.
+            Err(OxidGeneError::NotFound { .. }) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok((
+        [(header::CONTENT_TYPE, "image/svg+xml")],
+        portrait::placeholder(person.sex),
+    )
+        .into_response())
+}
+
+/// PUT /api/v1/trees/:tree_id/persons/:person_id/photo
+///
+/// Designate one of the person's media links, to an image, as their
+/// profile photo, replacing the previous one.
+pub async fn set_person_photo(
+    State(state): State<AppState>,
+    Path((tree_id, person_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<SetPersonPhotoRequest>,
+) -> Result<Json<MediaLink>, ApiError> {
+    let link = MediaLinkRepo::list_by_person(&state.db, person_id)
+        .await?
+        .into_iter()
+        .find(|l| l.id == body.link_id)
+        .ok_or(OxidGeneError::NotFound {
+            entity: "MediaLink",
+            id: body.link_id,
+        })?;
+    let media = MediaRepo::get(&state.db, link.media_id).await?;
+    if !media.mime_type.starts_with("image/") {
+        return Err(OxidGeneError::Validation(format!(
+            "media {} is not an image ({})",
+            media.id, media.mime_type
+        ))
+        .into());
+    }
+    MediaLinkRepo::set_profile(&state.db, person_id, Some(link.id)).await?;
+    state
+        .cache
+        .invalidate_for_mutation(tree_id, &[person_id])
+        .await?;
+    Ok(Json(MediaLink {
+        is_profile: true,
+        ..link
+    }))
+}
+
+/// DELETE /api/v1/trees/:tree_id/persons/:person_id/photo
+///
+/// Clear the person's profile photo; the media stays linked.
+pub async fn clear_person_photo(
+    State(state): State<AppState>,
+    Path((tree_id, person_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    PersonRepo::get(&state.db, person_id).await?;
+    MediaLinkRepo::set_profile(&state.db, person_id, None).await?;
+    state
+        .cache
+        .invalidate_for_mutation(tree_id, &[person_id])
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/v1/trees/:tree_id/persons/:person_id/media
+///
+/// One page of the media linked to the person, in display order. Links
+/// to deleted media are left out.
+pub async fn list_person_media(
+    State(state): State<AppState>,
+    Path((tree_id, person_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<PersonMediaQuery>,
+) -> Result<Json<PersonMediaPage>, ApiError> {
+    let person = PersonRepo::get(&state.db, person_id).await?;
+    if person.tree_id != tree_id {
+        return Err(OxidGeneError::NotFound {
+            entity: "Person",
+            id: person_id,
+        }
+        .into());
+    }
+    let links = MediaLinkRepo::list_by_person(&state.db, person_id).await?;
+    let media_ids: Vec<Uuid> = links.iter().map(|l| l.media_id).collect();
+    let mut media: std::collections::HashMap<Uuid, _> = MediaRepo::get_many(&state.db, &media_ids)
+        .await?
+        .into_iter()
+        .map(|m| (m.id, m))
+        .collect();
+    let entries: Vec<PersonMediaEntry> = links
+        .into_iter()
+        .filter_map(|link| {
+            media.remove(&link.media_id).map(|media| PersonMediaEntry {
+                link_id: link.id,
+                sort_order: link.sort_order,
+                is_profile: link.is_profile,
+                media,
+            })
+        })
+        .collect();
+    let total = entries.len();
+    let limit = state.config.page_limit(query.limit, 12) as usize;
+    let items = entries
+        .into_iter()
+        .skip(query.offset as usize)
+        .take(limit)
+        .collect();
+    Ok(Json(PersonMediaPage { items, total }))
+}
+
+/// PUT /api/v1/trees/:tree_id/persons/:person_id/media/order
+///
+/// Reorder the person's media: the body lists every link of the person,
+/// in the new display order.
+pub async fn reorder_person_media(
+    State(state): State<AppState>,
+    Path((tree_id, person_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<ReorderPersonMediaRequest>,
+) -> Result<StatusCode, ApiError> {
+    PersonRepo::get(&state.db, person_id).await?;
+    MediaLinkRepo::reorder_person(&state.db, person_id, &body.link_ids).await?;
+    state
+        .cache
+        .invalidate_for_mutation(tree_id, &[person_id])
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,55 @@
+//! REST handlers for a tree's members and their roles (see
+//! [`super::access`]). Any member may list them; only owners change them.
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use oxidgene_core::types::TreeMembership;
+use oxidgene_core::validation;
+use oxidgene_db::repo::{TreeMembershipRepo, TreeRepo};
+use uuid::Uuid;
+
+use super::access::{Owner, RequireTreeRole};
+use super::dto::SetTreeMemberRequest;
+use super::error::ApiError;
+use super::state::AppState;
+
+/// GET /api/v1/trees/:tree_id/members
+pub async fn list_members(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+) -> Result<Json<Vec<TreeMembership>>, ApiError> {
+    TreeRepo::get(&state.db, tree_id).await?;
+    Ok(Json(
+        TreeMembershipRepo::list_by_tree(&state.db, tree_id).await?,
+    ))
+}
+
+/// PUT /api/v1/trees/:tree_id/members/:actor_id
+///
+/// Add a member or change their role. Demoting the last owner is a `409`.
+/// A tree without members is only reachable by admins, who give it its
+/// first owner this way.
+pub async fn set_member(
+    State(state): State<AppState>,
+    Path((tree_id, actor_id)): Path<(Uuid, String)>,
+    _owner: RequireTreeRole<Owner>,
+    Json(body): Json<SetTreeMemberRequest>,
+) -> Result<Json<TreeMembership>, ApiError> {
+    validation::not_blank("actor_id", &actor_id)?;
+    TreeRepo::get(&state.db, tree_id).await?;
+    let member = TreeMembershipRepo::set(&state.db, tree_id, &actor_id, body.role).await?;
+    Ok(Json(member))
+}
+
+/// DELETE /api/v1/trees/:tree_id/members/:actor_id
+///
+/// Remove a member. Removing the last owner is a `409`.
+pub async fn remove_member(
+    State(state): State<AppState>,
+    Path((tree_id, actor_id)): Path<(Uuid, String)>,
+    _owner: RequireTreeRole<Owner>,
+) -> Result<StatusCode, ApiError> {
+    TreeMembershipRepo::remove(&state.db, tree_id, &actor_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
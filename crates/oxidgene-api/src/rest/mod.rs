@@ -2,22 +2,34 @@
 //!
 //! This module provides Axum handlers for all REST endpoints under `/api/v1`.
 
+pub mod access;
+pub mod admin;
+pub mod auth;
+pub mod backup;
 pub mod cache;
 pub mod citation;
 pub mod dictionary;
 pub mod dto;
 pub mod error;
+pub mod etag;
 pub mod event;
+pub mod export;
 pub mod family;
 pub mod family_member;
 pub mod gedcom;
 pub mod media;
 pub mod media_link;
+pub mod member;
 pub mod note;
 pub mod person;
 pub mod person_name;
 pub mod place;
+pub mod request_id;
+pub mod share;
 pub mod snapshot;
 pub mod source;
 pub mod state;
+pub mod tag;
+pub mod task;
+pub mod trace;
 pub mod tree;
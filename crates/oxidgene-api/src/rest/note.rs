@@ -2,20 +2,51 @@
 
 use axum::Json;
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
-use oxidgene_db::repo::NoteRepo;
+use axum::http::{HeaderMap, StatusCode};
+use oxidgene_core::OxidGeneError;
+use oxidgene_core::types::Note;
+use oxidgene_core::validation;
+use oxidgene_db::repo::{FamilyRepo, NoteRepo, TreeEntity};
 use uuid::Uuid;
 
-use super::dto::{CreateNoteRequest, NoteListQuery, UpdateNoteRequest};
+use super::access;
+use super::dto::{CreateNoteRequest, NoteListQuery, PersonScoped, UpdateNoteRequest};
 use super::error::ApiError;
+use super::etag::{self, ETagged};
 use super::state::AppState;
 
 /// GET /api/v1/trees/:tree_id/notes
+///
+/// With `person_id` and `include_family=true`, the person's notes followed
+/// by those of their families, each flagged `inherited` or not.
 pub async fn list_notes(
     State(state): State<AppState>,
     Path(tree_id): Path<Uuid>,
     Query(query): Query<NoteListQuery>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
+    if query.include_family {
+        let Some(person_id) = query.person_id else {
+            return Err(
+                OxidGeneError::Validation("include_family requires person_id".into()).into(),
+            );
+        };
+        let families = FamilyRepo::list_for_person(&state.db, person_id).await?;
+        let family_ids: Vec<Uuid> = families.iter().map(|(id, _)| *id).collect();
+        let notes =
+            NoteRepo::list_by_person_and_families(&state.db, tree_id, person_id, &family_ids)
+                .await?;
+        let mut notes: Vec<PersonScoped<Note>> = notes
+            .into_iter()
+            .map(|note| {
+                let on_person = note.person_id == Some(person_id);
+                let family_id = note.family_id;
+                PersonScoped::new(note, on_person, family_id, &families)
+            })
+            .collect();
+        // Direct notes first; the sort is stable, keeping creation order.
+        notes.sort_by_key(|n| n.inherited);
+        return Ok(Json(serde_json::to_value(notes).unwrap()));
+    }
     let notes = NoteRepo::list_by_entity(
         &state.db,
         tree_id,
@@ -35,17 +66,25 @@ pub async fn create_note(
     Path(tree_id): Path<Uuid>,
     Json(body): Json<CreateNoteRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
-    if body.text.trim().is_empty() {
-        return Err(ApiError(oxidgene_core::OxidGeneError::Validation(
-            "text must not be empty".to_string(),
-        )));
-    }
+    access::ensure_in_tree(
+        &state,
+        tree_id,
+        [
+            (TreeEntity::Person, body.person_id),
+            (TreeEntity::Event, body.event_id),
+            (TreeEntity::Family, body.family_id),
+            (TreeEntity::Source, body.source_id),
+        ],
+    )
+    .await?;
+    validation::not_blank("text", &body.text)?;
     let id = Uuid::now_v7();
     let note = NoteRepo::create(
         &state.db,
         id,
         tree_id,
         body.text,
+        body.format,
         body.person_id,
         body.event_id,
         body.family_id,
@@ -63,23 +102,28 @@ pub async fn create_note(
 pub async fn get_note(
     State(state): State<AppState>,
     Path((_tree_id, note_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<serde_json::Value>, ApiError> {
+) -> Result<ETagged, ApiError> {
     let note = NoteRepo::get(&state.db, note_id)
         .await
         .map_err(ApiError::from)?;
-    Ok(Json(serde_json::to_value(note).unwrap()))
+    Ok(etag::tagged(note.version, note))
 }
 
 /// PUT /api/v1/trees/:tree_id/notes/:note_id
 pub async fn update_note(
     State(state): State<AppState>,
     Path((_tree_id, note_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
     Json(body): Json<UpdateNoteRequest>,
-) -> Result<Json<serde_json::Value>, ApiError> {
-    let note = NoteRepo::update(&state.db, note_id, body.text)
+) -> Result<ETagged, ApiError> {
+    let expected_version = etag::if_match(&headers)?;
+    if let Some(text) = &body.text {
+        validation::not_blank("text", text)?;
+    }
+    let note = NoteRepo::update(&state.db, note_id, body.text, body.format, expected_version)
         .await
         .map_err(ApiError::from)?;
-    Ok(Json(serde_json::to_value(note).unwrap()))
+    Ok(etag::tagged(note.version, note))
 }
 
 /// DELETE /api/v1/trees/:tree_id/notes/:note_id
@@ -3,24 +3,45 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
 use axum::Json;
-use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
+use axum::extract::{Extension, Path, Query, State};
+use axum::http::{HeaderMap, HeaderName, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use chrono::{Datelike, Utc};
 use oxidgene_cache::invalidation;
-use oxidgene_core::enums::SpouseRole;
+use oxidgene_core::chronology;
+use oxidgene_core::compare::{self, PersonRecord, Relative};
+use oxidgene_core::enums::{EventType, NameSelectionPolicy, NameType, Sex, SpouseRole, TreeRole};
 use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::numbering::{self, AhnentafelEntry, Lineage};
+use oxidgene_core::summary::PersonSummary;
+use oxidgene_core::types::{Connection, Edge, Person, PersonName};
 use oxidgene_db::repo::{
-    FamilyChildRepo, FamilyRepo, FamilySpouseRepo, PaginationParams, PersonAncestryRepo,
-    PersonRepo, TreeRepo,
+    CitationRepo, EventRepo, FamilyChildRepo, FamilyRepo, FamilySpouseRepo, KinshipRepo,
+    NewRelative, PersonAncestryRepo, PersonDeleteRepo, PersonFilter, PersonMoveRepo,
+    PersonNameRepo, PersonRepo, PlaceRepo, RelativeRepo, TreeRepo, with_transaction,
 };
 use sea_orm::DatabaseConnection;
 use uuid::Uuid;
 
+use super::access;
+use super::auth::Actor;
 use super::dto::{
-    AncestryQuery, CreatePersonRequest, PaginationQuery, PersonDetailResponse, PersonSearchQuery,
-    UpdatePersonRequest,
+    AddRelativeRequest, AddRelativeResponse, AncestryQuery, BulkDeletePersonsRequest,
+    BulkDeletePersonsResponse, ConflictRecordResponse, CreatePersonRequest, DateConflictQuery,
+    DateConflictResponse, DescendantReportQuery, DuplicateCandidateResponse,
+    DuplicateCandidatesQuery, FanChartQuery, MovePersonResponse, PersonCompareQuery,
+    PersonCompareResponse, PersonDetailQuery, PersonDetailResponse, PersonListItem,
+    PersonListQuery, PersonSearchQuery, QuickPersonRequest, QuickPersonResponse,
+    RelationshipPathResponse, RelationshipStep, TreeSearchQuery, UpdatePersonRequest,
 };
 use super::error::ApiError;
+use super::etag::{self, ETagged};
 use super::state::AppState;
+use crate::render::fan;
+use crate::service::sourcing::sourcing_scores;
+
+/// Default edit-distance cutoff for typo-tolerant person search.
+const DEFAULT_FUZZY_MAX_DISTANCE: usize = 2;
 
 /// BFS from `sosa_root` through the ancestry graph to find the SOSA-Stradonitz
 /// number of `person_id`. Loads all family data for the tree in two queries.
@@ -149,19 +170,83 @@ async fn resolve_sosa_number(
     PersonRepo::get(db, current).await.map(Some)
 }
 
-/// GET /api/v1/trees/:tree_id/persons
+/// Display names of `person_ids` under `policy`, keyed by person; persons
+/// without a name are left out.
+async fn display_names(
+    db: &DatabaseConnection,
+    person_ids: &[Uuid],
+    policy: NameSelectionPolicy,
+) -> Result<HashMap<Uuid, String>, OxidGeneError> {
+    let mut names_by_person: HashMap<Uuid, Vec<PersonName>> = HashMap::new();
+    for name in PersonNameRepo::list_by_persons(db, person_ids).await? {
+        names_by_person
+            .entry(name.person_id)
+            .or_default()
+            .push(name);
+    }
+    Ok(names_by_person
+        .into_iter()
+        .filter_map(|(person_id, names)| {
+            PersonName::select(&names, policy).map(|n| (person_id, n.display_name()))
+        })
+        .collect())
+}
+
+/// Resolve the `display_name` of `persons` under `policy`. The default
+/// policy's name is cached on the person row, so names are only loaded for
+/// the other policies.
+async fn apply_name_policy(
+    db: &DatabaseConnection,
+    persons: &mut [&mut Person],
+    policy: NameSelectionPolicy,
+) -> Result<(), OxidGeneError> {
+    if policy == NameSelectionPolicy::default() {
+        return Ok(());
+    }
+    let person_ids: Vec<Uuid> = persons.iter().map(|p| p.id).collect();
+    let mut names = display_names(db, &person_ids, policy).await?;
+    for person in persons {
+        person.display_name = names.remove(&person.id);
+    }
+    Ok(())
+}
+
+/// GET /api/v1/trees/:tree_id/persons?surname=...&tag=...&no_parents=...&name_policy=...
 pub async fn list_persons(
     State(state): State<AppState>,
     Path(tree_id): Path<Uuid>,
-    Query(query): Query<PaginationQuery>,
+    Query(query): Query<PersonListQuery>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    let params = PaginationParams {
-        first: query.first.unwrap_or(25),
-        after: query.after,
+    let params = state.config.page_params(query.first, query.after);
+    let filter = PersonFilter {
+        surname: query.surname,
+        tag: query.tag,
+        no_parents: query.no_parents,
     };
-    let connection = PersonRepo::list(&state.db, tree_id, &params)
+    let mut connection = PersonRepo::list_filtered(&state.db, tree_id, &filter, &params)
         .await
         .map_err(ApiError::from)?;
+    let mut persons: Vec<&mut Person> = connection.edges.iter_mut().map(|e| &mut e.node).collect();
+    apply_name_policy(&state.db, &mut persons, query.name_policy).await?;
+    let person_ids: Vec<Uuid> = connection.edges.iter().map(|e| e.node.id).collect();
+    let scores = sourcing_scores(&state.db, &person_ids).await?;
+    let lineages = PersonAncestryRepo::lineages(&state.db, &person_ids).await?;
+    let connection = Connection {
+        edges: connection
+            .edges
+            .into_iter()
+            .map(|edge| Edge {
+                cursor: edge.cursor,
+                node: PersonListItem {
+                    sourcing_score: scores.get(&edge.node.id).copied().unwrap_or_default(),
+                    lineage: lineages.get(&edge.node.id).copied().unwrap_or_default(),
+                    person: edge.node,
+                },
+            })
+            .collect(),
+        page_info: connection.page_info,
+        total_count: connection.total_count,
+    };
     Ok(Json(serde_json::to_value(connection).unwrap()))
 }
 
@@ -187,23 +272,203 @@ pub async fn create_person(
     ))
 }
 
-/// GET /api/v1/trees/:tree_id/persons/:person_id
+/// POST /api/v1/trees/:tree_id/persons/quick
+///
+/// Creates a person with their primary name and, when given, their birth
+/// and death, in one transaction. Places are the tree's places of the same
+/// name, created when missing.
+pub async fn quick_create_person(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Json(body): Json<QuickPersonRequest>,
+) -> Result<(StatusCode, Json<QuickPersonResponse>), ApiError> {
+    let filled = |s: Option<String>| s.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    let given_names = filled(body.given_names);
+    let surname = filled(body.surname);
+    if given_names.is_none() && surname.is_none() {
+        return Err(OxidGeneError::Validation("given_names or surname is required".into()).into());
+    }
+    let vitals = [
+        (
+            EventType::Birth,
+            filled(body.birth_date),
+            filled(body.birth_place),
+        ),
+        (
+            EventType::Death,
+            filled(body.death_date),
+            filled(body.death_place),
+        ),
+    ];
+    TreeRepo::get(&state.db, tree_id).await?;
+
+    let (person, name, events) = with_transaction(&state.db, |txn| {
+        Box::pin(async move {
+            let person_id = Uuid::now_v7();
+            let person =
+                PersonRepo::create(txn, person_id, tree_id, body.sex.unwrap_or(Sex::Unknown))
+                    .await?;
+            let name = PersonNameRepo::create(
+                txn,
+                Uuid::now_v7(),
+                person_id,
+                NameType::Birth,
+                given_names,
+                surname,
+                None,
+                None,
+                None,
+                true,
+            )
+            .await?;
+            let mut events = Vec::new();
+            for (event_type, date, place) in vitals {
+                if date.is_none() && place.is_none() {
+                    continue;
+                }
+                let place_id = match place {
+                    Some(place) => Some(PlaceRepo::find_or_create(txn, tree_id, &place).await?.id),
+                    None => None,
+                };
+                let event = EventRepo::create(
+                    txn,
+                    Uuid::now_v7(),
+                    tree_id,
+                    event_type,
+                    date,
+                    None,
+                    place_id,
+                    None,
+                    Some(person_id),
+                    None,
+                    None,
+                )
+                .await?;
+                events.push(event);
+            }
+            Ok((person, name, events))
+        })
+    })
+    .await?;
+    // The name set the cached display name after the person was read.
+    let person = PersonRepo::get(&state.db, person.id).await?;
+    state
+        .cache
+        .rebuild_person(tree_id, person.id)
+        .await
+        .map_err(ApiError)?;
+    let summary = PersonSummary::new(
+        person.living,
+        std::slice::from_ref(&name),
+        &events,
+        Utc::now().year(),
+    );
+    Ok((
+        StatusCode::CREATED,
+        Json(QuickPersonResponse {
+            person,
+            name,
+            events,
+            summary,
+        }),
+    ))
+}
+
+/// POST /api/v1/trees/:tree_id/persons/:person_id/relatives
+///
+/// Creates the relative, finds or creates the family linking them, links
+/// both, and rebuilds the ancestry closure, in one transaction.
+pub async fn add_relative(
+    State(state): State<AppState>,
+    Path((tree_id, person_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<AddRelativeRequest>,
+) -> Result<(StatusCode, Json<AddRelativeResponse>), ApiError> {
+    let added = RelativeRepo::add(
+        &state.db,
+        tree_id,
+        person_id,
+        body.kind,
+        NewRelative {
+            sex: body.sex,
+            given_names: body.given_names,
+            surname: body.surname,
+        },
+    )
+    .await?;
+    // Every member of the linking family, the new person among them.
+    let affected = invalidation::affected_persons_for_family_spouse_change(
+        &state.db,
+        added.family_id,
+        added.person.id,
+    )
+    .await
+    .map_err(ApiError)?;
+    state
+        .cache
+        .invalidate_for_mutation(tree_id, &affected)
+        .await
+        .map_err(ApiError)?;
+    Ok((
+        StatusCode::CREATED,
+        Json(AddRelativeResponse {
+            person: added.person,
+            family_id: added.family_id,
+            family_created: added.family_created,
+        }),
+    ))
+}
+
+/// POST /api/v1/trees/:tree_id/persons/:person_id/move/:target_tree_id
+///
+/// Moves the person, with their names, events, notes and citations, to
+/// another tree in one transaction. Links that cannot follow — families,
+/// event participants, media — are dropped and reported as warnings. The
+/// actor must be an editor of both trees.
+pub async fn move_person(
+    State(state): State<AppState>,
+    Path((tree_id, person_id, target_tree_id)): Path<(Uuid, Uuid, Uuid)>,
+    Extension(actor): Extension<Actor>,
+) -> Result<Json<MovePersonResponse>, ApiError> {
+    access::require_tree_role(&state, &actor, target_tree_id, TreeRole::Editor).await?;
+    // The relatives left behind must be known before the links go.
+    let affected = invalidation::affected_persons(&state.db, person_id)
+        .await
+        .map_err(ApiError)?;
+    let moved = PersonMoveRepo::move_person(&state.db, tree_id, person_id, target_tree_id).await?;
+    state
+        .cache
+        .invalidate_for_person_move(tree_id, target_tree_id, person_id, &affected)
+        .await
+        .map_err(ApiError)?;
+    Ok(Json(MovePersonResponse {
+        person: moved.person,
+        warnings: moved.warnings,
+    }))
+}
+
+/// GET /api/v1/trees/:tree_id/persons/:person_id?name_policy=...
 pub async fn get_person(
     State(state): State<AppState>,
     Path((tree_id, person_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<serde_json::Value>, ApiError> {
-    let person = PersonRepo::get(&state.db, person_id)
+    Query(query): Query<PersonDetailQuery>,
+) -> Result<ETagged, ApiError> {
+    let mut person = PersonRepo::get(&state.db, person_id)
         .await
         .map_err(ApiError::from)?;
     let sosa_number = compute_sosa_number(&state.db, tree_id, person_id)
         .await
         .map_err(ApiError::from)?;
-    Ok(Json(
-        serde_json::to_value(PersonDetailResponse {
+    apply_name_policy(&state.db, &mut [&mut person], query.name_policy).await?;
+    let sourcing_score = sourcing_scores(&state.db, &[person_id]).await?[&person_id];
+    let lineage = PersonAncestryRepo::lineages(&state.db, &[person_id]).await?[&person_id];
+    Ok(etag::tagged(
+        person.version,
+        PersonDetailResponse {
             person,
             sosa_number,
-        })
-        .unwrap(),
+            sourcing_score,
+            lineage,
+        },
     ))
 }
 
@@ -211,11 +476,20 @@ pub async fn get_person(
 pub async fn update_person(
     State(state): State<AppState>,
     Path((tree_id, person_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
     Json(body): Json<UpdatePersonRequest>,
-) -> Result<Json<serde_json::Value>, ApiError> {
-    let person = PersonRepo::update(&state.db, person_id, body.sex, body.privacy)
-        .await
-        .map_err(ApiError::from)?;
+) -> Result<ETagged, ApiError> {
+    let expected_version = etag::if_match(&headers)?;
+    let person = PersonRepo::update(
+        &state.db,
+        person_id,
+        body.sex,
+        body.privacy,
+        body.living,
+        expected_version,
+    )
+    .await
+    .map_err(ApiError::from)?;
     let affected = invalidation::affected_persons(&state.db, person_id)
         .await
         .map_err(ApiError)?;
@@ -224,7 +498,7 @@ pub async fn update_person(
         .invalidate_for_mutation(tree_id, &affected)
         .await
         .map_err(ApiError)?;
-    Ok(Json(serde_json::to_value(person).unwrap()))
+    Ok(etag::tagged(person.version, person))
 }
 
 /// DELETE /api/v1/trees/:tree_id/persons/:person_id
@@ -235,6 +509,7 @@ pub async fn delete_person(
     PersonRepo::delete(&state.db, person_id)
         .await
         .map_err(ApiError::from)?;
+    PersonAncestryRepo::refresh(&state.db, tree_id, &[person_id]).await?;
     // Removes the person from cache + search table, rebuilds affected
     // relatives, and drops pedigrees.
     state
@@ -245,16 +520,78 @@ pub async fn delete_person(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// POST /api/v1/trees/:tree_id/persons/:person_id/lock
+///
+/// Locks the person: updates and deletion of the person and their names
+/// answer `423 Locked` until it is unlocked. The lock is not cached, so the
+/// cache is left alone.
+pub async fn lock_person(
+    State(state): State<AppState>,
+    Path((_tree_id, person_id)): Path<(Uuid, Uuid)>,
+) -> Result<ETagged, ApiError> {
+    let person = PersonRepo::set_locked(&state.db, person_id, true)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(etag::tagged(person.version, person))
+}
+
+/// POST /api/v1/trees/:tree_id/persons/:person_id/unlock
+pub async fn unlock_person(
+    State(state): State<AppState>,
+    Path((_tree_id, person_id)): Path<(Uuid, Uuid)>,
+) -> Result<ETagged, ApiError> {
+    let person = PersonRepo::set_locked(&state.db, person_id, false)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(etag::tagged(person.version, person))
+}
+
+/// POST /api/v1/trees/:tree_id/persons/bulk-delete
+///
+/// Deletes the listed persons in one transaction, reporting each id as
+/// deleted or skipped. Locked persons are skipped, and family members too
+/// unless `cascade` is set.
+pub async fn bulk_delete_persons(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Json(body): Json<BulkDeletePersonsRequest>,
+) -> Result<Json<BulkDeletePersonsResponse>, ApiError> {
+    if body.person_ids.is_empty() {
+        return Err(OxidGeneError::Validation("person_ids must not be empty".into()).into());
+    }
+    let result = PersonDeleteRepo::delete_many(&state.db, tree_id, &body.person_ids, body.cascade)
+        .await
+        .map_err(ApiError::from)?;
+    if !result.deleted.is_empty() {
+        // Relatives of many persons change at once: drop the tree's caches
+        // rather than rebuilding person by person.
+        state
+            .cache
+            .invalidate_tree(tree_id)
+            .await
+            .map_err(ApiError)?;
+    }
+    Ok(Json(result.into()))
+}
+
+/// Response header carrying the depth an ancestor or descendant query
+/// actually walked, once `max_depth` is clamped to the configured maximum.
+const MAX_DEPTH_HEADER: HeaderName = HeaderName::from_static("x-max-depth");
+
 /// GET /api/v1/trees/:tree_id/persons/:person_id/ancestors
 pub async fn get_ancestors(
     State(state): State<AppState>,
     Path((_tree_id, person_id)): Path<(Uuid, Uuid)>,
     Query(query): Query<AncestryQuery>,
-) -> Result<Json<serde_json::Value>, ApiError> {
-    let ancestors = PersonAncestryRepo::ancestors(&state.db, person_id, query.max_depth)
+) -> Result<impl IntoResponse, ApiError> {
+    let max_depth = state.config.ancestry_depth(query.max_depth);
+    let ancestors = PersonAncestryRepo::ancestors(&state.db, person_id, max_depth)
         .await
         .map_err(ApiError::from)?;
-    Ok(Json(serde_json::to_value(ancestors).unwrap()))
+    Ok((
+        [(MAX_DEPTH_HEADER, max_depth.to_string())],
+        Json(serde_json::to_value(ancestors).unwrap()),
+    ))
 }
 
 /// GET /api/v1/trees/:tree_id/persons/:person_id/descendants
@@ -262,11 +599,339 @@ pub async fn get_descendants(
     State(state): State<AppState>,
     Path((_tree_id, person_id)): Path<(Uuid, Uuid)>,
     Query(query): Query<AncestryQuery>,
-) -> Result<Json<serde_json::Value>, ApiError> {
-    let descendants = PersonAncestryRepo::descendants(&state.db, person_id, query.max_depth)
+) -> Result<impl IntoResponse, ApiError> {
+    let max_depth = state.config.ancestry_depth(query.max_depth);
+    let descendants = PersonAncestryRepo::descendants(&state.db, person_id, max_depth)
         .await
         .map_err(ApiError::from)?;
-    Ok(Json(serde_json::to_value(descendants).unwrap()))
+    Ok((
+        [(MAX_DEPTH_HEADER, max_depth.to_string())],
+        Json(serde_json::to_value(descendants).unwrap()),
+    ))
+}
+
+/// GET /api/v1/trees/:tree_id/persons/:a/path/:b
+///
+/// How `a` and `b` are related: the chain of persons from `a` up to their
+/// nearest common ancestor and down to `b`, each with its link to the
+/// previous one.
+pub async fn get_relationship_path(
+    State(state): State<AppState>,
+    Path((tree_id, a, b)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<Json<RelationshipPathResponse>, ApiError> {
+    let Some(path) = KinshipRepo::path(&state.db, tree_id, a, b).await? else {
+        return Ok(Json(RelationshipPathResponse {
+            common_ancestor_id: None,
+            generations_up: 0,
+            generations_down: 0,
+            steps: Vec::new(),
+        }));
+    };
+    let person_ids: Vec<Uuid> = path.steps.iter().map(|s| s.person.id).collect();
+    let scores = sourcing_scores(&state.db, &person_ids).await?;
+    let lineages = PersonAncestryRepo::lineages(&state.db, &person_ids).await?;
+    Ok(Json(RelationshipPathResponse {
+        common_ancestor_id: Some(path.common_ancestor_id),
+        generations_up: path.generations_up,
+        generations_down: path.generations_down,
+        steps: path
+            .steps
+            .into_iter()
+            .map(|step| RelationshipStep {
+                person: PersonListItem {
+                    sourcing_score: scores.get(&step.person.id).copied().unwrap_or_default(),
+                    lineage: lineages.get(&step.person.id).copied().unwrap_or_default(),
+                    person: step.person,
+                },
+                edge: step.edge,
+            })
+            .collect(),
+    }))
+}
+
+/// Parent and child links of every family in the tree.
+async fn load_lineage(db: &DatabaseConnection, tree_id: Uuid) -> Result<Lineage, OxidGeneError> {
+    let family_ids: Vec<Uuid> = FamilyRepo::list_all(db, tree_id)
+        .await?
+        .into_iter()
+        .map(|f| f.id)
+        .collect();
+    let spouses = FamilySpouseRepo::list_by_families(db, &family_ids).await?;
+    let children = FamilyChildRepo::list_by_families(db, &family_ids).await?;
+    Ok(Lineage::new(&spouses, &children))
+}
+
+/// GET /api/v1/trees/:tree_id/persons/:person_id/ahnentafel?max_depth=...
+///
+/// The person and their ancestors with Ahnentafel numbers (1 = the person,
+/// 2n = father, 2n+1 = mother), in number order. Unknown ancestors leave
+/// gaps in the numbering.
+pub async fn get_ahnentafel(
+    State(state): State<AppState>,
+    Path((tree_id, person_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<AncestryQuery>,
+) -> Result<Json<Vec<AhnentafelEntry>>, ApiError> {
+    PersonRepo::get(&state.db, person_id).await?;
+    let max_depth = query
+        .max_depth
+        .map_or(numbering::MAX_AHNENTAFEL_DEPTH, |d| {
+            d.min(numbering::MAX_AHNENTAFEL_DEPTH)
+        });
+    let ancestors = PersonAncestryRepo::ancestors(&state.db, person_id, max_depth).await?;
+    let lineage = load_lineage(&state.db, tree_id).await?;
+    Ok(Json(numbering::ahnentafel(person_id, &ancestors, &lineage)))
+}
+
+/// GET /api/v1/trees/:tree_id/persons/:person_id/fan.svg?generations=N&palette=sex|generation|gray
+///
+/// Printable SVG fan chart of the person's ancestors, one ring per
+/// generation, with empty wedges for unknown ancestors.
+pub async fn get_fan_chart(
+    State(state): State<AppState>,
+    Path((tree_id, person_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<FanChartQuery>,
+) -> Result<Response, ApiError> {
+    PersonRepo::get(&state.db, person_id).await?;
+    let generations = query
+        .generations
+        .unwrap_or(4)
+        .clamp(1, fan::MAX_GENERATIONS);
+    let ancestors = PersonAncestryRepo::ancestors(&state.db, person_id, generations as i32).await?;
+    let lineage = load_lineage(&state.db, tree_id).await?;
+    let entries = numbering::ahnentafel(person_id, &ancestors, &lineage);
+    let persons = state.cache.get_all_persons(tree_id).await?;
+    let body = fan::render(&entries, &persons, generations, query.palette);
+    Ok(([(header::CONTENT_TYPE, "image/svg+xml")], body).into_response())
+}
+
+/// GET /api/v1/trees/:tree_id/persons/:person_id/descendant-report?system=daboville|henry&max_depth=...
+///
+/// The person and their descendants with d'Aboville (`1.2.1`) or Henry
+/// (`121`) numbers, each person followed by their own descendants.
+pub async fn get_descendant_report(
+    State(state): State<AppState>,
+    Path((tree_id, person_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<DescendantReportQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    PersonRepo::get(&state.db, person_id).await?;
+    let max_depth = state.config.ancestry_depth(query.max_depth);
+    let descendants = PersonAncestryRepo::descendants(&state.db, person_id, max_depth).await?;
+    let lineage = load_lineage(&state.db, tree_id).await?;
+    Ok((
+        [(MAX_DEPTH_HEADER, max_depth.to_string())],
+        Json(numbering::descendant_numbers(
+            person_id,
+            &descendants,
+            &lineage,
+            query.system,
+        )),
+    ))
+}
+
+/// GET /api/v1/trees/:tree_id/persons/:person_id/date-conflicts?tolerance=...
+///
+/// Vital events (birth, death, marriage) whose records disagree by more
+/// than `tolerance` years — e.g. a baptism in 1820 against census ages
+/// implying 1816. Each contributing record comes with its citations so the
+/// sources can be weighed against each other.
+pub async fn get_date_conflicts(
+    State(state): State<AppState>,
+    Path((_tree_id, person_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<DateConflictQuery>,
+) -> Result<Json<Vec<DateConflictResponse>>, ApiError> {
+    PersonRepo::get(&state.db, person_id).await?;
+    let person_events = EventRepo::list_by_person(&state.db, person_id).await?;
+    let family_ids: Vec<Uuid> = FamilySpouseRepo::list_by_person(&state.db, person_id)
+        .await?
+        .into_iter()
+        .map(|s| s.family_id)
+        .collect();
+    let family_events = EventRepo::list_by_families(&state.db, &family_ids).await?;
+
+    let tolerance = query
+        .tolerance
+        .unwrap_or(chronology::DEFAULT_CONFLICT_TOLERANCE);
+    let conflicts = chronology::date_conflicts(&person_events, &family_events, tolerance);
+
+    let event_ids: Vec<Uuid> = conflicts
+        .iter()
+        .flat_map(|c| c.records.iter().map(|r| r.event_id))
+        .collect();
+    let mut citations: HashMap<Uuid, Vec<_>> = HashMap::new();
+    for citation in CitationRepo::list_by_events(&state.db, &event_ids).await? {
+        if let Some(event_id) = citation.event_id {
+            citations.entry(event_id).or_default().push(citation);
+        }
+    }
+
+    Ok(Json(
+        conflicts
+            .into_iter()
+            .map(|c| DateConflictResponse {
+                event_type: c.event_type,
+                family_id: c.family_id,
+                discrepancy_years: c.discrepancy_years,
+                records: c
+                    .records
+                    .into_iter()
+                    .map(|record| ConflictRecordResponse {
+                        citations: citations.get(&record.event_id).cloned().unwrap_or_default(),
+                        record,
+                    })
+                    .collect(),
+            })
+            .collect(),
+    ))
+}
+
+/// GET /api/v1/trees/:tree_id/persons/compare?a=...&b=...
+///
+/// Field-by-field comparison of two persons of the tree (names, sex, birth
+/// and death, parents, spouses), to decide whether they are duplicates.
+pub async fn compare_persons(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Query(query): Query<PersonCompareQuery>,
+) -> Result<Json<PersonCompareResponse>, ApiError> {
+    if query.a == query.b {
+        return Err(ApiError(OxidGeneError::Validation(
+            "cannot compare a person with itself".to_string(),
+        )));
+    }
+    let a = compare_record(&state.db, tree_id, query.a).await?;
+    let b = compare_record(&state.db, tree_id, query.b).await?;
+
+    let place_ids: Vec<Uuid> = a
+        .events
+        .iter()
+        .chain(&b.events)
+        .filter_map(|e| e.place_id)
+        .collect();
+    let places: HashMap<Uuid, String> = PlaceRepo::get_many(&state.db, &place_ids)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p.name))
+        .collect();
+
+    Ok(Json(PersonCompareResponse {
+        a: query.a,
+        b: query.b,
+        comparison: compare::compare_persons(&a, &b, &places),
+    }))
+}
+
+/// GET /api/v1/trees/:tree_id/persons/duplicates?limit=...
+///
+/// Pairs of persons whose surname and first given name sound alike
+/// (same Soundex codes), as candidates for [`compare_persons`] and a merge.
+pub async fn list_duplicate_candidates(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Query(query): Query<DuplicateCandidatesQuery>,
+) -> Result<Json<Vec<DuplicateCandidateResponse>>, ApiError> {
+    TreeRepo::get(&state.db, tree_id).await?;
+    let limit = state.config.page_limit(query.limit, 50);
+    let pairs = PersonNameRepo::phonetic_duplicates(&state.db, tree_id, limit).await?;
+
+    let mut person_ids: Vec<Uuid> = pairs.iter().flat_map(|&(a, b)| [a, b]).collect();
+    person_ids.sort_unstable();
+    person_ids.dedup();
+    let persons: HashMap<Uuid, _> = PersonRepo::get_many(&state.db, &person_ids)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p))
+        .collect();
+    let scores = sourcing_scores(&state.db, &person_ids).await?;
+    let lineages = PersonAncestryRepo::lineages(&state.db, &person_ids).await?;
+    let item = |id: Uuid| {
+        persons.get(&id).map(|person| PersonListItem {
+            person: person.clone(),
+            sourcing_score: scores.get(&id).copied().unwrap_or_default(),
+            lineage: lineages.get(&id).copied().unwrap_or_default(),
+        })
+    };
+
+    Ok(Json(
+        pairs
+            .into_iter()
+            .filter_map(|(a, b)| {
+                Some(DuplicateCandidateResponse {
+                    a: item(a)?,
+                    b: item(b)?,
+                })
+            })
+            .collect(),
+    ))
+}
+
+/// What [`compare::compare_persons`] needs to know about a person of the
+/// tree: names, events, and the parents and spouses with their names.
+async fn compare_record(
+    db: &DatabaseConnection,
+    tree_id: Uuid,
+    person_id: Uuid,
+) -> Result<PersonRecord, ApiError> {
+    let person = PersonRepo::get(db, person_id).await?;
+    if person.tree_id != tree_id {
+        return Err(ApiError(OxidGeneError::NotFound {
+            entity: "Person",
+            id: person_id,
+        }));
+    }
+
+    let parent_families: Vec<Uuid> = FamilyChildRepo::list_by_person(db, person_id)
+        .await?
+        .into_iter()
+        .map(|c| c.family_id)
+        .collect();
+    let spouse_families: Vec<Uuid> = FamilySpouseRepo::list_by_person(db, person_id)
+        .await?
+        .into_iter()
+        .map(|s| s.family_id)
+        .collect();
+    let mut parent_ids = Vec::new();
+    let mut spouse_ids = Vec::new();
+    for spouse in FamilySpouseRepo::list_by_families(
+        db,
+        &[parent_families.as_slice(), spouse_families.as_slice()].concat(),
+    )
+    .await?
+    {
+        if parent_families.contains(&spouse.family_id) {
+            parent_ids.push(spouse.person_id);
+        }
+        if spouse_families.contains(&spouse.family_id) && spouse.person_id != person_id {
+            spouse_ids.push(spouse.person_id);
+        }
+    }
+
+    let relative_ids: Vec<Uuid> = parent_ids.iter().chain(&spouse_ids).copied().collect();
+    let mut relative_names: HashMap<Uuid, Vec<PersonName>> = HashMap::new();
+    for name in PersonNameRepo::list_by_persons(db, &relative_ids).await? {
+        relative_names.entry(name.person_id).or_default().push(name);
+    }
+    let relatives = |ids: Vec<Uuid>| {
+        let mut seen = HashSet::new();
+        ids.into_iter()
+            .filter(|id| seen.insert(*id))
+            .map(|id| Relative {
+                person_id: id,
+                name: relative_names
+                    .get(&id)
+                    .and_then(|names| PersonName::select(names, NameSelectionPolicy::PreferPrimary))
+                    .map(PersonName::display_name)
+                    .unwrap_or_default(),
+            })
+            .collect()
+    };
+
+    Ok(PersonRecord {
+        sex: person.sex,
+        names: PersonNameRepo::list_by_person(db, person_id).await?,
+        events: EventRepo::list_by_person(db, person_id).await?,
+        parents: relatives(parent_ids),
+        spouses: relatives(spouse_ids),
+    })
 }
 
 /// GET /api/v1/trees/:tree_id/persons/search?q=...&limit=...&offset=...
@@ -276,13 +941,26 @@ pub async fn get_descendants(
 /// virtual table / plain PostgreSQL table). Returns a `SearchResult` with
 /// display-ready entries and a total count. An empty or missing `q` lists
 /// all persons sorted by name (browse mode).
+///
+/// With `?name=...&max_distance=...` the search is typo-tolerant instead:
+/// returns a `FuzzySearchResult` whose entries carry their edit `distance`
+/// to the query, closest first.
 pub async fn search_persons(
     State(state): State<AppState>,
     Path(tree_id): Path<Uuid>,
     Query(query): Query<PersonSearchQuery>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
+    let limit = state.config.page_limit(query.limit.map(|l| l as u64), 25) as usize;
+    if let Some(name) = query.name {
+        let max_distance = query.max_distance.unwrap_or(DEFAULT_FUZZY_MAX_DISTANCE);
+        let results = state
+            .cache
+            .fuzzy_search(tree_id, &name, max_distance, limit)
+            .await
+            .map_err(ApiError)?;
+        return Ok(Json(serde_json::to_value(results).unwrap()));
+    }
     let q = query.q.unwrap_or_default();
-    let limit = query.limit.unwrap_or(25).min(100);
     let offset = query.offset.unwrap_or(0);
     let results = state
         .cache
@@ -292,7 +970,33 @@ pub async fn search_persons(
     Ok(Json(serde_json::to_value(results).unwrap()))
 }
 
-/// GET /api/v1/trees/:tree_id/persons/sosa/:number
+/// GET /api/v1/search?q=...&limit=...&offset=...
+///
+/// The free-text search of [`search_persons`] across all trees: a
+/// `TreeSearchResult` whose entries carry their `tree_id` and `tree_name`.
+pub async fn search_all_trees(
+    State(state): State<AppState>,
+    Extension(actor): Extension<Actor>,
+    Query(query): Query<TreeSearchQuery>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // With authentication enabled, only the trees the actor is a member of,
+    // unless they are an admin.
+    let actor_id = (state.auth.is_enabled() && !actor.is_admin).then_some(actor.id.as_str());
+    let limit = state.config.page_limit(query.limit.map(|l| l as u64), 25) as usize;
+    let results = state
+        .cache
+        .search_all_trees(
+            &query.q.unwrap_or_default(),
+            limit,
+            query.offset.unwrap_or(0),
+            actor_id,
+        )
+        .await
+        .map_err(ApiError)?;
+    Ok(Json(serde_json::to_value(results).unwrap()))
+}
+
+/// GET /api/v1/trees/:tree_id/persons/sosa/:number?name_policy=...
 ///
 /// Resolves a SOSA-Stradonitz number to a person, walking down from the
 /// tree's configured SOSA root. 404 if the tree has no SOSA root configured
@@ -300,18 +1004,24 @@ pub async fn search_persons(
 pub async fn get_person_by_sosa(
     State(state): State<AppState>,
     Path((tree_id, number)): Path<(Uuid, u64)>,
+    Query(query): Query<PersonDetailQuery>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    let person = resolve_sosa_number(&state.db, tree_id, number)
+    let mut person = resolve_sosa_number(&state.db, tree_id, number)
         .await
         .map_err(ApiError::from)?
         .ok_or(ApiError(OxidGeneError::NotFound {
             entity: "Person (by SOSA number)",
             id: tree_id,
         }))?;
+    apply_name_policy(&state.db, &mut [&mut person], query.name_policy).await?;
+    let sourcing_score = sourcing_scores(&state.db, &[person.id]).await?[&person.id];
+    let lineage = PersonAncestryRepo::lineages(&state.db, &[person.id]).await?[&person.id];
     Ok(Json(
         serde_json::to_value(PersonDetailResponse {
             person,
             sosa_number: Some(number),
+            sourcing_score,
+            lineage,
         })
         .unwrap(),
     ))
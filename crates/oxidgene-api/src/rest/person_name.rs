@@ -1,13 +1,18 @@
-//! REST handlers for PersonName CRUD operations.
+//! REST handlers for PersonName CRUD operations, and the tree-wide
+//! capitalization rewrite of names.
 
 use axum::Json;
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use oxidgene_cache::invalidation;
-use oxidgene_db::repo::PersonNameRepo;
+use oxidgene_core::validation;
+use oxidgene_db::repo::{PersonNameRepo, TreeRepo};
 use uuid::Uuid;
 
-use super::dto::{CreatePersonNameRequest, UpdatePersonNameRequest};
+use super::dto::{
+    CreatePersonNameRequest, NormalizeNameCaseRequest, NormalizeNameCaseResponse, ReplaceQuery,
+    UpdatePersonNameRequest,
+};
 use super::error::ApiError;
 use super::state::AppState;
 
@@ -28,6 +33,7 @@ pub async fn create_person_name(
     Path((tree_id, person_id)): Path<(Uuid, Uuid)>,
     Json(body): Json<CreatePersonNameRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    validation::person_name(body.given_names.as_deref(), body.surname.as_deref())?;
     let id = Uuid::now_v7();
     let name = PersonNameRepo::create(
         &state.db,
@@ -64,6 +70,19 @@ pub async fn update_person_name(
     Path((tree_id, person_id, name_id)): Path<(Uuid, Uuid, Uuid)>,
     Json(body): Json<UpdatePersonNameRequest>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
+    if body.given_names.is_some() || body.surname.is_some() {
+        let current = PersonNameRepo::get(&state.db, name_id)
+            .await
+            .map_err(ApiError::from)?;
+        validation::person_name(
+            body.given_names
+                .as_ref()
+                .map_or(current.given_names.as_deref(), |g| g.as_deref()),
+            body.surname
+                .as_ref()
+                .map_or(current.surname.as_deref(), |s| s.as_deref()),
+        )?;
+    }
     let name = PersonNameRepo::update(
         &state.db,
         name_id,
@@ -106,3 +125,44 @@ pub async fn delete_person_name(
         .map_err(ApiError)?;
     Ok(StatusCode::NO_CONTENT)
 }
+
+/// POST /api/v1/trees/:tree_id/persons/names/normalize-case?dry_run=
+///
+/// Rewrite the capitalization of the given names and surnames of every
+/// live person of the tree (see [`oxidgene_core::text::title_case_name`]),
+/// in a single transaction; names of locked persons are skipped. With
+/// `dry_run=true` the changes are only listed.
+pub async fn normalize_name_case(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Query(query): Query<ReplaceQuery>,
+    Json(body): Json<NormalizeNameCaseRequest>,
+) -> Result<Json<NormalizeNameCaseResponse>, ApiError> {
+    TreeRepo::get(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    let outcome = PersonNameRepo::normalize_case(
+        &state.db,
+        tree_id,
+        body.given_names,
+        body.surname,
+        body.preserve_particles,
+        query.dry_run,
+    )
+    .await
+    .map_err(ApiError::from)?;
+
+    if !query.dry_run && !outcome.changes.is_empty() {
+        state
+            .cache
+            .rebuild_tree_full(tree_id)
+            .await
+            .map_err(ApiError)?;
+    }
+    Ok(Json(NormalizeNameCaseResponse {
+        dry_run: query.dry_run,
+        count: outcome.changes.len(),
+        changes: outcome.changes.into_iter().map(Into::into).collect(),
+        skipped_locked: outcome.skipped_locked,
+    }))
+}
@@ -1,14 +1,27 @@
 //! REST handlers for Place CRUD operations.
 
+use std::convert::Infallible;
+
 use axum::Json;
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
-use oxidgene_db::repo::{PaginationParams, PlaceRepo};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use oxidgene_core::OxidGeneError;
+use oxidgene_core::types::Place;
+use oxidgene_core::validation;
+use oxidgene_db::repo::{PlaceRepo, TreeRepo};
+use tokio_stream::wrappers::WatchStream;
+use tokio_stream::{Stream, StreamExt};
 use uuid::Uuid;
 
-use super::dto::{CreatePlaceRequest, PlaceListQuery, UpdatePlaceRequest};
+use super::dto::{
+    CreatePlaceRequest, GeocodeJobResponse, PlaceAutocompleteEntry, PlaceAutocompleteQuery,
+    PlaceDedupeResponse, PlaceListQuery, PlacePruneResponse, UpdatePlaceRequest,
+};
 use super::error::ApiError;
+use super::etag::{self, ETagged};
 use super::state::AppState;
+use crate::service::geocode::{self, GeocodePhase};
 
 /// GET /api/v1/trees/:tree_id/places
 pub async fn list_places(
@@ -16,27 +29,45 @@ pub async fn list_places(
     Path(tree_id): Path<Uuid>,
     Query(query): Query<PlaceListQuery>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    let params = PaginationParams {
-        first: query.first.unwrap_or(25),
-        after: query.after,
-    };
+    let params = state.config.page_params(query.first, query.after);
     let connection = PlaceRepo::list(&state.db, tree_id, query.search.as_deref(), &params)
         .await
         .map_err(ApiError::from)?;
     Ok(Json(serde_json::to_value(connection).unwrap()))
 }
 
+/// GET /api/v1/trees/:tree_id/places/autocomplete?q=...&limit=...
+///
+/// Places whose name starts with `q`, most used (by events) first, for the
+/// event place picker's type-ahead.
+pub async fn autocomplete_places(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Query(query): Query<PlaceAutocompleteQuery>,
+) -> Result<Json<Vec<PlaceAutocompleteEntry>>, ApiError> {
+    let limit = state.config.page_limit(query.limit, 10).min(50);
+    let places = PlaceRepo::autocomplete(&state.db, tree_id, query.q.trim(), limit)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(
+        places
+            .into_iter()
+            .map(|p| PlaceAutocompleteEntry {
+                place: p.place,
+                event_count: p.event_count,
+            })
+            .collect(),
+    ))
+}
+
 /// POST /api/v1/trees/:tree_id/places
 pub async fn create_place(
     State(state): State<AppState>,
     Path(tree_id): Path<Uuid>,
     Json(body): Json<CreatePlaceRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
-    if body.name.trim().is_empty() {
-        return Err(ApiError(oxidgene_core::OxidGeneError::Validation(
-            "name must not be empty".to_string(),
-        )));
-    }
+    validation::not_blank("name", &body.name)?;
+    validation::coordinates(body.latitude, body.longitude)?;
     let id = Uuid::now_v7();
     let place = PlaceRepo::create(
         &state.db,
@@ -58,29 +89,36 @@ pub async fn create_place(
 pub async fn get_place(
     State(state): State<AppState>,
     Path((_tree_id, place_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<serde_json::Value>, ApiError> {
+) -> Result<ETagged, ApiError> {
     let place = PlaceRepo::get(&state.db, place_id)
         .await
         .map_err(ApiError::from)?;
-    Ok(Json(serde_json::to_value(place).unwrap()))
+    Ok(etag::tagged(place.version, place))
 }
 
 /// PUT /api/v1/trees/:tree_id/places/:place_id
 pub async fn update_place(
     State(state): State<AppState>,
     Path((_tree_id, place_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
     Json(body): Json<UpdatePlaceRequest>,
-) -> Result<Json<serde_json::Value>, ApiError> {
+) -> Result<ETagged, ApiError> {
+    let expected_version = etag::if_match(&headers)?;
+    if let Some(name) = &body.name {
+        validation::not_blank("name", name)?;
+    }
+    validation::coordinates(body.latitude.flatten(), body.longitude.flatten())?;
     let place = PlaceRepo::update(
         &state.db,
         place_id,
         body.name,
         body.latitude,
         body.longitude,
+        expected_version,
     )
     .await
     .map_err(ApiError::from)?;
-    Ok(Json(serde_json::to_value(place).unwrap()))
+    Ok(etag::tagged(place.version, place))
 }
 
 /// DELETE /api/v1/trees/:tree_id/places/:place_id
@@ -93,3 +131,164 @@ pub async fn delete_place(
         .map_err(ApiError::from)?;
     Ok(StatusCode::NO_CONTENT)
 }
+
+/// POST /api/v1/trees/:tree_id/places/dedupe
+///
+/// Merge places sharing the same normalized name and near-identical
+/// coordinates, repointing events and media to the surviving place. Cached
+/// persons embed place IDs, so the tree cache is rebuilt when anything merged.
+pub async fn dedupe_places(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+) -> Result<Json<PlaceDedupeResponse>, ApiError> {
+    let merged = PlaceRepo::dedupe(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    if merged > 0 {
+        state
+            .cache
+            .rebuild_tree_full(tree_id)
+            .await
+            .map_err(ApiError)?;
+    }
+    Ok(Json(PlaceDedupeResponse { merged }))
+}
+
+/// GET /api/v1/trees/:tree_id/places/unused
+///
+/// Places no event (live or trashed) or media points at, by name.
+pub async fn list_unused_places(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+) -> Result<Json<Vec<Place>>, ApiError> {
+    let places = PlaceRepo::list_unused(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(places))
+}
+
+/// POST /api/v1/trees/:tree_id/places/prune
+///
+/// Delete the places listed by `GET .../places/unused`. Nothing cached
+/// refers to them, so the tree cache is left alone.
+pub async fn prune_places(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+) -> Result<Json<PlacePruneResponse>, ApiError> {
+    let pruned = PlaceRepo::prune_unused(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(PlacePruneResponse { pruned }))
+}
+
+/// POST /api/v1/trees/:tree_id/places/geocode-all
+///
+/// Start geocoding every place of the tree that lacks coordinates. The batch
+/// runs in the background at the provider's rate limit; the response carries
+/// a `job_id` whose progress can be followed at
+/// `GET /api/v1/trees/:tree_id/places/geocode-all/:job_id/events`.
+pub async fn geocode_all_places(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<GeocodeJobResponse>), ApiError> {
+    TreeRepo::get(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    if !state.geocoding.is_enabled() {
+        return Err(ApiError(OxidGeneError::Validation(
+            "geocoding is disabled: no provider configured".to_string(),
+        )));
+    }
+    let (job_id, tracker) =
+        state
+            .geocode_jobs
+            .start_exclusive(tree_id)
+            .ok_or(ApiError(OxidGeneError::Conflict(
+                "a geocoding job is already running for this tree".to_string(),
+            )))?;
+
+    tokio::spawn(async move {
+        match geocode::geocode_tree(&state.db, &state.geocoding, tree_id, &tracker).await {
+            Ok(summary) => tracker.finish(summary),
+            Err(e) => {
+                tracing::warn!(%tree_id, %job_id, error = %e, "batch geocoding failed");
+                tracker.fail(e.to_string());
+            }
+        }
+    });
+
+    Ok((StatusCode::ACCEPTED, Json(GeocodeJobResponse { job_id })))
+}
+
+/// GET /api/v1/trees/:tree_id/places/geocode-all/:job_id/events
+///
+/// Server-Sent Events stream of a geocoding job. Emits `progress` events
+/// while the job runs, then a single `done` event (carrying the per-place
+/// summary) or `failed` event (carrying `{ "error": ... }`).
+pub async fn geocode_events_handler(
+    State(state): State<AppState>,
+    Path((tree_id, job_id)): Path<(Uuid, Uuid)>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let rx = state
+        .geocode_jobs
+        .subscribe(tree_id, job_id)
+        .ok_or(OxidGeneError::NotFound {
+            entity: "GeocodeJob",
+            id: job_id,
+        })
+        .map_err(ApiError::from)?;
+
+    let events = WatchStream::new(rx).map(|progress| {
+        let event = match progress.phase {
+            GeocodePhase::Done => Event::default().event("done").json_data(&progress.summary),
+            GeocodePhase::Failed => Event::default()
+                .event("failed")
+                .json_data(serde_json::json!({ "error": progress.error })),
+            _ => Event::default().event("progress").json_data(&progress),
+        };
+        Ok(event.expect("geocode progress serializes to JSON"))
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// POST /api/v1/trees/:tree_id/places/:place_id/reverse-geocode
+///
+/// Replace the place's name with the normalized hierarchical name
+/// (`Locality, County, State, Country`) found at its coordinates.
+pub async fn reverse_geocode_place(
+    State(state): State<AppState>,
+    Path((_tree_id, place_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+) -> Result<ETagged, ApiError> {
+    let expected_version = etag::if_match(&headers)?;
+    let place = PlaceRepo::get(&state.db, place_id)
+        .await
+        .map_err(ApiError::from)?;
+    let (Some(latitude), Some(longitude)) = (place.latitude, place.longitude) else {
+        return Err(ApiError(OxidGeneError::Validation(
+            "place has no coordinates".to_string(),
+        )));
+    };
+    let name = state
+        .geocoding
+        .reverse(latitude, longitude)
+        .await
+        .map_err(ApiError::from)?
+        .map(|address| address.hierarchical_name())
+        .filter(|name| !name.is_empty())
+        .ok_or(ApiError(OxidGeneError::Validation(
+            "no address found at these coordinates".to_string(),
+        )))?;
+    let place = PlaceRepo::update(
+        &state.db,
+        place_id,
+        Some(name),
+        None,
+        None,
+        expected_version,
+    )
+    .await
+    .map_err(ApiError::from)?;
+    Ok(etag::tagged(place.version, place))
+}
@@ -0,0 +1,233 @@
+//! Read-only share links.
+//!
+//! Owners manage links under `/api/v1/trees/{tree_id}/share`. Anyone holding
+//! a link's token can browse the tree under `/api/v1/shared/{token}`:
+//! [`resolve_share_token`] turns the token into a [`SharedTree`] request
+//! extension, only `GET` gets through, and every view is privacy-filtered.
+//! These views bypass authentication; links record the [`Actor`] who
+//! created them, and only the tree's owners may manage them.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::Json;
+use axum::extract::{Extension, Path, Request, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Datelike, Utc};
+use oxidgene_core::OxidGeneError;
+use oxidgene_core::privacy::{is_hidden, is_living};
+use oxidgene_core::types::{Event, PersonName};
+use oxidgene_db::repo::{ShareLinkRepo, TreeRepo};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::access::{Owner, RequireTreeRole};
+use super::auth::Actor;
+use super::dto::{CreateShareLinkRequest, SharedTreeResponse};
+use super::error::ApiError;
+use super::snapshot::{TreeSnapshotResponse, load_snapshot};
+use super::state::AppState;
+
+/// The tree a share token resolved to, attached by [`resolve_share_token`].
+#[derive(Debug, Clone, Copy)]
+pub struct SharedTree {
+    pub tree_id: Uuid,
+    pub privatize: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+pub struct TokenPath {
+    token: Uuid,
+}
+
+// ── Owner endpoints ─────────────────────────────────────────────────
+
+/// GET /api/v1/trees/:tree_id/share
+pub async fn list_share_links(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    _owner: RequireTreeRole<Owner>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let links = ShareLinkRepo::list_by_tree(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(serde_json::to_value(links).unwrap()))
+}
+
+/// POST /api/v1/trees/:tree_id/share
+pub async fn create_share_link(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Extension(actor): Extension<Actor>,
+    _owner: RequireTreeRole<Owner>,
+    Json(body): Json<CreateShareLinkRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    if body.expires_at.is_some_and(|at| at <= Utc::now()) {
+        return Err(ApiError(OxidGeneError::Validation(
+            "expires_at must be in the future".to_string(),
+        )));
+    }
+    // 404 for an unknown or deleted tree rather than a dangling link.
+    TreeRepo::get(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    let link = ShareLinkRepo::create(
+        &state.db,
+        Uuid::now_v7(),
+        tree_id,
+        Uuid::new_v4(),
+        body.privatize,
+        body.expires_at,
+        Some(actor.id),
+    )
+    .await
+    .map_err(ApiError::from)?;
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::to_value(link).unwrap()),
+    ))
+}
+
+/// DELETE /api/v1/trees/:tree_id/share/:share_id
+pub async fn revoke_share_link(
+    State(state): State<AppState>,
+    Path((tree_id, share_id)): Path<(Uuid, Uuid)>,
+    _owner: RequireTreeRole<Owner>,
+) -> Result<StatusCode, ApiError> {
+    ShareLinkRepo::delete(&state.db, tree_id, share_id)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ── Shared (read-only) views ────────────────────────────────────────
+
+/// Middleware for `/api/v1/shared/{token}/...`: rejects anything but reads
+/// and resolves the token (unknown or expired → 404) to a [`SharedTree`].
+pub async fn resolve_share_token(
+    State(state): State<AppState>,
+    Path(TokenPath { token }): Path<TokenPath>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        return StatusCode::METHOD_NOT_ALLOWED.into_response();
+    }
+    match ShareLinkRepo::resolve(&state.db, token).await {
+        Ok(link) => {
+            request.extensions_mut().insert(SharedTree {
+                tree_id: link.tree_id,
+                privatize: link.privatize,
+                expires_at: link.expires_at,
+            });
+            next.run(request).await
+        }
+        Err(e) => ApiError(e).into_response(),
+    }
+}
+
+/// GET /api/v1/shared/:token
+pub async fn get_shared_tree(
+    State(state): State<AppState>,
+    Extension(shared): Extension<SharedTree>,
+) -> Result<Json<SharedTreeResponse>, ApiError> {
+    let tree = TreeRepo::get(&state.db, shared.tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(SharedTreeResponse {
+        name: tree.name,
+        description: tree.description,
+        sosa_root_person_id: tree.sosa_root_person_id,
+        privatize: shared.privatize,
+        expires_at: shared.expires_at,
+    }))
+}
+
+/// GET /api/v1/shared/:token/snapshot
+pub async fn shared_snapshot(
+    State(state): State<AppState>,
+    Extension(shared): Extension<SharedTree>,
+) -> Result<Json<TreeSnapshotResponse>, ApiError> {
+    // The tree itself may have been deleted since the link was made.
+    TreeRepo::get(&state.db, shared.tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    let snapshot = load_snapshot(&state.db, shared.tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(redact(snapshot, shared.privatize, Utc::now().year())))
+}
+
+/// Strip hidden persons down to their place in the structure: no given
+/// names (their display name keeps the surname only), no events of their
+/// own, no events of families they belong to, and no places only those
+/// events referenced.
+fn redact(
+    mut snapshot: TreeSnapshotResponse,
+    privatize: bool,
+    current_year: i32,
+) -> TreeSnapshotResponse {
+    let mut events_by_person: HashMap<Uuid, Vec<&Event>> = HashMap::new();
+    for event in &snapshot.events {
+        if let Some(pid) = event.person_id {
+            events_by_person.entry(pid).or_default().push(event);
+        }
+    }
+    let hidden: HashSet<Uuid> = snapshot
+        .persons
+        .iter()
+        .filter(|p| {
+            let events = events_by_person.get(&p.id).map(Vec::as_slice);
+            let living = is_living(
+                p.living,
+                events.unwrap_or_default().iter().copied(),
+                current_year,
+            );
+            is_hidden(p.privacy, living, privatize)
+        })
+        .map(|p| p.id)
+        .collect();
+    let hidden_families: HashSet<Uuid> = snapshot
+        .spouses
+        .iter()
+        .filter(|s| hidden.contains(&s.person_id))
+        .map(|s| s.family_id)
+        .collect();
+
+    for name in &mut snapshot.names {
+        if hidden.contains(&name.person_id) {
+            name.given_names = None;
+            name.prefix = None;
+            name.suffix = None;
+            name.nickname = None;
+        }
+    }
+    for person in &mut snapshot.persons {
+        if hidden.contains(&person.id) {
+            let names: Vec<PersonName> = snapshot
+                .names
+                .iter()
+                .filter(|n| n.person_id == person.id)
+                .cloned()
+                .collect();
+            person.display_name =
+                PersonName::display_name_of(&names).filter(|name| !name.is_empty());
+        }
+    }
+    snapshot.events.retain(|e| {
+        !e.person_id.is_some_and(|pid| hidden.contains(&pid))
+            && !e
+                .family_id
+                .is_some_and(|fid| hidden_families.contains(&fid))
+    });
+    let used_places: HashSet<Uuid> = snapshot
+        .events
+        .iter()
+        .flat_map(|e| [e.place_id, e.place_id_to])
+        .flatten()
+        .collect();
+    snapshot.places.retain(|p| used_places.contains(&p.id));
+    snapshot
+}
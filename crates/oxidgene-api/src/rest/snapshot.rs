@@ -5,10 +5,12 @@
 
 use axum::Json;
 use axum::extract::{Path, State};
+use oxidgene_core::OxidGeneError;
 use oxidgene_core::types::{Event, FamilyChild, FamilySpouse, Person, PersonName, Place};
 use oxidgene_db::repo::{
     EventRepo, FamilyChildRepo, FamilyRepo, FamilySpouseRepo, PersonNameRepo, PersonRepo, PlaceRepo,
 };
+use sea_orm::DatabaseConnection;
 use serde::Serialize;
 use uuid::Uuid;
 
@@ -31,32 +33,41 @@ pub async fn tree_snapshot(
     State(state): State<AppState>,
     Path(tree_id): Path<Uuid>,
 ) -> Result<Json<TreeSnapshotResponse>, ApiError> {
+    let snapshot = load_snapshot(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(snapshot))
+}
+
+/// Load a tree's full snapshot; shared with the read-only share views.
+pub(crate) async fn load_snapshot(
+    db: &DatabaseConnection,
+    tree_id: Uuid,
+) -> Result<TreeSnapshotResponse, OxidGeneError> {
     // Fetch persons and families first (needed for bulk lookups).
     let (persons, families) = tokio::try_join!(
-        PersonRepo::list_all(&state.db, tree_id),
-        FamilyRepo::list_all(&state.db, tree_id),
-    )
-    .map_err(ApiError::from)?;
+        PersonRepo::list_all(db, tree_id),
+        FamilyRepo::list_all(db, tree_id),
+    )?;
 
     let person_ids: Vec<Uuid> = persons.iter().map(|p| p.id).collect();
     let family_ids: Vec<Uuid> = families.iter().map(|f| f.id).collect();
 
     // Fetch names, events, places, spouses, and children in parallel.
     let (names, events, places, spouses, children) = tokio::try_join!(
-        PersonNameRepo::list_by_persons(&state.db, &person_ids),
-        EventRepo::list_all(&state.db, tree_id),
-        PlaceRepo::list_all(&state.db, tree_id),
-        FamilySpouseRepo::list_by_families(&state.db, &family_ids),
-        FamilyChildRepo::list_by_families(&state.db, &family_ids),
-    )
-    .map_err(ApiError::from)?;
-
-    Ok(Json(TreeSnapshotResponse {
+        PersonNameRepo::list_by_persons(db, &person_ids),
+        EventRepo::list_all(db, tree_id),
+        PlaceRepo::list_all(db, tree_id),
+        FamilySpouseRepo::list_by_families(db, &family_ids),
+        FamilyChildRepo::list_by_families(db, &family_ids),
+    )?;
+
+    Ok(TreeSnapshotResponse {
         persons,
         names,
         events,
         places,
         spouses,
         children,
-    }))
+    })
 }
@@ -2,12 +2,19 @@
 
 use axum::Json;
 use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
-use oxidgene_db::repo::{PaginationParams, SourceRepo};
+use axum::http::{HeaderMap, StatusCode};
+use oxidgene_core::OxidGeneError;
+use oxidgene_core::validation;
+use oxidgene_db::repo::{MediaLinkRepo, MediaRepo, SourceRepo, SourceUsageRepo};
 use uuid::Uuid;
 
-use super::dto::{CreateSourceRequest, PaginationQuery, UpdateSourceRequest};
+use super::dto::{
+    CreateSourceRequest, LinkSourceMediaRequest, MergeSourcesRequest, MergeSourcesResponse,
+    PaginationQuery, SourceDetailResponse, SourceDuplicatesResponse, SourceMediaEntry,
+    SourceUsageEntryResponse, UpdateSourceRequest,
+};
 use super::error::ApiError;
+use super::etag::{self, ETagged};
 use super::state::AppState;
 
 /// GET /api/v1/trees/:tree_id/sources
@@ -16,10 +23,7 @@ pub async fn list_sources(
     Path(tree_id): Path<Uuid>,
     Query(query): Query<PaginationQuery>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    let params = PaginationParams {
-        first: query.first.unwrap_or(25),
-        after: query.after,
-    };
+    let params = state.config.page_params(query.first, query.after);
     let connection = SourceRepo::list(&state.db, tree_id, &params)
         .await
         .map_err(ApiError::from)?;
@@ -32,11 +36,7 @@ pub async fn create_source(
     Path(tree_id): Path<Uuid>,
     Json(body): Json<CreateSourceRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
-    if body.title.trim().is_empty() {
-        return Err(ApiError(oxidgene_core::OxidGeneError::Validation(
-            "title must not be empty".to_string(),
-        )));
-    }
+    validation::not_blank("title", &body.title)?;
     let id = Uuid::now_v7();
     let source = SourceRepo::create(
         &state.db,
@@ -56,23 +56,153 @@ pub async fn create_source(
     ))
 }
 
+/// GET /api/v1/trees/:tree_id/sources/duplicates
+///
+/// Groups of sources that look like the same source entered twice: equal
+/// title and author once case, accents and punctuation are ignored.
+pub async fn source_duplicates(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+) -> Result<Json<SourceDuplicatesResponse>, ApiError> {
+    let groups = SourceRepo::duplicates(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(SourceDuplicatesResponse {
+        groups: groups
+            .into_iter()
+            .map(|group| group.into_iter().map(Into::into).collect())
+            .collect(),
+    }))
+}
+
+/// POST /api/v1/trees/:tree_id/sources/merge
+///
+/// Merge duplicate sources into a survivor: their citations, media and
+/// notes move to it, then the duplicates are deleted.
+pub async fn merge_sources(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Json(body): Json<MergeSourcesRequest>,
+) -> Result<Json<MergeSourcesResponse>, ApiError> {
+    let merge = SourceRepo::merge(&state.db, tree_id, body.survivor_id, &body.duplicate_ids)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(merge.into()))
+}
+
 /// GET /api/v1/trees/:tree_id/sources/:source_id
 pub async fn get_source(
     State(state): State<AppState>,
     Path((_tree_id, source_id)): Path<(Uuid, Uuid)>,
-) -> Result<Json<serde_json::Value>, ApiError> {
+) -> Result<ETagged, ApiError> {
     let source = SourceRepo::get(&state.db, source_id)
         .await
         .map_err(ApiError::from)?;
-    Ok(Json(serde_json::to_value(source).unwrap()))
+    let links = MediaLinkRepo::list_by_source(&state.db, source_id)
+        .await
+        .map_err(ApiError::from)?;
+    let media_ids: Vec<Uuid> = links.iter().map(|l| l.media_id).collect();
+    let mut media: std::collections::HashMap<Uuid, _> = MediaRepo::get_many(&state.db, &media_ids)
+        .await
+        .map_err(ApiError::from)?
+        .into_iter()
+        .map(|m| (m.id, m))
+        .collect();
+    // Links to deleted media are left out.
+    let media = links
+        .into_iter()
+        .filter_map(|link| {
+            media.remove(&link.media_id).map(|media| SourceMediaEntry {
+                link_id: link.id,
+                sort_order: link.sort_order,
+                media,
+            })
+        })
+        .collect();
+    Ok(etag::tagged(
+        source.version,
+        SourceDetailResponse { source, media },
+    ))
+}
+
+/// GET /api/v1/trees/:tree_id/sources/:source_id/usage
+///
+/// Every citation of the source with the person, event or family it
+/// supports — the inverse of an owner's citation list.
+pub async fn source_usage(
+    State(state): State<AppState>,
+    Path((tree_id, source_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Vec<SourceUsageEntryResponse>>, ApiError> {
+    let source = SourceRepo::get(&state.db, source_id)
+        .await
+        .map_err(ApiError::from)?;
+    if source.tree_id != tree_id {
+        return Err(ApiError(OxidGeneError::NotFound {
+            entity: "Source",
+            id: source_id,
+        }));
+    }
+    let entries = SourceUsageRepo::list(&state.db, source_id)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(entries.into_iter().map(Into::into).collect()))
+}
+
+/// POST /api/v1/trees/:tree_id/sources/:source_id/media
+///
+/// Attach an existing media object (e.g. a scan of the record) to a source.
+pub async fn link_source_media(
+    State(state): State<AppState>,
+    Path((tree_id, source_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<LinkSourceMediaRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    let source = SourceRepo::get(&state.db, source_id)
+        .await
+        .map_err(ApiError::from)?;
+    if source.tree_id != tree_id {
+        return Err(ApiError(OxidGeneError::NotFound {
+            entity: "Source",
+            id: source_id,
+        }));
+    }
+    let media = MediaRepo::get(&state.db, body.media_id)
+        .await
+        .map_err(ApiError::from)?;
+    if media.tree_id != tree_id {
+        return Err(ApiError(OxidGeneError::NotFound {
+            entity: "Media",
+            id: body.media_id,
+        }));
+    }
+    let link = MediaLinkRepo::create(
+        &state.db,
+        Uuid::now_v7(),
+        media.id,
+        None,
+        None,
+        Some(source_id),
+        None,
+        body.sort_order,
+    )
+    .await
+    .map_err(ApiError::from)?;
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::to_value(link).unwrap()),
+    ))
 }
 
 /// PUT /api/v1/trees/:tree_id/sources/:source_id
 pub async fn update_source(
     State(state): State<AppState>,
     Path((_tree_id, source_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
     Json(body): Json<UpdateSourceRequest>,
-) -> Result<Json<serde_json::Value>, ApiError> {
+) -> Result<ETagged, ApiError> {
+    let expected_version = etag::if_match(&headers)?;
+    if let Some(title) = &body.title {
+        validation::not_blank("title", title)?;
+    }
     let source = SourceRepo::update(
         &state.db,
         source_id,
@@ -81,10 +211,11 @@ pub async fn update_source(
         body.publisher,
         body.abbreviation,
         body.repository_name,
+        expected_version,
     )
     .await
     .map_err(ApiError::from)?;
-    Ok(Json(serde_json::to_value(source).unwrap()))
+    Ok(etag::tagged(source.version, source))
 }
 
 /// DELETE /api/v1/trees/:tree_id/sources/:source_id
@@ -6,6 +6,14 @@ use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 use tracing::info;
 
+use crate::config::ApiConfig;
+use crate::rest::auth::Authenticator;
+use crate::service::backup::Backups;
+use crate::service::export_job::ExportJobs;
+use crate::service::geocode::{GeocodeJobs, Geocoding, NominatimGeocoder};
+use crate::service::import_job::ImportJobs;
+use crate::service::import_preview::ImportPreviews;
+
 /// Default pedigree LRU budget in bytes (64 MB).
 const DEFAULT_PEDIGREE_BUDGET_BYTES: usize = 64 * 1024 * 1024;
 
@@ -14,6 +22,22 @@ const DEFAULT_PEDIGREE_BUDGET_BYTES: usize = 64 * 1024 * 1024;
 pub struct AppState {
     pub db: DatabaseConnection,
     pub cache: Arc<CacheService>,
+    /// Background GEDCOM import jobs, followed over SSE.
+    pub imports: Arc<ImportJobs>,
+    /// Previewed GEDCOM imports awaiting confirmation.
+    pub import_previews: Arc<ImportPreviews>,
+    /// Background GEDCOM export jobs, followed over SSE, with their files.
+    pub exports: Arc<ExportJobs>,
+    /// Geocoding provider with its shared cache and rate limit.
+    pub geocoding: Arc<Geocoding>,
+    /// Background batch geocoding jobs, followed over SSE.
+    pub geocode_jobs: Arc<GeocodeJobs>,
+    /// Request limits (GraphQL depth/complexity, ...).
+    pub config: Arc<ApiConfig>,
+    /// Bearer-token verification, per `config.auth`.
+    pub auth: Arc<Authenticator>,
+    /// SQLite backups; `None` disables the backup endpoints.
+    pub backups: Option<Arc<Backups>>,
 }
 
 impl AppState {
@@ -58,7 +82,7 @@ impl AppState {
         };
 
         let cache = Arc::new(CacheService::new(store, db.clone()));
-        Self { db, cache }
+        Self::with_cache(db, cache)
     }
 
     /// Create a new `AppState` with a pre-built `MemoryCacheStore`.
@@ -66,13 +90,64 @@ impl AppState {
     /// Used by the desktop binary to load the cache from disk before starting.
     pub fn with_memory_store(db: DatabaseConnection, store: MemoryCacheStore) -> Self {
         let cache = Arc::new(CacheService::new(Arc::new(store), db.clone()));
-        Self { db, cache }
+        Self::with_cache(db, cache)
     }
 
     /// Create a new `AppState` with an explicit cache service (for testing or
     /// alternative backends).
     pub fn with_cache(db: DatabaseConnection, cache: Arc<CacheService>) -> Self {
-        Self { db, cache }
+        let config = ApiConfig::default();
+        Self {
+            db,
+            cache,
+            imports: Arc::new(ImportJobs::new()),
+            import_previews: Arc::new(ImportPreviews::new()),
+            exports: Arc::new(ExportJobs::new()),
+            geocoding: Arc::new(geocoding_from_config(&config)),
+            geocode_jobs: Arc::new(GeocodeJobs::new()),
+            auth: Arc::new(Authenticator::new(config.auth.clone())),
+            config: Arc::new(config),
+            backups: None,
+        }
+    }
+
+    /// Replace the default API limits, geocoding provider and
+    /// authentication settings.
+    pub fn with_config(mut self, config: ApiConfig) -> Self {
+        self.geocoding = Arc::new(geocoding_from_config(&config));
+        self.auth = Arc::new(Authenticator::new(config.auth.clone()));
+        self.config = Arc::new(config);
+        self
+    }
+
+    /// Enable the backup endpoints (desktop app).
+    pub fn with_backups(mut self, backups: Arc<Backups>) -> Self {
+        self.backups = Some(backups);
+        self
+    }
+
+    /// Replace the geocoding provider (tests, alternative providers).
+    pub fn with_geocoding(mut self, geocoding: Geocoding) -> Self {
+        self.geocoding = Arc::new(geocoding);
+        self
+    }
+}
+
+/// Build the geocoding service described by `config`.
+fn geocoding_from_config(config: &ApiConfig) -> Geocoding {
+    let Some(url) = config.geocoder_url.as_deref().filter(|u| !u.is_empty()) else {
+        info!("Geocoding disabled");
+        return Geocoding::disabled();
+    };
+    match NominatimGeocoder::new(url) {
+        Ok(provider) => Geocoding::new(
+            Some(Arc::new(provider)),
+            std::time::Duration::from_millis(config.geocoder_min_interval_ms),
+        ),
+        Err(e) => {
+            tracing::warn!("Geocoder setup failed ({e}), geocoding disabled");
+            Geocoding::disabled()
+        }
     }
 }
 
@@ -0,0 +1,77 @@
+//! REST handlers for person research tags ("needs sources", "brick wall").
+//!
+//! Tags are tree-scoped labels for filtering persons; prose belongs in
+//! notes. Persons carrying a tag are listed by `GET /persons?tag=...`.
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::types::{MAX_TAG_LEN, PersonTag, normalize_tag};
+use oxidgene_db::repo::{PersonRepo, PersonTagRepo};
+use uuid::Uuid;
+
+use super::dto::{AddPersonTagRequest, TagCountDto};
+use super::error::ApiError;
+use super::state::AppState;
+
+/// GET /api/v1/trees/:tree_id/tags
+///
+/// Tags in use in the tree with the number of persons carrying each.
+pub async fn list_tags(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+) -> Result<Json<Vec<TagCountDto>>, ApiError> {
+    let counts = PersonTagRepo::counts(&state.db, tree_id).await?;
+    Ok(Json(counts.into_iter().map(Into::into).collect()))
+}
+
+/// GET /api/v1/trees/:tree_id/person-tags
+///
+/// Every tag of every person in the tree, for rendering tag chips on
+/// person lists without one request per row.
+pub async fn list_tree_person_tags(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+) -> Result<Json<Vec<PersonTag>>, ApiError> {
+    Ok(Json(PersonTagRepo::list_by_tree(&state.db, tree_id).await?))
+}
+
+/// GET /api/v1/trees/:tree_id/persons/:person_id/tags
+pub async fn list_person_tags(
+    State(state): State<AppState>,
+    Path((_tree_id, person_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Vec<PersonTag>>, ApiError> {
+    PersonRepo::get(&state.db, person_id).await?;
+    Ok(Json(
+        PersonTagRepo::list_by_person(&state.db, person_id).await?,
+    ))
+}
+
+/// POST /api/v1/trees/:tree_id/persons/:person_id/tags
+///
+/// Idempotent: tagging a person again with the same tag returns the
+/// existing tag.
+pub async fn add_person_tag(
+    State(state): State<AppState>,
+    Path((_tree_id, person_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<AddPersonTagRequest>,
+) -> Result<(StatusCode, Json<PersonTag>), ApiError> {
+    let tag = normalize_tag(&body.tag).ok_or_else(|| {
+        ApiError(OxidGeneError::Validation(format!(
+            "tag must be 1 to {MAX_TAG_LEN} characters"
+        )))
+    })?;
+    let person = PersonRepo::get(&state.db, person_id).await?;
+    let tag = PersonTagRepo::add(&state.db, Uuid::now_v7(), person.tree_id, person_id, tag).await?;
+    Ok((StatusCode::CREATED, Json(tag)))
+}
+
+/// DELETE /api/v1/trees/:tree_id/persons/:person_id/tags/:tag
+pub async fn remove_person_tag(
+    State(state): State<AppState>,
+    Path((_tree_id, person_id, tag)): Path<(Uuid, Uuid, String)>,
+) -> Result<StatusCode, ApiError> {
+    PersonTagRepo::remove(&state.db, person_id, &tag).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,104 @@
+//! REST handlers for research task CRUD operations.
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use oxidgene_core::types::ResearchTask;
+use oxidgene_core::validation;
+use oxidgene_db::repo::{ResearchTaskFilter, ResearchTaskRepo, TreeEntity};
+use uuid::Uuid;
+
+use super::access;
+use super::dto::{CreateTaskRequest, TaskListQuery, UpdateTaskRequest};
+use super::error::ApiError;
+use super::etag::{self, ETagged};
+use super::state::AppState;
+
+/// GET /api/v1/trees/:tree_id/tasks?status=...&person_id=...&family_id=...
+///
+/// The tree's research to-do list, soonest due first.
+pub async fn list_tasks(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Query(query): Query<TaskListQuery>,
+) -> Result<Json<Vec<ResearchTask>>, ApiError> {
+    let filter = ResearchTaskFilter {
+        status: query.status,
+        person_id: query.person_id,
+        family_id: query.family_id,
+    };
+    let tasks = ResearchTaskRepo::list(&state.db, tree_id, &filter).await?;
+    Ok(Json(tasks))
+}
+
+/// POST /api/v1/trees/:tree_id/tasks
+pub async fn create_task(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Json(body): Json<CreateTaskRequest>,
+) -> Result<(StatusCode, Json<ResearchTask>), ApiError> {
+    access::ensure_in_tree(
+        &state,
+        tree_id,
+        [
+            (TreeEntity::Person, body.person_id),
+            (TreeEntity::Family, body.family_id),
+        ],
+    )
+    .await?;
+    validation::not_blank("title", &body.title)?;
+    let task = ResearchTaskRepo::create(
+        &state.db,
+        Uuid::now_v7(),
+        tree_id,
+        body.person_id,
+        body.family_id,
+        body.title.trim().to_string(),
+        body.description.filter(|d| !d.trim().is_empty()),
+        body.due_date,
+    )
+    .await?;
+    Ok((StatusCode::CREATED, Json(task)))
+}
+
+/// GET /api/v1/trees/:tree_id/tasks/:task_id
+pub async fn get_task(
+    State(state): State<AppState>,
+    Path((_tree_id, task_id)): Path<(Uuid, Uuid)>,
+) -> Result<ETagged, ApiError> {
+    let task = ResearchTaskRepo::get(&state.db, task_id).await?;
+    Ok(etag::tagged(task.version, task))
+}
+
+/// PUT /api/v1/trees/:tree_id/tasks/:task_id
+pub async fn update_task(
+    State(state): State<AppState>,
+    Path((_tree_id, task_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    Json(body): Json<UpdateTaskRequest>,
+) -> Result<ETagged, ApiError> {
+    let expected_version = etag::if_match(&headers)?;
+    if let Some(title) = &body.title {
+        validation::not_blank("title", title)?;
+    }
+    let task = ResearchTaskRepo::update(
+        &state.db,
+        task_id,
+        body.title.map(|t| t.trim().to_string()),
+        body.description.map(|d| d.filter(|d| !d.trim().is_empty())),
+        body.status,
+        body.due_date,
+        expected_version,
+    )
+    .await?;
+    Ok(etag::tagged(task.version, task))
+}
+
+/// DELETE /api/v1/trees/:tree_id/tasks/:task_id
+pub async fn delete_task(
+    State(state): State<AppState>,
+    Path((_tree_id, task_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    ResearchTaskRepo::delete(&state.db, task_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
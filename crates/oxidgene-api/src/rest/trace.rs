@@ -0,0 +1,104 @@
+//! Request tracing tuned by [`TraceConfig`].
+//!
+//! Plugged into `tower_http`'s `TraceLayer` in place of its defaults:
+//! requests to skipped paths (health probes) get no span and no events, and
+//! the "finished processing request" event is logged at the success or
+//! failure level depending on the status, so that a server at `info` shows
+//! the failed requests without the flood of successful ones.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::{Request, Response};
+use tower_http::trace::{MakeSpan, OnRequest, OnResponse};
+use tracing::{Level, Span};
+
+use crate::config::TraceConfig;
+
+/// `tracing` macros need their level at compile time; dispatch on a
+/// runtime one.
+macro_rules! at_level {
+    ($level:expr, $macro:ident!($($arg:tt)*)) => {
+        match $level {
+            Level::ERROR => tracing::$macro!(Level::ERROR, $($arg)*),
+            Level::WARN => tracing::$macro!(Level::WARN, $($arg)*),
+            Level::INFO => tracing::$macro!(Level::INFO, $($arg)*),
+            Level::DEBUG => tracing::$macro!(Level::DEBUG, $($arg)*),
+            _ => tracing::$macro!(Level::TRACE, $($arg)*),
+        }
+    };
+}
+
+/// Opens the request span, or none for a skipped path.
+#[derive(Debug, Clone)]
+pub struct MakeRequestSpan(Arc<TraceConfig>);
+
+impl MakeRequestSpan {
+    pub fn new(config: Arc<TraceConfig>) -> Self {
+        Self(config)
+    }
+}
+
+impl<B> MakeSpan<B> for MakeRequestSpan {
+    fn make_span(&mut self, request: &Request<B>) -> Span {
+        if !self.0.traces(request.uri().path()) {
+            return Span::none();
+        }
+        at_level!(
+            self.0.span_level(),
+            span!(
+                "request",
+                method = %request.method(),
+                uri = %request.uri(),
+                version = ?request.version(),
+                actor = tracing::field::Empty,
+            )
+        )
+    }
+}
+
+/// Logs the start of a request at the success level.
+#[derive(Debug, Clone)]
+pub struct LogRequest(Arc<TraceConfig>);
+
+impl LogRequest {
+    pub fn new(config: Arc<TraceConfig>) -> Self {
+        Self(config)
+    }
+}
+
+impl<B> OnRequest<B> for LogRequest {
+    fn on_request(&mut self, _request: &Request<B>, span: &Span) {
+        // Skipped, or filtered out at the span level.
+        if span.is_disabled() {
+            return;
+        }
+        at_level!(self.0.success_level, event!("started processing request"));
+    }
+}
+
+/// Logs the end of a request at the level its status calls for.
+#[derive(Debug, Clone)]
+pub struct LogResponse(Arc<TraceConfig>);
+
+impl LogResponse {
+    pub fn new(config: Arc<TraceConfig>) -> Self {
+        Self(config)
+    }
+}
+
+impl<B> OnResponse<B> for LogResponse {
+    fn on_response(self, response: &Response<B>, latency: Duration, span: &Span) {
+        if span.is_disabled() {
+            return;
+        }
+        at_level!(
+            self.0.response_level(response.status()),
+            event!(
+                latency = %format_args!("{} ms", latency.as_millis()),
+                status = response.status().as_u16(),
+                "finished processing request"
+            )
+        );
+    }
+}
@@ -1,45 +1,113 @@
 //! REST handlers for Tree CRUD operations.
 
 use axum::Json;
-use axum::extract::{Path, Query, State};
-use axum::http::StatusCode;
-use oxidgene_db::repo::{PaginationParams, TreeRepo};
+use axum::extract::{Extension, Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use oxidgene_core::OxidGeneError;
+use oxidgene_core::replace::{ReplaceEntity, ReplaceField, Replacer};
+use oxidgene_core::types::{Connection, Edge, Tree};
+use oxidgene_core::validation;
+use oxidgene_db::repo::{
+    ActivityRepo, IntegrityRepo, ReplaceRepo, Severity, TreeEntity, TreeRepo, TreeStats,
+};
+use oxidgene_gedcom::ExportOptions;
 use uuid::Uuid;
 
-use super::dto::{CreateTreeRequest, DuplicateTreeRequest, PaginationQuery, UpdateTreeRequest};
+use super::access::{self, Owner, RequireTreeRole};
+use super::auth::Actor;
+use super::dto::{
+    ActivityEntryResponse, ActivityQuery, CreateTreeQuery, CreateTreeRequest, DuplicateTreeRequest,
+    IntegrityIssueResponse, IntegrityReportResponse, PaginationQuery, ReplaceQuery, ReplaceRequest,
+    ReplaceResponse, TreeResponse, UpdateTreeRequest,
+};
 use super::error::ApiError;
+use super::etag::{self, ETagged};
 use super::state::AppState;
-use crate::service::gedcom;
+use crate::service::{gedcom, template};
 
 /// GET /api/v1/trees
+///
+/// With authentication enabled, only the trees the actor is a member of,
+/// or every tree for an admin.
 pub async fn list_trees(
     State(state): State<AppState>,
+    Extension(actor): Extension<Actor>,
     Query(query): Query<PaginationQuery>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
-    let params = PaginationParams {
-        first: query.first.unwrap_or(25),
-        after: query.after,
-    };
-    let connection = TreeRepo::list(&state.db, &params)
+    let params = state.config.page_params(query.first, query.after);
+    let connection = if state.auth.is_enabled() && !actor.is_admin {
+        TreeRepo::list_for_actor(&state.db, &params, &actor.id).await
+    } else {
+        TreeRepo::list(&state.db, &params).await
+    }
+    .map_err(ApiError::from)?;
+    let ids: Vec<Uuid> = connection.edges.iter().map(|e| e.node.id).collect();
+    let stats = TreeRepo::stats(&state.db, &ids)
         .await
         .map_err(ApiError::from)?;
+    let connection = Connection {
+        edges: connection
+            .edges
+            .into_iter()
+            .map(|e| {
+                let tree_stats = stats.get(&e.node.id);
+                Edge {
+                    cursor: e.cursor,
+                    node: tree_response(e.node, tree_stats),
+                }
+            })
+            .collect(),
+        page_info: connection.page_info,
+        total_count: connection.total_count,
+    };
     Ok(Json(serde_json::to_value(connection).unwrap()))
 }
 
-/// POST /api/v1/trees
+/// A tree with its counts; a tree missing from the stats counts as empty.
+fn tree_response(tree: Tree, stats: Option<&TreeStats>) -> TreeResponse {
+    let stats = stats.copied().unwrap_or(TreeStats {
+        person_count: 0,
+        family_count: 0,
+        event_count: 0,
+        last_modified_at: tree.updated_at,
+        first_year: None,
+        last_year: None,
+    });
+    TreeResponse {
+        tree,
+        person_count: stats.person_count,
+        family_count: stats.family_count,
+        event_count: stats.event_count,
+        last_modified_at: stats.last_modified_at,
+        first_year: stats.first_year,
+        last_year: stats.last_year,
+    }
+}
+
+/// POST /api/v1/trees?template=empty|self
+///
+/// `template=self` seeds the tree with a home person (its SOSA root) and
+/// empty placeholders for their parents and grandparents. The creator
+/// becomes the tree's owner.
 pub async fn create_tree(
     State(state): State<AppState>,
+    Extension(actor): Extension<Actor>,
+    Query(query): Query<CreateTreeQuery>,
     Json(body): Json<CreateTreeRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
-    if body.name.trim().is_empty() {
-        return Err(ApiError(oxidgene_core::OxidGeneError::Validation(
-            "name must not be empty".to_string(),
-        )));
-    }
+    validation::not_blank("name", &body.name)?;
     let id = Uuid::now_v7();
-    let tree = TreeRepo::create(&state.db, id, body.name, body.description)
+    let tree = template::create_tree(&state.db, id, body.name, body.description, query.template)
         .await
         .map_err(ApiError::from)?;
+    access::claim_tree(&state, &actor, id).await?;
+    if tree.sosa_root_person_id.is_some() {
+        state
+            .cache
+            .rebuild_tree_full(id)
+            .await
+            .map_err(ApiError::from)?;
+    }
     Ok((
         StatusCode::CREATED,
         Json(serde_json::to_value(tree).unwrap()),
@@ -50,57 +118,76 @@ pub async fn create_tree(
 pub async fn get_tree(
     State(state): State<AppState>,
     Path(tree_id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, ApiError> {
+) -> Result<ETagged, ApiError> {
     let tree = TreeRepo::get(&state.db, tree_id)
         .await
         .map_err(ApiError::from)?;
-    Ok(Json(serde_json::to_value(tree).unwrap()))
+    let stats = TreeRepo::stats(&state.db, &[tree_id])
+        .await
+        .map_err(ApiError::from)?;
+    let version = tree.version;
+    Ok(etag::tagged(
+        version,
+        tree_response(tree, stats.get(&tree_id)),
+    ))
 }
 
 /// PUT /api/v1/trees/:tree_id
 pub async fn update_tree(
     State(state): State<AppState>,
     Path(tree_id): Path<Uuid>,
+    headers: HeaderMap,
     Json(body): Json<UpdateTreeRequest>,
-) -> Result<Json<serde_json::Value>, ApiError> {
+) -> Result<ETagged, ApiError> {
+    let expected_version = etag::if_match(&headers)?;
+    if let Some(name) = &body.name {
+        validation::not_blank("name", name)?;
+    }
+    access::ensure_in_tree(
+        &state,
+        tree_id,
+        [(TreeEntity::Person, body.sosa_root_person_id.flatten())],
+    )
+    .await?;
     let tree = TreeRepo::update(
         &state.db,
         tree_id,
         body.name,
         body.description,
         body.sosa_root_person_id,
+        expected_version,
     )
     .await
     .map_err(ApiError::from)?;
-    Ok(Json(serde_json::to_value(tree).unwrap()))
+    Ok(etag::tagged(tree.version, tree))
 }
 
 /// POST /api/v1/trees/:tree_id/duplicate
 ///
-/// Duplicate a tree by exporting its GEDCOM and importing it into a new tree.
+/// Duplicate a tree by exporting its GEDCOM and importing it into a new tree,
+/// owned by the requesting actor.
 pub async fn duplicate_tree(
     State(state): State<AppState>,
     Path(source_tree_id): Path<Uuid>,
+    Extension(actor): Extension<Actor>,
     Json(body): Json<DuplicateTreeRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
-    if body.name.trim().is_empty() {
-        return Err(ApiError(oxidgene_core::OxidGeneError::Validation(
-            "name must not be empty".to_string(),
-        )));
-    }
+    validation::not_blank("name", &body.name)?;
 
     // Export GEDCOM from source tree (lossless round-trip, so don't merge
     // OCCU tags — that's an opt-in compatibility trade-off for user-facing
     // export, not for internal duplication).
-    let export = gedcom::load_and_export(&state.db, source_tree_id, false)
-        .await
-        .map_err(ApiError::from)?;
+    let export =
+        gedcom::load_and_export(&state.db, source_tree_id, &ExportOptions::default(), None)
+            .await
+            .map_err(ApiError::from)?;
 
     // Create the new tree
     let new_id = Uuid::now_v7();
     let new_tree = TreeRepo::create(&state.db, new_id, body.name, None)
         .await
         .map_err(ApiError::from)?;
+    access::claim_tree(&state, &actor, new_id).await?;
 
     // Import GEDCOM into the new tree
     gedcom::import_and_persist(&state.db, new_id, &export.gedcom)
@@ -120,10 +207,109 @@ pub async fn duplicate_tree(
     ))
 }
 
+/// GET /api/v1/trees/:tree_id/check
+///
+/// Lint a tree: report dangling references and stale derived data, each
+/// with a suggested fix. Read-only.
+pub async fn check_tree(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+) -> Result<Json<IntegrityReportResponse>, ApiError> {
+    TreeRepo::get(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    let issues = IntegrityRepo::check(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+    let error_count = issues
+        .iter()
+        .filter(|i| i.severity == Severity::Error)
+        .count();
+
+    Ok(Json(IntegrityReportResponse {
+        error_count,
+        warning_count: issues.len() - error_count,
+        issues: issues
+            .into_iter()
+            .map(|i| IntegrityIssueResponse {
+                kind: i.kind.as_str(),
+                severity: i.severity.as_str(),
+                entity_id: i.entity_id,
+                message: i.message,
+                suggested_fix: i.suggested_fix,
+            })
+            .collect(),
+    }))
+}
+
+/// GET /api/v1/trees/:tree_id/activity?limit=
+///
+/// The most recently created or updated entities of the tree, all types
+/// mixed, newest first: the "recently edited" list.
+pub async fn tree_activity(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Query(query): Query<ActivityQuery>,
+) -> Result<Json<Vec<ActivityEntryResponse>>, ApiError> {
+    TreeRepo::get(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    let limit = state.config.page_limit(query.limit, 20);
+    let entries = ActivityRepo::recent(&state.db, tree_id, limit)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(entries.into_iter().map(Into::into).collect()))
+}
+
+/// POST /api/v1/trees/:tree_id/replace?dry_run=
+///
+/// Find-and-replace one field of every place or source of the tree, in a
+/// single transaction. With `dry_run=true` the changes are only listed.
+/// Cached persons embed place names, so renaming places rebuilds the tree
+/// cache.
+pub async fn replace_in_tree(
+    State(state): State<AppState>,
+    Path(tree_id): Path<Uuid>,
+    Query(query): Query<ReplaceQuery>,
+    Json(body): Json<ReplaceRequest>,
+) -> Result<Json<ReplaceResponse>, ApiError> {
+    TreeRepo::get(&state.db, tree_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    let field = ReplaceField::of(body.entity, &body.field).ok_or_else(|| {
+        ApiError(OxidGeneError::Validation(format!(
+            "unknown field '{}'; expected one of: {}",
+            body.field,
+            ReplaceField::names(body.entity).join(", ")
+        )))
+    })?;
+    let replacer = Replacer::new(body.match_mode, &body.find, &body.replace)?;
+    let changes = ReplaceRepo::replace(&state.db, tree_id, field, &replacer, query.dry_run)
+        .await
+        .map_err(ApiError::from)?;
+
+    if !query.dry_run && !changes.is_empty() && body.entity == ReplaceEntity::Place {
+        state
+            .cache
+            .rebuild_tree_full(tree_id)
+            .await
+            .map_err(ApiError)?;
+    }
+    Ok(Json(ReplaceResponse {
+        dry_run: query.dry_run,
+        count: changes.len(),
+        changes: changes.into_iter().map(Into::into).collect(),
+    }))
+}
+
 /// DELETE /api/v1/trees/:tree_id
 pub async fn delete_tree(
     State(state): State<AppState>,
     Path(tree_id): Path<Uuid>,
+    _owner: RequireTreeRole<Owner>,
 ) -> Result<StatusCode, ApiError> {
     TreeRepo::delete(&state.db, tree_id)
         .await
@@ -1,31 +1,59 @@
 //! Axum router combining REST routes under `/api/v1` and GraphQL at `/graphql`.
 
+use std::sync::Arc;
+
 use axum::Router;
 use axum::extract::DefaultBodyLimit;
+use axum::http::HeaderValue;
+use axum::middleware;
 use axum::routing::{delete, get, patch, post, put};
+use tower_http::trace::TraceLayer;
 
 #[cfg(feature = "graphql")]
-use crate::graphql::{build_schema, graphql_handler, graphql_playground};
+use crate::graphql::{build_schema, graphql_handler, graphql_playground, graphql_schema_sdl};
+use crate::rest::access;
+use crate::rest::admin;
+use crate::rest::auth;
+use crate::rest::backup;
 use crate::rest::cache;
 use crate::rest::citation;
 use crate::rest::dictionary;
+use crate::rest::error;
 use crate::rest::event;
+use crate::rest::export;
 use crate::rest::family;
 use crate::rest::family_member;
 use crate::rest::gedcom;
 use crate::rest::media;
 use crate::rest::media_link;
+use crate::rest::member;
 use crate::rest::note;
 use crate::rest::person;
 use crate::rest::person_name;
 use crate::rest::place;
+use crate::rest::request_id;
+use crate::rest::share;
 use crate::rest::snapshot;
 use crate::rest::source;
 use crate::rest::state::AppState;
+use crate::rest::tag;
+use crate::rest::task;
+use crate::rest::trace;
 use crate::rest::tree;
 
 /// Build the complete API router.
+///
+/// Every request is traced as the config's [`TraceConfig`] says (see
+/// [`trace`]) and tagged with an `X-Request-Id` (see [`request_id`]), so the
+/// layers wrap the REST and GraphQL routes alike. Every route but the
+/// anonymous `/api/v1/shared` views goes through [`auth::authenticate`],
+/// and the `/api/v1/trees` routes check the actor's role on the tree (see
+/// [`access`]). GraphQL resolvers do not check tree roles, so GraphQL is
+/// not served when authentication is enabled.
+///
+/// [`TraceConfig`]: crate::config::TraceConfig
 pub fn build_router(state: AppState) -> Router {
+    let trace_config = Arc::new(state.config.trace.clone());
     let tree_routes = Router::new()
         .route("/", get(tree::list_trees).post(tree::create_tree))
         .route(
@@ -34,7 +62,10 @@ pub fn build_router(state: AppState) -> Router {
                 .put(tree::update_tree)
                 .delete(tree::delete_tree),
         )
-        .route("/{tree_id}/duplicate", post(tree::duplicate_tree));
+        .route("/{tree_id}/duplicate", post(tree::duplicate_tree))
+        .route("/{tree_id}/check", get(tree::check_tree))
+        .route("/{tree_id}/replace", post(tree::replace_in_tree))
+        .route("/{tree_id}/activity", get(tree::tree_activity));
 
     let person_routes = Router::new()
         .route(
@@ -42,6 +73,19 @@ pub fn build_router(state: AppState) -> Router {
             get(person::list_persons).post(person::create_person),
         )
         .route("/{tree_id}/persons/search", get(person::search_persons))
+        .route(
+            "/{tree_id}/persons/quick",
+            post(person::quick_create_person),
+        )
+        .route(
+            "/{tree_id}/persons/bulk-delete",
+            post(person::bulk_delete_persons),
+        )
+        .route("/{tree_id}/persons/compare", get(person::compare_persons))
+        .route(
+            "/{tree_id}/persons/duplicates",
+            get(person::list_duplicate_candidates),
+        )
         .route(
             "/{tree_id}/persons/sosa/{number}",
             get(person::get_person_by_sosa),
@@ -52,6 +96,22 @@ pub fn build_router(state: AppState) -> Router {
                 .put(person::update_person)
                 .delete(person::delete_person),
         )
+        .route(
+            "/{tree_id}/persons/{person_id}/relatives",
+            post(person::add_relative),
+        )
+        .route(
+            "/{tree_id}/persons/{person_id}/lock",
+            post(person::lock_person),
+        )
+        .route(
+            "/{tree_id}/persons/{person_id}/unlock",
+            post(person::unlock_person),
+        )
+        .route(
+            "/{tree_id}/persons/{person_id}/move/{target_tree_id}",
+            post(person::move_person),
+        )
         .route(
             "/{tree_id}/persons/{person_id}/ancestors",
             get(person::get_ancestors),
@@ -59,9 +119,33 @@ pub fn build_router(state: AppState) -> Router {
         .route(
             "/{tree_id}/persons/{person_id}/descendants",
             get(person::get_descendants),
+        )
+        .route(
+            "/{tree_id}/persons/{person_id}/path/{other_id}",
+            get(person::get_relationship_path),
+        )
+        .route(
+            "/{tree_id}/persons/{person_id}/ahnentafel",
+            get(person::get_ahnentafel),
+        )
+        .route(
+            "/{tree_id}/persons/{person_id}/fan.svg",
+            get(person::get_fan_chart),
+        )
+        .route(
+            "/{tree_id}/persons/{person_id}/descendant-report",
+            get(person::get_descendant_report),
+        )
+        .route(
+            "/{tree_id}/persons/{person_id}/date-conflicts",
+            get(person::get_date_conflicts),
         );
 
     let person_name_routes = Router::new()
+        .route(
+            "/{tree_id}/persons/names/normalize-case",
+            post(person_name::normalize_name_case),
+        )
         .route(
             "/{tree_id}/persons/{person_id}/names",
             get(person_name::list_person_names).post(person_name::create_person_name),
@@ -81,6 +165,18 @@ pub fn build_router(state: AppState) -> Router {
             get(family::get_family)
                 .put(family::update_family)
                 .delete(family::delete_family),
+        )
+        .route(
+            "/{tree_id}/families/{family_id}/events",
+            get(family::list_family_events),
+        )
+        .route(
+            "/{tree_id}/families/{family_id}/notes",
+            get(family::list_family_notes),
+        )
+        .route(
+            "/{tree_id}/families/{family_id}/citations",
+            get(family::list_family_citations),
         );
 
     let family_member_routes = Router::new()
@@ -96,11 +192,36 @@ pub fn build_router(state: AppState) -> Router {
             "/{tree_id}/families/{family_id}/children",
             get(family_member::list_children).post(family_member::add_child),
         )
+        .route(
+            "/{tree_id}/families/{family_id}/children/bulk",
+            post(family_member::add_children),
+        )
+        .route(
+            "/{tree_id}/families/{family_id}/children/reorder",
+            patch(family_member::reorder_children),
+        )
+        .route(
+            "/{tree_id}/families/{family_id}/children/sort-by-birth",
+            post(family_member::sort_children_by_birth),
+        )
         .route(
             "/{tree_id}/families/{family_id}/children/{child_id}",
             delete(family_member::remove_child),
         );
 
+    // Deprecated aliases of the participant routes, from before
+    // participants had roles.
+    let witness_routes = Router::new()
+        .route(
+            "/{tree_id}/events/{event_id}/witnesses",
+            get(event::list_witnesses).post(event::add_participant),
+        )
+        .route(
+            "/{tree_id}/events/{event_id}/witnesses/{participant_id}",
+            delete(event::remove_participant),
+        )
+        .layer(middleware::map_response(deprecated));
+
     let event_routes = Router::new()
         .route(
             "/{tree_id}/events",
@@ -113,19 +234,43 @@ pub fn build_router(state: AppState) -> Router {
                 .delete(event::delete_event),
         )
         .route(
-            "/{tree_id}/events/{event_id}/witnesses",
-            get(event::list_witnesses).post(event::add_witness),
+            "/{tree_id}/events/{event_id}/owner",
+            patch(event::reassign_event),
         )
         .route(
-            "/{tree_id}/events/{event_id}/witnesses/{witness_id}",
-            delete(event::remove_witness),
-        );
+            "/{tree_id}/events/{event_id}/participants",
+            get(event::list_participants).post(event::add_participant),
+        )
+        .route(
+            "/{tree_id}/events/{event_id}/participants/{participant_id}",
+            delete(event::remove_participant),
+        )
+        .merge(witness_routes);
 
     let place_routes = Router::new()
         .route(
             "/{tree_id}/places",
             get(place::list_places).post(place::create_place),
         )
+        .route(
+            "/{tree_id}/places/autocomplete",
+            get(place::autocomplete_places),
+        )
+        .route("/{tree_id}/places/dedupe", post(place::dedupe_places))
+        .route("/{tree_id}/places/unused", get(place::list_unused_places))
+        .route("/{tree_id}/places/prune", post(place::prune_places))
+        .route(
+            "/{tree_id}/places/geocode-all",
+            post(place::geocode_all_places),
+        )
+        .route(
+            "/{tree_id}/places/geocode-all/{job_id}/events",
+            get(place::geocode_events_handler),
+        )
+        .route(
+            "/{tree_id}/places/{place_id}/reverse-geocode",
+            post(place::reverse_geocode_place),
+        )
         .route(
             "/{tree_id}/places/{place_id}",
             get(place::get_place)
@@ -138,11 +283,24 @@ pub fn build_router(state: AppState) -> Router {
             "/{tree_id}/sources",
             get(source::list_sources).post(source::create_source),
         )
+        .route(
+            "/{tree_id}/sources/duplicates",
+            get(source::source_duplicates),
+        )
+        .route("/{tree_id}/sources/merge", post(source::merge_sources))
         .route(
             "/{tree_id}/sources/{source_id}",
             get(source::get_source)
                 .put(source::update_source)
                 .delete(source::delete_source),
+        )
+        .route(
+            "/{tree_id}/sources/{source_id}/media",
+            post(source::link_source_media),
+        )
+        .route(
+            "/{tree_id}/sources/{source_id}/usage",
+            get(source::source_usage),
         );
 
     let citation_routes = Router::new()
@@ -150,6 +308,10 @@ pub fn build_router(state: AppState) -> Router {
             "/{tree_id}/citations",
             get(citation::list_citations).post(citation::create_citation),
         )
+        .route(
+            "/{tree_id}/citations/bulk",
+            post(citation::bulk_create_citations),
+        )
         .route(
             "/{tree_id}/citations/{citation_id}",
             put(citation::update_citation).delete(citation::delete_citation),
@@ -175,6 +337,32 @@ pub fn build_router(state: AppState) -> Router {
         .route(
             "/{tree_id}/media-links/{link_id}",
             delete(media_link::delete_media_link),
+        )
+        .route(
+            "/{tree_id}/persons/{person_id}/photo",
+            get(media_link::get_person_photo)
+                .put(media_link::set_person_photo)
+                .delete(media_link::clear_person_photo),
+        )
+        .route(
+            "/{tree_id}/persons/{person_id}/media",
+            get(media_link::list_person_media),
+        )
+        .route(
+            "/{tree_id}/persons/{person_id}/media/order",
+            put(media_link::reorder_person_media),
+        );
+
+    let task_routes = Router::new()
+        .route(
+            "/{tree_id}/tasks",
+            get(task::list_tasks).post(task::create_task),
+        )
+        .route(
+            "/{tree_id}/tasks/{task_id}",
+            get(task::get_task)
+                .put(task::update_task)
+                .delete(task::delete_task),
         );
 
     let note_routes = Router::new()
@@ -191,7 +379,48 @@ pub fn build_router(state: AppState) -> Router {
 
     let snapshot_routes = Router::new().route("/{tree_id}/snapshot", get(snapshot::tree_snapshot));
 
+    let share_routes = Router::new()
+        .route(
+            "/{tree_id}/share",
+            get(share::list_share_links).post(share::create_share_link),
+        )
+        .route(
+            "/{tree_id}/share/{share_id}",
+            delete(share::revoke_share_link),
+        );
+
+    let member_routes = Router::new()
+        .route("/{tree_id}/members", get(member::list_members))
+        .route(
+            "/{tree_id}/members/{actor_id}",
+            put(member::set_member).delete(member::remove_member),
+        );
+
+    // Anonymous, read-only views: the token resolves to a tree, so no
+    // tree id ever appears in these paths.
+    let shared_routes = Router::new()
+        .route("/{token}", get(share::get_shared_tree))
+        .route("/{token}/snapshot", get(share::shared_snapshot))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            share::resolve_share_token,
+        ));
+
+    let tag_routes = Router::new()
+        .route("/{tree_id}/tags", get(tag::list_tags))
+        .route("/{tree_id}/person-tags", get(tag::list_tree_person_tags))
+        .route(
+            "/{tree_id}/persons/{person_id}/tags",
+            get(tag::list_person_tags).post(tag::add_person_tag),
+        )
+        .route(
+            "/{tree_id}/persons/{person_id}/tags/{tag}",
+            delete(tag::remove_person_tag),
+        );
+
     let dictionary_routes = Router::new()
+        .route("/{tree_id}/surname-index", get(dictionary::surname_index))
+        .route("/{tree_id}/stats/surnames", get(dictionary::surname_stats))
         .route(
             "/{tree_id}/dictionary/family-names",
             get(dictionary::family_names),
@@ -252,14 +481,65 @@ pub fn build_router(state: AppState) -> Router {
             "/{tree_id}/gedcom/import",
             post(gedcom::import_gedcom_handler),
         )
+        .route(
+            "/{tree_id}/import/preview",
+            post(gedcom::preview_import_handler),
+        )
+        .route(
+            "/{tree_id}/import/preview/{token}/commit",
+            post(gedcom::commit_import_preview_handler),
+        )
+        .route(
+            "/{tree_id}/import/{job_id}/events",
+            get(gedcom::import_events_handler),
+        )
+        .route(
+            "/{tree_id}/import/{job_id}/cancel",
+            post(gedcom::cancel_import_handler),
+        )
         .route(
             "/{tree_id}/gedcom/export",
             get(gedcom::export_gedcom_handler),
         )
+        .route(
+            "/{tree_id}/export/estimate",
+            get(gedcom::export_estimate_handler),
+        )
+        .route("/{tree_id}/export/jobs", post(gedcom::start_export_handler))
+        .route(
+            "/{tree_id}/export/{job_id}/events",
+            get(gedcom::export_events_handler),
+        )
+        .route(
+            "/{tree_id}/export/{job_id}/download",
+            get(gedcom::download_export_handler),
+        )
+        .route("/{tree_id}/export", post(export::export_selection))
+        .route("/{tree_id}/export.dot", get(export::export_dot))
+        .route("/{tree_id}/export.csv", get(export::export_csv))
         .layer(DefaultBodyLimit::max(10 * 1024 * 1024)); // 10 MiB
 
+    let backup_routes = Router::new()
+        .route("/", get(backup::list_backups).post(backup::create_backup))
+        .route("/{file_name}/restore", post(backup::restore_backup));
+
+    let admin_routes = Router::new()
+        .nest("/backup", backup_routes)
+        .route("/admin/backfill-date-sort", post(admin::backfill_date_sort))
+        .route("/admin/backfill-phonetic", post(admin::backfill_phonetic))
+        .route(
+            "/admin/backfill-display-names",
+            post(admin::backfill_display_names),
+        )
+        .route_layer(middleware::from_fn(access::require_admin));
+
+    #[cfg(feature = "graphql")]
+    let schema = build_schema(state.db.clone(), state.cache.clone(), &state.config);
     #[cfg(feature = "graphql")]
-    let schema = build_schema(state.db.clone(), state.cache.clone());
+    let expose_schema = state.config.graphql_expose_schema;
+    #[cfg(feature = "graphql")]
+    let serve_graphql = !state.auth.is_enabled();
+    let authenticate = middleware::from_fn_with_state(state.auth.clone(), auth::authenticate);
 
     let rest_router = Router::new()
         .nest(
@@ -276,21 +556,55 @@ pub fn build_router(state: AppState) -> Router {
                 .merge(media_routes)
                 .merge(media_link_routes)
                 .merge(note_routes)
+                .merge(task_routes)
                 .merge(snapshot_routes)
+                .merge(share_routes)
+                .merge(member_routes)
+                .merge(tag_routes)
                 .merge(dictionary_routes)
                 .merge(cache_routes)
-                .merge(gedcom_routes),
-        )
+                .merge(gedcom_routes)
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    access::enforce_tree_role,
+                )),
+        )
+        .route("/api/v1/search", get(person::search_all_trees))
+        .nest("/api/v1", admin_routes)
+        .route_layer(authenticate.clone())
+        .nest("/api/v1/shared", shared_routes)
         .with_state(state);
 
+    let router = rest_router;
     #[cfg(feature = "graphql")]
-    {
-        let graphql_routes = Router::new()
-            .route("/graphql", post(graphql_handler).get(graphql_playground))
-            .with_state(schema);
-        rest_router.merge(graphql_routes)
-    }
-
-    #[cfg(not(feature = "graphql"))]
-    rest_router
+    let router = if serve_graphql {
+        let mut graphql_routes =
+            Router::new().route("/graphql", post(graphql_handler).get(graphql_playground));
+        if expose_schema {
+            graphql_routes =
+                graphql_routes.route("/graphql/schema.graphql", get(graphql_schema_sdl));
+        }
+        router.merge(graphql_routes.route_layer(authenticate).with_state(schema))
+    } else {
+        router
+    };
+
+    router
+        .layer(middleware::from_fn(error::problem_json))
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(trace::MakeRequestSpan::new(trace_config.clone()))
+                .on_request(trace::LogRequest::new(trace_config.clone()))
+                .on_response(trace::LogResponse::new(trace_config)),
+        )
+        .layer(middleware::from_fn(request_id::propagate_request_id))
+}
+
+/// Mark the response of a deprecated route with `Deprecation: true`, for
+/// clients to notice before the route goes away.
+async fn deprecated(mut response: axum::response::Response) -> axum::response::Response {
+    response
+        .headers_mut()
+        .insert("deprecation", HeaderValue::from_static("true"));
+    response
 }
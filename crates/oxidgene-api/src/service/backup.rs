@@ -0,0 +1,228 @@
+//! Backups of the desktop app's SQLite database.
+//!
+//! Each backup is a standalone copy of the database written with
+//! `VACUUM INTO` (see [`backup_sqlite`]), named after the time it was taken:
+//! `oxidgene-YYYYMMDD-HHMMSS.db`. Only the newest `keep` files are retained.
+//!
+//! Restoring cannot replace the file under the open connection pool, so it
+//! is staged instead: the chosen backup is copied next to the live database
+//! and swapped in by [`apply_pending_restore`] on the next launch, before the
+//! database is opened.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use oxidgene_core::OxidGeneError;
+use oxidgene_db::repo::backup_sqlite;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// File name prefix shared by all backups.
+const BACKUP_PREFIX: &str = "oxidgene-";
+
+/// File name extension shared by all backups.
+const BACKUP_EXTENSION: &str = ".db";
+
+/// `chrono` format of the timestamp in a backup's file name.
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Suffix of the staged restore file, next to the live database.
+const RESTORE_SUFFIX: &str = ".restore";
+
+/// A backup file on disk.
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupInfo {
+    /// File name inside the backup directory; identifies the backup.
+    pub file_name: String,
+    pub created_at: DateTime<Utc>,
+    pub size_bytes: u64,
+}
+
+/// Backup directory of one SQLite database, with its retention policy.
+#[derive(Debug)]
+pub struct Backups {
+    dir: PathBuf,
+    db_path: PathBuf,
+    keep: usize,
+    /// Serialises backups, so a manual trigger and the periodic task never
+    /// write and prune at the same time.
+    lock: Mutex<()>,
+}
+
+impl Backups {
+    /// Back up the database at `db_path` into `dir`, keeping the newest
+    /// `keep` backups (at least one).
+    pub fn new(dir: impl Into<PathBuf>, db_path: impl Into<PathBuf>, keep: usize) -> Self {
+        Self {
+            dir: dir.into(),
+            db_path: db_path.into(),
+            keep: keep.max(1),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Directory holding the backup files.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Take a backup now, then prune the oldest ones beyond the retention.
+    pub async fn create(&self, db: &DatabaseConnection) -> Result<BackupInfo, OxidGeneError> {
+        let _guard = self.lock.lock().await;
+        std::fs::create_dir_all(&self.dir)?;
+        let now = Utc::now();
+        let file_name = format!(
+            "{BACKUP_PREFIX}{}{BACKUP_EXTENSION}",
+            now.format(BACKUP_TIMESTAMP_FORMAT)
+        );
+        let path = self.dir.join(&file_name);
+        if path.exists() {
+            return Err(OxidGeneError::Conflict(format!(
+                "backup {file_name} already exists"
+            )));
+        }
+        backup_sqlite(db, &path)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        self.prune()?;
+        Ok(BackupInfo {
+            size_bytes: std::fs::metadata(&path)?.len(),
+            created_at: parse_backup_name(&file_name).unwrap_or(now),
+            file_name,
+        })
+    }
+
+    /// List the backups, newest first.
+    pub fn list(&self) -> Result<Vec<BackupInfo>, OxidGeneError> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut backups = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            let Ok(file_name) = entry.file_name().into_string() else {
+                continue;
+            };
+            let Some(created_at) = parse_backup_name(&file_name) else {
+                continue;
+            };
+            backups.push(BackupInfo {
+                size_bytes: entry.metadata()?.len(),
+                created_at,
+                file_name,
+            });
+        }
+        backups.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+        Ok(backups)
+    }
+
+    /// Stage `file_name` to replace the database on the next launch.
+    ///
+    /// A backup of the current state is also taken, so the restore itself
+    /// can be undone.
+    pub async fn stage_restore(
+        &self,
+        db: &DatabaseConnection,
+        file_name: &str,
+    ) -> Result<BackupInfo, OxidGeneError> {
+        // Only names from the listing are accepted: never a path.
+        let backup = self
+            .list()?
+            .into_iter()
+            .find(|b| b.file_name == file_name)
+            .ok_or_else(|| OxidGeneError::Validation(format!("unknown backup {file_name}")))?;
+        // Copy before backing up: pruning may remove the chosen backup.
+        let staged = restore_path(&self.db_path);
+        std::fs::copy(self.dir.join(file_name), &staged)?;
+        if let Err(e) = self.create(db).await {
+            let _ = std::fs::remove_file(&staged);
+            return Err(e);
+        }
+        info!(%file_name, "Restore staged for next launch");
+        Ok(backup)
+    }
+
+    /// Take a backup every `interval`, on a spawned task. The first one is
+    /// due `interval` after the newest existing backup, so frequent restarts
+    /// do not each add a backup.
+    pub fn spawn_periodic(
+        self: Arc<Self>,
+        db: DatabaseConnection,
+        interval: Duration,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let since_last = self
+                .list()
+                .ok()
+                .and_then(|backups| backups.first().map(|b| b.created_at))
+                .and_then(|last| (Utc::now() - last).to_std().ok());
+            let first_delay = since_last.map_or(Duration::ZERO, |age| interval.saturating_sub(age));
+            tokio::time::sleep(first_delay).await;
+            loop {
+                match self.create(&db).await {
+                    Ok(backup) => info!(file_name = %backup.file_name, "Periodic backup taken"),
+                    Err(e) => warn!(error = %e, "Periodic backup failed"),
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
+
+    /// Delete the oldest backups beyond the retention.
+    fn prune(&self) -> Result<(), OxidGeneError> {
+        for old in self.list()?.into_iter().skip(self.keep) {
+            std::fs::remove_file(self.dir.join(&old.file_name))?;
+            info!(file_name = %old.file_name, "Old backup removed");
+        }
+        Ok(())
+    }
+}
+
+/// Swap a restore staged by [`Backups::stage_restore`] in place of the
+/// database at `db_path`. Must run before the database is opened.
+///
+/// Returns `true` if a restore was applied; anything derived from the old
+/// database (such as the disk cache) is then stale.
+pub fn apply_pending_restore(db_path: &Path) -> std::io::Result<bool> {
+    let staged = restore_path(db_path);
+    if !staged.exists() {
+        return Ok(false);
+    }
+    // The write-ahead log belongs to the old database: replaying it over the
+    // restored file would corrupt it.
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = db_path.as_os_str().to_owned();
+        sidecar.push(suffix);
+        match std::fs::remove_file(PathBuf::from(sidecar)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+    std::fs::rename(&staged, db_path)?;
+    info!(db_path = %db_path.display(), "Staged restore applied");
+    Ok(true)
+}
+
+/// Path of the staged restore file for the database at `db_path`.
+fn restore_path(db_path: &Path) -> PathBuf {
+    let mut path = db_path.as_os_str().to_owned();
+    path.push(RESTORE_SUFFIX);
+    PathBuf::from(path)
+}
+
+/// Timestamp of a backup file name, or `None` if it is not a backup.
+fn parse_backup_name(file_name: &str) -> Option<DateTime<Utc>> {
+    let stamp = file_name
+        .strip_prefix(BACKUP_PREFIX)?
+        .strip_suffix(BACKUP_EXTENSION)?;
+    NaiveDateTime::parse_from_str(stamp, BACKUP_TIMESTAMP_FORMAT)
+        .ok()
+        .map(|dt| dt.and_utc())
+}
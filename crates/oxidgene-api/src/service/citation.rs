@@ -0,0 +1,41 @@
+//! Checks shared by the REST and GraphQL citation handlers.
+
+use oxidgene_core::OxidGeneError;
+use oxidgene_core::enums::FactType;
+use oxidgene_db::repo::PersonNameRepo;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+/// Check that a citation's `fact_type`/`fact_ref` point at a fact of the
+/// entity it cites: a name citation needs the person and one of their
+/// `PersonName`s, an event-field citation needs the event and no
+/// `fact_ref`.
+pub async fn validate_fact_target(
+    db: &DatabaseConnection,
+    person_id: Option<Uuid>,
+    event_id: Option<Uuid>,
+    fact_type: Option<FactType>,
+    fact_ref: Option<Uuid>,
+) -> Result<(), OxidGeneError> {
+    let invalid = |message: &str| Err(OxidGeneError::Validation(message.to_string()));
+    match (fact_type, fact_ref) {
+        (None, None) => Ok(()),
+        (None, Some(_)) => invalid("fact_ref requires a fact_type"),
+        (Some(FactType::Name), None) => invalid("a name citation needs fact_ref: the name id"),
+        (Some(FactType::Name), Some(name_id)) => {
+            let Some(person_id) = person_id else {
+                return invalid("a name citation needs person_id");
+            };
+            let name = PersonNameRepo::get(db, name_id).await?;
+            if name.person_id != person_id {
+                return invalid("fact_ref is not a name of this person");
+            }
+            Ok(())
+        }
+        (Some(fact), _) if fact.is_event_field() && event_id.is_none() => {
+            invalid("an event field citation needs event_id")
+        }
+        (Some(_), Some(_)) => invalid("fact_ref is only used by name citations"),
+        (Some(_), None) => Ok(()),
+    }
+}
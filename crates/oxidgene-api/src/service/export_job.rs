@@ -0,0 +1,125 @@
+//! Background GEDCOM export jobs, their progress reporting and their files.
+//!
+//! The REST endpoint starts a job on the [`super::job`] registry and returns
+//! its id right away; the export runs on a spawned task publishing
+//! [`ExportProgress`] snapshots, which clients follow over Server-Sent Events
+//! (see `rest::gedcom::export_events_handler`).
+//!
+//! The finished file is written to a temporary file kept with the job, for
+//! the client to download once the `done` event arrives. It is deleted when
+//! the job is pruned.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tempfile::TempPath;
+
+use super::job::{JobProgress, JobTracker, Jobs};
+
+/// Registry of export jobs, each keeping its file once done.
+pub type ExportJobs = Jobs<ExportProgress, ExportArtifact>;
+
+/// Write side of an export job's progress.
+pub type ExportTracker = JobTracker<ExportProgress, ExportArtifact>;
+
+/// Stage an export job is currently in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportPhase {
+    Queued,
+    Loading,
+    Serializing,
+    /// Writing the GEDZIP archive.
+    Packaging,
+    Done,
+    Failed,
+}
+
+/// The file produced by an export job.
+#[derive(Debug)]
+pub struct ExportArtifact {
+    pub file_name: String,
+    pub content_type: &'static str,
+    /// Where the file was written; deleted when the artifact is dropped.
+    pub path: TempPath,
+    pub size_bytes: usize,
+    pub warnings: Vec<String>,
+}
+
+/// What the `done` event reports about the file.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportSummary {
+    pub file_name: String,
+    pub size_bytes: usize,
+    pub warnings: Vec<String>,
+}
+
+/// Snapshot of an export job's progress.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportProgress {
+    pub phase: ExportPhase,
+    /// Number of GEDCOM records to write, known once the tree is loaded.
+    pub records_total: usize,
+    pub records_serialized: usize,
+    /// Set once the job is done.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<ExportSummary>,
+    /// Set if the job failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl JobProgress for ExportProgress {
+    fn queued() -> Self {
+        Self {
+            phase: ExportPhase::Queued,
+            records_total: 0,
+            records_serialized: 0,
+            summary: None,
+            error: None,
+            finished_at: None,
+        }
+    }
+
+    fn finished_at(&self) -> Option<DateTime<Utc>> {
+        self.finished_at
+    }
+}
+
+impl ExportTracker {
+    pub fn set_phase(&self, phase: ExportPhase) {
+        self.update(|p| p.phase = phase);
+    }
+
+    pub fn set_total(&self, total: usize) {
+        self.update(|p| p.records_total = total);
+    }
+
+    pub fn add_serialized(&self, count: usize) {
+        self.update(|p| p.records_serialized += count);
+    }
+
+    /// Store the file, then report the job done.
+    pub fn finish(&self, artifact: ExportArtifact) {
+        let summary = ExportSummary {
+            file_name: artifact.file_name.clone(),
+            size_bytes: artifact.size_bytes,
+            warnings: artifact.warnings.clone(),
+        };
+        self.set_output(artifact);
+        self.update(|p| {
+            p.phase = ExportPhase::Done;
+            p.summary = Some(summary);
+            p.finished_at = Some(Utc::now());
+        });
+    }
+
+    pub fn fail(&self, error: String) {
+        self.update(|p| {
+            p.phase = ExportPhase::Failed;
+            p.error = Some(error);
+            p.finished_at = Some(Utc::now());
+        });
+    }
+}
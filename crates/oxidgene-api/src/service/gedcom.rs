@@ -3,60 +3,150 @@
 //! Extracted so both REST and GraphQL handlers can reuse the same
 //! persist-all-entities and load-all-entities workflows.
 
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
 use chrono::Utc;
-use oxidgene_core::OxidGeneError;
+use oxidgene_core::fuzzy::{given_phonetic, surname_phonetic};
+use oxidgene_core::text::normalize_name;
+use oxidgene_core::types::{
+    Citation, Event, EventParticipant, ExternalId, Family, FamilyChild, FamilySpouse, Media,
+    MediaLink, Note, Person, PersonName, Place, Source,
+};
+use oxidgene_core::{ExternalIdKind, OxidGeneError};
 use oxidgene_db::entities::{
-    citation, event, event_witness, family, family_child, family_spouse, media, media_link, note,
-    person, person_ancestry, person_name, place, sea_enums, source,
+    citation, event, event_participant, external_id, family, family_child, family_spouse, media,
+    media_link, note, person, person_ancestry, person_name, place, sea_enums, source,
 };
 use oxidgene_db::repo::{
-    CitationRepo, EventRepo, EventWitnessRepo, FamilyChildRepo, FamilyRepo, FamilySpouseRepo,
-    MediaLinkRepo, MediaRepo, NoteRepo, PersonNameRepo, PersonRepo, PlaceRepo, SourceRepo,
-    TreeRepo,
+    CitationRepo, EventParticipantRepo, EventRepo, ExternalIdRepo, FamilyChildRepo, FamilyRepo,
+    FamilySpouseRepo, MediaLinkRepo, MediaRepo, NoteRepo, PersonAncestryRepo, PersonNameRepo,
+    PersonRepo, PlaceRepo, SourceRepo, TreeRepo, coordinates_match, normalize_place_name,
 };
 use oxidgene_gedcom::import::import_gedcom;
-use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set, TransactionTrait};
+use oxidgene_gedcom::{ExportOptions, ExportResult, ImportResult};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    Set, TransactionTrait,
+};
+use serde::Serialize;
+use tempfile::TempPath;
 use uuid::Uuid;
 
+use super::export_job::{ExportArtifact, ExportPhase, ExportTracker};
+use super::import_job::{ImportPhase, ImportTracker};
+
 /// Maximum number of rows per `insert_many` batch.
 ///
 /// SQLite has a variable limit of ~999; with 7 columns per row that's ~142 rows.
 /// We use 100 as a safe default that works for all entity shapes.
 const BATCH_SIZE: usize = 100;
 
+/// Number of records an export serializes between two progress updates.
+const EXPORT_PROGRESS_STEP: usize = 100;
+
 /// Summary returned after a GEDCOM import.
+///
+/// Every count is the number of rows the import inserts: places merged into
+/// an existing or earlier place of the same name are not counted.
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ImportSummary {
     pub persons_count: usize,
+    pub person_names_count: usize,
     pub families_count: usize,
     pub events_count: usize,
     pub sources_count: usize,
+    pub citations_count: usize,
     pub media_count: usize,
     pub places_count: usize,
     pub notes_count: usize,
     pub warnings: Vec<String>,
 }
 
+impl From<&ImportResult> for ImportSummary {
+    fn from(result: &ImportResult) -> Self {
+        Self {
+            persons_count: result.persons.len(),
+            person_names_count: result.person_names.len(),
+            families_count: result.families.len(),
+            events_count: result.events.len(),
+            sources_count: result.sources.len(),
+            citations_count: result.citations.len(),
+            media_count: result.media.len(),
+            places_count: result.places.len(),
+            notes_count: result.notes.len(),
+            warnings: result.warnings.clone(),
+        }
+    }
+}
+
+/// What importing a GEDCOM file into a tree would do, matched against the
+/// tree exactly as the import does before writing. Imports only add
+/// records: existing ones are never modified.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImportPlan {
+    /// Records the import would create, and its warnings.
+    #[serde(flatten)]
+    pub summary: ImportSummary,
+    /// Existing places the file's places would be merged into, by name.
+    pub reused_places: Vec<String>,
+    /// Imported records whose `_UID` the tree already holds: the import
+    /// adds them a second time.
+    pub conflicts: Vec<ImportConflict>,
+}
+
+/// An imported person or family whose `_UID` matches a record of the tree.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImportConflict {
+    pub uid: String,
+    /// The tree's person or family holding the `_UID`.
+    pub existing_person_id: Option<Uuid>,
+    pub existing_family_id: Option<Uuid>,
+    /// Primary name of the imported person; `None` for a family.
+    pub name: Option<String>,
+}
+
 /// Result returned after a GEDCOM export.
 pub struct ExportData {
     pub gedcom: String,
     pub warnings: Vec<String>,
 }
 
+/// Approximate size of a tree's GEDCOM export: its live top-level records.
+/// `records_total` is the denominator of an export job's progress (exact
+/// for a whole-tree export, an upper bound for a branch).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ExportEstimate {
+    pub persons: u64,
+    pub families: u64,
+    pub sources: u64,
+    pub media: u64,
+    pub records_total: u64,
+}
+
 /// Insert a batch of active models using `insert_many`, chunked to stay within
-/// SQLite's variable limit.
+/// SQLite's variable limit. Each inserted chunk is reported to `progress`.
+///
+/// Fails before the next chunk once the job is cancelled, so the caller's
+/// transaction is rolled back.
 async fn batch_insert<E, A>(
     txn: &impl sea_orm::ConnectionTrait,
     models: Vec<A>,
+    progress: &ImportTracker,
 ) -> Result<(), OxidGeneError>
 where
     E: EntityTrait,
     A: ActiveModelTrait<Entity = E> + Send + 'static,
 {
     for chunk in models.chunks(BATCH_SIZE) {
+        if progress.is_cancelled() {
+            return Err(OxidGeneError::Conflict("import cancelled".to_string()));
+        }
         E::insert_many(chunk.to_vec())
             .exec(txn)
             .await
             .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        progress.add_persisted(chunk.len());
     }
     Ok(())
 }
@@ -67,25 +157,117 @@ where
 /// performance. Entities are inserted in FK-safe order: places → sources →
 /// media → persons → person_names → families → family_spouses →
 /// family_children → events → citations → media_links → notes →
-/// person_ancestry.
+/// external_ids → person_ancestry.
 pub async fn import_and_persist(
     db: &DatabaseConnection,
     tree_id: Uuid,
     gedcom_str: &str,
+) -> Result<ImportSummary, OxidGeneError> {
+    import_and_persist_with_progress(db, tree_id, gedcom_str, &ImportTracker::detached()).await
+}
+
+/// Same as [`import_and_persist`], reporting parse/persist progress through
+/// `progress` as it goes. The terminal phase is left to the caller, which
+/// may still have a cache rebuild to run.
+pub async fn import_and_persist_with_progress(
+    db: &DatabaseConnection,
+    tree_id: Uuid,
+    gedcom_str: &str,
+    progress: &ImportTracker,
 ) -> Result<ImportSummary, OxidGeneError> {
     // Verify tree exists
     let _tree = TreeRepo::get(db, tree_id).await?;
 
-    // Parse GEDCOM
-    let result = import_gedcom(gedcom_str, tree_id).map_err(OxidGeneError::Gedcom)?;
+    progress.set_phase(ImportPhase::Parsing);
+    let (result, plan, located) = prepare_import(db, tree_id, gedcom_str, progress).await?;
 
-    let now = Utc::now();
+    progress.set_total(record_count(&result));
+    progress.set_phase(ImportPhase::Persisting);
 
     // Start a transaction for atomicity
     let txn = db
         .begin()
         .await
         .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+    insert_all(&txn, &result, progress).await?;
+    for place in &located {
+        PlaceRepo::fill_coordinates(&txn, place.id, place.latitude, place.longitude).await?;
+    }
+    if progress.is_cancelled() {
+        // Dropping the transaction rolls it back.
+        return Err(OxidGeneError::Conflict("import cancelled".to_string()));
+    }
+
+    // Commit the transaction
+    txn.commit()
+        .await
+        .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+
+    Ok(plan.summary)
+}
+
+/// What importing `gedcom_str` into the tree would do, without writing
+/// anything: the same parse and matching as [`import_and_persist`].
+pub async fn preview_import(
+    db: &DatabaseConnection,
+    tree_id: Uuid,
+    gedcom_str: &str,
+) -> Result<ImportPlan, OxidGeneError> {
+    let _tree = TreeRepo::get(db, tree_id).await?;
+    let (_, plan, _) = prepare_import(db, tree_id, gedcom_str, &ImportTracker::detached()).await?;
+    Ok(plan)
+}
+
+/// Parse a GEDCOM string and match it against the tree: places reuse
+/// existing ones (or repeated ones in the file) instead of inserting a new
+/// row per occurrence, and `_UID`s already in the tree are reported.
+/// Also returns the existing places that gain coordinates from the file.
+///
+/// Parsing runs on a blocking thread; the records parsed and the warnings
+/// are reported through `progress` as each step produces them.
+async fn prepare_import(
+    db: &DatabaseConnection,
+    tree_id: Uuid,
+    gedcom_str: &str,
+    progress: &ImportTracker,
+) -> Result<(ImportResult, ImportPlan, Vec<Place>), OxidGeneError> {
+    let gedcom = gedcom_str.to_owned();
+    let mut result = tokio::task::spawn_blocking(move || import_gedcom(&gedcom, tree_id))
+        .await
+        .map_err(|e| OxidGeneError::Gedcom(format!("import task failed: {e}")))?
+        .map_err(OxidGeneError::Gedcom)?;
+    progress.set_parsed(record_count(&result));
+    progress.add_warnings(&result.warnings);
+    let parse_warnings = result.warnings.len();
+
+    let existing_places = PlaceRepo::list_all(db, tree_id).await?;
+    let (reused_places, located) = reuse_places_by_name(&mut result, &existing_places);
+    let conflicts = known_records(db, tree_id, &result).await?;
+    if !conflicts.is_empty() {
+        result.warnings.push(format!(
+            "{} imported records have a _UID already present in this tree; they were added again",
+            conflicts.len()
+        ));
+    }
+    progress.add_warnings(&result.warnings[parse_warnings..]);
+
+    let plan = ImportPlan {
+        summary: ImportSummary::from(&result),
+        reused_places,
+        conflicts,
+    };
+    Ok((result, plan, located))
+}
+
+/// Insert every entity of `result` through `txn`, batched, in FK-safe order.
+/// Shared by GEDCOM imports and tree templates; the caller owns the
+/// transaction.
+pub(crate) async fn insert_all(
+    txn: &impl sea_orm::ConnectionTrait,
+    result: &ImportResult,
+    progress: &ImportTracker,
+) -> Result<(), OxidGeneError> {
+    let now = Utc::now();
 
     // 1. Places (no FKs to other imported entities)
     if !result.places.is_empty() {
@@ -100,9 +282,10 @@ pub async fn import_and_persist(
                 longitude: Set(p.longitude),
                 created_at: Set(now),
                 updated_at: Set(now),
+                version: Set(1),
             })
             .collect();
-        batch_insert::<place::Entity, _>(&txn, models).await?;
+        batch_insert::<place::Entity, _>(txn, models, progress).await?;
     }
 
     // 2. Sources (no FKs to other imported entities)
@@ -120,10 +303,11 @@ pub async fn import_and_persist(
                 repository_name: Set(s.repository_name.clone()),
                 created_at: Set(now),
                 updated_at: Set(now),
+                version: Set(1),
                 deleted_at: Set(None),
             })
             .collect();
-        batch_insert::<source::Entity, _>(&txn, models).await?;
+        batch_insert::<source::Entity, _>(txn, models, progress).await?;
     }
 
     // 3. Media (no FKs to other imported entities)
@@ -145,14 +329,22 @@ pub async fn import_and_persist(
                 place_id: Set(m.place_id),
                 created_at: Set(now),
                 updated_at: Set(now),
+                version: Set(1),
                 deleted_at: Set(None),
             })
             .collect();
-        batch_insert::<media::Entity, _>(&txn, models).await?;
+        batch_insert::<media::Entity, _>(txn, models, progress).await?;
     }
 
     // 4. Persons (FK → tree)
     if !result.persons.is_empty() {
+        let mut names_by_person: HashMap<Uuid, Vec<PersonName>> = HashMap::new();
+        for name in &result.person_names {
+            names_by_person
+                .entry(name.person_id)
+                .or_default()
+                .push(name.clone());
+        }
         let models: Vec<person::ActiveModel> = result
             .persons
             .iter()
@@ -161,12 +353,18 @@ pub async fn import_and_persist(
                 tree_id: Set(p.tree_id),
                 sex: Set(sea_enums::Sex::from(p.sex)),
                 privacy: Set(sea_enums::Privacy::from(p.privacy)),
+                living: Set(p.living),
+                display_name: Set(names_by_person
+                    .get(&p.id)
+                    .and_then(|names| PersonName::display_name_of(names))),
+                locked: Set(p.locked),
                 created_at: Set(now),
                 updated_at: Set(now),
+                version: Set(1),
                 deleted_at: Set(None),
             })
             .collect();
-        batch_insert::<person::Entity, _>(&txn, models).await?;
+        batch_insert::<person::Entity, _>(txn, models, progress).await?;
     }
 
     // 5. Person names (FK → person)
@@ -180,6 +378,10 @@ pub async fn import_and_persist(
                 name_type: Set(sea_enums::NameType::from(pn.name_type)),
                 given_names: Set(pn.given_names.clone()),
                 surname: Set(pn.surname.clone()),
+                surname_phonetic: Set(pn.surname.as_deref().and_then(surname_phonetic)),
+                given_phonetic: Set(pn.given_names.as_deref().and_then(given_phonetic)),
+                surname_norm: Set(pn.surname.as_deref().and_then(normalize_name)),
+                given_norm: Set(pn.given_names.as_deref().and_then(normalize_name)),
                 prefix: Set(pn.prefix.clone()),
                 suffix: Set(pn.suffix.clone()),
                 nickname: Set(pn.nickname.clone()),
@@ -188,7 +390,7 @@ pub async fn import_and_persist(
                 updated_at: Set(now),
             })
             .collect();
-        batch_insert::<person_name::Entity, _>(&txn, models).await?;
+        batch_insert::<person_name::Entity, _>(txn, models, progress).await?;
     }
 
     // 6. Families (FK → tree)
@@ -204,7 +406,7 @@ pub async fn import_and_persist(
                 deleted_at: Set(None),
             })
             .collect();
-        batch_insert::<family::Entity, _>(&txn, models).await?;
+        batch_insert::<family::Entity, _>(txn, models, progress).await?;
     }
 
     // 7. Family spouses (FK → family, person)
@@ -220,7 +422,7 @@ pub async fn import_and_persist(
                 sort_order: Set(fs.sort_order),
             })
             .collect();
-        batch_insert::<family_spouse::Entity, _>(&txn, models).await?;
+        batch_insert::<family_spouse::Entity, _>(txn, models, progress).await?;
     }
 
     // 8. Family children (FK → family, person)
@@ -236,7 +438,7 @@ pub async fn import_and_persist(
                 sort_order: Set(fc.sort_order),
             })
             .collect();
-        batch_insert::<family_child::Entity, _>(&txn, models).await?;
+        batch_insert::<family_child::Entity, _>(txn, models, progress).await?;
     }
 
     // 9. Events (FK → tree, person?, family?, place?)
@@ -254,32 +456,36 @@ pub async fn import_and_persist(
                 date_value2: Set(e.date_value2.clone()),
                 calendar: Set(sea_enums::Calendar::from(e.calendar)),
                 cause: Set(e.cause.clone()),
+                age: Set(e.age.clone()),
                 place_id: Set(e.place_id),
+                place_id_to: Set(e.place_id_to),
                 person_id: Set(e.person_id),
                 family_id: Set(e.family_id),
                 description: Set(e.description.clone()),
                 created_at: Set(now),
                 updated_at: Set(now),
+                version: Set(1),
                 deleted_at: Set(None),
             })
             .collect();
-        batch_insert::<event::Entity, _>(&txn, models).await?;
+        batch_insert::<event::Entity, _>(txn, models, progress).await?;
     }
 
-    // 9b. Event witnesses (FK → event, person)
-    if !result.event_witnesses.is_empty() {
-        let models: Vec<event_witness::ActiveModel> = result
-            .event_witnesses
+    // 9b. Event participants (FK → event, person)
+    if !result.event_participants.is_empty() {
+        let models: Vec<event_participant::ActiveModel> = result
+            .event_participants
             .iter()
-            .map(|w| event_witness::ActiveModel {
+            .map(|w| event_participant::ActiveModel {
                 id: Set(w.id),
                 event_id: Set(w.event_id),
                 person_id: Set(w.person_id),
+                role: Set(sea_enums::ParticipantRole::from(w.role)),
                 relation: Set(w.relation.clone()),
                 sort_order: Set(w.sort_order),
             })
             .collect();
-        batch_insert::<event_witness::Entity, _>(&txn, models).await?;
+        batch_insert::<event_participant::Entity, _>(txn, models, progress).await?;
     }
 
     // 10. Citations (FK → source, person?, event?, family?)
@@ -296,11 +502,13 @@ pub async fn import_and_persist(
                 page: Set(c.page.clone()),
                 confidence: Set(sea_enums::Confidence::from(c.confidence)),
                 text: Set(c.text.clone()),
+                fact_type: Set(c.fact_type.map(sea_enums::FactType::from)),
+                fact_ref: Set(c.fact_ref),
                 created_at: Set(now),
                 updated_at: Set(now),
             })
             .collect();
-        batch_insert::<citation::Entity, _>(&txn, models).await?;
+        batch_insert::<citation::Entity, _>(txn, models, progress).await?;
     }
 
     // 11. Media links (FK → media, person?, event?, source?, family?)
@@ -319,7 +527,7 @@ pub async fn import_and_persist(
                 is_profile: Set(ml.is_profile),
             })
             .collect();
-        batch_insert::<media_link::Entity, _>(&txn, models).await?;
+        batch_insert::<media_link::Entity, _>(txn, models, progress).await?;
     }
 
     // 12. Notes (FK → tree, person?, event?, family?, source?)
@@ -331,19 +539,40 @@ pub async fn import_and_persist(
                 id: Set(n.id),
                 tree_id: Set(n.tree_id),
                 text: Set(n.text.clone()),
+                format: Set(sea_enums::NoteFormat::from(n.format)),
                 person_id: Set(n.person_id),
                 event_id: Set(n.event_id),
                 family_id: Set(n.family_id),
                 source_id: Set(n.source_id),
                 created_at: Set(now),
                 updated_at: Set(now),
+                version: Set(1),
                 deleted_at: Set(None),
             })
             .collect();
-        batch_insert::<note::Entity, _>(&txn, models).await?;
+        batch_insert::<note::Entity, _>(txn, models, progress).await?;
+    }
+
+    // 13. External ids (FK → tree, person?, family?)
+    if !result.external_ids.is_empty() {
+        let models: Vec<external_id::ActiveModel> = result
+            .external_ids
+            .iter()
+            .map(|e| external_id::ActiveModel {
+                id: Set(e.id),
+                tree_id: Set(e.tree_id),
+                person_id: Set(e.person_id),
+                family_id: Set(e.family_id),
+                kind: Set(sea_enums::ExternalIdKind::from(e.kind)),
+                value: Set(e.value.clone()),
+                ref_type: Set(e.ref_type.clone()),
+                created_at: Set(now),
+            })
+            .collect();
+        batch_insert::<external_id::Entity, _>(txn, models, progress).await?;
     }
 
-    // 13. Person ancestry closure table
+    // 14. Person ancestry closure table
     if !result.person_ancestry.is_empty() {
         let models: Vec<person_ancestry::ActiveModel> = result
             .person_ancestry
@@ -356,39 +585,398 @@ pub async fn import_and_persist(
                 depth: Set(pa.depth),
             })
             .collect();
-        batch_insert::<person_ancestry::Entity, _>(&txn, models).await?;
+        batch_insert::<person_ancestry::Entity, _>(txn, models, progress).await?;
     }
 
-    // Commit the transaction
-    txn.commit()
-        .await
-        .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+    Ok(())
+}
 
-    Ok(ImportSummary {
-        persons_count: result.persons.len(),
-        families_count: result.families.len(),
-        events_count: result.events.len(),
-        sources_count: result.sources.len(),
-        media_count: result.media.len(),
-        places_count: result.places.len(),
-        notes_count: result.notes.len(),
-        warnings: result.warnings,
-    })
+/// Imported records carrying a `_UID` the tree already holds, one per
+/// record, by `_UID`: the file (or an earlier version of it) was imported
+/// before, and importing adds those records a second time.
+async fn known_records(
+    db: &DatabaseConnection,
+    tree_id: Uuid,
+    result: &ImportResult,
+) -> Result<Vec<ImportConflict>, OxidGeneError> {
+    let uids: Vec<String> = result
+        .external_ids
+        .iter()
+        .filter(|e| e.kind == ExternalIdKind::Uid)
+        .map(|e| e.value.clone())
+        .collect();
+    let mut known: HashMap<String, (Option<Uuid>, Option<Uuid>)> = HashMap::new();
+    for e in ExternalIdRepo::find_by_values(db, tree_id, &uids).await? {
+        if e.kind == ExternalIdKind::Uid {
+            known.entry(e.value).or_insert((e.person_id, e.family_id));
+        }
+    }
+
+    let mut seen: HashSet<(Option<Uuid>, Option<Uuid>)> = HashSet::new();
+    let mut conflicts: Vec<ImportConflict> = result
+        .external_ids
+        .iter()
+        .filter(|e| e.kind == ExternalIdKind::Uid)
+        .filter_map(|e| Some((e, *known.get(&e.value)?)))
+        .filter(|(e, _)| seen.insert((e.person_id, e.family_id)))
+        .map(
+            |(e, (existing_person_id, existing_family_id))| ImportConflict {
+                uid: e.value.clone(),
+                existing_person_id,
+                existing_family_id,
+                name: e.person_id.and_then(|pid| {
+                    result
+                        .person_names
+                        .iter()
+                        .find(|n| n.person_id == pid && n.is_primary)
+                        .map(PersonName::display_name)
+                }),
+            },
+        )
+        .collect();
+    conflicts.sort_by(|a, b| a.uid.cmp(&b.uid));
+    Ok(conflicts)
 }
 
-/// Load all entities from a tree and export them as a GEDCOM string.
+/// Number of rows an import will insert, across all entity tables.
+fn record_count(result: &ImportResult) -> usize {
+    result.places.len()
+        + result.sources.len()
+        + result.media.len()
+        + result.persons.len()
+        + result.person_names.len()
+        + result.families.len()
+        + result.family_spouses.len()
+        + result.family_children.len()
+        + result.events.len()
+        + result.event_participants.len()
+        + result.citations.len()
+        + result.media_links.len()
+        + result.notes.len()
+        + result.external_ids.len()
+        + result.person_ancestry.len()
+}
+
+/// Collapse imported places onto existing ones sharing the same normalized
+/// name (see [`normalize_place_name`]) and compatible coordinates (see
+/// [`coordinates_match`]), the rule [`PlaceRepo::dedupe`] merges by:
+/// matching places are dropped from `result.places` and every event/media
+/// reference is repointed to the place kept. Duplicates within the file
+/// itself collapse onto their first occurrence. A place kept inherits
+/// coordinates it was missing.
 ///
-/// Verifies the tree exists, loads all entities, then calls the GEDCOM
-/// exporter to produce the output string. `merge_occupations` collapses each
-/// person's multiple `OCCU` tags back into one (see
-/// `oxidgene_gedcom::export::export_gedcom`).
-pub async fn load_and_export(
+/// Returns the names of the existing places reused, sorted, and the existing
+/// places that inherited coordinates from the file.
+fn reuse_places_by_name(
+    result: &mut ImportResult,
+    existing: &[Place],
+) -> (Vec<String>, Vec<Place>) {
+    let existing_ids: HashSet<Uuid> = existing.iter().map(|p| p.id).collect();
+    let mut reused: Vec<String> = Vec::new();
+    let mut located: HashSet<Uuid> = HashSet::new();
+    // Places an imported place may collapse onto, by normalized name: the
+    // tree's, then the file's kept so far.
+    let mut by_name: HashMap<String, Vec<Place>> = HashMap::new();
+    for place in existing {
+        by_name
+            .entry(normalize_place_name(&place.name))
+            .or_default()
+            .push(place.clone());
+    }
+    let mut remap: HashMap<Uuid, Uuid> = HashMap::new();
+    let mut kept: Vec<Uuid> = Vec::with_capacity(result.places.len());
+
+    for place in std::mem::take(&mut result.places) {
+        let candidates = by_name
+            .entry(normalize_place_name(&place.name))
+            .or_default();
+        let target = candidates.iter_mut().find(|c| {
+            coordinates_match((c.latitude, c.longitude), (place.latitude, place.longitude))
+        });
+        let Some(target) = target else {
+            kept.push(place.id);
+            candidates.push(place);
+            continue;
+        };
+        if target.latitude.is_none()
+            && target.longitude.is_none()
+            && (place.latitude.is_some() || place.longitude.is_some())
+        {
+            target.latitude = place.latitude;
+            target.longitude = place.longitude;
+            if existing_ids.contains(&target.id) {
+                located.insert(target.id);
+            }
+        }
+        if existing_ids.contains(&target.id) {
+            reused.push(target.name.clone());
+        }
+        remap.insert(place.id, target.id);
+    }
+
+    let mut places: HashMap<Uuid, Place> =
+        by_name.into_values().flatten().map(|p| (p.id, p)).collect();
+    result.places = kept.iter().filter_map(|id| places.remove(id)).collect();
+    let mut located: Vec<Place> = located.iter().filter_map(|id| places.remove(id)).collect();
+    located.sort_by(|a, b| a.name.cmp(&b.name));
+    reused.sort();
+    reused.dedup();
+
+    if remap.is_empty() {
+        return (reused, located);
+    }
+    let repoint = |place_id: &mut Option<Uuid>| {
+        if let Some(target) = place_id.and_then(|id| remap.get(&id)) {
+            *place_id = Some(*target);
+        }
+    };
+    result.events.iter_mut().for_each(|e| {
+        repoint(&mut e.place_id);
+        repoint(&mut e.place_id_to);
+    });
+    result
+        .media
+        .iter_mut()
+        .for_each(|m| repoint(&mut m.place_id));
+    (reused, located)
+}
+
+/// Restricts an export to part of a tree: chosen persons, expanded with
+/// their ancestors, descendants and spouses.
+#[derive(Debug, Clone)]
+pub struct ExportSubset {
+    pub person_ids: Vec<Uuid>,
+    pub ancestors: Reach,
+    pub descendants: Reach,
+    /// Also add the spouses of every selected person (after expanding to
+    /// ancestors and descendants).
+    pub include_spouses: bool,
+}
+
+impl ExportSubset {
+    /// One branch: `person_id` with their ancestors and, optionally, their
+    /// descendants, up to `generations` in each direction.
+    pub fn branch(person_id: Uuid, generations: i32, include_descendants: bool) -> Self {
+        let reach = Reach::Generations(generations);
+        Self {
+            person_ids: vec![person_id],
+            ancestors: reach,
+            descendants: if include_descendants {
+                reach
+            } else {
+                Reach::None
+            },
+            include_spouses: false,
+        }
+    }
+}
+
+/// How far an [`ExportSubset`] extends from its persons in one direction.
+/// There is no unbounded reach: "all generations" is resolved to the
+/// configured maximum (see [`ApiConfig::ancestry_depth`]) by the caller.
+///
+/// [`ApiConfig::ancestry_depth`]: crate::config::ApiConfig::ancestry_depth
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reach {
+    None,
+    Generations(i32),
+}
+
+impl Reach {
+    /// The ancestry closure depth to query, `None` when not extending.
+    fn max_depth(self) -> Option<i32> {
+        match self {
+            Self::None => None,
+            Self::Generations(n) => Some(n),
+        }
+    }
+}
+
+/// Everything a GEDCOM export writes; also the JSON export format.
+#[derive(Debug, Serialize)]
+pub struct ExportRecords {
+    persons: Vec<Person>,
+    person_names: Vec<PersonName>,
+    families: Vec<Family>,
+    family_spouses: Vec<FamilySpouse>,
+    family_children: Vec<FamilyChild>,
+    events: Vec<Event>,
+    event_participants: Vec<EventParticipant>,
+    places: Vec<Place>,
+    sources: Vec<Source>,
+    citations: Vec<Citation>,
+    media: Vec<Media>,
+    media_links: Vec<MediaLink>,
+    notes: Vec<Note>,
+    external_ids: Vec<ExternalId>,
+}
+
+impl ExportRecords {
+    /// Number of top-level GEDCOM records the export writes: sources,
+    /// media, individuals and families.
+    pub fn record_count(&self) -> usize {
+        self.sources.len() + self.media.len() + self.persons.len() + self.families.len()
+    }
+
+    /// Keep only the records of `selected` persons: their names, events
+    /// and the families they are a spouse in, plus the places, sources,
+    /// media, notes and external ids those refer to.
+    ///
+    /// Family members outside the selection are dropped from the family
+    /// links (with a warning each) so the export has no dangling xrefs.
+    fn retain_persons(&mut self, selected: &HashSet<Uuid>, warnings: &mut Vec<String>) {
+        self.persons.retain(|p| selected.contains(&p.id));
+        self.person_names
+            .retain(|n| selected.contains(&n.person_id));
+
+        let families: HashSet<Uuid> = self
+            .family_spouses
+            .iter()
+            .filter(|s| selected.contains(&s.person_id))
+            .map(|s| s.family_id)
+            .collect();
+        self.families.retain(|f| families.contains(&f.id));
+        self.family_spouses
+            .retain(|s| families.contains(&s.family_id));
+        self.family_children
+            .retain(|c| families.contains(&c.family_id));
+        for (person_id, family_id, link) in self
+            .family_spouses
+            .iter()
+            .map(|s| (s.person_id, s.family_id, "spouse"))
+            .chain(
+                self.family_children
+                    .iter()
+                    .map(|c| (c.person_id, c.family_id, "child")),
+            )
+            .filter(|(person_id, ..)| !selected.contains(person_id))
+        {
+            warnings.push(format!(
+                "Person {person_id} is outside the export: dropped as {link} of family {family_id}"
+            ));
+        }
+        self.family_spouses
+            .retain(|s| selected.contains(&s.person_id));
+        self.family_children
+            .retain(|c| selected.contains(&c.person_id));
+
+        self.events.retain(|e| match (e.person_id, e.family_id) {
+            (Some(person_id), _) => selected.contains(&person_id),
+            (None, Some(family_id)) => families.contains(&family_id),
+            (None, None) => false,
+        });
+        let events: HashSet<Uuid> = self.events.iter().map(|e| e.id).collect();
+        self.event_participants.retain(|p| {
+            let keep = events.contains(&p.event_id) && selected.contains(&p.person_id);
+            if !keep && events.contains(&p.event_id) {
+                warnings.push(format!(
+                    "Person {} is outside the export: dropped as participant of event {}",
+                    p.person_id, p.event_id
+                ));
+            }
+            keep
+        });
+        let places: HashSet<Uuid> = self
+            .events
+            .iter()
+            .flat_map(|e| [e.place_id, e.place_id_to])
+            .flatten()
+            .collect();
+        self.places.retain(|p| places.contains(&p.id));
+
+        let attached =
+            |person_id: Option<Uuid>, event_id: Option<Uuid>, family_id: Option<Uuid>| {
+                person_id.is_some_and(|id| selected.contains(&id))
+                    || event_id.is_some_and(|id| events.contains(&id))
+                    || family_id.is_some_and(|id| families.contains(&id))
+            };
+        self.citations
+            .retain(|c| attached(c.person_id, c.event_id, c.family_id));
+        let sources: HashSet<Uuid> = self.citations.iter().map(|c| c.source_id).collect();
+        self.sources.retain(|s| sources.contains(&s.id));
+
+        self.media_links.retain(|l| {
+            attached(l.person_id, l.event_id, l.family_id)
+                || l.source_id.is_some_and(|id| sources.contains(&id))
+        });
+        let media: HashSet<Uuid> = self.media_links.iter().map(|l| l.media_id).collect();
+        self.media.retain(|m| media.contains(&m.id));
+
+        self.notes.retain(|n| {
+            attached(n.person_id, n.event_id, n.family_id)
+                || n.source_id.is_some_and(|id| sources.contains(&id))
+        });
+        self.external_ids
+            .retain(|e| attached(e.person_id, None, e.family_id));
+    }
+}
+
+/// Persons an [`ExportSubset`] selects, from the ancestry closure and the
+/// spouse links.
+async fn subset_persons(
+    db: &DatabaseConnection,
+    subset: &ExportSubset,
+) -> Result<HashSet<Uuid>, OxidGeneError> {
+    let mut selected: HashSet<Uuid> = subset.person_ids.iter().copied().collect();
+    for &person_id in &subset.person_ids {
+        if let Some(depth) = subset.ancestors.max_depth() {
+            for row in PersonAncestryRepo::ancestors(db, person_id, depth).await? {
+                selected.insert(row.ancestor_id);
+            }
+        }
+        if let Some(depth) = subset.descendants.max_depth() {
+            for row in PersonAncestryRepo::descendants(db, person_id, depth).await? {
+                selected.insert(row.descendant_id);
+            }
+        }
+    }
+    if subset.include_spouses {
+        let person_ids: Vec<Uuid> = selected.iter().copied().collect();
+        let family_ids: Vec<Uuid> = FamilySpouseRepo::list_by_persons(db, &person_ids)
+            .await?
+            .into_iter()
+            .map(|s| s.family_id)
+            .collect();
+        for spouse in FamilySpouseRepo::list_by_families(db, &family_ids).await? {
+            selected.insert(spouse.person_id);
+        }
+    }
+    Ok(selected)
+}
+
+/// Load the records of a tree to export, with the warnings of narrowing
+/// them down to a `subset` (see [`ExportRecords::retain_persons`]).
+///
+/// Verifies the tree exists and that the subset's persons belong to it.
+pub async fn load_records(
     db: &DatabaseConnection,
     tree_id: Uuid,
-    merge_occupations: bool,
-) -> Result<ExportData, OxidGeneError> {
+    subset: Option<&ExportSubset>,
+) -> Result<(ExportRecords, Vec<String>), OxidGeneError> {
     // Verify tree exists
     let _tree = TreeRepo::get(db, tree_id).await?;
+    let selected = match subset {
+        Some(subset) => {
+            if subset.person_ids.is_empty() {
+                return Err(OxidGeneError::Validation(
+                    "at least one person to export is required".to_string(),
+                ));
+            }
+            let found = PersonRepo::get_many(db, &subset.person_ids).await?;
+            if let Some(&missing) = subset
+                .person_ids
+                .iter()
+                .find(|id| !found.iter().any(|p| p.id == **id && p.tree_id == tree_id))
+            {
+                return Err(OxidGeneError::NotFound {
+                    entity: "Person",
+                    id: missing,
+                });
+            }
+            Some(subset_persons(db, subset).await?)
+        }
+        None => None,
+    };
 
     // Load all entities for the tree
     let persons = PersonRepo::list_all(db, tree_id).await?;
@@ -404,7 +992,7 @@ pub async fn load_and_export(
 
     let events = EventRepo::list_all(db, tree_id).await?;
     let event_ids: Vec<_> = events.iter().map(|e| e.id).collect();
-    let event_witnesses = EventWitnessRepo::list_by_events(db, &event_ids).await?;
+    let event_participants = EventParticipantRepo::list_by_events(db, &event_ids).await?;
     let places = PlaceRepo::list_all(db, tree_id).await?;
 
     let sources = SourceRepo::list_all(db, tree_id).await?;
@@ -416,28 +1004,192 @@ pub async fn load_and_export(
     let media_links = MediaLinkRepo::list_by_medias(db, &media_ids).await?;
 
     let notes = NoteRepo::list_all(db, tree_id).await?;
+    let external_ids = ExternalIdRepo::list_by_tree(db, tree_id).await?;
 
-    // Export to GEDCOM
-    let export_result = oxidgene_gedcom::export::export_gedcom(
-        &persons,
-        &person_names,
-        &families,
-        &family_spouses,
-        &family_children,
-        &events,
-        &event_witnesses,
-        &places,
-        &sources,
-        &citations,
-        &media,
-        &media_links,
-        &notes,
-        merge_occupations,
-    )
-    .map_err(OxidGeneError::Gedcom)?;
+    let mut records = ExportRecords {
+        persons,
+        person_names,
+        families,
+        family_spouses,
+        family_children,
+        events,
+        event_participants,
+        places,
+        sources,
+        citations,
+        media,
+        media_links,
+        notes,
+        external_ids,
+    };
+    let mut warnings = Vec::new();
+    if let Some(selected) = &selected {
+        records.retain_persons(selected, &mut warnings);
+    }
+    Ok((records, warnings))
+}
+
+/// Load all entities from a tree and export them as a GEDCOM string.
+///
+/// Loads the records with [`load_records`], then calls the GEDCOM exporter
+/// to produce the output string. `options` set the header metadata and
+/// occupation merging (see [`ExportOptions`]). With a `subset`, only that
+/// part of the tree is exported.
+pub async fn load_and_export(
+    db: &DatabaseConnection,
+    tree_id: Uuid,
+    options: &ExportOptions,
+    subset: Option<&ExportSubset>,
+) -> Result<ExportData, OxidGeneError> {
+    let (records, mut warnings) = load_records(db, tree_id, subset).await?;
 
+    let export_result = export_records(&records, options, &mut || {})?;
+
+    warnings.extend(export_result.warnings);
     Ok(ExportData {
         gedcom: export_result.gedcom,
-        warnings: export_result.warnings,
+        warnings,
+    })
+}
+
+/// Export `records` to GEDCOM, calling `on_record` once per top-level record
+/// written (see [`ExportRecords::record_count`]).
+pub fn export_records(
+    records: &ExportRecords,
+    options: &ExportOptions,
+    on_record: &mut dyn FnMut(),
+) -> Result<ExportResult, OxidGeneError> {
+    oxidgene_gedcom::export::export_gedcom_with_progress(
+        &records.persons,
+        &records.person_names,
+        &records.families,
+        &records.family_spouses,
+        &records.family_children,
+        &records.events,
+        &records.event_participants,
+        &records.places,
+        &records.sources,
+        &records.citations,
+        &records.media,
+        &records.media_links,
+        &records.notes,
+        &records.external_ids,
+        options,
+        on_record,
+    )
+    .map_err(OxidGeneError::Gedcom)
+}
+
+/// Count the live top-level records of a tree, without loading them.
+pub async fn estimate_export(
+    db: &DatabaseConnection,
+    tree_id: Uuid,
+) -> Result<ExportEstimate, OxidGeneError> {
+    TreeRepo::get(db, tree_id).await?;
+    let db_err = |e: sea_orm::DbErr| OxidGeneError::Database(e.to_string());
+
+    let persons = person::Entity::find()
+        .filter(person::Column::TreeId.eq(tree_id))
+        .filter(person::Column::DeletedAt.is_null())
+        .count(db)
+        .await
+        .map_err(db_err)?;
+    let families = family::Entity::find()
+        .filter(family::Column::TreeId.eq(tree_id))
+        .filter(family::Column::DeletedAt.is_null())
+        .count(db)
+        .await
+        .map_err(db_err)?;
+    let sources = source::Entity::find()
+        .filter(source::Column::TreeId.eq(tree_id))
+        .filter(source::Column::DeletedAt.is_null())
+        .count(db)
+        .await
+        .map_err(db_err)?;
+    let media = media::Entity::find()
+        .filter(media::Column::TreeId.eq(tree_id))
+        .filter(media::Column::DeletedAt.is_null())
+        .count(db)
+        .await
+        .map_err(db_err)?;
+
+    Ok(ExportEstimate {
+        persons,
+        families,
+        sources,
+        media,
+        records_total: persons + families + sources + media,
+    })
+}
+
+/// Run an export job: load the records to export, serialize them (on a
+/// blocking thread, reporting every [`EXPORT_PROGRESS_STEP`] records) and,
+/// with `gedzip`, wrap the result in a GEDZIP archive. The terminal phase
+/// is left to the caller, which stores the returned file.
+pub async fn export_with_progress(
+    db: &DatabaseConnection,
+    tree_id: Uuid,
+    options: &ExportOptions,
+    subset: Option<&ExportSubset>,
+    gedzip: bool,
+    progress: &ExportTracker,
+) -> Result<ExportArtifact, OxidGeneError> {
+    progress.set_phase(ExportPhase::Loading);
+    let (records, mut warnings) = load_records(db, tree_id, subset).await?;
+    progress.set_total(records.record_count());
+    progress.set_phase(ExportPhase::Serializing);
+
+    let options = options.clone();
+    let tracker = progress.clone();
+    let export_result = tokio::task::spawn_blocking(move || {
+        let mut pending = 0;
+        let result = export_records(&records, &options, &mut || {
+            pending += 1;
+            if pending == EXPORT_PROGRESS_STEP {
+                tracker.add_serialized(pending);
+                pending = 0;
+            }
+        });
+        tracker.add_serialized(pending);
+        result
+    })
+    .await
+    .map_err(|e| OxidGeneError::Gedcom(format!("export task failed: {e}")))??;
+    warnings.extend(export_result.warnings);
+
+    let (file_name, content_type) = if gedzip {
+        progress.set_phase(ExportPhase::Packaging);
+        ("export.gdz", "application/zip")
+    } else {
+        ("export.ged", "text/plain; charset=utf-8")
+    };
+    let (path, size_bytes) =
+        tokio::task::spawn_blocking(move || write_export_file(&export_result.gedcom, gedzip))
+            .await
+            .map_err(|e| OxidGeneError::Gedcom(format!("export task failed: {e}")))??;
+    Ok(ExportArtifact {
+        file_name: file_name.to_string(),
+        content_type,
+        path,
+        size_bytes,
+        warnings,
     })
 }
+
+/// Write an exported GEDCOM string to a temporary file, wrapped in a GEDZIP
+/// archive with `gedzip`, and return the file's path with its size.
+fn write_export_file(gedcom: &str, gedzip: bool) -> Result<(TempPath, usize), OxidGeneError> {
+    let io_error = |e: std::io::Error| OxidGeneError::Internal(format!("export file: {e}"));
+    let mut file = tempfile::Builder::new()
+        .prefix("oxidgene-export-")
+        .tempfile()
+        .map_err(io_error)?;
+    if gedzip {
+        oxidgene_gedcom::export::write_gedzip(gedcom, file.as_file_mut())
+            .map_err(OxidGeneError::Gedcom)?;
+    } else {
+        file.write_all(gedcom.as_bytes()).map_err(io_error)?;
+    }
+    let size = file.as_file().metadata().map_err(io_error)?.len();
+    Ok((file.into_temp_path(), size as usize))
+}
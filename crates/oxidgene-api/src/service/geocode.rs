@@ -0,0 +1,473 @@
+//! Place geocoding: provider abstraction, caching, rate limiting and the
+//! batch workflow behind `POST /api/v1/trees/:tree_id/places/geocode-all`.
+//!
+//! Providers implement [`Geocoder`]. The bundled [`NominatimGeocoder`] talks
+//! to any Nominatim-compatible endpoint. [`Geocoding`] wraps the configured
+//! provider with a per-process lookup cache and a minimum interval between
+//! provider calls, shared by every request and background job. Batches run
+//! as [`GeocodeJobs`] on the shared [`super::job`] registry.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use oxidgene_core::OxidGeneError;
+use oxidgene_db::repo::{PlaceRepo, normalize_place_name};
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use super::job::{JobProgress, JobTracker, Jobs};
+
+/// Candidates further apart than this (in degrees, on either axis) are
+/// treated as distinct locations, making the lookup ambiguous.
+const AMBIGUITY_TOLERANCE_DEG: f64 = 0.1;
+
+/// Timeout for a single provider request.
+const PROVIDER_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// One candidate location returned by a provider.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeoMatch {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Provider-formatted label, for display only.
+    pub display_name: String,
+}
+
+/// Address components found at a coordinate, smallest division first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GeoAddress {
+    /// City, town, village or hamlet.
+    pub locality: Option<String>,
+    pub county: Option<String>,
+    pub state: Option<String>,
+    pub country: Option<String>,
+}
+
+impl GeoAddress {
+    /// Normalized hierarchical place name — `Locality, County, State,
+    /// Country` — skipping missing levels and repeated ones (city-states).
+    pub fn hierarchical_name(&self) -> String {
+        let mut parts: Vec<&str> = Vec::new();
+        for part in [&self.locality, &self.county, &self.state, &self.country]
+            .into_iter()
+            .flatten()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+        {
+            if parts.last() != Some(&part) {
+                parts.push(part);
+            }
+        }
+        parts.join(", ")
+    }
+}
+
+/// A geocoding provider.
+///
+/// Errors are plain messages: they end up in per-place batch results rather
+/// than aborting the batch.
+#[async_trait]
+pub trait Geocoder: Send + Sync + Debug {
+    /// Candidate locations for a free-text place name, best match first.
+    async fn geocode(&self, query: &str) -> Result<Vec<GeoMatch>, String>;
+
+    /// Address at the given coordinates, or `None` if nothing is there.
+    async fn reverse(&self, latitude: f64, longitude: f64) -> Result<Option<GeoAddress>, String>;
+}
+
+/// [`Geocoder`] backed by a Nominatim-compatible HTTP API.
+#[derive(Debug, Clone)]
+pub struct NominatimGeocoder {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl NominatimGeocoder {
+    pub fn new(base_url: impl Into<String>) -> Result<Self, OxidGeneError> {
+        let client = reqwest::Client::builder()
+            // Nominatim's usage policy requires an identifying User-Agent.
+            .user_agent(concat!("OxidGene/", env!("CARGO_PKG_VERSION")))
+            .timeout(PROVIDER_TIMEOUT)
+            .build()
+            .map_err(|e| OxidGeneError::Internal(e.to_string()))?;
+        Ok(Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct NominatimPlace {
+    lat: String,
+    lon: String,
+    display_name: String,
+}
+
+#[derive(Deserialize)]
+struct NominatimReverse {
+    address: Option<NominatimAddress>,
+}
+
+#[derive(Deserialize)]
+struct NominatimAddress {
+    city: Option<String>,
+    town: Option<String>,
+    village: Option<String>,
+    hamlet: Option<String>,
+    municipality: Option<String>,
+    county: Option<String>,
+    state: Option<String>,
+    country: Option<String>,
+}
+
+#[async_trait]
+impl Geocoder for NominatimGeocoder {
+    async fn geocode(&self, query: &str) -> Result<Vec<GeoMatch>, String> {
+        let places: Vec<NominatimPlace> = self
+            .client
+            .get(format!("{}/search", self.base_url))
+            .query(&[("q", query), ("format", "jsonv2"), ("limit", "5")])
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| e.to_string())?
+            .json()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(places
+            .into_iter()
+            .filter_map(|p| {
+                Some(GeoMatch {
+                    latitude: p.lat.parse().ok()?,
+                    longitude: p.lon.parse().ok()?,
+                    display_name: p.display_name,
+                })
+            })
+            .collect())
+    }
+
+    async fn reverse(&self, latitude: f64, longitude: f64) -> Result<Option<GeoAddress>, String> {
+        let reply: NominatimReverse = self
+            .client
+            .get(format!("{}/reverse", self.base_url))
+            .query(&[
+                ("lat", latitude.to_string()),
+                ("lon", longitude.to_string()),
+                ("format", "jsonv2".to_string()),
+                // Settlement level: street-level detail has no place in a
+                // genealogical place name.
+                ("zoom", "14".to_string()),
+            ])
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| e.to_string())?
+            .json()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(reply.address.map(|a| GeoAddress {
+            locality: a
+                .city
+                .or(a.town)
+                .or(a.village)
+                .or(a.hamlet)
+                .or(a.municipality),
+            county: a.county,
+            state: a.state,
+            country: a.country,
+        }))
+    }
+}
+
+/// The configured provider plus the cache and throttle shared by all callers.
+#[derive(Debug)]
+pub struct Geocoding {
+    provider: Option<Arc<dyn Geocoder>>,
+    min_interval: Duration,
+    /// When the provider was last called; held across the wait so concurrent
+    /// callers queue up behind each other.
+    last_call: tokio::sync::Mutex<Option<Instant>>,
+    forward_cache: Mutex<HashMap<String, Vec<GeoMatch>>>,
+    reverse_cache: Mutex<HashMap<(i64, i64), Option<GeoAddress>>>,
+}
+
+impl Geocoding {
+    /// `provider: None` disables geocoding; every lookup then fails with a
+    /// validation error.
+    pub fn new(provider: Option<Arc<dyn Geocoder>>, min_interval: Duration) -> Self {
+        Self {
+            provider,
+            min_interval,
+            last_call: tokio::sync::Mutex::new(None),
+            forward_cache: Mutex::new(HashMap::new()),
+            reverse_cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Geocoding with no provider.
+    pub fn disabled() -> Self {
+        Self::new(None, Duration::ZERO)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.provider.is_some()
+    }
+
+    fn provider(&self) -> Result<&Arc<dyn Geocoder>, OxidGeneError> {
+        self.provider.as_ref().ok_or(OxidGeneError::Validation(
+            "geocoding is disabled: no provider configured".to_string(),
+        ))
+    }
+
+    /// Wait until the provider may be called again.
+    async fn throttle(&self) {
+        let mut last_call = self.last_call.lock().await;
+        if let Some(last) = *last_call {
+            tokio::time::sleep_until(last + self.min_interval).await;
+        }
+        *last_call = Some(Instant::now());
+    }
+
+    /// Candidate locations for a place name. Results (including "no match")
+    /// are cached by normalized name; provider errors are not.
+    pub async fn lookup(&self, name: &str) -> Result<Result<Vec<GeoMatch>, String>, OxidGeneError> {
+        let provider = self.provider()?;
+        let key = normalize_place_name(name);
+        if let Some(hit) = self.forward_cache.lock().unwrap().get(&key) {
+            return Ok(Ok(hit.clone()));
+        }
+        self.throttle().await;
+        let result = provider.geocode(name).await;
+        if let Ok(matches) = &result {
+            self.forward_cache
+                .lock()
+                .unwrap()
+                .insert(key, matches.clone());
+        }
+        Ok(result)
+    }
+
+    /// Address at the given coordinates, cached at ~1 m precision.
+    pub async fn reverse(
+        &self,
+        latitude: f64,
+        longitude: f64,
+    ) -> Result<Option<GeoAddress>, OxidGeneError> {
+        let provider = self.provider()?;
+        let key = (
+            (latitude * 1e5).round() as i64,
+            (longitude * 1e5).round() as i64,
+        );
+        if let Some(hit) = self.reverse_cache.lock().unwrap().get(&key) {
+            return Ok(hit.clone());
+        }
+        self.throttle().await;
+        let address = provider
+            .reverse(latitude, longitude)
+            .await
+            .map_err(OxidGeneError::Internal)?;
+        self.reverse_cache
+            .lock()
+            .unwrap()
+            .insert(key, address.clone());
+        Ok(address)
+    }
+}
+
+/// Outcome of geocoding one place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GeocodeStatus {
+    /// Coordinates were written to the place.
+    Success,
+    /// Several distinct locations matched; the place was left untouched.
+    Ambiguous,
+    /// No match, or the provider failed.
+    Failed,
+}
+
+/// Per-place line of a batch summary.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaceGeocodeResult {
+    pub place_id: Uuid,
+    pub name: String,
+    pub status: GeocodeStatus,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Number of candidates the provider returned.
+    pub candidates: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Summary of a batch geocoding job.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GeocodeSummary {
+    pub succeeded: usize,
+    pub ambiguous: usize,
+    pub failed: usize,
+    pub places: Vec<PlaceGeocodeResult>,
+}
+
+/// Registry of batch geocoding jobs, run one at a time per tree.
+pub type GeocodeJobs = Jobs<GeocodeProgress>;
+
+/// Write side of a batch geocoding job's progress.
+pub type GeocodeTracker = JobTracker<GeocodeProgress>;
+
+/// Stage a geocoding job is currently in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GeocodePhase {
+    Queued,
+    Geocoding,
+    Done,
+    Failed,
+}
+
+/// Snapshot of a geocoding job's progress.
+#[derive(Debug, Clone, Serialize)]
+pub struct GeocodeProgress {
+    pub phase: GeocodePhase,
+    /// Number of places lacking coordinates when the job started.
+    pub places_total: usize,
+    pub places_done: usize,
+    /// Set once the job is done.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<GeocodeSummary>,
+    /// Set if the job failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl JobProgress for GeocodeProgress {
+    fn queued() -> Self {
+        Self {
+            phase: GeocodePhase::Queued,
+            places_total: 0,
+            places_done: 0,
+            summary: None,
+            error: None,
+            finished_at: None,
+        }
+    }
+
+    fn finished_at(&self) -> Option<DateTime<Utc>> {
+        self.finished_at
+    }
+}
+
+impl GeocodeTracker {
+    pub fn start(&self, total: usize) {
+        self.update(|p| {
+            p.phase = GeocodePhase::Geocoding;
+            p.places_total = total;
+        });
+    }
+
+    pub fn add_done(&self, count: usize) {
+        self.update(|p| p.places_done += count);
+    }
+
+    pub fn finish(&self, summary: GeocodeSummary) {
+        self.update(|p| {
+            p.phase = GeocodePhase::Done;
+            p.summary = Some(summary);
+            p.finished_at = Some(Utc::now());
+        });
+    }
+
+    pub fn fail(&self, error: String) {
+        self.update(|p| {
+            p.phase = GeocodePhase::Failed;
+            p.error = Some(error);
+            p.finished_at = Some(Utc::now());
+        });
+    }
+}
+
+/// Classify provider candidates: one location (possibly reported several
+/// times) is a success, several distinct ones are ambiguous.
+fn classify(matches: &[GeoMatch]) -> GeocodeStatus {
+    match matches.split_first() {
+        None => GeocodeStatus::Failed,
+        Some((first, rest)) => {
+            let same_spot = rest.iter().all(|m| {
+                (m.latitude - first.latitude).abs() <= AMBIGUITY_TOLERANCE_DEG
+                    && (m.longitude - first.longitude).abs() <= AMBIGUITY_TOLERANCE_DEG
+            });
+            if same_spot {
+                GeocodeStatus::Success
+            } else {
+                GeocodeStatus::Ambiguous
+            }
+        }
+    }
+}
+
+/// Geocode every place of `tree_id` that lacks coordinates, writing the
+/// coordinates of unambiguous matches back to the place.
+pub async fn geocode_tree(
+    db: &DatabaseConnection,
+    geocoding: &Geocoding,
+    tree_id: Uuid,
+    tracker: &GeocodeTracker,
+) -> Result<GeocodeSummary, OxidGeneError> {
+    let places: Vec<_> = PlaceRepo::list_all(db, tree_id)
+        .await?
+        .into_iter()
+        .filter(|p| p.latitude.is_none() || p.longitude.is_none())
+        .collect();
+    tracker.start(places.len());
+
+    let mut summary = GeocodeSummary::default();
+    for place in places {
+        let mut result = PlaceGeocodeResult {
+            place_id: place.id,
+            name: place.name.clone(),
+            status: GeocodeStatus::Failed,
+            latitude: None,
+            longitude: None,
+            candidates: 0,
+            error: None,
+        };
+        match geocoding.lookup(&place.name).await? {
+            Ok(matches) => {
+                result.candidates = matches.len();
+                result.status = classify(&matches);
+                if result.status == GeocodeStatus::Success {
+                    let best = &matches[0];
+                    PlaceRepo::update(
+                        db,
+                        place.id,
+                        None,
+                        Some(Some(best.latitude)),
+                        Some(Some(best.longitude)),
+                        None,
+                    )
+                    .await?;
+                    result.latitude = Some(best.latitude);
+                    result.longitude = Some(best.longitude);
+                }
+            }
+            Err(e) => result.error = Some(e),
+        }
+        match result.status {
+            GeocodeStatus::Success => summary.succeeded += 1,
+            GeocodeStatus::Ambiguous => summary.ambiguous += 1,
+            GeocodeStatus::Failed => summary.failed += 1,
+        }
+        summary.places.push(result);
+        tracker.add_done(1);
+    }
+    Ok(summary)
+}
@@ -0,0 +1,134 @@
+//! Background GEDCOM import jobs and their progress reporting.
+//!
+//! The REST import endpoint starts a job on the [`super::job`] registry and
+//! returns its id right away; the import itself runs on a spawned task that
+//! publishes [`ImportProgress`] snapshots. Clients follow a job over
+//! Server-Sent Events (see `rest::gedcom::import_events_handler`).
+//!
+//! Import jobs can be cancelled: cancelling one makes the import stop before
+//! its next insert batch, rolling back its transaction.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::gedcom::ImportSummary;
+use super::job::{JobProgress, JobTracker, Jobs};
+
+/// Registry of import jobs.
+pub type ImportJobs = Jobs<ImportProgress>;
+
+/// Write side of an import job's progress.
+pub type ImportTracker = JobTracker<ImportProgress>;
+
+/// Stage an import job is currently in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportPhase {
+    Queued,
+    Parsing,
+    Persisting,
+    RebuildingCache,
+    Done,
+    Failed,
+    /// Stopped on request; nothing was written.
+    Cancelled,
+}
+
+impl ImportPhase {
+    /// Whether the job can still be cancelled, i.e. has not committed yet.
+    pub fn is_cancellable(self) -> bool {
+        matches!(self, Self::Queued | Self::Parsing | Self::Persisting)
+    }
+}
+
+/// Snapshot of an import job's progress.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportProgress {
+    pub phase: ImportPhase,
+    /// Number of records read from the file, known once parsing has
+    /// finished.
+    pub records_parsed: usize,
+    /// Total number of rows to insert, known once parsing has finished.
+    pub records_total: usize,
+    pub records_persisted: usize,
+    /// Warnings raised so far, in order.
+    pub warnings: Vec<String>,
+    /// Set once the job is done.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<ImportSummary>,
+    /// Set if the job failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl JobProgress for ImportProgress {
+    fn queued() -> Self {
+        Self {
+            phase: ImportPhase::Queued,
+            records_parsed: 0,
+            records_total: 0,
+            records_persisted: 0,
+            warnings: Vec::new(),
+            summary: None,
+            error: None,
+            finished_at: None,
+        }
+    }
+
+    fn finished_at(&self) -> Option<DateTime<Utc>> {
+        self.finished_at
+    }
+
+    fn is_cancellable(&self) -> bool {
+        self.phase.is_cancellable()
+    }
+}
+
+impl ImportTracker {
+    pub fn set_phase(&self, phase: ImportPhase) {
+        self.update(|p| p.phase = phase);
+    }
+
+    pub fn set_parsed(&self, parsed: usize) {
+        self.update(|p| p.records_parsed = parsed);
+    }
+
+    pub fn set_total(&self, total: usize) {
+        self.update(|p| p.records_total = total);
+    }
+
+    pub fn add_persisted(&self, count: usize) {
+        self.update(|p| p.records_persisted += count);
+    }
+
+    pub fn add_warnings(&self, warnings: &[String]) {
+        if !warnings.is_empty() {
+            self.update(|p| p.warnings.extend_from_slice(warnings));
+        }
+    }
+
+    pub fn finish(&self, summary: ImportSummary) {
+        self.update(|p| {
+            p.phase = ImportPhase::Done;
+            p.summary = Some(summary);
+            p.finished_at = Some(Utc::now());
+        });
+    }
+
+    pub fn fail(&self, error: String) {
+        self.update(|p| {
+            p.phase = ImportPhase::Failed;
+            p.error = Some(error);
+            p.finished_at = Some(Utc::now());
+        });
+    }
+
+    pub fn cancelled(&self) {
+        self.update(|p| {
+            p.phase = ImportPhase::Cancelled;
+            p.finished_at = Some(Utc::now());
+        });
+    }
+}
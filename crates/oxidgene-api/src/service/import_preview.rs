@@ -0,0 +1,71 @@
+//! GEDCOM imports previewed and awaiting confirmation.
+//!
+//! A preview parses the file and matches it against the tree without
+//! writing anything (see [`super::gedcom::preview_import`]); the file and
+//! its [`ImportPlan`] are kept here under a token, which the client sends
+//! back to run the import it reviewed.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use super::gedcom::ImportPlan;
+
+/// How long a preview can be confirmed.
+const PREVIEW_RETENTION_MINUTES: i64 = 30;
+
+/// A previewed import: the file and what importing it would do.
+#[derive(Debug)]
+pub struct PendingImport {
+    tree_id: Uuid,
+    pub gedcom: String,
+    pub plan: ImportPlan,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// In-process registry of previewed imports, shared through `AppState`.
+#[derive(Debug, Default)]
+pub struct ImportPreviews {
+    pending: Mutex<HashMap<Uuid, PendingImport>>,
+}
+
+impl ImportPreviews {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep a previewed import of `gedcom` into `tree_id` and return its
+    /// token with the time it expires. Expired previews are pruned on the
+    /// way.
+    pub fn insert(&self, tree_id: Uuid, gedcom: String, plan: ImportPlan) -> (Uuid, DateTime<Utc>) {
+        let token = Uuid::new_v4();
+        let now = Utc::now();
+        let expires_at = now + Duration::minutes(PREVIEW_RETENTION_MINUTES);
+
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|_, p| p.expires_at > now);
+        pending.insert(
+            token,
+            PendingImport {
+                tree_id,
+                gedcom,
+                plan,
+                expires_at,
+            },
+        );
+        (token, expires_at)
+    }
+
+    /// Take a previewed import out of the registry. Returns `None` if the
+    /// token is unknown, expired or belongs to another tree.
+    pub fn take(&self, tree_id: Uuid, token: Uuid) -> Option<PendingImport> {
+        let mut pending = self.pending.lock().unwrap();
+        let p = pending.get(&token)?;
+        if p.tree_id != tree_id {
+            return None;
+        }
+        pending.remove(&token).filter(|p| p.expires_at > Utc::now())
+    }
+}
@@ -0,0 +1,240 @@
+//! In-process registry of background jobs and their progress reporting.
+//!
+//! Long-running operations (GEDCOM import and export, batch geocoding) are
+//! started by a REST endpoint that returns a job id right away; the work
+//! itself runs on a spawned task that publishes progress snapshots through a
+//! `watch` channel, and clients follow it over Server-Sent Events. Each kind
+//! of job only defines its progress type (see [`super::import_job`],
+//! [`super::export_job`], [`super::geocode`]) and, optionally, the output a
+//! finished job keeps for the client to fetch.
+//!
+//! Finished jobs, and their outputs, are pruned once they are older than the
+//! retention window, whenever the registry is accessed.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// How long a finished job stays queryable before it is pruned.
+const FINISHED_JOB_RETENTION_MINUTES: i64 = 10;
+
+/// Progress snapshot of a kind of job.
+pub trait JobProgress: Clone + Send + Sync + 'static {
+    /// The snapshot of a job that has not started yet.
+    fn queued() -> Self;
+
+    /// When the job ended, successfully or not. `None` while it runs.
+    fn finished_at(&self) -> Option<DateTime<Utc>>;
+
+    /// Whether the job can still be cancelled. Jobs are not cancellable
+    /// unless their kind says otherwise.
+    fn is_cancellable(&self) -> bool {
+        false
+    }
+}
+
+/// Write side of a job's progress channel, holding the token that cancels
+/// the job and the slot its output goes into.
+///
+/// Dropping the last tracker closes the channel, which ends any SSE stream
+/// following the job.
+pub struct JobTracker<P, O = ()> {
+    tx: Arc<watch::Sender<P>>,
+    cancel: CancellationToken,
+    output: Arc<OnceLock<Arc<O>>>,
+}
+
+impl<P, O> Clone for JobTracker<P, O> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+            cancel: self.cancel.clone(),
+            output: self.output.clone(),
+        }
+    }
+}
+
+impl<P: fmt::Debug, O: fmt::Debug> fmt::Debug for JobTracker<P, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JobTracker")
+            .field("progress", &*self.tx.borrow())
+            .field("cancelled", &self.cancel.is_cancelled())
+            .field("output", &self.output.get())
+            .finish()
+    }
+}
+
+impl<P: JobProgress, O> JobTracker<P, O> {
+    /// A tracker nobody listens to, for work run synchronously (GraphQL,
+    /// tree duplication).
+    pub fn detached() -> Self {
+        let (tx, _rx) = watch::channel(P::queued());
+        Self {
+            tx: Arc::new(tx),
+            cancel: CancellationToken::new(),
+            output: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Whether cancellation of the job was requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Apply `update` to the job's progress and notify its subscribers.
+    pub(crate) fn update(&self, update: impl FnOnce(&mut P)) {
+        self.tx.send_modify(update);
+    }
+
+    /// Store the job's output. Call before publishing the terminal
+    /// progress, so a client reacting to it finds the output in place.
+    pub(crate) fn set_output(&self, output: O) {
+        let _ = self.output.set(Arc::new(output));
+    }
+}
+
+/// A registered job: the tree it works on, a receiver on its progress, the
+/// token that cancels it and its output once done.
+struct Job<P, O> {
+    tree_id: Uuid,
+    rx: watch::Receiver<P>,
+    cancel: CancellationToken,
+    output: Arc<OnceLock<Arc<O>>>,
+}
+
+/// In-process registry of one kind of job, shared through `AppState`.
+pub struct Jobs<P, O = ()> {
+    jobs: Mutex<HashMap<Uuid, Job<P, O>>>,
+    retention: Duration,
+}
+
+impl<P, O> fmt::Debug for Jobs<P, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Jobs")
+            .field("jobs", &self.jobs.lock().unwrap().len())
+            .field("retention", &self.retention)
+            .finish()
+    }
+}
+
+impl<P: JobProgress, O> Default for Jobs<P, O> {
+    fn default() -> Self {
+        Self::with_retention(Duration::minutes(FINISHED_JOB_RETENTION_MINUTES))
+    }
+}
+
+impl<P: JobProgress, O> Jobs<P, O> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry keeping finished jobs for `retention` instead of the
+    /// default window.
+    pub fn with_retention(retention: Duration) -> Self {
+        Self {
+            jobs: Mutex::new(HashMap::new()),
+            retention,
+        }
+    }
+
+    /// Register a new job for `tree_id` and return its id with the tracker
+    /// the job reports through.
+    pub fn start(&self, tree_id: Uuid) -> (Uuid, JobTracker<P, O>) {
+        let mut jobs = self.pruned();
+        Self::insert(&mut jobs, tree_id)
+    }
+
+    /// Like [`start`](Self::start), but returns `None` while another job for
+    /// the same tree is still running.
+    pub fn start_exclusive(&self, tree_id: Uuid) -> Option<(Uuid, JobTracker<P, O>)> {
+        let mut jobs = self.pruned();
+        if jobs
+            .values()
+            .any(|job| job.tree_id == tree_id && job.rx.borrow().finished_at().is_none())
+        {
+            return None;
+        }
+        Some(Self::insert(&mut jobs, tree_id))
+    }
+
+    /// Subscribe to a job's progress. Returns `None` if the job is unknown or
+    /// belongs to another tree.
+    pub fn subscribe(&self, tree_id: Uuid, job_id: Uuid) -> Option<watch::Receiver<P>> {
+        let jobs = self.pruned();
+        jobs.get(&job_id)
+            .filter(|job| job.tree_id == tree_id)
+            .map(|job| job.rx.clone())
+    }
+
+    /// Request cancellation of a job. Returns `None` if the job is unknown
+    /// or belongs to another tree, otherwise its progress at the time of the
+    /// request; the job only stops if that progress
+    /// [is cancellable](JobProgress::is_cancellable).
+    pub fn cancel(&self, tree_id: Uuid, job_id: Uuid) -> Option<P> {
+        let jobs = self.pruned();
+        let job = jobs.get(&job_id).filter(|job| job.tree_id == tree_id)?;
+        let progress = job.rx.borrow().clone();
+        if progress.is_cancellable() {
+            job.cancel.cancel();
+        }
+        Some(progress)
+    }
+
+    /// The output of a finished job. Returns `None` if the job is unknown or
+    /// belongs to another tree, otherwise its output, or its progress while
+    /// it has none (still running, or failed).
+    pub fn output(&self, tree_id: Uuid, job_id: Uuid) -> Option<Result<Arc<O>, P>> {
+        let jobs = self.pruned();
+        let job = jobs.get(&job_id).filter(|job| job.tree_id == tree_id)?;
+        Some(
+            job.output
+                .get()
+                .cloned()
+                .ok_or_else(|| job.rx.borrow().clone()),
+        )
+    }
+
+    /// Lock the registry, dropping the jobs finished longer ago than the
+    /// retention window along with their outputs.
+    fn pruned(&self) -> MutexGuard<'_, HashMap<Uuid, Job<P, O>>> {
+        let cutoff = Utc::now() - self.retention;
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.retain(|_, job| {
+            job.rx
+                .borrow()
+                .finished_at()
+                .is_none_or(|finished| finished > cutoff)
+        });
+        jobs
+    }
+
+    fn insert(jobs: &mut HashMap<Uuid, Job<P, O>>, tree_id: Uuid) -> (Uuid, JobTracker<P, O>) {
+        let (tx, rx) = watch::channel(P::queued());
+        let job_id = Uuid::now_v7();
+        let cancel = CancellationToken::new();
+        let output = Arc::new(OnceLock::new());
+        jobs.insert(
+            job_id,
+            Job {
+                tree_id,
+                rx,
+                cancel: cancel.clone(),
+                output: output.clone(),
+            },
+        );
+
+        (
+            job_id,
+            JobTracker {
+                tx: Arc::new(tx),
+                cancel,
+                output,
+            },
+        )
+    }
+}
@@ -1,3 +1,13 @@
 //! Service layer: shared business logic used by both REST and GraphQL handlers.
 
+pub mod backup;
+pub mod citation;
+pub mod export_job;
 pub mod gedcom;
+pub mod geocode;
+pub mod import_job;
+pub mod import_preview;
+pub mod job;
+pub mod plausibility;
+pub mod sourcing;
+pub mod template;
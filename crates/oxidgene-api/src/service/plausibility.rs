@@ -0,0 +1,75 @@
+//! Date plausibility warnings of a saved event, returned by the REST event
+//! create and update handlers.
+
+use std::collections::HashMap;
+
+use oxidgene_core::OxidGeneError;
+use oxidgene_core::enums::{EventType, SpouseRole};
+use oxidgene_core::types::Event;
+use oxidgene_core::validation::plausibility::{Lives, PlausibilityWarning, check_event};
+use oxidgene_db::repo::{EventRepo, FamilyChildRepo, FamilySpouseRepo};
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+/// The [`check_event`] warnings of `event`, loading only the lives its type
+/// is checked against: the person's own events for a birth or death, the
+/// mothers' (wives of the families the person is a child of) for a birth,
+/// the spouses' for a marriage.
+pub async fn event_warnings(
+    db: &DatabaseConnection,
+    event: &Event,
+) -> Result<Vec<PlausibilityWarning>, OxidGeneError> {
+    let mut own = Vec::new();
+    let mut spouses = Vec::new();
+    let mut mothers = Vec::new();
+    match (event.event_type, event.person_id, event.family_id) {
+        (EventType::Birth | EventType::Death, Some(person_id), _) => {
+            own = EventRepo::list_by_person(db, person_id).await?;
+            if event.event_type == EventType::Birth {
+                let family_ids: Vec<Uuid> = FamilyChildRepo::list_by_person(db, person_id)
+                    .await?
+                    .into_iter()
+                    .map(|c| c.family_id)
+                    .collect();
+                let mother_ids: Vec<Uuid> = FamilySpouseRepo::list_by_families(db, &family_ids)
+                    .await?
+                    .into_iter()
+                    .filter(|s| s.role == SpouseRole::Wife)
+                    .map(|s| s.person_id)
+                    .collect();
+                mothers = events_by_person(db, &mother_ids).await?;
+            }
+        }
+        (EventType::Marriage, _, Some(family_id)) => {
+            let spouse_ids: Vec<Uuid> = FamilySpouseRepo::list_by_family(db, family_id)
+                .await?
+                .into_iter()
+                .map(|s| s.person_id)
+                .collect();
+            spouses = events_by_person(db, &spouse_ids).await?;
+        }
+        _ => return Ok(Vec::new()),
+    }
+    Ok(check_event(
+        event,
+        Lives {
+            own: &own,
+            spouses: &spouses,
+            mothers: &mothers,
+        },
+    ))
+}
+
+/// The events of each of `person_ids`, in one query.
+async fn events_by_person(
+    db: &DatabaseConnection,
+    person_ids: &[Uuid],
+) -> Result<Vec<Vec<Event>>, OxidGeneError> {
+    let mut grouped: HashMap<Uuid, Vec<Event>> = HashMap::new();
+    for event in EventRepo::list_by_persons(db, person_ids).await? {
+        if let Some(pid) = event.person_id {
+            grouped.entry(pid).or_default().push(event);
+        }
+    }
+    Ok(grouped.into_values().collect())
+}
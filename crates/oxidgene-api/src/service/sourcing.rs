@@ -0,0 +1,64 @@
+//! Sourcing scores shared by the REST and GraphQL person handlers.
+
+use std::collections::HashMap;
+
+use oxidgene_core::OxidGeneError;
+use oxidgene_core::summary::sourcing_score;
+use oxidgene_core::types::{Citation, Event};
+use oxidgene_db::repo::{CitationRepo, EventRepo, FamilySpouseRepo};
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+/// The [`sourcing_score`] of each of `person_ids`, from their own events,
+/// the events of the families they are a spouse in, and the citations of
+/// those events. Four queries whatever the number of persons.
+pub async fn sourcing_scores(
+    db: &DatabaseConnection,
+    person_ids: &[Uuid],
+) -> Result<HashMap<Uuid, f32>, OxidGeneError> {
+    let mut events_by_person: HashMap<Uuid, Vec<Event>> = HashMap::new();
+    for event in EventRepo::list_by_persons(db, person_ids).await? {
+        if let Some(pid) = event.person_id {
+            events_by_person.entry(pid).or_default().push(event);
+        }
+    }
+
+    let spouse_links = FamilySpouseRepo::list_by_persons(db, person_ids).await?;
+    let family_ids: Vec<Uuid> = spouse_links.iter().map(|s| s.family_id).collect();
+    let mut events_by_family: HashMap<Uuid, Vec<Event>> = HashMap::new();
+    for event in EventRepo::list_by_families(db, &family_ids).await? {
+        if let Some(fid) = event.family_id {
+            events_by_family.entry(fid).or_default().push(event);
+        }
+    }
+    for link in &spouse_links {
+        if let Some(events) = events_by_family.get(&link.family_id) {
+            events_by_person
+                .entry(link.person_id)
+                .or_default()
+                .extend(events.iter().cloned());
+        }
+    }
+
+    let event_ids: Vec<Uuid> = events_by_person.values().flatten().map(|e| e.id).collect();
+    let mut citations_by_event: HashMap<Uuid, Vec<Citation>> = HashMap::new();
+    for citation in CitationRepo::list_by_events(db, &event_ids).await? {
+        if let Some(eid) = citation.event_id {
+            citations_by_event.entry(eid).or_default().push(citation);
+        }
+    }
+
+    Ok(person_ids
+        .iter()
+        .map(|&pid| {
+            let events = events_by_person.get(&pid).map_or(&[][..], Vec::as_slice);
+            let citations: Vec<Citation> = events
+                .iter()
+                .filter_map(|e| citations_by_event.get(&e.id))
+                .flatten()
+                .cloned()
+                .collect();
+            (pid, sourcing_score(events, &citations))
+        })
+        .collect())
+}
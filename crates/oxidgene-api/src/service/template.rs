@@ -0,0 +1,64 @@
+//! Tree creation from starter templates (see [`oxidgene_core::templates`]).
+
+use chrono::Utc;
+use oxidgene_core::OxidGeneError;
+use oxidgene_core::templates::{self, TreeTemplate};
+use oxidgene_core::types::Tree;
+use oxidgene_db::entities::tree;
+use oxidgene_db::repo::{TreeRepo, with_transaction};
+use oxidgene_gedcom::ImportResult;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use uuid::Uuid;
+
+use super::gedcom::insert_all;
+use super::import_job::ImportTracker;
+
+/// Create tree `id` seeded with `template`, in a single transaction: either
+/// the tree comes with all of its starter records or it is not created.
+/// The template's home person becomes the tree's SOSA root.
+pub async fn create_tree(
+    db: &DatabaseConnection,
+    id: Uuid,
+    name: String,
+    description: Option<String>,
+    template: TreeTemplate,
+) -> Result<Tree, OxidGeneError> {
+    let seed = templates::build(template, id);
+    let home_person_id = seed.home_person_id;
+    let now = Utc::now();
+
+    with_transaction(db, |txn| {
+        Box::pin(async move {
+            tree::ActiveModel {
+                id: Set(id),
+                name: Set(name),
+                description: Set(description),
+                sosa_root_person_id: Set(None),
+                created_at: Set(now),
+                updated_at: Set(now),
+                version: Set(1),
+                deleted_at: Set(None),
+            }
+            .insert(txn)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+
+            insert_all(txn, &ImportResult::from(seed), &ImportTracker::detached()).await?;
+
+            // The root can only be set once the person it points to exists.
+            if let Some(home_person_id) = home_person_id {
+                tree::ActiveModel {
+                    id: Set(id),
+                    sosa_root_person_id: Set(Some(home_person_id)),
+                    ..Default::default()
+                }
+                .update(txn)
+                .await
+                .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+            }
+            Ok(())
+        })
+    })
+    .await?;
+    TreeRepo::get(db, id).await
+}
@@ -6,7 +6,7 @@
 use axum::body::Body;
 use axum::http::{Method, Request, StatusCode};
 use http_body_util::BodyExt;
-use oxidgene_api::{AppState, build_router};
+use oxidgene_api::{ApiConfig, AppState, build_router};
 use oxidgene_db::repo::{connect, run_migrations};
 use sea_orm::DatabaseConnection;
 use serde_json::{Value, json};
@@ -238,6 +238,16 @@ async fn test_person_crud_with_names() {
     .await;
     assert_eq!(data(&resp)["updatePerson"]["sex"], "FEMALE");
 
+    let resp = graphql(
+        app.clone(),
+        &format!(
+            r#"mutation {{ updatePerson(id: "{person_id}", input: {{ sex: INTERSEX }}) {{ id sex }} }}"#
+        ),
+        None,
+    )
+    .await;
+    assert_eq!(data(&resp)["updatePerson"]["sex"], "INTERSEX");
+
     // Delete person
     let resp = graphql(
         app,
@@ -454,6 +464,20 @@ async fn test_event_with_place() {
     .await;
     assert_eq!(data(&resp)["updateEvent"]["description"], "Updated birth");
 
+    // `null` clears a field, an omitted one is left unchanged
+    let resp = graphql(
+        app.clone(),
+        &format!(
+            r#"mutation {{ updateEvent(id: "{event_id}", input: {{ description: null, placeId: null }}) {{ description dateValue placeId }} }}"#
+        ),
+        None,
+    )
+    .await;
+    let ev = &data(&resp)["updateEvent"];
+    assert!(ev["description"].is_null());
+    assert!(ev["placeId"].is_null());
+    assert_eq!(ev["dateValue"], "1 Jan 1900");
+
     // Delete event
     let resp = graphql(
         app,
@@ -790,6 +814,184 @@ async fn test_mutation_invalid_uuid() {
     assert!(resp.get("errors").is_some());
 }
 
+// ── Error handling: input validation ─────────────────────────────────
+
+#[tokio::test]
+async fn test_mutation_input_validation() {
+    let app = setup_app().await;
+    let resp = graphql(
+        app.clone(),
+        r#"mutation { createTree(input: { name: "V" }) { id } }"#,
+        None,
+    )
+    .await;
+    let tree_id = data(&resp)["createTree"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Field validators reject the value before the resolver runs.
+    let resp = graphql(
+        app.clone(),
+        &format!(
+            r#"mutation {{ createPlace(treeId: "{tree_id}", input: {{ name: "Paris", latitude: 148.8 }}) {{ id }} }}"#
+        ),
+        None,
+    )
+    .await;
+    let message = resp["errors"][0]["message"].as_str().unwrap();
+    assert!(
+        message.contains("latitude must be between -90 and 90"),
+        "{message}"
+    );
+
+    let resp = graphql(
+        app.clone(),
+        r#"mutation { createTree(input: { name: "  " }) { id } }"#,
+        None,
+    )
+    .await;
+    let message = resp["errors"][0]["message"].as_str().unwrap();
+    assert!(message.contains("name must not be empty"), "{message}");
+
+    let resp = graphql(
+        app.clone(),
+        &format!(
+            r#"mutation {{ createEvent(treeId: "{tree_id}", input: {{ eventType: BIRTH, dateSort: "1900-13-01" }}) {{ id }} }}"#
+        ),
+        None,
+    )
+    .await;
+    let message = resp["errors"][0]["message"].as_str().unwrap();
+    assert!(
+        message.contains("date_sort must be a YYYY-MM-DD date"),
+        "{message}"
+    );
+
+    // Checks spanning several fields name the field in the extensions.
+    let resp = graphql(
+        app.clone(),
+        &format!(
+            r#"mutation {{ createPerson(treeId: "{tree_id}", input: {{ sex: MALE }}) {{ id }} }}"#
+        ),
+        None,
+    )
+    .await;
+    let person_id = data(&resp)["createPerson"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let resp = graphql(
+        app.clone(),
+        &format!(
+            r#"mutation {{ addPersonName(personId: "{person_id}", input: {{ nameType: BIRTH, nickname: "Jojo", isPrimary: true }}) {{ id }} }}"#
+        ),
+        None,
+    )
+    .await;
+    let error = &resp["errors"][0];
+    assert_eq!(error["extensions"]["code"], "VALIDATION");
+    assert_eq!(error["extensions"]["field"], "given_names");
+
+    let resp = graphql(
+        app.clone(),
+        &format!(
+            r#"mutation {{ addPersonName(personId: "{person_id}", input: {{ nameType: BIRTH, surname: "Smith", isPrimary: true }}) {{ id }} }}"#
+        ),
+        None,
+    )
+    .await;
+    let name_id = data(&resp)["addPersonName"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let resp = graphql(
+        app,
+        &format!(
+            r#"mutation {{ updatePersonName(id: "{name_id}", input: {{ surname: "" }}) {{ id }} }}"#
+        ),
+        None,
+    )
+    .await;
+    assert_eq!(resp["errors"][0]["extensions"]["field"], "given_names");
+}
+
+// ── Error handling: query limits ─────────────────────────────────────
+
+#[tokio::test]
+async fn test_query_depth_and_complexity_limits() {
+    let app = setup_app().await;
+    let fake = "00000000-0000-0000-0000-000000000000";
+
+    // Recursive family walk, nested well past the default depth limit (the
+    // complexity limit is lifted so the depth check is the one that trips).
+    let config = ApiConfig {
+        graphql_max_complexity: usize::MAX,
+        ..ApiConfig::default()
+    };
+    let deep_app = build_router(AppState::new(setup_db().await).with_config(config));
+    let mut selection = "id".to_string();
+    for _ in 0..5 {
+        selection = format!("families {{ children {{ person {{ {selection} }} }} }}");
+    }
+    let resp = graphql(
+        deep_app,
+        &format!(r#"{{ person(treeId: "{fake}", id: "{fake}") {{ {selection} }} }}"#),
+        None,
+    )
+    .await;
+    assert!(resp["data"].is_null());
+    let message = resp["errors"][0]["message"].as_str().unwrap();
+    assert!(message.contains("nested too deep"), "{message}");
+
+    // Shallow but wide: a large page of persons each listing their events.
+    let resp = graphql(
+        app,
+        &format!(
+            r#"{{ persons(treeId: "{fake}", first: 1000) {{ edges {{ node {{ events {{ id description }} }} }} }} }}"#
+        ),
+        None,
+    )
+    .await;
+    assert!(resp["data"].is_null());
+    let message = resp["errors"][0]["message"].as_str().unwrap();
+    assert!(message.contains("too complex"), "{message}");
+}
+
+// ── Schema SDL ───────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_schema_sdl_endpoint() {
+    let get_sdl = |app: axum::Router| async move {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/graphql/schema.graphql")
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(request).await.unwrap()
+    };
+
+    let response = get_sdl(setup_app().await).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain")
+    );
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let sdl = String::from_utf8(bytes.to_vec()).unwrap();
+    assert!(sdl.contains("type QueryRoot"), "{sdl}");
+    assert!(sdl.contains("createTree"));
+
+    let config = ApiConfig {
+        graphql_expose_schema: false,
+        ..ApiConfig::default()
+    };
+    let hidden = build_router(AppState::new(setup_db().await).with_config(config));
+    assert_eq!(get_sdl(hidden).await.status(), StatusCode::NOT_FOUND);
+}
+
 // ── Place search ─────────────────────────────────────────────────────
 
 #[tokio::test]
@@ -1001,6 +1203,70 @@ async fn test_graphql_import_gedcom() {
     assert_eq!(persons["totalCount"], 2);
 }
 
+#[tokio::test]
+async fn test_person_summary_fields() {
+    let app = setup_app().await;
+    let resp = graphql(
+        app.clone(),
+        r#"mutation { createTree(input: { name: "Summary Tree" }) { id } }"#,
+        None,
+    )
+    .await;
+    let tree_id = data(&resp)["createTree"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let gedcom = concat!(
+        "0 HEAD\n",
+        "1 CHAR UTF-8\n",
+        "0 @I1@ INDI\n",
+        "1 NAME Paul /Martin/\n",
+        "1 SEX M\n",
+        "1 BIRT\n",
+        "2 DATE 3 MAY 1851\n",
+        "1 BURI\n",
+        "2 DATE 1920\n",
+        "0 @I2@ INDI\n",
+        "1 NAME Lise /Martin/\n",
+        "1 SEX F\n",
+        "1 BIRT\n",
+        "2 DATE 1990\n",
+        "0 @I3@ INDI\n",
+        "1 SEX U\n",
+        "0 TRLR\n",
+    );
+    let query = format!(
+        r#"mutation {{ importGedcom(treeId: "{tree_id}", input: {{ gedcom: "{}" }}) {{ personsCount }} }}"#,
+        gedcom.replace('\n', "\\n")
+    );
+    let resp = graphql(app.clone(), &query, None).await;
+    assert_eq!(data(&resp)["importGedcom"]["personsCount"], 3);
+
+    let query = format!(
+        r#"{{ persons(treeId: "{tree_id}") {{ edges {{ node {{ sex displayName lifespan birthYear deathYear isLiving }} }} }} }}"#
+    );
+    let resp = graphql(app.clone(), &query, None).await;
+    let mut nodes: Vec<Value> = data(&resp)["persons"]["edges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["node"].clone())
+        .collect();
+    nodes.sort_by_key(|n| n["sex"].as_str().unwrap().to_string());
+    assert_eq!(
+        nodes,
+        vec![
+            json!({ "sex": "FEMALE", "displayName": "Lise Martin", "lifespan": "1990\u{2013}",
+                    "birthYear": 1990, "deathYear": null, "isLiving": true }),
+            json!({ "sex": "MALE", "displayName": "Paul Martin", "lifespan": "1851\u{2013}1920",
+                    "birthYear": 1851, "deathYear": 1920, "isLiving": false }),
+            json!({ "sex": "UNKNOWN", "displayName": null, "lifespan": null,
+                    "birthYear": null, "deathYear": null, "isLiving": true }),
+        ]
+    );
+}
+
 #[tokio::test]
 async fn test_graphql_export_gedcom() {
     let app = setup_app().await;
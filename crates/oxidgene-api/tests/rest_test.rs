@@ -6,11 +6,17 @@
 use axum::body::Body;
 use axum::http::{Method, Request, StatusCode};
 use http_body_util::BodyExt;
-use oxidgene_api::{AppState, build_router};
+use oxidgene_api::config::{AuthConfig, JwtConfig, TraceConfig};
+use oxidgene_api::service::backup::{Backups, apply_pending_restore};
+use oxidgene_api::service::geocode::{GeoAddress, GeoMatch, Geocoder, Geocoding};
+use oxidgene_api::{ApiConfig, AppState, build_router};
 use oxidgene_db::repo::{connect, run_migrations};
 use sea_orm::DatabaseConnection;
 use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
 use tower::ServiceExt;
+use tracing::Level;
 
 /// Helper: create a fresh in-memory DB with migrations applied.
 async fn setup_db() -> DatabaseConnection {
@@ -148,7 +154,7 @@ async fn test_tree_create_validation() {
     )
     .await;
     assert_eq!(status, StatusCode::BAD_REQUEST);
-    assert_eq!(body["error"], "validation_error");
+    assert_eq!(body["type"], "urn:oxidgene:problem:validation_error");
 }
 
 #[tokio::test]
@@ -164,7 +170,7 @@ async fn test_tree_not_found() {
     )
     .await;
     assert_eq!(status, StatusCode::NOT_FOUND);
-    assert_eq!(body["error"], "not_found");
+    assert_eq!(body["type"], "urn:oxidgene:problem:not_found");
 }
 
 #[tokio::test]
@@ -205,6 +211,364 @@ async fn test_tree_pagination() {
     assert!(!body["page_info"]["has_next_page"].as_bool().unwrap());
 }
 
+#[tokio::test]
+async fn test_tree_counts() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let empty_tree_id = create_tree_via_api(&app).await;
+
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    add_relative_via_api(
+        &app,
+        &tree_id,
+        &person_id,
+        serde_json::json!({ "kind": "father" }),
+    )
+    .await;
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/events"),
+        Some(serde_json::json!({ "event_type": "birth", "person_id": person_id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["person_count"], 2);
+    assert_eq!(body["family_count"], 1);
+    assert_eq!(body["event_count"], 1);
+    assert!(body["last_modified_at"].as_str().unwrap() >= body["updated_at"].as_str().unwrap());
+
+    // The list carries the same counts; an empty tree counts zero.
+    let (status, body) = send_request(app.clone(), Method::GET, "/api/v1/trees", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let node = |id: &str| {
+        body["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| &e["node"])
+            .find(|n| n["id"] == id)
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(node(&tree_id)["person_count"], 2);
+    assert_eq!(node(&tree_id)["event_count"], 1);
+    let empty = node(&empty_tree_id);
+    assert_eq!(empty["person_count"], 0);
+    assert_eq!(empty["family_count"], 0);
+    assert_eq!(empty["last_modified_at"], empty["updated_at"]);
+}
+
+#[tokio::test]
+async fn test_page_size_is_clamped() {
+    let config = ApiConfig {
+        max_page_size: 2,
+        ..ApiConfig::default()
+    };
+    let app = build_router(AppState::new(setup_db().await).with_config(config));
+    for i in 0..3 {
+        send_request(
+            app.clone(),
+            Method::POST,
+            "/api/v1/trees",
+            Some(serde_json::json!({ "name": format!("Tree {i}") })),
+        )
+        .await;
+    }
+
+    // An oversized page is cut to the maximum, which `page_info` reports.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        "/api/v1/trees?first=1000000",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["edges"].as_array().unwrap().len(), 2);
+    assert_eq!(body["page_info"]["limit"], 2);
+    assert!(body["page_info"]["has_next_page"].as_bool().unwrap());
+
+    // Smaller pages are untouched.
+    let (_, body) = send_request(app.clone(), Method::GET, "/api/v1/trees?first=1", None).await;
+    assert_eq!(body["page_info"]["limit"], 1);
+
+    // `limit`-style endpoints share the ceiling.
+    let tree_id = body["edges"][0]["node"]["id"].as_str().unwrap().to_string();
+    let (status, body) = send_request(
+        app,
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/activity?limit=1000000"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.as_array().unwrap().len() <= 2);
+}
+
+#[tokio::test]
+async fn test_trace_config() {
+    let trace = TraceConfig::default();
+    assert!(!trace.traces("/healthz"));
+    assert!(trace.traces("/api/v1/trees"));
+    assert_eq!(trace.response_level(StatusCode::OK), Level::DEBUG);
+    assert_eq!(trace.response_level(StatusCode::NOT_MODIFIED), Level::DEBUG);
+    assert_eq!(trace.response_level(StatusCode::NOT_FOUND), Level::INFO);
+    assert_eq!(trace.span_level(), Level::INFO);
+
+    // Skipped paths are still served.
+    let config = ApiConfig {
+        trace: TraceConfig {
+            skip_paths: vec!["/api/v1/trees".to_string()],
+            ..TraceConfig::default()
+        },
+        ..ApiConfig::default()
+    };
+    let app = build_router(AppState::new(setup_db().await).with_config(config));
+    let (status, body) = send_request(app, Method::GET, "/api/v1/trees", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 0);
+}
+
+#[tokio::test]
+async fn test_tree_check() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    // A clean, empty tree has nothing to report.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/check"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["error_count"], 0);
+    assert_eq!(body["warning_count"], 0);
+
+    // A person without a name is a warning.
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/check"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["warning_count"], 1);
+    let issue = &body["issues"][0];
+    assert_eq!(issue["kind"], "person_without_name");
+    assert_eq!(issue["severity"], "warning");
+    assert_eq!(issue["entity_id"], person_id.as_str());
+    assert!(!issue["suggested_fix"].as_str().unwrap().is_empty());
+
+    let fake_id = uuid::Uuid::now_v7();
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{fake_id}/check"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_tree_replace() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let mut place_ids = Vec::new();
+    for name in ["St. Louis", "St. Louis, Missouri", "Paris"] {
+        let (_, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/places"),
+            Some(serde_json::json!({ "name": name })),
+        )
+        .await;
+        place_ids.push(body["id"].as_str().unwrap().to_string());
+    }
+    let replace = |dry_run: bool, body: serde_json::Value| {
+        let app = app.clone();
+        let uri = format!("/api/v1/trees/{tree_id}/replace?dry_run={dry_run}");
+        async move { send_request(app, Method::POST, &uri, Some(body)).await }
+    };
+    let rename = serde_json::json!({
+        "entity": "place",
+        "field": "name",
+        "find": "St. Louis",
+        "replace": "Saint Louis"
+    });
+
+    // A dry run lists the exact match without writing it.
+    let (status, body) = replace(true, rename.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["dry_run"], true);
+    assert_eq!(body["count"], 1);
+    assert_eq!(body["changes"][0]["id"], place_ids[0]);
+    assert_eq!(body["changes"][0]["before"], "St. Louis");
+    assert_eq!(body["changes"][0]["after"], "Saint Louis");
+    let (_, place) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/places/{}", place_ids[0]),
+        None,
+    )
+    .await;
+    assert_eq!(place["name"], "St. Louis");
+
+    let (status, body) = replace(false, rename).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["count"], 1);
+    let (_, place) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/places/{}", place_ids[0]),
+        None,
+    )
+    .await;
+    assert_eq!(place["name"], "Saint Louis");
+    assert_eq!(place["version"], 2);
+
+    // A regex rewrites every match inside the values.
+    for title in ["Reg. paroissial de Lyon", "Reg. matricule"] {
+        send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/sources"),
+            Some(serde_json::json!({ "title": title })),
+        )
+        .await;
+    }
+    let (status, body) = replace(
+        false,
+        serde_json::json!({
+            "entity": "source",
+            "field": "title",
+            "match": "regex",
+            "find": "^Reg\\. (\\w+)",
+            "replace": "Registre $1"
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["count"], 2);
+    assert_eq!(body["changes"][0]["after"], "Registre paroissial de Lyon");
+    assert_eq!(body["changes"][1]["after"], "Registre matricule");
+
+    // A required field cannot be emptied: nothing is written.
+    let (status, _) = replace(
+        false,
+        serde_json::json!({
+            "entity": "place",
+            "field": "name",
+            "match": "regex",
+            "find": ".*",
+            "replace": ""
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (_, place) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/places/{}", place_ids[2]),
+        None,
+    )
+    .await;
+    assert_eq!(place["name"], "Paris");
+
+    // Unknown fields and invalid patterns are rejected.
+    for body in [
+        serde_json::json!({ "entity": "place", "field": "title", "find": "a", "replace": "b" }),
+        serde_json::json!({
+            "entity": "source", "field": "title", "match": "regex", "find": "(", "replace": "b"
+        }),
+    ] {
+        let (status, body) = replace(true, body).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["type"], "urn:oxidgene:problem:validation_error");
+    }
+}
+
+#[tokio::test]
+async fn test_tree_create_from_template() {
+    let app = setup_app().await;
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        "/api/v1/trees?template=self",
+        Some(serde_json::json!({ "name": "My Family" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let tree_id = body["id"].as_str().unwrap().to_string();
+    let home_id = body["sosa_root_person_id"].as_str().unwrap().to_string();
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 7);
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{home_id}/ancestors"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 6);
+
+    // Grandfather on the paternal side sits at SOSA 4.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/sosa/4"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["sex"], "male");
+
+    // The empty template is a plain tree.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        "/api/v1/trees?template=empty",
+        Some(serde_json::json!({ "name": "Blank" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(body["sosa_root_person_id"].is_null());
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        "/api/v1/trees?template=royal",
+        Some(serde_json::json!({ "name": "Unknown template" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
 // ───────────────────────── Person tests ─────────────────────────
 
 /// Helper: create a tree via the API and return its ID.
@@ -258,6 +622,25 @@ async fn test_person_crud() {
     assert_eq!(status, StatusCode::OK);
     assert_eq!(body["sex"], "female");
 
+    // GEDCOM `SEX X` has its own value, stored as is.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/persons/{person_id}"),
+        Some(serde_json::json!({ "sex": "intersex" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["sex"], "intersex");
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{person_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(body["sex"], "intersex");
+
     // List persons
     let (status, body) = send_request(
         app.clone(),
@@ -374,70 +757,189 @@ async fn test_person_name_crud() {
     assert_eq!(body.as_array().unwrap().len(), 0);
 }
 
-/// Sprint E.6: free-text person search through the normal search path,
-/// backed by the `person_search_fts` FTS5 table, end-to-end over HTTP.
 #[tokio::test]
-async fn test_person_search_free_text() {
+async fn test_normalize_name_case() {
     let app = setup_app().await;
     let tree_id = create_tree_via_api(&app).await;
-
-    // Two persons with primary names created through the REST API
-    // (mutation handlers must keep person_search_fts in sync).
-    let p1 = create_person_via_api(&app, &tree_id).await;
-    send_request(
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    let (_, name) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/persons/{p1}/names"),
+        &format!("/api/v1/trees/{tree_id}/persons/{person_id}/names"),
         Some(serde_json::json!({
             "name_type": "birth",
-            "given_names": "Jean",
-            "surname": "Dupont",
+            "given_names": "JEAN-PIERRE",
+            "surname": "van mcdonald",
             "is_primary": true
         })),
     )
     .await;
-    let p2 = create_person_via_api(&app, &tree_id).await;
-    send_request(
-        app.clone(),
-        Method::POST,
-        &format!("/api/v1/trees/{tree_id}/persons/{p2}/names"),
-        Some(serde_json::json!({
-            "name_type": "birth",
-            "given_names": "Jane",
-            "surname": "Smith",
-            "is_primary": true
-        })),
+
+    let normalize = |dry_run: bool, body: serde_json::Value| {
+        let app = app.clone();
+        let uri = format!("/api/v1/trees/{tree_id}/persons/names/normalize-case?dry_run={dry_run}");
+        async move { send_request(app, Method::POST, &uri, Some(body)).await }
+    };
+
+    let (status, body) = normalize(true, serde_json::json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["dry_run"], true);
+    assert_eq!(body["count"], 1);
+    assert_eq!(body["changes"][0]["name_id"], name["id"]);
+    assert_eq!(body["changes"][0]["before"]["given_names"], "JEAN-PIERRE");
+    assert_eq!(body["changes"][0]["after"]["given_names"], "Jean-Pierre");
+    assert_eq!(body["changes"][0]["after"]["surname"], "van McDonald");
+    assert_eq!(body["skipped_locked"], serde_json::json!([]));
+
+    let (status, body) = normalize(
+        false,
+        serde_json::json!({ "surname": "upper", "preserve_particles": false }),
     )
     .await;
-
-    // Free-text mode returns a SearchResult with entries + total_count.
-    let (status, body) = send_request(
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["count"], 1);
+    let (_, person) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/persons/search?q=dupont"),
+        &format!("/api/v1/trees/{tree_id}/persons/{person_id}"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["total_count"], 1);
-    assert_eq!(body["entries"][0]["display_name"], "Jean Dupont");
+    assert_eq!(person["display_name"], "Jean-Pierre VAN MCDONALD");
 
-    // Accent-folded matching (query without accents finds Jane Smith).
-    let (status, body) = send_request(
-        app.clone(),
-        Method::GET,
-        &format!("/api/v1/trees/{tree_id}/persons/search?q=jane%20smith"),
-        None,
+    // Nothing left to change.
+    let (_, body) = normalize(
+        false,
+        serde_json::json!({ "surname": "upper", "preserve_particles": false }),
     )
     .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["total_count"], 1);
-    assert_eq!(body["entries"][0]["display_name"], "Jane Smith");
+    assert_eq!(body["count"], 0);
 
-    // Empty query = browse mode: everyone, sorted by surname.
-    let (status, body) = send_request(
-        app.clone(),
-        Method::GET,
+    let (status, _) = normalize(true, serde_json::json!({ "surname": "shout" })).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+/// Sprint E.6: free-text person search through the normal search path,
+/// backed by the `person_search_fts` FTS5 table, end-to-end over HTTP.
+#[tokio::test]
+async fn test_person_name_policy() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    for (name_type, surname, is_primary) in
+        [("married", "Martin", true), ("birth", "Dupont", false)]
+    {
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/persons/{person_id}/names"),
+            Some(serde_json::json!({
+                "name_type": name_type,
+                "given_names": "Marie",
+                "surname": surname,
+                "is_primary": is_primary
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let person_uri = format!("/api/v1/trees/{tree_id}/persons/{person_id}");
+    let (status, body) = send_request(app.clone(), Method::GET, &person_uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["display_name"], "Marie Martin");
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("{person_uri}?name_policy=prefer_birth"),
+        None,
+    )
+    .await;
+    assert_eq!(body["display_name"], "Marie Dupont");
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons?name_policy=prefer_birth"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["edges"][0]["node"]["display_name"], "Marie Dupont");
+    assert_eq!(body["edges"][0]["node"]["id"], person_id);
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("{person_uri}?name_policy=nickname"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_person_search_free_text() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    // Two persons with primary names created through the REST API
+    // (mutation handlers must keep person_search_fts in sync).
+    let p1 = create_person_via_api(&app, &tree_id).await;
+    send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/persons/{p1}/names"),
+        Some(serde_json::json!({
+            "name_type": "birth",
+            "given_names": "Jean",
+            "surname": "Dupont",
+            "is_primary": true
+        })),
+    )
+    .await;
+    let p2 = create_person_via_api(&app, &tree_id).await;
+    send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/persons/{p2}/names"),
+        Some(serde_json::json!({
+            "name_type": "birth",
+            "given_names": "Jane",
+            "surname": "Smith",
+            "is_primary": true
+        })),
+    )
+    .await;
+
+    // Free-text mode returns a SearchResult with entries + total_count.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/search?q=dupont"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 1);
+    assert_eq!(body["entries"][0]["display_name"], "Jean Dupont");
+
+    // Accent-folded matching (query without accents finds Jane Smith).
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/search?q=jane%20smith"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 1);
+    assert_eq!(body["entries"][0]["display_name"], "Jane Smith");
+
+    // Empty query = browse mode: everyone, sorted by surname.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
         &format!("/api/v1/trees/{tree_id}/persons/search?q="),
         None,
     )
@@ -522,1101 +1024,6923 @@ async fn test_person_search_free_text() {
     assert_eq!(body["entries"][0]["display_name"], "Jane Smith");
 }
 
-// ───────────────────────── Family tests ─────────────────────────
-
 #[tokio::test]
-async fn test_family_crud() {
+async fn test_person_search_matches_other_names() {
     let app = setup_app().await;
     let tree_id = create_tree_via_api(&app).await;
 
-    // Create a family
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    for (name_type, surname, is_primary) in
+        [("married", "Martin", true), ("birth", "Dupont", false)]
+    {
+        let (status, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/persons/{person_id}/names"),
+            Some(serde_json::json!({
+                "name_type": name_type,
+                "given_names": "Jeanne",
+                "surname": surname,
+                "is_primary": is_primary
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "POST name failed: {body}");
+    }
+
+    // The birth name finds her once, shown under her primary name.
     let (status, body) = send_request(
         app.clone(),
-        Method::POST,
-        &format!("/api/v1/trees/{tree_id}/families"),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/search?q=dupont"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::CREATED);
-    let family_id = body["id"].as_str().unwrap().to_string();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 1);
+    assert_eq!(body["entries"][0]["display_name"], "Jeanne Martin");
+    assert_eq!(body["entries"][0]["matched_name"], "Jeanne Dupont");
 
-    // Get the family
-    let (status, _) = send_request(
+    // A hit on the primary name reports no alias.
+    let (_, body) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/families/{family_id}"),
+        &format!("/api/v1/trees/{tree_id}/persons/search?q=martin"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 1);
+    assert!(body["entries"][0]["matched_name"].is_null());
+}
 
-    // Update the family (touches updated_at)
+#[tokio::test]
+async fn test_move_person_between_trees() {
+    let app = setup_app().await;
+    let source_tree = create_tree_via_api(&app).await;
+    let target_tree = create_tree_via_api(&app).await;
+
+    let person_id = create_person_via_api(&app, &source_tree).await;
+    send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{source_tree}/persons/{person_id}/names"),
+        Some(serde_json::json!({
+            "name_type": "birth",
+            "given_names": "Jeanne",
+            "surname": "Dupont",
+            "is_primary": true
+        })),
+    )
+    .await;
     let (status, _) = send_request(
         app.clone(),
-        Method::PUT,
-        &format!("/api/v1/trees/{tree_id}/families/{family_id}"),
-        None,
+        Method::POST,
+        &format!("/api/v1/trees/{source_tree}/persons/{person_id}/relatives"),
+        Some(serde_json::json!({ "kind": "father", "surname": "Dupont" })),
     )
     .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Both trees are searched at once.
+    let (status, body) =
+        send_request(app.clone(), Method::GET, "/api/v1/search?q=dupont", None).await;
     assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 2);
 
-    // List families
     let (status, body) = send_request(
         app.clone(),
-        Method::GET,
-        &format!("/api/v1/trees/{tree_id}/families"),
+        Method::POST,
+        &format!("/api/v1/trees/{source_tree}/persons/{person_id}/move/{target_tree}"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["total_count"], 1);
+    assert_eq!(status, StatusCode::OK, "move failed: {body}");
+    assert_eq!(body["person"]["tree_id"], target_tree);
+    assert_eq!(body["warnings"].as_array().unwrap().len(), 1);
 
-    // Delete the family
-    let (status, _) = send_request(
+    // The search index follows the person.
+    let (_, body) = send_request(
         app.clone(),
-        Method::DELETE,
-        &format!("/api/v1/trees/{tree_id}/families/{family_id}"),
+        Method::GET,
+        &format!("/api/v1/trees/{target_tree}/persons/search?q=jeanne"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::NO_CONTENT);
-
-    // Verify it's gone
-    let (status, _) = send_request(
+    assert_eq!(body["total_count"], 1);
+    let (_, body) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/families/{family_id}"),
+        &format!("/api/v1/trees/{source_tree}/persons/search?q=jeanne"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::NOT_FOUND);
-}
-
-// ───────────────────────── Family member tests ─────────────────────────
-
-#[tokio::test]
-async fn test_family_spouse_add_remove() {
-    let app = setup_app().await;
-    let tree_id = create_tree_via_api(&app).await;
-    let person_id = create_person_via_api(&app, &tree_id).await;
+    assert_eq!(body["total_count"], 0);
+    let (_, body) = send_request(app.clone(), Method::GET, "/api/v1/search?q=jeanne", None).await;
+    assert_eq!(body["total_count"], 1);
+    assert_eq!(body["entries"][0]["tree_id"], target_tree);
+    assert_eq!(body["entries"][0]["display_name"], "Jeanne Dupont");
+    assert!(body["entries"][0]["tree_name"].is_string());
 
-    // Create a family
+    // The person is no longer in the source tree.
     let (_, body) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/families"),
+        &format!("/api/v1/trees/{source_tree}/persons/{person_id}/move/{target_tree}"),
         None,
     )
     .await;
-    let family_id = body["id"].as_str().unwrap().to_string();
+    assert_eq!(body["status"], 404, "{body}");
+}
+
+#[tokio::test]
+async fn test_surname_index() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    let add_person = async |given: &str, surname: &str, is_primary: bool| {
+        let person_id = create_person_via_api(&app, &tree_id).await;
+        send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/persons/{person_id}/names"),
+            Some(serde_json::json!({
+                "name_type": "birth",
+                "given_names": given,
+                "surname": surname,
+                "is_primary": is_primary
+            })),
+        )
+        .await;
+        person_id
+    };
+    let jean = add_person("Jean", "Dupont", true).await;
+    let marie = add_person("Marie", "Dupont", true).await;
+    add_person("Louis", "\u{00C9}mery", true).await;
+    add_person("Anne", "Martin", true).await;
+    // Only primary names are indexed.
+    add_person("Paul", "Zola", false).await;
 
-    // Add a spouse
     let (status, body) = send_request(
         app.clone(),
-        Method::POST,
-        &format!("/api/v1/trees/{tree_id}/families/{family_id}/spouses"),
-        Some(serde_json::json!({
-            "person_id": person_id,
-            "role": "husband",
-            "sort_order": 0
-        })),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/surname-index"),
+        None,
     )
     .await;
-    assert_eq!(status, StatusCode::CREATED);
-    assert_eq!(body["role"], "husband");
-    let spouse_id = body["id"].as_str().unwrap().to_string();
+    assert_eq!(status, StatusCode::OK);
+    let index: Vec<(String, String, i64)> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            (
+                e["initial"].as_str().unwrap().to_string(),
+                e["surname"].as_str().unwrap().to_string(),
+                e["count"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        index,
+        [
+            ("D".to_string(), "Dupont".to_string(), 2),
+            ("E".to_string(), "\u{00C9}mery".to_string(), 1),
+            ("M".to_string(), "Martin".to_string(), 1),
+        ]
+    );
 
-    // Remove the spouse
-    let (status, _) = send_request(
+    let (status, body) = send_request(
         app.clone(),
-        Method::DELETE,
-        &format!("/api/v1/trees/{tree_id}/families/{family_id}/spouses/{spouse_id}"),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons?surname=Dupont"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 2);
+    let mut ids: Vec<&str> = body["edges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["node"]["id"].as_str().unwrap())
+        .collect();
+    ids.sort();
+    let mut expected = [jean.as_str(), marie.as_str()];
+    expected.sort();
+    assert_eq!(ids, expected);
 }
 
 #[tokio::test]
-async fn test_family_child_add_remove() {
+async fn test_surname_stats() {
     let app = setup_app().await;
     let tree_id = create_tree_via_api(&app).await;
-    let person_id = create_person_via_api(&app, &tree_id).await;
+    for (surname, birth_date, birth_place) in [
+        ("Dupont", "3 MAR 1852", "Lyon"),
+        ("DUPONT", "1858", "Lyon"),
+        ("Dupont", "1871", "Paris"),
+        ("Martin", "1855", "Paris"),
+        ("Zola", "", "Paris"),
+    ] {
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/persons/quick"),
+            Some(serde_json::json!({
+                "surname": surname,
+                "birth_date": birth_date,
+                "birth_place": birth_place,
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
 
-    // Create a family
-    let (_, body) = send_request(
+    // Spellings of one surname count together; undated births are left out.
+    let (status, body) = send_request(
         app.clone(),
-        Method::POST,
-        &format!("/api/v1/trees/{tree_id}/families"),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/stats/surnames?over=decade"),
         None,
     )
     .await;
-    let family_id = body["id"].as_str().unwrap().to_string();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["over"], "decade");
+    assert_eq!(
+        body["surnames"],
+        serde_json::json!([
+            { "surname": "DUPONT", "count": 3 },
+            { "surname": "Martin", "count": 1 },
+        ])
+    );
+    assert_eq!(body["buckets"], serde_json::json!(["1850", "1870"]));
+    assert_eq!(
+        body["counts"],
+        serde_json::json!([
+            { "surname": "DUPONT", "bucket": "1850", "count": 2 },
+            { "surname": "DUPONT", "bucket": "1870", "count": 1 },
+            { "surname": "Martin", "bucket": "1850", "count": 1 },
+        ])
+    );
 
-    // Add a child
     let (status, body) = send_request(
         app.clone(),
-        Method::POST,
-        &format!("/api/v1/trees/{tree_id}/families/{family_id}/children"),
-        Some(serde_json::json!({
-            "person_id": person_id,
-            "child_type": "biological",
-            "sort_order": 0
-        })),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/stats/surnames?over=place&limit=1"),
+        None,
     )
     .await;
-    assert_eq!(status, StatusCode::CREATED);
-    assert_eq!(body["child_type"], "biological");
-    let child_id = body["id"].as_str().unwrap().to_string();
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["surnames"],
+        serde_json::json!([{ "surname": "DUPONT", "count": 3 }])
+    );
+    assert_eq!(body["buckets"], serde_json::json!(["Lyon", "Paris"]));
+    assert_eq!(
+        body["counts"],
+        serde_json::json!([
+            { "surname": "DUPONT", "bucket": "Lyon", "count": 2 },
+            { "surname": "DUPONT", "bucket": "Paris", "count": 1 },
+        ])
+    );
 
-    // Remove the child
     let (status, _) = send_request(
         app.clone(),
-        Method::DELETE,
-        &format!("/api/v1/trees/{tree_id}/families/{family_id}/children/{child_id}"),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/stats/surnames?over=century"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(status, StatusCode::BAD_REQUEST);
 }
 
-// ───────────────────────── Ancestry tests ─────────────────────────
-
 #[tokio::test]
-async fn test_ancestors_descendants_empty() {
+async fn test_name_matching_ignores_case_and_accents() {
     let app = setup_app().await;
     let tree_id = create_tree_via_api(&app).await;
-    let person_id = create_person_via_api(&app, &tree_id).await;
 
-    // Ancestors — should be empty
-    let (status, body) = send_request(
-        app.clone(),
-        Method::GET,
-        &format!("/api/v1/trees/{tree_id}/persons/{person_id}/ancestors"),
-        None,
-    )
-    .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body.as_array().unwrap().len(), 0);
+    let mut persons = Vec::new();
+    for (given, surname) in [
+        ("Fran\u{00E7}ois", "Wr\u{00F3}bel"),
+        ("FRANCOIS", "wrobel"),
+        (
+            "\u{0418}\u{0432}\u{0430}\u{043D}",
+            "\u{041F}\u{0435}\u{0442}\u{0440}\u{043E}\u{0432}",
+        ),
+        (
+            "\u{0438}\u{0432}\u{0430}\u{043D}",
+            "\u{043F}\u{0435}\u{0442}\u{0440}\u{043E}\u{0432}",
+        ),
+    ] {
+        let person_id = create_person_via_api(&app, &tree_id).await;
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/persons/{person_id}/names"),
+            Some(serde_json::json!({
+                "name_type": "birth",
+                "given_names": given,
+                "surname": surname,
+                "is_primary": true
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        persons.push(person_id);
+    }
 
-    // Descendants — should be empty
+    for surname in ["wrobel", "WR%C3%93BEL", "Wrobel"] {
+        let (status, body) = send_request(
+            app.clone(),
+            Method::GET,
+            &format!("/api/v1/trees/{tree_id}/persons?surname={surname}"),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total_count"], 2, "surname={surname}");
+    }
+
+    // Cyrillic names have no Soundex code: they pair on their folded form.
     let (status, body) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/persons/{person_id}/descendants"),
+        &format!("/api/v1/trees/{tree_id}/persons/duplicates"),
         None,
     )
     .await;
     assert_eq!(status, StatusCode::OK);
-    assert_eq!(body.as_array().unwrap().len(), 0);
-}
-
-// ───────────────────────── Error handling tests ─────────────────────────
-
-#[tokio::test]
-async fn test_invalid_uuid_path_returns_400() {
-    let app = setup_app().await;
-
-    let (status, _) =
-        send_request(app.clone(), Method::GET, "/api/v1/trees/not-a-uuid", None).await;
-    // Axum returns 400 for path deserialization failures
-    assert!(
-        status == StatusCode::BAD_REQUEST || status == StatusCode::NOT_FOUND,
-        "Expected 400 or 404, got {status}"
-    );
-}
-
-#[tokio::test]
-async fn test_invalid_json_body_returns_error() {
-    let app = setup_app().await;
-
-    let request = Request::builder()
-        .method(Method::POST)
-        .uri("/api/v1/trees")
-        .header("content-type", "application/json")
-        .body(Body::from(r#"{"invalid json"#))
-        .unwrap();
-
-    let response = app.oneshot(request).await.unwrap();
-    let status = response.status();
-    // Axum returns 400 for JSON syntax errors, 422 for deserialization failures
-    assert!(
-        status == StatusCode::BAD_REQUEST || status == StatusCode::UNPROCESSABLE_ENTITY,
-        "Expected 400 or 422, got {status}"
+    let mut pairs: Vec<(&str, &str)> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| {
+            (
+                p["a"]["id"].as_str().unwrap(),
+                p["b"]["id"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    pairs.sort();
+    assert_eq!(
+        pairs,
+        [
+            (persons[0].as_str(), persons[1].as_str()),
+            (persons[2].as_str(), persons[3].as_str()),
+        ]
     );
 }
 
-// ───────────────────────── Event tests ─────────────────────────
-
 #[tokio::test]
-async fn test_event_crud() {
+async fn test_person_tags() {
     let app = setup_app().await;
     let tree_id = create_tree_via_api(&app).await;
-    let person_id = create_person_via_api(&app, &tree_id).await;
+    let alice = create_person_via_api(&app, &tree_id).await;
+    let bob = create_person_via_api(&app, &tree_id).await;
+    create_person_via_api(&app, &tree_id).await;
 
-    // Create an event
-    let (status, body) = send_request(
-        app.clone(),
-        Method::POST,
-        &format!("/api/v1/trees/{tree_id}/events"),
-        Some(serde_json::json!({
-            "event_type": "birth",
-            "date_value": "1 JAN 1990",
-            "date_sort": "1990-01-01",
-            "person_id": person_id,
-            "description": "Born in London"
-        })),
-    )
-    .await;
+    let tag = async |person_id: &str, tag: &str| {
+        send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/persons/{person_id}/tags"),
+            Some(serde_json::json!({ "tag": tag })),
+        )
+        .await
+    };
+    let (status, body) = tag(&alice, "  brick   wall ").await;
     assert_eq!(status, StatusCode::CREATED);
-    assert_eq!(body["event_type"], "birth");
-    assert_eq!(body["description"], "Born in London");
-    let event_id = body["id"].as_str().unwrap().to_string();
+    assert_eq!(body["tag"], "brick wall");
+    // Tagging twice keeps a single tag.
+    let (status, again) = tag(&alice, "brick wall").await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(again["id"], body["id"]);
+    tag(&alice, "needs sources").await;
+    tag(&bob, "needs sources").await;
+    let (status, _) = tag(&bob, "   ").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
 
-    // Get the event
     let (status, body) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/events/{event_id}"),
+        &format!("/api/v1/trees/{tree_id}/tags"),
         None,
     )
     .await;
     assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["event_type"], "birth");
-
-    // Update the event
-    let (status, body) = send_request(
-        app.clone(),
-        Method::PUT,
-        &format!("/api/v1/trees/{tree_id}/events/{event_id}"),
-        Some(serde_json::json!({
-            "description": "Born in London"
-        })),
-    )
-    .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["description"], "Born in London");
+    assert_eq!(
+        body,
+        serde_json::json!([
+            { "tag": "brick wall", "count": 1 },
+            { "tag": "needs sources", "count": 2 }
+        ])
+    );
 
-    // List events (no filter)
-    let (status, body) = send_request(
+    let (_, body) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/events"),
+        &format!("/api/v1/trees/{tree_id}/persons?tag=needs%20sources"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["total_count"], 1);
+    assert_eq!(body["total_count"], 2);
 
-    // List events (filter by person_id)
-    let (status, body) = send_request(
+    let (_, body) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/events?person_id={person_id}"),
+        &format!("/api/v1/trees/{tree_id}/person-tags"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["total_count"], 1);
+    assert_eq!(body.as_array().unwrap().len(), 3);
 
-    // List events (filter by event_type)
-    let (status, body) = send_request(
+    let (status, _) = send_request(
         app.clone(),
-        Method::GET,
-        &format!("/api/v1/trees/{tree_id}/events?event_type=birth"),
+        Method::DELETE,
+        &format!("/api/v1/trees/{tree_id}/persons/{alice}/tags/brick%20wall"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["total_count"], 1);
-
-    // Delete the event
-    let (status, _) = send_request(
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, body) = send_request(
         app.clone(),
-        Method::DELETE,
-        &format!("/api/v1/trees/{tree_id}/events/{event_id}"),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{alice}/tags"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::NO_CONTENT);
+    let tags: Vec<&str> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["tag"].as_str().unwrap())
+        .collect();
+    assert_eq!(tags, ["needs sources"]);
 
-    // Verify it's gone
     let (status, _) = send_request(
         app.clone(),
-        Method::GET,
-        &format!("/api/v1/trees/{tree_id}/events/{event_id}"),
+        Method::DELETE,
+        &format!("/api/v1/trees/{tree_id}/persons/{alice}/tags/brick%20wall"),
         None,
     )
     .await;
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
-// ───────────────────────── Place tests ─────────────────────────
+// ───────────────────────── Family tests ─────────────────────────
 
 #[tokio::test]
-async fn test_place_crud() {
+async fn test_family_crud() {
     let app = setup_app().await;
     let tree_id = create_tree_via_api(&app).await;
 
-    // Create a place
+    // Create a family
     let (status, body) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/places"),
-        Some(serde_json::json!({
-            "name": "Paris, France",
-            "latitude": 48.8566,
-            "longitude": 2.3522
-        })),
+        &format!("/api/v1/trees/{tree_id}/families"),
+        None,
     )
     .await;
     assert_eq!(status, StatusCode::CREATED);
-    assert_eq!(body["name"], "Paris, France");
-    let place_id = body["id"].as_str().unwrap().to_string();
+    let family_id = body["id"].as_str().unwrap().to_string();
 
-    // Get the place
-    let (status, body) = send_request(
+    // Get the family
+    let (status, _) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/places/{place_id}"),
+        &format!("/api/v1/trees/{tree_id}/families/{family_id}"),
         None,
     )
     .await;
     assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["name"], "Paris, France");
 
-    // Update the place
-    let (status, body) = send_request(
+    // Update the family (touches updated_at)
+    let (status, _) = send_request(
         app.clone(),
         Method::PUT,
-        &format!("/api/v1/trees/{tree_id}/places/{place_id}"),
-        Some(serde_json::json!({
-            "name": "Lyon, France"
-        })),
-    )
-    .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["name"], "Lyon, France");
-
-    // List places
-    let (status, body) = send_request(
-        app.clone(),
-        Method::GET,
-        &format!("/api/v1/trees/{tree_id}/places"),
+        &format!("/api/v1/trees/{tree_id}/families/{family_id}"),
         None,
     )
     .await;
     assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["total_count"], 1);
 
-    // List places with search
+    // List families
     let (status, body) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/places?search=Lyon"),
+        &format!("/api/v1/trees/{tree_id}/families"),
         None,
     )
     .await;
     assert_eq!(status, StatusCode::OK);
     assert_eq!(body["total_count"], 1);
 
-    // Search for non-existent place
-    let (status, body) = send_request(
+    // Delete the family
+    let (status, _) = send_request(
         app.clone(),
-        Method::GET,
-        &format!("/api/v1/trees/{tree_id}/places?search=Berlin"),
+        Method::DELETE,
+        &format!("/api/v1/trees/{tree_id}/families/{family_id}"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["total_count"], 0);
+    assert_eq!(status, StatusCode::NO_CONTENT);
 
-    // Delete the place
+    // Verify it's gone
     let (status, _) = send_request(
         app.clone(),
-        Method::DELETE,
-        &format!("/api/v1/trees/{tree_id}/places/{place_id}"),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/families/{family_id}"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
+// ───────────────────────── Family member tests ─────────────────────────
+
 #[tokio::test]
-async fn test_place_create_validation() {
+async fn test_family_spouse_add_remove() {
     let app = setup_app().await;
     let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
 
-    // Empty name should fail
-    let (status, body) = send_request(
+    // Create a family
+    let (_, body) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/places"),
-        Some(serde_json::json!({
-            "name": "   "
-        })),
+        &format!("/api/v1/trees/{tree_id}/families"),
+        None,
     )
     .await;
-    assert_eq!(status, StatusCode::BAD_REQUEST);
-    assert_eq!(body["error"], "validation_error");
-}
-
-// ───────────────────────── Source tests ─────────────────────────
-
-#[tokio::test]
-async fn test_source_crud() {
-    let app = setup_app().await;
-    let tree_id = create_tree_via_api(&app).await;
+    let family_id = body["id"].as_str().unwrap().to_string();
 
-    // Create a source
+    // Add a spouse
     let (status, body) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/sources"),
+        &format!("/api/v1/trees/{tree_id}/families/{family_id}/spouses"),
         Some(serde_json::json!({
-            "title": "Parish Records of Lyon",
-            "author": "Catholic Church",
-            "publisher": "Diocese of Lyon"
+            "person_id": person_id,
+            "role": "husband",
+            "sort_order": 0
         })),
     )
     .await;
     assert_eq!(status, StatusCode::CREATED);
-    assert_eq!(body["title"], "Parish Records of Lyon");
-    assert_eq!(body["author"], "Catholic Church");
-    let source_id = body["id"].as_str().unwrap().to_string();
-
-    // Get the source
-    let (status, body) = send_request(
-        app.clone(),
-        Method::GET,
-        &format!("/api/v1/trees/{tree_id}/sources/{source_id}"),
-        None,
-    )
-    .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["title"], "Parish Records of Lyon");
-
-    // Update the source
-    let (status, body) = send_request(
-        app.clone(),
-        Method::PUT,
-        &format!("/api/v1/trees/{tree_id}/sources/{source_id}"),
-        Some(serde_json::json!({
-            "title": "Parish Records of Paris",
-            "author": "Archdiocese of Paris"
-        })),
-    )
-    .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["title"], "Parish Records of Paris");
-    assert_eq!(body["author"], "Archdiocese of Paris");
-
-    // List sources
-    let (status, body) = send_request(
-        app.clone(),
-        Method::GET,
-        &format!("/api/v1/trees/{tree_id}/sources"),
-        None,
-    )
-    .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["total_count"], 1);
+    assert_eq!(body["role"], "husband");
+    let spouse_id = body["id"].as_str().unwrap().to_string();
 
-    // Delete the source
+    // Remove the spouse
     let (status, _) = send_request(
         app.clone(),
         Method::DELETE,
-        &format!("/api/v1/trees/{tree_id}/sources/{source_id}"),
+        &format!("/api/v1/trees/{tree_id}/families/{family_id}/spouses/{spouse_id}"),
         None,
     )
     .await;
     assert_eq!(status, StatusCode::NO_CONTENT);
-
-    // Verify it's gone
-    let (status, _) = send_request(
-        app.clone(),
-        Method::GET,
-        &format!("/api/v1/trees/{tree_id}/sources/{source_id}"),
-        None,
-    )
-    .await;
-    assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
 #[tokio::test]
-async fn test_source_create_validation() {
+async fn test_family_spouse_role_defaults_from_sex() {
     let app = setup_app().await;
     let tree_id = create_tree_via_api(&app).await;
-
-    // Empty title should fail
-    let (status, body) = send_request(
+    let (_, body) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/sources"),
-        Some(serde_json::json!({
-            "title": ""
-        })),
+        &format!("/api/v1/trees/{tree_id}/families"),
+        None,
     )
     .await;
-    assert_eq!(status, StatusCode::BAD_REQUEST);
-    assert_eq!(body["error"], "validation_error");
-}
+    let family_id = body["id"].as_str().unwrap().to_string();
 
-// ───────────────────────── Citation tests ─────────────────────────
+    for (sex, role) in [
+        ("male", "husband"),
+        ("female", "wife"),
+        ("unknown", "partner"),
+    ] {
+        let (_, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/persons"),
+            Some(serde_json::json!({ "sex": sex })),
+        )
+        .await;
+        let person_id = body["id"].as_str().unwrap();
+        let (status, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/families/{family_id}/spouses"),
+            Some(serde_json::json!({ "person_id": person_id })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["role"], role, "default role for {sex}");
+    }
 
-/// Helper: create a source via the API and return its ID.
-async fn create_source_via_api(app: &axum::Router, tree_id: &str) -> String {
-    let (_, body) = send_request(
+    // An explicit role still wins, e.g. for a same-sex couple.
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    let (status, body) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/sources"),
-        Some(serde_json::json!({
-            "title": "Test Source"
-        })),
+        &format!("/api/v1/trees/{tree_id}/families/{family_id}/spouses"),
+        Some(serde_json::json!({ "person_id": person_id, "role": "partner" })),
     )
     .await;
-    body["id"].as_str().unwrap().to_string()
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["role"], "partner");
 }
 
 #[tokio::test]
-async fn test_citation_crud() {
+async fn test_family_child_add_remove() {
     let app = setup_app().await;
     let tree_id = create_tree_via_api(&app).await;
-    let source_id = create_source_via_api(&app, &tree_id).await;
     let person_id = create_person_via_api(&app, &tree_id).await;
 
-    // Create a citation
-    let (status, body) = send_request(
+    // Create a family
+    let (_, body) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/citations"),
-        Some(serde_json::json!({
-            "source_id": source_id,
-            "person_id": person_id,
-            "page": "p. 42",
-            "confidence": "high",
-            "text": "Birth record found"
-        })),
+        &format!("/api/v1/trees/{tree_id}/families"),
+        None,
     )
     .await;
-    assert_eq!(status, StatusCode::CREATED);
-    assert_eq!(body["page"], "p. 42");
-    assert_eq!(body["confidence"], "high");
-    let citation_id = body["id"].as_str().unwrap().to_string();
+    let family_id = body["id"].as_str().unwrap().to_string();
 
-    // Update the citation
+    // Add a child
     let (status, body) = send_request(
         app.clone(),
-        Method::PUT,
-        &format!("/api/v1/trees/{tree_id}/citations/{citation_id}"),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/families/{family_id}/children"),
         Some(serde_json::json!({
-            "page": "p. 43",
-            "text": "Updated record"
+            "person_id": person_id,
+            "child_type": "biological",
+            "sort_order": 0
         })),
     )
     .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["page"], "p. 43");
-    assert_eq!(body["text"], "Updated record");
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["child_type"], "biological");
+    let child_id = body["id"].as_str().unwrap().to_string();
 
-    // Delete the citation
+    // Remove the child
     let (status, _) = send_request(
         app.clone(),
         Method::DELETE,
-        &format!("/api/v1/trees/{tree_id}/citations/{citation_id}"),
+        &format!("/api/v1/trees/{tree_id}/families/{family_id}/children/{child_id}"),
         None,
     )
     .await;
     assert_eq!(status, StatusCode::NO_CONTENT);
 }
 
-// ───────────────────────── Media tests ─────────────────────────
-
 #[tokio::test]
-async fn test_media_crud() {
+async fn test_family_children_reorder() {
     let app = setup_app().await;
     let tree_id = create_tree_via_api(&app).await;
-
-    // Create media
-    let (status, body) = send_request(
+    let (_, body) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/media"),
-        Some(serde_json::json!({
-            "file_name": "photo.jpg",
-            "mime_type": "image/jpeg",
-            "file_path": "/uploads/photo.jpg",
-            "file_size": 1024000,
-            "title": "Family portrait",
-            "description": "Summer 1990"
-        })),
+        &format!("/api/v1/trees/{tree_id}/families"),
+        None,
     )
     .await;
-    assert_eq!(status, StatusCode::CREATED);
-    assert_eq!(body["file_name"], "photo.jpg");
-    assert_eq!(body["title"], "Family portrait");
-    let media_id = body["id"].as_str().unwrap().to_string();
+    let family_id = body["id"].as_str().unwrap().to_string();
+    let children_url = format!("/api/v1/trees/{tree_id}/families/{family_id}/children");
 
-    // Get media
+    // Children added with sort_order 0 are placed last, in insertion order.
+    let mut link_ids = Vec::new();
+    for expected_order in 0..3 {
+        let person_id = create_person_via_api(&app, &tree_id).await;
+        let (status, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &children_url,
+            Some(serde_json::json!({
+                "person_id": person_id,
+                "child_type": "biological",
+                "sort_order": 0
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["sort_order"], expected_order);
+        link_ids.push(body["id"].as_str().unwrap().to_string());
+    }
+
+    // Reverse the order.
+    let reversed: Vec<String> = link_ids.iter().rev().cloned().collect();
     let (status, body) = send_request(
         app.clone(),
-        Method::GET,
-        &format!("/api/v1/trees/{tree_id}/media/{media_id}"),
-        None,
+        Method::PATCH,
+        &format!("{children_url}/reorder"),
+        Some(serde_json::json!({ "child_ids": reversed })),
     )
     .await;
     assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["file_name"], "photo.jpg");
-
-    // Update media
-    let (status, body) = send_request(
+    let ids: Vec<&str> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, reversed);
+
+    // The list endpoint returns the new order.
+    let (_, body) = send_request(app.clone(), Method::GET, &children_url, None).await;
+    let ids: Vec<&str> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, reversed);
+
+    // A list missing a child is rejected.
+    let (status, _) = send_request(
         app.clone(),
-        Method::PUT,
-        &format!("/api/v1/trees/{tree_id}/media/{media_id}"),
-        Some(serde_json::json!({
-            "title": "Updated portrait",
-            "description": "Winter 1990"
-        })),
+        Method::PATCH,
+        &format!("{children_url}/reorder"),
+        Some(serde_json::json!({ "child_ids": &reversed[..2] })),
     )
     .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["title"], "Updated portrait");
-    assert_eq!(body["description"], "Winter 1990");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
 
-    // List media
-    let (status, body) = send_request(
+#[tokio::test]
+async fn test_family_children_bulk_add() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let father_id = create_person_via_api(&app, &tree_id).await;
+    let (_, body) = send_request(
         app.clone(),
-        Method::GET,
-        &format!("/api/v1/trees/{tree_id}/media"),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/families"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["total_count"], 1);
+    let family_id = body["id"].as_str().unwrap().to_string();
+    send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/families/{family_id}/spouses"),
+        Some(serde_json::json!({ "person_id": father_id, "role": "husband" })),
+    )
+    .await;
+    let children_url = format!("/api/v1/trees/{tree_id}/families/{family_id}/children");
 
-    // Delete media
+    let eldest = create_person_via_api(&app, &tree_id).await;
     let (status, _) = send_request(
         app.clone(),
-        Method::DELETE,
-        &format!("/api/v1/trees/{tree_id}/media/{media_id}"),
-        None,
+        Method::POST,
+        &children_url,
+        Some(serde_json::json!({ "person_id": eldest, "child_type": "biological" })),
     )
     .await;
-    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(status, StatusCode::CREATED);
 
-    // Verify it's gone
-    let (status, _) = send_request(
+    // The sibship goes after the existing child, in the given order.
+    let second = create_person_via_api(&app, &tree_id).await;
+    let third = create_person_via_api(&app, &tree_id).await;
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("{children_url}/bulk"),
+        Some(serde_json::json!({ "children": [
+            { "person_id": second, "child_type": "biological" },
+            { "person_id": third, "child_type": "adopted" },
+        ]})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let created = body.as_array().unwrap();
+    assert_eq!(created.len(), 2);
+    assert_eq!(created[0]["person_id"], second.as_str());
+    assert_eq!(created[0]["sort_order"], 1);
+    assert_eq!(created[1]["person_id"], third.as_str());
+    assert_eq!(created[1]["sort_order"], 2);
+    assert_eq!(created[1]["child_type"], "adopted");
+
+    // The closure covers the new children.
+    let (_, ancestors) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/media/{media_id}"),
+        &format!("/api/v1/trees/{tree_id}/persons/{third}/ancestors"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::NOT_FOUND);
-}
-
-#[tokio::test]
-async fn test_media_create_validation() {
-    let app = setup_app().await;
-    let tree_id = create_tree_via_api(&app).await;
+    assert!(
+        ancestors
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|a| a["ancestor_id"] == father_id.as_str() && a["depth"] == 1)
+    );
 
-    // Empty file_name should fail
-    let (status, body) = send_request(
+    // A person already in the family rejects the whole batch.
+    let fourth = create_person_via_api(&app, &tree_id).await;
+    let (status, _) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/media"),
-        Some(serde_json::json!({
-            "file_name": "  ",
-            "mime_type": "image/jpeg",
-            "file_path": "/uploads/photo.jpg",
-            "file_size": 1024
-        })),
+        &format!("{children_url}/bulk"),
+        Some(serde_json::json!({ "children": [
+            { "person_id": fourth, "child_type": "biological" },
+            { "person_id": eldest, "child_type": "biological" },
+        ]})),
     )
     .await;
     assert_eq!(status, StatusCode::BAD_REQUEST);
-    assert_eq!(body["error"], "validation_error");
+    let (_, body) = send_request(app.clone(), Method::GET, &children_url, None).await;
+    assert_eq!(body.as_array().unwrap().len(), 3);
 }
 
-// ───────────────────────── MediaLink tests ─────────────────────────
-
 #[tokio::test]
-async fn test_media_link_create_delete() {
+async fn test_family_children_sort_by_birth() {
     let app = setup_app().await;
     let tree_id = create_tree_via_api(&app).await;
-    let person_id = create_person_via_api(&app, &tree_id).await;
-
-    // Create media first
-    let (_, media_body) = send_request(
+    let (_, body) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/media"),
-        Some(serde_json::json!({
-            "file_name": "doc.pdf",
-            "mime_type": "application/pdf",
-            "file_path": "/uploads/doc.pdf",
-            "file_size": 2048
-        })),
-    )
+        &format!("/api/v1/trees/{tree_id}/families"),
+        None,
+    )
     .await;
-    let media_id = media_body["id"].as_str().unwrap().to_string();
+    let family_id = body["id"].as_str().unwrap().to_string();
+    let children_url = format!("/api/v1/trees/{tree_id}/families/{family_id}/children");
+
+    // Added in this order: undated, 1990, 1985, undated.
+    let mut link_ids = Vec::new();
+    for birth in [None, Some("1990-05-01"), Some("1985-02-11"), None] {
+        let person_id = create_person_via_api(&app, &tree_id).await;
+        if let Some(date) = birth {
+            send_request(
+                app.clone(),
+                Method::POST,
+                &format!("/api/v1/trees/{tree_id}/events"),
+                Some(serde_json::json!({
+                    "event_type": "birth",
+                    "date_sort": date,
+                    "person_id": person_id
+                })),
+            )
+            .await;
+        }
+        let (_, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &children_url,
+            Some(serde_json::json!({
+                "person_id": person_id,
+                "child_type": "biological"
+            })),
+        )
+        .await;
+        link_ids.push(body["id"].as_str().unwrap().to_string());
+    }
 
-    // Create a media link
     let (status, body) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/media-links"),
-        Some(serde_json::json!({
-            "media_id": media_id,
-            "person_id": person_id,
-            "sort_order": 1
-        })),
+        &format!("{children_url}/sort-by-birth"),
+        None,
     )
     .await;
-    assert_eq!(status, StatusCode::CREATED);
-    assert_eq!(body["media_id"], media_id);
-    assert_eq!(body["person_id"], person_id);
-    let link_id = body["id"].as_str().unwrap().to_string();
+    assert_eq!(status, StatusCode::OK);
+    let ids: Vec<&str> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["id"].as_str().unwrap())
+        .collect();
+    // Dated children oldest first, then the undated ones in their prior order.
+    assert_eq!(
+        ids,
+        [&link_ids[2], &link_ids[1], &link_ids[0], &link_ids[3]]
+    );
+    let orders: Vec<i64> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["sort_order"].as_i64().unwrap())
+        .collect();
+    assert_eq!(orders, [0, 1, 2, 3]);
+}
 
-    // Delete the media link
+#[tokio::test]
+async fn test_family_events() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let (_, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/families"),
+        None,
+    )
+    .await;
+    let family_id = body["id"].as_str().unwrap().to_string();
+    let events_url = format!("/api/v1/trees/{tree_id}/families/{family_id}/events");
+
+    let mut event_ids = Vec::new();
+    for (event_type, date) in [("divorce", "1849-11-02"), ("marriage", "1850-06-12")] {
+        let (status, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/events"),
+            Some(serde_json::json!({
+                "event_type": event_type,
+                "date_sort": date,
+                "family_id": family_id
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        event_ids.push(body["id"].as_str().unwrap().to_string());
+    }
+
+    let (status, body) = send_request(app.clone(), Method::GET, &events_url, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let types: Vec<&str> = body["events"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["event_type"].as_str().unwrap())
+        .collect();
+    assert_eq!(types, ["divorce", "marriage"]);
+    let warnings = body["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0]["kind"], "divorce_before_marriage");
+    assert_eq!(warnings[0]["event_id"], event_ids[0].as_str());
+    assert_eq!(warnings[0]["related_event_id"], event_ids[1].as_str());
+
+    // Moving the divorce after the marriage clears the warning.
     let (status, _) = send_request(
         app.clone(),
-        Method::DELETE,
-        &format!("/api/v1/trees/{tree_id}/media-links/{link_id}"),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/events/{}", event_ids[0]),
+        Some(serde_json::json!({ "date_sort": "1862-03-01" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = send_request(app.clone(), Method::GET, &events_url, None).await;
+    assert!(body["warnings"].as_array().unwrap().is_empty());
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!(
+            "/api/v1/trees/{tree_id}/families/{}/events",
+            uuid::Uuid::now_v7()
+        ),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
-// ───────────────────────── Note tests ─────────────────────────
-
 #[tokio::test]
-async fn test_note_crud() {
+async fn test_family_notes_and_citations() {
     let app = setup_app().await;
     let tree_id = create_tree_via_api(&app).await;
-    let person_id = create_person_via_api(&app, &tree_id).await;
-
-    // Create a note
-    let (status, body) = send_request(
+    let (_, family) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/notes"),
-        Some(serde_json::json!({
-            "text": "Important note about this person",
-            "person_id": person_id
-        })),
+        &format!("/api/v1/trees/{tree_id}/families"),
+        None,
+    )
+    .await;
+    let family_id = family["id"].as_str().unwrap().to_string();
+    let (_, marriage) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/events"),
+        Some(serde_json::json!({ "event_type": "marriage", "family_id": family_id })),
+    )
+    .await;
+    let (_, source) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/sources"),
+        Some(serde_json::json!({ "title": "Registre des mariages" })),
     )
     .await;
-    assert_eq!(status, StatusCode::CREATED);
-    assert_eq!(body["text"], "Important note about this person");
-    let note_id = body["id"].as_str().unwrap().to_string();
 
-    // Get the note
-    let (status, body) = send_request(
+    // The family's own note and citation; the event's are not listed.
+    for owner in [
+        serde_json::json!({ "family_id": family_id }),
+        serde_json::json!({ "event_id": marriage["id"] }),
+    ] {
+        let mut note = owner.clone();
+        note["text"] = "Married against her father's wishes".into();
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/notes"),
+            Some(note),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let mut citation = owner;
+        citation["source_id"] = source["id"].clone();
+        citation["page"] = "f. 12".into();
+        citation["confidence"] = "high".into();
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/citations"),
+            Some(citation),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let family_uri = format!("/api/v1/trees/{tree_id}/families/{family_id}");
+    let (status, notes) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/notes/{note_id}"),
+        &format!("{family_uri}/notes"),
         None,
     )
     .await;
     assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["text"], "Important note about this person");
+    let notes = notes.as_array().unwrap();
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0]["family_id"], family_id.as_str());
+    assert_eq!(notes[0]["text"], "Married against her father's wishes");
 
-    // Update the note
-    let (status, body) = send_request(
+    let (status, citations) = send_request(
         app.clone(),
-        Method::PUT,
-        &format!("/api/v1/trees/{tree_id}/notes/{note_id}"),
-        Some(serde_json::json!({
-            "text": "Updated note text"
-        })),
+        Method::GET,
+        &format!("{family_uri}/citations"),
+        None,
     )
     .await;
     assert_eq!(status, StatusCode::OK);
-    assert_eq!(body["text"], "Updated note text");
+    let citations = citations.as_array().unwrap();
+    assert_eq!(citations.len(), 1);
+    assert_eq!(citations[0]["family_id"], family_id.as_str());
+    assert_eq!(citations[0]["page"], "f. 12");
+
+    let unknown = uuid::Uuid::now_v7();
+    for list in ["notes", "citations"] {
+        let (status, _) = send_request(
+            app.clone(),
+            Method::GET,
+            &format!("/api/v1/trees/{tree_id}/families/{unknown}/{list}"),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
 
-    // List notes by person
+// ───────────────────────── Ancestry tests ─────────────────────────
+
+#[tokio::test]
+async fn test_ancestors_descendants_empty() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+
+    // Ancestors — should be empty
     let (status, body) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/notes?person_id={person_id}"),
+        &format!("/api/v1/trees/{tree_id}/persons/{person_id}/ancestors"),
         None,
     )
     .await;
     assert_eq!(status, StatusCode::OK);
-    assert_eq!(body.as_array().unwrap().len(), 1);
+    assert_eq!(body.as_array().unwrap().len(), 0);
 
-    // Delete the note
-    let (status, _) = send_request(
+    // Descendants — should be empty
+    let (status, body) = send_request(
         app.clone(),
-        Method::DELETE,
-        &format!("/api/v1/trees/{tree_id}/notes/{note_id}"),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{person_id}/descendants"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 0);
+}
 
-    // Verify it's gone
-    let (status, _) = send_request(
+#[tokio::test]
+async fn test_ancestry_depth_is_clamped() {
+    let config = ApiConfig {
+        max_ancestry_depth: 1,
+        ..ApiConfig::default()
+    };
+    let app = build_router(AppState::new(setup_db().await).with_config(config));
+    let tree_id = create_tree_via_api(&app).await;
+    let child = create_person_via_api(&app, &tree_id).await;
+    let father = add_relative_via_api(
+        &app,
+        &tree_id,
+        &child,
+        serde_json::json!({ "kind": "father" }),
+    )
+    .await;
+    add_relative_via_api(
+        &app,
+        &tree_id,
+        &father,
+        serde_json::json!({ "kind": "father" }),
+    )
+    .await;
+
+    // Deeper and unbounded requests both stop at the maximum, which the
+    // response reports.
+    for query in ["?max_depth=5", ""] {
+        let request = Request::builder()
+            .uri(format!(
+                "/api/v1/trees/{tree_id}/persons/{child}/ancestors{query}"
+            ))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get("x-max-depth").unwrap(), "1");
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body.as_array().unwrap().len(), 1);
+        assert_eq!(body[0]["ancestor_id"], father.as_str());
+    }
+
+    // So do exports of a branch without a number of generations.
+    let (status, body) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/notes/{note_id}"),
+        &format!("/api/v1/trees/{tree_id}/gedcom/export?person_id={child}"),
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["gedcom"].as_str().unwrap().matches(" INDI").count(), 2);
 }
 
 #[tokio::test]
-async fn test_note_create_validation() {
+async fn test_quick_create_person() {
     let app = setup_app().await;
     let tree_id = create_tree_via_api(&app).await;
+    let uri = format!("/api/v1/trees/{tree_id}/persons/quick");
 
-    // Empty text should fail
     let (status, body) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/notes"),
+        &uri,
         Some(serde_json::json!({
-            "text": "   "
+            "sex": "female",
+            "given_names": " Marie ",
+            "surname": "Curie",
+            "birth_date": "7 NOV 1867",
+            "birth_place": "Warsaw",
+            "death_place": "Passy"
         })),
     )
     .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let person_id = body["person"]["id"].as_str().unwrap().to_string();
+    assert_eq!(body["person"]["sex"], "female");
+    assert_eq!(body["person"]["display_name"], "Marie Curie");
+    assert_eq!(body["name"]["given_names"], "Marie");
+    assert_eq!(body["name"]["is_primary"], true);
+    assert_eq!(body["events"].as_array().unwrap().len(), 2);
+    assert_eq!(body["events"][0]["event_type"], "birth");
+    assert_eq!(body["events"][1]["date_value"], Value::Null);
+    assert_eq!(body["summary"]["display_name"], "Marie Curie");
+    assert_eq!(body["summary"]["birth_year"], 1867);
+    assert_eq!(body["summary"]["living"], false);
+
+    // The person reads back like any other, and places are reused by name.
+    let (status, person) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{person_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(person["display_name"], "Marie Curie");
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &uri,
+        Some(serde_json::json!({ "surname": "Sklodowski", "birth_place": "warsaw" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["person"]["sex"], "unknown");
+    let (_, places) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/places"),
+        None,
+    )
+    .await;
+    assert_eq!(places["total_count"], 2);
+
+    // A name is required; nothing is written without one.
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &uri,
+        Some(serde_json::json!({ "given_names": "  ", "birth_place": "Lyon" })),
+    )
+    .await;
     assert_eq!(status, StatusCode::BAD_REQUEST);
-    assert_eq!(body["error"], "validation_error");
+    let (_, persons) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons"),
+        None,
+    )
+    .await;
+    assert_eq!(persons["total_count"], 2);
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{}/persons/quick", uuid::Uuid::now_v7()),
+        Some(serde_json::json!({ "surname": "Doe" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
 #[tokio::test]
-async fn test_note_list_by_multiple_entities() {
+async fn test_add_relatives() {
     let app = setup_app().await;
     let tree_id = create_tree_via_api(&app).await;
     let person_id = create_person_via_api(&app, &tree_id).await;
+    let relatives_uri = format!("/api/v1/trees/{tree_id}/persons/{person_id}/relatives");
 
-    // Create a note linked to a person
-    send_request(
+    // The first parent creates the parent family.
+    let (status, father) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/notes"),
-        Some(serde_json::json!({
-            "text": "Person note",
-            "person_id": person_id
-        })),
+        &relatives_uri,
+        Some(serde_json::json!({ "kind": "father", "given_names": "Jean", "surname": "Dupont" })),
     )
     .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(father["person"]["sex"], "male");
+    assert_eq!(father["family_created"], true);
+    let father_id = father["person"]["id"].as_str().unwrap().to_string();
+    let family_id = father["family_id"].as_str().unwrap().to_string();
 
-    // Create a family and a note linked to it
-    let (_, fam_body) = send_request(
+    // The second joins it.
+    let (status, mother) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/families"),
-        None,
+        &relatives_uri,
+        Some(serde_json::json!({ "kind": "mother", "sex": "male" })),
     )
     .await;
-    let family_id = fam_body["id"].as_str().unwrap().to_string();
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(mother["person"]["sex"], "female");
+    assert_eq!(mother["family_id"], family_id.as_str());
+    assert_eq!(mother["family_created"], false);
 
-    send_request(
+    // A person has one father.
+    let (status, body) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/notes"),
-        Some(serde_json::json!({
-            "text": "Family note",
-            "family_id": family_id
-        })),
+        &relatives_uri,
+        Some(serde_json::json!({ "kind": "father" })),
     )
     .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["type"], "urn:oxidgene:problem:validation_error");
 
-    // List by person — should get 1
     let (status, body) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/notes?person_id={person_id}"),
+        &format!("/api/v1/trees/{tree_id}/families/{family_id}/spouses"),
         None,
     )
     .await;
     assert_eq!(status, StatusCode::OK);
-    assert_eq!(body.as_array().unwrap().len(), 1);
-    assert_eq!(body[0]["text"], "Person note");
+    assert_eq!(body.as_array().unwrap().len(), 2);
+
+    // A child of the person starts a union for them; the closure reaches
+    // the grandfather.
+    let (status, child) = send_request(
+        app.clone(),
+        Method::POST,
+        &relatives_uri,
+        Some(serde_json::json!({ "kind": "child", "sex": "female", "given_names": "Marie" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(child["family_created"], true);
+    let child_id = child["person"]["id"].as_str().unwrap().to_string();
 
-    // List by family — should get 1
     let (status, body) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/notes?family_id={family_id}"),
+        &format!("/api/v1/trees/{tree_id}/persons/{child_id}/ancestors"),
         None,
     )
     .await;
     assert_eq!(status, StatusCode::OK);
-    assert_eq!(body.as_array().unwrap().len(), 1);
-    assert_eq!(body[0]["text"], "Family note");
-}
+    let ancestors = body.as_array().unwrap();
+    assert_eq!(ancestors.len(), 3);
+    assert!(
+        ancestors
+            .iter()
+            .any(|a| a["ancestor_id"] == father_id.as_str() && a["depth"] == 2)
+    );
 
-// ── GEDCOM Import/Export ─────────────────────────────────────────────
+    // A spouse fills that union, a sibling joins the parent family.
+    let (status, spouse) = send_request(
+        app.clone(),
+        Method::POST,
+        &relatives_uri,
+        Some(serde_json::json!({ "kind": "spouse", "sex": "female" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(spouse["family_id"], child["family_id"]);
+    let (status, sibling) = send_request(
+        app.clone(),
+        Method::POST,
+        &relatives_uri,
+        Some(serde_json::json!({ "kind": "sibling" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(sibling["family_id"], family_id.as_str());
 
-fn minimal_gedcom() -> &'static str {
-    concat!(
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/check"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["error_count"], 0);
+
+    // Unknown persons are not found.
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!(
+            "/api/v1/trees/{tree_id}/persons/{}/relatives",
+            uuid::Uuid::now_v7()
+        ),
+        Some(serde_json::json!({ "kind": "child" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_ahnentafel_and_descendant_report() {
+    let app = setup_app().await;
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        "/api/v1/trees?template=self",
+        Some(serde_json::json!({ "name": "Numbered" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let tree_id = body["id"].as_str().unwrap().to_string();
+    let home_id = body["sosa_root_person_id"].as_str().unwrap().to_string();
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{home_id}/ahnentafel"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = body.as_array().unwrap();
+    let numbers: Vec<u64> = entries
+        .iter()
+        .map(|e| e["number"].as_u64().unwrap())
+        .collect();
+    assert_eq!(numbers, [1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(entries[0]["person_id"], home_id);
+    assert_eq!(entries[6]["generation"], 3);
+    let grandfather_id = entries[3]["person_id"].as_str().unwrap().to_string();
+    let father_id = entries[1]["person_id"].as_str().unwrap().to_string();
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{home_id}/ahnentafel?max_depth=1"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 3);
+
+    let report_uri = format!("/api/v1/trees/{tree_id}/persons/{grandfather_id}/descendant-report");
+    let (status, body) = send_request(app.clone(), Method::GET, &report_uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = body.as_array().unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[1]["number"], "1.1");
+    assert_eq!(entries[1]["person_id"], father_id);
+    assert_eq!(entries[2]["number"], "1.1.1");
+    assert_eq!(entries[2]["person_id"], home_id);
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("{report_uri}?system=henry"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body[2]["number"], "111");
+}
+
+// ───────────────────────── Error handling tests ─────────────────────────
+
+#[tokio::test]
+async fn test_invalid_uuid_path_returns_400() {
+    let app = setup_app().await;
+
+    let (status, _) =
+        send_request(app.clone(), Method::GET, "/api/v1/trees/not-a-uuid", None).await;
+    // Axum returns 400 for path deserialization failures
+    assert!(
+        status == StatusCode::BAD_REQUEST || status == StatusCode::NOT_FOUND,
+        "Expected 400 or 404, got {status}"
+    );
+}
+
+#[tokio::test]
+async fn test_request_id_propagation() {
+    let app = setup_app().await;
+
+    // A fresh id is assigned when the client sends none.
+    let request = Request::builder()
+        .uri("/api/v1/trees")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let assigned = response.headers()["x-request-id"].to_str().unwrap();
+    assert!(uuid::Uuid::parse_str(assigned).is_ok());
+
+    // A client-supplied id is echoed back, and quoted in error bodies.
+    let request = Request::builder()
+        .uri(format!("/api/v1/trees/{}", uuid::Uuid::now_v7()))
+        .header("x-request-id", "bug-report-42")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()["x-request-id"], "bug-report-42");
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["type"], "urn:oxidgene:problem:not_found");
+    assert_eq!(body["request_id"], "bug-report-42");
+}
+
+#[tokio::test]
+async fn test_errors_are_problem_json() {
+    let app = setup_app().await;
+
+    // Domain error: typed problem, status and detail.
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/trees")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"name": "  "}"#))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/problem+json"
+    );
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["type"], "urn:oxidgene:problem:validation_error");
+    assert_eq!(body["title"], "Validation failed");
+    assert_eq!(body["status"], 400);
+    assert!(body["detail"].as_str().unwrap().contains("name"));
+    assert!(body["request_id"].is_string());
+
+    // Extractor rejection (malformed id): rewrapped, Axum's text as detail.
+    let request = Request::builder()
+        .uri("/api/v1/trees/not-a-uuid")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/problem+json"
+    );
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["type"], "about:blank");
+    assert_eq!(body["title"], "Bad Request");
+    assert_eq!(body["status"], 400);
+    assert!(body["detail"].as_str().unwrap().contains("UUID"));
+
+    // Unknown route.
+    let (status, body) = send_request(app, Method::GET, "/api/v1/nowhere", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["title"], "Not Found");
+}
+
+#[tokio::test]
+async fn test_invalid_json_body_returns_error() {
+    let app = setup_app().await;
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/api/v1/trees")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"invalid json"#))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    // Axum returns 400 for JSON syntax errors, 422 for deserialization failures
+    assert!(
+        status == StatusCode::BAD_REQUEST || status == StatusCode::UNPROCESSABLE_ENTITY,
+        "Expected 400 or 422, got {status}"
+    );
+}
+
+// ───────────────────────── Event tests ─────────────────────────
+
+#[tokio::test]
+async fn test_event_crud() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+
+    // Create an event
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/events"),
+        Some(serde_json::json!({
+            "event_type": "birth",
+            "date_value": "1 JAN 1990",
+            "date_sort": "1990-01-01",
+            "person_id": person_id,
+            "description": "Born in London"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["event_type"], "birth");
+    assert_eq!(body["description"], "Born in London");
+    let event_id = body["id"].as_str().unwrap().to_string();
+
+    // Get the event
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/events/{event_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["event_type"], "birth");
+
+    // Update the event
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/events/{event_id}"),
+        Some(serde_json::json!({
+            "description": "Born in London"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["description"], "Born in London");
+
+    // List events (no filter)
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/events"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 1);
+
+    // List events (filter by person_id)
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/events?person_id={person_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 1);
+
+    // List events (filter by event_type)
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/events?event_type=birth"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 1);
+
+    // Delete the event
+    let (status, _) = send_request(
+        app.clone(),
+        Method::DELETE,
+        &format!("/api/v1/trees/{tree_id}/events/{event_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Verify it's gone
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/events/{event_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_event_plausibility_warnings() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let mother_id = create_person_via_api(&app, &tree_id).await;
+    let child_id = create_person_via_api(&app, &tree_id).await;
+
+    let (_, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/families"),
+        None,
+    )
+    .await;
+    let family_id = body["id"].as_str().unwrap().to_string();
+    for (path, body) in [
+        (
+            "spouses",
+            serde_json::json!({ "person_id": mother_id, "role": "wife", "sort_order": 0 }),
+        ),
+        (
+            "children",
+            serde_json::json!({
+                "person_id": child_id,
+                "child_type": "biological",
+                "sort_order": 0
+            }),
+        ),
+    ] {
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/families/{family_id}/{path}"),
+            Some(body),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let create_event = |event_type: &str, owner: serde_json::Value, date: &str| {
+        let app = app.clone();
+        let uri = format!("/api/v1/trees/{tree_id}/events");
+        let mut body = serde_json::json!({ "event_type": event_type, "date_value": date });
+        body.as_object_mut()
+            .unwrap()
+            .extend(owner.as_object().unwrap().clone());
+        async move {
+            let (status, body) = send_request(app, Method::POST, &uri, Some(body)).await;
+            assert_eq!(status, StatusCode::CREATED);
+            body
+        }
+    };
+    let mother = serde_json::json!({ "person_id": mother_id });
+    let child = serde_json::json!({ "person_id": child_id });
+
+    let body = create_event("birth", mother.clone(), "1840").await;
+    assert_eq!(body["warnings"], serde_json::json!([]));
+    let mother_birth_id = body["id"].clone();
+    let body = create_event("death", mother.clone(), "1880").await;
+    let mother_death_id = body["id"].clone();
+
+    // Saved anyway, with the warning.
+    let body = create_event("birth", child.clone(), "1882").await;
+    assert_eq!(body["event_type"], "birth");
+    assert_eq!(body["warnings"].as_array().unwrap().len(), 1);
+    assert_eq!(body["warnings"][0]["kind"], "born_after_mother_death");
+    assert_eq!(body["warnings"][0]["related_event_id"], mother_death_id);
+    let child_birth_id = body["id"].as_str().unwrap().to_string();
+
+    let body = create_event("death", child.clone(), "1870").await;
+    assert_eq!(body["warnings"][0]["kind"], "death_before_birth");
+    assert_eq!(body["warnings"][0]["related_event_id"], child_birth_id);
+
+    let body = create_event(
+        "marriage",
+        serde_json::json!({ "family_id": family_id }),
+        "1850",
+    )
+    .await;
+    assert_eq!(body["warnings"][0]["kind"], "married_too_young");
+    assert_eq!(body["warnings"][0]["related_event_id"], mother_birth_id);
+
+    // Updates are checked too.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/events/{child_birth_id}"),
+        Some(serde_json::json!({ "date_value": "1851" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let kinds: Vec<&str> = body["warnings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, ["mother_too_young"]);
+}
+
+#[tokio::test]
+async fn test_event_destination_place() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+
+    let mut place_ids = Vec::new();
+    for name in ["Cork, Ireland", "Boston, USA"] {
+        let (status, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/places"),
+            Some(serde_json::json!({ "name": name })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        place_ids.push(body["id"].as_str().unwrap().to_string());
+    }
+
+    // A move keeps both places.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/events"),
+        Some(serde_json::json!({
+            "event_type": "emigration",
+            "person_id": person_id,
+            "place_id": place_ids[0],
+            "place_id_to": place_ids[1]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["place_id"], place_ids[0]);
+    assert_eq!(body["place_id_to"], place_ids[1]);
+    let event_id = body["id"].as_str().unwrap().to_string();
+
+    // A single-place type ignores it...
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/events"),
+        Some(serde_json::json!({
+            "event_type": "birth",
+            "person_id": person_id,
+            "place_id": place_ids[0],
+            "place_id_to": place_ids[1]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(body["place_id_to"].is_null());
+
+    // ...and drops it when a move changes to one.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/events/{event_id}"),
+        Some(serde_json::json!({ "event_type": "birth" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["place_id"], place_ids[0]);
+    assert!(body["place_id_to"].is_null());
+}
+
+#[tokio::test]
+async fn test_event_reassign_owner() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let father = create_person_via_api(&app, &tree_id).await;
+    let son = create_person_via_api(&app, &tree_id).await;
+    let source_id = create_source_via_api(&app, &tree_id).await;
+    let (_, family) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/families"),
+        None,
+    )
+    .await;
+    let other_tree_id = create_tree_via_api(&app).await;
+    let stranger = create_person_via_api(&app, &other_tree_id).await;
+
+    // A census imported onto the father instead of the son.
+    let (_, census) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/events"),
+        Some(serde_json::json!({
+            "event_type": "census",
+            "date_value": "1881",
+            "person_id": father
+        })),
+    )
+    .await;
+    let census_id = census["id"].as_str().unwrap().to_string();
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/citations"),
+        Some(serde_json::json!({
+            "source_id": source_id,
+            "event_id": census_id,
+            "page": "RG11/42",
+            "confidence": "high"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/notes"),
+        Some(serde_json::json!({ "event_id": census_id, "text": "Listed as scholar" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let owner_uri = format!("/api/v1/trees/{tree_id}/events/{census_id}/owner");
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PATCH,
+        &owner_uri,
+        Some(serde_json::json!({ "person_id": son })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["person_id"], son.as_str());
+    assert_eq!(body["version"], census["version"].as_i64().unwrap() + 1);
+    for (person_id, count) in [(&father, 0), (&son, 1)] {
+        let (_, body) = send_request(
+            app.clone(),
+            Method::GET,
+            &format!("/api/v1/trees/{tree_id}/events?person_id={person_id}"),
+            None,
+        )
+        .await;
+        assert_eq!(body["edges"].as_array().unwrap().len(), count);
+    }
+    for kind in ["citations", "notes"] {
+        let (_, body) = send_request(
+            app.clone(),
+            Method::GET,
+            &format!("/api/v1/trees/{tree_id}/{kind}?event_id={census_id}"),
+            None,
+        )
+        .await;
+        assert_eq!(body.as_array().unwrap().len(), 1, "{kind}");
+    }
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PATCH,
+        &owner_uri,
+        Some(serde_json::json!({ "family_id": family["id"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["family_id"], family["id"]);
+    assert!(body["person_id"].is_null());
+
+    for (owner, expected) in [
+        (serde_json::json!({}), StatusCode::BAD_REQUEST),
+        (
+            serde_json::json!({ "person_id": son, "family_id": family["id"] }),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            serde_json::json!({ "person_id": stranger }),
+            StatusCode::NOT_FOUND,
+        ),
+    ] {
+        let (status, _) = send_request(app.clone(), Method::PATCH, &owner_uri, Some(owner)).await;
+        assert_eq!(status, expected);
+    }
+}
+
+#[tokio::test]
+async fn test_person_date_conflicts() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    let source_id = create_source_via_api(&app, &tree_id).await;
+
+    let mut event_ids = Vec::new();
+    for (event_type, date_value) in [("birth", "12 MAR 1820"), ("census", "1861")] {
+        let (status, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/events"),
+            Some(serde_json::json!({
+                "event_type": event_type,
+                "date_value": date_value,
+                "person_id": person_id,
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        event_ids.push(body["id"].as_str().unwrap().to_string());
+    }
+    let census_id = &event_ids[1];
+
+    // Consistent so far: no age recorded on the census.
+    let conflicts_uri = format!("/api/v1/trees/{tree_id}/persons/{person_id}/date-conflicts");
+    let (status, body) = send_request(app.clone(), Method::GET, &conflicts_uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 0);
+
+    // The 1861 census gives age 45, implying a birth in 1815-1816.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/events/{census_id}"),
+        Some(serde_json::json!({ "age": "45y" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["age"], "45y");
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/citations"),
+        Some(serde_json::json!({
+            "source_id": source_id,
+            "event_id": census_id,
+            "page": "RG9/123",
+            "confidence": "medium"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, body) = send_request(app.clone(), Method::GET, &conflicts_uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let conflicts = body.as_array().unwrap();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0]["event_type"], "birth");
+    assert_eq!(conflicts[0]["discrepancy_years"], 4);
+    let records = conflicts[0]["records"].as_array().unwrap();
+    assert_eq!(records.len(), 2);
+    let census = records
+        .iter()
+        .find(|r| r["event_id"] == census_id.as_str())
+        .unwrap();
+    assert_eq!(census["age"], "45y");
+    assert_eq!(census["years"]["latest"], 1816);
+    assert_eq!(census["citations"][0]["page"], "RG9/123");
+
+    // A looser tolerance accepts the discrepancy.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("{conflicts_uri}?tolerance=5"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 0);
+}
+
+/// Helper: add a relative of `person_id` via the API and return its ID.
+async fn add_relative_via_api(
+    app: &axum::Router,
+    tree_id: &str,
+    person_id: &str,
+    body: Value,
+) -> String {
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/persons/{person_id}/relatives"),
+        Some(body),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    body["person"]["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_bulk_delete_persons() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let loner = create_person_via_api(&app, &tree_id).await;
+    let child = create_person_via_api(&app, &tree_id).await;
+    let father = add_relative_via_api(
+        &app,
+        &tree_id,
+        &child,
+        serde_json::json!({ "kind": "father" }),
+    )
+    .await;
+    let unknown = uuid::Uuid::now_v7().to_string();
+    let uri = format!("/api/v1/trees/{tree_id}/persons/bulk-delete");
+
+    // Family members are skipped without `cascade`.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &uri,
+        Some(serde_json::json!({ "person_ids": [loner, father, unknown, loner] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["deleted"], serde_json::json!([loner]));
+    assert_eq!(
+        body["skipped"],
+        serde_json::json!([
+            { "id": father, "reason": "family_member" },
+            { "id": unknown, "reason": "not_found" },
+        ])
+    );
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{loner}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // With `cascade` the links go too, and the emptied family with them.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &uri,
+        Some(serde_json::json!({ "person_ids": [father, child], "cascade": true })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["deleted"], serde_json::json!([father, child]));
+    assert_eq!(body["skipped"], serde_json::json!([]));
+    assert_eq!(body["families_deleted"].as_array().unwrap().len(), 1);
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons"),
+        None,
+    )
+    .await;
+    assert_eq!(body["total_count"], 0);
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &uri,
+        Some(serde_json::json!({ "person_ids": [] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_person_lock() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    let person_uri = format!("/api/v1/trees/{tree_id}/persons/{person_id}");
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("{person_uri}/names"),
+        Some(serde_json::json!({ "name_type": "birth", "surname": "Doe", "is_primary": true })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let name_id = body["id"].as_str().unwrap().to_string();
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("{person_uri}/lock"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["locked"], true);
+
+    // The person and their names refuse edits and deletion.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &person_uri,
+        Some(serde_json::json!({ "sex": "female" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::LOCKED);
+    assert_eq!(body["type"], "urn:oxidgene:problem:locked");
+    let (status, _) = send_request(app.clone(), Method::DELETE, &person_uri, None).await;
+    assert_eq!(status, StatusCode::LOCKED);
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("{person_uri}/names"),
+        Some(serde_json::json!({ "name_type": "married", "surname": "Roe", "is_primary": false })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::LOCKED);
+    let (status, _) = send_request(
+        app.clone(),
+        Method::DELETE,
+        &format!("{person_uri}/names/{name_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::LOCKED);
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/persons/bulk-delete"),
+        Some(serde_json::json!({ "person_ids": [person_id], "cascade": true })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["skipped"],
+        serde_json::json!([{ "id": person_id, "reason": "locked" }])
+    );
+
+    // Unlocked, edits go through again.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("{person_uri}/unlock"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["locked"], false);
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &person_uri,
+        Some(serde_json::json!({ "sex": "female" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["sex"], "female");
+}
+
+#[tokio::test]
+async fn test_person_lineage_and_brick_walls() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let child = create_person_via_api(&app, &tree_id).await;
+    let father = add_relative_via_api(
+        &app,
+        &tree_id,
+        &child,
+        serde_json::json!({ "kind": "father" }),
+    )
+    .await;
+    let grandfather = add_relative_via_api(
+        &app,
+        &tree_id,
+        &father,
+        serde_json::json!({ "kind": "father" }),
+    )
+    .await;
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{grandfather}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["descendant_count"], 2);
+    assert_eq!(body["has_descendants"], true);
+    assert_eq!(body["has_known_parents"], false);
+
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{child}"),
+        None,
+    )
+    .await;
+    assert_eq!(body["descendant_count"], 0);
+    assert_eq!(body["has_descendants"], false);
+    assert_eq!(body["has_known_parents"], true);
+
+    // Only the grandfather has no recorded parent.
+    let (status, body) = send_request(
+        app,
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons?no_parents=true"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let edges = body["edges"].as_array().unwrap();
+    assert_eq!(edges.len(), 1);
+    assert_eq!(edges[0]["node"]["id"], grandfather.as_str());
+    assert_eq!(edges[0]["node"]["descendant_count"], 2);
+}
+
+#[tokio::test]
+async fn test_relationship_path() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    // First cousins: a and b are the children of two siblings.
+    let a = create_person_via_api(&app, &tree_id).await;
+    let father = add_relative_via_api(
+        &app,
+        &tree_id,
+        &a,
+        serde_json::json!({ "kind": "father", "given_names": "Paul" }),
+    )
+    .await;
+    let grandfather = add_relative_via_api(
+        &app,
+        &tree_id,
+        &father,
+        serde_json::json!({ "kind": "father" }),
+    )
+    .await;
+    let aunt = add_relative_via_api(
+        &app,
+        &tree_id,
+        &grandfather,
+        serde_json::json!({ "kind": "child", "sex": "female" }),
+    )
+    .await;
+    let b = add_relative_via_api(
+        &app,
+        &tree_id,
+        &aunt,
+        serde_json::json!({ "kind": "child", "sex": "male" }),
+    )
+    .await;
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{a}/path/{b}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["common_ancestor_id"], grandfather.as_str());
+    assert_eq!(body["generations_up"], 2);
+    assert_eq!(body["generations_down"], 2);
+    let steps: Vec<(&str, &Value)> = body["steps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| (s["person"]["id"].as_str().unwrap(), &s["edge"]))
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            (a.as_str(), &Value::Null),
+            (father.as_str(), &serde_json::json!("father")),
+            (grandfather.as_str(), &serde_json::json!("father")),
+            (aunt.as_str(), &serde_json::json!("daughter")),
+            (b.as_str(), &serde_json::json!("son")),
+        ]
+    );
+    assert_eq!(body["steps"][1]["person"]["display_name"], "Paul");
+
+    // A direct ancestor is the common ancestor itself.
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{grandfather}/path/{a}"),
+        None,
+    )
+    .await;
+    assert_eq!(body["generations_up"], 0);
+    assert_eq!(body["steps"][1]["edge"], "son");
+    assert_eq!(body["steps"].as_array().unwrap().len(), 3);
+
+    // Unrelated persons have no path.
+    let stranger = create_person_via_api(&app, &tree_id).await;
+    let (status, body) = send_request(
+        app,
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{a}/path/{stranger}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["common_ancestor_id"].is_null());
+    assert_eq!(body["steps"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn test_person_compare() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let a = create_person_via_api(&app, &tree_id).await;
+    let b = create_person_via_api(&app, &tree_id).await;
+    let father = create_person_via_api(&app, &tree_id).await;
+
+    for (person_id, given, surname) in [
+        (&a, "Jean", "Dupont"),
+        (&b, "jean", "DUPONT"),
+        (&father, "Pierre", "Dupont"),
+    ] {
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/persons/{person_id}/names"),
+            Some(serde_json::json!({
+                "name_type": "birth",
+                "given_names": given,
+                "surname": surname,
+                "is_primary": true
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+    for (person_id, event_type, date_value) in [
+        (&a, "birth", "ABT 1842"),
+        (&b, "birth", "12 MAR 1842"),
+        (&b, "death", "1901"),
+    ] {
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/events"),
+            Some(serde_json::json!({
+                "event_type": event_type,
+                "date_value": date_value,
+                "person_id": person_id,
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    // Both are children of the same father.
+    let (_, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/families"),
+        None,
+    )
+    .await;
+    let family_id = body["id"].as_str().unwrap().to_string();
+    send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/families/{family_id}/spouses"),
+        Some(serde_json::json!({ "person_id": father, "role": "husband", "sort_order": 0 })),
+    )
+    .await;
+    for child in [&a, &b] {
+        send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/families/{family_id}/children"),
+            Some(serde_json::json!({
+                "person_id": child,
+                "child_type": "biological",
+                "sort_order": 0
+            })),
+        )
+        .await;
+    }
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/compare?a={a}&b={b}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["a"], a.as_str());
+    let field = |name: &str| {
+        body["fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|f| f["field"] == name)
+            .cloned()
+            .unwrap_or_default()
+    };
+    assert_eq!(field("name")["agreement"], "same");
+    assert_eq!(field("sex")["agreement"], "same");
+    assert_eq!(field("birth_date")["agreement"], "compatible");
+    assert_eq!(field("birth_date")["a"][0], "ABT 1842");
+    assert_eq!(field("death_date")["agreement"], "only_b");
+    assert_eq!(field("parents")["agreement"], "same");
+    assert_eq!(field("parents")["b"][0], "Pierre Dupont");
+    assert_eq!(body["differences"], 0);
+
+    // Comparing a person with itself is rejected.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/compare?a={a}&b={a}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["type"], "urn:oxidgene:problem:validation_error");
+
+    // Persons of another tree are not found.
+    let other_tree = create_tree_via_api(&app).await;
+    let stranger = create_person_via_api(&app, &other_tree).await;
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/compare?a={a}&b={stranger}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_person_sourcing_score() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    let source_id = create_source_via_api(&app, &tree_id).await;
+
+    let mut event_ids = Vec::new();
+    for event_type in ["birth", "death", "residence"] {
+        let (status, event) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/events"),
+            Some(serde_json::json!({ "event_type": event_type, "person_id": person_id })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        event_ids.push(event["id"].as_str().unwrap().to_string());
+    }
+    let person_uri = format!("/api/v1/trees/{tree_id}/persons/{person_id}");
+    let (_, person) = send_request(app.clone(), Method::GET, &person_uri, None).await;
+    assert_eq!(person["sourcing_score"], 0.0);
+
+    // A very-high citation of the birth; the death stays uncited and the
+    // residence is not a vital fact.
+    for event_id in [&event_ids[0], &event_ids[2]] {
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/citations"),
+            Some(serde_json::json!({
+                "source_id": source_id,
+                "event_id": event_id,
+                "confidence": "very_high"
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+    let (_, person) = send_request(app.clone(), Method::GET, &person_uri, None).await;
+    assert_eq!(person["sourcing_score"], 0.5);
+
+    let (_, list) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons"),
+        None,
+    )
+    .await;
+    assert_eq!(list["edges"][0]["node"]["sourcing_score"], 0.5);
+}
+
+#[tokio::test]
+async fn test_person_duplicate_candidates() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    let mut persons = Vec::new();
+    for (given, surname) in [("Jean", "Dupont"), ("Jehan", "Dupond"), ("Marie", "Dupont")] {
+        let person_id = create_person_via_api(&app, &tree_id).await;
+        let (status, name) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/persons/{person_id}/names"),
+            Some(serde_json::json!({
+                "name_type": "birth",
+                "given_names": given,
+                "surname": surname,
+                "is_primary": true
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        persons.push((person_id, name["id"].as_str().unwrap().to_string()));
+    }
+    let uri = format!("/api/v1/trees/{tree_id}/persons/duplicates");
+
+    let (status, body) = send_request(app.clone(), Method::GET, &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let pairs = body.as_array().unwrap();
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs[0]["a"]["id"], persons[0].0.as_str());
+    assert_eq!(pairs[0]["a"]["display_name"], "Jean Dupont");
+    assert_eq!(pairs[0]["b"]["id"], persons[1].0.as_str());
+    assert_eq!(pairs[0]["b"]["display_name"], "Jehan Dupond");
+
+    // Renaming Marie to Jeanne makes her sound like both.
+    let (marie, name_id) = &persons[2];
+    let (status, _) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/persons/{marie}/names/{name_id}"),
+        Some(serde_json::json!({ "given_names": "Jeanne" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, body) = send_request(app.clone(), Method::GET, &uri, None).await;
+    assert_eq!(body.as_array().unwrap().len(), 3);
+    let (_, body) = send_request(app.clone(), Method::GET, &format!("{uri}?limit=2"), None).await;
+    assert_eq!(body.as_array().unwrap().len(), 2);
+
+    // Names are written with their codes: nothing to backfill.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        "/api/v1/admin/backfill-phonetic",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, serde_json::json!({ "updated": 0 }));
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{}/persons/duplicates", uuid::Uuid::now_v7()),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_update_clears_nullable_fields() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+
+    let (_, place) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/places"),
+        Some(serde_json::json!({ "name": "Lyon", "latitude": 45.76, "longitude": 4.84 })),
+    )
+    .await;
+    let place_id = place["id"].as_str().unwrap().to_string();
+    let (_, event) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/events"),
+        Some(serde_json::json!({
+            "event_type": "birth",
+            "date_value": "1 JAN 1990",
+            "place_id": place_id,
+            "person_id": person_id,
+            "description": "Born at home"
+        })),
+    )
+    .await;
+    let event_id = event["id"].as_str().unwrap().to_string();
+
+    // `null` clears a field; an absent field is left unchanged.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/events/{event_id}"),
+        Some(serde_json::json!({ "description": null, "place_id": null })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["description"].is_null());
+    assert!(body["place_id"].is_null());
+    assert_eq!(body["date_value"], "1 JAN 1990");
+
+    // `null` on a required field leaves it unchanged too.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/places/{place_id}"),
+        Some(serde_json::json!({ "name": null, "latitude": null })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["name"], "Lyon");
+    assert!(body["latitude"].is_null());
+    assert_eq!(body["longitude"], 4.84);
+
+    send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}"),
+        Some(serde_json::json!({ "description": "Paternal side" })),
+    )
+    .await;
+    let (status, body) = send_request(
+        app,
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}"),
+        Some(serde_json::json!({ "description": null })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["description"].is_null());
+}
+
+#[tokio::test]
+async fn test_event_participants() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let child_id = create_person_via_api(&app, &tree_id).await;
+    let godmother_id = create_person_via_api(&app, &tree_id).await;
+    let priest_id = create_person_via_api(&app, &tree_id).await;
+
+    // Participants can be attached when the event is created.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/events"),
+        Some(serde_json::json!({
+            "event_type": "baptism",
+            "person_id": child_id,
+            "participants": [
+                { "person_id": godmother_id, "role": "godparent", "relation": "Godmother" }
+            ]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let event_id = body["id"].as_str().unwrap().to_string();
+    let participants_uri = format!("/api/v1/trees/{tree_id}/events/{event_id}/participants");
+
+    // Added later; the role defaults to witness when omitted.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &participants_uri,
+        Some(serde_json::json!({ "person_id": priest_id, "role": "officiant", "sort_order": 1 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["role"], "officiant");
+    let officiant_id = body["id"].as_str().unwrap().to_string();
+
+    let (status, body) = send_request(app.clone(), Method::GET, &participants_uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let list = body.as_array().unwrap();
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0]["person_id"], godmother_id);
+    assert_eq!(list[0]["role"], "godparent");
+    assert_eq!(list[0]["relation"], "Godmother");
+    assert_eq!(list[1]["role"], "officiant");
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::DELETE,
+        &format!("{participants_uri}/{officiant_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &participants_uri,
+        Some(serde_json::json!({ "person_id": priest_id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["role"], "witness");
+    let witness_id = body["id"].as_str().unwrap().to_string();
+
+    // The former witness routes remain as deprecated aliases.
+    let witnesses_uri = format!("/api/v1/trees/{tree_id}/events/{event_id}/witnesses");
+    let (status, body) = send_request(app.clone(), Method::GET, &witnesses_uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let list = body.as_array().unwrap();
+    assert_eq!(list.len(), 1);
+    assert_eq!(list[0]["id"], witness_id.as_str());
+    let (status, _) = send_request(
+        app.clone(),
+        Method::DELETE,
+        &format!("{witnesses_uri}/{witness_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &witnesses_uri,
+        Some(serde_json::json!({ "person_id": priest_id, "relation": "Priest" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["role"], "witness");
+
+    // An event whose participants cannot all be added is not created.
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/events"),
+        Some(serde_json::json!({
+            "event_type": "burial",
+            "person_id": priest_id,
+            "participants": [{ "person_id": uuid::Uuid::now_v7() }]
+        })),
+    )
+    .await;
+    assert!(!status.is_success());
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/events?person_id={priest_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(body["total_count"], 0);
+
+    // Unknown event.
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!(
+            "/api/v1/trees/{tree_id}/events/{}/participants",
+            uuid::Uuid::now_v7()
+        ),
+        Some(serde_json::json!({ "person_id": priest_id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// ───────────────────────── Place tests ─────────────────────────
+
+#[tokio::test]
+async fn test_place_crud() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    // Create a place
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/places"),
+        Some(serde_json::json!({
+            "name": "Paris, France",
+            "latitude": 48.8566,
+            "longitude": 2.3522
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["name"], "Paris, France");
+    let place_id = body["id"].as_str().unwrap().to_string();
+
+    // Get the place
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/places/{place_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["name"], "Paris, France");
+
+    // Update the place
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/places/{place_id}"),
+        Some(serde_json::json!({
+            "name": "Lyon, France"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["name"], "Lyon, France");
+
+    // List places
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/places"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 1);
+
+    // List places with search
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/places?search=Lyon"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 1);
+
+    // Search for non-existent place
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/places?search=Berlin"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 0);
+
+    // Delete the place
+    let (status, _) = send_request(
+        app.clone(),
+        Method::DELETE,
+        &format!("/api/v1/trees/{tree_id}/places/{place_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_place_autocomplete() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+
+    let mut place_ids = std::collections::HashMap::new();
+    for name in ["Paris, France", "Parthenay", "Pau", "Lyon", "Par_odd"] {
+        let (_, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/places"),
+            Some(serde_json::json!({ "name": name })),
+        )
+        .await;
+        place_ids.insert(name, body["id"].as_str().unwrap().to_string());
+    }
+    for (event_type, place) in [
+        ("birth", "Parthenay"),
+        ("death", "Parthenay"),
+        ("burial", "Paris, France"),
+    ] {
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/events"),
+            Some(serde_json::json!({
+                "event_type": event_type,
+                "person_id": person_id,
+                "place_id": place_ids[place]
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let suggest = |q: &str| {
+        let app = app.clone();
+        let uri = format!("/api/v1/trees/{tree_id}/places/autocomplete?q={q}");
+        async move {
+            let (status, body) = send_request(app, Method::GET, &uri, None).await;
+            assert_eq!(status, StatusCode::OK);
+            body.as_array()
+                .unwrap()
+                .iter()
+                .map(|p| {
+                    (
+                        p["name"].as_str().unwrap().to_string(),
+                        p["event_count"].as_i64().unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        }
+    };
+    let names = |entries: Vec<(String, i64)>| -> Vec<String> {
+        entries.into_iter().map(|(name, _)| name).collect()
+    };
+
+    let entries = suggest("Par").await;
+    assert_eq!(entries[0], ("Parthenay".to_string(), 2));
+    assert_eq!(entries[1], ("Paris, France".to_string(), 1));
+    assert_eq!(names(entries), ["Parthenay", "Paris, France", "Par_odd"]);
+    // `_` matches itself, not any character.
+    assert_eq!(names(suggest("Par_").await), ["Par_odd"]);
+    assert_eq!(names(suggest("Pa&limit=1").await), ["Parthenay"]);
+    assert!(suggest("Berlin").await.is_empty());
+}
+
+#[tokio::test]
+async fn test_place_prune_unused() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+
+    let mut place_ids = std::collections::HashMap::new();
+    for name in ["Lyon", "Nantes", "Brest", "Arles"] {
+        let (_, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/places"),
+            Some(serde_json::json!({ "name": name })),
+        )
+        .await;
+        place_ids.insert(name, body["id"].as_str().unwrap().to_string());
+    }
+    let mut event_ids = Vec::new();
+    for place in ["Lyon", "Nantes"] {
+        let (status, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/events"),
+            Some(serde_json::json!({
+                "event_type": "residence",
+                "person_id": person_id,
+                "place_id": place_ids[place]
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        event_ids.push(body["id"].as_str().unwrap().to_string());
+    }
+    // A trashed event still holds its place, so it can be restored.
+    let (status, _) = send_request(
+        app.clone(),
+        Method::DELETE,
+        &format!("/api/v1/trees/{tree_id}/events/{}", event_ids[1]),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/places/unused"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let names: Vec<&str> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["Arles", "Brest"]);
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/places/prune"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pruned"], 2);
+
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/places"),
+        None,
+    )
+    .await;
+    assert_eq!(body["edges"].as_array().unwrap().len(), 2);
+    let (_, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/places/prune"),
+        None,
+    )
+    .await;
+    assert_eq!(body["pruned"], 0);
+}
+
+#[tokio::test]
+async fn test_place_create_validation() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    // Empty name should fail
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/places"),
+        Some(serde_json::json!({
+            "name": "   "
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["type"], "urn:oxidgene:problem:validation_error");
+
+    // Out-of-range coordinates are rejected, on create and on update
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/places"),
+        Some(serde_json::json!({ "name": "Paris", "latitude": 148.8 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(
+        body["detail"]
+            .as_str()
+            .unwrap()
+            .contains("latitude must be between -90 and 90")
+    );
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/places"),
+        Some(serde_json::json!({ "name": "Paris", "latitude": 48.8, "longitude": 2.3 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let place_id = body["id"].as_str().unwrap();
+    let (status, _) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/places/{place_id}"),
+        Some(serde_json::json!({ "longitude": -200.0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+// ───────────────────────── Source tests ─────────────────────────
+
+#[tokio::test]
+async fn test_source_crud() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    // Create a source
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/sources"),
+        Some(serde_json::json!({
+            "title": "Parish Records of Lyon",
+            "author": "Catholic Church",
+            "publisher": "Diocese of Lyon"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["title"], "Parish Records of Lyon");
+    assert_eq!(body["author"], "Catholic Church");
+    let source_id = body["id"].as_str().unwrap().to_string();
+
+    // Get the source
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/sources/{source_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["title"], "Parish Records of Lyon");
+
+    // Update the source
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/sources/{source_id}"),
+        Some(serde_json::json!({
+            "title": "Parish Records of Paris",
+            "author": "Archdiocese of Paris"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["title"], "Parish Records of Paris");
+    assert_eq!(body["author"], "Archdiocese of Paris");
+
+    // List sources
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/sources"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 1);
+
+    // Delete the source
+    let (status, _) = send_request(
+        app.clone(),
+        Method::DELETE,
+        &format!("/api/v1/trees/{tree_id}/sources/{source_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Verify it's gone
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/sources/{source_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_source_create_validation() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    // Empty title should fail
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/sources"),
+        Some(serde_json::json!({
+            "title": ""
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["type"], "urn:oxidgene:problem:validation_error");
+}
+
+#[tokio::test]
+async fn test_source_duplicates_and_merge() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let mut ids = Vec::new();
+    for source in [
+        serde_json::json!({ "title": "Registres paroissiaux de Lyon", "author": "Église Saint-Nizier" }),
+        serde_json::json!({ "title": "REGISTRES PAROISSIAUX DE LYON.", "author": "eglise saint nizier", "publisher": "AD69" }),
+        serde_json::json!({ "title": "Registres paroissiaux de Lyon" }),
+        serde_json::json!({ "title": "Census 1851" }),
+    ] {
+        let (status, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/sources"),
+            Some(source),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        ids.push(body["id"].as_str().unwrap().to_string());
+    }
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    for source_id in [&ids[0], &ids[1], &ids[1]] {
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/citations"),
+            Some(serde_json::json!({
+                "source_id": source_id,
+                "person_id": person_id,
+                "confidence": "medium"
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let uri = format!("/api/v1/trees/{tree_id}/sources/duplicates");
+    let (status, body) = send_request(app.clone(), Method::GET, &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    // Case, accents and punctuation are ignored; a missing author is not a match.
+    let groups = body["groups"].as_array().unwrap();
+    assert_eq!(groups.len(), 1);
+    let group = groups[0].as_array().unwrap();
+    assert_eq!(group.len(), 2);
+    assert_eq!(group[0]["id"], ids[0].as_str());
+    assert_eq!(group[0]["citation_count"], 1);
+    assert_eq!(group[1]["id"], ids[1].as_str());
+    assert_eq!(group[1]["citation_count"], 2);
+
+    let merge_uri = format!("/api/v1/trees/{tree_id}/sources/merge");
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &merge_uri,
+        Some(serde_json::json!({ "survivor_id": ids[0], "duplicate_ids": [ids[0]] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &merge_uri,
+        Some(serde_json::json!({ "survivor_id": ids[0], "duplicate_ids": [ids[3], "00000000-0000-7000-8000-000000000000"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &merge_uri,
+        Some(serde_json::json!({ "survivor_id": ids[0], "duplicate_ids": [ids[1]] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["merged"], 1);
+    assert_eq!(body["citations_moved"], 2);
+    assert_eq!(body["survivor"]["title"], "Registres paroissiaux de Lyon");
+    // The survivor inherits the fields it lacked.
+    assert_eq!(body["survivor"]["publisher"], "AD69");
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/sources/{}", ids[1]),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, usage) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/sources/{}/usage", ids[0]),
+        None,
+    )
+    .await;
+    assert_eq!(usage.as_array().unwrap().len(), 3);
+    let (_, body) = send_request(app.clone(), Method::GET, &uri, None).await;
+    assert!(body["groups"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_source_usage() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let source_id = create_source_via_api(&app, &tree_id).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/persons/{person_id}/names"),
+        Some(serde_json::json!({
+            "name_type": "birth",
+            "given_names": "John",
+            "surname": "Doe",
+            "is_primary": true
+        })),
+    )
+    .await;
+    let (_, event) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/events"),
+        Some(serde_json::json!({
+            "event_type": "birth",
+            "date_value": "12 MAR 1850",
+            "person_id": person_id
+        })),
+    )
+    .await;
+    let (_, family) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/families"),
+        None,
+    )
+    .await;
+    let deleted_id = create_person_via_api(&app, &tree_id).await;
+
+    for owner in [
+        serde_json::json!({ "person_id": person_id, "page": "p. 1" }),
+        serde_json::json!({ "event_id": event["id"], "page": "f. 12", "text": "born the 12th" }),
+        serde_json::json!({ "family_id": family["id"] }),
+        serde_json::json!({ "person_id": deleted_id }),
+    ] {
+        let mut body = owner;
+        body["source_id"] = serde_json::json!(source_id);
+        body["confidence"] = serde_json::json!("high");
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/citations"),
+            Some(body),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+    send_request(
+        app.clone(),
+        Method::DELETE,
+        &format!("/api/v1/trees/{tree_id}/persons/{deleted_id}"),
+        None,
+    )
+    .await;
+
+    let uri = format!("/api/v1/trees/{tree_id}/sources/{source_id}/usage");
+    let (status, body) = send_request(app.clone(), Method::GET, &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = body.as_array().unwrap();
+    // The deleted person's citation is left out.
+    assert_eq!(entries.len(), 3);
+
+    let person_entry = entries
+        .iter()
+        .find(|e| e["owner_kind"] == "person")
+        .unwrap();
+    assert_eq!(person_entry["owner_id"], person_id.as_str());
+    assert_eq!(person_entry["label"], "John Doe");
+    assert_eq!(person_entry["page"], "p. 1");
+    assert_eq!(person_entry["confidence"], "high");
+
+    let event_entry = entries.iter().find(|e| e["owner_kind"] == "event").unwrap();
+    assert_eq!(event_entry["owner_id"], event["id"]);
+    assert_eq!(event_entry["event_type"], "birth");
+    assert_eq!(event_entry["event_date"], "12 MAR 1850");
+    assert_eq!(event_entry["person_id"], person_id.as_str());
+    assert_eq!(event_entry["label"], "John Doe");
+    assert_eq!(event_entry["text"], "born the 12th");
+
+    let family_entry = entries
+        .iter()
+        .find(|e| e["owner_kind"] == "family")
+        .unwrap();
+    assert_eq!(family_entry["family_id"], family["id"]);
+
+    // Unknown source, or a source of another tree.
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!(
+            "/api/v1/trees/{tree_id}/sources/{}/usage",
+            uuid::Uuid::now_v7()
+        ),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let other_tree = create_tree_via_api(&app).await;
+    let (status, _) = send_request(
+        app,
+        Method::GET,
+        &format!("/api/v1/trees/{other_tree}/sources/{source_id}/usage"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// ───────────────────────── Citation tests ─────────────────────────
+
+/// Helper: create a source via the API and return its ID.
+async fn create_source_via_api(app: &axum::Router, tree_id: &str) -> String {
+    let (_, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/sources"),
+        Some(serde_json::json!({
+            "title": "Test Source"
+        })),
+    )
+    .await;
+    body["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_tree_activity() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/persons/{person_id}/names"),
+        Some(serde_json::json!({
+            "name_type": "birth",
+            "given_names": "John",
+            "surname": "Doe",
+            "is_primary": true
+        })),
+    )
+    .await;
+    let (_, event) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/events"),
+        Some(serde_json::json!({ "event_type": "birth", "person_id": person_id })),
+    )
+    .await;
+    let source_id = create_source_via_api(&app, &tree_id).await;
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/activity"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = body.as_array().unwrap();
+    assert_eq!(entries.len(), 3);
+
+    // Newest first.
+    assert_eq!(entries[0]["kind"], "source");
+    assert_eq!(entries[0]["id"], source_id.as_str());
+    assert_eq!(entries[0]["label"], "Test Source");
+    assert_eq!(entries[0]["action"], "created");
+
+    let event_entry = entries.iter().find(|e| e["kind"] == "event").unwrap();
+    assert_eq!(event_entry["id"], event["id"]);
+    assert_eq!(event_entry["event_type"], "birth");
+    assert_eq!(event_entry["label"], "John Doe");
+    assert_eq!(event_entry["person_id"], person_id.as_str());
+
+    let person_entry = entries.iter().find(|e| e["kind"] == "person").unwrap();
+    assert_eq!(person_entry["label"], "John Doe");
+
+    // `limit` caps the list.
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/activity?limit=1"),
+        None,
+    )
+    .await;
+    assert_eq!(body.as_array().unwrap().len(), 1);
+
+    // Unknown tree.
+    let (status, _) = send_request(
+        app,
+        Method::GET,
+        &format!("/api/v1/trees/{}/activity", uuid::Uuid::now_v7()),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_citation_crud() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let source_id = create_source_via_api(&app, &tree_id).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+
+    // Create a citation
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/citations"),
+        Some(serde_json::json!({
+            "source_id": source_id,
+            "person_id": person_id,
+            "page": "p. 42",
+            "confidence": "high",
+            "text": "Birth record found"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["page"], "p. 42");
+    assert_eq!(body["confidence"], "high");
+    let citation_id = body["id"].as_str().unwrap().to_string();
+
+    // Update the citation
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/citations/{citation_id}"),
+        Some(serde_json::json!({
+            "page": "p. 43",
+            "text": "Updated record"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["page"], "p. 43");
+    assert_eq!(body["text"], "Updated record");
+
+    // Delete the citation
+    let (status, _) = send_request(
+        app.clone(),
+        Method::DELETE,
+        &format!("/api/v1/trees/{tree_id}/citations/{citation_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_bulk_citations() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let source_id = create_source_via_api(&app, &tree_id).await;
+    let a = create_person_via_api(&app, &tree_id).await;
+    let b = create_person_via_api(&app, &tree_id).await;
+    let uri = format!("/api/v1/trees/{tree_id}/citations/bulk");
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &uri,
+        Some(serde_json::json!({
+            "source_id": source_id,
+            "page": "f. 12",
+            "confidence": "high",
+            "owners": [{ "person_id": a }, { "person_id": b }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let citations = body.as_array().unwrap();
+    assert_eq!(citations.len(), 2);
+    assert_eq!(citations[0]["person_id"], a.as_str());
+    assert_eq!(citations[1]["person_id"], b.as_str());
+    assert!(citations.iter().all(|c| c["page"] == "f. 12"));
+
+    // An unknown owner rolls back the whole batch.
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &uri,
+        Some(serde_json::json!({
+            "source_id": source_id,
+            "confidence": "low",
+            "owners": [{ "person_id": a }, { "event_id": uuid::Uuid::now_v7() }]
+        })),
+    )
+    .await;
+    assert!(!status.is_success());
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/citations?source_id={source_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(body.as_array().unwrap().len(), 2);
+
+    // Each owner names exactly one record.
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &uri,
+        Some(serde_json::json!({
+            "source_id": source_id,
+            "confidence": "low",
+            "owners": [{ "person_id": a, "family_id": b }]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send_request(
+        app,
+        Method::POST,
+        &uri,
+        Some(serde_json::json!({
+            "source_id": source_id,
+            "confidence": "low",
+            "owners": []
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_citation_of_a_name() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let source_id = create_source_via_api(&app, &tree_id).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    let other_id = create_person_via_api(&app, &tree_id).await;
+
+    let (_, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/persons/{person_id}/names"),
+        Some(serde_json::json!({
+            "name_type": "birth",
+            "given_names": "Jean",
+            "surname": "Dupond",
+            "is_primary": true
+        })),
+    )
+    .await;
+    let name_id = body["id"].as_str().unwrap().to_string();
+
+    // A name citation must name the person the name belongs to.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/citations"),
+        Some(serde_json::json!({
+            "source_id": source_id,
+            "person_id": other_id,
+            "confidence": "high",
+            "fact_type": "name",
+            "fact_ref": name_id
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["type"], "urn:oxidgene:problem:validation_error");
+
+    // A date citation needs an event.
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/citations"),
+        Some(serde_json::json!({
+            "source_id": source_id,
+            "person_id": person_id,
+            "confidence": "high",
+            "fact_type": "date"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/citations"),
+        Some(serde_json::json!({
+            "source_id": source_id,
+            "person_id": person_id,
+            "page": "f. 12",
+            "confidence": "high",
+            "fact_type": "name",
+            "fact_ref": name_id
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["fact_type"], "name");
+    assert_eq!(body["fact_ref"], name_id.as_str());
+
+    // A whole-person citation alongside it.
+    send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/citations"),
+        Some(serde_json::json!({
+            "source_id": source_id,
+            "person_id": person_id,
+            "confidence": "medium"
+        })),
+    )
+    .await;
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/citations?person_id={person_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 2);
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/citations?fact_type=name&fact_ref={name_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let citations = body.as_array().unwrap();
+    assert_eq!(citations.len(), 1);
+    assert_eq!(citations[0]["page"], "f. 12");
+}
+
+// ───────────────────────── Media tests ─────────────────────────
+
+#[tokio::test]
+async fn test_media_crud() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    // Create media
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/media"),
+        Some(serde_json::json!({
+            "file_name": "photo.jpg",
+            "mime_type": "image/jpeg",
+            "file_path": "/uploads/photo.jpg",
+            "file_size": 1024000,
+            "title": "Family portrait",
+            "description": "Summer 1990"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["file_name"], "photo.jpg");
+    assert_eq!(body["title"], "Family portrait");
+    let media_id = body["id"].as_str().unwrap().to_string();
+
+    // Get media
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/media/{media_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["file_name"], "photo.jpg");
+
+    // Update media
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/media/{media_id}"),
+        Some(serde_json::json!({
+            "title": "Updated portrait",
+            "description": "Winter 1990"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["title"], "Updated portrait");
+    assert_eq!(body["description"], "Winter 1990");
+
+    // List media
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/media"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 1);
+
+    // Delete media
+    let (status, _) = send_request(
+        app.clone(),
+        Method::DELETE,
+        &format!("/api/v1/trees/{tree_id}/media/{media_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Verify it's gone
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/media/{media_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_media_create_validation() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    // Empty file_name should fail
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/media"),
+        Some(serde_json::json!({
+            "file_name": "  ",
+            "mime_type": "image/jpeg",
+            "file_path": "/uploads/photo.jpg",
+            "file_size": 1024
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["type"], "urn:oxidgene:problem:validation_error");
+}
+
+// ───────────────────────── MediaLink tests ─────────────────────────
+
+#[tokio::test]
+async fn test_media_link_create_delete() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+
+    // Create media first
+    let (_, media_body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/media"),
+        Some(serde_json::json!({
+            "file_name": "doc.pdf",
+            "mime_type": "application/pdf",
+            "file_path": "/uploads/doc.pdf",
+            "file_size": 2048
+        })),
+    )
+    .await;
+    let media_id = media_body["id"].as_str().unwrap().to_string();
+
+    // Create a media link
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/media-links"),
+        Some(serde_json::json!({
+            "media_id": media_id,
+            "person_id": person_id,
+            "sort_order": 1
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["media_id"], media_id);
+    assert_eq!(body["person_id"], person_id);
+    let link_id = body["id"].as_str().unwrap().to_string();
+
+    // Delete the media link
+    let (status, _) = send_request(
+        app.clone(),
+        Method::DELETE,
+        &format!("/api/v1/trees/{tree_id}/media-links/{link_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_person_profile_photo() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    let photo_uri = format!("/api/v1/trees/{tree_id}/persons/{person_id}/photo");
+
+    let mut link_ids = Vec::new();
+    for (file_name, mime_type) in [("photo.jpg", "image/jpeg"), ("doc.pdf", "application/pdf")] {
+        let (_, media) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/media"),
+            Some(serde_json::json!({
+                "file_name": file_name,
+                "mime_type": mime_type,
+                "file_path": format!("/uploads/{file_name}"),
+                "file_size": 1024
+            })),
+        )
+        .await;
+        let (_, link) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/media-links"),
+            Some(serde_json::json!({
+                "media_id": media["id"],
+                "person_id": person_id
+            })),
+        )
+        .await;
+        link_ids.push(link["id"].as_str().unwrap().to_string());
+    }
+    let (photo_link, pdf_link) = (&link_ids[0], &link_ids[1]);
+
+    // No photo designated yet: the placeholder for a man.
+    let (status, content_type, svg) = get_text(&app, &photo_uri).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "image/svg+xml");
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains("#cfe2ff"));
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &photo_uri,
+        Some(serde_json::json!({ "link_id": pdf_link })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["type"], "urn:oxidgene:problem:validation_error");
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &photo_uri,
+        Some(serde_json::json!({ "link_id": uuid::Uuid::now_v7() })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body}");
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &photo_uri,
+        Some(serde_json::json!({ "link_id": photo_link })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["id"], photo_link.as_str());
+    assert_eq!(body["is_profile"], true);
+
+    let (_, rows) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/media-links"),
+        None,
+    )
+    .await;
+    let rows = rows.as_array().unwrap();
+    assert_eq!(rows.len(), 2);
+    for row in rows {
+        assert_eq!(row["is_profile"], row["file_name"] == "photo.jpg");
+    }
+
+    let request = Request::builder()
+        .uri(photo_uri.as_str())
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+    assert_eq!(response.headers()["location"], "/uploads/photo.jpg");
+
+    let (status, _) = send_request(app.clone(), Method::DELETE, &photo_uri, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _, svg) = get_text(&app, &photo_uri).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(svg.starts_with("<svg "));
+}
+
+#[tokio::test]
+async fn test_person_media_gallery() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    let media_uri = format!("/api/v1/trees/{tree_id}/persons/{person_id}/media");
+
+    let mut link_ids = Vec::new();
+    for (i, title) in ["Wedding", "Census", "Portrait"].iter().enumerate() {
+        let (_, media) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/media"),
+            Some(serde_json::json!({
+                "file_name": format!("{i}.jpg"),
+                "mime_type": "image/jpeg",
+                "file_path": format!("/uploads/{i}.jpg"),
+                "file_size": 1024,
+                "title": title
+            })),
+        )
+        .await;
+        let (_, link) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/media-links"),
+            Some(serde_json::json!({
+                "media_id": media["id"],
+                "person_id": person_id,
+                "sort_order": i
+            })),
+        )
+        .await;
+        link_ids.push(link["id"].as_str().unwrap().to_string());
+    }
+
+    let (status, body) = send_request(app.clone(), Method::GET, &media_uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 3);
+    let titles: Vec<&str> = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(titles, ["Wedding", "Census", "Portrait"]);
+    assert_eq!(body["items"][0]["link_id"], link_ids[0].as_str());
+    assert_eq!(body["items"][0]["is_profile"], false);
+
+    // Pages load lazily.
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("{media_uri}?offset=1&limit=1"),
+        None,
+    )
+    .await;
+    assert_eq!(body["total"], 3);
+    assert_eq!(body["items"].as_array().unwrap().len(), 1);
+    assert_eq!(body["items"][0]["title"], "Census");
+
+    // Reorder: every link must be listed exactly once.
+    let order_uri = format!("{media_uri}/order");
+    let (status, _) = send_request(
+        app.clone(),
+        Method::PUT,
+        &order_uri,
+        Some(serde_json::json!({ "link_ids": [link_ids[2], link_ids[0]] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send_request(
+        app.clone(),
+        Method::PUT,
+        &order_uri,
+        Some(serde_json::json!({ "link_ids": [link_ids[2], link_ids[0], link_ids[1]] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, body) = send_request(app.clone(), Method::GET, &media_uri, None).await;
+    let titles: Vec<&str> = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(titles, ["Portrait", "Wedding", "Census"]);
+
+    // A person of another tree is not found.
+    let other_tree = create_tree_via_api(&app).await;
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{other_tree}/persons/{person_id}/media"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_source_media_link() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    let (_, source) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/sources"),
+        Some(serde_json::json!({ "title": "Parish register" })),
+    )
+    .await;
+    let source_id = source["id"].as_str().unwrap().to_string();
+    let (_, media) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/media"),
+        Some(serde_json::json!({
+            "file_name": "page12.jpg",
+            "mime_type": "image/jpeg",
+            "file_path": "/uploads/page12.jpg",
+            "file_size": 4096
+        })),
+    )
+    .await;
+    let media_id = media["id"].as_str().unwrap().to_string();
+
+    let (status, link) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/sources/{source_id}/media"),
+        Some(serde_json::json!({ "media_id": media_id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(link["source_id"], source_id);
+    assert!(link["person_id"].is_null());
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/sources/{source_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["title"], "Parish register");
+    let attached = body["media"].as_array().unwrap();
+    assert_eq!(attached.len(), 1);
+    assert_eq!(attached[0]["id"], media_id);
+    assert_eq!(attached[0]["link_id"], link["id"]);
+    assert_eq!(attached[0]["file_name"], "page12.jpg");
+
+    // Media from another tree cannot be attached.
+    let other_tree = create_tree_via_api(&app).await;
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{other_tree}/sources/{source_id}/media"),
+        Some(serde_json::json!({ "media_id": media_id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// ───────────────────────── Note tests ─────────────────────────
+
+#[tokio::test]
+async fn test_note_crud() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+
+    // Create a note
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/notes"),
+        Some(serde_json::json!({
+            "text": "Important note about this person",
+            "person_id": person_id
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["text"], "Important note about this person");
+    let note_id = body["id"].as_str().unwrap().to_string();
+
+    // Get the note
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/notes/{note_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["text"], "Important note about this person");
+
+    // Update the note
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/notes/{note_id}"),
+        Some(serde_json::json!({
+            "text": "Updated note text"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["text"], "Updated note text");
+
+    // List notes by person
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/notes?person_id={person_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 1);
+
+    // Delete the note
+    let (status, _) = send_request(
+        app.clone(),
+        Method::DELETE,
+        &format!("/api/v1/trees/{tree_id}/notes/{note_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Verify it's gone
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/notes/{note_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_note_markdown_format() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+
+    // Notes are plain text unless told otherwise.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/notes"),
+        Some(serde_json::json!({
+            "text": "Plain note",
+            "person_id": person_id
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["format"], "plain");
+    let note_id = body["id"].as_str().unwrap().to_string();
+
+    // Switching to markdown stores the source text as is.
+    let uri = format!("/api/v1/trees/{tree_id}/notes/{note_id}");
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &uri,
+        Some(serde_json::json!({
+            "text": "## Updated\n\n- note text",
+            "format": "markdown"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["text"], "## Updated\n\n- note text");
+    assert_eq!(body["format"], "markdown");
+
+    // Updating the text alone keeps the format.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &uri,
+        Some(serde_json::json!({ "text": "**Bold** text" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["format"], "markdown");
+    let (_, body) = send_request(app.clone(), Method::GET, &uri, None).await;
+    assert_eq!(body["text"], "**Bold** text");
+    assert_eq!(body["format"], "markdown");
+}
+
+#[tokio::test]
+async fn test_note_create_validation() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    // Empty text should fail
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/notes"),
+        Some(serde_json::json!({
+            "text": "   "
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["type"], "urn:oxidgene:problem:validation_error");
+}
+
+#[tokio::test]
+async fn test_research_tasks() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    let (_, family) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/families"),
+        None,
+    )
+    .await;
+    let family_id = family["id"].as_str().unwrap().to_string();
+
+    // A task needs exactly one owner.
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/tasks"),
+        Some(serde_json::json!({ "title": "Orphan" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, task) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/tasks"),
+        Some(serde_json::json!({
+            "person_id": person_id,
+            "title": "  Find baptism record  ",
+            "due_date": "2026-11-01"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(task["title"], "Find baptism record");
+    assert_eq!(task["status"], "open");
+    assert_eq!(task["due_date"], "2026-11-01");
+    let task_id = task["id"].as_str().unwrap().to_string();
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/tasks"),
+        Some(serde_json::json!({
+            "family_id": family_id,
+            "title": "Locate marriage banns"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, tasks) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/tasks?status=open&person_id={person_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let tasks = tasks.as_array().unwrap();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0]["id"], task_id.as_str());
+
+    // Mark done and clear the due date.
+    let (status, task) = send_request(
+        app.clone(),
+        Method::PUT,
+        &format!("/api/v1/trees/{tree_id}/tasks/{task_id}"),
+        Some(serde_json::json!({ "status": "done", "due_date": null })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(task["status"], "done");
+    assert!(task["due_date"].is_null());
+
+    let (_, open) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/tasks?status=open"),
+        None,
+    )
+    .await;
+    assert_eq!(open.as_array().unwrap().len(), 1);
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::DELETE,
+        &format!("/api/v1/trees/{tree_id}/tasks/{task_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/tasks/{task_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_note_list_by_multiple_entities() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+
+    // Create a note linked to a person
+    send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/notes"),
+        Some(serde_json::json!({
+            "text": "Person note",
+            "person_id": person_id
+        })),
+    )
+    .await;
+
+    // Create a family and a note linked to it
+    let (_, fam_body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/families"),
+        None,
+    )
+    .await;
+    let family_id = fam_body["id"].as_str().unwrap().to_string();
+
+    send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/notes"),
+        Some(serde_json::json!({
+            "text": "Family note",
+            "family_id": family_id
+        })),
+    )
+    .await;
+
+    // List by person — should get 1
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/notes?person_id={person_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 1);
+    assert_eq!(body[0]["text"], "Person note");
+
+    // List by family — should get 1
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/notes?family_id={family_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 1);
+    assert_eq!(body[0]["text"], "Family note");
+}
+
+#[tokio::test]
+async fn test_person_records_include_family() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+
+    let mut family_ids = Vec::new();
+    for _ in 0..3 {
+        let (_, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/families"),
+            None,
+        )
+        .await;
+        family_ids.push(body["id"].as_str().unwrap().to_string());
+    }
+    let (parents, own, unrelated) = (&family_ids[0], &family_ids[1], &family_ids[2]);
+    send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/families/{parents}/children"),
+        Some(serde_json::json!({ "person_id": person_id, "child_type": "biological" })),
+    )
+    .await;
+    send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/families/{own}/spouses"),
+        Some(serde_json::json!({ "person_id": person_id, "role": "husband" })),
+    )
+    .await;
+
+    for (owner, date) in [
+        (
+            serde_json::json!({ "event_type": "birth", "person_id": person_id }),
+            "1850",
+        ),
+        (
+            serde_json::json!({ "event_type": "marriage", "family_id": parents }),
+            "1848",
+        ),
+        (
+            serde_json::json!({ "event_type": "marriage", "family_id": own }),
+            "1875",
+        ),
+        (
+            serde_json::json!({ "event_type": "marriage", "family_id": unrelated }),
+            "1900",
+        ),
+    ] {
+        let mut body = owner;
+        body["date_value"] = serde_json::json!(date);
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/events"),
+            Some(body),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+    for owner in [
+        serde_json::json!({ "text": "On the person", "person_id": person_id }),
+        serde_json::json!({ "text": "Parents' union", "family_id": parents }),
+        serde_json::json!({ "text": "Own union", "family_id": own }),
+        serde_json::json!({ "text": "Someone else's", "family_id": unrelated }),
+    ] {
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/notes"),
+            Some(owner),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    // Without the flag, only the person's own records, unflagged.
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/events?person_id={person_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(body["total_count"], 1);
+    assert!(body["edges"][0]["node"].get("inherited").is_none());
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/events?person_id={person_id}&include_family=true"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_count"], 3);
+    let events: Vec<(&str, bool, Option<&str>)> = body["edges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| {
+            let node = &e["node"];
+            (
+                node["date_value"].as_str().unwrap(),
+                node["inherited"].as_bool().unwrap(),
+                node["family_role"].as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            ("1848", true, Some("child")),
+            ("1850", false, None),
+            ("1875", true, Some("spouse")),
+        ]
+    );
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/notes?person_id={person_id}&include_family=true"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let notes = body.as_array().unwrap();
+    assert_eq!(notes.len(), 3);
+    assert_eq!(notes[0]["text"], "On the person");
+    assert_eq!(notes[0]["inherited"], false);
+    assert!(notes[1..].iter().all(|n| n["inherited"] == true));
+    let own_note = notes.iter().find(|n| n["text"] == "Own union").unwrap();
+    assert_eq!(own_note["family_role"], "spouse");
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/notes?include_family=true"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+// ── GEDCOM Import/Export ─────────────────────────────────────────────
+
+fn minimal_gedcom() -> &'static str {
+    concat!(
+        "0 HEAD\n",
+        "1 SOUR OxidGene\n",
+        "1 GEDC\n",
+        "2 VERS 5.5.1\n",
+        "2 FORM LINEAGE-LINKED\n",
+        "1 CHAR UTF-8\n",
+        "0 @I1@ INDI\n",
+        "1 NAME John /Doe/\n",
+        "1 SEX M\n",
+        "1 BIRT\n",
+        "2 DATE 1 JAN 1980\n",
+        "2 PLAC Springfield\n",
+        "0 @I2@ INDI\n",
+        "1 NAME Jane /Smith/\n",
+        "1 SEX F\n",
+        "0 @F1@ FAM\n",
+        "1 HUSB @I1@\n",
+        "1 WIFE @I2@\n",
+        "1 MARR\n",
+        "2 DATE 15 JUN 2005\n",
+        "0 TRLR\n",
+    )
+}
+
+/// Helper: start a GEDCOM import, then follow the job's SSE stream until it
+/// ends. Returns every `(event, data)` pair received, in order.
+async fn import_gedcom_via_api(
+    app: &axum::Router,
+    tree_id: &str,
+    gedcom: &str,
+) -> Vec<(String, Value)> {
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/gedcom/import"),
+        Some(serde_json::json!({ "gedcom": gedcom })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let job_id = body["job_id"].as_str().unwrap();
+
+    follow_events(
+        app,
+        &format!("/api/v1/trees/{tree_id}/import/{job_id}/events"),
+    )
+    .await
+}
+
+/// Helper: read a Server-Sent Events stream until it ends. Returns every
+/// `(event, data)` pair received, in order.
+async fn follow_events(app: &axum::Router, uri: &str) -> Vec<(String, Value)> {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+
+    String::from_utf8(bytes.to_vec())
+        .unwrap()
+        .split("\n\n")
+        .filter_map(|block| {
+            let mut name = None;
+            let mut data = None;
+            for line in block.lines() {
+                if let Some(v) = line.strip_prefix("event: ") {
+                    name = Some(v.to_string());
+                } else if let Some(v) = line.strip_prefix("data: ") {
+                    data = Some(serde_json::from_str(v).unwrap());
+                }
+            }
+            Some((name?, data?))
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_gedcom_import() {
+    let app = setup_app().await;
+
+    // Create tree
+    let (_, tree_body) = send_request(
+        app.clone(),
+        Method::POST,
+        "/api/v1/trees",
+        Some(serde_json::json!({ "name": "GEDCOM Tree" })),
+    )
+    .await;
+    let tree_id = tree_body["id"].as_str().unwrap();
+
+    // Import GEDCOM and follow its progress to completion
+    let events = import_gedcom_via_api(&app, tree_id, minimal_gedcom()).await;
+    let (last, body) = events.last().unwrap();
+    assert_eq!(last, "done");
+    assert!(
+        events[..events.len() - 1]
+            .iter()
+            .all(|(e, _)| e == "progress")
+    );
+    assert_eq!(body["persons_count"], 2);
+    assert_eq!(body["families_count"], 1);
+    assert!(body["events_count"].as_i64().unwrap() >= 2); // BIRT + MARR
+    assert!(body["places_count"].as_i64().unwrap() >= 1); // Springfield
+
+    // Verify persons are actually in the DB
+    let (status, persons) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let edges = persons["edges"].as_array().unwrap();
+    assert_eq!(edges.len(), 2);
+}
+
+#[tokio::test]
+async fn test_gedcom_import_cancel() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/import/00000000-0000-0000-0000-000000000000/cancel"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/gedcom/import"),
+        Some(serde_json::json!({ "gedcom": minimal_gedcom() })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let job_id = body["job_id"].as_str().unwrap().to_string();
+    let (cancel_status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/import/{job_id}/cancel"),
+        None,
+    )
+    .await;
+    let events = follow_events(
+        &app,
+        &format!("/api/v1/trees/{tree_id}/import/{job_id}/events"),
+    )
+    .await;
+    let (last, _) = events.last().unwrap();
+
+    let (_, persons) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons"),
+        None,
+    )
+    .await;
+    // The import may have committed before the request arrived.
+    if cancel_status == StatusCode::ACCEPTED {
+        assert_eq!(last, "cancelled");
+        assert_eq!(persons["total_count"], 0);
+    } else {
+        assert_eq!(cancel_status, StatusCode::CONFLICT);
+        assert_eq!(last, "done");
+    }
+
+    // A finished job can no longer be cancelled.
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/import/{job_id}/cancel"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_gedcom_reimport_reuses_places() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    for _ in 0..2 {
+        let events = import_gedcom_via_api(&app, &tree_id, minimal_gedcom()).await;
+        assert_eq!(events.last().unwrap().0, "done");
+    }
+
+    // "Springfield" was seen twice but stored once.
+    let (_, places) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/places"),
+        None,
+    )
+    .await;
+    assert_eq!(places["total_count"], 1);
+
+    // Nothing left to merge.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/places/dedupe"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["merged"], 0);
+}
+
+#[tokio::test]
+async fn test_gedcom_import_reuses_places_with_matching_coordinates() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let (_, place) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/places"),
+        Some(serde_json::json!({ "name": "Paris" })),
+    )
+    .await;
+    let place_id = place["id"].as_str().unwrap();
+
+    let gedcom_at = |lati: &str, long: &str| {
+        format!(
+            "0 HEAD\n1 CHAR UTF-8\n0 @I1@ INDI\n1 NAME Jean /Martin/\n1 BIRT\n\
+             2 PLAC Paris\n3 MAP\n4 LATI {lati}\n4 LONG {long}\n0 TRLR\n"
+        )
+    };
+    let list_places = || {
+        let app = app.clone();
+        let uri = format!("/api/v1/trees/{tree_id}/places");
+        async move { send_request(app, Method::GET, &uri, None).await.1 }
+    };
+
+    // The existing place has no coordinates: it is reused and inherits them.
+    let events = import_gedcom_via_api(&app, &tree_id, &gedcom_at("N48.8566", "E2.3522")).await;
+    assert_eq!(events.last().unwrap().0, "done");
+    let places = list_places().await;
+    assert_eq!(places["total_count"], 1);
+    assert_eq!(places["edges"][0]["node"]["id"], place_id);
+    assert_eq!(places["edges"][0]["node"]["latitude"], 48.8566);
+    assert_eq!(places["edges"][0]["node"]["longitude"], 2.3522);
+    assert_eq!(places["edges"][0]["node"]["version"], 2);
+
+    // Same name, coordinates too far apart: another place, as dedupe would
+    // keep them apart.
+    let events = import_gedcom_via_api(&app, &tree_id, &gedcom_at("N33.6609", "W95.5555")).await;
+    assert_eq!(events.last().unwrap().0, "done");
+    assert_eq!(list_places().await["total_count"], 2);
+    let (_, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/places/dedupe"),
+        None,
+    )
+    .await;
+    assert_eq!(body["merged"], 0);
+}
+
+#[tokio::test]
+async fn test_gedcom_import_summary_counts() {
+    use oxidgene_api::service::gedcom::{ImportSummary, import_and_persist};
+    use oxidgene_db::entities::{
+        citation, event, family, media, note, person, person_name, place, source,
+    };
+    use sea_orm::{EntityTrait, PaginatorTrait};
+
+    let gedcom = concat!(
+        "0 HEAD\n",
+        "1 GEDC\n",
+        "2 VERS 5.5.1\n",
+        "1 CHAR UTF-8\n",
+        "0 @I1@ INDI\n",
+        "1 NAME John /Doe/\n",
+        "1 NAME Johnny /Doe/\n",
+        "2 TYPE aka\n",
+        "1 BIRT\n",
+        "2 DATE 1 JAN 1980\n",
+        "2 PLAC Springfield\n",
+        "2 SOUR @S1@\n",
+        "3 PAGE Folio 12\n",
+        "1 DEAT\n",
+        "2 PLAC Shelbyville\n",
+        "1 SOUR @S1@\n",
+        "2 PAGE Folio 13\n",
+        "1 OBJE @M1@\n",
+        "1 NOTE Farmer all his life.\n",
+        "0 @I2@ INDI\n",
+        "1 NAME Jane /Smith/\n",
+        "1 BIRT\n",
+        "2 PLAC Springfield\n",
+        "0 @F1@ FAM\n",
+        "1 HUSB @I1@\n",
+        "1 WIFE @I2@\n",
+        "1 MARR\n",
+        "2 PLAC Springfield\n",
+        "0 @S1@ SOUR\n",
+        "1 TITL Parish register\n",
+        "0 @M1@ OBJE\n",
+        "1 FILE photo.jpg\n",
+        "2 FORM jpg\n",
+        "0 TRLR\n",
+    );
+
+    // The summary counts every vector of the parsed result.
+    let parsed = oxidgene_gedcom::import::import_gedcom(gedcom, uuid::Uuid::now_v7()).unwrap();
+    let summary = ImportSummary::from(&parsed);
+    assert_eq!(summary.persons_count, parsed.persons.len());
+    assert_eq!(summary.person_names_count, parsed.person_names.len());
+    assert_eq!(summary.families_count, parsed.families.len());
+    assert_eq!(summary.events_count, parsed.events.len());
+    assert_eq!(summary.sources_count, parsed.sources.len());
+    assert_eq!(summary.citations_count, parsed.citations.len());
+    assert_eq!(summary.media_count, parsed.media.len());
+    assert_eq!(summary.places_count, parsed.places.len());
+    assert_eq!(summary.notes_count, parsed.notes.len());
+
+    // And, once persisted, the rows actually written: repeated places are
+    // merged before counting.
+    let db = setup_db().await;
+    let tree_id = uuid::Uuid::now_v7();
+    oxidgene_db::repo::TreeRepo::create(&db, tree_id, "Counts".into(), None)
+        .await
+        .unwrap();
+    let summary = import_and_persist(&db, tree_id, gedcom).await.unwrap();
+    assert_eq!(summary.persons_count, 2);
+    assert_eq!(summary.person_names_count, 3);
+    assert_eq!(summary.places_count, 2);
+    assert_eq!(summary.citations_count, 2);
+    assert_eq!(
+        summary.persons_count as u64,
+        person::Entity::find().count(&db).await.unwrap()
+    );
+    assert_eq!(
+        summary.person_names_count as u64,
+        person_name::Entity::find().count(&db).await.unwrap()
+    );
+    assert_eq!(
+        summary.families_count as u64,
+        family::Entity::find().count(&db).await.unwrap()
+    );
+    assert_eq!(
+        summary.events_count as u64,
+        event::Entity::find().count(&db).await.unwrap()
+    );
+    assert_eq!(
+        summary.sources_count as u64,
+        source::Entity::find().count(&db).await.unwrap()
+    );
+    assert_eq!(
+        summary.citations_count as u64,
+        citation::Entity::find().count(&db).await.unwrap()
+    );
+    assert_eq!(
+        summary.media_count as u64,
+        media::Entity::find().count(&db).await.unwrap()
+    );
+    assert_eq!(
+        summary.places_count as u64,
+        place::Entity::find().count(&db).await.unwrap()
+    );
+    assert_eq!(
+        summary.notes_count as u64,
+        note::Entity::find().count(&db).await.unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_gedcom_import_reports_parsed_records_and_warnings() {
+    use oxidgene_api::service::gedcom::import_and_persist_with_progress;
+    use oxidgene_api::service::import_job::ImportJobs;
+
+    let gedcom = concat!(
+        "0 HEAD\n",
+        "1 GEDC\n",
+        "2 VERS 5.5.1\n",
+        "0 @I1@ INDI\n",
+        "1 NAME John /Doe/\n",
+        "0 FAM\n",
+        "1 HUSB @I1@\n",
+        "0 TRLR\n",
+    );
+    let db = setup_db().await;
+    let tree_id = uuid::Uuid::now_v7();
+    oxidgene_db::repo::TreeRepo::create(&db, tree_id, "Progress".into(), None)
+        .await
+        .unwrap();
+
+    let jobs = ImportJobs::new();
+    let (job_id, tracker) = jobs.start(tree_id);
+    let rx = jobs.subscribe(tree_id, job_id).unwrap();
+    let summary = import_and_persist_with_progress(&db, tree_id, gedcom, &tracker)
+        .await
+        .unwrap();
+
+    // The progress carries the parse counts and the warnings raised so far,
+    // before the job is finished.
+    let progress = rx.borrow().clone();
+    assert_eq!(progress.records_parsed, 2); // the person and their name
+    assert_eq!(progress.records_total, 2);
+    assert_eq!(progress.records_persisted, 2);
+    assert_eq!(progress.warnings, ["Skipping family without xref"]);
+    assert_eq!(progress.warnings, summary.warnings);
+}
+
+#[tokio::test]
+async fn test_gedcom_export_file_is_deleted_with_its_job() {
+    use oxidgene_api::service::export_job::ExportJobs;
+    use oxidgene_api::service::gedcom::export_with_progress;
+
+    let db = setup_db().await;
+    let tree_id = uuid::Uuid::now_v7();
+    oxidgene_db::repo::TreeRepo::create(&db, tree_id, "Export".into(), None)
+        .await
+        .unwrap();
+
+    // Jobs are pruned as soon as they finish.
+    let jobs = ExportJobs::with_retention(chrono::Duration::zero());
+    let (job_id, tracker) = jobs.start(tree_id);
+    let artifact = export_with_progress(
+        &db,
+        tree_id,
+        &oxidgene_gedcom::ExportOptions::default(),
+        None,
+        false,
+        &tracker,
+    )
+    .await
+    .unwrap();
+    let path = artifact.path.to_path_buf();
+    assert_eq!(
+        std::fs::metadata(&path).unwrap().len() as usize,
+        artifact.size_bytes
+    );
+    tracker.finish(artifact);
+    drop(tracker);
+
+    // Looking the job up prunes it, deleting its file.
+    assert!(jobs.output(tree_id, job_id).is_none());
+    assert!(jobs.subscribe(tree_id, job_id).is_none());
+    assert!(!path.exists());
+}
+
+/// Geocoder answering from a fixed table: "Paris" has two distinct
+/// candidates, "Lyon" one, anything else none.
+#[derive(Debug)]
+struct FakeGeocoder;
+
+#[async_trait::async_trait]
+impl Geocoder for FakeGeocoder {
+    async fn geocode(&self, query: &str) -> Result<Vec<GeoMatch>, String> {
+        let at = |latitude, longitude| GeoMatch {
+            latitude,
+            longitude,
+            display_name: query.to_string(),
+        };
+        Ok(match query {
+            "Paris" => vec![at(48.85, 2.35), at(33.66, -95.55)],
+            "Lyon" => vec![at(45.76, 4.83)],
+            _ => vec![],
+        })
+    }
+
+    async fn reverse(&self, _lat: f64, _lon: f64) -> Result<Option<GeoAddress>, String> {
+        Ok(Some(GeoAddress {
+            locality: Some("Lyon".to_string()),
+            county: Some("Rhône".to_string()),
+            state: Some("Auvergne-Rhône-Alpes".to_string()),
+            country: Some("France".to_string()),
+        }))
+    }
+}
+
+#[tokio::test]
+async fn test_geocode_all_places() {
+    let state = AppState::new(setup_db().await)
+        .with_geocoding(Geocoding::new(Some(Arc::new(FakeGeocoder)), Duration::ZERO));
+    let app = build_router(state);
+    let tree_id = create_tree_via_api(&app).await;
+
+    for (name, coords) in [
+        ("Paris", None),
+        ("Lyon", None),
+        ("Atlantis", None),
+        ("Nice", Some((43.7, 7.27))),
+    ] {
+        let (status, _) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/places"),
+            Some(serde_json::json!({
+                "name": name,
+                "latitude": coords.map(|c: (f64, f64)| c.0),
+                "longitude": coords.map(|c: (f64, f64)| c.1),
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/places/geocode-all"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let job_id = body["job_id"].as_str().unwrap();
+
+    let events = follow_events(
+        &app,
+        &format!("/api/v1/trees/{tree_id}/places/geocode-all/{job_id}/events"),
+    )
+    .await;
+    let (last, summary) = events.last().unwrap();
+    assert_eq!(last, "done");
+    assert_eq!(summary["succeeded"], 1);
+    assert_eq!(summary["ambiguous"], 1);
+    assert_eq!(summary["failed"], 1);
+    // Places that already had coordinates are not looked up.
+    assert_eq!(summary["places"].as_array().unwrap().len(), 3);
+
+    // Only the unambiguous match was written back.
+    let (_, places) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/places"),
+        None,
+    )
+    .await;
+    let edges = places["edges"].as_array().unwrap();
+    let find = |name: &str| {
+        edges
+            .iter()
+            .map(|e| &e["node"])
+            .find(|p| p["name"] == name)
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(find("Lyon")["latitude"], 45.76);
+    assert!(find("Paris")["latitude"].is_null());
+
+    // Reverse geocoding replaces the name with the hierarchical one.
+    let lyon_id = find("Lyon")["id"].as_str().unwrap().to_string();
+    let (status, place) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/places/{lyon_id}/reverse-geocode"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(place["name"], "Lyon, Rhône, Auvergne-Rhône-Alpes, France");
+
+    // A place without coordinates cannot be reverse-geocoded.
+    let paris_id = find("Paris")["id"].as_str().unwrap().to_string();
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/places/{paris_id}/reverse-geocode"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_geocode_all_disabled() {
+    let state = AppState::new(setup_db().await).with_geocoding(Geocoding::disabled());
+    let app = build_router(state);
+    let tree_id = create_tree_via_api(&app).await;
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/places/geocode-all"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_gedcom_import_invalid_tree() {
+    let app = setup_app().await;
+    let fake_id = "00000000-0000-0000-0000-000000000000";
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{fake_id}/gedcom/import"),
+        Some(serde_json::json!({ "gedcom": minimal_gedcom() })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_gedcom_import_events() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+
+    // Unparseable input fails the job, reported on the stream.
+    let events = import_gedcom_via_api(&app, &tree_id, "not a gedcom file").await;
+    let (last, body) = events.last().unwrap();
+    assert_eq!(last, "failed");
+    assert!(body["error"].is_string());
+
+    // Unknown job
+    let fake_job = "00000000-0000-0000-0000-000000000000";
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/import/{fake_job}/events"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_gedcom_export_empty_tree() {
+    let app = setup_app().await;
+
+    // Create tree
+    let (_, tree_body) = send_request(
+        app.clone(),
+        Method::POST,
+        "/api/v1/trees",
+        Some(serde_json::json!({ "name": "Empty Tree" })),
+    )
+    .await;
+    let tree_id = tree_body["id"].as_str().unwrap();
+
+    // Export (empty tree)
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/gedcom/export"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["gedcom"].as_str().unwrap().contains("HEAD"));
+    assert!(body["warnings"].as_array().unwrap().is_empty());
+
+    // Header metadata comes from the query string.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!(
+            "/api/v1/trees/{tree_id}/gedcom/export\
+             ?submitter=Marie%20Curie&software_name=Archive&software_version=2.1&language=French"
+        ),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let gedcom = body["gedcom"].as_str().unwrap();
+    assert!(gedcom.contains("1 SOUR OXIDGENE\n2 VERS 2.1\n2 NAME Archive\n"));
+    assert!(gedcom.contains("1 SUBM @U1@\n1 LANG French\n"));
+    assert!(gedcom.contains("0 @U1@ SUBM\n1 NAME Marie Curie\n"));
+}
+
+#[tokio::test]
+async fn test_gedcom_roundtrip() {
+    let app = setup_app().await;
+
+    // Create tree
+    let (_, tree_body) = send_request(
+        app.clone(),
+        Method::POST,
+        "/api/v1/trees",
+        Some(serde_json::json!({ "name": "Roundtrip Tree" })),
+    )
+    .await;
+    let tree_id = tree_body["id"].as_str().unwrap();
+
+    // Import
+    let events = import_gedcom_via_api(&app, tree_id, minimal_gedcom()).await;
+    let (_, import_body) = events.last().unwrap();
+
+    // Export
+    let (status, export_body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/gedcom/export"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let exported = export_body["gedcom"].as_str().unwrap();
+
+    // Verify the exported GEDCOM contains the imported data
+    assert!(exported.contains("HEAD"));
+    assert!(exported.contains("INDI"));
+    assert!(exported.contains("FAM"));
+
+    // Verify counts match what we imported
+    assert_eq!(import_body["persons_count"], 2);
+    assert_eq!(import_body["families_count"], 1);
+}
+
+#[tokio::test]
+async fn test_gedcom_external_ids_roundtrip() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let gedcom = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 CHAR UTF-8\n\
+        0 @I1@ INDI\n1 NAME John /Doe/\n1 _UID 8F1C4E2B9A0D4C7E8B3F6A5D2E1C0B9A\n1 REFN 42\n\
+        0 TRLR\n";
+
+    let events = import_gedcom_via_api(&app, &tree_id, gedcom).await;
+    let (name, summary) = events.last().unwrap();
+    assert_eq!(name, "done");
+    assert!(summary["warnings"].as_array().unwrap().is_empty());
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/gedcom/export"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let exported = body["gedcom"].as_str().unwrap();
+    assert!(exported.contains("1 _UID 8F1C4E2B9A0D4C7E8B3F6A5D2E1C0B9A"));
+    assert!(exported.contains("1 REFN 42"));
+
+    // Importing the same file again is flagged.
+    let events = import_gedcom_via_api(&app, &tree_id, gedcom).await;
+    let (_, summary) = events.last().unwrap();
+    let warnings = summary["warnings"].as_array().unwrap();
+    assert!(
+        warnings
+            .iter()
+            .any(|w| w.as_str().unwrap().contains("_UID already present"))
+    );
+}
+
+#[tokio::test]
+async fn test_import_preview_and_commit() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let gedcom = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 CHAR UTF-8\n\
+        0 @I1@ INDI\n1 NAME John /Doe/\n1 _UID 8F1C4E2B9A0D4C7E8B3F6A5D2E1C0B9A\n\
+        1 BIRT\n2 DATE 1850\n2 PLAC Paris, France\n\
+        0 TRLR\n";
+    import_gedcom_via_api(&app, &tree_id, gedcom).await;
+    let (_, people) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons"),
+        None,
+    )
+    .await;
+    let john_id = people["edges"][0]["node"]["id"].clone();
+
+    // The preview reports what the import would do and writes nothing.
+    let (status, plan) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/import/preview"),
+        Some(serde_json::json!({ "gedcom": gedcom })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(plan["persons_count"], 1);
+    assert_eq!(plan["events_count"], 1);
+    assert_eq!(plan["reused_places"], serde_json::json!(["Paris, France"]));
+    let conflicts = plan["conflicts"].as_array().unwrap();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0]["uid"], "8F1C4E2B9A0D4C7E8B3F6A5D2E1C0B9A");
+    assert_eq!(conflicts[0]["existing_person_id"], john_id);
+    assert_eq!(conflicts[0]["name"], "John Doe");
+    let token = plan["token"].as_str().unwrap().to_string();
+    assert!(plan["expires_at"].is_string());
+
+    let (_, people) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons"),
+        None,
+    )
+    .await;
+    assert_eq!(people["total_count"], 1);
+
+    // Committing runs the previewed import as a job.
+    let commit_uri = format!("/api/v1/trees/{tree_id}/import/preview/{token}/commit");
+    let (status, body) = send_request(app.clone(), Method::POST, &commit_uri, None).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let job_id = body["job_id"].as_str().unwrap();
+    let events = follow_events(
+        &app,
+        &format!("/api/v1/trees/{tree_id}/import/{job_id}/events"),
+    )
+    .await;
+    let (name, summary) = events.last().unwrap();
+    assert_eq!(name, "done");
+    assert_eq!(summary["persons_count"], 1);
+
+    // A token is used once.
+    let (status, _) = send_request(app.clone(), Method::POST, &commit_uri, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // A preview outdated by a change to the tree is refused.
+    let other = "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n1 CHAR UTF-8\n\
+        0 @I1@ INDI\n1 NAME Jane /Roe/\n1 _UID 0A1B2C3D4E5F60718293A4B5C6D7E8F9\n\
+        0 TRLR\n";
+    let (_, plan) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/import/preview"),
+        Some(serde_json::json!({ "gedcom": other })),
+    )
+    .await;
+    assert!(plan["conflicts"].as_array().unwrap().is_empty());
+    import_gedcom_via_api(&app, &tree_id, other).await;
+    let token = plan["token"].as_str().unwrap();
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/import/preview/{token}/commit"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_gedcom_export_invalid_tree() {
+    let app = setup_app().await;
+    let fake_id = "00000000-0000-0000-0000-000000000000";
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{fake_id}/gedcom/export"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_gedcom_export_job() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let events = import_gedcom_via_api(&app, &tree_id, minimal_gedcom()).await;
+    assert_eq!(events.last().unwrap().0, "done");
+
+    let (status, estimate) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/export/estimate"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        estimate,
+        serde_json::json!({
+            "persons": 2,
+            "families": 1,
+            "sources": 0,
+            "media": 0,
+            "records_total": 3
+        })
+    );
+
+    for (format, content_type, magic) in [
+        ("gedcom", "text/plain; charset=utf-8", &b"0 HEAD"[..]),
+        ("gedzip", "application/zip", &b"PK"[..]),
+    ] {
+        let (status, body) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/export/jobs?format={format}"),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let job_id = body["job_id"].as_str().unwrap().to_string();
+
+        let events = follow_events(
+            &app,
+            &format!("/api/v1/trees/{tree_id}/export/{job_id}/events"),
+        )
+        .await;
+        let (name, done) = events.last().unwrap();
+        assert_eq!(name, "done", "{format}: {events:?}");
+        for (_, progress) in events.iter().filter(|(name, _)| name == "progress") {
+            assert!(progress["records_serialized"].as_u64() <= progress["records_total"].as_u64());
+        }
+
+        let request = Request::builder()
+            .uri(format!("/api/v1/trees/{tree_id}/export/{job_id}/download"))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], content_type);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(bytes.starts_with(magic), "{format}");
+        assert_eq!(done["size_bytes"].as_u64(), Some(bytes.len() as u64));
+    }
+
+    // Unknown jobs, and jobs of another tree, are not found.
+    let other_tree = create_tree_via_api(&app).await;
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/export/jobs"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let job_id = body["job_id"].as_str().unwrap();
+    for uri in [
+        format!("/api/v1/trees/{other_tree}/export/{job_id}/download"),
+        format!(
+            "/api/v1/trees/{tree_id}/export/{}/download",
+            uuid::Uuid::now_v7()
+        ),
+        format!("/api/v1/trees/{}/export/estimate", uuid::Uuid::now_v7()),
+    ] {
+        let (status, _) = send_request(app.clone(), Method::GET, &uri, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{uri}");
+    }
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{}/export/jobs", uuid::Uuid::now_v7()),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_gedcom_export_subset() {
+    let app = setup_app().await;
+    let (_, body) = send_request(
+        app.clone(),
+        Method::POST,
+        "/api/v1/trees?template=self",
+        Some(serde_json::json!({ "name": "Branch" })),
+    )
+    .await;
+    let tree_id = body["id"].as_str().unwrap().to_string();
+    let home_id = body["sosa_root_person_id"].as_str().unwrap().to_string();
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{home_id}/ahnentafel"),
+        None,
+    )
+    .await;
+    let father_id = body[1]["person_id"].as_str().unwrap().to_string();
+
+    let export = |query: String| {
+        let app = app.clone();
+        let uri = format!("/api/v1/trees/{tree_id}/gedcom/export?{query}");
+        async move {
+            let (status, body) = send_request(app, Method::GET, &uri, None).await;
+            assert_eq!(status, StatusCode::OK);
+            body
+        }
+    };
+    let individuals = |body: &Value| body["gedcom"].as_str().unwrap().matches(" INDI").count();
+
+    // The father and his parents; his wife and son are left out.
+    let body = export(format!("person_id={father_id}&generations=1")).await;
+    assert_eq!(individuals(&body), 3);
+    let warnings: Vec<&str> = body["warnings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w.as_str().unwrap())
+        .collect();
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings.iter().any(|w| w.contains(&home_id)));
+    assert!(warnings.iter().all(|w| w.contains("outside the export")));
+
+    let body = export(format!(
+        "person_id={father_id}&generations=1&include_descendants=true"
+    ))
+    .await;
+    assert_eq!(individuals(&body), 4);
+    assert_eq!(body["warnings"].as_array().unwrap().len(), 1);
+
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!(
+            "/api/v1/trees/{tree_id}/gedcom/export?person_id=00000000-0000-0000-0000-000000000000"
+        ),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_export_selection() {
+    let app = setup_app().await;
+    let (_, body) = send_request(
+        app.clone(),
+        Method::POST,
+        "/api/v1/trees?template=self",
+        Some(serde_json::json!({ "name": "Selection" })),
+    )
+    .await;
+    let tree_id = body["id"].as_str().unwrap().to_string();
+    let home_id = body["sosa_root_person_id"].as_str().unwrap().to_string();
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/{home_id}/ahnentafel"),
+        None,
+    )
+    .await;
+    let father_id = body[1]["person_id"].as_str().unwrap().to_string();
+
+    let export = |request: Value| {
+        let app = app.clone();
+        let uri = format!("/api/v1/trees/{tree_id}/export");
+        async move { send_request(app, Method::POST, &uri, Some(request)).await }
+    };
+    let individuals = |body: &Value| body["gedcom"].as_str().unwrap().matches(" INDI").count();
+
+    let (status, body) = export(serde_json::json!({ "person_ids": [home_id] })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(individuals(&body), 1);
+
+    // The father's wife comes along as his spouse.
+    let (_, body) = export(serde_json::json!({
+        "person_ids": [father_id],
+        "include_spouses": true,
+    }))
+    .await;
+    assert_eq!(individuals(&body), 2);
+
+    let (_, body) = export(serde_json::json!({
+        "person_ids": [home_id],
+        "ancestor_generations": 1,
+        "format": "json",
+    }))
+    .await;
+    assert_eq!(body["records"]["persons"].as_array().unwrap().len(), 3);
+    assert!(body["warnings"].as_array().unwrap().is_empty());
+
+    let (_, body) = export(serde_json::json!({
+        "person_ids": [father_id],
+        "include_descendants": true,
+    }))
+    .await;
+    assert_eq!(individuals(&body), 2);
+
+    let (status, _) = export(serde_json::json!({ "person_ids": [] })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = export(serde_json::json!({
+        "person_ids": ["00000000-0000-0000-0000-000000000000"],
+    }))
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_events_by_year_range() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let gedcom = concat!(
+        "0 HEAD\n",
+        "1 CHAR UTF-8\n",
+        "0 @I1@ INDI\n",
+        "1 NAME Paul /Martin/\n",
+        "1 SEX M\n",
+        "1 BIRT\n",
+        "2 DATE 3 MAY 1851\n",
+        "1 DEAT\n",
+        "2 DATE 1888\n",
+        "1 FAMS @F1@\n",
+        "0 @I2@ INDI\n",
+        "1 NAME Anne /Durand/\n",
+        "1 SEX F\n",
+        "1 BIRT\n",
+        "2 DATE ABT 1892\n",
+        "1 FAMS @F1@\n",
+        "0 @F1@ FAM\n",
+        "1 HUSB @I1@\n",
+        "1 WIFE @I2@\n",
+        "1 MARR\n",
+        "2 DATE 12 JUN 1880\n",
+        "0 TRLR\n",
+    );
+    import_gedcom_via_api(&app, &tree_id, gedcom).await;
+    let (_, persons) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons?surname=Durand"),
+        None,
+    )
+    .await;
+    let anne = persons["edges"][0]["node"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // An event recorded with a date phrase only gets its sort date from it.
+    let (status, created) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/events"),
+        Some(serde_json::json!({
+            "event_type": "census",
+            "date_value": "MAR 1886",
+            "person_id": anne
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["date_sort"], "1886-03-01");
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/events?year_from=1880&year_to=1889"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let events = body.as_array().unwrap();
+    let summary: Vec<(&str, &str)> = events
+        .iter()
+        .map(|e| {
+            (
+                e["event_type"].as_str().unwrap(),
+                e["date_sort"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("marriage", "1880-06-12"),
+            ("census", "1886-03-01"),
+            ("death", "1888-01-01"),
+        ]
+    );
+    assert_eq!(events[0]["owner_name"], "Paul Martin & Anne Durand");
+    assert_eq!(events[2]["owner_name"], "Paul Martin");
+
+    // One open bound, and a type filter.
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/events?year_from=1885&event_type=birth"),
+        None,
+    )
+    .await;
+    let events = body.as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["owner_name"], "Anne Durand");
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/events?year_from=1890&year_to=1880"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["type"], "urn:oxidgene:problem:validation_error");
+
+    // The tree's date range spans its dated events.
+    let (_, tree) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(tree["first_year"], 1851);
+    assert_eq!(tree["last_year"], 1892);
+}
+
+// ───────────────────────── DOT export tests ─────────────────────────
+
+/// Helper: GET a non-JSON endpoint and return (status, content type, body).
+async fn get_text(app: &axum::Router, uri: &str) -> (StatusCode, String, String) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .map(|v| v.to_str().unwrap().to_string())
+        .unwrap_or_default();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (
+        status,
+        content_type,
+        String::from_utf8(bytes.to_vec()).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_export_dot() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let gedcom = concat!(
         "0 HEAD\n",
-        "1 SOUR OxidGene\n",
-        "1 GEDC\n",
-        "2 VERS 5.5.1\n",
-        "2 FORM LINEAGE-LINKED\n",
         "1 CHAR UTF-8\n",
         "0 @I1@ INDI\n",
-        "1 NAME John /Doe/\n",
+        "1 NAME Paul /Martin/\n",
         "1 SEX M\n",
         "1 BIRT\n",
-        "2 DATE 1 JAN 1980\n",
-        "2 PLAC Springfield\n",
+        "2 DATE 1900\n",
+        "1 DEAT\n",
+        "2 DATE 1970\n",
         "0 @I2@ INDI\n",
-        "1 NAME Jane /Smith/\n",
+        "1 NAME Marie /Durand/\n",
         "1 SEX F\n",
+        "0 @I3@ INDI\n",
+        "1 NAME Louis /Martin/\n",
+        "1 SEX M\n",
+        "1 FAMC @F1@\n",
+        "1 FAMS @F2@\n",
+        "0 @I4@ INDI\n",
+        "1 NAME Claire /Martin/\n",
+        "1 SEX F\n",
+        "1 FAMC @F2@\n",
+        "0 @F1@ FAM\n",
+        "1 HUSB @I1@\n",
+        "1 WIFE @I2@\n",
+        "1 CHIL @I3@\n",
+        "0 @F2@ FAM\n",
+        "1 HUSB @I3@\n",
+        "1 CHIL @I4@\n",
+        "0 TRLR\n",
+    );
+    import_gedcom_via_api(&app, &tree_id, gedcom).await;
+
+    let (status, content_type, dot) =
+        get_text(&app, &format!("/api/v1/trees/{tree_id}/export.dot")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("text/vnd.graphviz"));
+    assert!(dot.starts_with("digraph "));
+    assert!(dot.contains("Paul Martin\\n1900\u{2013}1970"));
+    assert!(dot.contains("shape=ellipse"));
+    assert_eq!(dot.matches("shape=point").count(), 2);
+    assert_eq!(dot.matches("arrowhead=none").count(), 3);
+
+    // Scoped to Louis: zero generations keeps only him, one adds his
+    // parents and daughter.
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/search?q=louis"),
+        None,
+    )
+    .await;
+    let louis = body["entries"][0]["person_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let (status, _, dot) = get_text(
+        &app,
+        &format!("/api/v1/trees/{tree_id}/export.dot?root={louis}&generations=0"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(dot.contains("Louis Martin"));
+    assert!(!dot.contains("Paul Martin"));
+    assert!(!dot.contains("Claire Martin"));
+    assert!(!dot.contains("shape=point"));
+
+    let (status, _, dot) = get_text(
+        &app,
+        &format!("/api/v1/trees/{tree_id}/export.dot?root={louis}&generations=1"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(dot.contains("Paul Martin"));
+    assert!(dot.contains("Claire Martin"));
+
+    let fake_id = uuid::Uuid::now_v7();
+    let (status, _, _) = get_text(
+        &app,
+        &format!("/api/v1/trees/{tree_id}/export.dot?root={fake_id}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_export_csv() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let gedcom = concat!(
+        "0 HEAD\n",
+        "1 CHAR UTF-8\n",
+        "0 @I1@ INDI\n",
+        "1 NAME Paul /Martin/\n",
+        "1 SEX M\n",
+        "1 BIRT\n",
+        "2 DATE 1900\n",
+        "2 PLAC Lyon\n",
+        "0 @I2@ INDI\n",
+        "1 NAME Marie /Durand, dite \"Mimi\"/\n",
+        "1 SEX F\n",
+        "0 @I3@ INDI\n",
+        "1 NAME Louis /Martin/\n",
+        "1 SEX M\n",
+        "1 FAMC @F1@\n",
         "0 @F1@ FAM\n",
         "1 HUSB @I1@\n",
         "1 WIFE @I2@\n",
+        "1 CHIL @I3@\n",
         "1 MARR\n",
-        "2 DATE 15 JUN 2005\n",
+        "2 DATE 1925\n",
+        "2 PLAC Paris\n",
+        "0 TRLR\n",
+    );
+    import_gedcom_via_api(&app, &tree_id, gedcom).await;
+
+    let (status, content_type, csv) =
+        get_text(&app, &format!("/api/v1/trees/{tree_id}/export.csv")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(content_type.starts_with("text/csv"));
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "\u{feff}person_id,name,given_names,surname,sex,birth_year,birth_place,\
+         death_year,death_place,father_id,father,mother_id,mother"
+    );
+    assert_eq!(lines.len(), 4);
+    let louis = lines.iter().find(|l| l.contains(",Louis Martin,")).unwrap();
+    assert!(louis.contains(",Paul Martin,"));
+    assert!(louis.contains(r#","Marie Durand, dite ""Mimi""""#));
+    let paul = lines.iter().find(|l| l.contains(",Paul Martin,")).unwrap();
+    assert!(paul.contains(",male,1900,Lyon,"));
+
+    let (status, _, csv) = get_text(
+        &app,
+        &format!("/api/v1/trees/{tree_id}/export.csv?entity=families"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[1].contains(",Paul Martin,"));
+    assert!(lines[1].ends_with(",1925,Paris,1"));
+
+    let (status, _, csv) = get_text(
+        &app,
+        &format!("/api/v1/trees/{tree_id}/export.csv?entity=events"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines.iter().any(|l| l.contains(",birth,1900,1900,Lyon,")));
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.contains(",marriage,1925,1925,Paris,"))
+    );
+
+    let (status, _, _) = get_text(
+        &app,
+        &format!("/api/v1/trees/{tree_id}/export.csv?entity=sources"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_fan_chart_svg() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let gedcom = concat!(
+        "0 HEAD\n",
+        "1 CHAR UTF-8\n",
+        "0 @I1@ INDI\n",
+        "1 NAME Paul /Martin/\n",
+        "1 SEX M\n",
+        "1 BIRT\n",
+        "2 DATE 1900\n",
+        "1 FAMS @F1@\n",
+        "0 @I2@ INDI\n",
+        "1 NAME Ana /D&B/\n",
+        "1 SEX F\n",
+        "1 FAMS @F1@\n",
+        "0 @I3@ INDI\n",
+        "1 NAME Louis /Martin/\n",
+        "1 SEX M\n",
+        "1 FAMC @F1@\n",
+        "1 FAMS @F2@\n",
+        "0 @I4@ INDI\n",
+        "1 NAME Claire /Martin/\n",
+        "1 SEX F\n",
+        "1 FAMC @F2@\n",
+        "0 @F1@ FAM\n",
+        "1 HUSB @I1@\n",
+        "1 WIFE @I2@\n",
+        "1 CHIL @I3@\n",
+        "0 @F2@ FAM\n",
+        "1 HUSB @I3@\n",
+        "1 CHIL @I4@\n",
+        "0 TRLR\n",
+    );
+    import_gedcom_via_api(&app, &tree_id, gedcom).await;
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/persons/search?q=claire"),
+        None,
+    )
+    .await;
+    let claire = body["entries"][0]["person_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Two rings: father known, mother unknown; paternal grandparents known,
+    // maternal ones unknown.
+    let (status, content_type, svg) = get_text(
+        &app,
+        &format!("/api/v1/trees/{tree_id}/persons/{claire}/fan.svg?generations=2"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "image/svg+xml");
+    assert!(svg.starts_with("<svg "));
+    assert_eq!(svg.matches("class=\"wedge\"").count(), 3);
+    assert_eq!(svg.matches("class=\"wedge empty\"").count(), 3);
+    assert!(svg.contains("Claire Martin"));
+    assert!(svg.contains("Paul Martin"));
+    assert!(svg.contains("1900\u{2013}"));
+    assert!(svg.contains("Ana D&amp;B"));
+    assert!(svg.contains("#cfe2ff"));
+
+    let (status, _, svg) = get_text(
+        &app,
+        &format!("/api/v1/trees/{tree_id}/persons/{claire}/fan.svg?generations=1&palette=gray"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(svg.matches("class=\"wedge").count(), 3);
+    assert!(!svg.contains("#cfe2ff"));
+    assert!(!svg.contains("Paul Martin"));
+
+    let fake_id = uuid::Uuid::now_v7();
+    let (status, _, _) = get_text(
+        &app,
+        &format!("/api/v1/trees/{tree_id}/persons/{fake_id}/fan.svg"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// ───────────────────────── Optimistic concurrency ─────────────────────────
+
+/// Helper: PUT a JSON body with an optional `If-Match`, returning
+/// (status, ETag header, body).
+async fn put_if_match(
+    app: &axum::Router,
+    uri: &str,
+    if_match: Option<&str>,
+    body: Value,
+) -> (StatusCode, Option<String>, Value) {
+    let mut builder = Request::builder()
+        .method(Method::PUT)
+        .uri(uri)
+        .header("content-type", "application/json");
+    if let Some(tag) = if_match {
+        builder = builder.header("if-match", tag);
+    }
+    let request = builder
+        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let etag = response
+        .headers()
+        .get("etag")
+        .map(|v| v.to_str().unwrap().to_string());
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (
+        status,
+        etag,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[tokio::test]
+async fn test_update_with_stale_if_match_conflicts() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    let uri = format!("/api/v1/trees/{tree_id}/persons/{person_id}");
+
+    let request = Request::builder().uri(&uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.headers().get("etag").unwrap(), "\"1\"");
+
+    // First writer wins and bumps the version.
+    let (status, etag, body) = put_if_match(
+        &app,
+        &uri,
+        Some("\"1\""),
+        serde_json::json!({ "sex": "female" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"2\""));
+    assert_eq!(body["version"], 2);
+
+    // Second writer still holds version 1.
+    let (status, _, body) = put_if_match(
+        &app,
+        &uri,
+        Some("\"1\""),
+        serde_json::json!({ "sex": "male" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["type"], "urn:oxidgene:problem:conflict");
+
+    // Weak tags and unconditional updates are accepted.
+    let (status, etag, _) = put_if_match(
+        &app,
+        &uri,
+        Some("W/\"2\""),
+        serde_json::json!({ "sex": "male" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"3\""));
+    let (status, _, body) =
+        put_if_match(&app, &uri, None, serde_json::json!({ "sex": "unknown" })).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["version"], 4);
+
+    // A malformed tag is a client error.
+    let (status, _, _) = put_if_match(
+        &app,
+        &uri,
+        Some("nope"),
+        serde_json::json!({ "sex": "male" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+// ───────────────────────── Share links ─────────────────────────
+
+#[tokio::test]
+async fn test_share_link_read_only_and_redacted() {
+    let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let gedcom = concat!(
+        "0 HEAD\n",
+        "1 CHAR UTF-8\n",
+        "0 @I1@ INDI\n",
+        "1 NAME Paul /Martin/\n",
+        "1 BIRT\n",
+        "2 DATE 1900\n",
+        "1 DEAT\n",
+        "2 DATE 1970\n",
+        "0 @I2@ INDI\n",
+        "1 NAME Marie /Durand/\n",
+        "0 @I3@ INDI\n",
+        "1 NAME Louis /Martin/\n",
+        "1 BIRT\n",
+        "2 DATE 1995\n",
+        "1 FAMC @F1@\n",
+        "0 @F1@ FAM\n",
+        "1 HUSB @I1@\n",
+        "1 WIFE @I2@\n",
+        "1 CHIL @I3@\n",
+        "1 MARR\n",
+        "2 DATE 1925\n",
+        "2 PLAC Paris\n",
         "0 TRLR\n",
+    );
+    import_gedcom_via_api(&app, &tree_id, gedcom).await;
+
+    // Expiry in the past is rejected.
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/share"),
+        Some(serde_json::json!({ "expires_at": "2000-01-01T00:00:00Z" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, link) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/share"),
+        Some(serde_json::json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(link["privatize"], true);
+    // Without authentication, links are created by the local actor.
+    assert_eq!(link["created_by"], "local");
+    let token = link["token"].as_str().unwrap().to_string();
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/shared/{token}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["name"], "Test Tree");
+
+    // Marie (undated) and Louis (born 1995) are presumed living: their given
+    // names, own events and family events are gone; Paul is untouched.
+    let (status, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/shared/{token}/snapshot"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["persons"].as_array().unwrap().len(), 3);
+    let mut given: Vec<Value> = body["names"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n["given_names"].clone())
+        .collect();
+    given.sort_by_key(|v| v.to_string());
+    assert_eq!(given, vec!["Paul".into(), Value::Null, Value::Null]);
+    // Their given names appear nowhere in the payload; display names keep
+    // the surname only.
+    let payload = body.to_string();
+    for given_name in ["Marie", "Louis"] {
+        assert!(!payload.contains(given_name), "{given_name} in {payload}");
+    }
+    let mut display_names: Vec<&str> = body["persons"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["display_name"].as_str().unwrap())
+        .collect();
+    display_names.sort_unstable();
+    assert_eq!(display_names, vec!["Durand", "Martin", "Paul Martin"]);
+    let event_types: Vec<&str> = body["events"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["event_type"].as_str().unwrap())
+        .collect();
+    assert_eq!(event_types.len(), 2);
+    assert!(event_types.contains(&"birth") && event_types.contains(&"death"));
+    assert!(body["places"].as_array().unwrap().is_empty());
+    assert_eq!(body["children"].as_array().unwrap().len(), 1);
+
+    // Read-only.
+    let (status, _) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/shared/{token}/snapshot"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+
+    // A non-privatized link shows everyone.
+    let (_, open_link) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/share"),
+        Some(serde_json::json!({ "privatize": false })),
+    )
+    .await;
+    let open_token = open_link["token"].as_str().unwrap();
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/shared/{open_token}/snapshot"),
+        None,
+    )
+    .await;
+    assert_eq!(body["events"].as_array().unwrap().len(), 4);
+    assert_eq!(body["places"].as_array().unwrap().len(), 1);
+
+    // Revoking makes the token unknown.
+    let share_id = link["id"].as_str().unwrap();
+    let (status, _) = send_request(
+        app.clone(),
+        Method::DELETE,
+        &format!("/api/v1/trees/{tree_id}/share/{share_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/shared/{token}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, body) = send_request(
+        app.clone(),
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/share"),
+        None,
     )
+    .await;
+    assert_eq!(body.as_array().unwrap().len(), 1);
 }
 
 #[tokio::test]
-async fn test_gedcom_import() {
+async fn test_share_link_honors_living_override() {
     let app = setup_app().await;
-
-    // Create tree
-    let (_, tree_body) = send_request(
+    let tree_id = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    send_request(
         app.clone(),
         Method::POST,
-        "/api/v1/trees",
-        Some(serde_json::json!({ "name": "GEDCOM Tree" })),
+        &format!("/api/v1/trees/{tree_id}/persons/{person_id}/names"),
+        Some(serde_json::json!({ "name_type": "birth", "given_names": "Marie", "surname": "Durand", "is_primary": true })),
     )
     .await;
-    let tree_id = tree_body["id"].as_str().unwrap();
+    let (_, link) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/share"),
+        Some(serde_json::json!({})),
+    )
+    .await;
+    let snapshot_uri = format!(
+        "/api/v1/shared/{}/snapshot",
+        link["token"].as_str().unwrap()
+    );
+    let person_uri = format!("/api/v1/trees/{tree_id}/persons/{person_id}");
+    let given_names = |body: &Value| body["names"][0]["given_names"].clone();
 
-    // Import GEDCOM
+    // Undated, so presumed living by default.
+    let (_, body) = send_request(app.clone(), Method::GET, &snapshot_uri, None).await;
+    assert_eq!(given_names(&body), Value::Null);
+
+    // Marked deceased, she is shown.
     let (status, body) = send_request(
         app.clone(),
+        Method::PUT,
+        &person_uri,
+        Some(serde_json::json!({ "living": false })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["living"], false);
+    let (_, body) = send_request(app.clone(), Method::GET, &snapshot_uri, None).await;
+    assert_eq!(given_names(&body), "Marie");
+
+    // Leaving `living` out keeps the override; `null` clears it.
+    let (_, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &person_uri,
+        Some(serde_json::json!({ "sex": "female" })),
+    )
+    .await;
+    assert_eq!(body["living"], false);
+    let (_, body) = send_request(
+        app.clone(),
+        Method::PUT,
+        &person_uri,
+        Some(serde_json::json!({ "living": null })),
+    )
+    .await;
+    assert_eq!(body["living"], Value::Null);
+    let (_, body) = send_request(app.clone(), Method::GET, &snapshot_uri, None).await;
+    assert_eq!(given_names(&body), Value::Null);
+}
+
+// ───────────────────────── Authentication ─────────────────────────
+
+/// Helper: an HS256 token for `sub`, valid for an hour.
+fn bearer_token(secret: &str, sub: &str) -> String {
+    let claims = serde_json::json!({
+        "sub": sub,
+        "exp": chrono::Utc::now().timestamp() + 3600,
+    });
+    jsonwebtoken::encode(
+        &jsonwebtoken::Header::default(),
+        &claims,
+        &jsonwebtoken::EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .unwrap()
+}
+
+/// Helper: send a request with an optional `Authorization` header.
+async fn send_authorized(
+    app: &axum::Router,
+    method: Method,
+    uri: &str,
+    authorization: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Option<String>, Value) {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json");
+    if let Some(authorization) = authorization {
+        request = request.header("authorization", authorization);
+    }
+    let body = body.map_or_else(Body::empty, |json| Body::from(json.to_string()));
+    let response = app
+        .clone()
+        .oneshot(request.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let challenge = response
+        .headers()
+        .get("www-authenticate")
+        .map(|v| v.to_str().unwrap().to_string());
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, challenge, json)
+}
+
+#[tokio::test]
+async fn test_bearer_token_authentication() {
+    let secret = "test-secret";
+    let config = ApiConfig {
+        auth: AuthConfig::Jwt(JwtConfig {
+            secret: Some(secret.to_string()),
+            ..JwtConfig::default()
+        }),
+        ..ApiConfig::default()
+    };
+    let app = build_router(AppState::new(setup_db().await).with_config(config));
+
+    // No token, a malformed header, a token signed with another secret:
+    // all refused with a bearer challenge.
+    let wrong = format!("Bearer {}", bearer_token("other-secret", "alice"));
+    for authorization in [None, Some("Basic YWxpY2U6cHc="), Some(wrong.as_str())] {
+        let (status, challenge, body) =
+            send_authorized(&app, Method::GET, "/api/v1/trees", authorization, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "{authorization:?}");
+        assert_eq!(challenge.as_deref(), Some("Bearer"));
+        assert_eq!(body["type"], "urn:oxidgene:problem:unauthorized");
+    }
+
+    // A valid token acts as its subject.
+    let alice = format!("Bearer {}", bearer_token(secret, "alice"));
+    let (status, _, tree) = send_authorized(
+        &app,
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/gedcom/import"),
-        Some(serde_json::json!({ "gedcom": minimal_gedcom() })),
+        "/api/v1/trees",
+        Some(&alice),
+        Some(serde_json::json!({ "name": "Private" })),
     )
     .await;
     assert_eq!(status, StatusCode::CREATED);
-    assert_eq!(body["persons_count"], 2);
-    assert_eq!(body["families_count"], 1);
-    assert!(body["events_count"].as_i64().unwrap() >= 2); // BIRT + MARR
-    assert!(body["places_count"].as_i64().unwrap() >= 1); // Springfield
+    let tree_id = tree["id"].as_str().unwrap();
+    let (status, _, link) = send_authorized(
+        &app,
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/share"),
+        Some(&alice),
+        Some(serde_json::json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(link["created_by"], "alice");
 
-    // Verify persons are actually in the DB
-    let (status, persons) = send_request(
-        app.clone(),
+    // Share views stay anonymous.
+    let token = link["token"].as_str().unwrap();
+    let (status, _, body) = send_authorized(
+        &app,
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/persons"),
+        &format!("/api/v1/shared/{token}"),
+        None,
         None,
     )
     .await;
     assert_eq!(status, StatusCode::OK);
-    let edges = persons["edges"].as_array().unwrap();
-    assert_eq!(edges.len(), 2);
+    assert_eq!(body["name"], "Private");
 }
 
 #[tokio::test]
-async fn test_gedcom_import_invalid_tree() {
-    let app = setup_app().await;
-    let fake_id = "00000000-0000-0000-0000-000000000000";
+async fn test_tree_roles() {
+    let secret = "test-secret";
+    let config = ApiConfig {
+        auth: AuthConfig::Jwt(JwtConfig {
+            secret: Some(secret.to_string()),
+            ..JwtConfig::default()
+        }),
+        ..ApiConfig::default()
+    };
+    let app = build_router(AppState::new(setup_db().await).with_config(config));
+    let alice = format!("Bearer {}", bearer_token(secret, "alice"));
+    let bob = format!("Bearer {}", bearer_token(secret, "bob"));
+    let (alice, bob) = (Some(alice.as_str()), Some(bob.as_str()));
+
+    // The creator owns the tree; others don't see it at all.
+    let (status, _, tree) = send_authorized(
+        &app,
+        Method::POST,
+        "/api/v1/trees",
+        alice,
+        Some(serde_json::json!({ "name": "Family" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let tree_id = tree["id"].as_str().unwrap().to_string();
+    let tree_uri = format!("/api/v1/trees/{tree_id}");
+    let persons_uri = format!("{tree_uri}/persons");
+    let person = serde_json::json!({ "sex": "male" });
+    let (_, _, created) = send_authorized(
+        &app,
+        Method::POST,
+        &persons_uri,
+        alice,
+        Some(person.clone()),
+    )
+    .await;
+    let person_id = created["id"].as_str().unwrap().to_string();
+    let (_, _, members) = send_authorized(
+        &app,
+        Method::GET,
+        &format!("{tree_uri}/members"),
+        alice,
+        None,
+    )
+    .await;
+    assert_eq!(members[0]["actor_id"], "alice");
+    assert_eq!(members[0]["role"], "owner");
+    let (status, _, _) = send_authorized(&app, Method::GET, &tree_uri, bob, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, _, trees) = send_authorized(&app, Method::GET, "/api/v1/trees", bob, None).await;
+    assert_eq!(trees["total_count"], 0);
 
-    let (status, _) = send_request(
-        app.clone(),
+    // Only an owner manages members.
+    let bob_uri = format!("{tree_uri}/members/bob");
+    let (status, _, _) = send_authorized(
+        &app,
+        Method::PUT,
+        &bob_uri,
+        alice,
+        Some(serde_json::json!({ "role": "viewer" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _, _) = send_authorized(
+        &app,
+        Method::PUT,
+        &bob_uri,
+        bob,
+        Some(serde_json::json!({ "role": "owner" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // A viewer reads and exports, but cannot write.
+    let (status, _, _) = send_authorized(&app, Method::GET, &tree_uri, bob, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _, body) =
+        send_authorized(&app, Method::POST, &persons_uri, bob, Some(person.clone())).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["type"], "urn:oxidgene:problem:forbidden");
+    let (status, _, _) = send_authorized(
+        &app,
         Method::POST,
-        &format!("/api/v1/trees/{fake_id}/gedcom/import"),
-        Some(serde_json::json!({ "gedcom": minimal_gedcom() })),
+        &format!("{tree_uri}/export"),
+        bob,
+        Some(serde_json::json!({ "person_ids": [person_id], "format": "json" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // An editor writes, but can neither share nor delete the tree.
+    send_authorized(
+        &app,
+        Method::PUT,
+        &bob_uri,
+        alice,
+        Some(serde_json::json!({ "role": "editor" })),
+    )
+    .await;
+    let (status, _, _) = send_authorized(&app, Method::POST, &persons_uri, bob, Some(person)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _, _) = send_authorized(
+        &app,
+        Method::POST,
+        &format!("{tree_uri}/share"),
+        bob,
+        Some(serde_json::json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _, _) = send_authorized(&app, Method::DELETE, &tree_uri, bob, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // The last owner stays; the owner deletes the tree.
+    let (status, _, _) = send_authorized(
+        &app,
+        Method::DELETE,
+        &format!("{tree_uri}/members/alice"),
+        alice,
+        None,
     )
     .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _, _) = send_authorized(&app, Method::DELETE, &tree_uri, alice, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+async fn test_admins() {
+    let secret = "test-secret";
+    let db = setup_db().await;
+    // A tree created before authentication was turned on has no members.
+    let open_app = build_router(AppState::new(db.clone()));
+    let tree_id = create_tree_via_api(&open_app).await;
+    let tree_uri = format!("/api/v1/trees/{tree_id}");
+    let config = ApiConfig {
+        auth: AuthConfig::Jwt(JwtConfig {
+            secret: Some(secret.to_string()),
+            admins: vec!["root".to_string()],
+            ..JwtConfig::default()
+        }),
+        ..ApiConfig::default()
+    };
+    let app = build_router(AppState::new(db).with_config(config));
+    let root = format!("Bearer {}", bearer_token(secret, "root"));
+    let alice = format!("Bearer {}", bearer_token(secret, "alice"));
+    let (root, alice) = (Some(root.as_str()), Some(alice.as_str()));
+
+    // Nobody but an admin reaches a tree without members, nor can claim it.
+    let (status, _, _) = send_authorized(&app, Method::GET, &tree_uri, alice, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, _, trees) = send_authorized(&app, Method::GET, "/api/v1/trees", alice, None).await;
+    assert_eq!(trees["total_count"], 0);
+    let alice_uri = format!("{tree_uri}/members/alice");
+    let owner = serde_json::json!({ "role": "owner" });
+    let (status, _, _) =
+        send_authorized(&app, Method::PUT, &alice_uri, alice, Some(owner.clone())).await;
     assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // An admin sees every tree and gives it an owner.
+    let (_, _, trees) = send_authorized(&app, Method::GET, "/api/v1/trees", root, None).await;
+    assert_eq!(trees["total_count"], 1);
+    let (status, _, _) = send_authorized(&app, Method::PUT, &alice_uri, root, Some(owner)).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _, _) = send_authorized(&app, Method::GET, &tree_uri, alice, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Admin routes are for admins only.
+    for uri in ["/api/v1/admin/backfill-phonetic", "/api/v1/backup"] {
+        let (status, _, body) = send_authorized(&app, Method::POST, uri, alice, None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{uri}");
+        assert_eq!(body["type"], "urn:oxidgene:problem:forbidden");
+    }
+    let (status, _, _) = send_authorized(
+        &app,
+        Method::POST,
+        "/api/v1/admin/backfill-phonetic",
+        root,
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
 }
 
 #[tokio::test]
-async fn test_gedcom_export_empty_tree() {
+async fn test_entities_of_another_tree_are_not_found() {
     let app = setup_app().await;
+    let tree_id = create_tree_via_api(&app).await;
+    let other_tree = create_tree_via_api(&app).await;
+    let person_id = create_person_via_api(&app, &tree_id).await;
+    let (_, event) = send_request(
+        app.clone(),
+        Method::POST,
+        &format!("/api/v1/trees/{tree_id}/events"),
+        Some(serde_json::json!({ "event_type": "birth", "person_id": person_id })),
+    )
+    .await;
+    let event_id = event["id"].as_str().unwrap();
+
+    // The tree's entities, named under another tree's path.
+    let person_uri = format!("/api/v1/trees/{other_tree}/persons/{person_id}");
+    for (method, uri, body) in [
+        (Method::GET, person_uri.clone(), None),
+        (
+            Method::PUT,
+            person_uri.clone(),
+            Some(serde_json::json!({ "sex": "female" })),
+        ),
+        (Method::DELETE, person_uri, None),
+        (
+            Method::DELETE,
+            format!("/api/v1/trees/{other_tree}/events/{event_id}"),
+            None,
+        ),
+    ] {
+        let (status, body) = send_request(app.clone(), method.clone(), &uri, body).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{method} {uri}");
+        assert_eq!(body["type"], "urn:oxidgene:problem:not_found");
+    }
 
-    // Create tree
-    let (_, tree_body) = send_request(
+    // Nor can a request body link them into another tree.
+    let (status, _) = send_request(
         app.clone(),
         Method::POST,
-        "/api/v1/trees",
-        Some(serde_json::json!({ "name": "Empty Tree" })),
+        &format!("/api/v1/trees/{other_tree}/events"),
+        Some(serde_json::json!({ "event_type": "death", "person_id": person_id })),
     )
     .await;
-    let tree_id = tree_body["id"].as_str().unwrap();
+    assert_eq!(status, StatusCode::NOT_FOUND);
 
-    // Export (empty tree)
-    let (status, body) = send_request(
+    // Untouched in their own tree.
+    let (status, person) = send_request(
         app.clone(),
         Method::GET,
-        &format!("/api/v1/trees/{tree_id}/gedcom/export"),
+        &format!("/api/v1/trees/{tree_id}/persons/{person_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(person["sex"], "male");
+    let (status, _) = send_request(
+        app,
+        Method::GET,
+        &format!("/api/v1/trees/{tree_id}/events/{event_id}"),
         None,
     )
     .await;
     assert_eq!(status, StatusCode::OK);
-    assert!(body["gedcom"].as_str().unwrap().contains("HEAD"));
-    assert!(body["warnings"].as_array().unwrap().is_empty());
 }
 
+// ───────────────────────── Backups ─────────────────────────
+
 #[tokio::test]
-async fn test_gedcom_roundtrip() {
+async fn test_backups_disabled_by_default() {
     let app = setup_app().await;
+    let (status, body) = send_request(app, Method::POST, "/api/v1/backup", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["type"], "urn:oxidgene:problem:validation_error");
+}
 
-    // Create tree
-    let (_, tree_body) = send_request(
-        app.clone(),
-        Method::POST,
-        "/api/v1/trees",
-        Some(serde_json::json!({ "name": "Roundtrip Tree" })),
-    )
-    .await;
-    let tree_id = tree_body["id"].as_str().unwrap();
+#[tokio::test]
+async fn test_backup_and_staged_restore() {
+    let dir = std::env::temp_dir().join(format!("oxidgene-backups-{}", uuid::Uuid::now_v7()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let db_path = dir.join("oxidgene.db");
+    // `VACUUM INTO` needs a file database: it is a no-op on shared in-memory ones.
+    let db = connect(&format!("sqlite://{}?mode=rwc", db_path.display()))
+        .await
+        .unwrap();
+    run_migrations(&db).await.unwrap();
+    let backups = Arc::new(Backups::new(dir.join("backups"), &db_path, 1));
+    let app = build_router(AppState::new(db.clone()).with_backups(backups));
+    create_tree_via_api(&app).await;
 
-    // Import
-    let (status, import_body) = send_request(
+    let (status, body) = send_request(app.clone(), Method::POST, "/api/v1/backup", None).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(body["size_bytes"].as_u64().unwrap() > 0);
+    let file_name = body["file_name"].as_str().unwrap().to_string();
+
+    // The backup is a complete database holding the tree.
+    let copy = connect(&format!(
+        "sqlite://{}",
+        dir.join("backups").join(&file_name).display()
+    ))
+    .await
+    .unwrap();
+    let trees = oxidgene_db::repo::TreeRepo::list(&copy, &Default::default())
+        .await
+        .unwrap();
+    assert_eq!(trees.edges.len(), 1);
+    copy.close().await.unwrap();
+
+    let (status, body) = send_request(app.clone(), Method::GET, "/api/v1/backup", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 1);
+    assert_eq!(body[0]["file_name"], file_name.as_str());
+
+    // Changes after the backup are lost by the restore.
+    create_tree_via_api(&app).await;
+
+    // Only listed backups can be restored.
+    let (status, _) = send_request(
         app.clone(),
         Method::POST,
-        &format!("/api/v1/trees/{tree_id}/gedcom/import"),
-        Some(serde_json::json!({ "gedcom": minimal_gedcom() })),
+        "/api/v1/backup/..%2Foxidgene.db/restore",
+        None,
     )
     .await;
-    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(status, StatusCode::BAD_REQUEST);
 
-    // Export
-    let (status, export_body) = send_request(
+    // Let the clock move past the first backup's second: restoring takes a
+    // new backup, which with `keep = 1` prunes the restored one.
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    let (status, body) = send_request(
         app.clone(),
-        Method::GET,
-        &format!("/api/v1/trees/{tree_id}/gedcom/export"),
+        Method::POST,
+        &format!("/api/v1/backup/{file_name}/restore"),
         None,
     )
     .await;
     assert_eq!(status, StatusCode::OK);
-    let exported = export_body["gedcom"].as_str().unwrap();
+    assert_eq!(body["restart_required"], true);
+    assert_eq!(body["restored"]["file_name"], file_name.as_str());
 
-    // Verify the exported GEDCOM contains the imported data
-    assert!(exported.contains("HEAD"));
-    assert!(exported.contains("INDI"));
-    assert!(exported.contains("FAM"));
+    let (_, body) = send_request(app.clone(), Method::GET, "/api/v1/backup", None).await;
+    assert_eq!(body.as_array().unwrap().len(), 1);
+    assert_ne!(body[0]["file_name"], file_name.as_str());
+
+    // On the next launch the staged file replaces the database.
+    db.close().await.unwrap();
+    assert!(apply_pending_restore(&db_path).unwrap());
+    assert!(!dir.join("oxidgene.db-wal").exists());
+    assert!(!apply_pending_restore(&db_path).unwrap());
+    let db = connect(&format!("sqlite://{}", db_path.display()))
+        .await
+        .unwrap();
+    let trees = oxidgene_db::repo::TreeRepo::list(&db, &Default::default())
+        .await
+        .unwrap();
+    assert_eq!(trees.edges.len(), 1);
+    db.close().await.unwrap();
 
-    // Verify counts match what we imported
-    assert_eq!(import_body["persons_count"], 2);
-    assert_eq!(import_body["families_count"], 1);
+    std::fs::remove_dir_all(&dir).unwrap();
 }
 
 #[tokio::test]
-async fn test_gedcom_export_invalid_tree() {
+async fn test_backfill_date_sort() {
     let app = setup_app().await;
-    let fake_id = "00000000-0000-0000-0000-000000000000";
+    let tree_id = create_tree_via_api(&app).await;
 
-    let (status, _) = send_request(
+    let mut ids = Vec::new();
+    for date_value in ["MAR 1886", "sometime"] {
+        let (status, created) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/api/v1/trees/{tree_id}/events"),
+            Some(serde_json::json!({ "event_type": "census", "date_value": date_value })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        ids.push(created["id"].as_str().unwrap().to_string());
+    }
+    // As left by a version that did not derive `date_sort`.
+    let event_uri = format!("/api/v1/trees/{tree_id}/events/{}", ids[0]);
+    let (status, body) = send_request(
         app.clone(),
-        Method::GET,
-        &format!("/api/v1/trees/{fake_id}/gedcom/export"),
+        Method::PUT,
+        &event_uri,
+        Some(serde_json::json!({ "date_sort": null })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["date_sort"].is_null());
+
+    let (status, body) = send_request(
+        app.clone(),
+        Method::POST,
+        "/api/v1/admin/backfill-date-sort",
         None,
     )
     .await;
-    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, serde_json::json!({ "updated": 1, "unparsed": 1 }));
+    let (_, event) = send_request(app.clone(), Method::GET, &event_uri, None).await;
+    assert_eq!(event["date_sort"], "1886-03-01");
+
+    let (_, body) = send_request(
+        app.clone(),
+        Method::POST,
+        "/api/v1/admin/backfill-date-sort",
+        None,
+    )
+    .await;
+    assert_eq!(body, serde_json::json!({ "updated": 0, "unparsed": 1 }));
 }
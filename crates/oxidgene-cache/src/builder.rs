@@ -10,7 +10,7 @@ use oxidgene_core::enums::*;
 use oxidgene_core::types::{
     Event, FamilyChild, FamilySpouse, Media, MediaLink, Note, Person, PersonName, Place,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// Holds all raw data for a tree, used to build cache entries efficiently.
@@ -165,18 +165,21 @@ impl IndexedData {
 
 /// Build a `CachedEvent` from a raw `Event` and the place index.
 fn build_cached_event(event: &Event, places: &HashMap<Uuid, Place>) -> CachedEvent {
-    let place_name = event
-        .place_id
-        .and_then(|pid| places.get(&pid))
-        .map(|p| p.name.clone());
+    let place_name = |place_id: Option<Uuid>| {
+        place_id
+            .and_then(|pid| places.get(&pid))
+            .map(|p| p.name.clone())
+    };
 
     CachedEvent {
         event_id: event.id,
         event_type: event.event_type,
         date_value: event.date_value.clone(),
         date_sort: event.date_sort,
-        place_name,
+        place_name: place_name(event.place_id),
         place_id: event.place_id,
+        place_name_to: place_name(event.place_id_to),
+        place_id_to: event.place_id_to,
         description: event.description.clone(),
     }
 }
@@ -234,6 +237,7 @@ fn build_one_person(
             display_name: name.display_name(),
             given_names: name.given_names.clone(),
             surname: name.surname.clone(),
+            nickname: name.nickname.clone(),
         };
         if name.is_primary {
             primary_name = Some(cached);
@@ -243,7 +247,8 @@ fn build_one_person(
     }
 
     // ── Events ───────────────────────────────────────────────────────────
-    let events = idx.events_by_person.get(&pid).cloned().unwrap_or_default();
+    let mut events = idx.events_by_person.get(&pid).cloned().unwrap_or_default();
+    events.sort_by(Event::chronological_cmp);
     let mut birth: Option<CachedEvent> = None;
     let mut death: Option<CachedEvent> = None;
     let mut baptism: Option<CachedEvent> = None;
@@ -390,10 +395,10 @@ fn build_one_person(
         .unwrap_or_default();
     let media_count = person_media_links.len() as u32;
 
-    // Find the primary media (first by sort_order)
+    // Find the primary media: the profile photo, else the first by sort_order
     let primary_media = person_media_links
         .iter()
-        .min_by_key(|ml| ml.sort_order)
+        .min_by_key(|ml| (!ml.is_profile, ml.sort_order))
         .and_then(|ml| idx.media_by_id.get(&ml.media_id))
         .map(|m| CachedMediaRef {
             media_id: m.id,
@@ -472,9 +477,31 @@ pub fn build_search_entry(person: &CachedPerson) -> SearchEntry {
         birth_place: person.birth.as_ref().and_then(|e| e.place_name.clone()),
         death_year: person.death.as_ref().and_then(extract_year),
         date_sort: person.birth.as_ref().and_then(|e| e.date_sort),
+        matched_name: None,
     }
 }
 
+/// The names a person can also be found by: their other names, then the
+/// nicknames of all their names.
+fn search_aliases(person: &CachedPerson) -> Vec<String> {
+    let mut aliases: Vec<String> = person
+        .other_names
+        .iter()
+        .map(|n| n.display_name.clone())
+        .chain(
+            person
+                .primary_name
+                .iter()
+                .chain(&person.other_names)
+                .filter_map(|n| n.nickname.clone()),
+        )
+        .filter(|a| !a.trim().is_empty())
+        .collect();
+    let mut seen = HashSet::new();
+    aliases.retain(|a| seen.insert(a.clone()));
+    aliases
+}
+
 /// Build a `person_search_fts` row from a `CachedPerson` (Sprint E.6).
 ///
 /// This is the write model for the DB-native search table which replaced the
@@ -487,12 +514,14 @@ pub fn build_db_search_entry(person: &CachedPerson) -> oxidgene_db::repo::Person
         surname: entry.surname_normalized,
         given_names: entry.given_names_normalized,
         maiden_name: entry.maiden_name_normalized,
+        aliases: search_aliases(person),
         birth_year: entry.birth_year,
         death_year: entry.death_year,
         sex: entry.sex.to_string(),
         display_name: entry.display_name,
         birth_place: entry.birth_place,
         date_sort: entry.date_sort.map(|d| d.format("%Y-%m-%d").to_string()),
+        matched_alias: None,
     }
 }
 
@@ -501,6 +530,7 @@ pub fn search_entry_from_db(row: oxidgene_db::repo::PersonSearchEntry) -> Search
     let sex = match row.sex.as_str() {
         "male" => Sex::Male,
         "female" => Sex::Female,
+        "intersex" => Sex::Intersex,
         _ => Sex::Unknown,
     };
     SearchEntry {
@@ -516,6 +546,7 @@ pub fn search_entry_from_db(row: oxidgene_db::repo::PersonSearchEntry) -> Search
         date_sort: row
             .date_sort
             .and_then(|d| chrono::NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()),
+        matched_name: row.matched_alias,
     }
 }
 
@@ -568,6 +599,8 @@ mod tests {
             date_sort: None,
             place_name: None,
             place_id: None,
+            place_name_to: None,
+            place_id_to: None,
             description: None,
         };
         assert_eq!(extract_year(&event), Some("1842".to_string()));
@@ -592,6 +625,7 @@ mod tests {
             birth_place: Some("Berlin".to_string()),
             death_year: None,
             date_sort: chrono::NaiveDate::from_ymd_opt(1850, 6, 1),
+            matched_name: None,
         };
         let tree_id = Uuid::now_v7();
 
@@ -601,12 +635,14 @@ mod tests {
             surname: entry.surname_normalized.clone(),
             given_names: entry.given_names_normalized.clone(),
             maiden_name: entry.maiden_name_normalized.clone(),
+            aliases: vec!["Jeanne Dupont".to_string()],
             birth_year: entry.birth_year.clone(),
             death_year: entry.death_year.clone(),
             sex: entry.sex.to_string(),
             display_name: entry.display_name.clone(),
             birth_place: entry.birth_place.clone(),
             date_sort: entry.date_sort.map(|d| d.format("%Y-%m-%d").to_string()),
+            matched_alias: Some("Jeanne Dupont".to_string()),
         };
 
         let back = search_entry_from_db(db_row);
@@ -616,5 +652,6 @@ mod tests {
         assert_eq!(back.maiden_name_normalized.as_deref(), Some("dupont"));
         assert_eq!(back.date_sort, entry.date_sort);
         assert_eq!(back.display_name, "Jane Smith");
+        assert_eq!(back.matched_name.as_deref(), Some("Jeanne Dupont"));
     }
 }
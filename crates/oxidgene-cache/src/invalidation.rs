@@ -123,6 +123,26 @@ pub async fn affected_persons_for_family_spouse_change(
     Ok(affected)
 }
 
+/// Compute affected persons when a family's children are reordered.
+///
+/// Only the members of the family see the new order: the spouses through
+/// their family links, the children through their sibling lists.
+pub async fn affected_persons_for_family_reorder(
+    db: &DatabaseConnection,
+    family_id: Uuid,
+) -> Result<Vec<Uuid>, OxidGeneError> {
+    let spouses = FamilySpouseRepo::list_by_families(db, &[family_id]).await?;
+    let children = FamilyChildRepo::list_by_families(db, &[family_id]).await?;
+    let mut affected: Vec<Uuid> = spouses
+        .iter()
+        .map(|s| s.person_id)
+        .chain(children.iter().map(|c| c.person_id))
+        .collect();
+    affected.sort();
+    affected.dedup();
+    Ok(affected)
+}
+
 /// Compute affected persons when a family child link changes (child added/removed).
 ///
 /// The child itself, both parents in the family, and the child's other family
@@ -6,6 +6,7 @@
 //! constructing cache entries, and the [`invalidation`] module for computing
 //! affected sets.
 
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
 
@@ -25,8 +26,8 @@ use crate::builder::{
 use crate::invalidation;
 use crate::store::CacheStore;
 use crate::types::{
-    CachedPedigree, CachedPerson, PedigreeDelta, PedigreeDirection, PedigreeEdge, PedigreeNode,
-    SearchResult,
+    CachedPedigree, CachedPerson, FuzzySearchHit, FuzzySearchResult, PedigreeDelta,
+    PedigreeDirection, PedigreeEdge, PedigreeNode, SearchResult, TreeSearchHit, TreeSearchResult,
 };
 
 /// The cache service orchestrates all cache operations.
@@ -332,6 +333,70 @@ impl CacheService {
         })
     }
 
+    /// Search persons across all trees, sorted by name whatever their tree
+    /// (see [`PersonSearchRepo::search_trees`]), populating each tree's
+    /// search rows on demand like [`Self::search`]. With `actor_id`, only
+    /// the trees they may see are searched (see
+    /// [`TreeRepo::list_for_actor`]).
+    #[instrument(skip(self), fields(query = %query))]
+    pub async fn search_all_trees(
+        &self,
+        query: &str,
+        limit: usize,
+        offset: usize,
+        actor_id: Option<&str>,
+    ) -> Result<TreeSearchResult, OxidGeneError> {
+        let trees = match actor_id {
+            Some(actor_id) => TreeRepo::list_all_for_actor(&self.db, actor_id).await?,
+            None => TreeRepo::list_all(&self.db).await?,
+        };
+        for tree in &trees {
+            self.ensure_search_index(tree.id).await?;
+        }
+        let tree_ids: Vec<Uuid> = trees.iter().map(|t| t.id).collect();
+        let page =
+            PersonSearchRepo::search_trees(&self.db, &tree_ids, query, limit as u64, offset as u64)
+                .await?;
+        let tree_names: HashMap<Uuid, String> = trees.into_iter().map(|t| (t.id, t.name)).collect();
+        Ok(TreeSearchResult {
+            entries: page
+                .entries
+                .into_iter()
+                .map(|e| TreeSearchHit {
+                    tree_id: e.tree_id,
+                    tree_name: tree_names.get(&e.tree_id).cloned().unwrap_or_default(),
+                    entry: search_entry_from_db(e),
+                })
+                .collect(),
+            total_count: page.total_count as usize,
+        })
+    }
+
+    /// Typo-tolerant name search ranked by edit distance (see
+    /// [`PersonSearchRepo::fuzzy_search`]), populating the search table on
+    /// demand like [`Self::search`].
+    #[instrument(skip(self), fields(tree_id = %tree_id, name = %name))]
+    pub async fn fuzzy_search(
+        &self,
+        tree_id: Uuid,
+        name: &str,
+        max_distance: usize,
+        limit: usize,
+    ) -> Result<FuzzySearchResult, OxidGeneError> {
+        self.ensure_search_index(tree_id).await?;
+        let hits =
+            PersonSearchRepo::fuzzy_search(&self.db, tree_id, name, max_distance, limit).await?;
+        Ok(FuzzySearchResult {
+            entries: hits
+                .into_iter()
+                .map(|hit| FuzzySearchHit {
+                    entry: search_entry_from_db(hit.entry),
+                    distance: hit.distance,
+                })
+                .collect(),
+        })
+    }
+
     /// Populate `person_search_fts` for a tree if it has no rows yet.
     async fn ensure_search_index(&self, tree_id: Uuid) -> Result<(), OxidGeneError> {
         if PersonSearchRepo::count_tree(&self.db, tree_id).await? > 0 {
@@ -483,6 +548,36 @@ impl CacheService {
         Ok(())
     }
 
+    /// Invalidate after a person moved from `source_tree_id` to
+    /// `target_tree_id`.
+    ///
+    /// `affected` is the person's affected set computed *before* the move
+    /// (see [`invalidation::affected_persons`]): the relatives left in the
+    /// source tree are rebuilt without them, and the person is rebuilt in
+    /// the target tree.
+    #[instrument(skip(self, affected), fields(person_id = %person_id))]
+    pub async fn invalidate_for_person_move(
+        &self,
+        source_tree_id: Uuid,
+        target_tree_id: Uuid,
+        person_id: Uuid,
+        affected: &[Uuid],
+    ) -> Result<(), OxidGeneError> {
+        self.store.delete_person(source_tree_id, person_id).await?;
+        let remaining: Vec<Uuid> = affected
+            .iter()
+            .copied()
+            .filter(|&id| id != person_id)
+            .collect();
+        if remaining.is_empty() {
+            self.store.delete_all_pedigrees(source_tree_id).await?;
+        } else {
+            self.rebuild_affected(source_tree_id, &remaining).await?;
+        }
+        // The upsert replaces the person's search row, tree included.
+        self.rebuild_affected(target_tree_id, &[person_id]).await
+    }
+
     /// Invalidate all caches for a tree (used when the tree is deleted).
     #[instrument(skip(self), fields(tree_id = %tree_id))]
     pub async fn invalidate_tree(&self, tree_id: Uuid) -> Result<(), OxidGeneError> {
@@ -590,7 +685,11 @@ impl CacheService {
 
         let mut events = person_events;
         events.extend(family_events);
-        let mut place_ids: Vec<Uuid> = events.iter().filter_map(|e| e.place_id).collect();
+        let mut place_ids: Vec<Uuid> = events
+            .iter()
+            .flat_map(|e| [e.place_id, e.place_id_to])
+            .flatten()
+            .collect();
         place_ids.sort();
         place_ids.dedup();
         let media_ids: Vec<Uuid> = media_links.iter().map(|l| l.media_id).collect();
@@ -712,12 +811,8 @@ impl CacheService {
 
         // 1. Get ancestor and descendant IDs from the closure table.
         let (ancestors, descendants) = tokio::try_join!(
-            PersonAncestryRepo::ancestors(&self.db, root_person_id, Some(ancestor_depth as i32)),
-            PersonAncestryRepo::descendants(
-                &self.db,
-                root_person_id,
-                Some(descendant_depth as i32)
-            ),
+            PersonAncestryRepo::ancestors(&self.db, root_person_id, ancestor_depth as i32),
+            PersonAncestryRepo::descendants(&self.db, root_person_id, descendant_depth as i32),
         )?;
 
         // 2. Collect all person IDs we need.
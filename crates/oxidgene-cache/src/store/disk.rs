@@ -90,10 +90,7 @@ pub fn persist_to_disk(
     )?;
 
     // Persist metadata (JSON for human readability)
-    let db_modified_at = db_path
-        .and_then(|p| fs::metadata(p).ok())
-        .and_then(|m| m.modified().ok())
-        .map(DateTime::<Utc>::from);
+    let db_modified_at = db_path.and_then(db_modified_at);
 
     let metadata = CacheMetadata {
         persisted_at: Utc::now(),
@@ -182,6 +179,18 @@ pub fn load_metadata(cache_dir: &Path) -> Option<CacheMetadata> {
         .ok()
 }
 
+/// Last modification of the SQLite DB. In WAL mode commits go to the
+/// `-wal` file and only reach the main file at checkpoints, so the later of
+/// the two is used.
+fn db_modified_at(db_path: &Path) -> Option<DateTime<Utc>> {
+    let mut wal_path = db_path.as_os_str().to_owned();
+    wal_path.push("-wal");
+    let modified = |path: &Path| fs::metadata(path).ok()?.modified().ok();
+    let db = modified(db_path)?;
+    let latest = modified(Path::new(&wal_path)).map_or(db, |wal| wal.max(db));
+    Some(DateTime::<Utc>::from(latest))
+}
+
 /// Check whether the persisted cache is stale relative to the SQLite DB.
 ///
 /// Returns `true` if the cache should be discarded and rebuilt:
@@ -206,12 +215,7 @@ pub fn is_cache_stale(cache_dir: &Path, db_path: &Path) -> bool {
     }
 
     // Check DB modification time
-    let db_modified = fs::metadata(db_path)
-        .ok()
-        .and_then(|m| m.modified().ok())
-        .map(DateTime::<Utc>::from);
-
-    match (db_modified, metadata.db_modified_at) {
+    match (db_modified_at(db_path), metadata.db_modified_at) {
         (Some(current), Some(cached)) => {
             if current > cached {
                 info!("DB modified since cache was persisted (db={current}, cache={cached})");
@@ -406,6 +410,23 @@ mod tests {
         assert!(is_cache_stale(dir.path(), Path::new("/some/db.sqlite")));
     }
 
+    #[tokio::test]
+    async fn staleness_follows_wal_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path().join("cache");
+        let db_path = dir.path().join("oxidgene.db");
+        fs::write(&db_path, b"db").unwrap();
+
+        persist_to_disk(&MemoryCacheStore::new(), &cache_dir, Some(&db_path)).unwrap();
+        assert!(!is_cache_stale(&cache_dir, &db_path));
+
+        // A commit lands in the WAL; the main file is untouched.
+        let wal = fs::File::create(dir.path().join("oxidgene.db-wal")).unwrap();
+        wal.set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(60))
+            .unwrap();
+        assert!(is_cache_stale(&cache_dir, &db_path));
+    }
+
     #[tokio::test]
     async fn clear_disk_cache_removes_files() {
         let dir = tempfile::tempdir().unwrap();
@@ -292,6 +292,7 @@ mod tests {
                 display_name: "John Doe".to_string(),
                 given_names: Some("John".to_string()),
                 surname: Some("Doe".to_string()),
+                nickname: None,
             }),
             other_names: vec![],
             birth: None,
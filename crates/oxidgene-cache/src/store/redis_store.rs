@@ -365,6 +365,7 @@ mod tests {
             birth_place: Some("Paris".to_string()),
             death_year: Some("1975".to_string()),
             date_sort: None,
+            matched_name: None,
         };
 
         let bytes = RedisCacheStore::serialize(&entry).unwrap();
@@ -54,6 +54,8 @@ pub struct CachedName {
     pub display_name: String,
     pub given_names: Option<String>,
     pub surname: Option<String>,
+    #[serde(default)]
+    pub nickname: Option<String>,
 }
 
 /// An event with its place name denormalized.
@@ -65,6 +67,11 @@ pub struct CachedEvent {
     pub date_sort: Option<NaiveDate>,
     pub place_name: Option<String>,
     pub place_id: Option<Uuid>,
+    /// Destination of a move (emigration, immigration, residence).
+    #[serde(default)]
+    pub place_name_to: Option<String>,
+    #[serde(default)]
+    pub place_id_to: Option<Uuid>,
     pub description: Option<String>,
 }
 
@@ -217,6 +224,11 @@ pub struct SearchEntry {
     pub death_year: Option<String>,
     // For sorting / filtering
     pub date_sort: Option<NaiveDate>,
+    /// The other name or nickname the search matched, when the primary
+    /// name does not match it (a maiden name searched by a married woman's
+    /// descendants, ...).
+    #[serde(default)]
+    pub matched_name: Option<String>,
 }
 
 /// Paginated search results.
@@ -225,3 +237,33 @@ pub struct SearchResult {
     pub entries: Vec<SearchEntry>,
     pub total_count: usize,
 }
+
+/// A fuzzy search result: the entry plus its edit distance to the query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuzzySearchHit {
+    #[serde(flatten)]
+    pub entry: SearchEntry,
+    pub distance: usize,
+}
+
+/// A hit of a search across all trees: the entry plus its tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeSearchHit {
+    pub tree_id: Uuid,
+    pub tree_name: String,
+    #[serde(flatten)]
+    pub entry: SearchEntry,
+}
+
+/// Paginated results of a search across all trees.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeSearchResult {
+    pub entries: Vec<TreeSearchHit>,
+    pub total_count: usize,
+}
+
+/// Fuzzy search results, closest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuzzySearchResult {
+    pub entries: Vec<FuzzySearchHit>,
+}
@@ -69,6 +69,7 @@ async fn create_named_person(
             Some(year.to_string()),
             chrono::NaiveDate::from_ymd_opt(year, 1, 1),
             None,
+            None,
             Some(id),
             None,
             None,
@@ -121,6 +122,7 @@ async fn create_family_trio(db: &DatabaseConnection, tree_id: Uuid) -> (Uuid, Uu
         chrono::NaiveDate::from_ymd_opt(1878, 6, 15),
         None,
         None,
+        None,
         Some(family_id),
         None,
     )
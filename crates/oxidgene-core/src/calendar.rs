@@ -0,0 +1,218 @@
+//! Calendar systems of GEDCOM dates: the `@#DJULIAN@`-style escapes that
+//! name them in a date phrase, and conversion of their dates to the
+//! proleptic Gregorian calendar `date_sort` is kept in.
+
+use chrono::{Days, NaiveDate};
+
+use crate::enums::Calendar;
+
+/// Keywords of a GEDCOM date phrase that come before a date.
+const DATE_KEYWORDS: [&str; 10] = [
+    "ABT", "CAL", "EST", "BEF", "AFT", "BET", "AND", "FROM", "TO", "INT",
+];
+
+/// Months of the French Republican calendar, the 13th holding the five or
+/// six complementary days.
+const FRENCH_MONTHS: [&str; 13] = [
+    "VEND", "BRUM", "FRIM", "NIVO", "PLUV", "VENT", "GERM", "FLOR", "PRAI", "MESS", "THER", "FRUC",
+    "COMP",
+];
+
+impl Calendar {
+    /// The escape naming this calendar in a GEDCOM date phrase.
+    pub fn gedcom_escape(self) -> &'static str {
+        match self {
+            Self::Gregorian => "@#DGREGORIAN@",
+            Self::Julian => "@#DJULIAN@",
+            Self::Hebrew => "@#DHEBREW@",
+            Self::FrenchRepublican => "@#DFRENCH R@",
+        }
+    }
+
+    /// The calendar named by a GEDCOM escape such as `@#DJULIAN@`, or `None`
+    /// for an unknown one (`@#DROMAN@`, `@#DUNKNOWN@`).
+    pub fn from_gedcom_escape(escape: &str) -> Option<Self> {
+        match escape.trim().to_ascii_uppercase().as_str() {
+            "@#DGREGORIAN@" => Some(Self::Gregorian),
+            "@#DJULIAN@" => Some(Self::Julian),
+            "@#DHEBREW@" => Some(Self::Hebrew),
+            "@#DFRENCH R@" => Some(Self::FrenchRepublican),
+            _ => None,
+        }
+    }
+
+    /// Number of a month abbreviation (`MAR`, `VEND`, ...) in this calendar.
+    /// Hebrew months are not supported.
+    pub fn month_number(self, month: &str) -> Option<u32> {
+        let month = month.to_ascii_uppercase();
+        let months: &[&str] = match self {
+            Self::Gregorian | Self::Julian => &[
+                "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+            ],
+            Self::FrenchRepublican => &FRENCH_MONTHS,
+            Self::Hebrew => &[],
+        };
+        months
+            .iter()
+            .position(|m| *m == month)
+            .map(|i| i as u32 + 1)
+    }
+
+    /// The proleptic Gregorian date of a day of this calendar, or `None` if
+    /// the day does not exist. Hebrew dates are not converted.
+    pub fn to_gregorian(self, year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+        match self {
+            Self::Gregorian => NaiveDate::from_ymd_opt(year, month, day),
+            Self::Julian => julian_to_gregorian(year, month, day),
+            Self::FrenchRepublican => french_to_gregorian(year, month, day),
+            Self::Hebrew => None,
+        }
+    }
+}
+
+/// Take the calendar escapes out of a GEDCOM date phrase: returns the
+/// calendar of the first recognized escape and the phrase without them, or
+/// `None` and the phrase unchanged when it names no known calendar.
+///
+/// `"BET @#DJULIAN@ 1700 AND @#DJULIAN@ 1710"` gives
+/// `(Some(Julian), "BET 1700 AND 1710")`.
+pub fn split_calendar(date_value: &str) -> (Option<Calendar>, String) {
+    let mut calendar = None;
+    let mut rest = date_value;
+    let mut phrase = String::new();
+    while let Some(start) = rest.find("@#") {
+        let Some(len) = rest[start + 2..].find('@') else {
+            break;
+        };
+        let end = start + 2 + len + 1;
+        match Calendar::from_gedcom_escape(&rest[start..end]) {
+            Some(found) => {
+                calendar.get_or_insert(found);
+                phrase.push_str(&rest[..start]);
+            }
+            None => phrase.push_str(&rest[..end]),
+        }
+        rest = &rest[end..];
+    }
+    if calendar.is_none() {
+        return (None, date_value.to_string());
+    }
+    phrase.push_str(rest);
+    (
+        calendar,
+        phrase.split_whitespace().collect::<Vec<_>>().join(" "),
+    )
+}
+
+/// A date phrase of `calendar` as GEDCOM writes it: each date preceded by
+/// the calendar's escape, e.g. `"ABT @#DJULIAN@ 1700"`. Gregorian phrases
+/// are left as they are.
+pub fn with_calendar(calendar: Calendar, date_value: &str) -> String {
+    if calendar == Calendar::Gregorian {
+        return date_value.to_string();
+    }
+    let mut words = Vec::new();
+    let mut date_starts = true;
+    for word in date_value.split_whitespace() {
+        if DATE_KEYWORDS.contains(&word.to_ascii_uppercase().as_str()) {
+            date_starts = true;
+        } else if date_starts {
+            words.push(calendar.gedcom_escape());
+            date_starts = false;
+        }
+        words.push(word);
+    }
+    words.join(" ")
+}
+
+/// Julian to Gregorian through the Julian day number.
+fn julian_to_gregorian(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    let month_days = match month {
+        2 if year % 4 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+    if day == 0 || day > month_days {
+        return None;
+    }
+    let a = (14 - month as i64) / 12;
+    let y = year as i64 + 4800 - a;
+    let m = month as i64 + 12 * a - 3;
+    let jdn = day as i64 + (153 * m + 2) / 5 + 365 * y + y.div_euclid(4) - 32083;
+    // Julian day number of 31 December 1 BC (Gregorian).
+    NaiveDate::from_num_days_from_ce_opt(i32::try_from(jdn - 1_721_425).ok()?)
+}
+
+/// French Republican to Gregorian, counting from 1 Vendémiaire An I
+/// (22 September 1792). Years III, VII and XI, and every fourth year after,
+/// are sextile: their complementary month has six days.
+fn french_to_gregorian(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    let sextile = year % 4 == 3;
+    let month_days = match month {
+        1..=12 => 30,
+        13 if sextile => 6,
+        13 => 5,
+        _ => return None,
+    };
+    if year < 1 || day == 0 || day > month_days {
+        return None;
+    }
+    let days = (year as u64 - 1) * 365 + year as u64 / 4 + (month as u64 - 1) * 30 + day as u64 - 1;
+    NaiveDate::from_ymd_opt(1792, 9, 22)?.checked_add_days(Days::new(days))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ymd(y: i32, m: u32, d: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(y, m, d)
+    }
+
+    #[test]
+    fn test_split_and_rebuild_calendar() {
+        assert_eq!(
+            split_calendar("BET @#DJULIAN@ 1700 AND @#DJULIAN@ 1710"),
+            (Some(Calendar::Julian), "BET 1700 AND 1710".to_string())
+        );
+        assert_eq!(
+            split_calendar("@#DFRENCH R@ 1 VEND 3"),
+            (Some(Calendar::FrenchRepublican), "1 VEND 3".to_string())
+        );
+        assert_eq!(
+            split_calendar("@#DROMAN@ 1700"),
+            (None, "@#DROMAN@ 1700".to_string())
+        );
+        assert_eq!(
+            with_calendar(Calendar::Julian, "BET 1700 AND 1710"),
+            "BET @#DJULIAN@ 1700 AND @#DJULIAN@ 1710"
+        );
+        assert_eq!(with_calendar(Calendar::Gregorian, "ABT 1700"), "ABT 1700");
+    }
+
+    #[test]
+    fn test_to_gregorian() {
+        // The British switch: 2 September (Julian) was followed by
+        // 14 September (Gregorian) 1752.
+        assert_eq!(Calendar::Julian.to_gregorian(1752, 9, 3), ymd(1752, 9, 14));
+        assert_eq!(Calendar::Julian.to_gregorian(1700, 2, 29), ymd(1700, 3, 11));
+        assert_eq!(Calendar::Julian.to_gregorian(1701, 2, 29), None);
+        assert_eq!(
+            Calendar::FrenchRepublican.to_gregorian(1, 1, 1),
+            ymd(1792, 9, 22)
+        );
+        // 18 Brumaire An VIII.
+        assert_eq!(
+            Calendar::FrenchRepublican.to_gregorian(8, 2, 18),
+            ymd(1799, 11, 9)
+        );
+        assert_eq!(
+            Calendar::FrenchRepublican.to_gregorian(3, 13, 6),
+            ymd(1795, 9, 22)
+        );
+        assert_eq!(Calendar::FrenchRepublican.to_gregorian(4, 13, 6), None);
+        assert_eq!(Calendar::Hebrew.to_gregorian(5600, 1, 1), None);
+    }
+}
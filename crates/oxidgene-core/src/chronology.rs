@@ -0,0 +1,468 @@
+//! Year arithmetic on recorded dates and ages, and date reconciliation
+//! across the records of one person.
+//!
+//! Dates are handled at year granularity: sources disagreeing by a few
+//! months are not worth flagging, and most historical dates carry no more
+//! precision than that anyway.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::{DateQualifier, EventType};
+use crate::types::{Event, year_from_date};
+
+/// Default number of years two records may disagree by before they are
+/// reported as a conflict.
+pub const DEFAULT_CONFLICT_TOLERANCE: i32 = 2;
+
+/// Inclusive range of years; a missing bound is open-ended (`BEF 1850`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct YearRange {
+    pub earliest: Option<i32>,
+    pub latest: Option<i32>,
+}
+
+impl YearRange {
+    pub fn exact(year: i32) -> Self {
+        Self {
+            earliest: Some(year),
+            latest: Some(year),
+        }
+    }
+
+    /// Years separating two ranges; `0` when they overlap.
+    pub fn gap(&self, other: &YearRange) -> i32 {
+        let after = match (self.earliest, other.latest) {
+            (Some(a), Some(b)) => a - b,
+            _ => 0,
+        };
+        let before = match (other.earliest, self.latest) {
+            (Some(a), Some(b)) => a - b,
+            _ => 0,
+        };
+        after.max(before).max(0)
+    }
+}
+
+/// An age in completed years, as bounded by a recorded age.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgeSpan {
+    pub min: i32,
+    /// `None` for open-ended ages (`> 80y`).
+    pub max: Option<i32>,
+}
+
+/// Parse a GEDCOM `AGE` value: `32`, `32y 6m`, `< 1y`, `> 80y`, `CHILD`,
+/// `INFANT`, `STILLBORN`. Months, weeks and days only matter through the
+/// years they complete.
+pub fn parse_age(age: &str) -> Option<AgeSpan> {
+    let age = age.trim();
+    match age.to_ascii_uppercase().as_str() {
+        "CHILD" => {
+            return Some(AgeSpan {
+                min: 0,
+                max: Some(7),
+            });
+        }
+        "INFANT" | "STILLBORN" => {
+            return Some(AgeSpan {
+                min: 0,
+                max: Some(0),
+            });
+        }
+        _ => {}
+    }
+    let (modifier, rest) = match age.chars().next()? {
+        c @ ('<' | '>') => (Some(c), age[1..].trim_start()),
+        _ => (None, age),
+    };
+    let mut years = 0;
+    let mut parsed_any = false;
+    for token in rest.split_whitespace() {
+        let token = token.to_ascii_lowercase();
+        let (digits, unit) = match token.strip_suffix(['y', 'm', 'w', 'd']) {
+            Some(digits) => (digits, token.chars().last()),
+            None => (token.as_str(), Some('y')),
+        };
+        let value: i32 = digits.parse().ok()?;
+        match unit {
+            Some('y') => years += value,
+            Some('m') => years += value / 12,
+            Some('w') => years += value / 52,
+            Some('d') => years += value / 365,
+            _ => return None,
+        }
+        parsed_any = true;
+    }
+    if !parsed_any {
+        return None;
+    }
+    Some(match modifier {
+        Some('<') => AgeSpan {
+            min: 0,
+            max: Some((years - 1).max(0)),
+        },
+        Some('>') => AgeSpan {
+            min: years,
+            max: None,
+        },
+        _ => AgeSpan {
+            min: years,
+            max: Some(years),
+        },
+    })
+}
+
+/// Years an event may have happened in, from its qualifier and date
+/// phrases. Imported GEDCOM phrases (`BEF 1850`, `BET 1840 AND 1845`) are
+/// read the same way as the structured qualifiers.
+pub fn event_year_range(event: &Event) -> Option<YearRange> {
+    let phrase = event.date_value.as_deref().unwrap_or_default();
+    let first_word = phrase
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    let mut years: Vec<i32> = phrase
+        .split_whitespace()
+        .filter(|w| w.len() == 4 && w.chars().all(|c| c.is_ascii_digit()))
+        .filter_map(|w| w.parse().ok())
+        .collect();
+    if years.is_empty() {
+        years.extend(year_from_date(event.date_sort, None));
+    }
+    years.extend(year_from_date(None, event.date_value2.as_deref()));
+    let earliest = *years.iter().min()?;
+    let latest = *years.iter().max()?;
+
+    Some(
+        if event.date_qualifier == DateQualifier::Before || first_word == "BEF" {
+            YearRange {
+                earliest: None,
+                latest: Some(latest),
+            }
+        } else if event.date_qualifier == DateQualifier::After || first_word == "AFT" {
+            YearRange {
+                earliest: Some(earliest),
+                latest: None,
+            }
+        } else {
+            YearRange {
+                earliest: Some(earliest),
+                latest: Some(latest),
+            }
+        },
+    )
+}
+
+/// Birth years implied by being `age` old at an event in `at`: someone aged
+/// 32 in 1851 was born in 1818 or 1819.
+pub fn implied_birth_years(at: YearRange, age: AgeSpan) -> YearRange {
+    YearRange {
+        earliest: at.earliest.zip(age.max).map(|(year, max)| year - max - 1),
+        latest: at.latest.map(|year| year - age.min),
+    }
+}
+
+/// Largest gap between any two ranges.
+pub fn spread(ranges: &[YearRange]) -> i32 {
+    let mut widest = 0;
+    for (i, a) in ranges.iter().enumerate() {
+        for b in &ranges[i + 1..] {
+            widest = widest.max(a.gap(b));
+        }
+    }
+    widest
+}
+
+/// One record contributing to a [`DateConflict`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConflictRecord {
+    pub event_id: Uuid,
+    pub event_type: EventType,
+    pub date_value: Option<String>,
+    pub age: Option<String>,
+    /// Years this record places the vital event in — the event's own date,
+    /// or a birth computed from a recorded age.
+    pub years: YearRange,
+}
+
+/// A vital event whose records disagree by more than the tolerance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DateConflict {
+    /// `Birth`, `Death` or `Marriage`.
+    pub event_type: EventType,
+    /// Set for marriage conflicts.
+    pub family_id: Option<Uuid>,
+    /// Widest disagreement between two records, in years.
+    pub discrepancy_years: i32,
+    pub records: Vec<ConflictRecord>,
+}
+
+fn record(event: &Event, years: YearRange) -> ConflictRecord {
+    ConflictRecord {
+        event_id: event.id,
+        event_type: event.event_type,
+        date_value: event.date_value.clone(),
+        age: event.age.clone(),
+        years,
+    }
+}
+
+fn conflict(
+    event_type: EventType,
+    family_id: Option<Uuid>,
+    records: Vec<ConflictRecord>,
+    tolerance: i32,
+) -> Option<DateConflict> {
+    let ranges: Vec<YearRange> = records.iter().map(|r| r.years).collect();
+    let discrepancy_years = spread(&ranges);
+    (discrepancy_years > tolerance).then_some(DateConflict {
+        event_type,
+        family_id,
+        discrepancy_years,
+        records,
+    })
+}
+
+/// Find the vital events of one person whose records disagree by more than
+/// `tolerance` years.
+///
+/// Birth is checked against every birth event and every birth implied by an
+/// age recorded on one of the person's other events (census, death, ...).
+/// Death is checked across death events, marriage across the marriage
+/// events of each family in `family_events`.
+pub fn date_conflicts(
+    person_events: &[Event],
+    family_events: &[Event],
+    tolerance: i32,
+) -> Vec<DateConflict> {
+    let mut births = Vec::new();
+    let mut deaths = Vec::new();
+    for event in person_events {
+        let Some(years) = event_year_range(event) else {
+            continue;
+        };
+        match event.event_type {
+            EventType::Birth => births.push(record(event, years)),
+            EventType::Death => deaths.push(record(event, years)),
+            _ => {}
+        }
+        if event.event_type != EventType::Birth
+            && let Some(age) = event.age.as_deref().and_then(parse_age)
+        {
+            births.push(record(event, implied_birth_years(years, age)));
+        }
+    }
+
+    let mut conflicts: Vec<DateConflict> = [
+        conflict(EventType::Birth, None, births, tolerance),
+        conflict(EventType::Death, None, deaths, tolerance),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let mut family_ids: Vec<Uuid> = family_events.iter().filter_map(|e| e.family_id).collect();
+    family_ids.sort();
+    family_ids.dedup();
+    for family_id in family_ids {
+        let marriages = family_events
+            .iter()
+            .filter(|e| e.family_id == Some(family_id) && e.event_type == EventType::Marriage)
+            .filter_map(|e| Some(record(e, event_year_range(e)?)))
+            .collect();
+        conflicts.extend(conflict(
+            EventType::Marriage,
+            Some(family_id),
+            marriages,
+            tolerance,
+        ));
+    }
+    conflicts
+}
+
+/// A divorce dated before the marriage of the same family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DivorceBeforeMarriage {
+    pub family_id: Uuid,
+    pub divorce_event_id: Uuid,
+    pub marriage_event_id: Uuid,
+}
+
+/// Whether `later` certainly happened before `earlier`: full dates are
+/// compared when both are exact, year ranges otherwise.
+pub(crate) fn definitely_before(later: &Event, earlier: &Event) -> bool {
+    let exact = |e: &Event| e.date_qualifier == DateQualifier::Exact;
+    if exact(later)
+        && exact(earlier)
+        && let (Some(a), Some(b)) = (later.date_sort, earlier.date_sort)
+    {
+        return a < b;
+    }
+    match (event_year_range(later), event_year_range(earlier)) {
+        (Some(a), Some(b)) => matches!((a.latest, b.earliest), (Some(l), Some(e)) if l < e),
+        _ => false,
+    }
+}
+
+/// Divorce events (including filed divorces) dated before a marriage of
+/// the same family in `family_events`.
+pub fn divorces_before_marriage(family_events: &[Event]) -> Vec<DivorceBeforeMarriage> {
+    let mut found = Vec::new();
+    for divorce in family_events
+        .iter()
+        .filter(|e| matches!(e.event_type, EventType::Divorce | EventType::DivorceFiled))
+    {
+        let Some(family_id) = divorce.family_id else {
+            continue;
+        };
+        if let Some(marriage) = family_events.iter().find(|e| {
+            e.family_id == Some(family_id)
+                && e.event_type == EventType::Marriage
+                && definitely_before(divorce, e)
+        }) {
+            found.push(DivorceBeforeMarriage {
+                family_id,
+                divorce_event_id: divorce.id,
+                marriage_event_id: marriage.id,
+            });
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::Calendar;
+    use chrono::Utc;
+
+    fn event(event_type: EventType, date_value: &str, age: Option<&str>) -> Event {
+        let now = Utc::now();
+        Event {
+            id: Uuid::now_v7(),
+            tree_id: Uuid::now_v7(),
+            event_type,
+            date_value: Some(date_value.to_string()),
+            date_sort: None,
+            date_qualifier: DateQualifier::default(),
+            date_value2: None,
+            calendar: Calendar::default(),
+            cause: None,
+            age: age.map(str::to_string),
+            place_id: None,
+            place_id_to: None,
+            person_id: None,
+            family_id: None,
+            description: None,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn parses_gedcom_ages() {
+        let span = |min, max| Some(AgeSpan { min, max });
+        assert_eq!(parse_age("32"), span(32, Some(32)));
+        assert_eq!(parse_age("32y 6m"), span(32, Some(32)));
+        assert_eq!(parse_age("< 1y"), span(0, Some(0)));
+        assert_eq!(parse_age(">80y"), span(80, None));
+        assert_eq!(parse_age("CHILD"), span(0, Some(7)));
+        assert_eq!(parse_age("about thirty"), None);
+    }
+
+    #[test]
+    fn reads_ranges_from_phrases() {
+        let range = |e: &Event| event_year_range(e).unwrap();
+        let between = event(EventType::Census, "BET 1840 AND 1845", None);
+        assert_eq!(
+            range(&between),
+            YearRange {
+                earliest: Some(1840),
+                latest: Some(1845)
+            }
+        );
+        let before = event(EventType::Death, "BEF 1850", None);
+        assert_eq!(range(&before).earliest, None);
+        assert_eq!(range(&before).gap(&YearRange::exact(1860)), 10);
+        assert_eq!(range(&before).gap(&YearRange::exact(1700)), 0);
+    }
+
+    #[test]
+    fn census_ages_imply_birth_years() {
+        let at = YearRange::exact(1851);
+        let age = parse_age("32").unwrap();
+        assert_eq!(
+            implied_birth_years(at, age),
+            YearRange {
+                earliest: Some(1818),
+                latest: Some(1819)
+            }
+        );
+    }
+
+    #[test]
+    fn flags_census_ages_disagreeing_with_birth() {
+        let events = vec![
+            event(EventType::Birth, "12 MAR 1820", None),
+            event(EventType::Census, "1851", Some("31")),
+            event(EventType::Census, "1861", Some("45")),
+        ];
+        let conflicts = date_conflicts(&events, &[], DEFAULT_CONFLICT_TOLERANCE);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].event_type, EventType::Birth);
+        assert_eq!(conflicts[0].records.len(), 3);
+        // 1861 - 45 = 1816 at the latest, against a 1820 birth.
+        assert_eq!(conflicts[0].discrepancy_years, 4);
+
+        // Within tolerance: no conflict.
+        assert!(date_conflicts(&events, &[], 5).is_empty());
+    }
+
+    #[test]
+    fn flags_marriages_per_family() {
+        let family_id = Uuid::now_v7();
+        let mut first = event(EventType::Marriage, "1845", None);
+        first.family_id = Some(family_id);
+        let mut second = event(EventType::Marriage, "1852", None);
+        second.family_id = Some(family_id);
+        let conflicts = date_conflicts(&[], &[first, second], DEFAULT_CONFLICT_TOLERANCE);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].family_id, Some(family_id));
+        assert_eq!(conflicts[0].discrepancy_years, 7);
+    }
+
+    #[test]
+    fn flags_divorce_before_marriage() {
+        let family_id = Uuid::now_v7();
+        let mut marriage = event(EventType::Marriage, "12 JUN 1850", None);
+        marriage.family_id = Some(family_id);
+        marriage.date_sort = chrono::NaiveDate::from_ymd_opt(1850, 6, 12);
+        let mut divorce = event(EventType::Divorce, "3 MAR 1850", None);
+        divorce.family_id = Some(family_id);
+        divorce.date_sort = chrono::NaiveDate::from_ymd_opt(1850, 3, 3);
+
+        let found = divorces_before_marriage(&[marriage.clone(), divorce.clone()]);
+        assert_eq!(
+            found,
+            vec![DivorceBeforeMarriage {
+                family_id,
+                divorce_event_id: divorce.id,
+                marriage_event_id: marriage.id,
+            }]
+        );
+
+        // Same year but imprecise: not certainly before.
+        divorce.date_qualifier = DateQualifier::About;
+        assert!(divorces_before_marriage(&[marriage.clone(), divorce.clone()]).is_empty());
+
+        // A later divorce is fine.
+        divorce.date_qualifier = DateQualifier::Exact;
+        divorce.date_value = Some("1862".to_string());
+        divorce.date_sort = chrono::NaiveDate::from_ymd_opt(1862, 1, 1);
+        assert!(divorces_before_marriage(&[marriage, divorce]).is_empty());
+    }
+}
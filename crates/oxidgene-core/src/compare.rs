@@ -0,0 +1,463 @@
+//! Field-by-field comparison of two persons, to help decide whether they
+//! are the same individual before merging them.
+//!
+//! Values are compared after normalization (case, accents, whitespace), so
+//! "Émile DUPONT" and "emile dupont" agree. Dates are compared on their
+//! years: "ABT 1842" and "12 MAR 1842" are compatible rather than equal.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::chronology::event_year_range;
+use crate::enums::{EventType, NameSelectionPolicy, Sex};
+use crate::search::normalize_for_search;
+use crate::types::{Event, PersonName};
+
+/// A parent or spouse of a compared person.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Relative {
+    pub person_id: Uuid,
+    /// Display name, empty when the relative is unnamed.
+    pub name: String,
+}
+
+/// What is known about one side of a comparison.
+#[derive(Debug, Clone)]
+pub struct PersonRecord {
+    pub sex: Sex,
+    pub names: Vec<PersonName>,
+    /// The person's individual events.
+    pub events: Vec<Event>,
+    pub parents: Vec<Relative>,
+    pub spouses: Vec<Relative>,
+}
+
+/// A compared field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComparedField {
+    Name,
+    OtherNames,
+    Sex,
+    BirthDate,
+    BirthPlace,
+    DeathDate,
+    DeathPlace,
+    Parents,
+    Spouses,
+}
+
+impl ComparedField {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::OtherNames => "other_names",
+            Self::Sex => "sex",
+            Self::BirthDate => "birth_date",
+            Self::BirthPlace => "birth_place",
+            Self::DeathDate => "death_date",
+            Self::DeathPlace => "death_place",
+            Self::Parents => "parents",
+            Self::Spouses => "spouses",
+        }
+    }
+}
+
+/// How the two sides of a field relate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Agreement {
+    /// Both sides hold the same values.
+    Same,
+    /// The values differ but may describe the same fact: overlapping date
+    /// years, lists sharing some entries.
+    Compatible,
+    Different,
+    /// Only the first person has a value.
+    OnlyA,
+    /// Only the second person has a value.
+    OnlyB,
+}
+
+impl Agreement {
+    /// Whether the field speaks against the persons being the same.
+    pub fn is_conflict(self) -> bool {
+        self == Self::Different
+    }
+}
+
+/// One row of a [`PersonComparison`]. Single-valued fields hold at most one
+/// value per side.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldComparison {
+    pub field: ComparedField,
+    pub a: Vec<String>,
+    pub b: Vec<String>,
+    pub agreement: Agreement,
+}
+
+/// Comparison of two persons. Fields empty on both sides are left out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonComparison {
+    pub fields: Vec<FieldComparison>,
+    /// Fields on which both persons agree (same or compatible).
+    pub agreements: usize,
+    /// Fields on which they differ.
+    pub differences: usize,
+}
+
+/// Compare two persons field by field. `places` maps place ids to their
+/// names, for the places of the vital events.
+pub fn compare_persons(
+    a: &PersonRecord,
+    b: &PersonRecord,
+    places: &HashMap<Uuid, String>,
+) -> PersonComparison {
+    let mut fields = Vec::new();
+    let mut push = |field, a: Vec<String>, b: Vec<String>, agreement: Option<Agreement>| {
+        if let Some(agreement) = agreement {
+            fields.push(FieldComparison {
+                field,
+                a,
+                b,
+                agreement,
+            });
+        }
+    };
+
+    let (name_a, others_a) = names(a);
+    let (name_b, others_b) = names(b);
+    let agreement = compare_lists(&name_a, &name_b);
+    push(ComparedField::Name, name_a, name_b, agreement);
+    let agreement = compare_lists(&others_a, &others_b);
+    push(ComparedField::OtherNames, others_a, others_b, agreement);
+
+    let sex = |r: &PersonRecord| match r.sex {
+        Sex::Unknown => Vec::new(),
+        sex => vec![sex.to_string()],
+    };
+    let (sex_a, sex_b) = (sex(a), sex(b));
+    let agreement = compare_lists(&sex_a, &sex_b);
+    push(ComparedField::Sex, sex_a, sex_b, agreement);
+
+    for (event_type, date_field, place_field) in [
+        (
+            EventType::Birth,
+            ComparedField::BirthDate,
+            ComparedField::BirthPlace,
+        ),
+        (
+            EventType::Death,
+            ComparedField::DeathDate,
+            ComparedField::DeathPlace,
+        ),
+    ] {
+        let event_a = vital_event(a, event_type);
+        let event_b = vital_event(b, event_type);
+        let date = |e: Option<&Event>| {
+            e.and_then(|e| e.date_value.clone())
+                .filter(|d| !d.trim().is_empty())
+                .into_iter()
+                .collect::<Vec<_>>()
+        };
+        let (date_a, date_b) = (date(event_a), date(event_b));
+        let agreement = match (event_a, event_b) {
+            (Some(ea), Some(eb)) if !date_a.is_empty() && !date_b.is_empty() => {
+                Some(compare_dates(ea, eb))
+            }
+            _ => compare_lists(&date_a, &date_b),
+        };
+        push(date_field, date_a, date_b, agreement);
+
+        let place = |e: Option<&Event>| {
+            e.and_then(|e| e.place_id)
+                .and_then(|id| places.get(&id).cloned())
+                .into_iter()
+                .collect::<Vec<_>>()
+        };
+        let (place_a, place_b) = (place(event_a), place(event_b));
+        let agreement = compare_lists(&place_a, &place_b);
+        push(place_field, place_a, place_b, agreement);
+    }
+
+    for (field, relatives_a, relatives_b) in [
+        (ComparedField::Parents, &a.parents, &b.parents),
+        (ComparedField::Spouses, &a.spouses, &b.spouses),
+    ] {
+        let agreement = compare_relatives(relatives_a, relatives_b);
+        let names = |relatives: &[Relative]| relatives.iter().map(|r| r.name.clone()).collect();
+        push(field, names(relatives_a), names(relatives_b), agreement);
+    }
+
+    let agreements = fields
+        .iter()
+        .filter(|f| matches!(f.agreement, Agreement::Same | Agreement::Compatible))
+        .count();
+    let differences = fields.iter().filter(|f| f.agreement.is_conflict()).count();
+    PersonComparison {
+        fields,
+        agreements,
+        differences,
+    }
+}
+
+/// The primary name, and the display names of the other names.
+fn names(record: &PersonRecord) -> (Vec<String>, Vec<String>) {
+    let Some(primary) = PersonName::select(&record.names, NameSelectionPolicy::PreferPrimary)
+    else {
+        return (Vec::new(), Vec::new());
+    };
+    let display = |n: &PersonName| Some(n.display_name()).filter(|d| !d.is_empty());
+    let others = record
+        .names
+        .iter()
+        .filter(|n| n.id != primary.id)
+        .filter_map(display)
+        .collect();
+    (display(primary).into_iter().collect(), others)
+}
+
+/// The first event of a type, dated ones first.
+fn vital_event(record: &PersonRecord, event_type: EventType) -> Option<&Event> {
+    let mut events = record.events.iter().filter(|e| e.event_type == event_type);
+    let first = events.clone().next();
+    events.find(|e| e.date_value.is_some()).or(first)
+}
+
+fn compare_dates(a: &Event, b: &Event) -> Agreement {
+    let normalize = |e: &Event| normalize(e.date_value.as_deref().unwrap_or_default());
+    if normalize(a) == normalize(b) {
+        return Agreement::Same;
+    }
+    match (event_year_range(a), event_year_range(b)) {
+        (Some(ya), Some(yb)) if ya.gap(&yb) == 0 => Agreement::Compatible,
+        _ => Agreement::Different,
+    }
+}
+
+/// Same when both lists hold the same values, compatible when they share
+/// some; `None` when both are empty.
+fn compare_lists(a: &[String], b: &[String]) -> Option<Agreement> {
+    let a: Vec<String> = a.iter().map(|v| normalize(v)).collect();
+    let b: Vec<String> = b.iter().map(|v| normalize(v)).collect();
+    Some(match (a.is_empty(), b.is_empty()) {
+        (true, true) => return None,
+        (false, true) => Agreement::OnlyA,
+        (true, false) => Agreement::OnlyB,
+        _ => {
+            let shared = a.iter().filter(|v| b.contains(v)).count();
+            if shared == a.len() && b.iter().all(|v| a.contains(v)) {
+                Agreement::Same
+            } else if shared > 0 {
+                Agreement::Compatible
+            } else {
+                Agreement::Different
+            }
+        }
+    })
+}
+
+/// Like [`compare_lists`], a relative also matching on their person id so
+/// two unnamed links to the same parent agree.
+fn compare_relatives(a: &[Relative], b: &[Relative]) -> Option<Agreement> {
+    let matches = |r: &Relative, others: &[Relative]| {
+        others.iter().any(|o| {
+            o.person_id == r.person_id
+                || (!r.name.is_empty() && normalize(&o.name) == normalize(&r.name))
+        })
+    };
+    Some(match (a.is_empty(), b.is_empty()) {
+        (true, true) => return None,
+        (false, true) => Agreement::OnlyA,
+        (true, false) => Agreement::OnlyB,
+        _ => {
+            let shared = a.iter().filter(|r| matches(r, b)).count();
+            if shared == a.len() && b.iter().all(|r| matches(r, a)) {
+                Agreement::Same
+            } else if shared > 0 {
+                Agreement::Compatible
+            } else {
+                Agreement::Different
+            }
+        }
+    })
+}
+
+fn normalize(s: &str) -> String {
+    normalize_for_search(s)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Calendar, DateQualifier, NameType};
+    use chrono::Utc;
+
+    fn name(given: &str, surname: &str, is_primary: bool) -> PersonName {
+        let now = Utc::now();
+        PersonName {
+            id: Uuid::now_v7(),
+            person_id: Uuid::nil(),
+            name_type: NameType::Birth,
+            given_names: Some(given.to_string()),
+            surname: Some(surname.to_string()),
+            prefix: None,
+            suffix: None,
+            nickname: None,
+            is_primary,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn event(event_type: EventType, date_value: &str, place_id: Option<Uuid>) -> Event {
+        let now = Utc::now();
+        Event {
+            id: Uuid::now_v7(),
+            tree_id: Uuid::nil(),
+            event_type,
+            date_value: Some(date_value.to_string()),
+            date_sort: None,
+            date_qualifier: DateQualifier::default(),
+            date_value2: None,
+            calendar: Calendar::default(),
+            cause: None,
+            age: None,
+            place_id,
+            place_id_to: None,
+            person_id: None,
+            family_id: None,
+            description: None,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            deleted_at: None,
+        }
+    }
+
+    fn record() -> PersonRecord {
+        PersonRecord {
+            sex: Sex::Unknown,
+            names: Vec::new(),
+            events: Vec::new(),
+            parents: Vec::new(),
+            spouses: Vec::new(),
+        }
+    }
+
+    fn field(comparison: &PersonComparison, field: ComparedField) -> Option<&FieldComparison> {
+        comparison.fields.iter().find(|f| f.field == field)
+    }
+
+    #[test]
+    fn test_names_compared_normalized() {
+        let a = PersonRecord {
+            names: vec![name("Émile", "DUPONT", true)],
+            ..record()
+        };
+        let b = PersonRecord {
+            names: vec![name("emile", "Dupont", true)],
+            ..record()
+        };
+        let comparison = compare_persons(&a, &b, &HashMap::new());
+        let f = field(&comparison, ComparedField::Name).unwrap();
+        assert_eq!(f.agreement, Agreement::Same);
+        assert_eq!(f.a, vec!["Émile DUPONT"]);
+        assert!(field(&comparison, ComparedField::OtherNames).is_none());
+    }
+
+    #[test]
+    fn test_dates_compatible_by_year() {
+        let a = PersonRecord {
+            events: vec![event(EventType::Birth, "ABT 1842", None)],
+            ..record()
+        };
+        let b = PersonRecord {
+            events: vec![
+                event(EventType::Birth, "12 MAR 1842", None),
+                event(EventType::Death, "1901", None),
+            ],
+            ..record()
+        };
+        let comparison = compare_persons(&a, &b, &HashMap::new());
+        assert_eq!(
+            field(&comparison, ComparedField::BirthDate)
+                .unwrap()
+                .agreement,
+            Agreement::Compatible
+        );
+        assert_eq!(
+            field(&comparison, ComparedField::DeathDate)
+                .unwrap()
+                .agreement,
+            Agreement::OnlyB
+        );
+    }
+
+    #[test]
+    fn test_conflicting_dates_and_places() {
+        let (paris, lyon) = (Uuid::now_v7(), Uuid::now_v7());
+        let places = HashMap::from([(paris, "Paris".to_string()), (lyon, "Lyon".to_string())]);
+        let a = PersonRecord {
+            events: vec![event(EventType::Birth, "1842", Some(paris))],
+            ..record()
+        };
+        let b = PersonRecord {
+            events: vec![event(EventType::Birth, "1850", Some(lyon))],
+            ..record()
+        };
+        let comparison = compare_persons(&a, &b, &places);
+        assert_eq!(comparison.differences, 2);
+        assert_eq!(comparison.agreements, 0);
+        let place = field(&comparison, ComparedField::BirthPlace).unwrap();
+        assert_eq!(
+            (place.a.as_slice(), place.b.as_slice()),
+            (&["Paris".to_string()][..], &["Lyon".to_string()][..])
+        );
+    }
+
+    #[test]
+    fn test_relatives_match_by_id_or_name() {
+        let father = Relative {
+            person_id: Uuid::now_v7(),
+            name: String::new(),
+        };
+        let mother = |id| Relative {
+            person_id: id,
+            name: "Marie Martin".to_string(),
+        };
+        let a = PersonRecord {
+            sex: Sex::Male,
+            parents: vec![father.clone(), mother(Uuid::now_v7())],
+            ..record()
+        };
+        let b = PersonRecord {
+            sex: Sex::Female,
+            parents: vec![father, mother(Uuid::now_v7())],
+            spouses: vec![mother(Uuid::now_v7())],
+            ..record()
+        };
+        let comparison = compare_persons(&a, &b, &HashMap::new());
+        assert_eq!(
+            field(&comparison, ComparedField::Parents)
+                .unwrap()
+                .agreement,
+            Agreement::Same
+        );
+        assert_eq!(
+            field(&comparison, ComparedField::Sex).unwrap().agreement,
+            Agreement::Different
+        );
+        assert_eq!(
+            field(&comparison, ComparedField::Spouses)
+                .unwrap()
+                .agreement,
+            Agreement::OnlyB
+        );
+    }
+}
@@ -10,6 +10,8 @@ use serde::{Deserialize, Serialize};
 pub enum Sex {
     Male,
     Female,
+    /// Recorded as intersex (GEDCOM `SEX X`).
+    Intersex,
     Unknown,
 }
 
@@ -18,6 +20,7 @@ impl std::fmt::Display for Sex {
         match self {
             Self::Male => write!(f, "male"),
             Self::Female => write!(f, "female"),
+            Self::Intersex => write!(f, "intersex"),
             Self::Unknown => write!(f, "unknown"),
         }
     }
@@ -48,6 +51,22 @@ impl std::fmt::Display for NameType {
     }
 }
 
+/// Which of a person's names stands for them in lists and headers (see
+/// [`crate::types::PersonName::select`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameSelectionPolicy {
+    /// The name flagged primary.
+    #[default]
+    PreferPrimary,
+    /// The birth (or maiden) name, e.g. to index women by maiden name.
+    PreferBirth,
+    /// The latest married name.
+    PreferMarried,
+    /// The most recently recorded name.
+    MostRecent,
+}
+
 /// Role of a spouse in a family.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -57,6 +76,18 @@ pub enum SpouseRole {
     Partner,
 }
 
+impl SpouseRole {
+    /// The default role of a spouse of sex `sex`; same-sex couples and
+    /// ambiguous records pick theirs explicitly.
+    pub fn for_sex(sex: Sex) -> Self {
+        match sex {
+            Sex::Male => Self::Husband,
+            Sex::Female => Self::Wife,
+            Sex::Intersex | Sex::Unknown => Self::Partner,
+        }
+    }
+}
+
 impl std::fmt::Display for SpouseRole {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -90,6 +121,144 @@ impl std::fmt::Display for ChildType {
     }
 }
 
+/// How a newly created person relates to the person they are added to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelativeKind {
+    Father,
+    Mother,
+    Spouse,
+    Child,
+    Sibling,
+}
+
+impl std::fmt::Display for RelativeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Father => write!(f, "father"),
+            Self::Mother => write!(f, "mother"),
+            Self::Spouse => write!(f, "spouse"),
+            Self::Child => write!(f, "child"),
+            Self::Sibling => write!(f, "sibling"),
+        }
+    }
+}
+
+/// How a person on a relationship path relates to the person before them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KinshipEdge {
+    Father,
+    Mother,
+    /// A parent of unknown sex.
+    Parent,
+    Son,
+    Daughter,
+    /// A child of unknown sex.
+    Child,
+}
+
+impl KinshipEdge {
+    /// The edge to a parent of sex `sex`.
+    pub fn parent(sex: Sex) -> Self {
+        match sex {
+            Sex::Male => Self::Father,
+            Sex::Female => Self::Mother,
+            Sex::Intersex | Sex::Unknown => Self::Parent,
+        }
+    }
+
+    /// The edge to a child of sex `sex`.
+    pub fn child(sex: Sex) -> Self {
+        match sex {
+            Sex::Male => Self::Son,
+            Sex::Female => Self::Daughter,
+            Sex::Intersex | Sex::Unknown => Self::Child,
+        }
+    }
+}
+
+impl std::fmt::Display for KinshipEdge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Father => write!(f, "father"),
+            Self::Mother => write!(f, "mother"),
+            Self::Parent => write!(f, "parent"),
+            Self::Son => write!(f, "son"),
+            Self::Daughter => write!(f, "daughter"),
+            Self::Child => write!(f, "child"),
+        }
+    }
+}
+
+/// Part a person plays in an event they are linked to as a participant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParticipantRole {
+    /// A principal besides the event's owning person (e.g. another member
+    /// of a census household).
+    Primary,
+    #[default]
+    Witness,
+    Godparent,
+    Informant,
+    Officiant,
+}
+
+impl ParticipantRole {
+    /// Infer the role from a GEDCOM `RELA` phrase (`Godmother`, `witness`)
+    /// or GEDCOM 7 `ROLE` value (`GODP`, `WITN`, `CLERGY`, `OFFICIATOR`).
+    /// Anything unrecognized is a witness, which is what the `ASSO`
+    /// structure mostly records.
+    pub fn from_relation(relation: &str) -> Self {
+        let relation = relation.trim().to_lowercase();
+        let has = |words: &[&str]| words.iter().any(|w| relation.contains(w));
+        if has(&["god", "sponsor", "parrain", "marraine"]) {
+            Self::Godparent
+        } else if has(&["inform", "declarant", "d\u{e9}clarant"]) {
+            Self::Informant
+        } else if has(&[
+            "offic",
+            "clergy",
+            "priest",
+            "minister",
+            "rabbi",
+            "pr\u{ea}tre",
+            "cur\u{e9}",
+        ]) {
+            Self::Officiant
+        } else if has(&["head", "principal", "primary"]) {
+            Self::Primary
+        } else {
+            Self::Witness
+        }
+    }
+
+    /// `RELA` text written on export when no free-text relation was
+    /// recorded.
+    pub fn gedcom_relation(self) -> &'static str {
+        match self {
+            Self::Primary => "Principal",
+            Self::Witness => "Witness",
+            Self::Godparent => "Godparent",
+            Self::Informant => "Informant",
+            Self::Officiant => "Officiant",
+        }
+    }
+}
+
+impl std::fmt::Display for ParticipantRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Primary => write!(f, "primary"),
+            Self::Witness => write!(f, "witness"),
+            Self::Godparent => write!(f, "godparent"),
+            Self::Informant => write!(f, "informant"),
+            Self::Officiant => write!(f, "officiant"),
+        }
+    }
+}
+
 /// Per-person privacy override (§7 of the person edit modal spec).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -246,64 +415,373 @@ pub enum EventType {
 }
 
 impl EventType {
+    /// Natural order of events recorded on the same date: birth, then
+    /// baptism, then anything else, then death, burial and probate.
+    pub fn chronological_rank(&self) -> u8 {
+        match self {
+            Self::Birth => 0,
+            Self::Baptism => 1,
+            Self::Death => 3,
+            Self::Burial | Self::Cremation => 4,
+            Self::Probate => 5,
+            _ => 2,
+        }
+    }
+
+    /// Returns `true` for moves, which may record a destination
+    /// (`Event::place_id_to`) besides the place they started from.
+    pub fn has_destination(&self) -> bool {
+        matches!(self, Self::Emigration | Self::Immigration | Self::Residence)
+    }
+
     /// Returns `true` if this event type applies to an individual person.
     pub fn is_individual(&self) -> bool {
-        matches!(
-            self,
-            Self::Birth
-                | Self::Death
-                | Self::Baptism
-                | Self::Confirmation
-                | Self::FirstCommunion
-                | Self::BarBatMitzvah
-                | Self::MilitaryService
-                | Self::Burial
-                | Self::Cremation
-                | Self::Graduation
-                | Self::Immigration
-                | Self::Emigration
-                | Self::Naturalization
-                | Self::Census
-                | Self::Occupation
-                | Self::Residence
-                | Self::Retirement
-                | Self::Will
-                | Self::Probate
-                | Self::Adoption
-                | Self::CasteName
-                | Self::PhysicalDescription
-                | Self::Education
-                | Self::NationalId
-                | Self::NationalOrigin
-                | Self::ChildrenCount
-                | Self::MarriagesCount
-                | Self::Property
-                | Self::Religion
-                | Self::SocialSecurityNumber
-                | Self::NobilityTitle
-                | Self::Fact
-        )
+        self.category() != EventCategory::Family && *self != Self::Other
     }
 
     /// Returns `true` if this event type applies to a family.
     pub fn is_family(&self) -> bool {
-        matches!(
-            self,
-            Self::Marriage
-                | Self::Divorce
-                | Self::Annulment
-                | Self::Engagement
-                | Self::MarriageBann
-                | Self::MarriageContract
-                | Self::MarriageLicense
-                | Self::MarriageSettlement
-                | Self::CivilUnion
-                | Self::Separation
-                | Self::DivorceFiled
-        )
+        self.category() == EventCategory::Family
+    }
+
+    /// This type's entry in [`EVENT_TYPES`].
+    pub fn info(&self) -> &'static EventTypeInfo {
+        EVENT_TYPES
+            .iter()
+            .find(|info| info.event_type == *self)
+            .expect("every event type is listed in EVENT_TYPES")
+    }
+
+    /// The group this type is listed under in pickers.
+    pub fn category(&self) -> EventCategory {
+        self.info().category
+    }
+
+    /// The GEDCOM tag of this type; `None` for types written as a generic
+    /// `EVEN`.
+    pub fn gedcom_tag(&self) -> Option<&'static str> {
+        self.info().gedcom_tag
+    }
+
+    /// The type named `name`, its variant name (e.g. `"FirstCommunion"`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        EVENT_TYPES
+            .iter()
+            .find(|info| info.name == name)
+            .map(|info| info.event_type)
+    }
+
+    /// The type with GEDCOM tag `tag`.
+    pub fn from_gedcom_tag(tag: &str) -> Option<Self> {
+        EVENT_TYPES
+            .iter()
+            .find(|info| info.gedcom_tag == Some(tag))
+            .map(|info| info.event_type)
     }
 }
 
+/// Group of event types, as event-type pickers list them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventCategory {
+    /// Birth, baptism, adoption, death and burial.
+    Vital,
+    /// Events of a couple: engagement, marriage, divorce...
+    Family,
+    /// Everything else: rites, career, moves, legal events, attributes.
+    Other,
+}
+
+impl std::fmt::Display for EventCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Vital => write!(f, "vital"),
+            Self::Family => write!(f, "family"),
+            Self::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// What the UI and GEDCOM import/export need to know about an event type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventTypeInfo {
+    pub event_type: EventType,
+    /// The variant name, used as the value of event-type `<select>`s.
+    pub name: &'static str,
+    pub category: EventCategory,
+    /// `None` for types written as a generic `EVEN` (with a `TYPE`).
+    pub gedcom_tag: Option<&'static str>,
+}
+
+/// Every event type, grouped by category and in the order pickers list
+/// them. The display label of a type is the `event.type.<type>` message.
+pub const EVENT_TYPES: &[EventTypeInfo] = &[
+    EventTypeInfo {
+        event_type: EventType::Birth,
+        name: "Birth",
+        category: EventCategory::Vital,
+        gedcom_tag: Some("BIRT"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Baptism,
+        name: "Baptism",
+        category: EventCategory::Vital,
+        gedcom_tag: Some("BAPM"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Adoption,
+        name: "Adoption",
+        category: EventCategory::Vital,
+        gedcom_tag: Some("ADOP"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Death,
+        name: "Death",
+        category: EventCategory::Vital,
+        gedcom_tag: Some("DEAT"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Burial,
+        name: "Burial",
+        category: EventCategory::Vital,
+        gedcom_tag: Some("BURI"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Cremation,
+        name: "Cremation",
+        category: EventCategory::Vital,
+        gedcom_tag: Some("CREM"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Engagement,
+        name: "Engagement",
+        category: EventCategory::Family,
+        gedcom_tag: Some("ENGA"),
+    },
+    EventTypeInfo {
+        event_type: EventType::MarriageBann,
+        name: "MarriageBann",
+        category: EventCategory::Family,
+        gedcom_tag: Some("MARB"),
+    },
+    EventTypeInfo {
+        event_type: EventType::MarriageContract,
+        name: "MarriageContract",
+        category: EventCategory::Family,
+        gedcom_tag: Some("MARC"),
+    },
+    EventTypeInfo {
+        event_type: EventType::MarriageLicense,
+        name: "MarriageLicense",
+        category: EventCategory::Family,
+        gedcom_tag: Some("MARL"),
+    },
+    EventTypeInfo {
+        event_type: EventType::MarriageSettlement,
+        name: "MarriageSettlement",
+        category: EventCategory::Family,
+        gedcom_tag: Some("MARS"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Marriage,
+        name: "Marriage",
+        category: EventCategory::Family,
+        gedcom_tag: Some("MARR"),
+    },
+    EventTypeInfo {
+        event_type: EventType::CivilUnion,
+        name: "CivilUnion",
+        category: EventCategory::Family,
+        gedcom_tag: None,
+    },
+    EventTypeInfo {
+        event_type: EventType::Separation,
+        name: "Separation",
+        category: EventCategory::Family,
+        gedcom_tag: Some("SEP"),
+    },
+    EventTypeInfo {
+        event_type: EventType::DivorceFiled,
+        name: "DivorceFiled",
+        category: EventCategory::Family,
+        gedcom_tag: Some("DIVF"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Divorce,
+        name: "Divorce",
+        category: EventCategory::Family,
+        gedcom_tag: Some("DIV"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Annulment,
+        name: "Annulment",
+        category: EventCategory::Family,
+        gedcom_tag: Some("ANUL"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Confirmation,
+        name: "Confirmation",
+        category: EventCategory::Other,
+        gedcom_tag: Some("CONF"),
+    },
+    EventTypeInfo {
+        event_type: EventType::FirstCommunion,
+        name: "FirstCommunion",
+        category: EventCategory::Other,
+        gedcom_tag: Some("FCOM"),
+    },
+    EventTypeInfo {
+        event_type: EventType::BarBatMitzvah,
+        name: "BarBatMitzvah",
+        category: EventCategory::Other,
+        gedcom_tag: Some("BARM"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Graduation,
+        name: "Graduation",
+        category: EventCategory::Other,
+        gedcom_tag: Some("GRAD"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Education,
+        name: "Education",
+        category: EventCategory::Other,
+        gedcom_tag: Some("EDUC"),
+    },
+    EventTypeInfo {
+        event_type: EventType::MilitaryService,
+        name: "MilitaryService",
+        category: EventCategory::Other,
+        gedcom_tag: None,
+    },
+    EventTypeInfo {
+        event_type: EventType::Occupation,
+        name: "Occupation",
+        category: EventCategory::Other,
+        gedcom_tag: Some("OCCU"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Retirement,
+        name: "Retirement",
+        category: EventCategory::Other,
+        gedcom_tag: Some("RETI"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Residence,
+        name: "Residence",
+        category: EventCategory::Other,
+        gedcom_tag: Some("RESI"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Census,
+        name: "Census",
+        category: EventCategory::Other,
+        gedcom_tag: Some("CENS"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Emigration,
+        name: "Emigration",
+        category: EventCategory::Other,
+        gedcom_tag: Some("EMIG"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Immigration,
+        name: "Immigration",
+        category: EventCategory::Other,
+        gedcom_tag: Some("IMMI"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Naturalization,
+        name: "Naturalization",
+        category: EventCategory::Other,
+        gedcom_tag: Some("NATU"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Will,
+        name: "Will",
+        category: EventCategory::Other,
+        gedcom_tag: Some("WILL"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Probate,
+        name: "Probate",
+        category: EventCategory::Other,
+        gedcom_tag: Some("PROB"),
+    },
+    EventTypeInfo {
+        event_type: EventType::CasteName,
+        name: "CasteName",
+        category: EventCategory::Other,
+        gedcom_tag: Some("CAST"),
+    },
+    EventTypeInfo {
+        event_type: EventType::PhysicalDescription,
+        name: "PhysicalDescription",
+        category: EventCategory::Other,
+        gedcom_tag: Some("DSCR"),
+    },
+    EventTypeInfo {
+        event_type: EventType::NationalId,
+        name: "NationalId",
+        category: EventCategory::Other,
+        gedcom_tag: Some("IDNO"),
+    },
+    EventTypeInfo {
+        event_type: EventType::NationalOrigin,
+        name: "NationalOrigin",
+        category: EventCategory::Other,
+        gedcom_tag: Some("NATI"),
+    },
+    EventTypeInfo {
+        event_type: EventType::ChildrenCount,
+        name: "ChildrenCount",
+        category: EventCategory::Other,
+        gedcom_tag: Some("NCHI"),
+    },
+    EventTypeInfo {
+        event_type: EventType::MarriagesCount,
+        name: "MarriagesCount",
+        category: EventCategory::Other,
+        gedcom_tag: Some("NMR"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Property,
+        name: "Property",
+        category: EventCategory::Other,
+        gedcom_tag: Some("PROP"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Religion,
+        name: "Religion",
+        category: EventCategory::Other,
+        gedcom_tag: Some("RELI"),
+    },
+    EventTypeInfo {
+        event_type: EventType::SocialSecurityNumber,
+        name: "SocialSecurityNumber",
+        category: EventCategory::Other,
+        gedcom_tag: Some("SSN"),
+    },
+    EventTypeInfo {
+        event_type: EventType::NobilityTitle,
+        name: "NobilityTitle",
+        category: EventCategory::Other,
+        gedcom_tag: Some("TITL"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Fact,
+        name: "Fact",
+        category: EventCategory::Other,
+        gedcom_tag: Some("FACT"),
+    },
+    EventTypeInfo {
+        event_type: EventType::Other,
+        name: "Other",
+        category: EventCategory::Other,
+        gedcom_tag: None,
+    },
+];
+
 impl std::fmt::Display for EventType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -378,10 +856,157 @@ impl std::fmt::Display for Confidence {
     }
 }
 
+/// The single fact a citation supports, when it does not cite a whole
+/// person, event or family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FactType {
+    /// One of the person's names; the citation's `fact_ref` is the
+    /// `PersonName` id.
+    Name,
+    /// The event's date.
+    Date,
+    /// The event's place.
+    Place,
+    /// The event's description (e.g. the occupation text).
+    Description,
+    /// The principal's age recorded at the event.
+    Age,
+}
+
+impl FactType {
+    /// `true` for the facts that are fields of the cited event.
+    pub fn is_event_field(self) -> bool {
+        !matches!(self, Self::Name)
+    }
+}
+
+impl std::fmt::Display for FactType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Name => write!(f, "name"),
+            Self::Date => write!(f, "date"),
+            Self::Place => write!(f, "place"),
+            Self::Description => write!(f, "description"),
+            Self::Age => write!(f, "age"),
+        }
+    }
+}
+
+/// How a note's text is meant to be displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoteFormat {
+    /// Shown verbatim, line breaks preserved.
+    #[default]
+    Plain,
+    /// CommonMark, rendered to sanitized HTML by the UI.
+    Markdown,
+}
+
+impl std::fmt::Display for NoteFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Plain => write!(f, "plain"),
+            Self::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+/// Whether a research task still needs doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    #[default]
+    Open,
+    Done,
+}
+
+impl std::fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Open => write!(f, "open"),
+            Self::Done => write!(f, "done"),
+        }
+    }
+}
+
+/// Kind of identifier another program gave a record, kept so re-imports
+/// and round-trips can recognise it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalIdKind {
+    /// Globally unique id: GEDCOM 5.5.1 `_UID` (most programs) or GEDCOM 7
+    /// `UID`.
+    Uid,
+    /// User reference number: GEDCOM `REFN`, optionally typed.
+    Refn,
+}
+
+impl std::fmt::Display for ExternalIdKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Uid => write!(f, "uid"),
+            Self::Refn => write!(f, "refn"),
+        }
+    }
+}
+
+/// An actor's role on a tree. Roles are ordered: each one can do all that
+/// the roles before it can.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TreeRole {
+    /// Read the tree.
+    Viewer,
+    /// Also create, edit and delete its records.
+    Editor,
+    /// Also share or delete the tree and manage its members.
+    Owner,
+}
+
+impl std::fmt::Display for TreeRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Viewer => write!(f, "viewer"),
+            Self::Editor => write!(f, "editor"),
+            Self::Owner => write!(f, "owner"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_participant_role_from_relation() {
+        assert_eq!(
+            ParticipantRole::from_relation("Godmother"),
+            ParticipantRole::Godparent
+        );
+        assert_eq!(
+            ParticipantRole::from_relation("GODP"),
+            ParticipantRole::Godparent
+        );
+        assert_eq!(
+            ParticipantRole::from_relation("OFFICIATOR"),
+            ParticipantRole::Officiant
+        );
+        assert_eq!(
+            ParticipantRole::from_relation("Informant"),
+            ParticipantRole::Informant
+        );
+        assert_eq!(
+            ParticipantRole::from_relation("WITN"),
+            ParticipantRole::Witness
+        );
+        assert_eq!(
+            ParticipantRole::from_relation("friend"),
+            ParticipantRole::Witness
+        );
+    }
+
     #[test]
     fn test_event_type_individual() {
         assert!(EventType::Birth.is_individual());
@@ -398,6 +1023,24 @@ mod tests {
         assert!(!EventType::Other.is_family());
     }
 
+    #[test]
+    fn test_event_types_table_lists_every_type_once() {
+        for info in EVENT_TYPES {
+            assert_eq!(info.event_type.info(), info);
+            assert_eq!(EventType::from_name(info.name), Some(info.event_type));
+            assert_eq!(format!("{:?}", info.event_type), info.name);
+            if let Some(tag) = info.gedcom_tag {
+                assert_eq!(EventType::from_gedcom_tag(tag), Some(info.event_type));
+            }
+        }
+        let unique: std::collections::HashSet<EventType> =
+            EVENT_TYPES.iter().map(|i| i.event_type).collect();
+        assert_eq!(unique.len(), EVENT_TYPES.len());
+        assert_eq!(EventType::Marriage.category(), EventCategory::Family);
+        assert_eq!(EventType::Baptism.gedcom_tag(), Some("BAPM"));
+        assert_eq!(EventType::CivilUnion.gedcom_tag(), None);
+    }
+
     #[test]
     fn test_adoption_is_individual_not_family() {
         // GEDCOM 5.5.1 `ADOP` is an individual-level event that may
@@ -406,10 +1049,19 @@ mod tests {
         assert!(!EventType::Adoption.is_family());
     }
 
+    #[test]
+    fn test_spouse_role_for_sex() {
+        assert_eq!(SpouseRole::for_sex(Sex::Male), SpouseRole::Husband);
+        assert_eq!(SpouseRole::for_sex(Sex::Female), SpouseRole::Wife);
+        assert_eq!(SpouseRole::for_sex(Sex::Intersex), SpouseRole::Partner);
+        assert_eq!(SpouseRole::for_sex(Sex::Unknown), SpouseRole::Partner);
+    }
+
     #[test]
     fn test_sex_display() {
         assert_eq!(Sex::Male.to_string(), "male");
         assert_eq!(Sex::Female.to_string(), "female");
+        assert_eq!(Sex::Intersex.to_string(), "intersex");
         assert_eq!(Sex::Unknown.to_string(), "unknown");
     }
 
@@ -429,6 +1081,16 @@ mod tests {
         assert_eq!(deserialized, EventType::MarriageBann);
     }
 
+    #[test]
+    fn test_tree_role_order() {
+        assert!(TreeRole::Viewer < TreeRole::Editor);
+        assert!(TreeRole::Editor < TreeRole::Owner);
+        assert_eq!(
+            serde_json::from_str::<TreeRole>(r#""editor""#).unwrap(),
+            TreeRole::Editor
+        );
+    }
+
     #[test]
     fn test_confidence_serde_roundtrip() {
         let json = serde_json::to_string(&Confidence::VeryHigh).unwrap();
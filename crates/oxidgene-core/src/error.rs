@@ -14,6 +14,23 @@ pub enum OxidGeneError {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    /// The entity changed since the client read it (stale version).
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    /// The entity is locked against edits (see `Person::locked`).
+    #[error("{entity} with id {id} is locked")]
+    Locked { entity: &'static str, id: Uuid },
+
+    /// The request carries no valid credentials (bearer-token
+    /// authentication enabled).
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// The actor's role on the tree does not allow the operation.
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     /// Database error.
     #[error("Database error: {0}")]
     Database(String),
@@ -0,0 +1,190 @@
+//! Edit-distance helpers for typo-tolerant name search.
+//!
+//! Inputs are expected to be normalized already (see
+//! [`crate::search::normalize_for_search`]), so case and accents never count
+//! as edits.
+
+use crate::search::normalize_for_search;
+
+/// Levenshtein edit distance between two strings, counted in characters.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Distance between a search query and a person's name.
+///
+/// Each query word is matched against its closest word in the name and the
+/// distances are summed, so word order and extra name words (middle names,
+/// a surname the user did not type) cost nothing: `"catherine"` is 1 away
+/// from `"katherine marie dupont"`.
+pub fn name_distance(query: &str, name: &str) -> usize {
+    let name_words: Vec<&str> = name.split_whitespace().collect();
+    query
+        .split_whitespace()
+        .map(|q| {
+            name_words
+                .iter()
+                .map(|n| levenshtein(q, n))
+                .min()
+                .unwrap_or(q.chars().count())
+        })
+        .sum()
+}
+
+/// Coarse phonetic class of a name's first letter.
+///
+/// Letters that commonly stand in for each other at the start of a name
+/// share a bucket (`c`/`k`/`q`/`s`/..., `b`/`p`/`f`/`v`, all vowels), which
+/// lets a search pre-filter candidates cheaply without missing
+/// "Catherine" → "Katherine". Returns the bucket's representative letter.
+pub fn phonetic_bucket(c: char) -> char {
+    match c {
+        'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'h' | 'w' => 'a',
+        'b' | 'p' | 'f' | 'v' => 'b',
+        'c' | 'k' | 'q' | 'g' | 'j' | 's' | 'x' | 'z' => 'c',
+        'd' | 't' => 'd',
+        'm' | 'n' => 'm',
+        other => other,
+    }
+}
+
+/// All letters sharing `c`'s phonetic bucket, `c` included.
+pub fn bucket_letters(c: char) -> Vec<char> {
+    let bucket = phonetic_bucket(c);
+    let mut letters: Vec<char> = ('a'..='z')
+        .filter(|&l| phonetic_bucket(l) == bucket)
+        .collect();
+    if !letters.contains(&c) {
+        letters.push(c);
+    }
+    letters
+}
+
+/// American Soundex code of a word: its first letter, uppercased, then the
+/// digits of the consonants that follow (`Robert` → `R163`), padded or cut
+/// to four characters.
+///
+/// Only ASCII letters count, so the word should be normalized first.
+/// Returns `None` when it has none.
+pub fn soundex(word: &str) -> Option<String> {
+    fn digit(c: char) -> Option<char> {
+        match c {
+            'b' | 'f' | 'p' | 'v' => Some('1'),
+            'c' | 'g' | 'j' | 'k' | 'q' | 's' | 'x' | 'z' => Some('2'),
+            'd' | 't' => Some('3'),
+            'l' => Some('4'),
+            'm' | 'n' => Some('5'),
+            'r' => Some('6'),
+            _ => None,
+        }
+    }
+
+    let mut letters = word
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_lowercase());
+    let first = letters.next()?;
+    let mut code = String::with_capacity(4);
+    code.push(first.to_ascii_uppercase());
+    let mut last = digit(first);
+    for c in letters {
+        // `h` and `w` do not separate two consonants with the same digit;
+        // vowels do.
+        if c == 'h' || c == 'w' {
+            continue;
+        }
+        let d = digit(c);
+        if let Some(d) = d
+            && last != Some(d)
+        {
+            code.push(d);
+            if code.len() == 4 {
+                break;
+            }
+        }
+        last = d;
+    }
+    while code.len() < 4 {
+        code.push('0');
+    }
+    Some(code)
+}
+
+/// Phonetic code stored for a surname: the [`soundex`] of all its words run
+/// together, so `"de La Fontaine"` and `"Delafontaine"` share one.
+pub fn surname_phonetic(surname: &str) -> Option<String> {
+    soundex(&normalize_for_search(surname))
+}
+
+/// Phonetic code stored for given names: the [`soundex`] of the first one,
+/// the name a person is usually called by.
+pub fn given_phonetic(given_names: &str) -> Option<String> {
+    normalize_for_search(given_names)
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .find_map(soundex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("catherine", "katherine"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("élise", "elise"), 1);
+    }
+
+    #[test]
+    fn test_name_distance() {
+        assert_eq!(name_distance("catherine", "katherine marie dupont"), 1);
+        assert_eq!(name_distance("dupond catherine", "katherine dupont"), 2);
+        assert_eq!(name_distance("jean", ""), 4);
+    }
+
+    #[test]
+    fn test_phonetic_bucket() {
+        assert_eq!(phonetic_bucket('c'), phonetic_bucket('k'));
+        assert_eq!(phonetic_bucket('f'), phonetic_bucket('p'));
+        assert_ne!(phonetic_bucket('c'), phonetic_bucket('d'));
+        assert!(bucket_letters('k').contains(&'c'));
+        assert_eq!(bucket_letters('l'), vec!['l']);
+    }
+
+    #[test]
+    fn test_soundex() {
+        assert_eq!(soundex("robert").as_deref(), Some("R163"));
+        assert_eq!(soundex("rupert").as_deref(), Some("R163"));
+        assert_eq!(soundex("ashcraft").as_deref(), Some("A261"));
+        assert_eq!(soundex("tymczak").as_deref(), Some("T522"));
+        assert_eq!(soundex("pfister").as_deref(), Some("P236"));
+        assert_eq!(soundex("lee").as_deref(), Some("L000"));
+        assert_eq!(soundex("42"), None);
+    }
+
+    #[test]
+    fn test_name_phonetic() {
+        assert_eq!(surname_phonetic("Dupont"), surname_phonetic("Dupond"));
+        assert_eq!(
+            surname_phonetic("de La Fontaine"),
+            surname_phonetic("Delafontaine")
+        );
+        assert_eq!(given_phonetic("Jean-Marie"), given_phonetic("Jehan"));
+        assert_eq!(given_phonetic("Éloïse Marie").as_deref(), Some("E420"));
+        assert_eq!(given_phonetic("  "), None);
+    }
+}
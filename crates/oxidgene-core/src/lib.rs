@@ -3,10 +3,21 @@
 //! This crate contains the foundational types used across all other OxidGene crates.
 //! It has no internal dependencies on other workspace crates.
 
+pub mod calendar;
+pub mod chronology;
+pub mod compare;
 pub mod enums;
 pub mod error;
+pub mod fuzzy;
+pub mod numbering;
+pub mod privacy;
+pub mod replace;
 pub mod search;
+pub mod summary;
+pub mod templates;
+pub mod text;
 pub mod types;
+pub mod validation;
 
 pub use enums::*;
 pub use error::OxidGeneError;
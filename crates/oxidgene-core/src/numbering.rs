@@ -0,0 +1,337 @@
+//! Genealogical numbering systems for reports.
+//!
+//! - **Ahnentafel** (SOSA-Stradonitz) numbers ancestors: the root is 1, the
+//!   father of `n` is `2n` and the mother `2n + 1`. Numbers of unknown
+//!   ancestors are left out rather than reassigned, so the gaps show where
+//!   research is missing.
+//! - **d'Aboville** numbers descendants: the root is `1`, its children
+//!   `1.1`, `1.2`, ..., their children `1.1.1`, and so on.
+//! - **Henry** numbers descendants the same way without separators
+//!   (`1`, `11`, `12`, `111`); a tenth or later child is written in
+//!   parentheses (`1(10)`), as in the modified Henry system.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::{ChildType, SpouseRole};
+use crate::types::{FamilyChild, FamilySpouse, PersonAncestry};
+
+/// Deepest generation an Ahnentafel number still fits in a `u64` for.
+pub const MAX_AHNENTAFEL_DEPTH: i32 = 63;
+
+/// Numbering system for descendant reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DescendantNumbering {
+    #[default]
+    #[serde(rename = "daboville")]
+    DAboville,
+    Henry,
+}
+
+/// An ancestor with its Ahnentafel number.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AhnentafelEntry {
+    pub number: u64,
+    /// 1 for the root person, 2 for the parents, ...
+    pub generation: u32,
+    pub person_id: Uuid,
+}
+
+/// A descendant with its d'Aboville or Henry number.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DescendantEntry {
+    pub number: String,
+    /// 1 for the root person, 2 for the children, ...
+    pub generation: u32,
+    pub person_id: Uuid,
+}
+
+/// Parent and child links of a tree, indexed for walking generations.
+#[derive(Debug, Default)]
+pub struct Lineage {
+    /// Family a person was born into, biological links preferred.
+    birth_family: HashMap<Uuid, Uuid>,
+    /// `(father, mother)` of each family.
+    parents: HashMap<Uuid, (Option<Uuid>, Option<Uuid>)>,
+    /// Families a person is a spouse in, in their spouse order.
+    spouse_families: HashMap<Uuid, Vec<Uuid>>,
+    /// Children of each family, in birth order.
+    children: HashMap<Uuid, Vec<Uuid>>,
+}
+
+impl Lineage {
+    pub fn new(spouses: &[FamilySpouse], children: &[FamilyChild]) -> Self {
+        let mut lineage = Self::default();
+
+        let mut spouses: Vec<&FamilySpouse> = spouses.iter().collect();
+        spouses.sort_by_key(|s| s.sort_order);
+        for s in &spouses {
+            lineage
+                .spouse_families
+                .entry(s.person_id)
+                .or_default()
+                .push(s.family_id);
+            let (father, mother) = lineage.parents.entry(s.family_id).or_default();
+            match s.role {
+                SpouseRole::Husband => *father = Some(s.person_id),
+                SpouseRole::Wife => *mother = Some(s.person_id),
+                SpouseRole::Partner => {}
+            }
+        }
+        // Partners fill whichever parent slot the family has left.
+        for s in spouses.iter().filter(|s| s.role == SpouseRole::Partner) {
+            let (father, mother) = lineage.parents.entry(s.family_id).or_default();
+            if father.is_none() {
+                *father = Some(s.person_id);
+            } else if mother.is_none() {
+                *mother = Some(s.person_id);
+            }
+        }
+
+        let mut children: Vec<&FamilyChild> = children.iter().collect();
+        children.sort_by_key(|c| c.sort_order);
+        for c in children {
+            lineage
+                .children
+                .entry(c.family_id)
+                .or_default()
+                .push(c.person_id);
+            if c.child_type == ChildType::Biological {
+                lineage.birth_family.insert(c.person_id, c.family_id);
+            } else {
+                lineage
+                    .birth_family
+                    .entry(c.person_id)
+                    .or_insert(c.family_id);
+            }
+        }
+        lineage
+    }
+
+    fn parents_of(&self, person_id: Uuid) -> (Option<Uuid>, Option<Uuid>) {
+        self.birth_family
+            .get(&person_id)
+            .and_then(|family_id| self.parents.get(family_id))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn children_of(&self, person_id: Uuid) -> impl Iterator<Item = Uuid> + '_ {
+        self.spouse_families
+            .get(&person_id)
+            .into_iter()
+            .flatten()
+            .flat_map(|family_id| self.children.get(family_id).into_iter().flatten())
+            .copied()
+    }
+}
+
+/// Ahnentafel numbers of `root` and of its ancestors in `ancestors` (rows
+/// of the ancestry closure with `root` as descendant), in number order.
+///
+/// A person reached through several lines (pedigree collapse) is listed
+/// under each of their numbers.
+pub fn ahnentafel(
+    root: Uuid,
+    ancestors: &[PersonAncestry],
+    lineage: &Lineage,
+) -> Vec<AhnentafelEntry> {
+    let known: HashSet<Uuid> = ancestors.iter().map(|a| a.ancestor_id).collect();
+    let max_depth = ancestors
+        .iter()
+        .map(|a| a.depth)
+        .max()
+        .unwrap_or(0)
+        .min(MAX_AHNENTAFEL_DEPTH);
+
+    let mut entries = vec![AhnentafelEntry {
+        number: 1,
+        generation: 1,
+        person_id: root,
+    }];
+    let mut generation = vec![(1u64, root)];
+    for depth in 1..=max_depth {
+        let mut next = Vec::new();
+        for &(number, person_id) in &generation {
+            let (father, mother) = lineage.parents_of(person_id);
+            for (parent, parent_number) in [(father, number * 2), (mother, number * 2 + 1)] {
+                if let Some(parent) = parent.filter(|p| known.contains(p)) {
+                    next.push((parent_number, parent));
+                }
+            }
+        }
+        entries.extend(next.iter().map(|&(number, person_id)| AhnentafelEntry {
+            number,
+            generation: depth as u32 + 1,
+            person_id,
+        }));
+        generation = next;
+    }
+    entries
+}
+
+/// Descendant numbers of `root` and of its descendants in `descendants`
+/// (rows of the ancestry closure with `root` as ancestor), in report order:
+/// each person is followed by their own descendants before their next
+/// sibling.
+///
+/// A person descending from `root` through several lines is numbered on
+/// the first line only.
+pub fn descendant_numbers(
+    root: Uuid,
+    descendants: &[PersonAncestry],
+    lineage: &Lineage,
+    system: DescendantNumbering,
+) -> Vec<DescendantEntry> {
+    let known: HashSet<Uuid> = descendants.iter().map(|d| d.descendant_id).collect();
+    let mut entries = Vec::new();
+    let mut seen = HashSet::from([root]);
+    let mut stack = vec![(root, "1".to_string(), 1u32)];
+    while let Some((person_id, number, generation)) = stack.pop() {
+        let children: Vec<Uuid> = lineage
+            .children_of(person_id)
+            .filter(|c| known.contains(c) && seen.insert(*c))
+            .collect();
+        // Pushed in reverse so the eldest child is numbered first.
+        for (idx, child) in children.into_iter().enumerate().rev() {
+            let child_number = format!("{number}{}", child_suffix(idx + 1, system));
+            stack.push((child, child_number, generation + 1));
+        }
+        entries.push(DescendantEntry {
+            number,
+            generation,
+            person_id,
+        });
+    }
+    entries
+}
+
+fn child_suffix(position: usize, system: DescendantNumbering) -> String {
+    match system {
+        DescendantNumbering::DAboville => format!(".{position}"),
+        DescendantNumbering::Henry if position < 10 => position.to_string(),
+        DescendantNumbering::Henry => format!("({position})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spouse(family_id: Uuid, person_id: Uuid, role: SpouseRole) -> FamilySpouse {
+        FamilySpouse {
+            id: Uuid::now_v7(),
+            family_id,
+            person_id,
+            role,
+            sort_order: 0,
+        }
+    }
+
+    fn child(family_id: Uuid, person_id: Uuid, sort_order: i32) -> FamilyChild {
+        FamilyChild {
+            id: Uuid::now_v7(),
+            family_id,
+            person_id,
+            child_type: ChildType::Biological,
+            sort_order,
+        }
+    }
+
+    fn closure(pairs: &[(Uuid, Uuid, i32)]) -> Vec<PersonAncestry> {
+        pairs
+            .iter()
+            .map(|&(ancestor_id, descendant_id, depth)| PersonAncestry {
+                id: Uuid::now_v7(),
+                tree_id: Uuid::nil(),
+                ancestor_id,
+                descendant_id,
+                depth,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ahnentafel_leaves_gaps_for_unknown_ancestors() {
+        let [root, father, mother, maternal_grandmother] = [(); 4].map(|_| Uuid::now_v7());
+        let [f1, f2] = [(); 2].map(|_| Uuid::now_v7());
+        let lineage = Lineage::new(
+            &[
+                spouse(f1, father, SpouseRole::Husband),
+                spouse(f1, mother, SpouseRole::Wife),
+                spouse(f2, maternal_grandmother, SpouseRole::Wife),
+            ],
+            &[child(f1, root, 0), child(f2, mother, 0)],
+        );
+        let ancestors = closure(&[
+            (father, root, 1),
+            (mother, root, 1),
+            (maternal_grandmother, root, 2),
+        ]);
+
+        let numbers: Vec<(u64, u32, Uuid)> = ahnentafel(root, &ancestors, &lineage)
+            .into_iter()
+            .map(|e| (e.number, e.generation, e.person_id))
+            .collect();
+        assert_eq!(
+            numbers,
+            vec![
+                (1, 1, root),
+                (2, 2, father),
+                (3, 2, mother),
+                // 4, 5 (father's parents) and 6 (mother's father) unknown.
+                (7, 3, maternal_grandmother),
+            ]
+        );
+    }
+
+    #[test]
+    fn descendants_are_numbered_depth_first_in_birth_order() {
+        let [root, wife, first, second, grandchild] = [(); 5].map(|_| Uuid::now_v7());
+        let [f1, f2] = [(); 2].map(|_| Uuid::now_v7());
+        let lineage = Lineage::new(
+            &[
+                spouse(f1, root, SpouseRole::Husband),
+                spouse(f1, wife, SpouseRole::Wife),
+                spouse(f2, first, SpouseRole::Wife),
+            ],
+            &[
+                child(f1, second, 1),
+                child(f1, first, 0),
+                child(f2, grandchild, 0),
+            ],
+        );
+        let descendants = closure(&[(root, first, 1), (root, second, 1), (root, grandchild, 2)]);
+
+        let numbers = |system| -> Vec<(String, u32, Uuid)> {
+            descendant_numbers(root, &descendants, &lineage, system)
+                .into_iter()
+                .map(|e| (e.number, e.generation, e.person_id))
+                .collect()
+        };
+        assert_eq!(
+            numbers(DescendantNumbering::DAboville),
+            vec![
+                ("1".to_string(), 1, root),
+                ("1.1".to_string(), 2, first),
+                ("1.1.1".to_string(), 3, grandchild),
+                ("1.2".to_string(), 2, second),
+            ]
+        );
+        let henry: Vec<String> = numbers(DescendantNumbering::Henry)
+            .into_iter()
+            .map(|(number, ..)| number)
+            .collect();
+        assert_eq!(henry, ["1", "11", "111", "12"]);
+    }
+
+    #[test]
+    fn henry_wraps_tenth_child_in_parentheses() {
+        assert_eq!(child_suffix(9, DescendantNumbering::Henry), "9");
+        assert_eq!(child_suffix(10, DescendantNumbering::Henry), "(10)");
+        assert_eq!(child_suffix(10, DescendantNumbering::DAboville), ".10");
+    }
+}
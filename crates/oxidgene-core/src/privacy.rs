@@ -0,0 +1,139 @@
+//! Privacy rules for public views of a tree (share links).
+//!
+//! A person is hidden when their own [`Privacy`] says so, or — for
+//! `Privacy::Default` — when the view privatizes the living and the person
+//! is presumed alive.
+
+use crate::enums::{EventType, Privacy};
+use crate::types::Event;
+
+/// Persons born (or baptized) more than this many years ago are presumed
+/// dead even without a recorded death.
+pub const LIVING_MAX_AGE: i32 = 100;
+
+/// Whether a person is presumed alive.
+///
+/// An explicit `living` status (see [`Person::living`]) is taken as is.
+/// Otherwise it is judged from the person's own events: anything that
+/// marks the end of a life (death, burial, cremation, probate) means
+/// deceased, and so does a birth or baptism dated more than
+/// [`LIVING_MAX_AGE`] years before `current_year`. Persons with no usable
+/// date count as living: over-redacting is the safe failure mode.
+///
+/// [`Person::living`]: crate::types::Person::living
+pub fn is_living<'a>(
+    living: Option<bool>,
+    events: impl IntoIterator<Item = &'a Event>,
+    current_year: i32,
+) -> bool {
+    if let Some(living) = living {
+        return living;
+    }
+    let mut earliest_birth: Option<i32> = None;
+    for event in events {
+        match event.event_type {
+            EventType::Death | EventType::Burial | EventType::Cremation | EventType::Probate => {
+                return false;
+            }
+            EventType::Birth | EventType::Baptism => {
+                if let Some(year) = event.year() {
+                    earliest_birth = Some(earliest_birth.map_or(year, |y| y.min(year)));
+                }
+            }
+            _ => {}
+        }
+    }
+    earliest_birth.is_none_or(|year| current_year - year <= LIVING_MAX_AGE)
+}
+
+/// Whether a person must be hidden from a public view. An explicit
+/// `Private`/`Public` always wins; `Default` hides the living when
+/// `privatize_living` is set.
+pub fn is_hidden(privacy: Privacy, living: bool, privatize_living: bool) -> bool {
+    match privacy {
+        Privacy::Private => true,
+        Privacy::Public => false,
+        Privacy::Default => privatize_living && living,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Calendar, DateQualifier};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn event(event_type: EventType, date_value: Option<&str>) -> Event {
+        let now = Utc::now();
+        Event {
+            id: Uuid::now_v7(),
+            tree_id: Uuid::now_v7(),
+            event_type,
+            date_value: date_value.map(str::to_string),
+            date_sort: None,
+            date_qualifier: DateQualifier::default(),
+            date_value2: None,
+            calendar: Calendar::default(),
+            cause: None,
+            age: None,
+            place_id: None,
+            place_id_to: None,
+            person_id: None,
+            family_id: None,
+            description: None,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_is_living() {
+        assert!(is_living(None, &[], 2026));
+        assert!(is_living(
+            None,
+            &[event(EventType::Birth, Some("1990"))],
+            2026
+        ));
+        assert!(!is_living(
+            None,
+            &[event(EventType::Birth, Some("1850"))],
+            2026
+        ));
+        assert!(!is_living(
+            None,
+            &[
+                event(EventType::Birth, Some("1990")),
+                event(EventType::Death, None)
+            ],
+            2026
+        ));
+        assert!(!is_living(
+            None,
+            &[event(EventType::Baptism, Some("12 MAR 1901"))],
+            2026
+        ));
+    }
+
+    #[test]
+    fn test_is_living_override() {
+        // An undated 1700s ancestor the heuristic would keep hidden.
+        assert!(!is_living(Some(false), &[], 2026));
+        assert!(is_living(
+            Some(true),
+            &[event(EventType::Birth, Some("1850"))],
+            2026
+        ));
+    }
+
+    #[test]
+    fn test_is_hidden() {
+        assert!(is_hidden(Privacy::Private, false, false));
+        assert!(!is_hidden(Privacy::Public, true, true));
+        assert!(is_hidden(Privacy::Default, true, true));
+        assert!(!is_hidden(Privacy::Default, true, false));
+        assert!(!is_hidden(Privacy::Default, false, true));
+    }
+}
@@ -0,0 +1,191 @@
+//! Tree-wide find-and-replace on the text fields of places and sources —
+//! renaming "St. Louis" to "Saint Louis" everywhere, or expanding an
+//! abbreviation an import left in every source title.
+//!
+//! A [`Replacer`] either swaps whole values equal to the search text, or
+//! rewrites every match of a regular expression (with `$1`-style group
+//! references in the replacement).
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::OxidGeneError;
+
+/// The type of entity a replacement applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplaceEntity {
+    Place,
+    Source,
+}
+
+/// A text field that can be replaced across a tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplaceField {
+    PlaceName,
+    SourceTitle,
+    SourceAuthor,
+    SourcePublisher,
+    SourceAbbreviation,
+    SourceRepositoryName,
+}
+
+impl ReplaceField {
+    const ALL: [Self; 6] = [
+        Self::PlaceName,
+        Self::SourceTitle,
+        Self::SourceAuthor,
+        Self::SourcePublisher,
+        Self::SourceAbbreviation,
+        Self::SourceRepositoryName,
+    ];
+
+    /// The field of `entity` named `field`, if it can be replaced.
+    pub fn of(entity: ReplaceEntity, field: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|f| f.entity() == entity && f.as_str() == field)
+    }
+
+    /// Names of the replaceable fields of `entity`.
+    pub fn names(entity: ReplaceEntity) -> Vec<&'static str> {
+        Self::ALL
+            .into_iter()
+            .filter(|f| f.entity() == entity)
+            .map(Self::as_str)
+            .collect()
+    }
+
+    pub fn entity(self) -> ReplaceEntity {
+        match self {
+            Self::PlaceName => ReplaceEntity::Place,
+            _ => ReplaceEntity::Source,
+        }
+    }
+
+    /// The field's name, as its column is called.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PlaceName => "name",
+            Self::SourceTitle => "title",
+            Self::SourceAuthor => "author",
+            Self::SourcePublisher => "publisher",
+            Self::SourceAbbreviation => "abbreviation",
+            Self::SourceRepositoryName => "repository_name",
+        }
+    }
+
+    /// Whether the field must keep a value (a place's name, a source's
+    /// title); the others are cleared when replaced by an empty string.
+    pub fn is_required(self) -> bool {
+        matches!(self, Self::PlaceName | Self::SourceTitle)
+    }
+}
+
+/// How the search text is matched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchMode {
+    /// The whole value equals the search text.
+    #[default]
+    Exact,
+    /// The search text is a regular expression; every match is replaced.
+    Regex,
+}
+
+#[derive(Debug, Clone)]
+enum Pattern {
+    Exact(String),
+    Regex(Regex),
+}
+
+/// A compiled find-and-replace.
+#[derive(Debug, Clone)]
+pub struct Replacer {
+    pattern: Pattern,
+    replacement: String,
+}
+
+impl Replacer {
+    /// # Errors
+    ///
+    /// Returns [`OxidGeneError::Validation`] when `find` is empty or is not
+    /// a valid regular expression.
+    pub fn new(mode: MatchMode, find: &str, replacement: &str) -> Result<Self, OxidGeneError> {
+        if find.is_empty() {
+            return Err(OxidGeneError::Validation(
+                "the search text must not be empty".to_string(),
+            ));
+        }
+        let pattern = match mode {
+            MatchMode::Exact => Pattern::Exact(find.to_string()),
+            MatchMode::Regex => Pattern::Regex(Regex::new(find).map_err(|e| {
+                OxidGeneError::Validation(format!("invalid regular expression: {e}"))
+            })?),
+        };
+        Ok(Self {
+            pattern,
+            replacement: replacement.to_string(),
+        })
+    }
+
+    /// The value after replacement, or `None` when nothing matched or the
+    /// replacement leaves it unchanged.
+    pub fn apply(&self, value: &str) -> Option<String> {
+        let replaced = match &self.pattern {
+            Pattern::Exact(find) if value == find => self.replacement.clone(),
+            Pattern::Exact(_) => return None,
+            Pattern::Regex(regex) => regex.replace_all(value, &self.replacement).into_owned(),
+        };
+        (replaced != value).then_some(replaced)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_replaces_whole_values_only() {
+        let r = Replacer::new(MatchMode::Exact, "St. Louis", "Saint Louis").unwrap();
+        assert_eq!(r.apply("St. Louis").as_deref(), Some("Saint Louis"));
+        assert_eq!(r.apply("St. Louis, Missouri"), None);
+        assert_eq!(r.apply("st. louis"), None);
+    }
+
+    #[test]
+    fn test_regex_replaces_every_match_with_groups() {
+        let r = Replacer::new(MatchMode::Regex, r"\bSt\. (\w+)", "Saint $1").unwrap();
+        assert_eq!(
+            r.apply("St. Louis, St. Charles").as_deref(),
+            Some("Saint Louis, Saint Charles")
+        );
+        assert_eq!(r.apply("Paris"), None);
+    }
+
+    #[test]
+    fn test_unchanged_value_is_not_a_match() {
+        let r = Replacer::new(MatchMode::Regex, "Paris", "Paris").unwrap();
+        assert_eq!(r.apply("Paris, France"), None);
+    }
+
+    #[test]
+    fn test_invalid_input_is_rejected() {
+        assert!(Replacer::new(MatchMode::Regex, "(", "x").is_err());
+        assert!(Replacer::new(MatchMode::Exact, "", "x").is_err());
+    }
+
+    #[test]
+    fn test_fields_by_entity() {
+        assert_eq!(
+            ReplaceField::of(ReplaceEntity::Place, "name"),
+            Some(ReplaceField::PlaceName)
+        );
+        assert_eq!(ReplaceField::of(ReplaceEntity::Place, "title"), None);
+        assert_eq!(
+            ReplaceField::of(ReplaceEntity::Source, "abbreviation"),
+            Some(ReplaceField::SourceAbbreviation)
+        );
+        assert_eq!(ReplaceField::names(ReplaceEntity::Place), ["name"]);
+    }
+}
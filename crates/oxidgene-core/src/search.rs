@@ -4,30 +4,27 @@
 //! normalizing incoming queries, so stored tokens and query tokens always
 //! match regardless of the database backend.
 
-/// Normalize a string for search: lowercase + accent folding.
-///
-/// This is a simple implementation that handles common Latin diacritics.
-/// For more comprehensive accent folding, consider using the `deunicode` crate.
+use crate::text::fold_accent;
+
+/// Normalize a string for search: lowercase + accent folding (see
+/// [`crate::text::fold_accent`]). Whitespace is kept as is.
 pub fn normalize_for_search(s: &str) -> String {
     s.to_lowercase().chars().map(fold_accent).collect()
 }
 
-/// Fold a single accented character to its ASCII equivalent.
-fn fold_accent(c: char) -> char {
-    match c {
-        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
-        'æ' => 'a', // simplified
-        'ç' => 'c',
-        'è' | 'é' | 'ê' | 'ë' => 'e',
-        'ì' | 'í' | 'î' | 'ï' => 'i',
-        'ñ' => 'n',
-        'ò' | 'ó' | 'ô' | 'õ' | 'ö' => 'o',
-        'ù' | 'ú' | 'û' | 'ü' => 'u',
-        'ý' | 'ÿ' => 'y',
-        'ð' => 'd',
-        'ø' => 'o',
-        'ß' => 's',
-        _ => c,
+/// Letter a name is filed under in an alphabetical index: its first letter,
+/// accent-folded and uppercased (`Émery` → `E`), or `#` when it does not
+/// start with a letter.
+pub fn index_initial(s: &str) -> char {
+    let first = s
+        .trim_start()
+        .chars()
+        .next()
+        .and_then(|c| c.to_lowercase().next())
+        .map(fold_accent);
+    match first {
+        Some(c) if c.is_alphabetic() => c.to_uppercase().next().unwrap_or(c),
+        _ => '#',
     }
 }
 
@@ -45,10 +42,11 @@ mod tests {
     }
 
     #[test]
-    fn test_fold_accent() {
-        assert_eq!(fold_accent('é'), 'e');
-        assert_eq!(fold_accent('ç'), 'c');
-        assert_eq!(fold_accent('ü'), 'u');
-        assert_eq!(fold_accent('x'), 'x');
+    fn test_index_initial() {
+        assert_eq!(index_initial("Émery"), 'E');
+        assert_eq!(index_initial("dupont"), 'D');
+        assert_eq!(index_initial(" Çelik"), 'C');
+        assert_eq!(index_initial("'t Hooft"), '#');
+        assert_eq!(index_initial(""), '#');
     }
 }
@@ -0,0 +1,258 @@
+//! At-a-glance facts about a person — the name to show, birth and death
+//! years, whether they are presumed alive, how well their vital facts are
+//! sourced, where their recorded line starts and ends — derived from their
+//! names, events, citations and family links, so every client shows them
+//! the same way.
+
+use serde::Serialize;
+
+use crate::enums::{Confidence, EventType};
+use crate::privacy::is_living;
+use crate::types::{Citation, Event, PersonName};
+
+/// The facts [`sourcing_score`] expects to find evidence for.
+const VITAL_EVENTS: &[EventType] = &[EventType::Birth, EventType::Death, EventType::Marriage];
+
+/// What a list or card shows for a person.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PersonSummary {
+    /// The primary name (else the first one); `None` for a nameless person.
+    pub display_name: Option<String>,
+    /// Year of the earliest dated birth, else of the earliest baptism.
+    pub birth_year: Option<i32>,
+    /// Year of the earliest dated death, else of the burial or cremation.
+    pub death_year: Option<i32>,
+    /// See [`is_living`].
+    pub living: bool,
+}
+
+impl PersonSummary {
+    /// Summarize a person from their `living` status, names and own events.
+    pub fn new(
+        living: Option<bool>,
+        names: &[PersonName],
+        events: &[Event],
+        current_year: i32,
+    ) -> Self {
+        let display_name = names
+            .iter()
+            .find(|n| n.is_primary)
+            .or(names.first())
+            .map(PersonName::display_name)
+            .filter(|n| !n.is_empty());
+        let earliest = |types: &[EventType]| {
+            events
+                .iter()
+                .filter(|e| types.contains(&e.event_type))
+                .filter_map(Event::year)
+                .min()
+        };
+        Self {
+            display_name,
+            birth_year: earliest(&[EventType::Birth]).or_else(|| earliest(&[EventType::Baptism])),
+            death_year: earliest(&[EventType::Death])
+                .or_else(|| earliest(&[EventType::Burial, EventType::Cremation])),
+            living: is_living(living, events, current_year),
+        }
+    }
+
+    /// `birth–death` years, either side left blank when unknown (`1850–`,
+    /// `–1920`); `None` when both are.
+    pub fn lifespan(&self) -> Option<String> {
+        let year = |y: Option<i32>| y.map(|y| y.to_string()).unwrap_or_default();
+        (self.birth_year.is_some() || self.death_year.is_some())
+            .then(|| format!("{}\u{2013}{}", year(self.birth_year), year(self.death_year)))
+    }
+}
+
+/// Where a person stands in the recorded lineage. A person without known
+/// parents is a "brick wall", the next research target; one without
+/// descendants ends their line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Lineage {
+    /// Recorded descendants, at any depth.
+    pub descendant_count: u64,
+    /// Whether `descendant_count` is non-zero.
+    pub has_descendants: bool,
+    /// Whether a parent of the person is recorded.
+    pub has_known_parents: bool,
+}
+
+impl Lineage {
+    pub fn new(descendant_count: u64, has_known_parents: bool) -> Self {
+        Self {
+            descendant_count,
+            has_descendants: descendant_count > 0,
+            has_known_parents,
+        }
+    }
+}
+
+/// How well a person's vital facts (birth, death, marriage) are sourced,
+/// from 0 to 1: each vital event among `events` — the person's own and
+/// their unions' — scores the weight of its most confident citation, 0
+/// when uncited, and the score is their mean. A person without any vital
+/// event scores 0.
+pub fn sourcing_score(events: &[Event], citations: &[Citation]) -> f32 {
+    let scores: Vec<f32> = events
+        .iter()
+        .filter(|e| VITAL_EVENTS.contains(&e.event_type))
+        .map(|e| {
+            citations
+                .iter()
+                .filter(|c| c.event_id == Some(e.id))
+                .map(|c| confidence_weight(c.confidence))
+                .fold(0.0, f32::max)
+        })
+        .collect();
+    if scores.is_empty() {
+        return 0.0;
+    }
+    scores.iter().sum::<f32>() / scores.len() as f32
+}
+
+/// How much a citation of this confidence counts towards [`sourcing_score`].
+fn confidence_weight(confidence: Confidence) -> f32 {
+    match confidence {
+        Confidence::VeryHigh => 1.0,
+        Confidence::High => 0.8,
+        Confidence::Medium => 0.6,
+        Confidence::Low => 0.4,
+        Confidence::VeryLow => 0.2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Calendar, DateQualifier, NameType};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn event(event_type: EventType, date_value: &str) -> Event {
+        let now = Utc::now();
+        Event {
+            id: Uuid::now_v7(),
+            tree_id: Uuid::now_v7(),
+            event_type,
+            date_value: Some(date_value.to_string()),
+            date_sort: None,
+            date_qualifier: DateQualifier::default(),
+            date_value2: None,
+            calendar: Calendar::default(),
+            cause: None,
+            age: None,
+            place_id: None,
+            place_id_to: None,
+            person_id: None,
+            family_id: None,
+            description: None,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            deleted_at: None,
+        }
+    }
+
+    fn name(given: &str, surname: &str, is_primary: bool) -> PersonName {
+        let now = Utc::now();
+        PersonName {
+            id: Uuid::now_v7(),
+            person_id: Uuid::now_v7(),
+            name_type: NameType::Birth,
+            given_names: Some(given.to_string()),
+            surname: Some(surname.to_string()),
+            prefix: None,
+            suffix: None,
+            nickname: None,
+            is_primary,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = PersonSummary::new(
+            None,
+            &[
+                name("Marie", "Martin", false),
+                name("Marie", "Dupont", true),
+            ],
+            &[
+                event(EventType::Baptism, "1849"),
+                event(EventType::Birth, "ABT 1850"),
+                event(EventType::Burial, "2 FEB 1921"),
+            ],
+            2026,
+        );
+        assert_eq!(summary.display_name.as_deref(), Some("Marie Dupont"));
+        assert_eq!(summary.birth_year, Some(1850));
+        assert_eq!(summary.death_year, Some(1921));
+        assert!(!summary.living);
+        assert_eq!(summary.lifespan().as_deref(), Some("1850\u{2013}1921"));
+    }
+
+    fn citation(event: &Event, confidence: Confidence) -> Citation {
+        let now = Utc::now();
+        Citation {
+            id: Uuid::now_v7(),
+            source_id: Uuid::now_v7(),
+            person_id: None,
+            event_id: Some(event.id),
+            family_id: None,
+            page: None,
+            confidence,
+            text: None,
+            fact_type: None,
+            fact_ref: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_sourcing_score() {
+        let birth = event(EventType::Birth, "1850");
+        let death = event(EventType::Death, "1920");
+        let marriage = event(EventType::Marriage, "1875");
+        let census = event(EventType::Census, "1881");
+        let events = [
+            birth.clone(),
+            death.clone(),
+            marriage.clone(),
+            census.clone(),
+        ];
+
+        assert_eq!(sourcing_score(&events, &[]), 0.0);
+        assert_eq!(sourcing_score(std::slice::from_ref(&census), &[]), 0.0);
+
+        // The best citation of each fact counts; non-vital events do not.
+        let citations = [
+            citation(&birth, Confidence::Low),
+            citation(&birth, Confidence::VeryHigh),
+            citation(&death, Confidence::Medium),
+            citation(&census, Confidence::VeryHigh),
+        ];
+        let score = sourcing_score(&events, &citations);
+        assert!((score - (1.0 + 0.6 + 0.0) / 3.0).abs() < 1e-6, "{score}");
+
+        let citations = [
+            citation(&birth, Confidence::VeryHigh),
+            citation(&death, Confidence::VeryHigh),
+            citation(&marriage, Confidence::VeryHigh),
+        ];
+        assert_eq!(sourcing_score(&events, &citations), 1.0);
+    }
+
+    #[test]
+    fn test_summary_partial() {
+        let summary = PersonSummary::new(None, &[], &[event(EventType::Birth, "1990")], 2026);
+        assert_eq!(summary.display_name, None);
+        assert!(summary.living);
+        assert_eq!(summary.lifespan().as_deref(), Some("1990\u{2013}"));
+
+        let summary = PersonSummary::new(None, &[], &[], 2026);
+        assert_eq!(summary.lifespan(), None);
+    }
+}
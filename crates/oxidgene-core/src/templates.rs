@@ -0,0 +1,172 @@
+//! Starter data for newly created trees.
+//!
+//! A template expands into the records a new tree is seeded with. The
+//! [`TemplateSeed`] has the same shape as a GEDCOM import result, so the API
+//! persists it through the same transactional path as an import.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::{ChildType, Privacy, Sex, SpouseRole};
+use crate::types::{Family, FamilyChild, FamilySpouse, Person, PersonAncestry};
+
+/// Starter content for a new tree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TreeTemplate {
+    /// No records at all.
+    #[default]
+    Empty,
+    /// A home person with empty placeholders for their parents and four
+    /// grandparents, ready to be filled in.
+    #[serde(rename = "self")]
+    HomePerson,
+}
+
+/// Records a template seeds a tree with.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateSeed {
+    pub persons: Vec<Person>,
+    pub families: Vec<Family>,
+    pub family_spouses: Vec<FamilySpouse>,
+    pub family_children: Vec<FamilyChild>,
+    pub person_ancestry: Vec<PersonAncestry>,
+    /// Person the tree opens on; becomes the tree's SOSA root.
+    pub home_person_id: Option<Uuid>,
+}
+
+impl TemplateSeed {
+    fn person(&mut self, tree_id: Uuid, sex: Sex) -> Uuid {
+        let now = Utc::now();
+        let id = Uuid::now_v7();
+        self.persons.push(Person {
+            id,
+            tree_id,
+            sex,
+            privacy: Privacy::default(),
+            living: None,
+            display_name: None,
+            locked: false,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            deleted_at: None,
+        });
+        id
+    }
+
+    /// Create placeholder parents for `child` and link them through a new
+    /// family; returns `(father, mother)`.
+    fn parents(&mut self, tree_id: Uuid, child: Uuid) -> (Uuid, Uuid) {
+        let now = Utc::now();
+        let father = self.person(tree_id, Sex::Male);
+        let mother = self.person(tree_id, Sex::Female);
+        let family_id = Uuid::now_v7();
+        self.families.push(Family {
+            id: family_id,
+            tree_id,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        });
+        for (sort_order, (person_id, role)) in
+            [(father, SpouseRole::Husband), (mother, SpouseRole::Wife)]
+                .into_iter()
+                .enumerate()
+        {
+            self.family_spouses.push(FamilySpouse {
+                id: Uuid::now_v7(),
+                family_id,
+                person_id,
+                role,
+                sort_order: sort_order as i32,
+            });
+        }
+        self.family_children.push(FamilyChild {
+            id: Uuid::now_v7(),
+            family_id,
+            person_id: child,
+            child_type: ChildType::Biological,
+            sort_order: 0,
+        });
+        (father, mother)
+    }
+
+    fn ancestry(&mut self, tree_id: Uuid, ancestor_id: Uuid, descendant_id: Uuid, depth: i32) {
+        self.person_ancestry.push(PersonAncestry {
+            id: Uuid::now_v7(),
+            tree_id,
+            ancestor_id,
+            descendant_id,
+            depth,
+        });
+    }
+}
+
+/// Expand `template` into the records of tree `tree_id`.
+pub fn build(template: TreeTemplate, tree_id: Uuid) -> TemplateSeed {
+    let mut seed = TemplateSeed::default();
+    match template {
+        TreeTemplate::Empty => {}
+        TreeTemplate::HomePerson => {
+            let home = seed.person(tree_id, Sex::Unknown);
+            seed.home_person_id = Some(home);
+            let (father, mother) = seed.parents(tree_id, home);
+            for parent in [father, mother] {
+                seed.ancestry(tree_id, parent, home, 1);
+                let (grandfather, grandmother) = seed.parents(tree_id, parent);
+                for grandparent in [grandfather, grandmother] {
+                    seed.ancestry(tree_id, grandparent, parent, 1);
+                    seed.ancestry(tree_id, grandparent, home, 2);
+                }
+            }
+        }
+    }
+    seed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_template_seeds_nothing() {
+        let seed = build(TreeTemplate::Empty, Uuid::now_v7());
+        assert!(seed.persons.is_empty());
+        assert!(seed.home_person_id.is_none());
+    }
+
+    #[test]
+    fn self_template_seeds_three_generations() {
+        let tree_id = Uuid::now_v7();
+        let seed = build(TreeTemplate::HomePerson, tree_id);
+        let home = seed.home_person_id.unwrap();
+
+        assert_eq!(seed.persons.len(), 7);
+        assert_eq!(seed.families.len(), 3);
+        assert_eq!(seed.family_spouses.len(), 6);
+        assert_eq!(seed.family_children.len(), 3);
+        assert!(seed.persons.iter().all(|p| p.tree_id == tree_id));
+
+        let depths = |depth| {
+            seed.person_ancestry
+                .iter()
+                .filter(|a| a.descendant_id == home && a.depth == depth)
+                .count()
+        };
+        assert_eq!(depths(1), 2);
+        assert_eq!(depths(2), 4);
+        assert_eq!(seed.person_ancestry.len(), 10);
+    }
+
+    #[test]
+    fn template_names_round_trip() {
+        let parsed: TreeTemplate = serde_json::from_str("\"self\"").unwrap();
+        assert_eq!(parsed, TreeTemplate::HomePerson);
+        assert_eq!(
+            serde_json::to_string(&TreeTemplate::Empty).unwrap(),
+            "\"empty\""
+        );
+    }
+}
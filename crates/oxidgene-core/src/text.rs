@@ -0,0 +1,240 @@
+//! Case and accent folding of names, and their capitalization.
+//!
+//! Names are compared in this folded form wherever spelling variants must
+//! match: `François`, `FRANCOIS` and `francois` all fold to `francois`. The
+//! folding is done in Rust rather than by the database (e.g. PostgreSQL's
+//! `unaccent`), so SQLite and PostgreSQL store and match the same keys.
+//!
+//! [`title_case_name`] rewrites a name typed or imported in any case
+//! (`JEAN-PIERRE`, `mcdonald`) in its usual capitalization (`Jean-Pierre`,
+//! `McDonald`); [`NameCase`] picks how a batch normalization treats each
+//! part of a name.
+
+use serde::{Deserialize, Serialize};
+
+/// Nobiliary and toponymic particles, kept lowercase by
+/// [`title_case_name`] when another word follows (`Ludwig van
+/// Beethoven`). Articles (`le`, `la`) are left out: they are usually
+/// capitalized as part of the surname (`Le Goff`, `de La Fontaine`).
+const PARTICLES: &[&str] = &[
+    "da", "das", "de", "del", "della", "den", "der", "des", "di", "do", "dos", "du", "ten", "ter",
+    "van", "von", "y", "zu",
+];
+
+/// Elided particles, kept lowercase before the rest of their word
+/// (`d'Artagnan`).
+const ELIDED_PARTICLES: &[&str] = &["d'", "d’"];
+
+/// How a batch normalization rewrites the capitalization of a name part.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameCase {
+    /// Leave the part as written.
+    Keep,
+    /// [`title_case_name`]: `Jean-Pierre`, `McDonald`, `O'Brien`.
+    #[default]
+    TitleCase,
+    /// All capitals, as surnames are often written in genealogies
+    /// (`DUPONT`); particles follow the same rule as in title case.
+    Upper,
+}
+
+impl NameCase {
+    /// Rewrite `name` in this case.
+    pub fn apply(self, name: &str, preserve_particles: bool) -> String {
+        match self {
+            Self::Keep => name.to_string(),
+            Self::TitleCase => title_case_name(name, preserve_particles),
+            Self::Upper => case_words(name, preserve_particles, str::to_uppercase),
+        }
+    }
+}
+
+/// Normalize a name for matching: lowercase, fold accents and collapse
+/// whitespace runs into single spaces (`"  Łukasz   Wróbel "` →
+/// `"lukasz wrobel"`).
+pub fn normalize(s: &str) -> String {
+    let folded: String = s.to_lowercase().chars().map(fold_accent).collect();
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// [`normalize`] a stored name part, `None` when nothing is left of it.
+pub fn normalize_name(s: &str) -> Option<String> {
+    let normalized = normalize(s);
+    (!normalized.is_empty()).then_some(normalized)
+}
+
+/// Capitalize a name the usual way, whatever case it was written in: the
+/// first letter of each word and of each part of a compound
+/// (`jean-pierre` → `Jean-Pierre`, `o'brien` → `O'Brien`), and the letter
+/// after a `Mc` prefix (`MCDONALD` → `McDonald`); everything else is
+/// lowercased. `Mac` is left alone, being as often part of the name
+/// (`Macy`) as a prefix.
+///
+/// With `preserve_particles`, particles such as `de` or `van` stay
+/// lowercase when another word follows (`JEAN DE LA FONTAINE` → `Jean de
+/// La Fontaine`), as does an elided `d'` (`d'Artagnan`). Whitespace is
+/// kept as is.
+pub fn title_case_name(name: &str, preserve_particles: bool) -> String {
+    case_words(name, preserve_particles, title_case_word)
+}
+
+/// Rewrite each whitespace-separated word of `name` with `case`, but the
+/// particles kept lowercase under `preserve_particles`.
+fn case_words(name: &str, preserve_particles: bool, case: fn(&str) -> String) -> String {
+    let word_count = name.split_whitespace().count();
+    let mut out = String::with_capacity(name.len());
+    let mut index = 0;
+    for chunk in name.split_inclusive(char::is_whitespace) {
+        let word = chunk.trim_end_matches(char::is_whitespace);
+        let spacing = &chunk[word.len()..];
+        if word.is_empty() {
+            out.push_str(spacing);
+            continue;
+        }
+        index += 1;
+        let lower = word.to_lowercase();
+        let elided = ELIDED_PARTICLES
+            .iter()
+            .find(|p| lower.len() > p.len() && lower.starts_with(**p));
+        if preserve_particles && index < word_count && PARTICLES.contains(&lower.as_str()) {
+            out.push_str(&lower);
+        } else if let Some(particle) = elided.filter(|_| preserve_particles) {
+            out.push_str(particle);
+            out.push_str(&case(&lower[particle.len()..]));
+        } else {
+            out.push_str(&case(word));
+        }
+        out.push_str(spacing);
+    }
+    out
+}
+
+/// Title-case one word: each run of letters and digits is capitalized,
+/// whatever separates them (hyphen, apostrophe, period).
+fn title_case_word(word: &str) -> String {
+    let mut out = String::with_capacity(word.len());
+    let mut segment = String::new();
+    for c in word.chars() {
+        if c.is_alphanumeric() {
+            segment.push(c);
+        } else {
+            out.push_str(&title_case_segment(&segment));
+            segment.clear();
+            out.push(c);
+        }
+    }
+    out.push_str(&title_case_segment(&segment));
+    out
+}
+
+fn title_case_segment(segment: &str) -> String {
+    let lower = segment.to_lowercase();
+    let (mut out, rest) = match lower.strip_prefix("mc") {
+        Some(rest) if !rest.is_empty() => ("Mc".to_string(), rest),
+        _ => (String::new(), lower.as_str()),
+    };
+    let mut chars = rest.chars();
+    if let Some(first) = chars.next() {
+        out.extend(first.to_uppercase());
+        out.push_str(chars.as_str());
+    }
+    out
+}
+
+/// Fold a single lowercase accented Latin letter to its ASCII base letter.
+/// Other characters are returned unchanged.
+pub fn fold_accent(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => 'a',
+        'æ' => 'a', // simplified
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => 'c',
+        'ď' | 'đ' | 'ð' => 'd',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => 'e',
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => 'g',
+        'ĥ' | 'ħ' => 'h',
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => 'i',
+        'ĵ' => 'j',
+        'ķ' => 'k',
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => 'l',
+        'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => 'o',
+        'œ' => 'o', // simplified
+        'ŕ' | 'ŗ' | 'ř' => 'r',
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => 's',
+        'ß' => 's',
+        'ţ' | 'ť' | 'ŧ' | 'ț' => 't',
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+        'ŵ' => 'w',
+        'ý' | 'ÿ' | 'ŷ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("François"), "francois");
+        assert_eq!(normalize("FRANCOIS"), "francois");
+        assert_eq!(normalize("  Jean   Marie "), "jean marie");
+        assert_eq!(normalize("Łukasz Wróbel"), "lukasz wrobel");
+        assert_eq!(normalize("Żółć"), "zolc");
+        assert_eq!(normalize("Dvořák"), "dvorak");
+        assert_eq!(normalize("Ștefănescu"), "stefanescu");
+        assert_eq!(normalize("Øster"), "oster");
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("Müller"), Some("muller".to_string()));
+        assert_eq!(normalize_name("   "), None);
+        assert_eq!(normalize_name(""), None);
+    }
+
+    #[test]
+    fn test_fold_accent() {
+        assert_eq!(fold_accent('é'), 'e');
+        assert_eq!(fold_accent('ç'), 'c');
+        assert_eq!(fold_accent('ü'), 'u');
+        assert_eq!(fold_accent('ł'), 'l');
+        assert_eq!(fold_accent('x'), 'x');
+    }
+
+    #[test]
+    fn test_title_case_name() {
+        assert_eq!(title_case_name("JEAN-PIERRE", true), "Jean-Pierre");
+        assert_eq!(title_case_name("marie  louise", true), "Marie  Louise");
+        assert_eq!(title_case_name("mcdonald", true), "McDonald");
+        assert_eq!(title_case_name("MC", true), "Mc");
+        assert_eq!(title_case_name("O'BRIEN", true), "O'Brien");
+        assert_eq!(title_case_name("ÉLODIE", true), "Élodie");
+        assert_eq!(title_case_name("macy", true), "Macy");
+    }
+
+    #[test]
+    fn test_title_case_name_particles() {
+        assert_eq!(
+            title_case_name("JEAN DE LA FONTAINE", true),
+            "Jean de La Fontaine"
+        );
+        assert_eq!(title_case_name("Van Gogh", true), "van Gogh");
+        assert_eq!(title_case_name("Van Gogh", false), "Van Gogh");
+        // A particle alone is a name, not a particle.
+        assert_eq!(title_case_name("VAN", true), "Van");
+        assert_eq!(title_case_name("D'ARTAGNAN", true), "d'Artagnan");
+        assert_eq!(title_case_name("D'ARTAGNAN", false), "D'Artagnan");
+    }
+
+    #[test]
+    fn test_name_case_apply() {
+        assert_eq!(NameCase::Keep.apply("dUPONT", true), "dUPONT");
+        assert_eq!(NameCase::TitleCase.apply("dUPONT", true), "Dupont");
+        assert_eq!(NameCase::Upper.apply("de gaulle", true), "de GAULLE");
+        assert_eq!(NameCase::Upper.apply("d'artagnan", true), "d'ARTAGNAN");
+        assert_eq!(NameCase::Upper.apply("de gaulle", false), "DE GAULLE");
+    }
+}
@@ -2,9 +2,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::enums::Confidence;
+use crate::enums::{Confidence, FactType};
 
-/// A citation linking a source to a person, event, or family.
+/// A citation linking a source to a person, event, or family — or, with
+/// `fact_type`, to a single fact of one: a name of the person, or a field of
+/// the event.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Citation {
     pub id: Uuid,
@@ -17,6 +19,13 @@ pub struct Citation {
     pub confidence: Confidence,
     /// Extracted text from the source.
     pub text: Option<String>,
+    /// The fact cited; `None` cites the whole entity.
+    #[serde(default)]
+    pub fact_type: Option<FactType>,
+    /// Record holding the fact, for facts that are records of their own
+    /// (the `PersonName` id of a [`FactType::Name`] citation).
+    #[serde(default)]
+    pub fact_ref: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -1,8 +1,11 @@
+use std::cmp::Ordering;
+
 use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::enums::{Calendar, DateQualifier, EventType};
+use crate::calendar::split_calendar;
+use crate::enums::{Calendar, DateQualifier, EventType, ParticipantRole};
 
 /// A genealogical event (birth, death, marriage, etc.).
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,7 +25,14 @@ pub struct Event {
     pub calendar: Calendar,
     /// Cause of death/burial/etc. Maps to GEDCOM `CAUS`.
     pub cause: Option<String>,
+    /// Age of the principal at the event, as recorded (GEDCOM `AGE`, e.g.
+    /// "32y", "32y 6m", "<1y", "CHILD"). See [`crate::chronology::parse_age`].
+    pub age: Option<String>,
     pub place_id: Option<Uuid>,
+    /// Destination of a move (emigration, immigration, residence change),
+    /// `place_id` being where it started. Always `None` for other event
+    /// types, see [`EventType::has_destination`].
+    pub place_id_to: Option<Uuid>,
     /// Set for individual events.
     pub person_id: Option<Uuid>,
     /// Set for family events.
@@ -30,6 +40,8 @@ pub struct Event {
     pub description: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Bumped on every update; clients echo it back in `If-Match`.
+    pub version: i32,
     pub deleted_at: Option<DateTime<Utc>>,
 }
 
@@ -40,6 +52,91 @@ impl Event {
     pub fn year(&self) -> Option<i32> {
         year_from_date(self.date_sort, self.date_value.as_deref())
     }
+
+    /// How precisely `date_value` pins the date down, or `None` without a
+    /// readable date phrase.
+    pub fn date_precision(&self) -> Option<DatePrecision> {
+        self.date_value.as_deref().and_then(DatePrecision::of)
+    }
+
+    /// Life-event order: by date, compared only as precisely as the less
+    /// precise of the two (so "1850" and "MAR 1850" tie on the year); ties
+    /// go to the more precise date, then to the event type's natural order
+    /// (birth, baptism, ..., death, burial). Undated events come last.
+    pub fn chronological_cmp(&self, other: &Event) -> Ordering {
+        // A date_sort without a phrase to read its precision from is taken
+        // at face value.
+        let precision = |e: &Event| e.date_precision().unwrap_or(DatePrecision::Day);
+        let by_date = match (self.date_sort, other.date_sort) {
+            (Some(a), Some(b)) => {
+                let common = precision(self).min(precision(other));
+                common.truncate(a).cmp(&common.truncate(b))
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        by_date
+            .then_with(|| precision(other).cmp(&precision(self)))
+            .then_with(|| {
+                self.event_type
+                    .chronological_rank()
+                    .cmp(&other.event_type.chronological_rank())
+            })
+            .then_with(|| self.date_sort.cmp(&other.date_sort))
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+/// Granularity of a recorded date: "1850", "MAR 1850" or "12 MAR 1850".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DatePrecision {
+    Year,
+    Month,
+    Day,
+}
+
+impl DatePrecision {
+    /// Precision of a GEDCOM date phrase, read from its first date:
+    /// qualifiers (`ABT`, `BEF`, ...) are skipped and ranges (`BET ... AND
+    /// ...`, `FROM ... TO ...`) use their start. ISO-like `1850-03-12` and
+    /// `12/03/1850` forms count their parts the same way.
+    pub fn of(date_value: &str) -> Option<Self> {
+        let upper = date_value.trim().to_ascii_uppercase();
+        let mut rest = upper.as_str();
+        for prefix in [
+            "ABT ", "CAL ", "EST ", "BEF ", "AFT ", "BET ", "FROM ", "TO ",
+        ] {
+            if let Some(stripped) = rest.strip_prefix(prefix) {
+                rest = stripped;
+                break;
+            }
+        }
+        let first = rest
+            .split(" AND ")
+            .next()
+            .and_then(|d| d.split(" TO ").next())
+            .unwrap_or(rest);
+        let parts = first
+            .split(|c: char| c.is_whitespace() || c == '-' || c == '/')
+            .filter(|p| !p.is_empty())
+            .count();
+        match parts {
+            0 => None,
+            1 => Some(Self::Year),
+            2 => Some(Self::Month),
+            _ => Some(Self::Day),
+        }
+    }
+
+    /// `date` cut down to this precision (the first of its month or year).
+    pub fn truncate(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Year => date.with_ordinal(1).unwrap_or(date),
+            Self::Month => date.with_day(1).unwrap_or(date),
+            Self::Day => date,
+        }
+    }
 }
 
 /// Shared "resolve a display year" logic used everywhere a birth/death year
@@ -55,15 +152,204 @@ pub fn year_from_date(date_sort: Option<NaiveDate>, date_value: Option<&str>) ->
     })
 }
 
-/// A witness (or godparent, etc.) linked to an [`Event`] — a pointer to
-/// another [`Person`](crate::types::Person) in the tree, mirroring GEDCOM's
-/// `ASSO`/`RELA` association structure. `relation` is free text (e.g.
-/// "Godmother", "Witness").
+/// Parse a GEDCOM date phrase into the `date_sort` of its event.
+///
+/// Handles common formats:
+/// - `DD MMM YYYY` (e.g. `15 JAN 1842`)
+/// - `MMM YYYY` (e.g. `JAN 1842`) → first of month
+/// - `YYYY` (e.g. `1842`) → first of year
+/// - Prefixes like `ABT`, `BEF`, `AFT`, `CAL`, `EST` are stripped
+/// - Range formats `BET ... AND ...` → first date
+/// - Calendar escapes (`@#DJULIAN@ 3 SEP 1752`), see [`parse_date_sort_in`]
+pub fn parse_date_sort(s: &str) -> Option<NaiveDate> {
+    parse_date_sort_in(Calendar::Gregorian, s)
+}
+
+/// [`parse_date_sort`] for a phrase of `calendar`, converted to the
+/// proleptic Gregorian calendar (`12 VEND 3` is 3 October 1794). A calendar
+/// escape in the phrase overrides `calendar`. Dual years of the old-style
+/// new year (`11 FEB 1731/32`) count as the later year. Hebrew dates have no
+/// `date_sort`.
+pub fn parse_date_sort_in(calendar: Calendar, s: &str) -> Option<NaiveDate> {
+    let (escaped, s) = split_calendar(s);
+    let calendar = escaped.unwrap_or(calendar);
+    let s = s.trim();
+    if s.is_empty() {
+        return None;
+    }
+
+    // Strip common prefixes
+    let stripped = s
+        .strip_prefix("ABT ")
+        .or_else(|| s.strip_prefix("BEF "))
+        .or_else(|| s.strip_prefix("AFT "))
+        .or_else(|| s.strip_prefix("CAL "))
+        .or_else(|| s.strip_prefix("EST "))
+        .or_else(|| s.strip_prefix("FROM "))
+        .or_else(|| s.strip_prefix("TO "))
+        .unwrap_or(s);
+
+    // Handle BET ... AND ... → take first date
+    let stripped = if let Some(rest) = stripped.strip_prefix("BET ") {
+        rest.split(" AND ").next().unwrap_or(rest)
+    } else {
+        stripped
+    };
+
+    let stripped = stripped.trim();
+    let parts: Vec<&str> = stripped.split_whitespace().collect();
+
+    match parts.len() {
+        3 => {
+            // DD MMM YYYY
+            let day: u32 = parts[0].parse().ok()?;
+            let month = calendar.month_number(parts[1])?;
+            let year = parse_year(parts[2])?;
+            calendar.to_gregorian(year, month, day)
+        }
+        2 => {
+            // MMM YYYY
+            let month = calendar.month_number(parts[0])?;
+            let year = parse_year(parts[1])?;
+            calendar.to_gregorian(year, month, 1)
+        }
+        1 => {
+            // YYYY
+            let year = parse_year(parts[0])?;
+            calendar.to_gregorian(year, 1, 1)
+        }
+        _ => None,
+    }
+}
+
+/// A year, the later one of a dual year such as `1731/32`.
+fn parse_year(s: &str) -> Option<i32> {
+    match s.split_once('/') {
+        Some((year, _)) => year.parse::<i32>().ok().map(|year| year + 1),
+        None => s.parse().ok(),
+    }
+}
+
+/// A person taking part in an [`Event`] besides its owner — a witness,
+/// godparent, officiant, ... — pointing to another
+/// [`Person`](crate::types::Person) in the tree, mirroring GEDCOM's
+/// `ASSO`/`RELA` association structure. The event's own `person_id` stays
+/// its primary participant. `relation` keeps the source's free text (e.g.
+/// "Godmother") next to the structured `role`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EventWitness {
+pub struct EventParticipant {
     pub id: Uuid,
     pub event_id: Uuid,
     pub person_id: Uuid,
+    pub role: ParticipantRole,
     pub relation: Option<String>,
     pub sort_order: i32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(
+        event_type: EventType,
+        date_value: Option<&str>,
+        date_sort: Option<NaiveDate>,
+    ) -> Event {
+        Event {
+            id: Uuid::now_v7(),
+            tree_id: Uuid::nil(),
+            event_type,
+            date_value: date_value.map(str::to_string),
+            date_sort,
+            date_qualifier: DateQualifier::Exact,
+            date_value2: None,
+            calendar: Calendar::Gregorian,
+            cause: None,
+            age: None,
+            place_id: None,
+            place_id_to: None,
+            person_id: None,
+            family_id: None,
+            description: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+        }
+    }
+
+    fn ymd(y: i32, m: u32, d: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(y, m, d)
+    }
+
+    #[test]
+    fn test_date_precision_of() {
+        assert_eq!(DatePrecision::of("1850"), Some(DatePrecision::Year));
+        assert_eq!(DatePrecision::of("MAR 1850"), Some(DatePrecision::Month));
+        assert_eq!(DatePrecision::of("12 MAR 1850"), Some(DatePrecision::Day));
+        assert_eq!(DatePrecision::of("ABT 1850"), Some(DatePrecision::Year));
+        assert_eq!(
+            DatePrecision::of("BET MAR 1850 AND 1852"),
+            Some(DatePrecision::Month)
+        );
+        assert_eq!(DatePrecision::of("1850-03-12"), Some(DatePrecision::Day));
+        assert_eq!(DatePrecision::of("  "), None);
+    }
+
+    #[test]
+    fn test_chronological_cmp_same_year_prefers_precision() {
+        let year = event(EventType::Census, Some("1850"), ymd(1850, 1, 1));
+        let month = event(EventType::Residence, Some("MAR 1850"), ymd(1850, 3, 1));
+        let day = event(EventType::Occupation, Some("12 MAR 1850"), ymd(1850, 3, 12));
+        let mut events = [year.clone(), month.clone(), day.clone()];
+        events.sort_by(Event::chronological_cmp);
+        let ids: Vec<_> = events.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![day.id, month.id, year.id]);
+    }
+
+    #[test]
+    fn test_chronological_cmp_event_type_breaks_ties() {
+        let burial = event(EventType::Burial, Some("1850"), ymd(1850, 1, 1));
+        let death = event(EventType::Death, Some("1850"), ymd(1850, 1, 1));
+        let baptism = event(EventType::Baptism, Some("1850"), ymd(1850, 1, 1));
+        let birth = event(EventType::Birth, Some("1850"), ymd(1850, 1, 1));
+        let undated = event(EventType::Birth, None, None);
+        let mut events = [
+            undated.clone(),
+            burial.clone(),
+            death.clone(),
+            baptism.clone(),
+            birth.clone(),
+        ];
+        events.sort_by(Event::chronological_cmp);
+        let ids: Vec<_> = events.iter().map(|e| e.id).collect();
+        assert_eq!(
+            ids,
+            vec![birth.id, baptism.id, death.id, burial.id, undated.id]
+        );
+    }
+
+    #[test]
+    fn test_chronological_cmp_different_years() {
+        let earlier = event(EventType::Death, Some("DEC 1849"), ymd(1849, 12, 1));
+        let later = event(EventType::Birth, Some("1850"), ymd(1850, 1, 1));
+        assert_eq!(earlier.chronological_cmp(&later), Ordering::Less);
+    }
+
+    #[test]
+    fn test_parse_date_sort() {
+        assert_eq!(parse_date_sort("15 JAN 1842"), ymd(1842, 1, 15));
+        assert_eq!(parse_date_sort("ABT mar 1842"), ymd(1842, 3, 1));
+        assert_eq!(parse_date_sort("BET 1800 AND 1810"), ymd(1800, 1, 1));
+        assert_eq!(parse_date_sort("1842"), ymd(1842, 1, 1));
+        assert_eq!(parse_date_sort("sometime"), None);
+        assert_eq!(parse_date_sort("  "), None);
+        assert_eq!(parse_date_sort("@#DJULIAN@ 3 SEP 1752"), ymd(1752, 9, 14));
+        assert_eq!(parse_date_sort("11 FEB 1731/32"), ymd(1732, 2, 11));
+        assert_eq!(
+            parse_date_sort_in(Calendar::FrenchRepublican, "12 VEND 3"),
+            ymd(1794, 10, 3)
+        );
+        assert_eq!(parse_date_sort_in(Calendar::Hebrew, "5600"), None);
+    }
+}
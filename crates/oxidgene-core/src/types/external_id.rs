@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::ExternalIdKind;
+
+/// An identifier a person or family carries in another program (a GEDCOM
+/// `_UID` or `REFN`). A record may have several.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalId {
+    pub id: Uuid,
+    pub tree_id: Uuid,
+    /// Set for person ids.
+    pub person_id: Option<Uuid>,
+    /// Set for family ids.
+    pub family_id: Option<Uuid>,
+    pub kind: ExternalIdKind,
+    pub value: String,
+    /// `REFN.TYPE`, describing what a reference number refers to.
+    pub ref_type: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
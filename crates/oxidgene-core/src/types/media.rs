@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// A media file (image, PDF, video, etc.).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Media {
     pub id: Uuid,
     pub tree_id: Uuid,
@@ -21,6 +21,8 @@ pub struct Media {
     pub place_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Bumped on every update; clients echo it back in `If-Match`.
+    pub version: i32,
     pub deleted_at: Option<DateTime<Utc>>,
 }
 
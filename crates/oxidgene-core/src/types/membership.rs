@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::TreeRole;
+
+/// An actor's role on a tree. Only enforced with authentication enabled;
+/// a tree without any member is open to every authenticated actor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeMembership {
+    pub tree_id: Uuid,
+    /// The actor's id: the `sub` claim of their bearer tokens.
+    pub actor_id: String,
+    pub role: TreeRole,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
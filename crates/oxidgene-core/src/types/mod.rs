@@ -5,22 +5,34 @@
 
 mod citation;
 mod event;
+mod external_id;
 mod family;
 mod media;
+mod membership;
 mod note;
 mod pagination;
 mod person;
 mod place;
+mod share;
 mod source;
+mod tag;
+mod task;
 mod tree;
 
 pub use citation::Citation;
-pub use event::{Event, EventWitness, year_from_date};
+pub use event::{
+    DatePrecision, Event, EventParticipant, parse_date_sort, parse_date_sort_in, year_from_date,
+};
+pub use external_id::ExternalId;
 pub use family::{Family, FamilyChild, FamilySpouse};
 pub use media::{Media, MediaLink};
+pub use membership::TreeMembership;
 pub use note::Note;
 pub use pagination::{Connection, Edge, PageInfo};
 pub use person::{Person, PersonAncestry, PersonName};
 pub use place::Place;
+pub use share::ShareLink;
 pub use source::Source;
+pub use tag::{MAX_TAG_LEN, PersonTag, normalize_tag};
+pub use task::ResearchTask;
 pub use tree::Tree;
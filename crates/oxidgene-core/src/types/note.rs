@@ -2,17 +2,24 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::enums::NoteFormat;
+
 /// A textual note attached to a person, event, family, or source.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Note {
     pub id: Uuid,
     pub tree_id: Uuid,
     pub text: String,
+    /// Notes written before formats existed are plain text.
+    #[serde(default)]
+    pub format: NoteFormat,
     pub person_id: Option<Uuid>,
     pub event_id: Option<Uuid>,
     pub family_id: Option<Uuid>,
     pub source_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Bumped on every update; clients echo it back in `If-Match`.
+    pub version: i32,
     pub deleted_at: Option<DateTime<Utc>>,
 }
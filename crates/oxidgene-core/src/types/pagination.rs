@@ -5,6 +5,11 @@ use serde::{Deserialize, Serialize};
 pub struct PageInfo {
     pub has_next_page: bool,
     pub end_cursor: Option<String>,
+    /// Page size actually applied: the requested `first`, clamped to the
+    /// server's maximum. Fewer edges than requested with `has_next_page`
+    /// means the request was clamped.
+    #[serde(default)]
+    pub limit: u64,
 }
 
 /// A single edge in a paginated connection.
@@ -30,6 +35,7 @@ impl<T> Connection<T> {
             page_info: PageInfo {
                 has_next_page: false,
                 end_cursor: None,
+                limit: 0,
             },
             total_count: 0,
         }
@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::enums::{NameType, Privacy, Sex};
+use crate::enums::{NameSelectionPolicy, NameType, Privacy, Sex};
 
 /// A person in a genealogical tree.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,8 +12,24 @@ pub struct Person {
     pub sex: Sex,
     /// Per-person privacy override (§7 of the person edit modal spec).
     pub privacy: Privacy,
+    /// Whether the person is known to be alive (`Some(true)`) or dead
+    /// (`Some(false)`); `None` leaves it to [`crate::privacy::is_living`]'s
+    /// date heuristic.
+    #[serde(default)]
+    pub living: Option<bool>,
+    /// Cached name for lists and charts: see [`PersonName::display_name_of`].
+    /// Kept up to date whenever the person's names change; `None` without
+    /// names.
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// A locked person refuses edits and deletion until unlocked, to keep
+    /// finished research from being changed by mistake.
+    #[serde(default)]
+    pub locked: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Bumped on every update; clients echo it back in `If-Match`.
+    pub version: i32,
     pub deleted_at: Option<DateTime<Utc>>,
 }
 
@@ -51,6 +67,37 @@ impl PersonName {
         }
         parts.join(" ")
     }
+
+    /// The display name of the name standing for a person under the default
+    /// [`NameSelectionPolicy`], cached as [`Person::display_name`].
+    pub fn display_name_of(names: &[PersonName]) -> Option<String> {
+        Self::select(names, NameSelectionPolicy::default()).map(Self::display_name)
+    }
+
+    /// The name standing for a person under `policy`.
+    ///
+    /// Falls back to the primary name, then the first one, when no name
+    /// matches the policy; `None` only for an empty slice.
+    pub fn select(names: &[PersonName], policy: NameSelectionPolicy) -> Option<&PersonName> {
+        let latest = |name_type: NameType| {
+            names
+                .iter()
+                .filter(|n| n.name_type == name_type)
+                .max_by_key(|n| n.created_at)
+        };
+        let preferred = match policy {
+            NameSelectionPolicy::PreferPrimary => None,
+            NameSelectionPolicy::PreferBirth => names
+                .iter()
+                .find(|n| n.name_type == NameType::Birth)
+                .or_else(|| names.iter().find(|n| n.name_type == NameType::Maiden)),
+            NameSelectionPolicy::PreferMarried => latest(NameType::Married),
+            NameSelectionPolicy::MostRecent => names.iter().max_by_key(|n| n.created_at),
+        };
+        preferred
+            .or_else(|| names.iter().find(|n| n.is_primary))
+            .or_else(|| names.first())
+    }
 }
 
 /// An entry in the ancestry closure table for optimized traversal.
@@ -102,4 +149,53 @@ mod tests {
         };
         assert_eq!(name.display_name(), "Dupont");
     }
+
+    fn name(name_type: NameType, surname: &str, is_primary: bool, day: u32) -> PersonName {
+        let at = chrono::TimeZone::with_ymd_and_hms(&Utc, 2024, 1, day, 0, 0, 0).unwrap();
+        PersonName {
+            id: Uuid::now_v7(),
+            person_id: Uuid::nil(),
+            name_type,
+            given_names: Some("Marie".to_string()),
+            surname: Some(surname.to_string()),
+            prefix: None,
+            suffix: None,
+            nickname: None,
+            is_primary,
+            created_at: at,
+            updated_at: at,
+        }
+    }
+
+    #[test]
+    fn test_select_name_by_policy() {
+        let names = vec![
+            name(NameType::Married, "Martin", true, 1),
+            name(NameType::Birth, "Dupont", false, 2),
+            name(NameType::Married, "Bernard", false, 3),
+            name(NameType::AlsoKnownAs, "Mimi", false, 4),
+        ];
+        let surname = |policy| {
+            PersonName::select(&names, policy)
+                .and_then(|n| n.surname.clone())
+                .unwrap()
+        };
+        assert_eq!(surname(NameSelectionPolicy::PreferPrimary), "Martin");
+        assert_eq!(surname(NameSelectionPolicy::PreferBirth), "Dupont");
+        assert_eq!(surname(NameSelectionPolicy::PreferMarried), "Bernard");
+        assert_eq!(surname(NameSelectionPolicy::MostRecent), "Mimi");
+    }
+
+    #[test]
+    fn test_select_name_falls_back_to_primary_then_first() {
+        let names = vec![
+            name(NameType::AlsoKnownAs, "Mimi", false, 1),
+            name(NameType::Other, "Dupont", true, 2),
+        ];
+        let picked = PersonName::select(&names, NameSelectionPolicy::PreferBirth).unwrap();
+        assert_eq!(picked.surname.as_deref(), Some("Dupont"));
+        let picked = PersonName::select(&names[..1], NameSelectionPolicy::PreferMarried).unwrap();
+        assert_eq!(picked.surname.as_deref(), Some("Mimi"));
+        assert!(PersonName::select(&[], NameSelectionPolicy::PreferPrimary).is_none());
+    }
 }
@@ -12,4 +12,6 @@ pub struct Place {
     pub longitude: Option<f64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Bumped on every update; clients echo it back in `If-Match`.
+    pub version: i32,
 }
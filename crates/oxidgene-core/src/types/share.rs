@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A read-only link to a tree, served under `/api/v1/shared/{token}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShareLink {
+    pub id: Uuid,
+    pub tree_id: Uuid,
+    /// Random, unguessable; whoever holds it can browse the tree.
+    pub token: Uuid,
+    /// Hide persons presumed living (see [`crate::privacy`]).
+    pub privatize: bool,
+    /// The link stops resolving after this instant; `None` never expires.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Id of the actor who created the link (`"local"` without
+    /// authentication); `None` for links older than this field.
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
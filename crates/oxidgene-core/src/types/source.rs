@@ -14,5 +14,7 @@ pub struct Source {
     pub repository_name: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Bumped on every update; clients echo it back in `If-Match`.
+    pub version: i32,
     pub deleted_at: Option<DateTime<Utc>>,
 }
@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Longest tag accepted, in characters.
+pub const MAX_TAG_LEN: usize = 64;
+
+/// A free-text research tag on a person ("needs sources", "brick wall").
+///
+/// Tags are for filtering, not prose: a person carries each tag at most
+/// once, and the set of tags in use is listed per tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonTag {
+    pub id: Uuid,
+    pub tree_id: Uuid,
+    pub person_id: Uuid,
+    pub tag: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Trim a user-supplied tag, or `None` if it is empty or longer than
+/// [`MAX_TAG_LEN`].
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!tag.is_empty() && tag.chars().count() <= MAX_TAG_LEN).then_some(tag)
+}
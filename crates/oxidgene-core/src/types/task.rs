@@ -0,0 +1,26 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::TaskStatus;
+
+/// A research to-do ("order the 1852 marriage record") on a person or a
+/// family, with a status to filter what is still open and an optional due
+/// date.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResearchTask {
+    pub id: Uuid,
+    pub tree_id: Uuid,
+    /// Exactly one of `person_id` and `family_id` is set.
+    pub person_id: Option<Uuid>,
+    pub family_id: Option<Uuid>,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    pub due_date: Option<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Bumped on every update; clients echo it back in `If-Match`.
+    pub version: i32,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
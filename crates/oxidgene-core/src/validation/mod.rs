@@ -0,0 +1,139 @@
+//! Input checks shared by the REST and GraphQL APIs, so both reject the
+//! same values with the same messages before they reach the database.
+
+use chrono::NaiveDate;
+use thiserror::Error;
+
+use crate::error::OxidGeneError;
+
+pub mod plausibility;
+
+/// An input field holding an invalid value.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message}")]
+pub struct FieldError {
+    /// The field's name, as the APIs call it.
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl FieldError {
+    fn new(field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            field,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<FieldError> for OxidGeneError {
+    fn from(e: FieldError) -> Self {
+        Self::Validation(e.message)
+    }
+}
+
+/// A required text field: a tree or place name, a source title, a note.
+pub fn not_blank(field: &'static str, value: &str) -> Result<(), FieldError> {
+    if value.trim().is_empty() {
+        return Err(FieldError::new(field, format!("{field} must not be empty")));
+    }
+    Ok(())
+}
+
+/// A person name needs given names or a surname; the other parts alone
+/// (a prefix, a nickname) do not identify anyone.
+pub fn person_name(given_names: Option<&str>, surname: Option<&str>) -> Result<(), FieldError> {
+    let filled = |s: Option<&str>| s.is_some_and(|s| !s.trim().is_empty());
+    if !filled(given_names) && !filled(surname) {
+        return Err(FieldError::new(
+            "given_names",
+            "given_names or surname must not be empty",
+        ));
+    }
+    Ok(())
+}
+
+/// A latitude in decimal degrees.
+pub fn latitude(value: f64) -> Result<(), FieldError> {
+    if !(-90.0..=90.0).contains(&value) {
+        return Err(FieldError::new(
+            "latitude",
+            format!("latitude must be between -90 and 90, got {value}"),
+        ));
+    }
+    Ok(())
+}
+
+/// A longitude in decimal degrees.
+pub fn longitude(value: f64) -> Result<(), FieldError> {
+    if !(-180.0..=180.0).contains(&value) {
+        return Err(FieldError::new(
+            "longitude",
+            format!("longitude must be between -180 and 180, got {value}"),
+        ));
+    }
+    Ok(())
+}
+
+/// A place's coordinates, either of which may be unset.
+pub fn coordinates(latitude: Option<f64>, longitude: Option<f64>) -> Result<(), FieldError> {
+    latitude.map(self::latitude).transpose()?;
+    longitude.map(self::longitude).transpose()?;
+    Ok(())
+}
+
+/// An event's sort date, as `YYYY-MM-DD`.
+pub fn date_sort(value: &str) -> Result<NaiveDate, FieldError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        FieldError::new(
+            "date_sort",
+            format!("date_sort must be a YYYY-MM-DD date, got {value:?}"),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_not_blank() {
+        assert!(not_blank("name", "Martin").is_ok());
+        let err = not_blank("name", "  ").unwrap_err();
+        assert_eq!(err.field, "name");
+        assert_eq!(err.to_string(), "name must not be empty");
+    }
+
+    #[test]
+    fn test_person_name_needs_given_names_or_surname() {
+        assert!(person_name(Some("Jean"), None).is_ok());
+        assert!(person_name(None, Some("Martin")).is_ok());
+        assert!(person_name(Some(" "), Some("")).is_err());
+        assert!(person_name(None, None).is_err());
+    }
+
+    #[test]
+    fn test_coordinates_in_range() {
+        assert!(latitude(48.85).is_ok());
+        assert!(latitude(-90.0).is_ok());
+        assert!(latitude(91.0).is_err());
+        assert!(latitude(f64::NAN).is_err());
+        assert!(longitude(-180.0).is_ok());
+        assert!(longitude(180.5).is_err());
+        assert!(coordinates(None, Some(2.35)).is_ok());
+        assert_eq!(
+            coordinates(Some(482.8566), None).unwrap_err().field,
+            "latitude"
+        );
+    }
+
+    #[test]
+    fn test_date_sort() {
+        assert_eq!(
+            date_sort("1900-01-31").unwrap(),
+            NaiveDate::from_ymd_opt(1900, 1, 31).unwrap()
+        );
+        assert!(date_sort("1900-02-30").is_err());
+        assert_eq!(date_sort("31/01/1900").unwrap_err().field, "date_sort");
+    }
+}
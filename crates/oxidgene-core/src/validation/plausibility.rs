@@ -0,0 +1,255 @@
+//! Soft date checks of an event against the lives around it: a death
+//! before the birth, a marriage before age 12, a child born after their
+//! mother's death or before her 13th birthday.
+//!
+//! Unlike the input checks of the parent module these never reject a save:
+//! old records do contain such dates, and only the user can tell a typo
+//! from an oddity of the sources. Only contradictions that hold whatever
+//! the imprecision of the dates are reported.
+
+use chrono::Months;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::chronology::{definitely_before, event_year_range};
+use crate::enums::{DateQualifier, EventType};
+use crate::types::Event;
+
+/// Youngest plausible age at marriage.
+pub const MIN_MARRIAGE_AGE: u32 = 12;
+
+/// Youngest plausible age of a mother at a birth.
+pub const MIN_MOTHER_AGE: u32 = 13;
+
+/// What an implausible event contradicts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlausibilityKind {
+    /// A person's death dated before their birth.
+    DeathBeforeBirth,
+    /// A marriage before one of the spouses was [`MIN_MARRIAGE_AGE`].
+    MarriedTooYoung,
+    /// A birth after the mother's death.
+    BornAfterMotherDeath,
+    /// A birth before the mother was [`MIN_MOTHER_AGE`].
+    MotherTooYoung,
+}
+
+/// An implausible date of the checked event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlausibilityWarning {
+    pub kind: PlausibilityKind,
+    /// The event it contradicts: the birth or death of the person, of a
+    /// spouse or of the mother.
+    pub related_event_id: Uuid,
+}
+
+/// The dated lives an event is checked against.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lives<'a> {
+    /// Events of the event's person; unused for family events.
+    pub own: &'a [Event],
+    /// Events of each spouse, for a family event.
+    pub spouses: &'a [Vec<Event>],
+    /// Events of each mother of the event's person, for a birth.
+    pub mothers: &'a [Vec<Event>],
+}
+
+/// Warnings about `event`, being saved, given the `lives` around it.
+pub fn check_event(event: &Event, lives: Lives<'_>) -> Vec<PlausibilityWarning> {
+    let mut warnings = Vec::new();
+    let mut warn = |kind, related: &Event| {
+        warnings.push(PlausibilityWarning {
+            kind,
+            related_event_id: related.id,
+        });
+    };
+    let own: Vec<&Event> = lives.own.iter().filter(|e| e.id != event.id).collect();
+
+    match event.event_type {
+        EventType::Birth => {
+            for death in own.iter().filter(|e| e.event_type == EventType::Death) {
+                if definitely_before(death, event) {
+                    warn(PlausibilityKind::DeathBeforeBirth, death);
+                }
+            }
+            for mother in lives.mothers {
+                for other in mother {
+                    match other.event_type {
+                        EventType::Death if definitely_before(other, event) => {
+                            warn(PlausibilityKind::BornAfterMotherDeath, other);
+                        }
+                        EventType::Birth if younger_than(other, event, MIN_MOTHER_AGE) => {
+                            warn(PlausibilityKind::MotherTooYoung, other);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        EventType::Death => {
+            for birth in own.iter().filter(|e| e.event_type == EventType::Birth) {
+                if definitely_before(event, birth) {
+                    warn(PlausibilityKind::DeathBeforeBirth, birth);
+                }
+            }
+        }
+        EventType::Marriage => {
+            for birth in lives
+                .spouses
+                .iter()
+                .flatten()
+                .filter(|e| e.event_type == EventType::Birth)
+            {
+                if younger_than(birth, event, MIN_MARRIAGE_AGE) {
+                    warn(PlausibilityKind::MarriedTooYoung, birth);
+                }
+            }
+        }
+        _ => {}
+    }
+    warnings
+}
+
+/// Whether the person born at `birth` was certainly not yet `years` old at
+/// `at`: full dates are compared when both are exact, year ranges otherwise.
+fn younger_than(birth: &Event, at: &Event, years: u32) -> bool {
+    let exact = |e: &Event| e.date_qualifier == DateQualifier::Exact;
+    if exact(birth)
+        && exact(at)
+        && let (Some(born), Some(date)) = (birth.date_sort, at.date_sort)
+    {
+        return born
+            .checked_add_months(Months::new(years * 12))
+            .is_some_and(|anniversary| date < anniversary);
+    }
+    match (event_year_range(birth), event_year_range(at)) {
+        (Some(b), Some(a)) => matches!(
+            (b.earliest, a.latest),
+            (Some(born), Some(year)) if year - born < years as i32
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::Calendar;
+    use chrono::{NaiveDate, Utc};
+
+    fn event(event_type: EventType, date: &str) -> Event {
+        let now = Utc::now();
+        Event {
+            id: Uuid::now_v7(),
+            tree_id: Uuid::now_v7(),
+            event_type,
+            date_value: Some(date.to_string()),
+            date_sort: NaiveDate::parse_from_str(date, "%d %b %Y").ok(),
+            date_qualifier: DateQualifier::default(),
+            date_value2: None,
+            calendar: Calendar::default(),
+            cause: None,
+            age: None,
+            place_id: None,
+            place_id_to: None,
+            person_id: None,
+            family_id: None,
+            description: None,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            deleted_at: None,
+        }
+    }
+
+    fn kinds(warnings: Vec<PlausibilityWarning>) -> Vec<PlausibilityKind> {
+        warnings.into_iter().map(|w| w.kind).collect()
+    }
+
+    #[test]
+    fn flags_death_before_birth() {
+        let birth = event(EventType::Birth, "1850");
+        let death = event(EventType::Death, "1848");
+        let own = [birth.clone(), death.clone()];
+        let lives = Lives {
+            own: &own,
+            ..Lives::default()
+        };
+
+        let warnings = check_event(&death, lives);
+        assert_eq!(
+            kinds(warnings.clone()),
+            [PlausibilityKind::DeathBeforeBirth]
+        );
+        assert_eq!(warnings[0].related_event_id, birth.id);
+        assert_eq!(
+            kinds(check_event(&birth, lives)),
+            [PlausibilityKind::DeathBeforeBirth]
+        );
+
+        // Same year: either order is possible.
+        let death = event(EventType::Death, "1850");
+        assert!(check_event(&death, lives).is_empty());
+    }
+
+    #[test]
+    fn flags_young_spouses() {
+        let marriage = event(EventType::Marriage, "12 MAY 1862");
+        let spouses = [
+            vec![event(EventType::Birth, "1840")],
+            vec![event(EventType::Birth, "1851")],
+        ];
+        let lives = Lives {
+            spouses: &spouses,
+            ..Lives::default()
+        };
+        assert_eq!(
+            kinds(check_event(&marriage, lives)),
+            [PlausibilityKind::MarriedTooYoung]
+        );
+
+        // Born in 1850, possibly 12 by then.
+        let spouses = [vec![event(EventType::Birth, "1850")]];
+        let lives = Lives {
+            spouses: &spouses,
+            ..Lives::default()
+        };
+        assert!(check_event(&marriage, lives).is_empty());
+
+        // Full dates: one day short of 12.
+        let spouses = [vec![event(EventType::Birth, "13 MAY 1850")]];
+        let lives = Lives {
+            spouses: &spouses,
+            ..Lives::default()
+        };
+        assert_eq!(
+            kinds(check_event(&marriage, lives)),
+            [PlausibilityKind::MarriedTooYoung]
+        );
+    }
+
+    #[test]
+    fn flags_births_against_the_mother() {
+        let mothers = [vec![
+            event(EventType::Birth, "1840"),
+            event(EventType::Death, "1880"),
+        ]];
+        let lives = Lives {
+            mothers: &mothers,
+            ..Lives::default()
+        };
+
+        assert!(check_event(&event(EventType::Birth, "1870"), lives).is_empty());
+        assert_eq!(
+            kinds(check_event(&event(EventType::Birth, "1882"), lives)),
+            [PlausibilityKind::BornAfterMotherDeath]
+        );
+        assert_eq!(
+            kinds(check_event(&event(EventType::Birth, "1851"), lives)),
+            [PlausibilityKind::MotherTooYoung]
+        );
+        // Other events of the person are not checked against the mother.
+        assert!(check_event(&event(EventType::Census, "1890"), lives).is_empty());
+    }
+}
@@ -2,7 +2,7 @@
 
 use sea_orm::entity::prelude::*;
 
-use super::sea_enums::Confidence;
+use super::sea_enums::{Confidence, FactType};
 
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
 #[sea_orm(table_name = "citation")]
@@ -17,6 +17,8 @@ pub struct Model {
     pub confidence: Confidence,
     #[sea_orm(column_type = "Text")]
     pub text: Option<String>,
+    pub fact_type: Option<FactType>,
+    pub fact_ref: Option<Uuid>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
 }
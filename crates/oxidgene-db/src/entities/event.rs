@@ -17,12 +17,15 @@ pub struct Model {
     pub date_value2: Option<String>,
     pub calendar: Calendar,
     pub cause: Option<String>,
+    pub age: Option<String>,
     pub place_id: Option<Uuid>,
+    pub place_id_to: Option<Uuid>,
     pub person_id: Option<Uuid>,
     pub family_id: Option<Uuid>,
     pub description: Option<String>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub version: i32,
     pub deleted_at: Option<DateTimeUtc>,
 }
 
@@ -58,8 +61,8 @@ pub enum Relation {
     MediaLink,
     #[sea_orm(has_many = "super::note::Entity")]
     Note,
-    #[sea_orm(has_many = "super::event_witness::Entity")]
-    EventWitness,
+    #[sea_orm(has_many = "super::event_participant::Entity")]
+    EventParticipant,
 }
 
 impl Related<super::tree::Entity> for Entity {
@@ -104,9 +107,9 @@ impl Related<super::note::Entity> for Entity {
     }
 }
 
-impl Related<super::event_witness::Entity> for Entity {
+impl Related<super::event_participant::Entity> for Entity {
     fn to() -> RelationDef {
-        Relation::EventWitness.def()
+        Relation::EventParticipant.def()
     }
 }
 
@@ -1,14 +1,17 @@
-//! `event_witness` table entity.
+//! `event_participant` table entity.
 
 use sea_orm::entity::prelude::*;
 
+use super::sea_enums;
+
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
-#[sea_orm(table_name = "event_witness")]
+#[sea_orm(table_name = "event_participant")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: Uuid,
     pub event_id: Uuid,
     pub person_id: Uuid,
+    pub role: sea_enums::ParticipantRole,
     pub relation: Option<String>,
     pub sort_order: i32,
 }
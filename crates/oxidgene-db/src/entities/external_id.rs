@@ -0,0 +1,61 @@
+//! `external_id` table entity.
+
+use sea_orm::entity::prelude::*;
+
+use super::sea_enums::ExternalIdKind;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "external_id")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tree_id: Uuid,
+    pub person_id: Option<Uuid>,
+    pub family_id: Option<Uuid>,
+    pub kind: ExternalIdKind,
+    pub value: String,
+    pub ref_type: Option<String>,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::tree::Entity",
+        from = "Column::TreeId",
+        to = "super::tree::Column::Id"
+    )]
+    Tree,
+    #[sea_orm(
+        belongs_to = "super::person::Entity",
+        from = "Column::PersonId",
+        to = "super::person::Column::Id"
+    )]
+    Person,
+    #[sea_orm(
+        belongs_to = "super::family::Entity",
+        from = "Column::FamilyId",
+        to = "super::family::Column::Id"
+    )]
+    Family,
+}
+
+impl Related<super::tree::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tree.def()
+    }
+}
+
+impl Related<super::person::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Person.def()
+    }
+}
+
+impl Related<super::family::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Family.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -19,6 +19,7 @@ pub struct Model {
     pub place_id: Option<Uuid>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub version: i32,
     pub deleted_at: Option<DateTimeUtc>,
 }
 
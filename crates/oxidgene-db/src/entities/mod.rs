@@ -2,7 +2,8 @@
 
 pub mod citation;
 pub mod event;
-pub mod event_witness;
+pub mod event_participant;
+pub mod external_id;
 pub mod family;
 pub mod family_child;
 pub mod family_spouse;
@@ -12,7 +13,11 @@ pub mod note;
 pub mod person;
 pub mod person_ancestry;
 pub mod person_name;
+pub mod person_tag;
 pub mod place;
+pub mod research_task;
 pub mod sea_enums;
+pub mod share_link;
 pub mod source;
 pub mod tree;
+pub mod tree_membership;
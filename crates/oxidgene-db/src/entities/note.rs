@@ -2,6 +2,8 @@
 
 use sea_orm::entity::prelude::*;
 
+use super::sea_enums::NoteFormat;
+
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
 #[sea_orm(table_name = "note")]
 pub struct Model {
@@ -10,12 +12,14 @@ pub struct Model {
     pub tree_id: Uuid,
     #[sea_orm(column_type = "Text")]
     pub text: String,
+    pub format: NoteFormat,
     pub person_id: Option<Uuid>,
     pub event_id: Option<Uuid>,
     pub family_id: Option<Uuid>,
     pub source_id: Option<Uuid>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub version: i32,
     pub deleted_at: Option<DateTimeUtc>,
 }
 
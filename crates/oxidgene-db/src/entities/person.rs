@@ -12,8 +12,14 @@ pub struct Model {
     pub tree_id: Uuid,
     pub sex: Sex,
     pub privacy: Privacy,
+    pub living: Option<bool>,
+    /// Cached [`oxidgene_core::types::Person::display_name`].
+    pub display_name: Option<String>,
+    /// Set to refuse edits and deletion until unlocked.
+    pub locked: bool,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub version: i32,
     pub deleted_at: Option<DateTimeUtc>,
 }
 
@@ -17,6 +17,14 @@ pub struct Model {
     pub suffix: Option<String>,
     pub nickname: Option<String>,
     pub is_primary: bool,
+    /// Soundex code of the surname, for phonetic grouping.
+    pub surname_phonetic: Option<String>,
+    /// Soundex code of the first given name, for phonetic grouping.
+    pub given_phonetic: Option<String>,
+    /// Case- and accent-folded surname, for spelling-insensitive matching.
+    pub surname_norm: Option<String>,
+    /// Case- and accent-folded given names, for spelling-insensitive matching.
+    pub given_norm: Option<String>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
 }
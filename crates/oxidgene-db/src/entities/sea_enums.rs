@@ -15,6 +15,8 @@ pub enum Sex {
     Male,
     #[sea_orm(string_value = "female")]
     Female,
+    #[sea_orm(string_value = "intersex")]
+    Intersex,
     #[sea_orm(string_value = "unknown")]
     Unknown,
 }
@@ -24,6 +26,7 @@ impl From<enums::Sex> for Sex {
         match v {
             enums::Sex::Male => Self::Male,
             enums::Sex::Female => Self::Female,
+            enums::Sex::Intersex => Self::Intersex,
             enums::Sex::Unknown => Self::Unknown,
         }
     }
@@ -34,6 +37,7 @@ impl From<Sex> for enums::Sex {
         match v {
             Sex::Male => Self::Male,
             Sex::Female => Self::Female,
+            Sex::Intersex => Self::Intersex,
             Sex::Unknown => Self::Unknown,
         }
     }
@@ -115,6 +119,46 @@ impl From<SpouseRole> for enums::SpouseRole {
     }
 }
 
+/// Event participant role — stored as a string column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum ParticipantRole {
+    #[sea_orm(string_value = "primary")]
+    Primary,
+    #[sea_orm(string_value = "witness")]
+    Witness,
+    #[sea_orm(string_value = "godparent")]
+    Godparent,
+    #[sea_orm(string_value = "informant")]
+    Informant,
+    #[sea_orm(string_value = "officiant")]
+    Officiant,
+}
+
+impl From<enums::ParticipantRole> for ParticipantRole {
+    fn from(v: enums::ParticipantRole) -> Self {
+        match v {
+            enums::ParticipantRole::Primary => Self::Primary,
+            enums::ParticipantRole::Witness => Self::Witness,
+            enums::ParticipantRole::Godparent => Self::Godparent,
+            enums::ParticipantRole::Informant => Self::Informant,
+            enums::ParticipantRole::Officiant => Self::Officiant,
+        }
+    }
+}
+
+impl From<ParticipantRole> for enums::ParticipantRole {
+    fn from(v: ParticipantRole) -> Self {
+        match v {
+            ParticipantRole::Primary => Self::Primary,
+            ParticipantRole::Witness => Self::Witness,
+            ParticipantRole::Godparent => Self::Godparent,
+            ParticipantRole::Informant => Self::Informant,
+            ParticipantRole::Officiant => Self::Officiant,
+        }
+    }
+}
+
 /// Child type — stored as a string column.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
 #[sea_orm(rs_type = "String", db_type = "String(StringLen::N(15))")]
@@ -474,6 +518,130 @@ impl From<Calendar> for enums::Calendar {
     }
 }
 
+/// Note text format — stored as a string column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(10))")]
+pub enum NoteFormat {
+    #[sea_orm(string_value = "plain")]
+    Plain,
+    #[sea_orm(string_value = "markdown")]
+    Markdown,
+}
+
+impl From<enums::NoteFormat> for NoteFormat {
+    fn from(v: enums::NoteFormat) -> Self {
+        match v {
+            enums::NoteFormat::Plain => Self::Plain,
+            enums::NoteFormat::Markdown => Self::Markdown,
+        }
+    }
+}
+
+impl From<NoteFormat> for enums::NoteFormat {
+    fn from(v: NoteFormat) -> Self {
+        match v {
+            NoteFormat::Plain => Self::Plain,
+            NoteFormat::Markdown => Self::Markdown,
+        }
+    }
+}
+
+/// Research task status — stored as a string column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(8))")]
+pub enum TaskStatus {
+    #[sea_orm(string_value = "open")]
+    Open,
+    #[sea_orm(string_value = "done")]
+    Done,
+}
+
+impl From<enums::TaskStatus> for TaskStatus {
+    fn from(v: enums::TaskStatus) -> Self {
+        match v {
+            enums::TaskStatus::Open => Self::Open,
+            enums::TaskStatus::Done => Self::Done,
+        }
+    }
+}
+
+impl From<TaskStatus> for enums::TaskStatus {
+    fn from(v: TaskStatus) -> Self {
+        match v {
+            TaskStatus::Open => Self::Open,
+            TaskStatus::Done => Self::Done,
+        }
+    }
+}
+
+/// Fact a citation targets — stored as a string column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+pub enum FactType {
+    #[sea_orm(string_value = "name")]
+    Name,
+    #[sea_orm(string_value = "date")]
+    Date,
+    #[sea_orm(string_value = "place")]
+    Place,
+    #[sea_orm(string_value = "description")]
+    Description,
+    #[sea_orm(string_value = "age")]
+    Age,
+}
+
+impl From<enums::FactType> for FactType {
+    fn from(v: enums::FactType) -> Self {
+        match v {
+            enums::FactType::Name => Self::Name,
+            enums::FactType::Date => Self::Date,
+            enums::FactType::Place => Self::Place,
+            enums::FactType::Description => Self::Description,
+            enums::FactType::Age => Self::Age,
+        }
+    }
+}
+
+impl From<FactType> for enums::FactType {
+    fn from(v: FactType) -> Self {
+        match v {
+            FactType::Name => Self::Name,
+            FactType::Date => Self::Date,
+            FactType::Place => Self::Place,
+            FactType::Description => Self::Description,
+            FactType::Age => Self::Age,
+        }
+    }
+}
+
+/// Kind of an external record id — stored as a string column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(8))")]
+pub enum ExternalIdKind {
+    #[sea_orm(string_value = "uid")]
+    Uid,
+    #[sea_orm(string_value = "refn")]
+    Refn,
+}
+
+impl From<enums::ExternalIdKind> for ExternalIdKind {
+    fn from(v: enums::ExternalIdKind) -> Self {
+        match v {
+            enums::ExternalIdKind::Uid => Self::Uid,
+            enums::ExternalIdKind::Refn => Self::Refn,
+        }
+    }
+}
+
+impl From<ExternalIdKind> for enums::ExternalIdKind {
+    fn from(v: ExternalIdKind) -> Self {
+        match v {
+            ExternalIdKind::Uid => Self::Uid,
+            ExternalIdKind::Refn => Self::Refn,
+        }
+    }
+}
+
 /// Citation confidence level — stored as a string column.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
 #[sea_orm(rs_type = "String", db_type = "String(StringLen::N(10))")]
@@ -513,3 +681,35 @@ impl From<Confidence> for enums::Confidence {
         }
     }
 }
+
+/// Role of an actor on a tree — stored as a string column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(8))")]
+pub enum TreeRole {
+    #[sea_orm(string_value = "viewer")]
+    Viewer,
+    #[sea_orm(string_value = "editor")]
+    Editor,
+    #[sea_orm(string_value = "owner")]
+    Owner,
+}
+
+impl From<enums::TreeRole> for TreeRole {
+    fn from(v: enums::TreeRole) -> Self {
+        match v {
+            enums::TreeRole::Viewer => Self::Viewer,
+            enums::TreeRole::Editor => Self::Editor,
+            enums::TreeRole::Owner => Self::Owner,
+        }
+    }
+}
+
+impl From<TreeRole> for enums::TreeRole {
+    fn from(v: TreeRole) -> Self {
+        match v {
+            TreeRole::Viewer => Self::Viewer,
+            TreeRole::Editor => Self::Editor,
+            TreeRole::Owner => Self::Owner,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! `share_link` table entity.
+
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "share_link")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tree_id: Uuid,
+    #[sea_orm(unique)]
+    pub token: Uuid,
+    pub privatize: bool,
+    pub expires_at: Option<DateTimeUtc>,
+    pub created_by: Option<String>,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::tree::Entity",
+        from = "Column::TreeId",
+        to = "super::tree::Column::Id"
+    )]
+    Tree,
+}
+
+impl Related<super::tree::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tree.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -15,6 +15,7 @@ pub struct Model {
     pub repository_name: Option<String>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
+    pub version: i32,
     pub deleted_at: Option<DateTimeUtc>,
 }
 
@@ -0,0 +1,35 @@
+//! `tree_membership` table entity.
+
+use sea_orm::entity::prelude::*;
+
+use super::sea_enums::TreeRole;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "tree_membership")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub tree_id: Uuid,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub actor_id: String,
+    pub role: TreeRole,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::tree::Entity",
+        from = "Column::TreeId",
+        to = "super::tree::Column::Id"
+    )]
+    Tree,
+}
+
+impl Related<super::tree::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tree.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
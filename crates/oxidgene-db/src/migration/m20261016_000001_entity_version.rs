@@ -0,0 +1,50 @@
+//! Add a `version` column to the user-editable entities for optimistic
+//! concurrency: every update bumps it, and updates sent with a stale
+//! version are rejected instead of silently overwriting newer data.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tables that carry a `version` column.
+const TABLES: [&str; 7] = [
+    "tree", "person", "event", "place", "source", "media", "note",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One ALTER per table: SQLite can't add several columns at once.
+        for table in TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .add_column(
+                            ColumnDef::new(Alias::new("version"))
+                                .integer()
+                                .not_null()
+                                .default(1),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Alias::new(table))
+                        .drop_column(Alias::new("version"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+//! Add a `format` column to `note` (`plain` or `markdown`). Existing notes
+//! stay plain text.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Note::Table)
+                    .add_column(
+                        ColumnDef::new(Note::Format)
+                            .string_len(10)
+                            .not_null()
+                            .default("plain"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Note::Table)
+                    .drop_column(Note::Format)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Note {
+    Table,
+    Format,
+}
@@ -0,0 +1,66 @@
+//! Create the `share_link` table: read-only, token-addressed views of a
+//! tree that can be handed to people without edit access.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ShareLink::Table)
+                    .if_not_exists()
+                    .col(uuid(ShareLink::Id).primary_key())
+                    .col(uuid(ShareLink::TreeId))
+                    .col(uuid_uniq(ShareLink::Token))
+                    .col(boolean(ShareLink::Privatize))
+                    .col(timestamp_with_time_zone_null(ShareLink::ExpiresAt))
+                    .col(timestamp_with_time_zone(ShareLink::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_share_link_tree")
+                            .from(ShareLink::Table, ShareLink::TreeId)
+                            .to(Tree::Table, Tree::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_share_link_tree_id")
+                    .table(ShareLink::Table)
+                    .col(ShareLink::TreeId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ShareLink::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ShareLink {
+    Table,
+    Id,
+    TreeId,
+    Token,
+    Privatize,
+    ExpiresAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Tree {
+    Table,
+    Id,
+}
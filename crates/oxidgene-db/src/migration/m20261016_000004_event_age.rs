@@ -0,0 +1,38 @@
+//! Add an `age` column to `event`: the principal's age at the event as
+//! recorded in the source (GEDCOM `AGE`, e.g. `32y`, `CHILD`).
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Event::Table)
+                    .add_column(ColumnDef::new(Event::Age).string_len(32).null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Event::Table)
+                    .drop_column(Event::Age)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Age,
+}
@@ -0,0 +1,99 @@
+//! Generalize `event_witness` into `event_participant`: the table is
+//! renamed and gains a `role` column (`primary`, `witness`, `godparent`,
+//! `informant`, `officiant`). Existing rows get the role their free-text
+//! `relation` describes, `witness` otherwise.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// `(role, relation patterns)` applied in order; the first match wins.
+/// Mirrors `ParticipantRole::from_relation` for the common spellings.
+const ROLE_PATTERNS: &[(&str, &[&str])] = &[
+    (
+        "godparent",
+        &["%god%", "%sponsor%", "%parrain%", "%marraine%"],
+    ),
+    ("informant", &["%inform%", "%declarant%"]),
+    (
+        "officiant",
+        &["%offic%", "%clergy%", "%priest%", "%minister%", "%rabbi%"],
+    ),
+    ("primary", &["%head%", "%principal%", "%primary%"]),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .rename_table(
+                Table::rename()
+                    .table(EventWitness::Table, EventParticipant::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(EventParticipant::Table)
+                    .add_column(
+                        ColumnDef::new(EventParticipant::Role)
+                            .string_len(16)
+                            .not_null()
+                            .default("witness"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        for (role, patterns) in ROLE_PATTERNS.iter().rev() {
+            let mut matches = Condition::any();
+            for pattern in *patterns {
+                matches = matches.add(
+                    Expr::expr(Func::lower(Expr::col(EventParticipant::Relation))).like(*pattern),
+                );
+            }
+            manager
+                .exec_stmt(
+                    Query::update()
+                        .table(EventParticipant::Table)
+                        .value(EventParticipant::Role, *role)
+                        .cond_where(matches)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(EventParticipant::Table)
+                    .drop_column(EventParticipant::Role)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .rename_table(
+                Table::rename()
+                    .table(EventParticipant::Table, EventWitness::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum EventWitness {
+    Table,
+}
+
+#[derive(DeriveIden)]
+enum EventParticipant {
+    Table,
+    Role,
+    Relation,
+}
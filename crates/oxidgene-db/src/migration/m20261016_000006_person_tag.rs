@@ -0,0 +1,89 @@
+//! Create the `person_tag` table: free-text research tags on persons
+//! ("needs sources", "brick wall"), scoped to a tree for filtering.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PersonTag::Table)
+                    .if_not_exists()
+                    .col(uuid(PersonTag::Id).primary_key())
+                    .col(uuid(PersonTag::TreeId))
+                    .col(uuid(PersonTag::PersonId))
+                    .col(string_len(PersonTag::Tag, 64))
+                    .col(timestamp_with_time_zone(PersonTag::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_person_tag_tree")
+                            .from(PersonTag::Table, PersonTag::TreeId)
+                            .to(Tree::Table, Tree::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_person_tag_person")
+                            .from(PersonTag::Table, PersonTag::PersonId)
+                            .to(Person::Table, Person::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_person_tag_person_tag")
+                    .table(PersonTag::Table)
+                    .col(PersonTag::PersonId)
+                    .col(PersonTag::Tag)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_person_tag_tree_tag")
+                    .table(PersonTag::Table)
+                    .col(PersonTag::TreeId)
+                    .col(PersonTag::Tag)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PersonTag::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PersonTag {
+    Table,
+    Id,
+    TreeId,
+    PersonId,
+    Tag,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Tree {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Person {
+    Table,
+    Id,
+}
@@ -0,0 +1,41 @@
+//! Index `place` by `(tree_id, name)` for the place-name autocomplete's
+//! prefix (`LIKE 'q%'`) lookups.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_place_tree_id_name")
+                    .table(Place::Table)
+                    .col(Place::TreeId)
+                    .col(Place::Name)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_place_tree_id_name")
+                    .table(Place::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Place {
+    Table,
+    TreeId,
+    Name,
+}
@@ -0,0 +1,57 @@
+//! Add `fact_type` and `fact_ref` to `citation`, so a citation can support
+//! one fact (a name, an event's date or place) rather than a whole entity.
+//! Existing citations keep citing their whole entity.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only supports one column per ALTER TABLE.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Citation::Table)
+                    .add_column(ColumnDef::new(Citation::FactType).string_len(16).null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Citation::Table)
+                    .add_column(ColumnDef::new(Citation::FactRef).uuid().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Citation::Table)
+                    .drop_column(Citation::FactRef)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Citation::Table)
+                    .drop_column(Citation::FactType)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Citation {
+    Table,
+    FactType,
+    FactRef,
+}
@@ -0,0 +1,116 @@
+//! Create the `external_id` table: identifiers persons and families carry
+//! in other programs (GEDCOM `_UID`, `REFN`), kept for round-tripping and
+//! for recognising records on re-import.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ExternalId::Table)
+                    .if_not_exists()
+                    .col(uuid(ExternalId::Id).primary_key())
+                    .col(uuid(ExternalId::TreeId))
+                    .col(uuid_null(ExternalId::PersonId))
+                    .col(uuid_null(ExternalId::FamilyId))
+                    .col(string_len(ExternalId::Kind, 8))
+                    .col(string(ExternalId::Value))
+                    .col(string_null(ExternalId::RefType))
+                    .col(timestamp_with_time_zone(ExternalId::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_external_id_tree")
+                            .from(ExternalId::Table, ExternalId::TreeId)
+                            .to(Tree::Table, Tree::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_external_id_person")
+                            .from(ExternalId::Table, ExternalId::PersonId)
+                            .to(Person::Table, Person::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_external_id_family")
+                            .from(ExternalId::Table, ExternalId::FamilyId)
+                            .to(Family::Table, Family::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_external_id_tree_value")
+                    .table(ExternalId::Table)
+                    .col(ExternalId::TreeId)
+                    .col(ExternalId::Value)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_external_id_person")
+                    .table(ExternalId::Table)
+                    .col(ExternalId::PersonId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_external_id_family")
+                    .table(ExternalId::Table)
+                    .col(ExternalId::FamilyId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ExternalId::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ExternalId {
+    Table,
+    Id,
+    TreeId,
+    PersonId,
+    FamilyId,
+    Kind,
+    Value,
+    RefType,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Tree {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Person {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Family {
+    Table,
+    Id,
+}
@@ -0,0 +1,39 @@
+//! Add a `place_id_to` column to `event`: the destination of a move
+//! (emigration, immigration, residence change), `place_id` being where it
+//! started.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Event::Table)
+                    .add_column(ColumnDef::new(Event::PlaceIdTo).uuid().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Event::Table)
+                    .drop_column(Event::PlaceIdTo)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    PlaceIdTo,
+}
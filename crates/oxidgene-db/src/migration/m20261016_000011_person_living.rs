@@ -0,0 +1,39 @@
+//! Add a `living` column to `person`: an explicit living/deceased status
+//! that overrides the date heuristic of the privacy rules (`NULL` keeps
+//! the heuristic).
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Person::Table)
+                    .add_column(ColumnDef::new(Person::Living).boolean().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Person::Table)
+                    .drop_column(Person::Living)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Person {
+    Table,
+    Living,
+}
@@ -0,0 +1,100 @@
+//! Index every name of a person in `person_search_fts`, not just the
+//! primary one: an indexed `other_names` column (normalized) and an
+//! unindexed `aliases` column (display form, one per line) holding the
+//! non-primary names and nicknames.
+//!
+//! FTS5 virtual tables cannot gain columns, so the table is recreated. It
+//! only holds derived data: the cache service repopulates a tree's rows
+//! the first time the tree is searched.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, DbBackend, Statement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        recreate(manager, true).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        recreate(manager, false).await
+    }
+}
+
+async fn recreate(manager: &SchemaManager<'_>, with_aliases: bool) -> Result<(), DbErr> {
+    let conn = manager.get_connection();
+    let backend = manager.get_database_backend();
+    conn.execute(Statement::from_string(
+        backend,
+        "DROP TABLE IF EXISTS person_search_fts".to_owned(),
+    ))
+    .await?;
+
+    match backend {
+        DbBackend::Sqlite => {
+            let aliases = if with_aliases {
+                "other_names, aliases UNINDEXED,"
+            } else {
+                ""
+            };
+            conn.execute(Statement::from_string(
+                backend,
+                format!(
+                    "CREATE VIRTUAL TABLE person_search_fts USING fts5(
+                        surname,
+                        given_names,
+                        maiden_name,
+                        {aliases}
+                        birth_year,
+                        death_year,
+                        person_id UNINDEXED,
+                        tree_id UNINDEXED,
+                        sex UNINDEXED,
+                        display_name UNINDEXED,
+                        birth_place UNINDEXED,
+                        date_sort UNINDEXED
+                    )"
+                ),
+            ))
+            .await?;
+        }
+        _ => {
+            let aliases = if with_aliases {
+                "other_names TEXT NOT NULL DEFAULT '', aliases TEXT NOT NULL DEFAULT '',"
+            } else {
+                ""
+            };
+            conn.execute(Statement::from_string(
+                backend,
+                format!(
+                    "CREATE TABLE person_search_fts (
+                        person_id TEXT NOT NULL PRIMARY KEY,
+                        tree_id TEXT NOT NULL,
+                        surname TEXT NOT NULL DEFAULT '',
+                        given_names TEXT NOT NULL DEFAULT '',
+                        maiden_name TEXT,
+                        {aliases}
+                        birth_year TEXT,
+                        death_year TEXT,
+                        sex TEXT NOT NULL DEFAULT 'unknown',
+                        display_name TEXT NOT NULL DEFAULT '',
+                        birth_place TEXT,
+                        date_sort TEXT
+                    )"
+                ),
+            ))
+            .await?;
+            conn.execute(Statement::from_string(
+                backend,
+                "CREATE INDEX IF NOT EXISTS idx_person_search_fts_tree_id \
+                 ON person_search_fts (tree_id)"
+                    .to_owned(),
+            ))
+            .await?;
+        }
+    }
+    Ok(())
+}
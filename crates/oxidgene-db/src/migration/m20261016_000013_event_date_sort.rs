@@ -0,0 +1,74 @@
+//! Index `event` by `(tree_id, date_sort)` for the tree-wide timeline's
+//! year-range queries, and fill in the `date_sort` of events recorded with
+//! only a date phrase.
+
+use oxidgene_core::types::parse_date_sort;
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+use uuid::Uuid;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_event_tree_id_date_sort")
+                    .table(Event::Table)
+                    .col(Event::TreeId)
+                    .col(Event::DateSort)
+                    .to_owned(),
+            )
+            .await?;
+
+        let conn = manager.get_connection();
+        let backend = manager.get_database_backend();
+        let undated = Query::select()
+            .columns([Event::Id, Event::DateValue])
+            .from(Event::Table)
+            .and_where(Expr::col(Event::DateSort).is_null())
+            .and_where(Expr::col(Event::DateValue).is_not_null())
+            .to_owned();
+        for row in conn.query_all(backend.build(&undated)).await? {
+            let id: Uuid = row.try_get("", "id")?;
+            let date_value: String = row.try_get("", "date_value")?;
+            let Some(date_sort) = parse_date_sort(&date_value) else {
+                continue;
+            };
+            manager
+                .exec_stmt(
+                    Query::update()
+                        .table(Event::Table)
+                        .value(Event::DateSort, date_sort)
+                        .and_where(Expr::col(Event::Id).eq(id))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The filled-in dates stay: they are what the phrases say.
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_event_tree_id_date_sort")
+                    .table(Event::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Id,
+    TreeId,
+    DateValue,
+    DateSort,
+}
@@ -0,0 +1,99 @@
+//! Add `surname_phonetic` / `given_phonetic` columns to `person_name`: the
+//! Soundex codes of the name, indexed so that duplicate detection groups
+//! persons by an equality join instead of recomputing codes per query.
+//! Existing names are backfilled.
+
+use oxidgene_core::fuzzy::{given_phonetic, surname_phonetic};
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+use uuid::Uuid;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One column per statement: SQLite cannot add several at once.
+        for column in [PersonName::SurnamePhonetic, PersonName::GivenPhonetic] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(PersonName::Table)
+                        .add_column(ColumnDef::new(column).string().null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_person_name_phonetic")
+                    .table(PersonName::Table)
+                    .col(PersonName::SurnamePhonetic)
+                    .col(PersonName::GivenPhonetic)
+                    .to_owned(),
+            )
+            .await?;
+
+        let conn = manager.get_connection();
+        let backend = manager.get_database_backend();
+        let names = Query::select()
+            .columns([PersonName::Id, PersonName::GivenNames, PersonName::Surname])
+            .from(PersonName::Table)
+            .to_owned();
+        for row in conn.query_all(backend.build(&names)).await? {
+            let id: Uuid = row.try_get("", "id")?;
+            let given_names: Option<String> = row.try_get("", "given_names")?;
+            let surname: Option<String> = row.try_get("", "surname")?;
+            let surname_code = surname.as_deref().and_then(surname_phonetic);
+            let given_code = given_names.as_deref().and_then(given_phonetic);
+            if surname_code.is_none() && given_code.is_none() {
+                continue;
+            }
+            manager
+                .exec_stmt(
+                    Query::update()
+                        .table(PersonName::Table)
+                        .value(PersonName::SurnamePhonetic, surname_code)
+                        .value(PersonName::GivenPhonetic, given_code)
+                        .and_where(Expr::col(PersonName::Id).eq(id))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_person_name_phonetic")
+                    .table(PersonName::Table)
+                    .to_owned(),
+            )
+            .await?;
+        for column in [PersonName::SurnamePhonetic, PersonName::GivenPhonetic] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(PersonName::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum PersonName {
+    Table,
+    Id,
+    GivenNames,
+    Surname,
+    SurnamePhonetic,
+    GivenPhonetic,
+}
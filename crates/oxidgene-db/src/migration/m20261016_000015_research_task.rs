@@ -0,0 +1,115 @@
+//! Create the `research_task` table: research to-dos on a person or a
+//! family, with an open/done status and an optional due date, listed per
+//! tree.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ResearchTask::Table)
+                    .if_not_exists()
+                    .col(uuid(ResearchTask::Id).primary_key())
+                    .col(uuid(ResearchTask::TreeId))
+                    .col(uuid_null(ResearchTask::PersonId))
+                    .col(uuid_null(ResearchTask::FamilyId))
+                    .col(string(ResearchTask::Title))
+                    .col(text_null(ResearchTask::Description))
+                    .col(string_len(ResearchTask::Status, 8))
+                    .col(date_null(ResearchTask::DueDate))
+                    .col(timestamp_with_time_zone(ResearchTask::CreatedAt))
+                    .col(timestamp_with_time_zone(ResearchTask::UpdatedAt))
+                    .col(integer(ResearchTask::Version).default(1))
+                    .col(timestamp_with_time_zone_null(ResearchTask::DeletedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_research_task_tree")
+                            .from(ResearchTask::Table, ResearchTask::TreeId)
+                            .to(Tree::Table, Tree::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_research_task_person")
+                            .from(ResearchTask::Table, ResearchTask::PersonId)
+                            .to(Person::Table, Person::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_research_task_family")
+                            .from(ResearchTask::Table, ResearchTask::FamilyId)
+                            .to(Family::Table, Family::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_research_task_tree_status")
+                    .table(ResearchTask::Table)
+                    .col(ResearchTask::TreeId)
+                    .col(ResearchTask::Status)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_research_task_person")
+                    .table(ResearchTask::Table)
+                    .col(ResearchTask::PersonId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ResearchTask::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ResearchTask {
+    Table,
+    Id,
+    TreeId,
+    PersonId,
+    FamilyId,
+    Title,
+    Description,
+    Status,
+    DueDate,
+    CreatedAt,
+    UpdatedAt,
+    Version,
+    DeletedAt,
+}
+
+#[derive(DeriveIden)]
+enum Tree {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Person {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Family {
+    Table,
+    Id,
+}
@@ -0,0 +1,103 @@
+//! Add a `display_name` column to `person`: the display name of the person's
+//! primary name (else their first), cached so that person lists need not
+//! join names. Existing persons are backfilled.
+
+use std::collections::HashMap;
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::ConnectionTrait;
+use uuid::Uuid;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Person::Table)
+                    .add_column(ColumnDef::new(Person::DisplayName).string().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        let conn = manager.get_connection();
+        let backend = manager.get_database_backend();
+        let names = Query::select()
+            .columns([
+                PersonName::PersonId,
+                PersonName::Prefix,
+                PersonName::GivenNames,
+                PersonName::Surname,
+                PersonName::Suffix,
+                PersonName::IsPrimary,
+            ])
+            .from(PersonName::Table)
+            .order_by(PersonName::Id, Order::Asc)
+            .to_owned();
+        // Mirrors `PersonName::display_name_of`: the primary name wins, else
+        // the first one.
+        let mut display_names: HashMap<Uuid, (bool, String)> = HashMap::new();
+        for row in conn.query_all(backend.build(&names)).await? {
+            let person_id: Uuid = row.try_get("", "person_id")?;
+            let is_primary: bool = row.try_get("", "is_primary")?;
+            if display_names
+                .get(&person_id)
+                .is_some_and(|(primary, _)| *primary || !is_primary)
+            {
+                continue;
+            }
+            let mut parts = Vec::new();
+            for column in ["prefix", "given_names", "surname", "suffix"] {
+                if let Some(part) = row.try_get::<Option<String>>("", column)? {
+                    parts.push(part);
+                }
+            }
+            display_names.insert(person_id, (is_primary, parts.join(" ")));
+        }
+        for (person_id, (_, display_name)) in display_names {
+            manager
+                .exec_stmt(
+                    Query::update()
+                        .table(Person::Table)
+                        .value(Person::DisplayName, display_name)
+                        .and_where(Expr::col(Person::Id).eq(person_id))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Person::Table)
+                    .drop_column(Person::DisplayName)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Person {
+    Table,
+    Id,
+    DisplayName,
+}
+
+#[derive(DeriveIden)]
+enum PersonName {
+    Table,
+    Id,
+    PersonId,
+    Prefix,
+    GivenNames,
+    Surname,
+    Suffix,
+    IsPrimary,
+}
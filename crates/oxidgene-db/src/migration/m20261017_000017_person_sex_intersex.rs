@@ -0,0 +1,53 @@
+//! Allow `intersex` (GEDCOM `SEX X`) as a `person.sex` value.
+//!
+//! The column is a plain string, so existing `male`/`female`/`unknown` rows
+//! are left as they are. On PostgreSQL the allowed values are pinned by a
+//! `CHECK` constraint that includes the new one; SQLite cannot add a
+//! constraint to an existing table, and keeps relying on the enum mapping.
+
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, DbBackend, Statement};
+
+const CONSTRAINT: &str = "person_sex_check";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+        manager
+            .get_connection()
+            .execute(Statement::from_string(
+                DbBackend::Postgres,
+                format!(
+                    "ALTER TABLE person ADD CONSTRAINT {CONSTRAINT} \
+                     CHECK (sex IN ('male', 'female', 'intersex', 'unknown'))"
+                ),
+            ))
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+        let conn = manager.get_connection();
+        // Intersex persons become unknown again, as before the variant.
+        conn.execute(Statement::from_string(
+            DbBackend::Postgres,
+            "UPDATE person SET sex = 'unknown' WHERE sex = 'intersex'".to_owned(),
+        ))
+        .await?;
+        conn.execute(Statement::from_string(
+            DbBackend::Postgres,
+            format!("ALTER TABLE person DROP CONSTRAINT IF EXISTS {CONSTRAINT}"),
+        ))
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+//! Add a `locked` column to `person`: a locked person cannot be edited or
+//! deleted until it is unlocked, protecting finished research from stray
+//! edits.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Person::Table)
+                    .add_column(
+                        ColumnDef::new(Person::Locked)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Person::Table)
+                    .drop_column(Person::Locked)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Person {
+    Table,
+    Locked,
+}
@@ -0,0 +1,39 @@
+//! Add a `created_by` column to `share_link`: the id of the actor who
+//! created the link, so an owner can tell who shared their tree. Existing
+//! links predate authentication and keep it `NULL`.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ShareLink::Table)
+                    .add_column(ColumnDef::new(ShareLink::CreatedBy).string().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ShareLink::Table)
+                    .drop_column(ShareLink::CreatedBy)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ShareLink {
+    Table,
+    CreatedBy,
+}
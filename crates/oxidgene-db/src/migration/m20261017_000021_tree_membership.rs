@@ -0,0 +1,69 @@
+//! Create the `tree_membership` table: the role (`viewer`, `editor`,
+//! `owner`) of each actor on a tree, enforced when authentication is on.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TreeMembership::Table)
+                    .if_not_exists()
+                    .col(uuid(TreeMembership::TreeId))
+                    .col(string(TreeMembership::ActorId))
+                    .col(string_len(TreeMembership::Role, 8))
+                    .col(timestamp_with_time_zone(TreeMembership::CreatedAt))
+                    .col(timestamp_with_time_zone(TreeMembership::UpdatedAt))
+                    .primary_key(
+                        Index::create()
+                            .col(TreeMembership::TreeId)
+                            .col(TreeMembership::ActorId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_tree_membership_tree")
+                            .from(TreeMembership::Table, TreeMembership::TreeId)
+                            .to(Tree::Table, Tree::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tree_membership_actor_id")
+                    .table(TreeMembership::Table)
+                    .col(TreeMembership::ActorId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TreeMembership::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TreeMembership {
+    Table,
+    TreeId,
+    ActorId,
+    Role,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Tree {
+    Table,
+    Id,
+}
@@ -1,6 +1,27 @@
 //! Database migrations for OxidGene.
 
 pub mod m20250101_000001_initial;
+pub mod m20261016_000001_entity_version;
+pub mod m20261016_000002_note_format;
+pub mod m20261016_000003_share_link;
+pub mod m20261016_000004_event_age;
+pub mod m20261016_000005_event_participant;
+pub mod m20261016_000006_person_tag;
+pub mod m20261016_000007_place_name_index;
+pub mod m20261016_000008_citation_fact;
+pub mod m20261016_000009_external_id;
+pub mod m20261016_000010_event_place_to;
+pub mod m20261016_000011_person_living;
+pub mod m20261016_000012_search_aliases;
+pub mod m20261016_000013_event_date_sort;
+pub mod m20261016_000014_person_name_phonetic;
+pub mod m20261016_000015_research_task;
+pub mod m20261016_000016_person_display_name;
+pub mod m20261017_000017_person_sex_intersex;
+pub mod m20261017_000018_person_name_norm;
+pub mod m20261017_000019_person_locked;
+pub mod m20261017_000020_share_link_created_by;
+pub mod m20261017_000021_tree_membership;
 
 use sea_orm_migration::prelude::*;
 
@@ -9,6 +30,29 @@ pub struct Migrator;
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20250101_000001_initial::Migration)]
+        vec![
+            Box::new(m20250101_000001_initial::Migration),
+            Box::new(m20261016_000001_entity_version::Migration),
+            Box::new(m20261016_000002_note_format::Migration),
+            Box::new(m20261016_000003_share_link::Migration),
+            Box::new(m20261016_000004_event_age::Migration),
+            Box::new(m20261016_000005_event_participant::Migration),
+            Box::new(m20261016_000006_person_tag::Migration),
+            Box::new(m20261016_000007_place_name_index::Migration),
+            Box::new(m20261016_000008_citation_fact::Migration),
+            Box::new(m20261016_000009_external_id::Migration),
+            Box::new(m20261016_000010_event_place_to::Migration),
+            Box::new(m20261016_000011_person_living::Migration),
+            Box::new(m20261016_000012_search_aliases::Migration),
+            Box::new(m20261016_000013_event_date_sort::Migration),
+            Box::new(m20261016_000014_person_name_phonetic::Migration),
+            Box::new(m20261016_000015_research_task::Migration),
+            Box::new(m20261016_000016_person_display_name::Migration),
+            Box::new(m20261017_000017_person_sex_intersex::Migration),
+            Box::new(m20261017_000018_person_name_norm::Migration),
+            Box::new(m20261017_000019_person_locked::Migration),
+            Box::new(m20261017_000020_share_link_created_by::Migration),
+            Box::new(m20261017_000021_tree_membership::Migration),
+        ]
     }
 }
@@ -0,0 +1,236 @@
+//! Recent activity in a tree: the most recently created or updated
+//! entities across all types, for "recently edited" breadcrumbs.
+//!
+//! One `UNION ALL` over the entities' `updated_at` columns picks the rows;
+//! labels that need other tables (person names, family spouses) are then
+//! resolved in bulk for that page only.
+
+use chrono::{DateTime, Utc};
+use oxidgene_core::enums::EventType;
+use oxidgene_core::error::OxidGeneError;
+use sea_orm::entity::prelude::*;
+use sea_orm::{DbBackend, Statement};
+use uuid::Uuid;
+
+use crate::entities::sea_enums;
+use crate::repo::owner_label::OwnerLabels;
+
+/// Longest note excerpt used as a label, in characters.
+const NOTE_LABEL_MAX_CHARS: usize = 80;
+
+/// The type of entity an [`ActivityEntry`] points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityKind {
+    Person,
+    Family,
+    Event,
+    Place,
+    Source,
+    Media,
+    Note,
+}
+
+impl ActivityKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Person => "person",
+            Self::Family => "family",
+            Self::Event => "event",
+            Self::Place => "place",
+            Self::Source => "source",
+            Self::Media => "media",
+            Self::Note => "note",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "person" => Self::Person,
+            "family" => Self::Family,
+            "event" => Self::Event,
+            "place" => Self::Place,
+            "source" => Self::Source,
+            "media" => Self::Media,
+            "note" => Self::Note,
+            _ => return None,
+        })
+    }
+}
+
+/// A recently created or updated entity.
+#[derive(Debug, Clone)]
+pub struct ActivityEntry {
+    pub kind: ActivityKind,
+    pub id: Uuid,
+    /// Human-readable label: a person's or family's names, a place name, a
+    /// source or media title, a note excerpt. For events, the names of the
+    /// person or family the event belongs to.
+    pub label: String,
+    /// Type of the event, for [`ActivityKind::Event`] entries.
+    pub event_type: Option<EventType>,
+    /// Person the entity belongs to (the person itself for
+    /// [`ActivityKind::Person`]), for linking to their page.
+    pub person_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ActivityEntry {
+    /// Whether the entity has not been edited since it was created.
+    pub fn is_new(&self) -> bool {
+        self.created_at == self.updated_at
+    }
+}
+
+/// A row of the activity `UNION`, before labels are resolved.
+struct ActivityRow {
+    kind: ActivityKind,
+    id: Uuid,
+    label: Option<String>,
+    person_id: Option<Uuid>,
+    family_id: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+pub struct ActivityRepo;
+
+impl ActivityRepo {
+    /// The `limit` most recently created or updated live entities of a
+    /// tree, newest first.
+    pub async fn recent(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        limit: u64,
+    ) -> Result<Vec<ActivityEntry>, OxidGeneError> {
+        let backend = db.get_database_backend();
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                backend,
+                recent_sql(backend),
+                [tree_id.into(), (limit as i64).into()],
+            ))
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+
+        let mut activity = Vec::with_capacity(rows.len());
+        for row in rows {
+            let get_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+            let kind: String = row.try_get("", "kind").map_err(get_err)?;
+            let Some(kind) = ActivityKind::parse(&kind) else {
+                continue;
+            };
+            activity.push(ActivityRow {
+                kind,
+                id: row.try_get("", "id").map_err(get_err)?,
+                label: row.try_get("", "label").map_err(get_err)?,
+                person_id: row.try_get("", "person_id").map_err(get_err)?,
+                family_id: row.try_get("", "family_id").map_err(get_err)?,
+                created_at: row.try_get("", "created_at").map_err(get_err)?,
+                updated_at: row.try_get("", "updated_at").map_err(get_err)?,
+            });
+        }
+
+        let labels = OwnerLabels::load(
+            db,
+            activity.iter().filter_map(|r| r.person_id),
+            activity.iter().filter_map(|r| match r.kind {
+                ActivityKind::Family => Some(r.id),
+                ActivityKind::Event => r.family_id,
+                _ => None,
+            }),
+        )
+        .await?;
+
+        Ok(activity
+            .into_iter()
+            .map(|row| {
+                let (label, event_type) = match row.kind {
+                    ActivityKind::Person => (labels.person(row.person_id), None),
+                    ActivityKind::Family => (labels.family(Some(row.id)), None),
+                    ActivityKind::Event => {
+                        let event_type = row
+                            .label
+                            .as_ref()
+                            .and_then(|t| sea_enums::EventType::try_from_value(t).ok())
+                            .map(EventType::from);
+                        let owner = if row.person_id.is_some() {
+                            labels.person(row.person_id)
+                        } else {
+                            labels.family(row.family_id)
+                        };
+                        (owner, event_type)
+                    }
+                    ActivityKind::Note => {
+                        (note_excerpt(row.label.as_deref().unwrap_or_default()), None)
+                    }
+                    ActivityKind::Place | ActivityKind::Source | ActivityKind::Media => {
+                        (row.label.unwrap_or_default(), None)
+                    }
+                };
+                ActivityEntry {
+                    kind: row.kind,
+                    id: row.id,
+                    label,
+                    event_type,
+                    person_id: row.person_id,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                }
+            })
+            .collect())
+    }
+}
+
+/// The activity `UNION`: one branch per entity type, each yielding
+/// `kind, id, label, person_id, family_id, created_at, updated_at` (`label`
+/// is the raw event type for events, the full text for notes). Binds the
+/// tree id, then the limit.
+fn recent_sql(backend: DbBackend) -> String {
+    let (tree, limit) = match backend {
+        DbBackend::Sqlite => ("?1", "?2"),
+        _ => ("$1", "$2"),
+    };
+    format!(
+        r#"
+        SELECT 'event' AS kind, id, CAST(event_type AS TEXT) AS label,
+               person_id, family_id, created_at, updated_at
+        FROM event WHERE tree_id = {tree} AND deleted_at IS NULL
+        UNION ALL
+        SELECT 'person', id, NULL, id, NULL, created_at, updated_at
+        FROM person WHERE tree_id = {tree} AND deleted_at IS NULL
+        UNION ALL
+        SELECT 'family', id, NULL, NULL, id, created_at, updated_at
+        FROM family WHERE tree_id = {tree} AND deleted_at IS NULL
+        UNION ALL
+        SELECT 'place', id, name, NULL, NULL, created_at, updated_at
+        FROM place WHERE tree_id = {tree}
+        UNION ALL
+        SELECT 'source', id, title, NULL, NULL, created_at, updated_at
+        FROM source WHERE tree_id = {tree} AND deleted_at IS NULL
+        UNION ALL
+        SELECT 'media', id, COALESCE(title, file_name), NULL, NULL, created_at, updated_at
+        FROM media WHERE tree_id = {tree} AND deleted_at IS NULL
+        UNION ALL
+        SELECT 'note', id, text, person_id, family_id, created_at, updated_at
+        FROM note WHERE tree_id = {tree} AND deleted_at IS NULL
+        ORDER BY updated_at DESC, id DESC
+        LIMIT {limit}
+        "#
+    )
+}
+
+/// First line of a note, shortened to [`NOTE_LABEL_MAX_CHARS`].
+fn note_excerpt(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("");
+    if line.chars().count() > NOTE_LABEL_MAX_CHARS {
+        let cut: String = line.chars().take(NOTE_LABEL_MAX_CHARS - 1).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        line.to_string()
+    }
+}
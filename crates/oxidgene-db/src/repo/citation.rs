@@ -1,7 +1,7 @@
 //! Repository for `Citation` entities (CRUD, no soft delete).
 
 use chrono::Utc;
-use oxidgene_core::enums::Confidence;
+use oxidgene_core::enums::{Confidence, FactType};
 use oxidgene_core::error::OxidGeneError;
 use oxidgene_core::types::Citation;
 use sea_orm::entity::prelude::*;
@@ -41,6 +41,32 @@ impl CitationRepo {
         Ok(models.into_iter().map(into_domain).collect())
     }
 
+    /// List citations attached to any of the given events.
+    pub async fn list_by_events(
+        db: &DatabaseConnection,
+        event_ids: &[Uuid],
+    ) -> Result<Vec<Citation>, OxidGeneError> {
+        let models = Entity::find()
+            .filter(Column::EventId.is_in(event_ids.iter().copied()))
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
+    /// List citations attached to a family itself (not to its events).
+    pub async fn list_by_family(
+        db: &DatabaseConnection,
+        family_id: Uuid,
+    ) -> Result<Vec<Citation>, OxidGeneError> {
+        let models = Entity::find()
+            .filter(Column::FamilyId.eq(family_id))
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
     /// Get a single citation by ID.
     pub async fn get(db: &DatabaseConnection, id: Uuid) -> Result<Citation, OxidGeneError> {
         Entity::find_by_id(id)
@@ -57,7 +83,7 @@ impl CitationRepo {
     /// Create a new citation.
     #[allow(clippy::too_many_arguments)]
     pub async fn create(
-        db: &DatabaseConnection,
+        db: &impl ConnectionTrait,
         id: Uuid,
         source_id: Uuid,
         person_id: Option<Uuid>,
@@ -66,6 +92,8 @@ impl CitationRepo {
         page: Option<String>,
         confidence: Confidence,
         text: Option<String>,
+        fact_type: Option<FactType>,
+        fact_ref: Option<Uuid>,
     ) -> Result<Citation, OxidGeneError> {
         let now = Utc::now();
         let model = citation::ActiveModel {
@@ -77,6 +105,8 @@ impl CitationRepo {
             page: Set(page),
             confidence: Set(sea_enums::Confidence::from(confidence)),
             text: Set(text),
+            fact_type: Set(fact_type.map(sea_enums::FactType::from)),
+            fact_ref: Set(fact_ref),
             created_at: Set(now),
             updated_at: Set(now),
         };
@@ -150,6 +180,8 @@ fn into_domain(m: citation::Model) -> Citation {
         page: m.page,
         confidence: m.confidence.into(),
         text: m.text,
+        fact_type: m.fact_type.map(Into::into),
+        fact_ref: m.fact_ref,
         created_at: m.created_at,
         updated_at: m.updated_at,
     }
@@ -1,13 +1,16 @@
 //! Aggregated "dictionary" queries: distinct values entered across a tree
 //! (family names, occupations) or existing entities (sources, places) paired
 //! with how many persons/events reference them, plus drill-down lookups
-//! resolving a value back to the persons that carry it.
+//! resolving a value back to the persons that carry it. Surname counts can
+//! also be spread over birth decades or birth places, for distribution
+//! charts.
 
 use oxidgene_core::enums::EventType;
 use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::search::{index_initial, normalize_for_search};
 use oxidgene_core::types::{Place, Source, year_from_date};
-use sea_orm::QueryFilter;
 use sea_orm::entity::prelude::*;
+use sea_orm::{Condition, QueryFilter};
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
@@ -21,6 +24,45 @@ pub struct DictionaryValueEntry {
     pub count: i64,
 }
 
+/// A surname in the tree's alphabetical index: the number of persons whose
+/// primary name carries it and the letter it is filed under.
+#[derive(Debug, Clone)]
+pub struct SurnameIndexEntry {
+    pub surname: String,
+    pub initial: char,
+    pub count: i64,
+}
+
+/// What [`DictionaryRepo::surname_distribution`] spreads surnames over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurnameBucketing {
+    /// Decade of the birth date (`1850` for 1850–1859).
+    Decade,
+    /// Name of the birth place.
+    Place,
+}
+
+/// The number of persons of a surname born in one bucket.
+#[derive(Debug, Clone)]
+pub struct SurnameBucketCount {
+    pub surname: String,
+    /// The decade's first year, or the place name.
+    pub bucket: String,
+    pub count: i64,
+}
+
+/// Surnames counted per birth decade or place.
+#[derive(Debug, Clone, Default)]
+pub struct SurnameDistribution {
+    /// Surnames with their total over all buckets, most frequent first.
+    pub surnames: Vec<(String, i64)>,
+    /// Buckets in chart order: decades chronologically, places most
+    /// frequent first.
+    pub buckets: Vec<String>,
+    /// Non-zero counts, in surname then bucket order.
+    pub counts: Vec<SurnameBucketCount>,
+}
+
 /// A person's name (split given/surname) plus birth/death years, resolved in
 /// bulk for a dictionary usage drill-down list.
 #[derive(Debug, Clone)]
@@ -78,6 +120,217 @@ impl DictionaryRepo {
         Ok(sorted_entries(per_value))
     }
 
+    /// Distinct surnames of the persons' primary names, with the number of
+    /// persons carrying each, sorted by initial then surname. Grouped in SQL
+    /// so large trees never load their names.
+    pub async fn surname_index(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+    ) -> Result<Vec<SurnameIndexEntry>, OxidGeneError> {
+        use sea_orm::{DbBackend, Statement};
+
+        let backend = db.get_database_backend();
+        let sql = match backend {
+            DbBackend::Sqlite => {
+                r#"
+                    SELECT pn.surname, COUNT(DISTINCT pn.person_id) AS person_count
+                    FROM person_name pn
+                    INNER JOIN person p ON p.id = pn.person_id
+                    WHERE p.tree_id = ?
+                      AND p.deleted_at IS NULL
+                      AND pn.is_primary = ?
+                      AND pn.surname IS NOT NULL
+                      AND pn.surname <> ''
+                    GROUP BY pn.surname
+                "#
+            }
+            _ => {
+                r#"
+                    SELECT pn.surname, COUNT(DISTINCT pn.person_id) AS person_count
+                    FROM person_name pn
+                    INNER JOIN person p ON p.id = pn.person_id
+                    WHERE p.tree_id = $1
+                      AND p.deleted_at IS NULL
+                      AND pn.is_primary = $2
+                      AND pn.surname IS NOT NULL
+                      AND pn.surname <> ''
+                    GROUP BY pn.surname
+                "#
+            }
+        };
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                backend,
+                sql,
+                [tree_id.into(), true.into()],
+            ))
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+
+        let mut entries = Vec::with_capacity(rows.len());
+        for row in rows {
+            let surname: String = row
+                .try_get("", "surname")
+                .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+            let count: i64 = row
+                .try_get("", "person_count")
+                .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+            entries.push(SurnameIndexEntry {
+                initial: index_initial(&surname),
+                surname,
+                count,
+            });
+        }
+        entries.sort_by_cached_key(|e| (e.initial, normalize_for_search(&e.surname)));
+        Ok(entries)
+    }
+
+    /// The `limit` most frequent surnames of primary names, counted per
+    /// birth decade or birth place. Surnames are grouped case- and
+    /// accent-insensitively (on `surname_norm`); persons without a dated,
+    /// resp. placed, birth are left out. Grouped in SQL like
+    /// [`Self::surname_index`].
+    pub async fn surname_distribution(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        over: SurnameBucketing,
+        limit: usize,
+    ) -> Result<SurnameDistribution, OxidGeneError> {
+        use sea_orm::{DbBackend, Statement};
+
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        let backend = db.get_database_backend();
+        let sigil = match backend {
+            DbBackend::Sqlite => '?',
+            _ => '$',
+        };
+        let (bucket, place_join, bucket_filter) = match (over, backend) {
+            (SurnameBucketing::Decade, DbBackend::Sqlite) => (
+                "(CAST(strftime('%Y', e.date_sort) AS INTEGER) / 10) * 10",
+                "",
+                "AND e.date_sort IS NOT NULL",
+            ),
+            (SurnameBucketing::Decade, _) => (
+                "(CAST(EXTRACT(YEAR FROM e.date_sort) AS INTEGER) / 10) * 10",
+                "",
+                "AND e.date_sort IS NOT NULL",
+            ),
+            (SurnameBucketing::Place, _) => {
+                ("pl.name", "INNER JOIN place pl ON pl.id = e.place_id", "")
+            }
+        };
+        let sql = format!(
+            "SELECT pn.surname_norm, MIN(pn.surname) AS surname, {bucket} AS bucket,
+                COUNT(DISTINCT p.id) AS person_count
+            FROM person p
+            INNER JOIN person_name pn ON pn.person_id = p.id
+            INNER JOIN event e ON e.person_id = p.id
+            {place_join}
+            WHERE p.tree_id = {sigil}1
+              AND p.deleted_at IS NULL
+              AND pn.is_primary = {sigil}2
+              AND pn.surname_norm IS NOT NULL
+              AND e.deleted_at IS NULL
+              AND e.event_type = {sigil}3
+              {bucket_filter}
+            GROUP BY pn.surname_norm, {bucket}"
+        );
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                backend,
+                sql,
+                [
+                    tree_id.into(),
+                    true.into(),
+                    sea_enums::EventType::from(EventType::Birth).into(),
+                ],
+            ))
+            .await
+            .map_err(db_err)?;
+
+        // (surname key, surname, bucket sort key, bucket, count)
+        let mut counts = Vec::with_capacity(rows.len());
+        for row in rows {
+            let key: String = row.try_get("", "surname_norm").map_err(db_err)?;
+            let surname: String = row.try_get("", "surname").map_err(db_err)?;
+            let count: i64 = row.try_get("", "person_count").map_err(db_err)?;
+            let bucket = match over {
+                SurnameBucketing::Decade => {
+                    let decade: i32 = row.try_get("", "bucket").map_err(db_err)?;
+                    decade.to_string()
+                }
+                SurnameBucketing::Place => row.try_get("", "bucket").map_err(db_err)?,
+            };
+            counts.push((key, surname, bucket, count));
+        }
+
+        // A surname spelled several ways is shown the way `MIN` picks.
+        let mut totals: HashMap<&str, (&str, i64)> = HashMap::new();
+        for (key, surname, _, count) in &counts {
+            let total = totals.entry(key).or_insert((surname, 0));
+            total.0 = total.0.min(surname);
+            total.1 += count;
+        }
+        let mut surnames: Vec<(&str, &str, i64)> = totals
+            .into_iter()
+            .map(|(key, (surname, total))| (key, surname, total))
+            .collect();
+        surnames.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(b.0)));
+        surnames.truncate(limit);
+        let rank: HashMap<&str, usize> = surnames
+            .iter()
+            .enumerate()
+            .map(|(i, (key, _, _))| (*key, i))
+            .collect();
+
+        let mut bucket_totals: HashMap<&str, i64> = HashMap::new();
+        for (key, _, bucket, count) in &counts {
+            if rank.contains_key(key.as_str()) {
+                *bucket_totals.entry(bucket).or_default() += count;
+            }
+        }
+        let mut buckets: Vec<(&str, i64)> = bucket_totals.into_iter().collect();
+        match over {
+            SurnameBucketing::Decade => {
+                buckets.sort_by_key(|(decade, _)| decade.parse::<i32>().unwrap_or_default())
+            }
+            SurnameBucketing::Place => {
+                buckets.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)))
+            }
+        }
+        let bucket_rank: HashMap<&str, usize> = buckets
+            .iter()
+            .enumerate()
+            .map(|(i, (bucket, _))| (*bucket, i))
+            .collect();
+
+        let mut kept: Vec<&(String, String, String, i64)> = counts
+            .iter()
+            .filter(|(key, ..)| rank.contains_key(key.as_str()))
+            .collect();
+        kept.sort_by_key(|(key, _, bucket, _)| (rank[key.as_str()], bucket_rank[bucket.as_str()]));
+        let surname_of: HashMap<&str, &str> = surnames.iter().map(|(k, s, _)| (*k, *s)).collect();
+
+        Ok(SurnameDistribution {
+            counts: kept
+                .into_iter()
+                .map(|(key, _, bucket, count)| SurnameBucketCount {
+                    surname: surname_of[key.as_str()].to_string(),
+                    bucket: bucket.clone(),
+                    count: *count,
+                })
+                .collect(),
+            surnames: surnames
+                .into_iter()
+                .map(|(_, surname, total)| (surname.to_string(), total))
+                .collect(),
+            buckets: buckets
+                .into_iter()
+                .map(|(bucket, _)| bucket.to_string())
+                .collect(),
+        })
+    }
+
     /// Distinct occupation labels (`Event.description` for `Occupation`
     /// events) across a tree, with the number of persons holding each.
     pub async fn occupations(
@@ -267,13 +520,17 @@ impl DictionaryRepo {
         let mut counts: HashMap<Uuid, i64> = HashMap::new();
         if !place_ids.is_empty() {
             let events = event::Entity::find()
-                .filter(event::Column::PlaceId.is_in(place_ids.clone()))
+                .filter(
+                    Condition::any()
+                        .add(event::Column::PlaceId.is_in(place_ids.clone()))
+                        .add(event::Column::PlaceIdTo.is_in(place_ids.clone())),
+                )
                 .filter(event::Column::DeletedAt.is_null())
                 .all(db)
                 .await
                 .map_err(|e| OxidGeneError::Database(e.to_string()))?;
             for e in events {
-                if let Some(pid) = e.place_id {
+                for pid in e.place_id.into_iter().chain(e.place_id_to) {
                     *counts.entry(pid).or_insert(0) += 1;
                 }
             }
@@ -336,13 +593,18 @@ impl DictionaryRepo {
         Ok(dedup(person_ids))
     }
 
-    /// Distinct persons with an individual event at a given place.
+    /// Distinct persons with an individual event at a given place (or
+    /// moving to it).
     pub async fn place_usage_person_ids(
         db: &DatabaseConnection,
         place_id: Uuid,
     ) -> Result<Vec<Uuid>, OxidGeneError> {
         let events = event::Entity::find()
-            .filter(event::Column::PlaceId.eq(place_id))
+            .filter(
+                Condition::any()
+                    .add(event::Column::PlaceId.eq(place_id))
+                    .add(event::Column::PlaceIdTo.eq(place_id)),
+            )
             .filter(event::Column::DeletedAt.is_null())
             .all(db)
             .await
@@ -515,6 +777,7 @@ fn into_source(m: source::Model) -> Source {
         repository_name: m.repository_name,
         created_at: m.created_at,
         updated_at: m.updated_at,
+        version: m.version,
         deleted_at: m.deleted_at,
     }
 }
@@ -528,5 +791,6 @@ fn into_place(m: place::Model) -> Place {
         longitude: m.longitude,
         created_at: m.created_at,
         updated_at: m.updated_at,
+        version: m.version,
     }
 }
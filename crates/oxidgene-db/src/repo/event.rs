@@ -1,16 +1,22 @@
 //! Repository for `Event` entities (CRUD with soft delete, type/person/family filters).
 
 use chrono::{NaiveDate, Utc};
+use oxidgene_core::calendar::split_calendar;
 use oxidgene_core::enums::{Calendar, DateQualifier, EventType};
 use oxidgene_core::error::OxidGeneError;
-use oxidgene_core::types::{Connection, Event};
+use oxidgene_core::types::{Connection, Event, parse_date_sort_in};
 use sea_orm::entity::prelude::*;
-use sea_orm::{ActiveModelTrait, IntoActiveModel, QueryFilter, Set};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, Condition, IntoActiveModel, QueryFilter, QueryOrder, QuerySelect, Set,
+    TransactionTrait,
+};
 use uuid::Uuid;
 
 use crate::entities::event::{self, ActiveModel, Column, Entity};
-use crate::entities::sea_enums;
+use crate::entities::{family, person, sea_enums};
 use crate::repo::pagination::{PaginationParams, paginate};
+use crate::repo::version;
 
 /// Optional filters for listing events.
 #[derive(Debug, Clone, Default)]
@@ -18,6 +24,20 @@ pub struct EventFilter {
     pub event_type: Option<EventType>,
     pub person_id: Option<Uuid>,
     pub family_id: Option<Uuid>,
+    /// With `person_id`, also the events of these families (e.g. the
+    /// person's own union and their parents').
+    pub person_family_ids: Vec<Uuid>,
+}
+
+/// What [`EventRepo::backfill_date_sort`] did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DateSortBackfill {
+    /// Events whose `date_sort` was filled in.
+    pub updated: u64,
+    /// Events left without one: their `date_value` does not parse.
+    pub unparsed: u64,
+    /// Trees holding the updated events.
+    pub tree_ids: Vec<Uuid>,
 }
 
 /// Repository for event CRUD operations.
@@ -39,7 +59,11 @@ impl EventRepo {
             query = query.filter(Column::EventType.eq(sea_enums::EventType::from(*et)));
         }
         if let Some(pid) = filter.person_id {
-            query = query.filter(Column::PersonId.eq(pid));
+            query = query.filter(
+                Condition::any()
+                    .add(Column::PersonId.eq(pid))
+                    .add(Column::FamilyId.is_in(filter.person_family_ids.iter().copied())),
+            );
         }
         if let Some(fid) = filter.family_id {
             query = query.filter(Column::FamilyId.eq(fid));
@@ -62,6 +86,38 @@ impl EventRepo {
         Ok(models.into_iter().map(into_domain).collect())
     }
 
+    /// List the dated events of a tree whose `date_sort` falls within the
+    /// years `year_from..=year_to` (either bound open when `None`), whoever
+    /// owns them, by date.
+    pub async fn list_in_years(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        year_from: Option<i32>,
+        year_to: Option<i32>,
+        event_type: Option<EventType>,
+    ) -> Result<Vec<Event>, OxidGeneError> {
+        let mut query = Entity::find()
+            .filter(Column::TreeId.eq(tree_id))
+            .filter(Column::DeletedAt.is_null())
+            .filter(Column::DateSort.is_not_null());
+        if let Some(from) = year_from.and_then(|y| NaiveDate::from_ymd_opt(y, 1, 1)) {
+            query = query.filter(Column::DateSort.gte(from));
+        }
+        if let Some(to) = year_to.and_then(|y| NaiveDate::from_ymd_opt(y, 12, 31)) {
+            query = query.filter(Column::DateSort.lte(to));
+        }
+        if let Some(et) = event_type {
+            query = query.filter(Column::EventType.eq(sea_enums::EventType::from(et)));
+        }
+        let models = query
+            .order_by_asc(Column::DateSort)
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
     /// List all events attached to a person (excludes soft-deleted).
     pub async fn list_by_person(
         db: &DatabaseConnection,
@@ -76,6 +132,20 @@ impl EventRepo {
         Ok(models.into_iter().map(into_domain).collect())
     }
 
+    /// List all events attached to any of the given persons (excludes soft-deleted).
+    pub async fn list_by_persons(
+        db: &DatabaseConnection,
+        person_ids: &[Uuid],
+    ) -> Result<Vec<Event>, OxidGeneError> {
+        let models = Entity::find()
+            .filter(Column::PersonId.is_in(person_ids.iter().copied()))
+            .filter(Column::DeletedAt.is_null())
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
     /// List all events attached to any of the given families (excludes soft-deleted).
     pub async fn list_by_families(
         db: &DatabaseConnection,
@@ -104,21 +174,30 @@ impl EventRepo {
             })
     }
 
-    /// Create a new event.
+    /// Create a new event. Without a `date_sort`, it is read from
+    /// `date_value` when that parses.
     #[allow(clippy::too_many_arguments)]
     pub async fn create(
-        db: &DatabaseConnection,
+        db: &impl ConnectionTrait,
         id: Uuid,
         tree_id: Uuid,
         event_type: EventType,
         date_value: Option<String>,
         date_sort: Option<NaiveDate>,
         place_id: Option<Uuid>,
+        place_id_to: Option<Uuid>,
         person_id: Option<Uuid>,
         family_id: Option<Uuid>,
         description: Option<String>,
     ) -> Result<Event, OxidGeneError> {
         let now = Utc::now();
+        let (calendar, date_value) = split_date_value(date_value);
+        let calendar = calendar.unwrap_or_default();
+        let date_sort = date_sort.or_else(|| {
+            date_value
+                .as_deref()
+                .and_then(|v| parse_date_sort_in(calendar, v))
+        });
         let model = event::ActiveModel {
             id: Set(id),
             tree_id: Set(tree_id),
@@ -127,14 +206,17 @@ impl EventRepo {
             date_sort: Set(date_sort),
             date_qualifier: Set(sea_enums::DateQualifier::from(DateQualifier::default())),
             date_value2: Set(None),
-            calendar: Set(sea_enums::Calendar::from(Calendar::default())),
+            calendar: Set(sea_enums::Calendar::from(calendar)),
             cause: Set(None),
+            age: Set(None),
             place_id: Set(place_id),
+            place_id_to: Set(place_id_to.filter(|_| event_type.has_destination())),
             person_id: Set(person_id),
             family_id: Set(family_id),
             description: Set(description),
             created_at: Set(now),
             updated_at: Set(now),
+            version: Set(1),
             deleted_at: Set(None),
         };
         let result = model
@@ -144,7 +226,8 @@ impl EventRepo {
         Ok(into_domain(result))
     }
 
-    /// Update an existing event.
+    /// Update an existing event. A new `date_value` without a `date_sort`
+    /// gets its `date_sort` from the phrase, as in [`Self::create`].
     #[allow(clippy::too_many_arguments)]
     pub async fn update(
         db: &DatabaseConnection,
@@ -153,11 +236,14 @@ impl EventRepo {
         date_value: Option<Option<String>>,
         date_sort: Option<Option<NaiveDate>>,
         place_id: Option<Option<Uuid>>,
+        place_id_to: Option<Option<Uuid>>,
         description: Option<Option<String>>,
         date_qualifier: Option<DateQualifier>,
         date_value2: Option<Option<String>>,
         calendar: Option<Calendar>,
         cause: Option<Option<String>>,
+        age: Option<Option<String>>,
+        expected_version: Option<i32>,
     ) -> Result<Event, OxidGeneError> {
         let existing = Entity::find_by_id(id)
             .filter(Column::DeletedAt.is_null())
@@ -169,19 +255,51 @@ impl EventRepo {
                 id,
             })?;
 
+        let current_version = existing.version;
+        version::check("Event", id, expected_version, current_version)?;
+
+        let existing_calendar: Calendar = existing.calendar.into();
+        let existing_date_value = existing.date_value.clone();
         let mut active: ActiveModel = existing.into_active_model();
         if let Some(event_type) = event_type {
             active.event_type = Set(sea_enums::EventType::from(event_type));
         }
-        if let Some(date_value) = date_value {
-            active.date_value = Set(date_value);
-        }
-        if let Some(date_sort) = date_sort {
-            active.date_sort = Set(date_sort);
+        // A calendar escape in a new phrase sets the calendar, unless one
+        // is given explicitly.
+        let (escaped, date_value) = match date_value {
+            Some(date_value) => {
+                let (escaped, date_value) = split_date_value(date_value);
+                (escaped, Some(date_value))
+            }
+            None => (None, None),
+        };
+        let calendar = calendar.or(escaped);
+        let effective_calendar = calendar.unwrap_or(existing_calendar);
+        match (date_value, date_sort) {
+            (Some(date_value), date_sort) => {
+                let date_sort = date_sort.flatten().or_else(|| {
+                    date_value
+                        .as_deref()
+                        .and_then(|v| parse_date_sort_in(effective_calendar, v))
+                });
+                active.date_value = Set(date_value);
+                active.date_sort = Set(date_sort);
+            }
+            (None, Some(date_sort)) => active.date_sort = Set(date_sort),
+            // The same phrase in another calendar is another day.
+            (None, None) if calendar.is_some_and(|c| c != existing_calendar) => {
+                active.date_sort = Set(existing_date_value
+                    .as_deref()
+                    .and_then(|v| parse_date_sort_in(effective_calendar, v)));
+            }
+            (None, None) => {}
         }
         if let Some(place_id) = place_id {
             active.place_id = Set(place_id);
         }
+        if let Some(place_id_to) = place_id_to {
+            active.place_id_to = Set(place_id_to);
+        }
         if let Some(description) = description {
             active.description = Set(description);
         }
@@ -197,12 +315,93 @@ impl EventRepo {
         if let Some(c) = cause {
             active.cause = Set(c);
         }
+        if let Some(a) = age {
+            active.age = Set(a);
+        }
+        // Only moves keep a destination, whatever type the event changed to.
+        let event_type: EventType = active.event_type.clone().unwrap().into();
+        if !event_type.has_destination() {
+            active.place_id_to = Set(None);
+        }
         active.updated_at = Set(Utc::now());
+        active.version = Set(current_version + 1);
 
-        let result = active
-            .update(db)
+        let result = Entity::update(active)
+            .filter(Column::Version.eq(current_version))
+            .exec(db)
             .await
-            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+            .map_err(version::update_error("Event", id))?;
+        Ok(into_domain(result))
+    }
+
+    /// Move an event of `tree_id` to another owner: the person `person_id`
+    /// or the family `family_id`, exactly one of which must be given. The
+    /// target must be live and in the same tree. Only the owner changes, so
+    /// the event's citations, notes and participants stay attached to it.
+    pub async fn reassign(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        id: Uuid,
+        person_id: Option<Uuid>,
+        family_id: Option<Uuid>,
+        expected_version: Option<i32>,
+    ) -> Result<Event, OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        let existing = Entity::find_by_id(id)
+            .filter(Column::TreeId.eq(tree_id))
+            .filter(Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .map_err(db_err)?
+            .ok_or(OxidGeneError::NotFound {
+                entity: "Event",
+                id,
+            })?;
+        let current_version = existing.version;
+        version::check("Event", id, expected_version, current_version)?;
+
+        match (person_id, family_id) {
+            (Some(pid), None) => {
+                person::Entity::find_by_id(pid)
+                    .filter(person::Column::TreeId.eq(tree_id))
+                    .filter(person::Column::DeletedAt.is_null())
+                    .one(db)
+                    .await
+                    .map_err(db_err)?
+                    .ok_or(OxidGeneError::NotFound {
+                        entity: "Person",
+                        id: pid,
+                    })?;
+            }
+            (None, Some(fid)) => {
+                family::Entity::find_by_id(fid)
+                    .filter(family::Column::TreeId.eq(tree_id))
+                    .filter(family::Column::DeletedAt.is_null())
+                    .one(db)
+                    .await
+                    .map_err(db_err)?
+                    .ok_or(OxidGeneError::NotFound {
+                        entity: "Family",
+                        id: fid,
+                    })?;
+            }
+            _ => {
+                return Err(OxidGeneError::Validation(
+                    "exactly one of person_id and family_id is required".to_string(),
+                ));
+            }
+        }
+
+        let mut active: ActiveModel = existing.into_active_model();
+        active.person_id = Set(person_id);
+        active.family_id = Set(family_id);
+        active.updated_at = Set(Utc::now());
+        active.version = Set(current_version + 1);
+        let result = Entity::update(active)
+            .filter(Column::Version.eq(current_version))
+            .exec(db)
+            .await
+            .map_err(version::update_error("Event", id))?;
         Ok(into_domain(result))
     }
 
@@ -226,6 +425,74 @@ impl EventRepo {
             .map_err(|e| OxidGeneError::Database(e.to_string()))?;
         Ok(())
     }
+
+    /// Fill in the `date_sort` of every event, deleted ones included, that
+    /// has a `date_value` but no `date_sort`, by parsing the phrase as
+    /// [`Self::create`] does. Events are walked by id, `chunk_size` at a
+    /// time, each chunk in its own transaction; running it again only
+    /// revisits the phrases that did not parse.
+    pub async fn backfill_date_sort(
+        db: &DatabaseConnection,
+        chunk_size: u64,
+    ) -> Result<DateSortBackfill, OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        let mut result = DateSortBackfill::default();
+        let mut after: Option<Uuid> = None;
+        loop {
+            let mut query = Entity::find()
+                .filter(Column::DateSort.is_null())
+                .filter(Column::DateValue.is_not_null());
+            if let Some(after) = after {
+                query = query.filter(Column::Id.gt(after));
+            }
+            let chunk = query
+                .order_by_asc(Column::Id)
+                .limit(chunk_size.max(1))
+                .all(db)
+                .await
+                .map_err(db_err)?;
+            let Some(last) = chunk.last() else {
+                break;
+            };
+            after = Some(last.id);
+
+            let txn = db.begin().await.map_err(db_err)?;
+            for model in chunk {
+                let calendar: Calendar = model.calendar.into();
+                let Some(date_sort) = model
+                    .date_value
+                    .as_deref()
+                    .and_then(|v| parse_date_sort_in(calendar, v))
+                else {
+                    result.unparsed += 1;
+                    continue;
+                };
+                Entity::update_many()
+                    .col_expr(Column::DateSort, Expr::value(date_sort))
+                    .filter(Column::Id.eq(model.id))
+                    .exec(&txn)
+                    .await
+                    .map_err(db_err)?;
+                result.updated += 1;
+                if !result.tree_ids.contains(&model.tree_id) {
+                    result.tree_ids.push(model.tree_id);
+                }
+            }
+            txn.commit().await.map_err(db_err)?;
+        }
+        Ok(result)
+    }
+}
+
+/// A date phrase without its calendar escape, and the calendar it named.
+fn split_date_value(date_value: Option<String>) -> (Option<Calendar>, Option<String>) {
+    match date_value {
+        Some(value) => {
+            let (calendar, phrase) = split_calendar(&value);
+            (calendar, Some(phrase))
+        }
+        None => (None, None),
+    }
 }
 
 fn into_domain(m: event::Model) -> Event {
@@ -239,12 +506,15 @@ fn into_domain(m: event::Model) -> Event {
         date_value2: m.date_value2,
         calendar: m.calendar.into(),
         cause: m.cause,
+        age: m.age,
         place_id: m.place_id,
+        place_id_to: m.place_id_to,
         person_id: m.person_id,
         family_id: m.family_id,
         description: m.description,
         created_at: m.created_at,
         updated_at: m.updated_at,
+        version: m.version,
         deleted_at: m.deleted_at,
     }
 }
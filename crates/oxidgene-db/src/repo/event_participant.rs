@@ -1,22 +1,24 @@
-//! Repository for `EventWitness` junction table (create/list/delete only).
+//! Repository for `EventParticipant` junction table (create/list/delete only).
 
+use oxidgene_core::enums::ParticipantRole;
 use oxidgene_core::error::OxidGeneError;
-use oxidgene_core::types::EventWitness;
+use oxidgene_core::types::EventParticipant;
 use sea_orm::entity::prelude::*;
 use sea_orm::{QueryFilter, QueryOrder, Set};
 use uuid::Uuid;
 
-use crate::entities::event_witness::{self, Column, Entity};
+use crate::entities::event_participant::{self, Column, Entity};
+use crate::entities::sea_enums;
 
-/// Repository for event–witness links.
-pub struct EventWitnessRepo;
+/// Repository for event–participant links.
+pub struct EventParticipantRepo;
 
-impl EventWitnessRepo {
-    /// List witnesses on an event, ordered by `sort_order`.
+impl EventParticipantRepo {
+    /// List participants of an event, ordered by `sort_order`.
     pub async fn list_by_event(
         db: &DatabaseConnection,
         event_id: Uuid,
-    ) -> Result<Vec<EventWitness>, OxidGeneError> {
+    ) -> Result<Vec<EventParticipant>, OxidGeneError> {
         let models = Entity::find()
             .filter(Column::EventId.eq(event_id))
             .order_by_asc(Column::SortOrder)
@@ -26,11 +28,11 @@ impl EventWitnessRepo {
         Ok(models.into_iter().map(into_domain).collect())
     }
 
-    /// List witnesses for multiple events, ordered by `sort_order`.
+    /// List participants of multiple events, ordered by `sort_order`.
     pub async fn list_by_events(
         db: &DatabaseConnection,
         event_ids: &[Uuid],
-    ) -> Result<Vec<EventWitness>, OxidGeneError> {
+    ) -> Result<Vec<EventParticipant>, OxidGeneError> {
         let models = Entity::find()
             .filter(Column::EventId.is_in(event_ids.iter().copied()))
             .order_by_asc(Column::SortOrder)
@@ -40,19 +42,21 @@ impl EventWitnessRepo {
         Ok(models.into_iter().map(into_domain).collect())
     }
 
-    /// Create an event–witness link.
+    /// Create an event–participant link.
     pub async fn create(
-        db: &DatabaseConnection,
+        db: &impl ConnectionTrait,
         id: Uuid,
         event_id: Uuid,
         person_id: Uuid,
+        role: ParticipantRole,
         relation: Option<String>,
         sort_order: i32,
-    ) -> Result<EventWitness, OxidGeneError> {
-        let model = event_witness::ActiveModel {
+    ) -> Result<EventParticipant, OxidGeneError> {
+        let model = event_participant::ActiveModel {
             id: Set(id),
             event_id: Set(event_id),
             person_id: Set(person_id),
+            role: Set(sea_enums::ParticipantRole::from(role)),
             relation: Set(relation),
             sort_order: Set(sort_order),
         };
@@ -63,7 +67,7 @@ impl EventWitnessRepo {
         Ok(into_domain(result))
     }
 
-    /// Hard-delete an event–witness link.
+    /// Hard-delete an event–participant link.
     pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<(), OxidGeneError> {
         let result = Entity::delete_by_id(id)
             .exec(db)
@@ -71,7 +75,7 @@ impl EventWitnessRepo {
             .map_err(|e| OxidGeneError::Database(e.to_string()))?;
         if result.rows_affected == 0 {
             return Err(OxidGeneError::NotFound {
-                entity: "EventWitness",
+                entity: "EventParticipant",
                 id,
             });
         }
@@ -79,11 +83,12 @@ impl EventWitnessRepo {
     }
 }
 
-fn into_domain(m: event_witness::Model) -> EventWitness {
-    EventWitness {
+fn into_domain(m: event_participant::Model) -> EventParticipant {
+    EventParticipant {
         id: m.id,
         event_id: m.event_id,
         person_id: m.person_id,
+        role: m.role.into(),
         relation: m.relation,
         sort_order: m.sort_order,
     }
@@ -0,0 +1,63 @@
+//! Repository for `ExternalId` entities: the `_UID`/`REFN` identifiers
+//! persons and families carry in other programs. Written by GEDCOM import,
+//! read back by export; they go with their person or family.
+
+use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::types::ExternalId;
+use sea_orm::entity::prelude::*;
+use sea_orm::{QueryFilter, QueryOrder};
+use uuid::Uuid;
+
+use crate::entities::external_id::{self, Column, Entity};
+
+/// Repository for external id operations.
+pub struct ExternalIdRepo;
+
+impl ExternalIdRepo {
+    /// Every external id in a tree, in creation order.
+    pub async fn list_by_tree(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+    ) -> Result<Vec<ExternalId>, OxidGeneError> {
+        let models = Entity::find()
+            .filter(Column::TreeId.eq(tree_id))
+            .order_by_asc(Column::CreatedAt)
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
+    /// External ids of a tree with one of the given values, to recognise
+    /// records that are already in the tree.
+    pub async fn find_by_values(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        values: &[String],
+    ) -> Result<Vec<ExternalId>, OxidGeneError> {
+        if values.is_empty() {
+            return Ok(Vec::new());
+        }
+        let models = Entity::find()
+            .filter(Column::TreeId.eq(tree_id))
+            .filter(Column::Value.is_in(values.iter().cloned()))
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+}
+
+fn into_domain(m: external_id::Model) -> ExternalId {
+    ExternalId {
+        id: m.id,
+        tree_id: m.tree_id,
+        person_id: m.person_id,
+        family_id: m.family_id,
+        kind: m.kind.into(),
+        value: m.value,
+        ref_type: m.ref_type,
+        created_at: m.created_at,
+    }
+}
@@ -4,16 +4,76 @@ use chrono::Utc;
 use oxidgene_core::error::OxidGeneError;
 use oxidgene_core::types::{Connection, Family};
 use sea_orm::entity::prelude::*;
-use sea_orm::{ActiveModelTrait, IntoActiveModel, QueryFilter, Set};
+use sea_orm::{ActiveModelTrait, IntoActiveModel, QueryFilter, QuerySelect, Set};
 use uuid::Uuid;
 
 use crate::entities::family::{self, ActiveModel, Column, Entity};
+use crate::entities::{family_child, family_spouse};
 use crate::repo::pagination::{PaginationParams, paginate};
 
+/// How a person belongs to a family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PersonFamilyRole {
+    /// One of the couple: the person's own union.
+    Spouse,
+    /// One of the children: the person's parents' union.
+    Child,
+}
+
+impl PersonFamilyRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Spouse => "spouse",
+            Self::Child => "child",
+        }
+    }
+}
+
 /// Repository for family CRUD operations.
 pub struct FamilyRepo;
 
 impl FamilyRepo {
+    /// The live families a person belongs to, with their role in each:
+    /// the unions they are a spouse in, then the ones they are a child of.
+    pub async fn list_for_person(
+        db: &DatabaseConnection,
+        person_id: Uuid,
+    ) -> Result<Vec<(Uuid, PersonFamilyRole)>, OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        let as_spouse = family_spouse::Entity::find()
+            .filter(family_spouse::Column::PersonId.eq(person_id))
+            .all(db)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .map(|m| (m.family_id, PersonFamilyRole::Spouse));
+        let as_child = family_child::Entity::find()
+            .filter(family_child::Column::PersonId.eq(person_id))
+            .all(db)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .map(|m| (m.family_id, PersonFamilyRole::Child));
+        let memberships: Vec<(Uuid, PersonFamilyRole)> = as_spouse.chain(as_child).collect();
+        if memberships.is_empty() {
+            return Ok(memberships);
+        }
+
+        let live: Vec<Uuid> = Entity::find()
+            .select_only()
+            .column(Column::Id)
+            .filter(Column::Id.is_in(memberships.iter().map(|(id, _)| *id)))
+            .filter(Column::DeletedAt.is_null())
+            .into_tuple()
+            .all(db)
+            .await
+            .map_err(db_err)?;
+        Ok(memberships
+            .into_iter()
+            .filter(|(id, _)| live.contains(id))
+            .collect())
+    }
+
     /// List families in a tree with pagination (excludes soft-deleted).
     pub async fn list(
         db: &DatabaseConnection,
@@ -1,39 +1,46 @@
-//! Repository for `FamilyChild` junction table (create/delete only).
+//! Repository for `FamilyChild` junction table (create/reorder/delete).
+
+use std::collections::{HashMap, HashSet};
 
 use oxidgene_core::enums::ChildType;
 use oxidgene_core::error::OxidGeneError;
 use oxidgene_core::types::FamilyChild;
 use sea_orm::entity::prelude::*;
-use sea_orm::{QueryFilter, Set};
+use sea_orm::{QueryFilter, QueryOrder, Set, TransactionTrait};
 use uuid::Uuid;
 
+use super::PersonAncestryRepo;
 use crate::entities::family_child::{self, Column, Entity};
-use crate::entities::sea_enums;
+use crate::entities::{event, sea_enums};
 
 /// Repository for family–child membership.
 pub struct FamilyChildRepo;
 
 impl FamilyChildRepo {
-    /// List children in a family.
+    /// List children in a family, in `sort_order`.
     pub async fn list_by_family(
         db: &DatabaseConnection,
         family_id: Uuid,
     ) -> Result<Vec<FamilyChild>, OxidGeneError> {
         let models = Entity::find()
             .filter(Column::FamilyId.eq(family_id))
+            .order_by_asc(Column::SortOrder)
+            .order_by_asc(Column::Id)
             .all(db)
             .await
             .map_err(|e| OxidGeneError::Database(e.to_string()))?;
         Ok(models.into_iter().map(into_domain).collect())
     }
 
-    /// List children for multiple families.
+    /// List children for multiple families, in `sort_order` within each family.
     pub async fn list_by_families(
         db: &DatabaseConnection,
         family_ids: &[Uuid],
     ) -> Result<Vec<FamilyChild>, OxidGeneError> {
         let models = Entity::find()
             .filter(Column::FamilyId.is_in(family_ids.iter().copied()))
+            .order_by_asc(Column::SortOrder)
+            .order_by_asc(Column::Id)
             .all(db)
             .await
             .map_err(|e| OxidGeneError::Database(e.to_string()))?;
@@ -53,6 +60,26 @@ impl FamilyChildRepo {
         Ok(models.into_iter().map(into_domain).collect())
     }
 
+    /// The `sort_order` to store for a new child link: `requested` if it is
+    /// non-zero, otherwise one past the family's current last child, so
+    /// members added without an explicit position keep their insertion order.
+    pub async fn sort_order_for_new(
+        db: &DatabaseConnection,
+        family_id: Uuid,
+        requested: i32,
+    ) -> Result<i32, OxidGeneError> {
+        if requested != 0 {
+            return Ok(requested);
+        }
+        let last = Entity::find()
+            .filter(Column::FamilyId.eq(family_id))
+            .order_by_desc(Column::SortOrder)
+            .one(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(last.map_or(0, |m| m.sort_order + 1))
+    }
+
     /// Create a family–child link.
     pub async fn create(
         db: &DatabaseConnection,
@@ -76,6 +103,139 @@ impl FamilyChildRepo {
         Ok(into_domain(result))
     }
 
+    /// Add several children to a family in one transaction, after its
+    /// current last child and in the given order, then bring the ancestry
+    /// closure up to date once for all of them. A person listed twice, or
+    /// already a child of the family, is a validation error and nothing is
+    /// written.
+    pub async fn create_many(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        family_id: Uuid,
+        children: &[(Uuid, ChildType)],
+    ) -> Result<Vec<FamilyChild>, OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        let txn = db.begin().await.map_err(db_err)?;
+
+        let existing = Entity::find()
+            .filter(Column::FamilyId.eq(family_id))
+            .order_by_desc(Column::SortOrder)
+            .all(&txn)
+            .await
+            .map_err(db_err)?;
+        let mut seen: HashSet<Uuid> = existing.iter().map(|m| m.person_id).collect();
+        for (person_id, _) in children {
+            if !seen.insert(*person_id) {
+                return Err(OxidGeneError::Validation(format!(
+                    "person {person_id} is listed twice or already a child of the family"
+                )));
+            }
+        }
+
+        let first = existing.first().map_or(0, |m| m.sort_order + 1);
+        let mut created = Vec::with_capacity(children.len());
+        for (index, (person_id, child_type)) in children.iter().enumerate() {
+            let model = family_child::ActiveModel {
+                id: Set(Uuid::now_v7()),
+                family_id: Set(family_id),
+                person_id: Set(*person_id),
+                child_type: Set(sea_enums::ChildType::from(*child_type)),
+                sort_order: Set(first + index as i32),
+            };
+            created.push(into_domain(model.insert(&txn).await.map_err(db_err)?));
+        }
+
+        let persons: Vec<Uuid> = children.iter().map(|(person_id, _)| *person_id).collect();
+        PersonAncestryRepo::refresh(&txn, tree_id, &persons).await?;
+
+        txn.commit().await.map_err(db_err)?;
+        Ok(created)
+    }
+
+    /// Reorder a family's children: `child_ids` lists every child link of the
+    /// family (link IDs, not person IDs) in the new order, and each gets its
+    /// index as `sort_order`. Runs in a single transaction.
+    pub async fn reorder(
+        db: &DatabaseConnection,
+        family_id: Uuid,
+        child_ids: &[Uuid],
+    ) -> Result<Vec<FamilyChild>, OxidGeneError> {
+        let txn = db
+            .begin()
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+
+        let models = Entity::find()
+            .filter(Column::FamilyId.eq(family_id))
+            .all(&txn)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        let mut existing: Vec<Uuid> = models.iter().map(|m| m.id).collect();
+        let mut requested = child_ids.to_vec();
+        existing.sort();
+        requested.sort();
+        if existing != requested {
+            return Err(OxidGeneError::Validation(
+                "child_ids must list each child link of the family exactly once".to_string(),
+            ));
+        }
+
+        let mut reordered = Vec::with_capacity(models.len());
+        for model in models {
+            let index = child_ids.iter().position(|&id| id == model.id).unwrap_or(0);
+            let mut active: family_child::ActiveModel = model.into();
+            active.sort_order = Set(index as i32);
+            let updated = active
+                .update(&txn)
+                .await
+                .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+            reordered.push(into_domain(updated));
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+
+        reordered.sort_by_key(|c| c.sort_order);
+        Ok(reordered)
+    }
+
+    /// Reorder a family's children by birth date (the earliest dated birth
+    /// event of each child). Undated children go last; ties and undated
+    /// children keep their current relative order. Returns the new ordering.
+    pub async fn sort_by_birth(
+        db: &DatabaseConnection,
+        family_id: Uuid,
+    ) -> Result<Vec<FamilyChild>, OxidGeneError> {
+        let mut children = Self::list_by_family(db, family_id).await?;
+
+        let births = event::Entity::find()
+            .filter(event::Column::EventType.eq(sea_enums::EventType::Birth))
+            .filter(event::Column::PersonId.is_in(children.iter().map(|c| c.person_id)))
+            .filter(event::Column::DateSort.is_not_null())
+            .filter(event::Column::DeletedAt.is_null())
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        let mut birth_dates = HashMap::new();
+        for ev in births {
+            if let (Some(person_id), Some(date)) = (ev.person_id, ev.date_sort) {
+                birth_dates
+                    .entry(person_id)
+                    .and_modify(|d: &mut Date| *d = (*d).min(date))
+                    .or_insert(date);
+            }
+        }
+
+        // Stable sort: `None` sorts after every date via the leading flag.
+        children.sort_by_key(|c| {
+            let date = birth_dates.get(&c.person_id).copied();
+            (date.is_none(), date)
+        });
+        let ids: Vec<Uuid> = children.iter().map(|c| c.id).collect();
+        Self::reorder(db, family_id, &ids).await
+    }
+
     /// Hard-delete a family–child link.
     pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<(), OxidGeneError> {
         let result = Entity::delete_by_id(id)
@@ -4,7 +4,7 @@ use oxidgene_core::enums::SpouseRole;
 use oxidgene_core::error::OxidGeneError;
 use oxidgene_core::types::FamilySpouse;
 use sea_orm::entity::prelude::*;
-use sea_orm::{QueryFilter, Set};
+use sea_orm::{QueryFilter, QueryOrder, Set};
 use uuid::Uuid;
 
 use crate::entities::family_spouse::{self, Column, Entity};
@@ -14,26 +14,30 @@ use crate::entities::sea_enums;
 pub struct FamilySpouseRepo;
 
 impl FamilySpouseRepo {
-    /// List spouses in a family.
+    /// List spouses in a family, in `sort_order`.
     pub async fn list_by_family(
         db: &DatabaseConnection,
         family_id: Uuid,
     ) -> Result<Vec<FamilySpouse>, OxidGeneError> {
         let models = Entity::find()
             .filter(Column::FamilyId.eq(family_id))
+            .order_by_asc(Column::SortOrder)
+            .order_by_asc(Column::Id)
             .all(db)
             .await
             .map_err(|e| OxidGeneError::Database(e.to_string()))?;
         Ok(models.into_iter().map(into_domain).collect())
     }
 
-    /// List spouses for multiple families.
+    /// List spouses for multiple families, in `sort_order` within each family.
     pub async fn list_by_families(
         db: &DatabaseConnection,
         family_ids: &[Uuid],
     ) -> Result<Vec<FamilySpouse>, OxidGeneError> {
         let models = Entity::find()
             .filter(Column::FamilyId.is_in(family_ids.iter().copied()))
+            .order_by_asc(Column::SortOrder)
+            .order_by_asc(Column::Id)
             .all(db)
             .await
             .map_err(|e| OxidGeneError::Database(e.to_string()))?;
@@ -53,6 +57,39 @@ impl FamilySpouseRepo {
         Ok(models.into_iter().map(into_domain).collect())
     }
 
+    /// List all family memberships where any of these persons is a spouse.
+    pub async fn list_by_persons(
+        db: &DatabaseConnection,
+        person_ids: &[Uuid],
+    ) -> Result<Vec<FamilySpouse>, OxidGeneError> {
+        let models = Entity::find()
+            .filter(Column::PersonId.is_in(person_ids.iter().copied()))
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
+    /// The `sort_order` to store for a new spouse link: `requested` if it is
+    /// non-zero, otherwise one past the family's current last spouse, so
+    /// members added without an explicit position keep their insertion order.
+    pub async fn sort_order_for_new(
+        db: &DatabaseConnection,
+        family_id: Uuid,
+        requested: i32,
+    ) -> Result<i32, OxidGeneError> {
+        if requested != 0 {
+            return Ok(requested);
+        }
+        let last = Entity::find()
+            .filter(Column::FamilyId.eq(family_id))
+            .order_by_desc(Column::SortOrder)
+            .one(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(last.map_or(0, |m| m.sort_order + 1))
+    }
+
     /// Create a family–spouse link.
     pub async fn create(
         db: &DatabaseConnection,
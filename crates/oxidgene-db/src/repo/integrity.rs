@@ -0,0 +1,320 @@
+//! Tree integrity checks: dangling references and derived data that drifted
+//! from its source (partial imports, concurrent edits, manual DB surgery).
+//!
+//! [`IntegrityRepo::check`] only reads; each [`Issue`] carries a suggested
+//! fix for the user to apply.
+
+use std::collections::HashSet;
+
+use oxidgene_core::error::OxidGeneError;
+use sea_orm::entity::prelude::*;
+use sea_orm::{Condition, JoinType, QueryFilter, QuerySelect};
+use uuid::Uuid;
+
+use super::person_ancestry::closure_from_links;
+use crate::entities::{
+    citation, event, family, family_child, family_spouse, person, person_ancestry, person_name,
+    place, source,
+};
+
+/// How serious an [`Issue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Broken reference: something points at data that is gone.
+    Error,
+    /// Suspicious or stale data that does not break anything by itself.
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+}
+
+/// The kind of problem an [`Issue`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    EventMissingPlace,
+    CitationDeletedSource,
+    FamilyChildMissingPerson,
+    PersonWithoutName,
+    FamilyWithoutMembers,
+    AncestryMissing,
+    AncestryStale,
+}
+
+impl IssueKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::EventMissingPlace => "event_missing_place",
+            Self::CitationDeletedSource => "citation_deleted_source",
+            Self::FamilyChildMissingPerson => "family_child_missing_person",
+            Self::PersonWithoutName => "person_without_name",
+            Self::FamilyWithoutMembers => "family_without_members",
+            Self::AncestryMissing => "ancestry_missing",
+            Self::AncestryStale => "ancestry_stale",
+        }
+    }
+
+    pub fn severity(self) -> Severity {
+        match self {
+            Self::EventMissingPlace
+            | Self::CitationDeletedSource
+            | Self::FamilyChildMissingPerson => Severity::Error,
+            Self::PersonWithoutName
+            | Self::FamilyWithoutMembers
+            | Self::AncestryMissing
+            | Self::AncestryStale => Severity::Warning,
+        }
+    }
+
+    pub fn suggested_fix(self) -> &'static str {
+        match self {
+            Self::EventMissingPlace => "Pick an existing place for the event, or clear its place.",
+            Self::CitationDeletedSource => "Delete the citation, or restore its source.",
+            Self::FamilyChildMissingPerson => "Remove the child from the family.",
+            Self::PersonWithoutName => "Add a name to the person, or delete the person.",
+            Self::FamilyWithoutMembers => "Delete the family, or add spouses or children to it.",
+            Self::AncestryMissing | Self::AncestryStale => {
+                "Rebuild the ancestry closure from the tree's family links."
+            }
+        }
+    }
+}
+
+/// A single integrity finding.
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub kind: IssueKind,
+    pub severity: Severity,
+    /// The entity the issue is about (event, citation, family-child link,
+    /// person, family, or ancestry row / descendant).
+    pub entity_id: Uuid,
+    pub message: String,
+    pub suggested_fix: &'static str,
+}
+
+impl Issue {
+    fn new(kind: IssueKind, entity_id: Uuid, message: String) -> Self {
+        Self {
+            kind,
+            severity: kind.severity(),
+            entity_id,
+            message,
+            suggested_fix: kind.suggested_fix(),
+        }
+    }
+}
+
+pub struct IntegrityRepo;
+
+impl IntegrityRepo {
+    /// Run every integrity check on a tree. Errors come first, then
+    /// warnings, each group in check order.
+    pub async fn check(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+    ) -> Result<Vec<Issue>, OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+
+        // Persons of the tree, deleted ones included: a link to a
+        // soft-deleted person is as broken as one to a missing person.
+        let persons = person::Entity::find()
+            .filter(person::Column::TreeId.eq(tree_id))
+            .all(db)
+            .await
+            .map_err(db_err)?;
+        let live_persons: HashSet<Uuid> = persons
+            .iter()
+            .filter(|p| p.deleted_at.is_none())
+            .map(|p| p.id)
+            .collect();
+
+        let families = family::Entity::find()
+            .filter(family::Column::TreeId.eq(tree_id))
+            .filter(family::Column::DeletedAt.is_null())
+            .all(db)
+            .await
+            .map_err(db_err)?;
+
+        let spouses = family_spouse::Entity::find()
+            .join(JoinType::InnerJoin, family_spouse::Relation::Family.def())
+            .filter(family::Column::TreeId.eq(tree_id))
+            .filter(family::Column::DeletedAt.is_null())
+            .all(db)
+            .await
+            .map_err(db_err)?;
+        let children = family_child::Entity::find()
+            .join(JoinType::InnerJoin, family_child::Relation::Family.def())
+            .filter(family::Column::TreeId.eq(tree_id))
+            .filter(family::Column::DeletedAt.is_null())
+            .all(db)
+            .await
+            .map_err(db_err)?;
+
+        let mut issues = Vec::new();
+
+        // Events pointing at a place that does not exist.
+        let place_ids: HashSet<Uuid> = place::Entity::find()
+            .filter(place::Column::TreeId.eq(tree_id))
+            .all(db)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .map(|p| p.id)
+            .collect();
+        let events = event::Entity::find()
+            .filter(event::Column::TreeId.eq(tree_id))
+            .filter(event::Column::DeletedAt.is_null())
+            .filter(
+                Condition::any()
+                    .add(event::Column::PlaceId.is_not_null())
+                    .add(event::Column::PlaceIdTo.is_not_null()),
+            )
+            .all(db)
+            .await
+            .map_err(db_err)?;
+        for ev in events {
+            for place_id in ev.place_id.into_iter().chain(ev.place_id_to) {
+                if place_ids.contains(&place_id) {
+                    continue;
+                }
+                issues.push(Issue::new(
+                    IssueKind::EventMissingPlace,
+                    ev.id,
+                    format!(
+                        "Event {} references place {place_id}, which does not exist",
+                        ev.id
+                    ),
+                ));
+            }
+        }
+
+        // Citations of soft-deleted sources.
+        let citations = citation::Entity::find()
+            .join(JoinType::InnerJoin, citation::Relation::Source.def())
+            .filter(source::Column::TreeId.eq(tree_id))
+            .filter(source::Column::DeletedAt.is_not_null())
+            .all(db)
+            .await
+            .map_err(db_err)?;
+        for cit in citations {
+            issues.push(Issue::new(
+                IssueKind::CitationDeletedSource,
+                cit.id,
+                format!(
+                    "Citation {} cites source {}, which was deleted",
+                    cit.id, cit.source_id
+                ),
+            ));
+        }
+
+        // Child links to missing or deleted persons.
+        for ch in &children {
+            if !live_persons.contains(&ch.person_id) {
+                issues.push(Issue::new(
+                    IssueKind::FamilyChildMissingPerson,
+                    ch.id,
+                    format!(
+                        "Family {} lists child {}, who does not exist or was deleted",
+                        ch.family_id, ch.person_id
+                    ),
+                ));
+            }
+        }
+
+        // Persons without any name.
+        let named: HashSet<Uuid> = person_name::Entity::find()
+            .join(JoinType::InnerJoin, person_name::Relation::Person.def())
+            .filter(person::Column::TreeId.eq(tree_id))
+            .all(db)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .map(|n| n.person_id)
+            .collect();
+        for p in persons.iter().filter(|p| p.deleted_at.is_none()) {
+            if !named.contains(&p.id) {
+                issues.push(Issue::new(
+                    IssueKind::PersonWithoutName,
+                    p.id,
+                    format!("Person {} has no name", p.id),
+                ));
+            }
+        }
+
+        // Families without any live spouse or child.
+        let members: HashSet<Uuid> = spouses
+            .iter()
+            .map(|s| (s.family_id, s.person_id))
+            .chain(children.iter().map(|c| (c.family_id, c.person_id)))
+            .filter(|(_, person_id)| live_persons.contains(person_id))
+            .map(|(family_id, _)| family_id)
+            .collect();
+        for fam in &families {
+            if !members.contains(&fam.id) {
+                issues.push(Issue::new(
+                    IssueKind::FamilyWithoutMembers,
+                    fam.id,
+                    format!("Family {} has no spouses or children", fam.id),
+                ));
+            }
+        }
+
+        // Ancestry closure vs. what the current family links imply.
+        let expected = closure_from_links(&spouses, &children, &live_persons);
+        let stored = person_ancestry::Entity::find()
+            .filter(person_ancestry::Column::TreeId.eq(tree_id))
+            .all(db)
+            .await
+            .map_err(db_err)?;
+        let mut stored_pairs = HashSet::with_capacity(stored.len());
+        for row in &stored {
+            // Self rows (depth 0) are harmless and not implied by links.
+            if row.ancestor_id == row.descendant_id {
+                continue;
+            }
+            stored_pairs.insert((row.ancestor_id, row.descendant_id));
+            match expected.get(&(row.ancestor_id, row.descendant_id)) {
+                Some(&depth) if depth == row.depth => {}
+                Some(&depth) => issues.push(Issue::new(
+                    IssueKind::AncestryStale,
+                    row.id,
+                    format!(
+                        "Ancestry row {} records {} as an ancestor of {} at depth {}, but family links give depth {depth}",
+                        row.id, row.ancestor_id, row.descendant_id, row.depth
+                    ),
+                )),
+                None => issues.push(Issue::new(
+                    IssueKind::AncestryStale,
+                    row.id,
+                    format!(
+                        "Ancestry row {} records {} as an ancestor of {}, but no family links connect them",
+                        row.id, row.ancestor_id, row.descendant_id
+                    ),
+                )),
+            }
+        }
+        let mut missing: Vec<_> = expected
+            .iter()
+            .filter(|(pair, _)| !stored_pairs.contains(pair))
+            .collect();
+        missing.sort();
+        for (&(ancestor_id, descendant_id), depth) in missing {
+            issues.push(Issue::new(
+                IssueKind::AncestryMissing,
+                descendant_id,
+                format!(
+                    "{ancestor_id} is an ancestor of {descendant_id} (depth {depth}) per family links, but the ancestry closure has no row for it"
+                ),
+            ));
+        }
+
+        issues.sort_by_key(|i| i.severity == Severity::Warning);
+        Ok(issues)
+    }
+}
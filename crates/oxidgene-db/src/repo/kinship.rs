@@ -0,0 +1,191 @@
+//! The blood relationship between two persons, as the chain of persons
+//! linking them through their nearest common ancestor.
+
+use std::collections::HashMap;
+
+use oxidgene_core::enums::KinshipEdge;
+use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::types::Person;
+use sea_orm::entity::prelude::*;
+use sea_orm::{JoinType, QueryFilter, QuerySelect};
+use uuid::Uuid;
+
+use super::person::into_domain;
+use crate::entities::{family, family_child, family_spouse, person, person_ancestry};
+
+/// One person on a [`KinshipPath`].
+#[derive(Debug, Clone)]
+pub struct KinshipStep {
+    pub person: Person,
+    /// How the person relates to the previous step; `None` for the first.
+    pub edge: Option<KinshipEdge>,
+}
+
+/// The chain from one person up to a common ancestor and down to another.
+#[derive(Debug, Clone)]
+pub struct KinshipPath {
+    pub common_ancestor_id: Uuid,
+    /// Generations from the first person up to the common ancestor.
+    pub generations_up: i32,
+    /// Generations from the common ancestor down to the second person.
+    pub generations_down: i32,
+    /// Both persons included, the first person first.
+    pub steps: Vec<KinshipStep>,
+}
+
+pub struct KinshipRepo;
+
+impl KinshipRepo {
+    /// The path from `a` to `b` through their nearest common ancestor, from
+    /// the ancestry closure: the ancestor with the fewest generations in
+    /// total, then the fewest on the longer side. One of the two persons is
+    /// the common ancestor when they descend from each other. `None` when
+    /// they share no ancestor.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OxidGeneError::NotFound`] when `a` or `b` is not a live
+    /// person of the tree.
+    pub async fn path(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        a: Uuid,
+        b: Uuid,
+    ) -> Result<Option<KinshipPath>, OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+
+        for id in [a, b] {
+            person::Entity::find_by_id(id)
+                .filter(person::Column::TreeId.eq(tree_id))
+                .filter(person::Column::DeletedAt.is_null())
+                .one(db)
+                .await
+                .map_err(db_err)?
+                .ok_or(OxidGeneError::NotFound {
+                    entity: "Person",
+                    id,
+                })?;
+        }
+
+        let ancestors_a = ancestry(db, a).await?;
+        let ancestors_b = ancestry(db, b).await?;
+        let Some((&common, &up, &down)) = ancestors_a
+            .iter()
+            .filter_map(|(id, up)| ancestors_b.get(id).map(|down| (id, up, down)))
+            .min_by_key(|&(id, &up, &down)| (up + down, up.max(down), *id))
+        else {
+            return Ok(None);
+        };
+
+        // Walk down from the common ancestor towards each person: every
+        // generation has a child one step closer, the closure storing the
+        // shortest depths.
+        let mut up_chain = descent(db, common, up, &ancestors_a).await?;
+        let down_chain = descent(db, common, down, &ancestors_b).await?;
+        up_chain.reverse();
+        let ids: Vec<Uuid> = up_chain
+            .iter()
+            .chain(down_chain.iter().skip(1))
+            .copied()
+            .collect();
+
+        let mut persons: HashMap<Uuid, Person> = person::Entity::find()
+            .filter(person::Column::Id.is_in(ids.iter().copied()))
+            .all(db)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .map(|m| (m.id, into_domain(m)))
+            .collect();
+        let steps = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let person = persons.remove(id).ok_or(OxidGeneError::NotFound {
+                    entity: "Person",
+                    id: *id,
+                })?;
+                let edge = match i {
+                    0 => None,
+                    i if i <= up as usize => Some(KinshipEdge::parent(person.sex)),
+                    _ => Some(KinshipEdge::child(person.sex)),
+                };
+                Ok(KinshipStep { person, edge })
+            })
+            .collect::<Result<_, OxidGeneError>>()?;
+
+        Ok(Some(KinshipPath {
+            common_ancestor_id: common,
+            generations_up: up,
+            generations_down: down,
+            steps,
+        }))
+    }
+}
+
+/// `person_id`'s ancestors by depth, the person included at depth 0.
+async fn ancestry(
+    db: &DatabaseConnection,
+    person_id: Uuid,
+) -> Result<HashMap<Uuid, i32>, OxidGeneError> {
+    let mut depths: HashMap<Uuid, i32> = person_ancestry::Entity::find()
+        .filter(person_ancestry::Column::DescendantId.eq(person_id))
+        .filter(person_ancestry::Column::Depth.gt(0))
+        .all(db)
+        .await
+        .map_err(|e| OxidGeneError::Database(e.to_string()))?
+        .into_iter()
+        .map(|row| (row.ancestor_id, row.depth))
+        .collect();
+    depths.insert(person_id, 0);
+    Ok(depths)
+}
+
+/// The chain from `ancestor`, `depth` generations above the person whose
+/// ancestry is `ancestors`, down to that person.
+async fn descent(
+    db: &DatabaseConnection,
+    ancestor: Uuid,
+    depth: i32,
+    ancestors: &HashMap<Uuid, i32>,
+) -> Result<Vec<Uuid>, OxidGeneError> {
+    let mut chain = vec![ancestor];
+    for remaining in (0..depth).rev() {
+        let current = *chain.last().expect("chain starts with the ancestor");
+        let mut children = children_of(db, current).await?;
+        children.sort();
+        let next = children
+            .into_iter()
+            .find(|c| ancestors.get(c) == Some(&remaining))
+            .ok_or_else(|| {
+                OxidGeneError::Database(format!(
+                    "ancestry closure out of date below person {current}"
+                ))
+            })?;
+        chain.push(next);
+    }
+    Ok(chain)
+}
+
+/// Children of `person_id` through the live families they are a spouse of.
+async fn children_of(db: &DatabaseConnection, person_id: Uuid) -> Result<Vec<Uuid>, OxidGeneError> {
+    let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+    let family_ids: Vec<Uuid> = family_spouse::Entity::find()
+        .join(JoinType::InnerJoin, family_spouse::Relation::Family.def())
+        .filter(family::Column::DeletedAt.is_null())
+        .filter(family_spouse::Column::PersonId.eq(person_id))
+        .all(db)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|s| s.family_id)
+        .collect();
+    Ok(family_child::Entity::find()
+        .filter(family_child::Column::FamilyId.is_in(family_ids))
+        .all(db)
+        .await
+        .map_err(db_err)?
+        .into_iter()
+        .map(|c| c.person_id)
+        .collect())
+}
@@ -9,6 +9,7 @@ use uuid::Uuid;
 
 use crate::entities::media::{self, ActiveModel, Column, Entity};
 use crate::repo::pagination::{PaginationParams, paginate};
+use crate::repo::version;
 
 /// Repository for media CRUD operations.
 pub struct MediaRepo;
@@ -96,6 +97,7 @@ impl MediaRepo {
             place_id: Set(None),
             created_at: Set(now),
             updated_at: Set(now),
+            version: Set(1),
             deleted_at: Set(None),
         };
         let result = model
@@ -111,6 +113,7 @@ impl MediaRepo {
         id: Uuid,
         title: Option<Option<String>>,
         description: Option<Option<String>>,
+        expected_version: Option<i32>,
     ) -> Result<Media, OxidGeneError> {
         let existing = Entity::find_by_id(id)
             .filter(Column::DeletedAt.is_null())
@@ -122,6 +125,9 @@ impl MediaRepo {
                 id,
             })?;
 
+        let current_version = existing.version;
+        version::check("Media", id, expected_version, current_version)?;
+
         let mut active: ActiveModel = existing.into_active_model();
         if let Some(title) = title {
             active.title = Set(title);
@@ -130,11 +136,13 @@ impl MediaRepo {
             active.description = Set(description);
         }
         active.updated_at = Set(Utc::now());
+        active.version = Set(current_version + 1);
 
-        let result = active
-            .update(db)
+        let result = Entity::update(active)
+            .filter(Column::Version.eq(current_version))
+            .exec(db)
             .await
-            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+            .map_err(version::update_error("Media", id))?;
         Ok(into_domain(result))
     }
 
@@ -175,6 +183,7 @@ fn into_domain(m: media::Model) -> Media {
         place_id: m.place_id,
         created_at: m.created_at,
         updated_at: m.updated_at,
+        version: m.version,
         deleted_at: m.deleted_at,
     }
 }
@@ -1,21 +1,27 @@
-//! Repository for `MediaLink` junction table (create/delete only).
+//! Repository for `MediaLink` junction table (create/delete, profile photo).
 
 use oxidgene_core::error::OxidGeneError;
 use oxidgene_core::types::MediaLink;
 use sea_orm::entity::prelude::*;
-use sea_orm::{QueryFilter, Set};
+use sea_orm::sea_query::Expr;
+use sea_orm::{QueryFilter, QueryOrder, Set};
 use uuid::Uuid;
 
 use crate::entities::media_link::{self, Column, Entity};
+use crate::repo::transaction::with_transaction;
 
 /// Flat row for the bulk media-links query.
 #[derive(Debug)]
 pub struct MediaLinkRow {
+    pub link_id: Uuid,
     pub entity_id: Uuid,
     pub entity_type: String,
     pub media_id: Uuid,
     pub file_path: String,
     pub file_name: String,
+    pub mime_type: String,
+    /// The link designates the person's profile photo.
+    pub is_profile: bool,
 }
 
 /// Repository for media–entity links.
@@ -36,7 +42,8 @@ impl MediaLinkRepo {
         let (sql, values): (&str, &[sea_orm::Value]) = match backend {
             DbBackend::Sqlite => (
                 r#"
-                    SELECT ml.person_id, ml.media_id, m.file_path, m.file_name
+                    SELECT ml.id, ml.person_id, ml.media_id, m.file_path, m.file_name, m.mime_type,
+                           ml.is_profile
                     FROM media_link ml
                     INNER JOIN media m ON m.id = ml.media_id
                     INNER JOIN person p ON p.id = ml.person_id
@@ -49,7 +56,8 @@ impl MediaLinkRepo {
             ),
             _ => (
                 r#"
-                    SELECT ml.person_id, ml.media_id, m.file_path, m.file_name
+                    SELECT ml.id, ml.person_id, ml.media_id, m.file_path, m.file_name, m.mime_type,
+                           ml.is_profile
                     FROM media_link ml
                     INNER JOIN media m ON m.id = ml.media_id
                     INNER JOIN person p ON p.id = ml.person_id
@@ -71,6 +79,9 @@ impl MediaLinkRepo {
 
         let mut rows = Vec::new();
         for row in query_results {
+            let link_id: Uuid = row
+                .try_get("", "id")
+                .map_err(|e| OxidGeneError::Database(e.to_string()))?;
             let person_id: Uuid = row
                 .try_get("", "person_id")
                 .map_err(|e| OxidGeneError::Database(e.to_string()))?;
@@ -83,12 +94,21 @@ impl MediaLinkRepo {
             let file_name: String = row
                 .try_get("", "file_name")
                 .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+            let mime_type: String = row
+                .try_get("", "mime_type")
+                .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+            let is_profile: bool = row
+                .try_get("", "is_profile")
+                .map_err(|e| OxidGeneError::Database(e.to_string()))?;
             rows.push(MediaLinkRow {
+                link_id,
                 entity_id: person_id,
                 entity_type: "person".to_string(),
                 media_id,
                 file_path,
                 file_name,
+                mime_type,
+                is_profile,
             });
         }
         Ok(rows)
@@ -120,19 +140,135 @@ impl MediaLinkRepo {
         Ok(models.into_iter().map(into_domain).collect())
     }
 
-    /// List all media links attached to a person.
+    /// List all media links attached to a person, in display order.
     pub async fn list_by_person(
         db: &DatabaseConnection,
         person_id: Uuid,
     ) -> Result<Vec<MediaLink>, OxidGeneError> {
         let models = Entity::find()
             .filter(Column::PersonId.eq(person_id))
+            .order_by_asc(Column::SortOrder)
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
+    /// List all media links attached to a source, in display order.
+    pub async fn list_by_source(
+        db: &DatabaseConnection,
+        source_id: Uuid,
+    ) -> Result<Vec<MediaLink>, OxidGeneError> {
+        let models = Entity::find()
+            .filter(Column::SourceId.eq(source_id))
+            .order_by_asc(Column::SortOrder)
             .all(db)
             .await
             .map_err(|e| OxidGeneError::Database(e.to_string()))?;
         Ok(models.into_iter().map(into_domain).collect())
     }
 
+    /// The link designating `person_id`'s profile photo, if any.
+    pub async fn profile(
+        db: &DatabaseConnection,
+        person_id: Uuid,
+    ) -> Result<Option<MediaLink>, OxidGeneError> {
+        let model = Entity::find()
+            .filter(Column::PersonId.eq(person_id))
+            .filter(Column::IsProfile.eq(true))
+            .order_by_asc(Column::SortOrder)
+            .one(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(model.map(into_domain))
+    }
+
+    /// Designate the link `link_id` as `person_id`'s profile photo, or
+    /// clear the designation with `None`. A person has at most one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OxidGeneError::NotFound`] when `link_id` is not a link of
+    /// the person.
+    pub async fn set_profile(
+        db: &DatabaseConnection,
+        person_id: Uuid,
+        link_id: Option<Uuid>,
+    ) -> Result<(), OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        with_transaction(db, |txn| {
+            Box::pin(async move {
+                Entity::update_many()
+                    .col_expr(Column::IsProfile, Expr::value(false))
+                    .filter(Column::PersonId.eq(person_id))
+                    .exec(txn)
+                    .await
+                    .map_err(db_err)?;
+                if let Some(id) = link_id {
+                    let result = Entity::update_many()
+                        .col_expr(Column::IsProfile, Expr::value(true))
+                        .filter(Column::Id.eq(id))
+                        .filter(Column::PersonId.eq(person_id))
+                        .exec(txn)
+                        .await
+                        .map_err(db_err)?;
+                    if result.rows_affected == 0 {
+                        return Err(OxidGeneError::NotFound {
+                            entity: "MediaLink",
+                            id,
+                        });
+                    }
+                }
+                Ok(())
+            })
+        })
+        .await
+    }
+
+    /// Renumber `person_id`'s media links in the order of `link_ids`,
+    /// which must list each of the person's links exactly once.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OxidGeneError::Validation`] when `link_ids` is not a
+    /// permutation of the person's links.
+    pub async fn reorder_person(
+        db: &DatabaseConnection,
+        person_id: Uuid,
+        link_ids: &[Uuid],
+    ) -> Result<(), OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        let mut current: Vec<Uuid> = Self::list_by_person(db, person_id)
+            .await?
+            .into_iter()
+            .map(|l| l.id)
+            .collect();
+        let mut requested = link_ids.to_vec();
+        current.sort_unstable();
+        requested.sort_unstable();
+        if current != requested {
+            return Err(OxidGeneError::Validation(
+                "link_ids must list each of the person's media links exactly once".into(),
+            ));
+        }
+        let link_ids = link_ids.to_vec();
+        with_transaction(db, |txn| {
+            Box::pin(async move {
+                for (position, id) in link_ids.into_iter().enumerate() {
+                    Entity::update_many()
+                        .col_expr(Column::SortOrder, Expr::value(position as i32))
+                        .filter(Column::Id.eq(id))
+                        .exec(txn)
+                        .await
+                        .map_err(db_err)?;
+                }
+                Ok(())
+            })
+        })
+        .await
+    }
+
     /// Create a media link.
     #[allow(clippy::too_many_arguments)]
     pub async fn create(
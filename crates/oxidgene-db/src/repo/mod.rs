@@ -1,48 +1,88 @@
 //! Repository layer: CRUD operations, pagination, and database utilities.
 //!
 //! This module provides:
-//! - Database connection and migration helpers (`connect`, `run_migrations`)
+//! - Database connection, migration and backup helpers (`connect`, `SqliteTuning`,
+//!   `run_migrations`, `backup_sqlite`)
 //! - A generic cursor-based pagination helper
+//! - [`with_transaction`], to run multi-step writes all-or-nothing
 //! - Repository implementations for all entities
 
+mod activity;
 mod citation;
 mod connection;
 mod dictionary;
 mod event;
-mod event_witness;
+mod event_participant;
+mod external_id;
 mod family;
 mod family_child;
 mod family_spouse;
+mod integrity;
+mod kinship;
 mod media;
 mod media_link;
 mod note;
+mod owner_label;
 mod pagination;
 mod person;
 mod person_ancestry;
+mod person_delete;
+mod person_move;
 mod person_name;
 mod person_search;
+mod person_tag;
 mod place;
+mod relative;
+mod replace;
+mod research_task;
+mod share_link;
 mod source;
+mod source_usage;
+mod transaction;
 mod tree;
+mod tree_membership;
+mod tree_scope;
+mod version;
 
+pub use activity::{ActivityEntry, ActivityKind, ActivityRepo};
 pub use citation::CitationRepo;
-pub use connection::{connect, rollback_migrations, run_migrations};
+pub use connection::{
+    SqliteSynchronous, SqliteTuning, backup_sqlite, connect, connect_with, rollback_migrations,
+    run_migrations,
+};
 pub use dictionary::{
     DictionaryRepo, DictionaryValueEntry, PersonUsageEntry, SOURCE_DRILL_THRESHOLD,
+    SurnameBucketCount, SurnameBucketing, SurnameDistribution, SurnameIndexEntry,
 };
-pub use event::{EventFilter, EventRepo};
-pub use event_witness::EventWitnessRepo;
-pub use family::FamilyRepo;
+pub use event::{DateSortBackfill, EventFilter, EventRepo};
+pub use event_participant::EventParticipantRepo;
+pub use external_id::ExternalIdRepo;
+pub use family::{FamilyRepo, PersonFamilyRole};
 pub use family_child::FamilyChildRepo;
 pub use family_spouse::FamilySpouseRepo;
+pub use integrity::{IntegrityRepo, Issue, IssueKind, Severity};
+pub use kinship::{KinshipPath, KinshipRepo, KinshipStep};
 pub use media::MediaRepo;
 pub use media_link::{MediaLinkRepo, MediaLinkRow};
 pub use note::NoteRepo;
-pub use pagination::PaginationParams;
-pub use person::PersonRepo;
+pub use pagination::{DEFAULT_PAGE_SIZE, PaginationParams};
+pub use person::{PersonFilter, PersonRepo};
 pub use person_ancestry::PersonAncestryRepo;
-pub use person_name::PersonNameRepo;
-pub use person_search::{PersonSearchEntry, PersonSearchPage, PersonSearchRepo};
-pub use place::PlaceRepo;
-pub use source::SourceRepo;
-pub use tree::TreeRepo;
+pub use person_delete::{BulkDeleted, DeleteSkipReason, PersonDeleteRepo};
+pub use person_move::{MovedPerson, PersonMoveRepo};
+pub use person_name::{NameCaseNormalization, PersonNameRepo, RecasedName};
+pub use person_search::{FuzzySearchHit, PersonSearchEntry, PersonSearchPage, PersonSearchRepo};
+pub use person_tag::{PersonTagRepo, TagCount};
+pub use place::{
+    DEDUPE_COORD_TOLERANCE, PlaceRepo, PlaceUsage, coordinates_match, normalize_place_name,
+};
+pub use relative::{AddedRelative, NewRelative, RelativeRepo};
+pub use replace::{ReplaceRepo, ReplacedValue};
+pub use research_task::{ResearchTaskFilter, ResearchTaskRepo};
+pub use share_link::ShareLinkRepo;
+pub use source::{SourceCitationCount, SourceMerge, SourceRepo, normalize_source_field};
+pub use source_usage::{CitationOwnerKind, SourceUsageEntry, SourceUsageRepo};
+pub use transaction::with_transaction;
+pub use tree::{TreeRepo, TreeStats};
+pub use tree_membership::TreeMembershipRepo;
+pub use tree_scope::{TreeEntity, TreeScopeRepo};
@@ -1,13 +1,16 @@
 //! Repository for `Note` entities (CRUD with soft delete).
 
 use chrono::Utc;
+use oxidgene_core::enums::NoteFormat;
 use oxidgene_core::error::OxidGeneError;
 use oxidgene_core::types::Note;
 use sea_orm::entity::prelude::*;
-use sea_orm::{ActiveModelTrait, IntoActiveModel, QueryFilter, Set};
+use sea_orm::{ActiveModelTrait, Condition, IntoActiveModel, QueryFilter, Set};
 use uuid::Uuid;
 
 use crate::entities::note::{self, ActiveModel, Column, Entity};
+use crate::entities::sea_enums;
+use crate::repo::version;
 
 /// Repository for note CRUD operations.
 pub struct NoteRepo;
@@ -71,6 +74,28 @@ impl NoteRepo {
         Ok(models.into_iter().map(into_domain).collect())
     }
 
+    /// List the notes of a person together with those of the given families
+    /// (excludes soft-deleted).
+    pub async fn list_by_person_and_families(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        person_id: Uuid,
+        family_ids: &[Uuid],
+    ) -> Result<Vec<Note>, OxidGeneError> {
+        let models = Entity::find()
+            .filter(Column::TreeId.eq(tree_id))
+            .filter(Column::DeletedAt.is_null())
+            .filter(
+                Condition::any()
+                    .add(Column::PersonId.eq(person_id))
+                    .add(Column::FamilyId.is_in(family_ids.iter().copied())),
+            )
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
     /// Create a new note.
     #[allow(clippy::too_many_arguments)]
     pub async fn create(
@@ -78,6 +103,7 @@ impl NoteRepo {
         id: Uuid,
         tree_id: Uuid,
         text: String,
+        format: NoteFormat,
         person_id: Option<Uuid>,
         event_id: Option<Uuid>,
         family_id: Option<Uuid>,
@@ -88,12 +114,14 @@ impl NoteRepo {
             id: Set(id),
             tree_id: Set(tree_id),
             text: Set(text),
+            format: Set(sea_enums::NoteFormat::from(format)),
             person_id: Set(person_id),
             event_id: Set(event_id),
             family_id: Set(family_id),
             source_id: Set(source_id),
             created_at: Set(now),
             updated_at: Set(now),
+            version: Set(1),
             deleted_at: Set(None),
         };
         let result = model
@@ -103,11 +131,13 @@ impl NoteRepo {
         Ok(into_domain(result))
     }
 
-    /// Update a note's text.
+    /// Update a note's text and/or format.
     pub async fn update(
         db: &DatabaseConnection,
         id: Uuid,
         text: Option<String>,
+        format: Option<NoteFormat>,
+        expected_version: Option<i32>,
     ) -> Result<Note, OxidGeneError> {
         let existing = Entity::find_by_id(id)
             .filter(Column::DeletedAt.is_null())
@@ -116,16 +146,24 @@ impl NoteRepo {
             .map_err(|e| OxidGeneError::Database(e.to_string()))?
             .ok_or(OxidGeneError::NotFound { entity: "Note", id })?;
 
+        let current_version = existing.version;
+        version::check("Note", id, expected_version, current_version)?;
+
         let mut active: ActiveModel = existing.into_active_model();
         if let Some(text) = text {
             active.text = Set(text);
         }
+        if let Some(format) = format {
+            active.format = Set(sea_enums::NoteFormat::from(format));
+        }
         active.updated_at = Set(Utc::now());
+        active.version = Set(current_version + 1);
 
-        let result = active
-            .update(db)
+        let result = Entity::update(active)
+            .filter(Column::Version.eq(current_version))
+            .exec(db)
             .await
-            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+            .map_err(version::update_error("Note", id))?;
         Ok(into_domain(result))
     }
 
@@ -153,12 +191,14 @@ fn into_domain(m: note::Model) -> Note {
         id: m.id,
         tree_id: m.tree_id,
         text: m.text,
+        format: m.format.into(),
         person_id: m.person_id,
         event_id: m.event_id,
         family_id: m.family_id,
         source_id: m.source_id,
         created_at: m.created_at,
         updated_at: m.updated_at,
+        version: m.version,
         deleted_at: m.deleted_at,
     }
 }
@@ -0,0 +1,69 @@
+//! Display labels of the persons and families that own events, notes and
+//! citations, resolved in bulk for a page of rows.
+
+use std::collections::{HashMap, HashSet};
+
+use oxidgene_core::error::OxidGeneError;
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::repo::{FamilySpouseRepo, PersonNameRepo};
+
+/// Person names (primary name preferred) and family labels (spouse names
+/// joined with " & ").
+pub(crate) struct OwnerLabels {
+    persons: HashMap<Uuid, String>,
+    families: HashMap<Uuid, Vec<String>>,
+}
+
+impl OwnerLabels {
+    /// Load the labels of the given persons and families. Spouses of the
+    /// families are looked up too.
+    pub(crate) async fn load(
+        db: &DatabaseConnection,
+        person_ids: impl IntoIterator<Item = Uuid>,
+        family_ids: impl IntoIterator<Item = Uuid>,
+    ) -> Result<Self, OxidGeneError> {
+        let family_ids: Vec<Uuid> = family_ids
+            .into_iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let spouses = FamilySpouseRepo::list_by_families(db, &family_ids).await?;
+        let person_ids: Vec<Uuid> = person_ids
+            .into_iter()
+            .chain(spouses.iter().map(|s| s.person_id))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let mut persons: HashMap<Uuid, String> = HashMap::new();
+        for name in PersonNameRepo::list_by_persons(db, &person_ids).await? {
+            if name.is_primary || !persons.contains_key(&name.person_id) {
+                persons.insert(name.person_id, name.display_name());
+            }
+        }
+        let mut families: HashMap<Uuid, Vec<String>> = HashMap::new();
+        for spouse in spouses {
+            if let Some(name) = persons.get(&spouse.person_id) {
+                families
+                    .entry(spouse.family_id)
+                    .or_default()
+                    .push(name.clone());
+            }
+        }
+        Ok(Self { persons, families })
+    }
+
+    /// A person's name, empty if unknown or unnamed.
+    pub(crate) fn person(&self, id: Option<Uuid>) -> String {
+        id.and_then(|id| self.persons.get(&id).cloned())
+            .unwrap_or_default()
+    }
+
+    /// A family's spouse names, empty if unknown or without named spouses.
+    pub(crate) fn family(&self, id: Option<Uuid>) -> String {
+        id.and_then(|id| self.families.get(&id))
+            .map(|names| names.join(" & "))
+            .unwrap_or_default()
+    }
+}
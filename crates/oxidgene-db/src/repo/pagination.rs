@@ -11,10 +11,11 @@ use uuid::Uuid;
 
 /// Default page size.
 pub const DEFAULT_PAGE_SIZE: u64 = 25;
-/// Maximum page size.
-pub const MAX_PAGE_SIZE: u64 = 100;
 
 /// Parameters for cursor-based pagination.
+///
+/// `first` is taken as given (at least 1): bounding it is the caller's job,
+/// which the API does against its configured maximum page size.
 #[derive(Debug, Clone)]
 pub struct PaginationParams {
     /// Number of items to return.
@@ -33,9 +34,9 @@ impl Default for PaginationParams {
 }
 
 impl PaginationParams {
-    /// Clamp `first` to [1, MAX_PAGE_SIZE].
+    /// `first`, at least 1.
     pub fn clamped_first(&self) -> u64 {
-        self.first.clamp(1, MAX_PAGE_SIZE)
+        self.first.max(1)
     }
 
     /// Decode the `after` cursor into a UUID.
@@ -120,6 +121,7 @@ where
         page_info: PageInfo {
             has_next_page,
             end_cursor,
+            limit,
         },
         total_count: total_count as i64,
     })
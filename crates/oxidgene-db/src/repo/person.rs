@@ -3,17 +3,40 @@
 //! Free-text person search lives in [`crate::repo::PersonSearchRepo`]
 //! (the `person_search_fts` table) since Sprint E.6.
 
+use std::collections::HashMap;
+
 use chrono::Utc;
 use oxidgene_core::enums::{Privacy, Sex};
 use oxidgene_core::error::OxidGeneError;
-use oxidgene_core::types::{Connection, Person};
+use oxidgene_core::text::normalize;
+use oxidgene_core::types::{Connection, Person, PersonName};
 use sea_orm::entity::prelude::*;
-use sea_orm::{ActiveModelTrait, IntoActiveModel, QueryFilter, Set};
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, IntoActiveModel, QueryFilter, QueryOrder, QuerySelect,
+    QueryTrait, Set,
+};
 use uuid::Uuid;
 
 use crate::entities::person::{self, ActiveModel, Column, Entity};
+use crate::entities::person_ancestry;
+use crate::entities::person_name;
+use crate::entities::person_tag;
 use crate::entities::sea_enums;
 use crate::repo::pagination::{PaginationParams, paginate};
+use crate::repo::transaction::with_transaction;
+use crate::repo::version;
+
+/// Optional filters for listing persons.
+#[derive(Debug, Clone, Default)]
+pub struct PersonFilter {
+    /// Only persons whose primary name has this surname, ignoring case and
+    /// accents (see [`oxidgene_core::text::normalize`]).
+    pub surname: Option<String>,
+    /// Only persons carrying this tag.
+    pub tag: Option<String>,
+    /// Only persons without a recorded parent.
+    pub no_parents: bool,
+}
 
 /// Repository for person CRUD operations.
 pub struct PersonRepo;
@@ -31,6 +54,46 @@ impl PersonRepo {
         paginate(db, query, Column::Id, params, |m| (m.id, into_domain(m))).await
     }
 
+    /// List persons in a tree matching `filter`, with pagination
+    /// (excludes soft-deleted).
+    pub async fn list_filtered(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        filter: &PersonFilter,
+        params: &PaginationParams,
+    ) -> Result<Connection<Person>, OxidGeneError> {
+        let mut query = Entity::find()
+            .filter(Column::TreeId.eq(tree_id))
+            .filter(Column::DeletedAt.is_null());
+        if let Some(surname) = filter.surname.as_deref() {
+            let named = person_name::Entity::find()
+                .select_only()
+                .column(person_name::Column::PersonId)
+                .filter(person_name::Column::IsPrimary.eq(true))
+                .filter(person_name::Column::SurnameNorm.eq(normalize(surname)))
+                .into_query();
+            query = query.filter(Column::Id.in_subquery(named));
+        }
+        if let Some(tag) = filter.tag.as_deref() {
+            let tagged = person_tag::Entity::find()
+                .select_only()
+                .column(person_tag::Column::PersonId)
+                .filter(person_tag::Column::Tag.eq(tag))
+                .into_query();
+            query = query.filter(Column::Id.in_subquery(tagged));
+        }
+        if filter.no_parents {
+            let with_parents = person_ancestry::Entity::find()
+                .select_only()
+                .column(person_ancestry::Column::DescendantId)
+                .filter(person_ancestry::Column::TreeId.eq(tree_id))
+                .filter(person_ancestry::Column::Depth.eq(1))
+                .into_query();
+            query = query.filter(Column::Id.not_in_subquery(with_parents));
+        }
+        paginate(db, query, Column::Id, params, |m| (m.id, into_domain(m))).await
+    }
+
     /// List all persons in a tree without pagination (excludes soft-deleted).
     pub async fn list_all(
         db: &DatabaseConnection,
@@ -75,7 +138,7 @@ impl PersonRepo {
 
     /// Create a new person.
     pub async fn create(
-        db: &DatabaseConnection,
+        db: &impl ConnectionTrait,
         id: Uuid,
         tree_id: Uuid,
         sex: Sex,
@@ -86,8 +149,12 @@ impl PersonRepo {
             tree_id: Set(tree_id),
             sex: Set(sea_enums::Sex::from(sex)),
             privacy: Set(sea_enums::Privacy::from(Privacy::default())),
+            living: Set(None),
+            display_name: Set(None),
+            locked: Set(false),
             created_at: Set(now),
             updated_at: Set(now),
+            version: Set(1),
             deleted_at: Set(None),
         };
         let result = model
@@ -97,12 +164,16 @@ impl PersonRepo {
         Ok(into_domain(result))
     }
 
-    /// Update a person's sex and/or privacy.
+    /// Update a person's sex, privacy and/or living status (`Some(None)`
+    /// clears the status). Fails with [`OxidGeneError::Locked`] if the
+    /// person is locked.
     pub async fn update(
         db: &DatabaseConnection,
         id: Uuid,
         sex: Option<Sex>,
         privacy: Option<Privacy>,
+        living: Option<Option<bool>>,
+        expected_version: Option<i32>,
     ) -> Result<Person, OxidGeneError> {
         let existing = Entity::find_by_id(id)
             .filter(Column::DeletedAt.is_null())
@@ -113,6 +184,15 @@ impl PersonRepo {
                 entity: "Person",
                 id,
             })?;
+        if existing.locked {
+            return Err(OxidGeneError::Locked {
+                entity: "Person",
+                id,
+            });
+        }
+
+        let current_version = existing.version;
+        version::check("Person", id, expected_version, current_version)?;
 
         let mut active: ActiveModel = existing.into_active_model();
         if let Some(sex) = sex {
@@ -121,16 +201,22 @@ impl PersonRepo {
         if let Some(privacy) = privacy {
             active.privacy = Set(sea_enums::Privacy::from(privacy));
         }
+        if let Some(living) = living {
+            active.living = Set(living);
+        }
         active.updated_at = Set(Utc::now());
+        active.version = Set(current_version + 1);
 
-        let result = active
-            .update(db)
+        let result = Entity::update(active)
+            .filter(Column::Version.eq(current_version))
+            .exec(db)
             .await
-            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+            .map_err(version::update_error("Person", id))?;
         Ok(into_domain(result))
     }
 
-    /// Soft-delete a person.
+    /// Soft-delete a person. Fails with [`OxidGeneError::Locked`] if the
+    /// person is locked.
     pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<(), OxidGeneError> {
         let existing = Entity::find_by_id(id)
             .filter(Column::DeletedAt.is_null())
@@ -141,6 +227,12 @@ impl PersonRepo {
                 entity: "Person",
                 id,
             })?;
+        if existing.locked {
+            return Err(OxidGeneError::Locked {
+                entity: "Person",
+                id,
+            });
+        }
 
         let mut active: ActiveModel = existing.into_active_model();
         active.deleted_at = Set(Some(Utc::now()));
@@ -150,16 +242,180 @@ impl PersonRepo {
             .map_err(|e| OxidGeneError::Database(e.to_string()))?;
         Ok(())
     }
+
+    /// Lock or unlock a person (see [`Person::locked`]). Like an edit, it
+    /// bumps the person's version.
+    pub async fn set_locked(
+        db: &DatabaseConnection,
+        id: Uuid,
+        locked: bool,
+    ) -> Result<Person, OxidGeneError> {
+        let existing = Entity::find_by_id(id)
+            .filter(Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?
+            .ok_or(OxidGeneError::NotFound {
+                entity: "Person",
+                id,
+            })?;
+        let current_version = existing.version;
+
+        let mut active: ActiveModel = existing.into_active_model();
+        active.locked = Set(locked);
+        active.updated_at = Set(Utc::now());
+        active.version = Set(current_version + 1);
+        let result = Entity::update(active)
+            .filter(Column::Version.eq(current_version))
+            .exec(db)
+            .await
+            .map_err(version::update_error("Person", id))?;
+        Ok(into_domain(result))
+    }
+
+    /// Fail with [`OxidGeneError::Locked`] if `id` is a locked person. The
+    /// writes a lock also covers (the person's names) call it first; an
+    /// unknown person passes, for the write itself to report.
+    pub async fn ensure_unlocked(db: &impl ConnectionTrait, id: Uuid) -> Result<(), OxidGeneError> {
+        let locked = Entity::find_by_id(id)
+            .filter(Column::Locked.eq(true))
+            .one(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?
+            .is_some();
+        if locked {
+            return Err(OxidGeneError::Locked {
+                entity: "Person",
+                id,
+            });
+        }
+        Ok(())
+    }
+
+    /// Recompute the cached [`Person::display_name`] of `person_ids` from
+    /// their names. Every write to a person's names calls it; the person's
+    /// version is left alone, the cache not being an edit.
+    pub async fn refresh_display_names(
+        db: &impl ConnectionTrait,
+        person_ids: &[Uuid],
+    ) -> Result<(), OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        let mut display_names = display_names(db, person_ids).await?;
+        for &person_id in person_ids {
+            Entity::update_many()
+                .col_expr(
+                    Column::DisplayName,
+                    Expr::value(display_names.remove(&person_id)),
+                )
+                .filter(Column::Id.eq(person_id))
+                .exec(db)
+                .await
+                .map_err(db_err)?;
+        }
+        Ok(())
+    }
+
+    /// Recompute the cached display name of every person whose cache is out
+    /// of date, e.g. written before it existed. Persons are walked by id,
+    /// `chunk_size` at a time, each chunk in its own transaction. Returns
+    /// how many persons were updated, and the trees they belong to.
+    pub async fn backfill_display_names(
+        db: &DatabaseConnection,
+        chunk_size: u64,
+    ) -> Result<(u64, Vec<Uuid>), OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        let mut updated = 0;
+        let mut tree_ids = Vec::new();
+        let mut after: Option<Uuid> = None;
+        loop {
+            let mut query = Entity::find();
+            if let Some(after) = after {
+                query = query.filter(Column::Id.gt(after));
+            }
+            let chunk = query
+                .order_by_asc(Column::Id)
+                .limit(chunk_size.max(1))
+                .all(db)
+                .await
+                .map_err(db_err)?;
+            let Some(last) = chunk.last() else {
+                break;
+            };
+            after = Some(last.id);
+
+            let person_ids: Vec<Uuid> = chunk.iter().map(|m| m.id).collect();
+            let mut display_names = display_names(db, &person_ids).await?;
+            // Trees of the persons whose display name changed.
+            let changed = with_transaction(db, |txn| {
+                Box::pin(async move {
+                    let mut changed = Vec::new();
+                    for model in chunk {
+                        let display_name = display_names.remove(&model.id);
+                        if display_name == model.display_name {
+                            continue;
+                        }
+                        Entity::update_many()
+                            .col_expr(Column::DisplayName, Expr::value(display_name))
+                            .filter(Column::Id.eq(model.id))
+                            .exec(txn)
+                            .await
+                            .map_err(db_err)?;
+                        changed.push(model.tree_id);
+                    }
+                    Ok(changed)
+                })
+            })
+            .await?;
+            updated += changed.len() as u64;
+            for tree_id in changed {
+                if !tree_ids.contains(&tree_id) {
+                    tree_ids.push(tree_id);
+                }
+            }
+        }
+        Ok((updated, tree_ids))
+    }
+}
+
+/// Display names of `person_ids` per [`PersonName::display_name_of`], with
+/// names in creation order; persons without a name are left out.
+async fn display_names(
+    db: &impl ConnectionTrait,
+    person_ids: &[Uuid],
+) -> Result<HashMap<Uuid, String>, OxidGeneError> {
+    let mut names_by_person: HashMap<Uuid, Vec<PersonName>> = HashMap::new();
+    for model in person_name::Entity::find()
+        .filter(person_name::Column::PersonId.is_in(person_ids.iter().copied()))
+        .order_by_asc(person_name::Column::Id)
+        .all(db)
+        .await
+        .map_err(|e| OxidGeneError::Database(e.to_string()))?
+    {
+        names_by_person
+            .entry(model.person_id)
+            .or_default()
+            .push(super::person_name::into_domain(model));
+    }
+    Ok(names_by_person
+        .into_iter()
+        .filter_map(|(person_id, names)| {
+            PersonName::display_name_of(&names).map(|name| (person_id, name))
+        })
+        .collect())
 }
 
-fn into_domain(m: person::Model) -> Person {
+pub(super) fn into_domain(m: person::Model) -> Person {
     Person {
         id: m.id,
         tree_id: m.tree_id,
         sex: m.sex.into(),
         privacy: m.privacy.into(),
+        living: m.living,
+        display_name: m.display_name,
+        locked: m.locked,
         created_at: m.created_at,
         updated_at: m.updated_at,
+        version: m.version,
         deleted_at: m.deleted_at,
     }
 }
@@ -1,32 +1,36 @@
-//! Repository for `PersonAncestry` closure table (read queries).
+//! Repository for `PersonAncestry` closure table.
+
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::summary::Lineage;
 use oxidgene_core::types::PersonAncestry;
 use sea_orm::entity::prelude::*;
-use sea_orm::{Order, QueryFilter, QueryOrder, Set};
+use sea_orm::{ConnectionTrait, JoinType, Order, QueryFilter, QueryOrder, QuerySelect, Set};
 use uuid::Uuid;
 
 use crate::entities::person_ancestry::{self, Column, Entity};
+use crate::entities::{family, family_child, family_spouse, person};
+
+/// Rows per `INSERT`, and ids per `IN (...)` list, when rewriting closure
+/// rows.
+const REBUILD_BATCH: usize = 500;
 
 /// Repository for person ancestry closure table operations.
 pub struct PersonAncestryRepo;
 
 impl PersonAncestryRepo {
-    /// Get all ancestors of a person (optionally limited by max depth).
+    /// Get the ancestors of a person, up to `max_depth` generations. The
+    /// bound is required so that no query walks a whole pathological tree.
     pub async fn ancestors(
         db: &DatabaseConnection,
         descendant_id: Uuid,
-        max_depth: Option<i32>,
+        max_depth: i32,
     ) -> Result<Vec<PersonAncestry>, OxidGeneError> {
-        let mut query = Entity::find()
+        let models = Entity::find()
             .filter(Column::DescendantId.eq(descendant_id))
-            .filter(Column::Depth.gt(0)); // Exclude self-reference
-
-        if let Some(max) = max_depth {
-            query = query.filter(Column::Depth.lte(max));
-        }
-
-        let models = query
+            .filter(Column::Depth.gt(0)) // Exclude self-reference
+            .filter(Column::Depth.lte(max_depth))
             .order_by(Column::Depth, Order::Asc)
             .all(db)
             .await
@@ -34,21 +38,17 @@ impl PersonAncestryRepo {
         Ok(models.into_iter().map(into_domain).collect())
     }
 
-    /// Get all descendants of a person (optionally limited by max depth).
+    /// Get the descendants of a person, up to `max_depth` generations, see
+    /// [`Self::ancestors`].
     pub async fn descendants(
         db: &DatabaseConnection,
         ancestor_id: Uuid,
-        max_depth: Option<i32>,
+        max_depth: i32,
     ) -> Result<Vec<PersonAncestry>, OxidGeneError> {
-        let mut query = Entity::find()
+        let models = Entity::find()
             .filter(Column::AncestorId.eq(ancestor_id))
-            .filter(Column::Depth.gt(0)); // Exclude self-reference
-
-        if let Some(max) = max_depth {
-            query = query.filter(Column::Depth.lte(max));
-        }
-
-        let models = query
+            .filter(Column::Depth.gt(0)) // Exclude self-reference
+            .filter(Column::Depth.lte(max_depth))
             .order_by(Column::Depth, Order::Asc)
             .all(db)
             .await
@@ -56,6 +56,51 @@ impl PersonAncestryRepo {
         Ok(models.into_iter().map(into_domain).collect())
     }
 
+    /// The [`Lineage`] of each of `person_ids`, read off the closure: its
+    /// rows as ancestor count the descendants, a depth-1 row as descendant
+    /// is a recorded parent. Two queries whatever the number of persons.
+    pub async fn lineages(
+        db: &DatabaseConnection,
+        person_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Lineage>, OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        let counts: HashMap<Uuid, i64> = Entity::find()
+            .select_only()
+            .column(Column::AncestorId)
+            .column_as(Column::DescendantId.count(), "count")
+            .filter(Column::AncestorId.is_in(person_ids.iter().copied()))
+            .filter(Column::Depth.gt(0))
+            .group_by(Column::AncestorId)
+            .into_tuple::<(Uuid, i64)>()
+            .all(db)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .collect();
+        let with_parents: HashSet<Uuid> = Entity::find()
+            .select_only()
+            .column(Column::DescendantId)
+            .filter(Column::DescendantId.is_in(person_ids.iter().copied()))
+            .filter(Column::Depth.eq(1))
+            .distinct()
+            .into_tuple::<Uuid>()
+            .all(db)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .collect();
+        Ok(person_ids
+            .iter()
+            .map(|&id| {
+                let count = counts.get(&id).copied().unwrap_or_default();
+                (
+                    id,
+                    Lineage::new(count.max(0) as u64, with_parents.contains(&id)),
+                )
+            })
+            .collect())
+    }
+
     /// Insert a closure table entry (used internally when family relationships change).
     pub async fn create(
         db: &DatabaseConnection,
@@ -91,6 +136,349 @@ impl PersonAncestryRepo {
             .map_err(|e| OxidGeneError::Database(e.to_string()))?;
         Ok(result.rows_affected)
     }
+
+    /// Recompute a tree's whole closure from its current family links,
+    /// replacing the stored rows. Takes a connection or a transaction, so
+    /// a mutation of family links can rebuild the closure atomically.
+    pub async fn rebuild<C: ConnectionTrait>(db: &C, tree_id: Uuid) -> Result<(), OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+
+        let live_persons: HashSet<Uuid> = person::Entity::find()
+            .filter(person::Column::TreeId.eq(tree_id))
+            .filter(person::Column::DeletedAt.is_null())
+            .all(db)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .map(|p| p.id)
+            .collect();
+        let spouses = family_spouse::Entity::find()
+            .join(JoinType::InnerJoin, family_spouse::Relation::Family.def())
+            .filter(family::Column::TreeId.eq(tree_id))
+            .filter(family::Column::DeletedAt.is_null())
+            .all(db)
+            .await
+            .map_err(db_err)?;
+        let children = family_child::Entity::find()
+            .join(JoinType::InnerJoin, family_child::Relation::Family.def())
+            .filter(family::Column::TreeId.eq(tree_id))
+            .filter(family::Column::DeletedAt.is_null())
+            .all(db)
+            .await
+            .map_err(db_err)?;
+        let closure = closure_from_links(&spouses, &children, &live_persons);
+
+        Entity::delete_many()
+            .filter(Column::TreeId.eq(tree_id))
+            .exec(db)
+            .await
+            .map_err(db_err)?;
+        let models: Vec<person_ancestry::ActiveModel> = closure
+            .into_iter()
+            .map(
+                |((ancestor_id, descendant_id), depth)| person_ancestry::ActiveModel {
+                    id: Set(Uuid::now_v7()),
+                    tree_id: Set(tree_id),
+                    ancestor_id: Set(ancestor_id),
+                    descendant_id: Set(descendant_id),
+                    depth: Set(depth),
+                },
+            )
+            .collect();
+        for batch in models.chunks(REBUILD_BATCH) {
+            Entity::insert_many(batch.to_vec())
+                .exec(db)
+                .await
+                .map_err(db_err)?;
+        }
+        Ok(())
+    }
+
+    /// Bring the closure up to date for `persons` and all their descendants
+    /// after their parent links changed: a child link added or removed
+    /// (the child), a spouse link added or removed or a family deleted
+    /// (the family's children), a person deleted (that person).
+    ///
+    /// Only those persons' ancestries can change, so only their rows are
+    /// read and rewritten: each is recomputed from its parents', parents
+    /// first, and the result diffed against the stored rows. An edit thus
+    /// costs O(affected subtree), not O(tree) like [`Self::rebuild`] — which
+    /// it falls back to if the links contain a cycle.
+    pub async fn refresh<C: ConnectionTrait>(
+        db: &C,
+        tree_id: Uuid,
+        persons: &[Uuid],
+    ) -> Result<(), OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+
+        // The affected persons: the roots and everyone below them. Links
+        // below the roots did not change, so the stored rows find them.
+        let mut affected: Vec<Uuid> = persons.to_vec();
+        for chunk in persons.chunks(REBUILD_BATCH) {
+            let rows = Entity::find()
+                .filter(Column::AncestorId.is_in(chunk.iter().copied()))
+                .filter(Column::Depth.gt(0))
+                .all(db)
+                .await
+                .map_err(db_err)?;
+            affected.extend(rows.into_iter().map(|r| r.descendant_id));
+        }
+        affected.sort();
+        affected.dedup();
+        let affected_set: HashSet<Uuid> = affected.iter().copied().collect();
+
+        // Their parent links, between live persons of live families.
+        let mut child_links = Vec::new();
+        for chunk in affected.chunks(REBUILD_BATCH) {
+            child_links.extend(
+                family_child::Entity::find()
+                    .join(JoinType::InnerJoin, family_child::Relation::Family.def())
+                    .filter(family::Column::TreeId.eq(tree_id))
+                    .filter(family::Column::DeletedAt.is_null())
+                    .filter(family_child::Column::PersonId.is_in(chunk.iter().copied()))
+                    .all(db)
+                    .await
+                    .map_err(db_err)?,
+            );
+        }
+        let family_ids: Vec<Uuid> = child_links
+            .iter()
+            .map(|c| c.family_id)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let mut family_parents: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for chunk in family_ids.chunks(REBUILD_BATCH) {
+            let spouses = family_spouse::Entity::find()
+                .filter(family_spouse::Column::FamilyId.is_in(chunk.iter().copied()))
+                .all(db)
+                .await
+                .map_err(db_err)?;
+            for sp in spouses {
+                family_parents
+                    .entry(sp.family_id)
+                    .or_default()
+                    .push(sp.person_id);
+            }
+        }
+        let involved: Vec<Uuid> = affected
+            .iter()
+            .copied()
+            .chain(family_parents.values().flatten().copied())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let mut live_persons = HashSet::new();
+        for chunk in involved.chunks(REBUILD_BATCH) {
+            let live = person::Entity::find()
+                .filter(person::Column::Id.is_in(chunk.iter().copied()))
+                .filter(person::Column::DeletedAt.is_null())
+                .all(db)
+                .await
+                .map_err(db_err)?;
+            live_persons.extend(live.into_iter().map(|p| p.id));
+        }
+        let mut parents: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for link in &child_links {
+            if !live_persons.contains(&link.person_id) {
+                continue;
+            }
+            for &parent_id in family_parents.get(&link.family_id).into_iter().flatten() {
+                if live_persons.contains(&parent_id) {
+                    parents.entry(link.person_id).or_default().push(parent_id);
+                }
+            }
+        }
+
+        // Ancestries of unaffected parents are current: start from them.
+        let outside: Vec<Uuid> = parents
+            .values()
+            .flatten()
+            .filter(|p| !affected_set.contains(p))
+            .copied()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let mut ancestries: HashMap<Uuid, HashMap<Uuid, i32>> = HashMap::new();
+        for chunk in outside.chunks(REBUILD_BATCH) {
+            let rows = Entity::find()
+                .filter(Column::DescendantId.is_in(chunk.iter().copied()))
+                .filter(Column::Depth.gt(0))
+                .all(db)
+                .await
+                .map_err(db_err)?;
+            for row in rows {
+                ancestries
+                    .entry(row.descendant_id)
+                    .or_default()
+                    .insert(row.ancestor_id, row.depth);
+            }
+        }
+
+        // Parents before children (Kahn's algorithm over the affected set).
+        let mut pending: HashMap<Uuid, usize> = affected.iter().map(|&id| (id, 0)).collect();
+        let mut children_of: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for (&child_id, parent_ids) in &parents {
+            for &parent_id in parent_ids {
+                if affected_set.contains(&parent_id) {
+                    *pending.get_mut(&child_id).expect("affected child") += 1;
+                    children_of.entry(parent_id).or_default().push(child_id);
+                }
+            }
+        }
+        let mut ready: VecDeque<Uuid> = affected
+            .iter()
+            .filter(|id| pending[*id] == 0)
+            .copied()
+            .collect();
+        let mut order = Vec::with_capacity(affected.len());
+        while let Some(id) = ready.pop_front() {
+            order.push(id);
+            for &child_id in children_of.get(&id).into_iter().flatten() {
+                let count = pending.get_mut(&child_id).expect("affected child");
+                *count -= 1;
+                if *count == 0 {
+                    ready.push_back(child_id);
+                }
+            }
+        }
+        if order.len() < affected.len() {
+            return Self::rebuild(db, tree_id).await;
+        }
+
+        for &id in &order {
+            let mut ancestry: HashMap<Uuid, i32> = HashMap::new();
+            for &parent_id in parents.get(&id).into_iter().flatten() {
+                ancestry.insert(parent_id, 1);
+                for (&ancestor_id, &depth) in ancestries.get(&parent_id).into_iter().flatten() {
+                    let entry = ancestry.entry(ancestor_id).or_insert(depth + 1);
+                    *entry = (*entry).min(depth + 1);
+                }
+            }
+            ancestries.insert(id, ancestry);
+        }
+
+        // Diff against the stored rows. Self rows (depth 0) are left alone.
+        let mut stale = Vec::new();
+        for chunk in affected.chunks(REBUILD_BATCH) {
+            let rows = Entity::find()
+                .filter(Column::DescendantId.is_in(chunk.iter().copied()))
+                .filter(Column::Depth.gt(0))
+                .all(db)
+                .await
+                .map_err(db_err)?;
+            for row in rows {
+                let ancestry = ancestries.get_mut(&row.descendant_id).expect("affected");
+                match ancestry.remove(&row.ancestor_id) {
+                    Some(depth) if depth == row.depth => {}
+                    Some(depth) => {
+                        let mut active: person_ancestry::ActiveModel = row.into();
+                        active.depth = Set(depth);
+                        active.update(db).await.map_err(db_err)?;
+                    }
+                    None => stale.push(row.id),
+                }
+            }
+        }
+        for chunk in stale.chunks(REBUILD_BATCH) {
+            Entity::delete_many()
+                .filter(Column::Id.is_in(chunk.iter().copied()))
+                .exec(db)
+                .await
+                .map_err(db_err)?;
+        }
+        let models: Vec<person_ancestry::ActiveModel> = affected
+            .iter()
+            .flat_map(|id| {
+                ancestries[id]
+                    .iter()
+                    .map(|(&ancestor_id, &depth)| person_ancestry::ActiveModel {
+                        id: Set(Uuid::now_v7()),
+                        tree_id: Set(tree_id),
+                        ancestor_id: Set(ancestor_id),
+                        descendant_id: Set(*id),
+                        depth: Set(depth),
+                    })
+            })
+            .collect();
+        for batch in models.chunks(REBUILD_BATCH) {
+            Entity::insert_many(batch.to_vec())
+                .exec(db)
+                .await
+                .map_err(db_err)?;
+        }
+        Ok(())
+    }
+
+    /// [`Self::refresh`] the children of a family whose spouses changed,
+    /// or that was deleted.
+    pub async fn refresh_family<C: ConnectionTrait>(
+        db: &C,
+        tree_id: Uuid,
+        family_id: Uuid,
+    ) -> Result<(), OxidGeneError> {
+        let children: Vec<Uuid> = family_child::Entity::find()
+            .filter(family_child::Column::FamilyId.eq(family_id))
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?
+            .into_iter()
+            .map(|c| c.person_id)
+            .collect();
+        Self::refresh(db, tree_id, &children).await
+    }
+}
+
+/// The `(ancestor, descendant) → depth` closure implied by family links
+/// between live persons, using the shortest path when several exist.
+pub(crate) fn closure_from_links(
+    spouses: &[family_spouse::Model],
+    children: &[family_child::Model],
+    live_persons: &HashSet<Uuid>,
+) -> HashMap<(Uuid, Uuid), i32> {
+    let mut family_parents: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for sp in spouses
+        .iter()
+        .filter(|s| live_persons.contains(&s.person_id))
+    {
+        family_parents
+            .entry(sp.family_id)
+            .or_default()
+            .push(sp.person_id);
+    }
+    let mut parent_children: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for ch in children
+        .iter()
+        .filter(|c| live_persons.contains(&c.person_id))
+    {
+        for &parent_id in family_parents.get(&ch.family_id).into_iter().flatten() {
+            parent_children
+                .entry(parent_id)
+                .or_default()
+                .push(ch.person_id);
+        }
+    }
+
+    let mut closure = HashMap::new();
+    for &ancestor_id in parent_children.keys() {
+        // Breadth-first, so the first depth recorded is the shortest.
+        let mut frontier = vec![ancestor_id];
+        let mut depth = 0;
+        while !frontier.is_empty() {
+            depth += 1;
+            let mut next = Vec::new();
+            for id in frontier {
+                for &child_id in parent_children.get(&id).into_iter().flatten() {
+                    if child_id != ancestor_id && !closure.contains_key(&(ancestor_id, child_id)) {
+                        closure.insert((ancestor_id, child_id), depth);
+                        next.push(child_id);
+                    }
+                }
+            }
+            frontier = next;
+        }
+    }
+    closure
 }
 
 fn into_domain(m: person_ancestry::Model) -> PersonAncestry {
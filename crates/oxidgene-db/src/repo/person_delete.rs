@@ -0,0 +1,187 @@
+//! Deleting many persons at once, e.g. to clean up after a bad import: the
+//! persons are soft-deleted in one transaction, each id reported as
+//! deleted or skipped with the reason. Locked persons are always skipped.
+//!
+//! A person still linked to a family is skipped unless the caller asks to
+//! cascade: their spouse and child links are then removed, and families
+//! left without any member are soft-deleted with them.
+
+use std::collections::HashSet;
+
+use chrono::Utc;
+use oxidgene_core::error::OxidGeneError;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{QueryFilter, TransactionTrait};
+use uuid::Uuid;
+
+use super::PersonAncestryRepo;
+use crate::entities::{family, family_child, family_spouse, person};
+
+/// Why [`PersonDeleteRepo::delete_many`] left a person alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeleteSkipReason {
+    /// Not a live person of the tree.
+    NotFound,
+    /// A spouse or child of a family, and the delete does not cascade.
+    FamilyMember,
+    /// Locked against edits (see `Person::locked`).
+    Locked,
+}
+
+impl DeleteSkipReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::FamilyMember => "family_member",
+            Self::Locked => "locked",
+        }
+    }
+}
+
+/// What [`PersonDeleteRepo::delete_many`] did.
+#[derive(Debug, Clone, Default)]
+pub struct BulkDeleted {
+    /// Deleted persons, in request order.
+    pub deleted: Vec<Uuid>,
+    /// Persons left alone, in request order.
+    pub skipped: Vec<(Uuid, DeleteSkipReason)>,
+    /// Families deleted because the cascade left them without members.
+    pub families_deleted: Vec<Uuid>,
+}
+
+pub struct PersonDeleteRepo;
+
+impl PersonDeleteRepo {
+    /// Soft-delete the persons of `tree_id` among `person_ids`, in one
+    /// transaction. Repeated ids count once.
+    ///
+    /// Family members are skipped unless `cascade` is set, in which case
+    /// they are detached from their families first (see the module docs).
+    pub async fn delete_many(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        person_ids: &[Uuid],
+        cascade: bool,
+    ) -> Result<BulkDeleted, OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        let mut seen = HashSet::new();
+        let ids: Vec<Uuid> = person_ids
+            .iter()
+            .copied()
+            .filter(|id| seen.insert(*id))
+            .collect();
+
+        let txn = db.begin().await.map_err(db_err)?;
+        let (locked, live): (Vec<person::Model>, Vec<person::Model>) = person::Entity::find()
+            .filter(person::Column::Id.is_in(ids.iter().copied()))
+            .filter(person::Column::TreeId.eq(tree_id))
+            .filter(person::Column::DeletedAt.is_null())
+            .all(&txn)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .partition(|p| p.locked);
+        let locked: HashSet<Uuid> = locked.into_iter().map(|p| p.id).collect();
+        let live: HashSet<Uuid> = live.into_iter().map(|p| p.id).collect();
+
+        let spouse_links = family_spouse::Entity::find()
+            .filter(family_spouse::Column::PersonId.is_in(live.iter().copied()))
+            .all(&txn)
+            .await
+            .map_err(db_err)?;
+        let child_links = family_child::Entity::find()
+            .filter(family_child::Column::PersonId.is_in(live.iter().copied()))
+            .all(&txn)
+            .await
+            .map_err(db_err)?;
+        let members: HashSet<Uuid> = spouse_links
+            .iter()
+            .map(|s| s.person_id)
+            .chain(child_links.iter().map(|c| c.person_id))
+            .collect();
+
+        let mut result = BulkDeleted::default();
+        for id in ids {
+            if locked.contains(&id) {
+                result.skipped.push((id, DeleteSkipReason::Locked));
+            } else if !live.contains(&id) {
+                result.skipped.push((id, DeleteSkipReason::NotFound));
+            } else if members.contains(&id) && !cascade {
+                result.skipped.push((id, DeleteSkipReason::FamilyMember));
+            } else {
+                result.deleted.push(id);
+            }
+        }
+        if result.deleted.is_empty() {
+            txn.commit().await.map_err(db_err)?;
+            return Ok(result);
+        }
+
+        if cascade {
+            // Every unlocked live person requested is deleted, so the links
+            // found above are exactly theirs.
+            let mut touched: Vec<Uuid> = spouse_links
+                .iter()
+                .map(|s| s.family_id)
+                .chain(child_links.iter().map(|c| c.family_id))
+                .collect();
+            touched.sort();
+            touched.dedup();
+            family_spouse::Entity::delete_many()
+                .filter(family_spouse::Column::PersonId.is_in(result.deleted.iter().copied()))
+                .exec(&txn)
+                .await
+                .map_err(db_err)?;
+            family_child::Entity::delete_many()
+                .filter(family_child::Column::PersonId.is_in(result.deleted.iter().copied()))
+                .exec(&txn)
+                .await
+                .map_err(db_err)?;
+
+            let still_used: HashSet<Uuid> = family_spouse::Entity::find()
+                .filter(family_spouse::Column::FamilyId.is_in(touched.iter().copied()))
+                .all(&txn)
+                .await
+                .map_err(db_err)?
+                .into_iter()
+                .map(|s| s.family_id)
+                .chain(
+                    family_child::Entity::find()
+                        .filter(family_child::Column::FamilyId.is_in(touched.iter().copied()))
+                        .all(&txn)
+                        .await
+                        .map_err(db_err)?
+                        .into_iter()
+                        .map(|c| c.family_id),
+                )
+                .collect();
+            let emptied = touched.into_iter().filter(|fid| !still_used.contains(fid));
+            result.families_deleted = family::Entity::find()
+                .filter(family::Column::Id.is_in(emptied))
+                .filter(family::Column::DeletedAt.is_null())
+                .all(&txn)
+                .await
+                .map_err(db_err)?
+                .into_iter()
+                .map(|f| f.id)
+                .collect();
+            family::Entity::update_many()
+                .col_expr(family::Column::DeletedAt, Expr::value(Some(Utc::now())))
+                .filter(family::Column::Id.is_in(result.families_deleted.iter().copied()))
+                .exec(&txn)
+                .await
+                .map_err(db_err)?;
+        }
+
+        person::Entity::update_many()
+            .col_expr(person::Column::DeletedAt, Expr::value(Some(Utc::now())))
+            .filter(person::Column::Id.is_in(result.deleted.iter().copied()))
+            .exec(&txn)
+            .await
+            .map_err(db_err)?;
+        PersonAncestryRepo::refresh(&txn, tree_id, &result.deleted).await?;
+        txn.commit().await.map_err(db_err)?;
+        Ok(result)
+    }
+}
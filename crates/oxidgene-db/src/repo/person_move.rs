@@ -0,0 +1,410 @@
+//! Moving a person to another tree: the person and the records they own —
+//! names, events, notes, citations, tags, research tasks and external ids —
+//! change tree in one transaction, so a failure leaves both trees as they were.
+//!
+//! Links to records of the source tree cannot follow: family links, event
+//! participants and media links are dropped, each with a warning. Places
+//! and sources are tree-scoped, so the moved events and citations are
+//! pointed at the target tree's place or source of the same name, created
+//! when there is none.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::types::Person;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    Condition, DatabaseTransaction, IntoActiveModel, QueryFilter, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+use super::PersonAncestryRepo;
+use super::person::into_domain;
+use crate::entities::{
+    citation, event, event_participant, external_id, family_child, family_spouse, media_link, note,
+    person, person_ancestry, person_tag, place, research_task, source, tree,
+};
+
+/// What [`PersonMoveRepo::move_person`] did.
+#[derive(Debug, Clone)]
+pub struct MovedPerson {
+    /// The person, now in the target tree.
+    pub person: Person,
+    /// One entry per link to the source tree that was dropped.
+    pub warnings: Vec<String>,
+}
+
+pub struct PersonMoveRepo;
+
+impl PersonMoveRepo {
+    /// Move `person_id` from `source_tree_id` to `target_tree_id`.
+    ///
+    /// The person is detached from every family of the source tree (the
+    /// source tree's ancestry closure is updated accordingly), and stops
+    /// being its SOSA root if they were. Their events' witnesses, the
+    /// events they witness and their media links stay behind.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OxidGeneError::NotFound`] when the person is not a live
+    /// person of the source tree or the target tree does not exist,
+    /// [`OxidGeneError::Locked`] when the person is locked, and
+    /// [`OxidGeneError::Validation`] when both trees are the same.
+    pub async fn move_person(
+        db: &DatabaseConnection,
+        source_tree_id: Uuid,
+        person_id: Uuid,
+        target_tree_id: Uuid,
+    ) -> Result<MovedPerson, OxidGeneError> {
+        if source_tree_id == target_tree_id {
+            return Err(OxidGeneError::Validation(format!(
+                "person {person_id} is already in tree {target_tree_id}"
+            )));
+        }
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        let txn = db.begin().await.map_err(db_err)?;
+
+        let moved = person::Entity::find_by_id(person_id)
+            .filter(person::Column::TreeId.eq(source_tree_id))
+            .filter(person::Column::DeletedAt.is_null())
+            .one(&txn)
+            .await
+            .map_err(db_err)?
+            .ok_or(OxidGeneError::NotFound {
+                entity: "Person",
+                id: person_id,
+            })?;
+        if moved.locked {
+            return Err(OxidGeneError::Locked {
+                entity: "Person",
+                id: person_id,
+            });
+        }
+        tree::Entity::find_by_id(target_tree_id)
+            .filter(tree::Column::DeletedAt.is_null())
+            .one(&txn)
+            .await
+            .map_err(db_err)?
+            .ok_or(OxidGeneError::NotFound {
+                entity: "Tree",
+                id: target_tree_id,
+            })?;
+
+        let mut warnings = Vec::new();
+        detach_families(&txn, person_id, &mut warnings).await?;
+        // Only the person's parent links changed, and with them the
+        // ancestries of their descendants.
+        PersonAncestryRepo::refresh(&txn, source_tree_id, &[person_id]).await?;
+        person_ancestry::Entity::update_many()
+            .col_expr(person_ancestry::Column::TreeId, Expr::value(target_tree_id))
+            .filter(person_ancestry::Column::AncestorId.eq(person_id))
+            .filter(person_ancestry::Column::DescendantId.eq(person_id))
+            .exec(&txn)
+            .await
+            .map_err(db_err)?;
+
+        let sosa_tree = tree::Entity::find_by_id(source_tree_id)
+            .filter(tree::Column::SosaRootPersonId.eq(person_id))
+            .one(&txn)
+            .await
+            .map_err(db_err)?;
+        if let Some(source_tree) = sosa_tree {
+            let mut active = source_tree.into_active_model();
+            active.sosa_root_person_id = Set(None);
+            active.update(&txn).await.map_err(db_err)?;
+            warnings.push(format!(
+                "person {person_id} was the SOSA root of tree {source_tree_id}, which now has none"
+            ));
+        }
+
+        // The person's own events, deleted ones included so that nothing
+        // of theirs is left in the source tree.
+        let events = event::Entity::find()
+            .filter(event::Column::PersonId.eq(person_id))
+            .all(&txn)
+            .await
+            .map_err(db_err)?;
+        let event_ids: Vec<Uuid> = events.iter().map(|e| e.id).collect();
+        drop_foreign_participants(&txn, person_id, &event_ids, &mut warnings).await?;
+
+        let mut places = HashMap::new();
+        for evt in events {
+            let place_id = target_place(&txn, target_tree_id, evt.place_id, &mut places).await?;
+            let place_id_to =
+                target_place(&txn, target_tree_id, evt.place_id_to, &mut places).await?;
+            let mut active = evt.into_active_model();
+            active.tree_id = Set(target_tree_id);
+            active.place_id = Set(place_id);
+            active.place_id_to = Set(place_id_to);
+            active.update(&txn).await.map_err(db_err)?;
+        }
+
+        let owned = Condition::any()
+            .add(note::Column::PersonId.eq(person_id))
+            .add(note::Column::EventId.is_in(event_ids.clone()));
+        note::Entity::update_many()
+            .col_expr(note::Column::TreeId, Expr::value(target_tree_id))
+            .filter(owned)
+            .exec(&txn)
+            .await
+            .map_err(db_err)?;
+
+        let citations = citation::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(citation::Column::PersonId.eq(person_id))
+                    .add(citation::Column::EventId.is_in(event_ids.clone())),
+            )
+            .all(&txn)
+            .await
+            .map_err(db_err)?;
+        let mut sources = HashMap::new();
+        for cit in citations {
+            let source_id =
+                target_source(&txn, target_tree_id, cit.source_id, &mut sources).await?;
+            let mut active = cit.into_active_model();
+            active.source_id = Set(source_id);
+            active.updated_at = Set(Utc::now());
+            active.update(&txn).await.map_err(db_err)?;
+        }
+
+        let links = media_link::Entity::find()
+            .filter(
+                Condition::any()
+                    .add(media_link::Column::PersonId.eq(person_id))
+                    .add(media_link::Column::EventId.is_in(event_ids.clone())),
+            )
+            .all(&txn)
+            .await
+            .map_err(db_err)?;
+        for link in links {
+            warnings.push(format!(
+                "unlinked media {} from person {person_id}: it stays in tree {source_tree_id}",
+                link.media_id
+            ));
+            media_link::Entity::delete_by_id(link.id)
+                .exec(&txn)
+                .await
+                .map_err(db_err)?;
+        }
+
+        person_tag::Entity::update_many()
+            .col_expr(person_tag::Column::TreeId, Expr::value(target_tree_id))
+            .filter(person_tag::Column::PersonId.eq(person_id))
+            .exec(&txn)
+            .await
+            .map_err(db_err)?;
+        research_task::Entity::update_many()
+            .col_expr(research_task::Column::TreeId, Expr::value(target_tree_id))
+            .filter(research_task::Column::PersonId.eq(person_id))
+            .exec(&txn)
+            .await
+            .map_err(db_err)?;
+        external_id::Entity::update_many()
+            .col_expr(external_id::Column::TreeId, Expr::value(target_tree_id))
+            .filter(external_id::Column::PersonId.eq(person_id))
+            .exec(&txn)
+            .await
+            .map_err(db_err)?;
+
+        let version = moved.version;
+        let mut active = moved.into_active_model();
+        active.tree_id = Set(target_tree_id);
+        active.updated_at = Set(Utc::now());
+        active.version = Set(version + 1);
+        let person = active.update(&txn).await.map_err(db_err)?;
+
+        txn.commit().await.map_err(db_err)?;
+        Ok(MovedPerson {
+            person: into_domain(person),
+            warnings,
+        })
+    }
+}
+
+/// Remove the person from every family, as a spouse or as a child.
+async fn detach_families(
+    txn: &DatabaseTransaction,
+    person_id: Uuid,
+    warnings: &mut Vec<String>,
+) -> Result<(), OxidGeneError> {
+    let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+    let spouse_links = family_spouse::Entity::find()
+        .filter(family_spouse::Column::PersonId.eq(person_id))
+        .all(txn)
+        .await
+        .map_err(db_err)?;
+    for link in spouse_links {
+        warnings.push(format!(
+            "detached person {person_id} from family {} (spouse)",
+            link.family_id
+        ));
+        family_spouse::Entity::delete_by_id(link.id)
+            .exec(txn)
+            .await
+            .map_err(db_err)?;
+    }
+    let child_links = family_child::Entity::find()
+        .filter(family_child::Column::PersonId.eq(person_id))
+        .all(txn)
+        .await
+        .map_err(db_err)?;
+    for link in child_links {
+        warnings.push(format!(
+            "detached person {person_id} from family {} (child)",
+            link.family_id
+        ));
+        family_child::Entity::delete_by_id(link.id)
+            .exec(txn)
+            .await
+            .map_err(db_err)?;
+    }
+    Ok(())
+}
+
+/// Remove the participants who are not the person from the person's
+/// events, and the person from the events of others.
+async fn drop_foreign_participants(
+    txn: &DatabaseTransaction,
+    person_id: Uuid,
+    event_ids: &[Uuid],
+    warnings: &mut Vec<String>,
+) -> Result<(), OxidGeneError> {
+    let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+    let foreign = Condition::any()
+        .add(
+            Condition::all()
+                .add(event_participant::Column::EventId.is_in(event_ids.iter().copied()))
+                .add(event_participant::Column::PersonId.ne(person_id)),
+        )
+        .add(
+            Condition::all()
+                .add(event_participant::Column::PersonId.eq(person_id))
+                .add(event_participant::Column::EventId.is_not_in(event_ids.iter().copied())),
+        );
+    let participants = event_participant::Entity::find()
+        .filter(foreign)
+        .all(txn)
+        .await
+        .map_err(db_err)?;
+    for participant in participants {
+        warnings.push(format!(
+            "removed person {} from event {}: the person and the event are now in different trees",
+            participant.person_id, participant.event_id
+        ));
+        event_participant::Entity::delete_by_id(participant.id)
+            .exec(txn)
+            .await
+            .map_err(db_err)?;
+    }
+    Ok(())
+}
+
+/// The target tree's counterpart of a source tree place: the place of the
+/// same name, or a copy. `mapped` remembers the places already resolved.
+async fn target_place(
+    txn: &DatabaseTransaction,
+    target_tree_id: Uuid,
+    place_id: Option<Uuid>,
+    mapped: &mut HashMap<Uuid, Uuid>,
+) -> Result<Option<Uuid>, OxidGeneError> {
+    let Some(place_id) = place_id else {
+        return Ok(None);
+    };
+    if let Some(&target_id) = mapped.get(&place_id) {
+        return Ok(Some(target_id));
+    }
+    let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+    let Some(original) = place::Entity::find_by_id(place_id)
+        .one(txn)
+        .await
+        .map_err(db_err)?
+    else {
+        return Ok(None);
+    };
+    let existing = place::Entity::find()
+        .filter(place::Column::TreeId.eq(target_tree_id))
+        .filter(place::Column::Name.eq(original.name.clone()))
+        .one(txn)
+        .await
+        .map_err(db_err)?;
+    let target_id = match existing {
+        Some(existing) => existing.id,
+        None => {
+            let now = Utc::now();
+            place::ActiveModel {
+                id: Set(Uuid::now_v7()),
+                tree_id: Set(target_tree_id),
+                name: Set(original.name),
+                latitude: Set(original.latitude),
+                longitude: Set(original.longitude),
+                created_at: Set(now),
+                updated_at: Set(now),
+                version: Set(1),
+            }
+            .insert(txn)
+            .await
+            .map_err(db_err)?
+            .id
+        }
+    };
+    mapped.insert(place_id, target_id);
+    Ok(Some(target_id))
+}
+
+/// The target tree's counterpart of a source tree source: the live source
+/// with the same title, or a copy. `mapped` remembers the sources already
+/// resolved.
+async fn target_source(
+    txn: &DatabaseTransaction,
+    target_tree_id: Uuid,
+    source_id: Uuid,
+    mapped: &mut HashMap<Uuid, Uuid>,
+) -> Result<Uuid, OxidGeneError> {
+    if let Some(&target_id) = mapped.get(&source_id) {
+        return Ok(target_id);
+    }
+    let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+    let original = source::Entity::find_by_id(source_id)
+        .one(txn)
+        .await
+        .map_err(db_err)?
+        .ok_or(OxidGeneError::NotFound {
+            entity: "Source",
+            id: source_id,
+        })?;
+    let existing = source::Entity::find()
+        .filter(source::Column::TreeId.eq(target_tree_id))
+        .filter(source::Column::Title.eq(original.title.clone()))
+        .filter(source::Column::DeletedAt.is_null())
+        .one(txn)
+        .await
+        .map_err(db_err)?;
+    let target_id = match existing {
+        Some(existing) => existing.id,
+        None => {
+            let now = Utc::now();
+            source::ActiveModel {
+                id: Set(Uuid::now_v7()),
+                tree_id: Set(target_tree_id),
+                title: Set(original.title),
+                author: Set(original.author),
+                publisher: Set(original.publisher),
+                abbreviation: Set(original.abbreviation),
+                repository_name: Set(original.repository_name),
+                created_at: Set(now),
+                updated_at: Set(now),
+                version: Set(1),
+                deleted_at: Set(None),
+            }
+            .insert(txn)
+            .await
+            .map_err(db_err)?
+            .id
+        }
+    };
+    mapped.insert(source_id, target_id);
+    Ok(target_id)
+}
@@ -1,15 +1,45 @@
 //! Repository for `PersonName` entities (CRUD, no soft delete, scoped by person_id).
 
+use std::collections::BTreeSet;
+
 use chrono::Utc;
 use oxidgene_core::enums::NameType;
 use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::fuzzy::{given_phonetic, surname_phonetic};
+use oxidgene_core::text::{NameCase, normalize_name};
 use oxidgene_core::types::PersonName;
 use sea_orm::entity::prelude::*;
-use sea_orm::{ActiveModelTrait, IntoActiveModel, QueryFilter, Set};
+use sea_orm::{
+    ActiveModelTrait, Condition, ConnectionTrait, DbBackend, IntoActiveModel, QueryFilter,
+    QueryOrder, QuerySelect, Set, Statement, TransactionTrait,
+};
 use uuid::Uuid;
 
 use crate::entities::person_name::{self, ActiveModel, Column, Entity};
-use crate::entities::sea_enums;
+use crate::entities::{person, sea_enums};
+use crate::repo::PersonRepo;
+use crate::repo::transaction::with_transaction;
+
+/// A name whose capitalization [`PersonNameRepo::normalize_case`] changed
+/// — or, in a dry run, would change.
+#[derive(Debug, Clone)]
+pub struct RecasedName {
+    pub id: Uuid,
+    pub person_id: Uuid,
+    pub given_names: Option<String>,
+    pub surname: Option<String>,
+    pub new_given_names: Option<String>,
+    pub new_surname: Option<String>,
+}
+
+/// Outcome of [`PersonNameRepo::normalize_case`].
+#[derive(Debug, Clone, Default)]
+pub struct NameCaseNormalization {
+    pub changes: Vec<RecasedName>,
+    /// Locked persons with names that would have changed, left as they
+    /// are; in id order.
+    pub skipped_locked: Vec<Uuid>,
+}
 
 /// Repository for person name operations.
 pub struct PersonNameRepo;
@@ -54,10 +84,11 @@ impl PersonNameRepo {
             })
     }
 
-    /// Create a new person name.
+    /// Create a new person name. Fails with [`OxidGeneError::Locked`] if the
+    /// person is locked.
     #[allow(clippy::too_many_arguments)]
     pub async fn create(
-        db: &DatabaseConnection,
+        db: &impl ConnectionTrait,
         id: Uuid,
         person_id: Uuid,
         name_type: NameType,
@@ -68,11 +99,16 @@ impl PersonNameRepo {
         nickname: Option<String>,
         is_primary: bool,
     ) -> Result<PersonName, OxidGeneError> {
+        PersonRepo::ensure_unlocked(db, person_id).await?;
         let now = Utc::now();
         let model = person_name::ActiveModel {
             id: Set(id),
             person_id: Set(person_id),
             name_type: Set(sea_enums::NameType::from(name_type)),
+            surname_phonetic: Set(surname.as_deref().and_then(surname_phonetic)),
+            given_phonetic: Set(given_names.as_deref().and_then(given_phonetic)),
+            surname_norm: Set(surname.as_deref().and_then(normalize_name)),
+            given_norm: Set(given_names.as_deref().and_then(normalize_name)),
             given_names: Set(given_names),
             surname: Set(surname),
             prefix: Set(prefix),
@@ -86,10 +122,12 @@ impl PersonNameRepo {
             .insert(db)
             .await
             .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        PersonRepo::refresh_display_names(db, &[person_id]).await?;
         Ok(into_domain(result))
     }
 
-    /// Update a person name.
+    /// Update a person name. Fails with [`OxidGeneError::Locked`] if its
+    /// person is locked.
     #[allow(clippy::too_many_arguments)]
     pub async fn update(
         db: &DatabaseConnection,
@@ -110,15 +148,20 @@ impl PersonNameRepo {
                 entity: "PersonName",
                 id,
             })?;
+        PersonRepo::ensure_unlocked(db, existing.person_id).await?;
 
         let mut active: ActiveModel = existing.into_active_model();
         if let Some(name_type) = name_type {
             active.name_type = Set(sea_enums::NameType::from(name_type));
         }
         if let Some(given_names) = given_names {
+            active.given_phonetic = Set(given_names.as_deref().and_then(given_phonetic));
+            active.given_norm = Set(given_names.as_deref().and_then(normalize_name));
             active.given_names = Set(given_names);
         }
         if let Some(surname) = surname {
+            active.surname_phonetic = Set(surname.as_deref().and_then(surname_phonetic));
+            active.surname_norm = Set(surname.as_deref().and_then(normalize_name));
             active.surname = Set(surname);
         }
         if let Some(prefix) = prefix {
@@ -139,26 +182,224 @@ impl PersonNameRepo {
             .update(db)
             .await
             .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        PersonRepo::refresh_display_names(db, &[result.person_id]).await?;
         Ok(into_domain(result))
     }
 
-    /// Hard-delete a person name.
+    /// Hard-delete a person name. Fails with [`OxidGeneError::Locked`] if its
+    /// person is locked.
     pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<(), OxidGeneError> {
-        let result = Entity::delete_by_id(id)
+        let name = Self::get(db, id).await?;
+        PersonRepo::ensure_unlocked(db, name.person_id).await?;
+        Entity::delete_by_id(id)
             .exec(db)
             .await
             .map_err(|e| OxidGeneError::Database(e.to_string()))?;
-        if result.rows_affected == 0 {
-            return Err(OxidGeneError::NotFound {
-                entity: "PersonName",
-                id,
-            });
+        PersonRepo::refresh_display_names(db, &[name.person_id]).await
+    }
+
+    /// Pairs of live persons of a tree who share a name by sound: both the
+    /// surname and the first given name of one of their names have the same
+    /// Soundex codes (see [`oxidgene_core::fuzzy::soundex`]), or their
+    /// surname and given names are the same once case- and accent-folded
+    /// (see [`oxidgene_core::text::normalize`]), which also pairs names
+    /// Soundex cannot encode. Each pair is returned once, lower id first,
+    /// ordered by id; at most `limit` of them.
+    ///
+    /// The codes and folded names are stored with the names, so this is an
+    /// indexed equality join rather than a pass over every name of the tree.
+    pub async fn phonetic_duplicates(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        limit: u64,
+    ) -> Result<Vec<(Uuid, Uuid)>, OxidGeneError> {
+        let backend = db.get_database_backend();
+        let (tree, max) = match backend {
+            DbBackend::Sqlite => ("?1", "?2"),
+            _ => ("$1", "$2"),
+        };
+        let sql = format!(
+            r#"
+            SELECT DISTINCT a.person_id AS a_id, b.person_id AS b_id
+            FROM person_name a
+            JOIN person_name b
+              ON b.person_id > a.person_id
+             AND ((b.surname_phonetic = a.surname_phonetic
+                   AND b.given_phonetic = a.given_phonetic)
+               OR (b.surname_norm = a.surname_norm
+                   AND b.given_norm = a.given_norm))
+            JOIN person pa ON pa.id = a.person_id
+            JOIN person pb ON pb.id = b.person_id
+            WHERE pa.tree_id = {tree} AND pb.tree_id = {tree}
+              AND pa.deleted_at IS NULL AND pb.deleted_at IS NULL
+            ORDER BY a_id, b_id
+            LIMIT {max}
+            "#
+        );
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                backend,
+                sql,
+                [tree_id.into(), (limit as i64).into()],
+            ))
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        rows.iter()
+            .map(|row| {
+                let a: Uuid = row.try_get("", "a_id")?;
+                let b: Uuid = row.try_get("", "b_id")?;
+                Ok((a, b))
+            })
+            .collect::<Result<_, DbErr>>()
+            .map_err(|e| OxidGeneError::Database(e.to_string()))
+    }
+
+    /// Rewrite the capitalization of the given names and surname of every
+    /// name of the tree's live persons, with `given` and `surname` (see
+    /// [`NameCase::apply`]), in a single transaction. The names of locked
+    /// persons are left alone, their persons reported in `skipped_locked`.
+    /// With `dry_run`, nothing is written: the changes are only reported.
+    /// Names are visited by id.
+    pub async fn normalize_case(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        given: NameCase,
+        surname: NameCase,
+        preserve_particles: bool,
+        dry_run: bool,
+    ) -> Result<NameCaseNormalization, OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        with_transaction(db, |txn| {
+            Box::pin(async move {
+                let rows = Entity::find()
+                    .find_also_related(person::Entity)
+                    .filter(person::Column::TreeId.eq(tree_id))
+                    .filter(person::Column::DeletedAt.is_null())
+                    .order_by_asc(Column::Id)
+                    .all(txn)
+                    .await
+                    .map_err(db_err)?;
+
+                let mut changes = Vec::new();
+                let mut changed_persons = BTreeSet::new();
+                let mut skipped_locked = BTreeSet::new();
+                for (model, owner) in rows {
+                    let new_given_names = model
+                        .given_names
+                        .as_deref()
+                        .map(|g| given.apply(g, preserve_particles));
+                    let new_surname = model
+                        .surname
+                        .as_deref()
+                        .map(|s| surname.apply(s, preserve_particles));
+                    if new_given_names == model.given_names && new_surname == model.surname {
+                        continue;
+                    }
+                    if owner.is_some_and(|p| p.locked) {
+                        skipped_locked.insert(model.person_id);
+                        continue;
+                    }
+                    let change = RecasedName {
+                        id: model.id,
+                        person_id: model.person_id,
+                        given_names: model.given_names.clone(),
+                        surname: model.surname.clone(),
+                        new_given_names: new_given_names.clone(),
+                        new_surname: new_surname.clone(),
+                    };
+                    if !dry_run {
+                        let mut active: ActiveModel = model.into_active_model();
+                        active.given_phonetic =
+                            Set(new_given_names.as_deref().and_then(given_phonetic));
+                        active.given_norm =
+                            Set(new_given_names.as_deref().and_then(normalize_name));
+                        active.given_names = Set(new_given_names);
+                        active.surname_phonetic =
+                            Set(new_surname.as_deref().and_then(surname_phonetic));
+                        active.surname_norm = Set(new_surname.as_deref().and_then(normalize_name));
+                        active.surname = Set(new_surname);
+                        active.updated_at = Set(Utc::now());
+                        active.update(txn).await.map_err(db_err)?;
+                    }
+                    changed_persons.insert(change.person_id);
+                    changes.push(change);
+                }
+                if !dry_run {
+                    let person_ids: Vec<Uuid> = changed_persons.into_iter().collect();
+                    PersonRepo::refresh_display_names(txn, &person_ids).await?;
+                }
+                Ok(NameCaseNormalization {
+                    changes,
+                    skipped_locked: skipped_locked.into_iter().collect(),
+                })
+            })
+        })
+        .await
+    }
+
+    /// Fill in the phonetic codes of every name that has a surname or given
+    /// names but no code for it, as [`Self::create`] computes them. Names
+    /// are walked by id, `chunk_size` at a time, each chunk in its own
+    /// transaction. Returns how many names were updated; running it again
+    /// only revisits the names without any letter to encode.
+    pub async fn backfill_phonetic(
+        db: &DatabaseConnection,
+        chunk_size: u64,
+    ) -> Result<u64, OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        let mut updated = 0;
+        let mut after: Option<Uuid> = None;
+        loop {
+            let mut query = Entity::find().filter(
+                Condition::any()
+                    .add(
+                        Condition::all()
+                            .add(Column::SurnamePhonetic.is_null())
+                            .add(Column::Surname.is_not_null()),
+                    )
+                    .add(
+                        Condition::all()
+                            .add(Column::GivenPhonetic.is_null())
+                            .add(Column::GivenNames.is_not_null()),
+                    ),
+            );
+            if let Some(after) = after {
+                query = query.filter(Column::Id.gt(after));
+            }
+            let chunk = query
+                .order_by_asc(Column::Id)
+                .limit(chunk_size.max(1))
+                .all(db)
+                .await
+                .map_err(db_err)?;
+            let Some(last) = chunk.last() else {
+                break;
+            };
+            after = Some(last.id);
+
+            let txn = db.begin().await.map_err(db_err)?;
+            for model in chunk {
+                let surname_code = model.surname.as_deref().and_then(surname_phonetic);
+                let given_code = model.given_names.as_deref().and_then(given_phonetic);
+                if surname_code == model.surname_phonetic && given_code == model.given_phonetic {
+                    continue;
+                }
+                Entity::update_many()
+                    .col_expr(Column::SurnamePhonetic, Expr::value(surname_code))
+                    .col_expr(Column::GivenPhonetic, Expr::value(given_code))
+                    .filter(Column::Id.eq(model.id))
+                    .exec(&txn)
+                    .await
+                    .map_err(db_err)?;
+                updated += 1;
+            }
+            txn.commit().await.map_err(db_err)?;
         }
-        Ok(())
+        Ok(updated)
     }
 }
 
-fn into_domain(m: person_name::Model) -> PersonName {
+pub(super) fn into_domain(m: person_name::Model) -> PersonName {
     PersonName {
         id: m.id,
         person_id: m.person_id,
@@ -4,12 +4,18 @@
 //! with per-word prefix queries (`"jean"* "dup"*`). On PostgreSQL the table
 //! is a plain table and matching falls back to per-word `LIKE` conditions.
 //!
-//! All searchable columns (`surname`, `given_names`, `maiden_name`) are
-//! pre-normalized (lowercase + accent-folded) by the caller via
-//! [`oxidgene_core::search::normalize_for_search`]; queries are normalized
-//! here, so both backends match identically.
+//! A person is found by any of their names: besides the primary name, the
+//! other names and nicknames are indexed as `other_names`, and a hit found
+//! through one of them reports it as [`PersonSearchEntry::matched_alias`].
+//!
+//! All searchable columns (`surname`, `given_names`, `maiden_name`,
+//! `other_names`) are pre-normalized (lowercase + accent-folded) by the caller via
+//! [`oxidgene_core::search::normalize_for_search`] (`other_names` here, from
+//! the aliases); queries are normalized here, so both backends match
+//! identically.
 
 use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::fuzzy::{bucket_letters, name_distance};
 use oxidgene_core::search::normalize_for_search;
 use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement, Value};
 use uuid::Uuid;
@@ -28,6 +34,8 @@ pub struct PersonSearchEntry {
     pub given_names: String,
     /// Normalized maiden name, if any.
     pub maiden_name: Option<String>,
+    /// The person's other names and nicknames, as displayed.
+    pub aliases: Vec<String>,
     pub birth_year: Option<String>,
     pub death_year: Option<String>,
     /// Sex as its lowercase string form (`male` / `female` / `unknown`).
@@ -37,6 +45,9 @@ pub struct PersonSearchEntry {
     pub birth_place: Option<String>,
     /// ISO date (`YYYY-MM-DD`) used for sorting, if known.
     pub date_sort: Option<String>,
+    /// On search hits: the alias the query matched, when the primary name
+    /// does not match it. Ignored on write.
+    pub matched_alias: Option<String>,
 }
 
 /// Paginated search hits plus the total match count.
@@ -46,10 +57,21 @@ pub struct PersonSearchPage {
     pub total_count: u64,
 }
 
+/// A fuzzy search hit with its edit distance to the query.
+#[derive(Debug, Clone)]
+pub struct FuzzySearchHit {
+    pub entry: PersonSearchEntry,
+    pub distance: usize,
+}
+
 const COLUMNS: &str = "person_id, tree_id, surname, given_names, maiden_name, \
-                       birth_year, death_year, sex, display_name, birth_place, date_sort";
+                       other_names, aliases, birth_year, death_year, sex, display_name, \
+                       birth_place, date_sort";
+
+/// Separator of the `aliases` column.
+const ALIAS_SEPARATOR: char = '\n';
 
-/// Maximum rows per INSERT batch (11 bind values per row, well under the
+/// Maximum rows per INSERT batch (13 bind values per row, well under the
 /// SQLite / PostgreSQL parameter limits).
 const INSERT_CHUNK: usize = 500;
 
@@ -144,6 +166,24 @@ impl PersonSearchRepo {
         limit: u64,
         offset: u64,
     ) -> Result<PersonSearchPage, OxidGeneError> {
+        Self::search_trees(db, &[tree_id], query, limit, offset).await
+    }
+
+    /// [`Self::search`] across several trees at once, sorted by name
+    /// whatever the tree.
+    pub async fn search_trees(
+        db: &DatabaseConnection,
+        tree_ids: &[Uuid],
+        query: &str,
+        limit: u64,
+        offset: u64,
+    ) -> Result<PersonSearchPage, OxidGeneError> {
+        if tree_ids.is_empty() {
+            return Ok(PersonSearchPage {
+                entries: Vec::new(),
+                total_count: 0,
+            });
+        }
         let backend = db.get_database_backend();
         let words: Vec<String> = normalize_for_search(query)
             .split_whitespace()
@@ -151,11 +191,11 @@ impl PersonSearchRepo {
             .collect();
 
         let stmt = if words.is_empty() {
-            Self::browse_statement(backend, tree_id, limit, offset)
+            Self::browse_statement(backend, tree_ids, limit, offset)
         } else {
             match backend {
-                DbBackend::Sqlite => Self::fts_statement(tree_id, &words, limit, offset),
-                _ => Self::like_statement(backend, tree_id, &words, limit, offset),
+                DbBackend::Sqlite => Self::fts_statement(tree_ids, &words, limit, offset),
+                _ => Self::like_statement(backend, tree_ids, &words, limit, offset),
             }
         };
 
@@ -171,7 +211,9 @@ impl PersonSearchRepo {
                 .try_get("", "total_count")
                 .map_err(|e| OxidGeneError::Database(e.to_string()))?;
             total_count = total.max(0) as u64;
-            entries.push(Self::row_to_entry(&row)?);
+            let mut entry = Self::row_to_entry(&row)?;
+            entry.matched_alias = matched_alias(&entry, &words, backend == DbBackend::Sqlite);
+            entries.push(entry);
         }
 
         Ok(PersonSearchPage {
@@ -180,74 +222,161 @@ impl PersonSearchRepo {
         })
     }
 
-    // ── Statement builders ──────────────────────────────────────────────
+    /// Typo-tolerant name search, closest matches first.
+    ///
+    /// Candidates are pre-filtered in SQL to persons whose surname, given
+    /// names or maiden name start with a letter in the same phonetic bucket
+    /// as one of the query words (see [`oxidgene_core::fuzzy::phonetic_bucket`]),
+    /// then scored with [`name_distance`] against each of their names.
+    /// Persons with aliases are always candidates, since the prefilter only
+    /// sees the first letter of a column. Hits further than `max_distance`
+    /// are dropped; ties are broken by name.
+    pub async fn fuzzy_search(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        name: &str,
+        max_distance: usize,
+        limit: usize,
+    ) -> Result<Vec<FuzzySearchHit>, OxidGeneError> {
+        let query = normalize_for_search(name);
+        let mut letters: Vec<char> = query
+            .split_whitespace()
+            .filter_map(|w| w.chars().next())
+            .flat_map(bucket_letters)
+            .collect();
+        letters.sort_unstable();
+        letters.dedup();
+        if letters.is_empty() {
+            return Ok(Vec::new());
+        }
 
-    fn browse_statement(backend: DbBackend, tree_id: Uuid, limit: u64, offset: u64) -> Statement {
-        let sql = match backend {
-            DbBackend::Sqlite => format!(
-                "SELECT {COLUMNS}, COUNT(*) OVER () AS total_count \
-                 FROM person_search_fts WHERE tree_id = ? \
-                 ORDER BY surname, given_names LIMIT ? OFFSET ?"
-            ),
-            _ => format!(
-                "SELECT {COLUMNS}, COUNT(*) OVER () AS total_count \
-                 FROM person_search_fts WHERE tree_id = $1 \
-                 ORDER BY surname, given_names LIMIT $2 OFFSET $3"
-            ),
+        let backend = db.get_database_backend();
+        let mut values: Vec<Value> = vec![Value::from(tree_id.to_string())];
+        let placeholders: Vec<String> = letters
+            .iter()
+            .map(|l| {
+                values.push(Value::from(l.to_string()));
+                match backend {
+                    DbBackend::Sqlite => "?".to_owned(),
+                    _ => format!("${}", values.len()),
+                }
+            })
+            .collect();
+        let set = placeholders.join(", ");
+        let tree_param = match backend {
+            DbBackend::Sqlite => "?",
+            _ => "$1",
         };
-        Statement::from_sql_and_values(
-            backend,
-            sql,
-            [
-                Value::from(tree_id.to_string()),
-                Value::from(limit as i64),
-                Value::from(offset as i64),
-            ],
-        )
+        let sql = format!(
+            "SELECT {COLUMNS} FROM person_search_fts \
+             WHERE tree_id = {tree_param} AND (substr(surname, 1, 1) IN ({set}) \
+             OR substr(given_names, 1, 1) IN ({set}) \
+             OR substr(COALESCE(maiden_name, ''), 1, 1) IN ({set}) \
+             OR other_names <> '')"
+        );
+        // The letter set is referenced three times; positional SQLite
+        // parameters need their values repeated.
+        if backend == DbBackend::Sqlite {
+            let letter_values = values[1..].to_vec();
+            values.extend(letter_values.iter().cloned());
+            values.extend(letter_values);
+        }
+
+        let rows = db
+            .query_all(Statement::from_sql_and_values(backend, sql, values))
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+
+        let mut hits = Vec::new();
+        for row in rows {
+            let mut entry = Self::row_to_entry(&row)?;
+            let full_name = format!(
+                "{} {} {}",
+                entry.given_names,
+                entry.surname,
+                entry.maiden_name.as_deref().unwrap_or_default()
+            );
+            let mut distance = name_distance(&query, &full_name);
+            for alias in &entry.aliases {
+                let alias_distance = name_distance(&query, &normalize_for_search(alias));
+                if alias_distance < distance {
+                    distance = alias_distance;
+                    entry.matched_alias = Some(alias.clone());
+                }
+            }
+            if distance <= max_distance {
+                hits.push(FuzzySearchHit { entry, distance });
+            }
+        }
+        hits.sort_by(|a, b| {
+            a.distance
+                .cmp(&b.distance)
+                .then_with(|| a.entry.surname.cmp(&b.entry.surname))
+                .then_with(|| a.entry.given_names.cmp(&b.entry.given_names))
+        });
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    // ── Statement builders ──────────────────────────────────────────────
+
+    fn browse_statement(
+        backend: DbBackend,
+        tree_ids: &[Uuid],
+        limit: u64,
+        offset: u64,
+    ) -> Statement {
+        let mut values = Vec::new();
+        let scope = tree_scope(backend, tree_ids, &mut values);
+        let limit_param = placeholder(backend, &mut values, Value::from(limit as i64));
+        let offset_param = placeholder(backend, &mut values, Value::from(offset as i64));
+        let sql = format!(
+            "SELECT {COLUMNS}, COUNT(*) OVER () AS total_count \
+             FROM person_search_fts WHERE {scope} \
+             ORDER BY surname, given_names LIMIT {limit_param} OFFSET {offset_param}"
+        );
+        Statement::from_sql_and_values(backend, sql, values)
     }
 
     /// SQLite FTS5: match every word as a prefix query across the indexed
-    /// columns (surname, given_names, maiden_name, birth_year, death_year).
-    fn fts_statement(tree_id: Uuid, words: &[String], limit: u64, offset: u64) -> Statement {
+    /// columns (surname, given_names, maiden_name, other_names, birth_year,
+    /// death_year).
+    fn fts_statement(tree_ids: &[Uuid], words: &[String], limit: u64, offset: u64) -> Statement {
         let match_expr = words
             .iter()
             .map(|w| format!("\"{}\"*", w.replace('"', "\"\"")))
             .collect::<Vec<_>>()
             .join(" ");
+        let mut values = vec![Value::from(match_expr)];
+        let scope = tree_scope(DbBackend::Sqlite, tree_ids, &mut values);
+        values.push(Value::from(limit as i64));
+        values.push(Value::from(offset as i64));
         let sql = format!(
             "SELECT {COLUMNS}, COUNT(*) OVER () AS total_count \
              FROM person_search_fts \
-             WHERE person_search_fts MATCH ? AND tree_id = ? \
+             WHERE person_search_fts MATCH ? AND {scope} \
              ORDER BY surname, given_names LIMIT ? OFFSET ?"
         );
-        Statement::from_sql_and_values(
-            DbBackend::Sqlite,
-            sql,
-            [
-                Value::from(match_expr),
-                Value::from(tree_id.to_string()),
-                Value::from(limit as i64),
-                Value::from(offset as i64),
-            ],
-        )
+        Statement::from_sql_and_values(DbBackend::Sqlite, sql, values)
     }
 
     /// PostgreSQL fallback: every word must appear (substring) in one of the
     /// searchable columns.
     fn like_statement(
         backend: DbBackend,
-        tree_id: Uuid,
+        tree_ids: &[Uuid],
         words: &[String],
         limit: u64,
         offset: u64,
     ) -> Statement {
-        let mut values: Vec<Value> = vec![Value::from(tree_id.to_string())];
+        let mut values: Vec<Value> = Vec::new();
+        let scope = tree_scope(backend, tree_ids, &mut values);
         let mut conditions = Vec::with_capacity(words.len());
         for word in words {
             let idx = values.len() + 1;
             conditions.push(format!(
                 "(surname LIKE ${idx} OR given_names LIKE ${idx} \
-                 OR COALESCE(maiden_name, '') LIKE ${idx} \
+                 OR COALESCE(maiden_name, '') LIKE ${idx} OR other_names LIKE ${idx} \
                  OR COALESCE(birth_year, '') LIKE ${idx} \
                  OR COALESCE(death_year, '') LIKE ${idx})"
             ));
@@ -261,7 +390,7 @@ impl PersonSearchRepo {
         let sql = format!(
             "SELECT {COLUMNS}, COUNT(*) OVER () AS total_count \
              FROM person_search_fts \
-             WHERE tree_id = $1 AND {} \
+             WHERE {scope} AND {} \
              ORDER BY surname, given_names LIMIT ${limit_idx} OFFSET ${offset_idx}",
             conditions.join(" AND ")
         );
@@ -308,14 +437,14 @@ impl PersonSearchRepo {
         let backend = db.get_database_backend();
 
         for chunk in entries.chunks(INSERT_CHUNK) {
-            let mut values: Vec<Value> = Vec::with_capacity(chunk.len() * 11);
+            let mut values: Vec<Value> = Vec::with_capacity(chunk.len() * 13);
             let mut rows = Vec::with_capacity(chunk.len());
             for entry in chunk {
                 let base = values.len();
                 let row = match backend {
-                    DbBackend::Sqlite => "(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)".to_owned(),
+                    DbBackend::Sqlite => "(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)".to_owned(),
                     _ => format!(
-                        "(${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${})",
+                        "(${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${}, ${})",
                         base + 1,
                         base + 2,
                         base + 3,
@@ -326,7 +455,9 @@ impl PersonSearchRepo {
                         base + 8,
                         base + 9,
                         base + 10,
-                        base + 11
+                        base + 11,
+                        base + 12,
+                        base + 13
                     ),
                 };
                 rows.push(row);
@@ -336,6 +467,8 @@ impl PersonSearchRepo {
                     Value::from(entry.surname.clone()),
                     Value::from(entry.given_names.clone()),
                     Value::from(entry.maiden_name.clone()),
+                    Value::from(normalize_for_search(&entry.aliases.join(" "))),
+                    Value::from(entry.aliases.join(&ALIAS_SEPARATOR.to_string())),
                     Value::from(entry.birth_year.clone()),
                     Value::from(entry.death_year.clone()),
                     Value::from(entry.sex.clone()),
@@ -374,12 +507,68 @@ impl PersonSearchRepo {
             surname: get_string("surname")?,
             given_names: get_string("given_names")?,
             maiden_name: get_opt("maiden_name")?,
+            aliases: get_string("aliases")?
+                .split(ALIAS_SEPARATOR)
+                .filter(|a| !a.is_empty())
+                .map(str::to_owned)
+                .collect(),
             birth_year: get_opt("birth_year")?,
             death_year: get_opt("death_year")?,
             sex: get_string("sex")?,
             display_name: get_string("display_name")?,
             birth_place: get_opt("birth_place")?,
             date_sort: get_opt("date_sort")?,
+            matched_alias: None,
         })
     }
 }
+
+/// Push `value` and return its placeholder.
+fn placeholder(backend: DbBackend, values: &mut Vec<Value>, value: Value) -> String {
+    values.push(value);
+    match backend {
+        DbBackend::Sqlite => "?".to_owned(),
+        _ => format!("${}", values.len()),
+    }
+}
+
+/// The `tree_id IN (...)` condition restricting a query to `tree_ids`.
+fn tree_scope(backend: DbBackend, tree_ids: &[Uuid], values: &mut Vec<Value>) -> String {
+    let placeholders: Vec<String> = tree_ids
+        .iter()
+        .map(|id| placeholder(backend, values, Value::from(id.to_string())))
+        .collect();
+    format!("tree_id IN ({})", placeholders.join(", "))
+}
+
+/// The alias a search hit was found through: the first one matching a
+/// query word that the primary name and the years do not. `prefix` mirrors
+/// the backend's matching — FTS5 prefix tokens, or `LIKE` substrings.
+fn matched_alias(entry: &PersonSearchEntry, words: &[String], prefix: bool) -> Option<String> {
+    let matches = |text: &str, word: &str| {
+        if prefix {
+            text.split_whitespace().any(|token| token.starts_with(word))
+        } else {
+            text.contains(word)
+        }
+    };
+    let primary = format!(
+        "{} {} {} {}",
+        entry.surname,
+        entry.given_names,
+        entry.birth_year.as_deref().unwrap_or_default(),
+        entry.death_year.as_deref().unwrap_or_default()
+    );
+    let unmatched: Vec<&String> = words.iter().filter(|w| !matches(&primary, w)).collect();
+    if unmatched.is_empty() {
+        return None;
+    }
+    entry
+        .aliases
+        .iter()
+        .find(|alias| {
+            let alias = normalize_for_search(alias);
+            unmatched.iter().any(|w| matches(&alias, w))
+        })
+        .cloned()
+}
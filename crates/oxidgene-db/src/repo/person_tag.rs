@@ -0,0 +1,142 @@
+//! Repository for `PersonTag` entities (tag/untag, per-tree listing and
+//! counts). Tags are hard-deleted; a person's tags go with the person.
+
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::types::PersonTag;
+use sea_orm::entity::prelude::*;
+use sea_orm::{QueryFilter, QueryOrder, QuerySelect, QueryTrait, Set};
+use uuid::Uuid;
+
+use crate::entities::person;
+use crate::entities::person_tag::{self, Column, Entity};
+
+/// A tag in use in a tree, with the number of persons carrying it.
+#[derive(Debug, Clone)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: i64,
+}
+
+/// Repository for person tag operations.
+pub struct PersonTagRepo;
+
+impl PersonTagRepo {
+    /// Tags of one person, alphabetically.
+    pub async fn list_by_person(
+        db: &DatabaseConnection,
+        person_id: Uuid,
+    ) -> Result<Vec<PersonTag>, OxidGeneError> {
+        let models = Entity::find()
+            .filter(Column::PersonId.eq(person_id))
+            .order_by_asc(Column::Tag)
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
+    /// Every tag of every live (not soft-deleted) person in a tree.
+    pub async fn list_by_tree(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+    ) -> Result<Vec<PersonTag>, OxidGeneError> {
+        let live = person::Entity::find()
+            .select_only()
+            .column(person::Column::Id)
+            .filter(person::Column::TreeId.eq(tree_id))
+            .filter(person::Column::DeletedAt.is_null())
+            .into_query();
+        let models = Entity::find()
+            .filter(Column::TreeId.eq(tree_id))
+            .filter(Column::PersonId.in_subquery(live))
+            .order_by_asc(Column::Tag)
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
+    /// Tags in use in a tree with the number of live persons carrying
+    /// each, alphabetically (case-insensitive).
+    pub async fn counts(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+    ) -> Result<Vec<TagCount>, OxidGeneError> {
+        let mut counts: BTreeMap<String, i64> = BTreeMap::new();
+        for tag in Self::list_by_tree(db, tree_id).await? {
+            *counts.entry(tag.tag).or_default() += 1;
+        }
+        let mut entries: Vec<TagCount> = counts
+            .into_iter()
+            .map(|(tag, count)| TagCount { tag, count })
+            .collect();
+        entries.sort_by_cached_key(|e| e.tag.to_lowercase());
+        Ok(entries)
+    }
+
+    /// Tag a person. Tagging a person with a tag they already carry
+    /// returns the existing tag.
+    pub async fn add(
+        db: &DatabaseConnection,
+        id: Uuid,
+        tree_id: Uuid,
+        person_id: Uuid,
+        tag: String,
+    ) -> Result<PersonTag, OxidGeneError> {
+        let existing = Entity::find()
+            .filter(Column::PersonId.eq(person_id))
+            .filter(Column::Tag.eq(tag.as_str()))
+            .one(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        if let Some(existing) = existing {
+            return Ok(into_domain(existing));
+        }
+        let model = person_tag::ActiveModel {
+            id: Set(id),
+            tree_id: Set(tree_id),
+            person_id: Set(person_id),
+            tag: Set(tag),
+            created_at: Set(Utc::now()),
+        };
+        let result = model
+            .insert(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(into_domain(result))
+    }
+
+    /// Remove a tag from a person.
+    pub async fn remove(
+        db: &DatabaseConnection,
+        person_id: Uuid,
+        tag: &str,
+    ) -> Result<(), OxidGeneError> {
+        let result = Entity::delete_many()
+            .filter(Column::PersonId.eq(person_id))
+            .filter(Column::Tag.eq(tag))
+            .exec(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        if result.rows_affected == 0 {
+            return Err(OxidGeneError::NotFound {
+                entity: "PersonTag",
+                id: person_id,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn into_domain(m: person_tag::Model) -> PersonTag {
+    PersonTag {
+        id: m.id,
+        tree_id: m.tree_id,
+        person_id: m.person_id,
+        tag: m.tag,
+        created_at: m.created_at,
+    }
+}
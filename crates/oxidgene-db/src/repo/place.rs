@@ -1,14 +1,86 @@
-//! Repository for `Place` entities (CRUD, no soft delete, search filter).
+//! Repository for `Place` entities (CRUD, no soft delete, search filter,
+//! duplicate merging).
+
+use std::collections::HashMap;
 
 use chrono::Utc;
 use oxidgene_core::error::OxidGeneError;
 use oxidgene_core::types::{Connection, Place};
 use sea_orm::entity::prelude::*;
-use sea_orm::{ActiveModelTrait, IntoActiveModel, QueryFilter, Set};
+use sea_orm::sea_query::{Expr, IntoCondition, IntoIden, LikeExpr, Query};
+use sea_orm::{
+    ActiveModelTrait, ConnectionTrait, FromQueryResult, IntoActiveModel, JoinType, Order,
+    QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
 use uuid::Uuid;
 
 use crate::entities::place::{self, ActiveModel, Column, Entity};
+use crate::entities::{event, media};
 use crate::repo::pagination::{PaginationParams, paginate};
+use crate::repo::transaction::with_transaction;
+use crate::repo::version;
+
+/// Two places whose coordinates differ by at most this many degrees on both
+/// axes (~11 m at the equator) are considered the same spot when merging
+/// duplicates.
+pub const DEDUPE_COORD_TOLERANCE: f64 = 1e-4;
+
+/// Normalize a place name for duplicate detection: trimmed, lowercased, with
+/// runs of inner whitespace collapsed to a single space — so
+/// `"  Paris,  France "` and `"paris, france"` compare equal.
+pub fn normalize_place_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Whether two coordinate pairs can belong to the same place: either side
+/// missing a coordinate is compatible with anything (imports often carry
+/// `MAP` on only some occurrences), otherwise both axes must agree within
+/// [`DEDUPE_COORD_TOLERANCE`].
+pub fn coordinates_match(a: (Option<f64>, Option<f64>), b: (Option<f64>, Option<f64>)) -> bool {
+    match (a, b) {
+        ((Some(lat_a), Some(lon_a)), (Some(lat_b), Some(lon_b))) => {
+            (lat_a - lat_b).abs() <= DEDUPE_COORD_TOLERANCE
+                && (lon_a - lon_b).abs() <= DEDUPE_COORD_TOLERANCE
+        }
+        _ => true,
+    }
+}
+
+/// A place with the number of live events held there.
+#[derive(Debug, Clone)]
+pub struct PlaceUsage {
+    pub place: Place,
+    pub event_count: i64,
+}
+
+/// Row of the autocomplete query: a place plus its event count.
+#[derive(Debug, FromQueryResult)]
+struct PlaceUsageRow {
+    id: Uuid,
+    tree_id: Uuid,
+    name: String,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    created_at: DateTimeUtc,
+    updated_at: DateTimeUtc,
+    version: i32,
+    event_count: i64,
+}
+
+/// Escape `LIKE` wildcards (and the escape character itself) in `s`.
+fn escape_like(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
 
 /// Repository for place CRUD operations.
 pub struct PlaceRepo;
@@ -30,6 +102,60 @@ impl PlaceRepo {
         paginate(db, query, Column::Id, params, |m| (m.id, into_domain(m))).await
     }
 
+    /// Places whose name starts with `prefix`, most used first (by the
+    /// number of live events held there), then by name.
+    ///
+    /// Matching is a `LIKE 'prefix%'` on the `(tree_id, name)` index, so it
+    /// follows the database's `LIKE` case rules (case-insensitive for ASCII
+    /// on SQLite, case-sensitive on PostgreSQL).
+    pub async fn autocomplete(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        prefix: &str,
+        limit: u64,
+    ) -> Result<Vec<PlaceUsage>, OxidGeneError> {
+        let event_count = Expr::col((event::Entity, event::Column::Id)).count();
+        let rows = Entity::find()
+            .column_as(event_count.clone(), "event_count")
+            .join(
+                JoinType::LeftJoin,
+                place::Relation::Event.def().on_condition(|_, right| {
+                    Expr::col((right, event::Column::DeletedAt))
+                        .is_null()
+                        .into_condition()
+                }),
+            )
+            .filter(Column::TreeId.eq(tree_id))
+            .filter(
+                Expr::col((Entity, Column::Name))
+                    .like(LikeExpr::new(format!("{}%", escape_like(prefix))).escape('\\')),
+            )
+            .group_by(Column::Id)
+            .order_by(event_count, Order::Desc)
+            .order_by_asc(Column::Name)
+            .limit(limit)
+            .into_model::<PlaceUsageRow>()
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(rows
+            .into_iter()
+            .map(|r| PlaceUsage {
+                place: Place {
+                    id: r.id,
+                    tree_id: r.tree_id,
+                    name: r.name,
+                    latitude: r.latitude,
+                    longitude: r.longitude,
+                    created_at: r.created_at,
+                    updated_at: r.updated_at,
+                    version: r.version,
+                },
+                event_count: r.event_count,
+            })
+            .collect())
+    }
+
     /// List all places in a tree without pagination.
     pub async fn list_all(
         db: &DatabaseConnection,
@@ -69,9 +195,41 @@ impl PlaceRepo {
             })
     }
 
+    /// The tree's place named `name` (compared with
+    /// [`normalize_place_name`]), created without coordinates when there is
+    /// none.
+    pub async fn find_or_create(
+        db: &impl ConnectionTrait,
+        tree_id: Uuid,
+        name: &str,
+    ) -> Result<Place, OxidGeneError> {
+        let wanted = normalize_place_name(name);
+        let existing = Entity::find()
+            .filter(Column::TreeId.eq(tree_id))
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?
+            .into_iter()
+            .find(|p| normalize_place_name(&p.name) == wanted);
+        match existing {
+            Some(model) => Ok(into_domain(model)),
+            None => {
+                Self::create(
+                    db,
+                    Uuid::now_v7(),
+                    tree_id,
+                    name.trim().to_string(),
+                    None,
+                    None,
+                )
+                .await
+            }
+        }
+    }
+
     /// Create a new place.
     pub async fn create(
-        db: &DatabaseConnection,
+        db: &impl ConnectionTrait,
         id: Uuid,
         tree_id: Uuid,
         name: String,
@@ -87,6 +245,7 @@ impl PlaceRepo {
             longitude: Set(longitude),
             created_at: Set(now),
             updated_at: Set(now),
+            version: Set(1),
         };
         let result = model
             .insert(db)
@@ -102,6 +261,7 @@ impl PlaceRepo {
         name: Option<String>,
         latitude: Option<Option<f64>>,
         longitude: Option<Option<f64>>,
+        expected_version: Option<i32>,
     ) -> Result<Place, OxidGeneError> {
         let existing = Entity::find_by_id(id)
             .one(db)
@@ -112,6 +272,9 @@ impl PlaceRepo {
                 id,
             })?;
 
+        let current_version = existing.version;
+        version::check("Place", id, expected_version, current_version)?;
+
         let mut active: ActiveModel = existing.into_active_model();
         if let Some(name) = name {
             active.name = Set(name);
@@ -123,16 +286,211 @@ impl PlaceRepo {
             active.longitude = Set(longitude);
         }
         active.updated_at = Set(Utc::now());
+        active.version = Set(current_version + 1);
 
-        let result = active
-            .update(db)
+        let result = Entity::update(active)
+            .filter(Column::Version.eq(current_version))
+            .exec(db)
             .await
-            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+            .map_err(version::update_error("Place", id))?;
         Ok(into_domain(result))
     }
 
-    /// Hard-delete a place.
+    /// Give a place the coordinates it lacks. A place that already has a
+    /// latitude or longitude is left untouched; returns whether it changed.
+    pub async fn fill_coordinates(
+        db: &impl ConnectionTrait,
+        id: Uuid,
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+    ) -> Result<bool, OxidGeneError> {
+        let result = Entity::update_many()
+            .col_expr(Column::Latitude, Expr::value(latitude))
+            .col_expr(Column::Longitude, Expr::value(longitude))
+            .col_expr(Column::UpdatedAt, Expr::value(Utc::now()))
+            .col_expr(Column::Version, Expr::col(Column::Version).add(1))
+            .filter(Column::Id.eq(id))
+            .filter(Column::Latitude.is_null())
+            .filter(Column::Longitude.is_null())
+            .exec(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(result.rows_affected > 0)
+    }
+
+    /// Merge duplicate places in a tree: places sharing the same
+    /// [`normalize_place_name`] and compatible coordinates (see
+    /// [`coordinates_match`]) collapse into the oldest one. Events and media
+    /// pointing at a duplicate are repointed to the survivor, which also
+    /// inherits coordinates it was missing; the duplicates are then deleted.
+    /// Runs in a single transaction and returns the number of places removed.
+    pub async fn dedupe(db: &DatabaseConnection, tree_id: Uuid) -> Result<u64, OxidGeneError> {
+        with_transaction(db, |txn| {
+            Box::pin(async move {
+                // UUID v7 ordering puts the oldest place of each group first, so it
+                // becomes the survivor.
+                let models = Entity::find()
+                    .filter(Column::TreeId.eq(tree_id))
+                    .order_by_asc(Column::Id)
+                    .all(txn)
+                    .await
+                    .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+
+                let mut groups: HashMap<String, Vec<place::Model>> = HashMap::new();
+                for m in models {
+                    groups
+                        .entry(normalize_place_name(&m.name))
+                        .or_default()
+                        .push(m);
+                }
+
+                let mut merged = 0;
+                for group in groups.into_values() {
+                    // (survivor, coordinates it inherited from a duplicate)
+                    let mut survivors: Vec<(place::Model, bool)> = Vec::new();
+                    for candidate in group {
+                        let Some((survivor, changed)) = survivors.iter_mut().find(|(s, _)| {
+                            coordinates_match(
+                                (s.latitude, s.longitude),
+                                (candidate.latitude, candidate.longitude),
+                            )
+                        }) else {
+                            survivors.push((candidate, false));
+                            continue;
+                        };
+
+                        event::Entity::update_many()
+                            .col_expr(event::Column::PlaceId, Expr::value(survivor.id))
+                            .filter(event::Column::PlaceId.eq(candidate.id))
+                            .exec(txn)
+                            .await
+                            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+                        event::Entity::update_many()
+                            .col_expr(event::Column::PlaceIdTo, Expr::value(survivor.id))
+                            .filter(event::Column::PlaceIdTo.eq(candidate.id))
+                            .exec(txn)
+                            .await
+                            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+                        media::Entity::update_many()
+                            .col_expr(media::Column::PlaceId, Expr::value(survivor.id))
+                            .filter(media::Column::PlaceId.eq(candidate.id))
+                            .exec(txn)
+                            .await
+                            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+
+                        if survivor.latitude.is_none() && survivor.longitude.is_none() {
+                            survivor.latitude = candidate.latitude;
+                            survivor.longitude = candidate.longitude;
+                            *changed |=
+                                candidate.latitude.is_some() || candidate.longitude.is_some();
+                        }
+
+                        Entity::delete_by_id(candidate.id)
+                            .exec(txn)
+                            .await
+                            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+                        merged += 1;
+                    }
+
+                    for (survivor, changed) in survivors {
+                        if !changed {
+                            continue;
+                        }
+                        let (latitude, longitude) = (survivor.latitude, survivor.longitude);
+                        let version = survivor.version;
+                        let mut active: ActiveModel = survivor.into_active_model();
+                        active.latitude = Set(latitude);
+                        active.longitude = Set(longitude);
+                        active.updated_at = Set(Utc::now());
+                        active.version = Set(version + 1);
+                        active
+                            .update(txn)
+                            .await
+                            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+                    }
+                }
+
+                Ok(merged)
+            })
+        })
+        .await
+    }
+
+    /// Places of a tree that nothing points at, by name: no event (live or
+    /// in the trash) holds them as place or destination, and no media is
+    /// placed there.
+    pub async fn list_unused(
+        db: &impl ConnectionTrait,
+        tree_id: Uuid,
+    ) -> Result<Vec<Place>, OxidGeneError> {
+        let mut query = Entity::find().filter(Column::TreeId.eq(tree_id));
+        for (table, column) in [
+            (
+                event::Entity.into_iden(),
+                event::Column::PlaceId.into_iden(),
+            ),
+            (
+                event::Entity.into_iden(),
+                event::Column::PlaceIdTo.into_iden(),
+            ),
+            (
+                media::Entity.into_iden(),
+                media::Column::PlaceId.into_iden(),
+            ),
+        ] {
+            let used = Query::select()
+                .column(column.clone())
+                .from(table)
+                .and_where(Expr::col(column).is_not_null())
+                .to_owned();
+            query = query.filter(Column::Id.not_in_subquery(used));
+        }
+        let models = query
+            .order_by_asc(Column::Name)
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
+    /// Delete the tree's unused places (see [`Self::list_unused`]) in a
+    /// single transaction and return how many were removed.
+    pub async fn prune_unused(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+    ) -> Result<u64, OxidGeneError> {
+        let txn = db
+            .begin()
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        let ids: Vec<Uuid> = Self::list_unused(&txn, tree_id)
+            .await?
+            .into_iter()
+            .map(|p| p.id)
+            .collect();
+        let result = Entity::delete_many()
+            .filter(Column::Id.is_in(ids))
+            .exec(&txn)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        txn.commit()
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(result.rows_affected)
+    }
+
+    /// Hard-delete a place. Events pointing at it lose their place (or
+    /// destination).
     pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<(), OxidGeneError> {
+        // `event.place_id` is cleared by its foreign key; `place_id_to` was
+        // added later without one (SQLite cannot add constraints to an
+        // existing table).
+        event::Entity::update_many()
+            .col_expr(event::Column::PlaceIdTo, Expr::value(Option::<Uuid>::None))
+            .filter(event::Column::PlaceIdTo.eq(id))
+            .exec(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
         let result = Entity::delete_by_id(id)
             .exec(db)
             .await
@@ -156,5 +514,6 @@ fn into_domain(m: place::Model) -> Place {
         longitude: m.longitude,
         created_at: m.created_at,
         updated_at: m.updated_at,
+        version: m.version,
     }
 }
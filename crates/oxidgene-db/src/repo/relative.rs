@@ -0,0 +1,347 @@
+//! Adding a relative to a person in one step: the new person, their name,
+//! the family that connects them (found or created) and the link, with the
+//! ancestry closure updated — all in one transaction.
+
+use chrono::Utc;
+use oxidgene_core::enums::{ChildType, NameType, Privacy, RelativeKind, Sex, SpouseRole};
+use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::fuzzy::{given_phonetic, surname_phonetic};
+use oxidgene_core::text::normalize_name;
+use oxidgene_core::types::Person;
+use sea_orm::entity::prelude::*;
+use sea_orm::{
+    DatabaseTransaction, JoinType, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+use super::PersonAncestryRepo;
+use crate::entities::{family, family_child, family_spouse, person, person_name, sea_enums};
+
+/// The new person's details.
+#[derive(Debug, Clone, Default)]
+pub struct NewRelative {
+    /// Ignored for fathers (male) and mothers (female).
+    pub sex: Option<Sex>,
+    pub given_names: Option<String>,
+    pub surname: Option<String>,
+}
+
+/// What [`RelativeRepo::add`] created.
+#[derive(Debug, Clone)]
+pub struct AddedRelative {
+    pub person: Person,
+    /// The family linking the new person to the anchor person.
+    pub family_id: Uuid,
+    /// Whether that family was created for the occasion.
+    pub family_created: bool,
+}
+
+pub struct RelativeRepo;
+
+impl RelativeRepo {
+    /// Create a person related to `person_id` as `kind`, and wire them in:
+    ///
+    /// - a father or mother becomes a spouse of the family the person is a
+    ///   child of, created (with the person as its child) if there is none;
+    /// - a child joins the person's first union, created (with the person
+    ///   as its only spouse) if there is none;
+    /// - a spouse fills the person's first union that has no other spouse,
+    ///   or starts a new one;
+    /// - a sibling joins the family the person is a child of, created (with
+    ///   the person as its first child) if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OxidGeneError::NotFound`] when the person is not a live
+    /// person of the tree, and [`OxidGeneError::Validation`] when adding a
+    /// father or mother to a person whose parent family already has one.
+    pub async fn add(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        person_id: Uuid,
+        kind: RelativeKind,
+        relative: NewRelative,
+    ) -> Result<AddedRelative, OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        let txn = db.begin().await.map_err(db_err)?;
+
+        let anchor = person::Entity::find_by_id(person_id)
+            .filter(person::Column::TreeId.eq(tree_id))
+            .filter(person::Column::DeletedAt.is_null())
+            .one(&txn)
+            .await
+            .map_err(db_err)?
+            .ok_or(OxidGeneError::NotFound {
+                entity: "Person",
+                id: person_id,
+            })?;
+
+        let sex = match kind {
+            RelativeKind::Father => Sex::Male,
+            RelativeKind::Mother => Sex::Female,
+            _ => relative.sex.unwrap_or(Sex::Unknown),
+        };
+        let new_person = create_person(&txn, tree_id, sex, relative).await?;
+
+        let (family_id, family_created) = match kind {
+            RelativeKind::Father | RelativeKind::Mother => {
+                let role = SpouseRole::for_sex(sex);
+                let (family_id, created) = match parent_family(&txn, person_id).await? {
+                    Some(family_id) => (family_id, false),
+                    None => {
+                        let family_id = create_family(&txn, tree_id).await?;
+                        add_child(&txn, family_id, person_id).await?;
+                        (family_id, true)
+                    }
+                };
+                let spouses = family_spouses(&txn, family_id).await?;
+                if spouses
+                    .iter()
+                    .any(|s| s.role == sea_enums::SpouseRole::from(role))
+                {
+                    return Err(OxidGeneError::Validation(format!(
+                        "person {person_id} already has a {kind}"
+                    )));
+                }
+                add_spouse(&txn, family_id, new_person.id, role).await?;
+                (family_id, created)
+            }
+            RelativeKind::Child => {
+                let (family_id, created) = match own_families(&txn, person_id).await?.first() {
+                    Some(&family_id) => (family_id, false),
+                    None => {
+                        let family_id = create_family(&txn, tree_id).await?;
+                        add_spouse(
+                            &txn,
+                            family_id,
+                            person_id,
+                            SpouseRole::for_sex(anchor.sex.into()),
+                        )
+                        .await?;
+                        (family_id, true)
+                    }
+                };
+                add_child(&txn, family_id, new_person.id).await?;
+                (family_id, created)
+            }
+            RelativeKind::Spouse => {
+                let mut open_union = None;
+                for family_id in own_families(&txn, person_id).await? {
+                    if family_spouses(&txn, family_id).await?.len() == 1 {
+                        open_union = Some(family_id);
+                        break;
+                    }
+                }
+                let (family_id, created) = match open_union {
+                    Some(family_id) => (family_id, false),
+                    None => {
+                        let family_id = create_family(&txn, tree_id).await?;
+                        add_spouse(
+                            &txn,
+                            family_id,
+                            person_id,
+                            SpouseRole::for_sex(anchor.sex.into()),
+                        )
+                        .await?;
+                        (family_id, true)
+                    }
+                };
+                add_spouse(&txn, family_id, new_person.id, SpouseRole::for_sex(sex)).await?;
+                (family_id, created)
+            }
+            RelativeKind::Sibling => {
+                let (family_id, created) = match parent_family(&txn, person_id).await? {
+                    Some(family_id) => (family_id, false),
+                    None => {
+                        let family_id = create_family(&txn, tree_id).await?;
+                        add_child(&txn, family_id, person_id).await?;
+                        (family_id, true)
+                    }
+                };
+                add_child(&txn, family_id, new_person.id).await?;
+                (family_id, created)
+            }
+        };
+
+        // Whatever the kind, the family's children are the persons whose
+        // ancestry may have changed.
+        PersonAncestryRepo::refresh_family(&txn, tree_id, family_id).await?;
+        txn.commit().await.map_err(db_err)?;
+
+        Ok(AddedRelative {
+            person: new_person,
+            family_id,
+            family_created,
+        })
+    }
+}
+
+async fn create_person(
+    txn: &DatabaseTransaction,
+    tree_id: Uuid,
+    sex: Sex,
+    relative: NewRelative,
+) -> Result<Person, OxidGeneError> {
+    let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+    let now = Utc::now();
+    let given_names = relative.given_names.filter(|s| !s.trim().is_empty());
+    let surname = relative.surname.filter(|s| !s.trim().is_empty());
+    // The only name, hence the one displayed.
+    let display_name = (given_names.is_some() || surname.is_some()).then(|| {
+        [given_names.as_deref(), surname.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ")
+    });
+    let model = person::ActiveModel {
+        id: Set(Uuid::now_v7()),
+        tree_id: Set(tree_id),
+        sex: Set(sea_enums::Sex::from(sex)),
+        privacy: Set(sea_enums::Privacy::from(Privacy::default())),
+        living: Set(None),
+        display_name: Set(display_name),
+        locked: Set(false),
+        created_at: Set(now),
+        updated_at: Set(now),
+        version: Set(1),
+        deleted_at: Set(None),
+    }
+    .insert(txn)
+    .await
+    .map_err(db_err)?;
+
+    if given_names.is_some() || surname.is_some() {
+        person_name::ActiveModel {
+            id: Set(Uuid::now_v7()),
+            person_id: Set(model.id),
+            name_type: Set(sea_enums::NameType::from(NameType::Birth)),
+            surname_phonetic: Set(surname.as_deref().and_then(surname_phonetic)),
+            given_phonetic: Set(given_names.as_deref().and_then(given_phonetic)),
+            surname_norm: Set(surname.as_deref().and_then(normalize_name)),
+            given_norm: Set(given_names.as_deref().and_then(normalize_name)),
+            given_names: Set(given_names),
+            surname: Set(surname),
+            prefix: Set(None),
+            suffix: Set(None),
+            nickname: Set(None),
+            is_primary: Set(true),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(txn)
+        .await
+        .map_err(db_err)?;
+    }
+
+    Ok(super::person::into_domain(model))
+}
+
+async fn create_family(txn: &DatabaseTransaction, tree_id: Uuid) -> Result<Uuid, OxidGeneError> {
+    let now = Utc::now();
+    let model = family::ActiveModel {
+        id: Set(Uuid::now_v7()),
+        tree_id: Set(tree_id),
+        created_at: Set(now),
+        updated_at: Set(now),
+        deleted_at: Set(None),
+    }
+    .insert(txn)
+    .await
+    .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+    Ok(model.id)
+}
+
+/// The live family `person_id` is a child of, if any.
+async fn parent_family(
+    txn: &DatabaseTransaction,
+    person_id: Uuid,
+) -> Result<Option<Uuid>, OxidGeneError> {
+    let link = family_child::Entity::find()
+        .join(JoinType::InnerJoin, family_child::Relation::Family.def())
+        .filter(family_child::Column::PersonId.eq(person_id))
+        .filter(family::Column::DeletedAt.is_null())
+        .order_by_asc(family_child::Column::FamilyId)
+        .one(txn)
+        .await
+        .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+    Ok(link.map(|l| l.family_id))
+}
+
+/// The live families `person_id` is a spouse of, in union order.
+async fn own_families(
+    txn: &DatabaseTransaction,
+    person_id: Uuid,
+) -> Result<Vec<Uuid>, OxidGeneError> {
+    let links = family_spouse::Entity::find()
+        .join(JoinType::InnerJoin, family_spouse::Relation::Family.def())
+        .filter(family_spouse::Column::PersonId.eq(person_id))
+        .filter(family::Column::DeletedAt.is_null())
+        .order_by_asc(family_spouse::Column::FamilyId)
+        .all(txn)
+        .await
+        .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+    Ok(links.into_iter().map(|l| l.family_id).collect())
+}
+
+async fn family_spouses(
+    txn: &DatabaseTransaction,
+    family_id: Uuid,
+) -> Result<Vec<family_spouse::Model>, OxidGeneError> {
+    family_spouse::Entity::find()
+        .filter(family_spouse::Column::FamilyId.eq(family_id))
+        .all(txn)
+        .await
+        .map_err(|e| OxidGeneError::Database(e.to_string()))
+}
+
+async fn add_spouse(
+    txn: &DatabaseTransaction,
+    family_id: Uuid,
+    person_id: Uuid,
+    role: SpouseRole,
+) -> Result<(), OxidGeneError> {
+    let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+    let last = family_spouse::Entity::find()
+        .filter(family_spouse::Column::FamilyId.eq(family_id))
+        .order_by_desc(family_spouse::Column::SortOrder)
+        .one(txn)
+        .await
+        .map_err(db_err)?;
+    family_spouse::ActiveModel {
+        id: Set(Uuid::now_v7()),
+        family_id: Set(family_id),
+        person_id: Set(person_id),
+        role: Set(sea_enums::SpouseRole::from(role)),
+        sort_order: Set(last.map_or(0, |m| m.sort_order + 1)),
+    }
+    .insert(txn)
+    .await
+    .map_err(db_err)?;
+    Ok(())
+}
+
+async fn add_child(
+    txn: &DatabaseTransaction,
+    family_id: Uuid,
+    person_id: Uuid,
+) -> Result<(), OxidGeneError> {
+    let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+    let last = family_child::Entity::find()
+        .filter(family_child::Column::FamilyId.eq(family_id))
+        .order_by_desc(family_child::Column::SortOrder)
+        .one(txn)
+        .await
+        .map_err(db_err)?;
+    family_child::ActiveModel {
+        id: Set(Uuid::now_v7()),
+        family_id: Set(family_id),
+        person_id: Set(person_id),
+        child_type: Set(sea_enums::ChildType::from(ChildType::Biological)),
+        sort_order: Set(last.map_or(0, |m| m.sort_order + 1)),
+    }
+    .insert(txn)
+    .await
+    .map_err(db_err)?;
+    Ok(())
+}
@@ -0,0 +1,177 @@
+//! Tree-wide find-and-replace on place and source fields (see
+//! [`oxidgene_core::replace`]).
+
+use chrono::Utc;
+use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::replace::{ReplaceEntity, ReplaceField, Replacer};
+use sea_orm::entity::prelude::*;
+use sea_orm::{
+    DatabaseTransaction, IntoActiveModel, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+use crate::entities::{place, source};
+
+/// A value changed by a replacement — or, in a dry run, that would be.
+#[derive(Debug, Clone)]
+pub struct ReplacedValue {
+    /// The place or source.
+    pub id: Uuid,
+    pub before: String,
+    /// `None` when an optional field is cleared.
+    pub after: Option<String>,
+}
+
+pub struct ReplaceRepo;
+
+impl ReplaceRepo {
+    /// Replace `field` in every place, or every live source, of a tree, in
+    /// a single transaction. With `dry_run`, nothing is written: the
+    /// changes are only reported. Each changed entity gets a new version.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OxidGeneError::Validation`] — and changes nothing — when a
+    /// replacement would empty a required field (a place's name, a
+    /// source's title).
+    pub async fn replace(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        field: ReplaceField,
+        replacer: &Replacer,
+        dry_run: bool,
+    ) -> Result<Vec<ReplacedValue>, OxidGeneError> {
+        let txn = db
+            .begin()
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        let changes = match field.entity() {
+            ReplaceEntity::Place => replace_places(&txn, tree_id, replacer, dry_run).await?,
+            ReplaceEntity::Source => {
+                replace_sources(&txn, tree_id, field, replacer, dry_run).await?
+            }
+        };
+        txn.commit()
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(changes)
+    }
+}
+
+async fn replace_places(
+    txn: &DatabaseTransaction,
+    tree_id: Uuid,
+    replacer: &Replacer,
+    dry_run: bool,
+) -> Result<Vec<ReplacedValue>, OxidGeneError> {
+    let models = place::Entity::find()
+        .filter(place::Column::TreeId.eq(tree_id))
+        .order_by_asc(place::Column::Id)
+        .all(txn)
+        .await
+        .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+
+    let mut changes = Vec::new();
+    for m in models {
+        let Some(change) = replaced(ReplaceField::PlaceName, m.id, Some(&m.name), replacer)? else {
+            continue;
+        };
+        if !dry_run {
+            let version = m.version;
+            let mut active = m.into_active_model();
+            active.name = Set(change.after.clone().unwrap_or_default());
+            active.updated_at = Set(Utc::now());
+            active.version = Set(version + 1);
+            active
+                .update(txn)
+                .await
+                .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        }
+        changes.push(change);
+    }
+    Ok(changes)
+}
+
+async fn replace_sources(
+    txn: &DatabaseTransaction,
+    tree_id: Uuid,
+    field: ReplaceField,
+    replacer: &Replacer,
+    dry_run: bool,
+) -> Result<Vec<ReplacedValue>, OxidGeneError> {
+    let models = source::Entity::find()
+        .filter(source::Column::TreeId.eq(tree_id))
+        .filter(source::Column::DeletedAt.is_null())
+        .order_by_asc(source::Column::Id)
+        .all(txn)
+        .await
+        .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+
+    let mut changes = Vec::new();
+    for m in models {
+        let value = match field {
+            ReplaceField::SourceTitle => Some(m.title.as_str()),
+            ReplaceField::SourceAuthor => m.author.as_deref(),
+            ReplaceField::SourcePublisher => m.publisher.as_deref(),
+            ReplaceField::SourceAbbreviation => m.abbreviation.as_deref(),
+            ReplaceField::SourceRepositoryName => m.repository_name.as_deref(),
+            ReplaceField::PlaceName => None,
+        };
+        let Some(change) = replaced(field, m.id, value, replacer)? else {
+            continue;
+        };
+        if !dry_run {
+            let version = m.version;
+            let mut active = m.into_active_model();
+            let after = change.after.clone();
+            match field {
+                ReplaceField::SourceTitle => active.title = Set(after.unwrap_or_default()),
+                ReplaceField::SourceAuthor => active.author = Set(after),
+                ReplaceField::SourcePublisher => active.publisher = Set(after),
+                ReplaceField::SourceAbbreviation => active.abbreviation = Set(after),
+                ReplaceField::SourceRepositoryName => active.repository_name = Set(after),
+                ReplaceField::PlaceName => {}
+            }
+            active.updated_at = Set(Utc::now());
+            active.version = Set(version + 1);
+            active
+                .update(txn)
+                .await
+                .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        }
+        changes.push(change);
+    }
+    Ok(changes)
+}
+
+/// The change `replacer` makes to one value, if any. A value replaced by
+/// blanks is cleared, which required fields refuse.
+fn replaced(
+    field: ReplaceField,
+    id: Uuid,
+    before: Option<&str>,
+    replacer: &Replacer,
+) -> Result<Option<ReplacedValue>, OxidGeneError> {
+    let Some(before) = before else {
+        return Ok(None);
+    };
+    let Some(after) = replacer.apply(before) else {
+        return Ok(None);
+    };
+    let after = Some(after).filter(|a| !a.trim().is_empty());
+    if after.is_none() && field.is_required() {
+        let entity = match field.entity() {
+            ReplaceEntity::Place => "place",
+            ReplaceEntity::Source => "source",
+        };
+        return Err(OxidGeneError::Validation(format!(
+            "the replacement would leave {entity} {id} without a {}",
+            field.as_str()
+        )));
+    }
+    Ok(Some(ReplacedValue {
+        id,
+        before: before.to_string(),
+        after,
+    }))
+}
@@ -0,0 +1,253 @@
+//! Repository for `ResearchTask` entities (CRUD with soft delete, owned by
+//! a person or a family).
+
+use chrono::{NaiveDate, Utc};
+use oxidgene_core::enums::TaskStatus;
+use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::types::ResearchTask;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::{Expr, Query};
+use sea_orm::{ActiveModelTrait, Condition, IntoActiveModel, Order, QueryFilter, QueryOrder, Set};
+use uuid::Uuid;
+
+use crate::entities::research_task::{self, ActiveModel, Column, Entity};
+use crate::entities::{family, person, sea_enums};
+use crate::repo::version;
+
+/// Optional filters for listing research tasks.
+#[derive(Debug, Clone, Default)]
+pub struct ResearchTaskFilter {
+    pub status: Option<TaskStatus>,
+    pub person_id: Option<Uuid>,
+    pub family_id: Option<Uuid>,
+}
+
+/// Repository for research task CRUD operations.
+pub struct ResearchTaskRepo;
+
+impl ResearchTaskRepo {
+    /// List the tasks of a tree, soonest due first (undated ones last, then
+    /// by creation). Deleted tasks and the tasks of deleted persons or
+    /// families are left out.
+    pub async fn list(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        filter: &ResearchTaskFilter,
+    ) -> Result<Vec<ResearchTask>, OxidGeneError> {
+        let live_persons = Query::select()
+            .column(person::Column::Id)
+            .from(person::Entity)
+            .and_where(person::Column::DeletedAt.is_null())
+            .to_owned();
+        let live_families = Query::select()
+            .column(family::Column::Id)
+            .from(family::Entity)
+            .and_where(family::Column::DeletedAt.is_null())
+            .to_owned();
+        let mut query = Entity::find()
+            .filter(Column::TreeId.eq(tree_id))
+            .filter(Column::DeletedAt.is_null())
+            .filter(
+                Condition::any()
+                    .add(Column::PersonId.is_null())
+                    .add(Column::PersonId.in_subquery(live_persons)),
+            )
+            .filter(
+                Condition::any()
+                    .add(Column::FamilyId.is_null())
+                    .add(Column::FamilyId.in_subquery(live_families)),
+            );
+        if let Some(status) = filter.status {
+            query = query.filter(Column::Status.eq(sea_enums::TaskStatus::from(status)));
+        }
+        if let Some(pid) = filter.person_id {
+            query = query.filter(Column::PersonId.eq(pid));
+        }
+        if let Some(fid) = filter.family_id {
+            query = query.filter(Column::FamilyId.eq(fid));
+        }
+
+        let models = query
+            .order_by(Expr::col(Column::DueDate).is_null(), Order::Asc)
+            .order_by_asc(Column::DueDate)
+            .order_by_asc(Column::CreatedAt)
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
+    /// Get a single task by ID (excludes soft-deleted).
+    pub async fn get(db: &DatabaseConnection, id: Uuid) -> Result<ResearchTask, OxidGeneError> {
+        Entity::find_by_id(id)
+            .filter(Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?
+            .map(into_domain)
+            .ok_or(OxidGeneError::NotFound {
+                entity: "ResearchTask",
+                id,
+            })
+    }
+
+    /// Create a task on the person `person_id` or the family `family_id`,
+    /// exactly one of which must be given, live and in the tree. New tasks
+    /// are open.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        db: &DatabaseConnection,
+        id: Uuid,
+        tree_id: Uuid,
+        person_id: Option<Uuid>,
+        family_id: Option<Uuid>,
+        title: String,
+        description: Option<String>,
+        due_date: Option<NaiveDate>,
+    ) -> Result<ResearchTask, OxidGeneError> {
+        check_owner(db, tree_id, person_id, family_id).await?;
+        let now = Utc::now();
+        let model = research_task::ActiveModel {
+            id: Set(id),
+            tree_id: Set(tree_id),
+            person_id: Set(person_id),
+            family_id: Set(family_id),
+            title: Set(title),
+            description: Set(description),
+            status: Set(sea_enums::TaskStatus::Open),
+            due_date: Set(due_date),
+            created_at: Set(now),
+            updated_at: Set(now),
+            version: Set(1),
+            deleted_at: Set(None),
+        };
+        let result = model
+            .insert(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(into_domain(result))
+    }
+
+    /// Update a task's title, description, status and/or due date.
+    pub async fn update(
+        db: &DatabaseConnection,
+        id: Uuid,
+        title: Option<String>,
+        description: Option<Option<String>>,
+        status: Option<TaskStatus>,
+        due_date: Option<Option<NaiveDate>>,
+        expected_version: Option<i32>,
+    ) -> Result<ResearchTask, OxidGeneError> {
+        let existing = Entity::find_by_id(id)
+            .filter(Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?
+            .ok_or(OxidGeneError::NotFound {
+                entity: "ResearchTask",
+                id,
+            })?;
+
+        let current_version = existing.version;
+        version::check("ResearchTask", id, expected_version, current_version)?;
+
+        let mut active: ActiveModel = existing.into_active_model();
+        if let Some(title) = title {
+            active.title = Set(title);
+        }
+        if let Some(description) = description {
+            active.description = Set(description);
+        }
+        if let Some(status) = status {
+            active.status = Set(sea_enums::TaskStatus::from(status));
+        }
+        if let Some(due_date) = due_date {
+            active.due_date = Set(due_date);
+        }
+        active.updated_at = Set(Utc::now());
+        active.version = Set(current_version + 1);
+
+        let result = Entity::update(active)
+            .filter(Column::Version.eq(current_version))
+            .exec(db)
+            .await
+            .map_err(version::update_error("ResearchTask", id))?;
+        Ok(into_domain(result))
+    }
+
+    /// Soft-delete a task.
+    pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<(), OxidGeneError> {
+        let existing = Entity::find_by_id(id)
+            .filter(Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?
+            .ok_or(OxidGeneError::NotFound {
+                entity: "ResearchTask",
+                id,
+            })?;
+
+        let mut active: ActiveModel = existing.into_active_model();
+        active.deleted_at = Set(Some(Utc::now()));
+        active
+            .update(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Check that exactly one owner is given and that it is a live person or
+/// family of the tree.
+async fn check_owner(
+    db: &DatabaseConnection,
+    tree_id: Uuid,
+    person_id: Option<Uuid>,
+    family_id: Option<Uuid>,
+) -> Result<(), OxidGeneError> {
+    let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+    match (person_id, family_id) {
+        (Some(pid), None) => person::Entity::find_by_id(pid)
+            .filter(person::Column::TreeId.eq(tree_id))
+            .filter(person::Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .map_err(db_err)?
+            .map(|_| ())
+            .ok_or(OxidGeneError::NotFound {
+                entity: "Person",
+                id: pid,
+            }),
+        (None, Some(fid)) => family::Entity::find_by_id(fid)
+            .filter(family::Column::TreeId.eq(tree_id))
+            .filter(family::Column::DeletedAt.is_null())
+            .one(db)
+            .await
+            .map_err(db_err)?
+            .map(|_| ())
+            .ok_or(OxidGeneError::NotFound {
+                entity: "Family",
+                id: fid,
+            }),
+        _ => Err(OxidGeneError::Validation(
+            "exactly one of person_id and family_id is required".to_string(),
+        )),
+    }
+}
+
+fn into_domain(m: research_task::Model) -> ResearchTask {
+    ResearchTask {
+        id: m.id,
+        tree_id: m.tree_id,
+        person_id: m.person_id,
+        family_id: m.family_id,
+        title: m.title,
+        description: m.description,
+        status: m.status.into(),
+        due_date: m.due_date,
+        created_at: m.created_at,
+        updated_at: m.updated_at,
+        version: m.version,
+        deleted_at: m.deleted_at,
+    }
+}
@@ -0,0 +1,109 @@
+//! Repository for `ShareLink` entities (create/list/resolve/revoke).
+
+use chrono::{DateTime, Utc};
+use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::types::ShareLink;
+use sea_orm::entity::prelude::*;
+use sea_orm::{Condition, QueryFilter, QueryOrder, Set};
+use uuid::Uuid;
+
+use crate::entities::share_link::{self, Column, Entity};
+
+/// Repository for share link operations.
+pub struct ShareLinkRepo;
+
+impl ShareLinkRepo {
+    /// List a tree's share links, oldest first (expired ones included, so
+    /// the owner can still see and revoke them).
+    pub async fn list_by_tree(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+    ) -> Result<Vec<ShareLink>, OxidGeneError> {
+        let models = Entity::find()
+            .filter(Column::TreeId.eq(tree_id))
+            .order_by_asc(Column::CreatedAt)
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
+    /// Resolve a token to its link. Unknown and expired tokens are both
+    /// `NotFound`, so a stale link reveals nothing about the tree.
+    pub async fn resolve(db: &DatabaseConnection, token: Uuid) -> Result<ShareLink, OxidGeneError> {
+        Entity::find()
+            .filter(Column::Token.eq(token))
+            .filter(
+                Condition::any()
+                    .add(Column::ExpiresAt.is_null())
+                    .add(Column::ExpiresAt.gt(Utc::now())),
+            )
+            .one(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?
+            .map(into_domain)
+            .ok_or(OxidGeneError::NotFound {
+                entity: "ShareLink",
+                id: token,
+            })
+    }
+
+    /// Create a share link on behalf of actor `created_by`.
+    pub async fn create(
+        db: &DatabaseConnection,
+        id: Uuid,
+        tree_id: Uuid,
+        token: Uuid,
+        privatize: bool,
+        expires_at: Option<DateTime<Utc>>,
+        created_by: Option<String>,
+    ) -> Result<ShareLink, OxidGeneError> {
+        let model = share_link::ActiveModel {
+            id: Set(id),
+            tree_id: Set(tree_id),
+            token: Set(token),
+            privatize: Set(privatize),
+            expires_at: Set(expires_at),
+            created_by: Set(created_by),
+            created_at: Set(Utc::now()),
+        };
+        let result = model
+            .insert(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(into_domain(result))
+    }
+
+    /// Revoke (hard-delete) one of a tree's share links.
+    pub async fn delete(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        id: Uuid,
+    ) -> Result<(), OxidGeneError> {
+        let result = Entity::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::TreeId.eq(tree_id))
+            .exec(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        if result.rows_affected == 0 {
+            return Err(OxidGeneError::NotFound {
+                entity: "ShareLink",
+                id,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn into_domain(m: share_link::Model) -> ShareLink {
+    ShareLink {
+        id: m.id,
+        tree_id: m.tree_id,
+        token: m.token,
+        privatize: m.privatize,
+        expires_at: m.expires_at,
+        created_by: m.created_by,
+        created_at: m.created_at,
+    }
+}
@@ -1,14 +1,52 @@
-//! Repository for `Source` entities (CRUD with soft delete).
+//! Repository for `Source` entities (CRUD with soft delete, duplicate
+//! merging).
+
+use std::collections::HashMap;
 
 use chrono::Utc;
 use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::text::normalize;
 use oxidgene_core::types::{Connection, Source};
 use sea_orm::entity::prelude::*;
-use sea_orm::{ActiveModelTrait, IntoActiveModel, QueryFilter, Set};
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveModelTrait, IntoActiveModel, QueryFilter, QueryOrder, QuerySelect, Set};
 use uuid::Uuid;
 
 use crate::entities::source::{self, ActiveModel, Column, Entity};
+use crate::entities::{citation, media_link, note};
 use crate::repo::pagination::{PaginationParams, paginate};
+use crate::repo::transaction::with_transaction;
+use crate::repo::version;
+
+/// Normalize a source's title or author for duplicate detection: case and
+/// accents folded (see [`normalize`]) and punctuation dropped, so
+/// `"Recensement 1851, Lyon."` and `"recensement 1851 lyon"` compare equal.
+pub fn normalize_source_field(value: &str) -> String {
+    let spaced: String = value
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    normalize(&spaced)
+}
+
+/// A source with the number of citations pointing at it.
+#[derive(Debug, Clone)]
+pub struct SourceCitationCount {
+    pub source: Source,
+    pub citation_count: i64,
+}
+
+/// What [`SourceRepo::merge`] did.
+#[derive(Debug, Clone)]
+pub struct SourceMerge {
+    /// The surviving source, with the fields it inherited.
+    pub survivor: Source,
+    /// Sources merged into it, and deleted.
+    pub merged: u64,
+    pub citations_moved: u64,
+    pub media_links_moved: u64,
+    pub notes_moved: u64,
+}
 
 /// Repository for source CRUD operations.
 pub struct SourceRepo;
@@ -77,6 +115,7 @@ impl SourceRepo {
             repository_name: Set(repository_name),
             created_at: Set(now),
             updated_at: Set(now),
+            version: Set(1),
             deleted_at: Set(None),
         };
         let result = model
@@ -96,6 +135,7 @@ impl SourceRepo {
         publisher: Option<Option<String>>,
         abbreviation: Option<Option<String>>,
         repository_name: Option<Option<String>>,
+        expected_version: Option<i32>,
     ) -> Result<Source, OxidGeneError> {
         let existing = Entity::find_by_id(id)
             .filter(Column::DeletedAt.is_null())
@@ -107,6 +147,9 @@ impl SourceRepo {
                 id,
             })?;
 
+        let current_version = existing.version;
+        version::check("Source", id, expected_version, current_version)?;
+
         let mut active: ActiveModel = existing.into_active_model();
         if let Some(title) = title {
             active.title = Set(title);
@@ -124,14 +167,188 @@ impl SourceRepo {
             active.repository_name = Set(repository_name);
         }
         active.updated_at = Set(Utc::now());
+        active.version = Set(current_version + 1);
 
-        let result = active
-            .update(db)
+        let result = Entity::update(active)
+            .filter(Column::Version.eq(current_version))
+            .exec(db)
             .await
-            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+            .map_err(version::update_error("Source", id))?;
         Ok(into_domain(result))
     }
 
+    /// Groups of live sources of a tree that share the same title and
+    /// author once normalized (see [`normalize_source_field`]), each source
+    /// with its citation count. Sources are oldest first within a group, the
+    /// groups ordered by title.
+    pub async fn duplicates(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+    ) -> Result<Vec<Vec<SourceCitationCount>>, OxidGeneError> {
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        // UUID v7 ordering puts the oldest source of each group first.
+        let models = Entity::find()
+            .filter(Column::TreeId.eq(tree_id))
+            .filter(Column::DeletedAt.is_null())
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await
+            .map_err(db_err)?;
+
+        let mut groups: HashMap<(String, String), Vec<source::Model>> = HashMap::new();
+        for m in models {
+            let key = (
+                normalize_source_field(&m.title),
+                normalize_source_field(m.author.as_deref().unwrap_or_default()),
+            );
+            groups.entry(key).or_default().push(m);
+        }
+        let mut groups: Vec<((String, String), Vec<source::Model>)> = groups
+            .into_iter()
+            .filter(|(_, group)| group.len() > 1)
+            .collect();
+        groups.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let ids = groups
+            .iter()
+            .flat_map(|(_, group)| group.iter().map(|m| m.id));
+        let counts: HashMap<Uuid, i64> = citation::Entity::find()
+            .select_only()
+            .column(citation::Column::SourceId)
+            .column_as(citation::Column::Id.count(), "citation_count")
+            .filter(citation::Column::SourceId.is_in(ids))
+            .group_by(citation::Column::SourceId)
+            .into_tuple::<(Uuid, i64)>()
+            .all(db)
+            .await
+            .map_err(db_err)?
+            .into_iter()
+            .collect();
+
+        Ok(groups
+            .into_iter()
+            .map(|(_, group)| {
+                group
+                    .into_iter()
+                    .map(|m| SourceCitationCount {
+                        citation_count: counts.get(&m.id).copied().unwrap_or(0),
+                        source: into_domain(m),
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Merge `duplicate_ids` into `survivor_id`, in one transaction: their
+    /// citations, media links and notes are repointed to the survivor, which
+    /// also inherits the author, publisher, abbreviation and repository it
+    /// lacks (from the first duplicate having one); the duplicates are then
+    /// soft-deleted. Every id must be a live source of `tree_id`.
+    pub async fn merge(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        survivor_id: Uuid,
+        duplicate_ids: &[Uuid],
+    ) -> Result<SourceMerge, OxidGeneError> {
+        if duplicate_ids.is_empty() {
+            return Err(OxidGeneError::Validation(
+                "duplicate_ids must not be empty".into(),
+            ));
+        }
+        if duplicate_ids.contains(&survivor_id) {
+            return Err(OxidGeneError::Validation(
+                "the survivor cannot be one of the duplicates".into(),
+            ));
+        }
+        let mut duplicate_ids = duplicate_ids.to_vec();
+        duplicate_ids.sort();
+        duplicate_ids.dedup();
+
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+        with_transaction(db, |txn| {
+            Box::pin(async move {
+                let models: HashMap<Uuid, source::Model> = Entity::find()
+                    .filter(Column::Id.is_in(duplicate_ids.iter().copied().chain([survivor_id])))
+                    .filter(Column::TreeId.eq(tree_id))
+                    .filter(Column::DeletedAt.is_null())
+                    .all(txn)
+                    .await
+                    .map_err(db_err)?
+                    .into_iter()
+                    .map(|m| (m.id, m))
+                    .collect();
+                if let Some(&id) = duplicate_ids
+                    .iter()
+                    .chain([&survivor_id])
+                    .find(|id| !models.contains_key(id))
+                {
+                    return Err(OxidGeneError::NotFound {
+                        entity: "Source",
+                        id,
+                    });
+                }
+
+                let citations_moved = citation::Entity::update_many()
+                    .col_expr(citation::Column::SourceId, Expr::value(survivor_id))
+                    .filter(citation::Column::SourceId.is_in(duplicate_ids.iter().copied()))
+                    .exec(txn)
+                    .await
+                    .map_err(db_err)?
+                    .rows_affected;
+                let media_links_moved = media_link::Entity::update_many()
+                    .col_expr(media_link::Column::SourceId, Expr::value(survivor_id))
+                    .filter(media_link::Column::SourceId.is_in(duplicate_ids.iter().copied()))
+                    .exec(txn)
+                    .await
+                    .map_err(db_err)?
+                    .rows_affected;
+                let notes_moved = note::Entity::update_many()
+                    .col_expr(note::Column::SourceId, Expr::value(survivor_id))
+                    .filter(note::Column::SourceId.is_in(duplicate_ids.iter().copied()))
+                    .exec(txn)
+                    .await
+                    .map_err(db_err)?
+                    .rows_affected;
+
+                let now = Utc::now();
+                let survivor = models[&survivor_id].clone();
+                let inherit = |field: fn(&source::Model) -> &Option<String>| {
+                    field(&survivor).clone().or_else(|| {
+                        duplicate_ids
+                            .iter()
+                            .find_map(|id| field(&models[id]).clone())
+                    })
+                };
+                let current_version = survivor.version;
+                let mut active: ActiveModel = survivor.clone().into_active_model();
+                active.author = Set(inherit(|m| &m.author));
+                active.publisher = Set(inherit(|m| &m.publisher));
+                active.abbreviation = Set(inherit(|m| &m.abbreviation));
+                active.repository_name = Set(inherit(|m| &m.repository_name));
+                active.updated_at = Set(now);
+                active.version = Set(current_version + 1);
+                let survivor = active.update(txn).await.map_err(db_err)?;
+
+                let merged = Entity::update_many()
+                    .col_expr(Column::DeletedAt, Expr::value(now))
+                    .filter(Column::Id.is_in(duplicate_ids.iter().copied()))
+                    .exec(txn)
+                    .await
+                    .map_err(db_err)?
+                    .rows_affected;
+
+                Ok(SourceMerge {
+                    survivor: into_domain(survivor),
+                    merged,
+                    citations_moved,
+                    media_links_moved,
+                    notes_moved,
+                })
+            })
+        })
+        .await
+    }
+
     /// Soft-delete a source.
     pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<(), OxidGeneError> {
         let existing = Entity::find_by_id(id)
@@ -165,6 +382,7 @@ fn into_domain(m: source::Model) -> Source {
         repository_name: m.repository_name,
         created_at: m.created_at,
         updated_at: m.updated_at,
+        version: m.version,
         deleted_at: m.deleted_at,
     }
 }
@@ -0,0 +1,160 @@
+//! Usage of a source: every citation of it, joined back to the person,
+//! event or family it is attached to — "what does this source prove".
+//!
+//! The inverse of listing an owner's citations. One join picks the live
+//! citations (owners that are soft-deleted are left out); owner labels
+//! are then resolved in bulk.
+
+use oxidgene_core::enums::{Confidence, EventType, FactType};
+use oxidgene_core::error::OxidGeneError;
+use sea_orm::entity::prelude::*;
+use sea_orm::{DbBackend, Statement};
+use uuid::Uuid;
+
+use crate::entities::sea_enums;
+use crate::repo::owner_label::OwnerLabels;
+
+/// The type of entity a cited fact belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CitationOwnerKind {
+    Person,
+    Event,
+    Family,
+}
+
+impl CitationOwnerKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Person => "person",
+            Self::Event => "event",
+            Self::Family => "family",
+        }
+    }
+}
+
+/// A citation of a source, with the entity it supports.
+#[derive(Debug, Clone)]
+pub struct SourceUsageEntry {
+    pub citation_id: Uuid,
+    pub owner_kind: CitationOwnerKind,
+    /// The person, event or family the citation is attached to.
+    pub owner_id: Uuid,
+    /// Names of the person or family concerned (for events, the event's
+    /// person or family).
+    pub label: String,
+    /// Type and date of the event, for [`CitationOwnerKind::Event`].
+    pub event_type: Option<EventType>,
+    pub event_date: Option<String>,
+    /// Person concerned, for linking to their page.
+    pub person_id: Option<Uuid>,
+    /// Family concerned, for family citations and family events.
+    pub family_id: Option<Uuid>,
+    pub page: Option<String>,
+    pub text: Option<String>,
+    pub confidence: Confidence,
+    pub fact_type: Option<FactType>,
+}
+
+pub struct SourceUsageRepo;
+
+impl SourceUsageRepo {
+    /// All citations of a source whose owner is live, ordered by the names
+    /// of the persons and families concerned.
+    pub async fn list(
+        db: &DatabaseConnection,
+        source_id: Uuid,
+    ) -> Result<Vec<SourceUsageEntry>, OxidGeneError> {
+        let backend = db.get_database_backend();
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                backend,
+                usage_sql(backend),
+                [source_id.into()],
+            ))
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+
+        let mut entries = Vec::with_capacity(rows.len());
+        for row in rows {
+            let get_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+            let citation_person: Option<Uuid> = row.try_get("", "person_id").map_err(get_err)?;
+            let event_id: Option<Uuid> = row.try_get("", "event_id").map_err(get_err)?;
+            let citation_family: Option<Uuid> = row.try_get("", "family_id").map_err(get_err)?;
+            let (owner_kind, owner_id) = match (citation_person, event_id, citation_family) {
+                (Some(id), _, _) => (CitationOwnerKind::Person, id),
+                (None, Some(id), _) => (CitationOwnerKind::Event, id),
+                (None, None, Some(id)) => (CitationOwnerKind::Family, id),
+                (None, None, None) => continue,
+            };
+            let event_type: Option<String> = row.try_get("", "event_type").map_err(get_err)?;
+            let confidence: String = row.try_get("", "confidence").map_err(get_err)?;
+            let fact_type: Option<String> = row.try_get("", "fact_type").map_err(get_err)?;
+            entries.push(SourceUsageEntry {
+                citation_id: row.try_get("", "id").map_err(get_err)?,
+                owner_kind,
+                owner_id,
+                label: String::new(),
+                event_type: event_type
+                    .and_then(|t| sea_enums::EventType::try_from_value(&t).ok())
+                    .map(EventType::from),
+                event_date: row.try_get("", "date_value").map_err(get_err)?,
+                person_id: row.try_get("", "owner_person_id").map_err(get_err)?,
+                family_id: row.try_get("", "owner_family_id").map_err(get_err)?,
+                page: row.try_get("", "page").map_err(get_err)?,
+                text: row.try_get("", "text").map_err(get_err)?,
+                confidence: sea_enums::Confidence::try_from_value(&confidence)
+                    .map(Confidence::from)
+                    .map_err(get_err)?,
+                fact_type: fact_type
+                    .and_then(|t| sea_enums::FactType::try_from_value(&t).ok())
+                    .map(FactType::from),
+            });
+        }
+
+        let labels = OwnerLabels::load(
+            db,
+            entries.iter().filter_map(|e| e.person_id),
+            entries.iter().filter_map(|e| e.family_id),
+        )
+        .await?;
+        for entry in &mut entries {
+            entry.label = if entry.person_id.is_some() {
+                labels.person(entry.person_id)
+            } else {
+                labels.family(entry.family_id)
+            };
+        }
+        // Stable: citations of the same owner keep their creation order.
+        entries.sort_by_cached_key(|e| e.label.to_lowercase());
+        Ok(entries)
+    }
+}
+
+/// Citations of the bound source, joined to their event and to the person
+/// and family they concern — directly, or through the event.
+fn usage_sql(backend: DbBackend) -> String {
+    let source = match backend {
+        DbBackend::Sqlite => "?1",
+        _ => "$1",
+    };
+    format!(
+        r#"
+        SELECT c.id, c.person_id, c.event_id, c.family_id, c.page, c.text,
+               CAST(c.confidence AS TEXT) AS confidence,
+               CAST(c.fact_type AS TEXT) AS fact_type,
+               CAST(e.event_type AS TEXT) AS event_type, e.date_value,
+               p.id AS owner_person_id, f.id AS owner_family_id
+        FROM citation c
+        LEFT JOIN event e ON e.id = c.event_id
+        LEFT JOIN person p ON p.id = COALESCE(c.person_id, e.person_id)
+        LEFT JOIN family f ON f.id = COALESCE(c.family_id, e.family_id)
+        WHERE c.source_id = {source}
+          AND (c.event_id IS NULL OR (e.id IS NOT NULL AND e.deleted_at IS NULL))
+          AND (p.id IS NULL OR p.deleted_at IS NULL)
+          AND (f.id IS NULL OR f.deleted_at IS NULL)
+          AND (c.person_id IS NULL OR p.id IS NOT NULL)
+          AND (c.family_id IS NULL OR f.id IS NOT NULL)
+        ORDER BY c.created_at, c.id
+        "#
+    )
+}
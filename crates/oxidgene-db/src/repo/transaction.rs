@@ -0,0 +1,54 @@
+//! Running several writes as one unit of work.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use oxidgene_core::error::OxidGeneError;
+use sea_orm::{DatabaseConnection, DatabaseTransaction, TransactionTrait};
+
+/// Run `f` in a transaction: committed when it returns `Ok`, rolled back
+/// when it returns `Err`, so a failure halfway through leaves nothing
+/// behind.
+///
+/// The closure borrows the transaction, hence the boxed future:
+///
+/// ```ignore
+/// let person = with_transaction(db, |txn| {
+///     Box::pin(async move {
+///         let person = new_person.insert(txn).await.map_err(db_err)?;
+///         new_name.insert(txn).await.map_err(db_err)?;
+///         Ok(person)
+///     })
+/// })
+/// .await?;
+/// ```
+///
+/// # Errors
+///
+/// Returns the closure's error, or [`OxidGeneError::Database`] when the
+/// transaction cannot be opened or committed. A failed rollback is not
+/// reported over the error that caused it: the transaction is dropped, which
+/// rolls it back anyway.
+pub async fn with_transaction<T, F>(db: &DatabaseConnection, f: F) -> Result<T, OxidGeneError>
+where
+    F: for<'c> FnOnce(
+        &'c DatabaseTransaction,
+    ) -> Pin<Box<dyn Future<Output = Result<T, OxidGeneError>> + Send + 'c>>,
+{
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+    match f(&txn).await {
+        Ok(value) => {
+            txn.commit()
+                .await
+                .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+            Ok(value)
+        }
+        Err(e) => {
+            let _ = txn.rollback().await;
+            Err(e)
+        }
+    }
+}
@@ -1,14 +1,34 @@
 //! Repository for `Tree` entities (full CRUD with soft delete).
 
-use chrono::Utc;
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use oxidgene_core::error::OxidGeneError;
 use oxidgene_core::types::{Connection, Tree};
 use sea_orm::entity::prelude::*;
-use sea_orm::{ActiveModelTrait, IntoActiveModel, QueryFilter, Set};
+use sea_orm::{
+    ActiveModelTrait, DbBackend, IntoActiveModel, QueryFilter, QueryOrder, Set, Statement,
+};
 use uuid::Uuid;
 
 use crate::entities::tree::{self, ActiveModel, Column, Entity};
 use crate::repo::pagination::{PaginationParams, paginate};
+use crate::repo::tree_membership::visible_trees;
+use crate::repo::version;
+
+/// Live record counts of a tree and when its content last changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeStats {
+    pub person_count: i64,
+    pub family_count: i64,
+    pub event_count: i64,
+    /// The latest `updated_at` of the tree and its live persons, families
+    /// and events.
+    pub last_modified_at: DateTime<Utc>,
+    /// Years of the earliest and latest dated events, if any.
+    pub first_year: Option<i32>,
+    pub last_year: Option<i32>,
+}
 
 /// Repository for tree CRUD operations.
 pub struct TreeRepo;
@@ -23,6 +43,44 @@ impl TreeRepo {
         paginate(db, query, Column::Id, params, |m| (m.id, into_domain(m))).await
     }
 
+    /// [`Self::list`], restricted to the trees `actor_id` is a member of.
+    pub async fn list_for_actor(
+        db: &DatabaseConnection,
+        params: &PaginationParams,
+        actor_id: &str,
+    ) -> Result<Connection<Tree>, OxidGeneError> {
+        let query = Entity::find()
+            .filter(Column::DeletedAt.is_null())
+            .filter(visible_trees(Column::Id, actor_id));
+        paginate(db, query, Column::Id, params, |m| (m.id, into_domain(m))).await
+    }
+
+    /// [`Self::list_all`], restricted like [`Self::list_for_actor`].
+    pub async fn list_all_for_actor(
+        db: &DatabaseConnection,
+        actor_id: &str,
+    ) -> Result<Vec<Tree>, OxidGeneError> {
+        let models = Entity::find()
+            .filter(Column::DeletedAt.is_null())
+            .filter(visible_trees(Column::Id, actor_id))
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
+    /// List every tree, by creation (excludes soft-deleted).
+    pub async fn list_all(db: &DatabaseConnection) -> Result<Vec<Tree>, OxidGeneError> {
+        let models = Entity::find()
+            .filter(Column::DeletedAt.is_null())
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await
+            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
     /// Get a single tree by ID (excludes soft-deleted).
     pub async fn get(db: &DatabaseConnection, id: Uuid) -> Result<Tree, OxidGeneError> {
         Entity::find_by_id(id)
@@ -34,6 +92,97 @@ impl TreeRepo {
             .ok_or(OxidGeneError::NotFound { entity: "Tree", id })
     }
 
+    /// [`TreeStats`] of each tree in `tree_ids`, in a single query joining
+    /// the per-tree aggregates of persons, families and events. Unknown or
+    /// deleted trees are missing from the map.
+    pub async fn stats(
+        db: &DatabaseConnection,
+        tree_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, TreeStats>, OxidGeneError> {
+        if tree_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let db_err = |e: DbErr| OxidGeneError::Database(e.to_string());
+
+        // Numbered placeholders, so each aggregate reuses the same ids.
+        let backend = db.get_database_backend();
+        let sigil = match backend {
+            DbBackend::Sqlite => '?',
+            _ => '$',
+        };
+        let ids = (1..=tree_ids.len())
+            .map(|i| format!("{sigil}{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let aggregate = |table: &str, extra: &str| {
+            format!(
+                "LEFT JOIN (
+                    SELECT tree_id, COUNT(*) AS n, MAX(updated_at) AS last_at{extra}
+                    FROM {table}
+                    WHERE deleted_at IS NULL AND tree_id IN ({ids})
+                    GROUP BY tree_id
+                ) {table}_agg ON {table}_agg.tree_id = t.id"
+            )
+        };
+        let sql = format!(
+            "SELECT t.id, t.updated_at,
+                person_agg.n AS person_count, person_agg.last_at AS person_at,
+                family_agg.n AS family_count, family_agg.last_at AS family_at,
+                event_agg.n AS event_count, event_agg.last_at AS event_at,
+                event_agg.first_date, event_agg.last_date
+            FROM tree t
+            {}
+            {}
+            {}
+            WHERE t.deleted_at IS NULL AND t.id IN ({ids})",
+            aggregate("person", ""),
+            aggregate("family", ""),
+            aggregate(
+                "event",
+                ", MIN(date_sort) AS first_date, MAX(date_sort) AS last_date"
+            ),
+        );
+        let rows = db
+            .query_all(Statement::from_sql_and_values(
+                backend,
+                sql,
+                tree_ids.iter().map(|&id| id.into()),
+            ))
+            .await
+            .map_err(db_err)?;
+
+        let mut stats = HashMap::with_capacity(rows.len());
+        for row in rows {
+            let id: Uuid = row.try_get("", "id").map_err(db_err)?;
+            let tree_at: DateTime<Utc> = row.try_get("", "updated_at").map_err(db_err)?;
+            let mut last_modified_at = tree_at;
+            for column in ["person_at", "family_at", "event_at"] {
+                let at: Option<DateTime<Utc>> = row.try_get("", column).map_err(db_err)?;
+                last_modified_at = last_modified_at.max(at.unwrap_or(tree_at));
+            }
+            let year = |column: &str| -> Result<Option<i32>, OxidGeneError> {
+                let date: Option<NaiveDate> = row.try_get("", column).map_err(db_err)?;
+                Ok(date.map(|d| d.year()))
+            };
+            let count = |column: &str| -> Result<i64, OxidGeneError> {
+                let n: Option<i64> = row.try_get("", column).map_err(db_err)?;
+                Ok(n.unwrap_or(0))
+            };
+            stats.insert(
+                id,
+                TreeStats {
+                    person_count: count("person_count")?,
+                    family_count: count("family_count")?,
+                    event_count: count("event_count")?,
+                    last_modified_at,
+                    first_year: year("first_date")?,
+                    last_year: year("last_date")?,
+                },
+            );
+        }
+        Ok(stats)
+    }
+
     /// Create a new tree.
     pub async fn create(
         db: &DatabaseConnection,
@@ -49,6 +198,7 @@ impl TreeRepo {
             sosa_root_person_id: Set(None),
             created_at: Set(now),
             updated_at: Set(now),
+            version: Set(1),
             deleted_at: Set(None),
         };
         let result = model
@@ -65,6 +215,7 @@ impl TreeRepo {
         name: Option<String>,
         description: Option<Option<String>>,
         sosa_root_person_id: Option<Option<Uuid>>,
+        expected_version: Option<i32>,
     ) -> Result<Tree, OxidGeneError> {
         let existing = Entity::find_by_id(id)
             .filter(Column::DeletedAt.is_null())
@@ -73,6 +224,9 @@ impl TreeRepo {
             .map_err(|e| OxidGeneError::Database(e.to_string()))?
             .ok_or(OxidGeneError::NotFound { entity: "Tree", id })?;
 
+        let current_version = existing.version;
+        version::check("Tree", id, expected_version, current_version)?;
+
         let mut active: ActiveModel = existing.into_active_model();
         if let Some(name) = name {
             active.name = Set(name);
@@ -84,11 +238,13 @@ impl TreeRepo {
             active.sosa_root_person_id = Set(sosa_root);
         }
         active.updated_at = Set(Utc::now());
+        active.version = Set(current_version + 1);
 
-        let result = active
-            .update(db)
+        let result = Entity::update(active)
+            .filter(Column::Version.eq(current_version))
+            .exec(db)
             .await
-            .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+            .map_err(version::update_error("Tree", id))?;
         Ok(into_domain(result))
     }
 
@@ -119,6 +275,7 @@ fn into_domain(m: tree::Model) -> Tree {
         sosa_root_person_id: m.sosa_root_person_id,
         created_at: m.created_at,
         updated_at: m.updated_at,
+        version: m.version,
         deleted_at: m.deleted_at,
     }
 }
@@ -0,0 +1,189 @@
+//! Repository for `TreeMembership` entities: the role of each actor on a
+//! tree (list/set/remove), keeping at least one owner on a tree that has
+//! members.
+
+use chrono::Utc;
+use oxidgene_core::enums::TreeRole;
+use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::types::TreeMembership;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::Query;
+use sea_orm::{Condition, QueryFilter, QueryOrder, Set};
+use uuid::Uuid;
+
+use crate::entities::sea_enums;
+use crate::entities::tree_membership::{self, Column, Entity};
+use crate::repo::transaction::with_transaction;
+
+fn db_err(e: DbErr) -> OxidGeneError {
+    OxidGeneError::Database(e.to_string())
+}
+
+/// Repository for tree membership operations.
+pub struct TreeMembershipRepo;
+
+impl TreeMembershipRepo {
+    /// List a tree's members, in the order they joined.
+    pub async fn list_by_tree(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+    ) -> Result<Vec<TreeMembership>, OxidGeneError> {
+        let models = Entity::find()
+            .filter(Column::TreeId.eq(tree_id))
+            .order_by_asc(Column::CreatedAt)
+            .order_by_asc(Column::ActorId)
+            .all(db)
+            .await
+            .map_err(db_err)?;
+        Ok(models.into_iter().map(into_domain).collect())
+    }
+
+    /// The role of `actor_id` on a tree, `None` if they are not a member.
+    pub async fn role(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        actor_id: &str,
+    ) -> Result<Option<TreeRole>, OxidGeneError> {
+        let model = Entity::find_by_id((tree_id, actor_id.to_string()))
+            .one(db)
+            .await
+            .map_err(db_err)?;
+        Ok(model.map(|m| m.role.into()))
+    }
+
+    /// Whether a tree has any member. A tree without one (created before
+    /// authentication was turned on) is only reachable by admins.
+    pub async fn has_members(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+    ) -> Result<bool, OxidGeneError> {
+        let count = Entity::find()
+            .filter(Column::TreeId.eq(tree_id))
+            .count(db)
+            .await
+            .map_err(db_err)?;
+        Ok(count > 0)
+    }
+
+    /// Give `actor_id` a role on a tree, adding them as a member or
+    /// changing their role. Demoting the tree's last owner fails with
+    /// [`OxidGeneError::Conflict`].
+    pub async fn set(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        actor_id: &str,
+        role: TreeRole,
+    ) -> Result<TreeMembership, OxidGeneError> {
+        let actor_id = actor_id.to_string();
+        let model = with_transaction(db, |txn| {
+            Box::pin(async move {
+                let existing = Entity::find_by_id((tree_id, actor_id.clone()))
+                    .one(txn)
+                    .await
+                    .map_err(db_err)?;
+                let now = Utc::now();
+                let model = match existing {
+                    Some(existing) => {
+                        if existing.role == sea_enums::TreeRole::Owner && role != TreeRole::Owner {
+                            ensure_other_owner(txn, tree_id, &actor_id).await?;
+                        }
+                        let mut active: tree_membership::ActiveModel = existing.into();
+                        active.role = Set(role.into());
+                        active.updated_at = Set(now);
+                        active.update(txn).await.map_err(db_err)?
+                    }
+                    None => tree_membership::ActiveModel {
+                        tree_id: Set(tree_id),
+                        actor_id: Set(actor_id),
+                        role: Set(role.into()),
+                        created_at: Set(now),
+                        updated_at: Set(now),
+                    }
+                    .insert(txn)
+                    .await
+                    .map_err(db_err)?,
+                };
+                Ok(model)
+            })
+        })
+        .await?;
+        Ok(into_domain(model))
+    }
+
+    /// Remove `actor_id` from a tree's members. Fails with
+    /// [`OxidGeneError::NotFound`] if they are not one, and with
+    /// [`OxidGeneError::Conflict`] if they are its last owner.
+    pub async fn remove(
+        db: &DatabaseConnection,
+        tree_id: Uuid,
+        actor_id: &str,
+    ) -> Result<(), OxidGeneError> {
+        let actor_id = actor_id.to_string();
+        with_transaction(db, |txn| {
+            Box::pin(async move {
+                let existing = Entity::find_by_id((tree_id, actor_id.clone()))
+                    .one(txn)
+                    .await
+                    .map_err(db_err)?
+                    .ok_or(OxidGeneError::NotFound {
+                        entity: "TreeMembership",
+                        id: tree_id,
+                    })?;
+                if existing.role == sea_enums::TreeRole::Owner {
+                    ensure_other_owner(txn, tree_id, &actor_id).await?;
+                }
+                Entity::delete_by_id((tree_id, actor_id))
+                    .exec(txn)
+                    .await
+                    .map_err(db_err)?;
+                Ok(())
+            })
+        })
+        .await
+    }
+}
+
+/// Fail with [`OxidGeneError::Conflict`] unless the tree has an owner other
+/// than `actor_id`.
+async fn ensure_other_owner<C: ConnectionTrait>(
+    db: &C,
+    tree_id: Uuid,
+    actor_id: &str,
+) -> Result<(), OxidGeneError> {
+    let other_owners = Entity::find()
+        .filter(Column::TreeId.eq(tree_id))
+        .filter(Column::Role.eq(sea_enums::TreeRole::Owner))
+        .filter(Column::ActorId.ne(actor_id))
+        .count(db)
+        .await
+        .map_err(db_err)?;
+    if other_owners == 0 {
+        return Err(OxidGeneError::Conflict(format!(
+            "`{actor_id}` is the tree's last owner"
+        )));
+    }
+    Ok(())
+}
+
+/// Condition on `tree.id` matching the trees `actor_id` is a member of.
+pub(crate) fn visible_trees(tree_id: crate::entities::tree::Column, actor_id: &str) -> Condition {
+    Condition::all().add(
+        tree_id.in_subquery(
+            Query::select()
+                .column(Column::TreeId)
+                .from(Entity)
+                .and_where(Column::ActorId.eq(actor_id))
+                .to_owned(),
+        ),
+    )
+}
+
+fn into_domain(m: tree_membership::Model) -> TreeMembership {
+    TreeMembership {
+        tree_id: m.tree_id,
+        actor_id: m.actor_id,
+        role: m.role.into(),
+        created_at: m.created_at,
+        updated_at: m.updated_at,
+    }
+}
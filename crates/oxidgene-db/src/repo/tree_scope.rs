@@ -0,0 +1,137 @@
+//! Which tree an entity belongs to, so that a request under one tree's
+//! path cannot read or change an entity of another tree by naming its id.
+//!
+//! Entities without a `tree_id` of their own (names, family links,
+//! participants, citations, media links) take the tree of their parent.
+
+use oxidgene_core::error::OxidGeneError;
+use sea_orm::entity::prelude::*;
+use sea_orm::{JoinType, QuerySelect};
+use uuid::Uuid;
+
+use crate::entities::{
+    citation, event, event_participant, family, family_child, family_spouse, media, media_link,
+    note, person, person_name, place, research_task, share_link, source,
+};
+
+/// An entity addressed by id under a tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeEntity {
+    Person,
+    PersonName,
+    Family,
+    FamilySpouse,
+    FamilyChild,
+    Event,
+    EventParticipant,
+    Place,
+    Source,
+    Citation,
+    Media,
+    MediaLink,
+    Note,
+    ResearchTask,
+    ShareLink,
+}
+
+impl TreeEntity {
+    /// The entity name reported in `NotFound` errors.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Person => "Person",
+            Self::PersonName => "PersonName",
+            Self::Family => "Family",
+            Self::FamilySpouse => "FamilySpouse",
+            Self::FamilyChild => "FamilyChild",
+            Self::Event => "Event",
+            Self::EventParticipant => "EventParticipant",
+            Self::Place => "Place",
+            Self::Source => "Source",
+            Self::Citation => "Citation",
+            Self::Media => "Media",
+            Self::MediaLink => "MediaLink",
+            Self::Note => "Note",
+            Self::ResearchTask => "ResearchTask",
+            Self::ShareLink => "ShareLink",
+        }
+    }
+}
+
+/// Repository resolving the tree of entities.
+pub struct TreeScopeRepo;
+
+impl TreeScopeRepo {
+    /// The tree `id` belongs to, `None` if there is no such entity.
+    /// Soft-deleted entities still belong to their tree.
+    pub async fn tree_of(
+        db: &impl ConnectionTrait,
+        entity: TreeEntity,
+        id: Uuid,
+    ) -> Result<Option<Uuid>, OxidGeneError> {
+        macro_rules! own {
+            ($module:ident) => {
+                $module::Entity::find_by_id(id)
+                    .select_only()
+                    .column($module::Column::TreeId)
+                    .into_tuple::<Uuid>()
+                    .one(db)
+                    .await
+            };
+        }
+        macro_rules! parent {
+            ($module:ident, $relation:expr, $parent:ident) => {
+                $module::Entity::find_by_id(id)
+                    .select_only()
+                    .column($parent::Column::TreeId)
+                    .join(JoinType::InnerJoin, $relation.def())
+                    .into_tuple::<Uuid>()
+                    .one(db)
+                    .await
+            };
+        }
+        let tree_id = match entity {
+            TreeEntity::Person => own!(person),
+            TreeEntity::Family => own!(family),
+            TreeEntity::Event => own!(event),
+            TreeEntity::Place => own!(place),
+            TreeEntity::Source => own!(source),
+            TreeEntity::Media => own!(media),
+            TreeEntity::Note => own!(note),
+            TreeEntity::ResearchTask => own!(research_task),
+            TreeEntity::ShareLink => own!(share_link),
+            TreeEntity::PersonName => {
+                parent!(person_name, person_name::Relation::Person, person)
+            }
+            TreeEntity::FamilySpouse => {
+                parent!(family_spouse, family_spouse::Relation::Family, family)
+            }
+            TreeEntity::FamilyChild => {
+                parent!(family_child, family_child::Relation::Family, family)
+            }
+            TreeEntity::EventParticipant => {
+                parent!(event_participant, event_participant::Relation::Event, event)
+            }
+            TreeEntity::Citation => parent!(citation, citation::Relation::Source, source),
+            TreeEntity::MediaLink => parent!(media_link, media_link::Relation::Media, media),
+        };
+        tree_id.map_err(|e| OxidGeneError::Database(e.to_string()))
+    }
+
+    /// Fail with `NotFound` if `id` is an entity of another tree than
+    /// `tree_id`, as if it did not exist. An unknown id passes, for the
+    /// request itself to report.
+    pub async fn ensure_in_tree(
+        db: &impl ConnectionTrait,
+        tree_id: Uuid,
+        entity: TreeEntity,
+        id: Uuid,
+    ) -> Result<(), OxidGeneError> {
+        match Self::tree_of(db, entity, id).await? {
+            Some(owner) if owner != tree_id => Err(OxidGeneError::NotFound {
+                entity: entity.name(),
+                id,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
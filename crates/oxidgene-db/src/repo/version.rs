@@ -0,0 +1,41 @@
+//! Optimistic concurrency for the versioned entities.
+//!
+//! Each `update` reads the row, rejects a stale client version up front,
+//! then writes with `WHERE version = <read version>` and bumps it, so a
+//! concurrent writer slipping in between the read and the write is caught
+//! too.
+
+use oxidgene_core::error::OxidGeneError;
+use sea_orm::DbErr;
+use uuid::Uuid;
+
+/// Reject an update whose `expected` version (from the client's `If-Match`)
+/// is not the stored `current` one. `None` skips the check.
+pub(crate) fn check(
+    entity: &'static str,
+    id: Uuid,
+    expected: Option<i32>,
+    current: i32,
+) -> Result<(), OxidGeneError> {
+    match expected {
+        Some(expected) if expected != current => Err(conflict(entity, id, current)),
+        _ => Ok(()),
+    }
+}
+
+/// Error mapper for a version-guarded update: no row updated means the
+/// version moved under us.
+pub(crate) fn update_error(entity: &'static str, id: Uuid) -> impl Fn(DbErr) -> OxidGeneError {
+    move |e| match e {
+        DbErr::RecordNotUpdated => OxidGeneError::Conflict(format!(
+            "{entity} {id} was modified concurrently; reload and retry"
+        )),
+        e => OxidGeneError::Database(e.to_string()),
+    }
+}
+
+fn conflict(entity: &'static str, id: Uuid, current: i32) -> OxidGeneError {
+    OxidGeneError::Conflict(format!(
+        "{entity} {id} has been modified (now at version {current}); reload and retry"
+    ))
+}
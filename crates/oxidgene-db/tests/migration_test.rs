@@ -1,6 +1,9 @@
-//! Integration test: run migrations against in-memory SQLite.
+//! Integration tests: migrations and SQLite connection tuning.
 
-use oxidgene_db::repo::{connect, rollback_migrations, run_migrations};
+use oxidgene_db::repo::{
+    SqliteSynchronous, SqliteTuning, connect, connect_with, rollback_migrations, run_migrations,
+};
+use sea_orm::{ConnectionTrait, DatabaseConnection, Statement};
 
 #[tokio::test]
 async fn test_migrate_up_and_down_sqlite() {
@@ -19,3 +22,48 @@ async fn test_migrate_up_and_down_sqlite() {
     // Re-apply to ensure idempotency.
     run_migrations(&db).await.expect("Re-migration up failed");
 }
+
+async fn pragma(db: &DatabaseConnection, name: &str) -> String {
+    let row = db
+        .query_one(Statement::from_string(
+            db.get_database_backend(),
+            format!("PRAGMA {name}"),
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+    row.try_get_by_index::<i64>(0)
+        .map(|v| v.to_string())
+        .or_else(|_| row.try_get_by_index::<String>(0))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_sqlite_tuning_pragmas() {
+    let path = std::env::temp_dir().join(format!("oxidgene-tuning-{}.db", uuid::Uuid::now_v7()));
+    let url = format!("sqlite://{}?mode=rwc", path.display());
+
+    let db = connect(&url).await.unwrap();
+    assert_eq!(pragma(&db, "journal_mode").await, "wal");
+    // NORMAL = 1.
+    assert_eq!(pragma(&db, "synchronous").await, "1");
+    assert_eq!(pragma(&db, "foreign_keys").await, "1");
+    assert_eq!(pragma(&db, "busy_timeout").await, "5000");
+    db.close().await.unwrap();
+
+    let tuning = SqliteTuning {
+        wal: false,
+        synchronous: SqliteSynchronous::Full,
+        ..SqliteTuning::default()
+    };
+    let db = connect_with(&url, &tuning).await.unwrap();
+    assert_eq!(pragma(&db, "journal_mode").await, "delete");
+    assert_eq!(pragma(&db, "synchronous").await, "2");
+    // Always on, whatever the tuning.
+    assert_eq!(pragma(&db, "foreign_keys").await, "1");
+    db.close().await.unwrap();
+
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+    }
+}
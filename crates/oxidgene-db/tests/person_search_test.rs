@@ -31,12 +31,14 @@ fn entry(
         surname: normalize_for_search(surname),
         given_names: normalize_for_search(given_names),
         maiden_name: None,
+        aliases: Vec::new(),
         birth_year: birth_year.map(str::to_owned),
         death_year: death_year.map(str::to_owned),
         sex: "male".into(),
         display_name: format!("{given_names} {surname}"),
         birth_place: None,
         date_sort: None,
+        matched_alias: None,
     }
 }
 
@@ -119,6 +121,94 @@ async fn search_prefix_and_accent_folding() {
     assert_eq!(page.total_count, 1);
 }
 
+#[tokio::test]
+async fn fuzzy_search_ranks_by_edit_distance() {
+    let db = setup_db().await;
+    let tree_id = Uuid::now_v7();
+
+    let entries = vec![
+        entry(tree_id, "Dupont", "Katherine", None, None),
+        entry(tree_id, "Dupond", "Catherine", None, None),
+        entry(tree_id, "Martin", "Caroline", None, None),
+        entry(tree_id, "Lefèvre", "Éloïse", None, None),
+    ];
+    PersonSearchRepo::replace_tree(&db, tree_id, &entries)
+        .await
+        .unwrap();
+
+    // "Catherine" (C) still reaches "Katherine" (K) through the phonetic
+    // bucket pre-filter; the exact match ranks first.
+    let hits = PersonSearchRepo::fuzzy_search(&db, tree_id, "Catherine", 2, 10)
+        .await
+        .unwrap();
+    let names: Vec<_> = hits.iter().map(|h| h.entry.display_name.as_str()).collect();
+    assert_eq!(names, ["Catherine Dupond", "Katherine Dupont"]);
+    assert_eq!(hits[0].distance, 0);
+    assert_eq!(hits[1].distance, 1);
+
+    // Typos across surname and given name add up.
+    let hits = PersonSearchRepo::fuzzy_search(&db, tree_id, "Katerine Dupon", 2, 10)
+        .await
+        .unwrap();
+    assert_eq!(hits[0].entry.display_name, "Katherine Dupont");
+    assert_eq!(hits[0].distance, 2);
+
+    // A tighter cutoff drops the farther match.
+    let hits = PersonSearchRepo::fuzzy_search(&db, tree_id, "Catherine", 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+
+    // Accents are folded before scoring.
+    let hits = PersonSearchRepo::fuzzy_search(&db, tree_id, "eloise lefevre", 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+}
+
+#[tokio::test]
+async fn search_matches_aliases() {
+    let db = setup_db().await;
+    let tree_id = Uuid::now_v7();
+
+    let mut marie = entry(tree_id, "Martin", "Marie", Some("1880"), None);
+    marie.aliases = vec!["Marie Lefèvre".into(), "Mimi".into()];
+    let entries = vec![marie, entry(tree_id, "Lefevre", "Paul", None, None)];
+    PersonSearchRepo::replace_tree(&db, tree_id, &entries)
+        .await
+        .unwrap();
+
+    // Her birth surname finds her, reporting the name it matched.
+    let page = PersonSearchRepo::search(&db, tree_id, "marie lefevre", 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(page.total_count, 1);
+    assert_eq!(page.entries[0].display_name, "Marie Martin");
+    assert_eq!(
+        page.entries[0].matched_alias.as_deref(),
+        Some("Marie Lefèvre")
+    );
+
+    let page = PersonSearchRepo::search(&db, tree_id, "mimi", 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(page.entries[0].matched_alias.as_deref(), Some("Mimi"));
+
+    // Found by the primary name, no alias is reported.
+    let page = PersonSearchRepo::search(&db, tree_id, "martin", 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(page.total_count, 1);
+    assert_eq!(page.entries[0].matched_alias, None);
+    assert_eq!(page.entries[0].aliases, ["Marie Lefèvre", "Mimi"]);
+
+    let hits = PersonSearchRepo::fuzzy_search(&db, tree_id, "Mimie", 1, 10)
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].entry.matched_alias.as_deref(), Some("Mimi"));
+}
+
 #[tokio::test]
 async fn browse_mode_sorted_and_paginated() {
     let db = setup_db().await;
@@ -2,14 +2,21 @@
 //!
 //! All tests run against an in-memory SQLite database.
 
-use oxidgene_core::enums::{ChildType, Confidence, EventType, NameType, Sex, SpouseRole};
+use oxidgene_core::enums::{
+    Calendar, ChildType, Confidence, EventType, NameType, NoteFormat, ParticipantRole,
+    RelativeKind, Sex, SpouseRole, TaskStatus, TreeRole,
+};
 use oxidgene_core::error::OxidGeneError;
+use oxidgene_core::text::NameCase;
 use oxidgene_db::repo::{
-    CitationRepo, DictionaryRepo, EventFilter, EventRepo, FamilyChildRepo, FamilyRepo,
-    FamilySpouseRepo, MediaLinkRepo, MediaRepo, NoteRepo, PaginationParams, PersonAncestryRepo,
-    PersonNameRepo, PersonRepo, PlaceRepo, SourceRepo, TreeRepo, connect, run_migrations,
+    CitationRepo, DictionaryRepo, EventFilter, EventParticipantRepo, EventRepo, FamilyChildRepo,
+    FamilyRepo, FamilySpouseRepo, IntegrityRepo, IssueKind, MediaLinkRepo, MediaRepo, NewRelative,
+    NoteRepo, PaginationParams, PersonAncestryRepo, PersonMoveRepo, PersonNameRepo, PersonRepo,
+    PersonTagRepo, PlaceRepo, RelativeRepo, ResearchTaskFilter, ResearchTaskRepo, Severity,
+    ShareLinkRepo, SourceRepo, TreeEntity, TreeMembershipRepo, TreeRepo, TreeScopeRepo, connect,
+    run_migrations, with_transaction,
 };
-use sea_orm::DatabaseConnection;
+use sea_orm::{ConnectionTrait, DatabaseConnection};
 use uuid::Uuid;
 
 /// Helper: create a fresh in-memory DB with migrations applied.
@@ -39,6 +46,56 @@ async fn create_person(db: &DatabaseConnection, tree_id: Uuid) -> Uuid {
     id
 }
 
+// ───────────────────────── Transaction tests ─────────────────────────
+
+#[tokio::test]
+async fn with_transaction_commits_or_rolls_back() {
+    use oxidgene_db::entities::tree;
+    use sea_orm::{ActiveModelTrait, Set};
+
+    let db = setup_db().await;
+    let insert_tree = |id: Uuid, fail: bool| {
+        let db = db.clone();
+        async move {
+            with_transaction(&db, |txn| {
+                Box::pin(async move {
+                    let now = chrono::Utc::now();
+                    tree::ActiveModel {
+                        id: Set(id),
+                        name: Set("Txn".into()),
+                        description: Set(None),
+                        sosa_root_person_id: Set(None),
+                        created_at: Set(now),
+                        updated_at: Set(now),
+                        version: Set(1),
+                        deleted_at: Set(None),
+                    }
+                    .insert(txn)
+                    .await
+                    .map_err(|e| OxidGeneError::Database(e.to_string()))?;
+                    if fail {
+                        return Err(OxidGeneError::Validation("halfway".into()));
+                    }
+                    Ok(id)
+                })
+            })
+            .await
+        }
+    };
+
+    let kept = Uuid::now_v7();
+    assert_eq!(insert_tree(kept, false).await.unwrap(), kept);
+    assert!(TreeRepo::get(&db, kept).await.is_ok());
+
+    let dropped = Uuid::now_v7();
+    let err = insert_tree(dropped, true).await.unwrap_err();
+    assert!(matches!(err, OxidGeneError::Validation(_)));
+    assert!(matches!(
+        TreeRepo::get(&db, dropped).await,
+        Err(OxidGeneError::NotFound { .. })
+    ));
+}
+
 // ───────────────────────── Tree tests ─────────────────────────
 
 #[tokio::test]
@@ -60,14 +117,14 @@ async fn tree_crud() {
     assert_eq!(fetched.id, id);
 
     // Update
-    let updated = TreeRepo::update(&db, id, Some("Renamed".into()), None, None)
+    let updated = TreeRepo::update(&db, id, Some("Renamed".into()), None, None, None)
         .await
         .unwrap();
     assert_eq!(updated.name, "Renamed");
     assert_eq!(updated.description.as_deref(), Some("desc")); // unchanged
 
     // Update description to None
-    let updated2 = TreeRepo::update(&db, id, None, Some(None), None)
+    let updated2 = TreeRepo::update(&db, id, None, Some(None), None, None)
         .await
         .unwrap();
     assert!(updated2.description.is_none());
@@ -146,10 +203,21 @@ async fn person_crud() {
     assert_eq!(fetched.sex, Sex::Female);
 
     // Update sex
-    let updated = PersonRepo::update(&db, id, Some(Sex::Male), None)
+    let updated = PersonRepo::update(&db, id, Some(Sex::Male), None, None, None)
         .await
         .unwrap();
     assert_eq!(updated.sex, Sex::Male);
+    assert_eq!(updated.version, 2);
+
+    // Update against a stale version
+    let err = PersonRepo::update(&db, id, Some(Sex::Female), None, None, Some(1))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OxidGeneError::Conflict(_)));
+    let updated = PersonRepo::update(&db, id, Some(Sex::Female), None, None, Some(2))
+        .await
+        .unwrap();
+    assert_eq!(updated.version, 3);
 
     // Soft-delete
     PersonRepo::delete(&db, id).await.unwrap();
@@ -236,6 +304,255 @@ async fn person_name_crud() {
     assert!(matches!(err, OxidGeneError::NotFound { .. }));
 }
 
+#[tokio::test]
+async fn person_name_phonetic_duplicates_and_backfill() {
+    let db = setup_db().await;
+    let tree_id = create_tree(&db).await;
+    let other_tree_id = create_tree(&db).await;
+
+    let mut ids = Vec::new();
+    for (tree_id, given, surname) in [
+        (tree_id, "Jean", "Dupont"),
+        (tree_id, "Jehan Marie", "Dupond"),
+        (tree_id, "Marie", "Dupont"),
+        (other_tree_id, "Jean", "Dupont"),
+    ] {
+        let person_id = create_person(&db, tree_id).await;
+        PersonNameRepo::create(
+            &db,
+            Uuid::now_v7(),
+            person_id,
+            NameType::Birth,
+            Some(given.into()),
+            Some(surname.into()),
+            None,
+            None,
+            None,
+            true,
+        )
+        .await
+        .unwrap();
+        ids.push(person_id);
+    }
+    let pairs = PersonNameRepo::phonetic_duplicates(&db, tree_id, 10)
+        .await
+        .unwrap();
+    assert_eq!(pairs, vec![(ids[0], ids[1])]);
+
+    // As left by a version that did not store the codes.
+    db.execute_unprepared("UPDATE person_name SET surname_phonetic = NULL, given_phonetic = NULL")
+        .await
+        .unwrap();
+    assert!(
+        PersonNameRepo::phonetic_duplicates(&db, tree_id, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(PersonNameRepo::backfill_phonetic(&db, 3).await.unwrap(), 4);
+    assert_eq!(PersonNameRepo::backfill_phonetic(&db, 3).await.unwrap(), 0);
+    let pairs = PersonNameRepo::phonetic_duplicates(&db, tree_id, 10)
+        .await
+        .unwrap();
+    assert_eq!(pairs, vec![(ids[0], ids[1])]);
+
+    PersonRepo::delete(&db, ids[1]).await.unwrap();
+    assert!(
+        PersonNameRepo::phonetic_duplicates(&db, tree_id, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn person_display_name_follows_names() {
+    let db = setup_db().await;
+    let tree_id = create_tree(&db).await;
+    let person_id = create_person(&db, tree_id).await;
+    let display_name = |db: DatabaseConnection| async move {
+        PersonRepo::get(&db, person_id).await.unwrap().display_name
+    };
+    assert_eq!(display_name(db.clone()).await, None);
+
+    let birth = PersonNameRepo::create(
+        &db,
+        Uuid::now_v7(),
+        person_id,
+        NameType::Birth,
+        Some("Marie".into()),
+        Some("Dupont".into()),
+        None,
+        None,
+        None,
+        false,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        display_name(db.clone()).await.as_deref(),
+        Some("Marie Dupont")
+    );
+
+    // The primary name wins over the first one.
+    let married = PersonNameRepo::create(
+        &db,
+        Uuid::now_v7(),
+        person_id,
+        NameType::Married,
+        Some("Marie".into()),
+        Some("Martin".into()),
+        None,
+        None,
+        None,
+        true,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        display_name(db.clone()).await.as_deref(),
+        Some("Marie Martin")
+    );
+
+    PersonNameRepo::update(
+        &db,
+        married.id,
+        None,
+        Some(Some("Anne".into())),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        display_name(db.clone()).await.as_deref(),
+        Some("Anne Martin")
+    );
+
+    PersonNameRepo::delete(&db, married.id).await.unwrap();
+    assert_eq!(
+        display_name(db.clone()).await.as_deref(),
+        Some("Marie Dupont")
+    );
+    PersonNameRepo::delete(&db, birth.id).await.unwrap();
+    assert_eq!(display_name(db.clone()).await, None);
+
+    PersonNameRepo::create(
+        &db,
+        Uuid::now_v7(),
+        person_id,
+        NameType::Birth,
+        Some("Jean".into()),
+        Some("Durand".into()),
+        None,
+        None,
+        None,
+        true,
+    )
+    .await
+    .unwrap();
+    // As left by a version that did not cache the name.
+    db.execute_unprepared("UPDATE person SET display_name = NULL")
+        .await
+        .unwrap();
+    assert_eq!(
+        PersonRepo::backfill_display_names(&db, 1).await.unwrap(),
+        (1, vec![tree_id])
+    );
+    assert_eq!(
+        PersonRepo::backfill_display_names(&db, 1).await.unwrap(),
+        (0, vec![])
+    );
+    assert_eq!(
+        display_name(db.clone()).await.as_deref(),
+        Some("Jean Durand")
+    );
+}
+
+#[tokio::test]
+async fn person_name_normalize_case() {
+    let db = setup_db().await;
+    let tree_id = create_tree(&db).await;
+    let add_name = |person_id: Uuid, given: &str, surname: &str| {
+        let db = db.clone();
+        let (given, surname) = (given.to_string(), surname.to_string());
+        async move {
+            PersonNameRepo::create(
+                &db,
+                Uuid::now_v7(),
+                person_id,
+                NameType::Birth,
+                Some(given),
+                Some(surname),
+                None,
+                None,
+                None,
+                true,
+            )
+            .await
+            .unwrap()
+        }
+    };
+    let jean = create_person(&db, tree_id).await;
+    let jean_name = add_name(jean, "JEAN-PIERRE", "DE LA FONTAINE").await;
+    let ok = create_person(&db, tree_id).await;
+    add_name(ok, "Marie", "Dupont").await;
+    let locked = create_person(&db, tree_id).await;
+    add_name(locked, "ANNE", "mcdonald").await;
+    PersonRepo::set_locked(&db, locked, true).await.unwrap();
+
+    let preview = PersonNameRepo::normalize_case(
+        &db,
+        tree_id,
+        NameCase::TitleCase,
+        NameCase::TitleCase,
+        true,
+        true,
+    )
+    .await
+    .unwrap();
+    assert_eq!(preview.changes.len(), 1);
+    assert_eq!(preview.skipped_locked, vec![locked]);
+    let change = &preview.changes[0];
+    assert_eq!(change.id, jean_name.id);
+    assert_eq!(change.new_given_names.as_deref(), Some("Jean-Pierre"));
+    assert_eq!(change.new_surname.as_deref(), Some("de La Fontaine"));
+    // A dry run writes nothing.
+    assert_eq!(
+        PersonNameRepo::get(&db, jean_name.id)
+            .await
+            .unwrap()
+            .surname,
+        Some("DE LA FONTAINE".into())
+    );
+
+    let applied =
+        PersonNameRepo::normalize_case(&db, tree_id, NameCase::Keep, NameCase::Upper, true, false)
+            .await
+            .unwrap();
+    assert_eq!(applied.changes.len(), 2);
+    let name = PersonNameRepo::get(&db, jean_name.id).await.unwrap();
+    assert_eq!(name.given_names.as_deref(), Some("JEAN-PIERRE"));
+    assert_eq!(name.surname.as_deref(), Some("de LA FONTAINE"));
+    assert_eq!(
+        PersonRepo::get(&db, ok)
+            .await
+            .unwrap()
+            .display_name
+            .as_deref(),
+        Some("Marie DUPONT")
+    );
+    assert_eq!(
+        PersonNameRepo::list_by_person(&db, locked).await.unwrap()[0]
+            .surname
+            .as_deref(),
+        Some("mcdonald")
+    );
+}
+
 // ───────────────────────── Family + Spouse + Child tests ─────────────────────────
 
 #[tokio::test]
@@ -304,6 +621,56 @@ async fn family_lifecycle() {
 
 // ───────────────────────── Event tests ─────────────────────────
 
+#[tokio::test]
+async fn event_calendar_dates() {
+    let db = setup_db().await;
+    let tree_id = create_tree(&db).await;
+    let person_id = create_person(&db, tree_id).await;
+    let ymd = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d);
+
+    // The escape sets the calendar and leaves the phrase.
+    let event = EventRepo::create(
+        &db,
+        Uuid::now_v7(),
+        tree_id,
+        EventType::Birth,
+        Some("@#DJULIAN@ 3 SEP 1752".into()),
+        None,
+        None,
+        None,
+        Some(person_id),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(event.calendar, Calendar::Julian);
+    assert_eq!(event.date_value.as_deref(), Some("3 SEP 1752"));
+    assert_eq!(event.date_sort, ymd(1752, 9, 14));
+
+    // Switching calendar re-reads the same phrase.
+    let event = EventRepo::update(
+        &db,
+        event.id,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(Calendar::Gregorian),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(event.date_value.as_deref(), Some("3 SEP 1752"));
+    assert_eq!(event.date_sort, ymd(1752, 9, 3));
+}
+
 #[tokio::test]
 async fn event_crud_and_filters() {
     let db = setup_db().await;
@@ -319,6 +686,7 @@ async fn event_crud_and_filters() {
         Some("1 JAN 1900".into()),
         Some(chrono::NaiveDate::from_ymd_opt(1900, 1, 1).unwrap()),
         None,
+        None,
         Some(person_id),
         None,
         Some("Born in Paris".into()),
@@ -336,6 +704,7 @@ async fn event_crud_and_filters() {
         None,
         None,
         None,
+        None,
         Some(person_id),
         None,
         None,
@@ -382,15 +751,19 @@ async fn event_crud_and_filters() {
         None,
         None,
         None,
+        None,
         Some(Some("Updated description".into())),
         None,
         None,
         None,
         None,
+        Some(Some("32y".into())),
+        None,
     )
     .await
     .unwrap();
     assert_eq!(updated.description.as_deref(), Some("Updated description"));
+    assert_eq!(updated.age.as_deref(), Some("32y"));
 
     // Soft-delete
     EventRepo::delete(&db, ev1_id).await.unwrap();
@@ -398,6 +771,76 @@ async fn event_crud_and_filters() {
     assert!(matches!(err, OxidGeneError::NotFound { .. }));
 }
 
+#[tokio::test]
+async fn event_backfill_date_sort() {
+    let db = setup_db().await;
+    let tree_id = create_tree(&db).await;
+    let person_id = create_person(&db, tree_id).await;
+
+    let mut ids = Vec::new();
+    for date_value in ["15 JAN 1842", "ABT 1900", "sometime"] {
+        let event = EventRepo::create(
+            &db,
+            Uuid::now_v7(),
+            tree_id,
+            EventType::Residence,
+            Some(date_value.into()),
+            None,
+            None,
+            None,
+            Some(person_id),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        // As left by a version that did not derive `date_sort`.
+        EventRepo::update(
+            &db,
+            event.id,
+            None,
+            None,
+            Some(None),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        ids.push(event.id);
+    }
+
+    let result = EventRepo::backfill_date_sort(&db, 2).await.unwrap();
+    assert_eq!(result.updated, 2);
+    assert_eq!(result.unparsed, 1);
+    assert_eq!(result.tree_ids, vec![tree_id]);
+    let date_sorts = [
+        EventRepo::get(&db, ids[0]).await.unwrap().date_sort,
+        EventRepo::get(&db, ids[1]).await.unwrap().date_sort,
+        EventRepo::get(&db, ids[2]).await.unwrap().date_sort,
+    ];
+    assert_eq!(
+        date_sorts,
+        [
+            chrono::NaiveDate::from_ymd_opt(1842, 1, 15),
+            chrono::NaiveDate::from_ymd_opt(1900, 1, 1),
+            None,
+        ]
+    );
+
+    // Idempotent: only the unparsable phrase is seen again.
+    let result = EventRepo::backfill_date_sort(&db, 2).await.unwrap();
+    assert_eq!(result.updated, 0);
+    assert_eq!(result.unparsed, 1);
+    assert!(result.tree_ids.is_empty());
+}
+
 // ───────────────────────── Place tests ─────────────────────────
 
 #[tokio::test]
@@ -447,7 +890,7 @@ async fn place_crud_and_search() {
     assert_eq!(conn3.total_count, 2);
 
     // Update
-    let updated = PlaceRepo::update(&db, p1_id, Some("Paris".into()), Some(None), None)
+    let updated = PlaceRepo::update(&db, p1_id, Some("Paris".into()), Some(None), None, None)
         .await
         .unwrap();
     assert_eq!(updated.name, "Paris");
@@ -460,6 +903,88 @@ async fn place_crud_and_search() {
     assert!(matches!(err, OxidGeneError::NotFound { .. }));
 }
 
+#[tokio::test]
+async fn place_dedupe_merges_by_normalized_name_and_coordinates() {
+    let db = setup_db().await;
+    let tree_id = create_tree(&db).await;
+    let person_id = create_person(&db, tree_id).await;
+
+    let survivor = Uuid::now_v7();
+    PlaceRepo::create(&db, survivor, tree_id, "Paris, France".into(), None, None)
+        .await
+        .unwrap();
+    let dup = Uuid::now_v7();
+    PlaceRepo::create(
+        &db,
+        dup,
+        tree_id,
+        "  paris,   FRANCE ".into(),
+        Some(48.8566),
+        Some(2.3522),
+    )
+    .await
+    .unwrap();
+    // Same name but far away: a different place, must survive.
+    let elsewhere = Uuid::now_v7();
+    PlaceRepo::create(
+        &db,
+        elsewhere,
+        tree_id,
+        "Paris, France".into(),
+        Some(33.6609),
+        Some(-95.5555),
+    )
+    .await
+    .unwrap();
+
+    let event = EventRepo::create(
+        &db,
+        Uuid::now_v7(),
+        tree_id,
+        EventType::Birth,
+        None,
+        None,
+        Some(dup),
+        None,
+        Some(person_id),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    let emigration = EventRepo::create(
+        &db,
+        Uuid::now_v7(),
+        tree_id,
+        EventType::Emigration,
+        None,
+        None,
+        Some(elsewhere),
+        Some(dup),
+        Some(person_id),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let merged = PlaceRepo::dedupe(&db, tree_id).await.unwrap();
+    assert_eq!(merged, 1);
+
+    let kept = PlaceRepo::get(&db, survivor).await.unwrap();
+    assert_eq!(kept.latitude, Some(48.8566)); // inherited from the duplicate
+    assert!(PlaceRepo::get(&db, dup).await.is_err());
+    assert!(PlaceRepo::get(&db, elsewhere).await.is_ok());
+    let repointed = EventRepo::get(&db, event.id).await.unwrap();
+    assert_eq!(repointed.place_id, Some(survivor));
+    let repointed = EventRepo::get(&db, emigration.id).await.unwrap();
+    assert_eq!(repointed.place_id, Some(elsewhere));
+    assert_eq!(repointed.place_id_to, Some(survivor));
+
+    // Idempotent.
+    assert_eq!(PlaceRepo::dedupe(&db, tree_id).await.unwrap(), 0);
+}
+
 // ───────────────────────── Source + Citation tests ─────────────────────────
 
 #[tokio::test]
@@ -493,6 +1018,7 @@ async fn source_and_citation_lifecycle() {
         Some(Some("Publisher X".into())),
         None,
         None,
+        None,
     )
     .await
     .unwrap();
@@ -518,6 +1044,8 @@ async fn source_and_citation_lifecycle() {
         Some("p. 42".into()),
         Confidence::High,
         Some("Baptism recorded".into()),
+        None,
+        None,
     )
     .await
     .unwrap();
@@ -583,9 +1111,15 @@ async fn media_and_media_link_lifecycle() {
     assert_eq!(fetched.title.as_deref(), Some("Family Photo"));
 
     // Update
-    let updated = MediaRepo::update(&db, media_id, None, Some(Some("A family gathering".into())))
-        .await
-        .unwrap();
+    let updated = MediaRepo::update(
+        &db,
+        media_id,
+        None,
+        Some(Some("A family gathering".into())),
+        None,
+    )
+    .await
+    .unwrap();
     assert_eq!(updated.description.as_deref(), Some("A family gathering"));
 
     // Create media link
@@ -627,44 +1161,420 @@ async fn note_crud() {
     let note_id = Uuid::now_v7();
     let note = NoteRepo::create(
         &db,
-        note_id,
+        note_id,
+        tree_id,
+        "Some important note".into(),
+        NoteFormat::Plain,
+        Some(person_id),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(note.text, "Some important note");
+    assert_eq!(note.person_id, Some(person_id));
+
+    // Get
+    let fetched = NoteRepo::get(&db, note_id).await.unwrap();
+    assert_eq!(fetched.text, "Some important note");
+
+    // List by entity (person)
+    let notes = NoteRepo::list_by_entity(&db, tree_id, Some(person_id), None, None, None)
+        .await
+        .unwrap();
+    assert_eq!(notes.len(), 1);
+
+    // List by entity (no filter = all in tree)
+    let notes_all = NoteRepo::list_by_entity(&db, tree_id, None, None, None, None)
+        .await
+        .unwrap();
+    assert_eq!(notes_all.len(), 1);
+
+    // Update text
+    let updated = NoteRepo::update(&db, note_id, Some("Updated note".into()), None, None)
+        .await
+        .unwrap();
+    assert_eq!(updated.text, "Updated note");
+
+    // Soft-delete
+    NoteRepo::delete(&db, note_id).await.unwrap();
+    let err = NoteRepo::get(&db, note_id).await.unwrap_err();
+    assert!(matches!(err, OxidGeneError::NotFound { .. }));
+}
+
+#[tokio::test]
+async fn note_format() {
+    let db = setup_db().await;
+    let tree_id = create_tree(&db).await;
+    let person_id = create_person(&db, tree_id).await;
+
+    let note_id = Uuid::now_v7();
+    let note = NoteRepo::create(
+        &db,
+        note_id,
+        tree_id,
+        "Some important note".into(),
+        NoteFormat::Plain,
+        Some(person_id),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(note.format, NoteFormat::Plain);
+
+    // Switch to markdown without touching the text
+    let updated = NoteRepo::update(&db, note_id, None, Some(NoteFormat::Markdown), None)
+        .await
+        .unwrap();
+    assert_eq!(updated.text, "Some important note");
+    assert_eq!(updated.format, NoteFormat::Markdown);
+
+    // Updating the text keeps the format
+    let updated = NoteRepo::update(&db, note_id, Some("# Title".into()), None, None)
+        .await
+        .unwrap();
+    assert_eq!(updated.format, NoteFormat::Markdown);
+    assert_eq!(
+        NoteRepo::get(&db, note_id).await.unwrap().format,
+        NoteFormat::Markdown
+    );
+}
+
+// ───────────────────────── ShareLink tests ─────────────────────────
+
+#[tokio::test]
+async fn share_link_resolve_and_revoke() {
+    let db = setup_db().await;
+    let tree_id = create_tree(&db).await;
+
+    let link_id = Uuid::now_v7();
+    let token = Uuid::now_v7();
+    ShareLinkRepo::create(&db, link_id, tree_id, token, true, None, None)
+        .await
+        .unwrap();
+    let resolved = ShareLinkRepo::resolve(&db, token).await.unwrap();
+    assert_eq!(resolved.tree_id, tree_id);
+    assert!(resolved.privatize);
+
+    // Expired links don't resolve but are still listed.
+    let expired = Uuid::now_v7();
+    let yesterday = chrono::Utc::now() - chrono::Duration::days(1);
+    ShareLinkRepo::create(
+        &db,
+        Uuid::now_v7(),
+        tree_id,
+        expired,
+        false,
+        Some(yesterday),
+        Some("alice".to_string()),
+    )
+    .await
+    .unwrap();
+    let err = ShareLinkRepo::resolve(&db, expired).await.unwrap_err();
+    assert!(matches!(err, OxidGeneError::NotFound { .. }));
+    let links = ShareLinkRepo::list_by_tree(&db, tree_id).await.unwrap();
+    assert_eq!(links.len(), 2);
+    assert_eq!(links[0].created_by, None);
+    assert_eq!(links[1].created_by.as_deref(), Some("alice"));
+
+    // Revoke: only within the owning tree.
+    let other_tree = create_tree(&db).await;
+    let err = ShareLinkRepo::delete(&db, other_tree, link_id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OxidGeneError::NotFound { .. }));
+    ShareLinkRepo::delete(&db, tree_id, link_id).await.unwrap();
+    let err = ShareLinkRepo::resolve(&db, token).await.unwrap_err();
+    assert!(matches!(err, OxidGeneError::NotFound { .. }));
+}
+
+// ───────────────────────── TreeMembership tests ─────────────────────────
+
+#[tokio::test]
+async fn tree_membership_keeps_an_owner() {
+    let db = setup_db().await;
+    let tree_id = create_tree(&db).await;
+    create_tree(&db).await;
+    assert!(!TreeMembershipRepo::has_members(&db, tree_id).await.unwrap());
+
+    TreeMembershipRepo::set(&db, tree_id, "alice", TreeRole::Owner)
+        .await
+        .unwrap();
+    TreeMembershipRepo::set(&db, tree_id, "bob", TreeRole::Viewer)
+        .await
+        .unwrap();
+    let bob = TreeMembershipRepo::set(&db, tree_id, "bob", TreeRole::Editor)
+        .await
+        .unwrap();
+    assert_eq!(bob.role, TreeRole::Editor);
+    assert_eq!(
+        TreeMembershipRepo::role(&db, tree_id, "bob").await.unwrap(),
+        Some(TreeRole::Editor)
+    );
+    assert_eq!(
+        TreeMembershipRepo::role(&db, tree_id, "carol")
+            .await
+            .unwrap(),
+        None
+    );
+
+    // The last owner can be neither demoted nor removed.
+    let err = TreeMembershipRepo::set(&db, tree_id, "alice", TreeRole::Editor)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OxidGeneError::Conflict(_)));
+    let err = TreeMembershipRepo::remove(&db, tree_id, "alice")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OxidGeneError::Conflict(_)));
+    TreeMembershipRepo::set(&db, tree_id, "bob", TreeRole::Owner)
+        .await
+        .unwrap();
+    TreeMembershipRepo::remove(&db, tree_id, "alice")
+        .await
+        .unwrap();
+    let err = TreeMembershipRepo::remove(&db, tree_id, "alice")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OxidGeneError::NotFound { .. }));
+
+    // Only members see a tree: Alice sees none, not even the tree without
+    // members; Bob sees his.
+    let params = PaginationParams::default();
+    for (actor, expected) in [("alice", vec![]), ("bob", vec![tree_id])] {
+        let trees = TreeRepo::list_for_actor(&db, &params, actor).await.unwrap();
+        let ids: Vec<Uuid> = trees.edges.into_iter().map(|e| e.node.id).collect();
+        assert_eq!(ids, expected, "{actor}");
+        assert_eq!(trees.total_count, expected.len() as i64);
+    }
+}
+
+// ───────────────────────── TreeScope tests ─────────────────────────
+
+#[tokio::test]
+async fn tree_scope_resolves_parent_trees() {
+    let db = setup_db().await;
+    let tree_id = create_tree(&db).await;
+    let other_tree = create_tree(&db).await;
+    let person_id = create_person(&db, tree_id).await;
+    let name_id = Uuid::now_v7();
+    PersonNameRepo::create(
+        &db,
+        name_id,
+        person_id,
+        NameType::Birth,
+        Some("Jean".into()),
+        Some("Dupont".into()),
+        None,
+        None,
+        None,
+        true,
+    )
+    .await
+    .unwrap();
+
+    for (entity, id) in [
+        (TreeEntity::Person, person_id),
+        (TreeEntity::PersonName, name_id),
+    ] {
+        assert_eq!(
+            TreeScopeRepo::tree_of(&db, entity, id).await.unwrap(),
+            Some(tree_id)
+        );
+        TreeScopeRepo::ensure_in_tree(&db, tree_id, entity, id)
+            .await
+            .unwrap();
+        let err = TreeScopeRepo::ensure_in_tree(&db, other_tree, entity, id)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, OxidGeneError::NotFound { .. }), "{entity:?}");
+    }
+
+    // An unknown id is left for the caller to report.
+    let unknown = Uuid::now_v7();
+    assert_eq!(
+        TreeScopeRepo::tree_of(&db, TreeEntity::Event, unknown)
+            .await
+            .unwrap(),
+        None
+    );
+    TreeScopeRepo::ensure_in_tree(&db, other_tree, TreeEntity::Event, unknown)
+        .await
+        .unwrap();
+}
+
+// ───────────────────────── PersonTag tests ─────────────────────────
+
+#[tokio::test]
+async fn person_tag_counts_skip_deleted_persons() {
+    let db = setup_db().await;
+    let tree_id = create_tree(&db).await;
+    let kept = create_person(&db, tree_id).await;
+    let deleted = create_person(&db, tree_id).await;
+
+    for person_id in [kept, deleted] {
+        PersonTagRepo::add(
+            &db,
+            Uuid::now_v7(),
+            tree_id,
+            person_id,
+            "DNA confirmed".into(),
+        )
+        .await
+        .unwrap();
+    }
+    PersonRepo::delete(&db, deleted).await.unwrap();
+
+    let counts = PersonTagRepo::counts(&db, tree_id).await.unwrap();
+    assert_eq!(counts.len(), 1);
+    assert_eq!(counts[0].tag, "DNA confirmed");
+    assert_eq!(counts[0].count, 1);
+
+    PersonTagRepo::remove(&db, kept, "DNA confirmed")
+        .await
+        .unwrap();
+    assert!(
+        PersonTagRepo::counts(&db, tree_id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+// ───────────────────────── ResearchTask tests ─────────────────────────
+
+#[tokio::test]
+async fn research_task_crud_and_filters() {
+    let db = setup_db().await;
+    let tree_id = create_tree(&db).await;
+    let person_id = create_person(&db, tree_id).await;
+    let family_id = Uuid::now_v7();
+    FamilyRepo::create(&db, family_id, tree_id).await.unwrap();
+    let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d);
+
+    let undated = ResearchTaskRepo::create(
+        &db,
+        Uuid::now_v7(),
+        tree_id,
+        Some(person_id),
+        None,
+        "Find the burial".into(),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(undated.status, TaskStatus::Open);
+    let due = ResearchTaskRepo::create(
+        &db,
+        Uuid::now_v7(),
         tree_id,
-        "Some important note".into(),
         Some(person_id),
         None,
+        "Order the marriage record".into(),
+        Some("Archives of Lyon".into()),
+        date(2026, 12, 1),
+    )
+    .await
+    .unwrap();
+    let on_family = ResearchTaskRepo::create(
+        &db,
+        Uuid::now_v7(),
+        tree_id,
         None,
+        Some(family_id),
+        "Check the 1851 census".into(),
         None,
+        date(2027, 1, 15),
     )
     .await
     .unwrap();
-    assert_eq!(note.text, "Some important note");
-    assert_eq!(note.person_id, Some(person_id));
-
-    // Get
-    let fetched = NoteRepo::get(&db, note_id).await.unwrap();
-    assert_eq!(fetched.text, "Some important note");
 
-    // List by entity (person)
-    let notes = NoteRepo::list_by_entity(&db, tree_id, Some(person_id), None, None, None)
+    // Exactly one owner, in the tree.
+    for (pid, fid) in [(None, None), (Some(person_id), Some(family_id))] {
+        let err = ResearchTaskRepo::create(
+            &db,
+            Uuid::now_v7(),
+            tree_id,
+            pid,
+            fid,
+            "x".into(),
+            None,
+            None,
+        )
         .await
-        .unwrap();
-    assert_eq!(notes.len(), 1);
-
-    // List by entity (no filter = all in tree)
-    let notes_all = NoteRepo::list_by_entity(&db, tree_id, None, None, None, None)
+        .unwrap_err();
+        assert!(matches!(err, OxidGeneError::Validation(_)));
+    }
+    let other_tree = create_tree(&db).await;
+    let err = ResearchTaskRepo::create(
+        &db,
+        Uuid::now_v7(),
+        other_tree,
+        Some(person_id),
+        None,
+        "x".into(),
+        None,
+        None,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        OxidGeneError::NotFound {
+            entity: "Person",
+            ..
+        }
+    ));
+
+    // Soonest due first, undated last.
+    let all = ResearchTaskRepo::list(&db, tree_id, &ResearchTaskFilter::default())
         .await
         .unwrap();
-    assert_eq!(notes_all.len(), 1);
+    let ids: Vec<Uuid> = all.iter().map(|t| t.id).collect();
+    assert_eq!(ids, vec![due.id, on_family.id, undated.id]);
 
-    // Update text
-    let updated = NoteRepo::update(&db, note_id, Some("Updated note".into()))
-        .await
-        .unwrap();
-    assert_eq!(updated.text, "Updated note");
+    let done = ResearchTaskRepo::update(
+        &db,
+        due.id,
+        None,
+        Some(None),
+        Some(TaskStatus::Done),
+        None,
+        Some(1),
+    )
+    .await
+    .unwrap();
+    assert_eq!(done.status, TaskStatus::Done);
+    assert_eq!(done.description, None);
+    assert_eq!(done.version, 2);
+    let open = ResearchTaskRepo::list(
+        &db,
+        tree_id,
+        &ResearchTaskFilter {
+            status: Some(TaskStatus::Open),
+            person_id: Some(person_id),
+            family_id: None,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].id, undated.id);
 
-    // Soft-delete
-    NoteRepo::delete(&db, note_id).await.unwrap();
-    let err = NoteRepo::get(&db, note_id).await.unwrap_err();
+    // Tasks of a deleted person are hidden; deleted tasks are gone.
+    PersonRepo::delete(&db, person_id).await.unwrap();
+    ResearchTaskRepo::delete(&db, on_family.id).await.unwrap();
+    assert!(
+        ResearchTaskRepo::list(&db, tree_id, &ResearchTaskFilter::default())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let err = ResearchTaskRepo::get(&db, on_family.id).await.unwrap_err();
     assert!(matches!(err, OxidGeneError::NotFound { .. }));
 }
 
@@ -713,7 +1623,7 @@ async fn person_ancestry_queries() {
         .unwrap();
 
     // Ancestors of child (excludes self-reference)
-    let ancestors = PersonAncestryRepo::ancestors(&db, child_id, None)
+    let ancestors = PersonAncestryRepo::ancestors(&db, child_id, 10)
         .await
         .unwrap();
     assert_eq!(ancestors.len(), 2);
@@ -724,14 +1634,14 @@ async fn person_ancestry_queries() {
     assert_eq!(ancestors[1].ancestor_id, grandparent_id);
 
     // Ancestors with max_depth=1
-    let ancestors_limited = PersonAncestryRepo::ancestors(&db, child_id, Some(1))
+    let ancestors_limited = PersonAncestryRepo::ancestors(&db, child_id, 1)
         .await
         .unwrap();
     assert_eq!(ancestors_limited.len(), 1);
     assert_eq!(ancestors_limited[0].ancestor_id, parent_id);
 
     // Descendants of grandparent (excludes self-reference)
-    let descendants = PersonAncestryRepo::descendants(&db, grandparent_id, None)
+    let descendants = PersonAncestryRepo::descendants(&db, grandparent_id, 10)
         .await
         .unwrap();
     assert_eq!(descendants.len(), 2);
@@ -746,12 +1656,155 @@ async fn person_ancestry_queries() {
         .unwrap();
     assert_eq!(deleted, 3); // self-ref + parent + grandparent
 
-    let ancestors_after = PersonAncestryRepo::ancestors(&db, child_id, None)
+    let ancestors_after = PersonAncestryRepo::ancestors(&db, child_id, 10)
         .await
         .unwrap();
     assert_eq!(ancestors_after.len(), 0);
 }
 
+/// A live family and its spouse and child links, as (link id, person index).
+type TestFamily = (Uuid, Vec<(Uuid, usize)>, Vec<(Uuid, usize)>);
+
+/// Every closure row below depth 0, as (ancestor, descendant, depth).
+async fn ancestry_rows(db: &DatabaseConnection, persons: &[Uuid]) -> Vec<(Uuid, Uuid, i32)> {
+    let mut rows = Vec::new();
+    for &id in persons {
+        for r in PersonAncestryRepo::descendants(db, id, 10).await.unwrap() {
+            rows.push((r.ancestor_id, r.descendant_id, r.depth));
+        }
+    }
+    rows.sort();
+    rows
+}
+
+#[tokio::test]
+async fn ancestry_refresh_matches_rebuild() {
+    // Random link edits on random genealogies, each followed by the
+    // refresh its handler runs; the closure must match a full rebuild.
+    for seed in 1..=12u64 {
+        let db = setup_db().await;
+        let tree_id = create_tree(&db).await;
+        let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let mut rand = move |n: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % n as u64) as usize
+        };
+
+        // Persons are ordered: parents always come before their children,
+        // so the links never form a cycle.
+        let mut persons = Vec::new();
+        for _ in 0..12 {
+            persons.push(create_person(&db, tree_id).await);
+        }
+        let mut deleted_persons = Vec::new();
+        let mut families: Vec<TestFamily> = Vec::new();
+
+        for _ in 0..60 {
+            match rand(10) {
+                0 | 1 => {
+                    let family_id = Uuid::now_v7();
+                    FamilyRepo::create(&db, family_id, tree_id).await.unwrap();
+                    families.push((family_id, Vec::new(), Vec::new()));
+                }
+                2 | 3 if !families.is_empty() => {
+                    let f = rand(families.len());
+                    let (family_id, spouses, children) = &mut families[f];
+                    let limit = children.iter().map(|c| c.1).min().unwrap_or(persons.len());
+                    let p = rand(persons.len());
+                    if p < limit && spouses.len() < 2 && !spouses.iter().any(|s| s.1 == p) {
+                        let link_id = Uuid::now_v7();
+                        FamilySpouseRepo::create(
+                            &db,
+                            link_id,
+                            *family_id,
+                            persons[p],
+                            SpouseRole::Partner,
+                            0,
+                        )
+                        .await
+                        .unwrap();
+                        spouses.push((link_id, p));
+                        PersonAncestryRepo::refresh_family(&db, tree_id, *family_id)
+                            .await
+                            .unwrap();
+                    }
+                }
+                4..=6 if !families.is_empty() => {
+                    let f = rand(families.len());
+                    let (family_id, spouses, children) = &mut families[f];
+                    let floor = spouses.iter().map(|s| s.1).max();
+                    let c = rand(persons.len());
+                    if floor.is_none_or(|floor| c > floor) && !children.iter().any(|l| l.1 == c) {
+                        let link_id = Uuid::now_v7();
+                        FamilyChildRepo::create(
+                            &db,
+                            link_id,
+                            *family_id,
+                            persons[c],
+                            ChildType::Biological,
+                            0,
+                        )
+                        .await
+                        .unwrap();
+                        children.push((link_id, c));
+                        PersonAncestryRepo::refresh(&db, tree_id, &[persons[c]])
+                            .await
+                            .unwrap();
+                    }
+                }
+                7 if !families.is_empty() => {
+                    let f = rand(families.len());
+                    let (family_id, spouses, _) = &mut families[f];
+                    if !spouses.is_empty() {
+                        let (link_id, _) = spouses.remove(rand(spouses.len()));
+                        FamilySpouseRepo::delete(&db, link_id).await.unwrap();
+                        PersonAncestryRepo::refresh_family(&db, tree_id, *family_id)
+                            .await
+                            .unwrap();
+                    }
+                }
+                8 if !families.is_empty() => {
+                    let f = rand(families.len());
+                    let (_, _, children) = &mut families[f];
+                    if !children.is_empty() {
+                        let (link_id, c) = children.remove(rand(children.len()));
+                        FamilyChildRepo::delete(&db, link_id).await.unwrap();
+                        PersonAncestryRepo::refresh(&db, tree_id, &[persons[c]])
+                            .await
+                            .unwrap();
+                    }
+                }
+                9 if !families.is_empty() => {
+                    if rand(2) == 0 {
+                        let (family_id, _, _) = families.remove(rand(families.len()));
+                        FamilyRepo::delete(&db, family_id).await.unwrap();
+                        PersonAncestryRepo::refresh_family(&db, tree_id, family_id)
+                            .await
+                            .unwrap();
+                    } else {
+                        let p = rand(persons.len());
+                        if !deleted_persons.contains(&p) {
+                            PersonRepo::delete(&db, persons[p]).await.unwrap();
+                            PersonAncestryRepo::refresh(&db, tree_id, &[persons[p]])
+                                .await
+                                .unwrap();
+                            deleted_persons.push(p);
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            let refreshed = ancestry_rows(&db, &persons).await;
+            PersonAncestryRepo::rebuild(&db, tree_id).await.unwrap();
+            let rebuilt = ancestry_rows(&db, &persons).await;
+            assert_eq!(refreshed, rebuilt, "seed {seed}");
+        }
+    }
+}
+
 // ───────────────────────── Pagination edge cases ─────────────────────────
 
 #[tokio::test]
@@ -795,14 +1848,16 @@ async fn pagination_clamps_page_size() {
     let conn = TreeRepo::list(&db, &params).await.unwrap();
     assert_eq!(conn.edges.len(), 1);
     assert!(conn.page_info.has_next_page);
+    assert_eq!(conn.page_info.limit, 1);
 
-    // first=200 should be clamped to MAX_PAGE_SIZE (100)
+    // Large pages are honored as given: the API caps `first` beforehand
     let params2 = PaginationParams {
         first: 200,
         after: None,
     };
     let conn2 = TreeRepo::list(&db, &params2).await.unwrap();
     assert_eq!(conn2.edges.len(), 3); // only 3 exist
+    assert_eq!(conn2.page_info.limit, 200);
 }
 
 // ───────────────────────── Delete non-existent returns NotFound ─────────────────────────
@@ -983,6 +2038,7 @@ async fn dictionary_occupations_groups_by_person_and_ignores_other_event_types()
         None,
         None,
         None,
+        None,
         Some(p1),
         None,
         Some("Farmer".into()),
@@ -997,6 +2053,7 @@ async fn dictionary_occupations_groups_by_person_and_ignores_other_event_types()
         None,
         None,
         None,
+        None,
         Some(p1),
         None,
         Some("Farmer".into()),
@@ -1012,6 +2069,7 @@ async fn dictionary_occupations_groups_by_person_and_ignores_other_event_types()
         None,
         None,
         None,
+        None,
         Some(p2),
         None,
         Some("Baker".into()),
@@ -1028,6 +2086,7 @@ async fn dictionary_occupations_groups_by_person_and_ignores_other_event_types()
         None,
         None,
         None,
+        None,
         Some(p2),
         None,
         Some("Farmer".into()),
@@ -1044,6 +2103,7 @@ async fn dictionary_occupations_groups_by_person_and_ignores_other_event_types()
         None,
         None,
         None,
+        None,
         Some(p2),
         None,
         Some("  ".into()),
@@ -1061,6 +2121,7 @@ async fn dictionary_occupations_groups_by_person_and_ignores_other_event_types()
         None,
         None,
         None,
+        None,
         Some(p2),
         None,
         Some("Blacksmith".into()),
@@ -1126,6 +2187,8 @@ async fn dictionary_sources_with_usage_counts_citations() {
         None,
         Confidence::High,
         None,
+        None,
+        None,
     )
     .await
     .unwrap();
@@ -1139,6 +2202,7 @@ async fn dictionary_sources_with_usage_counts_citations() {
         None,
         None,
         None,
+        None,
         Some(person_id),
         None,
         None,
@@ -1155,6 +2219,8 @@ async fn dictionary_sources_with_usage_counts_citations() {
         None,
         Confidence::Medium,
         None,
+        None,
+        None,
     )
     .await
     .unwrap();
@@ -1379,6 +2445,7 @@ async fn dictionary_places_with_usage_counts_events() {
         None,
         None,
         Some(used_place),
+        None,
         Some(person_id),
         None,
         None,
@@ -1393,6 +2460,7 @@ async fn dictionary_places_with_usage_counts_events() {
         None,
         None,
         Some(used_place),
+        None,
         Some(person_id),
         None,
         None,
@@ -1416,3 +2484,360 @@ async fn dictionary_places_with_usage_counts_events() {
         .unwrap();
     assert_eq!(usage, vec![person_id]);
 }
+
+// ───────────────────────── Integrity check tests ─────────────────────────
+
+#[tokio::test]
+async fn integrity_check_reports_orphans() {
+    let db = setup_db().await;
+    let tree_id = create_tree(&db).await;
+
+    let named = |person_id: Uuid| {
+        let db = db.clone();
+        async move {
+            PersonNameRepo::create(
+                &db,
+                Uuid::now_v7(),
+                person_id,
+                NameType::Birth,
+                Some("Jean".into()),
+                Some("Dupont".into()),
+                None,
+                None,
+                None,
+                true,
+            )
+            .await
+            .unwrap();
+        }
+    };
+
+    // Parent/child linked by a family, but no ancestry rows: closure missing.
+    let father = create_person(&db, tree_id).await;
+    let child = create_person(&db, tree_id).await;
+    named(father).await;
+    named(child).await;
+    let family_id = Uuid::now_v7();
+    FamilyRepo::create(&db, family_id, tree_id).await.unwrap();
+    FamilySpouseRepo::create(
+        &db,
+        Uuid::now_v7(),
+        family_id,
+        father,
+        SpouseRole::Husband,
+        0,
+    )
+    .await
+    .unwrap();
+    FamilyChildRepo::create(
+        &db,
+        Uuid::now_v7(),
+        family_id,
+        child,
+        ChildType::Biological,
+        0,
+    )
+    .await
+    .unwrap();
+    // A reversed ancestry row no family link supports.
+    PersonAncestryRepo::create(&db, Uuid::now_v7(), tree_id, child, father, 1)
+        .await
+        .unwrap();
+
+    // A person without a name, and an empty family.
+    let nameless = create_person(&db, tree_id).await;
+    let empty_family = Uuid::now_v7();
+    FamilyRepo::create(&db, empty_family, tree_id)
+        .await
+        .unwrap();
+
+    // A family whose only child was deleted.
+    let gone = create_person(&db, tree_id).await;
+    named(gone).await;
+    let orphan_family = Uuid::now_v7();
+    FamilyRepo::create(&db, orphan_family, tree_id)
+        .await
+        .unwrap();
+    let link_id = Uuid::now_v7();
+    FamilyChildRepo::create(&db, link_id, orphan_family, gone, ChildType::Biological, 0)
+        .await
+        .unwrap();
+    PersonRepo::delete(&db, gone).await.unwrap();
+
+    // A citation of a deleted source.
+    let source_id = Uuid::now_v7();
+    SourceRepo::create(
+        &db,
+        source_id,
+        tree_id,
+        "Register".into(),
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    let citation_id = Uuid::now_v7();
+    CitationRepo::create(
+        &db,
+        citation_id,
+        source_id,
+        Some(father),
+        None,
+        None,
+        None,
+        Confidence::High,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    SourceRepo::delete(&db, source_id).await.unwrap();
+
+    let issues = IntegrityRepo::check(&db, tree_id).await.unwrap();
+    let found =
+        |kind: IssueKind, id: Uuid| issues.iter().any(|i| i.kind == kind && i.entity_id == id);
+
+    assert!(found(IssueKind::CitationDeletedSource, citation_id));
+    assert!(found(IssueKind::FamilyChildMissingPerson, link_id));
+    assert!(found(IssueKind::PersonWithoutName, nameless));
+    assert!(found(IssueKind::FamilyWithoutMembers, empty_family));
+    assert!(found(IssueKind::FamilyWithoutMembers, orphan_family));
+    assert!(found(IssueKind::AncestryMissing, child));
+    assert!(issues.iter().any(|i| i.kind == IssueKind::AncestryStale));
+    assert!(!found(IssueKind::FamilyWithoutMembers, family_id));
+    assert_eq!(issues.len(), 7);
+
+    // Errors are listed before warnings.
+    let first_warning = issues
+        .iter()
+        .position(|i| i.severity == Severity::Warning)
+        .unwrap();
+    assert!(
+        issues[..first_warning]
+            .iter()
+            .all(|i| i.severity == Severity::Error)
+    );
+    assert!(
+        issues[first_warning..]
+            .iter()
+            .all(|i| i.severity == Severity::Warning)
+    );
+}
+
+// ───────────────────────── Person move tests ─────────────────────────
+
+#[tokio::test]
+async fn person_move_to_another_tree() {
+    let db = setup_db().await;
+    let source_tree = create_tree(&db).await;
+    let target_tree = create_tree(&db).await;
+
+    // A person with a father and a child in the source tree.
+    let person_id = create_person(&db, source_tree).await;
+    let father = RelativeRepo::add(
+        &db,
+        source_tree,
+        person_id,
+        RelativeKind::Father,
+        NewRelative::default(),
+    )
+    .await
+    .unwrap();
+    let child = RelativeRepo::add(
+        &db,
+        source_tree,
+        person_id,
+        RelativeKind::Child,
+        NewRelative::default(),
+    )
+    .await
+    .unwrap();
+    let child_id = child.person.id;
+    assert_eq!(
+        PersonAncestryRepo::ancestors(&db, child_id, 10)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+
+    // Their birth in Paris, witnessed, with a note and a cited source.
+    let paris = PlaceRepo::create(&db, Uuid::now_v7(), source_tree, "Paris".into(), None, None)
+        .await
+        .unwrap();
+    let target_paris =
+        PlaceRepo::create(&db, Uuid::now_v7(), target_tree, "Paris".into(), None, None)
+            .await
+            .unwrap();
+    let birth = EventRepo::create(
+        &db,
+        Uuid::now_v7(),
+        source_tree,
+        EventType::Birth,
+        None,
+        None,
+        Some(paris.id),
+        None,
+        Some(person_id),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    let witness = create_person(&db, source_tree).await;
+    EventParticipantRepo::create(
+        &db,
+        Uuid::now_v7(),
+        birth.id,
+        witness,
+        ParticipantRole::Witness,
+        None,
+        0,
+    )
+    .await
+    .unwrap();
+    let note = NoteRepo::create(
+        &db,
+        Uuid::now_v7(),
+        source_tree,
+        "Born at home".into(),
+        NoteFormat::Plain,
+        None,
+        Some(birth.id),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    let register = SourceRepo::create(
+        &db,
+        Uuid::now_v7(),
+        source_tree,
+        "Parish Register".into(),
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    let citation = CitationRepo::create(
+        &db,
+        Uuid::now_v7(),
+        register.id,
+        Some(person_id),
+        None,
+        None,
+        Some("p. 42".into()),
+        Confidence::High,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let moved = PersonMoveRepo::move_person(&db, source_tree, person_id, target_tree)
+        .await
+        .unwrap();
+    assert_eq!(moved.person.tree_id, target_tree);
+    assert_eq!(moved.person.version, 2);
+    // Two family links and the witness were left behind.
+    assert_eq!(moved.warnings.len(), 3, "{:?}", moved.warnings);
+
+    // Family links and the source tree closure no longer involve them.
+    assert!(
+        FamilySpouseRepo::list_by_person(&db, person_id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        FamilyChildRepo::list_by_person(&db, person_id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        PersonAncestryRepo::ancestors(&db, child_id, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        PersonRepo::get(&db, father.person.id)
+            .await
+            .unwrap()
+            .tree_id,
+        source_tree
+    );
+
+    // Owned records followed, pointing at the target tree's place and a
+    // copy of the source.
+    let birth = EventRepo::get(&db, birth.id).await.unwrap();
+    assert_eq!(birth.tree_id, target_tree);
+    assert_eq!(birth.place_id, Some(target_paris.id));
+    assert!(
+        EventParticipantRepo::list_by_event(&db, birth.id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        NoteRepo::get(&db, note.id).await.unwrap().tree_id,
+        target_tree
+    );
+    let citation = CitationRepo::get(&db, citation.id).await.unwrap();
+    let copied = SourceRepo::get(&db, citation.source_id).await.unwrap();
+    assert_eq!(copied.tree_id, target_tree);
+    assert_eq!(copied.title, "Parish Register");
+    assert_eq!(
+        SourceRepo::get(&db, register.id).await.unwrap().tree_id,
+        source_tree
+    );
+
+    // Moving again, or from the wrong tree, is refused.
+    let err = PersonMoveRepo::move_person(&db, target_tree, person_id, target_tree)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OxidGeneError::Validation(_)));
+    let err = PersonMoveRepo::move_person(&db, source_tree, person_id, target_tree)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        OxidGeneError::NotFound {
+            entity: "Person",
+            ..
+        }
+    ));
+
+    // A failed move changes nothing.
+    TreeRepo::delete(&db, source_tree).await.unwrap();
+    let err = PersonMoveRepo::move_person(&db, target_tree, person_id, source_tree)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        OxidGeneError::NotFound { entity: "Tree", .. }
+    ));
+    assert_eq!(
+        PersonRepo::get(&db, person_id).await.unwrap().tree_id,
+        target_tree
+    );
+
+    // A locked person stays where they are.
+    let other_tree = create_tree(&db).await;
+    PersonRepo::set_locked(&db, person_id, true).await.unwrap();
+    let err = PersonMoveRepo::move_person(&db, target_tree, person_id, other_tree)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OxidGeneError::Locked { .. }));
+    assert_eq!(
+        PersonRepo::get(&db, person_id).await.unwrap().tree_id,
+        target_tree
+    );
+}
@@ -6,6 +6,7 @@ use std::collections::HashMap;
 
 use ged_io::GedcomWriter;
 use ged_io::types::GedcomData;
+use ged_io::types::age::{Age as GedAge, AgeModifier};
 use ged_io::types::date::Date;
 use ged_io::types::event::Event as GedEvent;
 use ged_io::types::event::detail::Detail as GedDetail;
@@ -31,26 +32,34 @@ use ged_io::types::source::Source as GedSource;
 use ged_io::types::source::citation::Citation as GedCitation;
 use ged_io::types::source::citation::CitationSource;
 use ged_io::types::source::quay::CertaintyAssessment;
+use ged_io::types::submitter::Submitter;
 use uuid::Uuid;
 
+use oxidgene_core::calendar::with_calendar;
 use oxidgene_core::types::{
-    Citation, Event, EventWitness, Family, FamilyChild, FamilySpouse, Media, MediaLink, Note,
-    Person, PersonName, Place, Source,
+    Citation, Event, EventParticipant, ExternalId, Family, FamilyChild, FamilySpouse, Media,
+    MediaLink, Note, Person, PersonName, Place, Source,
 };
-use oxidgene_core::{ChildType, Confidence, EventType, NameType, Sex, SpouseRole};
+use oxidgene_core::{
+    ChildType, Confidence, EventType, ExternalIdKind, FactType, NameType, ParticipantRole, Sex,
+    SpouseRole,
+};
+
+use crate::{ConformanceIssue, ExportOptions, ExportResult};
 
-use crate::ExportResult;
+/// Xref of the single submitter record the header points to.
+const SUBMITTER_XREF: &str = "@U1@";
 
 /// Export domain model entities to a GEDCOM 5.5.1 string.
 ///
 /// All entity slices should belong to the same tree.
 ///
-/// `merge_occupations` collapses every `EventType::Occupation` event for a
-/// person back into a single `OCCU` tag (values joined with `", "`) instead
-/// of one `OCCU` tag per event. Some importers — Geneanet in particular —
-/// only support a single profession field per individual, so this is an
-/// opt-in, lossy compatibility option; leave it `false` to keep the
-/// lossless one-`OCCU`-per-profession export.
+/// `options` set the header metadata (submitter, software, language) and
+/// whether occupations are merged into one `OCCU` tag per person (see
+/// [`ExportOptions`]).
+///
+/// `external_ids` are written back as `_UID` and `REFN` on their `INDI` and
+/// `FAM` records.
 ///
 /// # Errors
 ///
@@ -63,14 +72,61 @@ pub fn export_gedcom(
     family_spouses: &[FamilySpouse],
     family_children: &[FamilyChild],
     events: &[Event],
-    event_witnesses: &[EventWitness],
+    event_participants: &[EventParticipant],
     places: &[Place],
     sources: &[Source],
     citations: &[Citation],
     media: &[Media],
     media_links: &[MediaLink],
     notes: &[Note],
-    merge_occupations: bool,
+    external_ids: &[ExternalId],
+    options: &ExportOptions,
+) -> Result<ExportResult, String> {
+    export_gedcom_with_progress(
+        persons,
+        person_names,
+        families,
+        family_spouses,
+        family_children,
+        events,
+        event_participants,
+        places,
+        sources,
+        citations,
+        media,
+        media_links,
+        notes,
+        external_ids,
+        options,
+        &mut || {},
+    )
+}
+
+/// [`export_gedcom`], calling `on_record` once per top-level record built:
+/// each source, media object, individual and family, so a caller can report
+/// progress against the sum of those counts.
+///
+/// # Errors
+///
+/// Returns `Err` if the GEDCOM writer encounters an I/O error.
+#[allow(clippy::too_many_arguments)]
+pub fn export_gedcom_with_progress(
+    persons: &[Person],
+    person_names: &[PersonName],
+    families: &[Family],
+    family_spouses: &[FamilySpouse],
+    family_children: &[FamilyChild],
+    events: &[Event],
+    event_participants: &[EventParticipant],
+    places: &[Place],
+    sources: &[Source],
+    citations: &[Citation],
+    media: &[Media],
+    media_links: &[MediaLink],
+    notes: &[Note],
+    external_ids: &[ExternalId],
+    options: &ExportOptions,
+    on_record: &mut dyn FnMut(),
 ) -> Result<ExportResult, String> {
     let mut warnings: Vec<String> = Vec::new();
 
@@ -116,10 +172,10 @@ pub fn export_gedcom(
         }
     }
 
-    // event_id → witnesses
-    let mut witnesses_by_event: HashMap<Uuid, Vec<&EventWitness>> = HashMap::new();
-    for w in event_witnesses {
-        witnesses_by_event.entry(w.event_id).or_default().push(w);
+    // event_id → participants
+    let mut participants_by_event: HashMap<Uuid, Vec<&EventParticipant>> = HashMap::new();
+    for w in event_participants {
+        participants_by_event.entry(w.event_id).or_default().push(w);
     }
 
     // GEDCOM only allows `ASSO` as a level-1 substructure of an INDI record
@@ -139,7 +195,7 @@ pub fn export_gedcom(
     // an inherent GEDCOM/Gramps limitation, not something round-tripped.
     let mut assoc_by_person: HashMap<Uuid, Vec<GedAssociation>> = HashMap::new();
     for evt in events {
-        let Some(witnesses) = witnesses_by_event.get(&evt.id) else {
+        let Some(witnesses) = participants_by_event.get(&evt.id) else {
             continue;
         };
         for w in witnesses {
@@ -161,7 +217,7 @@ pub fn export_gedcom(
                 .or_default()
                 .push(GedAssociation {
                     xref: target_xref,
-                    relationship: w.relation.clone(),
+                    relationship: Some(participant_relation(w)),
                     association_type: None,
                     note: None,
                     custom_data: Vec::new(),
@@ -172,8 +228,12 @@ pub fn export_gedcom(
     // entity_id → citations
     let mut cites_by_person: HashMap<Uuid, Vec<&Citation>> = HashMap::new();
     let mut cites_by_event: HashMap<Uuid, Vec<&Citation>> = HashMap::new();
+    // Citations of one name are written under that NAME rather than the INDI.
+    let mut cites_by_name: HashMap<Uuid, Vec<&Citation>> = HashMap::new();
     for cite in citations {
-        if let Some(pid) = cite.person_id {
+        if let (Some(FactType::Name), Some(name_id)) = (cite.fact_type, cite.fact_ref) {
+            cites_by_name.entry(name_id).or_default().push(cite);
+        } else if let Some(pid) = cite.person_id {
             cites_by_person.entry(pid).or_default().push(cite);
         }
         if let Some(eid) = cite.event_id {
@@ -205,6 +265,7 @@ pub fn export_gedcom(
     let mut mlinks_by_person: HashMap<Uuid, Vec<&MediaLink>> = HashMap::new();
     let mut mlinks_by_event: HashMap<Uuid, Vec<&MediaLink>> = HashMap::new();
     let mut mlinks_by_family: HashMap<Uuid, Vec<&MediaLink>> = HashMap::new();
+    let mut mlinks_by_source: HashMap<Uuid, Vec<&MediaLink>> = HashMap::new();
     let media_by_id: HashMap<Uuid, &Media> = media.iter().map(|m| (m.id, m)).collect();
     for ml in media_links {
         if let Some(pid) = ml.person_id {
@@ -216,6 +277,9 @@ pub fn export_gedcom(
         if let Some(fid) = ml.family_id {
             mlinks_by_family.entry(fid).or_default().push(ml);
         }
+        if let Some(sid) = ml.source_id {
+            mlinks_by_source.entry(sid).or_default().push(ml);
+        }
     }
 
     // family_id → spouses / children
@@ -255,23 +319,54 @@ pub fn export_gedcom(
         }),
         source: Some(HeadSour {
             value: Some("OXIDGENE".to_string()),
-            name: Some("OxidGene".to_string()),
-            version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            name: non_blank(&options.software_name),
+            version: non_blank(&options.software_version),
             ..Default::default()
         }),
         encoding: Some(Encoding {
             value: Some("UTF-8".to_string()),
             ..Default::default()
         }),
+        submitter_tag: Some(SUBMITTER_XREF.to_string()),
+        language: options.language.as_deref().and_then(non_blank),
+        ..Default::default()
+    };
+    let submitter = Submitter {
+        xref: Some(SUBMITTER_XREF.to_string()),
+        name: Some(non_blank(&options.submitter).unwrap_or_else(|| "Unknown".to_string())),
         ..Default::default()
     };
 
     // ── Build GedcomData ─────────────────────────────────────────────
     let mut data = GedcomData {
         header: Some(header),
+        submitters: vec![submitter],
         ..Default::default()
     };
 
+    // ged_io writes neither a source's multimedia nor `_UID`/`REFN` ids, so
+    // those lines are spliced into the serialized text at the end, keyed by
+    // record xref.
+    let mut record_lines: HashMap<String, Vec<String>> = HashMap::new();
+    for ext in external_ids {
+        let xref = match (ext.person_id, ext.family_id) {
+            (Some(person_id), _) => person_xref.get(&person_id),
+            (None, Some(family_id)) => family_xref.get(&family_id),
+            (None, None) => None,
+        };
+        if let Some(xref) = xref {
+            record_lines
+                .entry(xref.clone())
+                .or_default()
+                .extend(external_id_lines(ext));
+        }
+    }
+
+    // Nor does it write the destination of a move: `2 _TO <place>` lines go
+    // right after the move's own line, keyed by (record xref, tag, rank of
+    // the move among the record's moves with that tag).
+    let mut move_lines: HashMap<(String, &'static str, usize), Vec<String>> = HashMap::new();
+
     // ── Export Sources ────────────────────────────────────────────────
     for src in sources {
         let xref = source_xref.get(&src.id).cloned();
@@ -279,6 +374,13 @@ pub fn export_gedcom(
             .get(&src.id)
             .map(|ns| ns.iter().map(|n| to_ged_note(&n.text)).collect())
             .unwrap_or_default();
+        if let (Some(xref), Some(mls)) = (&xref, mlinks_by_source.get(&src.id)) {
+            record_lines.entry(xref.clone()).or_default().extend(
+                mls.iter()
+                    .filter_map(|ml| media_xref.get(&ml.media_id))
+                    .map(|obje| format!("1 OBJE {obje}")),
+            );
+        }
 
         data.sources.push(GedSource {
             xref,
@@ -289,6 +391,7 @@ pub fn export_gedcom(
             notes: ged_notes,
             ..Default::default()
         });
+        on_record();
     }
 
     // ── Export Multimedia ─────────────────────────────────────────────
@@ -307,6 +410,7 @@ pub fn export_gedcom(
             title: m.title.clone(),
             ..Default::default()
         });
+        on_record();
     }
 
     // ── Export Individuals ────────────────────────────────────────────
@@ -328,7 +432,19 @@ pub fn export_gedcom(
             .map(|names| {
                 let mut ordered: Vec<_> = names.iter().collect();
                 ordered.sort_by_key(|n| !n.is_primary);
-                ordered.into_iter().map(|pn| to_ged_name(pn)).collect()
+                ordered
+                    .into_iter()
+                    .map(|pn| {
+                        let mut name = to_ged_name(pn);
+                        name.source = cites_by_name
+                            .get(&pn.id)
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|c| to_ged_citation(c, &source_xref, &mut warnings))
+                            .collect();
+                        name
+                    })
+                    .collect()
             })
             .unwrap_or_default();
 
@@ -361,9 +477,25 @@ pub fn export_gedcom(
                 )),
             }
         }
-        if merge_occupations {
+        if options.merge_occupations {
             indi_attributes = merge_occupation_attributes(indi_attributes);
         }
+        if let Some(xref) = &xref {
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for evt in events_by_person.get(&person.id).into_iter().flatten() {
+                let Some(tag) = move_tag(evt.event_type) else {
+                    continue;
+                };
+                let rank = counts.entry(tag).or_default();
+                *rank += 1;
+                if let Some(place) = evt.place_id_to.and_then(|id| place_map.get(&id)) {
+                    move_lines.insert(
+                        (xref.clone(), tag, *rank - 1),
+                        vec![format!("2 _TO {}", place.name)],
+                    );
+                }
+            }
+        }
 
         // Source citations on the individual
         let source_cites: Vec<GedCitation> = cites_by_person
@@ -413,6 +545,7 @@ pub fn export_gedcom(
             associations: assoc_by_person.get(&person.id).cloned().unwrap_or_default(),
             ..Default::default()
         });
+        on_record();
     }
 
     // ── Export Families ───────────────────────────────────────────────
@@ -502,16 +635,176 @@ pub fn export_gedcom(
             multimedia: fam_multimedia,
             ..Default::default()
         });
+        on_record();
     }
 
     // ── Serialize ────────────────────────────────────────────────────
     let gedcom = GedcomWriter::new()
         .write_to_string(&data)
         .map_err(|e| format!("GEDCOM write error: {e}"))?;
+    let gedcom = insert_record_lines(&gedcom, &record_lines);
+    let gedcom = insert_move_lines(&gedcom, &move_lines);
+    let gedcom = wrap_long_lines(&gedcom);
+    warnings.extend(validate(&gedcom).iter().map(ToString::to_string));
 
     Ok(ExportResult { gedcom, warnings })
 }
 
+/// Longest GEDCOM 5.5.1 cross-reference id, `@` delimiters included.
+const MAX_XREF_BYTES: usize = 22;
+/// Longest GEDCOM 5.5.1 tag.
+const MAX_TAG_BYTES: usize = 31;
+
+/// Check a GEDCOM string against the GEDCOM 5.5.1 structural rules: a
+/// `HEAD` first and a `TRLR` last, well-formed lines no longer than
+/// [`MAX_LINE_BYTES`], levels going down by at most one step at a time,
+/// well-formed and unique record xrefs, and pointers that resolve — to a
+/// record of the right kind for the family links.
+///
+/// Only the structure is checked, not the tags each record may hold, so a
+/// file passing this can still be refused by a strict reader.
+pub fn validate(gedcom: &str) -> Vec<ConformanceIssue> {
+    let mut issues = Vec::new();
+    let mut issue = |line: Option<usize>, message: String| {
+        issues.push(ConformanceIssue { line, message });
+    };
+
+    // Record xref → record tag, and every pointer with its line number.
+    let mut records: HashMap<&str, &str> = HashMap::new();
+    let mut pointers: Vec<(usize, &str, &str)> = Vec::new();
+    let mut first_record: Option<&str> = None;
+    let mut last_record: Option<(usize, &str)> = None;
+    let mut trailer_has_children = false;
+    let mut previous_level: Option<usize> = None;
+
+    let gedcom = gedcom.strip_prefix('\u{feff}').unwrap_or(gedcom);
+    for (index, raw) in gedcom.lines().enumerate() {
+        let number = index + 1;
+        if raw.len() > MAX_LINE_BYTES {
+            issue(
+                Some(number),
+                format!("line is {} bytes long, over {MAX_LINE_BYTES}", raw.len()),
+            );
+        }
+        let Some(line) = GedLine::parse(raw).filter(|l| !l.tag.is_empty()) else {
+            issue(Some(number), "malformed line".to_string());
+            continue;
+        };
+
+        let max_level = previous_level.map_or(0, |previous| previous + 1);
+        if line.level > max_level {
+            issue(
+                Some(number),
+                format!(
+                    "level {} {} where at most {max_level} is allowed",
+                    line.level, line.tag
+                ),
+            );
+        }
+        previous_level = Some(line.level);
+
+        if !valid_tag(line.tag) {
+            issue(Some(number), format!("invalid tag {:?}", line.tag));
+        }
+        // The head is `level tag`, or `level xref tag`.
+        let head: Vec<&str> = line.head.split(' ').collect();
+        let xref = (head.len() == 3).then(|| head[1]);
+        if let Some(xref) = xref {
+            if line.level != 0 {
+                issue(
+                    Some(number),
+                    format!("xref {xref} on a level {} line", line.level),
+                );
+            } else if !valid_xref(xref) {
+                issue(Some(number), format!("invalid xref {xref:?}"));
+            } else if records.insert(xref, line.tag).is_some() {
+                issue(Some(number), format!("xref {xref} is defined twice"));
+            }
+        }
+
+        if line.level == 0 {
+            first_record.get_or_insert(line.tag);
+            last_record = Some((number, line.tag));
+            let needs_xref = !matches!(line.tag, "HEAD" | "TRLR");
+            if needs_xref && xref.is_none() {
+                issue(Some(number), format!("{} record without an xref", line.tag));
+            }
+        } else if last_record.is_some_and(|(_, tag)| tag == "TRLR") {
+            trailer_has_children = true;
+        }
+
+        if let Some(value) = line.value
+            && value.len() > 2
+            && value.starts_with('@')
+            && value.ends_with('@')
+            && !value.starts_with("@#")
+        {
+            if valid_xref(value) {
+                pointers.push((number, line.tag, value));
+            } else {
+                issue(Some(number), format!("invalid pointer {value:?}"));
+            }
+        }
+    }
+
+    if first_record != Some("HEAD") {
+        issue(
+            Some(1),
+            "the file does not start with a HEAD record".to_string(),
+        );
+    }
+    match last_record {
+        Some((_, "TRLR")) if trailer_has_children => {
+            issue(None, "the TRLR record has substructures".to_string());
+        }
+        Some((_, "TRLR")) => {}
+        _ => issue(None, "the file does not end with a TRLR record".to_string()),
+    }
+
+    for (number, tag, pointer) in pointers {
+        let expected = match tag {
+            "HUSB" | "WIFE" | "CHIL" => Some("INDI"),
+            "FAMC" | "FAMS" => Some("FAM"),
+            _ => None,
+        };
+        match records.get(pointer) {
+            None => issue(
+                Some(number),
+                format!("{tag} points to {pointer}, which is not defined"),
+            ),
+            Some(&target) if expected.is_some_and(|expected| expected != target) => issue(
+                Some(number),
+                format!(
+                    "{tag} must point to a {} record, but {pointer} is {target}",
+                    expected.unwrap_or_default()
+                ),
+            ),
+            Some(_) => {}
+        }
+    }
+    issues
+}
+
+/// A GEDCOM tag: letters, digits and underscores, at most
+/// [`MAX_TAG_BYTES`] long.
+fn valid_tag(tag: &str) -> bool {
+    tag.len() <= MAX_TAG_BYTES && tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// A GEDCOM cross-reference id: `@`, a letter or digit, then anything but
+/// `@`, `@`; at most [`MAX_XREF_BYTES`] long.
+fn valid_xref(xref: &str) -> bool {
+    let Some(inner) = xref
+        .strip_prefix('@')
+        .and_then(|rest| rest.strip_suffix('@'))
+    else {
+        return false;
+    };
+    xref.len() <= MAX_XREF_BYTES
+        && inner.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && !inner.contains(['@', ' '])
+}
+
 /// Wrap a GEDCOM string into a GEDZIP archive (a ZIP file containing
 /// `gedcom.ged`), per the GEDCOM 7.0 GEDZIP format.
 ///
@@ -519,14 +812,23 @@ pub fn export_gedcom(
 ///
 /// Returns `Err` if the ZIP archive cannot be written.
 pub fn export_gedzip(gedcom: &str) -> Result<Vec<u8>, String> {
-    let cursor = std::io::Cursor::new(Vec::new());
+    let cursor = write_gedzip(gedcom, std::io::Cursor::new(Vec::new()))?;
+    Ok(cursor.into_inner())
+}
+
+/// Write a GEDCOM string as a GEDZIP archive into `out`, e.g. a file, and
+/// return it. See [`export_gedzip`].
+///
+/// # Errors
+///
+/// Returns `Err` if the ZIP archive cannot be written.
+pub fn write_gedzip<W: std::io::Write + std::io::Seek>(gedcom: &str, out: W) -> Result<W, String> {
     let mut writer =
-        ged_io::gedzip::GedzipWriter::new(cursor).map_err(|e| format!("GEDZIP error: {e}"))?;
+        ged_io::gedzip::GedzipWriter::new(out).map_err(|e| format!("GEDZIP error: {e}"))?;
     writer
         .write_gedcom_bytes(gedcom.as_bytes())
         .map_err(|e| format!("GEDZIP error: {e}"))?;
-    let cursor = writer.finish().map_err(|e| format!("GEDZIP error: {e}"))?;
-    Ok(cursor.into_inner())
+    writer.finish().map_err(|e| format!("GEDZIP error: {e}"))
 }
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -537,6 +839,7 @@ fn convert_sex(sex: Sex) -> GenderType {
     match sex {
         Sex::Male => GenderType::Male,
         Sex::Female => GenderType::Female,
+        Sex::Intersex => GenderType::Nonbinary,
         Sex::Unknown => GenderType::Unknown,
     }
 }
@@ -697,6 +1000,18 @@ fn convert_event_type(et: EventType) -> GedEvent {
     }
 }
 
+/// `RELA` text for a participant: the recorded relation when it still reads
+/// as the participant's role, otherwise the role itself, since GEDCOM has a
+/// single slot for both and the structured role must survive a round trip.
+fn participant_relation(participant: &EventParticipant) -> String {
+    match &participant.relation {
+        Some(relation) if ParticipantRole::from_relation(relation) == participant.role => {
+            relation.clone()
+        }
+        _ => participant.role.gedcom_relation().to_string(),
+    }
+}
+
 fn convert_confidence(c: Confidence) -> CertaintyAssessment {
     match c {
         Confidence::VeryLow => CertaintyAssessment::Unreliable,
@@ -727,7 +1042,7 @@ fn to_ged_detail(
 ) -> GedDetail {
     let event = convert_event_type(evt.event_type);
     let date = evt.date_value.as_ref().map(|dv| Date {
-        value: Some(dv.clone()),
+        value: Some(with_calendar(evt.calendar, dv)),
         ..Default::default()
     });
 
@@ -799,7 +1114,7 @@ fn to_ged_detail(
         associations,
         cause: evt.cause.clone(),
         restriction: None,
-        age: None,
+        age: evt.age.as_deref().and_then(to_ged_age),
         agency: None,
         religion: None,
     }
@@ -830,7 +1145,7 @@ fn event_type_to_attribute(et: EventType) -> Option<GedIndividualAttribute> {
 }
 
 /// Collapses every `OCCU` attribute in a person's attribute list into one,
-/// for the `merge_occupations` export option (see `export_gedcom`). Values
+/// for the `merge_occupations` export option (see [`ExportOptions`]). Values
 /// are joined with `", "`; the first occupation's date/place/cause/etc. are
 /// kept, and every occupation's source citations and first note are
 /// preserved on the merged entry. A no-op if the person has 0 or 1 `OCCU`.
@@ -888,7 +1203,7 @@ fn to_ged_attribute_detail(
     warnings: &mut Vec<String>,
 ) -> GedAttributeDetail {
     let date = evt.date_value.as_ref().map(|dv| Date {
-        value: Some(dv.clone()),
+        value: Some(with_calendar(evt.calendar, dv)),
         ..Default::default()
     });
 
@@ -942,6 +1257,47 @@ fn to_ged_attribute_detail(
     }
 }
 
+/// Parse a stored `AGE` value back into `ged_io`'s structure. Unparseable
+/// values are dropped rather than written as an invalid `AGE` line.
+fn to_ged_age(age: &str) -> Option<GedAge> {
+    let age = age.trim();
+    match age.to_ascii_uppercase().as_str() {
+        "CHILD" => return Some(GedAge::Child),
+        "INFANT" => return Some(GedAge::Infant),
+        "STILLBORN" => return Some(GedAge::Stillborn),
+        _ => {}
+    }
+    let (modifier, rest) = if let Some(rest) = age.strip_prefix('<') {
+        (AgeModifier::LessThan, rest)
+    } else if let Some(rest) = age.strip_prefix('>') {
+        (AgeModifier::GreaterThan, rest)
+    } else {
+        (AgeModifier::Exact, age)
+    };
+    let (mut years, mut months, mut weeks, mut days) = (None, None, None, None);
+    for token in rest.split_whitespace() {
+        let token = token.to_ascii_lowercase();
+        match token.strip_suffix(['y', 'm', 'w', 'd']) {
+            Some(n) if token.ends_with('m') => months = Some(n.parse().ok()?),
+            Some(n) if token.ends_with('w') => weeks = Some(n.parse().ok()?),
+            Some(n) if token.ends_with('d') => days = Some(n.parse().ok()?),
+            Some(n) => years = Some(n.parse().ok()?),
+            None => years = Some(token.parse().ok()?),
+        }
+    }
+    if years.is_none() && months.is_none() && weeks.is_none() && days.is_none() {
+        return None;
+    }
+    Some(GedAge::Numeric {
+        years,
+        months,
+        weeks,
+        days,
+        modifier,
+        phrase: None,
+    })
+}
+
 fn to_ged_citation(
     cite: &Citation,
     source_xref: &HashMap<Uuid, String>,
@@ -986,6 +1342,228 @@ fn to_ged_multimedia_ref(
     })
 }
 
+/// GEDCOM lines of an external id: `1 _UID <value>`, or `1 REFN <value>`
+/// with its `2 TYPE`.
+fn external_id_lines(ext: &ExternalId) -> Vec<String> {
+    match ext.kind {
+        ExternalIdKind::Uid => vec![format!("1 _UID {}", ext.value)],
+        ExternalIdKind::Refn => {
+            let mut lines = vec![format!("1 REFN {}", ext.value)];
+            if let Some(ref_type) = ext.ref_type.as_deref().filter(|t| !t.is_empty()) {
+                lines.push(format!("2 TYPE {ref_type}"));
+            }
+            lines
+        }
+    }
+}
+
+/// Add lines right after the header line of each record listed in
+/// `record_lines` (record xref → lines, e.g. `1 OBJE @M1@` for a `SOUR`).
+fn insert_record_lines(gedcom: &str, record_lines: &HashMap<String, Vec<String>>) -> String {
+    if record_lines.is_empty() {
+        return gedcom.to_string();
+    }
+    let mut out = String::with_capacity(gedcom.len());
+    for line in gedcom.split_inclusive('\n') {
+        out.push_str(line);
+        let mut parts = line.split_whitespace();
+        if let (Some("0"), Some(xref)) = (parts.next(), parts.next())
+            && let Some(lines) = record_lines.get(xref)
+        {
+            let eol = if line.ends_with("\r\n") { "\r\n" } else { "\n" };
+            for extra in lines {
+                out.push_str(extra);
+                out.push_str(eol);
+            }
+        }
+    }
+    out
+}
+
+/// GEDCOM tag of a move, i.e. an event type with a destination place.
+fn move_tag(et: EventType) -> Option<&'static str> {
+    et.has_destination().then(|| et.gedcom_tag()).flatten()
+}
+
+/// Add lines right after each move listed in `move_lines` (see
+/// `export_gedcom`), counting the moves of each record as they are written.
+fn insert_move_lines(
+    gedcom: &str,
+    move_lines: &HashMap<(String, &'static str, usize), Vec<String>>,
+) -> String {
+    if move_lines.is_empty() {
+        return gedcom.to_string();
+    }
+    let mut out = String::with_capacity(gedcom.len());
+    let mut record = String::new();
+    let mut counts: HashMap<&'static str, usize> = HashMap::new();
+    for line in gedcom.split_inclusive('\n') {
+        out.push_str(line);
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next()) {
+            (Some("0"), Some(xref)) => {
+                record = xref.to_string();
+                counts.clear();
+            }
+            (Some("1"), Some(tag)) => {
+                let Some(tag) = ["EMIG", "IMMI", "RESI"].into_iter().find(|t| *t == tag) else {
+                    continue;
+                };
+                let rank = counts.entry(tag).or_default();
+                *rank += 1;
+                if let Some(lines) = move_lines.get(&(record.clone(), tag, *rank - 1)) {
+                    let eol = if line.ends_with("\r\n") { "\r\n" } else { "\n" };
+                    for extra in lines {
+                        out.push_str(extra);
+                        out.push_str(eol);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Longest GEDCOM 5.5.1 line, in bytes, line terminator excluded.
+const MAX_LINE_BYTES: usize = 255;
+
+/// Rewrap every value split over `CONC`/`CONT` lines, and every line over
+/// [`MAX_LINE_BYTES`]. ged_io cuts long values by characters, so lines of
+/// accented text run over the limit, and it may cut next to a space, which
+/// readers trim from `CONC` lines.
+fn wrap_long_lines(gedcom: &str) -> String {
+    let mut out = String::with_capacity(gedcom.len());
+    // The line being continued, its value so far, whether `CONC`/`CONT`
+    // lines were folded into it, and its line terminator.
+    let mut pending: Option<(GedLine, String, bool, &str)> = None;
+    for line in gedcom.split_inclusive('\n') {
+        let eol = if line.ends_with("\r\n") { "\r\n" } else { "\n" };
+        let parsed = GedLine::parse(line.trim_end_matches(['\r', '\n']));
+        if let (Some((head, value, continued, _)), Some(next)) = (&mut pending, &parsed)
+            && next.level == head.level + 1
+            && matches!(next.tag, "CONC" | "CONT")
+        {
+            if next.tag == "CONT" {
+                value.push('\n');
+            }
+            value.push_str(next.value.unwrap_or_default());
+            *continued = true;
+            continue;
+        }
+        if let Some(pending) = pending.take() {
+            flush_wrapped(&mut out, pending);
+        }
+        match parsed {
+            Some(parsed) => {
+                let value = parsed.value.unwrap_or_default().to_string();
+                pending = Some((parsed, value, false, eol));
+            }
+            None => out.push_str(line),
+        }
+    }
+    if let Some(pending) = pending {
+        flush_wrapped(&mut out, pending);
+    }
+    out
+}
+
+/// Write a line gathered by [`wrap_long_lines`], as it was if it needs no
+/// rewrapping.
+fn flush_wrapped(out: &mut String, (line, value, continued, eol): (GedLine, String, bool, &str)) {
+    if continued || line.head.len() + 1 + value.len() > MAX_LINE_BYTES {
+        push_wrapped(out, &line, &value, eol);
+    } else {
+        out.push_str(line.raw);
+        out.push_str(eol);
+    }
+}
+
+/// A GEDCOM line split into its parts.
+struct GedLine<'a> {
+    raw: &'a str,
+    level: usize,
+    tag: &'a str,
+    /// Level, xref and tag.
+    head: &'a str,
+    value: Option<&'a str>,
+}
+
+impl<'a> GedLine<'a> {
+    fn parse(raw: &'a str) -> Option<Self> {
+        let (level, rest) = raw.split_once(' ')?;
+        let level = level.parse().ok()?;
+        let tag_start = match rest.strip_prefix('@') {
+            Some(xref) => raw.len() - xref.len() + xref.find(' ')? + 1,
+            None => raw.len() - rest.len(),
+        };
+        let (tag, value) = match raw[tag_start..].split_once(' ') {
+            Some((tag, value)) => (tag, Some(value)),
+            None => (&raw[tag_start..], None),
+        };
+        Some(GedLine {
+            raw,
+            level,
+            tag,
+            head: &raw[..tag_start + tag.len()],
+            value,
+        })
+    }
+}
+
+/// Write `value` after `line.head`: each line break starts a `CONT` line,
+/// and text past [`MAX_LINE_BYTES`] goes on `CONC` lines, cut between two
+/// non-space characters where possible.
+fn push_wrapped(out: &mut String, line: &GedLine, value: &str, eol: &str) {
+    let cont = format!("{} CONT", line.level + 1);
+    let conc = format!("{} CONC", line.level + 1);
+    for (i, mut segment) in value.split('\n').enumerate() {
+        let mut head = if i == 0 { line.head } else { cont.as_str() };
+        loop {
+            let budget = MAX_LINE_BYTES - head.len() - 1;
+            let cut = if segment.len() <= budget {
+                segment.len()
+            } else {
+                wrap_point(segment, budget)
+            };
+            out.push_str(head);
+            if cut > 0 {
+                out.push(' ');
+                out.push_str(&segment[..cut]);
+            }
+            out.push_str(eol);
+            segment = &segment[cut..];
+            if segment.is_empty() {
+                break;
+            }
+            head = conc.as_str();
+        }
+    }
+}
+
+/// Where to cut `text` to keep at most `budget` bytes: on a character
+/// boundary, and away from spaces unless the text leaves no choice.
+fn wrap_point(text: &str, budget: usize) -> usize {
+    let mut cut = budget;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    let fits = cut;
+    let next_to_space = |cut: usize| {
+        text[..cut].ends_with(char::is_whitespace) || text[cut..].starts_with(char::is_whitespace)
+    };
+    while cut > 0 && next_to_space(cut) {
+        cut = text[..cut].char_indices().last().map_or(0, |(i, _)| i);
+    }
+    if cut == 0 { fits } else { cut }
+}
+
+/// `value` trimmed, or `None` if that leaves nothing.
+fn non_blank(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
 /// Format a float coordinate as a GEDCOM coordinate string.
 ///
 /// Latitude: positive → `N`, negative → `S`
@@ -4,23 +4,27 @@
 //! Tracks xref → UUID mappings so that cross-references between GEDCOM records
 //! are correctly translated into foreign-key relationships.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use chrono::{NaiveDate, Utc};
 use ged_io::GedcomBuilder;
+use ged_io::types::custom::UserDefinedTag;
 use ged_io::types::event::Event as GedEvent;
 use ged_io::types::source::citation::CitationSource;
 use uuid::Uuid;
 
+use oxidgene_core::calendar::split_calendar;
 use oxidgene_core::types::{
-    Citation, Event, EventWitness, Family, FamilyChild, FamilySpouse, Media, MediaLink, Note,
-    Person, PersonAncestry, PersonName, Place, Source,
+    Citation, Event, EventParticipant, ExternalId, Family, FamilyChild, FamilySpouse, Media,
+    MediaLink, Note, Person, PersonAncestry, PersonName, Place, Source, parse_date_sort_in,
 };
+use oxidgene_core::validation;
 use oxidgene_core::{
-    Calendar, ChildType, Confidence, DateQualifier, EventType, NameType, Privacy, Sex, SpouseRole,
+    Calendar, ChildType, Confidence, DateQualifier, EventType, ExternalIdKind, FactType, NameType,
+    NoteFormat, ParticipantRole, Privacy, Sex, SpouseRole,
 };
 
-use crate::ImportResult;
+use crate::{CustomTagTarget, ImportOptions, ImportResult};
 
 /// Import a GEDCOM string into OxidGene domain model entities.
 ///
@@ -30,6 +34,19 @@ use crate::ImportResult;
 ///
 /// Returns `Err` if the GEDCOM string cannot be parsed.
 pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, String> {
+    import_gedcom_with(gedcom_str, tree_id, &ImportOptions::default())
+}
+
+/// [`import_gedcom`] with custom tags mapped per `options`.
+///
+/// # Errors
+///
+/// Returns `Err` if the GEDCOM string cannot be parsed.
+pub fn import_gedcom_with(
+    gedcom_str: &str,
+    tree_id: Uuid,
+    options: &ImportOptions,
+) -> Result<ImportResult, String> {
     let data = GedcomBuilder::new()
         .build_from_str(gedcom_str)
         .map_err(|e| format!("GEDCOM parse error: {e}"))?;
@@ -51,22 +68,39 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
     // ── Pass 1: Allocate UUIDs for all top-level records ────────────
     for indi in &data.individuals {
         if let Some(xref) = &indi.xref {
-            indi_map.insert(xref.clone(), Uuid::now_v7());
+            indi_map.insert(
+                xref.clone(),
+                options.id_strategy.record_id(tree_id, "INDI", xref),
+            );
         }
     }
     for fam in &data.families {
         if let Some(xref) = &fam.xref {
-            fam_map.insert(xref.clone(), Uuid::now_v7());
+            fam_map.insert(
+                xref.clone(),
+                options.id_strategy.record_id(tree_id, "FAM", xref),
+            );
         }
     }
     for src in &data.sources {
         if let Some(xref) = &src.xref {
-            source_map.insert(xref.clone(), Uuid::now_v7());
+            source_map.insert(
+                xref.clone(),
+                options.id_strategy.record_id(tree_id, "SOUR", xref),
+            );
         }
     }
+    // Records whose content is an embedded `BLOB` get no id: they are
+    // skipped below, and pointers to them are dropped.
+    let blobs = object_blobs(gedcom_str);
     for mm in &data.multimedia {
-        if let Some(xref) = &mm.xref {
-            media_map.insert(xref.clone(), Uuid::now_v7());
+        if let Some(xref) = &mm.xref
+            && !is_blob_only(mm, &blobs, xref)
+        {
+            media_map.insert(
+                xref.clone(),
+                options.id_strategy.record_id(tree_id, "OBJE", xref),
+            );
         }
     }
 
@@ -85,6 +119,7 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
             longitude: None,
             created_at: now,
             updated_at: now,
+            version: 1,
         });
         id
     };
@@ -114,11 +149,19 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
             repository_name: None,
             created_at: now,
             updated_at: now,
+            version: 1,
             deleted_at: None,
         });
         id
     };
 
+    // Custom tags kept as notes, by tag, for one warning per tag.
+    let mut kept_custom_tags: BTreeMap<String, usize> = BTreeMap::new();
+
+    let source_objects = source_object_pointers(gedcom_str);
+    let moves = move_places(gedcom_str);
+    let indi_objects = individual_objects(gedcom_str);
+
     // ── Import Sources ──────────────────────────────────────────────
     for src in &data.sources {
         let xref = match &src.xref {
@@ -139,6 +182,7 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
             repository_name: None, // repo_citations not directly mappable to a single name
             created_at: now,
             updated_at: now,
+            version: 1,
             deleted_at: None,
         });
 
@@ -155,6 +199,45 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
                 &mut result,
             );
         }
+        import_custom_tags(
+            &src.custom_data,
+            &CustomTagOwner {
+                source_id: Some(id),
+                ..CustomTagOwner::default()
+            },
+            options,
+            tree_id,
+            now,
+            &mut get_or_create_place,
+            &mut kept_custom_tags,
+            &mut result,
+        );
+
+        // Multimedia links on the source (scanned pages, photos of records).
+        // ged_io drops the pointer of `1 OBJE @M1@` under a SOUR record, so
+        // linked objects come from a scan of the raw text instead.
+        let linked = source_objects
+            .get(xref.as_str())
+            .into_iter()
+            .flatten()
+            .filter_map(|obje| media_map.get(*obje).copied());
+        let inline: Vec<Uuid> = src
+            .multimedia
+            .iter()
+            .filter_map(|mm| resolve_or_create_media(mm, tree_id, now, &media_map, &mut result))
+            .collect();
+        for (sort_order, media_id) in linked.chain(inline).enumerate() {
+            result.media_links.push(MediaLink {
+                id: Uuid::now_v7(),
+                media_id,
+                person_id: None,
+                event_id: None,
+                source_id: Some(id),
+                family_id: None,
+                sort_order: sort_order as i32,
+                is_profile: false,
+            });
+        }
     }
 
     // ── Import Multimedia ───────────────────────────────────────────
@@ -168,6 +251,25 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
                 continue;
             }
         };
+
+        // GEDCOM 5.5 records may embed the file itself as a `BLOB`. There
+        // is no storage for media content, only for its metadata, so such
+        // a record would point at nothing: it is skipped.
+        if is_blob_only(mm, &blobs, xref) {
+            let warning = match decode_blob(&blobs[xref.as_str()]) {
+                None => format!("Skipping multimedia {xref}: undecodable BLOB"),
+                Some(data) => match sniff_media_type(&data) {
+                    None => format!("Skipping multimedia {xref}: BLOB of unknown format"),
+                    Some((mime_type, _)) => format!(
+                        "Skipping multimedia {xref}: embedded {mime_type} BLOB ({} bytes) \
+                         cannot be imported without media storage",
+                        data.len()
+                    ),
+                },
+            };
+            result.warnings.push(warning);
+            continue;
+        }
         let id = media_map[xref];
 
         // Extract file info from the multimedia record
@@ -188,6 +290,7 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
             .next()
             .unwrap_or(&file_path)
             .to_string();
+        let file_size = 0; // Unknown from GEDCOM
 
         result.media.push(Media {
             id,
@@ -195,7 +298,7 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
             file_name,
             mime_type,
             file_path,
-            file_size: 0, // Unknown from GEDCOM
+            file_size,
             title: mm.title.clone(),
             description: None,
             date_value: None,
@@ -203,6 +306,7 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
             place_id: None,
             created_at: now,
             updated_at: now,
+            version: 1,
             deleted_at: None,
         });
     }
@@ -232,19 +336,73 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
             tree_id,
             sex,
             privacy: Privacy::default(),
+            living: None,
+            display_name: None,
+            locked: false,
             created_at: now,
             updated_at: now,
+            version: 1,
             deleted_at: None,
         });
+        for (kind, value, ref_type) in external_ids(
+            indi.uid.as_deref(),
+            indi.user_reference_number.as_deref(),
+            indi.user_reference_type.as_deref(),
+            &indi.custom_data,
+        ) {
+            result.external_ids.push(ExternalId {
+                id: Uuid::now_v7(),
+                tree_id,
+                person_id: Some(person_id),
+                family_id: None,
+                kind,
+                value,
+                ref_type,
+                created_at: now,
+            });
+        }
 
         // Names (GEDCOM allows {0:M} NAME structures per individual; the
         // first is primary, the rest import as additional PersonNames).
         for (i, name) in indi.names.iter().enumerate() {
             let person_name = convert_name(name, person_id, i == 0, now);
+            // NAME.SOUR cites this spelling of the name, not the person.
+            let first_citation = result.citations.len();
+            for cite in &name.source {
+                import_citation(
+                    cite,
+                    Some(person_id),
+                    None,
+                    None,
+                    &source_map,
+                    &mut get_or_create_text_source,
+                    &mut result,
+                );
+            }
+            for citation in &mut result.citations[first_citation..] {
+                citation.fact_type = Some(FactType::Name);
+                citation.fact_ref = Some(person_name.id);
+            }
+            let given_names = person_name.given_names.clone();
             result.person_names.push(person_name);
+            import_custom_tags(
+                &name.custom_data,
+                &CustomTagOwner {
+                    person_id: Some(person_id),
+                    given_names,
+                    ..CustomTagOwner::default()
+                },
+                options,
+                tree_id,
+                now,
+                &mut get_or_create_place,
+                &mut kept_custom_tags,
+                &mut result,
+            );
         }
 
         // Events
+        let first_event = result.events.len();
         for evt_detail in &indi.events {
             import_event_detail(
                 evt_detail,
@@ -276,6 +434,13 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
                 &mut result,
             );
         }
+        apply_move_places(
+            &mut result,
+            first_event,
+            xref,
+            &moves,
+            &mut get_or_create_place,
+        );
 
         // Source citations on the individual
         for cite in &indi.source {
@@ -304,10 +469,45 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
             );
         }
 
-        // Multimedia links on the individual
-        for mm in &indi.multimedia {
-            let media_id = resolve_or_create_media(mm, tree_id, now, &media_map, &mut result);
+        // Custom tags on the individual and on its FAMC / FAMS links.
+        let owner = CustomTagOwner {
+            person_id: Some(person_id),
+            given_names: result
+                .person_names
+                .iter()
+                .find(|n| n.person_id == person_id && n.is_primary)
+                .and_then(|n| n.given_names.clone()),
+            ..CustomTagOwner::default()
+        };
+        for tags in
+            std::iter::once(&indi.custom_data).chain(indi.families.iter().map(|fl| &fl.custom_data))
+        {
+            import_custom_tags(
+                tags,
+                &owner,
+                options,
+                tree_id,
+                now,
+                &mut get_or_create_place,
+                &mut kept_custom_tags,
+                &mut result,
+            );
+        }
+
+        // Multimedia links on the individual. ged_io keeps neither the
+        // pointer of `1 OBJE @M1@` nor `_PRIM`, both read from the raw text.
+        let objects = indi_objects.get(xref.as_str());
+        let mut has_profile = false;
+        for (rank, mm) in indi.multimedia.iter().enumerate() {
+            let scanned = objects.and_then(|o| o.get(rank));
+            let media_id = resolve_or_create_media(mm, tree_id, now, &media_map, &mut result)
+                .or_else(|| {
+                    let pointer = scanned?.pointer?;
+                    media_map.get(pointer).copied()
+                });
             if let Some(media_id) = media_id {
+                let is_profile = !has_profile && scanned.is_some_and(|o| o.primary);
+                has_profile |= is_profile;
                 result.media_links.push(MediaLink {
                     id: Uuid::now_v7(),
                     media_id,
@@ -316,7 +516,7 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
                     source_id: None,
                     family_id: None,
                     sort_order: 0,
-                    is_profile: false,
+                    is_profile,
                 });
             }
         }
@@ -340,6 +540,23 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
             updated_at: now,
             deleted_at: None,
         });
+        for (kind, value, ref_type) in external_ids(
+            fam.uid.as_deref(),
+            fam.user_reference_number.as_deref(),
+            fam.user_reference_type.as_deref(),
+            &fam.custom_data,
+        ) {
+            result.external_ids.push(ExternalId {
+                id: Uuid::now_v7(),
+                tree_id,
+                person_id: None,
+                family_id: Some(family_id),
+                kind,
+                value,
+                ref_type,
+                created_at: now,
+            });
+        }
 
         // Spouses
         let mut sort_order = 0i32;
@@ -451,6 +668,19 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
                 &mut result,
             );
         }
+        import_custom_tags(
+            &fam.custom_data,
+            &CustomTagOwner {
+                family_id: Some(family_id),
+                ..CustomTagOwner::default()
+            },
+            options,
+            tree_id,
+            now,
+            &mut get_or_create_place,
+            &mut kept_custom_tags,
+            &mut result,
+        );
 
         // Multimedia links on the family
         for mm in &fam.multimedia {
@@ -528,14 +758,14 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
     // inside the witnessed event's own detail (caught above by
     // `import_event_detail`'s `detail.associations` loop) for the same
     // fact this pass would otherwise reconstruct — skip any (event,
-    // person) pair already present in `result.event_witnesses` so the
+    // person) pair already present in `result.event_participants` so the
     // same witness isn't recorded twice.
     let mut seen_witnesses: std::collections::HashSet<(Uuid, Uuid)> = result
-        .event_witnesses
+        .event_participants
         .iter()
         .map(|w| (w.event_id, w.person_id))
         .collect();
-    let mut event_witness_sort: HashMap<Uuid, i32> = HashMap::new();
+    let mut event_participant_sort: HashMap<Uuid, i32> = HashMap::new();
     for indi in &data.individuals {
         let owner_xref = match &indi.xref {
             Some(x) => x,
@@ -564,11 +794,12 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
                     Some(evt) => {
                         let event_id = evt.id;
                         seen_witnesses.insert((event_id, owner_person_id));
-                        let sort_order = event_witness_sort.entry(event_id).or_insert(0);
-                        result.event_witnesses.push(EventWitness {
+                        let sort_order = event_participant_sort.entry(event_id).or_insert(0);
+                        result.event_participants.push(EventParticipant {
                             id: Uuid::now_v7(),
                             event_id,
                             person_id: owner_person_id,
+                            role: participant_role(assoc),
                             relation: assoc.relationship.clone(),
                             sort_order: *sort_order,
                         });
@@ -603,11 +834,12 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
                         }
                         let event_id = evt.id;
                         seen_witnesses.insert((event_id, role_holder_id));
-                        let sort_order = event_witness_sort.entry(event_id).or_insert(0);
-                        result.event_witnesses.push(EventWitness {
+                        let sort_order = event_participant_sort.entry(event_id).or_insert(0);
+                        result.event_participants.push(EventParticipant {
                             id: Uuid::now_v7(),
                             event_id,
                             person_id: role_holder_id,
+                            role: participant_role(assoc),
                             relation: assoc.relationship.clone(),
                             sort_order: *sort_order,
                         });
@@ -627,6 +859,13 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
         }
     }
 
+    for (tag, count) in kept_custom_tags {
+        result.warnings.push(format!(
+            "Custom tag {tag} has no equivalent field — kept as a note ({count} occurrence{})",
+            if count == 1 { "" } else { "s" }
+        ));
+    }
+
     // ── Build PersonAncestry closure table ───────────────────────────
     result.person_ancestry =
         build_ancestry_closure(&result.family_spouses, &result.family_children, tree_id);
@@ -638,12 +877,196 @@ pub fn import_gedcom(gedcom_str: &str, tree_id: Uuid) -> Result<ImportResult, St
 // Conversion helpers
 // ═══════════════════════════════════════════════════════════════════════
 
+/// The identifiers a record carries in the program that wrote the file:
+/// its `_UID`s (GEDCOM 5.5.1 extension) and GEDCOM 7 `UID`, deduplicated,
+/// then its `REFN` with the reference `TYPE`.
+fn external_ids(
+    uid: Option<&str>,
+    refn: Option<&str>,
+    refn_type: Option<&str>,
+    custom_data: &[Box<UserDefinedTag>],
+) -> Vec<(ExternalIdKind, String, Option<String>)> {
+    let mut ids: Vec<(ExternalIdKind, String, Option<String>)> = Vec::new();
+    let uids = custom_data
+        .iter()
+        .filter(|tag| tag.tag.eq_ignore_ascii_case("_UID"))
+        .filter_map(|tag| tag.value.as_deref())
+        .chain(uid);
+    for value in uids.map(str::trim).filter(|v| !v.is_empty()) {
+        if !ids.iter().any(|(_, v, _)| v == value) {
+            ids.push((ExternalIdKind::Uid, value.to_string(), None));
+        }
+    }
+    if let Some(refn) = refn.map(str::trim).filter(|v| !v.is_empty()) {
+        ids.push((
+            ExternalIdKind::Refn,
+            refn.to_string(),
+            refn_type.map(str::to_string),
+        ));
+    }
+    ids
+}
+
+/// Custom tags already imported elsewhere (see [`external_ids`]).
+const CONSUMED_CUSTOM_TAGS: &[&str] = &["_UID"];
+
+/// The record a custom tag was found on.
+#[derive(Default)]
+struct CustomTagOwner {
+    person_id: Option<Uuid>,
+    family_id: Option<Uuid>,
+    source_id: Option<Uuid>,
+    /// Given names completing a bare surname mapped to a name.
+    given_names: Option<String>,
+}
+
+/// Import a record's custom (`_`-prefixed) tags: those mapped in `options`
+/// into their field, the others (and mapped ones the record cannot hold)
+/// as a note on the record, counted in `kept` by tag.
+#[allow(clippy::too_many_arguments)]
+fn import_custom_tags(
+    tags: &[Box<UserDefinedTag>],
+    owner: &CustomTagOwner,
+    options: &ImportOptions,
+    tree_id: Uuid,
+    now: chrono::DateTime<Utc>,
+    get_or_create_place: &mut dyn FnMut(&str, &mut ImportResult) -> Uuid,
+    kept: &mut BTreeMap<String, usize>,
+    result: &mut ImportResult,
+) {
+    for tag in tags {
+        let name = tag.tag.to_ascii_uppercase();
+        if !name.starts_with('_') || CONSUMED_CUSTOM_TAGS.contains(&name.as_str()) {
+            continue;
+        }
+        let target = options
+            .custom_tags
+            .iter()
+            .find(|(t, _)| t.eq_ignore_ascii_case(&name))
+            .map(|(_, target)| *target);
+        match (target, owner.person_id) {
+            (Some(CustomTagTarget::Name(name_type)), Some(person_id)) => {
+                let (given, surname) = parse_name_value(tag.value.as_deref());
+                let bare = tag
+                    .value
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty());
+                let (given_names, surname) = match surname {
+                    Some(surname) => (given, Some(surname)),
+                    None => (owner.given_names.clone(), bare.map(str::to_string)),
+                };
+                if surname.is_none() && given_names.is_none() {
+                    continue;
+                }
+                result.person_names.push(PersonName {
+                    id: Uuid::now_v7(),
+                    person_id,
+                    name_type,
+                    given_names,
+                    surname,
+                    prefix: None,
+                    suffix: None,
+                    nickname: None,
+                    is_primary: false,
+                    created_at: now,
+                    updated_at: now,
+                });
+            }
+            (Some(CustomTagTarget::Event(event_type)), person_id)
+                if person_id.is_some() || owner.family_id.is_some() =>
+            {
+                let child = |t: &str| {
+                    tag.children
+                        .iter()
+                        .find(|c| c.tag.eq_ignore_ascii_case(t))
+                        .and_then(|c| c.value.clone())
+                };
+                let (date_value, date_sort, calendar) = import_date(child("DATE"));
+                let place_id = child("PLAC").map(|p| get_or_create_place(&p, result));
+                let event_id = Uuid::now_v7();
+                result.events.push(Event {
+                    id: event_id,
+                    tree_id,
+                    event_type,
+                    date_sort,
+                    date_value,
+                    date_qualifier: DateQualifier::default(),
+                    date_value2: None,
+                    calendar,
+                    cause: None,
+                    age: None,
+                    place_id,
+                    place_id_to: None,
+                    person_id,
+                    family_id: owner.family_id,
+                    description: tag.value.clone().or_else(|| child("TYPE")),
+                    created_at: now,
+                    updated_at: now,
+                    version: 1,
+                    deleted_at: None,
+                });
+                for c in &tag.children {
+                    let text = match c.tag.to_ascii_uppercase().as_str() {
+                        "DATE" | "PLAC" | "TYPE" => continue,
+                        "NOTE" => c.value.clone(),
+                        _ => Some(render_custom_tag(c)),
+                    };
+                    import_note(
+                        &text,
+                        tree_id,
+                        now,
+                        None,
+                        Some(event_id),
+                        None,
+                        None,
+                        result,
+                    );
+                }
+            }
+            _ => {
+                import_note(
+                    &Some(render_custom_tag(tag)),
+                    tree_id,
+                    now,
+                    owner.person_id,
+                    None,
+                    owner.family_id,
+                    owner.source_id,
+                    result,
+                );
+                *kept.entry(name).or_default() += 1;
+            }
+        }
+    }
+}
+
+/// A custom tag and its subordinate lines as note text, one `TAG value`
+/// line each, indented by level.
+fn render_custom_tag(tag: &UserDefinedTag) -> String {
+    fn push_lines(tag: &UserDefinedTag, depth: usize, lines: &mut Vec<String>) {
+        let mut line = format!("{}{}", "  ".repeat(depth), tag.tag);
+        if let Some(value) = tag.value.as_deref().filter(|v| !v.is_empty()) {
+            line.push(' ');
+            line.push_str(value);
+        }
+        lines.push(line);
+        for child in &tag.children {
+            push_lines(child, depth + 1, lines);
+        }
+    }
+    let mut lines = Vec::new();
+    push_lines(tag, 0, &mut lines);
+    lines.join("\n")
+}
+
 fn convert_gender(g: &ged_io::types::individual::gender::GenderType) -> Sex {
     use ged_io::types::individual::gender::GenderType;
     match g {
         GenderType::Male => Sex::Male,
         GenderType::Female => Sex::Female,
-        _ => Sex::Unknown,
+        GenderType::Nonbinary => Sex::Intersex,
+        GenderType::Unknown => Sex::Unknown,
     }
 }
 
@@ -825,86 +1248,23 @@ fn convert_quay(quay: Option<&ged_io::types::source::quay::CertaintyAssessment>)
     }
 }
 
-/// Try to parse a GEDCOM date string into a `NaiveDate` for sorting.
-///
-/// Handles common formats:
-/// - `DD MMM YYYY` (e.g. `15 JAN 1842`)
-/// - `MMM YYYY` (e.g. `JAN 1842`) → first of month
-/// - `YYYY` (e.g. `1842`) → first of year
-/// - Prefixes like `ABT`, `BEF`, `AFT`, `CAL`, `EST` are stripped
-/// - Range formats `BET ... AND ...` → first date
-fn parse_gedcom_date(s: &str) -> Option<NaiveDate> {
-    let s = s.trim();
-    if s.is_empty() {
-        return None;
-    }
-
-    // Strip common prefixes
-    let stripped = s
-        .strip_prefix("ABT ")
-        .or_else(|| s.strip_prefix("BEF "))
-        .or_else(|| s.strip_prefix("AFT "))
-        .or_else(|| s.strip_prefix("CAL "))
-        .or_else(|| s.strip_prefix("EST "))
-        .or_else(|| s.strip_prefix("FROM "))
-        .or_else(|| s.strip_prefix("TO "))
-        .unwrap_or(s);
-
-    // Handle BET ... AND ... → take first date
-    let stripped = if let Some(rest) = stripped.strip_prefix("BET ") {
-        rest.split(" AND ").next().unwrap_or(rest)
-    } else {
-        stripped
-    };
-
-    let stripped = stripped.trim();
-    let parts: Vec<&str> = stripped.split_whitespace().collect();
-
-    match parts.len() {
-        3 => {
-            // DD MMM YYYY
-            let day: u32 = parts[0].parse().ok()?;
-            let month = gedcom_month(parts[1])?;
-            let year: i32 = parts[2].parse().ok()?;
-            NaiveDate::from_ymd_opt(year, month, day)
-        }
-        2 => {
-            // MMM YYYY
-            let month = gedcom_month(parts[0])?;
-            let year: i32 = parts[1].parse().ok()?;
-            NaiveDate::from_ymd_opt(year, month, 1)
-        }
-        1 => {
-            // YYYY
-            let year: i32 = parts[0].parse().ok()?;
-            NaiveDate::from_ymd_opt(year, 1, 1)
-        }
-        _ => None,
-    }
-}
-
-fn gedcom_month(s: &str) -> Option<u32> {
-    match s.to_uppercase().as_str() {
-        "JAN" => Some(1),
-        "FEB" => Some(2),
-        "MAR" => Some(3),
-        "APR" => Some(4),
-        "MAY" => Some(5),
-        "JUN" => Some(6),
-        "JUL" => Some(7),
-        "AUG" => Some(8),
-        "SEP" => Some(9),
-        "OCT" => Some(10),
-        "NOV" => Some(11),
-        "DEC" => Some(12),
-        _ => None,
-    }
-}
-
 // ═══════════════════════════════════════════════════════════════════════
 // Import sub-record helpers
 // ═══════════════════════════════════════════════════════════════════════
 
+/// A `DATE` value as stored on an event: the phrase without its calendar
+/// escape, its `date_sort` and the calendar the escape names (Gregorian
+/// without one).
+fn import_date(value: Option<String>) -> (Option<String>, Option<NaiveDate>, Calendar) {
+    let Some(value) = value else {
+        return (None, None, Calendar::default());
+    };
+    let (calendar, phrase) = split_calendar(&value);
+    let calendar = calendar.unwrap_or_default();
+    let date_sort = parse_date_sort_in(calendar, &phrase);
+    (Some(phrase), date_sort, calendar)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn import_event_detail(
     detail: &ged_io::types::event::detail::Detail,
@@ -922,8 +1282,8 @@ fn import_event_detail(
     let event_type = convert_event_type(&detail.event, detail.event_type.as_deref());
 
     // Date
-    let date_value = detail.date.as_ref().and_then(|d| d.value.clone());
-    let date_sort = date_value.as_deref().and_then(parse_gedcom_date);
+    let (date_value, date_sort, calendar) =
+        import_date(detail.date.as_ref().and_then(|d| d.value.clone()));
 
     // Place
     let place_id = detail.place.as_ref().and_then(|p| {
@@ -932,8 +1292,8 @@ fn import_event_detail(
             // Update lat/long if available
             if let Some(ref map) = p.map
                 && let (Some(lat_str), Some(lon_str)) = (&map.latitude, &map.longitude)
-                && let (Ok(lat), Ok(lon)) =
-                    (parse_gedcom_coord(lat_str), parse_gedcom_coord(lon_str))
+                && let Some((lat, lon)) =
+                    import_coordinates(name, lat_str, lon_str, &mut result.warnings)
                 && let Some(place) = result.places.iter_mut().find(|pl| pl.id == pid)
             {
                 place.latitude = Some(lat);
@@ -944,6 +1304,7 @@ fn import_event_detail(
     });
 
     let cause = detail.cause.clone();
+    let age = import_age(detail.age.as_ref());
 
     // The GEDCOM `TYPE` sub-tag classifies a generic `EVEN`/`FACT` event
     // (e.g. "PACS", "Concubinage") — preserve it as the description so the
@@ -969,27 +1330,31 @@ fn import_event_detail(
         date_sort,
         date_qualifier: DateQualifier::default(),
         date_value2: None,
-        calendar: Calendar::default(),
+        calendar,
         cause,
+        age,
         place_id,
+        place_id_to: None,
         person_id,
         family_id,
         description,
         created_at: now,
         updated_at: now,
+        version: 1,
         deleted_at: None,
     });
 
     // Associations (witnesses, godparents, ...) attached to this event —
     // only associations pointing at a known INDI xref can be captured
     // (an `ASSO` may point at a FAM record per the GEDCOM grammar, which
-    // has no home in `EventWitness`).
+    // has no home in `EventParticipant`).
     for (idx, assoc) in detail.associations.iter().enumerate() {
         if let Some(&witness_person_id) = indi_map.get(&assoc.xref) {
-            result.event_witnesses.push(EventWitness {
+            result.event_participants.push(EventParticipant {
                 id: Uuid::now_v7(),
                 event_id,
                 person_id: witness_person_id,
+                role: participant_role(assoc),
                 relation: assoc.relationship.clone(),
                 sort_order: idx as i32,
             });
@@ -1062,8 +1427,8 @@ fn import_attribute_detail(
     let event_type = convert_individual_attribute(&detail.attribute);
 
     // Date
-    let date_value = detail.date.as_ref().and_then(|d| d.value.clone());
-    let date_sort = date_value.as_deref().and_then(parse_gedcom_date);
+    let (date_value, date_sort, calendar) =
+        import_date(detail.date.as_ref().and_then(|d| d.value.clone()));
 
     // Place
     let place_id = detail.place.as_ref().and_then(|p| {
@@ -1071,8 +1436,8 @@ fn import_attribute_detail(
             let pid = get_or_create_place(name, result);
             if let Some(ref map) = p.map
                 && let (Some(lat_str), Some(lon_str)) = (&map.latitude, &map.longitude)
-                && let (Ok(lat), Ok(lon)) =
-                    (parse_gedcom_coord(lat_str), parse_gedcom_coord(lon_str))
+                && let Some((lat, lon)) =
+                    import_coordinates(name, lat_str, lon_str, &mut result.warnings)
                 && let Some(place) = result.places.iter_mut().find(|pl| pl.id == pid)
             {
                 place.latitude = Some(lat);
@@ -1083,6 +1448,7 @@ fn import_attribute_detail(
     });
 
     let cause = detail.cause.clone();
+    let age = import_age(detail.age.as_ref());
 
     // Preserve the tag's own value (e.g. "Acccount Manager", "Presales, Trainer"
     // for OCCU) or, failing that, its TYPE sub-tag.
@@ -1116,14 +1482,17 @@ fn import_attribute_detail(
             date_sort,
             date_qualifier: DateQualifier::default(),
             date_value2: None,
-            calendar: Calendar::default(),
+            calendar,
             cause: cause.clone(),
+            age: age.clone(),
             place_id,
+            place_id_to: None,
             person_id: Some(person_id),
             family_id: None,
             description,
             created_at: now,
             updated_at: now,
+            version: 1,
             deleted_at: None,
         });
 
@@ -1228,6 +1597,8 @@ fn import_citation(
         page,
         confidence,
         text,
+        fact_type: None,
+        fact_ref: None,
         created_at: Utc::now(),
         updated_at: Utc::now(),
     });
@@ -1244,8 +1615,10 @@ fn import_note(
     source_id: Option<Uuid>,
     result: &mut ImportResult,
 ) {
+    // ged_io joins `CONT` lines with `\n` and `CONC` lines with nothing;
+    // files written on Windows may still carry `\r` in the values.
     let text = match value {
-        Some(t) if !t.is_empty() => t.clone(),
+        Some(t) if !t.trim().is_empty() => t.replace("\r\n", "\n").replace('\r', "\n"),
         _ => return,
     };
 
@@ -1253,12 +1626,14 @@ fn import_note(
         id: Uuid::now_v7(),
         tree_id,
         text,
+        format: NoteFormat::Plain,
         person_id,
         event_id,
         family_id,
         source_id,
         created_at: now,
         updated_at: now,
+        version: 1,
         deleted_at: None,
     });
 }
@@ -1314,6 +1689,7 @@ fn resolve_or_create_media(
             place_id: None,
             created_at: now,
             updated_at: now,
+            version: 1,
             deleted_at: None,
         });
         return Some(id);
@@ -1322,18 +1698,358 @@ fn resolve_or_create_media(
     None
 }
 
-/// Parse a GEDCOM coordinate string (e.g. `"N01.4242"` or `"W1.4242"`).
-fn parse_gedcom_coord(s: &str) -> Result<f64, std::num::ParseFloatError> {
-    let s = s.trim();
-    if let Some(rest) = s.strip_prefix('N').or_else(|| s.strip_prefix('E')) {
-        rest.parse::<f64>()
-    } else if let Some(rest) = s.strip_prefix('S').or_else(|| s.strip_prefix('W')) {
-        rest.parse::<f64>().map(|v| -v)
-    } else {
-        s.parse::<f64>()
+/// `OBJE` pointers of each level-0 `SOUR` record, keyed by the source xref.
+fn source_object_pointers(gedcom_str: &str) -> HashMap<&str, Vec<&str>> {
+    let mut pointers: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut current_source: Option<&str> = None;
+    for line in gedcom_str.lines() {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("0"), Some(xref), Some("SOUR")) if xref.starts_with('@') => {
+                current_source = Some(xref);
+            }
+            (Some("0"), ..) => current_source = None,
+            (Some("1"), Some("OBJE"), Some(pointer)) if pointer.starts_with('@') => {
+                if let Some(source) = current_source {
+                    pointers.entry(source).or_default().push(pointer);
+                }
+            }
+            _ => {}
+        }
+    }
+    pointers
+}
+
+/// `BLOB` payload of each level-0 `OBJE` record, keyed by the record
+/// xref: the `CONT` lines concatenated without whitespace. ged_io skips
+/// `BLOB` entirely.
+fn object_blobs(gedcom_str: &str) -> HashMap<&str, String> {
+    let mut blobs: HashMap<&str, String> = HashMap::new();
+    let mut current_object: Option<&str> = None;
+    let mut in_blob = false;
+    for line in gedcom_str.lines() {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("0"), Some(xref), Some("OBJE")) if xref.starts_with('@') => {
+                current_object = Some(xref);
+                in_blob = false;
+            }
+            (Some("0"), ..) => current_object = None,
+            (Some("1"), Some("BLOB"), _) => {
+                in_blob = current_object.is_some();
+                if let Some(xref) = current_object {
+                    blobs.entry(xref).or_default();
+                }
+            }
+            (Some("1"), ..) => in_blob = false,
+            (Some("2"), Some("CONT"), Some(data)) if in_blob => {
+                if let Some(blob) = current_object.and_then(|xref| blobs.get_mut(xref)) {
+                    blob.push_str(data);
+                    parts.for_each(|rest| blob.push_str(rest));
+                }
+            }
+            _ => {}
+        }
+    }
+    blobs
+}
+
+/// Whether a level-0 `OBJE` record has no file path but an embedded
+/// `BLOB` (see [`object_blobs`]).
+fn is_blob_only(
+    mm: &ged_io::types::multimedia::Multimedia,
+    blobs: &HashMap<&str, String>,
+    xref: &str,
+) -> bool {
+    let has_path = mm
+        .file
+        .as_ref()
+        .and_then(|f| f.value.as_deref())
+        .is_some_and(|path| !path.is_empty());
+    !has_path && blobs.contains_key(xref)
+}
+
+/// Decodes a GEDCOM 5.5 `BLOB`: 6 bits per character over the alphabet
+/// `./0-9A-Za-z`, 4 characters to 3 bytes. Some exporters wrote standard
+/// base64 instead, which is tried when the GEDCOM alphabet yields no known
+/// file format.
+fn decode_blob(blob: &str) -> Option<Vec<u8>> {
+    let gedcom = decode_sextets(blob, |c| match c {
+        b'.'..=b'9' => Some(c - b'.'),
+        b'A'..=b'Z' => Some(c - b'A' + 12),
+        b'a'..=b'z' => Some(c - b'a' + 38),
+        _ => None,
+    });
+    if gedcom
+        .as_deref()
+        .is_some_and(|data| sniff_media_type(data).is_some())
+    {
+        return gedcom;
+    }
+    let base64 = decode_sextets(blob.trim_end_matches('='), |c| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    });
+    match base64 {
+        Some(data) if sniff_media_type(&data).is_some() => Some(data),
+        _ => gedcom.or(base64),
+    }
+}
+
+/// Packs 6-bit values, most significant bits first, dropping the trailing
+/// partial byte. `None` if a character is outside the alphabet.
+fn decode_sextets(text: &str, value: impl Fn(u8) -> Option<u8>) -> Option<Vec<u8>> {
+    let mut data = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in text.bytes() {
+        buffer = (buffer << 6) | u32::from(value(c)?);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            data.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    (!data.is_empty()).then_some(data)
+}
+
+/// MIME type and file extension of decoded media, from its magic bytes.
+fn sniff_media_type(data: &[u8]) -> Option<(&'static str, &'static str)> {
+    match data {
+        [0xFF, 0xD8, 0xFF, ..] => Some(("image/jpeg", "jpg")),
+        [0x89, b'P', b'N', b'G', ..] => Some(("image/png", "png")),
+        [b'G', b'I', b'F', b'8', ..] => Some(("image/gif", "gif")),
+        [b'B', b'M', ..] => Some(("image/bmp", "bmp")),
+        [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => Some(("image/tiff", "tif")),
+        [b'%', b'P', b'D', b'F', ..] => Some(("application/pdf", "pdf")),
+        [
+            b'R',
+            b'I',
+            b'F',
+            b'F',
+            _,
+            _,
+            _,
+            _,
+            b'W',
+            b'A',
+            b'V',
+            b'E',
+            ..,
+        ] => Some(("audio/wav", "wav")),
+        _ => None,
     }
 }
 
+/// An individual's `OBJE` line as written in the raw GEDCOM.
+#[derive(Debug, Default)]
+struct IndiObject<'a> {
+    /// Xref of the linked `OBJE` record; `None` for an inline object.
+    pointer: Option<&'a str>,
+    /// Flagged `_PRIM Y`: the individual's profile photo.
+    primary: bool,
+}
+
+/// `OBJE` lines of each individual, in order, keyed by individual xref.
+fn individual_objects(gedcom_str: &str) -> HashMap<&str, Vec<IndiObject<'_>>> {
+    let mut objects: HashMap<&str, Vec<IndiObject<'_>>> = HashMap::new();
+    let mut current_indi: Option<&str> = None;
+    let mut in_object = false;
+    for line in gedcom_str.lines() {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("0"), Some(xref), Some("INDI")) => current_indi = Some(xref),
+            (Some("0"), ..) => current_indi = None,
+            (Some("1"), Some("OBJE"), pointer) => {
+                in_object = current_indi.is_some();
+                if let Some(xref) = current_indi {
+                    objects.entry(xref).or_default().push(IndiObject {
+                        pointer: pointer.filter(|p| p.starts_with('@')),
+                        primary: false,
+                    });
+                }
+            }
+            (Some("1"), ..) => in_object = false,
+            (Some("2"), Some("_PRIM"), Some(flag)) if in_object => {
+                if let Some(object) = current_indi
+                    .and_then(|xref| objects.get_mut(xref))
+                    .and_then(|o| o.last_mut())
+                {
+                    object.primary = flag.eq_ignore_ascii_case("Y");
+                }
+            }
+            _ => {}
+        }
+    }
+    objects
+}
+
+/// Both ends of a move (`EMIG`, `IMMI`, `RESI`) as written in the raw
+/// GEDCOM: either two `PLAC` lines (origin, then destination) or the
+/// `_FROM`/`_TO` custom tags.
+#[derive(Debug, Default)]
+struct MovePlaces<'a> {
+    places: Vec<&'a str>,
+    from: Option<&'a str>,
+    to: Option<&'a str>,
+}
+
+impl<'a> MovePlaces<'a> {
+    fn origin(&self) -> Option<&'a str> {
+        self.from.or_else(|| self.places.first().copied())
+    }
+
+    /// With `_FROM` given, the `PLAC` is the other end of the move.
+    fn destination(&self) -> Option<&'a str> {
+        let plac = if self.from.is_some() { 0 } else { 1 };
+        self.to.or_else(|| self.places.get(plac).copied())
+    }
+}
+
+/// Places of each individual's moves, keyed by individual xref, move type
+/// and rank among that individual's moves of the type. ged_io keeps only
+/// the last `PLAC` of an event and drops `_FROM`/`_TO`, so both are read
+/// from the raw text.
+fn move_places(gedcom_str: &str) -> HashMap<(&str, EventType, usize), MovePlaces<'_>> {
+    let mut moves: HashMap<(&str, EventType, usize), MovePlaces<'_>> = HashMap::new();
+    let mut counts: HashMap<EventType, usize> = HashMap::new();
+    let mut current_indi: Option<&str> = None;
+    let mut current_move: Option<(EventType, usize)> = None;
+    for line in gedcom_str.lines() {
+        let mut parts = line.trim().splitn(3, ' ');
+        let (level, tag) = (parts.next(), parts.next());
+        let value = parts.next().map(str::trim).filter(|v| !v.is_empty());
+        match (level, tag) {
+            (Some("0"), Some(xref)) => {
+                current_indi = (value == Some("INDI")).then_some(xref);
+                current_move = None;
+                counts.clear();
+            }
+            (Some("1"), Some(tag)) => {
+                current_move = current_indi.and(move_type(tag)).map(|event_type| {
+                    let rank = counts.entry(event_type).or_default();
+                    *rank += 1;
+                    (event_type, *rank - 1)
+                });
+            }
+            (Some("2"), Some(tag @ ("PLAC" | "_FROM" | "_TO"))) => {
+                let (Some(xref), Some((event_type, rank)), Some(value)) =
+                    (current_indi, current_move, value)
+                else {
+                    continue;
+                };
+                let entry = moves.entry((xref, event_type, rank)).or_default();
+                match tag {
+                    "PLAC" => entry.places.push(value),
+                    "_FROM" => entry.from = Some(value),
+                    _ => entry.to = Some(value),
+                }
+            }
+            _ => {}
+        }
+    }
+    moves
+}
+
+/// Event type of a GEDCOM move tag.
+fn move_type(tag: &str) -> Option<EventType> {
+    EventType::from_gedcom_tag(tag).filter(EventType::has_destination)
+}
+
+/// Set both places of the moves imported for one individual (the events
+/// from `first_event` on) from their raw-text places — see [`move_places`].
+fn apply_move_places(
+    result: &mut ImportResult,
+    first_event: usize,
+    xref: &str,
+    moves: &HashMap<(&str, EventType, usize), MovePlaces<'_>>,
+    get_or_create_place: &mut dyn FnMut(&str, &mut ImportResult) -> Uuid,
+) {
+    let mut counts: HashMap<EventType, usize> = HashMap::new();
+    for i in first_event..result.events.len() {
+        let event_type = result.events[i].event_type;
+        if !event_type.has_destination() {
+            continue;
+        }
+        let rank = counts.entry(event_type).or_default();
+        *rank += 1;
+        let Some(places) = moves.get(&(xref, event_type, *rank - 1)) else {
+            continue;
+        };
+        if let Some(name) = places.origin() {
+            result.events[i].place_id = Some(get_or_create_place(name, result));
+        }
+        if let Some(name) = places.destination() {
+            result.events[i].place_id_to = Some(get_or_create_place(name, result));
+        }
+    }
+}
+
+/// Role of an `ASSO` participant, read from its `RELA` phrase. ged_io drops
+/// GEDCOM 7's `ROLE` substructure, but its keywords (`GODP`, `CLERGY`, ...)
+/// are recognized when a file carries them as `RELA` text.
+fn participant_role(
+    assoc: &ged_io::types::individual::association::Association,
+) -> ParticipantRole {
+    assoc
+        .relationship
+        .as_deref()
+        .map(ParticipantRole::from_relation)
+        .unwrap_or_default()
+}
+
+/// The `AGE` recorded on an event, in its GEDCOM form (`32y 6m`, `CHILD`).
+fn import_age(age: Option<&ged_io::types::age::Age>) -> Option<String> {
+    age.map(|a| a.to_string()).filter(|a| !a.is_empty())
+}
+
+/// The signed decimal coordinates of a place's GEDCOM `MAP`, whose `LATI`
+/// and `LONG` carry their hemisphere as a prefix (`N48.8566`, `W1.55`); a
+/// bare signed number is read as is. A value that does not parse, carries
+/// the other axis's hemisphere or is out of range drops both, with a
+/// warning.
+fn import_coordinates(
+    place: &str,
+    latitude: &str,
+    longitude: &str,
+    warnings: &mut Vec<String>,
+) -> Option<(f64, f64)> {
+    let coordinates = parse_gedcom_coord(latitude, 'N', 'S')
+        .zip(parse_gedcom_coord(longitude, 'E', 'W'))
+        .filter(|&(lat, lon)| {
+            validation::latitude(lat).is_ok() && validation::longitude(lon).is_ok()
+        });
+    if coordinates.is_none() {
+        warnings.push(format!(
+            "Place \"{place}\": ignoring invalid coordinates LATI {latitude} LONG {longitude}"
+        ));
+    }
+    coordinates
+}
+
+/// Parse a GEDCOM coordinate, `positive` and `negative` being the
+/// hemisphere letters of its axis (`N`/`S` or `E`/`W`), in either case.
+fn parse_gedcom_coord(s: &str, positive: char, negative: char) -> Option<f64> {
+    let s = s.trim();
+    let mut chars = s.chars();
+    let (sign, number) = match chars.next().map(|c| c.to_ascii_uppercase()) {
+        Some(c) if c == positive => (1.0, chars.as_str()),
+        Some(c) if c == negative => (-1.0, chars.as_str()),
+        _ => (1.0, s),
+    };
+    number
+        .trim()
+        .replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .map(|v| sign * v)
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // Ancestry closure table builder
 // ═══════════════════════════════════════════════════════════════════════
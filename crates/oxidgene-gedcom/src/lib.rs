@@ -7,11 +7,14 @@ pub mod export;
 pub mod import;
 
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
+use oxidgene_core::templates::TemplateSeed;
 use oxidgene_core::types::{
-    Citation, Event, EventWitness, Family, FamilyChild, FamilySpouse, Media, MediaLink, Note,
-    Person, PersonAncestry, PersonName, Place, Source,
+    Citation, Event, EventParticipant, ExternalId, Family, FamilyChild, FamilySpouse, Media,
+    MediaLink, Note, Person, PersonAncestry, PersonName, Place, Source,
 };
+use oxidgene_core::{EventType, NameType};
 
 /// The result of importing a GEDCOM file — all domain model entities extracted
 /// from the file, ready to be persisted.
@@ -23,7 +26,7 @@ pub struct ImportResult {
     pub family_spouses: Vec<FamilySpouse>,
     pub family_children: Vec<FamilyChild>,
     pub events: Vec<Event>,
-    pub event_witnesses: Vec<EventWitness>,
+    pub event_participants: Vec<EventParticipant>,
     pub places: Vec<Place>,
     pub sources: Vec<Source>,
     pub citations: Vec<Citation>,
@@ -31,15 +34,155 @@ pub struct ImportResult {
     pub media_links: Vec<MediaLink>,
     pub notes: Vec<Note>,
     pub person_ancestry: Vec<PersonAncestry>,
+    /// `_UID`/`REFN` identifiers of the imported persons and families.
+    pub external_ids: Vec<ExternalId>,
     /// Warnings collected during import (non-fatal issues).
     pub warnings: Vec<String>,
 }
 
+impl From<TemplateSeed> for ImportResult {
+    fn from(seed: TemplateSeed) -> Self {
+        Self {
+            persons: seed.persons,
+            families: seed.families,
+            family_spouses: seed.family_spouses,
+            family_children: seed.family_children,
+            person_ancestry: seed.person_ancestry,
+            ..Self::default()
+        }
+    }
+}
+
+/// Options of [`import::import_gedcom_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportOptions {
+    /// Custom (`_`-prefixed) tags imported into a proper field, by tag
+    /// (case-insensitive). Any other custom tag on a person, family or
+    /// source is kept as a note on it, with a warning.
+    pub custom_tags: Vec<(String, CustomTagTarget)>,
+    /// How records carrying an xref (`INDI`, `FAM`, `SOUR`, `OBJE`) get
+    /// their ids.
+    pub id_strategy: IdStrategy,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            custom_tags: vec![
+                (
+                    "_MARNM".to_string(),
+                    CustomTagTarget::Name(NameType::Married),
+                ),
+                (
+                    "_AKA".to_string(),
+                    CustomTagTarget::Name(NameType::AlsoKnownAs),
+                ),
+                (
+                    "_MILT".to_string(),
+                    CustomTagTarget::Event(EventType::MilitaryService),
+                ),
+            ],
+            id_strategy: IdStrategy::default(),
+        }
+    }
+}
+
+/// Id assignment of imported records, per [`ImportOptions::id_strategy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdStrategy {
+    /// A fresh time-ordered (v7) id on every import.
+    #[default]
+    Random,
+    /// A UUIDv5 of the record tag and xref (`INDI:@I1@`) in the tree's
+    /// namespace, so re-importing a file into the same tree yields the same
+    /// ids. Records without an xref (names, events, places…) still get
+    /// fresh ids.
+    DeterministicFromXref,
+}
+
+impl IdStrategy {
+    /// Id of the `tag` record with `xref` imported into `tree_id`.
+    pub fn record_id(self, tree_id: Uuid, tag: &str, xref: &str) -> Uuid {
+        match self {
+            Self::Random => Uuid::now_v7(),
+            Self::DeterministicFromXref => {
+                Uuid::new_v5(&tree_id, format!("{tag}:{xref}").as_bytes())
+            }
+        }
+    }
+}
+
+/// What a custom tag listed in [`ImportOptions::custom_tags`] becomes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustomTagTarget {
+    /// An additional name of the person. The tag's value is a GEDCOM name
+    /// (`Mary /Smith/`) or a bare surname, completed with the given names
+    /// of the name it sits under (else of the primary name).
+    Name(NameType),
+    /// An event of the person or family, dated and placed by the tag's
+    /// `DATE` and `PLAC`; its value (else `TYPE`) is the description.
+    Event(EventType),
+}
+
 /// The result of exporting domain model entities to a GEDCOM string.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportResult {
     /// The GEDCOM 5.5.1 formatted string.
     pub gedcom: String,
-    /// Warnings collected during export.
+    /// Warnings collected during export, including the
+    /// [`ConformanceIssue`]s found in the written file.
     pub warnings: Vec<String>,
 }
+
+/// A breach of the GEDCOM 5.5.1 structural rules found by
+/// [`export::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConformanceIssue {
+    /// 1-based line number, `None` for issues with the file as a whole
+    /// (e.g. a missing trailer).
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl std::fmt::Display for ConformanceIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "GEDCOM conformance, line {line}: {}", self.message),
+            None => write!(f, "GEDCOM conformance: {}", self.message),
+        }
+    }
+}
+
+/// Options of [`export::export_gedcom`], including the metadata written to
+/// the GEDCOM header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// Collapse every `EventType::Occupation` event of a person back into a
+    /// single `OCCU` tag (values joined with `", "`) instead of one `OCCU`
+    /// tag per event. Some importers — Geneanet in particular — only
+    /// support a single profession field per individual, so this is an
+    /// opt-in, lossy compatibility option.
+    pub merge_occupations: bool,
+    /// Name of the submitter record (`SUBM`) the header points to. GEDCOM
+    /// 5.5.1 requires one, and some importers reject files without it.
+    pub submitter: String,
+    /// Name of the producing software (`HEAD.SOUR.NAME`).
+    pub software_name: String,
+    /// Version of the producing software (`HEAD.SOUR.VERS`).
+    pub software_version: String,
+    /// Language of the file's texts (`HEAD.LANG`), as a GEDCOM 5.5.1
+    /// language name such as `English` or `French`. Omitted when `None`.
+    pub language: Option<String>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            merge_occupations: false,
+            submitter: "Unknown".to_string(),
+            software_name: "OxidGene".to_string(),
+            software_version: env!("CARGO_PKG_VERSION").to_string(),
+            language: None,
+        }
+    }
+}
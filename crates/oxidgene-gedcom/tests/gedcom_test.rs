@@ -1,12 +1,15 @@
 //! Integration tests for GEDCOM import and export.
 
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 use uuid::Uuid;
 
 use oxidgene_core::types::{Note, Person, PersonName};
-use oxidgene_core::{NameType, Privacy, Sex};
-use oxidgene_gedcom::export::export_gedcom;
-use oxidgene_gedcom::import::import_gedcom;
+use oxidgene_core::{
+    Calendar, EventType, ExternalIdKind, FactType, NameType, NoteFormat, Privacy, Sex,
+};
+use oxidgene_gedcom::export::{export_gedcom, validate};
+use oxidgene_gedcom::import::{import_gedcom, import_gedcom_with};
+use oxidgene_gedcom::{CustomTagTarget, ExportOptions, IdStrategy, ImportOptions, ImportResult};
 
 /// Minimal GEDCOM 5.5.1 with one individual.
 const MINIMAL_GEDCOM: &str = "\
@@ -375,6 +378,355 @@ fn test_import_multimedia() {
     assert!(ml.person_id.is_some());
 }
 
+#[test]
+fn test_import_multimedia_blobs() {
+    let gedcom = "\
+0 HEAD
+1 CHAR UTF-8
+0 @M1@ OBJE
+1 FORM PNG
+1 TITL Portrait
+1 BLOB
+2 CONT WJ/CFko84
+2 CONT Uc....B
+0 @M2@ OBJE
+1 BLOB
+2 CONT /9j/4AAQSkZJRgAB
+0 @M3@ OBJE
+1 BLOB
+2 CONT **not a blob**
+0 @M4@ OBJE
+1 BLOB
+2 CONT ............
+0 @I1@ INDI
+1 NAME Jean /Dupont/
+1 OBJE @M1@
+0 TRLR
+";
+    let result = import_gedcom(gedcom, Uuid::now_v7()).unwrap();
+
+    // Embedded content has nowhere to go: no media row, and no link to one.
+    assert!(result.media.is_empty());
+    assert!(result.media_links.is_empty());
+    assert_eq!(result.persons.len(), 1);
+
+    // GEDCOM 5.5 alphabet, split over several CONT lines, and standard
+    // base64 from other exporters, are still decoded for the warning.
+    assert!(result.warnings.iter().any(|w| w.contains("@M1@")
+        && w.contains("image/png BLOB (12 bytes)")
+        && w.contains("without media storage")));
+    assert!(
+        result
+            .warnings
+            .iter()
+            .any(|w| w.contains("@M2@") && w.contains("image/jpeg BLOB (12 bytes)"))
+    );
+
+    assert!(
+        result
+            .warnings
+            .iter()
+            .any(|w| w.contains("@M3@") && w.contains("undecodable"))
+    );
+    assert!(
+        result
+            .warnings
+            .iter()
+            .any(|w| w.contains("@M4@") && w.contains("unknown format"))
+    );
+}
+
+#[test]
+fn test_import_prim_flags_profile_photo() {
+    let gedcom = "\
+0 HEAD
+1 CHAR UTF-8
+0 @M1@ OBJE
+1 FILE /photos/wedding.jpg
+2 FORM image/jpeg
+0 @I1@ INDI
+1 NAME John /Doe/
+1 OBJE @M1@
+1 OBJE
+2 FILE /photos/portrait.jpg
+3 FORM image/jpeg
+2 _PRIM Y
+1 BIRT
+2 _PRIM Y
+0 @I2@ INDI
+1 NAME Jane /Doe/
+1 OBJE @M1@
+2 _PRIM N
+0 TRLR
+";
+    let tree_id = Uuid::now_v7();
+    let result = import_gedcom(gedcom, tree_id).unwrap();
+
+    let portrait = result
+        .media
+        .iter()
+        .find(|m| m.file_path == "/photos/portrait.jpg")
+        .unwrap();
+    assert_eq!(result.media_links.len(), 3);
+    for link in &result.media_links {
+        assert_eq!(link.is_profile, link.media_id == portrait.id);
+    }
+}
+
+#[test]
+fn test_import_custom_tags() {
+    let gedcom = "\
+0 HEAD
+1 CHAR UTF-8
+0 @I1@ INDI
+1 NAME Mary /Jones/
+2 _MARNM Smith
+1 SEX F
+1 _UID 0A1B2C
+1 _MILT Served in the Great War
+2 DATE 1917
+2 PLAC Verdun
+2 NOTE 3rd infantry
+1 _ELEC
+2 TYPE Mayor
+1 FAMC @F1@
+2 _FREL Natural
+0 @F1@ FAM
+1 _STAT Married
+0 @S1@ SOUR
+1 TITL Census
+1 _APID 1,7602::123
+0 TRLR
+";
+    let result = import_gedcom(gedcom, Uuid::now_v7()).unwrap();
+    let person_id = result.persons[0].id;
+
+    // Mapped: a married name and a military service event.
+    let married = result
+        .person_names
+        .iter()
+        .find(|n| n.name_type == NameType::Married)
+        .unwrap();
+    assert_eq!(married.given_names.as_deref(), Some("Mary"));
+    assert_eq!(married.surname.as_deref(), Some("Smith"));
+    assert!(!married.is_primary);
+    let service = &result.events[0];
+    assert_eq!(service.event_type, EventType::MilitaryService);
+    assert_eq!(service.person_id, Some(person_id));
+    assert_eq!(service.date_value.as_deref(), Some("1917"));
+    assert_eq!(
+        service.description.as_deref(),
+        Some("Served in the Great War")
+    );
+    assert_eq!(result.places[0].name, "Verdun");
+    assert!(
+        result
+            .notes
+            .iter()
+            .any(|n| n.event_id == Some(service.id) && n.text == "3rd infantry")
+    );
+
+    // Unmapped: kept as notes on their record, `_UID` excepted.
+    let kept: Vec<&str> = result
+        .notes
+        .iter()
+        .filter(|n| n.event_id.is_none())
+        .map(|n| n.text.as_str())
+        .collect();
+    assert_eq!(
+        kept,
+        vec![
+            "_APID 1,7602::123",
+            "_ELEC\n  TYPE Mayor",
+            "_FREL Natural",
+            "_STAT Married",
+        ]
+    );
+    assert!(
+        result
+            .notes
+            .iter()
+            .any(|n| n.family_id.is_some() && n.text == "_STAT Married")
+    );
+    assert!(
+        result
+            .notes
+            .iter()
+            .any(|n| n.source_id.is_some() && n.text.starts_with("_APID"))
+    );
+    assert!(
+        result
+            .warnings
+            .iter()
+            .any(|w| w.contains("_ELEC") && w.contains("1 occurrence"))
+    );
+
+    // Without the mapping, `_MILT` is kept as a note too.
+    let options = ImportOptions {
+        custom_tags: vec![("_marnm".into(), CustomTagTarget::Name(NameType::Married))],
+        ..ImportOptions::default()
+    };
+    let result = import_gedcom_with(gedcom, Uuid::now_v7(), &options).unwrap();
+    assert!(result.events.is_empty());
+    assert!(result.notes.iter().any(|n| n.text
+        == "_MILT Served in the Great War\n  DATE 1917\n  PLAC Verdun\n  NOTE 3rd infantry"));
+}
+
+#[test]
+fn test_deterministic_ids_from_xref() {
+    let gedcom = "\
+0 HEAD
+1 CHAR UTF-8
+0 @M1@ OBJE
+1 FILE /photos/portrait.jpg
+2 FORM image/jpeg
+0 @I1@ INDI
+1 NAME John /Doe/
+1 OBJE @M1@
+0 @I2@ INDI
+1 NAME Jane /Doe/
+0 TRLR
+";
+    let tree_id = Uuid::now_v7();
+    let options = ImportOptions {
+        id_strategy: IdStrategy::DeterministicFromXref,
+        ..ImportOptions::default()
+    };
+    let first = import_gedcom_with(gedcom, tree_id, &options).unwrap();
+    let second = import_gedcom_with(gedcom, tree_id, &options).unwrap();
+
+    assert_eq!(first.persons[0].id, second.persons[0].id);
+    assert_eq!(first.persons[1].id, second.persons[1].id);
+    assert_eq!(first.media[0].id, second.media[0].id);
+    assert_eq!(first.media_links[0].media_id, first.media[0].id);
+    assert_ne!(first.persons[0].id, first.persons[1].id);
+
+    // Scoped to the tree, and random by default.
+    let other_tree = import_gedcom_with(gedcom, Uuid::now_v7(), &options).unwrap();
+    assert_ne!(first.persons[0].id, other_tree.persons[0].id);
+    let random = import_gedcom(gedcom, tree_id).unwrap();
+    assert_ne!(first.persons[0].id, random.persons[0].id);
+}
+
+#[test]
+fn test_name_citation_round_trips_under_name() {
+    let gedcom = "\
+0 HEAD
+1 GEDC
+2 VERS 5.5.1
+0 @I1@ INDI
+1 NAME Jean /Dupond/
+2 SOUR @S1@
+3 PAGE f. 12
+1 SOUR @S1@
+0 @S1@ SOUR
+1 TITL Parish register
+0 TRLR
+";
+    let imported = import_gedcom(gedcom, Uuid::now_v7()).unwrap();
+    assert_eq!(imported.citations.len(), 2);
+    let name_id = imported.person_names[0].id;
+    let name_cite = imported
+        .citations
+        .iter()
+        .find(|c| c.fact_type == Some(FactType::Name))
+        .expect("name citation");
+    assert_eq!(name_cite.fact_ref, Some(name_id));
+    assert_eq!(name_cite.person_id, Some(imported.persons[0].id));
+    assert_eq!(name_cite.page.as_deref(), Some("f. 12"));
+    assert_eq!(
+        imported
+            .citations
+            .iter()
+            .filter(|c| c.fact_type.is_none())
+            .count(),
+        1
+    );
+
+    let exported = export_gedcom(
+        &imported.persons,
+        &imported.person_names,
+        &imported.families,
+        &imported.family_spouses,
+        &imported.family_children,
+        &imported.events,
+        &imported.event_participants,
+        &imported.places,
+        &imported.sources,
+        &imported.citations,
+        &imported.media,
+        &imported.media_links,
+        &imported.notes,
+        &imported.external_ids,
+        &ExportOptions::default(),
+    )
+    .unwrap();
+    assert!(
+        exported
+            .gedcom
+            .contains("2 SURN Dupond\n2 SOUR @S1@\n3 PAGE f. 12")
+    );
+    let reimported = import_gedcom(&exported.gedcom, Uuid::now_v7()).unwrap();
+    assert_eq!(
+        reimported
+            .citations
+            .iter()
+            .filter(|c| c.fact_type == Some(FactType::Name))
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_source_multimedia_round_trips() {
+    let gedcom = "\
+0 HEAD
+1 GEDC
+2 VERS 5.5.1
+0 @S1@ SOUR
+1 TITL Parish register of Saint-Jean
+1 OBJE @M1@
+0 @M1@ OBJE
+1 FILE /scans/register_p12.jpg
+2 FORM image/jpeg
+0 TRLR
+";
+    let tree_id = Uuid::now_v7();
+    let imported = import_gedcom(gedcom, tree_id).unwrap();
+
+    assert_eq!(imported.media.len(), 1);
+    assert_eq!(imported.media_links.len(), 1);
+    let ml = &imported.media_links[0];
+    assert_eq!(ml.media_id, imported.media[0].id);
+    assert_eq!(ml.source_id, Some(imported.sources[0].id));
+    assert!(ml.person_id.is_none());
+
+    let exported = export_gedcom(
+        &imported.persons,
+        &imported.person_names,
+        &imported.families,
+        &imported.family_spouses,
+        &imported.family_children,
+        &imported.events,
+        &imported.event_participants,
+        &imported.places,
+        &imported.sources,
+        &imported.citations,
+        &imported.media,
+        &imported.media_links,
+        &imported.notes,
+        &imported.external_ids,
+        &ExportOptions::default(),
+    )
+    .unwrap();
+    let reimported = import_gedcom(&exported.gedcom, Uuid::now_v7()).unwrap();
+    assert_eq!(reimported.media_links.len(), 1);
+    assert_eq!(
+        reimported.media_links[0].source_id,
+        Some(reimported.sources[0].id)
+    );
+}
+
 #[test]
 fn test_import_place_dedup() {
     // London appears twice (birth and marriage) — should be deduplicated
@@ -386,6 +738,32 @@ fn test_import_place_dedup() {
     assert!(result.places[0].name.contains("London"));
 }
 
+#[test]
+fn test_import_multiline_note_keeps_line_breaks() {
+    let gedcom = "\
+0 HEAD
+1 CHAR UTF-8
+0 @I1@ INDI
+1 NAME John /Doe/
+1 NOTE # Research
+2 CONT
+2 CONT - parish regis
+2 CONC ter checked\r
+2 CONT - census pending
+0 TRLR
+";
+    let tree_id = Uuid::now_v7();
+    let result = import_gedcom(gedcom, tree_id).unwrap();
+
+    assert_eq!(result.notes.len(), 1);
+    let note = &result.notes[0];
+    assert_eq!(
+        note.text,
+        "# Research\n\n- parish register checked\n- census pending"
+    );
+    assert_eq!(note.format, NoteFormat::Plain);
+}
+
 #[test]
 fn test_import_invalid_gedcom() {
     let tree_id = Uuid::now_v7();
@@ -446,7 +824,7 @@ fn test_import_asso_witness_and_godparent() {
     let result = import_gedcom(ASSOCIATION_GEDCOM, tree_id).unwrap();
 
     assert_eq!(result.persons.len(), 5); // I1 Guillaume, I2 Jeanne, I3 witness, I4 baby, I5 godmother
-    assert_eq!(result.event_witnesses.len(), 2);
+    assert_eq!(result.event_participants.len(), 2);
 
     let witness_person_id = result.persons[2].id; // I3
     let marriage = result
@@ -455,12 +833,16 @@ fn test_import_asso_witness_and_godparent() {
         .find(|e| e.event_type == oxidgene_core::EventType::Marriage)
         .expect("marriage event missing");
     let marriage_witness = result
-        .event_witnesses
+        .event_participants
         .iter()
         .find(|w| w.event_id == marriage.id)
         .expect("marriage witness missing");
     assert_eq!(marriage_witness.person_id, witness_person_id);
     assert_eq!(marriage_witness.relation.as_deref(), Some("witness"));
+    assert_eq!(
+        marriage_witness.role,
+        oxidgene_core::ParticipantRole::Witness
+    );
 
     let godmother_person_id = result.persons[4].id; // I5
     let baptism = result
@@ -469,12 +851,16 @@ fn test_import_asso_witness_and_godparent() {
         .find(|e| e.event_type == oxidgene_core::EventType::Baptism)
         .expect("baptism event missing");
     let baptism_witness = result
-        .event_witnesses
+        .event_participants
         .iter()
         .find(|w| w.event_id == baptism.id)
         .expect("baptism association missing");
     assert_eq!(baptism_witness.person_id, godmother_person_id);
     assert_eq!(baptism_witness.relation.as_deref(), Some("Godmother"));
+    assert_eq!(
+        baptism_witness.role,
+        oxidgene_core::ParticipantRole::Godparent
+    );
 }
 
 #[test]
@@ -482,11 +868,118 @@ fn test_import_asso_dedups_against_nested_event_association() {
     // Gramps sometimes writes the same witness fact twice: once as a
     // non-standard `ASSO` nested inside the event, once as the standard
     // level-1 `ASSO` on the witness's own record. Both must collapse into
-    // a single EventWitness, not two.
+    // a single EventParticipant, not two.
     let tree_id = Uuid::now_v7();
     let result = import_gedcom(ASSOCIATION_DUPLICATE_GEDCOM, tree_id).unwrap();
 
-    assert_eq!(result.event_witnesses.len(), 1);
+    assert_eq!(result.event_participants.len(), 1);
+}
+
+#[test]
+fn test_import_normalizes_place_coordinates() {
+    let gedcom = concat!(
+        "0 HEAD\n",
+        "1 CHAR UTF-8\n",
+        "0 @I1@ INDI\n",
+        "1 NAME Jean /Martin/\n",
+        "1 BIRT\n",
+        "2 PLAC Paris\n",
+        "3 MAP\n",
+        "4 LATI N48.8566\n",
+        "4 LONG E2.3522\n",
+        "1 BAPM\n",
+        "2 PLAC Rio de Janeiro\n",
+        "3 MAP\n",
+        "4 LATI s22.9068\n",
+        "4 LONG W43.1729\n",
+        "1 DEAT\n",
+        "2 PLAC Nowhere\n",
+        "3 MAP\n",
+        "4 LATI N482.8566\n",
+        "4 LONG E2.3522\n",
+        "1 BURI\n",
+        "2 PLAC Swapped\n",
+        "3 MAP\n",
+        "4 LATI E2.3522\n",
+        "4 LONG N48.8566\n",
+        "0 TRLR\n",
+    );
+    let result = import_gedcom(gedcom, Uuid::now_v7()).unwrap();
+    let coordinates = |name: &str| {
+        let place = result.places.iter().find(|p| p.name == name).unwrap();
+        place.latitude.zip(place.longitude)
+    };
+
+    assert_eq!(coordinates("Paris"), Some((48.8566, 2.3522)));
+    assert_eq!(coordinates("Rio de Janeiro"), Some((-22.9068, -43.1729)));
+    // Out of range, or the hemisphere of the other axis: dropped.
+    assert_eq!(coordinates("Nowhere"), None);
+    assert_eq!(coordinates("Swapped"), None);
+    assert_eq!(
+        result
+            .warnings
+            .iter()
+            .filter(|w| w.contains("invalid coordinates"))
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn test_import_calendar_escapes_round_trip() {
+    let gedcom = concat!(
+        "0 HEAD\n",
+        "1 CHAR UTF-8\n",
+        "0 @I1@ INDI\n",
+        "1 NAME Jean /Martin/\n",
+        "1 BIRT\n",
+        "2 DATE @#DJULIAN@ 3 SEP 1752\n",
+        "1 BAPM\n",
+        "2 DATE @#DFRENCH R@ 12 VEND 3\n",
+        "1 DEAT\n",
+        "2 DATE ABT 1830\n",
+        "0 TRLR\n",
+    );
+    let result = import_gedcom(gedcom, Uuid::now_v7()).unwrap();
+    let event = |event_type| {
+        result
+            .events
+            .iter()
+            .find(|e| e.event_type == event_type)
+            .unwrap()
+    };
+
+    let birth = event(EventType::Birth);
+    assert_eq!(birth.calendar, Calendar::Julian);
+    assert_eq!(birth.date_value.as_deref(), Some("3 SEP 1752"));
+    assert_eq!(birth.date_sort, NaiveDate::from_ymd_opt(1752, 9, 14));
+    let baptism = event(EventType::Baptism);
+    assert_eq!(baptism.calendar, Calendar::FrenchRepublican);
+    assert_eq!(baptism.date_sort, NaiveDate::from_ymd_opt(1794, 10, 3));
+    assert_eq!(event(EventType::Death).calendar, Calendar::Gregorian);
+
+    let exported = export_gedcom(
+        &result.persons,
+        &result.person_names,
+        &result.families,
+        &result.family_spouses,
+        &result.family_children,
+        &result.events,
+        &result.event_participants,
+        &result.places,
+        &result.sources,
+        &result.citations,
+        &result.media,
+        &result.media_links,
+        &result.notes,
+        &result.external_ids,
+        &ExportOptions::default(),
+    )
+    .unwrap()
+    .gedcom;
+    assert!(exported.contains("2 DATE @#DJULIAN@ 3 SEP 1752"));
+    assert!(exported.contains("2 DATE @#DFRENCH R@ 12 VEND 3"));
+    assert!(exported.contains("2 DATE ABT 1830"));
 }
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -505,14 +998,15 @@ fn test_export_produces_valid_gedcom() {
         &result.family_spouses,
         &result.family_children,
         &result.events,
-        &result.event_witnesses,
+        &result.event_participants,
         &result.places,
         &result.sources,
         &result.citations,
         &result.media,
         &result.media_links,
         &result.notes,
-        false,
+        &result.external_ids,
+        &ExportOptions::default(),
     )
     .unwrap();
 
@@ -527,6 +1021,129 @@ fn test_export_produces_valid_gedcom() {
     assert!(export.gedcom.contains("Doe"));
 }
 
+#[test]
+fn test_validate_accepts_exported_gedcom() {
+    let tree_id = Uuid::now_v7();
+    let result = import_gedcom(MINIMAL_GEDCOM, tree_id).unwrap();
+    let export = export_gedcom(
+        &result.persons,
+        &result.person_names,
+        &result.families,
+        &result.family_spouses,
+        &result.family_children,
+        &result.events,
+        &result.event_participants,
+        &result.places,
+        &result.sources,
+        &result.citations,
+        &result.media,
+        &result.media_links,
+        &result.notes,
+        &result.external_ids,
+        &ExportOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(validate(&export.gedcom), vec![]);
+    assert!(export.warnings.is_empty(), "{:?}", export.warnings);
+}
+
+#[test]
+fn test_validate_reports_structural_issues() {
+    let long_note = format!("1 NOTE {}", "x".repeat(260));
+    let gedcom = [
+        "0 @I1@ INDI",
+        "1 NAME John /Doe/",
+        "3 DATE 1900",
+        "1 FAMS @F9@",
+        "1 FAMC @I1@",
+        &long_note,
+        "0 @I1@ INDI",
+        "0 FAM",
+        "1 HUSB @not valid@",
+        "garbage",
+    ]
+    .join("\n");
+
+    let issues = validate(&gedcom);
+    let lines: Vec<Option<usize>> = issues.iter().map(|i| i.line).collect();
+    assert_eq!(
+        lines,
+        vec![
+            Some(3),  // level 3 under a level 1 line
+            Some(6),  // over 255 bytes
+            Some(7),  // xref defined twice
+            Some(8),  // FAM without an xref
+            Some(9),  // malformed pointer
+            Some(10), // not a GEDCOM line
+            Some(1),  // no HEAD
+            None,     // no TRLR
+            Some(4),  // dangling pointer
+            Some(5),  // FAMC pointing to an individual
+        ],
+        "{issues:?}"
+    );
+    assert_eq!(
+        issues[8].to_string(),
+        "GEDCOM conformance, line 4: FAMS points to @F9@, which is not defined"
+    );
+    assert_eq!(
+        issues[9].message,
+        "FAMC must point to a FAM record, but @I1@ is INDI"
+    );
+}
+
+#[test]
+fn test_export_header_metadata() {
+    let tree_id = Uuid::now_v7();
+    let result = import_gedcom(MINIMAL_GEDCOM, tree_id).unwrap();
+    let export = |options: &ExportOptions| {
+        export_gedcom(
+            &result.persons,
+            &result.person_names,
+            &result.families,
+            &result.family_spouses,
+            &result.family_children,
+            &result.events,
+            &result.event_participants,
+            &result.places,
+            &result.sources,
+            &result.citations,
+            &result.media,
+            &result.media_links,
+            &result.notes,
+            &result.external_ids,
+            options,
+        )
+        .unwrap()
+        .gedcom
+    };
+
+    // Defaults: a submitter record is always present and pointed to.
+    let gedcom = export(&ExportOptions::default());
+    let version = env!("CARGO_PKG_VERSION");
+    assert!(gedcom.starts_with(&format!(
+        "0 HEAD\n1 GEDC\n2 VERS 5.5.1\n2 FORM LINEAGE-LINKED\n1 CHAR UTF-8\n\
+         1 SOUR OXIDGENE\n2 VERS {version}\n2 NAME OxidGene\n1 SUBM @U1@\n\
+         0 @U1@ SUBM\n1 NAME Unknown\n"
+    )));
+    assert!(!gedcom.contains("1 LANG"));
+
+    let gedcom = export(&ExportOptions {
+        submitter: "Marie Curie".to_string(),
+        software_name: "Family Archive".to_string(),
+        software_version: "2.1".to_string(),
+        language: Some("French".to_string()),
+        ..ExportOptions::default()
+    });
+    assert!(gedcom.contains("1 SOUR OXIDGENE\n2 VERS 2.1\n2 NAME Family Archive\n"));
+    assert!(gedcom.contains("1 SUBM @U1@\n1 LANG French\n0 @U1@ SUBM\n1 NAME Marie Curie\n"));
+
+    // The header survives a round trip.
+    let reimported = import_gedcom(&gedcom, tree_id).unwrap();
+    assert_eq!(reimported.persons.len(), 1);
+}
+
 #[test]
 fn test_export_family() {
     let tree_id = Uuid::now_v7();
@@ -539,14 +1156,15 @@ fn test_export_family() {
         &result.family_spouses,
         &result.family_children,
         &result.events,
-        &result.event_witnesses,
+        &result.event_participants,
         &result.places,
         &result.sources,
         &result.citations,
         &result.media,
         &result.media_links,
         &result.notes,
-        false,
+        &result.external_ids,
+        &ExportOptions::default(),
     )
     .unwrap();
 
@@ -575,14 +1193,15 @@ fn test_export_source() {
         &result.family_spouses,
         &result.family_children,
         &result.events,
-        &result.event_witnesses,
+        &result.event_participants,
         &result.places,
         &result.sources,
         &result.citations,
         &result.media,
         &result.media_links,
         &result.notes,
-        false,
+        &result.external_ids,
+        &ExportOptions::default(),
     )
     .unwrap();
 
@@ -607,7 +1226,8 @@ fn test_export_empty() {
         &[],
         &[],
         &[],
-        false,
+        &[],
+        &ExportOptions::default(),
     )
     .unwrap();
 
@@ -627,8 +1247,12 @@ fn test_export_long_utf8_note_does_not_panic() {
         tree_id,
         sex: Sex::Unknown,
         privacy: Privacy::Default,
+        living: None,
+        display_name: None,
+        locked: false,
         created_at: now,
         updated_at: now,
+        version: 1,
         deleted_at: None,
     };
     let name = PersonName {
@@ -648,12 +1272,14 @@ fn test_export_long_utf8_note_does_not_panic() {
         id: Uuid::now_v7(),
         tree_id,
         text: format!("{}é suite de la note", "a".repeat(254)),
+        format: NoteFormat::Plain,
         person_id: Some(person_id),
         event_id: None,
         family_id: None,
         source_id: None,
         created_at: now,
         updated_at: now,
+        version: 1,
         deleted_at: None,
     };
 
@@ -671,7 +1297,8 @@ fn test_export_long_utf8_note_does_not_panic() {
         &[],
         &[],
         &[note],
-        false,
+        &[],
+        &ExportOptions::default(),
     )
     .unwrap();
 
@@ -680,6 +1307,80 @@ fn test_export_long_utf8_note_does_not_panic() {
     assert!(export.gedcom.contains('é'));
 }
 
+#[test]
+fn test_long_accented_note_round_trips_within_line_limit() {
+    let tree_id = Uuid::now_v7();
+    let person_id = Uuid::now_v7();
+    let now = Utc::now();
+    let person = Person {
+        id: person_id,
+        tree_id,
+        sex: Sex::Female,
+        privacy: Privacy::Default,
+        living: None,
+        display_name: None,
+        locked: false,
+        created_at: now,
+        updated_at: now,
+        version: 1,
+        deleted_at: None,
+    };
+    // About 2 KB: runs of two-byte characters, spaces at every possible
+    // cut, and blank lines.
+    let text = format!(
+        "{}\nNée à Orléans, baptisée à Sées.\n\n{}",
+        "Élisabeth Bénédicte, fille de Jérôme, négociant à Nîmes. ".repeat(24),
+        "àéèêëîïôöùûüç".repeat(40)
+    );
+    assert!(text.len() > 2048);
+    let note = Note {
+        id: Uuid::now_v7(),
+        tree_id,
+        text: text.clone(),
+        format: NoteFormat::Plain,
+        person_id: Some(person_id),
+        event_id: None,
+        family_id: None,
+        source_id: None,
+        created_at: now,
+        updated_at: now,
+        version: 1,
+        deleted_at: None,
+    };
+
+    let export = export_gedcom(
+        &[person],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[note],
+        &[],
+        &ExportOptions::default(),
+    )
+    .unwrap();
+
+    for line in export.gedcom.lines() {
+        assert!(line.len() <= 255, "{} bytes: {line}", line.len());
+        if let Some(value) = line.strip_prefix("2 CONC ") {
+            assert!(!value.starts_with(' '), "{line}");
+        }
+    }
+    assert!(export.gedcom.contains("2 CONC"));
+    assert!(export.gedcom.contains("2 CONT\n"));
+
+    let imported = import_gedcom(&export.gedcom, tree_id).unwrap();
+    assert_eq!(imported.notes.len(), 1);
+    assert_eq!(imported.notes[0].text, text);
+}
+
 #[test]
 fn test_export_association_is_level_one_not_nested_in_event() {
     // GEDCOM 5.5.1 only allows `ASSO` as a direct child of an INDI record;
@@ -694,14 +1395,15 @@ fn test_export_association_is_level_one_not_nested_in_event() {
         &result.family_spouses,
         &result.family_children,
         &result.events,
-        &result.event_witnesses,
+        &result.event_participants,
         &result.places,
         &result.sources,
         &result.citations,
         &result.media,
         &result.media_links,
         &result.notes,
-        false,
+        &result.external_ids,
+        &ExportOptions::default(),
     )
     .unwrap();
 
@@ -736,14 +1438,15 @@ fn test_roundtrip_preserves_individuals() {
         &imported.family_spouses,
         &imported.family_children,
         &imported.events,
-        &imported.event_witnesses,
+        &imported.event_participants,
         &imported.places,
         &imported.sources,
         &imported.citations,
         &imported.media,
         &imported.media_links,
         &imported.notes,
-        false,
+        &imported.external_ids,
+        &ExportOptions::default(),
     )
     .unwrap();
 
@@ -774,6 +1477,55 @@ fn test_roundtrip_preserves_individuals() {
     );
 }
 
+#[test]
+fn test_roundtrip_intersex_sex() {
+    let gedcom = "\
+0 HEAD
+1 GEDC
+2 VERS 5.5.1
+2 FORM LINEAGE-LINKED
+1 CHAR UTF-8
+0 @I1@ INDI
+1 NAME Alex /Martin/
+1 SEX X
+0 @I2@ INDI
+1 NAME Claude /Martin/
+1 SEX U
+0 TRLR
+";
+    let sexes = |result: &ImportResult| {
+        let mut sexes: Vec<Sex> = result.persons.iter().map(|p| p.sex).collect();
+        sexes.sort_by_key(|s| s.to_string());
+        sexes
+    };
+    let imported = import_gedcom(gedcom, Uuid::now_v7()).unwrap();
+    assert_eq!(sexes(&imported), vec![Sex::Intersex, Sex::Unknown]);
+
+    let exported = export_gedcom(
+        &imported.persons,
+        &imported.person_names,
+        &imported.families,
+        &imported.family_spouses,
+        &imported.family_children,
+        &imported.events,
+        &imported.event_participants,
+        &imported.places,
+        &imported.sources,
+        &imported.citations,
+        &imported.media,
+        &imported.media_links,
+        &imported.notes,
+        &imported.external_ids,
+        &ExportOptions::default(),
+    )
+    .unwrap();
+    assert!(exported.gedcom.contains("1 SEX X"));
+    assert!(exported.gedcom.contains("1 SEX U"));
+
+    let reimported = import_gedcom(&exported.gedcom, Uuid::now_v7()).unwrap();
+    assert_eq!(sexes(&reimported), sexes(&imported));
+}
+
 #[test]
 fn test_roundtrip_preserves_names() {
     let tree_id = Uuid::now_v7();
@@ -786,14 +1538,15 @@ fn test_roundtrip_preserves_names() {
         &imported.family_spouses,
         &imported.family_children,
         &imported.events,
-        &imported.event_witnesses,
+        &imported.event_participants,
         &imported.places,
         &imported.sources,
         &imported.citations,
         &imported.media,
         &imported.media_links,
         &imported.notes,
-        false,
+        &imported.external_ids,
+        &ExportOptions::default(),
     )
     .unwrap();
 
@@ -806,6 +1559,176 @@ fn test_roundtrip_preserves_names() {
     assert_eq!(name.surname.as_deref(), Some("Doe"));
 }
 
+/// Records carrying the identifiers other programs give them.
+const EXTERNAL_ID_GEDCOM: &str = "\
+0 HEAD
+1 GEDC
+2 VERS 5.5.1
+2 FORM LINEAGE-LINKED
+1 CHAR UTF-8
+0 @I1@ INDI
+1 NAME John /Doe/
+1 _UID 8F1C4E2B9A0D4C7E8B3F6A5D2E1C0B9A
+1 _UID 0AB1C2D3E4F5A6B7C8D9E0F1A2B3C4D5
+1 REFN 1234
+0 @I2@ INDI
+1 NAME Jane /Roe/
+0 @F1@ FAM
+1 HUSB @I1@
+1 WIFE @I2@
+1 _UID 11112222333344445555666677778888
+0 TRLR
+";
+
+#[test]
+fn test_roundtrip_external_ids() {
+    let imported = import_gedcom(EXTERNAL_ID_GEDCOM, Uuid::now_v7()).unwrap();
+    let john = imported.persons[0].id;
+    let john_ids: Vec<_> = imported
+        .external_ids
+        .iter()
+        .filter(|e| e.person_id == Some(john))
+        .map(|e| (e.kind, e.value.as_str()))
+        .collect();
+    assert_eq!(
+        john_ids,
+        [
+            (ExternalIdKind::Uid, "8F1C4E2B9A0D4C7E8B3F6A5D2E1C0B9A"),
+            (ExternalIdKind::Uid, "0AB1C2D3E4F5A6B7C8D9E0F1A2B3C4D5"),
+            (ExternalIdKind::Refn, "1234"),
+        ]
+    );
+    let family_id = imported.families[0].id;
+    assert!(
+        imported.external_ids.iter().any(
+            |e| e.family_id == Some(family_id) && e.value == "11112222333344445555666677778888"
+        )
+    );
+    assert_eq!(imported.external_ids.len(), 4);
+
+    let exported = export_gedcom(
+        &imported.persons,
+        &imported.person_names,
+        &imported.families,
+        &imported.family_spouses,
+        &imported.family_children,
+        &imported.events,
+        &imported.event_participants,
+        &imported.places,
+        &imported.sources,
+        &imported.citations,
+        &imported.media,
+        &imported.media_links,
+        &imported.notes,
+        &imported.external_ids,
+        &ExportOptions::default(),
+    )
+    .unwrap();
+    assert!(exported.gedcom.contains(
+        "0 @I1@ INDI\n1 _UID 8F1C4E2B9A0D4C7E8B3F6A5D2E1C0B9A\n1 _UID 0AB1C2D3E4F5A6B7C8D9E0F1A2B3C4D5\n1 REFN 1234\n"
+    ));
+    assert!(
+        exported
+            .gedcom
+            .contains("0 @F1@ FAM\n1 _UID 11112222333344445555666677778888\n")
+    );
+
+    let reimported = import_gedcom(&exported.gedcom, Uuid::now_v7()).unwrap();
+    let mut values: Vec<_> = reimported
+        .external_ids
+        .iter()
+        .map(|e| e.value.clone())
+        .collect();
+    let mut expected: Vec<_> = imported
+        .external_ids
+        .iter()
+        .map(|e| e.value.clone())
+        .collect();
+    values.sort();
+    expected.sort();
+    assert_eq!(values, expected);
+}
+
+/// Moves recorded with both ends: two `PLAC`s, and `_FROM`/`_TO`.
+const MOVE_GEDCOM: &str = "\
+0 HEAD
+1 GEDC
+2 VERS 5.5.1
+2 FORM LINEAGE-LINKED
+1 CHAR UTF-8
+0 @I1@ INDI
+1 NAME John /Doe/
+1 EMIG
+2 DATE 1892
+2 PLAC Cork, Ireland
+2 PLAC Boston, USA
+1 IMMI
+2 DATE 1892
+2 PLAC Boston, USA
+2 _FROM Liverpool, England
+1 BIRT
+2 PLAC Cork, Ireland
+0 TRLR
+";
+
+/// (type, origin, destination) of each event, by place name.
+fn event_places(result: &ImportResult) -> Vec<(EventType, Option<&str>, Option<&str>)> {
+    let name = |id: Option<Uuid>| {
+        id.and_then(|id| result.places.iter().find(|p| p.id == id))
+            .map(|p| p.name.as_str())
+    };
+    result
+        .events
+        .iter()
+        .map(|e| (e.event_type, name(e.place_id), name(e.place_id_to)))
+        .collect()
+}
+
+#[test]
+fn test_roundtrip_move_places() {
+    let imported = import_gedcom(MOVE_GEDCOM, Uuid::now_v7()).unwrap();
+    let places = event_places(&imported);
+    assert!(places.contains(&(
+        EventType::Emigration,
+        Some("Cork, Ireland"),
+        Some("Boston, USA")
+    )));
+    assert!(places.contains(&(
+        EventType::Immigration,
+        Some("Liverpool, England"),
+        Some("Boston, USA")
+    )));
+    assert!(places.contains(&(EventType::Birth, Some("Cork, Ireland"), None)));
+
+    let exported = export_gedcom(
+        &imported.persons,
+        &imported.person_names,
+        &imported.families,
+        &imported.family_spouses,
+        &imported.family_children,
+        &imported.events,
+        &imported.event_participants,
+        &imported.places,
+        &imported.sources,
+        &imported.citations,
+        &imported.media,
+        &imported.media_links,
+        &imported.notes,
+        &imported.external_ids,
+        &ExportOptions::default(),
+    )
+    .unwrap();
+    assert!(exported.gedcom.contains("1 EMIG\n2 _TO Boston, USA\n"));
+    assert!(exported.gedcom.contains("1 IMMI\n2 _TO Boston, USA\n"));
+
+    let reimported = import_gedcom(&exported.gedcom, Uuid::now_v7()).unwrap();
+    let mut expected = places;
+    let mut places = event_places(&reimported);
+    expected.sort_by_key(|p| format!("{p:?}"));
+    places.sort_by_key(|p| format!("{p:?}"));
+    assert_eq!(places, expected);
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // Geneanet-style import tests (no GIVN/SURN, inline SOUR, OBJE, OCCU)
 // ═══════════════════════════════════════════════════════════════════════
@@ -1022,14 +1945,15 @@ fn test_roundtrip_occupation_exports_as_occu_tag() {
         &imported.family_spouses,
         &imported.family_children,
         &imported.events,
-        &imported.event_witnesses,
+        &imported.event_participants,
         &imported.places,
         &imported.sources,
         &imported.citations,
         &imported.media,
         &imported.media_links,
         &imported.notes,
-        false,
+        &imported.external_ids,
+        &ExportOptions::default(),
     )
     .unwrap();
 
@@ -1152,14 +2076,15 @@ fn test_export_default_keeps_one_occu_tag_per_profession() {
         &imported.family_spouses,
         &imported.family_children,
         &imported.events,
-        &imported.event_witnesses,
+        &imported.event_participants,
         &imported.places,
         &imported.sources,
         &imported.citations,
         &imported.media,
         &imported.media_links,
         &imported.notes,
-        false,
+        &imported.external_ids,
+        &ExportOptions::default(),
     )
     .unwrap();
 
@@ -1180,14 +2105,18 @@ fn test_export_merge_occupations_option_collapses_to_one_occu_tag() {
         &imported.family_spouses,
         &imported.family_children,
         &imported.events,
-        &imported.event_witnesses,
+        &imported.event_participants,
         &imported.places,
         &imported.sources,
         &imported.citations,
         &imported.media,
         &imported.media_links,
         &imported.notes,
-        true,
+        &imported.external_ids,
+        &ExportOptions {
+            merge_occupations: true,
+            ..ExportOptions::default()
+        },
     )
     .unwrap();
 
@@ -1327,7 +2256,8 @@ fn test_export_result_serialization() {
         &[],
         &[],
         &[],
-        false,
+        &[],
+        &ExportOptions::default(),
     )
     .unwrap();
 
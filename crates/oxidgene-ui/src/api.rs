@@ -5,13 +5,18 @@
 //! from [`oxidgene_core`] directly, since those types already derive
 //! `Serialize` / `Deserialize`.
 
-use oxidgene_cache::types::{CachedPedigree, PedigreeDelta, SearchResult};
+use oxidgene_cache::types::{CachedPedigree, FuzzySearchResult, PedigreeDelta, SearchResult};
+use oxidgene_core::compare::PersonComparison;
+use oxidgene_core::templates::TreeTemplate;
 use oxidgene_core::types::{
-    Citation, Connection, Event, EventWitness, Family, FamilyChild, FamilySpouse, Note, Person,
-    PersonAncestry, PersonName, Place, Source, Tree,
+    Citation, Connection, Event, EventParticipant, Family, FamilyChild, FamilySpouse, Media,
+    MediaLink, Note, Person, PersonAncestry, PersonName, PersonTag, Place, ResearchTask, ShareLink,
+    Source, Tree,
 };
+use oxidgene_core::validation::plausibility::PlausibilityWarning;
 use oxidgene_core::{
-    Calendar, ChildType, Confidence, DateQualifier, EventType, NameType, Privacy, Sex, SpouseRole,
+    Calendar, ChildType, Confidence, DateQualifier, EventType, FactType, KinshipEdge, NameType,
+    NoteFormat, ParticipantRole, Privacy, RelativeKind, Sex, SpouseRole, TaskStatus,
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -25,10 +30,62 @@ pub struct PersonDetail {
     pub tree_id: Uuid,
     pub sex: Sex,
     pub privacy: Privacy,
+    #[serde(default)]
+    pub living: Option<bool>,
+    /// Locked against edits and deletion.
+    #[serde(default)]
+    pub locked: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub version: i32,
     pub sosa_number: Option<u64>,
+    /// How well the vital facts are sourced, from 0 to 1.
+    #[serde(default)]
+    pub sourcing_score: f32,
+    #[serde(default)]
+    pub has_descendants: bool,
+    #[serde(default)]
+    pub has_known_parents: bool,
+}
+
+// ── Person comparison ────────────────────────────────────────────────
+
+/// Mirrors `PersonCompareResponse`: the two compared persons and their
+/// field-by-field comparison.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PersonCompareResult {
+    pub a: Uuid,
+    pub b: Uuid,
+    #[serde(flatten)]
+    pub comparison: PersonComparison,
+}
+
+// ── Relationship path ────────────────────────────────────────────────
+
+/// Mirrors `RelationshipStep`: a person on the path and how they relate to
+/// the previous step (`None` for the first).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RelationshipStep {
+    pub person: RelationshipStepPerson,
+    pub edge: Option<KinshipEdge>,
+}
+
+/// The fields of a path step's person the UI shows.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RelationshipStepPerson {
+    pub id: Uuid,
+    pub display_name: Option<String>,
+}
+
+/// Mirrors `RelationshipPathResponse`: no common ancestor and no steps
+/// when the two persons are not related by blood.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RelationshipPath {
+    pub common_ancestor_id: Option<Uuid>,
+    pub generations_up: i32,
+    pub generations_down: i32,
+    pub steps: Vec<RelationshipStep>,
 }
 
 // ── Re-usable request / response DTOs (client-side mirrors) ─────────
@@ -47,6 +104,100 @@ pub struct DictionaryEntry {
     pub count: i64,
 }
 
+/// A surname in the tree's alphabetical index.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SurnameIndexEntry {
+    pub surname: String,
+    /// Letter the surname is filed under (`#` for non-letters).
+    pub initial: char,
+    pub count: i64,
+}
+
+/// Mirrors `SurnameStatsResponse`: the most frequent surnames counted per
+/// birth decade or birth place.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SurnameStats {
+    /// `decade` or `place`.
+    pub over: String,
+    /// Surnames with their total, most frequent first.
+    pub surnames: Vec<SurnameTotal>,
+    /// Decades chronologically, places most frequent first.
+    pub buckets: Vec<String>,
+    pub counts: Vec<SurnameBucketCount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SurnameTotal {
+    pub surname: String,
+    pub count: i64,
+}
+
+/// Persons of a surname born in one decade or place.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SurnameBucketCount {
+    pub surname: String,
+    pub bucket: String,
+    pub count: i64,
+}
+
+/// A recently created or updated entity of a tree.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ActivityEntry {
+    /// `person`, `family`, `event`, `place`, `source`, `media` or `note`.
+    pub kind: String,
+    pub id: Uuid,
+    /// Names, title or excerpt; for events, their person's or family's names.
+    pub label: String,
+    #[serde(default)]
+    pub event_type: Option<EventType>,
+    /// Person whose page shows the entity, when there is one.
+    #[serde(default)]
+    pub person_id: Option<Uuid>,
+    /// `created` or `updated`.
+    pub action: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// A citation of a source and the person, event or family it supports.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SourceUsageEntry {
+    pub citation_id: Uuid,
+    /// `person`, `event` or `family`.
+    pub owner_kind: String,
+    pub owner_id: Uuid,
+    /// Names of the person or family concerned.
+    pub label: String,
+    #[serde(default)]
+    pub event_type: Option<EventType>,
+    #[serde(default)]
+    pub event_date: Option<String>,
+    /// Person concerned (directly or through the event).
+    #[serde(default)]
+    pub person_id: Option<Uuid>,
+    #[serde(default)]
+    pub family_id: Option<Uuid>,
+    pub page: Option<String>,
+    pub text: Option<String>,
+    pub confidence: Confidence,
+    pub fact_type: Option<FactType>,
+}
+
+/// A tag in use in a tree, with the number of persons carrying it.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: i64,
+}
+
+/// A place suggested by the place-name autocomplete.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PlaceSuggestion {
+    pub id: Uuid,
+    pub name: String,
+    /// Number of events held at this place.
+    pub event_count: i64,
+}
+
 /// A source paired with its citation count.
 #[derive(Debug, Clone, Deserialize)]
 pub struct SourceDictionaryEntry {
@@ -120,6 +271,41 @@ pub struct DuplicateTreeBody {
     pub name: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct CreateShareLinkBody {
+    pub privatize: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// A tree of the tree list, with its live record counts.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TreeSummary {
+    #[serde(flatten)]
+    pub tree: Tree,
+    pub person_count: i64,
+    pub family_count: i64,
+    pub event_count: i64,
+    /// The latest change to the tree or any of its persons, families and
+    /// events.
+    pub last_modified_at: chrono::DateTime<chrono::Utc>,
+    /// Years of the earliest and latest dated events.
+    #[serde(default)]
+    pub first_year: Option<i32>,
+    #[serde(default)]
+    pub last_year: Option<i32>,
+}
+
+/// Tree metadata as exposed through a share link.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SharedTree {
+    pub name: String,
+    pub description: Option<String>,
+    pub sosa_root_person_id: Option<Uuid>,
+    pub privatize: bool,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 // ── Person request bodies ───────────────────────────────────────────
 
 #[derive(Debug, Serialize)]
@@ -133,6 +319,90 @@ pub struct UpdatePersonBody {
     pub sex: Option<Sex>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub privacy: Option<Privacy>,
+    /// `Some(None)` is sent as `null`, clearing the living-status override.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub living: Option<Option<bool>>,
+}
+
+/// Body for `POST .../persons/{id}/relatives`.
+#[derive(Debug, Serialize)]
+pub struct AddRelativeBody {
+    pub kind: RelativeKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sex: Option<Sex>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub given_names: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub surname: Option<String>,
+}
+
+/// Request body for quick entry: a person with their name, birth and
+/// death in one request. Places are given by name.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct QuickPersonBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sex: Option<Sex>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub given_names: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub surname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub birth_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub birth_place: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub death_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub death_place: Option<String>,
+}
+
+/// Mirrors `PersonSummary` as serialized by the quick-entry endpoint.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct QuickPersonSummary {
+    pub display_name: Option<String>,
+    pub birth_year: Option<i32>,
+    pub death_year: Option<i32>,
+    pub living: bool,
+}
+
+/// Mirrors `QuickPersonResponse`: the new person with their name, events
+/// and summary.
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuickPerson {
+    pub person: Person,
+    pub name: PersonName,
+    pub events: Vec<Event>,
+    pub summary: QuickPersonSummary,
+}
+
+/// Mirrors `AddRelativeResponse`: the new person and the family linking
+/// them to the person they were added to.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AddedRelative {
+    pub person: Person,
+    pub family_id: Uuid,
+    pub family_created: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkDeletePersonsBody {
+    pub person_ids: Vec<Uuid>,
+    pub cascade: bool,
+}
+
+/// A person a bulk delete left alone; `reason` is `not_found`,
+/// `family_member` or `locked`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BulkDeleteSkipped {
+    pub id: Uuid,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BulkDeleteResult {
+    pub deleted: Vec<Uuid>,
+    pub skipped: Vec<BulkDeleteSkipped>,
+    pub families_deleted: Vec<Uuid>,
 }
 
 // ── PersonName request bodies ───────────────────────────────────────
@@ -171,7 +441,9 @@ pub struct UpdatePersonNameBody {
 #[derive(Debug, Serialize)]
 pub struct AddSpouseBody {
     pub person_id: Uuid,
-    pub role: SpouseRole,
+    /// `None` lets the server infer it from the person's sex.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<SpouseRole>,
     #[serde(default)]
     pub sort_order: i32,
 }
@@ -186,6 +458,16 @@ pub struct AddChildBody {
 
 // ── Event request bodies ────────────────────────────────────────────
 
+/// Response of event create and update: the saved event with the date
+/// plausibility warnings it raised.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SavedEvent {
+    #[serde(flatten)]
+    pub event: Event,
+    #[serde(default)]
+    pub warnings: Vec<PlausibilityWarning>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct CreateEventBody {
     pub event_type: EventType,
@@ -196,6 +478,8 @@ pub struct CreateEventBody {
     pub calendar: Calendar,
     pub cause: Option<String>,
     pub place_id: Option<Uuid>,
+    /// Destination of a move; ignored for other event types.
+    pub place_id_to: Option<Uuid>,
     pub person_id: Option<Uuid>,
     pub family_id: Option<Uuid>,
     pub description: Option<String>,
@@ -220,13 +504,16 @@ pub struct UpdateEventBody {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub place_id: Option<Option<Uuid>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub place_id_to: Option<Option<Uuid>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<Option<String>>,
 }
 
-/// Request body for adding a witness to an event.
+/// Request body for adding a participant to an event.
 #[derive(Debug, Serialize)]
-pub struct AddEventWitnessBody {
+pub struct AddEventParticipantBody {
     pub person_id: Uuid,
+    pub role: ParticipantRole,
     pub relation: Option<String>,
     #[serde(default)]
     pub sort_order: i32,
@@ -287,6 +574,10 @@ pub struct CreateCitationBody {
     pub page: Option<String>,
     pub confidence: Confidence,
     pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fact_type: Option<FactType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fact_ref: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize)]
@@ -304,6 +595,7 @@ pub struct UpdateCitationBody {
 #[derive(Debug, Serialize)]
 pub struct CreateNoteBody {
     pub text: String,
+    pub format: NoteFormat,
     pub person_id: Option<Uuid>,
     pub event_id: Option<Uuid>,
     pub family_id: Option<Uuid>,
@@ -314,18 +606,132 @@ pub struct CreateNoteBody {
 pub struct UpdateNoteBody {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<NoteFormat>,
+}
+
+// ── Research task request bodies ────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct CreateTaskBody {
+    pub person_id: Option<Uuid>,
+    pub family_id: Option<Uuid>,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<chrono::NaiveDate>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct UpdateTaskBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<TaskStatus>,
+    /// `Some(None)` is sent as `null`, clearing the due date.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<Option<chrono::NaiveDate>>,
 }
 
 // ── MediaLink DTOs ───────────────────────────────────────────────────
 
 /// A row from the bulk media-links endpoint.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct MediaLinkRow {
+    pub link_id: uuid::Uuid,
     pub entity_id: uuid::Uuid,
     pub entity_type: String,
     pub media_id: uuid::Uuid,
     pub file_path: String,
     pub file_name: String,
+    pub mime_type: String,
+    /// The link designates the person's profile photo.
+    pub is_profile: bool,
+}
+
+impl MediaLinkRow {
+    fn is_image(&self) -> bool {
+        self.mime_type.starts_with("image/")
+    }
+}
+
+/// Portrait of each person among `rows` (person id → file path): their
+/// profile photo, else the first image linked to them.
+pub fn person_photos(rows: &[MediaLinkRow]) -> std::collections::HashMap<uuid::Uuid, String> {
+    let mut photos = std::collections::HashMap::new();
+    for row in rows
+        .iter()
+        .filter(|r| r.entity_type == "person" && r.is_image())
+    {
+        if row.is_profile {
+            photos.insert(row.entity_id, row.file_path.clone());
+        } else {
+            photos
+                .entry(row.entity_id)
+                .or_insert_with(|| row.file_path.clone());
+        }
+    }
+    photos
+}
+
+/// Request body for designating a person's profile photo.
+#[derive(Debug, Serialize)]
+pub struct SetPersonPhotoBody {
+    pub link_id: uuid::Uuid,
+}
+
+/// A note listed for a person with its families' (`include_family=true`):
+/// on the person, or inherited from one of their families.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PersonScopedNote {
+    #[serde(flatten)]
+    pub note: Note,
+    pub inherited: bool,
+    /// For inherited notes: `spouse` (the person's own union) or `child`
+    /// (their parents').
+    #[serde(default)]
+    pub family_role: Option<String>,
+}
+
+/// A media object linked to a person, from the person media endpoint.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PersonMediaEntry {
+    pub link_id: uuid::Uuid,
+    pub sort_order: i32,
+    pub is_profile: bool,
+    #[serde(flatten)]
+    pub media: Media,
+}
+
+impl PersonMediaEntry {
+    pub fn is_image(&self) -> bool {
+        self.media.mime_type.starts_with("image/")
+    }
+
+    /// The media title, else its file name.
+    pub fn caption(&self) -> &str {
+        self.media
+            .title
+            .as_deref()
+            .filter(|t| !t.is_empty())
+            .unwrap_or(&self.media.file_name)
+    }
+}
+
+/// One page of a person's media, in display order.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PersonMediaPage {
+    pub items: Vec<PersonMediaEntry>,
+    pub total: usize,
+}
+
+/// Request body for reordering a person's media.
+#[derive(Debug, Serialize)]
+pub struct ReorderPersonMediaBody {
+    pub link_ids: Vec<uuid::Uuid>,
 }
 
 // ── GEDCOM DTOs ─────────────────────────────────────────────────────
@@ -335,24 +741,147 @@ pub struct ImportGedcomBody {
     pub gedcom: String,
 }
 
+/// Result of `GET /api/v1/trees/{id}/check`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IntegrityReport {
+    pub error_count: usize,
+    pub warning_count: usize,
+    pub issues: Vec<IntegrityIssue>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IntegrityIssue {
+    pub kind: String,
+    pub severity: String,
+    pub entity_id: Uuid,
+    pub message: String,
+    pub suggested_fix: String,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ImportGedcomResult {
     pub persons_count: usize,
+    pub person_names_count: usize,
     pub families_count: usize,
     pub events_count: usize,
     pub sources_count: usize,
+    pub citations_count: usize,
     pub media_count: usize,
     pub places_count: usize,
     pub notes_count: usize,
     pub warnings: Vec<String>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+struct ImportJobStarted {
+    job_id: Uuid,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GeocodeJobStarted {
+    job_id: Uuid,
+}
+
+/// Progress of a running batch geocoding job, as streamed by the server.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct GeocodeProgress {
+    /// `queued` or `geocoding`.
+    pub phase: String,
+    pub places_total: usize,
+    pub places_done: usize,
+}
+
+/// Outcome of geocoding one place.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PlaceGeocodeResult {
+    pub place_id: Uuid,
+    pub name: String,
+    /// `success`, `ambiguous` or `failed`.
+    pub status: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub candidates: usize,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Final summary of a batch geocoding job.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GeocodeSummary {
+    pub succeeded: usize,
+    pub ambiguous: usize,
+    pub failed: usize,
+    pub places: Vec<PlaceGeocodeResult>,
+}
+
+/// Progress of a running GEDCOM import, as streamed by the server.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ImportProgress {
+    /// `queued`, `parsing`, `persisting` or `rebuilding_cache`.
+    /// Only the first three can still be cancelled.
+    pub phase: String,
+    /// Known once the file has been read.
+    pub records_parsed: usize,
+    pub records_total: usize,
+    pub records_persisted: usize,
+    /// Warnings raised so far.
+    pub warnings: Vec<String>,
+}
+
+/// Payload of a background job's `failed` event.
+#[derive(Debug, Clone, Deserialize)]
+struct JobFailure {
+    error: String,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ExportGedcomResult {
     pub gedcom: String,
     pub warnings: Vec<String>,
 }
 
+/// Result of `GET /api/v1/trees/{id}/export/estimate`: live record counts.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct ExportEstimate {
+    pub persons: u64,
+    pub families: u64,
+    pub sources: u64,
+    pub media: u64,
+    /// What an export job's progress counts against.
+    pub records_total: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ExportJobStarted {
+    job_id: Uuid,
+}
+
+/// Progress of a running export job, as streamed by the server.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ExportProgress {
+    /// `queued`, `loading`, `serializing` or `packaging`.
+    pub phase: String,
+    pub records_total: usize,
+    pub records_serialized: usize,
+}
+
+// ── Backups ─────────────────────────────────────────────────────────
+
+/// A backup of the desktop database.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Backup {
+    pub file_name: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub size_bytes: u64,
+}
+
+/// A restore staged for the next launch.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RestoreBackupResult {
+    pub restored: Backup,
+    pub restart_required: bool,
+}
+
 // ── Tree Snapshot ───────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Deserialize)]
@@ -435,6 +964,70 @@ pub enum ApiError {
 
     #[error("API error ({status}): {body}")]
     Api { status: u16, body: String },
+    #[error("{0}")]
+    Problem(Problem),
+    #[error("Import failed: {0}")]
+    ImportFailed(String),
+    #[error("Geocoding failed: {0}")]
+    GeocodeFailed(String),
+    #[error("Export failed: {0}")]
+    ExportFailed(String),
+    #[error("Cancelled")]
+    Cancelled,
+}
+
+impl ApiError {
+    /// Error for a failed response: the server's problem details when the
+    /// body carries them, the raw body otherwise.
+    fn from_response(status: reqwest::StatusCode, body: String) -> Self {
+        match serde_json::from_str::<Problem>(&body) {
+            Ok(problem) => Self::Problem(problem),
+            Err(_) => Self::Api {
+                status: status.as_u16(),
+                body,
+            },
+        }
+    }
+
+    /// HTTP status of a server-side failure, if this is one.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Api { status, .. } => Some(*status),
+            Self::Problem(problem) => Some(problem.status),
+            _ => None,
+        }
+    }
+
+    /// Whether the server rejected an update because the entity changed
+    /// since it was read (stale `If-Match`).
+    pub fn is_conflict(&self) -> bool {
+        self.status() == Some(409)
+    }
+}
+
+/// RFC 7807 problem details, the body of every REST error response.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Problem {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    #[serde(default)]
+    pub detail: Option<String>,
+    #[serde(default)]
+    pub request_id: Option<String>,
+}
+
+impl std::fmt::Display for Problem {
+    /// The detail when there is one (it says what was wrong), else the
+    /// title; with the request id to quote in bug reports.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.detail.as_deref().unwrap_or(&self.title))?;
+        if let Some(id) = &self.request_id {
+            write!(f, " (request {id})")?;
+        }
+        Ok(())
+    }
 }
 
 impl ApiClient {
@@ -478,10 +1071,7 @@ impl ApiClient {
         if !status.is_success() {
             let body = resp.text().await.unwrap_or_default();
             tracing::debug!("GET {url} -> {status} {body}");
-            return Err(ApiError::Api {
-                status: status.as_u16(),
-                body,
-            });
+            return Err(ApiError::from_response(status, body));
         }
         let bytes = resp.bytes().await?;
         tracing::debug!(
@@ -521,10 +1111,7 @@ impl ApiClient {
         if !status.is_success() {
             let body = resp.text().await.unwrap_or_default();
             tracing::debug!("GET {url} -> {status} {body}");
-            return Err(ApiError::Api {
-                status: status.as_u16(),
-                body,
-            });
+            return Err(ApiError::from_response(status, body));
         }
         let bytes = resp.bytes().await?;
         tracing::debug!(
@@ -539,7 +1126,7 @@ impl ApiClient {
 
     /// Helper: send a GET request with query parameters, returning the raw
     /// response body bytes (not cached, not JSON-decoded).
-    async fn get_bytes_with_query<Q: Serialize>(
+    async fn get_bytes_with_query<Q: Serialize + ?Sized>(
         &self,
         path: &str,
         query: &Q,
@@ -554,10 +1141,7 @@ impl ApiClient {
         if !status.is_success() {
             let body = resp.text().await.unwrap_or_default();
             tracing::debug!("GET {url} -> {status} {body}");
-            return Err(ApiError::Api {
-                status: status.as_u16(),
-                body,
-            });
+            return Err(ApiError::from_response(status, body));
         }
         let bytes = resp.bytes().await?;
         tracing::debug!("GET {url} -> {status} ({} bytes)", bytes.len());
@@ -590,6 +1174,37 @@ impl ApiClient {
         Self::handle_response(&url, "PUT", resp).await
     }
 
+    /// Helper: send a PUT request conditional on the entity still being at
+    /// `version` (`If-Match`); unconditional when `version` is `None`.
+    async fn put_if_match<T: serde::de::DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+        version: Option<i32>,
+    ) -> Result<T, ApiError> {
+        let url = self.url(path);
+        let body_json = serde_json::to_string(body).unwrap_or_default();
+        tracing::debug!("PUT {url} if-match={version:?} body={body_json}");
+        let mut req = self.client.put(&url).json(body);
+        if let Some(v) = version {
+            req = req.header(reqwest::header::IF_MATCH, format!("\"{v}\""));
+        }
+        let resp = req.send().await?;
+        Self::handle_response(&url, "PUT", resp).await
+    }
+
+    async fn patch<T: serde::de::DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T, ApiError> {
+        let url = self.url(path);
+        let body_json = serde_json::to_string(body).unwrap_or_default();
+        tracing::debug!("PATCH {url} body={body_json}");
+        let resp = self.client.patch(&url).json(body).send().await?;
+        Self::handle_response(&url, "PATCH", resp).await
+    }
+
     /// Helper: send a DELETE request expecting 204 No Content.
     async fn delete_no_content(&self, path: &str) -> Result<(), ApiError> {
         let url = self.url(path);
@@ -599,15 +1214,44 @@ impl ApiClient {
         if !status.is_success() {
             let body = resp.text().await.unwrap_or_default();
             tracing::debug!("DELETE {url} -> {status} {body}");
-            return Err(ApiError::Api {
-                status: status.as_u16(),
-                body,
-            });
+            return Err(ApiError::from_response(status, body));
         }
         tracing::debug!("DELETE {url} -> {status}");
         Ok(())
     }
 
+    /// Helper: send a PUT request with a JSON body, expecting 204 No Content.
+    async fn put_no_content<B: Serialize>(&self, path: &str, body: &B) -> Result<(), ApiError> {
+        let url = self.url(path);
+        let body_json = serde_json::to_string(body).unwrap_or_default();
+        tracing::debug!("PUT {url} body={body_json}");
+        let resp = self.client.put(&url).json(body).send().await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            tracing::debug!("PUT {url} -> {status} {body}");
+            return Err(ApiError::from_response(status, body));
+        }
+        tracing::debug!("PUT {url} -> {status}");
+        Ok(())
+    }
+
+    /// Helper: send a POST request without a body, expecting no content
+    /// back (any success status).
+    async fn post_no_content(&self, path: &str) -> Result<(), ApiError> {
+        let url = self.url(path);
+        tracing::debug!("POST {url}");
+        let resp = self.client.post(&url).send().await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            tracing::debug!("POST {url} -> {status} {body}");
+            return Err(ApiError::from_response(status, body));
+        }
+        tracing::debug!("POST {url} -> {status}");
+        Ok(())
+    }
+
     /// Handle HTTP response: check status, parse JSON.
     async fn handle_response<T: serde::de::DeserializeOwned>(
         url: &str,
@@ -618,10 +1262,7 @@ impl ApiClient {
         if !status.is_success() {
             let body = resp.text().await.unwrap_or_default();
             tracing::debug!("{method} {url} -> {status} {body}");
-            return Err(ApiError::Api {
-                status: status.as_u16(),
-                body,
-            });
+            return Err(ApiError::from_response(status, body));
         }
         let bytes = resp.bytes().await?;
         tracing::debug!(
@@ -638,7 +1279,7 @@ impl ApiClient {
         &self,
         first: Option<u64>,
         after: Option<&str>,
-    ) -> Result<PaginatedResponse<Tree>, ApiError> {
+    ) -> Result<PaginatedResponse<TreeSummary>, ApiError> {
         let mut params = Vec::new();
         if let Some(f) = first {
             params.push(("first", f.to_string()));
@@ -653,8 +1294,17 @@ impl ApiClient {
         self.get(&format!("/api/v1/trees/{id}")).await
     }
 
-    pub async fn create_tree(&self, body: &CreateTreeBody) -> Result<Tree, ApiError> {
-        let result = self.post("/api/v1/trees", body).await?;
+    /// Create a tree, seeded with the starter records of `template`.
+    pub async fn create_tree(
+        &self,
+        body: &CreateTreeBody,
+        template: TreeTemplate,
+    ) -> Result<Tree, ApiError> {
+        let path = match template {
+            TreeTemplate::Empty => "/api/v1/trees",
+            TreeTemplate::HomePerson => "/api/v1/trees?template=self",
+        };
+        let result = self.post(path, body).await?;
         self.cache.invalidate_prefix("/api/v1/trees");
         Ok(result)
     }
@@ -677,6 +1327,21 @@ impl ApiClient {
         Ok(result)
     }
 
+    /// Lint a tree for dangling references and stale derived data.
+    pub async fn check_tree(&self, id: Uuid) -> Result<IntegrityReport, ApiError> {
+        self.get(&format!("/api/v1/trees/{id}/check")).await
+    }
+
+    /// The `limit` most recently created or updated entities of a tree.
+    pub async fn tree_activity(
+        &self,
+        tree_id: Uuid,
+        limit: u64,
+    ) -> Result<Vec<ActivityEntry>, ApiError> {
+        self.get(&format!("/api/v1/trees/{tree_id}/activity?limit={limit}"))
+            .await
+    }
+
     pub async fn delete_tree(&self, id: Uuid) -> Result<(), ApiError> {
         self.delete_no_content(&format!("/api/v1/trees/{id}"))
             .await?;
@@ -684,6 +1349,44 @@ impl ApiClient {
         Ok(())
     }
 
+    // ── Share Links ──────────────────────────────────────────────────
+
+    pub async fn list_share_links(&self, tree_id: Uuid) -> Result<Vec<ShareLink>, ApiError> {
+        self.get(&format!("/api/v1/trees/{tree_id}/share")).await
+    }
+
+    pub async fn create_share_link(
+        &self,
+        tree_id: Uuid,
+        body: &CreateShareLinkBody,
+    ) -> Result<ShareLink, ApiError> {
+        let result = self
+            .post(&format!("/api/v1/trees/{tree_id}/share"), body)
+            .await?;
+        self.cache
+            .invalidate_prefix(&format!("/api/v1/trees/{tree_id}/share"));
+        Ok(result)
+    }
+
+    pub async fn revoke_share_link(&self, tree_id: Uuid, share_id: Uuid) -> Result<(), ApiError> {
+        self.delete_no_content(&format!("/api/v1/trees/{tree_id}/share/{share_id}"))
+            .await?;
+        self.cache
+            .invalidate_prefix(&format!("/api/v1/trees/{tree_id}/share"));
+        self.cache.invalidate_prefix("/api/v1/shared");
+        Ok(())
+    }
+
+    /// Resolve a share token to the shared tree's metadata.
+    pub async fn get_shared_tree(&self, token: &str) -> Result<SharedTree, ApiError> {
+        self.get(&format!("/api/v1/shared/{token}")).await
+    }
+
+    /// Read-only (and possibly redacted) snapshot behind a share token.
+    pub async fn get_shared_snapshot(&self, token: &str) -> Result<TreeSnapshot, ApiError> {
+        self.get(&format!("/api/v1/shared/{token}/snapshot")).await
+    }
+
     // ── Tree Snapshot ────────────────────────────────────────────────
 
     pub async fn get_tree_snapshot(&self, tree_id: Uuid) -> Result<TreeSnapshot, ApiError> {
@@ -713,6 +1416,23 @@ impl ApiClient {
             .await
     }
 
+    /// Typo-tolerant name search, closest matches first.
+    pub async fn fuzzy_search_persons(
+        &self,
+        tree_id: Uuid,
+        name: &str,
+        max_distance: u32,
+        limit: u32,
+    ) -> Result<FuzzySearchResult, ApiError> {
+        let params = [
+            ("name", name.to_string()),
+            ("max_distance", max_distance.to_string()),
+            ("limit", limit.to_string()),
+        ];
+        self.get_with_query(&format!("/api/v1/trees/{tree_id}/persons/search"), &params)
+            .await
+    }
+
     pub async fn list_persons(
         &self,
         tree_id: Uuid,
@@ -764,6 +1484,30 @@ impl ApiClient {
             .await
     }
 
+    /// Field-by-field comparison of two persons, before merging them.
+    pub async fn compare_persons(
+        &self,
+        tree_id: Uuid,
+        a: Uuid,
+        b: Uuid,
+    ) -> Result<PersonCompareResult, ApiError> {
+        let params = [("a", a.to_string()), ("b", b.to_string())];
+        self.get_with_query(&format!("/api/v1/trees/{tree_id}/persons/compare"), &params)
+            .await
+    }
+
+    /// The chain of persons linking `a` to `b` through their nearest
+    /// common ancestor.
+    pub async fn get_relationship_path(
+        &self,
+        tree_id: Uuid,
+        a: Uuid,
+        b: Uuid,
+    ) -> Result<RelationshipPath, ApiError> {
+        self.get(&format!("/api/v1/trees/{tree_id}/persons/{a}/path/{b}"))
+            .await
+    }
+
     pub async fn create_person(
         &self,
         tree_id: Uuid,
@@ -776,14 +1520,50 @@ impl ApiClient {
         Ok(result)
     }
 
+    /// Create a person with their name, birth and death in one request.
+    pub async fn quick_create_person(
+        &self,
+        tree_id: Uuid,
+        body: &QuickPersonBody,
+    ) -> Result<QuickPerson, ApiError> {
+        let result = self
+            .post(&format!("/api/v1/trees/{tree_id}/persons/quick"), body)
+            .await?;
+        self.invalidate_tree(tree_id);
+        Ok(result)
+    }
+
+    /// Create a relative of a person and link them, family included, in
+    /// one request.
+    pub async fn add_relative(
+        &self,
+        tree_id: Uuid,
+        person_id: Uuid,
+        body: &AddRelativeBody,
+    ) -> Result<AddedRelative, ApiError> {
+        let result = self
+            .post(
+                &format!("/api/v1/trees/{tree_id}/persons/{person_id}/relatives"),
+                body,
+            )
+            .await?;
+        self.invalidate_tree(tree_id);
+        Ok(result)
+    }
+
     pub async fn update_person(
         &self,
         tree_id: Uuid,
         id: Uuid,
         body: &UpdatePersonBody,
+        version: Option<i32>,
     ) -> Result<Person, ApiError> {
         let result = self
-            .put(&format!("/api/v1/trees/{tree_id}/persons/{id}"), body)
+            .put_if_match(
+                &format!("/api/v1/trees/{tree_id}/persons/{id}"),
+                body,
+                version,
+            )
             .await?;
         self.invalidate_tree(tree_id);
         Ok(result)
@@ -793,7 +1573,43 @@ impl ApiClient {
         self.delete_no_content(&format!("/api/v1/trees/{tree_id}/persons/{id}"))
             .await?;
         self.invalidate_tree(tree_id);
-        Ok(())
+        Ok(())
+    }
+
+    /// Lock (`locked`) or unlock a person; a locked person and their names
+    /// refuse edits and deletion.
+    pub async fn set_person_locked(
+        &self,
+        tree_id: Uuid,
+        id: Uuid,
+        locked: bool,
+    ) -> Result<Person, ApiError> {
+        let action = if locked { "lock" } else { "unlock" };
+        let result = self
+            .post(
+                &format!("/api/v1/trees/{tree_id}/persons/{id}/{action}"),
+                &serde_json::json!({}),
+            )
+            .await?;
+        self.invalidate_tree(tree_id);
+        Ok(result)
+    }
+
+    /// Delete several persons at once; family members are skipped unless
+    /// `cascade` is set.
+    pub async fn bulk_delete_persons(
+        &self,
+        tree_id: Uuid,
+        body: &BulkDeletePersonsBody,
+    ) -> Result<BulkDeleteResult, ApiError> {
+        let result = self
+            .post(
+                &format!("/api/v1/trees/{tree_id}/persons/bulk-delete"),
+                body,
+            )
+            .await?;
+        self.invalidate_tree(tree_id);
+        Ok(result)
     }
 
     pub async fn get_ancestors(
@@ -962,6 +1778,30 @@ impl ApiClient {
         .await
     }
 
+    /// Notes attached to the family itself.
+    pub async fn list_family_notes(
+        &self,
+        tree_id: Uuid,
+        family_id: Uuid,
+    ) -> Result<Vec<Note>, ApiError> {
+        self.get(&format!(
+            "/api/v1/trees/{tree_id}/families/{family_id}/notes"
+        ))
+        .await
+    }
+
+    /// Citations attached to the family itself (not to its events).
+    pub async fn list_family_citations(
+        &self,
+        tree_id: Uuid,
+        family_id: Uuid,
+    ) -> Result<Vec<Citation>, ApiError> {
+        self.get(&format!(
+            "/api/v1/trees/{tree_id}/families/{family_id}/citations"
+        ))
+        .await
+    }
+
     pub async fn add_spouse(
         &self,
         tree_id: Uuid,
@@ -1035,6 +1875,40 @@ impl ApiClient {
         Ok(())
     }
 
+    /// Order a family's children by birth date, undated ones last.
+    pub async fn sort_children_by_birth(
+        &self,
+        tree_id: Uuid,
+        family_id: Uuid,
+    ) -> Result<Vec<FamilyChild>, ApiError> {
+        let result = self
+            .post(
+                &format!("/api/v1/trees/{tree_id}/families/{family_id}/children/sort-by-birth"),
+                &serde_json::json!({}),
+            )
+            .await?;
+        self.invalidate_tree(tree_id);
+        Ok(result)
+    }
+
+    /// Reorder a family's children. `child_ids` lists every child link ID
+    /// of the family in the new order.
+    pub async fn reorder_children(
+        &self,
+        tree_id: Uuid,
+        family_id: Uuid,
+        child_ids: &[Uuid],
+    ) -> Result<Vec<FamilyChild>, ApiError> {
+        let result = self
+            .patch(
+                &format!("/api/v1/trees/{tree_id}/families/{family_id}/children/reorder"),
+                &serde_json::json!({ "child_ids": child_ids }),
+            )
+            .await?;
+        self.invalidate_tree(tree_id);
+        Ok(result)
+    }
+
     // ── Events ──────────────────────────────────────────────────────
 
     pub async fn list_events(
@@ -1098,7 +1972,7 @@ impl ApiClient {
         &self,
         tree_id: Uuid,
         body: &CreateEventBody,
-    ) -> Result<Event, ApiError> {
+    ) -> Result<SavedEvent, ApiError> {
         let result = self
             .post(&format!("/api/v1/trees/{tree_id}/events"), body)
             .await?;
@@ -1111,9 +1985,14 @@ impl ApiClient {
         tree_id: Uuid,
         id: Uuid,
         body: &UpdateEventBody,
-    ) -> Result<Event, ApiError> {
+        version: Option<i32>,
+    ) -> Result<SavedEvent, ApiError> {
         let result = self
-            .put(&format!("/api/v1/trees/{tree_id}/events/{id}"), body)
+            .put_if_match(
+                &format!("/api/v1/trees/{tree_id}/events/{id}"),
+                body,
+                version,
+            )
             .await?;
         self.invalidate_tree(tree_id);
         Ok(result)
@@ -1126,28 +2005,28 @@ impl ApiClient {
         Ok(())
     }
 
-    // ── Event Witnesses ────────────────────────────────────────────────
+    // ── Event Participants ─────────────────────────────────────────────
 
-    pub async fn list_event_witnesses(
+    pub async fn list_event_participants(
         &self,
         tree_id: Uuid,
         event_id: Uuid,
-    ) -> Result<Vec<EventWitness>, ApiError> {
+    ) -> Result<Vec<EventParticipant>, ApiError> {
         self.get(&format!(
-            "/api/v1/trees/{tree_id}/events/{event_id}/witnesses"
+            "/api/v1/trees/{tree_id}/events/{event_id}/participants"
         ))
         .await
     }
 
-    pub async fn add_event_witness(
+    pub async fn add_event_participant(
         &self,
         tree_id: Uuid,
         event_id: Uuid,
-        body: &AddEventWitnessBody,
-    ) -> Result<EventWitness, ApiError> {
+        body: &AddEventParticipantBody,
+    ) -> Result<EventParticipant, ApiError> {
         let result = self
             .post(
-                &format!("/api/v1/trees/{tree_id}/events/{event_id}/witnesses"),
+                &format!("/api/v1/trees/{tree_id}/events/{event_id}/participants"),
                 body,
             )
             .await?;
@@ -1155,14 +2034,14 @@ impl ApiClient {
         Ok(result)
     }
 
-    pub async fn remove_event_witness(
+    pub async fn remove_event_participant(
         &self,
         tree_id: Uuid,
         event_id: Uuid,
-        witness_id: Uuid,
+        participant_id: Uuid,
     ) -> Result<(), ApiError> {
         self.delete_no_content(&format!(
-            "/api/v1/trees/{tree_id}/events/{event_id}/witnesses/{witness_id}"
+            "/api/v1/trees/{tree_id}/events/{event_id}/participants/{participant_id}"
         ))
         .await?;
         self.invalidate_tree(tree_id);
@@ -1192,6 +2071,21 @@ impl ApiClient {
             .await
     }
 
+    /// Places whose name starts with `q`, most used first.
+    pub async fn autocomplete_places(
+        &self,
+        tree_id: Uuid,
+        q: &str,
+        limit: u64,
+    ) -> Result<Vec<PlaceSuggestion>, ApiError> {
+        let params = [("q", q.to_string()), ("limit", limit.to_string())];
+        self.get_with_query(
+            &format!("/api/v1/trees/{tree_id}/places/autocomplete"),
+            &params,
+        )
+        .await
+    }
+
     /// Fetch all places by paginating through all pages.
     pub async fn list_all_places(&self, tree_id: Uuid) -> Result<Vec<Place>, ApiError> {
         let mut all = Vec::new();
@@ -1246,6 +2140,45 @@ impl ApiClient {
         Ok(())
     }
 
+    /// Geocode every place lacking coordinates as a background job, calling
+    /// `on_progress` for each update until it ends.
+    pub async fn geocode_all_places(
+        &self,
+        tree_id: Uuid,
+        on_progress: impl FnMut(GeocodeProgress),
+    ) -> Result<GeocodeSummary, ApiError> {
+        let job: GeocodeJobStarted = self
+            .post(
+                &format!("/api/v1/trees/{tree_id}/places/geocode-all"),
+                &serde_json::json!({}),
+            )
+            .await?;
+        let outcome = self
+            .follow_job(
+                &format!(
+                    "/api/v1/trees/{tree_id}/places/geocode-all/{}/events",
+                    job.job_id
+                ),
+                on_progress,
+                ApiError::GeocodeFailed,
+            )
+            .await;
+        self.invalidate_tree(tree_id);
+        outcome
+    }
+
+    /// Rename a place after the address found at its coordinates.
+    pub async fn reverse_geocode_place(&self, tree_id: Uuid, id: Uuid) -> Result<Place, ApiError> {
+        let result = self
+            .post(
+                &format!("/api/v1/trees/{tree_id}/places/{id}/reverse-geocode"),
+                &serde_json::json!({}),
+            )
+            .await?;
+        self.invalidate_tree(tree_id);
+        Ok(result)
+    }
+
     // ── Sources ─────────────────────────────────────────────────────
 
     pub async fn list_sources(
@@ -1287,6 +2220,16 @@ impl ApiClient {
             .await
     }
 
+    /// Every citation of a source, with what it is attached to.
+    pub async fn source_usage(
+        &self,
+        tree_id: Uuid,
+        id: Uuid,
+    ) -> Result<Vec<SourceUsageEntry>, ApiError> {
+        self.get(&format!("/api/v1/trees/{tree_id}/sources/{id}/usage"))
+            .await
+    }
+
     pub async fn create_source(
         &self,
         tree_id: Uuid,
@@ -1319,6 +2262,68 @@ impl ApiClient {
         Ok(())
     }
 
+    // ── Tags ─────────────────────────────────────────────────────────
+
+    /// Tags in use in the tree with their person counts.
+    pub async fn list_tags(&self, tree_id: Uuid) -> Result<Vec<TagCount>, ApiError> {
+        self.get(&format!("/api/v1/trees/{tree_id}/tags")).await
+    }
+
+    /// Every tag of every person in the tree.
+    pub async fn list_tree_person_tags(&self, tree_id: Uuid) -> Result<Vec<PersonTag>, ApiError> {
+        self.get(&format!("/api/v1/trees/{tree_id}/person-tags"))
+            .await
+    }
+
+    pub async fn list_person_tags(
+        &self,
+        tree_id: Uuid,
+        person_id: Uuid,
+    ) -> Result<Vec<PersonTag>, ApiError> {
+        self.get(&format!("/api/v1/trees/{tree_id}/persons/{person_id}/tags"))
+            .await
+    }
+
+    pub async fn add_person_tag(
+        &self,
+        tree_id: Uuid,
+        person_id: Uuid,
+        tag: &str,
+    ) -> Result<PersonTag, ApiError> {
+        let result = self
+            .post(
+                &format!("/api/v1/trees/{tree_id}/persons/{person_id}/tags"),
+                &serde_json::json!({ "tag": tag }),
+            )
+            .await?;
+        self.invalidate_tags(tree_id, person_id);
+        Ok(result)
+    }
+
+    pub async fn remove_person_tag(
+        &self,
+        tree_id: Uuid,
+        person_id: Uuid,
+        tag: &str,
+    ) -> Result<(), ApiError> {
+        self.delete_no_content(&format!(
+            "/api/v1/trees/{tree_id}/persons/{person_id}/tags/{}",
+            encode_path_segment(tag)
+        ))
+        .await?;
+        self.invalidate_tags(tree_id, person_id);
+        Ok(())
+    }
+
+    fn invalidate_tags(&self, tree_id: Uuid, person_id: Uuid) {
+        self.cache
+            .invalidate_prefix(&format!("/api/v1/trees/{tree_id}/tags"));
+        self.cache
+            .invalidate_prefix(&format!("/api/v1/trees/{tree_id}/person-tags"));
+        self.cache
+            .invalidate_prefix(&format!("/api/v1/trees/{tree_id}/persons/{person_id}/tags"));
+    }
+
     // ── Dictionary ───────────────────────────────────────────────────
 
     /// Distinct surnames in the tree, with the number of persons carrying each.
@@ -1330,6 +2335,27 @@ impl ApiClient {
             .await
     }
 
+    /// Surnames of primary names with their person count, for A–Z browsing.
+    pub async fn surname_index(&self, tree_id: Uuid) -> Result<Vec<SurnameIndexEntry>, ApiError> {
+        self.get(&format!("/api/v1/trees/{tree_id}/surname-index"))
+            .await
+    }
+
+    /// The `limit` most frequent surnames counted per birth decade or birth
+    /// place (`over` is `decade` or `place`).
+    pub async fn surname_stats(
+        &self,
+        tree_id: Uuid,
+        over: &str,
+        limit: usize,
+    ) -> Result<SurnameStats, ApiError> {
+        self.get_with_query(
+            &format!("/api/v1/trees/{tree_id}/stats/surnames"),
+            &[("over", over.to_string()), ("limit", limit.to_string())],
+        )
+        .await
+    }
+
     /// Persons carrying a given family name.
     pub async fn dictionary_family_name_usage(
         &self,
@@ -1521,6 +2547,20 @@ impl ApiClient {
             .await
     }
 
+    /// A person's notes followed by those of the families they belong to.
+    pub async fn list_person_notes(
+        &self,
+        tree_id: Uuid,
+        person_id: Uuid,
+    ) -> Result<Vec<PersonScopedNote>, ApiError> {
+        let params = [
+            ("person_id", person_id.to_string()),
+            ("include_family", "true".to_string()),
+        ];
+        self.get_with_query(&format!("/api/v1/trees/{tree_id}/notes"), &params)
+            .await
+    }
+
     pub async fn create_note(
         &self,
         tree_id: Uuid,
@@ -1553,6 +2593,59 @@ impl ApiClient {
         Ok(())
     }
 
+    // ── Research tasks ──────────────────────────────────────────────
+
+    /// The tree's research to-do list, optionally narrowed to a status or
+    /// to one person's tasks.
+    pub async fn list_tasks(
+        &self,
+        tree_id: Uuid,
+        status: Option<TaskStatus>,
+        person_id: Option<Uuid>,
+    ) -> Result<Vec<ResearchTask>, ApiError> {
+        let mut params: Vec<(&str, String)> = Vec::new();
+        if let Some(status) = status {
+            params.push(("status", status.to_string()));
+        }
+        if let Some(pid) = person_id {
+            params.push(("person_id", pid.to_string()));
+        }
+        self.get_with_query(&format!("/api/v1/trees/{tree_id}/tasks"), &params)
+            .await
+    }
+
+    pub async fn create_task(
+        &self,
+        tree_id: Uuid,
+        body: &CreateTaskBody,
+    ) -> Result<ResearchTask, ApiError> {
+        let result = self
+            .post(&format!("/api/v1/trees/{tree_id}/tasks"), body)
+            .await?;
+        self.invalidate_tree(tree_id);
+        Ok(result)
+    }
+
+    pub async fn update_task(
+        &self,
+        tree_id: Uuid,
+        task_id: Uuid,
+        body: &UpdateTaskBody,
+    ) -> Result<ResearchTask, ApiError> {
+        let result = self
+            .put(&format!("/api/v1/trees/{tree_id}/tasks/{task_id}"), body)
+            .await?;
+        self.invalidate_tree(tree_id);
+        Ok(result)
+    }
+
+    pub async fn delete_task(&self, tree_id: Uuid, task_id: Uuid) -> Result<(), ApiError> {
+        self.delete_no_content(&format!("/api/v1/trees/{tree_id}/tasks/{task_id}"))
+            .await?;
+        self.invalidate_tree(tree_id);
+        Ok(())
+    }
+
     // ── MediaLinks ──────────────────────────────────────────────────
 
     /// Fetch all media links for persons in a tree (for photo display).
@@ -1564,14 +2657,77 @@ impl ApiClient {
             .await
     }
 
+    /// Designate one of a person's media links as their profile photo.
+    pub async fn set_person_photo(
+        &self,
+        tree_id: Uuid,
+        person_id: Uuid,
+        link_id: Uuid,
+    ) -> Result<MediaLink, ApiError> {
+        let link = self
+            .put(
+                &format!("/api/v1/trees/{tree_id}/persons/{person_id}/photo"),
+                &SetPersonPhotoBody { link_id },
+            )
+            .await?;
+        self.invalidate_tree(tree_id);
+        Ok(link)
+    }
+
+    /// Clear a person's profile photo; the media stays linked.
+    pub async fn clear_person_photo(&self, tree_id: Uuid, person_id: Uuid) -> Result<(), ApiError> {
+        self.delete_no_content(&format!(
+            "/api/v1/trees/{tree_id}/persons/{person_id}/photo"
+        ))
+        .await?;
+        self.invalidate_tree(tree_id);
+        Ok(())
+    }
+
+    /// One page of the media linked to a person, in display order.
+    pub async fn list_person_media(
+        &self,
+        tree_id: Uuid,
+        person_id: Uuid,
+        offset: usize,
+        limit: usize,
+    ) -> Result<PersonMediaPage, ApiError> {
+        self.get(&format!(
+            "/api/v1/trees/{tree_id}/persons/{person_id}/media?offset={offset}&limit={limit}"
+        ))
+        .await
+    }
+
+    /// Reorder a person's media; `link_ids` lists every link of the person.
+    pub async fn reorder_person_media(
+        &self,
+        tree_id: Uuid,
+        person_id: Uuid,
+        link_ids: Vec<Uuid>,
+    ) -> Result<(), ApiError> {
+        self.put_no_content(
+            &format!("/api/v1/trees/{tree_id}/persons/{person_id}/media/order"),
+            &ReorderPersonMediaBody { link_ids },
+        )
+        .await?;
+        self.invalidate_tree(tree_id);
+        Ok(())
+    }
+
     // ── GEDCOM ──────────────────────────────────────────────────────
 
+    /// Start a GEDCOM import and follow its progress over Server-Sent
+    /// Events, calling `on_progress` for each update until the job ends.
+    /// `on_started` receives the job id, for [`Self::cancel_import`]; a
+    /// cancelled import ends with [`ApiError::Cancelled`].
     pub async fn import_gedcom(
         &self,
         tree_id: Uuid,
         gedcom: &str,
+        on_started: impl FnOnce(Uuid),
+        on_progress: impl FnMut(ImportProgress),
     ) -> Result<ImportGedcomResult, ApiError> {
-        let result = self
+        let job: ImportJobStarted = self
             .post(
                 &format!("/api/v1/trees/{tree_id}/gedcom/import"),
                 &ImportGedcomBody {
@@ -1579,8 +2735,92 @@ impl ApiClient {
                 },
             )
             .await?;
+        on_started(job.job_id);
+
+        let outcome = self
+            .follow_job(
+                &format!("/api/v1/trees/{tree_id}/import/{}/events", job.job_id),
+                on_progress,
+                ApiError::ImportFailed,
+            )
+            .await;
         self.invalidate_tree(tree_id);
-        Ok(result)
+        outcome
+    }
+
+    /// Ask a running GEDCOM import to stop and roll back.
+    pub async fn cancel_import(&self, tree_id: Uuid, job_id: Uuid) -> Result<(), ApiError> {
+        self.post_no_content(&format!("/api/v1/trees/{tree_id}/import/{job_id}/cancel"))
+            .await
+    }
+
+    /// Follow a background job's Server-Sent Events stream: `progress`
+    /// events go to `on_progress`, then the `done` payload is returned, the
+    /// `failed` error wrapped by `failed`, or [`ApiError::Cancelled`].
+    async fn follow_job<P, D>(
+        &self,
+        path: &str,
+        mut on_progress: impl FnMut(P),
+        failed: fn(String) -> ApiError,
+    ) -> Result<D, ApiError>
+    where
+        P: serde::de::DeserializeOwned,
+        D: serde::de::DeserializeOwned,
+    {
+        let url = self.url(path);
+        tracing::debug!("GET {url} (event stream)");
+        // Large jobs can outlast the client-wide timeout; the server keeps
+        // the stream alive while the job runs.
+        let mut resp = self
+            .client
+            .get(&url)
+            .timeout(std::time::Duration::from_secs(3600))
+            .send()
+            .await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            return Err(ApiError::from_response(status, body));
+        }
+
+        // Raw bytes, so a UTF-8 sequence split across chunks stays intact.
+        let mut buffer: Vec<u8> = Vec::new();
+        loop {
+            // Handle every complete event (blank-line terminated) buffered so far.
+            if let Some(end) = buffer.windows(2).position(|w| w == b"\n\n") {
+                let raw: Vec<u8> = buffer.drain(..end + 2).collect();
+                let block = String::from_utf8_lossy(&raw);
+                let mut event = "message";
+                let mut data = String::new();
+                for line in block.lines() {
+                    if let Some(v) = line.strip_prefix("event:") {
+                        event = v.trim();
+                    } else if let Some(v) = line.strip_prefix("data:") {
+                        data.push_str(v.trim_start());
+                    }
+                }
+                match event {
+                    "progress" => on_progress(serde_json::from_str(&data)?),
+                    "done" => break Ok(serde_json::from_str(&data)?),
+                    "failed" => {
+                        let failure: JobFailure = serde_json::from_str(&data)?;
+                        break Err(failed(failure.error));
+                    }
+                    "cancelled" => break Err(ApiError::Cancelled),
+                    _ => {}
+                }
+                continue;
+            }
+
+            match resp.chunk().await? {
+                Some(bytes) => buffer.extend_from_slice(&bytes),
+                None => {
+                    break Err(failed(
+                        "event stream ended before the job finished".to_string(),
+                    ));
+                }
+            }
+        }
     }
 
     /// `merge_occupations` collapses each person's multiple `OCCU` tags back
@@ -1612,6 +2852,68 @@ impl ApiClient {
             .await
     }
 
+    /// Approximate size of a tree's export, cheap enough to show before
+    /// starting one.
+    pub async fn export_estimate(&self, tree_id: Uuid) -> Result<ExportEstimate, ApiError> {
+        self.get(&format!("/api/v1/trees/{tree_id}/export/estimate"))
+            .await
+    }
+
+    /// Export a tree as a background job, calling `on_progress` for each
+    /// update, then download the file (GEDZIP with `gedzip`, else GEDCOM).
+    /// See `export_gedcom` for `merge_occupations`.
+    pub async fn export_tree(
+        &self,
+        tree_id: Uuid,
+        gedzip: bool,
+        merge_occupations: bool,
+        on_progress: impl FnMut(ExportProgress),
+    ) -> Result<Vec<u8>, ApiError> {
+        let format = if gedzip { "gedzip" } else { "gedcom" };
+        let job: ExportJobStarted = self
+            .post(
+                &format!(
+                    "/api/v1/trees/{tree_id}/export/jobs?format={format}&merge_occupations={merge_occupations}"
+                ),
+                &serde_json::json!({}),
+            )
+            .await?;
+        // The `done` payload only describes the file; it is downloaded next.
+        let _: serde::de::IgnoredAny = self
+            .follow_job(
+                &format!("/api/v1/trees/{tree_id}/export/{}/events", job.job_id),
+                on_progress,
+                ApiError::ExportFailed,
+            )
+            .await?;
+        self.get_bytes_with_query(
+            &format!("/api/v1/trees/{tree_id}/export/{}/download", job.job_id),
+            &[] as &[(&str, &str)],
+        )
+        .await
+    }
+
+    // ── Backups ─────────────────────────────────────────────────────
+
+    /// List the database backups, newest first (desktop only).
+    pub async fn list_backups(&self) -> Result<Vec<Backup>, ApiError> {
+        self.get("/api/v1/backup").await
+    }
+
+    /// Take a database backup now.
+    pub async fn create_backup(&self) -> Result<Backup, ApiError> {
+        self.post("/api/v1/backup", &serde_json::json!({})).await
+    }
+
+    /// Stage a backup to replace the database on the next launch.
+    pub async fn restore_backup(&self, file_name: &str) -> Result<RestoreBackupResult, ApiError> {
+        self.post(
+            &format!("/api/v1/backup/{}/restore", encode_path_segment(file_name)),
+            &serde_json::json!({}),
+        )
+        .await
+    }
+
     // ── Pedigree Cache ──────────────────────────────────────────────
 
     /// Helper: send a PATCH request with query parameters (no body).
@@ -1672,3 +2974,17 @@ impl ApiClient {
         .await
     }
 }
+
+/// Percent-encode a free-text value for use as one URL path segment.
+fn encode_path_segment(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
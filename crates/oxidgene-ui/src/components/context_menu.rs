@@ -1,7 +1,8 @@
 //! Floating context menu for person nodes in pedigree charts.
 //!
-//! Shows actions like Edit, Merge, Edit Union, Add Spouse, Add Child,
-//! Add Sibling, Delete when the user interacts with a person box.
+//! Shows actions like Edit, Merge, Relationship, Edit Union, Add Father, Add Mother,
+//! Add Spouse, Add Child, Add Sibling, Delete when the user interacts with
+//! a person box.
 
 use dioxus::prelude::*;
 use uuid::Uuid;
@@ -13,7 +14,9 @@ use crate::i18n::use_i18n;
 pub enum PersonAction {
     Edit,
     Merge,
-    AddParents,
+    Relationship,
+    AddFather,
+    AddMother,
     AddSpouse,
     AddChild,
     AddSibling,
@@ -97,6 +100,11 @@ pub fn ContextMenu(props: ContextMenuProps) -> Element {
                     onclick: move |_| props.on_action.call(PersonAction::Merge),
                     {i18n.t("context.merge")}
                 }
+                button {
+                    class: "context-menu-item",
+                    onclick: move |_| props.on_action.call(PersonAction::Relationship),
+                    {i18n.t("context.relationship")}
+                }
                 if props.has_union {
                     if union_count > 1 {
                         button {
@@ -112,6 +120,16 @@ pub fn ContextMenu(props: ContextMenuProps) -> Element {
                         }
                     }
                 }
+                button {
+                    class: "context-menu-item",
+                    onclick: move |_| props.on_action.call(PersonAction::AddFather),
+                    {i18n.t("context.add_father")}
+                }
+                button {
+                    class: "context-menu-item",
+                    onclick: move |_| props.on_action.call(PersonAction::AddMother),
+                    {i18n.t("context.add_mother")}
+                }
                 button {
                     class: "context-menu-item",
                     onclick: move |_| props.on_action.call(PersonAction::AddSpouse),
@@ -386,6 +386,184 @@ pub const LAYOUT_STYLES: &str = r#"
         border: 1px solid var(--border);
     }
 
+    .pd-avatar-wrap {
+        position: relative;
+        flex: none;
+    }
+
+    .pd-avatar-btn {
+        display: block;
+        padding: 0;
+        border: none;
+        border-radius: 50%;
+        background: none;
+        cursor: pointer;
+    }
+
+    .pd-avatar-btn:hover .pd-avatar {
+        border-color: var(--orange);
+    }
+
+    .pd-photo-picker {
+        position: absolute;
+        top: 84px;
+        left: 0;
+        z-index: 20;
+        width: 260px;
+        padding: 12px;
+        background: var(--bg-card);
+        border: 1px solid var(--border);
+        border-radius: var(--radius);
+        box-shadow: var(--shadow-md);
+        display: flex;
+        flex-direction: column;
+        gap: 10px;
+    }
+
+    .pd-photo-picker-title {
+        font-size: 0.85rem;
+        font-weight: 600;
+        color: var(--text-primary);
+    }
+
+    .pd-photo-picker-grid {
+        display: grid;
+        grid-template-columns: repeat(4, 1fr);
+        gap: 6px;
+    }
+
+    .pd-photo-option {
+        padding: 0;
+        aspect-ratio: 1;
+        border: 2px solid transparent;
+        border-radius: 6px;
+        background: none;
+        cursor: pointer;
+        overflow: hidden;
+    }
+
+    .pd-photo-option img {
+        width: 100%;
+        height: 100%;
+        object-fit: cover;
+    }
+
+    .pd-photo-option.active {
+        border-color: var(--orange);
+    }
+
+    .media-gallery {
+        display: grid;
+        grid-template-columns: repeat(auto-fill, minmax(120px, 1fr));
+        gap: 12px;
+    }
+
+    .media-gallery-item {
+        display: flex;
+        flex-direction: column;
+        gap: 4px;
+        min-width: 0;
+    }
+
+    .media-gallery-thumb {
+        padding: 0;
+        aspect-ratio: 1;
+        border: 1px solid var(--border);
+        border-radius: 6px;
+        background: var(--bg-panel);
+        cursor: pointer;
+        overflow: hidden;
+    }
+
+    .media-gallery-thumb:hover {
+        border-color: var(--orange);
+    }
+
+    .media-gallery-thumb img {
+        width: 100%;
+        height: 100%;
+        object-fit: cover;
+    }
+
+    .media-gallery-file {
+        display: flex;
+        align-items: center;
+        justify-content: center;
+        height: 100%;
+        font-size: 0.85rem;
+        font-weight: 600;
+        color: var(--text-secondary);
+    }
+
+    .media-gallery-caption {
+        font-size: 0.8rem;
+        color: var(--text-primary);
+        overflow: hidden;
+        text-overflow: ellipsis;
+        white-space: nowrap;
+    }
+
+    .media-gallery-profile {
+        color: var(--orange);
+    }
+
+    .media-gallery-order {
+        display: flex;
+        gap: 4px;
+    }
+
+    .media-lightbox {
+        position: fixed;
+        inset: 0;
+        background: rgba(0, 0, 0, 0.85);
+        display: flex;
+        align-items: center;
+        justify-content: center;
+        z-index: 200;
+        outline: none;
+    }
+
+    .media-lightbox-content {
+        position: relative;
+        display: flex;
+        flex-direction: column;
+        align-items: center;
+        gap: 10px;
+        max-width: 90vw;
+        max-height: 90vh;
+        padding: 16px;
+        background: var(--bg-panel);
+        border-radius: var(--radius);
+    }
+
+    .media-lightbox-content img {
+        max-width: 100%;
+        max-height: 70vh;
+        object-fit: contain;
+    }
+
+    .media-lightbox-close {
+        position: absolute;
+        top: 4px;
+        right: 8px;
+        border: none;
+        background: none;
+        font-size: 1.4rem;
+        color: var(--text-secondary);
+        cursor: pointer;
+    }
+
+    .media-lightbox-caption {
+        text-align: center;
+        font-size: 0.9rem;
+    }
+
+    .media-lightbox-nav {
+        display: flex;
+        align-items: center;
+        gap: 12px;
+    }
+
     .pd-header-left {
         display: flex;
         gap: 18px;
@@ -406,6 +584,13 @@ pub const LAYOUT_STYLES: &str = r#"
         gap: 12px;
     }
 
+    .pd-name-policy {
+        width: auto;
+        padding: 2px 6px;
+        font-size: 0.75rem;
+        color: var(--text-secondary);
+    }
+
     .pd-header-actions {
         display: flex;
         flex-direction: column;
@@ -418,6 +603,7 @@ pub const LAYOUT_STYLES: &str = r#"
     .pd-header-sosa {
         min-height: 24px;
         display: flex;
+        gap: 6px;
         justify-content: flex-end;
     }
 
@@ -434,6 +620,25 @@ pub const LAYOUT_STYLES: &str = r#"
         font-size: 0.8rem;
     }
 
+    .badge.pd-sourcing-badge { font-size: 0.8rem; }
+    .badge.pd-sourcing-badge.well-sourced {
+        color: var(--green);
+        border-color: var(--green);
+    }
+    .badge.pd-sourcing-badge.needs-evidence {
+        color: var(--orange);
+        border-color: var(--orange);
+    }
+    .badge.pd-lineage-badge {
+        font-size: 0.8rem;
+        color: var(--text-muted);
+        border-color: var(--border);
+    }
+    .pd-lock-icon {
+        font-size: 0.8em;
+        cursor: help;
+    }
+
     .pd-sex-mark {
         color: var(--orange);
         font-weight: 600;
@@ -640,6 +845,20 @@ pub const LAYOUT_STYLES: &str = r#"
         margin: 4px 0 0;
     }
 
+    /* Count of citations supporting one name, next to that name. */
+    .pd-cite-badge {
+        display: inline-block;
+        margin-left: 6px;
+        padding: 0 5px;
+        border-radius: 8px;
+        font-size: 0.65rem;
+        font-weight: 700;
+        vertical-align: super;
+        color: var(--text-secondary);
+        border: 1px solid var(--text-muted);
+        cursor: help;
+    }
+
     .pd-vitals {
         font-size: 0.9rem;
         color: var(--text-secondary);
@@ -741,6 +960,22 @@ pub const LAYOUT_STYLES: &str = r#"
         margin: 0;
         padding: 0;
     }
+    .pd-ev-calendar {
+        display: block;
+        font-size: 0.7rem;
+        color: var(--text-muted);
+    }
+    .pd-ev-group + .pd-ev-group {
+        margin-top: 16px;
+    }
+    .pd-ev-group-title {
+        font-size: 0.8rem;
+        font-weight: 600;
+        text-transform: uppercase;
+        letter-spacing: 0.04em;
+        color: var(--text-muted);
+        margin: 0 0 6px;
+    }
     .pd-timeline li {
         display: flex;
         gap: 14px;
@@ -790,6 +1025,17 @@ pub const LAYOUT_STYLES: &str = r#"
         font-style: italic;
         margin-top: 2px;
     }
+    .pd-notes-inherited-title {
+        font-size: 0.95rem;
+        margin: 16px 0 8px;
+        color: var(--text-muted);
+    }
+    .pd-note-inherited {
+        margin-bottom: 12px;
+        padding: 12px;
+        border: 1px dashed var(--color-border);
+        border-radius: var(--radius);
+    }
     /* ── Modal / confirmation dialog ─────────────────────────────── */
 
     .modal-backdrop {
@@ -829,8 +1075,61 @@ pub const LAYOUT_STYLES: &str = r#"
         margin-top: 16px;
     }
 
+    .modal-card.quick-entry {
+        max-width: 560px;
+    }
+
+    .quick-entry-hint {
+        font-size: 0.8rem;
+    }
+
+    .quick-entry-full {
+        margin-right: auto;
+    }
+
     /* ── Tree detail topbar ──────────────────────────────────────── */
 
+    .td-family-cards {
+        display: flex;
+        gap: 8px;
+        padding: 8px 12px;
+        overflow-x: auto;
+        background: var(--bg-panel);
+        border-bottom: 1px solid var(--border);
+        flex-shrink: 0;
+    }
+
+    .td-family-card {
+        display: flex;
+        flex-direction: column;
+        align-items: flex-start;
+        gap: 2px;
+        min-width: 180px;
+        padding: 6px 10px;
+        background: var(--bg-card);
+        border: 1px solid var(--border);
+        border-radius: var(--radius);
+        font: inherit;
+        font-size: 0.8rem;
+        color: var(--text-secondary);
+        text-align: left;
+        cursor: pointer;
+        transition: border-color 0.15s;
+    }
+
+    .td-family-card:hover { border-color: var(--orange); }
+    .td-family-card-warn { border-color: var(--orange); background: rgba(224, 120, 32, 0.12); }
+    .td-family-card-partner { color: var(--text-primary); font-weight: 600; }
+    .td-family-card-warning { color: var(--orange); }
+    .td-family-card-note,
+    .td-family-card-source {
+        max-width: 260px;
+        overflow: hidden;
+        text-overflow: ellipsis;
+        white-space: nowrap;
+        color: var(--text-muted);
+    }
+
     .td-topbar {
         display: flex;
         align-items: center;
@@ -956,6 +1255,69 @@ pub const LAYOUT_STYLES: &str = r#"
         color: var(--text-muted);
     }
 
+    /* ── Recently edited menu ─────────────────────────────────────── */
+
+    .td-recent {
+        position: relative;
+        flex-shrink: 0;
+    }
+
+    .td-recent-menu {
+        position: absolute;
+        top: 100%;
+        right: 0;
+        z-index: 100;
+        width: 300px;
+        max-height: 60vh;
+        overflow-y: auto;
+        margin-top: 4px;
+        padding: 4px;
+        background: var(--bg-panel);
+        border: 1px solid var(--border);
+        border-radius: 8px;
+        box-shadow: 0 8px 24px color-mix(in srgb, var(--shadow-black) 40%, transparent);
+    }
+
+    .td-recent-title {
+        padding: 6px 8px 4px;
+        font-size: 0.72rem;
+        font-weight: 600;
+        text-transform: uppercase;
+        color: var(--text-muted);
+    }
+
+    .td-recent-empty {
+        padding: 6px 8px;
+        font-size: 0.8rem;
+        color: var(--text-muted);
+    }
+
+    .td-recent-item {
+        display: flex;
+        flex-direction: column;
+        gap: 1px;
+        padding: 6px 8px;
+        border-radius: 5px;
+        color: var(--text-primary);
+        text-decoration: none;
+    }
+
+    a.td-recent-item:hover {
+        background: var(--bg-card);
+    }
+
+    .td-recent-label {
+        font-size: 0.82rem;
+        white-space: nowrap;
+        overflow: hidden;
+        text-overflow: ellipsis;
+    }
+
+    .td-recent-meta {
+        font-size: 0.72rem;
+        color: var(--text-muted);
+    }
+
     /* ── Pedigree outer container ────────────────────────────────── */
 
     .pedigree-outer {
@@ -1434,6 +1796,49 @@ pub const LAYOUT_STYLES: &str = r#"
         flex: 1;
     }
 
+    .place-picker {
+        position: relative;
+    }
+
+    .place-picker-list {
+        position: absolute;
+        z-index: 20;
+        top: 100%;
+        left: 0;
+        right: 0;
+        margin: 2px 0 0;
+        padding: 4px 0;
+        list-style: none;
+        max-height: 240px;
+        overflow-y: auto;
+        background: var(--bg-panel);
+        border: 1px solid var(--border);
+        border-radius: var(--radius);
+        box-shadow: 0 4px 12px rgba(0, 0, 0, 0.15);
+    }
+
+    .place-picker-option {
+        display: flex;
+        justify-content: space-between;
+        gap: 8px;
+        padding: 6px 12px;
+        cursor: pointer;
+    }
+
+    .place-picker-option:hover {
+        background: var(--bg-card-hover);
+    }
+
+    .place-picker-create {
+        border-top: 1px solid var(--border);
+        color: var(--green);
+    }
+
+    .place-picker-count {
+        font-size: 0.75rem;
+        color: var(--text-muted);
+    }
+
     .search-person-results {
         max-height: 300px;
         overflow-y: auto;
@@ -1516,9 +1921,158 @@ pub const LAYOUT_STYLES: &str = r#"
         margin-top: 1px;
     }
 
+    .sp-result-alias {
+        font-size: 0.73rem;
+        font-style: italic;
+        color: var(--text-muted);
+    }
+
+    .sp-result-tags {
+        display: flex;
+        flex-wrap: wrap;
+        gap: 4px;
+        margin-top: 3px;
+    }
+
     .search-person-result.male { border-left: 3px solid rgba(74,144,217,0.4); }
     .search-person-result.female { border-left: 3px solid rgba(196,88,122,0.4); }
 
+    /* ── Research tags ───────────────────────────────────────────── */
+
+    .tag-chip {
+        display: inline-flex;
+        align-items: center;
+        gap: 4px;
+        padding: 1px 8px;
+        font-size: 0.72rem;
+        font-weight: 600;
+        border-radius: 12px;
+        border: 1px solid currentColor;
+        white-space: nowrap;
+    }
+    .tag-color-0 { color: var(--orange); background: rgba(224,120,32,0.10); }
+    .tag-color-1 { color: var(--green); background: rgba(78,168,50,0.10); }
+    .tag-color-2 { color: var(--blue); background: rgba(74,144,217,0.10); }
+    .tag-color-3 { color: var(--pink); background: rgba(196,88,122,0.10); }
+    .tag-color-4 { color: var(--red); background: rgba(224,85,85,0.10); }
+    .tag-color-5 { color: var(--text-secondary); background: var(--bg-panel); }
+
+    .tag-chip-remove {
+        border: none;
+        background: none;
+        padding: 0;
+        font: inherit;
+        color: inherit;
+        cursor: pointer;
+        opacity: 0.7;
+    }
+    .tag-chip-remove:hover { opacity: 1; }
+
+    .tag-editor {
+        display: flex;
+        flex-wrap: wrap;
+        align-items: center;
+        gap: 6px;
+        margin-top: 8px;
+    }
+    .tag-editor-input {
+        width: 180px;
+        padding: 3px 8px;
+        font-size: 0.8rem;
+        border: 1px solid var(--border);
+        border-radius: 12px;
+        background: var(--bg-card);
+        color: var(--text-primary);
+    }
+    .tag-editor-error { font-size: 0.75rem; }
+
+    /* ── Research tasks ───────────────────────────────────────────── */
+
+    .task-list {
+        list-style: none;
+        margin: 0 0 12px;
+        padding: 0;
+    }
+    .task-item {
+        display: flex;
+        align-items: flex-start;
+        gap: 10px;
+        padding: 8px 0;
+        border-bottom: 1px solid var(--border);
+    }
+    .task-item:last-child { border-bottom: none; }
+    .task-item input[type="checkbox"] { margin-top: 3px; }
+    .task-body {
+        flex: 1;
+        display: flex;
+        flex-wrap: wrap;
+        align-items: baseline;
+        gap: 4px 10px;
+    }
+    .task-title { color: var(--text-primary); }
+    .task-done .task-title {
+        color: var(--text-muted);
+        text-decoration: line-through;
+    }
+    .task-description {
+        flex-basis: 100%;
+        font-size: 0.85rem;
+        color: var(--text-secondary);
+    }
+    .task-due {
+        font-size: 0.8rem;
+        color: var(--text-secondary);
+    }
+    .task-overdue .task-due { color: var(--red); font-weight: 600; }
+    .task-owner { font-size: 0.8rem; }
+    .task-delete {
+        border: none;
+        background: none;
+        padding: 0 4px;
+        font-size: 1rem;
+        color: var(--text-muted);
+        cursor: pointer;
+    }
+    .task-delete:hover { color: var(--red); }
+    .task-add {
+        display: flex;
+        flex-wrap: wrap;
+        gap: 8px;
+    }
+    .task-add input[type="text"] { flex: 1; min-width: 200px; }
+    .task-filter {
+        display: flex;
+        gap: 6px;
+        margin-bottom: 16px;
+    }
+
+    /* ── Plausibility warnings ───────────────────────────────────── */
+
+    .plausibility-warnings {
+        background: rgba(224, 120, 32, 0.1);
+        border: 1px solid rgba(224, 120, 32, 0.4);
+        padding: 10px 14px;
+        border-radius: var(--radius);
+        margin-bottom: 16px;
+    }
+    .plausibility-title { font-weight: 600; margin-bottom: 4px; }
+    .plausibility-warnings ul { list-style: none; margin: 0; padding: 0; }
+    .plausibility-warnings li {
+        display: flex;
+        align-items: center;
+        justify-content: space-between;
+        gap: 8px;
+    }
+    .plausibility-dismiss {
+        border: none;
+        background: none;
+        padding: 0 4px;
+        font-size: 1rem;
+        color: var(--text-muted);
+        cursor: pointer;
+    }
+    .plausibility-dismiss:hover { color: var(--orange); }
+
     /* ── Person form modal ────────────────────────────────────────── */
 
     .person-form-backdrop {
@@ -1611,6 +2165,46 @@ pub const LAYOUT_STYLES: &str = r#"
         flex-shrink: 0;
     }
 
+    .pf-note-format {
+        display: flex;
+        align-items: center;
+        gap: 6px;
+        margin-top: 6px;
+        font-size: 0.8rem;
+        color: var(--text-secondary);
+        cursor: pointer;
+    }
+
+    /* ── Note text (plain / markdown) ──────────────────────────────── */
+
+    .note-plain {
+        margin: 0;
+        white-space: pre-wrap;
+    }
+
+    .note-markdown > :first-child { margin-top: 0; }
+    .note-markdown > :last-child { margin-bottom: 0; }
+    .note-markdown h1, .note-markdown h2, .note-markdown h3 {
+        font-size: 1rem;
+        margin: 12px 0 6px;
+    }
+    .note-markdown ul, .note-markdown ol {
+        margin: 6px 0;
+        padding-left: 20px;
+    }
+    .note-markdown p { margin: 6px 0; }
+    .note-markdown blockquote {
+        margin: 6px 0;
+        padding-left: 10px;
+        border-left: 3px solid var(--border);
+        color: var(--text-secondary);
+    }
+    .note-markdown table { border-collapse: collapse; }
+    .note-markdown th, .note-markdown td {
+        border: 1px solid var(--border);
+        padding: 4px 8px;
+    }
+
     /* ── Person form — section redesign ────────────────────────────── */
 
     .pf-subtitle {
@@ -1707,12 +2301,13 @@ pub const LAYOUT_STYLES: &str = r#"
         padding-top: 7px;
     }
 
-    /* ── Witnesses list ────────────────────────────────────────────── */
+    /* ── Participants list ─────────────────────────────────────────── */
 
     .pf-witness-list { margin-bottom: 6px; }
     .pf-witness-row { display: flex; gap: 6px; align-items: center; margin-bottom: 6px; }
     .pf-witness-row input { flex: 1; }
     .pf-witness-name { font-weight: 500; }
+    .pf-witness-role { color: var(--text-secondary); font-size: 0.88rem; }
     .pf-witness-relation { color: var(--text-secondary); font-size: 0.88rem; }
     .pf-witness-add { display: flex; flex-direction: column; gap: 6px; margin-top: 6px; }
     .pf-witness-remove {
@@ -1954,6 +2549,17 @@ pub const LAYOUT_STYLES: &str = r#"
         opacity: 0.45;
     }
 
+    .uf-child-row.dragging {
+        opacity: 0.6;
+        border-style: dashed;
+    }
+
+    .uf-child-drag-handle {
+        cursor: grab;
+        color: var(--text-muted);
+        flex-shrink: 0;
+    }
+
     .uf-child-avatar {
         width: 26px;
         height: 26px;
@@ -2031,26 +2637,75 @@ pub const LAYOUT_STYLES: &str = r#"
         overflow: hidden;
     }
 
-    .search-results-page .sub-page-content {
-        flex: 1;
-        overflow-y: auto;
-        max-width: 1200px;
-        margin: 0 auto;
-        width: 100%;
-        padding: 16px 24px;
+    .search-results-page .sub-page-content {
+        flex: 1;
+        overflow-y: auto;
+        max-width: 1200px;
+        margin: 0 auto;
+        width: 100%;
+        padding: 16px 24px;
+    }
+
+    .sr-count {
+        font-size: 0.85rem;
+        color: var(--text-muted);
+        margin: 0;
+    }
+
+    /* Filters */
+    .sr-filters-toggle {
+        display: flex;
+        gap: 8px;
+        margin-bottom: 8px;
+    }
+
+    /* Surname index */
+    .sr-surname-index {
+        display: flex;
+        flex-direction: column;
+        gap: 10px;
+        padding: 12px;
+        margin-bottom: 12px;
+        background: var(--bg-card);
+        border: 1px solid var(--border);
+        border-radius: 6px;
+    }
+
+    .sr-surname-list {
+        display: flex;
+        flex-wrap: wrap;
+        gap: 6px;
+    }
+
+    .sr-surname-btn {
+        display: inline-flex;
+        align-items: center;
+        gap: 6px;
+        padding: 4px 10px;
+        background: none;
+        border: 1px solid var(--border);
+        border-radius: 12px;
+        color: var(--text-primary);
+        font-size: 0.82rem;
+        cursor: pointer;
+    }
+
+    .sr-surname-btn.active {
+        border-color: var(--orange);
+        color: var(--orange);
+    }
+
+    .sr-surname-count {
+        color: var(--text-secondary);
+        font-size: 0.72rem;
     }
 
-    .sr-count {
+    .sr-surname-empty {
+        color: var(--text-secondary);
         font-size: 0.85rem;
-        color: var(--text-muted);
         margin: 0;
     }
 
-    /* Filters */
-    .sr-filters-toggle {
-        margin-bottom: 8px;
-    }
-
     .sr-chevron {
         display: inline-block;
         font-size: 0.6rem;
@@ -2142,6 +2797,50 @@ pub const LAYOUT_STYLES: &str = r#"
         border-radius: 8px;
     }
 
+    /* Multi-select */
+    .sr-selection-bar {
+        display: flex;
+        align-items: center;
+        flex-wrap: wrap;
+        gap: 10px;
+        margin-bottom: 12px;
+        padding: 8px 12px;
+        background: var(--bg-card);
+        border: 1px solid var(--border);
+        border-radius: 8px;
+    }
+
+    .sr-selection-summary {
+        font-size: 0.85rem;
+        color: var(--text-primary);
+        margin-right: auto;
+    }
+
+    .sr-selection-detach,
+    .sr-select-all {
+        display: inline-flex;
+        align-items: center;
+        gap: 6px;
+        font-size: 0.82rem;
+        color: var(--text-muted);
+        margin-bottom: 0;
+    }
+
+    .sr-select-all {
+        margin: 0 0 8px 12px;
+    }
+
+    .sr-select-row {
+        display: flex;
+        align-items: center;
+        gap: 8px;
+    }
+
+    .sr-select-row .sr-select-box {
+        flex-shrink: 0;
+        margin-left: 12px;
+    }
+
     .sr-sort {
         display: flex;
         align-items: center;
@@ -2336,6 +3035,63 @@ pub const LAYOUT_STYLES: &str = r#"
         color: var(--text-primary);
     }
 
+    .surname-distribution-toolbar {
+        display: flex;
+        align-items: center;
+        gap: 8px;
+        margin: 12px 0 4px;
+    }
+    .surname-distribution-hint {
+        font-size: 0.85rem;
+        margin-bottom: 12px;
+    }
+    .surname-heatmap-wrap {
+        overflow-x: auto;
+    }
+    .surname-heatmap {
+        border-collapse: separate;
+        border-spacing: 2px;
+        font-size: 0.82rem;
+    }
+    .surname-heatmap thead th {
+        font-weight: 500;
+        color: var(--text-muted);
+        padding: 4px;
+        white-space: nowrap;
+    }
+    .surname-heatmap-place {
+        max-width: 110px;
+        overflow: hidden;
+        text-overflow: ellipsis;
+    }
+    .surname-heatmap-name {
+        text-align: left;
+        padding-right: 12px;
+        white-space: nowrap;
+    }
+    .surname-heatmap-total {
+        position: relative;
+        min-width: 80px;
+        padding: 2px 6px;
+    }
+    .surname-heatmap-total span {
+        position: relative;
+    }
+    .surname-heatmap-bar {
+        position: absolute;
+        inset: 2px auto 2px 0;
+        background: var(--orange);
+        opacity: 0.25;
+        border-radius: 2px;
+    }
+    .surname-heatmap-cell {
+        min-width: 36px;
+        height: 26px;
+        text-align: center;
+        border-radius: 3px;
+        background: color-mix(in srgb, var(--orange) calc(var(--heat) * 100%), transparent);
+    }
+
     .dict-alphabet {
         display: flex;
         align-items: center;
@@ -2596,6 +3352,223 @@ pub const LAYOUT_STYLES: &str = r#"
         padding: 4px 0;
     }
 
+    /* ── Source detail ────────────────────────────────────────────── */
+
+    .src-header {
+        margin-bottom: 20px;
+    }
+
+    .src-title {
+        font-family: var(--font-heading);
+        font-size: 1.3rem;
+        color: var(--text-primary);
+        margin: 0 0 4px;
+    }
+
+    .src-abbr {
+        font-size: 0.78rem;
+        color: var(--orange);
+    }
+
+    .src-meta {
+        font-size: 0.85rem;
+        color: var(--text-muted);
+        margin-top: 4px;
+    }
+
+    .src-section-title {
+        font-family: var(--font-heading);
+        font-size: 0.9rem;
+        letter-spacing: 0.05em;
+        color: var(--orange);
+        margin: 0 0 6px;
+    }
+
+    .src-usage-list {
+        display: flex;
+        flex-direction: column;
+        gap: 6px;
+    }
+
+    .src-usage-group {
+        padding: 8px 12px;
+        border: 1px solid var(--border);
+        border-radius: 6px;
+        background: var(--bg-card);
+    }
+
+    .src-usage-owner {
+        display: block;
+        font-size: 0.9rem;
+        color: var(--text-primary);
+        text-decoration: none;
+        margin-bottom: 4px;
+    }
+
+    a.src-usage-owner:hover {
+        color: var(--orange);
+    }
+
+    .src-usage-item {
+        padding: 4px 0 4px 12px;
+        border-left: 2px solid var(--border);
+    }
+
+    .src-usage-line {
+        display: flex;
+        flex-wrap: wrap;
+        align-items: baseline;
+        gap: 8px;
+        font-size: 0.82rem;
+    }
+
+    .src-usage-what {
+        color: var(--text-primary);
+    }
+
+    .src-usage-page,
+    .src-usage-confidence {
+        color: var(--text-muted);
+        font-size: 0.76rem;
+    }
+
+    .src-usage-text {
+        margin: 4px 0 0;
+        padding: 4px 8px;
+        background: var(--bg-deep);
+        border-radius: 4px;
+        font-size: 0.8rem;
+        color: var(--text-muted);
+        white-space: pre-wrap;
+    }
+
+    .src-usage-empty {
+        color: var(--text-muted);
+        font-size: 0.82rem;
+        padding: 4px 0;
+    }
+
+    /* ── Person comparison ────────────────────────────────────────── */
+
+    .cmp-title {
+        font-family: var(--font-heading);
+        font-size: 1.3rem;
+        color: var(--text-primary);
+        margin: 0 0 4px;
+    }
+
+    .cmp-summary {
+        font-size: 0.85rem;
+        color: var(--text-muted);
+        margin-bottom: 16px;
+    }
+
+    .cmp-table {
+        display: flex;
+        flex-direction: column;
+        border: 1px solid var(--border);
+        border-radius: 6px;
+        background: var(--bg-card);
+        overflow: hidden;
+    }
+
+    .cmp-row {
+        display: grid;
+        grid-template-columns: 180px 1fr 1fr;
+        gap: 12px;
+        padding: 8px 12px;
+        border-top: 1px solid var(--border);
+        border-left: 3px solid transparent;
+        font-size: 0.85rem;
+    }
+
+    .cmp-head {
+        border-top: none;
+        background: var(--bg-deep);
+    }
+
+    .cmp-field {
+        display: flex;
+        flex-direction: column;
+        gap: 2px;
+        color: var(--text-muted);
+    }
+
+    .cmp-badge {
+        font-size: 0.7rem;
+        text-transform: uppercase;
+        letter-spacing: 0.04em;
+    }
+
+    .cmp-value {
+        color: var(--text-primary);
+        overflow-wrap: anywhere;
+    }
+
+    .cmp-person {
+        font-family: var(--font-heading);
+        text-decoration: none;
+    }
+
+    a.cmp-person:hover {
+        color: var(--orange);
+    }
+
+    .cmp-empty {
+        color: var(--text-muted);
+    }
+
+    .cmp-same { border-left-color: var(--green); }
+    .cmp-same .cmp-badge { color: var(--green); }
+    .cmp-compatible { border-left-color: var(--green-light); }
+    .cmp-compatible .cmp-badge { color: var(--green-light); }
+    .cmp-different { border-left-color: var(--red); background: rgba(224,85,85,0.06); }
+    .cmp-different .cmp-badge { color: var(--red); }
+    .cmp-partial .cmp-badge { color: var(--text-muted); }
+
+    /* ── Relationship path ────────────────────────────────────────── */
+
+    .rel-path {
+        display: flex;
+        flex-wrap: wrap;
+        align-items: flex-end;
+        gap: 8px 0;
+    }
+
+    .rel-step {
+        display: flex;
+        align-items: flex-end;
+    }
+
+    .rel-sep {
+        margin: 0 10px;
+        color: var(--text-muted);
+    }
+
+    .rel-edge {
+        display: block;
+        margin-right: 6px;
+        font-size: 0.7rem;
+        text-transform: uppercase;
+        letter-spacing: 0.04em;
+        color: var(--text-muted);
+    }
+
+    .rel-person {
+        font-family: var(--font-heading);
+        color: var(--text-primary);
+        text-decoration: none;
+    }
+
+    a.rel-person:hover {
+        color: var(--orange);
+    }
+
+    .rel-common .rel-person {
+        color: var(--orange);
+        font-weight: 600;
+    }
+
     /* ── Import overlay (blocking spinner) ───────────────────────── */
 
     .import-overlay {
@@ -2631,6 +3604,25 @@ pub const LAYOUT_STYLES: &str = r#"
         letter-spacing: 0.04em;
     }
 
+    .import-progress {
+        width: min(360px, 80vw);
+        height: 6px;
+        background: var(--border);
+        border-radius: 3px;
+        overflow: hidden;
+    }
+
+    .import-progress-fill {
+        height: 100%;
+        background: var(--orange);
+        transition: width 0.2s ease;
+    }
+
+    .import-progress-text {
+        font-size: 0.85rem;
+        color: var(--text-muted);
+    }
+
     /* ── Scrollbar ────────────────────────────────────────────────── */
 
     ::-webkit-scrollbar { width: 6px; height: 6px; }
@@ -0,0 +1,59 @@
+//! Note text rendering.
+//!
+//! Plain notes are shown verbatim with their line breaks. Markdown notes are
+//! rendered to HTML and then sanitized: notes routinely come from GEDCOM
+//! files of unknown origin, so scripts, event handlers, `javascript:` links
+//! and other active content must never reach the DOM.
+
+use dioxus::prelude::*;
+use oxidgene_core::NoteFormat;
+use pulldown_cmark::{Options, Parser, html};
+
+/// Render CommonMark `text` to sanitized HTML.
+pub fn render_markdown(text: &str) -> String {
+    let parser = Parser::new_ext(text, Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH);
+    let mut raw = String::new();
+    html::push_html(&mut raw, parser);
+    ammonia::Builder::default()
+        .link_rel(Some("noopener noreferrer nofollow"))
+        .clean(&raw)
+        .to_string()
+}
+
+/// Display a note's text according to its format.
+#[component]
+pub fn NoteText(text: String, format: NoteFormat) -> Element {
+    match format {
+        NoteFormat::Plain => rsx! {
+            p { class: "note-plain", "{text}" }
+        },
+        NoteFormat::Markdown => {
+            let html = render_markdown(&text);
+            rsx! {
+                div { class: "note-markdown", dangerous_inner_html: "{html}" }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_headings_and_lists() {
+        let html = render_markdown("# Research\n\n- parish register\n- census");
+        assert!(html.contains("<h1>Research</h1>"));
+        assert!(html.contains("<li>parish register</li>"));
+    }
+
+    #[test]
+    fn strips_scripts_and_event_handlers() {
+        let html = render_markdown(
+            "<script>alert(1)</script>\n\n<img src=\"x.png\" onerror=\"alert(1)\">\n\n[x](javascript:alert(1))",
+        );
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("javascript:"));
+    }
+}
@@ -0,0 +1,274 @@
+//! Person media gallery: thumbnails of every media linked to a person,
+//! captioned with the media title, with a lightbox and reordering.
+//!
+//! Media are fetched a page at a time and thumbnails use the browser's
+//! lazy image loading, so a person with many documents stays cheap to open.
+
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use crate::api::{ApiClient, PersonMediaEntry};
+use crate::i18n::use_i18n;
+
+/// Number of media fetched per "show more".
+const PAGE_SIZE: usize = 12;
+
+/// Props for the [`PersonMediaGallery`] component.
+#[derive(Props, Clone, PartialEq)]
+pub struct PersonMediaGalleryProps {
+    pub tree_id: Uuid,
+    pub person_id: Uuid,
+}
+
+/// A person's media as a thumbnail grid; clicking one opens the lightbox.
+#[component]
+pub fn PersonMediaGallery(props: PersonMediaGalleryProps) -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+    let tree_id = props.tree_id;
+    let person_id = props.person_id;
+
+    let mut shown = use_signal(|| PAGE_SIZE);
+    let mut refresh = use_signal(|| 0u32);
+    let mut lightbox = use_signal(|| None::<usize>);
+    let mut error = use_signal(|| None::<String>);
+
+    let api_list = api.clone();
+    let media_resource = use_resource(move || {
+        let api = api_list.clone();
+        let limit = shown();
+        let _tick = refresh();
+        async move { api.list_person_media(tree_id, person_id, 0, limit).await }
+    });
+
+    let (items, total) = match &*media_resource.read() {
+        Some(Ok(page)) => (page.items.clone(), page.total),
+        _ => (vec![], 0),
+    };
+    // The reorder endpoint takes every link, so moving needs them all loaded.
+    let can_reorder = items.len() == total && total > 1;
+
+    let api_move = api.clone();
+    let move_item = move |index: usize, to: usize| {
+        let mut link_ids: Vec<Uuid> = match &*media_resource.read() {
+            Some(Ok(page)) => page.items.iter().map(|m| m.link_id).collect(),
+            _ => return,
+        };
+        if index >= link_ids.len() || to >= link_ids.len() {
+            return;
+        }
+        let link_id = link_ids.remove(index);
+        link_ids.insert(to, link_id);
+        let api = api_move.clone();
+        spawn(async move {
+            match api.reorder_person_media(tree_id, person_id, link_ids).await {
+                Ok(()) => {
+                    error.set(None);
+                    refresh += 1;
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    if total == 0 && error().is_none() && !matches!(&*media_resource.read(), Some(Err(_))) {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "card", style: "margin-bottom: 24px;",
+            h2 { style: "font-size: 1.1rem; margin-bottom: 12px;",
+                {i18n.t("media.gallery.title")}
+                span { class: "text-muted", " ({total})" }
+            }
+
+            if let Some(Err(e)) = &*media_resource.read() {
+                div { class: "error-msg", {i18n.t_args("media.gallery.load_error", &[("error", &e.to_string())])} }
+            }
+            if let Some(err) = error() {
+                div { class: "error-msg", "{err}" }
+            }
+
+            div { class: "media-gallery",
+                for (index, entry) in items.iter().cloned().enumerate() {
+                    div { key: "{entry.link_id}", class: "media-gallery-item",
+                        button {
+                            class: "media-gallery-thumb",
+                            title: "{entry.caption()}",
+                            onclick: move |_| lightbox.set(Some(index)),
+                            MediaPreview { entry: entry.clone(), lazy: true }
+                        }
+                        div { class: "media-gallery-caption",
+                            if entry.is_profile {
+                                span { class: "media-gallery-profile", title: i18n.t("media.gallery.profile"), "\u{2605} " }
+                            }
+                            "{entry.caption()}"
+                        }
+                        if can_reorder {
+                            div { class: "media-gallery-order",
+                                button {
+                                    class: "btn btn-outline btn-sm",
+                                    title: i18n.t("media.gallery.move_before"),
+                                    disabled: index == 0,
+                                    onclick: {
+                                        let move_item = move_item.clone();
+                                        move |_| move_item(index, index - 1)
+                                    },
+                                    "\u{2190}"
+                                }
+                                button {
+                                    class: "btn btn-outline btn-sm",
+                                    title: i18n.t("media.gallery.move_after"),
+                                    disabled: index + 1 == total,
+                                    onclick: {
+                                        let move_item = move_item.clone();
+                                        move |_| move_item(index, index + 1)
+                                    },
+                                    "\u{2192}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if items.len() < total {
+                button {
+                    class: "btn btn-outline btn-sm",
+                    style: "margin-top: 12px;",
+                    onclick: move |_| shown += PAGE_SIZE,
+                    {i18n.t_args("media.gallery.show_more", &[("n", &(total - items.len()).to_string())])}
+                }
+            }
+        }
+
+        if let Some(index) = lightbox().filter(|&i| i < items.len()) {
+            MediaLightbox {
+                entry: items[index].clone(),
+                position: index + 1,
+                count: items.len(),
+                on_navigate: move |i: usize| lightbox.set(Some(i - 1)),
+                on_close: move |_| lightbox.set(None),
+            }
+        }
+    }
+}
+
+/// Props for the [`MediaPreview`] component.
+#[derive(Props, Clone, PartialEq)]
+struct MediaPreviewProps {
+    entry: PersonMediaEntry,
+    /// Defer loading the image until it nears the viewport.
+    lazy: bool,
+}
+
+/// The image itself, or a file-type tile for documents.
+#[component]
+fn MediaPreview(props: MediaPreviewProps) -> Element {
+    let entry = &props.entry;
+    if entry.is_image() {
+        let loading = if props.lazy { "lazy" } else { "eager" };
+        rsx! {
+            img {
+                alt: "{entry.caption()}",
+                src: "{entry.media.file_path}",
+                loading: "{loading}",
+                decoding: "async",
+            }
+        }
+    } else {
+        let extension = entry
+            .media
+            .file_name
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_uppercase())
+            .unwrap_or_default();
+        rsx! {
+            div { class: "media-gallery-file", "{extension}" }
+        }
+    }
+}
+
+/// Props for the [`MediaLightbox`] component.
+#[derive(Props, Clone, PartialEq)]
+struct MediaLightboxProps {
+    entry: PersonMediaEntry,
+    /// 1-based position of the entry among the loaded media.
+    position: usize,
+    count: usize,
+    /// Called with the 1-based position to show instead.
+    on_navigate: EventHandler<usize>,
+    on_close: EventHandler<()>,
+}
+
+/// Full-size view of one media, with previous/next navigation.
+#[component]
+fn MediaLightbox(props: MediaLightboxProps) -> Element {
+    let i18n = use_i18n();
+    let entry = props.entry.clone();
+    let position = props.position;
+    let count = props.count;
+    let on_navigate = props.on_navigate;
+    let on_close = props.on_close;
+
+    rsx! {
+        div {
+            class: "media-lightbox",
+            tabindex: "0",
+            onmounted: move |e| async move {
+                let _ = e.set_focus(true).await;
+            },
+            onclick: move |_| on_close.call(()),
+            onkeydown: move |e: Event<KeyboardData>| match e.key() {
+                Key::Escape => on_close.call(()),
+                Key::ArrowLeft if position > 1 => on_navigate.call(position - 1),
+                Key::ArrowRight if position < count => on_navigate.call(position + 1),
+                _ => {}
+            },
+            div {
+                class: "media-lightbox-content",
+                onclick: move |e| e.stop_propagation(),
+                button {
+                    class: "media-lightbox-close",
+                    title: i18n.t("common.close"),
+                    onclick: move |_| on_close.call(()),
+                    "\u{00D7}"
+                }
+                if entry.is_image() {
+                    img { alt: "{entry.caption()}", src: "{entry.media.file_path}" }
+                } else {
+                    a {
+                        class: "btn btn-primary",
+                        href: "{entry.media.file_path}",
+                        target: "_blank",
+                        {i18n.t("media.gallery.open_file")}
+                    }
+                }
+                div { class: "media-lightbox-caption",
+                    strong { "{entry.caption()}" }
+                    if let Some(date) = &entry.media.date_value {
+                        span { class: "text-muted", " \u{00B7} {date}" }
+                    }
+                    if let Some(description) = entry.media.description.as_deref().filter(|d| !d.is_empty()) {
+                        p { "{description}" }
+                    }
+                }
+                div { class: "media-lightbox-nav",
+                    button {
+                        class: "btn btn-outline btn-sm",
+                        disabled: position <= 1,
+                        onclick: move |_| on_navigate.call(position - 1),
+                        "\u{2190}"
+                    }
+                    span { class: "text-muted", "{position} / {count}" }
+                    button {
+                        class: "btn btn-outline btn-sm",
+                        disabled: position >= count,
+                        onclick: move |_| on_navigate.call(position + 1),
+                        "\u{2192}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -3,9 +3,18 @@
 pub mod confirm_dialog;
 pub mod context_menu;
 pub mod layout;
+pub mod markdown;
+pub mod media_gallery;
 pub mod pedigree_chart;
 pub mod person_form;
+pub mod person_tags;
+pub mod place_input;
+pub mod plausibility_warnings;
+pub mod quick_entry;
+pub mod recent_activity;
+pub mod research_tasks;
 pub mod search_person;
+pub mod surname_distribution;
 pub mod topbar_search;
 pub mod tree_cache;
 pub mod tree_icon_sidebar;
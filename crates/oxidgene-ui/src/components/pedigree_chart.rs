@@ -25,7 +25,7 @@ use oxidgene_core::{Calendar, ChildType, DateQualifier, EventType, Privacy, Sex,
 
 use crate::i18n::use_i18n;
 
-use crate::utils::truncate_text_to_fit;
+use crate::utils::{surname_color, truncate_text_to_fit};
 
 // ── Layout constants (matching the JS reference implementation) ──────────
 
@@ -100,6 +100,7 @@ const VIEWPORT_DEFAULT_H: f64 = 600.0;
 const FIT_SIDE_PADDING_RATIO: f64 = 0.05;
 const EVENT_PANEL_AUTO_COLLAPSE_WIDTH: f64 = 900.0;
 const EVENT_PANEL_MANUAL_STORAGE_KEY: &str = "oxidgene-ev-panel-manual";
+const NODE_COLORING_STORAGE_KEY: &str = "oxidgene-pedigree-coloring";
 const ZOOM_FACTOR: f64 = 1.2;
 const ZOOM_MIN: f64 = 0.3;
 const ZOOM_MAX: f64 = 2.0;
@@ -127,10 +128,20 @@ pub(crate) fn default_portrait(sex: Sex) -> &'static str {
     match sex {
         Sex::Male => PORTRAIT_MALE,
         Sex::Female => PORTRAIT_FEMALE,
-        Sex::Unknown => PORTRAIT_UNKNOWN,
+        Sex::Intersex | Sex::Unknown => PORTRAIT_UNKNOWN,
     }
 }
 
+/// Marriage and divorce details shown on a family card.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnionEvents {
+    pub marriage_date: Option<String>,
+    pub marriage_place: Option<String>,
+    pub divorce_date: Option<String>,
+    /// A divorce is dated before the marriage.
+    pub divorce_before_marriage: bool,
+}
+
 // ── Helper functions ─────────────────────────────────────────────────────
 
 /// Extract a 4-digit year from a GEDCOM date string (e.g. "ABT 1842", "1 JAN 1900").
@@ -170,6 +181,46 @@ fn gender_stroke(sex: Sex) -> &'static str {
     }
 }
 
+/// What the colored line on the left of each card encodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NodeColoring {
+    #[default]
+    Sex,
+    /// One color per surname, also tinting the card border.
+    Surname,
+}
+
+impl NodeColoring {
+    /// The value persisted in localStorage.
+    fn as_str(self) -> &'static str {
+        match self {
+            NodeColoring::Sex => "sex",
+            NodeColoring::Surname => "surname",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "surname" => NodeColoring::Surname,
+            _ => NodeColoring::Sex,
+        }
+    }
+
+    /// `(line stroke, card border stroke)` for a person card.
+    fn card_strokes(self, sex: Sex, surname: &str) -> (String, String) {
+        match self {
+            NodeColoring::Sex => (
+                gender_stroke(sex).to_string(),
+                "var(--pn-border)".to_string(),
+            ),
+            NodeColoring::Surname => {
+                let color = surname_color(surname);
+                (color.clone(), color)
+            }
+        }
+    }
+}
+
 /// CSS variable for the card background fill.
 fn card_bg(is_focus: bool, is_sibling: bool) -> &'static str {
     if is_focus {
@@ -301,8 +352,12 @@ impl PedigreeData {
                 tree_id,
                 sex: node.sex,
                 privacy: Privacy::default(),
+                living: None,
+                display_name: None,
+                locked: false,
                 created_at: now,
                 updated_at: now,
+                version: 1,
                 deleted_at: None,
             };
             persons.insert(node.person_id, person);
@@ -351,12 +406,15 @@ impl PedigreeData {
                     date_value2: None,
                     calendar: Calendar::default(),
                     cause: None,
+                    age: None,
                     place_id: None,
+                    place_id_to: None,
                     person_id: Some(node.person_id),
                     family_id: None,
                     description: None,
                     created_at: now,
                     updated_at: now,
+                    version: 1,
                     deleted_at: None,
                 };
                 // If we have a birth place string, we can't create a real Place
@@ -382,12 +440,15 @@ impl PedigreeData {
                     date_value2: None,
                     calendar: Calendar::default(),
                     cause: None,
+                    age: None,
                     place_id: None,
+                    place_id_to: None,
                     person_id: Some(node.person_id),
                     family_id: None,
                     description: None,
                     created_at: now,
                     updated_at: now,
+                    version: 1,
                     deleted_at: None,
                 };
                 if node.death_place.is_some() {
@@ -493,12 +554,15 @@ impl PedigreeData {
                     date_value2: None,
                     calendar: Calendar::default(),
                     cause: None,
+                    age: None,
                     place_id: ce.place_id,
+                    place_id_to: None,
                     person_id: None,
                     family_id: Some(*family_id),
                     description: ce.description.clone(),
                     created_at: now,
                     updated_at: now,
+                    version: 1,
                     deleted_at: None,
                 })
                 .collect();
@@ -522,8 +586,12 @@ impl PedigreeData {
                     tree_id,
                     sex: member.sex,
                     privacy: Privacy::default(),
+                    living: None,
+                    display_name: Some(member.display_name.clone()),
+                    locked: false,
                     created_at: now,
                     updated_at: now,
+                    version: 1,
                     deleted_at: None,
                 };
                 persons.insert(member.person_id, person);
@@ -559,12 +627,15 @@ impl PedigreeData {
                         date_value2: None,
                         calendar: Calendar::default(),
                         cause: None,
+                        age: None,
                         place_id: None,
+                        place_id_to: None,
                         person_id: Some(member.person_id),
                         family_id: None,
                         description: None,
                         created_at: now,
                         updated_at: now,
+                        version: 1,
                         deleted_at: None,
                     });
                 }
@@ -583,12 +654,15 @@ impl PedigreeData {
                         date_value2: None,
                         calendar: Calendar::default(),
                         cause: None,
+                        age: None,
                         place_id: None,
+                        place_id_to: None,
                         person_id: Some(member.person_id),
                         family_id: None,
                         description: None,
                         created_at: now,
                         updated_at: now,
+                        version: 1,
                         deleted_at: None,
                     });
                 }
@@ -662,7 +736,7 @@ impl PedigreeData {
                             mother = Some(sp.person_id);
                         }
                     }
-                    Sex::Unknown => {
+                    Sex::Intersex | Sex::Unknown => {
                         if father.is_none() {
                             father = Some(sp.person_id);
                         } else if mother.is_none() {
@@ -737,6 +811,27 @@ impl PedigreeData {
         self.places.get(&place_id).map(|p| p.name.as_str())
     }
 
+    /// Marriage and divorce of a family, for the family cards.
+    pub fn union_events(&self, family_id: Uuid) -> UnionEvents {
+        let events = self
+            .events_by_family
+            .get(&family_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let marriage = events.iter().find(|e| e.event_type == EventType::Marriage);
+        let divorce = events.iter().find(|e| e.event_type == EventType::Divorce);
+        UnionEvents {
+            marriage_date: marriage.and_then(|e| e.date_value.clone()),
+            marriage_place: marriage
+                .and_then(|e| e.place_id)
+                .and_then(|pid| self.place_name(pid))
+                .map(String::from),
+            divorce_date: divorce.and_then(|e| e.date_value.clone()),
+            divorce_before_marriage: !oxidgene_core::chronology::divorces_before_marriage(events)
+                .is_empty(),
+        }
+    }
+
     /// Get unions for a person: Vec<(family_id, partner_name, marriage_year)>.
     pub fn unions_for_person(&self, person_id: Uuid) -> Vec<(Uuid, String, String)> {
         let Some(family_ids) = self.families_as_spouse.get(&person_id) else {
@@ -2809,6 +2904,7 @@ pub fn MiniPedigree(props: MiniPedigreeProps) -> Element {
                                     noop_click,
                                     noop_empty_slot,
                                     false,
+                                    NodeColoring::Sex,
                                 )}
                             }
                         }
@@ -2828,6 +2924,7 @@ pub fn MiniPedigree(props: MiniPedigreeProps) -> Element {
                                     noop_click,
                                     noop_empty_slot,
                                     false,
+                                    NodeColoring::Sex,
                                 )}
                             }
                         }
@@ -2871,6 +2968,8 @@ pub struct PedigreeChartProps {
     pub on_settings: EventHandler<()>,
     #[props(default)]
     pub on_dictionary: EventHandler<()>,
+    #[props(default)]
+    pub on_tasks: EventHandler<()>,
 }
 
 /// Render one card (person or empty slot) of the pedigree as an SVG `<g>`.
@@ -2890,6 +2989,7 @@ fn render_pedigree_card(
     on_person_click: EventHandler<(Uuid, f64, f64)>,
     on_empty_slot: EventHandler<(Uuid, bool)>,
     allow_empty_click: bool,
+    coloring: NodeColoring,
 ) -> Element {
     let is_compact = node.is_compact;
     let (rw, rh) = if is_compact {
@@ -2927,13 +3027,13 @@ fn render_pedigree_card(
             } else {
                 "var(--pn-text)"
             };
-            let stroke = gender_stroke(node.sex);
             let label_surname = node
                 .label_surname
                 .split(",")
                 .next()
                 .unwrap_or("")
                 .to_string();
+            let (stroke, border) = coloring.card_strokes(node.sex, &label_surname);
             let surname_up = label_surname.to_uppercase();
             let surname_disp = if is_compact {
                 surname_up
@@ -2993,7 +3093,7 @@ fn render_pedigree_card(
                         let coords = evt.client_coordinates();
                         on_person_click.call((pid, coords.x, coords.y));
                     },
-                    rect { class: "ped-card-rect", x: "{CARD_PADDING}", y: "{CARD_PADDING}", rx: "{CARD_BORDER_RADIUS}", ry: "{CARD_BORDER_RADIUS}", width: "{rw}", height: "{rh}", style: "fill:{bg};stroke:var(--pn-border);stroke-width:1" }
+                    rect { class: "ped-card-rect", x: "{CARD_PADDING}", y: "{CARD_PADDING}", rx: "{CARD_BORDER_RADIUS}", ry: "{CARD_BORDER_RADIUS}", width: "{rw}", height: "{rh}", style: "fill:{bg};stroke:{border};stroke-width:1" }
                     // if is_selected || is_focus {
                     //     rect { x: "4", y: "4", rx: "6", ry: "6", width: "{rw+2.0}", height: "{rh+2.0}", style: "fill:none;stroke:var(--orange);stroke-width:2;pointer-events:none" }
                     //}
@@ -3127,6 +3227,7 @@ pub fn PedigreeChart(props: PedigreeChartProps) -> Element {
 
     // ── Event panel collapse (persisted via localStorage) ──
     let mut panel_collapsed = use_signal(|| false);
+    let mut coloring = use_signal(NodeColoring::default);
     let mut panel_init = use_signal(|| false);
     if !panel_init() {
         panel_init.set(true);
@@ -3135,7 +3236,11 @@ pub fn PedigreeChart(props: PedigreeChartProps) -> Element {
                 r#"
                 localStorage.removeItem('oxidgene-ev-panel');
                 const width = window.innerWidth || document.documentElement.clientWidth || 1024;
-                return [localStorage.getItem('{EVENT_PANEL_MANUAL_STORAGE_KEY}') === 'collapsed', width];
+                return [
+                    localStorage.getItem('{EVENT_PANEL_MANUAL_STORAGE_KEY}') === 'collapsed',
+                    width,
+                    localStorage.getItem('{NODE_COLORING_STORAGE_KEY}') || '',
+                ];
                 "#,
             ))
             .await
@@ -3146,6 +3251,9 @@ pub fn PedigreeChart(props: PedigreeChartProps) -> Element {
                     .and_then(|v| v.as_f64())
                     .unwrap_or(VIEWPORT_DEFAULT_W);
                 last_viewport_width.set(width);
+                if let Some(mode) = val.get(2).and_then(|v| v.as_str()) {
+                    coloring.set(NodeColoring::parse(mode));
+                }
                 panel_collapsed.set(manual_collapsed || width <= EVENT_PANEL_AUTO_COLLAPSE_WIDTH);
             }
         });
@@ -3491,6 +3599,7 @@ pub fn PedigreeChart(props: PedigreeChartProps) -> Element {
                 on_add_person: props.on_add_person,
                 on_settings: props.on_settings,
                 on_dictionary: props.on_dictionary,
+                on_tasks: props.on_tasks,
 
                 // Depth selector (hover popover)
                 div {
@@ -3646,6 +3755,34 @@ pub fn PedigreeChart(props: PedigreeChartProps) -> Element {
                         path { d: "M8 21H5a2 2 0 0 1-2-2v-3" }
                     }
                 }
+                button {
+                    class: if coloring() == NodeColoring::Surname { "isb-btn isb-btn-active" } else { "isb-btn" },
+                    title: if coloring() == NodeColoring::Surname { i18n.t("pedigree.color_by_sex") } else { i18n.t("pedigree.color_by_surname") },
+                    onclick: move |_| {
+                        let next = match coloring() {
+                            NodeColoring::Sex => NodeColoring::Surname,
+                            NodeColoring::Surname => NodeColoring::Sex,
+                        };
+                        coloring.set(next);
+                        document::eval(&format!(
+                            "localStorage.setItem('{NODE_COLORING_STORAGE_KEY}', '{}')",
+                            next.as_str(),
+                        ));
+                    },
+                    svg {
+                        width: "16",
+                        height: "16",
+                        fill: "none",
+                        "viewBox": "0 0 24 24",
+                        stroke: "currentColor",
+                        "strokeWidth": "2",
+                        // Palette icon
+                        circle { cx: "12", cy: "12", r: "9" }
+                        circle { cx: "8", cy: "10", r: "1.5" }
+                        circle { cx: "12", cy: "7.5", r: "1.5" }
+                        circle { cx: "16", cy: "10", r: "1.5" }
+                    }
+                }
 
                 div { class: "isb-hr" }
             }
@@ -3762,6 +3899,7 @@ pub fn PedigreeChart(props: PedigreeChartProps) -> Element {
                                             props.on_person_click,
                                             props.on_empty_slot,
                                             true,
+                                            coloring(),
                                         )}
                                     }
                                 }
@@ -3783,6 +3921,7 @@ pub fn PedigreeChart(props: PedigreeChartProps) -> Element {
                                             props.on_person_click,
                                             desc_empty_slot_adapter,
                                             true,
+                                            coloring(),
                                         )}
                                     }
                                 }
@@ -4498,4 +4637,20 @@ mod layout_overlap_tests {
             );
         }
     }
+
+    #[test]
+    fn surname_coloring_matches_same_surnames() {
+        let (line_a, border_a) = NodeColoring::Surname.card_strokes(Sex::Male, "Martin");
+        let (line_b, _) = NodeColoring::Surname.card_strokes(Sex::Female, " MARTIN ");
+        assert_eq!(line_a, line_b);
+        assert_eq!(line_a, border_a);
+        assert_eq!(
+            NodeColoring::Sex.card_strokes(Sex::Female, "Martin").0,
+            "var(--pn-female-line)"
+        );
+        assert_eq!(
+            NodeColoring::parse(NodeColoring::Surname.as_str()),
+            NodeColoring::Surname
+        );
+    }
 }
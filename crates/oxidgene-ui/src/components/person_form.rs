@@ -1,9 +1,10 @@
 //! Modal-based person edit form — single scrollable body with section dividers.
 //!
-//! Sections: Civil Status · Birth · Death · Privacy · Additional Fields ·
-//!           Other Events · Notes.
-//! A single footer Save button persists sex + privacy + birth event + death event
-//! (including qualifier, calendar, witnesses) and closes the modal.
+//! Sections: Civil Status · Birth · Death · Privacy · Living Status ·
+//!           Additional Fields · Other Events · Notes.
+//! A single footer Save button persists sex + privacy + living status + birth
+//! event + death event (including qualifier, calendar, witnesses) and closes
+//! the modal.
 //! Name, event, and note CRUD use inline per-item saves.
 
 use dioxus::prelude::*;
@@ -13,13 +14,19 @@ use crate::api::{
     AddChildBody, AddSpouseBody, ApiClient, CreateEventBody, CreateNoteBody, CreatePersonBody,
     CreatePersonNameBody, UpdateEventBody, UpdatePersonBody, UpdatePersonNameBody,
 };
+use crate::components::place_input::PlaceInput;
+use crate::components::plausibility_warnings::PlausibilityWarnings;
 use crate::i18n::use_i18n;
 use crate::utils::{
     opt_str, parse_calendar, parse_date_qualifier, parse_event_type, parse_name_type,
-    parse_privacy, parse_sex,
+    parse_privacy, parse_sex, save_error_text,
 };
 use oxidgene_core::types::{Event as CoreEvent, Note as CoreNote};
-use oxidgene_core::{Calendar, ChildType, DateQualifier, EventType, SpouseRole};
+use oxidgene_core::validation::plausibility::PlausibilityWarning;
+use oxidgene_core::{
+    Calendar, ChildType, DateQualifier, EVENT_TYPES, EventCategory, EventType, NoteFormat,
+    ParticipantRole, SpouseRole,
+};
 
 // ── Props ────────────────────────────────────────────────────────────────
 
@@ -70,6 +77,9 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
     let mut sex_loaded = use_signal(|| false);
     let mut privacy_val = use_signal(|| "Default".to_string());
     let mut privacy_loaded = use_signal(|| false);
+    // Living-status override; `None` leaves it to the date heuristic.
+    let mut living_val = use_signal(|| None::<bool>);
+    let mut person_version = use_signal(|| None::<i32>);
 
     // ── Name CRUD state ──
     let mut show_name_form = use_signal(move || is_create); // visible by default in create mode
@@ -100,6 +110,7 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
     let mut birth_note = use_signal(String::new);
     let mut birth_calendar = use_signal(|| "Gregorian".to_string());
     let mut birth_event_id = use_signal(|| None::<Uuid>);
+    let mut birth_event_version = use_signal(|| None::<i32>);
     let birth_witnesses_tick = use_signal(|| 0u32);
 
     // ── Death state ──
@@ -110,6 +121,7 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
     let mut death_note = use_signal(String::new);
     let mut death_calendar = use_signal(|| "Gregorian".to_string());
     let mut death_event_id = use_signal(|| None::<Uuid>);
+    let mut death_event_version = use_signal(|| None::<i32>);
     let death_witnesses_tick = use_signal(|| 0u32);
 
     let mut birth_death_loaded = use_signal(|| false);
@@ -122,13 +134,18 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
     let mut event_form_type = use_signal(|| "Baptism".to_string());
     let mut event_form_date = use_signal(String::new);
     let mut event_form_place_id = use_signal(String::new);
+    let mut event_form_place_to_id = use_signal(String::new);
     let mut event_form_note = use_signal(String::new);
     let mut event_form_cause = use_signal(String::new);
     let mut event_form_error = use_signal(|| None::<String>);
+    let mut event_form_warnings = use_signal(Vec::<PlausibilityWarning>::new);
+    // Warnings of the footer save, shown in place of its buttons.
+    let mut save_warnings = use_signal(Vec::<PlausibilityWarning>::new);
 
     // ── Note CRUD state ──
     let mut show_note_form = use_signal(|| false);
     let mut note_form_text = use_signal(String::new);
+    let mut note_form_markdown = use_signal(|| false);
     let mut note_form_error = use_signal(|| None::<String>);
 
     // ── UI state ──
@@ -232,8 +249,14 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
         && let Some(Ok(person)) = &*person_resource.read()
     {
         privacy_val.set(format!("{:?}", person.privacy));
+        living_val.set(person.living);
         privacy_loaded.set(true);
     }
+    if person_version().is_none()
+        && let Some(Ok(person)) = &*person_resource.read()
+    {
+        person_version.set(Some(person.version));
+    }
 
     // ── Populate birth/death (once) ──
     if !birth_death_loaded()
@@ -244,6 +267,7 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
             match ev.event_type {
                 EventType::Birth => {
                     birth_event_id.set(Some(ev.id));
+                    birth_event_version.set(Some(ev.version));
                     birth_date.set(ev.date_value.clone().unwrap_or_default());
                     birth_qualifier.set(format!("{:?}", ev.date_qualifier));
                     birth_date2.set(ev.date_value2.clone().unwrap_or_default());
@@ -253,6 +277,7 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                 }
                 EventType::Death => {
                     death_event_id.set(Some(ev.id));
+                    death_event_version.set(Some(ev.version));
                     death_date.set(ev.date_value.clone().unwrap_or_default());
                     death_qualifier.set(format!("{:?}", ev.date_qualifier));
                     death_date2.set(ev.date_value2.clone().unwrap_or_default());
@@ -318,15 +343,16 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
         }
     };
 
-    let place_options: Vec<(String, String)> = {
-        let data = places_resource.read();
-        match &*data {
+    // Name of a selected place for the place inputs (empty if unknown).
+    let place_label = |place_id: &str| -> String {
+        match &*places_resource.read() {
             Some(Ok(conn)) => conn
                 .edges
                 .iter()
-                .map(|e| (e.node.id.to_string(), e.node.name.clone()))
-                .collect(),
-            _ => vec![],
+                .find(|e| e.node.id.to_string() == place_id)
+                .map(|e| e.node.name.clone())
+                .unwrap_or_default(),
+            _ => String::new(),
         }
     };
 
@@ -398,6 +424,7 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
         let event_type_str = event_form_type();
         let date = event_form_date().trim().to_string();
         let place_str = event_form_place_id();
+        let place_to_str = event_form_place_to_id();
         let note = event_form_note().trim().to_string();
         let cause = event_form_cause().trim().to_string();
         spawn(async move {
@@ -406,6 +433,7 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
             } else {
                 place_str.parse::<Uuid>().ok()
             };
+            let place_id_to = place_to_str.parse::<Uuid>().ok();
             let body = CreateEventBody {
                 event_type: parse_event_type(&event_type_str),
                 date_value: opt_str(&date),
@@ -415,19 +443,22 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                 calendar: Calendar::default(),
                 cause: opt_str(&cause),
                 place_id,
+                place_id_to,
                 person_id: Some(pid),
                 family_id: None,
                 description: opt_str(&note),
             };
             match api.create_event(tid, &body).await {
-                Ok(_) => {
+                Ok(saved) => {
                     show_event_form.set(false);
                     event_form_type.set("Baptism".to_string());
                     event_form_date.set(String::new());
                     event_form_place_id.set(String::new());
+                    event_form_place_to_id.set(String::new());
                     event_form_note.set(String::new());
                     event_form_cause.set(String::new());
                     event_form_error.set(None);
+                    event_form_warnings.set(saved.warnings);
                     on_saved_event.call(());
                     refresh += 1;
                 }
@@ -444,6 +475,11 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
     let on_create_note = move |_| {
         let api = api_create_note.clone();
         let text = note_form_text().trim().to_string();
+        let format = if note_form_markdown() {
+            NoteFormat::Markdown
+        } else {
+            NoteFormat::Plain
+        };
         spawn(async move {
             if text.is_empty() {
                 note_form_error.set(Some(i18n.t("person_form.note_required")));
@@ -451,6 +487,7 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
             }
             let body = CreateNoteBody {
                 text,
+                format,
                 person_id: Some(pid),
                 event_id: None,
                 family_id: None,
@@ -460,6 +497,7 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                 Ok(_) => {
                     show_note_form.set(false);
                     note_form_text.set(String::new());
+                    note_form_markdown.set(false);
                     note_form_error.set(None);
                     on_saved_note.call(());
                     refresh += 1;
@@ -483,6 +521,7 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
             let ctx = create_ctx.clone();
             let sex_str = sex_val();
             let privacy_str = privacy_val();
+            let living = living_val();
             // Name form values (used in create mode)
             let nm_type = name_form_type();
             let nm_given = name_form_given().trim().to_string();
@@ -493,6 +532,9 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
             // Event form values
             let birth_eid = birth_event_id();
             let death_eid = death_event_id();
+            let birth_version = birth_event_version();
+            let death_version = death_event_version();
+            let p_version = person_version();
             let b_date = birth_date().trim().to_string();
             let b_qual = birth_qualifier();
             let b_date2 = birth_date2().trim().to_string();
@@ -508,6 +550,8 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
             spawn(async move {
                 saving.set(true);
                 save_error.set(None);
+                // Plausibility warnings of the birth and death saves.
+                let mut warnings = Vec::new();
 
                 if let Some(context) = ctx {
                     // ── Create mode ──
@@ -562,14 +606,18 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                             calendar: parse_calendar(&b_cal),
                             cause: None,
                             place_id: b_place_id,
+                            place_id_to: None,
                             person_id: Some(new_pid),
                             family_id: None,
                             description: opt_str(&b_note),
                         };
-                        if let Err(e) = api.create_event(tid, &body).await {
-                            save_error.set(Some(format!("{e}")));
-                            saving.set(false);
-                            return;
+                        match api.create_event(tid, &body).await {
+                            Ok(saved) => warnings.extend(saved.warnings),
+                            Err(e) => {
+                                save_error.set(Some(format!("{e}")));
+                                saving.set(false);
+                                return;
+                            }
                         }
                     }
 
@@ -589,14 +637,18 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                             calendar: parse_calendar(&d_cal),
                             cause: None,
                             place_id: d_place_id,
+                            place_id_to: None,
                             person_id: Some(new_pid),
                             family_id: None,
                             description: opt_str(&d_note),
                         };
-                        if let Err(e) = api.create_event(tid, &body).await {
-                            save_error.set(Some(format!("{e}")));
-                            saving.set(false);
-                            return;
+                        match api.create_event(tid, &body).await {
+                            Ok(saved) => warnings.extend(saved.warnings),
+                            Err(e) => {
+                                save_error.set(Some(format!("{e}")));
+                                saving.set(false);
+                                return;
+                            }
                         }
                     }
 
@@ -630,7 +682,7 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                             };
                             let spouse_body = AddSpouseBody {
                                 person_id: new_pid,
-                                role,
+                                role: Some(role),
                                 sort_order: 0,
                             };
                             if let Err(e) = api.add_spouse(tid, fid, &spouse_body).await {
@@ -644,13 +696,14 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                 } else {
                     // ── Edit mode ──
 
-                    // 1. Update person sex + privacy.
+                    // 1. Update person sex + privacy + living status.
                     let person_body = UpdatePersonBody {
                         sex: Some(parse_sex(&sex_str)),
                         privacy: Some(parse_privacy(&privacy_str)),
+                        living: Some(living),
                     };
-                    if let Err(e) = api.update_person(tid, pid, &person_body).await {
-                        save_error.set(Some(format!("{e}")));
+                    if let Err(e) = api.update_person(tid, pid, &person_body, p_version).await {
+                        save_error.set(Some(save_error_text(&i18n, &e)));
                         saving.set(false);
                         return;
                     }
@@ -673,12 +726,16 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                             calendar: Some(b_calendar_enum),
                             cause: None,
                             place_id: Some(b_place_id),
+                            place_id_to: None,
                             description: Some(opt_str(&b_note)),
                         };
-                        if let Err(e) = api.update_event(tid, eid, &body).await {
-                            save_error.set(Some(format!("{e}")));
-                            saving.set(false);
-                            return;
+                        match api.update_event(tid, eid, &body, birth_version).await {
+                            Ok(saved) => warnings.extend(saved.warnings),
+                            Err(e) => {
+                                save_error.set(Some(save_error_text(&i18n, &e)));
+                                saving.set(false);
+                                return;
+                            }
                         }
                     } else if !b_date.is_empty() || b_place_id.is_some() {
                         let body = CreateEventBody {
@@ -690,14 +747,18 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                             calendar: b_calendar_enum,
                             cause: None,
                             place_id: b_place_id,
+                            place_id_to: None,
                             person_id: Some(pid),
                             family_id: None,
                             description: opt_str(&b_note),
                         };
-                        if let Err(e) = api.create_event(tid, &body).await {
-                            save_error.set(Some(format!("{e}")));
-                            saving.set(false);
-                            return;
+                        match api.create_event(tid, &body).await {
+                            Ok(saved) => warnings.extend(saved.warnings),
+                            Err(e) => {
+                                save_error.set(Some(format!("{e}")));
+                                saving.set(false);
+                                return;
+                            }
                         }
                     }
 
@@ -719,12 +780,16 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                             calendar: Some(d_calendar_enum),
                             cause: None,
                             place_id: Some(d_place_id),
+                            place_id_to: None,
                             description: Some(opt_str(&d_note)),
                         };
-                        if let Err(e) = api.update_event(tid, eid, &body).await {
-                            save_error.set(Some(format!("{e}")));
-                            saving.set(false);
-                            return;
+                        match api.update_event(tid, eid, &body, death_version).await {
+                            Ok(saved) => warnings.extend(saved.warnings),
+                            Err(e) => {
+                                save_error.set(Some(save_error_text(&i18n, &e)));
+                                saving.set(false);
+                                return;
+                            }
                         }
                     } else if !d_date.is_empty() || d_place_id.is_some() {
                         let body = CreateEventBody {
@@ -736,21 +801,30 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                             calendar: d_calendar_enum,
                             cause: None,
                             place_id: d_place_id,
+                            place_id_to: None,
                             person_id: Some(pid),
                             family_id: None,
                             description: opt_str(&d_note),
                         };
-                        if let Err(e) = api.create_event(tid, &body).await {
-                            save_error.set(Some(format!("{e}")));
-                            saving.set(false);
-                            return;
+                        match api.create_event(tid, &body).await {
+                            Ok(saved) => warnings.extend(saved.warnings),
+                            Err(e) => {
+                                save_error.set(Some(format!("{e}")));
+                                saving.set(false);
+                                return;
+                            }
                         }
                     }
                 }
 
                 saving.set(false);
                 on_saved.call(());
-                on_close.call(());
+                // Keep the form open until the warnings are read.
+                if warnings.is_empty() {
+                    on_close.call(());
+                } else {
+                    save_warnings.set(warnings);
+                }
             });
         }
     };
@@ -803,6 +877,7 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                                     let gender_opts = [
                                         ("Male",    i18n.t("sex.male")),
                                         ("Female",  i18n.t("sex.female")),
+                                        ("Intersex", i18n.t("sex.intersex")),
                                         ("Unknown", i18n.t("sex.unknown")),
                                     ];
                                     rsx! {
@@ -1057,13 +1132,11 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                         div { class: "form-row",
                             div { class: "form-group",
                                 label { {i18n.t("person_form.place")} }
-                                select {
-                                    value: "{birth_place_id}",
-                                    oninput: move |e: Event<FormData>| { birth_place_id.set(e.value()); has_changes.set(true); },
-                                    option { value: "", {i18n.t("person_form.no_place")} }
-                                    for (pid_opt, pname) in place_options.iter() {
-                                        option { value: "{pid_opt}", "{pname}" }
-                                    }
+                                PlaceInput {
+                                    tree_id: tid,
+                                    value: birth_place_id(),
+                                    value_name: place_label(&birth_place_id()),
+                                    on_change: move |place_id: String| { birth_place_id.set(place_id); has_changes.set(true); },
                                 }
                             }
                             div { class: "form-group",
@@ -1113,13 +1186,11 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                         div { class: "form-row",
                             div { class: "form-group",
                                 label { {i18n.t("person_form.place")} }
-                                select {
-                                    value: "{death_place_id}",
-                                    oninput: move |e: Event<FormData>| { death_place_id.set(e.value()); has_changes.set(true); },
-                                    option { value: "", {i18n.t("person_form.no_place")} }
-                                    for (pid_opt, pname) in place_options.iter() {
-                                        option { value: "{pid_opt}", "{pname}" }
-                                    }
+                                PlaceInput {
+                                    tree_id: tid,
+                                    value: death_place_id(),
+                                    value_name: place_label(&death_place_id()),
+                                    on_change: move |place_id: String| { death_place_id.set(place_id); has_changes.set(true); },
                                 }
                             }
                             div { class: "form-group",
@@ -1163,6 +1234,32 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                         }
                     }
 
+                    // ── Living status ──
+                    if !is_create {
+                        div { class: "person-form-section",
+                            div { class: "pf-section-title", {i18n.t("person_form.living_status")} }
+                            div { class: "pf-gender-group",
+                                {
+                                    let living_opts = [
+                                        (None, i18n.t("living.auto")),
+                                        (Some(true), i18n.t("living.living")),
+                                        (Some(false), i18n.t("living.deceased")),
+                                    ];
+                                    rsx! {
+                                        for (val, label) in living_opts {
+                                            button {
+                                                class: if living_val() == val { "pf-gender-btn active" } else { "pf-gender-btn" },
+                                                r#type: "button",
+                                                onclick: move |_| { living_val.set(val); has_changes.set(true); },
+                                                "{label}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
                     // ── Additional Fields (collapsible) ──
                     div { class: "person-form-section",
                         div { class: "pf-collapsible-header",
@@ -1191,7 +1288,7 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                                     }
                                     div { class: "form-group",
                                         label { {i18n.t("person_form.witnesses")} }
-                                        {render_event_witnesses(&i18n, &api, tid, birth_event_id(), birth_witnesses_tick)}
+                                        {render_event_participants(&i18n, &api, tid, birth_event_id(), birth_witnesses_tick)}
                                     }
                                 }
 
@@ -1207,7 +1304,7 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                                     }
                                     div { class: "form-group",
                                         label { {i18n.t("person_form.witnesses")} }
-                                        {render_event_witnesses(&i18n, &api, tid, death_event_id(), death_witnesses_tick)}
+                                        {render_event_participants(&i18n, &api, tid, death_event_id(), death_witnesses_tick)}
                                     }
                                 }
                             }
@@ -1225,6 +1322,8 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                             }
                         }
 
+                        PlausibilityWarnings { warnings: event_form_warnings }
+
                         if show_event_form() {
                             div { style: "padding: 12px; background: var(--color-bg); border-radius: var(--radius); margin-bottom: 12px;",
                                 if let Some(err) = event_form_error() {
@@ -1252,12 +1351,21 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                                 div { class: "form-row",
                                     div { class: "form-group",
                                         label { {i18n.t("person_form.place")} }
-                                        select {
-                                            value: "{event_form_place_id}",
-                                            oninput: move |e: Event<FormData>| event_form_place_id.set(e.value()),
-                                            option { value: "", {i18n.t("person_form.no_place")} }
-                                            for (pid_opt, pname) in place_options.iter() {
-                                                option { value: "{pid_opt}", "{pname}" }
+                                        PlaceInput {
+                                            tree_id: tid,
+                                            value: event_form_place_id(),
+                                            value_name: place_label(&event_form_place_id()),
+                                            on_change: move |place_id: String| event_form_place_id.set(place_id),
+                                        }
+                                    }
+                                    if parse_event_type(&event_form_type()).has_destination() {
+                                        div { class: "form-group",
+                                            label { {i18n.t("person_form.place_to")} }
+                                            PlaceInput {
+                                                tree_id: tid,
+                                                value: event_form_place_to_id(),
+                                                value_name: place_label(&event_form_place_to_id()),
+                                                on_change: move |place_id: String| event_form_place_to_id.set(place_id),
                                             }
                                         }
                                     }
@@ -1294,7 +1402,10 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                                     let eid = ev.id;
                                     let et = format!("{}", ev.event_type);
                                     let date = ev.date_value.clone().unwrap_or_default();
-                                    let place = ev.place_id.map(&place_name).unwrap_or_default();
+                                    let mut place = ev.place_id.map(&place_name).unwrap_or_default();
+                                    if let Some(to) = ev.place_id_to.map(&place_name) {
+                                        place = format!("{place} \u{2192} {to}");
+                                    }
                                     rsx! {
                                         div { class: "person-form-item",
                                             div { class: "person-form-item-info",
@@ -1350,6 +1461,14 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
                                         value: "{note_form_text}",
                                         oninput: move |e: Event<FormData>| note_form_text.set(e.value()),
                                     }
+                                    label { class: "pf-note-format",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: note_form_markdown(),
+                                            onchange: move |e: Event<FormData>| note_form_markdown.set(e.checked()),
+                                        }
+                                        {i18n.t("person_form.note_markdown")}
+                                    }
                                 }
                                 button {
                                     class: "btn btn-primary btn-sm",
@@ -1439,28 +1558,43 @@ pub fn PersonForm(props: PersonFormProps) -> Element {
     };
 
     // ── Fixed footer ──
+    // Once saved with warnings, the footer shows them and only closes.
+    let on_close_warned = props.on_close;
     let footer = rsx! {
         div { class: "pf-footer",
             if let Some(err) = save_error() {
                 div { class: "error-msg", "{err}" }
             }
+            PlausibilityWarnings {
+                warnings: save_warnings,
+                on_cleared: move |_| on_close_warned.call(()),
+            }
             div { class: "pf-footer-right",
-                if !is_embedded {
+                if !save_warnings.read().is_empty() {
                     button {
-                        class: "btn btn-outline",
+                        class: "btn btn-primary",
                         r#type: "button",
-                        onclick: try_close,
-                        {i18n.t("common.cancel")}
+                        onclick: move |_| on_close_warned.call(()),
+                        {i18n.t("common.close")}
+                    }
+                } else {
+                    if !is_embedded {
+                        button {
+                            class: "btn btn-outline",
+                            r#type: "button",
+                            onclick: try_close,
+                            {i18n.t("common.cancel")}
+                        }
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        r#type: "button",
+                        disabled: saving(),
+                        onclick: on_save,
+                        if saving() { {i18n.t("common.saving")} }
+                        else if is_create { {i18n.t("person_form.btn_create")} }
+                        else { {i18n.t("common.save")} }
                     }
-                }
-                button {
-                    class: "btn btn-primary",
-                    r#type: "button",
-                    disabled: saving(),
-                    onclick: on_save,
-                    if saving() { {i18n.t("common.saving")} }
-                    else if is_create { {i18n.t("person_form.btn_create")} }
-                    else { {i18n.t("common.save")} }
                 }
             }
         }
@@ -1556,49 +1690,24 @@ fn calendar_options(i18n: &crate::i18n::I18n) -> Element {
     }
 }
 
+/// The event types a person's own events can have, grouped by category:
+/// birth and death have their own fields, family events belong to unions.
 fn event_type_options(i18n: &crate::i18n::I18n) -> Element {
     let i18n = *i18n;
     rsx! {
-        optgroup { label: "{i18n.t(\"person_form.sacraments\")}",
-            option { value: "Baptism",        {i18n.t("event.type.baptism")} }
-            option { value: "Confirmation",   {i18n.t("event.type.confirmation")} }
-            option { value: "FirstCommunion", {i18n.t("event.type.first_communion")} }
-            option { value: "BarBatMitzvah",  {i18n.t("event.type.bar_bat_mitzvah")} }
-            option { value: "Burial",         {i18n.t("event.type.burial")} }
-            option { value: "Cremation",      {i18n.t("event.type.cremation")} }
-        }
-        optgroup { label: "{i18n.t(\"person_form.civil\")}",
-            option { value: "Census",          {i18n.t("event.type.census")} }
-            option { value: "Graduation",      {i18n.t("event.type.graduation")} }
-            option { value: "Immigration",     {i18n.t("event.type.immigration")} }
-            option { value: "Emigration",      {i18n.t("event.type.emigration")} }
-            option { value: "Naturalization",  {i18n.t("event.type.naturalization")} }
-            option { value: "Occupation",      {i18n.t("event.type.occupation")} }
-            option { value: "Residence",       {i18n.t("event.type.residence")} }
-            option { value: "Retirement",      {i18n.t("event.type.retirement")} }
-            option { value: "MilitaryService", {i18n.t("event.type.military_service")} }
-        }
-        optgroup { label: "{i18n.t(\"person_form.legal\")}",
-            option { value: "Will",    {i18n.t("event.type.will")} }
-            option { value: "Probate", {i18n.t("event.type.probate")} }
-        }
-        optgroup { label: "{i18n.t(\"person_form.attributes\")}",
-            option { value: "CasteName",            {i18n.t("event.type.caste_name")} }
-            option { value: "PhysicalDescription",  {i18n.t("event.type.physical_description")} }
-            option { value: "Education",            {i18n.t("event.type.education")} }
-            option { value: "NationalId",           {i18n.t("event.type.national_id")} }
-            option { value: "NationalOrigin",       {i18n.t("event.type.national_origin")} }
-            option { value: "ChildrenCount",        {i18n.t("event.type.children_count")} }
-            option { value: "MarriagesCount",       {i18n.t("event.type.marriages_count")} }
-            option { value: "Property",             {i18n.t("event.type.property")} }
-            option { value: "Religion",             {i18n.t("event.type.religion")} }
-            option { value: "SocialSecurityNumber", {i18n.t("event.type.social_security_number")} }
-            option { value: "NobilityTitle",        {i18n.t("event.type.nobility_title")} }
-            option { value: "Fact",                 {i18n.t("event.type.fact")} }
-        }
-        optgroup { label: "{i18n.t(\"person_form.other_events\")}",
-            option { value: "Adoption", {i18n.t("event.type.adoption")} }
-            option { value: "Other",    {i18n.t("event.type.other")} }
+        for category in [EventCategory::Vital, EventCategory::Other] {
+            optgroup {
+                key: "{category}",
+                label: i18n.t(&format!("event.category.{category}")),
+                for info in EVENT_TYPES.iter().filter(|info| {
+                    info.category == category
+                        && !matches!(info.event_type, EventType::Birth | EventType::Death)
+                }) {
+                    option { key: "{info.name}", value: "{info.name}",
+                        {i18n.t(&format!("event.type.{}", info.event_type))}
+                    }
+                }
+            }
         }
     }
 }
@@ -1609,8 +1718,8 @@ fn event_type_options(i18n: &crate::i18n::I18n) -> Element {
 async fn resolve_witness_names(
     api: &ApiClient,
     tree_id: Uuid,
-    witnesses: Vec<oxidgene_core::types::EventWitness>,
-) -> Vec<(oxidgene_core::types::EventWitness, String)> {
+    witnesses: Vec<oxidgene_core::types::EventParticipant>,
+) -> Vec<(oxidgene_core::types::EventParticipant, String)> {
     let mut out = Vec::with_capacity(witnesses.len());
     for w in witnesses {
         let name = match api.list_person_names(tree_id, w.person_id).await {
@@ -1636,11 +1745,30 @@ async fn resolve_witness_names(
     out
 }
 
-/// Witness list + add/remove editor for an event. Each add/remove is
+/// Roles offered when adding a participant, in menu order.
+const PARTICIPANT_ROLES: [ParticipantRole; 5] = [
+    ParticipantRole::Witness,
+    ParticipantRole::Godparent,
+    ParticipantRole::Officiant,
+    ParticipantRole::Informant,
+    ParticipantRole::Primary,
+];
+
+fn participant_role_key(role: ParticipantRole) -> &'static str {
+    match role {
+        ParticipantRole::Primary => "participant_role.primary",
+        ParticipantRole::Witness => "participant_role.witness",
+        ParticipantRole::Godparent => "participant_role.godparent",
+        ParticipantRole::Informant => "participant_role.informant",
+        ParticipantRole::Officiant => "participant_role.officiant",
+    }
+}
+
+/// Participant list + add/remove editor for an event. Each add/remove is
 /// persisted immediately via the API (mirrors the inline per-item saves
 /// used elsewhere in this form), so it's only usable once the event has
 /// been saved (`event_id.is_some()`).
-fn render_event_witnesses(
+fn render_event_participants(
     i18n: &crate::i18n::I18n,
     api: &ApiClient,
     tree_id: Uuid,
@@ -1657,6 +1785,7 @@ fn render_event_witnesses(
     };
 
     let mut adding = use_signal(|| false);
+    let mut role_input = use_signal(ParticipantRole::default);
     let mut relation_input = use_signal(String::new);
 
     let api_list = api.clone();
@@ -1665,7 +1794,7 @@ fn render_event_witnesses(
         let _tick = refresh_tick();
         async move {
             let witnesses = api
-                .list_event_witnesses(tree_id, event_id)
+                .list_event_participants(tree_id, event_id)
                 .await
                 .unwrap_or_default();
             resolve_witness_names(&api, tree_id, witnesses).await
@@ -1678,6 +1807,7 @@ fn render_event_witnesses(
             for (w , name) in entries {
                 div { class: "pf-witness-row",
                     span { class: "pf-witness-name", "{name}" }
+                    span { class: "pf-witness-role", " \u{00B7} ", {i18n.t(participant_role_key(w.role))} }
                     if let Some(rel) = &w.relation {
                         span { class: "pf-witness-relation", " ({rel})" }
                     }
@@ -1690,7 +1820,7 @@ fn render_event_witnesses(
                             move |_| {
                                 let api = api.clone();
                                 spawn(async move {
-                                    let _ = api.remove_event_witness(tree_id, event_id, witness_id).await;
+                                    let _ = api.remove_event_participant(tree_id, event_id, witness_id).await;
                                     refresh_tick.set(refresh_tick() + 1);
                                 });
                             }
@@ -1702,6 +1832,20 @@ fn render_event_witnesses(
         }
         if adding() {
             div { class: "pf-witness-add",
+                select {
+                    value: "{role_input}",
+                    oninput: move |e: Event<FormData>| {
+                        let role = PARTICIPANT_ROLES
+                            .iter()
+                            .copied()
+                            .find(|r| r.to_string() == e.value())
+                            .unwrap_or_default();
+                        role_input.set(role);
+                    },
+                    for role in PARTICIPANT_ROLES {
+                        option { value: "{role}", {i18n.t(participant_role_key(role))} }
+                    }
+                }
                 input {
                     r#type: "text",
                     placeholder: i18n.t("person_form.witness_relation_placeholder"),
@@ -1717,15 +1861,17 @@ fn render_event_witnesses(
                             let api = api.clone();
                             let relation = opt_str(&relation_input());
                             spawn(async move {
-                                let body = crate::api::AddEventWitnessBody {
+                                let body = crate::api::AddEventParticipantBody {
                                     person_id,
+                                    role: role_input(),
                                     relation,
                                     sort_order: 0,
                                 };
-                                let _ = api.add_event_witness(tree_id, event_id, &body).await;
+                                let _ = api.add_event_participant(tree_id, event_id, &body).await;
                                 refresh_tick.set(refresh_tick() + 1);
                             });
                             adding.set(false);
+                            role_input.set(ParticipantRole::default());
                             relation_input.set(String::new());
                         }
                     },
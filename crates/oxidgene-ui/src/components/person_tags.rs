@@ -0,0 +1,169 @@
+//! Research tag chips ("needs sources", "brick wall").
+//!
+//! [`TagChip`] renders one tag in a color derived from its text, so a tag
+//! looks the same on every page. [`PersonTagEditor`] lists a person's tags
+//! with add/remove controls and suggests the tags already used in the tree.
+
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use crate::api::ApiClient;
+use crate::i18n::use_i18n;
+
+/// Number of `tag-color-N` classes defined in the global stylesheet.
+const TAG_COLORS: u32 = 6;
+
+/// CSS class coloring a tag; stable across pages and sessions.
+pub fn tag_color_class(tag: &str) -> String {
+    let hash = tag
+        .to_lowercase()
+        .bytes()
+        .fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(u32::from(b)));
+    format!("tag-color-{}", hash % TAG_COLORS)
+}
+
+/// Props for the [`TagChip`] component.
+#[derive(Props, Clone, PartialEq)]
+pub struct TagChipProps {
+    pub tag: String,
+    /// Shows a remove button when set.
+    #[props(default)]
+    pub on_remove: Option<EventHandler<String>>,
+}
+
+/// A single colored tag.
+#[component]
+pub fn TagChip(props: TagChipProps) -> Element {
+    let i18n = use_i18n();
+    let class = format!("tag-chip {}", tag_color_class(&props.tag));
+    let tag = props.tag.clone();
+    rsx! {
+        span { class: "{class}",
+            "{props.tag}"
+            if let Some(on_remove) = props.on_remove {
+                button {
+                    class: "tag-chip-remove",
+                    title: i18n.t("tags.remove"),
+                    onclick: move |_| on_remove.call(tag.clone()),
+                    "\u{00D7}"
+                }
+            }
+        }
+    }
+}
+
+/// Props for the [`PersonTagEditor`] component.
+#[derive(Props, Clone, PartialEq)]
+pub struct PersonTagEditorProps {
+    pub tree_id: Uuid,
+    pub person_id: Uuid,
+}
+
+/// A person's tags with an input to add more and a remove button on each.
+#[component]
+pub fn PersonTagEditor(props: PersonTagEditorProps) -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+    let tree_id = props.tree_id;
+    let person_id = props.person_id;
+
+    let mut refresh = use_signal(|| 0u32);
+    let mut draft = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    let api_tags = api.clone();
+    let tags_resource = use_resource(move || {
+        let api = api_tags.clone();
+        let _tick = refresh();
+        async move { api.list_person_tags(tree_id, person_id).await }
+    });
+    let api_known = api.clone();
+    let known_resource = use_resource(move || {
+        let api = api_known.clone();
+        let _tick = refresh();
+        async move { api.list_tags(tree_id).await }
+    });
+
+    let tags: Vec<String> = match &*tags_resource.read() {
+        Some(Ok(tags)) => tags.iter().map(|t| t.tag.clone()).collect(),
+        _ => vec![],
+    };
+    let suggestions: Vec<String> = match &*known_resource.read() {
+        Some(Ok(known)) => known
+            .iter()
+            .map(|t| t.tag.clone())
+            .filter(|t| !tags.contains(t))
+            .collect(),
+        _ => vec![],
+    };
+    let datalist_id = format!("tag-suggestions-{person_id}");
+
+    let api_add = api.clone();
+    let add_tag = move || {
+        let tag = draft().trim().to_string();
+        if tag.is_empty() {
+            return;
+        }
+        let api = api_add.clone();
+        spawn(async move {
+            match api.add_person_tag(tree_id, person_id, &tag).await {
+                Ok(_) => {
+                    draft.set(String::new());
+                    error.set(None);
+                    refresh += 1;
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+    let add_tag_click = add_tag.clone();
+
+    let api_remove = api.clone();
+    let remove_tag = move |tag: String| {
+        let api = api_remove.clone();
+        spawn(async move {
+            match api.remove_person_tag(tree_id, person_id, &tag).await {
+                Ok(()) => {
+                    error.set(None);
+                    refresh += 1;
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    rsx! {
+        div { class: "tag-editor",
+            for tag in tags {
+                TagChip { key: "{tag}", tag: tag.clone(), on_remove: remove_tag.clone() }
+            }
+            input {
+                class: "tag-editor-input",
+                r#type: "text",
+                list: "{datalist_id}",
+                maxlength: "64",
+                placeholder: i18n.t("tags.add_placeholder"),
+                value: "{draft}",
+                oninput: move |e: Event<FormData>| draft.set(e.value()),
+                onkeydown: move |e: Event<KeyboardData>| {
+                    if e.key() == Key::Enter {
+                        add_tag();
+                    }
+                },
+            }
+            button {
+                class: "btn btn-outline btn-sm",
+                onclick: move |_| add_tag_click(),
+                {i18n.t("tags.add")}
+            }
+            datalist { id: "{datalist_id}",
+                for tag in suggestions {
+                    option { key: "{tag}", value: "{tag}" }
+                }
+            }
+            if let Some(err) = error() {
+                span { class: "error-msg tag-editor-error", "{err}" }
+            }
+        }
+    }
+}
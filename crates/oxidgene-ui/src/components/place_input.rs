@@ -0,0 +1,180 @@
+//! Place input with autocomplete for event forms (ui-shared-components.md
+//! §5).
+//!
+//! Typing queries `/places/autocomplete`, which lists the tree's places
+//! whose name starts with the text, most used first — so "Par" offers
+//! Paris before a rarely used Parthenay. Clearing the text clears the
+//! place; leaving the input without picking keeps the previous one. When no
+//! suggestion has the typed name, a last option creates that place and
+//! picks it, without leaving the form.
+
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use crate::api::{ApiClient, CreatePlaceBody, PlaceSuggestion};
+use crate::i18n::use_i18n;
+
+/// Suggestions shown at once.
+const SUGGESTION_LIMIT: u64 = 10;
+
+/// Characters typed before suggestions appear.
+const MIN_QUERY_CHARS: usize = 3;
+
+/// Props for [`PlaceInput`].
+#[derive(Props, Clone, PartialEq)]
+pub struct PlaceInputProps {
+    pub tree_id: Uuid,
+    /// Id of the selected place, or empty for none.
+    pub value: String,
+    /// Name of the selected place, shown while the user is not typing.
+    #[props(default)]
+    pub value_name: String,
+    /// Called with the picked place id, or an empty string when cleared.
+    pub on_change: EventHandler<String>,
+}
+
+/// A text input suggesting places as the user types.
+#[component]
+pub fn PlaceInput(props: PlaceInputProps) -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+    let tree_id = props.tree_id;
+
+    // Text being typed; `None` while not editing.
+    let mut query = use_signal(|| None::<String>);
+    // Name of the last picked suggestion, in case the parent cannot name it.
+    let mut picked_name = use_signal(|| None::<(String, String)>);
+    let mut creating = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    // Debounce: only query once typing pauses.
+    let mut debounced_query = use_signal(String::new);
+    let _debounce_task = use_resource(move || {
+        let raw = query().unwrap_or_default();
+        async move {
+            #[cfg(target_arch = "wasm32")]
+            gloo_timers::future::TimeoutFuture::new(300).await;
+            #[cfg(not(target_arch = "wasm32"))]
+            tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+            debounced_query.set(raw);
+        }
+    });
+
+    let api_suggest = api.clone();
+    let suggestions_resource = use_resource(move || {
+        let api = api_suggest.clone();
+        let q = debounced_query();
+        async move {
+            match q {
+                q if q.trim().chars().count() >= MIN_QUERY_CHARS => {
+                    api.autocomplete_places(tree_id, q.trim(), SUGGESTION_LIMIT)
+                        .await
+                }
+                _ => Ok(Vec::new()),
+            }
+        }
+    });
+    let suggestions: Vec<PlaceSuggestion> = match &*suggestions_resource.read() {
+        Some(Ok(list)) => list.clone(),
+        _ => vec![],
+    };
+
+    let shown = match query() {
+        Some(q) => q,
+        None if props.value.is_empty() => String::new(),
+        None => match picked_name() {
+            Some((id, name)) if id == props.value => name,
+            _ => props.value_name.clone(),
+        },
+    };
+    let on_change = props.on_change;
+
+    // Offer to create the typed place unless a suggestion already has its
+    // name.
+    let new_name = query()
+        .map(|q| q.trim().to_string())
+        .filter(|q| q.chars().count() >= MIN_QUERY_CHARS)
+        .filter(|q| !suggestions.iter().any(|p| p.name.eq_ignore_ascii_case(q)));
+    let mut create_place = move |name: String| {
+        let api = api.clone();
+        creating.set(true);
+        spawn(async move {
+            let body = CreatePlaceBody {
+                name,
+                latitude: None,
+                longitude: None,
+            };
+            match api.create_place(tree_id, &body).await {
+                Ok(place) => {
+                    let id = place.id.to_string();
+                    picked_name.set(Some((id.clone(), place.name)));
+                    query.set(None);
+                    error.set(None);
+                    on_change.call(id);
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            creating.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "place-picker",
+            input {
+                r#type: "text",
+                value: "{shown}",
+                placeholder: i18n.t("person_form.no_place"),
+                oninput: move |e: Event<FormData>| {
+                    let text = e.value();
+                    if text.trim().is_empty() {
+                        on_change.call(String::new());
+                    }
+                    query.set(Some(text));
+                },
+                onblur: move |_| {
+                    if !creating() {
+                        query.set(None);
+                    }
+                },
+            }
+            if query().is_some() && (!suggestions.is_empty() || new_name.is_some()) {
+                ul { class: "place-picker-list",
+                    for place in suggestions {
+                        li {
+                            key: "{place.id}",
+                            class: "place-picker-option",
+                            // Mouse-down fires before the input's blur
+                            // closes the list.
+                            onmousedown: move |e: Event<MouseData>| {
+                                e.prevent_default();
+                                let id = place.id.to_string();
+                                picked_name.set(Some((id.clone(), place.name.clone())));
+                                query.set(None);
+                                on_change.call(id);
+                            },
+                            span { "{place.name}" }
+                            if place.event_count > 0 {
+                                span { class: "place-picker-count", "{place.event_count}" }
+                            }
+                        }
+                    }
+                    if let Some(name) = new_name {
+                        li {
+                            class: "place-picker-option place-picker-create",
+                            onmousedown: move |e: Event<MouseData>| {
+                                e.prevent_default();
+                                if !creating() {
+                                    create_place(name.clone());
+                                }
+                            },
+                            {i18n.t_args("place_input.create", &[("name", &name)])}
+                        }
+                    }
+                }
+            }
+            if let Some(err) = error() {
+                div { class: "error-msg", "{err}" }
+            }
+        }
+    }
+}
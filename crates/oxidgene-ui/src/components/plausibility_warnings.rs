@@ -0,0 +1,66 @@
+//! Date plausibility warnings returned when an event is saved ("death
+//! before birth"), shown until dismissed. They never prevent the save.
+
+use dioxus::prelude::*;
+use oxidgene_core::validation::plausibility::{PlausibilityKind, PlausibilityWarning};
+
+use crate::i18n::use_i18n;
+
+/// i18n key of a warning's message.
+fn message_key(kind: PlausibilityKind) -> &'static str {
+    match kind {
+        PlausibilityKind::DeathBeforeBirth => "plausibility.death_before_birth",
+        PlausibilityKind::MarriedTooYoung => "plausibility.married_too_young",
+        PlausibilityKind::BornAfterMotherDeath => "plausibility.born_after_mother_death",
+        PlausibilityKind::MotherTooYoung => "plausibility.mother_too_young",
+    }
+}
+
+/// Props for the [`PlausibilityWarnings`] component.
+#[derive(Props, Clone, PartialEq)]
+pub struct PlausibilityWarningsProps {
+    /// Pending warnings; dismissing one removes it.
+    pub warnings: Signal<Vec<PlausibilityWarning>>,
+    /// Called once the last warning is dismissed.
+    #[props(default)]
+    pub on_cleared: EventHandler<()>,
+}
+
+/// The pending warnings, each with a dismiss button. Renders nothing when
+/// there are none.
+#[component]
+pub fn PlausibilityWarnings(props: PlausibilityWarningsProps) -> Element {
+    let i18n = use_i18n();
+    let mut warnings = props.warnings;
+    let on_cleared = props.on_cleared;
+
+    if warnings.read().is_empty() {
+        return rsx! {};
+    }
+    let items: Vec<(usize, PlausibilityKind)> =
+        warnings.read().iter().map(|w| w.kind).enumerate().collect();
+
+    rsx! {
+        div { class: "plausibility-warnings",
+            div { class: "plausibility-title", {i18n.t("plausibility.title")} }
+            ul {
+                for (idx, kind) in items {
+                    li { key: "{idx}",
+                        span { {i18n.t(message_key(kind))} }
+                        button {
+                            class: "plausibility-dismiss",
+                            title: i18n.t("plausibility.dismiss"),
+                            onclick: move |_| {
+                                warnings.write().remove(idx);
+                                if warnings.read().is_empty() {
+                                    on_cleared.call(());
+                                }
+                            },
+                            "\u{00D7}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! Quick entry: one short form creating a person with their name, birth
+//! and death in a single request, for transcribing records at speed.
+//!
+//! After each save the form clears for the next person (the surname stays,
+//! as relatives often share it) and links to the person just created.
+
+use dioxus::prelude::*;
+use oxidgene_core::Sex;
+use uuid::Uuid;
+
+use crate::api::{ApiClient, QuickPerson, QuickPersonBody};
+use crate::i18n::use_i18n;
+use crate::router::Route;
+
+/// Props for the [`QuickEntry`] component.
+#[derive(Props, Clone, PartialEq)]
+pub struct QuickEntryProps {
+    pub tree_id: Uuid,
+    pub on_close: EventHandler<()>,
+    /// Called with each person created.
+    pub on_created: EventHandler<QuickPerson>,
+    /// Switch to the full person form.
+    pub on_full_form: EventHandler<()>,
+}
+
+/// A trimmed field, `None` when blank.
+fn filled(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Modal quick-entry form.
+#[component]
+pub fn QuickEntry(props: QuickEntryProps) -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+    let tree_id = props.tree_id;
+    let on_created = props.on_created;
+
+    let mut sex = use_signal(|| Sex::Unknown);
+    let mut given_names = use_signal(String::new);
+    let mut surname = use_signal(String::new);
+    let mut birth_date = use_signal(String::new);
+    let mut birth_place = use_signal(String::new);
+    let mut death_date = use_signal(String::new);
+    let mut death_place = use_signal(String::new);
+    let mut saving = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let mut last_created = use_signal(|| None::<QuickPerson>);
+
+    let save = move || {
+        if saving() {
+            return;
+        }
+        let body = QuickPersonBody {
+            sex: Some(sex()),
+            given_names: filled(given_names()),
+            surname: filled(surname()),
+            birth_date: filled(birth_date()),
+            birth_place: filled(birth_place()),
+            death_date: filled(death_date()),
+            death_place: filled(death_place()),
+        };
+        if body.given_names.is_none() && body.surname.is_none() {
+            error.set(Some(i18n.t("quick_entry.name_required")));
+            return;
+        }
+        let api = api.clone();
+        saving.set(true);
+        spawn(async move {
+            match api.quick_create_person(tree_id, &body).await {
+                Ok(created) => {
+                    error.set(None);
+                    sex.set(Sex::Unknown);
+                    given_names.set(String::new());
+                    birth_date.set(String::new());
+                    birth_place.set(String::new());
+                    death_date.set(String::new());
+                    death_place.set(String::new());
+                    on_created.call(created.clone());
+                    last_created.set(Some(created));
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            saving.set(false);
+        });
+    };
+    let mut save_on_enter = save.clone();
+    let mut save_click = save.clone();
+
+    let sex_value = match sex() {
+        Sex::Male => "male",
+        Sex::Female => "female",
+        Sex::Intersex => "intersex",
+        Sex::Unknown => "unknown",
+    };
+
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            onclick: move |_| props.on_close.call(()),
+            div {
+                class: "modal-card quick-entry",
+                onclick: move |e: Event<MouseData>| e.stop_propagation(),
+                onkeydown: move |e: Event<KeyboardData>| match e.key() {
+                    Key::Enter => save_on_enter(),
+                    Key::Escape => props.on_close.call(()),
+                    _ => {}
+                },
+                h3 { {i18n.t("quick_entry.title")} }
+
+                if let Some(created) = last_created() {
+                    div { class: "success-msg",
+                        {i18n.t("quick_entry.created")}
+                        " "
+                        Link {
+                            to: Route::PersonDetail {
+                                tree_id: tree_id.to_string(),
+                                person_id: created.person.id.to_string(),
+                            },
+                            onclick: move |_| props.on_close.call(()),
+                            {created.summary.display_name.clone().unwrap_or_default()}
+                        }
+                        match (created.summary.birth_year, created.summary.death_year) {
+                            (None, None) => rsx! {},
+                            (birth, death) => rsx! {
+                                span { class: "text-muted",
+                                    " ({birth.map(|y| y.to_string()).unwrap_or_default()}\u{2013}{death.map(|y| y.to_string()).unwrap_or_default()})"
+                                }
+                            },
+                        }
+                    }
+                }
+
+                div { class: "form-row",
+                    div { class: "form-group",
+                        label { {i18n.t("person_form.given_names")} }
+                        input {
+                            r#type: "text",
+                            autofocus: true,
+                            value: "{given_names}",
+                            oninput: move |e: Event<FormData>| given_names.set(e.value()),
+                        }
+                    }
+                    div { class: "form-group",
+                        label { {i18n.t("person_form.surname")} }
+                        input {
+                            r#type: "text",
+                            value: "{surname}",
+                            oninput: move |e: Event<FormData>| surname.set(e.value()),
+                        }
+                    }
+                }
+                div { class: "form-group",
+                    label { {i18n.t("person_form.sex")} }
+                    select {
+                        value: "{sex_value}",
+                        oninput: move |e: Event<FormData>| {
+                            sex.set(match e.value().as_str() {
+                                "male" => Sex::Male,
+                                "female" => Sex::Female,
+                                "intersex" => Sex::Intersex,
+                                _ => Sex::Unknown,
+                            })
+                        },
+                        option { value: "unknown", {i18n.t("sex.unknown")} }
+                        option { value: "male", {i18n.t("sex.male")} }
+                        option { value: "female", {i18n.t("sex.female")} }
+                        option { value: "intersex", {i18n.t("sex.intersex")} }
+                    }
+                }
+                div { class: "form-row",
+                    div { class: "form-group",
+                        label { {i18n.t("quick_entry.birth_date")} }
+                        input {
+                            r#type: "text",
+                            placeholder: "{i18n.t(\"union_form.date_placeholder\")}",
+                            value: "{birth_date}",
+                            oninput: move |e: Event<FormData>| birth_date.set(e.value()),
+                        }
+                    }
+                    div { class: "form-group",
+                        label { {i18n.t("quick_entry.birth_place")} }
+                        input {
+                            r#type: "text",
+                            value: "{birth_place}",
+                            oninput: move |e: Event<FormData>| birth_place.set(e.value()),
+                        }
+                    }
+                }
+                div { class: "form-row",
+                    div { class: "form-group",
+                        label { {i18n.t("quick_entry.death_date")} }
+                        input {
+                            r#type: "text",
+                            placeholder: "{i18n.t(\"union_form.date_placeholder\")}",
+                            value: "{death_date}",
+                            oninput: move |e: Event<FormData>| death_date.set(e.value()),
+                        }
+                    }
+                    div { class: "form-group",
+                        label { {i18n.t("quick_entry.death_place")} }
+                        input {
+                            r#type: "text",
+                            value: "{death_place}",
+                            oninput: move |e: Event<FormData>| death_place.set(e.value()),
+                        }
+                    }
+                }
+                p { class: "text-muted quick-entry-hint", {i18n.t("quick_entry.hint")} }
+
+                if let Some(err) = error() {
+                    div { class: "error-msg", "{err}" }
+                }
+                div { class: "modal-actions",
+                    button {
+                        class: "btn btn-outline quick-entry-full",
+                        onclick: move |_| props.on_full_form.call(()),
+                        {i18n.t("quick_entry.full_form")}
+                    }
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| props.on_close.call(()),
+                        {i18n.t("common.close")}
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        disabled: saving(),
+                        onclick: move |_| save_click(),
+                        if saving() {
+                            {i18n.t("common.saving")}
+                        } else {
+                            {i18n.t("quick_entry.save_next")}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Topbar "recently edited" menu: the tree's most recently created or
+//! updated entities, linking back to the page that shows each one.
+
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use crate::api::{ActivityEntry, ApiClient};
+use crate::i18n::use_i18n;
+use crate::router::Route;
+
+/// Number of entries listed in the menu.
+const RECENT_LIMIT: u64 = 15;
+
+#[component]
+pub fn RecentActivity(tree_id: String) -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+    let mut open = use_signal(|| false);
+
+    // Fetched when the menu opens, so every opening shows fresh activity.
+    let activity = use_resource({
+        let tree_id = tree_id.clone();
+        move || {
+            let api = api.clone();
+            let tree_id = tree_id.clone();
+            let is_open = open();
+            async move {
+                let tid = Uuid::parse_str(&tree_id).ok()?;
+                if !is_open {
+                    return None;
+                }
+                Some(api.tree_activity(tid, RECENT_LIMIT).await)
+            }
+        }
+    });
+
+    rsx! {
+        div { class: "td-recent",
+            button {
+                class: "td-search-btn",
+                title: i18n.t("recent.title"),
+                onclick: move |_| open.set(!open()),
+                // Clock icon
+                svg {
+                    width: "14",
+                    height: "14",
+                    fill: "none",
+                    "viewBox": "0 0 24 24",
+                    stroke: "currentColor",
+                    "strokeWidth": "2.5",
+                    circle { cx: "12", cy: "12", r: "9" }
+                    polyline { points: "12 7 12 12 15 14" }
+                }
+            }
+            if open() {
+                div { class: "td-recent-menu",
+                    div { class: "td-recent-title", {i18n.t("recent.title")} }
+                    {
+                        let guard = activity.read();
+                        match &*guard {
+                            Some(Some(Ok(entries))) if entries.is_empty() => rsx! {
+                                div { class: "td-recent-empty", {i18n.t("recent.empty")} }
+                            },
+                            Some(Some(Ok(entries))) => rsx! {
+                                for entry in entries.clone() {
+                                    RecentActivityItem {
+                                        key: "{entry.kind}-{entry.id}",
+                                        tree_id: tree_id.clone(),
+                                        entry: entry,
+                                        on_navigate: move |_| open.set(false),
+                                    }
+                                }
+                            },
+                            Some(Some(Err(e))) => rsx! {
+                                div { class: "error-msg", "{e}" }
+                            },
+                            _ => rsx! {
+                                div { class: "td-recent-empty", {i18n.t("common.loading")} }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn RecentActivityItem(
+    tree_id: String,
+    entry: ActivityEntry,
+    on_navigate: EventHandler<()>,
+) -> Element {
+    let i18n = use_i18n();
+
+    let what = match entry.event_type {
+        Some(event_type) => i18n.t(&format!("event.type.{event_type}")),
+        None => i18n.t(&format!("recent.kind.{}", entry.kind)),
+    };
+    let label = if entry.label.is_empty() {
+        i18n.t("recent.unnamed")
+    } else {
+        entry.label.clone()
+    };
+    let when = i18n.t_args(
+        &format!("recent.{}", entry.action),
+        &[(
+            "date",
+            &entry
+                .timestamp
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+        )],
+    );
+    let target = match (entry.person_id, entry.kind.as_str()) {
+        (Some(person_id), _) => Some(Route::PersonDetail {
+            tree_id,
+            person_id: person_id.to_string(),
+        }),
+        (None, "source") => Some(Route::SourceDetail {
+            tree_id,
+            source_id: entry.id.to_string(),
+        }),
+        (None, "place") => Some(Route::Dictionary { tree_id }),
+        _ => None,
+    };
+
+    let body = rsx! {
+        span { class: "td-recent-label", "{label}" }
+        span { class: "td-recent-meta", "{what} \u{00B7} {when}" }
+    };
+    match target {
+        Some(to) => rsx! {
+            Link {
+                to: to,
+                class: "td-recent-item",
+                onclick: move |_| on_navigate.call(()),
+                {body}
+            }
+        },
+        None => rsx! {
+            div { class: "td-recent-item", {body} }
+        },
+    }
+}
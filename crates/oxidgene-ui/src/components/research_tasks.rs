@@ -0,0 +1,238 @@
+//! Research to-do tasks ("find the baptism record").
+//!
+//! [`TaskItem`] renders one task with a done checkbox and a delete button;
+//! [`PersonTasksPanel`] lists a person's tasks with a form to add more. The
+//! tree-wide list lives in the tasks page.
+
+use chrono::NaiveDate;
+use dioxus::prelude::*;
+use oxidgene_core::TaskStatus;
+use oxidgene_core::types::ResearchTask;
+use uuid::Uuid;
+
+use crate::api::{ApiClient, CreateTaskBody, UpdateTaskBody};
+use crate::i18n::use_i18n;
+
+/// Whether an open task is past its due date.
+pub fn is_overdue(task: &ResearchTask) -> bool {
+    task.status == TaskStatus::Open
+        && task
+            .due_date
+            .is_some_and(|due| due < chrono::Local::now().date_naive())
+}
+
+/// Props for the [`TaskItem`] component.
+#[derive(Props, Clone, PartialEq)]
+pub struct TaskItemProps {
+    pub task: ResearchTask,
+    /// Called after the task was toggled or deleted.
+    pub on_change: EventHandler<()>,
+    /// Extra content shown after the title, e.g. a link to the owner.
+    #[props(default)]
+    pub children: Element,
+}
+
+/// One task with a done checkbox, its due date and a delete button.
+#[component]
+pub fn TaskItem(props: TaskItemProps) -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+    let mut error = use_signal(|| None::<String>);
+
+    let tree_id = props.task.tree_id;
+    let task_id = props.task.id;
+    let done = props.task.status == TaskStatus::Done;
+    let on_change = props.on_change;
+
+    let api_toggle = api.clone();
+    let toggle = move |_: Event<FormData>| {
+        let api = api_toggle.clone();
+        let body = UpdateTaskBody {
+            status: Some(if done {
+                TaskStatus::Open
+            } else {
+                TaskStatus::Done
+            }),
+            ..Default::default()
+        };
+        spawn(async move {
+            match api.update_task(tree_id, task_id, &body).await {
+                Ok(_) => {
+                    error.set(None);
+                    on_change.call(());
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    let api_delete = api.clone();
+    let delete = move |_: Event<MouseData>| {
+        let api = api_delete.clone();
+        spawn(async move {
+            match api.delete_task(tree_id, task_id).await {
+                Ok(()) => {
+                    error.set(None);
+                    on_change.call(());
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    let mut class = String::from("task-item");
+    if done {
+        class.push_str(" task-done");
+    }
+    if is_overdue(&props.task) {
+        class.push_str(" task-overdue");
+    }
+
+    rsx! {
+        li { class: "{class}",
+            input {
+                r#type: "checkbox",
+                checked: done,
+                title: i18n.t("tasks.toggle_done"),
+                onchange: toggle,
+            }
+            div { class: "task-body",
+                span { class: "task-title", "{props.task.title}" }
+                {props.children}
+                if let Some(description) = &props.task.description {
+                    div { class: "task-description", "{description}" }
+                }
+                if let Some(due) = props.task.due_date {
+                    span { class: "task-due",
+                        {i18n.t_args("tasks.due", &[("date", &due.to_string())])}
+                    }
+                }
+                if let Some(err) = error() {
+                    div { class: "error-msg", "{err}" }
+                }
+            }
+            button {
+                class: "task-delete",
+                title: i18n.t("common.delete"),
+                onclick: delete,
+                "\u{00D7}"
+            }
+        }
+    }
+}
+
+/// Props for the [`PersonTasksPanel`] component.
+#[derive(Props, Clone, PartialEq)]
+pub struct PersonTasksPanelProps {
+    pub tree_id: Uuid,
+    pub person_id: Uuid,
+}
+
+/// A person's research tasks, open ones first, with a form to add a task.
+#[component]
+pub fn PersonTasksPanel(props: PersonTasksPanelProps) -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+    let tree_id = props.tree_id;
+    let person_id = props.person_id;
+
+    let mut refresh = use_signal(|| 0u32);
+    let mut title = use_signal(String::new);
+    let mut due_date = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    let api_list = api.clone();
+    let tasks_resource = use_resource(move || {
+        let api = api_list.clone();
+        let _tick = refresh();
+        async move { api.list_tasks(tree_id, None, Some(person_id)).await }
+    });
+
+    let api_add = api.clone();
+    let add_task = move || {
+        let text = title().trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        let body = CreateTaskBody {
+            person_id: Some(person_id),
+            family_id: None,
+            title: text,
+            description: None,
+            due_date: NaiveDate::parse_from_str(&due_date(), "%Y-%m-%d").ok(),
+        };
+        let api = api_add.clone();
+        spawn(async move {
+            match api.create_task(tree_id, &body).await {
+                Ok(_) => {
+                    title.set(String::new());
+                    due_date.set(String::new());
+                    error.set(None);
+                    refresh += 1;
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+    let add_task_click = add_task.clone();
+
+    let mut tasks: Vec<ResearchTask> = match &*tasks_resource.read() {
+        Some(Ok(tasks)) => tasks.clone(),
+        _ => vec![],
+    };
+    // Stable sort keeps the server's due-date order within each group.
+    tasks.sort_by_key(|t| t.status == TaskStatus::Done);
+
+    rsx! {
+        div { class: "card", style: "margin-bottom: 24px;",
+            h2 { style: "font-size: 1.1rem; margin-bottom: 12px;", {i18n.t("tasks.person_section")} }
+
+            if let Some(Err(e)) = &*tasks_resource.read() {
+                div { class: "error-msg", {i18n.t_args("tasks.load_error", &[("error", &e.to_string())])} }
+            }
+            if tasks.is_empty() {
+                p { class: "text-muted", {i18n.t("tasks.none")} }
+            } else {
+                ul { class: "task-list",
+                    for task in tasks {
+                        TaskItem {
+                            key: "{task.id}",
+                            task: task.clone(),
+                            on_change: move |_| refresh += 1,
+                        }
+                    }
+                }
+            }
+
+            div { class: "task-add",
+                input {
+                    r#type: "text",
+                    maxlength: "200",
+                    placeholder: i18n.t("tasks.add_placeholder"),
+                    value: "{title}",
+                    oninput: move |e: Event<FormData>| title.set(e.value()),
+                    onkeydown: move |e: Event<KeyboardData>| {
+                        if e.key() == Key::Enter {
+                            add_task();
+                        }
+                    },
+                }
+                input {
+                    r#type: "date",
+                    title: i18n.t("tasks.due_date"),
+                    value: "{due_date}",
+                    oninput: move |e: Event<FormData>| due_date.set(e.value()),
+                }
+                button {
+                    class: "btn btn-outline",
+                    disabled: title().trim().is_empty(),
+                    onclick: move |_| add_task_click(),
+                    {i18n.t("tasks.add")}
+                }
+            }
+            if let Some(err) = error() {
+                div { class: "error-msg", "{err}" }
+            }
+        }
+    }
+}
@@ -5,7 +5,9 @@
 //!
 //! Performance: uses the server-side `/persons/search?q=...` endpoint, backed
 //! by the `person_search_fts` DB table (SQLite FTS5 / PostgreSQL) with
-//! accent-folded matching, instead of downloading the full tree.
+//! accent-folded matching, instead of downloading the full tree. When the
+//! prefix search finds nothing, falls back to the typo-tolerant
+//! `/persons/search?name=...` so "Catherine" still finds "Katherine".
 
 use dioxus::prelude::*;
 use oxidgene_cache::types::SearchEntry;
@@ -13,7 +15,7 @@ use oxidgene_core::Sex;
 use uuid::Uuid;
 
 use crate::api::ApiClient;
-use crate::i18n::use_i18n;
+use crate::i18n::{I18n, use_i18n};
 
 /// Props for [`SearchPerson`].
 #[derive(Props, Clone, PartialEq)]
@@ -67,16 +69,25 @@ pub fn SearchPerson(props: SearchPersonProps) -> Element {
         async move {
             if q.is_empty() {
                 // Empty query: return first 20 persons (no filter).
-                return api.search_persons(tree_id, "", 20, 0).await;
+                return api
+                    .search_persons(tree_id, "", 20, 0)
+                    .await
+                    .map(|sr| sr.entries);
             }
-            api.search_persons(tree_id, &q, 20, 0).await
+            let result = api.search_persons(tree_id, &q, 20, 0).await?;
+            if !result.entries.is_empty() {
+                return Ok(result.entries);
+            }
+            // No prefix match: retry tolerating typos, closest first.
+            let fuzzy = api.fuzzy_search_persons(tree_id, &q, 2, 20).await?;
+            Ok(fuzzy.entries.into_iter().map(|hit| hit.entry).collect())
         }
     });
 
     let results: Vec<SearchEntry> = {
         let data = search_resource.read();
         match &*data {
-            Some(Ok(sr)) => sr.entries.clone(),
+            Some(Ok(entries)) => entries.clone(),
             _ => vec![],
         }
     };
@@ -108,7 +119,7 @@ pub fn SearchPerson(props: SearchPersonProps) -> Element {
             } else {
                 div { class: "search-person-results",
                     for entry in results.iter() {
-                        {render_search_entry(entry, props.on_select)}
+                        {render_search_entry(entry, props.on_select, i18n)}
                     }
                 }
             }
@@ -117,12 +128,12 @@ pub fn SearchPerson(props: SearchPersonProps) -> Element {
 }
 
 /// Render a single search result row.
-fn render_search_entry(entry: &SearchEntry, on_select: EventHandler<Uuid>) -> Element {
+fn render_search_entry(entry: &SearchEntry, on_select: EventHandler<Uuid>, i18n: I18n) -> Element {
     let rid = entry.person_id;
     let sex_class = match entry.sex {
         Sex::Male => "male",
         Sex::Female => "female",
-        Sex::Unknown => "",
+        Sex::Intersex | Sex::Unknown => "",
     };
 
     // Parse display_name into given_names + surname for initials.
@@ -159,6 +170,11 @@ fn render_search_entry(entry: &SearchEntry, on_select: EventHandler<Uuid>) -> El
                         span { class: "sp-given", "?" }
                     }
                 }
+                if let Some(ref alias) = entry.matched_name {
+                    div { class: "sp-result-alias",
+                        {i18n.t_args("search.also_known_as", &[("name", alias)])}
+                    }
+                }
                 div { class: "sp-result-dates",
                     if let Some(ref bd) = entry.birth_year {
                         span { class: "sp-birth", "\u{2726} {bd}" }
@@ -0,0 +1,159 @@
+//! Surname distribution: the most frequent surnames of a tree counted per
+//! birth decade or birth place, drawn as a heatmap (one row per surname,
+//! one column per bucket) with each surname's total as a bar.
+//!
+//! Fed by `GET /stats/surnames`; shading a cell by its share of the busiest
+//! cell makes a family's drift across decades or places stand out.
+
+use std::collections::HashMap;
+
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use crate::api::ApiClient;
+use crate::i18n::use_i18n;
+
+/// Surnames shown.
+const SURNAME_LIMIT: usize = 15;
+/// Place columns shown; the rarest places beyond it are left out.
+const MAX_PLACE_COLUMNS: usize = 15;
+
+/// Props for the [`SurnameDistribution`] component.
+#[derive(Props, Clone, PartialEq)]
+pub struct SurnameDistributionProps {
+    pub tree_id: Uuid,
+}
+
+/// Heatmap of surnames over birth decades or birth places.
+#[component]
+pub fn SurnameDistribution(props: SurnameDistributionProps) -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+    let tree_id = props.tree_id;
+    let mut over = use_signal(|| "decade");
+
+    let stats_resource = use_resource(move || {
+        let api = api.clone();
+        let over = over();
+        async move { api.surname_stats(tree_id, over, SURNAME_LIMIT).await }
+    });
+
+    let body = match &*stats_resource.read() {
+        None => rsx! {
+            div { class: "loading", {i18n.t("common.loading")} }
+        },
+        Some(Err(e)) => rsx! {
+            div { class: "error-msg", "{e}" }
+        },
+        Some(Ok(stats)) if stats.counts.is_empty() => rsx! {
+            p { class: "text-muted", {i18n.t("stats.surnames.empty")} }
+        },
+        Some(Ok(stats)) => {
+            let hidden = stats.buckets.len().saturating_sub(MAX_PLACE_COLUMNS);
+            let buckets: Vec<String> = if stats.over == "place" {
+                stats
+                    .buckets
+                    .iter()
+                    .take(MAX_PLACE_COLUMNS)
+                    .cloned()
+                    .collect()
+            } else {
+                stats.buckets.clone()
+            };
+            let cells: HashMap<(&str, &str), i64> = stats
+                .counts
+                .iter()
+                .map(|c| ((c.surname.as_str(), c.bucket.as_str()), c.count))
+                .collect();
+            let max_cell = stats.counts.iter().map(|c| c.count).max().unwrap_or(1);
+            let max_total = stats.surnames.first().map(|s| s.count).unwrap_or(1);
+            let rows: Vec<(String, i64, Vec<i64>)> = stats
+                .surnames
+                .iter()
+                .map(|s| {
+                    let counts = buckets
+                        .iter()
+                        .map(|b| {
+                            cells
+                                .get(&(s.surname.as_str(), b.as_str()))
+                                .copied()
+                                .unwrap_or(0)
+                        })
+                        .collect();
+                    (s.surname.clone(), s.count, counts)
+                })
+                .collect();
+            let is_decade = stats.over == "decade";
+            rsx! {
+                div { class: "surname-heatmap-wrap",
+                    table { class: "surname-heatmap",
+                        thead {
+                            tr {
+                                th {}
+                                th {}
+                                for bucket in buckets.iter() {
+                                    th {
+                                        class: if is_decade { "surname-heatmap-decade" } else { "surname-heatmap-place" },
+                                        title: "{bucket}",
+                                        if is_decade {
+                                            {i18n.t_args("stats.surnames.decade", &[("decade", bucket.as_str())])}
+                                        } else {
+                                            "{bucket}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        tbody {
+                            for (surname, total, counts) in rows {
+                                tr { key: "{surname}",
+                                    th { class: "surname-heatmap-name", "{surname}" }
+                                    td { class: "surname-heatmap-total",
+                                        div {
+                                            class: "surname-heatmap-bar",
+                                            style: "width: {total * 100 / max_total.max(1)}%;",
+                                        }
+                                        span { "{total}" }
+                                    }
+                                    for (bucket, count) in buckets.iter().zip(counts) {
+                                        td {
+                                            class: "surname-heatmap-cell",
+                                            title: "{surname} \u{00B7} {bucket} \u{00B7} {count}",
+                                            style: "--heat: {count as f64 / max_cell.max(1) as f64:.2};",
+                                            if count > 0 { "{count}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if stats.over == "place" && hidden > 0 {
+                    p { class: "text-muted",
+                        {i18n.t_plural("stats.surnames.more_places", hidden)}
+                    }
+                }
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "surname-distribution",
+            div { class: "surname-distribution-toolbar",
+                span { {i18n.t("stats.surnames.over")} }
+                button {
+                    class: if over() == "decade" { "btn btn-sm btn-primary" } else { "btn btn-sm btn-outline" },
+                    onclick: move |_| over.set("decade"),
+                    {i18n.t("stats.surnames.by_decade")}
+                }
+                button {
+                    class: if over() == "place" { "btn btn-sm btn-primary" } else { "btn btn-sm btn-outline" },
+                    onclick: move |_| over.set("place"),
+                    {i18n.t("stats.surnames.by_place")}
+                }
+            }
+            p { class: "text-muted surname-distribution-hint", {i18n.t("stats.surnames.hint")} }
+            {body}
+        }
+    }
+}
@@ -20,10 +20,12 @@ pub fn TreeIconSidebar(
     on_pedigree_view: EventHandler<Option<Uuid>>,
     on_add_person: EventHandler<()>,
     on_dictionary: EventHandler<()>,
+    on_tasks: EventHandler<()>,
     on_settings: EventHandler<()>,
     #[props(default = true)] show_middle_separator: bool,
     #[props(default = true)] show_add_person: bool,
     #[props(default = true)] show_dictionary: bool,
+    #[props(default = true)] show_tasks: bool,
     #[props(default = true)] show_settings: bool,
     #[props(default)] children: Element,
 ) -> Element {
@@ -107,7 +109,7 @@ pub fn TreeIconSidebar(
                 }
             }
 
-            if show_dictionary || show_settings {
+            if show_dictionary || show_tasks || show_settings {
                 div { class: "isb-hr" }
             }
 
@@ -129,6 +131,24 @@ pub fn TreeIconSidebar(
                 }
             }
 
+            if show_tasks {
+                button {
+                    class: "isb-btn",
+                    title: "{i18n.t(\"tasks.breadcrumb\")}",
+                    onclick: move |_| on_tasks.call(()),
+                    svg {
+                        width: "16",
+                        height: "16",
+                        fill: "none",
+                        "viewBox": "0 0 24 24",
+                        stroke: "currentColor",
+                        "strokeWidth": "2",
+                        path { d: "M9 11l3 3L22 4" }
+                        path { d: "M21 12v7a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2V5a2 2 0 0 1 2-2h11" }
+                    }
+                }
+            }
+
             if show_settings {
                 button {
                     class: "isb-btn",
@@ -1,6 +1,7 @@
 //! Modal-based couple/family edit form (spec §16).
 //!
 //! Body is divided into: Union (events, date/place/note shorthand),
+//! Notes & sources of the union itself (applied immediately),
 //! Children (with staged detach, applied on Save), Person 1 / Person 2
 //! (collapsible, embedding the full person edit fields). Footer holds
 //! Delete couple (removes the union only — persons remain in the tree)
@@ -11,12 +12,18 @@ use std::collections::HashSet;
 use dioxus::prelude::*;
 use uuid::Uuid;
 
-use crate::api::{AddChildBody, ApiClient, CreateEventBody, UpdateEventBody};
+use crate::api::{
+    AddChildBody, ApiClient, CreateCitationBody, CreateEventBody, CreateNoteBody,
+    UpdateCitationBody, UpdateEventBody, UpdateNoteBody,
+};
 use crate::components::person_form::PersonForm;
+use crate::components::place_input::PlaceInput;
+use crate::components::plausibility_warnings::PlausibilityWarnings;
 use crate::components::search_person::SearchPerson;
 use crate::i18n::use_i18n;
-use crate::utils::{opt_str, resolve_name};
-use oxidgene_core::{Calendar, ChildType, DateQualifier, EventType};
+use crate::utils::{opt_str, resolve_name, save_error_text};
+use oxidgene_core::validation::plausibility::PlausibilityWarning;
+use oxidgene_core::{Calendar, ChildType, Confidence, DateQualifier, EventType, NoteFormat};
 
 // ── Props ────────────────────────────────────────────────────────────────
 
@@ -46,12 +53,14 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
 
     // ── State ──
     let mut save_error = use_signal(|| None::<String>);
+    let mut marriage_warnings = use_signal(Vec::<PlausibilityWarning>::new);
 
     // Marriage event state (primary/first union event).
     let mut marriage_date = use_signal(String::new);
     let mut marriage_place_id = use_signal(String::new);
     let mut marriage_desc = use_signal(String::new);
     let mut marriage_event_id = use_signal(|| None::<Uuid>);
+    let mut marriage_event_version = use_signal(|| None::<i32>);
     let mut marriage_loaded = use_signal(|| false);
 
     // Add union event state.
@@ -61,6 +70,18 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
     let mut new_union_place = use_signal(String::new);
     let mut new_union_desc = use_signal(String::new);
 
+    // Union note form: a new note, or the edited one (`editing_note`).
+    let mut show_note_form = use_signal(|| false);
+    let mut editing_note = use_signal(|| None::<Uuid>);
+    let mut note_text = use_signal(String::new);
+    let mut note_markdown = use_signal(|| false);
+
+    // Union citation form: a new citation, or the edited one's page.
+    let mut show_citation_form = use_signal(|| false);
+    let mut editing_citation = use_signal(|| None::<Uuid>);
+    let mut citation_source = use_signal(String::new);
+    let mut citation_page = use_signal(String::new);
+
     // Add child linking mode.
     let mut show_add_child = use_signal(|| false);
 
@@ -68,6 +89,9 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
     let mut show_person1 = use_signal(|| false);
     let mut show_person2 = use_signal(|| false);
 
+    // Child being dragged to a new position (family-child link ID).
+    let mut dragged_child = use_signal(|| None::<Uuid>);
+
     // Staged child detach (applied on Save).
     let mut pending_detach = use_signal(HashSet::<Uuid>::new);
     let mut confirm_detach_id = use_signal(|| None::<Uuid>);
@@ -107,6 +131,28 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
         }
     });
 
+    // Notes and citations of the union itself
+    let api_notes = api.clone();
+    let notes_resource = use_resource(move || {
+        let api = api_notes.clone();
+        let _tick = refresh();
+        async move { api.list_family_notes(tid, fid).await }
+    });
+
+    let api_citations = api.clone();
+    let citations_resource = use_resource(move || {
+        let api = api_citations.clone();
+        let _tick = refresh();
+        async move { api.list_family_citations(tid, fid).await }
+    });
+
+    // Sources (for the citation picker and labels)
+    let api_sources = api.clone();
+    let sources_resource = use_resource(move || {
+        let api = api_sources.clone();
+        async move { api.list_all_sources(tid).await }
+    });
+
     // Places (for picker)
     let api_places = api.clone();
     let places_resource = use_resource(move || {
@@ -120,7 +166,7 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
     let _persons_resource = use_resource(move || {
         let api = api_persons.clone();
         let _tick = refresh();
-        async move { api.list_persons(tid, Some(500), None).await }
+        async move { api.list_all_persons(tid).await }
     });
 
     let api_names_res = api.clone();
@@ -128,14 +174,14 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
         let api = api_names_res.clone();
         let _tick = refresh();
         async move {
-            let persons = api.list_persons(tid, Some(500), None).await?;
+            let persons = api.list_all_persons(tid).await?;
             let mut name_map: std::collections::HashMap<
                 Uuid,
                 Vec<oxidgene_core::types::PersonName>,
             > = std::collections::HashMap::new();
-            for edge in &persons.edges {
-                if let Ok(names) = api.list_person_names(tid, edge.node.id).await {
-                    name_map.insert(edge.node.id, names);
+            for person in &persons {
+                if let Ok(names) = api.list_person_names(tid, person.id).await {
+                    name_map.insert(person.id, names);
                 }
             }
             Ok::<_, crate::api::ApiError>(name_map)
@@ -158,6 +204,7 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
                     | EventType::MarriageSettlement
             ) {
                 marriage_event_id.set(Some(ev.id));
+                marriage_event_version.set(Some(ev.version));
                 marriage_date.set(ev.date_value.clone().unwrap_or_default());
                 marriage_place_id.set(ev.place_id.map(|id| id.to_string()).unwrap_or_default());
                 marriage_desc.set(ev.description.clone().unwrap_or_default());
@@ -203,16 +250,16 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
         }
     };
 
-    // Place options.
-    let place_options: Vec<(String, String)> = {
-        let data = places_resource.read();
-        match &*data {
+    // Name of a selected place for the place inputs (empty if unknown).
+    let place_label = |place_id: &str| -> String {
+        match &*places_resource.read() {
             Some(Ok(conn)) => conn
                 .edges
                 .iter()
-                .map(|e| (e.node.id.to_string(), e.node.name.clone()))
-                .collect(),
-            _ => vec![],
+                .find(|e| e.node.id.to_string() == place_id)
+                .map(|e| e.node.name.clone())
+                .unwrap_or_default(),
+            _ => String::new(),
         }
     };
 
@@ -253,6 +300,26 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
         _ => i18n.t("union_form.title"),
     };
 
+    let union_notes = match &*notes_resource.read() {
+        Some(Ok(notes)) => notes.clone(),
+        _ => vec![],
+    };
+    let union_citations = match &*citations_resource.read() {
+        Some(Ok(citations)) => citations.clone(),
+        _ => vec![],
+    };
+    let sources: Vec<(Uuid, String)> = match &*sources_resource.read() {
+        Some(Ok(sources)) => sources.iter().map(|s| (s.id, s.title.clone())).collect(),
+        _ => vec![],
+    };
+    let source_title = |source_id: Uuid| -> String {
+        sources
+            .iter()
+            .find(|(id, _)| *id == source_id)
+            .map(|(_, title)| title.clone())
+            .unwrap_or_default()
+    };
+
     // ── Handlers ──
 
     // Save marriage event.
@@ -264,6 +331,7 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
         let place_str = marriage_place_id();
         let desc = marriage_desc().trim().to_string();
         let existing_id = marriage_event_id();
+        let existing_version = marriage_event_version();
         spawn(async move {
             let place_id = if place_str.is_empty() {
                 None
@@ -280,15 +348,18 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
                     calendar: None,
                     cause: None,
                     place_id: Some(place_id),
+                    place_id_to: None,
                     description: Some(opt_str(&desc)),
                 };
-                match api.update_event(tid, eid, &body).await {
-                    Ok(_) => {
+                match api.update_event(tid, eid, &body, existing_version).await {
+                    Ok(ev) => {
+                        marriage_event_version.set(Some(ev.event.version));
+                        marriage_warnings.set(ev.warnings);
                         save_error.set(None);
                         on_saved_marriage.call(());
                         refresh += 1;
                     }
-                    Err(e) => save_error.set(Some(format!("{e}"))),
+                    Err(e) => save_error.set(Some(save_error_text(&i18n, &e))),
                 }
             } else {
                 let body = CreateEventBody {
@@ -300,13 +371,16 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
                     calendar: Calendar::default(),
                     cause: None,
                     place_id,
+                    place_id_to: None,
                     person_id: None,
                     family_id: Some(fid),
                     description: opt_str(&desc),
                 };
                 match api.create_event(tid, &body).await {
                     Ok(ev) => {
-                        marriage_event_id.set(Some(ev.id));
+                        marriage_event_id.set(Some(ev.event.id));
+                        marriage_event_version.set(Some(ev.event.version));
+                        marriage_warnings.set(ev.warnings);
                         save_error.set(None);
                         on_saved_marriage.call(());
                         refresh += 1;
@@ -342,6 +416,7 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
                 calendar: Calendar::default(),
                 cause: None,
                 place_id,
+                place_id_to: None,
                 person_id: None,
                 family_id: Some(fid),
                 description: opt_str(&desc),
@@ -365,6 +440,115 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
     let api_del_union = api.clone();
     let on_saved_del_union = props.on_saved;
 
+    // Save the union note form: create, or update the edited note.
+    let api_save_note = api.clone();
+    let on_saved_note = props.on_saved;
+    let on_save_note = move |_| {
+        let api = api_save_note.clone();
+        let text = note_text().trim().to_string();
+        let format = if note_markdown() {
+            NoteFormat::Markdown
+        } else {
+            NoteFormat::Plain
+        };
+        let editing = editing_note();
+        spawn(async move {
+            if text.is_empty() {
+                save_error.set(Some(i18n.t("person_form.note_required")));
+                return;
+            }
+            let result = match editing {
+                Some(nid) => {
+                    let body = UpdateNoteBody {
+                        text: Some(text),
+                        format: Some(format),
+                    };
+                    api.update_note(tid, nid, &body).await
+                }
+                None => {
+                    let body = CreateNoteBody {
+                        text,
+                        format,
+                        person_id: None,
+                        event_id: None,
+                        family_id: Some(fid),
+                        source_id: None,
+                    };
+                    api.create_note(tid, &body).await
+                }
+            };
+            match result {
+                Ok(_) => {
+                    show_note_form.set(false);
+                    editing_note.set(None);
+                    note_text.set(String::new());
+                    note_markdown.set(false);
+                    save_error.set(None);
+                    on_saved_note.call(());
+                    refresh += 1;
+                }
+                Err(e) => save_error.set(Some(save_error_text(&i18n, &e))),
+            }
+        });
+    };
+
+    // Save the union citation form: create, or update the edited page.
+    let api_save_citation = api.clone();
+    let on_saved_citation = props.on_saved;
+    let on_save_citation = move |_| {
+        let api = api_save_citation.clone();
+        let page = citation_page().trim().to_string();
+        let source = citation_source();
+        let editing = editing_citation();
+        spawn(async move {
+            let result = match editing {
+                Some(cid) => {
+                    let body = UpdateCitationBody {
+                        page: Some(opt_str(&page)),
+                        confidence: None,
+                        text: None,
+                    };
+                    api.update_citation(tid, cid, &body).await
+                }
+                None => {
+                    let Ok(source_id) = source.parse::<Uuid>() else {
+                        save_error.set(Some(i18n.t("union_form.source_required")));
+                        return;
+                    };
+                    let body = CreateCitationBody {
+                        source_id,
+                        person_id: None,
+                        event_id: None,
+                        family_id: Some(fid),
+                        page: opt_str(&page),
+                        confidence: Confidence::Medium,
+                        text: None,
+                        fact_type: None,
+                        fact_ref: None,
+                    };
+                    api.create_citation(tid, &body).await
+                }
+            };
+            match result {
+                Ok(_) => {
+                    show_citation_form.set(false);
+                    editing_citation.set(None);
+                    citation_source.set(String::new());
+                    citation_page.set(String::new());
+                    save_error.set(None);
+                    on_saved_citation.call(());
+                    refresh += 1;
+                }
+                Err(e) => save_error.set(Some(format!("{e}"))),
+            }
+        });
+    };
+
+    let api_del_note = api.clone();
+    let on_saved_del_note = props.on_saved;
+    let api_del_citation = api.clone();
+    let on_saved_del_citation = props.on_saved;
+
     // Add child by linking existing person.
     let api_add_child_link = api.clone();
     let on_saved_add_child = props.on_saved;
@@ -388,6 +572,27 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
         });
     };
 
+    // Drag-to-reorder children (applied immediately, not on Save).
+    let api_reorder = api.clone();
+    let on_saved_reorder = props.on_saved;
+
+    // One-click "sort by age".
+    let api_sort_birth = api.clone();
+    let on_saved_sort_birth = props.on_saved;
+    let on_sort_by_birth = move |_| {
+        let api = api_sort_birth.clone();
+        spawn(async move {
+            match api.sort_children_by_birth(tid, fid).await {
+                Ok(_) => {
+                    save_error.set(None);
+                    on_saved_sort_birth.call(());
+                    refresh += 1;
+                }
+                Err(e) => save_error.set(Some(format!("{e}"))),
+            }
+        });
+    };
+
     // Apply staged child detachments, then close.
     let api_save_footer = api.clone();
     let on_saved_footer = props.on_saved;
@@ -481,6 +686,9 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
                 if let Some(err) = save_error() {
                     div { class: "error-msg", style: "margin: 0 16px;", "{err}" }
                 }
+                div { style: "margin: 0 16px;",
+                    PlausibilityWarnings { warnings: marriage_warnings }
+                }
 
                 div { class: "union-form-body",
                     // ── Union block ──
@@ -515,13 +723,11 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
                                 }
                                 div { class: "form-group",
                                     label { {i18n.t("person_form.place")} }
-                                    select {
-                                        value: "{marriage_place_id}",
-                                        oninput: move |e: Event<FormData>| marriage_place_id.set(e.value()),
-                                        option { value: "", {i18n.t("person_form.no_place")} }
-                                        for (pid, pname) in place_options.iter() {
-                                            option { value: "{pid}", "{pname}" }
-                                        }
+                                    PlaceInput {
+                                        tree_id: tid,
+                                        value: marriage_place_id(),
+                                        value_name: place_label(&marriage_place_id()),
+                                        on_change: move |place_id: String| marriage_place_id.set(place_id),
                                     }
                                 }
                                 div { class: "form-group",
@@ -629,13 +835,11 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
                                 div { class: "form-row",
                                     div { class: "form-group",
                                         label { {i18n.t("person_form.place")} }
-                                        select {
-                                            value: "{new_union_place}",
-                                            oninput: move |e: Event<FormData>| new_union_place.set(e.value()),
-                                            option { value: "", {i18n.t("person_form.no_place")} }
-                                            for (pid, pname) in place_options.iter() {
-                                                option { value: "{pid}", "{pname}" }
-                                            }
+                                        PlaceInput {
+                                            tree_id: tid,
+                                            value: new_union_place(),
+                                            value_name: place_label(&new_union_place()),
+                                            on_change: move |place_id: String| new_union_place.set(place_id),
                                         }
                                     }
                                     div { class: "form-group",
@@ -656,10 +860,204 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
                         }
                     }
 
+                    // ── Notes & sources block ──
+                    div { class: "union-form-section",
+                        div { class: "section-header",
+                            h3 { style: "font-size: 0.95rem;", {i18n.t("union_form.notes")} }
+                            button {
+                                class: "btn btn-primary btn-sm",
+                                onclick: move |_| {
+                                    editing_note.set(None);
+                                    note_text.set(String::new());
+                                    note_markdown.set(false);
+                                    show_note_form.toggle();
+                                },
+                                if show_note_form() && editing_note().is_none() { {i18n.t("common.cancel")} } else { {i18n.t("person_form.add_note")} }
+                            }
+                        }
+
+                        if show_note_form() {
+                            div { style: "padding: 12px; background: var(--bg-card); border-radius: var(--radius); border: 1px solid var(--border); margin-bottom: 8px;",
+                                div { class: "form-group",
+                                    textarea {
+                                        rows: 4,
+                                        placeholder: "{i18n.t(\"person_form.note_placeholder\")}",
+                                        value: "{note_text}",
+                                        oninput: move |e: Event<FormData>| note_text.set(e.value()),
+                                    }
+                                    label { class: "pf-note-format",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: note_markdown(),
+                                            onchange: move |e: Event<FormData>| note_markdown.set(e.checked()),
+                                        }
+                                        {i18n.t("person_form.note_markdown")}
+                                    }
+                                }
+                                button {
+                                    class: "btn btn-primary btn-sm",
+                                    onclick: on_save_note,
+                                    if editing_note().is_some() { {i18n.t("common.save")} } else { {i18n.t("person.create_note")} }
+                                }
+                            }
+                        }
+
+                        if union_notes.is_empty() {
+                            div { class: "empty-state", p { {i18n.t("union_form.no_notes")} } }
+                        }
+                        for note in union_notes.iter() {
+                            {
+                                let nid = note.id;
+                                let text = note.text.clone();
+                                let markdown = note.format == NoteFormat::Markdown;
+                                let preview = if text.chars().count() > 120 {
+                                    format!("{}…", text.chars().take(120).collect::<String>())
+                                } else {
+                                    text.clone()
+                                };
+                                let edit_text = text;
+                                rsx! {
+                                    div { class: "person-form-item",
+                                        div { class: "person-form-item-info", span { "{preview}" } }
+                                        div { class: "person-form-item-actions",
+                                            button {
+                                                class: "btn btn-outline btn-sm",
+                                                onclick: move |_| {
+                                                    editing_note.set(Some(nid));
+                                                    note_text.set(edit_text.clone());
+                                                    note_markdown.set(markdown);
+                                                    show_note_form.set(true);
+                                                },
+                                                {i18n.t("common.edit")}
+                                            }
+                                            button {
+                                                class: "btn btn-danger btn-sm",
+                                                onclick: {
+                                                    let api = api_del_note.clone();
+                                                    move |_| {
+                                                        let api = api.clone();
+                                                        spawn(async move {
+                                                            match api.delete_note(tid, nid).await {
+                                                                Ok(_) => { on_saved_del_note.call(()); refresh += 1; }
+                                                                Err(e) => save_error.set(Some(format!("{e}"))),
+                                                            }
+                                                        });
+                                                    }
+                                                },
+                                                {i18n.t("common.delete")}
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        div { class: "section-header", style: "margin-top: 12px;",
+                            h3 { style: "font-size: 0.95rem;", {i18n.t("union_form.sources")} }
+                            button {
+                                class: "btn btn-primary btn-sm",
+                                onclick: move |_| {
+                                    editing_citation.set(None);
+                                    citation_source.set(String::new());
+                                    citation_page.set(String::new());
+                                    show_citation_form.toggle();
+                                },
+                                if show_citation_form() && editing_citation().is_none() { {i18n.t("common.cancel")} } else { {i18n.t("union_form.add_source")} }
+                            }
+                        }
+
+                        if show_citation_form() {
+                            div { style: "padding: 12px; background: var(--bg-card); border-radius: var(--radius); border: 1px solid var(--border); margin-bottom: 8px;",
+                                div { class: "form-row",
+                                    div { class: "form-group",
+                                        label { {i18n.t("union_form.source")} }
+                                        select {
+                                            value: "{citation_source}",
+                                            disabled: editing_citation().is_some(),
+                                            oninput: move |e: Event<FormData>| citation_source.set(e.value()),
+                                            option { value: "", {i18n.t("union_form.choose_source")} }
+                                            for (id, title) in sources.iter() {
+                                                option { key: "{id}", value: "{id}", "{title}" }
+                                            }
+                                        }
+                                    }
+                                    div { class: "form-group",
+                                        label { {i18n.t("union_form.page")} }
+                                        input {
+                                            r#type: "text",
+                                            value: "{citation_page}",
+                                            oninput: move |e: Event<FormData>| citation_page.set(e.value()),
+                                        }
+                                    }
+                                }
+                                button {
+                                    class: "btn btn-primary btn-sm",
+                                    onclick: on_save_citation,
+                                    if editing_citation().is_some() { {i18n.t("common.save")} } else { {i18n.t("person.create_citation")} }
+                                }
+                            }
+                        }
+
+                        if union_citations.is_empty() {
+                            div { class: "empty-state", p { {i18n.t("union_form.no_sources")} } }
+                        }
+                        for citation in union_citations.iter() {
+                            {
+                                let cid = citation.id;
+                                let source_id = citation.source_id;
+                                let title = source_title(source_id);
+                                let page = citation.page.clone().unwrap_or_default();
+                                let edit_page = page.clone();
+                                rsx! {
+                                    div { class: "person-form-item",
+                                        div { class: "person-form-item-info",
+                                            strong { "{title}" }
+                                            if !page.is_empty() { span { class: "text-muted", "— {page}" } }
+                                        }
+                                        div { class: "person-form-item-actions",
+                                            button {
+                                                class: "btn btn-outline btn-sm",
+                                                onclick: move |_| {
+                                                    editing_citation.set(Some(cid));
+                                                    citation_source.set(source_id.to_string());
+                                                    citation_page.set(edit_page.clone());
+                                                    show_citation_form.set(true);
+                                                },
+                                                {i18n.t("common.edit")}
+                                            }
+                                            button {
+                                                class: "btn btn-danger btn-sm",
+                                                onclick: {
+                                                    let api = api_del_citation.clone();
+                                                    move |_| {
+                                                        let api = api.clone();
+                                                        spawn(async move {
+                                                            match api.delete_citation(tid, cid).await {
+                                                                Ok(_) => { on_saved_del_citation.call(()); refresh += 1; }
+                                                                Err(e) => save_error.set(Some(format!("{e}"))),
+                                                            }
+                                                        });
+                                                    }
+                                                },
+                                                {i18n.t("common.delete")}
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
                     // ── Children block ──
                     div { class: "union-form-section",
                         div { class: "section-header",
                             h3 { style: "font-size: 0.95rem;", {i18n.t("union_form.children")} }
+                            button {
+                                class: "btn btn-outline btn-sm",
+                                r#type: "button",
+                                onclick: on_sort_by_birth,
+                                {i18n.t("union_form.sort_by_birth")}
+                            }
                             button {
                                 class: "btn btn-primary btn-sm",
                                 onclick: move |_| show_add_child.toggle(),
@@ -689,6 +1087,10 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
                                     for child in children.iter() {
                                         {
                                             let cid = child.person_id;
+                                            let link_id = child.id;
+                                            let link_order: Vec<Uuid> = children.iter().map(|c| c.id).collect();
+                                            let api_drop = api_reorder.clone();
+                                            let is_dragged = dragged_child() == Some(link_id);
                                             let ct = format!("{:?}", child.child_type);
                                             let name = resolve_name(cid, &name_map_for_display);
                                             let is_pending = pending_detach().contains(&cid);
@@ -719,7 +1121,41 @@ pub fn UnionForm(props: UnionFormProps) -> Element {
                                                         }
                                                     }
                                                 } else {
-                                                    div { class: if is_pending { "uf-child-row pending-detach" } else { "uf-child-row" },
+                                                    div {
+                                                        class: if is_pending { "uf-child-row pending-detach" } else if is_dragged { "uf-child-row dragging" } else { "uf-child-row" },
+                                                        title: i18n.t("union_form.drag_to_reorder"),
+                                                        draggable: "true",
+                                                        ondragstart: move |_| dragged_child.set(Some(link_id)),
+                                                        ondragend: move |_| dragged_child.set(None),
+                                                        ondragover: move |e: Event<DragData>| e.prevent_default(),
+                                                        ondrop: move |e: Event<DragData>| {
+                                                            e.prevent_default();
+                                                            let Some(dragged) = dragged_child() else { return };
+                                                            dragged_child.set(None);
+                                                            let mut order = link_order.clone();
+                                                            let (Some(from), Some(to)) = (
+                                                                order.iter().position(|&id| id == dragged),
+                                                                order.iter().position(|&id| id == link_id),
+                                                            ) else { return };
+                                                            if from == to {
+                                                                return;
+                                                            }
+                                                            // Dropping moves the child into the target's slot.
+                                                            let moved = order.remove(from);
+                                                            order.insert(to, moved);
+                                                            let api = api_drop.clone();
+                                                            spawn(async move {
+                                                                match api.reorder_children(tid, fid, &order).await {
+                                                                    Ok(_) => {
+                                                                        save_error.set(None);
+                                                                        on_saved_reorder.call(());
+                                                                        refresh += 1;
+                                                                    }
+                                                                    Err(e) => save_error.set(Some(format!("{e}"))),
+                                                                }
+                                                            });
+                                                        },
+                                                        div { class: "uf-child-drag-handle", "\u{2807}" }
                                                         div { class: "uf-child-avatar", "\u{1F464}" }
                                                         div { class: "uf-child-info",
                                                             span { class: "badge", "{ct}" }
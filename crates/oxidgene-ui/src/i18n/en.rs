@@ -28,6 +28,10 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("common.duplicate", "Duplicate"),
         ("common.duplicating", "Duplicating\u{2026}"),
         ("common.settings", "Settings"),
+        (
+            "common.conflict",
+            "This record was changed elsewhere since you opened it. Close and reopen it to load the latest version.",
+        ),
         ("common.export", "Export"),
         ("common.import", "Import"),
         ("common.exporting", "Exporting\u{2026}"),
@@ -56,6 +60,17 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("app_settings.language", "Language"),
         ("app_settings.language_title", "Language"),
         ("app_settings.language_desc", "Choose the display language for the interface."),
+        ("app_settings.data", "Data"),
+        ("app_settings.backups", "Backups"),
+        ("app_settings.backups_desc", "Copies of your database, taken automatically at regular intervals. Restoring one replaces all your trees with their state at that time."),
+        ("app_settings.backup_now", "Back up now"),
+        ("app_settings.backup_now_hint", "Take a backup in addition to the automatic ones."),
+        ("app_settings.backup_created", "Backup {file} created."),
+        ("app_settings.no_backups", "No backups yet."),
+        ("app_settings.restore", "Restore"),
+        ("app_settings.restore_confirm_title", "Restore backup"),
+        ("app_settings.restore_confirm_message", "Replace all your data with the backup from {date}? A backup of the current data is taken first."),
+        ("app_settings.restore_staged", "Restore scheduled. Restart OxidGene to apply it."),
         // ── Home page ───────────────────────────────────────────────
         ("home.title_prefix", "My "),
         ("home.title_accent", "Genealogy Trees"),
@@ -72,6 +87,24 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("home.add_card_subtitle", "Start a new family lineage from scratch or import a GEDCOM file"),
         ("home.badge_recent", "Recent"),
         ("home.tree_actions", "Tree actions"),
+        ("home.count_persons_one", "{count} person"),
+        ("home.count_persons_other", "{count} persons"),
+        ("home.count_families_one", "{count} family"),
+        ("home.count_families_other", "{count} families"),
+        ("home.count_events_one", "{count} event"),
+        ("home.count_events_other", "{count} events"),
+        ("home.count_names_one", "{count} name"),
+        ("home.count_names_other", "{count} names"),
+        ("home.count_sources_one", "{count} source"),
+        ("home.count_sources_other", "{count} sources"),
+        ("home.count_citations_one", "{count} citation"),
+        ("home.count_citations_other", "{count} citations"),
+        ("home.count_media_one", "{count} media"),
+        ("home.count_media_other", "{count} media"),
+        ("home.count_places_one", "{count} place"),
+        ("home.count_places_other", "{count} places"),
+        ("home.count_notes_one", "{count} note"),
+        ("home.count_notes_other", "{count} notes"),
         ("home.modified_today", "Modified today"),
         ("home.modified_1day", "Modified 1 day ago"),
         ("home.modified_days", "Modified {count} days ago"),
@@ -90,15 +123,44 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("tree.search_last", "Last name / # SOSA"),
         ("tree.search_first", "First name"),
         ("tree.search", "Search"),
+        // ── Recently edited menu ────────────────────────────────────
+        ("recent.title", "Recently edited"),
+        ("recent.empty", "Nothing edited yet"),
+        ("recent.unnamed", "(unnamed)"),
+        ("recent.created", "created {date}"),
+        ("recent.updated", "edited {date}"),
+        ("recent.kind.person", "Person"),
+        ("recent.kind.family", "Family"),
+        ("recent.kind.event", "Event"),
+        ("recent.kind.place", "Place"),
+        ("recent.kind.source", "Source"),
+        ("recent.kind.media", "Media"),
+        ("recent.kind.note", "Note"),
         // ── Tree create / edit form ─────────────────────────────────
         ("tree.form.name_label", "Name"),
         ("tree.form.name_placeholder", "e.g. Martin Family Tree"),
         ("tree.form.name_required", "Name is required"),
         ("tree.form.description_label", "Description (optional)"),
         ("tree.form.description_placeholder", "A brief description\u{2026}"),
+        ("tree.form.template_self", "Start with me, my parents and grandparents"),
         // ── Confirm dialogs ─────────────────────────────────────────
         ("home.rename_tree", "Rename Tree"),
         ("home.duplicate_suffix", " (copy)"),
+        ("home.check_tree", "Check tree"),
+        ("home.check_clean", "\"{name}\": no problems found."),
+        ("home.share_tree", "Share read-only link"),
+        ("home.share_created", "\"{name}\": share link created (living persons hidden)."),
+        ("home.share_open", "Open link"),
+        ("home.share_revoke", "Revoke"),
+        ("home.import_done", "\"{name}\" imported: {counts}."),
+        ("home.check_summary", "\"{name}\": {errors} error(s), {warnings} warning(s)."),
+        ("home.import_phase.queued", "Starting\u{2026}"),
+        ("home.import_phase.parsing", "Reading file\u{2026}"),
+        ("home.import_phase.persisting", "Saving {done} / {total} records\u{2026}"),
+        ("home.import_phase.rebuilding_cache", "Preparing the tree\u{2026}"),
+        ("home.import_parsed", "{parsed} records read, {warnings} warning(s)"),
+        ("home.import_cancelling", "Cancelling\u{2026}"),
+        ("home.import_cancelled", "Import cancelled; nothing was imported."),
         ("confirm.delete_tree.title", "Delete Tree"),
         ("confirm.delete_tree.message", "Are you sure you want to delete this tree and all its data? This action cannot be undone."),
         ("confirm.delete_tree.message_name", "Delete \"{name}\"? This action cannot be undone."),
@@ -115,17 +177,22 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("confirm.delete_citation.message", "Are you sure you want to delete this citation?"),
         // ── Linking mode labels ─────────────────────────────────────
         ("linking.add_spouse", "Add Spouse"),
-        ("linking.add_parent", "Add Parent"),
+        ("linking.add_father", "Add Father"),
+        ("linking.add_mother", "Add Mother"),
         ("linking.add_child", "Add Child"),
         ("linking.add_sibling", "Add Sibling"),
         ("linking.merge", "Merge with\u{2026}"),
+        ("linking.relationship", "Relationship with\u{2026}"),
         ("linking.search_spouse", "Search for spouse..."),
-        ("linking.search_parent", "Search for parent..."),
+        ("linking.search_father", "Search for father..."),
+        ("linking.search_mother", "Search for mother..."),
         ("linking.search_child", "Search for child..."),
         ("linking.search_sibling", "Search for sibling..."),
         ("linking.search_merge", "Search for person to merge..."),
+        ("linking.search_relationship", "Search for a relative..."),
         ("linking.create_spouse", "Create New Person as Spouse"),
-        ("linking.create_parent", "Create New Person as Parent"),
+        ("linking.create_father", "Create New Person as Father"),
+        ("linking.create_mother", "Create New Person as Mother"),
         ("linking.create_child", "Create New Person as Child"),
         ("linking.create_sibling", "Create New Person as Sibling"),
         ("linking.search_existing", "Search for an existing person to link, or create a new one:"),
@@ -133,6 +200,9 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         // ── Context menu ────────────────────────────────────────────
         ("context.edit_individual", "Edit individual"),
         ("context.merge", "Merge with\u{2026}"),
+        ("context.relationship", "Relationship with\u{2026}"),
+        ("context.add_father", "Add father"),
+        ("context.add_mother", "Add mother"),
         ("context.edit_union", "Edit union"),
         ("context.edit_union_submenu", "Edit union \u{25B8}"),
         ("context.add_spouse", "Add spouse"),
@@ -146,6 +216,8 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("pedigree.depth", "Generation depth"),
         ("pedigree.fit_screen", "Fit to screen"),
         ("pedigree.zoom_in", "Zoom in"),
+        ("pedigree.color_by_sex", "Color cards by sex"),
+        ("pedigree.color_by_surname", "Color cards by surname"),
         ("pedigree.zoom_out", "Zoom out"),
         ("pedigree.zoom_reset", "Reset"),
         ("pedigree.breadcrumb", "Tree"),
@@ -167,6 +239,20 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person.vitals.age_months_other", "{n} months old"),
         ("person.vitals.age_days_one", "{n} day old"),
         ("person.vitals.age_days_other", "{n} days old"),
+        ("person.name_policy.label", "Name shown"),
+        ("person.photo.choose", "Choose profile photo"),
+        ("person.photo.clear", "Remove profile photo"),
+        ("media.gallery.title", "Photos & documents"),
+        ("media.gallery.load_error", "Error loading media: {error}"),
+        ("media.gallery.profile", "Profile photo"),
+        ("media.gallery.move_before", "Move earlier"),
+        ("media.gallery.move_after", "Move later"),
+        ("media.gallery.show_more", "Show {n} more"),
+        ("media.gallery.open_file", "Open file"),
+        ("person.name_policy.prefer_primary", "Primary name"),
+        ("person.name_policy.prefer_birth", "Birth name"),
+        ("person.name_policy.prefer_married", "Married name"),
+        ("person.name_policy.most_recent", "Most recent name"),
         ("person.family_connections", "Family Connections"),
         ("person.parents", "Parents"),
         ("person.spouses_partners", "Spouses & Partners"),
@@ -198,6 +284,30 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person.view_in_tree", "View in tree"),
         ("person.edit_sex", "Edit sex"),
         ("person.refresh", "Refresh"),
+        ("plausibility.title", "Saved, but some dates look implausible:"),
+        ("plausibility.dismiss", "Dismiss"),
+        ("plausibility.death_before_birth", "The death is dated before the birth."),
+        ("plausibility.married_too_young", "A spouse was under 12 at the marriage."),
+        ("plausibility.born_after_mother_death", "The birth is dated after the mother\u{2019}s death."),
+        ("plausibility.mother_too_young", "The mother was under 13 at the birth."),
+        ("tags.add", "Add tag"),
+        ("tags.add_placeholder", "Tag (e.g. needs sources)"),
+        ("tags.remove", "Remove tag"),
+        ("tasks.breadcrumb", "To-do"),
+        ("tasks.title", "Research to-do list"),
+        ("tasks.person_section", "Research tasks"),
+        ("tasks.none", "No tasks."),
+        ("tasks.load_error", "Error loading tasks: {error}"),
+        ("tasks.add", "Add task"),
+        ("tasks.add_placeholder", "Task (e.g. find the baptism record)"),
+        ("tasks.due_date", "Due date"),
+        ("tasks.due", "Due {date}"),
+        ("tasks.toggle_done", "Mark as done"),
+        ("tasks.filter_open", "Open"),
+        ("tasks.filter_done", "Done"),
+        ("tasks.filter_all", "All"),
+        ("tasks.family_owner", "Family"),
+        ("tasks.invalid_tree", "Invalid tree link."),
         ("person.no_family_connections", "No family connections recorded."),
         ("person.no_names", "No names recorded."),
         ("person.actions", "Actions"),
@@ -208,17 +318,38 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person.origin_parental", "Parental family"),
         ("person.loading_names", "Loading names..."),
         ("person.events_section", "Events"),
+        ("person.events_flat", "Chronological view"),
+        ("person.events_grouped", "Group by category"),
         ("person.new_event", "New Event"),
         ("person.event_type", "Event Type"),
         ("person.no_events", "No events recorded."),
         ("person.loading_events", "Loading events..."),
         ("person.notes_section", "Notes"),
+        ("person.notes_from_families", "From families"),
+        ("person.note_from_parents", "Parents' union"),
+        ("person.note_from_union", "Own union"),
+        ("person.well_sourced", "Well-sourced"),
+        ("person.needs_evidence", "Needs evidence"),
+        ("person.sourcing_score", "Vital facts sourced: {percent}%"),
+        ("person.no_parents", "No known parents"),
+        ("person.lock", "Lock"),
+        ("person.unlock", "Unlock"),
+        ("person.lock_hint", "Lock this person against edits and deletion"),
+        ("person.unlock_hint", "Unlock this person to edit or delete them"),
+        ("person.locked_hint", "Locked: unlock to edit or delete"),
+        ("person.no_parents_hint", "No parent recorded yet: a research target"),
+        ("person.end_of_line", "End of line"),
         ("person.add_note", "Add Note"),
         ("person.new_note", "New Note"),
         ("person.note_text_label", "Note"),
         ("person.no_notes", "No notes recorded."),
         ("person.loading_notes", "Loading notes..."),
         ("person.sources_section", "Sources"),
+        ("citation.fact.name", "name"),
+        ("citation.fact.date", "date"),
+        ("citation.fact.place", "place"),
+        ("citation.fact.description", "description"),
+        ("citation.fact.age", "age"),
         ("person.add_citation", "Add Citation"),
         ("person.new_citation", "New Citation"),
         ("person.source", "Source"),
@@ -239,6 +370,16 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person.ancestry_hint", "Ancestors and descendants are computed from family relationships."),
         // ── Person form (modal) ─────────────────────────────────────
         ("person_form.subtitle_edit", "Edit individual"),
+        ("quick_entry.title", "Quick entry"),
+        ("quick_entry.birth_date", "Birth date"),
+        ("quick_entry.birth_place", "Birth place"),
+        ("quick_entry.death_date", "Death date"),
+        ("quick_entry.death_place", "Death place"),
+        ("quick_entry.hint", "Places are matched by name and created when new. Press Enter to save and start the next person."),
+        ("quick_entry.name_required", "Enter a given name or a surname."),
+        ("quick_entry.created", "Created:"),
+        ("quick_entry.save_next", "Save & next"),
+        ("quick_entry.full_form", "Full form\u{2026}"),
         ("person_form.tab_civil", "Civil Status"),
         ("person_form.tab_names", "Names"),
         ("person_form.tab_events", "Events ({count})"),
@@ -255,16 +396,17 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person_form.primary", "Primary"),
         ("person_form.birth", "Birth"),
         ("person_form.death", "Death"),
-        ("person_form.sacraments", "Sacraments"),
         ("person_form.other_events", "Other Events"),
         ("person_form.add_event", "Add Event"),
         ("person_form.no_other_events", "No other events recorded."),
         ("person_form.notes", "Notes"),
         ("person_form.date", "Date"),
         ("person_form.place", "Place"),
+        ("person_form.place_to", "Destination"),
         ("person_form.type", "Type"),
         ("person_form.description", "Description"),
         ("person_form.no_place", "-- No place --"),
+        ("place_input.create", "+ Add \"{name}\" as a new place"),
         ("person_form.save_birth", "Save Birth"),
         ("person_form.update_birth", "Update Birth"),
         ("person_form.save_death", "Save Death"),
@@ -272,6 +414,7 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person_form.given_or_surname_required", "Given names or surname is required"),
         ("person_form.note_required", "Note text is required"),
         ("person_form.note_placeholder", "Enter note text..."),
+        ("person_form.note_markdown", "Markdown (headings, lists, links)"),
         ("person_form.date_placeholder", "e.g. 1 Jan 1900"),
         ("person_form.date_placeholder_long", "e.g. 1 Jan 1900, ABT 1850, BET 1800 AND 1810"),
         ("person_form.death_date_placeholder", "e.g. 15 Mar 1975"),
@@ -284,9 +427,6 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person_form.loading_names", "Loading names..."),
         ("person_form.no_notes", "No notes recorded."),
         ("person_form.add_note", "Add Note"),
-        ("person_form.civil", "Civil"),
-        ("person_form.legal", "Legal"),
-        ("person_form.attributes", "Attributes"),
         ("person_form.discard_title", "Discard changes?"),
         ("person_form.discard_message", "You have unsaved changes. Are you sure you want to close?"),
         ("person_form.discard_confirm", "Discard"),
@@ -300,13 +440,14 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person_form.delete_confirm_button", "Confirm deletion"),
         ("person_form.deleting", "Deleting\u{2026}"),
         ("person_form.privacy", "Privacy"),
+        ("person_form.living_status", "Living status"),
         ("person_form.date_qualifier", "Qualifier"),
         ("person_form.note", "Note"),
         ("person_form.cause", "Cause"),
         ("person_form.calendar", "Calendar"),
-        ("person_form.witnesses", "Witnesses"),
-        ("person_form.add_witness", "+ Add a witness"),
-        ("person_form.witnesses_save_first", "Save this event first to add witnesses."),
+        ("person_form.witnesses", "Participants"),
+        ("person_form.add_witness", "+ Add a participant"),
+        ("person_form.witnesses_save_first", "Save this event first to add participants."),
         ("person_form.witness_relation_placeholder", "Relation (e.g. Godmother)"),
         ("person_form.search_witness", "Search for a person\u{2026}"),
         ("person_form.date2_label_or", "or"),
@@ -317,6 +458,9 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("privacy.default", "Default"),
         ("privacy.public", "Public"),
         ("privacy.private", "Private"),
+        ("living.auto", "From dates"),
+        ("living.living", "Living"),
+        ("living.deceased", "Deceased"),
         // ── Date qualifiers ──────────────────────────────────────────
         ("date_qualifier.exact", "Exact"),
         ("date_qualifier.about", "About"),
@@ -333,6 +477,12 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("calendar.french_republican", "French Republican"),
         // ── Union form (modal) ──────────────────────────────────────
         ("union_form.title", "Edit Union"),
+        ("family_card.married", "Married {date}"),
+        ("family_card.married_at", "Married {date}, {place}"),
+        ("family_card.married_in", "Married in {place}"),
+        ("family_card.no_marriage", "No marriage recorded"),
+        ("family_card.divorced", "Divorced {date}"),
+        ("family_card.divorce_before_marriage", "Divorce dated before the marriage"),
         ("union_form.spouses", "Spouses"),
         ("union_form.children", "Children"),
         ("union_form.events", "Union Events"),
@@ -359,6 +509,8 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("union_form.person1", "Person 1: {name}"),
         ("union_form.person2", "Person 2: {name}"),
         ("union_form.detach_button", "Detach"),
+        ("union_form.drag_to_reorder", "Drag to reorder"),
+        ("union_form.sort_by_birth", "Sort by age"),
         ("union_form.undo_detach", "Undo"),
         ("union_form.detach_confirm_title", "Detach {name} from this union?"),
         (
@@ -374,18 +526,32 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ),
         ("union_form.delete_confirm_button", "Confirm deletion"),
         ("union_form.deleting", "Deleting…"),
+        ("union_form.notes", "Notes"),
+        ("union_form.no_notes", "No notes on this union."),
+        ("union_form.sources", "Sources"),
+        ("union_form.no_sources", "No sources cited for this union."),
+        ("union_form.add_source", "+ Cite a source"),
+        ("union_form.source", "Source"),
+        ("union_form.choose_source", "Choose a source\u{2026}"),
+        ("union_form.page", "Page"),
+        ("union_form.source_required", "Choose the source to cite"),
         // ── Search person component ─────────────────────────────────
         ("search.placeholder", "Search for a person..."),
         ("search.loading", "Loading persons..."),
         ("search.no_match", "No matching persons found."),
+        ("search.also_known_as", "also known as {name}"),
         // ── Search results page ───────────────────────────────────────
         ("search.title", "Search"),
         ("search.go", "Search"),
         ("search.filters", "Filters"),
+        ("search.surname_index", "Surnames A\u{2013}Z"),
+        ("search.surname_index_empty", "No surname recorded yet."),
         ("search.gender", "Gender"),
+        ("search.tag", "Tag"),
         ("search.all", "All"),
         ("search.male", "Male"),
         ("search.female", "Female"),
+        ("search.intersex", "Intersex"),
         ("search.unknown", "Unknown"),
         ("search.born_between", "Born between"),
         ("search.died_between", "Died between"),
@@ -398,6 +564,22 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("search.sort_birth_desc", "Birth date \u{2193}"),
         ("search.no_results", "No person found. Try adjusting your search terms."),
         ("search.error", "An error occurred while searching."),
+        ("search.select_page", "Select this page"),
+        ("search.selected_one", "{count} selected"),
+        ("search.selected_other", "{count} selected"),
+        ("search.detach_families", "Detach from families"),
+        ("search.delete_selected", "Delete selected"),
+        ("search.clear_selection", "Clear selection"),
+        ("search.confirm_bulk_delete_one", "Delete {count} person? Persons still in a family are kept."),
+        ("search.confirm_bulk_delete_other", "Delete {count} persons? Persons still in a family are kept."),
+        ("search.confirm_bulk_delete_detach_one", "Delete {count} person? They are removed from their families, and families left empty are deleted."),
+        ("search.confirm_bulk_delete_detach_other", "Delete {count} persons? They are removed from their families, and families left empty are deleted."),
+        ("search.bulk_deleted_one", "{count} person deleted"),
+        ("search.bulk_deleted_other", "{count} persons deleted"),
+        ("search.bulk_skipped_family_one", "{count} kept: still in a family"),
+        ("search.bulk_skipped_family_other", "{count} kept: still in a family"),
+        ("search.bulk_skipped_locked_one", "{count} kept: locked"),
+        ("search.bulk_skipped_locked_other", "{count} kept: locked"),
         ("search.view_list", "List view"),
         ("search.view_grid", "Pedigree grid view"),
         // ── Dictionary page ─────────────────────────────────────────
@@ -406,6 +588,15 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("dictionary.tab.sources", "Sources"),
         ("dictionary.tab.places", "Places"),
         ("dictionary.tab.occupations", "Occupations"),
+        ("dictionary.tab.distribution", "Distribution"),
+        ("stats.surnames.over", "Surnames by"),
+        ("stats.surnames.by_decade", "Birth decade"),
+        ("stats.surnames.by_place", "Birth place"),
+        ("stats.surnames.hint", "The most frequent surnames, counted by the birth of each person. Darker cells hold more births."),
+        ("stats.surnames.decade", "{decade}s"),
+        ("stats.surnames.empty", "No births with a date or place yet."),
+        ("stats.surnames.more_places_one", "{count} more place not shown"),
+        ("stats.surnames.more_places_other", "{count} more places not shown"),
         ("dictionary.letter_all", "All"),
         ("dictionary.filter_placeholder", "Filter..."),
         ("dictionary.page_size_all", "All"),
@@ -433,6 +624,49 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("dictionary.no_matches", "No entries match."),
         ("dictionary.clear_filter", "Clear filter"),
         ("dictionary.view_usage", "View usage"),
+        ("dictionary.open_source", "Open source"),
+        ("source_detail.usage_title", "Cited for"),
+        ("source_detail.no_usage", "This source is not cited yet."),
+        ("source_detail.invalid_id", "Invalid source link."),
+        ("source_detail.kind.person", "Person"),
+        ("source_detail.kind.event", "Event"),
+        ("source_detail.kind.family", "Family"),
+        ("compare.breadcrumb", "Compare"),
+        ("compare.title", "Compare persons"),
+        ("compare.invalid_ids", "Invalid comparison link."),
+        ("compare.agreement_count_one", "{count} agreement"),
+        ("compare.agreement_count_other", "{count} agreements"),
+        ("compare.difference_count_one", "{count} difference"),
+        ("compare.difference_count_other", "{count} differences"),
+        ("compare.same", "Same"),
+        ("compare.compatible", "Compatible"),
+        ("compare.different", "Different"),
+        ("compare.partial", "One side only"),
+        ("compare.field.name", "Name"),
+        ("compare.field.other_names", "Other names"),
+        ("compare.field.sex", "Sex"),
+        ("compare.field.birth_date", "Birth date"),
+        ("compare.field.birth_place", "Birth place"),
+        ("compare.field.death_date", "Death date"),
+        ("compare.field.death_place", "Death place"),
+        ("compare.field.parents", "Parents"),
+        ("compare.field.spouses", "Spouses"),
+        // ── Relationship path ───────────────────────────────────────
+        ("relationship.breadcrumb", "Relationship"),
+        ("relationship.title", "How are they related?"),
+        ("relationship.invalid_ids", "Invalid relationship link."),
+        ("relationship.unrelated", "No common ancestor found: these persons are not related by blood in this tree."),
+        ("relationship.common_ancestor", "Common ancestor: {name}"),
+        ("relationship.generations_up_one", "{count} generation up"),
+        ("relationship.generations_up_other", "{count} generations up"),
+        ("relationship.generations_down_one", "{count} down"),
+        ("relationship.generations_down_other", "{count} down"),
+        ("kinship.father", "father"),
+        ("kinship.mother", "mother"),
+        ("kinship.parent", "parent"),
+        ("kinship.son", "son"),
+        ("kinship.daughter", "daughter"),
+        ("kinship.child", "child"),
         ("dictionary.view_in_search", "View in search"),
         ("dictionary.usage_empty", "No persons found."),
         ("dictionary.usage_error", "Failed to load usage."),
@@ -484,6 +718,17 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("settings.export_success", "Export completed successfully."),
         ("settings.export_saved_to", "Export saved to {path}."),
         ("settings.export_write_error", "Failed to save export file: {error}"),
+        ("settings.export_preparing", "Preparing the export ({total} records)\u{2026}"),
+        ("settings.export_progress", "{done} / {total} records exported"),
+        ("settings.export_packaging", "Compressing the archive\u{2026}"),
+        ("settings.geocoding", "Geocoding"),
+        ("settings.geocoding_desc", "Look up coordinates for every place that has none. Runs in the background at the provider's rate limit; places with several possible locations are left for you to pick."),
+        ("settings.geocoding_start", "Geocode all places"),
+        ("settings.geocoding_running", "Geocoding..."),
+        ("settings.geocoding_progress", "{done} / {total} places"),
+        ("settings.geocoding_summary", "{succeeded} geocoded, {ambiguous} ambiguous, {failed} failed."),
+        ("settings.geocoding_ambiguous", "{count} possible locations"),
+        ("settings.geocoding_no_match", "no match"),
         ("settings.coming_soon", "Coming soon"),
         ("settings.coming_soon_desc", "This section is under development."),
         // ── 404 page ────────────────────────────────────────────────
@@ -491,12 +736,18 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("not_found.message_prefix", "The page \""),
         ("not_found.message_suffix", "\" does not exist."),
         ("not_found.go_home", "Go Home"),
+        // ── Shared tree ─────────────────────────────────────────────
+        ("shared.read_only", "Read-only shared view. Living persons are hidden."),
+        ("shared.living", "(living)"),
+        ("shared.unavailable", "Link unavailable"),
+        ("shared.unavailable_hint", "This share link has expired or been revoked."),
         // ── GEDCOM I/O ──────────────────────────────────────────────
         ("gedcom.select_file", "Select a GEDCOM file"),
         ("gedcom.save_file", "Save GEDCOM file"),
         // ── Sex enum display ────────────────────────────────────────
         ("sex.male", "Male"),
         ("sex.female", "Female"),
+        ("sex.intersex", "Intersex"),
         ("sex.unknown", "Unknown"),
         // ── Name type enum display ──────────────────────────────────
         ("name_type.birth", "Birth name"),
@@ -505,6 +756,12 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("name_type.maiden", "Maiden name"),
         ("name_type.religious", "Religious name"),
         ("name_type.other", "Other"),
+        // ── Event participant roles ─────────────────────────────────
+        ("participant_role.witness", "Witness"),
+        ("participant_role.godparent", "Godparent"),
+        ("participant_role.officiant", "Officiant"),
+        ("participant_role.informant", "Informant"),
+        ("participant_role.primary", "Principal"),
         // ── Name type enum (short, for selects) ─────────────────────
         ("name_type_short.birth", "Birth"),
         ("name_type_short.married", "Married"),
@@ -513,6 +770,9 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("name_type_short.religious", "Religious"),
         ("name_type_short.other", "Other"),
         // ── Event type display names ────────────────────────────────
+        ("event.category.vital", "Vital records"),
+        ("event.category.family", "Family"),
+        ("event.category.other", "Other"),
         ("event.type.birth", "Birth"),
         ("event.type.death", "Death"),
         ("event.type.baptism", "Baptism"),
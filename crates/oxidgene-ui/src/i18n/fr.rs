@@ -28,6 +28,10 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("common.duplicate", "Dupliquer"),
         ("common.duplicating", "Duplication en cours\u{2026}"),
         ("common.settings", "Param\u{00E8}tres"),
+        (
+            "common.conflict",
+            "Cette fiche a \u{00E9}t\u{00E9} modifi\u{00E9}e ailleurs depuis son ouverture. Fermez-la et rouvrez-la pour charger la derni\u{00E8}re version.",
+        ),
         ("common.export", "Exporter"),
         ("common.import", "Importer"),
         ("common.exporting", "Export en cours\u{2026}"),
@@ -56,6 +60,17 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("app_settings.language", "Langue"),
         ("app_settings.language_title", "Langue"),
         ("app_settings.language_desc", "Choisissez la langue d\u{2019}affichage de l\u{2019}interface."),
+        ("app_settings.data", "Donn\u{00E9}es"),
+        ("app_settings.backups", "Sauvegardes"),
+        ("app_settings.backups_desc", "Copies de votre base de donn\u{00E9}es, prises automatiquement \u{00E0} intervalles r\u{00E9}guliers. En restaurer une remplace tous vos arbres par leur \u{00E9}tat \u{00E0} ce moment-l\u{00E0}."),
+        ("app_settings.backup_now", "Sauvegarder maintenant"),
+        ("app_settings.backup_now_hint", "Prendre une sauvegarde en plus des sauvegardes automatiques."),
+        ("app_settings.backup_created", "Sauvegarde {file} cr\u{00E9}\u{00E9}e."),
+        ("app_settings.no_backups", "Aucune sauvegarde pour l\u{2019}instant."),
+        ("app_settings.restore", "Restaurer"),
+        ("app_settings.restore_confirm_title", "Restaurer la sauvegarde"),
+        ("app_settings.restore_confirm_message", "Remplacer toutes vos donn\u{00E9}es par la sauvegarde du {date} ? Une sauvegarde des donn\u{00E9}es actuelles est prise au pr\u{00E9}alable."),
+        ("app_settings.restore_staged", "Restauration programm\u{00E9}e. Red\u{00E9}marrez OxidGene pour l\u{2019}appliquer."),
         // ── Home page ───────────────────────────────────────────────
         ("home.title_prefix", "Mes "),
         ("home.title_accent", "Arbres G\u{00E9}n\u{00E9}alogiques"),
@@ -72,6 +87,24 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("home.add_card_subtitle", "D\u{00E9}marrez une nouvelle lign\u{00E9}e familiale ou importez un fichier GEDCOM"),
         ("home.badge_recent", "R\u{00E9}cent"),
         ("home.tree_actions", "Actions sur l\u{2019}arbre"),
+        ("home.count_persons_one", "{count} personne"),
+        ("home.count_persons_other", "{count} personnes"),
+        ("home.count_families_one", "{count} famille"),
+        ("home.count_families_other", "{count} familles"),
+        ("home.count_events_one", "{count} \u{00E9}v\u{00E9}nement"),
+        ("home.count_events_other", "{count} \u{00E9}v\u{00E9}nements"),
+        ("home.count_names_one", "{count} nom"),
+        ("home.count_names_other", "{count} noms"),
+        ("home.count_sources_one", "{count} source"),
+        ("home.count_sources_other", "{count} sources"),
+        ("home.count_citations_one", "{count} citation"),
+        ("home.count_citations_other", "{count} citations"),
+        ("home.count_media_one", "{count} m\u{00E9}dia"),
+        ("home.count_media_other", "{count} m\u{00E9}dias"),
+        ("home.count_places_one", "{count} lieu"),
+        ("home.count_places_other", "{count} lieux"),
+        ("home.count_notes_one", "{count} note"),
+        ("home.count_notes_other", "{count} notes"),
         ("home.modified_today", "Modifi\u{00E9} aujourd\u{2019}hui"),
         ("home.modified_1day", "Modifi\u{00E9} hier"),
         ("home.modified_days", "Modifi\u{00E9} il y a {count} jours"),
@@ -90,15 +123,44 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("tree.search_last", "Nom / # SOSA"),
         ("tree.search_first", "Pr\u{00E9}nom"),
         ("tree.search", "Rechercher"),
+        // ── Recently edited menu ────────────────────────────────────
+        ("recent.title", "Modifi\u{00E9}s r\u{00E9}cemment"),
+        ("recent.empty", "Aucune modification pour l\u{2019}instant"),
+        ("recent.unnamed", "(sans nom)"),
+        ("recent.created", "cr\u{00E9}\u{00E9} le {date}"),
+        ("recent.updated", "modifi\u{00E9} le {date}"),
+        ("recent.kind.person", "Personne"),
+        ("recent.kind.family", "Famille"),
+        ("recent.kind.event", "\u{00C9}v\u{00E9}nement"),
+        ("recent.kind.place", "Lieu"),
+        ("recent.kind.source", "Source"),
+        ("recent.kind.media", "M\u{00E9}dia"),
+        ("recent.kind.note", "Note"),
         // ── Tree create / edit form ─────────────────────────────────
         ("tree.form.name_label", "Nom"),
         ("tree.form.name_placeholder", "ex. Arbre de la famille Martin"),
         ("tree.form.name_required", "Le nom est obligatoire"),
         ("tree.form.description_label", "Description (facultatif)"),
         ("tree.form.description_placeholder", "Une br\u{00E8}ve description\u{2026}"),
+        ("tree.form.template_self", "Commencer avec moi, mes parents et grands-parents"),
         // ── Confirm dialogs ─────────────────────────────────────────
         ("home.rename_tree", "Renommer l\u{2019}arbre"),
         ("home.duplicate_suffix", " (copie)"),
+        ("home.check_tree", "V\u{00E9}rifier l\u{2019}arbre"),
+        ("home.check_clean", "\u{00AB}\u{00A0}{name}\u{00A0}\u{00BB}\u{00A0}: aucun probl\u{00E8}me d\u{00E9}tect\u{00E9}."),
+        ("home.share_tree", "Partager en lecture seule"),
+        ("home.share_created", "\u{00AB}\u{00A0}{name}\u{00A0}\u{00BB}\u{00A0}: lien de partage cr\u{00E9}\u{00E9} (personnes vivantes masqu\u{00E9}es)."),
+        ("home.share_open", "Ouvrir le lien"),
+        ("home.share_revoke", "R\u{00E9}voquer"),
+        ("home.import_done", "\u{00AB}\u{00A0}{name}\u{00A0}\u{00BB} import\u{00E9}\u{00A0}: {counts}."),
+        ("home.check_summary", "\u{00AB}\u{00A0}{name}\u{00A0}\u{00BB}\u{00A0}: {errors} erreur(s), {warnings} avertissement(s)."),
+        ("home.import_phase.queued", "D\u{00E9}marrage\u{2026}"),
+        ("home.import_phase.parsing", "Lecture du fichier\u{2026}"),
+        ("home.import_phase.persisting", "Enregistrement de {done} / {total} fiches\u{2026}"),
+        ("home.import_phase.rebuilding_cache", "Pr\u{00E9}paration de l\u{2019}arbre\u{2026}"),
+        ("home.import_parsed", "{parsed} fiches lues, {warnings} avertissement(s)"),
+        ("home.import_cancelling", "Annulation\u{2026}"),
+        ("home.import_cancelled", "Import annul\u{00E9} ; rien n\u{2019}a \u{00E9}t\u{00E9} import\u{00E9}."),
         ("confirm.delete_tree.title", "Supprimer l\u{2019}arbre"),
         ("confirm.delete_tree.message", "\u{00CA}tes-vous s\u{00FB}r de vouloir supprimer cet arbre et toutes ses donn\u{00E9}es\u{00A0}? Cette action est irr\u{00E9}versible."),
         ("confirm.delete_tree.message_name", "Supprimer \u{00AB}\u{00A0}{name}\u{00A0}\u{00BB}\u{00A0}? Cette action est irr\u{00E9}versible."),
@@ -115,17 +177,22 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("confirm.delete_citation.message", "\u{00CA}tes-vous s\u{00FB}r de vouloir supprimer cette citation\u{00A0}?"),
         // ── Linking mode labels ─────────────────────────────────────
         ("linking.add_spouse", "Ajouter un conjoint"),
-        ("linking.add_parent", "Ajouter un parent"),
+        ("linking.add_father", "Ajouter le p\u{00E8}re"),
+        ("linking.add_mother", "Ajouter la m\u{00E8}re"),
         ("linking.add_child", "Ajouter un enfant"),
         ("linking.add_sibling", "Ajouter un fr\u{00E8}re/s\u{0153}ur"),
         ("linking.merge", "Fusionner avec\u{2026}"),
+        ("linking.relationship", "Lien de parent\u{00E9} avec\u{2026}"),
         ("linking.search_spouse", "Rechercher un conjoint\u{2026}"),
-        ("linking.search_parent", "Rechercher un parent\u{2026}"),
+        ("linking.search_father", "Rechercher le p\u{00E8}re\u{2026}"),
+        ("linking.search_mother", "Rechercher la m\u{00E8}re\u{2026}"),
         ("linking.search_child", "Rechercher un enfant\u{2026}"),
         ("linking.search_sibling", "Rechercher un fr\u{00E8}re/s\u{0153}ur\u{2026}"),
         ("linking.search_merge", "Rechercher une personne \u{00E0} fusionner\u{2026}"),
+        ("linking.search_relationship", "Rechercher un parent\u{2026}"),
         ("linking.create_spouse", "Cr\u{00E9}er une nouvelle personne comme conjoint"),
-        ("linking.create_parent", "Cr\u{00E9}er une nouvelle personne comme parent"),
+        ("linking.create_father", "Cr\u{00E9}er une nouvelle personne comme p\u{00E8}re"),
+        ("linking.create_mother", "Cr\u{00E9}er une nouvelle personne comme m\u{00E8}re"),
         ("linking.create_child", "Cr\u{00E9}er une nouvelle personne comme enfant"),
         ("linking.create_sibling", "Cr\u{00E9}er une nouvelle personne comme fr\u{00E8}re/s\u{0153}ur"),
         ("linking.search_existing", "Rechercher une personne existante \u{00E0} lier, ou en cr\u{00E9}er une nouvelle\u{00A0}:"),
@@ -133,6 +200,9 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         // ── Context menu ────────────────────────────────────────────
         ("context.edit_individual", "Modifier l\u{2019}individu"),
         ("context.merge", "Fusionner avec\u{2026}"),
+        ("context.relationship", "Lien de parent\u{00E9} avec\u{2026}"),
+        ("context.add_father", "Ajouter le p\u{00E8}re"),
+        ("context.add_mother", "Ajouter la m\u{00E8}re"),
         ("context.edit_union", "Modifier l\u{2019}union"),
         ("context.edit_union_submenu", "Modifier l\u{2019}union \u{25B8}"),
         ("context.add_spouse", "Ajouter un conjoint"),
@@ -146,6 +216,8 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("pedigree.depth", "Profondeur des g\u{00E9}n\u{00E9}rations"),
         ("pedigree.fit_screen", "Ajuster \u{00E0} l\u{2019}\u{00E9}cran"),
         ("pedigree.zoom_in", "Zoom avant"),
+        ("pedigree.color_by_sex", "Colorer les fiches par sexe"),
+        ("pedigree.color_by_surname", "Colorer les fiches par nom"),
         ("pedigree.zoom_out", "Zoom arri\u{00E8}re"),
         ("pedigree.zoom_reset", "R\u{00E9}initialiser"),
         ("pedigree.breadcrumb", "Arbre"),
@@ -167,6 +239,20 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person.vitals.age_months_other", "{n} mois"),
         ("person.vitals.age_days_one", "{n} jour"),
         ("person.vitals.age_days_other", "{n} jours"),
+        ("person.name_policy.label", "Nom affich\u{00E9}"),
+        ("person.photo.choose", "Choisir la photo de profil"),
+        ("person.photo.clear", "Retirer la photo de profil"),
+        ("media.gallery.title", "Photos et documents"),
+        ("media.gallery.load_error", "Erreur lors du chargement des m\u{00E9}dias\u{00A0}: {error}"),
+        ("media.gallery.profile", "Photo de profil"),
+        ("media.gallery.move_before", "D\u{00E9}placer avant"),
+        ("media.gallery.move_after", "D\u{00E9}placer apr\u{00E8}s"),
+        ("media.gallery.show_more", "Afficher {n} de plus"),
+        ("media.gallery.open_file", "Ouvrir le fichier"),
+        ("person.name_policy.prefer_primary", "Nom principal"),
+        ("person.name_policy.prefer_birth", "Nom de naissance"),
+        ("person.name_policy.prefer_married", "Nom d'usage"),
+        ("person.name_policy.most_recent", "Nom le plus r\u{00E9}cent"),
         ("person.family_connections", "Liens familiaux"),
         ("person.parents", "Parents"),
         ("person.spouses_partners", "Conjoints et partenaires"),
@@ -198,6 +284,30 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person.view_in_tree", "Voir dans l\u{2019}arbre"),
         ("person.edit_sex", "Modifier le sexe"),
         ("person.refresh", "Actualiser"),
+        ("plausibility.title", "Enregistr\u{00E9}, mais certaines dates semblent improbables\u{00A0}:"),
+        ("plausibility.dismiss", "Ignorer"),
+        ("plausibility.death_before_birth", "Le d\u{00E9}c\u{00E8}s est dat\u{00E9} avant la naissance."),
+        ("plausibility.married_too_young", "Un conjoint avait moins de 12\u{00A0}ans au mariage."),
+        ("plausibility.born_after_mother_death", "La naissance est dat\u{00E9}e apr\u{00E8}s le d\u{00E9}c\u{00E8}s de la m\u{00E8}re."),
+        ("plausibility.mother_too_young", "La m\u{00E8}re avait moins de 13\u{00A0}ans \u{00E0} la naissance."),
+        ("tags.add", "Ajouter l\u{2019}\u{E9}tiquette"),
+        ("tags.add_placeholder", "\u{C9}tiquette (ex. sources \u{E0} trouver)"),
+        ("tags.remove", "Retirer l\u{2019}\u{E9}tiquette"),
+        ("tasks.breadcrumb", "\u{C0} faire"),
+        ("tasks.title", "Recherches \u{E0} faire"),
+        ("tasks.person_section", "Recherches \u{E0} faire"),
+        ("tasks.none", "Aucune t\u{E2}che."),
+        ("tasks.load_error", "\u{00C9}chec du chargement des t\u{E2}ches\u{00A0}: {error}"),
+        ("tasks.add", "Ajouter la t\u{E2}che"),
+        ("tasks.add_placeholder", "T\u{E2}che (ex. trouver l\u{2019}acte de bapt\u{EA}me)"),
+        ("tasks.due_date", "\u{C9}ch\u{E9}ance"),
+        ("tasks.due", "\u{C9}ch\u{E9}ance\u{00A0}: {date}"),
+        ("tasks.toggle_done", "Marquer comme faite"),
+        ("tasks.filter_open", "\u{C0} faire"),
+        ("tasks.filter_done", "Faites"),
+        ("tasks.filter_all", "Toutes"),
+        ("tasks.family_owner", "Famille"),
+        ("tasks.invalid_tree", "Lien d\u{2019}arbre invalide."),
         ("person.no_family_connections", "Aucun lien familial enregistr\u{00E9}."),
         ("person.no_names", "Aucun nom enregistr\u{00E9}."),
         ("person.actions", "Actions"),
@@ -208,17 +318,38 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person.origin_parental", "Famille parentale"),
         ("person.loading_names", "Chargement des noms\u{2026}"),
         ("person.events_section", "\u{00C9}v\u{00E9}nements"),
+        ("person.events_flat", "Vue chronologique"),
+        ("person.events_grouped", "Grouper par cat\u{00E9}gorie"),
         ("person.new_event", "Nouvel \u{00E9}v\u{00E9}nement"),
         ("person.event_type", "Type d\u{2019}\u{00E9}v\u{00E9}nement"),
         ("person.no_events", "Aucun \u{00E9}v\u{00E9}nement enregistr\u{00E9}."),
         ("person.loading_events", "Chargement des \u{00E9}v\u{00E9}nements\u{2026}"),
         ("person.notes_section", "Notes"),
+        ("person.notes_from_families", "Des familles"),
+        ("person.note_from_parents", "Union des parents"),
+        ("person.note_from_union", "Union de la personne"),
+        ("person.well_sourced", "Bien sourc\u{E9}"),
+        ("person.needs_evidence", "Preuves \u{E0} trouver"),
+        ("person.sourcing_score", "Faits d\u{2019}\u{E9}tat civil sourc\u{E9}s\u{00A0}: {percent}\u{00A0}%"),
+        ("person.no_parents", "Parents inconnus"),
+        ("person.lock", "Verrouiller"),
+        ("person.unlock", "D\u{E9}verrouiller"),
+        ("person.lock_hint", "Verrouiller cette personne contre les modifications et la suppression"),
+        ("person.unlock_hint", "D\u{E9}verrouiller cette personne pour la modifier ou la supprimer"),
+        ("person.locked_hint", "Verrouill\u{E9}e\u{00A0}: d\u{E9}verrouiller pour modifier ou supprimer"),
+        ("person.no_parents_hint", "Aucun parent enregistr\u{E9}\u{00A0}: une piste de recherche"),
+        ("person.end_of_line", "Fin de lign\u{E9}e"),
         ("person.add_note", "Ajouter une note"),
         ("person.new_note", "Nouvelle note"),
         ("person.note_text_label", "Note"),
         ("person.no_notes", "Aucune note enregistr\u{00E9}e."),
         ("person.loading_notes", "Chargement des notes\u{2026}"),
         ("person.sources_section", "Sources"),
+        ("citation.fact.name", "nom"),
+        ("citation.fact.date", "date"),
+        ("citation.fact.place", "lieu"),
+        ("citation.fact.description", "description"),
+        ("citation.fact.age", "\u{00E2}ge"),
         ("person.add_citation", "Ajouter une citation"),
         ("person.new_citation", "Nouvelle citation"),
         ("person.source", "Source"),
@@ -238,6 +369,16 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person.no_ancestry_data", "{label}"),
         ("person.ancestry_hint", "Les anc\u{00EA}tres et descendants sont calcul\u{00E9}s \u{00E0} partir des liens familiaux."),
         // ── Person form (modal) ─────────────────────────────────────
+        ("quick_entry.title", "Saisie rapide"),
+        ("quick_entry.birth_date", "Date de naissance"),
+        ("quick_entry.birth_place", "Lieu de naissance"),
+        ("quick_entry.death_date", "Date de d\u{00E9}c\u{00E8}s"),
+        ("quick_entry.death_place", "Lieu de d\u{00E9}c\u{00E8}s"),
+        ("quick_entry.hint", "Les lieux sont retrouv\u{00E9}s par leur nom et cr\u{00E9}\u{00E9}s s\u{2019}ils sont nouveaux. Entr\u{00E9}e enregistre et passe \u{00E0} la personne suivante."),
+        ("quick_entry.name_required", "Saisissez un pr\u{00E9}nom ou un nom."),
+        ("quick_entry.created", "Cr\u{00E9}\u{00E9}\u{00A0}:"),
+        ("quick_entry.save_next", "Enregistrer et continuer"),
+        ("quick_entry.full_form", "Formulaire complet\u{2026}"),
         ("person_form.subtitle_edit", "Modifier la personne"),
         ("person_form.tab_civil", "\u{00C9}tat civil"),
         ("person_form.tab_names", "Noms"),
@@ -255,16 +396,17 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person_form.primary", "Principal"),
         ("person_form.birth", "Naissance"),
         ("person_form.death", "D\u{00E9}c\u{00E8}s"),
-        ("person_form.sacraments", "Sacrements"),
         ("person_form.other_events", "Autres \u{00E9}v\u{00E9}nements"),
         ("person_form.add_event", "Ajouter un \u{00E9}v\u{00E9}nement"),
         ("person_form.no_other_events", "Aucun autre \u{00E9}v\u{00E9}nement enregistr\u{00E9}."),
         ("person_form.notes", "Notes"),
         ("person_form.date", "Date"),
         ("person_form.place", "Lieu"),
+        ("person_form.place_to", "Destination"),
         ("person_form.type", "Type"),
         ("person_form.description", "Description"),
         ("person_form.no_place", "-- Aucun lieu --"),
+        ("place_input.create", "+ Ajouter \u{00AB}\u{00A0}{name}\u{00A0}\u{00BB} comme nouveau lieu"),
         ("person_form.save_birth", "Enregistrer la naissance"),
         ("person_form.update_birth", "Mettre \u{00E0} jour la naissance"),
         ("person_form.save_death", "Enregistrer le d\u{00E9}c\u{00E8}s"),
@@ -272,6 +414,7 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person_form.given_or_surname_required", "Pr\u{00E9}nom ou nom de famille obligatoire"),
         ("person_form.note_required", "Le texte de la note est obligatoire"),
         ("person_form.note_placeholder", "Saisissez le texte de la note\u{2026}"),
+        ("person_form.note_markdown", "Markdown (titres, listes, liens)"),
         ("person_form.date_placeholder", "ex. 1 janv. 1900"),
         ("person_form.date_placeholder_long", "ex. 1 janv. 1900, ABT 1850, BET 1800 AND 1810"),
         ("person_form.death_date_placeholder", "ex. 15 mars 1975"),
@@ -284,9 +427,6 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person_form.loading_names", "Chargement des noms\u{2026}"),
         ("person_form.no_notes", "Aucune note enregistr\u{00E9}e."),
         ("person_form.add_note", "Ajouter une note"),
-        ("person_form.civil", "Civil"),
-        ("person_form.legal", "L\u{00E9}gal"),
-        ("person_form.attributes", "Attributs"),
         ("person_form.discard_title", "Abandonner les modifications\u{00A0}?"),
         ("person_form.discard_message", "Vous avez des modifications non enregistr\u{00E9}es. \u{00CA}tes-vous s\u{00FB}r de vouloir fermer\u{00A0}?"),
         ("person_form.discard_confirm", "Abandonner"),
@@ -300,15 +440,16 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("person_form.delete_confirm_button", "Confirmer la suppression"),
         ("person_form.deleting", "Suppression\u{2026}"),
         ("person_form.privacy", "Confidentialit\u{00E9}"),
+        ("person_form.living_status", "Statut vital"),
         ("person_form.date_qualifier", "Qualificatif"),
         ("person_form.note", "Note"),
         ("person_form.cause", "Cause"),
         ("person_form.calendar", "Calendrier"),
-        ("person_form.witnesses", "T\u{00E9}moins"),
-        ("person_form.add_witness", "+ Ajouter un t\u{00E9}moin"),
+        ("person_form.witnesses", "Participants"),
+        ("person_form.add_witness", "+ Ajouter un participant"),
         (
             "person_form.witnesses_save_first",
-            "Enregistrez d'abord cet \u{00E9}v\u{00E9}nement pour ajouter des t\u{00E9}moins.",
+            "Enregistrez d'abord cet \u{00E9}v\u{00E9}nement pour ajouter des participants.",
         ),
         ("person_form.witness_relation_placeholder", "Relation (ex. Marraine)"),
         ("person_form.search_witness", "Rechercher une personne\u{2026}"),
@@ -320,6 +461,9 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("privacy.default", "Par d\u{00E9}faut"),
         ("privacy.public", "Public"),
         ("privacy.private", "Priv\u{00E9}"),
+        ("living.auto", "D'apr\u{00E8}s les dates"),
+        ("living.living", "Vivant"),
+        ("living.deceased", "D\u{00E9}c\u{00E9}d\u{00E9}"),
         // ── Date qualifiers ──────────────────────────────────────────
         ("date_qualifier.exact", "Exact"),
         ("date_qualifier.about", "Environ"),
@@ -336,6 +480,12 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("calendar.french_republican", "R\u{00E9}publicain fran\u{00E7}ais"),
         // ── Union form (modal) ──────────────────────────────────────
         ("union_form.title", "Modifier l\u{2019}union"),
+        ("family_card.married", "Mari\u{E9}s le {date}"),
+        ("family_card.married_at", "Mari\u{E9}s le {date}, {place}"),
+        ("family_card.married_in", "Mari\u{E9}s \u{E0} {place}"),
+        ("family_card.no_marriage", "Aucun mariage enregistr\u{E9}"),
+        ("family_card.divorced", "Divorc\u{E9}s le {date}"),
+        ("family_card.divorce_before_marriage", "Divorce dat\u{E9} avant le mariage"),
         ("union_form.spouses", "Conjoints"),
         ("union_form.children", "Enfants"),
         ("union_form.events", "\u{00C9}v\u{00E9}nements de l\u{2019}union"),
@@ -362,6 +512,8 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("union_form.person1", "Personne 1\u{00A0}: {name}"),
         ("union_form.person2", "Personne 2\u{00A0}: {name}"),
         ("union_form.detach_button", "D\u{00E9}tacher"),
+        ("union_form.drag_to_reorder", "Glisser pour r\u{00E9}ordonner"),
+        ("union_form.sort_by_birth", "Trier par \u{00E2}ge"),
         ("union_form.undo_detach", "Annuler"),
         ("union_form.detach_confirm_title", "D\u{00E9}tacher {name} de cette union\u{00A0}?"),
         (
@@ -377,18 +529,32 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ),
         ("union_form.delete_confirm_button", "Confirmer la suppression"),
         ("union_form.deleting", "Suppression\u{2026}"),
+        ("union_form.notes", "Notes"),
+        ("union_form.no_notes", "Aucune note sur cette union."),
+        ("union_form.sources", "Sources"),
+        ("union_form.no_sources", "Aucune source cit\u{E9}e pour cette union."),
+        ("union_form.add_source", "+ Citer une source"),
+        ("union_form.source", "Source"),
+        ("union_form.choose_source", "Choisir une source\u{2026}"),
+        ("union_form.page", "Page"),
+        ("union_form.source_required", "Choisissez la source \u{E0} citer"),
         // ── Search person component ─────────────────────────────────
         ("search.placeholder", "Rechercher une personne\u{2026}"),
         ("search.loading", "Chargement des personnes\u{2026}"),
         ("search.no_match", "Aucune personne correspondante."),
+        ("search.also_known_as", "alias {name}"),
         // ── Search results page ───────────────────────────────────────
         ("search.title", "Recherche"),
         ("search.go", "Rechercher"),
         ("search.filters", "Filtres"),
+        ("search.surname_index", "Patronymes A\u{2013}Z"),
+        ("search.surname_index_empty", "Aucun patronyme enregistr\u{00E9}."),
         ("search.gender", "Sexe"),
+        ("search.tag", "\u{C9}tiquette"),
         ("search.all", "Tous"),
         ("search.male", "Masculin"),
         ("search.female", "F\u{00E9}minin"),
+        ("search.intersex", "Intersexe"),
         ("search.unknown", "Inconnu"),
         ("search.born_between", "N\u{00E9}(e) entre"),
         ("search.died_between", "D\u{00E9}c\u{00E9}d\u{00E9}(e) entre"),
@@ -401,6 +567,22 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("search.sort_birth_desc", "Date de naissance \u{2193}"),
         ("search.no_results", "Aucune personne trouv\u{00E9}e. Essayez de modifier vos termes de recherche."),
         ("search.error", "Une erreur est survenue lors de la recherche."),
+        ("search.select_page", "S\u{00E9}lectionner cette page"),
+        ("search.selected_one", "{count} s\u{00E9}lectionn\u{00E9}e"),
+        ("search.selected_other", "{count} s\u{00E9}lectionn\u{00E9}es"),
+        ("search.detach_families", "D\u{00E9}tacher des familles"),
+        ("search.delete_selected", "Supprimer la s\u{00E9}lection"),
+        ("search.clear_selection", "Vider la s\u{00E9}lection"),
+        ("search.confirm_bulk_delete_one", "Supprimer {count} personne\u{00A0}? Les personnes encore dans une famille sont conserv\u{00E9}es."),
+        ("search.confirm_bulk_delete_other", "Supprimer {count} personnes\u{00A0}? Les personnes encore dans une famille sont conserv\u{00E9}es."),
+        ("search.confirm_bulk_delete_detach_one", "Supprimer {count} personne\u{00A0}? Elle est retir\u{00E9}e de ses familles, et les familles devenues vides sont supprim\u{00E9}es."),
+        ("search.confirm_bulk_delete_detach_other", "Supprimer {count} personnes\u{00A0}? Elles sont retir\u{00E9}es de leurs familles, et les familles devenues vides sont supprim\u{00E9}es."),
+        ("search.bulk_deleted_one", "{count} personne supprim\u{00E9}e"),
+        ("search.bulk_deleted_other", "{count} personnes supprim\u{00E9}es"),
+        ("search.bulk_skipped_family_one", "{count} conserv\u{00E9}e\u{00A0}: encore dans une famille"),
+        ("search.bulk_skipped_family_other", "{count} conserv\u{00E9}es\u{00A0}: encore dans une famille"),
+        ("search.bulk_skipped_locked_one", "{count} conserv\u{00E9}e\u{00A0}: verrouill\u{00E9}e"),
+        ("search.bulk_skipped_locked_other", "{count} conserv\u{00E9}es\u{00A0}: verrouill\u{00E9}es"),
         ("search.view_list", "Vue liste"),
         ("search.view_grid", "Vue grille d'ascendances"),
         // ── Dictionary page ─────────────────────────────────────────
@@ -409,6 +591,15 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("dictionary.tab.sources", "Sources"),
         ("dictionary.tab.places", "Lieux"),
         ("dictionary.tab.occupations", "Professions"),
+        ("dictionary.tab.distribution", "R\u{E9}partition"),
+        ("stats.surnames.over", "Noms par"),
+        ("stats.surnames.by_decade", "D\u{E9}cennie de naissance"),
+        ("stats.surnames.by_place", "Lieu de naissance"),
+        ("stats.surnames.hint", "Les noms les plus fr\u{E9}quents, compt\u{E9}s d\u{2019}apr\u{E8}s la naissance de chaque personne. Plus une case est fonc\u{E9}e, plus elle compte de naissances."),
+        ("stats.surnames.decade", "Ann\u{E9}es {decade}"),
+        ("stats.surnames.empty", "Aucune naissance dat\u{E9}e ou localis\u{E9}e pour l\u{2019}instant."),
+        ("stats.surnames.more_places_one", "{count} autre lieu non affich\u{E9}"),
+        ("stats.surnames.more_places_other", "{count} autres lieux non affich\u{E9}s"),
         ("dictionary.letter_all", "Tout"),
         ("dictionary.filter_placeholder", "Filtrer..."),
         ("dictionary.page_size_all", "Tout"),
@@ -442,6 +633,49 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("dictionary.no_matches", "Aucune entr\u{00E9}e ne correspond."),
         ("dictionary.clear_filter", "Effacer le filtre"),
         ("dictionary.view_usage", "Voir les usages"),
+        ("dictionary.open_source", "Ouvrir la source"),
+        ("source_detail.usage_title", "Cit\u{00E9}e pour"),
+        ("source_detail.no_usage", "Cette source n\u{2019}est encore cit\u{00E9}e nulle part."),
+        ("source_detail.invalid_id", "Lien de source invalide."),
+        ("source_detail.kind.person", "Personne"),
+        ("source_detail.kind.event", "\u{00C9}v\u{00E9}nement"),
+        ("source_detail.kind.family", "Famille"),
+        ("compare.breadcrumb", "Comparer"),
+        ("compare.title", "Comparer deux personnes"),
+        ("compare.invalid_ids", "Lien de comparaison invalide."),
+        ("compare.agreement_count_one", "{count} concordance"),
+        ("compare.agreement_count_other", "{count} concordances"),
+        ("compare.difference_count_one", "{count} diff\u{00E9}rence"),
+        ("compare.difference_count_other", "{count} diff\u{00E9}rences"),
+        ("compare.same", "Identique"),
+        ("compare.compatible", "Compatible"),
+        ("compare.different", "Diff\u{00E9}rent"),
+        ("compare.partial", "D\u{2019}un seul c\u{00F4}t\u{00E9}"),
+        ("compare.field.name", "Nom"),
+        ("compare.field.other_names", "Autres noms"),
+        ("compare.field.sex", "Sexe"),
+        ("compare.field.birth_date", "Date de naissance"),
+        ("compare.field.birth_place", "Lieu de naissance"),
+        ("compare.field.death_date", "Date de d\u{00E9}c\u{00E8}s"),
+        ("compare.field.death_place", "Lieu de d\u{00E9}c\u{00E8}s"),
+        ("compare.field.parents", "Parents"),
+        ("compare.field.spouses", "Conjoints"),
+        // ── Relationship path ───────────────────────────────────────
+        ("relationship.breadcrumb", "Parent\u{00E9}"),
+        ("relationship.title", "Quel lien de parent\u{00E9} ?"),
+        ("relationship.invalid_ids", "Lien de parent\u{00E9} invalide."),
+        ("relationship.unrelated", "Aucun anc\u{00EA}tre commun : ces personnes n'ont pas de lien de sang dans cet arbre."),
+        ("relationship.common_ancestor", "Anc\u{00EA}tre commun : {name}"),
+        ("relationship.generations_up_one", "{count} g\u{00E9}n\u{00E9}ration en remontant"),
+        ("relationship.generations_up_other", "{count} g\u{00E9}n\u{00E9}rations en remontant"),
+        ("relationship.generations_down_one", "{count} en descendant"),
+        ("relationship.generations_down_other", "{count} en descendant"),
+        ("kinship.father", "p\u{00E8}re"),
+        ("kinship.mother", "m\u{00E8}re"),
+        ("kinship.parent", "parent"),
+        ("kinship.son", "fils"),
+        ("kinship.daughter", "fille"),
+        ("kinship.child", "enfant"),
         ("dictionary.view_in_search", "Voir dans la recherche"),
         ("dictionary.usage_empty", "Aucune personne trouv\u{00E9}e."),
         ("dictionary.usage_error", "\u{00C9}chec du chargement des usages."),
@@ -493,6 +727,17 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("settings.export_success", "Export termin\u{00E9} avec succ\u{00E8}s."),
         ("settings.export_saved_to", "Export enregistr\u{00E9} dans {path}."),
         ("settings.export_write_error", "Impossible d\u{2019}enregistrer le fichier export\u{00E9}\u{00A0}: {error}"),
+        ("settings.export_preparing", "Pr\u{00E9}paration de l\u{2019}export ({total} enregistrements)\u{2026}"),
+        ("settings.export_progress", "{done} / {total} enregistrements export\u{00E9}s"),
+        ("settings.export_packaging", "Compression de l\u{2019}archive\u{2026}"),
+        ("settings.geocoding", "G\u{00E9}ocodage"),
+        ("settings.geocoding_desc", "Recherche les coordonn\u{00E9}es de chaque lieu qui n\u{2019}en a pas. Le traitement s\u{2019}ex\u{00E9}cute en arri\u{00E8}re-plan au rythme autoris\u{00E9} par le fournisseur\u{00A0}; les lieux aux correspondances multiples vous sont laiss\u{00E9}s."),
+        ("settings.geocoding_start", "G\u{00E9}ocoder tous les lieux"),
+        ("settings.geocoding_running", "G\u{00E9}ocodage..."),
+        ("settings.geocoding_progress", "{done} / {total} lieux"),
+        ("settings.geocoding_summary", "{succeeded} g\u{00E9}ocod\u{00E9}s, {ambiguous} ambigus, {failed} en \u{00E9}chec."),
+        ("settings.geocoding_ambiguous", "{count} emplacements possibles"),
+        ("settings.geocoding_no_match", "aucune correspondance"),
         ("settings.coming_soon", "Bient\u{00F4}t disponible"),
         ("settings.coming_soon_desc", "Cette section est en cours de d\u{00E9}veloppement."),
         // ── 404 page ────────────────────────────────────────────────
@@ -500,12 +745,18 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("not_found.message_prefix", "La page \u{00AB}\u{00A0}"),
         ("not_found.message_suffix", "\u{00A0}\u{00BB} n\u{2019}existe pas."),
         ("not_found.go_home", "Retour \u{00E0} l\u{2019}accueil"),
+        // ── Shared tree ─────────────────────────────────────────────
+        ("shared.read_only", "Vue partag\u{00E9}e en lecture seule. Les personnes vivantes sont masqu\u{00E9}es."),
+        ("shared.living", "(vivant)"),
+        ("shared.unavailable", "Lien indisponible"),
+        ("shared.unavailable_hint", "Ce lien de partage a expir\u{00E9} ou a \u{00E9}t\u{00E9} r\u{00E9}voqu\u{00E9}."),
         // ── GEDCOM I/O ──────────────────────────────────────────────
         ("gedcom.select_file", "S\u{00E9}lectionner un fichier GEDCOM"),
         ("gedcom.save_file", "Enregistrer le fichier GEDCOM"),
         // ── Sex enum display ────────────────────────────────────────
         ("sex.male", "Masculin"),
         ("sex.female", "F\u{00E9}minin"),
+        ("sex.intersex", "Intersexe"),
         ("sex.unknown", "Inconnu"),
         // ── Name type enum display ──────────────────────────────────
         ("name_type.birth", "Nom de naissance"),
@@ -514,6 +765,12 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("name_type.maiden", "Nom de jeune fille"),
         ("name_type.religious", "Nom religieux"),
         ("name_type.other", "Autre"),
+        // ── Event participant roles ─────────────────────────────────
+        ("participant_role.witness", "T\u{00E9}moin"),
+        ("participant_role.godparent", "Parrain / marraine"),
+        ("participant_role.officiant", "Officiant"),
+        ("participant_role.informant", "D\u{00E9}clarant"),
+        ("participant_role.primary", "Principal"),
         // ── Name type enum (short, for selects) ─────────────────────
         ("name_type_short.birth", "Naissance"),
         ("name_type_short.married", "Marital"),
@@ -522,6 +779,9 @@ static TRANSLATIONS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
         ("name_type_short.religious", "Religieux"),
         ("name_type_short.other", "Autre"),
         // ── Event type display names ────────────────────────────────
+        ("event.category.vital", "\u{00C9}tat civil"),
+        ("event.category.family", "Famille"),
+        ("event.category.other", "Autres"),
         ("event.type.birth", "Naissance"),
         ("event.type.death", "D\u{00E9}c\u{00E8}s"),
         ("event.type.baptism", "Bapt\u{00EA}me"),
@@ -5,6 +5,7 @@
 //! - [`router::Route`] — compile-time-checked routing
 //! - [`components`] — shared layout and reusable UI widgets
 //! - [`pages`] — one component per route
+//! - [`session::Session`] — where the app left off (desktop)
 //! - [`App`] — top-level application component
 
 pub mod api;
@@ -12,6 +13,7 @@ pub mod components;
 pub mod i18n;
 pub mod pages;
 pub mod router;
+pub mod session;
 pub mod utils;
 
 use dioxus::prelude::*;
@@ -1,16 +1,22 @@
-//! Application-level settings page (theme, language).
+//! Application-level settings page (theme, language, database backups).
 
 use dioxus::prelude::*;
 
+use crate::api::{ApiClient, Backup};
+use crate::components::confirm_dialog::ConfirmDialog;
 use crate::components::layout::set_theme;
 use crate::i18n::{self, Language, use_i18n};
 use crate::router::Route;
 
+/// Backups only exist for the desktop app's embedded SQLite database.
+const SHOW_BACKUPS: bool = cfg!(not(target_arch = "wasm32"));
+
 /// Sidebar sections.
 #[derive(Clone, Copy, PartialEq)]
 enum Section {
     Appearance,
     Language,
+    Backups,
 }
 
 #[component]
@@ -58,6 +64,18 @@ pub fn AppSettings() -> Element {
                             {i18n.t("app_settings.language")}
                         }
                     }
+                    if SHOW_BACKUPS {
+                        div { class: "settings-nav-group",
+                            span { class: "settings-nav-group-label",
+                                {i18n.t("app_settings.data")}
+                            }
+                            button {
+                                class: if *active_section.read() == Section::Backups { "settings-nav-item active" } else { "settings-nav-item" },
+                                onclick: move |_| active_section.set(Section::Backups),
+                                {i18n.t("app_settings.backups")}
+                            }
+                        }
+                    }
                 }
 
                 // ── Content area ────────────────────────────
@@ -69,6 +87,9 @@ pub fn AppSettings() -> Element {
                         Section::Language => rsx! {
                             LanguageSection { lang_signal }
                         },
+                        Section::Backups => rsx! {
+                            BackupsSection {}
+                        },
                     }
                 }
             }
@@ -184,6 +205,161 @@ pub fn LanguageSection(lang_signal: Signal<Language>) -> Element {
     }
 }
 
+// ── Backups section ─────────────────────────────────────────────────────────
+
+/// Database backups: take one now, or stage one to replace the database on
+/// the next launch.
+#[component]
+fn BackupsSection() -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+    let mut refresh = use_signal(|| 0u32);
+    let mut busy = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let mut message = use_signal(|| None::<String>);
+    let mut confirm_restore = use_signal(|| None::<Backup>);
+
+    let api_list = api.clone();
+    let backups = use_resource(move || {
+        let api = api_list.clone();
+        let _tick = refresh();
+        async move { api.list_backups().await }
+    });
+
+    let api_create = api.clone();
+    let on_create = move |_| {
+        let api = api_create.clone();
+        spawn(async move {
+            busy.set(true);
+            error.set(None);
+            message.set(None);
+            match api.create_backup().await {
+                Ok(b) => {
+                    message.set(Some(
+                        i18n.t_args("app_settings.backup_created", &[("file", &b.file_name)]),
+                    ));
+                    refresh += 1;
+                }
+                Err(e) => error.set(Some(format!("{e}"))),
+            }
+            busy.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "settings-section",
+            span { class: "settings-section-eyebrow", {i18n.t("app_settings.data")} }
+            h2 { class: "settings-section-title", {i18n.t("app_settings.backups")} }
+            p { class: "settings-section-subtitle", {i18n.t("app_settings.backups_desc")} }
+
+            div { class: "app-settings-card",
+                div { class: "app-settings-option",
+                    div { class: "app-settings-option-info",
+                        span { class: "app-settings-option-label", {i18n.t("app_settings.backup_now")} }
+                        span { class: "app-settings-option-hint", {i18n.t("app_settings.backup_now_hint")} }
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        disabled: busy(),
+                        onclick: on_create,
+                        {i18n.t("app_settings.backup_now")}
+                    }
+                }
+                if let Some(err) = error() {
+                    div { class: "error-msg", style: "margin-top: 12px;", "{err}" }
+                }
+                if let Some(msg) = message() {
+                    div { class: "success-msg", style: "margin-top: 12px;", "{msg}" }
+                }
+
+                match &*backups.read() {
+                    Some(Ok(list)) if list.is_empty() => rsx! {
+                        p { class: "backup-empty", {i18n.t("app_settings.no_backups")} }
+                    },
+                    Some(Ok(list)) => rsx! {
+                        ul { class: "backup-list",
+                            for b in list.iter() {
+                                li { key: "{b.file_name}", class: "backup-row",
+                                    {
+                                        let target = b.clone();
+                                        rsx! {
+                                            span { class: "backup-date", {format_backup_date(b)} }
+                                            span { class: "backup-size", {format_size(b.size_bytes)} }
+                                            button {
+                                                class: "btn btn-outline btn-sm",
+                                                disabled: busy(),
+                                                onclick: move |_| confirm_restore.set(Some(target.clone())),
+                                                {i18n.t("app_settings.restore")}
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    Some(Err(e)) => rsx! {
+                        div { class: "error-msg", style: "margin-top: 12px;", "{e}" }
+                    },
+                    None => rsx! {
+                        p { class: "backup-empty", {i18n.t("common.loading")} }
+                    },
+                }
+            }
+        }
+
+        if let Some(target) = confirm_restore() {
+            ConfirmDialog {
+                title: i18n.t("app_settings.restore_confirm_title"),
+                message: i18n.t_args(
+                    "app_settings.restore_confirm_message",
+                    &[("date", &format_backup_date(&target))],
+                ),
+                confirm_label: i18n.t("app_settings.restore"),
+                confirm_class: "btn btn-danger",
+                on_confirm: move |_| {
+                    let api = api.clone();
+                    let file_name = target.file_name.clone();
+                    confirm_restore.set(None);
+                    spawn(async move {
+                        busy.set(true);
+                        error.set(None);
+                        message.set(None);
+                        match api.restore_backup(&file_name).await {
+                            Ok(_) => {
+                                message.set(Some(i18n.t("app_settings.restore_staged")));
+                                refresh += 1;
+                            }
+                            Err(e) => error.set(Some(format!("{e}"))),
+                        }
+                        busy.set(false);
+                    });
+                },
+                on_cancel: move |_| confirm_restore.set(None),
+            }
+        }
+    }
+}
+
+/// When a backup was taken, in local time.
+fn format_backup_date(backup: &Backup) -> String {
+    backup
+        .created_at
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+/// Human-readable file size ("1.4 MB").
+fn format_size(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    let b = bytes as f64;
+    if b >= KB * KB {
+        format!("{:.1} MB", b / (KB * KB))
+    } else {
+        format!("{:.0} KB", (b / KB).ceil())
+    }
+}
+
 // ── Styles ──────────────────────────────────────────────────────────────────
 
 /// Styles for the [`AppearanceSection`] / [`LanguageSection`] widgets only
@@ -257,6 +433,37 @@ pub(crate) const APP_SETTINGS_WIDGET_STYLES: &str = r#"
         color: var(--white);
     }
 
+    .backup-list {
+        list-style: none;
+        margin: 1rem 0 0;
+        padding: 0;
+        border-top: 1px solid var(--border);
+    }
+
+    .backup-row {
+        display: flex;
+        align-items: center;
+        gap: 1rem;
+        padding: 0.5rem 0;
+        border-bottom: 1px solid var(--border);
+        font-size: 0.85rem;
+    }
+
+    .backup-date {
+        flex: 1;
+        color: var(--text-primary);
+    }
+
+    .backup-size {
+        color: var(--text-muted);
+    }
+
+    .backup-empty {
+        margin: 1rem 0 0;
+        font-size: 0.85rem;
+        color: var(--text-muted);
+    }
+
     .lang-options {
         display: flex;
         flex-direction: column;
@@ -1,5 +1,6 @@
 //! Dictionary page: read-only index of family names, sources, places, and
-//! occupations across a tree, each paired with a usage count. See
+//! occupations across a tree, each paired with a usage count, plus the
+//! distribution of surnames over birth decades and places. See
 //! `docs/specifications/ui-dictionary.md`.
 
 use std::collections::HashSet;
@@ -12,6 +13,7 @@ use crate::api::{
     SourceDictionaryEntry, SourceGroupEntry,
 };
 use crate::components::pedigree_chart::format_lifespan;
+use crate::components::surname_distribution::SurnameDistribution;
 use crate::components::tree_cache::{fetch_tree_cached, use_tree_cache};
 use crate::components::tree_icon_sidebar::{TreeIconSidebar, TreeSidebarView};
 use crate::i18n::{I18n, Language, use_i18n};
@@ -47,6 +49,7 @@ enum DictTab {
     Sources,
     Places,
     Occupations,
+    Distribution,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -318,6 +321,14 @@ pub fn Dictionary(tree_id: String) -> Element {
                 },
                 on_add_person: move |_| {},
                 on_dictionary: move |_| {},
+                on_tasks: {
+                    let tree_id = tree_id.clone();
+                    move |_| {
+                        nav.push(Route::Tasks {
+                            tree_id: tree_id.clone(),
+                        });
+                    }
+                },
                 on_settings: {
                     let tree_id = tree_id.clone();
                     move |_| {
@@ -350,6 +361,11 @@ pub fn Dictionary(tree_id: String) -> Element {
                         onclick: move |_| active_tab.set(DictTab::Occupations),
                         {i18n.t("dictionary.tab.occupations")}
                     }
+                    button {
+                        class: if active_tab() == DictTab::Distribution { "dict-tab active" } else { "dict-tab" },
+                        onclick: move |_| active_tab.set(DictTab::Distribution),
+                        {i18n.t("dictionary.tab.distribution")}
+                    }
                 }
 
                 match active_tab() {
@@ -399,6 +415,12 @@ pub fn Dictionary(tree_id: String) -> Element {
                         expanded,
                         usage_resource,
                     ),
+                    DictTab::Distribution => match tree_id_parsed() {
+                        Some(tid) => rsx! {
+                            SurnameDistribution { tree_id: tid }
+                        },
+                        None => rsx! {},
+                    },
                 }
             }
             }
@@ -1027,6 +1049,15 @@ fn render_sources_list(
                                         }
                                     }
                                     span { class: "dict-row-count", {i18n.t_plural("dictionary.citation_count", entry.count as usize)} }
+                                    Link {
+                                        to: Route::SourceDetail {
+                                            tree_id: tree_id.to_string(),
+                                            source_id: entry.source.id.to_string(),
+                                        },
+                                        class: "dict-row-action",
+                                        title: "{i18n.t(\"dictionary.open_source\")}",
+                                        "\u{2197}"
+                                    }
                                     button {
                                         class: "dict-row-action",
                                         title: "{i18n.t(\"dictionary.view_usage\")}",
@@ -4,10 +4,16 @@ use chrono::Utc;
 use dioxus::prelude::*;
 use uuid::Uuid;
 
-use crate::api::{ApiClient, CreateTreeBody, DuplicateTreeBody, UpdateTreeBody};
+use oxidgene_core::templates::TreeTemplate;
+use oxidgene_core::types::ShareLink;
+
+use crate::api::{
+    ApiClient, CreateShareLinkBody, CreateTreeBody, DuplicateTreeBody, UpdateTreeBody,
+};
 use crate::components::confirm_dialog::ConfirmDialog;
 use crate::i18n::use_i18n;
 use crate::router::Route;
+use crate::session::use_session;
 
 /// Dashboard shown at `/`.
 #[component]
@@ -22,10 +28,30 @@ pub fn Home() -> Element {
         async move { api.list_trees(Some(100), None).await }
     });
 
+    // Reopen the tree the app was closed on, once the trees are listed
+    // (and only if it still exists).
+    let session = use_session();
+    let nav = use_navigator();
+    use_effect(move || {
+        let Some(Ok(trees)) = &*trees_resource.read() else {
+            return;
+        };
+        let Some(tree_id) = session.as_ref().and_then(|s| s.take_resume()) else {
+            return;
+        };
+        if trees.edges.iter().any(|e| e.node.tree.id == tree_id) {
+            nav.replace(Route::TreeDetail {
+                tree_id: tree_id.to_string(),
+                person: None,
+            });
+        }
+    });
+
     // Create form state.
     let mut show_create = use_signal(|| false);
     let mut new_name = use_signal(String::new);
     let mut new_desc = use_signal(String::new);
+    let mut new_from_template = use_signal(|| false);
     let mut form_error = use_signal(|| None::<String>);
 
     // Delete confirmation state.
@@ -37,6 +63,11 @@ pub fn Home() -> Element {
     let mut import_error = use_signal(|| None::<String>);
     let mut import_result = use_signal(|| None::<(String, crate::api::ImportGedcomResult)>);
     let mut importing_tree_id = use_signal(|| None::<Uuid>);
+    let mut import_progress = use_signal(|| None::<crate::api::ImportProgress>);
+    // Server job id of the running import, for the Cancel button.
+    let mut import_job_id = use_signal(|| None::<Uuid>);
+    let mut cancelling_import = use_signal(|| false);
+    let api_cancel = api.clone();
 
     // Rename state.
     let mut rename_tree_id = use_signal(|| None::<Uuid>);
@@ -46,6 +77,12 @@ pub fn Home() -> Element {
     // Duplicate state.
     let mut duplicating_tree_id = use_signal(|| None::<Uuid>);
 
+    // Integrity check state.
+    let mut check_report = use_signal(|| None::<(String, crate::api::IntegrityReport)>);
+
+    // Share link state: tree name + the freshly minted link.
+    let mut share_link = use_signal(|| None::<(String, ShareLink)>);
+
     // Search & sort state.
     let mut search_query = use_signal(String::new);
     let mut sort_mode = use_signal(|| "recent".to_string());
@@ -66,10 +103,16 @@ pub fn Home() -> Element {
                 name,
                 description: if desc.is_empty() { None } else { Some(desc) },
             };
-            match api.create_tree(&body).await {
+            let template = if new_from_template() {
+                TreeTemplate::HomePerson
+            } else {
+                TreeTemplate::Empty
+            };
+            match api.create_tree(&body, template).await {
                 Ok(_) => {
                     new_name.set(String::new());
                     new_desc.set(String::new());
+                    new_from_template.set(false);
                     show_create.set(false);
                     form_error.set(None);
                     refresh_counter += 1;
@@ -190,8 +233,8 @@ pub fn Home() -> Element {
                             if query.is_empty() {
                                 return true;
                             }
-                            let name_match = edge.node.name.to_lowercase().contains(&query);
-                            let desc_match = edge.node.description.as_deref()
+                            let name_match = edge.node.tree.name.to_lowercase().contains(&query);
+                            let desc_match = edge.node.tree.description.as_deref()
                                 .unwrap_or("")
                                 .to_lowercase()
                                 .contains(&query);
@@ -200,8 +243,8 @@ pub fn Home() -> Element {
 
                         // Sort
                         match sort.as_str() {
-                            "name" => filtered.sort_by_key(|a| a.node.name.to_lowercase()),
-                            _ => filtered.sort_by_key(|b| std::cmp::Reverse(b.node.updated_at)),
+                            "name" => filtered.sort_by_key(|a| a.node.tree.name.to_lowercase()),
+                            _ => filtered.sort_by_key(|b| std::cmp::Reverse(b.node.last_modified_at)),
                         }
 
                         if conn.edges.is_empty() {
@@ -241,7 +284,8 @@ pub fn Home() -> Element {
                             rsx! {
                                 div { class: "trees-grid",
                                     for edge in filtered.iter() {{
-                                        let tree = &edge.node;
+                                        let summary = &edge.node;
+                                        let tree = &summary.tree;
                                         let tid = tree.id;
                                         let tid_str = tid.to_string();
                                         let tree_name = tree.name.clone();
@@ -249,18 +293,30 @@ pub fn Home() -> Element {
                                         let tree_name_rename = tree_name.clone();
                                         let tree_name_import = tree_name.clone();
                                         let tree_name_dup = tree_name.clone();
+                                        let tree_name_check = tree_name.clone();
+                                        let tree_name_share = tree_name.clone();
                                         let desc = tree.description.clone().unwrap_or_default();
-                                        let updated_at = tree.updated_at;
+                                        let updated_at = summary.last_modified_at;
+                                        let person_count = summary.person_count;
+                                        let family_count = summary.family_count;
+                                        let event_count = summary.event_count;
+                                        let years = summary.first_year.zip(summary.last_year);
                                         let is_importing = importing_tree_id() == Some(tid);
                                         let is_duplicating = duplicating_tree_id() == Some(tid);
                                         let api_import = api.clone();
                                         let api_dup = api.clone();
+                                        let api_check = api.clone();
+                                        let api_share = api.clone();
                                         rsx! {
                                             TreeCard {
                                                 key: "{tid}",
                                                 name: tree_name,
                                                 description: desc,
                                                 updated_at,
+                                                person_count,
+                                                family_count,
+                                                event_count,
+                                                years,
                                                 tree_id: tid_str,
                                                 importing: is_importing,
                                                 duplicating: is_duplicating,
@@ -287,6 +343,29 @@ pub fn Home() -> Element {
                                                         }
                                                     });
                                                 },
+                                                on_check: move |_| {
+                                                    let api = api_check.clone();
+                                                    let name = tree_name_check.clone();
+                                                    spawn(async move {
+                                                        check_report.set(None);
+                                                        match api.check_tree(tid).await {
+                                                            Ok(report) => check_report.set(Some((name, report))),
+                                                            Err(e) => import_error.set(Some(format!("{e}"))),
+                                                        }
+                                                    });
+                                                },
+                                                on_share: move |_| {
+                                                    let api = api_share.clone();
+                                                    let name = tree_name_share.clone();
+                                                    spawn(async move {
+                                                        share_link.set(None);
+                                                        let body = CreateShareLinkBody { privatize: true, expires_at: None };
+                                                        match api.create_share_link(tid, &body).await {
+                                                            Ok(link) => share_link.set(Some((name, link))),
+                                                            Err(e) => import_error.set(Some(format!("{e}"))),
+                                                        }
+                                                    });
+                                                },
                                                 on_delete: move |_| {
                                                     confirm_delete_id.set(Some(tid));
                                                     confirm_delete_name.set(tree_name_del.clone());
@@ -305,6 +384,9 @@ pub fn Home() -> Element {
                                                         let Some(file) = file else { return };
 
                                                         importing_tree_id.set(Some(tid));
+                                                        import_progress.set(None);
+                                                        import_job_id.set(None);
+                                                        cancelling_import.set(false);
                                                         import_error.set(None);
                                                         import_result.set(None);
 
@@ -317,12 +399,18 @@ pub fn Home() -> Element {
                                                                 return;
                                                             }
                                                         };
-                                                        match api.import_gedcom(tid, &gedcom).await {
+                                                        let on_started = move |job_id| import_job_id.set(Some(job_id));
+                                                        let on_progress = move |p| import_progress.set(Some(p));
+                                                        match api.import_gedcom(tid, &gedcom, on_started, on_progress).await {
                                                             Ok(result) => {
                                                                 import_result.set(Some((name_for_result, result)));
                                                                 importing_tree_id.set(None);
                                                                 refresh_counter += 1;
                                                             }
+                                                            Err(crate::api::ApiError::Cancelled) => {
+                                                                import_error.set(Some(i18n.t("home.import_cancelled")));
+                                                                importing_tree_id.set(None);
+                                                            }
                                                             Err(e) => {
                                                                 import_error.set(Some(format!("{e}")));
                                                                 importing_tree_id.set(None);
@@ -404,6 +492,14 @@ pub fn Home() -> Element {
                                 oninput: move |e: Event<FormData>| new_desc.set(e.value()),
                             }
                         }
+                        label { class: "home-create-template",
+                            input {
+                                r#type: "checkbox",
+                                checked: new_from_template(),
+                                onchange: move |e: Event<FormData>| new_from_template.set(e.checked()),
+                            }
+                            {i18n.t("tree.form.template_self")}
+                        }
                         div { class: "modal-actions",
                             button {
                                 class: "btn btn-outline",
@@ -528,6 +624,54 @@ pub fn Home() -> Element {
             div { class: "import-overlay",
                 div { class: "import-spinner" }
                 div { class: "import-overlay-text", {i18n.t("common.importing_gedcom")} }
+                if let Some(progress) = import_progress() {
+                    {
+                        let percent = (progress.records_persisted * 100)
+                            .checked_div(progress.records_total)
+                            .unwrap_or(0);
+                        let label = i18n
+                            .t(&format!("home.import_phase.{}", progress.phase))
+                            .replace("{done}", &progress.records_persisted.to_string())
+                            .replace("{total}", &progress.records_total.to_string());
+                        let parsed = (progress.records_parsed > 0).then(|| {
+                            i18n.t("home.import_parsed")
+                                .replace("{parsed}", &progress.records_parsed.to_string())
+                                .replace("{warnings}", &progress.warnings.len().to_string())
+                        });
+                        rsx! {
+                            div { class: "import-progress",
+                                div { class: "import-progress-fill", style: "width: {percent}%" }
+                            }
+                            div { class: "import-progress-text", "{label}" }
+                            if let Some(parsed) = parsed {
+                                div { class: "import-progress-text", "{parsed}" }
+                            }
+                        }
+                    }
+                }
+                if let (Some(tid), Some(job_id)) = (importing_tree_id(), import_job_id()) {
+                    if import_progress()
+                        .is_none_or(|p| matches!(p.phase.as_str(), "queued" | "parsing" | "persisting"))
+                    {
+                        button {
+                            class: "btn btn-outline",
+                            disabled: cancelling_import(),
+                            onclick: move |_| {
+                                let api = api_cancel.clone();
+                                cancelling_import.set(true);
+                                spawn(async move {
+                                    // The import task reports the outcome over its event
+                                    // stream; only a failed request needs surfacing here.
+                                    if let Err(e) = api.cancel_import(tid, job_id).await {
+                                        import_error.set(Some(format!("{e}")));
+                                        cancelling_import.set(false);
+                                    }
+                                });
+                            },
+                            {i18n.t(if cancelling_import() { "home.import_cancelling" } else { "common.cancel" })}
+                        }
+                    }
+                }
             }
         }
         if duplicating_tree_id().is_some() {
@@ -543,11 +687,89 @@ pub fn Home() -> Element {
         }
         if let Some((tree_name_imp, result)) = import_result() {
             div { class: "home-import-banner success-msg",
-                "\"{tree_name_imp}\": {result.persons_count} persons, \
-                 {result.families_count} families, \
-                 {result.events_count} events imported."
+                {
+                    let counts = [
+                        ("home.count_persons", result.persons_count),
+                        ("home.count_names", result.person_names_count),
+                        ("home.count_families", result.families_count),
+                        ("home.count_events", result.events_count),
+                        ("home.count_places", result.places_count),
+                        ("home.count_sources", result.sources_count),
+                        ("home.count_citations", result.citations_count),
+                        ("home.count_media", result.media_count),
+                        ("home.count_notes", result.notes_count),
+                    ]
+                    .into_iter()
+                    .map(|(key, n)| i18n.t_plural(key, n))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                    i18n.t_args("home.import_done", &[("name", &tree_name_imp), ("counts", &counts)])
+                }
+            }
+        }
+        if let Some((tree_name_check, report)) = check_report() {
+            div {
+                class: if report.issues.is_empty() { "home-import-banner success-msg" } else { "home-import-banner home-check-report" },
+                onclick: move |_| check_report.set(None),
+                if report.issues.is_empty() {
+                    {i18n.t_args("home.check_clean", &[("name", &tree_name_check)])}
+                } else {
+                    div {
+                        {i18n.t_args("home.check_summary", &[
+                            ("name", &tree_name_check),
+                            ("errors", &report.error_count.to_string()),
+                            ("warnings", &report.warning_count.to_string()),
+                        ])}
+                    }
+                    ul {
+                        for issue in report.issues.iter() {
+                            li { key: "{issue.kind}-{issue.entity_id}", class: "check-issue-{issue.severity}",
+                                "{issue.message}"
+                                span { class: "check-issue-fix", " \u{2014} {issue.suggested_fix}" }
+                            }
+                        }
+                    }
+                }
             }
         }
+        if let Some((tree_name_share, link)) = share_link() {{
+            let api_revoke = api.clone();
+            let token = link.token.to_string();
+            let path = format!("/shared/{token}");
+            rsx! {
+                div { class: "home-import-banner home-share-banner",
+                    div {
+                        {i18n.t_args("home.share_created", &[("name", &tree_name_share)])}
+                    }
+                    code { class: "home-share-url", "{path}" }
+                    div { class: "home-share-actions",
+                        Link {
+                            to: Route::SharedTree { token: token.clone() },
+                            class: "btn btn-outline btn-sm",
+                            {i18n.t("home.share_open")}
+                        }
+                        button {
+                            class: "btn btn-danger btn-sm",
+                            onclick: move |_| {
+                                let api = api_revoke.clone();
+                                spawn(async move {
+                                    match api.revoke_share_link(link.tree_id, link.id).await {
+                                        Ok(()) => share_link.set(None),
+                                        Err(e) => import_error.set(Some(format!("{e}"))),
+                                    }
+                                });
+                            },
+                            {i18n.t("home.share_revoke")}
+                        }
+                        button {
+                            class: "btn btn-outline btn-sm",
+                            onclick: move |_| share_link.set(None),
+                            {i18n.t("common.close")}
+                        }
+                    }
+                }
+            }
+        }}
 
         style { {HOME_STYLES} }
     }
@@ -559,11 +781,17 @@ fn TreeCard(
     name: String,
     description: String,
     updated_at: chrono::DateTime<Utc>,
+    person_count: i64,
+    family_count: i64,
+    event_count: i64,
+    years: Option<(i32, i32)>,
     tree_id: String,
     importing: bool,
     duplicating: bool,
     on_rename: EventHandler<()>,
     on_duplicate: EventHandler<()>,
+    on_check: EventHandler<()>,
+    on_share: EventHandler<()>,
     on_delete: EventHandler<()>,
     on_import: EventHandler<()>,
 ) -> Element {
@@ -682,6 +910,24 @@ fn TreeCard(
                                     },
                                     if importing { {i18n.t("common.importing")} } else { {i18n.t("common.import")} }
                                 }
+                                button {
+                                    class: "tree-card-dropdown-item",
+                                    onclick: move |e: Event<MouseData>| {
+                                        e.stop_propagation();
+                                        menu_open.set(false);
+                                        on_check.call(());
+                                    },
+                                    {i18n.t("home.check_tree")}
+                                }
+                                button {
+                                    class: "tree-card-dropdown-item",
+                                    onclick: move |e: Event<MouseData>| {
+                                        e.stop_propagation();
+                                        menu_open.set(false);
+                                        on_share.call(());
+                                    },
+                                    {i18n.t("home.share_tree")}
+                                }
                                 Link {
                                     to: Route::Settings { tree_id: tree_id.clone() },
                                     class: "tree-card-dropdown-item",
@@ -704,6 +950,22 @@ fn TreeCard(
                 if !description.is_empty() {
                     div { class: "tree-card-desc", "{description}" }
                 }
+                div { class: "tree-card-counts",
+                    span { class: "tree-count-badge",
+                        {i18n.t_plural("home.count_persons", person_count as usize)}
+                    }
+                    span { class: "tree-count-badge",
+                        {i18n.t_plural("home.count_families", family_count as usize)}
+                    }
+                    span { class: "tree-count-badge",
+                        {i18n.t_plural("home.count_events", event_count as usize)}
+                    }
+                    if let Some((first, last)) = years {
+                        span { class: "tree-count-badge",
+                            if first == last { "{first}" } else { "{first}\u{2013}{last}" }
+                        }
+                    }
+                }
                 div { class: "tree-card-footer",
                     div { class: "tree-card-footer-left",
                         span { class: "tree-last-update", "{time_ago}" }
@@ -1101,6 +1363,22 @@ const HOME_STYLES: &str = r#"
         color: var(--text-muted);
     }
 
+    .tree-card-counts {
+        display: flex;
+        flex-wrap: wrap;
+        gap: 0.35rem;
+        margin-top: 0.6rem;
+    }
+
+    .tree-count-badge {
+        font-size: 0.7rem;
+        color: var(--text-muted);
+        background: var(--bg-card-hover);
+        border: 1px solid var(--border);
+        border-radius: 10px;
+        padding: 2px 8px;
+    }
+
     .tree-badge-recent {
         font-size: 0.65rem;
         font-weight: 600;
@@ -1244,6 +1522,15 @@ const HOME_STYLES: &str = r#"
         padding: 1.5rem;
     }
 
+    .home-create-template {
+        display: flex;
+        align-items: center;
+        gap: 0.5rem;
+        margin-bottom: 1rem;
+        font-size: 0.85rem;
+        cursor: pointer;
+    }
+
     /* ── Animations ──────────────────────────────────────────────── */
 
     @keyframes home-fade-up {
@@ -1266,6 +1553,36 @@ const HOME_STYLES: &str = r#"
         box-shadow: 0 4px 20px color-mix(in srgb, var(--shadow-black) 40%, transparent);
     }
 
+    .home-check-report {
+        background: var(--bg-card);
+        border: 1px solid var(--border);
+        max-height: 50vh;
+        overflow-y: auto;
+        cursor: pointer;
+    }
+
+    .home-check-report ul {
+        margin: 0.5rem 0 0;
+        padding-left: 1.25rem;
+    }
+
+    .home-check-report li { margin-bottom: 0.25rem; }
+    .check-issue-error { color: var(--color-danger); }
+    .check-issue-fix { color: var(--text-muted); }
+
+    .home-share-banner {
+        background: var(--bg-card);
+        border: 1px solid var(--border);
+    }
+
+    .home-share-url {
+        display: block;
+        margin: 0.5rem 0;
+        word-break: break-all;
+    }
+
+    .home-share-actions { display: flex; gap: 0.5rem; }
+
     /* ── Responsive ──────────────────────────────────────────────── */
 
     @media (max-width: 640px) {
@@ -4,7 +4,12 @@ pub mod app_settings;
 pub mod dictionary;
 pub mod home;
 pub mod not_found;
+pub mod person_compare;
 pub mod person_detail;
+pub mod relationship;
 pub mod search_results;
 pub mod settings;
+pub mod shared_tree;
+pub mod source_detail;
+pub mod tasks;
 pub mod tree_detail;
@@ -0,0 +1,257 @@
+//! Person comparison page: two persons side by side, field by field, with
+//! their agreements and differences highlighted — to decide whether they
+//! are duplicates before merging them.
+
+use dioxus::prelude::*;
+use oxidgene_core::compare::{Agreement, ComparedField, FieldComparison};
+use uuid::Uuid;
+
+use crate::api::{ApiClient, PersonCompareResult};
+use crate::components::tree_cache::{fetch_tree_cached, use_tree_cache};
+use crate::components::tree_icon_sidebar::{TreeIconSidebar, TreeSidebarView};
+use crate::i18n::{I18n, use_i18n};
+use crate::router::Route;
+
+#[component]
+pub fn PersonCompare(tree_id: String, a: String, b: String) -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+    let nav = use_navigator();
+    let tree_cache = use_tree_cache();
+
+    let parse = |tree_id: &str, a: &str, b: &str| {
+        (
+            tree_id.parse::<Uuid>().ok(),
+            a.parse::<Uuid>().ok(),
+            b.parse::<Uuid>().ok(),
+        )
+    };
+    let mut ids = use_signal(|| parse(&tree_id, &a, &b));
+    let new_ids = parse(&tree_id, &a, &b);
+    if new_ids != *ids.peek() {
+        *ids.write() = new_ids;
+    }
+
+    let api_tree = api.clone();
+    let tree_resource = use_resource(move || {
+        let api = api_tree.clone();
+        let (tid, _, _) = ids();
+        let _gen = tree_cache.generation();
+        async move {
+            let tid = tid?;
+            Some(fetch_tree_cached(&api, &tree_cache, tid).await)
+        }
+    });
+
+    let api_compare = api.clone();
+    let compare_resource = use_resource(move || {
+        let api = api_compare.clone();
+        let (tid, a, b) = ids();
+        let _gen = tree_cache.generation();
+        async move {
+            let (tid, a, b) = (tid?, a?, b?);
+            Some(api.compare_persons(tid, a, b).await)
+        }
+    });
+
+    let tree_name = match &*tree_resource.read() {
+        Some(Some(Ok(tree))) => tree.name.clone(),
+        _ => ids()
+            .0
+            .and_then(|tid| tree_cache.tree(tid))
+            .map(|tree| tree.name)
+            .unwrap_or_default(),
+    };
+    let selected_person_id = ids().1;
+
+    rsx! {
+        div { class: "sub-page",
+            div { class: "td-topbar",
+                nav { class: "td-bc",
+                    Link { to: Route::Home {}, class: "td-bc-logo",
+                        img {
+                            src: crate::components::layout::LOGO_PNG_B64,
+                            alt: "OxidGene",
+                            class: "td-bc-logo-img",
+                        }
+                    }
+                    if !tree_name.is_empty() {
+                        Link {
+                            to: Route::TreeDetail { tree_id: tree_id.clone(), person: None },
+                            class: "td-bc-link",
+                            "{tree_name}"
+                        }
+                        span { class: "td-bc-sep", "/" }
+                    }
+                    span { class: "td-bc-current", {i18n.t("compare.breadcrumb")} }
+                }
+            }
+
+            div { class: "pd-page-shell",
+            TreeIconSidebar {
+                active_view: TreeSidebarView::None,
+                selected_person_id: selected_person_id,
+                show_middle_separator: false,
+                show_add_person: false,
+                show_dictionary: true,
+                show_settings: true,
+                on_profile_view: {
+                    let tree_id = tree_id.clone();
+                    move |pid: Option<Uuid>| {
+                        if let Some(pid) = pid {
+                            nav.push(Route::PersonDetail {
+                                tree_id: tree_id.clone(),
+                                person_id: pid.to_string(),
+                            });
+                        }
+                    }
+                },
+                on_pedigree_view: {
+                    let tree_id = tree_id.clone();
+                    move |pid: Option<Uuid>| {
+                        nav.push(Route::TreeDetail {
+                            tree_id: tree_id.clone(),
+                            person: pid.map(|pid| pid.to_string()),
+                        });
+                    }
+                },
+                on_add_person: move |_| {},
+                on_dictionary: {
+                    let tree_id = tree_id.clone();
+                    move |_| {
+                        nav.push(Route::Dictionary {
+                            tree_id: tree_id.clone(),
+                        });
+                    }
+                },
+                on_tasks: {
+                    let tree_id = tree_id.clone();
+                    move |_| {
+                        nav.push(Route::Tasks {
+                            tree_id: tree_id.clone(),
+                        });
+                    }
+                },
+                on_settings: {
+                    let tree_id = tree_id.clone();
+                    move |_| {
+                        nav.push(Route::Settings {
+                            tree_id: tree_id.clone(),
+                        });
+                    }
+                },
+            }
+
+            div { class: "sub-page-content",
+                h1 { class: "cmp-title", {i18n.t("compare.title")} }
+                match &*compare_resource.read() {
+                    Some(Some(Ok(compare))) => render_comparison(i18n, &tree_id, compare),
+                    Some(Some(Err(e))) => rsx! {
+                        div { class: "error-msg", "{e}" }
+                    },
+                    Some(None) => rsx! {
+                        div { class: "error-msg", {i18n.t("compare.invalid_ids")} }
+                    },
+                    None => rsx! {
+                        div { class: "loading", {i18n.t("common.loading")} }
+                    },
+                }
+            }
+            }
+        }
+    }
+}
+
+/// The two-column diff: one row per field, colored by agreement.
+fn render_comparison(i18n: I18n, tree_id: &str, compare: &PersonCompareResult) -> Element {
+    let comparison = &compare.comparison;
+    let names = comparison
+        .fields
+        .iter()
+        .find(|f| f.field == ComparedField::Name);
+    let name = |values: Option<&Vec<String>>| {
+        values
+            .and_then(|v| v.first().cloned())
+            .unwrap_or_else(|| i18n.t("common.unnamed"))
+    };
+    let (name_a, name_b) = (name(names.map(|f| &f.a)), name(names.map(|f| &f.b)));
+    let summary = format!(
+        "{} \u{00B7} {}",
+        i18n.t_plural("compare.agreement_count", comparison.agreements),
+        i18n.t_plural("compare.difference_count", comparison.differences),
+    );
+
+    rsx! {
+        div { class: "cmp-summary", "{summary}" }
+        div { class: "cmp-table",
+            div { class: "cmp-row cmp-head",
+                span { class: "cmp-field" }
+                Link {
+                    to: Route::PersonDetail {
+                        tree_id: tree_id.to_string(),
+                        person_id: compare.a.to_string(),
+                    },
+                    class: "cmp-value cmp-person",
+                    "{name_a}"
+                }
+                Link {
+                    to: Route::PersonDetail {
+                        tree_id: tree_id.to_string(),
+                        person_id: compare.b.to_string(),
+                    },
+                    class: "cmp-value cmp-person",
+                    "{name_b}"
+                }
+            }
+            for field in comparison.fields.iter() {
+                {render_field(i18n, field)}
+            }
+        }
+    }
+}
+
+fn render_field(i18n: I18n, field: &FieldComparison) -> Element {
+    let key = field.field.as_str();
+    let (class, badge) = match field.agreement {
+        Agreement::Same => ("cmp-same", "compare.same"),
+        Agreement::Compatible => ("cmp-compatible", "compare.compatible"),
+        Agreement::Different => ("cmp-different", "compare.different"),
+        Agreement::OnlyA | Agreement::OnlyB => ("cmp-partial", "compare.partial"),
+    };
+    let values = |values: &[String]| -> Vec<String> {
+        values
+            .iter()
+            .map(|v| match field.field {
+                ComparedField::Sex => i18n.t(&format!("sex.{v}")),
+                _ if v.is_empty() => i18n.t("common.unnamed"),
+                _ => v.clone(),
+            })
+            .collect()
+    };
+    let (a, b) = (values(&field.a), values(&field.b));
+
+    rsx! {
+        div { key: "{key}", class: "cmp-row {class}",
+            span { class: "cmp-field",
+                {i18n.t(&format!("compare.field.{key}"))}
+                span { class: "cmp-badge", {i18n.t(badge)} }
+            }
+            div { class: "cmp-value",
+                if a.is_empty() {
+                    span { class: "cmp-empty", "\u{2014}" }
+                }
+                for value in a.iter() {
+                    div { "{value}" }
+                }
+            }
+            div { class: "cmp-value",
+                if b.is_empty() {
+                    span { class: "cmp-empty", "\u{2014}" }
+                }
+                for value in b.iter() {
+                    div { "{value}" }
+                }
+            }
+        }
+    }
+}
@@ -4,13 +4,17 @@ use std::collections::{HashMap, HashSet};
 
 use dioxus::prelude::*;
 use oxidgene_cache::types::CachedPedigree;
-use oxidgene_core::EventType;
-use oxidgene_core::types::Event as DomainEvent;
+use oxidgene_core::types::{Event as DomainEvent, PersonName};
+use oxidgene_core::{Calendar, EventCategory, EventType, FactType, NameSelectionPolicy};
 use uuid::Uuid;
 
-use crate::api::ApiClient;
+use crate::api::{ApiClient, MediaLinkRow, person_photos};
 use crate::components::confirm_dialog::ConfirmDialog;
+use crate::components::markdown::NoteText;
+use crate::components::media_gallery::PersonMediaGallery;
 use crate::components::person_form::{PersonForm, PersonFormCreateContext};
+use crate::components::person_tags::PersonTagEditor;
+use crate::components::research_tasks::PersonTasksPanel;
 use crate::components::topbar_search::TopbarSearch;
 use crate::components::tree_cache::{fetch_tree_cached, use_tree_cache};
 use crate::components::tree_icon_sidebar::{TreeIconSidebar, TreeSidebarView};
@@ -21,6 +25,9 @@ use oxidgene_core::Sex;
 
 const SHOW_MANUAL_REFRESH: bool = cfg!(target_arch = "wasm32");
 
+/// Sourcing score from which a person counts as well-sourced.
+const WELL_SOURCED: f32 = 0.5;
+
 /// Indicates the origin of an event relative to the displayed person.
 #[derive(Clone, Debug, PartialEq)]
 enum EventOrigin {
@@ -34,6 +41,16 @@ enum EventOrigin {
     ParentalFamily,
 }
 
+/// Section of the grouped event list an event is shown in: the person's own
+/// events by the category of their type, and every event of a relative or
+/// of a couple under Family.
+fn event_group(ee: &EnrichedEvent) -> EventCategory {
+    match ee.origin {
+        EventOrigin::Individual => ee.event.event_type.category(),
+        _ => EventCategory::Family,
+    }
+}
+
 /// An event enriched with origin metadata for display purposes.
 #[derive(Clone, Debug)]
 struct EnrichedEvent {
@@ -67,11 +84,17 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
         *person_id_parsed.write() = new_pid;
     }
 
+    // Which name the header shows (primary, birth, married, most recent).
+    let mut name_policy = use_signal(NameSelectionPolicy::default);
+
     // Delete confirmation state.
     let mut confirm_delete = use_signal(|| false);
     let mut delete_error = use_signal(|| None::<String>);
+    let mut lock_error = use_signal(|| None::<String>);
 
     // Person edit modal (names are managed there — see PersonForm).
+    // Events grouped into Vital / Family / Other, or one chronological list.
+    let mut group_events = use_signal(|| true);
     let mut show_edit_person = use_signal(|| false);
     let mut show_create_person = use_signal(|| false);
 
@@ -149,7 +172,7 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
         }
     });
 
-    // Fetch notes for this person.
+    // Fetch notes for this person, and those of their families.
     let api_notes = api.clone();
     let notes_resource = use_resource(move || {
         let api = api_notes.clone();
@@ -163,7 +186,7 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
                     body: i18n.t("common.invalid_ids"),
                 });
             };
-            api.list_notes(tid, Some(pid), None, None, None).await
+            api.list_person_notes(tid, pid).await
         }
     });
 
@@ -277,35 +300,47 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
         }
     });
 
-    // Tree-wide photo map (person_id -> file_path), used both for this
-    // person's header avatar and for the mini pedigrees below — a single
-    // `list_media_links_for_tree` call instead of two duplicate ones.
-    let api_photos_map = api.clone();
-    let photos_map_resource = use_resource(move || {
-        let api = api_photos_map.clone();
+    // Tree-wide media links, used for this person's header avatar and its
+    // photo picker and for the mini pedigrees below — a single
+    // `list_media_links_for_tree` call instead of duplicate ones.
+    let api_media_rows = api.clone();
+    let mut media_rows_resource = use_resource(move || {
+        let api = api_media_rows.clone();
         let tid = tree_id_parsed();
         async move {
             let Some(tid) = tid else {
-                return HashMap::new();
+                return Vec::new();
             };
-            api.list_media_links_for_tree(tid)
-                .await
-                .map(|rows| {
-                    rows.into_iter()
-                        .filter(|r| r.entity_type == "person")
-                        .map(|r| (r.entity_id, r.file_path))
-                        .collect()
-                })
-                .unwrap_or_default()
+            api.list_media_links_for_tree(tid).await.unwrap_or_default()
         }
     });
+    let photos_map = use_memo(move || {
+        media_rows_resource
+            .read()
+            .as_deref()
+            .map(person_photos)
+            .unwrap_or_default()
+    });
 
-    // This person's portrait photo, derived from `photos_map_resource`
-    // rather than issuing a second `list_media_links_for_tree` call.
+    // This person's portrait photo, and the images they could pick from.
     let photo = use_memo(move || {
         let pid = person_id_parsed()?;
-        photos_map_resource.read().as_ref()?.get(&pid).cloned()
+        photos_map.read().get(&pid).cloned()
     });
+    let person_images = use_memo(move || {
+        let Some(pid) = person_id_parsed() else {
+            return Vec::new();
+        };
+        media_rows_resource
+            .read()
+            .iter()
+            .flatten()
+            .filter(|r| r.entity_id == pid && r.mime_type.starts_with("image/"))
+            .cloned()
+            .collect::<Vec<MediaLinkRow>>()
+    });
+    let mut show_photo_picker = use_signal(|| false);
+    let mut photo_error = use_signal(|| None::<String>);
 
     // Small static pedigree window (self + parents + grandparents), for the
     // Ancestors section.
@@ -335,10 +370,16 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
             .unwrap_or_default(),
     };
 
+    // Id of the name picked for display, for its citation badge.
+    let display_name_id: Option<Uuid> = match &*names_resource.read() {
+        Some(Ok(names)) => PersonName::select(names, name_policy()).map(|n| n.id),
+        _ => None,
+    };
+
     // Derive display name from loaded names.
     let display_name = match &*names_resource.read() {
         Some(Ok(names)) => {
-            let primary = names.iter().find(|n| n.is_primary).or(names.first());
+            let primary = PersonName::select(names, name_policy());
             match primary {
                 Some(name) => {
                     let dn = name.display_name();
@@ -359,9 +400,9 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
     // Alternate names shown under the header name, e.g."(Given Surname)".
     // Excludes whichever name was picked as display_name above,
     // and de-duplicates identical given/surname combinations.
-    let alt_names: Vec<String> = match &*names_resource.read() {
+    let alt_names: Vec<(Uuid, String)> = match &*names_resource.read() {
         Some(Ok(names)) => {
-            let primary = names.iter().find(|n| n.is_primary).or(names.first());
+            let primary = PersonName::select(names, name_policy());
             let primary_id = primary.map(|n| n.id);
             let mut seen: std::collections::HashSet<(String, String)> =
                 std::collections::HashSet::new();
@@ -383,7 +424,11 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
                         return None;
                     }
                     let dn = n.display_name();
-                    if dn.is_empty() { None } else { Some(dn) }
+                    if dn.is_empty() {
+                        None
+                    } else {
+                        Some((n.id, dn))
+                    }
                 })
                 .collect()
         }
@@ -499,7 +544,7 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
         let mut divorce_date = None;
         if let Some(events) = events_by_family.get(&fid) {
             let mut sorted: Vec<&DomainEvent> = events.iter().collect();
-            sorted.sort_by_key(|e| e.date_sort);
+            sorted.sort_by(|a, b| a.chronological_cmp(b));
             for e in sorted {
                 match e.event_type {
                     EventType::Marriage if marriage_date.is_none() => {
@@ -525,6 +570,48 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
 
     // ── Handlers ─────────────────────────────────────────────────────
 
+    // Lock toggle: a locked person can be neither edited nor deleted.
+    let api_lock = api.clone();
+    let on_toggle_lock = move |locked: bool| {
+        let api = api_lock.clone();
+        let (Some(tid), Some(pid)) = (tree_id_parsed(), person_id_parsed()) else {
+            return;
+        };
+        spawn(async move {
+            match api.set_person_locked(tid, pid, locked).await {
+                Ok(_) => {
+                    lock_error.set(None);
+                    refresh += 1;
+                }
+                Err(e) => lock_error.set(Some(format!("{e}"))),
+            }
+        });
+    };
+
+    // Profile photo handler: designate one of the person's images, or clear
+    // the designation with `None`.
+    let api_photo = api.clone();
+    let on_pick_photo = move |link_id: Option<Uuid>| {
+        let api = api_photo.clone();
+        let (Some(tid), Some(pid)) = (tree_id_parsed(), person_id_parsed()) else {
+            return;
+        };
+        spawn(async move {
+            let result = match link_id {
+                Some(link_id) => api.set_person_photo(tid, pid, link_id).await.map(|_| ()),
+                None => api.clear_person_photo(tid, pid).await,
+            };
+            match result {
+                Ok(()) => {
+                    photo_error.set(None);
+                    show_photo_picker.set(false);
+                    media_rows_resource.restart();
+                }
+                Err(e) => photo_error.set(Some(format!("{e}"))),
+            }
+        });
+    };
+
     // Delete person handler.
     let tree_id_nav = tree_id.clone();
     let api_del = api.clone();
@@ -664,7 +751,7 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
                             .map(|person| match person.sex {
                                 Sex::Male => 0,
                                 Sex::Female => 1,
-                                Sex::Unknown => 2,
+                                Sex::Intersex | Sex::Unknown => 2,
                             })
                             .unwrap_or(2)
                     });
@@ -765,12 +852,13 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
         let sex_glyph = match sex {
             Sex::Male => "\u{2642}",
             Sex::Female => "\u{2640}",
+            Sex::Intersex => "\u{26A5}",
             Sex::Unknown => "?",
         };
         let sex_class = match sex {
             Sex::Male => "pd-sex-glyph male",
             Sex::Female => "pd-sex-glyph female",
-            Sex::Unknown => "pd-sex-glyph",
+            Sex::Intersex | Sex::Unknown => "pd-sex-glyph",
         };
         let lifespan = person_lifespan_map.get(&pid).cloned().unwrap_or_default();
         let is_sosa = sosa_ancestors.contains(&pid);
@@ -972,43 +1060,80 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
                 }
 
                 // Sort by date.
-                result.sort_by_key(|a| a.event.date_sort);
+                result.sort_by(|a, b| a.event.chronological_cmp(&b.event));
                 result
             }
             _ => Vec::new(),
         }
     };
 
-    // ── Build per-event source citations ──────────────────────────────
+    // Sections of the events list, each still in chronological order; a
+    // single untitled one in the flat view.
+    let event_groups: Vec<(Option<EventCategory>, Vec<&EnrichedEvent>)> = if group_events() {
+        [
+            EventCategory::Vital,
+            EventCategory::Family,
+            EventCategory::Other,
+        ]
+        .into_iter()
+        .map(|category| {
+            let events: Vec<&EnrichedEvent> = enriched_events
+                .iter()
+                .filter(|ee| event_group(ee) == category)
+                .collect();
+            (Some(category), events)
+        })
+        .filter(|(_, events)| !events.is_empty())
+        .collect()
+    } else {
+        vec![(None, enriched_events.iter().collect())]
+    };
+
+    // ── Build per-event and per-name source citations ─────────────────
     //
     // One entry per event, listing its citations ("Source title — page"),
     // rendered directly under that event in the timeline instead of a
-    // separate "Sources" section.
-    let citations_by_event: HashMap<Uuid, Vec<String>> = {
+    // separate "Sources" section. Citations of a single event field carry
+    // that field's label; citations of one name are keyed by the
+    // `PersonName` and shown as a badge next to it.
+    let (citations_by_event, citations_by_name): (
+        HashMap<Uuid, Vec<String>>,
+        HashMap<Uuid, Vec<String>>,
+    ) = {
         match (&*citations_resource.read(), &*sources_resource.read()) {
             (Some(Ok(citations)), Some(Ok(sources))) => {
                 let source_by_id: HashMap<Uuid, &oxidgene_core::types::Source> =
                     sources.iter().map(|s| (s.id, s)).collect();
 
-                let mut result: HashMap<Uuid, Vec<String>> = HashMap::new();
+                let mut by_event: HashMap<Uuid, Vec<String>> = HashMap::new();
+                let mut by_name: HashMap<Uuid, Vec<String>> = HashMap::new();
                 for citation in citations {
-                    let Some(eid) = citation.event_id else {
-                        continue;
-                    };
                     let Some(source) = source_by_id.get(&citation.source_id) else {
                         continue;
                     };
-                    let text = match &citation.page {
+                    let mut text = match &citation.page {
                         Some(page) if !page.is_empty() => {
                             format!("{} \u{2014} {page}", source.title)
                         }
                         _ => source.title.clone(),
                     };
-                    result.entry(eid).or_default().push(text);
+                    if let (Some(FactType::Name), Some(name_id)) =
+                        (citation.fact_type, citation.fact_ref)
+                    {
+                        by_name.entry(name_id).or_default().push(text);
+                        continue;
+                    }
+                    let Some(eid) = citation.event_id else {
+                        continue;
+                    };
+                    if let Some(fact) = citation.fact_type {
+                        text = format!("{text} ({})", i18n.t(&format!("citation.fact.{fact}")));
+                    }
+                    by_event.entry(eid).or_default().push(text);
                 }
-                result
+                (by_event, by_name)
             }
-            _ => HashMap::new(),
+            _ => (HashMap::new(), HashMap::new()),
         }
     };
 
@@ -1069,6 +1194,14 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
                     });
                 }
             },
+            on_tasks: {
+                let tree_id = tree_id.clone();
+                move |_| {
+                    nav.push(Route::Tasks {
+                        tree_id: tree_id.clone(),
+                    });
+                }
+            },
         }
 
         div { class: "sub-page-content pd-content",
@@ -1119,9 +1252,11 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
         match &*person_resource.read() {
             Some(Ok(person)) => {
                 let person_sex = person.sex;
+                let locked = person.locked;
                 let sex_symbol = match person_sex {
                     Sex::Male => "\u{2642}",
                     Sex::Female => "\u{2640}",
+                    Sex::Intersex => "\u{26A5}",
                     Sex::Unknown => "?",
                 };
                 let avatar_src = match &*photo.read() {
@@ -1131,15 +1266,104 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
                 rsx! {
                     div { class: "card page-header",
                         div { class: "pd-header-left",
-                            img { class: "pd-avatar", alt: "", src: "{avatar_src}" }
+                            div { class: "pd-avatar-wrap",
+                                if person_images.read().is_empty() {
+                                    img { class: "pd-avatar", alt: "", src: "{avatar_src}" }
+                                } else {
+                                    button {
+                                        class: "pd-avatar-btn",
+                                        title: i18n.t("person.photo.choose"),
+                                        onclick: move |_| show_photo_picker.toggle(),
+                                        img { class: "pd-avatar", alt: "", src: "{avatar_src}" }
+                                    }
+                                }
+                                if show_photo_picker() {
+                                    div { class: "pd-photo-picker",
+                                        div { class: "pd-photo-picker-title", {i18n.t("person.photo.choose")} }
+                                        div { class: "pd-photo-picker-grid",
+                                            for row in person_images.read().iter().cloned() {
+                                                button {
+                                                    key: "{row.link_id}",
+                                                    class: if row.is_profile { "pd-photo-option active" } else { "pd-photo-option" },
+                                                    title: "{row.file_name}",
+                                                    onclick: {
+                                                        let on_pick_photo = on_pick_photo.clone();
+                                                        move |_| on_pick_photo(Some(row.link_id))
+                                                    },
+                                                    img { alt: "{row.file_name}", src: "{row.file_path}" }
+                                                }
+                                            }
+                                        }
+                                        if person_images.read().iter().any(|r| r.is_profile) {
+                                            button {
+                                                class: "btn btn-outline btn-sm",
+                                                onclick: {
+                                                    let on_pick_photo = on_pick_photo.clone();
+                                                    move |_| on_pick_photo(None)
+                                                },
+                                                {i18n.t("person.photo.clear")}
+                                            }
+                                        }
+                                        if let Some(err) = photo_error() {
+                                            div { class: "error-msg", "{err}" }
+                                        }
+                                    }
+                                }
+                            }
                             div { class: "pd-header-main",
                                 div { class: "pd-header-top",
-                                    h1 { "{display_name}" }
+                                    h1 {
+                                        if locked {
+                                            span {
+                                                class: "pd-lock-icon",
+                                                title: i18n.t("person.locked_hint"),
+                                                "\u{1F512} "
+                                            }
+                                        }
+                                        "{display_name}"
+                                        if let Some(sources) = display_name_id.and_then(|id| citations_by_name.get(&id)) {
+                                            span {
+                                                class: "pd-cite-badge",
+                                                title: "{sources.join(\"; \")}",
+                                                "{sources.len()}"
+                                            }
+                                        }
+                                    }
+                                    select {
+                                        class: "pd-name-policy",
+                                        title: i18n.t("person.name_policy.label"),
+                                        onchange: move |e: Event<FormData>| {
+                                            let policy = match e.value().as_str() {
+                                                "prefer_birth" => NameSelectionPolicy::PreferBirth,
+                                                "prefer_married" => NameSelectionPolicy::PreferMarried,
+                                                "most_recent" => NameSelectionPolicy::MostRecent,
+                                                _ => NameSelectionPolicy::PreferPrimary,
+                                            };
+                                            name_policy.set(policy);
+                                        },
+                                        for (value, key) in [
+                                            ("prefer_primary", "person.name_policy.prefer_primary"),
+                                            ("prefer_birth", "person.name_policy.prefer_birth"),
+                                            ("prefer_married", "person.name_policy.prefer_married"),
+                                            ("most_recent", "person.name_policy.most_recent"),
+                                        ] {
+                                            option { key: "{value}", value: "{value}", {i18n.t(key)} }
+                                        }
+                                    }
                                 }
                                 if !alt_names.is_empty() {
                                     p { class: "pd-alt-names",
-                                        for n in alt_names.iter() {
-                                            span { key: "{n}", "({n})" }
+                                        for (name_id, n) in alt_names.iter() {
+                                            span { key: "{name_id}",
+                                                "({n})"
+                                                if let Some(sources) = citations_by_name.get(name_id) {
+                                                    span {
+                                                        class: "pd-cite-badge",
+                                                        title: "{sources.join(\"; \")}",
+                                                        "{sources.len()}"
+                                                    }
+                                                }
+                                            }
                                         }
                                     }
                                 }
@@ -1204,6 +1428,11 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
                                         }
                                     }
                                 }
+                                PersonTagEditor {
+                                    key: "{person.id}",
+                                    tree_id: person.tree_id,
+                                    person_id: person.id,
+                                }
                             }
                         }
                         div { class: "pd-header-actions",
@@ -1213,10 +1442,49 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
                                         "SOSA {sosa}"
                                     }
                                 }
+                                {
+                                    let percent = (person.sourcing_score * 100.0).round();
+                                    let (class, key) = if person.sourcing_score >= WELL_SOURCED {
+                                        ("badge pd-sourcing-badge well-sourced", "person.well_sourced")
+                                    } else {
+                                        ("badge pd-sourcing-badge needs-evidence", "person.needs_evidence")
+                                    };
+                                    rsx! {
+                                        span {
+                                            class: "{class}",
+                                            title: i18n.t_args("person.sourcing_score", &[("percent", &percent.to_string())]),
+                                            {i18n.t(key)}
+                                        }
+                                    }
+                                }
+                                if !person.has_known_parents {
+                                    span {
+                                        class: "badge pd-lineage-badge",
+                                        title: i18n.t("person.no_parents_hint"),
+                                        {i18n.t("person.no_parents")}
+                                    }
+                                }
+                                if !person.has_descendants {
+                                    span { class: "badge pd-lineage-badge", {i18n.t("person.end_of_line")} }
+                                }
                             }
                             div { class: "pd-header-buttons",
+                                button {
+                                    class: "btn btn-outline",
+                                    title: if locked { i18n.t("person.unlock_hint") } else { i18n.t("person.lock_hint") },
+                                    onclick: {
+                                        let on_toggle_lock = on_toggle_lock.clone();
+                                        move |_| on_toggle_lock(!locked)
+                                    },
+                                    if locked {
+                                        {i18n.t("person.unlock")}
+                                    } else {
+                                        {i18n.t("person.lock")}
+                                    }
+                                }
                                 button {
                                     class: "btn btn-danger",
+                                    disabled: locked,
                                     onclick: move |_| {
                                         confirm_delete.set(true);
                                         delete_error.set(None);
@@ -1225,9 +1493,13 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
                                 }
                                 button {
                                     class: "btn btn-outline",
+                                    disabled: locked,
                                     onclick: move |_| show_edit_person.set(true),
                                     {i18n.t("common.edit")}
                                 }
+                                if let Some(err) = lock_error() {
+                                    div { class: "error-msg", "{err}" }
+                                }
                                 if SHOW_MANUAL_REFRESH {
                                     button {
                                         class: "btn btn-outline",
@@ -1250,26 +1522,59 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
 
         // ── Notes section ────────────────────────────────────────────
         match &*notes_resource.read() {
-            Some(Ok(notes)) if !notes.is_empty() => rsx! {
-                div { class: "card", style: "margin-bottom: 24px;",
-                    h2 { style: "font-size: 1.1rem; margin-bottom: 12px;", {i18n.t("person.notes_section")} }
-
-                    div {
-                        for note in notes.iter() {
+            Some(Ok(notes)) if !notes.is_empty() => {
+                let (inherited, direct): (Vec<_>, Vec<_>) = notes.iter().partition(|n| n.inherited);
+                rsx! {
+                    div { class: "card", style: "margin-bottom: 24px;",
+                        h2 { style: "font-size: 1.1rem; margin-bottom: 12px;", {i18n.t("person.notes_section")} }
+
+                        div {
+                            for entry in direct {
+                                div {
+                                    key: "{entry.note.id}",
+                                    style: "margin-bottom: 12px; padding: 12px; border: 1px solid var(--color-border); border-radius: var(--radius);",
+                                    NoteText { text: entry.note.text.clone(), format: entry.note.format }
+                                }
+                            }
+                        }
+                        if !inherited.is_empty() {
+                            h3 { class: "pd-notes-inherited-title", {i18n.t("person.notes_from_families")} }
                             div {
-                                style: "margin-bottom: 12px; padding: 12px; border: 1px solid var(--color-border); border-radius: var(--radius);",
-                                p { style: "margin: 0; white-space: pre-wrap;", "{note.text}" }
+                                for entry in inherited {
+                                    div {
+                                        key: "{entry.note.id}",
+                                        class: "pd-note-inherited",
+                                        div { class: "pd-ev-origin",
+                                            if entry.family_role.as_deref() == Some("child") {
+                                                {i18n.t("person.note_from_parents")}
+                                            } else {
+                                                {i18n.t("person.note_from_union")}
+                                            }
+                                        }
+                                        NoteText { text: entry.note.text.clone(), format: entry.note.format }
+                                    }
+                                }
                             }
                         }
                     }
                 }
-            },
+            }
             Some(Err(e)) => rsx! {
                 div { class: "error-msg", {i18n.t_args("person.load_notes_error", &[("error", &e.to_string())])} }
             },
             _ => rsx! {},
         }
 
+        // ── Media gallery ────────────────────────────────────────────
+        if let (Some(tid), Some(pid)) = (tree_id_parsed(), person_id_parsed()) {
+            PersonMediaGallery { key: "{pid}", tree_id: tid, person_id: pid }
+        }
+
+        // ── Research tasks ───────────────────────────────────────────
+        if let (Some(tid), Some(pid)) = (tree_id_parsed(), person_id_parsed()) {
+            PersonTasksPanel { key: "{pid}", tree_id: tid, person_id: pid }
+        }
+
         // ── Family section (narrative) ────────────────────────────────
         if let Some((parent_ids, unions, full_sibling_ids, half_sibling_groups)) = &family_data {
             div { class: "card", style: "margin-bottom: 24px;",
@@ -1452,6 +1757,17 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
         div { class: "card", style: "margin-bottom: 24px;",
             div { class: "section-header",
                 h2 { style: "font-size: 1.1rem;", {i18n.t("person.events_section")} }
+                if !enriched_events.is_empty() {
+                    button {
+                        class: "btn btn-outline btn-sm",
+                        onclick: move |_| group_events.set(!group_events()),
+                        if group_events() {
+                            {i18n.t("person.events_flat")}
+                        } else {
+                            {i18n.t("person.events_grouped")}
+                        }
+                    }
+                }
             }
 
             match &*events_resource.read() {
@@ -1461,58 +1777,76 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
                             p { {i18n.t("person.no_events")} }
                         }
                     } else {
-                        ul { class: "pd-timeline",
-                            for ee in enriched_events.iter() {
-                                {
-                                    let event = &ee.event;
-                                    let eid = event.id;
-                                    let event_type_key = format!("event.type.{}", event.event_type);
-                                    let event_type_label = i18n.t(&event_type_key);
-                                    let desc = event.description.clone().unwrap_or_default();
-                                    let place_display = event.place_id.map(&place_name);
-
-                                    // Origin label.
-                                    let origin_label = match &ee.origin {
-                                        EventOrigin::Individual => i18n.t("person.origin_individual"),
-                                        EventOrigin::ConjugalFamily => i18n.t("person.origin_conjugal"),
-                                        EventOrigin::ChildFamily => i18n.t("person.origin_child"),
-                                        EventOrigin::ParentalFamily => i18n.t("person.origin_parental"),
-                                    };
-                                    let origin_display = if let Some(ref ctx) = ee.context {
-                                        format!("{origin_label} ({ctx})")
-                                    } else {
-                                        origin_label
-                                    };
-
-                                    let is_direct = matches!(
-                                        ee.origin,
-                                        EventOrigin::Individual | EventOrigin::ConjugalFamily
-                                    );
-                                    let li_class = if is_direct { "pd-ev-direct" } else { "" };
-                                    let event_sources = citations_by_event.get(&eid);
-
-                                    rsx! {
-                                        li { key: "{eid}", class: "{li_class}",
-                                            span { class: "pd-ev-date",
-                                                {event.date_value.as_deref().unwrap_or("--")}
-                                            }
-                                            div { class: "pd-ev-body",
-                                                div { class: "pd-ev-row",
-                                                    div {
-                                                        span { class: "badge", "{event_type_label}" }
-                                                        if let Some(place) = &place_display {
-                                                            " \u{2014} {place}"
-                                                        }
-                                                        if !desc.is_empty() {
-                                                            span { class: "text-muted", " \u{2014} {desc}" }
+                        for (category, events) in event_groups.into_iter() {
+                            div { key: "{category:?}", class: "pd-ev-group",
+                                if let Some(category) = category {
+                                    h3 { class: "pd-ev-group-title",
+                                        {i18n.t(&format!("event.category.{category}"))}
+                                    }
+                                }
+                                ul { class: "pd-timeline",
+                                    for ee in events {
+                                        {
+                                            let event = &ee.event;
+                                            let eid = event.id;
+                                            let event_type_key = format!("event.type.{}", event.event_type);
+                                            let event_type_label = i18n.t(&event_type_key);
+                                            let desc = event.description.clone().unwrap_or_default();
+                                            let place_display = match (event.place_id.map(&place_name), event.place_id_to.map(&place_name)) {
+                                                (Some(from), Some(to)) => Some(format!("{from} \u{2192} {to}")),
+                                                (from, to) => from.or(to),
+                                            };
+
+                                            // Origin label.
+                                            let origin_label = match &ee.origin {
+                                                EventOrigin::Individual => i18n.t("person.origin_individual"),
+                                                EventOrigin::ConjugalFamily => i18n.t("person.origin_conjugal"),
+                                                EventOrigin::ChildFamily => i18n.t("person.origin_child"),
+                                                EventOrigin::ParentalFamily => i18n.t("person.origin_parental"),
+                                            };
+                                            let origin_display = if let Some(ref ctx) = ee.context {
+                                                format!("{origin_label} ({ctx})")
+                                            } else {
+                                                origin_label
+                                            };
+
+                                            let is_direct = matches!(
+                                                ee.origin,
+                                                EventOrigin::Individual | EventOrigin::ConjugalFamily
+                                            );
+                                            let li_class = if is_direct { "pd-ev-direct" } else { "" };
+                                            let event_sources = citations_by_event.get(&eid);
+                                            // Dates stay in the calendar they were recorded in.
+                                            let calendar_label = (event.calendar != Calendar::Gregorian)
+                                                .then(|| i18n.t(&format!("calendar.{}", event.calendar)));
+
+                                            rsx! {
+                                                li { key: "{eid}", class: "{li_class}",
+                                                    span { class: "pd-ev-date",
+                                                        {event.date_value.as_deref().unwrap_or("--")}
+                                                        if let Some(label) = &calendar_label {
+                                                            span { class: "pd-ev-calendar", "{label}" }
                                                         }
                                                     }
-                                                }
-                                                div { class: "pd-ev-origin", "{origin_display}" }
-                                                if let Some(sources) = event_sources {
-                                                    div { class: "pd-ev-sources",
-                                                        {i18n.t("person.sources_section")}
-                                                        ": {sources.join(\"; \")}"
+                                                    div { class: "pd-ev-body",
+                                                        div { class: "pd-ev-row",
+                                                            div {
+                                                                span { class: "badge", "{event_type_label}" }
+                                                                if let Some(place) = &place_display {
+                                                                    " \u{2014} {place}"
+                                                                }
+                                                                if !desc.is_empty() {
+                                                                    span { class: "text-muted", " \u{2014} {desc}" }
+                                                                }
+                                                            }
+                                                        }
+                                                        div { class: "pd-ev-origin", "{origin_display}" }
+                                                        if let Some(sources) = event_sources {
+                                                            div { class: "pd-ev-sources",
+                                                                {i18n.t("person.sources_section")}
+                                                                ": {sources.join(\"; \")}"
+                                                            }
+                                                        }
                                                     }
                                                 }
                                             }
@@ -1545,7 +1879,7 @@ pub fn PersonDetail(tree_id: String, person_id: String) -> Element {
                 });
                 render_mini_pedigree(
                     &ancestor_pedigree_resource,
-                    &photos_map_resource,
+                    &photos_map.read(),
                     2,
                     0,
                     on_navigate,
@@ -1608,7 +1942,7 @@ fn age_span(birth: chrono::NaiveDate, end: chrono::NaiveDate) -> AgeSpan {
 /// grandchildren) sections, depending on the levels passed in.
 fn render_mini_pedigree(
     pedigree_resource: &Resource<Result<Option<CachedPedigree>, crate::api::ApiError>>,
-    photos_resource: &Resource<HashMap<Uuid, String>>,
+    photos: &HashMap<Uuid, String>,
     ancestor_levels: usize,
     descendant_levels: usize,
     on_navigate: EventHandler<Uuid>,
@@ -1630,7 +1964,7 @@ fn render_mini_pedigree(
     };
 
     let mut data = crate::components::pedigree_chart::PedigreeData::from_cached_pedigree(cached);
-    data.photos = photos_resource.read().clone().unwrap_or_default();
+    data.photos = photos.clone();
     let root_person_id = cached.root_person_id;
 
     rsx! {
@@ -0,0 +1,220 @@
+//! Relationship page: how two persons are related, as a breadcrumb of the
+//! persons linking them through their nearest common ancestor.
+
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use crate::api::{ApiClient, RelationshipPath};
+use crate::components::tree_cache::{fetch_tree_cached, use_tree_cache};
+use crate::components::tree_icon_sidebar::{TreeIconSidebar, TreeSidebarView};
+use crate::i18n::{I18n, use_i18n};
+use crate::router::Route;
+
+#[component]
+pub fn Relationship(tree_id: String, a: String, b: String) -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+    let nav = use_navigator();
+    let tree_cache = use_tree_cache();
+
+    let parse = |tree_id: &str, a: &str, b: &str| {
+        (
+            tree_id.parse::<Uuid>().ok(),
+            a.parse::<Uuid>().ok(),
+            b.parse::<Uuid>().ok(),
+        )
+    };
+    let mut ids = use_signal(|| parse(&tree_id, &a, &b));
+    let new_ids = parse(&tree_id, &a, &b);
+    if new_ids != *ids.peek() {
+        *ids.write() = new_ids;
+    }
+
+    let api_tree = api.clone();
+    let tree_resource = use_resource(move || {
+        let api = api_tree.clone();
+        let (tid, _, _) = ids();
+        let _gen = tree_cache.generation();
+        async move {
+            let tid = tid?;
+            Some(fetch_tree_cached(&api, &tree_cache, tid).await)
+        }
+    });
+
+    let api_path = api.clone();
+    let path_resource = use_resource(move || {
+        let api = api_path.clone();
+        let (tid, a, b) = ids();
+        let _gen = tree_cache.generation();
+        async move {
+            let (tid, a, b) = (tid?, a?, b?);
+            Some(api.get_relationship_path(tid, a, b).await)
+        }
+    });
+
+    let tree_name = match &*tree_resource.read() {
+        Some(Some(Ok(tree))) => tree.name.clone(),
+        _ => ids()
+            .0
+            .and_then(|tid| tree_cache.tree(tid))
+            .map(|tree| tree.name)
+            .unwrap_or_default(),
+    };
+    let selected_person_id = ids().1;
+
+    rsx! {
+        div { class: "sub-page",
+            div { class: "td-topbar",
+                nav { class: "td-bc",
+                    Link { to: Route::Home {}, class: "td-bc-logo",
+                        img {
+                            src: crate::components::layout::LOGO_PNG_B64,
+                            alt: "OxidGene",
+                            class: "td-bc-logo-img",
+                        }
+                    }
+                    if !tree_name.is_empty() {
+                        Link {
+                            to: Route::TreeDetail { tree_id: tree_id.clone(), person: None },
+                            class: "td-bc-link",
+                            "{tree_name}"
+                        }
+                        span { class: "td-bc-sep", "/" }
+                    }
+                    span { class: "td-bc-current", {i18n.t("relationship.breadcrumb")} }
+                }
+            }
+
+            div { class: "pd-page-shell",
+            TreeIconSidebar {
+                active_view: TreeSidebarView::None,
+                selected_person_id: selected_person_id,
+                show_middle_separator: false,
+                show_add_person: false,
+                show_dictionary: true,
+                show_settings: true,
+                on_profile_view: {
+                    let tree_id = tree_id.clone();
+                    move |pid: Option<Uuid>| {
+                        if let Some(pid) = pid {
+                            nav.push(Route::PersonDetail {
+                                tree_id: tree_id.clone(),
+                                person_id: pid.to_string(),
+                            });
+                        }
+                    }
+                },
+                on_pedigree_view: {
+                    let tree_id = tree_id.clone();
+                    move |pid: Option<Uuid>| {
+                        nav.push(Route::TreeDetail {
+                            tree_id: tree_id.clone(),
+                            person: pid.map(|pid| pid.to_string()),
+                        });
+                    }
+                },
+                on_add_person: move |_| {},
+                on_dictionary: {
+                    let tree_id = tree_id.clone();
+                    move |_| {
+                        nav.push(Route::Dictionary {
+                            tree_id: tree_id.clone(),
+                        });
+                    }
+                },
+                on_tasks: {
+                    let tree_id = tree_id.clone();
+                    move |_| {
+                        nav.push(Route::Tasks {
+                            tree_id: tree_id.clone(),
+                        });
+                    }
+                },
+                on_settings: {
+                    let tree_id = tree_id.clone();
+                    move |_| {
+                        nav.push(Route::Settings {
+                            tree_id: tree_id.clone(),
+                        });
+                    }
+                },
+            }
+
+            div { class: "sub-page-content",
+                h1 { class: "cmp-title", {i18n.t("relationship.title")} }
+                match &*path_resource.read() {
+                    Some(Some(Ok(path))) => render_path(i18n, &tree_id, path),
+                    Some(Some(Err(e))) => rsx! {
+                        div { class: "error-msg", "{e}" }
+                    },
+                    Some(None) => rsx! {
+                        div { class: "error-msg", {i18n.t("relationship.invalid_ids")} }
+                    },
+                    None => rsx! {
+                        div { class: "loading", {i18n.t("common.loading")} }
+                    },
+                }
+            }
+            }
+        }
+    }
+}
+
+/// The path as a breadcrumb: each person links to their page, preceded by
+/// how they relate to the person before them.
+fn render_path(i18n: I18n, tree_id: &str, path: &RelationshipPath) -> Element {
+    let Some(common) = path.common_ancestor_id else {
+        return rsx! {
+            div { class: "cmp-summary", {i18n.t("relationship.unrelated")} }
+        };
+    };
+    let name = |display_name: &Option<String>| {
+        display_name
+            .clone()
+            .unwrap_or_else(|| i18n.t("common.unnamed"))
+    };
+    let ancestor = path
+        .steps
+        .iter()
+        .find(|s| s.person.id == common)
+        .map(|s| name(&s.person.display_name))
+        .unwrap_or_default();
+    let summary = format!(
+        "{} \u{00B7} {}, {}",
+        i18n.t_args(
+            "relationship.common_ancestor",
+            &[("name", ancestor.as_str())]
+        ),
+        i18n.t_plural("relationship.generations_up", path.generations_up as usize),
+        i18n.t_plural(
+            "relationship.generations_down",
+            path.generations_down as usize
+        ),
+    );
+
+    rsx! {
+        div { class: "cmp-summary", "{summary}" }
+        nav { class: "rel-path",
+            for (i, step) in path.steps.iter().enumerate() {
+                div {
+                    key: "{step.person.id}",
+                    class: if step.person.id == common { "rel-step rel-common" } else { "rel-step" },
+                    if i > 0 {
+                        span { class: "rel-sep", "\u{2192}" }
+                    }
+                    if let Some(edge) = step.edge {
+                        span { class: "rel-edge", {i18n.t(&format!("kinship.{edge}"))} }
+                    }
+                    Link {
+                        to: Route::PersonDetail {
+                            tree_id: tree_id.to_string(),
+                            person_id: step.person.id.to_string(),
+                        },
+                        class: "rel-person",
+                        {name(&step.person.display_name)}
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,16 +1,22 @@
 //! Full-page search results powered by the server-side cache search index.
 //!
 //! Combines server-side accent-folded name matching with lightweight
-//! client-side filters (gender, date range), sorting, and pagination.
+//! client-side filters (gender, date range, research tag), sorting, and
+//! pagination. Rows of the list view can be selected and deleted together.
 //! Uses the `sub-page` layout pattern (no left sidebar).
 
+use std::collections::{HashMap, HashSet};
+
 use dioxus::prelude::*;
 use oxidgene_cache::types::SearchEntry;
 use oxidgene_core::Sex;
+use oxidgene_core::search::normalize_for_search;
 use uuid::Uuid;
 
-use crate::api::ApiClient;
-use crate::i18n::use_i18n;
+use crate::api::{ApiClient, BulkDeletePersonsBody, SurnameIndexEntry};
+use crate::components::confirm_dialog::ConfirmDialog;
+use crate::components::person_tags::TagChip;
+use crate::i18n::{I18n, use_i18n};
 use crate::router::Route;
 
 const RESULTS_PER_PAGE: usize = 25;
@@ -37,6 +43,7 @@ enum GenderFilter {
     All,
     Male,
     Female,
+    Intersex,
     Unknown,
 }
 
@@ -91,6 +98,19 @@ pub fn SearchResults(props: SearchResultsProps) -> Element {
     let mut born_to = use_signal(String::new);
     let mut died_from = use_signal(String::new);
     let mut died_to = use_signal(String::new);
+    let mut show_surname_index = use_signal(|| false);
+    let mut index_letter = use_signal(|| None::<char>);
+    // Set when a surname is picked from the index: results are narrowed to
+    // that exact surname rather than every fuzzy match of the query.
+    let mut surname_filter = use_signal(|| None::<String>);
+    let mut tag_filter = use_signal(|| None::<String>);
+
+    // ── Multi-select for bulk delete ──
+    let mut selected = use_signal(HashSet::<Uuid>::new);
+    let mut detach_families = use_signal(|| false);
+    let mut confirm_bulk_delete = use_signal(|| false);
+    let mut bulk_delete_error = use_signal(|| None::<String>);
+    let mut bulk_delete_summary = use_signal(|| None::<String>);
 
     // Sync props into signals when navigation changes the query parameters.
     let prop_last = props.last.clone();
@@ -108,7 +128,7 @@ pub fn SearchResults(props: SearchResultsProps) -> Element {
 
     // ── Server-side search ──
     let api_search = api.clone();
-    let search_resource = use_resource(move || {
+    let mut search_resource = use_resource(move || {
         let api = api_search.clone();
         let q = committed_query();
         async move {
@@ -122,6 +142,46 @@ pub fn SearchResults(props: SearchResultsProps) -> Element {
         }
     });
 
+    // ── Surname index (loaded the first time it is opened) ──
+    let api_index = api.clone();
+    let surname_index_resource = use_resource(move || {
+        let api = api_index.clone();
+        let open = show_surname_index();
+        async move {
+            match tree_id {
+                Some(tid) if open => api.surname_index(tid).await,
+                _ => Ok(Vec::new()),
+            }
+        }
+    });
+
+    // ── Research tags of every person, for row chips and the tag filter ──
+    let api_tags = api.clone();
+    let tags_resource = use_resource(move || {
+        let api = api_tags.clone();
+        async move {
+            match tree_id {
+                Some(tid) => api.list_tree_person_tags(tid).await,
+                None => Ok(Vec::new()),
+            }
+        }
+    });
+    let tags_by_person: HashMap<Uuid, Vec<String>> = {
+        let mut map: HashMap<Uuid, Vec<String>> = HashMap::new();
+        if let Some(Ok(tags)) = &*tags_resource.read() {
+            for t in tags {
+                map.entry(t.person_id).or_default().push(t.tag.clone());
+            }
+        }
+        map
+    };
+    let known_tags: Vec<String> = {
+        let mut tags: Vec<String> = tags_by_person.values().flatten().cloned().collect();
+        tags.sort_by_key(|t| t.to_lowercase());
+        tags.dedup();
+        tags
+    };
+
     // Search action: combine last + first into a query string.
     let mut do_search = move || {
         let parts: Vec<String> = [search_last(), search_first()]
@@ -130,10 +190,20 @@ pub fn SearchResults(props: SearchResultsProps) -> Element {
             .collect();
         if !parts.is_empty() {
             committed_query.set(parts.join(" "));
+            surname_filter.set(None);
             current_page.set(1);
         }
     };
 
+    let mut pick_surname = move |surname: String| {
+        search_last.set(surname.clone());
+        search_first.set(String::new());
+        committed_query.set(surname.clone());
+        surname_filter.set(Some(normalize_for_search(&surname)));
+        sort_order.set(SortOrder::NameAZ);
+        current_page.set(1);
+    };
+
     let mut do_search2 = do_search;
     let mut do_search3 = do_search;
     let on_search_enter = move |e: Event<KeyboardData>| {
@@ -156,14 +226,25 @@ pub fn SearchResults(props: SearchResultsProps) -> Element {
         }
     };
 
-    // 1) Gender filter
+    // 1) Gender filter (and exact surname, when picked from the index, and tag)
     let gender = gender_filter();
+    let surname = surname_filter();
+    let tag = tag_filter();
     let after_gender: Vec<&SearchEntry> = all_entries
         .iter()
+        .filter(|e| surname.as_deref().is_none_or(|s| e.surname_normalized == s))
+        .filter(|e| {
+            tag.as_ref().is_none_or(|t| {
+                tags_by_person
+                    .get(&e.person_id)
+                    .is_some_and(|tags| tags.contains(t))
+            })
+        })
         .filter(|e| match gender {
             GenderFilter::All => true,
             GenderFilter::Male => e.sex == Sex::Male,
             GenderFilter::Female => e.sex == Sex::Female,
+            GenderFilter::Intersex => e.sex == Sex::Intersex,
             GenderFilter::Unknown => e.sex == Sex::Unknown,
         })
         .collect();
@@ -225,6 +306,53 @@ pub fn SearchResults(props: SearchResultsProps) -> Element {
     let start = (page - 1) * per_page;
     let page_results: Vec<&SearchEntry> = sorted.into_iter().skip(start).take(per_page).collect();
 
+    let page_ids: Vec<Uuid> = page_results.iter().map(|e| e.person_id).collect();
+    let page_all_selected =
+        !page_ids.is_empty() && page_ids.iter().all(|id| selected.read().contains(id));
+    let selected_count = selected.read().len();
+
+    let api_bulk = api.clone();
+    let on_confirm_bulk_delete = move |_| {
+        let api = api_bulk.clone();
+        let Some(tid) = tree_id else { return };
+        let body = BulkDeletePersonsBody {
+            person_ids: selected.read().iter().copied().collect(),
+            cascade: detach_families(),
+        };
+        spawn(async move {
+            match api.bulk_delete_persons(tid, &body).await {
+                Ok(result) => {
+                    let family_members = result
+                        .skipped
+                        .iter()
+                        .filter(|s| s.reason == "family_member")
+                        .count();
+                    let locked = result
+                        .skipped
+                        .iter()
+                        .filter(|s| s.reason == "locked")
+                        .count();
+                    let mut summary = i18n.t_plural("search.bulk_deleted", result.deleted.len());
+                    if family_members > 0 {
+                        summary.push_str(" \u{00B7} ");
+                        summary
+                            .push_str(&i18n.t_plural("search.bulk_skipped_family", family_members));
+                    }
+                    if locked > 0 {
+                        summary.push_str(" \u{00B7} ");
+                        summary.push_str(&i18n.t_plural("search.bulk_skipped_locked", locked));
+                    }
+                    bulk_delete_summary.set(Some(summary));
+                    // Keep the skipped persons selected, to retry with families detached.
+                    selected.set(result.skipped.iter().map(|s| s.id).collect());
+                    confirm_bulk_delete.set(false);
+                    search_resource.restart();
+                }
+                Err(e) => bulk_delete_error.set(Some(format!("{e}"))),
+            }
+        });
+    };
+
     let is_loading = search_resource.read().is_none();
     let is_error = matches!(&*search_resource.read(), Some(Err(_)));
 
@@ -295,6 +423,58 @@ pub fn SearchResults(props: SearchResultsProps) -> Element {
                         span { class: if show_filters() { "sr-chevron open" } else { "sr-chevron" }, "\u{25BC}" }
                         " {i18n.t(\"search.filters\")}"
                     }
+                    button {
+                        class: if show_surname_index() { "btn btn-outline btn-sm active" } else { "btn btn-outline btn-sm" },
+                        onclick: move |_| show_surname_index.set(!show_surname_index()),
+                        span { class: if show_surname_index() { "sr-chevron open" } else { "sr-chevron" }, "\u{25BC}" }
+                        " {i18n.t(\"search.surname_index\")}"
+                    }
+                }
+                if show_surname_index() {
+                    {
+                        let entries: Vec<SurnameIndexEntry> = match &*surname_index_resource.read() {
+                            Some(Ok(entries)) => entries.clone(),
+                            _ => Vec::new(),
+                        };
+                        let letter = index_letter().or_else(|| entries.first().map(|e| e.initial));
+                        let selected = surname_filter();
+                        rsx! {
+                            div { class: "sr-surname-index",
+                                if entries.is_empty() && surname_index_resource.read().is_some() {
+                                    p { class: "sr-surname-empty", {i18n.t("search.surname_index_empty")} }
+                                } else {
+                                    div { class: "dict-letter-strip",
+                                        for c in ('A'..='Z').chain(['#']) {
+                                            button {
+                                                key: "{c}",
+                                                class: if letter == Some(c) { "dict-letter-btn active" } else { "dict-letter-btn" },
+                                                disabled: !entries.iter().any(|e| e.initial == c),
+                                                onclick: move |_| index_letter.set(Some(c)),
+                                                "{c}"
+                                            }
+                                        }
+                                    }
+                                    div { class: "sr-surname-list",
+                                        for entry in entries.into_iter().filter(|e| Some(e.initial) == letter) {
+                                            {
+                                                let is_selected = selected.as_deref() == Some(normalize_for_search(&entry.surname).as_str());
+                                                let surname = entry.surname.clone();
+                                                rsx! {
+                                                    button {
+                                                        key: "{entry.surname}",
+                                                        class: if is_selected { "sr-surname-btn active" } else { "sr-surname-btn" },
+                                                        onclick: move |_| pick_surname(surname.clone()),
+                                                        "{entry.surname}"
+                                                        span { class: "sr-surname-count", "{entry.count}" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
                 }
                 if show_filters() {
                     div { class: "sr-filters",
@@ -307,6 +487,7 @@ pub fn SearchResults(props: SearchResultsProps) -> Element {
                                         gender_filter.set(match e.value().as_str() {
                                             "Male" => GenderFilter::Male,
                                             "Female" => GenderFilter::Female,
+                                            "Intersex" => GenderFilter::Intersex,
                                             "Unknown" => GenderFilter::Unknown,
                                             _ => GenderFilter::All,
                                         });
@@ -315,6 +496,7 @@ pub fn SearchResults(props: SearchResultsProps) -> Element {
                                     option { value: "All", {i18n.t("search.all")} }
                                     option { value: "Male", {i18n.t("search.male")} }
                                     option { value: "Female", {i18n.t("search.female")} }
+                                    option { value: "Intersex", {i18n.t("search.intersex")} }
                                     option { value: "Unknown", {i18n.t("search.unknown")} }
                                 }
                             }
@@ -366,10 +548,28 @@ pub fn SearchResults(props: SearchResultsProps) -> Element {
                                     }
                                 }
                             }
+                            if !known_tags.is_empty() {
+                                div { class: "sr-filter-group",
+                                    label { {i18n.t("search.tag")} }
+                                    select {
+                                        value: tag_filter().unwrap_or_default(),
+                                        onchange: move |e: Event<FormData>| {
+                                            let value = e.value();
+                                            tag_filter.set((!value.is_empty()).then_some(value));
+                                            current_page.set(1);
+                                        },
+                                        option { value: "", {i18n.t("search.all")} }
+                                        for t in known_tags.iter() {
+                                            option { key: "{t}", value: "{t}", "{t}" }
+                                        }
+                                    }
+                                }
+                            }
                             button {
                                 class: "sr-clear-filters",
                                 onclick: move |_| {
                                     gender_filter.set(GenderFilter::All);
+                                    tag_filter.set(None);
                                     born_from.set(String::new());
                                     born_to.set(String::new());
                                     died_from.set(String::new());
@@ -432,6 +632,41 @@ pub fn SearchResults(props: SearchResultsProps) -> Element {
                     }
                 }
 
+                // ── Selection (list view) ──
+                if view_mode() == ViewMode::List && (selected_count > 0 || bulk_delete_summary().is_some()) {
+                    div { class: "sr-selection-bar",
+                        if let Some(summary) = bulk_delete_summary() {
+                            span { class: "sr-selection-summary", "{summary}" }
+                        }
+                        if selected_count > 0 {
+                            span { class: "sr-count",
+                                {i18n.t_plural("search.selected", selected_count)}
+                            }
+                            label { class: "sr-selection-detach",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: detach_families(),
+                                    onchange: move |e: Event<FormData>| detach_families.set(e.checked()),
+                                }
+                                {i18n.t("search.detach_families")}
+                            }
+                            button {
+                                class: "btn btn-danger btn-sm",
+                                onclick: move |_| {
+                                    bulk_delete_error.set(None);
+                                    confirm_bulk_delete.set(true);
+                                },
+                                {i18n.t("search.delete_selected")}
+                            }
+                            button {
+                                class: "btn btn-outline btn-sm",
+                                onclick: move |_| selected.write().clear(),
+                                {i18n.t("search.clear_selection")}
+                            }
+                        }
+                    }
+                }
+
                 // ── Results ──
                 if is_loading {
                     div { class: "sr-empty", {i18n.t("search.loading")} }
@@ -458,10 +693,48 @@ pub fn SearchResults(props: SearchResultsProps) -> Element {
                         }
                     }
                 } else {
+                    label { class: "sr-select-all",
+                        input {
+                            r#type: "checkbox",
+                            checked: page_all_selected,
+                            onchange: move |e: Event<FormData>| {
+                                let mut set = selected.write();
+                                for id in &page_ids {
+                                    if e.checked() {
+                                        set.insert(*id);
+                                    } else {
+                                        set.remove(id);
+                                    }
+                                }
+                            },
+                        }
+                        {i18n.t("search.select_page")}
+                    }
                     div {
                         class: "search-person-results sr-results-page",
                         for entry in page_results.iter() {
-                            {render_result_item(entry, &props.tree_id, &props.origin)}
+                            {
+                                let tags = tags_by_person.get(&entry.person_id).cloned().unwrap_or_default();
+                                let pid = entry.person_id;
+                                let is_selected = selected.read().contains(&pid);
+                                rsx! {
+                                    div { key: "{pid}", class: "sr-select-row",
+                                        input {
+                                            r#type: "checkbox",
+                                            class: "sr-select-box",
+                                            checked: is_selected,
+                                            onchange: move |e: Event<FormData>| {
+                                                if e.checked() {
+                                                    selected.write().insert(pid);
+                                                } else {
+                                                    selected.write().remove(&pid);
+                                                }
+                                            },
+                                        }
+                                        {render_result_item(entry, &tags, &props.tree_id, &props.origin, i18n)}
+                                    }
+                                }
+                            }
                         }
                     }
                 }
@@ -495,6 +768,21 @@ pub fn SearchResults(props: SearchResultsProps) -> Element {
                     }
                 }
             }
+
+            if confirm_bulk_delete() {
+                ConfirmDialog {
+                    title: i18n.t("search.delete_selected"),
+                    message: if detach_families() {
+                        i18n.t_plural("search.confirm_bulk_delete_detach", selected_count)
+                    } else {
+                        i18n.t_plural("search.confirm_bulk_delete", selected_count)
+                    },
+                    confirm_label: i18n.t("common.delete"),
+                    error: bulk_delete_error(),
+                    on_confirm: on_confirm_bulk_delete,
+                    on_cancel: move |_| confirm_bulk_delete.set(false),
+                }
+            }
         }
     }
 }
@@ -505,11 +793,17 @@ pub fn SearchResults(props: SearchResultsProps) -> Element {
 // SearchPerson typeahead component (used in SOSA root selector, etc.)
 // so that person rows look identical everywhere.
 
-fn render_result_item(entry: &SearchEntry, tree_id: &str, origin: &str) -> Element {
+fn render_result_item(
+    entry: &SearchEntry,
+    tags: &[String],
+    tree_id: &str,
+    origin: &str,
+    i18n: I18n,
+) -> Element {
     let sex_class = match entry.sex {
         Sex::Male => "male",
         Sex::Female => "female",
-        Sex::Unknown => "",
+        Sex::Intersex | Sex::Unknown => "",
     };
 
     let (given, surname) = match entry.display_name.rsplit_once(' ') {
@@ -560,6 +854,11 @@ fn render_result_item(entry: &SearchEntry, tree_id: &str, origin: &str) -> Eleme
                         span { class: "sp-given", "?" }
                     }
                 }
+                if let Some(ref alias) = entry.matched_name {
+                    div { class: "sp-result-alias",
+                        {i18n.t_args("search.also_known_as", &[("name", alias)])}
+                    }
+                }
                 div { class: "sp-result-dates",
                     if let Some(ref by) = entry.birth_year {
                         span { class: "sp-birth", "\u{2726} {by}" }
@@ -573,6 +872,13 @@ fn render_result_item(entry: &SearchEntry, tree_id: &str, origin: &str) -> Eleme
                         span { class: "sp-place", "{bp}" }
                     }
                 }
+                if !tags.is_empty() {
+                    div { class: "sp-result-tags",
+                        for tag in tags.iter() {
+                            TagChip { key: "{tag}", tag: tag.clone() }
+                        }
+                    }
+                }
             }
         }
     }
@@ -635,7 +941,7 @@ fn SearchPedigreeCard(
     let sex_class = match sex {
         Sex::Male => "male",
         Sex::Female => "female",
-        Sex::Unknown => "",
+        Sex::Intersex | Sex::Unknown => "",
     };
     let (given, surname) = match display_name.rsplit_once(' ') {
         Some((g, s)) => (g.to_string(), s.to_string()),
@@ -9,7 +9,7 @@ use dioxus::prelude::*;
 use oxidgene_core::types::PersonName;
 use uuid::Uuid;
 
-use crate::api::{ApiClient, UpdateTreeBody};
+use crate::api::{ApiClient, ExportProgress, GeocodeProgress, GeocodeSummary, UpdateTreeBody};
 use crate::components::search_person::SearchPerson;
 use crate::components::tree_cache::{fetch_tree_cached, use_tree_cache};
 use crate::components::tree_icon_sidebar::{TreeIconSidebar, TreeSidebarView};
@@ -33,6 +33,9 @@ pub fn Settings(tree_id: String) -> Element {
     let mut export_success = use_signal(|| None::<String>);
     let export_format = use_signal(|| "gedcom".to_string());
     let export_merge_occupations = use_signal(|| false);
+    let mut export_progress = use_signal(ExportProgress::default);
+    // Record count from the estimate, until the job reports its own.
+    let mut export_estimate = use_signal(|| 0usize);
 
     let tree_id_parsed = tree_id.parse::<Uuid>().ok();
 
@@ -76,17 +79,22 @@ pub fn Settings(tree_id: String) -> Element {
         export_loading.set(true);
         export_error.set(None);
         export_success.set(None);
+        export_progress.set(ExportProgress::default());
         spawn(async move {
             if let Some(tid) = tree_id_parsed {
                 let extension = if is_gedzip { "gdz" } else { "ged" };
                 let file_name = format!("{base_name}.{extension}");
-                let bytes_result = if is_gedzip {
-                    api.export_gedzip(tid, merge_occupations).await
-                } else {
-                    api.export_gedcom(tid, merge_occupations)
+                export_estimate.set(
+                    api.export_estimate(tid)
                         .await
-                        .map(|r| r.gedcom.into_bytes())
-                };
+                        .map(|e| e.records_total as usize)
+                        .unwrap_or(0),
+                );
+                let bytes_result = api
+                    .export_tree(tid, is_gedzip, merge_occupations, |p| {
+                        export_progress.set(p)
+                    })
+                    .await;
                 match bytes_result {
                     Ok(bytes) => {
                         #[cfg(target_arch = "wasm32")]
@@ -227,6 +235,14 @@ pub fn Settings(tree_id: String) -> Element {
                         });
                     }
                 },
+                on_tasks: {
+                    let tree_id = tree_id.clone();
+                    move |_| {
+                        nav.push(Route::Tasks {
+                            tree_id: tree_id.clone(),
+                        });
+                    }
+                },
                 on_settings: move |_| {},
             }
 
@@ -274,6 +290,11 @@ pub fn Settings(tree_id: String) -> Element {
                             onclick: move |_| active_section.set("duplicates".to_string()),
                             {i18n.t("settings.duplicates")}
                         }
+                        button {
+                            class: if sec == "geocoding" { "settings-nav-item active" } else { "settings-nav-item" },
+                            onclick: move |_| active_section.set("geocoding".to_string()),
+                            {i18n.t("settings.geocoding")}
+                        }
                     }
                     div { class: "settings-nav-group",
                         div { class: "settings-nav-group-label", {i18n.t("common.export")} }
@@ -309,11 +330,15 @@ pub fn Settings(tree_id: String) -> Element {
                         ExportSection {
                             on_export: on_export,
                             loading: export_loading(),
+                            progress: export_progress(),
+                            estimate: export_estimate(),
                             error: export_error(),
                             success: export_success(),
                             format: export_format,
                             merge_occupations: export_merge_occupations,
                         }
+                    } else if sec == "geocoding" {
+                        GeocodingSection { tree_id: tree_id.clone() }
                     } else if sec == "appearance" {
                         AppearanceSection { is_dark }
                     } else if sec == "language" {
@@ -545,6 +570,10 @@ fn TreeRootsSection(
 fn ExportSection(
     on_export: EventHandler<MouseEvent>,
     loading: bool,
+    /// Progress of the running export job.
+    progress: ExportProgress,
+    /// Estimated record count, shown until the job knows its own.
+    estimate: usize,
     error: Option<String>,
     success: Option<String>,
     format: Signal<String>,
@@ -556,6 +585,25 @@ fn ExportSection(
     } else {
         i18n.t("settings.download_ged")
     };
+    let total = if progress.records_total > 0 {
+        progress.records_total
+    } else {
+        estimate
+    };
+    let progress_label = match progress.phase.as_str() {
+        "serializing" => i18n.t_args(
+            "settings.export_progress",
+            &[
+                ("done", &progress.records_serialized.to_string()),
+                ("total", &total.to_string()),
+            ],
+        ),
+        "packaging" => i18n.t("settings.export_packaging"),
+        _ => i18n.t_args(
+            "settings.export_preparing",
+            &[("total", &total.to_string())],
+        ),
+    };
     rsx! {
         div { class: "settings-section",
             div { class: "settings-section-eyebrow", {i18n.t("common.export")} }
@@ -605,6 +653,15 @@ fn ExportSection(
                         }
                     }
                 }
+                if loading {
+                    div { class: "export-progress",
+                        progress {
+                            max: "{total.max(1)}",
+                            value: "{progress.records_serialized}",
+                        }
+                        span { "{progress_label}" }
+                    }
+                }
                 if let Some(err) = &error {
                     div { class: "error-msg", style: "margin-top: 12px;", "{err}" }
                 }
@@ -618,6 +675,90 @@ fn ExportSection(
     }
 }
 
+/// Batch geocoding of every place lacking coordinates. The job runs
+/// server-side at the provider's rate limit; this section only follows it.
+#[component]
+fn GeocodingSection(tree_id: String) -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+    let tree_id_parsed = tree_id.parse::<Uuid>().ok();
+    let mut running = use_signal(|| false);
+    let mut progress = use_signal(GeocodeProgress::default);
+    let mut summary = use_signal(|| None::<GeocodeSummary>);
+    let mut error = use_signal(|| None::<String>);
+
+    let on_start = move |_| {
+        let Some(tid) = tree_id_parsed else { return };
+        let api = api.clone();
+        spawn(async move {
+            running.set(true);
+            summary.set(None);
+            error.set(None);
+            progress.set(GeocodeProgress::default());
+            match api.geocode_all_places(tid, |p| progress.set(p)).await {
+                Ok(s) => summary.set(Some(s)),
+                Err(e) => error.set(Some(format!("{e}"))),
+            }
+            running.set(false);
+        });
+    };
+
+    let p = progress();
+    rsx! {
+        div { class: "settings-section",
+            div { class: "settings-section-eyebrow", {i18n.t("settings.tools")} }
+            h2 { class: "settings-section-title", {i18n.t("settings.geocoding")} }
+            p { class: "settings-section-subtitle", {i18n.t("settings.geocoding_desc")} }
+
+            div { class: "card", style: "margin-top: 16px;",
+                div { style: "display: flex; align-items: center; gap: 16px;",
+                    div { style: "flex: 1; font-size: 0.85rem; color: var(--text-secondary);",
+                        if running() {
+                            {i18n.t_args("settings.geocoding_progress", &[
+                                ("done", &p.places_done.to_string()),
+                                ("total", &p.places_total.to_string()),
+                            ])}
+                        }
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        disabled: running(),
+                        onclick: on_start,
+                        if running() { {i18n.t("settings.geocoding_running")} } else { {i18n.t("settings.geocoding_start")} }
+                    }
+                }
+                if let Some(err) = error() {
+                    div { class: "error-msg", style: "margin-top: 12px;", "{err}" }
+                }
+                if let Some(s) = summary() {
+                    div { class: "success-msg", style: "margin-top: 12px;",
+                        {i18n.t_args("settings.geocoding_summary", &[
+                            ("succeeded", &s.succeeded.to_string()),
+                            ("ambiguous", &s.ambiguous.to_string()),
+                            ("failed", &s.failed.to_string()),
+                        ])}
+                    }
+                    ul { style: "margin-top: 8px; font-size: 0.82rem;",
+                        for r in s.places.iter().filter(|r| r.status != "success") {
+                            li { key: "{r.place_id}",
+                                class: if r.status == "failed" { "check-issue-error" } else { "" },
+                                "{r.name} \u{2014} "
+                                if r.status == "ambiguous" {
+                                    {i18n.t_args("settings.geocoding_ambiguous", &[("count", &r.candidates.to_string())])}
+                                } else if let Some(e) = &r.error {
+                                    "{e}"
+                                } else {
+                                    {i18n.t("settings.geocoding_no_match")}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 fn safe_export_file_name(tree_name: &str) -> String {
     let safe = tree_name
         .chars()
@@ -689,6 +830,21 @@ const SETTINGS_STYLES: &str = r#"
         margin-bottom: 20px;
     }
 
+    .export-progress {
+        display: flex;
+        align-items: center;
+        gap: 12px;
+        margin-top: 12px;
+        font-size: 0.82rem;
+        color: var(--text-secondary);
+    }
+
+    .export-progress progress {
+        flex: 1;
+        height: 6px;
+        accent-color: var(--orange);
+    }
+
     .settings-nav-group-label {
         font-size: 0.68rem;
         font-weight: 700;
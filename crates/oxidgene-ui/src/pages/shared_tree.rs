@@ -0,0 +1,158 @@
+//! Read-only view of a tree reached through a share link.
+
+use std::collections::HashMap;
+
+use dioxus::prelude::*;
+use oxidgene_core::EventType;
+use uuid::Uuid;
+
+use crate::api::ApiClient;
+use crate::i18n::use_i18n;
+
+/// One row of the shared person list.
+#[derive(Clone, PartialEq)]
+struct SharedPersonRow {
+    id: Uuid,
+    surname: String,
+    given_names: String,
+    birth_year: Option<i32>,
+    death_year: Option<i32>,
+}
+
+/// Shared tree page: tree name plus an alphabetical person list built from
+/// the (possibly redacted) snapshot. No editing controls are rendered.
+#[component]
+pub fn SharedTree(token: String) -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+
+    let token_sig = use_signal(|| token.clone());
+    let resource = use_resource(move || {
+        let api = api.clone();
+        let token = token_sig();
+        async move {
+            let tree = api.get_shared_tree(&token).await?;
+            let snapshot = api.get_shared_snapshot(&token).await?;
+            Ok::<_, crate::api::ApiError>((tree, snapshot))
+        }
+    });
+
+    let content = match &*resource.read() {
+        None => rsx! {
+            div { class: "loading", {i18n.t("common.loading")} }
+        },
+        Some(Err(_)) => rsx! {
+            div { class: "empty-state",
+                h1 { {i18n.t("shared.unavailable")} }
+                p { class: "text-muted", {i18n.t("shared.unavailable_hint")} }
+            }
+        },
+        Some(Ok((tree, snapshot))) => {
+            let mut years: HashMap<Uuid, (Option<i32>, Option<i32>)> = HashMap::new();
+            for event in &snapshot.events {
+                let Some(pid) = event.person_id else { continue };
+                let entry = years.entry(pid).or_default();
+                match event.event_type {
+                    EventType::Birth if entry.0.is_none() => entry.0 = event.year(),
+                    EventType::Death if entry.1.is_none() => entry.1 = event.year(),
+                    _ => {}
+                }
+            }
+            let mut rows: Vec<SharedPersonRow> = snapshot
+                .names
+                .iter()
+                .filter(|n| n.is_primary)
+                .map(|n| {
+                    let (birth_year, death_year) =
+                        years.get(&n.person_id).copied().unwrap_or_default();
+                    SharedPersonRow {
+                        id: n.person_id,
+                        surname: n.surname.clone().unwrap_or_default(),
+                        given_names: n.given_names.clone().unwrap_or_default(),
+                        birth_year,
+                        death_year,
+                    }
+                })
+                .collect();
+            rows.sort_by(|a, b| {
+                a.surname
+                    .to_lowercase()
+                    .cmp(&b.surname.to_lowercase())
+                    .then_with(|| a.given_names.cmp(&b.given_names))
+            });
+            let living_label = i18n.t("shared.living");
+            rsx! {
+                div { class: "shared-header",
+                    h1 { "{tree.name}" }
+                    if let Some(desc) = &tree.description {
+                        p { class: "text-muted", "{desc}" }
+                    }
+                    p { class: "shared-notice", {i18n.t("shared.read_only")} }
+                }
+                ul { class: "shared-person-list",
+                    for row in rows {{
+                        let lifespan = match (row.birth_year, row.death_year) {
+                            (None, None) => String::new(),
+                            (b, d) => format!(
+                                "{}\u{2013}{}",
+                                b.map(|y| y.to_string()).unwrap_or_default(),
+                                d.map(|y| y.to_string()).unwrap_or_default(),
+                            ),
+                        };
+                        let hidden = row.given_names.is_empty() && tree.privatize;
+                        rsx! {
+                            li { key: "{row.id}",
+                                strong { "{row.surname}" }
+                                if hidden {
+                                    span { class: "text-muted", " {living_label}" }
+                                } else {
+                                    " {row.given_names}"
+                                }
+                                if !lifespan.is_empty() {
+                                    span { class: "shared-lifespan", " ({lifespan})" }
+                                }
+                            }
+                        }
+                    }}
+                }
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "shared-page",
+            {content}
+        }
+        style { {SHARED_STYLES} }
+    }
+}
+
+const SHARED_STYLES: &str = r#"
+    .shared-page {
+        max-width: 760px;
+        margin: 0 auto;
+        padding: 2rem 1.5rem 4rem;
+    }
+
+    .shared-header h1 { margin-bottom: 0.25rem; }
+
+    .shared-notice {
+        font-size: 0.85rem;
+        color: var(--text-muted);
+        border-left: 3px solid var(--border);
+        padding-left: 0.5rem;
+    }
+
+    .shared-person-list {
+        list-style: none;
+        padding: 0;
+        margin-top: 1.5rem;
+    }
+
+    .shared-person-list li {
+        padding: 0.4rem 0;
+        border-bottom: 1px solid var(--border);
+    }
+
+    .shared-lifespan { color: var(--text-muted); }
+"#;
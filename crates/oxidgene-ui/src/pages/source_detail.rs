@@ -0,0 +1,298 @@
+//! Source detail page: a source's metadata and everything derived from it —
+//! the persons, events and families whose citations reference the source,
+//! with the cited page and text.
+
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+use crate::api::{ApiClient, SourceUsageEntry};
+use crate::components::tree_cache::{fetch_tree_cached, use_tree_cache};
+use crate::components::tree_icon_sidebar::{TreeIconSidebar, TreeSidebarView};
+use crate::i18n::{I18n, use_i18n};
+use crate::router::Route;
+
+#[component]
+pub fn SourceDetail(tree_id: String, source_id: String) -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+    let nav = use_navigator();
+    let tree_cache = use_tree_cache();
+
+    let mut ids = use_signal(|| (tree_id.parse::<Uuid>().ok(), source_id.parse::<Uuid>().ok()));
+    let new_ids = (tree_id.parse::<Uuid>().ok(), source_id.parse::<Uuid>().ok());
+    if new_ids != *ids.peek() {
+        *ids.write() = new_ids;
+    }
+
+    let api_tree = api.clone();
+    let tree_resource = use_resource(move || {
+        let api = api_tree.clone();
+        let (tid, _) = ids();
+        let _gen = tree_cache.generation();
+        async move {
+            let tid = tid?;
+            Some(fetch_tree_cached(&api, &tree_cache, tid).await)
+        }
+    });
+
+    let api_source = api.clone();
+    let source_resource = use_resource(move || {
+        let api = api_source.clone();
+        let (tid, sid) = ids();
+        async move {
+            let (tid, sid) = (tid?, sid?);
+            Some(api.get_source(tid, sid).await)
+        }
+    });
+
+    let api_usage = api.clone();
+    let usage_resource = use_resource(move || {
+        let api = api_usage.clone();
+        let (tid, sid) = ids();
+        async move {
+            let (tid, sid) = (tid?, sid?);
+            Some(api.source_usage(tid, sid).await)
+        }
+    });
+
+    let tree_name = match &*tree_resource.read() {
+        Some(Some(Ok(tree))) => tree.name.clone(),
+        _ => ids()
+            .0
+            .and_then(|tid| tree_cache.tree(tid))
+            .map(|tree| tree.name)
+            .unwrap_or_default(),
+    };
+    let selected_person_id = match &*tree_resource.read() {
+        Some(Some(Ok(tree))) => tree.sosa_root_person_id,
+        _ => None,
+    };
+    let source_title = match &*source_resource.read() {
+        Some(Some(Ok(source))) => source.title.clone(),
+        _ => String::new(),
+    };
+
+    rsx! {
+        div { class: "sub-page",
+            div { class: "td-topbar",
+                nav { class: "td-bc",
+                    Link { to: Route::Home {}, class: "td-bc-logo",
+                        img {
+                            src: crate::components::layout::LOGO_PNG_B64,
+                            alt: "OxidGene",
+                            class: "td-bc-logo-img",
+                        }
+                    }
+                    if !tree_name.is_empty() {
+                        Link {
+                            to: Route::TreeDetail { tree_id: tree_id.clone(), person: None },
+                            class: "td-bc-link",
+                            "{tree_name}"
+                        }
+                        span { class: "td-bc-sep", "/" }
+                    }
+                    Link {
+                        to: Route::Dictionary { tree_id: tree_id.clone() },
+                        class: "td-bc-link",
+                        {i18n.t("dictionary.breadcrumb")}
+                    }
+                    span { class: "td-bc-sep", "/" }
+                    span { class: "td-bc-current", "{source_title}" }
+                }
+            }
+
+            div { class: "pd-page-shell",
+            TreeIconSidebar {
+                active_view: TreeSidebarView::None,
+                selected_person_id: selected_person_id,
+                show_middle_separator: false,
+                show_add_person: false,
+                show_dictionary: true,
+                show_settings: true,
+                on_profile_view: {
+                    let tree_id = tree_id.clone();
+                    move |pid: Option<Uuid>| {
+                        if let Some(pid) = pid {
+                            nav.push(Route::PersonDetail {
+                                tree_id: tree_id.clone(),
+                                person_id: pid.to_string(),
+                            });
+                        }
+                    }
+                },
+                on_pedigree_view: {
+                    let tree_id = tree_id.clone();
+                    move |pid: Option<Uuid>| {
+                        nav.push(Route::TreeDetail {
+                            tree_id: tree_id.clone(),
+                            person: pid.map(|pid| pid.to_string()),
+                        });
+                    }
+                },
+                on_add_person: move |_| {},
+                on_dictionary: {
+                    let tree_id = tree_id.clone();
+                    move |_| {
+                        nav.push(Route::Dictionary {
+                            tree_id: tree_id.clone(),
+                        });
+                    }
+                },
+                on_tasks: {
+                    let tree_id = tree_id.clone();
+                    move |_| {
+                        nav.push(Route::Tasks {
+                            tree_id: tree_id.clone(),
+                        });
+                    }
+                },
+                on_settings: {
+                    let tree_id = tree_id.clone();
+                    move |_| {
+                        nav.push(Route::Settings {
+                            tree_id: tree_id.clone(),
+                        });
+                    }
+                },
+            }
+
+            div { class: "sub-page-content",
+                match &*source_resource.read() {
+                    Some(Some(Ok(source))) => {
+                        let meta = [
+                            source.author.clone(),
+                            source.publisher.clone(),
+                            source.repository_name.clone(),
+                        ]
+                        .into_iter()
+                        .flatten()
+                        .filter(|s| !s.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" \u{00B7} ");
+                        rsx! {
+                            div { class: "src-header",
+                                h1 { class: "src-title", "{source.title}" }
+                                if let Some(abbr) = source.abbreviation.as_ref().filter(|a| !a.is_empty()) {
+                                    span { class: "src-abbr", "{abbr}" }
+                                }
+                                if !meta.is_empty() {
+                                    div { class: "src-meta", "{meta}" }
+                                }
+                            }
+                        }
+                    }
+                    Some(Some(Err(e))) => rsx! {
+                        div { class: "error-msg", "{e}" }
+                    },
+                    Some(None) => rsx! {
+                        div { class: "error-msg", {i18n.t("source_detail.invalid_id")} }
+                    },
+                    None => rsx! {
+                        div { class: "loading", {i18n.t("common.loading")} }
+                    },
+                }
+
+                match &*usage_resource.read() {
+                    Some(Some(Ok(entries))) => render_usage(i18n, &tree_id, entries),
+                    Some(Some(Err(e))) => rsx! {
+                        div { class: "error-msg", "{e}" }
+                    },
+                    _ => rsx! {},
+                }
+            }
+            }
+        }
+    }
+}
+
+/// Citations grouped by the person or family they concern (the server
+/// returns them ordered by that label).
+fn render_usage(i18n: I18n, tree_id: &str, entries: &[SourceUsageEntry]) -> Element {
+    let mut groups: Vec<Vec<&SourceUsageEntry>> = Vec::new();
+    for entry in entries {
+        match groups.last_mut() {
+            Some(group)
+                if group[0].person_id == entry.person_id
+                    && group[0].family_id == entry.family_id =>
+            {
+                group.push(entry)
+            }
+            _ => groups.push(vec![entry]),
+        }
+    }
+
+    rsx! {
+        h2 { class: "src-section-title", {i18n.t("source_detail.usage_title")} }
+        div { class: "dict-src-summary",
+            {i18n.t_plural("dictionary.citation_count", entries.len())}
+        }
+        if entries.is_empty() {
+            div { class: "src-usage-empty", {i18n.t("source_detail.no_usage")} }
+        }
+        div { class: "src-usage-list",
+            for group in groups {
+                {
+                    let first = group[0];
+                    let label = if first.label.is_empty() {
+                        i18n.t("common.unnamed")
+                    } else {
+                        first.label.clone()
+                    };
+                    rsx! {
+                        div {
+                            key: "{first.citation_id}",
+                            class: "src-usage-group",
+                            if let Some(person_id) = first.person_id {
+                                Link {
+                                    to: Route::PersonDetail {
+                                        tree_id: tree_id.to_string(),
+                                        person_id: person_id.to_string(),
+                                    },
+                                    class: "src-usage-owner",
+                                    "{label}"
+                                }
+                            } else {
+                                span { class: "src-usage-owner", "{label}" }
+                            }
+                            for entry in group {
+                                {render_citation(i18n, entry)}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn render_citation(i18n: I18n, entry: &SourceUsageEntry) -> Element {
+    let mut what = match (entry.owner_kind.as_str(), entry.event_type) {
+        ("event", Some(event_type)) => {
+            let kind = i18n.t(&format!("event.type.{event_type}"));
+            match entry.event_date.as_deref().filter(|d| !d.is_empty()) {
+                Some(date) => format!("{kind} \u{00B7} {date}"),
+                None => kind,
+            }
+        }
+        (kind, _) => i18n.t(&format!("source_detail.kind.{kind}")),
+    };
+    if let Some(fact) = entry.fact_type {
+        what = format!("{what} ({})", i18n.t(&format!("citation.fact.{fact}")));
+    }
+    let confidence = i18n.t(&format!("confidence.{}", entry.confidence));
+
+    rsx! {
+        div { key: "{entry.citation_id}", class: "src-usage-item",
+            div { class: "src-usage-line",
+                span { class: "src-usage-what", "{what}" }
+                if let Some(page) = entry.page.as_ref().filter(|p| !p.is_empty()) {
+                    span { class: "src-usage-page", "{page}" }
+                }
+                span { class: "src-usage-confidence", "{confidence}" }
+            }
+            if let Some(text) = entry.text.as_ref().filter(|t| !t.is_empty()) {
+                blockquote { class: "src-usage-text", "{text}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,227 @@
+//! Research to-do list: every task of a tree, soonest due first, filterable
+//! by status, each linked to the person it concerns.
+
+use std::collections::HashMap;
+
+use dioxus::prelude::*;
+use oxidgene_core::TaskStatus;
+use uuid::Uuid;
+
+use crate::api::ApiClient;
+use crate::components::research_tasks::TaskItem;
+use crate::components::tree_cache::{fetch_tree_cached, use_tree_cache};
+use crate::components::tree_icon_sidebar::{TreeIconSidebar, TreeSidebarView};
+use crate::i18n::use_i18n;
+use crate::router::Route;
+use crate::utils::resolve_name;
+
+#[component]
+pub fn Tasks(tree_id: String) -> Element {
+    let i18n = use_i18n();
+    let api = use_context::<ApiClient>();
+    let nav = use_navigator();
+    let tree_cache = use_tree_cache();
+
+    let mut tree_id_parsed = use_signal(|| tree_id.parse::<Uuid>().ok());
+    let new_tid = tree_id.parse::<Uuid>().ok();
+    if new_tid != *tree_id_parsed.peek() {
+        *tree_id_parsed.write() = new_tid;
+    }
+
+    // `None` shows every task.
+    let mut status_filter = use_signal(|| Some(TaskStatus::Open));
+    let mut refresh = use_signal(|| 0u32);
+
+    let api_tree = api.clone();
+    let tree_resource = use_resource(move || {
+        let api = api_tree.clone();
+        let tid = tree_id_parsed();
+        let _gen = tree_cache.generation();
+        async move {
+            let tid = tid?;
+            Some(fetch_tree_cached(&api, &tree_cache, tid).await)
+        }
+    });
+
+    let api_tasks = api.clone();
+    let tasks_resource = use_resource(move || {
+        let api = api_tasks.clone();
+        let tid = tree_id_parsed();
+        let status = status_filter();
+        let _tick = refresh();
+        async move {
+            let tid = tid?;
+            Some(api.list_tasks(tid, status, None).await)
+        }
+    });
+
+    // Names of the persons the listed tasks concern.
+    let api_names = api.clone();
+    let names_resource = use_resource(move || {
+        let api = api_names.clone();
+        let tid = tree_id_parsed();
+        let mut person_ids: Vec<Uuid> = match &*tasks_resource.read() {
+            Some(Some(Ok(tasks))) => tasks.iter().filter_map(|t| t.person_id).collect(),
+            _ => vec![],
+        };
+        person_ids.sort();
+        person_ids.dedup();
+        async move {
+            let mut name_map = HashMap::new();
+            let Some(tid) = tid else {
+                return name_map;
+            };
+            for pid in person_ids {
+                if let Ok(names) = api.list_person_names(tid, pid).await {
+                    name_map.insert(pid, names);
+                }
+            }
+            name_map
+        }
+    });
+
+    let tree_name = match &*tree_resource.read() {
+        Some(Some(Ok(tree))) => tree.name.clone(),
+        _ => tree_id_parsed()
+            .and_then(|tid| tree_cache.tree(tid))
+            .map(|tree| tree.name)
+            .unwrap_or_default(),
+    };
+    let selected_person_id = match &*tree_resource.read() {
+        Some(Some(Ok(tree))) => tree.sosa_root_person_id,
+        _ => None,
+    };
+    let name_map = names_resource.read().clone().unwrap_or_default();
+
+    let filters = [
+        (Some(TaskStatus::Open), "tasks.filter_open"),
+        (Some(TaskStatus::Done), "tasks.filter_done"),
+        (None, "tasks.filter_all"),
+    ];
+
+    rsx! {
+        div { class: "sub-page",
+            div { class: "td-topbar",
+                nav { class: "td-bc",
+                    Link { to: Route::Home {}, class: "td-bc-logo",
+                        img {
+                            src: crate::components::layout::LOGO_PNG_B64,
+                            alt: "OxidGene",
+                            class: "td-bc-logo-img",
+                        }
+                    }
+                    if !tree_name.is_empty() {
+                        Link {
+                            to: Route::TreeDetail { tree_id: tree_id.clone(), person: None },
+                            class: "td-bc-link",
+                            "{tree_name}"
+                        }
+                        span { class: "td-bc-sep", "/" }
+                    }
+                    span { class: "td-bc-current", {i18n.t("tasks.breadcrumb")} }
+                }
+            }
+
+            div { class: "pd-page-shell",
+            TreeIconSidebar {
+                active_view: TreeSidebarView::None,
+                selected_person_id: selected_person_id,
+                show_middle_separator: false,
+                show_add_person: false,
+                show_tasks: false,
+                on_profile_view: {
+                    let tree_id = tree_id.clone();
+                    move |pid: Option<Uuid>| {
+                        if let Some(pid) = pid {
+                            nav.push(Route::PersonDetail {
+                                tree_id: tree_id.clone(),
+                                person_id: pid.to_string(),
+                            });
+                        }
+                    }
+                },
+                on_pedigree_view: {
+                    let tree_id = tree_id.clone();
+                    move |pid: Option<Uuid>| {
+                        nav.push(Route::TreeDetail {
+                            tree_id: tree_id.clone(),
+                            person: pid.map(|pid| pid.to_string()),
+                        });
+                    }
+                },
+                on_add_person: move |_| {},
+                on_dictionary: {
+                    let tree_id = tree_id.clone();
+                    move |_| {
+                        nav.push(Route::Dictionary {
+                            tree_id: tree_id.clone(),
+                        });
+                    }
+                },
+                on_tasks: move |_| {},
+                on_settings: {
+                    let tree_id = tree_id.clone();
+                    move |_| {
+                        nav.push(Route::Settings {
+                            tree_id: tree_id.clone(),
+                        });
+                    }
+                },
+            }
+
+            div { class: "sub-page-content",
+                h1 { class: "src-title", {i18n.t("tasks.title")} }
+
+                div { class: "task-filter",
+                    for (status, key) in filters {
+                        button {
+                            key: "{key}",
+                            class: if status_filter() == status { "btn btn-primary" } else { "btn btn-outline" },
+                            onclick: move |_| status_filter.set(status),
+                            {i18n.t(key)}
+                        }
+                    }
+                }
+
+                match &*tasks_resource.read() {
+                    Some(Some(Ok(tasks))) if tasks.is_empty() => rsx! {
+                        p { class: "text-muted", {i18n.t("tasks.none")} }
+                    },
+                    Some(Some(Ok(tasks))) => rsx! {
+                        ul { class: "task-list",
+                            for task in tasks.iter().cloned() {
+                                TaskItem {
+                                    key: "{task.id}",
+                                    task: task.clone(),
+                                    on_change: move |_| refresh += 1,
+                                    if let Some(pid) = task.person_id {
+                                        Link {
+                                            to: Route::PersonDetail {
+                                                tree_id: tree_id.clone(),
+                                                person_id: pid.to_string(),
+                                            },
+                                            class: "task-owner",
+                                            {resolve_name(pid, &name_map)}
+                                        }
+                                    } else {
+                                        span { class: "task-owner text-muted", {i18n.t("tasks.family_owner")} }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    Some(Some(Err(e))) => rsx! {
+                        div { class: "error-msg", {i18n.t_args("tasks.load_error", &[("error", &e.to_string())])} }
+                    },
+                    Some(None) => rsx! {
+                        div { class: "error-msg", {i18n.t("tasks.invalid_tree")} }
+                    },
+                    None => rsx! {
+                        div { class: "loading", {i18n.t("common.loading")} }
+                    },
+                }
+            }
+            }
+        }
+    }
+}
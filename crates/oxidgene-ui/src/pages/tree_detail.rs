@@ -2,24 +2,30 @@
 //!
 //! Shows the tree breadcrumb, search fields, the [`PedigreeChart`] as the
 //! main view, a context menu for person actions (including search-or-create
-//! flows for AddSpouse/AddParents/AddChild), and union editing.
+//! flows for AddFather/AddMother/AddSpouse/AddChild/AddSibling), union
+//! editing, and a strip of family cards with the marriage and divorce,
+//! notes and sources of each union of the person the chart is centered on.
 
 use std::collections::HashMap;
 
 use dioxus::prelude::*;
+use oxidgene_core::RelativeKind;
 use uuid::Uuid;
 
-use crate::api::ApiClient;
+use crate::api::{ApiClient, person_photos};
 use crate::components::confirm_dialog::ConfirmDialog;
 use crate::components::context_menu::{ContextMenu, PersonAction};
-use crate::components::pedigree_chart::{PedigreeChart, PedigreeData};
+use crate::components::pedigree_chart::{PedigreeChart, PedigreeData, UnionEvents};
 use crate::components::person_form::{PersonForm, PersonFormCreateContext};
+use crate::components::quick_entry::QuickEntry;
+use crate::components::recent_activity::RecentActivity;
 use crate::components::search_person::SearchPerson;
 use crate::components::topbar_search::TopbarSearch;
 use crate::components::tree_cache::{fetch_tree_cached, use_tree_cache, use_view_state_cache};
 use crate::components::union_form::UnionForm;
 use crate::i18n::use_i18n;
 use crate::router::Route;
+use crate::session::use_session;
 use crate::utils::resolve_name;
 
 /// Describes which linking flow is active.
@@ -27,14 +33,16 @@ use crate::utils::resolve_name;
 enum LinkingMode {
     /// Adding a spouse for the given person.
     Spouse(Uuid),
-    /// Adding parents for the given person (child_id).
-    Parents(Uuid),
+    /// Adding a father or mother for the given person (child_id).
+    Parent(Uuid, RelativeKind),
     /// Adding a child for the given person (parent_id).
     Child(Uuid),
     /// Adding a sibling for the given person.
     Sibling(Uuid),
     /// Merging person with another (search target).
     Merge(Uuid),
+    /// Showing how the person is related to another (search target).
+    Relationship(Uuid),
 }
 
 /// Page rendered at `/trees/:tree_id?person=...`.
@@ -101,6 +109,7 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
     // ── Person edit modal ──
     let mut editing_person_id = use_signal(|| None::<Uuid>);
     let mut creating_person_ctx = use_signal(|| None::<PersonFormCreateContext>);
+    let mut show_quick_entry = use_signal(|| false);
 
     // ── Union edit modal ──
     let mut editing_union_id = use_signal(|| None::<Uuid>);
@@ -129,6 +138,14 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
         }
     });
 
+    // Tell the shell which tree is open, so it can be reopened next time.
+    let session = use_session();
+    use_effect(move || {
+        if let (Some(session), Some(Ok(tree))) = (&session, &*tree_resource.read()) {
+            session.tree_opened(tree.id);
+        }
+    });
+
     // ── Fetch SOSA ancestor IDs from closure table ──
     // This set is used to display the green SOSA badge on ancestor cards,
     // even when jumping to a distant ancestor outside the pedigree window.
@@ -225,11 +242,7 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
                 return std::collections::HashMap::new();
             };
             match api.list_media_links_for_tree(tid).await {
-                Ok(rows) => rows
-                    .into_iter()
-                    .filter(|r| r.entity_type == "person")
-                    .map(|r| (r.entity_id, r.file_path))
-                    .collect::<std::collections::HashMap<Uuid, String>>(),
+                Ok(rows) => person_photos(&rows),
                 Err(_) => std::collections::HashMap::new(),
             }
         }
@@ -288,6 +301,16 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
         }
     };
 
+    // Family cards for the unions of the person the chart is centered on.
+    let family_cards: Vec<(Uuid, String, UnionEvents)> = match (&pedigree_data, root_person_id) {
+        (Some(data), Some(root_id)) => data
+            .unions_for_person(root_id)
+            .into_iter()
+            .map(|(fid, partner, _)| (fid, partner, data.union_events(fid)))
+            .collect(),
+        _ => vec![],
+    };
+
     // Union list for context menu multi-union sub-list.
     let ctx_unions: Vec<(Uuid, String, String)> = {
         let ctx = context_menu_person();
@@ -317,8 +340,14 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
             PersonAction::Merge => {
                 linking_mode.set(Some(LinkingMode::Merge(pid)));
             }
-            PersonAction::AddParents => {
-                linking_mode.set(Some(LinkingMode::Parents(pid)));
+            PersonAction::Relationship => {
+                linking_mode.set(Some(LinkingMode::Relationship(pid)));
+            }
+            PersonAction::AddFather => {
+                linking_mode.set(Some(LinkingMode::Parent(pid, RelativeKind::Father)));
+            }
+            PersonAction::AddMother => {
+                linking_mode.set(Some(LinkingMode::Parent(pid, RelativeKind::Mother)));
             }
             PersonAction::AddSpouse => {
                 linking_mode.set(Some(LinkingMode::Spouse(pid)));
@@ -398,7 +427,7 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
                 };
                 let body = crate::api::AddSpouseBody {
                     person_id: for_pid,
-                    role: oxidgene_core::SpouseRole::Partner,
+                    role: None,
                     sort_order: 0,
                 };
                 let _ = api.add_spouse(tid, family.id, &body).await;
@@ -406,7 +435,7 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
             };
             let body = crate::api::AddSpouseBody {
                 person_id,
-                role: oxidgene_core::SpouseRole::Partner,
+                role: None,
                 sort_order: 1,
             };
             let _ = api.add_spouse(tid, fid, &body).await;
@@ -417,60 +446,34 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
 
     // AddSpouse: create new person as spouse.
     let api_new_spouse = api.clone();
-    let pedigree_data_new_spouse = pedigree_data.clone();
     let on_create_new_spouse = move |_| {
         let api = api_new_spouse.clone();
         let Some(tid) = tree_id_parsed() else { return };
         let Some(LinkingMode::Spouse(for_pid)) = linking_mode() else {
             return;
         };
-        let existing_family_id = pedigree_data_new_spouse
-            .as_ref()
-            .and_then(|data| data.families_as_spouse.get(&for_pid))
-            .and_then(|fids| fids.first().copied());
         spawn(async move {
-            let fid = if let Some(fid) = existing_family_id {
-                fid
-            } else {
-                let Ok(family) = api.create_family(tid).await else {
-                    return;
-                };
-                let body = crate::api::AddSpouseBody {
-                    person_id: for_pid,
-                    role: oxidgene_core::SpouseRole::Partner,
-                    sort_order: 0,
-                };
-                let _ = api.add_spouse(tid, family.id, &body).await;
-                family.id
+            let body = crate::api::AddRelativeBody {
+                kind: RelativeKind::Spouse,
+                sex: None,
+                given_names: None,
+                surname: None,
             };
-            if let Ok(new_person) = api
-                .create_person(
-                    tid,
-                    &crate::api::CreatePersonBody {
-                        sex: oxidgene_core::Sex::Unknown,
-                    },
-                )
-                .await
-            {
-                let body = crate::api::AddSpouseBody {
-                    person_id: new_person.id,
-                    role: oxidgene_core::SpouseRole::Partner,
-                    sort_order: 1,
-                };
-                let _ = api.add_spouse(tid, fid, &body).await;
+            if let Ok(added) = api.add_relative(tid, for_pid, &body).await {
+                editing_person_id.set(Some(added.person.id));
             }
             linking_mode.set(None);
             tree_cache.invalidate();
         });
     };
 
-    // AddParents: link existing person as parent.
+    // AddFather/AddMother: link existing person as parent.
     let api_link_parent = api.clone();
     let pedigree_data_parent = pedigree_data.clone();
     let on_link_parent = move |person_id: Uuid| {
         let api = api_link_parent.clone();
         let Some(tid) = tree_id_parsed() else { return };
-        let Some(LinkingMode::Parents(child_id)) = linking_mode() else {
+        let Some(LinkingMode::Parent(child_id, kind)) = linking_mode() else {
             return;
         };
         // Find or create a family where child_id is a child.
@@ -493,9 +496,14 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
                 let _ = api.add_child(tid, family.id, &body).await;
                 family.id
             };
+            let role = if kind == RelativeKind::Mother {
+                oxidgene_core::SpouseRole::Wife
+            } else {
+                oxidgene_core::SpouseRole::Husband
+            };
             let body = crate::api::AddSpouseBody {
                 person_id,
-                role: oxidgene_core::SpouseRole::Partner,
+                role: Some(role),
                 sort_order: 0,
             };
             let _ = api.add_spouse(tid, fid, &body).await;
@@ -504,49 +512,23 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
         });
     };
 
-    // AddParents: create new person as parent.
+    // AddFather/AddMother: create new person as parent.
     let api_new_parent = api.clone();
-    let pedigree_data_new_parent = pedigree_data.clone();
     let on_create_new_parent = move |_| {
         let api = api_new_parent.clone();
         let Some(tid) = tree_id_parsed() else { return };
-        let Some(LinkingMode::Parents(child_id)) = linking_mode() else {
+        let Some(LinkingMode::Parent(child_id, kind)) = linking_mode() else {
             return;
         };
-        let existing_family_id = pedigree_data_new_parent
-            .as_ref()
-            .and_then(|data| data.families_as_child.get(&child_id))
-            .and_then(|fids| fids.first().copied());
         spawn(async move {
-            let fid = if let Some(fid) = existing_family_id {
-                fid
-            } else {
-                let Ok(family) = api.create_family(tid).await else {
-                    return;
-                };
-                let body = crate::api::AddChildBody {
-                    person_id: child_id,
-                    child_type: oxidgene_core::ChildType::Biological,
-                    sort_order: 0,
-                };
-                let _ = api.add_child(tid, family.id, &body).await;
-                family.id
+            let body = crate::api::AddRelativeBody {
+                kind,
+                sex: None,
+                given_names: None,
+                surname: None,
             };
-            if let Ok(new_person) = api
-                .create_person(
-                    tid,
-                    &crate::api::CreatePersonBody {
-                        sex: oxidgene_core::Sex::Unknown,
-                    },
-                )
-                .await
-            {
-                let body = crate::api::AddSpouseBody {
-                    person_id: new_person.id,
-                    role: oxidgene_core::SpouseRole::Partner,
-                    sort_order: 0,
-                };
-                let _ = api.add_spouse(tid, fid, &body).await;
+            if let Ok(added) = api.add_relative(tid, child_id, &body).await {
+                editing_person_id.set(Some(added.person.id));
             }
             linking_mode.set(None);
             tree_cache.invalidate();
@@ -575,7 +557,7 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
                 };
                 let body = crate::api::AddSpouseBody {
                     person_id: parent_id,
-                    role: oxidgene_core::SpouseRole::Partner,
+                    role: None,
                     sort_order: 0,
                 };
                 let _ = api.add_spouse(tid, family.id, &body).await;
@@ -594,47 +576,21 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
 
     // AddChild: create new person as child.
     let api_new_child = api.clone();
-    let pedigree_data_new_child = pedigree_data.clone();
     let on_create_new_child = move |_| {
         let api = api_new_child.clone();
         let Some(tid) = tree_id_parsed() else { return };
         let Some(LinkingMode::Child(parent_id)) = linking_mode() else {
             return;
         };
-        let existing_family_id = pedigree_data_new_child
-            .as_ref()
-            .and_then(|data| data.families_as_spouse.get(&parent_id))
-            .and_then(|fids| fids.first().copied());
         spawn(async move {
-            let fid = if let Some(fid) = existing_family_id {
-                fid
-            } else {
-                let Ok(family) = api.create_family(tid).await else {
-                    return;
-                };
-                let body = crate::api::AddSpouseBody {
-                    person_id: parent_id,
-                    role: oxidgene_core::SpouseRole::Partner,
-                    sort_order: 0,
-                };
-                let _ = api.add_spouse(tid, family.id, &body).await;
-                family.id
+            let body = crate::api::AddRelativeBody {
+                kind: RelativeKind::Child,
+                sex: None,
+                given_names: None,
+                surname: None,
             };
-            if let Ok(new_person) = api
-                .create_person(
-                    tid,
-                    &crate::api::CreatePersonBody {
-                        sex: oxidgene_core::Sex::Unknown,
-                    },
-                )
-                .await
-            {
-                let body = crate::api::AddChildBody {
-                    person_id: new_person.id,
-                    child_type: oxidgene_core::ChildType::Biological,
-                    sort_order: 0,
-                };
-                let _ = api.add_child(tid, fid, &body).await;
+            if let Ok(added) = api.add_relative(tid, parent_id, &body).await {
+                editing_person_id.set(Some(added.person.id));
             }
             linking_mode.set(None);
             tree_cache.invalidate();
@@ -684,68 +640,66 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
 
     // AddSibling: create new person as sibling.
     let api_new_sibling = api.clone();
-    let pedigree_data_new_sibling = pedigree_data.clone();
     let on_create_new_sibling = move |_| {
         let api = api_new_sibling.clone();
         let Some(tid) = tree_id_parsed() else { return };
         let Some(LinkingMode::Sibling(for_pid)) = linking_mode() else {
             return;
         };
-        let parent_family_id = pedigree_data_new_sibling
-            .as_ref()
-            .and_then(|data| data.families_as_child.get(&for_pid))
-            .and_then(|fids| fids.first().copied());
         spawn(async move {
-            let fid = if let Some(fid) = parent_family_id {
-                fid
-            } else {
-                let Ok(family) = api.create_family(tid).await else {
-                    return;
-                };
-                let body = crate::api::AddChildBody {
-                    person_id: for_pid,
-                    child_type: oxidgene_core::ChildType::Biological,
-                    sort_order: 0,
-                };
-                let _ = api.add_child(tid, family.id, &body).await;
-                family.id
+            let body = crate::api::AddRelativeBody {
+                kind: RelativeKind::Sibling,
+                sex: None,
+                given_names: None,
+                surname: None,
             };
-            if let Ok(new_person) = api
-                .create_person(
-                    tid,
-                    &crate::api::CreatePersonBody {
-                        sex: oxidgene_core::Sex::Unknown,
-                    },
-                )
-                .await
-            {
-                let body = crate::api::AddChildBody {
-                    person_id: new_person.id,
-                    child_type: oxidgene_core::ChildType::Biological,
-                    sort_order: 1,
-                };
-                let _ = api.add_child(tid, fid, &body).await;
+            if let Ok(added) = api.add_relative(tid, for_pid, &body).await {
+                editing_person_id.set(Some(added.person.id));
             }
             linking_mode.set(None);
             tree_cache.invalidate();
         });
     };
 
-    // Merge: link existing person to merge with.
-    let on_link_merge = move |_target_id: Uuid| {
-        // TODO: Implement merge UI — should show a modal to choose which
-        // events/info/sources to keep from each person, then delete one.
-        // For now, just close the linking panel.
+    // Merge: compare the person with the one picked, side by side.
+    let on_link_merge = move |target_id: Uuid| {
+        let Some(LinkingMode::Merge(for_pid)) = linking_mode() else {
+            return;
+        };
         linking_mode.set(None);
+        if let Some(tid) = tree_id_parsed() {
+            nav.push(Route::PersonCompare {
+                tree_id: tid.to_string(),
+                a: for_pid.to_string(),
+                b: target_id.to_string(),
+            });
+        }
+    };
+
+    // Relationship: the path between the person and the one picked.
+    let on_link_relationship = move |target_id: Uuid| {
+        let Some(LinkingMode::Relationship(for_pid)) = linking_mode() else {
+            return;
+        };
+        linking_mode.set(None);
+        if let Some(tid) = tree_id_parsed() {
+            nav.push(Route::Relationship {
+                tree_id: tid.to_string(),
+                a: for_pid.to_string(),
+                b: target_id.to_string(),
+            });
+        }
     };
 
     // Linking mode label for the panel header.
     let linking_label: Option<String> = linking_mode().map(|mode| match &mode {
         LinkingMode::Spouse(_) => i18n.t("linking.add_spouse"),
-        LinkingMode::Parents(_) => i18n.t("linking.add_parent"),
+        LinkingMode::Parent(_, RelativeKind::Mother) => i18n.t("linking.add_mother"),
+        LinkingMode::Parent(..) => i18n.t("linking.add_father"),
         LinkingMode::Child(_) => i18n.t("linking.add_child"),
         LinkingMode::Sibling(_) => i18n.t("linking.add_sibling"),
         LinkingMode::Merge(_) => i18n.t("linking.merge"),
+        LinkingMode::Relationship(_) => i18n.t("linking.relationship"),
     });
 
     // ── Render ──
@@ -784,6 +738,7 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
                     }
                     if root_person_id.is_some() {
                         TopbarSearch { tree_id: tree_id.clone() }
+                        RecentActivity { tree_id: tree_id.clone() }
                     }
                 }
             }
@@ -842,6 +797,21 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
             }
         }
 
+        // Quick entry modal (new standalone persons)
+        if show_quick_entry() {
+            if let Some(tid) = tree_id_parsed() {
+                QuickEntry {
+                    tree_id: tid,
+                    on_close: move |_| show_quick_entry.set(false),
+                    on_created: move |_| tree_cache.invalidate(),
+                    on_full_form: move |_| {
+                        show_quick_entry.set(false);
+                        creating_person_ctx.set(Some(PersonFormCreateContext::Standalone));
+                    },
+                }
+            }
+        }
+
         // Union edit modal
         if let Some(union_fid) = editing_union_id() {
             if let Some(tid) = tree_id_parsed() {
@@ -854,6 +824,43 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
             }
         }
 
+        // ── Family cards ──
+        if !family_cards.is_empty() {
+            div { class: "td-family-cards",
+                for (fid, partner, events) in family_cards {
+                    button {
+                        key: "{fid}",
+                        class: if events.divorce_before_marriage { "td-family-card td-family-card-warn" } else { "td-family-card" },
+                        title: i18n.t("context.edit_union"),
+                        onclick: move |_| editing_union_id.set(Some(fid)),
+                        span { class: "td-family-card-partner", "\u{26AD} {partner}" }
+                        span { class: "td-family-card-event",
+                            {match (events.marriage_date, events.marriage_place) {
+                                (Some(date), Some(place)) => i18n.t_args("family_card.married_at", &[("date", &date), ("place", &place)]),
+                                (Some(date), None) => i18n.t_args("family_card.married", &[("date", &date)]),
+                                (None, Some(place)) => i18n.t_args("family_card.married_in", &[("place", &place)]),
+                                (None, None) => i18n.t("family_card.no_marriage"),
+                            }}
+                        }
+                        if let Some(date) = events.divorce_date {
+                            span { class: "td-family-card-event",
+                                {i18n.t_args("family_card.divorced", &[("date", &date)])}
+                            }
+                        }
+                        if events.divorce_before_marriage {
+                            span { class: "td-family-card-warning",
+                                "\u{26A0} "
+                                {i18n.t("family_card.divorce_before_marriage")}
+                            }
+                        }
+                        if let Some(tid) = tree_id_parsed() {
+                            FamilyCardRecords { tree_id: tid, family_id: fid }
+                        }
+                    }
+                }
+            }
+        }
+
         // ── Pedigree chart (fills remaining space) ──
         div { class: "pedigree-card",
 
@@ -898,9 +905,7 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
                     on_add_spouse_slot: move |person_id| {
                         linking_mode.set(Some(LinkingMode::Spouse(person_id)));
                     },
-                    on_add_person: move |_| {
-                        creating_person_ctx.set(Some(PersonFormCreateContext::Standalone));
-                    },
+                    on_add_person: move |_| show_quick_entry.set(true),
                     on_profile_view: {
                         let tree_id = tree_id.clone();
                         move |pid: Uuid| {
@@ -926,6 +931,14 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
                             });
                         }
                     },
+                    on_tasks: {
+                        let tree_id = tree_id.clone();
+                        move |_| {
+                            nav.push(Route::Tasks {
+                                tree_id: tree_id.clone(),
+                            });
+                        }
+                    },
                 }
             } else {
                 // Loading or empty state
@@ -978,7 +991,7 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
             }
         }
 
-        // ── Linking panel (search-or-create for AddSpouse/AddParents/AddChild) ──
+        // ── Linking panel (search-or-create for AddSpouse/AddFather/AddMother/AddChild) ──
         if let (Some(label), Some(tid)) = (linking_label, tree_id_parsed()) {
             div { class: "card linking-card",
                 div { class: "section-header",
@@ -1013,10 +1026,14 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
                                     {i18n.t("linking.create_spouse")}
                                 }
                             },
-                            Some(LinkingMode::Parents(_)) => rsx! {
+                            Some(LinkingMode::Parent(_, kind)) => rsx! {
                                 SearchPerson {
                                     tree_id: tid,
-                                    placeholder: i18n.t("linking.search_parent"),
+                                    placeholder: i18n.t(if kind == RelativeKind::Mother {
+                                        "linking.search_mother"
+                                    } else {
+                                        "linking.search_father"
+                                    }),
                                     on_select: on_link_parent,
                                     on_cancel: move |_| linking_mode.set(None),
                                 }
@@ -1024,7 +1041,11 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
                                 button {
                                     class: "btn btn-outline",
                                     onclick: on_create_new_parent,
-                                    {i18n.t("linking.create_parent")}
+                                    {i18n.t(if kind == RelativeKind::Mother {
+                                        "linking.create_mother"
+                                    } else {
+                                        "linking.create_father"
+                                    })}
                                 }
                             },
                             Some(LinkingMode::Child(_)) => rsx! {
@@ -1066,6 +1087,14 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
                                     on_cancel: move |_| linking_mode.set(None),
                                 }
                             },
+                            Some(LinkingMode::Relationship(_)) => rsx! {
+                                SearchPerson {
+                                    tree_id: tid,
+                                    placeholder: i18n.t("linking.search_relationship"),
+                                    on_select: on_link_relationship,
+                                    on_cancel: move |_| linking_mode.set(None),
+                                }
+                            },
                             None => rsx! {},
                         }
                     }
@@ -1076,3 +1105,56 @@ pub fn TreeDetail(tree_id: String, person: Option<String>) -> Element {
         } // close .tree-detail-page
     }
 }
+
+/// Notes and sources of a union, listed on its family card. Edited in the
+/// union modal the card opens.
+#[component]
+fn FamilyCardRecords(tree_id: Uuid, family_id: Uuid) -> Element {
+    let api = use_context::<ApiClient>();
+    let tree_cache = use_tree_cache();
+
+    let api_records = api.clone();
+    let records = use_resource(move || {
+        let api = api_records.clone();
+        let _generation = tree_cache.generation();
+        async move {
+            let notes = api.list_family_notes(tree_id, family_id).await?;
+            let citations = api.list_family_citations(tree_id, family_id).await?;
+            let sources = if citations.is_empty() {
+                vec![]
+            } else {
+                api.list_all_sources(tree_id).await?
+            };
+            let cited: Vec<String> = citations
+                .iter()
+                .map(|c| {
+                    let title = sources
+                        .iter()
+                        .find(|s| s.id == c.source_id)
+                        .map(|s| s.title.clone())
+                        .unwrap_or_default();
+                    match &c.page {
+                        Some(page) => format!("{title} \u{2014} {page}"),
+                        None => title,
+                    }
+                })
+                .collect();
+            Ok::<_, crate::api::ApiError>((notes, cited))
+        }
+    });
+
+    let records = records.read();
+    let Some(Ok((notes, cited))) = &*records else {
+        return rsx! {};
+    };
+    rsx! {
+        for note in notes.iter() {
+            span { key: "{note.id}", class: "td-family-card-note", title: "{note.text}",
+                "\u{1F4DD} {note.text}"
+            }
+        }
+        for (i, source) in cited.iter().enumerate() {
+            span { key: "{i}", class: "td-family-card-source", "\u{1F4D6} {source}" }
+        }
+    }
+}
@@ -7,8 +7,9 @@ use dioxus::prelude::*;
 
 use crate::pages::{
     app_settings::AppSettings, dictionary::Dictionary, home::Home, not_found::NotFound,
-    person_detail::PersonDetail, search_results::SearchResults, settings::Settings,
-    tree_detail::TreeDetail,
+    person_compare::PersonCompare, person_detail::PersonDetail, relationship::Relationship,
+    search_results::SearchResults, settings::Settings, shared_tree::SharedTree,
+    source_detail::SourceDetail, tasks::Tasks, tree_detail::TreeDetail,
 };
 
 /// All application routes.
@@ -50,11 +51,36 @@ pub enum Route {
     #[route("/trees/:tree_id/persons/:person_id")]
     PersonDetail { tree_id: String, person_id: String },
 
+    /// Side-by-side comparison of two persons, before merging them.
+    #[route("/trees/:tree_id/compare?:a&:b")]
+    PersonCompare {
+        tree_id: String,
+        a: String,
+        b: String,
+    },
+
+    /// How two persons are related: the chain of persons through their
+    /// nearest common ancestor.
+    #[route("/trees/:tree_id/relationship?:a&:b")]
+    Relationship {
+        tree_id: String,
+        a: String,
+        b: String,
+    },
+
     /// Dictionary page for a tree: family names, sources, places, occupations
     /// with usage counts.
     #[route("/trees/:tree_id/dictionary")]
     Dictionary { tree_id: String },
 
+    /// The tree's research to-do list, with links to each task's person.
+    #[route("/trees/:tree_id/tasks")]
+    Tasks { tree_id: String },
+
+    /// Detail view for a source: its metadata and every citation of it.
+    #[route("/trees/:tree_id/sources/:source_id")]
+    SourceDetail { tree_id: String, source_id: String },
+
     /// Settings page for a tree.
     #[route("/trees/:tree_id/settings")]
     Settings { tree_id: String },
@@ -63,6 +89,10 @@ pub enum Route {
     #[route("/settings")]
     AppSettings {},
 
+    /// Read-only view of a tree opened through a share link.
+    #[route("/shared/:token")]
+    SharedTree { token: String },
+
     /// Catch-all 404 page.
     #[end_layout]
     #[route("/:..segments")]
@@ -0,0 +1,54 @@
+//! Where the app left off, for shells that remember it (the desktop app
+//! keeps it in its settings file).
+//!
+//! The shell provides a [`Session`] in the Dioxus context before launching:
+//! the home page then reopens the last tree once, at startup, and the tree
+//! view reports every tree opened. Without one (web), nothing is restored.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+/// The tree to reopen, and where to report opened trees.
+#[derive(Clone)]
+pub struct Session {
+    last_tree_id: Option<Uuid>,
+    on_tree_opened: Arc<dyn Fn(Uuid) + Send + Sync>,
+    resumed: Arc<AtomicBool>,
+}
+
+impl Session {
+    /// A session reopening `last_tree_id`, calling `on_tree_opened` with
+    /// each tree the user opens.
+    pub fn new(
+        last_tree_id: Option<Uuid>,
+        on_tree_opened: impl Fn(Uuid) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            last_tree_id,
+            on_tree_opened: Arc::new(on_tree_opened),
+            resumed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// The tree to reopen at startup; `None` after the first call.
+    pub fn take_resume(&self) -> Option<Uuid> {
+        if self.resumed.swap(true, Ordering::Relaxed) {
+            None
+        } else {
+            self.last_tree_id
+        }
+    }
+
+    /// Report that the user opened `tree_id`.
+    pub fn tree_opened(&self, tree_id: Uuid) {
+        (self.on_tree_opened)(tree_id);
+    }
+}
+
+/// The session provided by the shell, if any.
+pub fn use_session() -> Option<Session> {
+    try_use_context::<Session>()
+}
@@ -13,6 +13,7 @@ pub fn parse_sex(s: &str) -> Sex {
     match s {
         "Male" => Sex::Male,
         "Female" => Sex::Female,
+        "Intersex" => Sex::Intersex,
         _ => Sex::Unknown,
     }
 }
@@ -29,54 +30,10 @@ pub fn parse_name_type(s: &str) -> NameType {
     }
 }
 
-/// Parse a string value from a `<select>` into an [`EventType`] enum.
+/// Parse a string value from a `<select>` into an [`EventType`] enum; see
+/// [`oxidgene_core::EVENT_TYPES`].
 pub fn parse_event_type(s: &str) -> EventType {
-    match s {
-        "Birth" => EventType::Birth,
-        "Death" => EventType::Death,
-        "Baptism" => EventType::Baptism,
-        "Confirmation" => EventType::Confirmation,
-        "FirstCommunion" => EventType::FirstCommunion,
-        "BarBatMitzvah" => EventType::BarBatMitzvah,
-        "Burial" => EventType::Burial,
-        "Cremation" => EventType::Cremation,
-        "Graduation" => EventType::Graduation,
-        "Immigration" => EventType::Immigration,
-        "Emigration" => EventType::Emigration,
-        "Naturalization" => EventType::Naturalization,
-        "Census" => EventType::Census,
-        "Occupation" => EventType::Occupation,
-        "Residence" => EventType::Residence,
-        "Retirement" => EventType::Retirement,
-        "MilitaryService" => EventType::MilitaryService,
-        "Will" => EventType::Will,
-        "Probate" => EventType::Probate,
-        "Adoption" => EventType::Adoption,
-        "CasteName" => EventType::CasteName,
-        "PhysicalDescription" => EventType::PhysicalDescription,
-        "Education" => EventType::Education,
-        "NationalId" => EventType::NationalId,
-        "NationalOrigin" => EventType::NationalOrigin,
-        "ChildrenCount" => EventType::ChildrenCount,
-        "MarriagesCount" => EventType::MarriagesCount,
-        "Property" => EventType::Property,
-        "Religion" => EventType::Religion,
-        "SocialSecurityNumber" => EventType::SocialSecurityNumber,
-        "NobilityTitle" => EventType::NobilityTitle,
-        "Fact" => EventType::Fact,
-        "Marriage" => EventType::Marriage,
-        "Divorce" => EventType::Divorce,
-        "Annulment" => EventType::Annulment,
-        "Engagement" => EventType::Engagement,
-        "MarriageBann" => EventType::MarriageBann,
-        "MarriageContract" => EventType::MarriageContract,
-        "MarriageLicense" => EventType::MarriageLicense,
-        "MarriageSettlement" => EventType::MarriageSettlement,
-        "CivilUnion" => EventType::CivilUnion,
-        "Separation" => EventType::Separation,
-        "DivorceFiled" => EventType::DivorceFiled,
-        _ => EventType::Other,
-    }
+    EventType::from_name(s).unwrap_or(EventType::Other)
 }
 
 /// Parse a string value from a `<select>` into a [`DateQualifier`] enum.
@@ -134,6 +91,16 @@ pub fn opt_str(s: &str) -> Option<String> {
     }
 }
 
+/// Message for a failed save: a friendly explanation when another edit got
+/// there first (409 Conflict), the raw error otherwise.
+pub fn save_error_text(i18n: &crate::i18n::I18n, err: &crate::api::ApiError) -> String {
+    if err.is_conflict() {
+        i18n.t("common.conflict")
+    } else {
+        err.to_string()
+    }
+}
+
 // ── Name resolution ─────────────────────────────────────────────────────
 
 /// Resolve a display name for a person from a name map.
@@ -163,6 +130,29 @@ pub fn resolve_name(
     }
 }
 
+// ── Surname colors ──────────────────────────────────────────────────────
+
+/// Colors handed out to surnames, distinct enough side by side on a chart.
+const SURNAME_PALETTE: [&str; 12] = [
+    "#E0634A", "#2E8BC0", "#4CA64C", "#B55FC4", "#E0A030", "#1FA5A0", "#C2477A", "#6D7FD8",
+    "#8C9A2E", "#D97E2B", "#5B8F73", "#A0665A",
+];
+
+/// A stable palette color for a surname, so persons sharing a family name
+/// stand out together. Case and surrounding whitespace are ignored; an empty
+/// surname gets the neutral card border.
+pub fn surname_color(surname: &str) -> String {
+    let key = surname.trim().to_lowercase();
+    if key.is_empty() {
+        return "var(--pn-border)".to_string();
+    }
+    // FNV-1a: fixed across runs and platforms, unlike the std hasher.
+    let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+        (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    SURNAME_PALETTE[(hash % SURNAME_PALETTE.len() as u64) as usize].to_string()
+}
+
 /// ── Text truncation ─────────────────────────────────────────────────────
 ///
 /// Estimate rendered text width in pixels for Lato-like sans fonts.